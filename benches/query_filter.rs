@@ -0,0 +1,78 @@
+//! Cost of filtering, sorting and paginating a `task list --limit 500`
+//! sized query, copying every candidate up front versus filtering borrowed
+//! views and copying only the returned page.
+//!
+//! Run with `cargo bench --features bench --bench query_filter`.
+
+use chrono::{Duration, TimeZone, Utc};
+use engram::entities::GenericEntity;
+use engram::storage::query::{apply_filter, apply_filter_borrowed, EntityRef};
+use engram::storage::{QueryFilter, SortOrder};
+use serde_json::json;
+use std::hint::black_box;
+use std::time::Instant;
+
+const ENTITIES: usize = 10_000;
+const ITERATIONS: u32 = 20;
+
+fn fixtures() -> Vec<GenericEntity> {
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    (0..ENTITIES)
+        .map(|i| {
+            let status = ["todo", "inprogress", "done"][i % 3];
+            let priority = ["low", "medium", "high", "critical"][i % 4];
+            GenericEntity {
+            id: format!("task-{:05}", i),
+            entity_type: "task".to_string(),
+            agent: format!("agent-{}", i % 7),
+            timestamp: start + Duration::minutes(i as i64),
+            data: json!({
+                "title": format!("Task number {}", i),
+                "description": "A task with enough text to make copying it cost something ".repeat(4),
+                "status": status,
+                "priority": priority,
+                "tags": ["backend", "storage", "performance"],
+                "metadata": {"estimate": i % 13, "owner": format!("owner-{}", i % 5)},
+            }),
+            }
+        })
+        .collect()
+}
+
+/// Mean time per iteration of `run`, printed in the style of a criterion report
+fn bench(name: &str, mut run: impl FnMut() -> usize) {
+    black_box(run());
+    let started = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(run());
+    }
+    println!(
+        "{:<32} time: {:>10.3?}",
+        name,
+        started.elapsed() / ITERATIONS
+    );
+}
+
+fn main() {
+    let stored = fixtures();
+    let filter = QueryFilter {
+        entity_type: Some("task".to_string()),
+        sort_by: Some("priority".to_string()),
+        sort_order: SortOrder::Desc,
+        limit: Some(500),
+        ..Default::default()
+    };
+
+    bench("copy all, then filter", || {
+        let candidates = stored.to_vec();
+        apply_filter(candidates, &filter).unwrap().entities.len()
+    });
+    bench("filter borrowed, copy page", || {
+        let candidates = stored.iter().map(EntityRef::from).collect();
+        apply_filter_borrowed(candidates, &filter)
+            .unwrap()
+            .map(|entity| entity.to_entity())
+            .entities
+            .len()
+    });
+}
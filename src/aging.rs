@@ -0,0 +1,337 @@
+//! Priority aging for tasks left untouched
+//!
+//! Opt-in through the `aging` section of `engram.yaml`:
+//!
+//! ```yaml
+//! aging:
+//!   enabled: true
+//!   after_days: 14
+//!   max_priority: high
+//! ```
+//!
+//! An open task idle for `after_days` is bumped one priority level, never
+//! above `max_priority` and never down. Idle time runs from the task's last
+//! recorded activity: its start, its end, its last store, or its latest
+//! automatic bump.
+//! Tasks tagged `no-age` are left alone. Each bump is appended to the task's
+//! `priority_aging` metadata.
+
+use crate::config::Config;
+use crate::entities::{Entity, Task, TaskPriority, TaskStatus};
+use crate::error::EngramError;
+use crate::storage::Storage;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Tag that exempts a task from aging
+pub const NO_AGE_TAG: &str = "no-age";
+
+/// Task metadata key holding the list of automatic bumps
+pub const AGING_METADATA: &str = "priority_aging";
+
+/// `aging` section of `engram.yaml`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AgingPolicy {
+    /// Aging only runs when explicitly enabled
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Days without activity before a task is bumped one level
+    #[serde(default = "default_after_days")]
+    pub after_days: i64,
+
+    /// Highest priority aging may raise a task to
+    #[serde(default = "default_max_priority")]
+    pub max_priority: TaskPriority,
+}
+
+fn default_after_days() -> i64 {
+    14
+}
+
+fn default_max_priority() -> TaskPriority {
+    TaskPriority::High
+}
+
+impl Default for AgingPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            after_days: default_after_days(),
+            max_priority: default_max_priority(),
+        }
+    }
+}
+
+impl AgingPolicy {
+    /// Read the `aging` section of `<workspace>/engram.yaml` (or
+    /// `engram.yml`). A missing file or section leaves aging disabled.
+    pub fn load(workspace: &Path) -> Result<Self, EngramError> {
+        Self::validated(Config::load_section(workspace, "aging")?)
+    }
+
+    pub fn from_yaml(content: &str) -> Result<Self, EngramError> {
+        Self::validated(Config::section_from_yaml(content, "aging")?)
+    }
+
+    fn validated(policy: Self) -> Result<Self, EngramError> {
+        if policy.after_days < 1 {
+            return Err(EngramError::Validation(format!(
+                "aging.after_days must be at least 1, got {}",
+                policy.after_days
+            )));
+        }
+        Ok(policy)
+    }
+}
+
+/// A task aging raised, or would raise, by one level
+#[derive(Debug, Clone, Serialize)]
+pub struct PriorityBump {
+    pub task_id: String,
+    pub title: String,
+    pub from: TaskPriority,
+    pub to: TaskPriority,
+    pub idle_days: i64,
+}
+
+fn level(priority: &TaskPriority) -> u8 {
+    match priority {
+        TaskPriority::Low => 0,
+        TaskPriority::Medium => 1,
+        TaskPriority::High => 2,
+        TaskPriority::Critical => 3,
+    }
+}
+
+/// Lowercase name of a priority, as stored
+pub fn priority_label(priority: &TaskPriority) -> &'static str {
+    match priority {
+        TaskPriority::Low => "low",
+        TaskPriority::Medium => "medium",
+        TaskPriority::High => "high",
+        TaskPriority::Critical => "critical",
+    }
+}
+
+fn next_level(priority: &TaskPriority) -> Option<TaskPriority> {
+    match priority {
+        TaskPriority::Low => Some(TaskPriority::Medium),
+        TaskPriority::Medium => Some(TaskPriority::High),
+        TaskPriority::High => Some(TaskPriority::Critical),
+        TaskPriority::Critical => None,
+    }
+}
+
+/// When the task last showed activity: its start, end, last store
+/// (`last_modified`, from the entity's modification metadata) or latest bump
+pub fn last_activity(task: &Task, last_modified: Option<DateTime<Utc>>) -> DateTime<Utc> {
+    let last_bump = task
+        .metadata
+        .get(AGING_METADATA)
+        .and_then(|bumps| bumps.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|bump| bump.get("at")?.as_str()?.parse::<DateTime<Utc>>().ok())
+        .max();
+    [
+        Some(task.start_time),
+        task.end_time,
+        last_modified,
+        last_bump,
+    ]
+    .into_iter()
+    .flatten()
+    .max()
+    .unwrap_or(task.start_time)
+}
+
+/// The bump `policy` calls for on `task`, last stored at `last_modified`,
+/// at `now`, if any
+pub fn bump_for(
+    task: &Task,
+    last_modified: Option<DateTime<Utc>>,
+    policy: &AgingPolicy,
+    now: DateTime<Utc>,
+) -> Option<PriorityBump> {
+    if matches!(task.status, TaskStatus::Done | TaskStatus::Cancelled)
+        || task.tags.iter().any(|tag| tag == NO_AGE_TAG)
+    {
+        return None;
+    }
+    let idle = now.signed_duration_since(last_activity(task, last_modified));
+    if idle < Duration::days(policy.after_days) {
+        return None;
+    }
+    let to = next_level(&task.priority)?;
+    if level(&to) > level(&policy.max_priority) {
+        return None;
+    }
+    Some(PriorityBump {
+        task_id: task.id.clone(),
+        title: task.title.clone(),
+        from: task.priority.clone(),
+        to,
+        idle_days: idle.num_days(),
+    })
+}
+
+/// Find the tasks `policy` bumps at `now`, storing each bump when `apply`
+/// is set. Returns the bumps, applied or not.
+pub fn age_tasks<S: Storage>(
+    storage: &mut S,
+    policy: &AgingPolicy,
+    now: DateTime<Utc>,
+    apply: bool,
+) -> Result<Vec<PriorityBump>, EngramError> {
+    let mut bumps = Vec::new();
+    for generic in storage.get_all(Task::entity_type())? {
+        let last_modified = generic.modification().last_modified_at;
+        let Ok(mut task) = Task::from_generic(generic) else {
+            continue;
+        };
+        let Some(bump) = bump_for(&task, last_modified, policy, now) else {
+            continue;
+        };
+        if apply {
+            task.priority = bump.to.clone();
+            let history = task
+                .metadata
+                .entry(AGING_METADATA.to_string())
+                .or_insert_with(|| serde_json::json!([]));
+            if !history.is_array() {
+                *history = serde_json::json!([]);
+            }
+            if let Some(history) = history.as_array_mut() {
+                history.push(serde_json::json!({
+                    "from": priority_label(&bump.from),
+                    "to": priority_label(&bump.to),
+                    "at": now.to_rfc3339(),
+                    "idle_days": bump.idle_days,
+                }));
+            }
+            storage.store(&task.to_generic())?;
+        }
+        bumps.push(bump);
+    }
+    bumps.sort_by(|a, b| {
+        b.idle_days
+            .cmp(&a.idle_days)
+            .then(a.task_id.cmp(&b.task_id))
+    });
+    Ok(bumps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::MemoryStorage;
+
+    fn store_task(
+        storage: &mut MemoryStorage,
+        priority: TaskPriority,
+        started: DateTime<Utc>,
+        tags: &[&str],
+    ) -> String {
+        let mut task = Task::new(
+            "Old task".to_string(),
+            String::new(),
+            "agent".to_string(),
+            priority,
+            None,
+        );
+        task.start_time = started;
+        task.tags = tags.iter().map(|tag| tag.to_string()).collect();
+        storage.store(&task.to_generic()).unwrap();
+        task.id
+    }
+
+    #[test]
+    fn test_policy_defaults_and_validation() {
+        let policy = AgingPolicy::from_yaml("name: ws\n").unwrap();
+        assert!(!policy.enabled);
+        assert_eq!(policy.after_days, 14);
+
+        let policy =
+            AgingPolicy::from_yaml("aging:\n  enabled: true\n  max_priority: critical\n").unwrap();
+        assert!(policy.enabled);
+        assert_eq!(policy.max_priority, TaskPriority::Critical);
+
+        assert!(AgingPolicy::from_yaml("aging:\n  after_days: 0\n").is_err());
+    }
+
+    #[test]
+    fn test_aging_across_fourteen_day_boundary() {
+        let mut storage = MemoryStorage::new("default");
+        let policy = AgingPolicy {
+            enabled: true,
+            ..Default::default()
+        };
+        // Start ahead of the clock so the stores' own modification times,
+        // stamped with the current time, are never the latest activity
+        let start = Utc::now() + Duration::hours(1);
+        let low = store_task(&mut storage, TaskPriority::Low, start, &[]);
+        store_task(&mut storage, TaskPriority::Low, start, &[NO_AGE_TAG]);
+        store_task(&mut storage, TaskPriority::High, start, &[]);
+
+        let just_before = start + Duration::days(14) - Duration::seconds(1);
+        assert!(age_tasks(&mut storage, &policy, just_before, true)
+            .unwrap()
+            .is_empty());
+
+        let boundary = start + Duration::days(14);
+        let bumps = age_tasks(&mut storage, &policy, boundary, false).unwrap();
+        assert_eq!(bumps.len(), 1);
+        assert_eq!(bumps[0].task_id, low);
+        let stored = Task::from_generic(storage.get(&low, "task").unwrap().unwrap()).unwrap();
+        assert_eq!(stored.priority, TaskPriority::Low);
+
+        age_tasks(&mut storage, &policy, boundary, true).unwrap();
+        let generic = storage.get(&low, "task").unwrap().unwrap();
+        let last_modified = generic.modification().last_modified_at;
+        let stored = Task::from_generic(generic).unwrap();
+        assert_eq!(stored.priority, TaskPriority::Medium);
+        assert_eq!(stored.metadata[AGING_METADATA][0]["from"], "low");
+        assert_eq!(last_activity(&stored, last_modified), boundary);
+
+        // The bump restarts the clock; the next one waits another 14 days
+        let later = boundary + Duration::days(13);
+        assert!(age_tasks(&mut storage, &policy, later, true)
+            .unwrap()
+            .is_empty());
+        let much_later = boundary + Duration::days(28);
+        age_tasks(&mut storage, &policy, much_later, true).unwrap();
+        age_tasks(&mut storage, &policy, much_later + Duration::days(14), true).unwrap();
+        let stored = Task::from_generic(storage.get(&low, "task").unwrap().unwrap()).unwrap();
+        assert_eq!(stored.priority, TaskPriority::High);
+        assert_eq!(stored.metadata[AGING_METADATA].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_update_inside_window_prevents_bump() {
+        let mut storage = MemoryStorage::new("default");
+        let policy = AgingPolicy {
+            enabled: true,
+            ..Default::default()
+        };
+        let id = store_task(
+            &mut storage,
+            TaskPriority::Low,
+            Utc::now() - Duration::days(20),
+            &[],
+        );
+        let mut task = Task::from_generic(storage.get(&id, "task").unwrap().unwrap()).unwrap();
+        task.description = "Picked back up".to_string();
+        storage.store(&task.to_generic()).unwrap();
+
+        // Started 20 days ago, but the update restarts the clock
+        let now = Utc::now();
+        assert!(age_tasks(&mut storage, &policy, now, true)
+            .unwrap()
+            .is_empty());
+        let bumps = age_tasks(&mut storage, &policy, now + Duration::days(14), false).unwrap();
+        assert_eq!(bumps.len(), 1);
+        assert_eq!(bumps[0].idle_days, 14);
+    }
+}
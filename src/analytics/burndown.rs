@@ -0,0 +1,288 @@
+//! Burndown and cumulative-flow data for a milestone
+//!
+//! A milestone is either a tag or a parent task. Each task in it is turned
+//! into a timeline of state changes, and the timelines are replayed to count
+//! open and done tasks at the end of every day. Storage keeps no per-entity
+//! revision history, so a task's timeline comes from its own record: it
+//! opens at `start_time` and, once finished, closes at `end_time`. Tasks
+//! enter the counts on the day they were created; cancelled tasks leave the
+//! scope on the day they were cancelled.
+
+use crate::entities::{Entity, Task, TaskStatus};
+use crate::error::EngramError;
+use crate::storage::Storage;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::Serialize;
+use std::fmt::Write;
+
+/// Which tasks make up a milestone
+#[derive(Debug, Clone, PartialEq)]
+pub enum MilestoneScope {
+    /// Tasks carrying the tag
+    Tag(String),
+    /// Direct children of the task
+    Parent(String),
+}
+
+impl MilestoneScope {
+    pub fn contains(&self, task: &Task, parent: Option<&Task>) -> bool {
+        match self {
+            Self::Tag(tag) => task.tags.iter().any(|t| t == tag),
+            Self::Parent(id) => {
+                task.parent.as_deref() == Some(id.as_str())
+                    || parent.is_some_and(|parent| parent.children.contains(&task.id))
+            }
+        }
+    }
+}
+
+/// Where a task stands in the flow at some moment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FlowState {
+    Open,
+    Done,
+    /// No longer part of the milestone, e.g. cancelled
+    Removed,
+}
+
+/// A task entering `state` at `at`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FlowEvent {
+    pub at: DateTime<Utc>,
+    pub state: FlowState,
+}
+
+/// State changes recorded on a task, oldest first
+pub fn task_timeline(task: &Task) -> Vec<FlowEvent> {
+    let mut events = vec![FlowEvent {
+        at: task.start_time,
+        state: FlowState::Open,
+    }];
+    let closed_state = match task.status {
+        TaskStatus::Done => Some(FlowState::Done),
+        TaskStatus::Cancelled => Some(FlowState::Removed),
+        _ => None,
+    };
+    if let (Some(state), Some(at)) = (closed_state, task.end_time) {
+        events.push(FlowEvent {
+            at: at.max(task.start_time),
+            state,
+        });
+    }
+    events
+}
+
+/// Open and done counts at the end of one day
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BurndownPoint {
+    pub date: NaiveDate,
+    pub open: usize,
+    pub done: usize,
+}
+
+impl BurndownPoint {
+    pub fn total(&self) -> usize {
+        self.open + self.done
+    }
+}
+
+fn end_of_day(date: NaiveDate) -> DateTime<Utc> {
+    (date + Duration::days(1))
+        .and_hms_opt(0, 0, 0)
+        .expect("midnight is a valid time")
+        .and_utc()
+}
+
+/// Replay `timelines` and count each task's latest state at the end of
+/// every day from `from` to `to`, inclusive
+pub fn reconstruct_burndown(
+    timelines: &[Vec<FlowEvent>],
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Vec<BurndownPoint> {
+    let mut points = Vec::new();
+    let mut date = from;
+    while date <= to {
+        let cutoff = end_of_day(date);
+        let mut point = BurndownPoint {
+            date,
+            open: 0,
+            done: 0,
+        };
+        for timeline in timelines {
+            let state = timeline
+                .iter()
+                .filter(|event| event.at < cutoff)
+                .max_by_key(|event| event.at)
+                .map(|event| event.state);
+            match state {
+                Some(FlowState::Open) => point.open += 1,
+                Some(FlowState::Done) => point.done += 1,
+                Some(FlowState::Removed) | None => {}
+            }
+        }
+        points.push(point);
+        date += Duration::days(1);
+    }
+    points
+}
+
+/// Daily burndown of the tasks in `scope` over the `days` days ending on
+/// `now`'s date
+pub fn generate_burndown<S: Storage + ?Sized>(
+    storage: &S,
+    scope: &MilestoneScope,
+    days: u32,
+    now: DateTime<Utc>,
+) -> Result<Vec<BurndownPoint>, EngramError> {
+    let tasks: Vec<Task> = storage
+        .get_all(Task::entity_type())?
+        .into_iter()
+        .filter_map(|generic| Task::from_generic(generic).ok())
+        .collect();
+    let parent = match scope {
+        MilestoneScope::Parent(id) => Some(
+            tasks
+                .iter()
+                .find(|task| task.id == *id)
+                .ok_or_else(|| EngramError::not_found("task", id))?,
+        ),
+        MilestoneScope::Tag(_) => None,
+    };
+    let timelines: Vec<Vec<FlowEvent>> = tasks
+        .iter()
+        .filter(|task| scope.contains(task, parent))
+        .map(task_timeline)
+        .collect();
+
+    let to = now.date_naive();
+    let from = to - Duration::days(i64::from(days.max(1)) - 1);
+    Ok(reconstruct_burndown(&timelines, from, to))
+}
+
+/// Burndown as CSV with a `date,open,done,total` header
+pub fn render_burndown_csv(points: &[BurndownPoint]) -> String {
+    let mut out = String::from("date,open,done,total\n");
+    for point in points {
+        let _ = writeln!(
+            out,
+            "{},{},{},{}",
+            point.date,
+            point.open,
+            point.done,
+            point.total()
+        );
+    }
+    out
+}
+
+/// Horizontal bar per day: `#` for open tasks, `=` for done ones, scaled
+/// to fit `width` columns
+pub fn render_burndown_chart(points: &[BurndownPoint], width: usize) -> String {
+    let max_total = points.iter().map(BurndownPoint::total).max().unwrap_or(0);
+    let width = width.max(1);
+    let scale = |count: usize| {
+        if max_total <= width {
+            count
+        } else {
+            (count * width).div_ceil(max_total)
+        }
+    };
+
+    let mut out = String::new();
+    for point in points {
+        let _ = writeln!(
+            out,
+            "{} |{}{} {}/{}",
+            point.date.format("%m-%d"),
+            "#".repeat(scale(point.open)),
+            "=".repeat(scale(point.done)),
+            point.open,
+            point.total()
+        );
+    }
+    out.push_str("      # open  = done\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::TaskPriority;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, day).unwrap()
+    }
+
+    fn at(day: u32, hour: u32) -> DateTime<Utc> {
+        date(day).and_hms_opt(hour, 0, 0).unwrap().and_utc()
+    }
+
+    fn task(created: DateTime<Utc>, closed: Option<(TaskStatus, DateTime<Utc>)>) -> Task {
+        let mut task = Task::new(
+            "Milestone work".to_string(),
+            String::new(),
+            "agent".to_string(),
+            TaskPriority::Medium,
+            None,
+        );
+        task.start_time = created;
+        task.tags = vec!["milestone-1".to_string()];
+        if let Some((status, end)) = closed {
+            task.status = status;
+            task.end_time = Some(end);
+        }
+        task
+    }
+
+    #[test]
+    fn test_reconstruct_synthetic_timeline() {
+        let tasks = [
+            task(at(1, 9), Some((TaskStatus::Done, at(3, 17)))),
+            task(at(1, 10), None),
+            // Created mid-period: absent until day 4
+            task(at(4, 8), Some((TaskStatus::Done, at(5, 12)))),
+            task(at(2, 9), Some((TaskStatus::Cancelled, at(4, 9)))),
+        ];
+        let timelines: Vec<_> = tasks.iter().map(task_timeline).collect();
+        let points = reconstruct_burndown(&timelines, date(1), date(5));
+
+        let counts: Vec<(usize, usize)> = points.iter().map(|p| (p.open, p.done)).collect();
+        assert_eq!(counts, [(2, 0), (3, 0), (2, 1), (2, 1), (1, 2)]);
+        assert_eq!(points[0].date, date(1));
+        assert_eq!(points[4].total(), 3);
+    }
+
+    #[test]
+    fn test_generate_burndown_by_parent_and_render() {
+        let mut storage = crate::storage::MemoryStorage::new("default");
+        let parent = task(at(1, 8), None);
+        let mut child = task(at(2, 9), Some((TaskStatus::Done, at(3, 9))));
+        child.parent = Some(parent.id.clone());
+        child.tags.clear();
+        let unrelated = task(at(2, 9), None);
+        for task in [&parent, &child, &unrelated] {
+            storage.store(&task.to_generic()).unwrap();
+        }
+
+        let scope = MilestoneScope::Parent(parent.id.clone());
+        let points = generate_burndown(&storage, &scope, 3, at(3, 23)).unwrap();
+        assert_eq!(points.len(), 3);
+        let counts: Vec<(usize, usize)> = points.iter().map(|p| (p.open, p.done)).collect();
+        assert_eq!(counts, [(0, 0), (1, 0), (0, 1)]);
+
+        let csv = render_burndown_csv(&points);
+        assert!(csv.starts_with("date,open,done,total\n2026-03-01,0,0,0\n"));
+        let chart = render_burndown_chart(&points, 40);
+        assert!(chart.contains("03-02 |# 1/1"));
+        assert!(chart.contains("03-03 |= 0/1"));
+
+        let tagged = MilestoneScope::Tag("milestone-1".to_string());
+        let points = generate_burndown(&storage, &tagged, 1, at(3, 23)).unwrap();
+        assert_eq!(points[0].open, 2);
+        assert!(
+            generate_burndown(&storage, &MilestoneScope::Parent("x".into()), 1, at(3, 23)).is_err()
+        );
+    }
+}
@@ -0,0 +1,317 @@
+//! Markdown digest of recent workspace activity
+//!
+//! Renders completed and in-progress tasks, new ADRs and notable knowledge
+//! as GitHub-flavored markdown for standups. Returns a `String` so any
+//! front end can serve or save it.
+
+use crate::entities::{AdrStatus, DependencyGraph, Entity, Knowledge, Task, TaskStatus, ADR};
+use crate::error::EngramError;
+use crate::storage::Storage;
+use chrono::{DateTime, Duration, Utc};
+use std::fmt::Write;
+
+/// Knowledge at or above this confidence is listed as notable
+pub const NOTABLE_KNOWLEDGE_CONFIDENCE: f64 = 0.8;
+
+/// Options for [`generate_markdown_report`]
+#[derive(Debug, Clone)]
+pub struct MarkdownReportOptions {
+    /// Start of the reporting window
+    pub since: DateTime<Utc>,
+    /// End of the reporting window, also used to age in-progress tasks
+    pub now: DateTime<Utc>,
+    /// Only include entities from this agent
+    pub agent: Option<String>,
+}
+
+/// Short link label for a task, e.g. `[T-1a2b3c4d]`
+pub fn task_alias(task: &Task) -> String {
+    format!("[T-{}]", &task.id[..8.min(task.id.len())])
+}
+
+fn knowledge_alias(knowledge: &Knowledge) -> String {
+    format!("[K-{}]", &knowledge.id[..8.min(knowledge.id.len())])
+}
+
+fn format_duration(duration: Duration) -> String {
+    let minutes = duration.num_minutes().max(0);
+    let (days, hours, minutes) = (minutes / 1440, minutes / 60 % 24, minutes % 60);
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+fn status_label(status: &TaskStatus) -> &'static str {
+    match status {
+        TaskStatus::Todo => "todo",
+        TaskStatus::InProgress => "in progress",
+        TaskStatus::Done => "done",
+        TaskStatus::Blocked => "blocked",
+        TaskStatus::Cancelled => "cancelled",
+    }
+}
+
+fn adr_status_label(status: &AdrStatus) -> &'static str {
+    match status {
+        AdrStatus::Proposed => "proposed",
+        AdrStatus::Accepted => "accepted",
+        AdrStatus::Deprecated => "deprecated",
+        AdrStatus::Superseded => "superseded",
+    }
+}
+
+fn load<T: Entity>(storage: &dyn Storage, agent: Option<&str>) -> Result<Vec<T>, EngramError> {
+    Ok(storage
+        .get_all(T::entity_type())?
+        .into_iter()
+        .filter(|generic| agent.is_none_or(|agent| generic.agent == agent))
+        .filter_map(|generic| T::from_generic(generic).ok())
+        .collect())
+}
+
+fn section(out: &mut String, heading: &str, lines: &[String]) {
+    let _ = writeln!(out, "\n## {} ({})\n", heading, lines.len());
+    if lines.is_empty() {
+        out.push_str("_None_\n");
+    }
+    for line in lines {
+        out.push_str(line);
+        out.push('\n');
+    }
+}
+
+/// Render the markdown digest for `options`
+pub fn generate_markdown_report(
+    storage: &dyn Storage,
+    options: &MarkdownReportOptions,
+) -> Result<String, EngramError> {
+    let agent = options.agent.as_deref();
+    let in_window = |time: DateTime<Utc>| time >= options.since && time <= options.now;
+    let graph = DependencyGraph::load(storage)?;
+    let tasks: Vec<Task> = load(storage, agent)?;
+
+    let mut completed: Vec<&Task> = tasks
+        .iter()
+        .filter(|t| t.status == TaskStatus::Done && t.end_time.is_some_and(in_window))
+        .collect();
+    completed.sort_by(|a, b| b.end_time.cmp(&a.end_time).then(a.title.cmp(&b.title)));
+    let completed: Vec<String> = completed
+        .into_iter()
+        .map(|task| {
+            let end = task.end_time.unwrap_or(options.now);
+            format!(
+                "- {} {} — took {}",
+                task_alias(task),
+                task.title,
+                format_duration(end - task.start_time)
+            )
+        })
+        .collect();
+
+    let mut in_progress: Vec<&Task> = tasks
+        .iter()
+        .filter(|t| matches!(t.status, TaskStatus::InProgress | TaskStatus::Blocked))
+        .collect();
+    in_progress.sort_by(|a, b| a.start_time.cmp(&b.start_time).then(a.title.cmp(&b.title)));
+    let in_progress: Vec<String> = in_progress
+        .into_iter()
+        .map(|task| {
+            let mut entry = format!(
+                "- {} {} — open {}",
+                task_alias(task),
+                task.title,
+                format_duration(options.now - task.start_time)
+            );
+            if let Some(reason) = &task.block_reason {
+                let _ = write!(entry, "\n  - ⛔ {}", reason);
+            }
+            let mut blockers: Vec<&Task> = graph
+                .dependencies_of(&task.id)
+                .iter()
+                .filter_map(|id| graph.task(id))
+                .filter(|dep| !matches!(dep.status, TaskStatus::Done | TaskStatus::Cancelled))
+                .collect();
+            blockers.sort_by(|a, b| a.title.cmp(&b.title));
+            for blocker in blockers {
+                let _ = write!(
+                    entry,
+                    "\n  - waiting on {} {} ({})",
+                    task_alias(blocker),
+                    blocker.title,
+                    status_label(&blocker.status)
+                );
+            }
+            entry
+        })
+        .collect();
+
+    let mut adrs: Vec<ADR> = load(storage, agent)?;
+    adrs.retain(|adr| in_window(adr.created_at));
+    adrs.sort_by_key(|adr| adr.number);
+    let adrs: Vec<String> = adrs
+        .iter()
+        .map(|adr| {
+            format!(
+                "- [ADR-{}] {} — {}",
+                adr.number,
+                adr.title,
+                adr_status_label(&adr.status)
+            )
+        })
+        .collect();
+
+    let mut knowledge: Vec<Knowledge> = load(storage, agent)?;
+    knowledge.retain(|k| in_window(k.created_at) && k.confidence >= NOTABLE_KNOWLEDGE_CONFIDENCE);
+    knowledge.sort_by(|a, b| {
+        b.confidence
+            .total_cmp(&a.confidence)
+            .then(a.title.cmp(&b.title))
+    });
+    let knowledge: Vec<String> = knowledge
+        .iter()
+        .map(|k| {
+            format!(
+                "- {} {} ({}, confidence {:.2})",
+                knowledge_alias(k),
+                k.title,
+                format!("{:?}", k.knowledge_type).to_lowercase(),
+                k.confidence
+            )
+        })
+        .collect();
+
+    let mut out = format!(
+        "# Engram report: {} to {}\n",
+        options.since.format("%Y-%m-%d"),
+        options.now.format("%Y-%m-%d")
+    );
+    if let Some(agent) = agent {
+        let _ = writeln!(out, "\nAgent: `{}`", agent);
+    }
+    section(&mut out, "✅ Completed", &completed);
+    section(&mut out, "🚧 In progress", &in_progress);
+    section(&mut out, "📐 New ADRs", &adrs);
+    section(&mut out, "💡 Notable knowledge", &knowledge);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{EntityRelationType, EntityRelationship, KnowledgeType, TaskPriority};
+    use crate::storage::MemoryStorage;
+    use chrono::TimeZone;
+
+    /// Fixture workspace with fixed IDs and timestamps
+    fn fixture(now: DateTime<Utc>) -> MemoryStorage {
+        let mut storage = MemoryStorage::new("default");
+        let task = |id: &str, title: &str, agent: &str, status: TaskStatus, age_hours: i64| {
+            let mut task = Task::new(
+                title.to_string(),
+                String::new(),
+                agent.to_string(),
+                TaskPriority::Medium,
+                None,
+            );
+            task.id = id.to_string();
+            task.status = status;
+            task.start_time = now - Duration::hours(age_hours);
+            task
+        };
+
+        let mut done = task("aaaa1111-0000", "Ship login", "alice", TaskStatus::Done, 50);
+        done.end_time = Some(now - Duration::hours(20));
+        let mut old_done = task("aaaa2222-0000", "Old work", "alice", TaskStatus::Done, 900);
+        old_done.end_time = Some(now - Duration::days(30));
+        let api = task(
+            "bbbb1111-0000",
+            "Build API",
+            "alice",
+            TaskStatus::InProgress,
+            75,
+        );
+        let schema = task(
+            "bbbb2222-0000",
+            "Design schema",
+            "bob",
+            TaskStatus::Todo,
+            10,
+        );
+        let mut blocked = task("bbbb3333-0000", "Deploy", "bob", TaskStatus::Blocked, 3);
+        blocked.block_reason = Some("Waiting for credentials".to_string());
+        for t in [&done, &old_done, &api, &schema, &blocked] {
+            storage.store(&t.to_generic()).unwrap();
+        }
+        let depends = EntityRelationship::new(
+            "rel-1".to_string(),
+            "alice".to_string(),
+            api.id.clone(),
+            "task".to_string(),
+            schema.id.clone(),
+            "task".to_string(),
+            EntityRelationType::DependsOn,
+        );
+        storage.store(&depends.to_generic()).unwrap();
+
+        let mut adr = ADR::new(
+            "Use Postgres".to_string(),
+            7,
+            "alice".to_string(),
+            "We need a database".to_string(),
+        );
+        adr.id = "adr-1".to_string();
+        adr.status = AdrStatus::Accepted;
+        adr.created_at = now - Duration::days(2);
+        storage.store(&adr.to_generic()).unwrap();
+
+        for (id, title, confidence) in [
+            ("cccc1111-0000", "Retries need jitter", 0.9),
+            ("cccc2222-0000", "Maybe cache tokens", 0.4),
+        ] {
+            let mut knowledge = Knowledge::new(
+                title.to_string(),
+                "content".to_string(),
+                KnowledgeType::Pattern,
+                confidence,
+                "bob".to_string(),
+            );
+            knowledge.id = id.to_string();
+            knowledge.created_at = now - Duration::days(1);
+            storage.store(&knowledge.to_generic()).unwrap();
+        }
+        storage
+    }
+
+    #[test]
+    fn test_markdown_report_matches_golden_file() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 13, 12, 0, 0).unwrap();
+        let storage = fixture(now);
+        let options = MarkdownReportOptions {
+            since: now - Duration::days(7),
+            now,
+            agent: None,
+        };
+        let report = generate_markdown_report(&storage, &options).unwrap();
+        assert_eq!(
+            report,
+            include_str!("../../tests/fixtures/markdown_report.md")
+        );
+
+        let alice = generate_markdown_report(
+            &storage,
+            &MarkdownReportOptions {
+                agent: Some("alice".to_string()),
+                ..options
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            alice,
+            include_str!("../../tests/fixtures/markdown_report_alice.md")
+        );
+    }
+}
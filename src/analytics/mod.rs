@@ -0,0 +1,620 @@
+//! Cross-entity analytics for Engram
+//!
+//! Report generators that aggregate over many stored entities at once.
+//! Unlike the report entities in `crate::entities`, these reports are
+//! computed on demand and are not persisted.
+
+pub mod burndown;
+pub mod markdown_report;
+
+pub use burndown::*;
+pub use markdown_report::*;
+
+use crate::engines::workflow_engine::WorkflowStatus;
+use crate::entities::{
+    full_execution_history, Entity, ExecutionResult, Knowledge, Session, SessionStatus, Task,
+    TaskStatus, WorkflowInstance,
+};
+use crate::error::EngramError;
+use crate::storage::Storage;
+use chrono::{Duration, Utc};
+use serde::{Serialize, Serializer};
+use std::collections::{BTreeSet, HashMap};
+
+/// Aggregated execution history across all instances of a workflow
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkflowAuditReport {
+    pub workflow_id: String,
+    pub days: u32,
+    pub total_instances: u32,
+    pub completed_instances: u32,
+    pub failed_instances: u32,
+    pub average_duration_seconds: f64,
+    pub slowest_instance_id: Option<String>,
+    pub most_common_failure_state: Option<String>,
+    /// Transition counts keyed by (from_state, to_state)
+    #[serde(serialize_with = "serialize_heatmap")]
+    pub transition_heatmap: HashMap<(String, String), u32>,
+    /// Number of instances executed by each agent
+    pub agent_usage: HashMap<String, u32>,
+}
+
+#[derive(Serialize)]
+struct HeatmapEntry<'a> {
+    from: &'a str,
+    to: &'a str,
+    count: u32,
+}
+
+/// JSON object keys must be strings, so the heatmap is emitted as a sorted list
+fn serialize_heatmap<S: Serializer>(
+    heatmap: &HashMap<(String, String), u32>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut entries: Vec<HeatmapEntry> = heatmap
+        .iter()
+        .map(|((from, to), count)| HeatmapEntry {
+            from,
+            to,
+            count: *count,
+        })
+        .collect();
+    entries.sort_by(|a, b| (a.from, a.to).cmp(&(b.from, b.to)));
+    entries.serialize(serializer)
+}
+
+/// Build an audit report for every instance of `workflow_id` started in the last `days` days
+pub fn generate_workflow_audit_report(
+    storage: &dyn Storage,
+    workflow_id: &str,
+    days: u32,
+) -> Result<WorkflowAuditReport, EngramError> {
+    let cutoff = Utc::now() - Duration::days(days as i64);
+
+    let instances: Vec<WorkflowInstance> = storage
+        .get_all(WorkflowInstance::entity_type())?
+        .into_iter()
+        .filter_map(|generic| WorkflowInstance::from_generic(generic).ok())
+        .filter(|instance| instance.workflow_id == workflow_id && instance.started_at >= cutoff)
+        .collect();
+
+    let mut report = WorkflowAuditReport {
+        workflow_id: workflow_id.to_string(),
+        days,
+        total_instances: instances.len() as u32,
+        completed_instances: 0,
+        failed_instances: 0,
+        average_duration_seconds: 0.0,
+        slowest_instance_id: None,
+        most_common_failure_state: None,
+        transition_heatmap: HashMap::new(),
+        agent_usage: HashMap::new(),
+    };
+
+    let mut failure_states: HashMap<String, u32> = HashMap::new();
+    let mut total_duration = 0i64;
+    let mut finished = 0i64;
+    let mut slowest: Option<(i64, String)> = None;
+
+    for instance in &instances {
+        match &instance.status {
+            WorkflowStatus::Completed => report.completed_instances += 1,
+            WorkflowStatus::Failed(_) => {
+                report.failed_instances += 1;
+                *failure_states
+                    .entry(instance.current_state.clone())
+                    .or_insert(0) += 1;
+            }
+            _ => {}
+        }
+
+        if let Some(completed_at) = instance.completed_at {
+            let seconds = (completed_at - instance.started_at).num_seconds();
+            total_duration += seconds;
+            finished += 1;
+            if slowest.as_ref().is_none_or(|(max, _)| seconds > *max) {
+                slowest = Some((seconds, instance.id.clone()));
+            }
+        }
+
+        for event in &full_execution_history(storage, instance)? {
+            if let (Some(from), Some(to)) = (&event.from_state, &event.to_state) {
+                *report
+                    .transition_heatmap
+                    .entry((from.clone(), to.clone()))
+                    .or_insert(0) += 1;
+            }
+        }
+
+        *report
+            .agent_usage
+            .entry(instance.context.executing_agent.clone())
+            .or_insert(0) += 1;
+    }
+
+    if finished > 0 {
+        report.average_duration_seconds = total_duration as f64 / finished as f64;
+    }
+    report.slowest_instance_id = slowest.map(|(_, id)| id);
+    report.most_common_failure_state = failure_states
+        .into_iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(&a.0)))
+        .map(|(state, _)| state);
+
+    Ok(report)
+}
+
+/// Render the transition heatmap as an ASCII matrix (rows = from, columns = to)
+pub fn render_transition_matrix(heatmap: &HashMap<(String, String), u32>) -> String {
+    if heatmap.is_empty() {
+        return "(no transitions recorded)\n".to_string();
+    }
+
+    let from_states: BTreeSet<&str> = heatmap.keys().map(|(from, _)| from.as_str()).collect();
+    let to_states: BTreeSet<&str> = heatmap.keys().map(|(_, to)| to.as_str()).collect();
+
+    let label_width = from_states
+        .iter()
+        .map(|s| s.len())
+        .max()
+        .unwrap_or(0)
+        .max("from \\ to".len());
+    let col_widths: Vec<usize> = to_states
+        .iter()
+        .map(|to| {
+            let max_count = from_states
+                .iter()
+                .filter_map(|from| heatmap.get(&(from.to_string(), to.to_string())))
+                .map(|count| count.to_string().len())
+                .max()
+                .unwrap_or(1);
+            to.len().max(max_count)
+        })
+        .collect();
+
+    let mut out = String::new();
+    out.push_str(&format!("{:<width$}", "from \\ to", width = label_width));
+    for (to, width) in to_states.iter().zip(&col_widths) {
+        out.push_str(&format!(" | {:>width$}", to, width = width));
+    }
+    out.push('\n');
+
+    let rule_len = label_width + col_widths.iter().map(|w| w + 3).sum::<usize>();
+    out.push_str(&"-".repeat(rule_len));
+    out.push('\n');
+
+    for from in &from_states {
+        out.push_str(&format!("{:<width$}", from, width = label_width));
+        for (to, width) in to_states.iter().zip(&col_widths) {
+            let cell = heatmap
+                .get(&(from.to_string(), to.to_string()))
+                .map(|count| count.to_string())
+                .unwrap_or_else(|| ".".to_string());
+            out.push_str(&format!(" | {:>width$}", cell, width = width));
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+/// Composite score weights for [`compute_agent_leaderboard`]
+pub const LEADERBOARD_TASK_WEIGHT: f64 = 0.4;
+pub const LEADERBOARD_GATE_WEIGHT: f64 = 0.3;
+pub const LEADERBOARD_KNOWLEDGE_WEIGHT: f64 = 0.2;
+pub const LEADERBOARD_SESSION_WEIGHT: f64 = 0.1;
+
+/// One agent's activity over the leaderboard window
+#[derive(Debug, Clone, Serialize)]
+pub struct AgentLeaderboardEntry {
+    pub agent_id: String,
+    pub tasks_completed: u32,
+    pub avg_task_duration_seconds: f64,
+    pub knowledge_items_created: u32,
+    pub sessions_started: u32,
+    /// Sessions closed with recorded outcomes, over sessions started
+    pub session_discipline: f64,
+    /// Passed over passed + failed execution results; skipped results are ignored
+    pub quality_gate_pass_rate: f64,
+    pub composite_score: f64,
+}
+
+#[derive(Default)]
+struct AgentActivity {
+    tasks_completed: u32,
+    task_seconds: i64,
+    knowledge: u32,
+    sessions: u32,
+    sessions_closed: u32,
+    gates_passed: u32,
+    gates_failed: u32,
+}
+
+/// Rank agents by activity in the last `days` days.
+///
+/// Task completion and knowledge creation are normalised against the most
+/// active agent before weighting, so the composite score is in `0.0..=1.0`.
+pub fn compute_agent_leaderboard(
+    storage: &dyn Storage,
+    days: u32,
+) -> Result<Vec<AgentLeaderboardEntry>, EngramError> {
+    let cutoff = Utc::now() - Duration::days(days as i64);
+    let mut activity: HashMap<String, AgentActivity> = HashMap::new();
+
+    for generic in storage.get_all(Task::entity_type())? {
+        let Ok(task) = Task::from_generic(generic) else {
+            continue;
+        };
+        let Some(end) = task.end_time else {
+            continue;
+        };
+        if task.status == TaskStatus::Done && end >= cutoff {
+            let entry = activity.entry(task.agent.clone()).or_default();
+            entry.tasks_completed += 1;
+            entry.task_seconds += (end - task.start_time).num_seconds().max(0);
+        }
+    }
+
+    for generic in storage.get_all(Knowledge::entity_type())? {
+        if let Ok(knowledge) = Knowledge::from_generic(generic) {
+            if knowledge.created_at >= cutoff {
+                activity.entry(knowledge.agent).or_default().knowledge += 1;
+            }
+        }
+    }
+
+    for generic in storage.get_all(Session::entity_type())? {
+        if let Ok(session) = Session::from_generic(generic) {
+            if session.start_time >= cutoff {
+                let entry = activity.entry(session.agent.clone()).or_default();
+                entry.sessions += 1;
+                if session.status == SessionStatus::Completed && !session.outcomes.is_empty() {
+                    entry.sessions_closed += 1;
+                }
+            }
+        }
+    }
+
+    for generic in storage.get_all(ExecutionResult::entity_type())? {
+        if let Ok(result) = ExecutionResult::from_generic(generic) {
+            if result.timestamp >= cutoff {
+                let entry = activity.entry(result.agent.clone()).or_default();
+                if result.passed() {
+                    entry.gates_passed += 1;
+                } else if result.failed() {
+                    entry.gates_failed += 1;
+                }
+            }
+        }
+    }
+
+    let max_tasks = activity
+        .values()
+        .map(|a| a.tasks_completed)
+        .max()
+        .unwrap_or(0);
+    let max_knowledge = activity.values().map(|a| a.knowledge).max().unwrap_or(0);
+    let ratio = |num: u32, den: u32| {
+        if den == 0 {
+            0.0
+        } else {
+            num as f64 / den as f64
+        }
+    };
+
+    let mut entries: Vec<AgentLeaderboardEntry> = activity
+        .into_iter()
+        .map(|(agent_id, a)| {
+            let pass_rate = ratio(a.gates_passed, a.gates_passed + a.gates_failed);
+            let discipline = ratio(a.sessions_closed, a.sessions);
+            let composite_score = LEADERBOARD_TASK_WEIGHT * ratio(a.tasks_completed, max_tasks)
+                + LEADERBOARD_GATE_WEIGHT * pass_rate
+                + LEADERBOARD_KNOWLEDGE_WEIGHT * ratio(a.knowledge, max_knowledge)
+                + LEADERBOARD_SESSION_WEIGHT * discipline;
+            AgentLeaderboardEntry {
+                agent_id,
+                tasks_completed: a.tasks_completed,
+                avg_task_duration_seconds: if a.tasks_completed == 0 {
+                    0.0
+                } else {
+                    a.task_seconds as f64 / a.tasks_completed as f64
+                },
+                knowledge_items_created: a.knowledge,
+                sessions_started: a.sessions,
+                session_discipline: discipline,
+                quality_gate_pass_rate: pass_rate,
+                composite_score,
+            }
+        })
+        .collect();
+
+    entries.sort_by(|a, b| {
+        b.composite_score
+            .partial_cmp(&a.composite_score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| b.tasks_completed.cmp(&a.tasks_completed))
+            .then_with(|| a.agent_id.cmp(&b.agent_id))
+    });
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::workflow_engine::{
+        WorkflowEventType, WorkflowExecutionContext, WorkflowExecutionEvent,
+    };
+    use crate::storage::MemoryStorage;
+
+    fn event(from: &str, to: &str, agent: &str) -> WorkflowExecutionEvent {
+        WorkflowExecutionEvent {
+            id: uuid::Uuid::new_v4().to_string(),
+            timestamp: Utc::now(),
+            event_type: WorkflowEventType::Transitioned,
+            from_state: Some(from.to_string()),
+            to_state: Some(to.to_string()),
+            transition_id: None,
+            agent: agent.to_string(),
+            message: String::new(),
+            metadata: HashMap::new(),
+        }
+    }
+
+    fn instance(
+        id: &str,
+        workflow_id: &str,
+        agent: &str,
+        status: WorkflowStatus,
+        current_state: &str,
+        path: &[&str],
+        duration_secs: Option<i64>,
+    ) -> WorkflowInstance {
+        let started_at = Utc::now() - Duration::hours(2);
+        let execution_history = path
+            .windows(2)
+            .map(|pair| event(pair[0], pair[1], agent))
+            .collect();
+        WorkflowInstance {
+            id: id.to_string(),
+            workflow_id: workflow_id.to_string(),
+            current_state: current_state.to_string(),
+            context: WorkflowExecutionContext {
+                variables: HashMap::new(),
+                entity_id: None,
+                entity_type: None,
+                executing_agent: agent.to_string(),
+                permissions: Vec::new(),
+                metadata: HashMap::new(),
+            },
+            status,
+            started_at,
+            updated_at: started_at,
+            completed_at: duration_secs.map(|secs| started_at + Duration::seconds(secs)),
+            execution_history,
+            step_count: path.len().saturating_sub(1) as u64,
+            archived_event_count: 0,
+        }
+    }
+
+    fn seeded_storage() -> MemoryStorage {
+        let mut storage = MemoryStorage::new("test-agent");
+        let mut instances = Vec::new();
+        for i in 0..6 {
+            instances.push(instance(
+                &format!("done-{}", i),
+                "wf-1",
+                if i % 2 == 0 { "alice" } else { "bob" },
+                WorkflowStatus::Completed,
+                "done",
+                &["todo", "doing", "review", "done"],
+                Some(60 * (i + 1)),
+            ));
+        }
+        for i in 0..3 {
+            instances.push(instance(
+                &format!("failed-{}", i),
+                "wf-1",
+                "carol",
+                WorkflowStatus::Failed("checks failed".to_string()),
+                "review",
+                &["todo", "doing", "review"],
+                Some(30),
+            ));
+        }
+        instances.push(instance(
+            "running-0",
+            "wf-1",
+            "alice",
+            WorkflowStatus::Running,
+            "doing",
+            &["todo", "doing"],
+            None,
+        ));
+        // Belongs to a different workflow and must be ignored
+        instances.push(instance(
+            "other-0",
+            "wf-2",
+            "alice",
+            WorkflowStatus::Completed,
+            "done",
+            &["todo", "blocked"],
+            Some(10_000),
+        ));
+
+        // Keep one embedded event so the reports must also read archived ones
+        for mut instance in instances {
+            crate::entities::store_workflow_instance(&mut storage, &mut instance, 1).unwrap();
+        }
+        storage
+    }
+
+    #[test]
+    fn test_audit_report_counts() {
+        let storage = seeded_storage();
+        let report = generate_workflow_audit_report(&storage, "wf-1", 30).unwrap();
+
+        assert_eq!(report.total_instances, 10);
+        assert_eq!(report.completed_instances, 6);
+        assert_eq!(report.failed_instances, 3);
+        assert_eq!(report.slowest_instance_id.as_deref(), Some("done-5"));
+        assert_eq!(report.most_common_failure_state.as_deref(), Some("review"));
+        assert_eq!(report.agent_usage.get("carol"), Some(&3));
+        assert_eq!(report.agent_usage.get("alice"), Some(&4));
+    }
+
+    #[test]
+    fn test_audit_report_heatmap_contains_all_transitions() {
+        let storage = seeded_storage();
+        let report = generate_workflow_audit_report(&storage, "wf-1", 30).unwrap();
+
+        let key = |from: &str, to: &str| (from.to_string(), to.to_string());
+        assert_eq!(
+            report.transition_heatmap.get(&key("todo", "doing")),
+            Some(&10)
+        );
+        assert_eq!(
+            report.transition_heatmap.get(&key("doing", "review")),
+            Some(&9)
+        );
+        assert_eq!(
+            report.transition_heatmap.get(&key("review", "done")),
+            Some(&6)
+        );
+        assert!(!report
+            .transition_heatmap
+            .contains_key(&key("todo", "blocked")));
+        assert_eq!(report.transition_heatmap.len(), 3);
+    }
+
+    #[test]
+    fn test_audit_report_average_duration() {
+        let storage = seeded_storage();
+        let report = generate_workflow_audit_report(&storage, "wf-1", 30).unwrap();
+
+        // 6 completed (60..360s) + 3 failed (30s each) finished instances
+        let expected = (60 + 120 + 180 + 240 + 300 + 360 + 90) as f64 / 9.0;
+        assert!((report.average_duration_seconds - expected).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_audit_report_empty_workflow() {
+        let storage = MemoryStorage::new("test-agent");
+        let report = generate_workflow_audit_report(&storage, "missing", 30).unwrap();
+
+        assert_eq!(report.total_instances, 0);
+        assert!(report.slowest_instance_id.is_none());
+        assert!(report.most_common_failure_state.is_none());
+        assert_eq!(
+            render_transition_matrix(&report.transition_heatmap),
+            "(no transitions recorded)\n"
+        );
+    }
+
+    #[test]
+    fn test_render_transition_matrix() {
+        let storage = seeded_storage();
+        let report = generate_workflow_audit_report(&storage, "wf-1", 30).unwrap();
+        let matrix = render_transition_matrix(&report.transition_heatmap);
+
+        let lines: Vec<&str> = matrix.lines().collect();
+        assert!(lines[0].starts_with("from \\ to"));
+        assert!(lines[0].contains("doing") && lines[0].contains("done"));
+        assert!(lines
+            .iter()
+            .any(|l| l.starts_with("todo") && l.contains("10")));
+        assert_eq!(lines.len(), 2 + 3);
+    }
+
+    #[test]
+    fn test_audit_report_json_heatmap_is_list() {
+        let storage = seeded_storage();
+        let report = generate_workflow_audit_report(&storage, "wf-1", 30).unwrap();
+        let json = serde_json::to_value(&report).unwrap();
+
+        let heatmap = json["transition_heatmap"].as_array().unwrap();
+        assert_eq!(heatmap.len(), 3);
+        assert_eq!(heatmap[0]["from"], "doing");
+        assert_eq!(heatmap[0]["to"], "review");
+        assert_eq!(heatmap[0]["count"], 9);
+    }
+
+    fn seed_agent(
+        storage: &mut MemoryStorage,
+        agent: &str,
+        tasks_done: usize,
+        knowledge: usize,
+        gates: (usize, usize),
+        sessions: (usize, usize),
+    ) {
+        for i in 0..tasks_done {
+            let mut task = Task::new(
+                format!("{} task {}", agent, i),
+                String::new(),
+                agent.to_string(),
+                crate::entities::TaskPriority::Medium,
+                None,
+            );
+            task.start_time = Utc::now() - Duration::hours(3);
+            task.complete("done".to_string());
+            storage.store(&task.to_generic()).unwrap();
+        }
+        for i in 0..knowledge {
+            let k = Knowledge::new(
+                format!("{} fact {}", agent, i),
+                "content".to_string(),
+                crate::entities::KnowledgeType::Fact,
+                0.8,
+                agent.to_string(),
+            );
+            storage.store(&k.to_generic()).unwrap();
+        }
+        let (passed, failed) = gates;
+        for i in 0..passed + failed {
+            let mut result = ExecutionResult::new(
+                "t".to_string(),
+                "dev".to_string(),
+                "gate".to_string(),
+                "true".to_string(),
+                agent.to_string(),
+            );
+            result.set_expected_result(crate::entities::ExpectedResult::Success);
+            result.set_results(
+                if i < passed { 0 } else { 1 },
+                String::new(),
+                String::new(),
+                5,
+            );
+            storage.store(&result.to_generic()).unwrap();
+        }
+        let (started, closed) = sessions;
+        for i in 0..started {
+            let mut session = Session::new(format!("s{}", i), agent.to_string(), Vec::new());
+            if i < closed {
+                session.complete(vec!["shipped".to_string()]);
+            }
+            storage.store(&session.to_generic()).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_agent_leaderboard_ranking() {
+        let mut storage = MemoryStorage::new("test-agent");
+        seed_agent(&mut storage, "alice", 4, 2, (9, 1), (2, 2));
+        seed_agent(&mut storage, "bob", 2, 4, (1, 1), (2, 1));
+        seed_agent(&mut storage, "carol", 1, 0, (0, 2), (1, 0));
+
+        let board = compute_agent_leaderboard(&storage, 30).unwrap();
+        let order: Vec<&str> = board.iter().map(|e| e.agent_id.as_str()).collect();
+        assert_eq!(order, vec!["alice", "bob", "carol"]);
+
+        let alice = &board[0];
+        assert_eq!(alice.tasks_completed, 4);
+        assert_eq!(alice.knowledge_items_created, 2);
+        assert_eq!(alice.sessions_started, 2);
+        assert!((alice.quality_gate_pass_rate - 0.9).abs() < 1e-9);
+        assert!(alice.avg_task_duration_seconds >= 3.0 * 3600.0);
+        // 0.4 * 1.0 + 0.3 * 0.9 + 0.2 * 0.5 + 0.1 * 1.0
+        assert!((alice.composite_score - 0.87).abs() < 1e-9);
+        assert_eq!(board[2].quality_gate_pass_rate, 0.0);
+    }
+}
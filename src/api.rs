@@ -0,0 +1,453 @@
+//! Library-facing API
+//!
+//! [`Engram`] bundles a storage backend with the identity it acts as and
+//! exposes the common operations as typed methods that return values
+//! instead of printing. The CLI commands for creating and listing tasks,
+//! linking entities, validating commits and picking the next task go
+//! through the same code, so embedding engram behaves like running it.
+//!
+//! Every method is synchronous; no async runtime is needed.
+//!
+//! ```
+//! use engram::storage::MemoryStorage;
+//! use engram::{Engram, TaskDraft, TaskFilter, TaskPriority};
+//!
+//! let mut engram = Engram::with_storage(MemoryStorage::new("alice"), "alice");
+//! let task = engram.create_task(TaskDraft {
+//!     priority: TaskPriority::High,
+//!     ..TaskDraft::new("Write the release notes")
+//! })?;
+//!
+//! let page = engram.list_tasks(TaskFilter {
+//!     agent: Some("alice".to_string()),
+//!     ..TaskFilter::default()
+//! })?;
+//! assert_eq!(page.entities[0].id, task.id);
+//! assert_eq!(engram.next_task("alice")?.map(|next| next.id), Some(task.id));
+//! # Ok::<(), engram::EngramError>(())
+//! ```
+
+use crate::cli::identity::{resolve_identity, IDENTITY_ENV_VAR};
+use crate::cli::next::{find_next_task, NextScope};
+use crate::config::Config;
+use crate::entities::{
+    Entity, EntityRelationType, EntityRelationship, Task, TaskPriority, TaskStatus,
+};
+use crate::error::EngramError;
+use crate::storage::{
+    GitRefsStorage, QueryFilter, QueryResult, RelationshipStorage, SortOrder, Storage,
+};
+use crate::validation::{classify_commit, CommitValidator, ValidationConfig, ValidationResult};
+use chrono::{DateTime, Utc};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// A task to create with [`Engram::create_task`]
+#[derive(Debug, Clone)]
+pub struct TaskDraft {
+    pub title: String,
+    pub description: String,
+    pub priority: TaskPriority,
+    /// Assigned agent; the facade's own agent when `None`
+    pub agent: Option<String>,
+    pub parent: Option<String>,
+    pub tags: Vec<String>,
+    pub due_at: Option<DateTime<Utc>>,
+    pub estimate_seconds: Option<u64>,
+}
+
+impl TaskDraft {
+    /// A medium-priority task with only a title
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            description: String::new(),
+            priority: TaskPriority::Medium,
+            agent: None,
+            parent: None,
+            tags: Vec::new(),
+            due_at: None,
+            estimate_seconds: None,
+        }
+    }
+
+    fn into_task(self, default_agent: &str) -> Task {
+        let mut task = Task::new(
+            self.title,
+            self.description,
+            self.agent.unwrap_or_else(|| default_agent.to_string()),
+            self.priority,
+            None,
+        );
+        task.parent = self.parent;
+        task.tags = self.tags;
+        task.due_at = self.due_at;
+        task.estimate_seconds = self.estimate_seconds;
+        task
+    }
+}
+
+/// Which tasks [`Engram::list_tasks`] returns. Unset fields don't filter.
+#[derive(Debug, Clone, Default)]
+pub struct TaskFilter {
+    pub agent: Option<String>,
+    pub status: Option<TaskStatus>,
+    pub workflow_instance_id: Option<String>,
+    pub workflow_state: Option<String>,
+    pub search: Option<String>,
+    pub fuzzy: bool,
+    /// Data field to sort by; dots address nested fields
+    pub sort_by: Option<String>,
+    /// Defaults to ascending with `sort_by`, newest first without
+    pub sort_order: Option<SortOrder>,
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+}
+
+impl TaskFilter {
+    /// The storage query selecting these tasks
+    pub fn to_query_filter(&self) -> QueryFilter {
+        let mut filter = QueryFilter {
+            entity_type: Some(Task::entity_type().to_string()),
+            agent: self.agent.clone(),
+            text_search: self.search.clone(),
+            fuzzy: self.fuzzy,
+            sort_by: self.sort_by.clone(),
+            limit: self.limit,
+            offset: self.offset,
+            ..Default::default()
+        };
+        if let Some(order) = &self.sort_order {
+            filter.sort_order = order.clone();
+        } else if self.sort_by.is_some() {
+            filter.sort_order = SortOrder::Asc;
+        }
+        if let Some(workflow_id) = &self.workflow_instance_id {
+            filter
+                .field_filters
+                .insert("workflow_id".to_string(), workflow_id.clone().into());
+        }
+        if let Some(status) = &self.status {
+            filter.field_filters.insert(
+                "status".to_string(),
+                serde_json::to_value(status).unwrap_or_default(),
+            );
+        }
+        if let Some(state) = &self.workflow_state {
+            filter
+                .field_filters
+                .insert("workflow_state".to_string(), state.clone().into());
+        }
+        filter
+    }
+
+    /// Run the query against `storage`. Entities that don't parse as tasks
+    /// are left out of the page but still counted in `total_count`.
+    pub fn query<S: Storage + ?Sized>(
+        &self,
+        storage: &S,
+    ) -> Result<QueryResult<Task>, EngramError> {
+        let result = storage.query(&self.to_query_filter())?;
+        Ok(QueryResult {
+            entities: result
+                .entities
+                .into_iter()
+                .filter_map(|entity| Task::from_generic(entity).ok())
+                .collect(),
+            total_count: result.total_count,
+            has_more: result.has_more,
+        })
+    }
+}
+
+/// High-level handle on a workspace for embedding engram in other tools
+pub struct Engram<S: Storage = GitRefsStorage> {
+    storage: S,
+    agent: String,
+    root: PathBuf,
+}
+
+impl Engram<GitRefsStorage> {
+    /// Open (initializing if needed) the workspace at `root`. The agent is
+    /// `ENGRAM_AGENT`, else `workspace.default_agent` from the root's
+    /// `engram.yaml`, else `default`. An `engram.yaml` that does not parse
+    /// is an error.
+    ///
+    /// ```
+    /// let dir = tempfile::tempdir()?;
+    /// let mut engram = engram::Engram::open(dir.path())?;
+    /// let task = engram.create_task(engram::TaskDraft::new("Set up CI"))?;
+    /// assert_eq!(task.agent, engram.agent());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn open(root: impl AsRef<Path>) -> Result<Self, EngramError> {
+        let root = root.as_ref();
+        let config = Config::workspace_config_file(root)
+            .map(|path| Config::load_from_file(&path.to_string_lossy()))
+            .transpose()?;
+        Self::open_as(root, current_identity(config.as_ref()))
+    }
+
+    /// Open the workspace at `root` as the agent `config` names, unless
+    /// `ENGRAM_AGENT` overrides it
+    pub fn from_config(config: &Config, root: impl AsRef<Path>) -> Result<Self, EngramError> {
+        Self::open_as(root.as_ref(), current_identity(Some(config)))
+    }
+
+    fn open_as(root: &Path, agent: String) -> Result<Self, EngramError> {
+        let storage = GitRefsStorage::new(&root.to_string_lossy(), &agent)?;
+        Ok(Self {
+            storage,
+            agent,
+            root: root.to_path_buf(),
+        })
+    }
+}
+
+fn current_identity(config: Option<&Config>) -> String {
+    resolve_identity(std::env::var(IDENTITY_ENV_VAR).ok().as_deref(), config)
+}
+
+impl<S: Storage> Engram<S> {
+    /// Wrap an already opened backend, acting as `agent` in the current
+    /// directory's workspace
+    pub fn with_storage(storage: S, agent: impl Into<String>) -> Self {
+        Self {
+            storage,
+            agent: agent.into(),
+            root: PathBuf::from("."),
+        }
+    }
+
+    /// Agent that new entities are attributed to
+    pub fn agent(&self) -> &str {
+        &self.agent
+    }
+
+    pub fn storage(&self) -> &S {
+        &self.storage
+    }
+
+    pub fn storage_mut(&mut self) -> &mut S {
+        &mut self.storage
+    }
+
+    pub fn into_storage(self) -> S {
+        self.storage
+    }
+
+    /// Store a new task and return it. Duplicate detection is left to the
+    /// caller (the CLI prompts for it).
+    pub fn create_task(&mut self, draft: TaskDraft) -> Result<Task, EngramError> {
+        let task = draft.into_task(&self.agent);
+        self.storage.store(&task.to_generic())?;
+        tracing::info!(task_id = %task.id, agent = %task.agent, "task created");
+        Ok(task)
+    }
+
+    /// One page of tasks matching `filter`
+    pub fn list_tasks(&self, filter: TaskFilter) -> Result<QueryResult<Task>, EngramError> {
+        filter.query(&self.storage)
+    }
+
+    /// Relate `source` to `target`, attributed to the facade's agent
+    pub fn link<A: Entity, B: Entity>(
+        &mut self,
+        source: &A,
+        target: &B,
+        relationship_type: EntityRelationType,
+    ) -> Result<EntityRelationship, EngramError> {
+        self.add_relationship(EntityRelationship::new(
+            Uuid::new_v4().to_string(),
+            self.agent.clone(),
+            source.id().to_string(),
+            A::entity_type().to_string(),
+            target.id().to_string(),
+            B::entity_type().to_string(),
+            relationship_type,
+        ))
+    }
+
+    /// Validate and store a fully specified relationship
+    pub fn add_relationship(
+        &mut self,
+        relationship: EntityRelationship,
+    ) -> Result<EntityRelationship, EngramError> {
+        relationship
+            .validate_entity()
+            .map_err(|e| EngramError::Validation(e.to_string()))?;
+        self.storage.store(&relationship.to_generic())?;
+        Ok(relationship)
+    }
+
+    /// The open task `agent` should work on next: in progress before todo
+    /// before blocked, then by priority
+    pub fn next_task(&self, agent: &str) -> Result<Option<Task>, EngramError> {
+        self.next_task_in(agent, &NextScope::default())
+    }
+
+    /// [`Engram::next_task`] limited to `scope`
+    pub fn next_task_in(
+        &self,
+        agent: &str,
+        scope: &NextScope,
+    ) -> Result<Option<Task>, EngramError> {
+        find_next_task(&self.storage, agent, scope)
+    }
+
+    /// Check `message` and the changed `files` against the workspace's
+    /// validation config. Merge and fixup detection, the task bound to the
+    /// current branch and the committing agent come from the git checkout
+    /// at the workspace root (`self.root`), not the current directory.
+    pub fn validate_commit(
+        &mut self,
+        message: &str,
+        files: &[String],
+    ) -> Result<ValidationResult, EngramError>
+    where
+        S: RelationshipStorage,
+    {
+        let config = ValidationConfig::load_for_workspace(&self.root)?;
+        let git_dir = crate::validation::commit_kind::git_dir_in(&self.root);
+        let kind = classify_commit(message, git_dir.as_deref());
+        if config.skip_commit_kinds.contains(&kind) {
+            return Ok(
+                ValidationResult::success("exempt".to_string(), vec![], vec![], 0).with_warnings(
+                    vec![format!("Skipped validation for {} commit", kind.label())],
+                ),
+            );
+        }
+        let branch_task =
+            crate::branch::current_branch_task_in(&self.storage, &self.root)?.map(|task| task.id);
+        let mut validator = CommitValidator::with_config(&mut self.storage, config)?
+            .with_default_task(branch_task)
+            .with_repo_root(&self.root);
+        Ok(validator.validate_commit(message, files))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::MemoryStorage;
+
+    fn engram() -> Engram<MemoryStorage> {
+        Engram::with_storage(MemoryStorage::new("alice"), "alice")
+    }
+
+    #[test]
+    fn test_create_task_defaults_agent_and_stores() {
+        let mut engram = engram();
+        let task = engram
+            .create_task(TaskDraft {
+                tags: vec!["release".to_string()],
+                ..TaskDraft::new("Tag the release")
+            })
+            .unwrap();
+
+        assert_eq!(task.agent, "alice");
+        assert_eq!(task.priority, TaskPriority::Medium);
+        let stored = engram.storage().get(&task.id, "task").unwrap().unwrap();
+        assert_eq!(Task::from_generic(stored).unwrap().tags, vec!["release"]);
+    }
+
+    #[test]
+    fn test_list_tasks_filters_by_status_and_agent() {
+        let mut engram = engram();
+        let open = engram.create_task(TaskDraft::new("Open")).unwrap();
+        let mut done = engram.create_task(TaskDraft::new("Done")).unwrap();
+        done.complete("shipped".to_string());
+        engram.storage_mut().store(&done.to_generic()).unwrap();
+        engram
+            .create_task(TaskDraft {
+                agent: Some("bob".to_string()),
+                ..TaskDraft::new("Bob's")
+            })
+            .unwrap();
+
+        let page = engram
+            .list_tasks(TaskFilter {
+                agent: Some("alice".to_string()),
+                status: Some(TaskStatus::Todo),
+                ..TaskFilter::default()
+            })
+            .unwrap();
+        let ids: Vec<_> = page.entities.iter().map(|task| task.id.as_str()).collect();
+        assert_eq!(ids, vec![open.id.as_str()]);
+    }
+
+    #[test]
+    fn test_link_stores_relationship_between_entities() {
+        let mut engram = engram();
+        let parent = engram.create_task(TaskDraft::new("Parent")).unwrap();
+        let child = engram.create_task(TaskDraft::new("Child")).unwrap();
+
+        let relationship = engram
+            .link(&child, &parent, EntityRelationType::DependsOn)
+            .unwrap();
+
+        assert_eq!(relationship.source_id, child.id);
+        assert_eq!(relationship.target_type, "task");
+        assert!(engram
+            .storage()
+            .exists(&relationship.id, "relationship")
+            .unwrap());
+    }
+
+    #[test]
+    fn test_next_task_prefers_higher_priority() {
+        let mut engram = engram();
+        engram.create_task(TaskDraft::new("Low")).unwrap();
+        let urgent = engram
+            .create_task(TaskDraft {
+                priority: TaskPriority::Critical,
+                ..TaskDraft::new("Urgent")
+            })
+            .unwrap();
+
+        let next = engram.next_task("alice").unwrap().unwrap();
+        assert_eq!(next.id, urgent.id);
+        assert!(engram.next_task("bob").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_open_rejects_malformed_config() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("engram.yaml"), "workspace: [unclosed").unwrap();
+        assert!(matches!(
+            Engram::open(dir.path()),
+            Err(EngramError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate_commit_reads_git_state_from_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut engram = Engram::open(dir.path()).unwrap();
+        let repo = git2::Repository::open(dir.path()).unwrap();
+        let branch = repo.head().unwrap().shorthand().unwrap().to_string();
+
+        // The branch bound in the opened workspace supplies the default task
+        let task = engram.create_task(TaskDraft::new("Bound")).unwrap();
+        let mut index = crate::branch::BranchIndex::default();
+        crate::branch::bind_branch(engram.storage_mut(), &mut index, &task.id, &branch).unwrap();
+        index
+            .save(&dir.path().join(crate::branch::BRANCH_INDEX_FILE))
+            .unwrap();
+        let result = engram.validate_commit("feat: no reference", &[]).unwrap();
+        assert!(
+            !result
+                .errors
+                .iter()
+                .any(|e| e.error_type == crate::validation::ValidationErrorType::NoTaskReference),
+            "{:?}",
+            result.errors
+        );
+
+        // A merge in progress in the opened workspace's git directory is exempt
+        std::fs::write(dir.path().join(".git").join("MERGE_HEAD"), "").unwrap();
+        let result = engram.validate_commit("wip", &[]).unwrap();
+        assert!(result.valid);
+        assert_eq!(result.warnings, vec!["Skipped validation for merge commit"]);
+    }
+}
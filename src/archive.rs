@@ -0,0 +1,112 @@
+//! ZIP archive support for workspace snapshots
+//!
+//! A thin layer over the `zip` crate that works on in-memory archives. Entries
+//! are written deflated; the reader accepts any archive standard tools
+//! produce, including ZIP64, and verifies each entry's checksum.
+
+use crate::error::EngramError;
+use std::io::{Cursor, Read, Write};
+use zip::result::ZipError;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive};
+
+fn zip_error(error: ZipError) -> EngramError {
+    match error {
+        ZipError::Io(error) => EngramError::Io(error),
+        other => EngramError::Validation(format!("Invalid ZIP archive: {}", other)),
+    }
+}
+
+/// Builds a ZIP archive in memory
+pub struct ZipWriter {
+    inner: zip::ZipWriter<Cursor<Vec<u8>>>,
+}
+
+impl Default for ZipWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ZipWriter {
+    pub fn new() -> Self {
+        Self {
+            inner: zip::ZipWriter::new(Cursor::new(Vec::new())),
+        }
+    }
+
+    /// Append a file entry
+    pub fn add_file(&mut self, name: &str, data: &[u8]) -> Result<(), EngramError> {
+        let options = SimpleFileOptions::default()
+            .compression_method(CompressionMethod::Deflated)
+            .large_file(data.len() as u64 >= u64::from(u32::MAX));
+        self.inner.start_file(name, options).map_err(zip_error)?;
+        self.inner.write_all(data)?;
+        Ok(())
+    }
+
+    /// Write the central directory and return the archive bytes
+    pub fn finish(self) -> Result<Vec<u8>, EngramError> {
+        Ok(self.inner.finish().map_err(zip_error)?.into_inner())
+    }
+}
+
+/// Read every file entry of an archive, in central directory order
+pub fn read_zip(data: &[u8]) -> Result<Vec<(String, Vec<u8>)>, EngramError> {
+    let mut archive = ZipArchive::new(Cursor::new(data)).map_err(zip_error)?;
+    let mut files = Vec::with_capacity(archive.len());
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index).map_err(zip_error)?;
+        if entry.is_dir() {
+            continue;
+        }
+        let name = entry.name().to_string();
+        let mut contents = Vec::with_capacity(entry.size() as usize);
+        // The checksum is verified once the entry is read to its end
+        entry.read_to_end(&mut contents).map_err(|e| {
+            EngramError::Validation(format!("ZIP entry {} is corrupt: {}", name, e))
+        })?;
+        files.push((name, contents));
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_and_corruption() {
+        let mut writer = ZipWriter::new();
+        writer.add_file("a.txt", &b"hello ".repeat(64)).unwrap();
+        writer.add_file("dir/b.json", b"{}").unwrap();
+        writer.add_file("empty", b"").unwrap();
+        let mut bytes = writer.finish().unwrap();
+
+        let files = read_zip(&bytes).unwrap();
+        let names: Vec<&str> = files.iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(names, ["a.txt", "dir/b.json", "empty"]);
+        assert_eq!(files[0].1, b"hello ".repeat(64));
+
+        // Flip a byte of the first entry's compressed contents
+        bytes[30 + "a.txt".len() + 2] ^= 0xFF;
+        assert!(matches!(read_zip(&bytes), Err(EngramError::Validation(_))));
+        assert!(matches!(
+            read_zip(b"not a zip"),
+            Err(EngramError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_reads_entries_stored_by_other_writers() {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        let stored = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+        writer.add_directory("dir/", stored).unwrap();
+        writer.start_file("dir/raw.txt", stored).unwrap();
+        writer.write_all(b"raw").unwrap();
+        let bytes = writer.finish().unwrap().into_inner();
+
+        let files = read_zip(&bytes).unwrap();
+        assert_eq!(files, [("dir/raw.txt".to_string(), b"raw".to_vec())]);
+    }
+}
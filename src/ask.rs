@@ -3,11 +3,17 @@
 //! Provides conversational access to Engram's memory system through pattern matching
 //! and entity extraction. Maps natural language queries to structured Engram operations.
 
+#[cfg(feature = "tui")]
+mod line_editor;
+pub mod repl;
+
 use crate::error::EngramError;
-use crate::nlq::NLQEngine;
-use crate::storage::GitRefsStorage;
+use crate::nlq::{ChunkType, NLQEngine, OutputMode};
+use crate::storage::Storage;
 use clap::Subcommand;
+use futures::StreamExt;
 use serde_json;
+use std::io::Write;
 
 /// Natural language query commands
 #[derive(Subcommand)]
@@ -57,23 +63,136 @@ pub enum AskCommands {
         /// Output in JSON format for programmatic use
         #[arg(long, short = 'j', help = "Output in JSON format for programmatic use")]
         json: bool,
+
+        /// How to render the response
+        #[arg(
+            long,
+            short = 'f',
+            default_value = "plain",
+            value_parser = ["plain", "table", "markdown", "json"],
+            help = "How to render the response (plain, table, markdown, json)"
+        )]
+        format: String,
+    },
+    /// Stream the response to a natural language query as it is produced
+    Stream {
+        /// Natural language query to execute
+        #[arg(help = "Natural language query about your Engram data")]
+        query: String,
+
+        /// Context for the query (task ID, agent, etc.)
+        #[arg(
+            long,
+            short = 'c',
+            help = "Context for the query (task ID, agent, etc.)"
+        )]
+        context: Option<String>,
+
+        /// Print every chunk as a JSON line
+        #[arg(long, short = 'j', help = "Print every chunk as a JSON line")]
+        json: bool,
+    },
+    /// Start an interactive query session that keeps context between queries
+    ///
+    /// Reads one query per line from stdin when it is not a terminal.
+    Repl {
+        /// Start with raw JSON output (toggle with /json)
+        #[arg(
+            long,
+            short = 'j',
+            help = "Start with raw JSON output (toggle with /json)"
+        )]
+        json: bool,
     },
 }
 
 /// Handle natural language query commands
-pub async fn handle_ask_command(command: AskCommands) -> Result<(), EngramError> {
-    let AskCommands::Query {
-        query,
-        context,
-        knowledge_type,
-        deep,
-        max_depth,
-        verbose,
-        json,
-    } = command;
+pub async fn handle_ask_command(
+    command: AskCommands,
+    storage: &dyn Storage,
+) -> Result<(), EngramError> {
+    match command {
+        AskCommands::Query {
+            query,
+            context,
+            knowledge_type,
+            deep,
+            max_depth,
+            verbose,
+            json,
+            format,
+        } => {
+            run_query(
+                storage,
+                query,
+                context,
+                knowledge_type,
+                deep,
+                max_depth,
+                verbose,
+                json,
+                format.parse()?,
+            )
+            .await
+        }
+        AskCommands::Stream {
+            query,
+            context,
+            json,
+        } => run_stream(storage, query, context, json).await,
+        AskCommands::Repl { json } => repl::run_repl(storage, json).await,
+    }
+}
 
+/// Print stream chunks as they arrive: progress on stderr, the response on
+/// stdout
+async fn run_stream(
+    storage: &dyn Storage,
+    query: String,
+    context: Option<String>,
+    json: bool,
+) -> Result<(), EngramError> {
     let nlq_engine = NLQEngine::new();
-    let storage = GitRefsStorage::new(".", "default")?;
+
+    let mut chunks = Box::pin(nlq_engine.process_query_stream(&query, context, storage));
+    let mut stdout = std::io::stdout();
+    while let Some(chunk) = chunks.next().await {
+        let chunk = chunk?;
+        if json {
+            println!("{}", serde_json::to_string(&chunk)?);
+            continue;
+        }
+        match chunk.chunk_type {
+            ChunkType::ResponseChunk => {
+                print!("{}", chunk.content);
+                stdout.flush()?;
+            }
+            ChunkType::IntentClassified => eprintln!("» intent: {}", chunk.content),
+            ChunkType::EntitiesExtracted => eprintln!("» entities: {}", chunk.content),
+            ChunkType::QueryExecuting => eprintln!("» executing {} query", chunk.content),
+            ChunkType::Done => {
+                println!();
+                eprintln!("» done in {}ms", chunk.content);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn run_query(
+    storage: &dyn Storage,
+    query: String,
+    context: Option<String>,
+    knowledge_type: Option<String>,
+    deep: bool,
+    max_depth: Option<usize>,
+    verbose: bool,
+    json: bool,
+    output_mode: OutputMode,
+) -> Result<(), EngramError> {
+    let nlq_engine = NLQEngine::new().with_output_mode(output_mode);
 
     let query_context = match (&context, &knowledge_type) {
         (Some(ctx), Some(kt)) => Some(format!("{} [knowledge-type:{}]", ctx, kt)),
@@ -83,7 +202,7 @@ pub async fn handle_ask_command(command: AskCommands) -> Result<(), EngramError>
     };
 
     match nlq_engine
-        .process_query_with_deep(&query, query_context, &storage, deep, max_depth)
+        .process_query_with_deep(&query, query_context, storage, deep, max_depth)
         .await
     {
         Ok(result) => {
@@ -98,6 +217,12 @@ pub async fn handle_ask_command(command: AskCommands) -> Result<(), EngramError>
                 println!("{}", serde_json::to_string_pretty(&json_output)?);
             } else {
                 println!("{}", result.formatted_response);
+                if let Some(first) = result.data["suggestions"][0]["example"].as_str() {
+                    println!(
+                        "Re-run with one of these, e.g. engram ask query \"{}\"",
+                        first
+                    );
+                }
 
                 if verbose {
                     println!("\n--- Debug Information ---");
@@ -0,0 +1,258 @@
+//! Minimal readline-style line editor for the ask REPL
+//!
+//! Supports cursor movement, Emacs-style shortcuts and history navigation.
+//! Key handling is kept separate from terminal I/O so it can be tested
+//! without a terminal.
+
+use crossterm::cursor::MoveToColumn;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::Print;
+use crossterm::terminal::{self, Clear, ClearType};
+use crossterm::{execute, queue};
+use std::io::Write;
+use std::path::Path;
+
+/// Maximum number of history entries kept on disk
+const MAX_HISTORY: usize = 1000;
+
+/// What a key press did to the line being edited
+#[derive(Debug, PartialEq, Eq)]
+enum KeyOutcome {
+    Continue,
+    Submit(String),
+    Eof,
+}
+
+/// Disables raw mode again however `read_line` returns
+struct RawModeGuard;
+
+impl RawModeGuard {
+    fn enable() -> std::io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        Ok(Self)
+    }
+}
+
+impl Drop for RawModeGuard {
+    fn drop(&mut self) {
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+#[derive(Default)]
+pub struct LineEditor {
+    history: Vec<String>,
+    buffer: Vec<char>,
+    cursor: usize,
+    /// Position while browsing history; `None` when editing a new line
+    history_index: Option<usize>,
+    /// Line being edited before history browsing started
+    draft: Vec<char>,
+}
+
+impl LineEditor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load history from `path`; a missing or unreadable file is ignored
+    pub fn load_history(&mut self, path: &Path) {
+        if let Ok(contents) = std::fs::read_to_string(path) {
+            for line in contents.lines() {
+                self.add_history(line);
+            }
+        }
+    }
+
+    pub fn save_history(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut contents = self.history.join("\n");
+        contents.push('\n');
+        std::fs::write(path, contents)
+    }
+
+    /// Append `line` to the history unless it is blank or repeats the last entry
+    pub fn add_history(&mut self, line: &str) {
+        let line = line.trim();
+        if line.is_empty() || self.history.last().map(String::as_str) == Some(line) {
+            return;
+        }
+        self.history.push(line.to_string());
+        if self.history.len() > MAX_HISTORY {
+            let excess = self.history.len() - MAX_HISTORY;
+            self.history.drain(..excess);
+        }
+    }
+
+    /// Read one line; `None` means Ctrl-D on an empty line
+    pub fn read_line(&mut self, prompt: &str) -> std::io::Result<Option<String>> {
+        self.buffer.clear();
+        self.cursor = 0;
+        self.history_index = None;
+
+        let mut stdout = std::io::stdout();
+        let _raw_mode = RawModeGuard::enable()?;
+        self.render(&mut stdout, prompt)?;
+
+        loop {
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match self.apply(key) {
+                KeyOutcome::Continue => self.render(&mut stdout, prompt)?,
+                KeyOutcome::Submit(line) => {
+                    execute!(stdout, Print("\r\n"))?;
+                    return Ok(Some(line));
+                }
+                KeyOutcome::Eof => {
+                    execute!(stdout, Print("\r\n"))?;
+                    return Ok(None);
+                }
+            }
+        }
+    }
+
+    fn render(&self, stdout: &mut impl Write, prompt: &str) -> std::io::Result<()> {
+        let line: String = self.buffer.iter().collect();
+        let column = prompt.chars().count() + self.cursor;
+        queue!(
+            stdout,
+            MoveToColumn(0),
+            Clear(ClearType::CurrentLine),
+            Print(prompt),
+            Print(line),
+            MoveToColumn(column as u16)
+        )?;
+        stdout.flush()
+    }
+
+    fn apply(&mut self, key: KeyEvent) -> KeyOutcome {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Enter => return KeyOutcome::Submit(self.buffer.drain(..).collect()),
+            KeyCode::Char('d') if ctrl => {
+                if self.buffer.is_empty() {
+                    return KeyOutcome::Eof;
+                }
+                if self.cursor < self.buffer.len() {
+                    self.buffer.remove(self.cursor);
+                }
+            }
+            KeyCode::Char('c') if ctrl => {
+                self.buffer.clear();
+                self.cursor = 0;
+                self.history_index = None;
+            }
+            KeyCode::Char('u') if ctrl => {
+                self.buffer.drain(..self.cursor);
+                self.cursor = 0;
+            }
+            KeyCode::Char('a') if ctrl => self.cursor = 0,
+            KeyCode::Char('e') if ctrl => self.cursor = self.buffer.len(),
+            KeyCode::Char(_) if ctrl => {}
+            KeyCode::Char(c) => {
+                self.buffer.insert(self.cursor, c);
+                self.cursor += 1;
+            }
+            KeyCode::Backspace if self.cursor > 0 => {
+                self.cursor -= 1;
+                self.buffer.remove(self.cursor);
+            }
+            KeyCode::Delete if self.cursor < self.buffer.len() => {
+                self.buffer.remove(self.cursor);
+            }
+            KeyCode::Left => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Right => self.cursor = (self.cursor + 1).min(self.buffer.len()),
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.cursor = self.buffer.len(),
+            KeyCode::Up => self.history_previous(),
+            KeyCode::Down => self.history_next(),
+            _ => {}
+        }
+        KeyOutcome::Continue
+    }
+
+    fn history_previous(&mut self) {
+        let index = match self.history_index {
+            None if self.history.is_empty() => return,
+            None => {
+                self.draft = self.buffer.clone();
+                self.history.len() - 1
+            }
+            Some(index) => index.saturating_sub(1),
+        };
+        self.show_history(Some(index));
+    }
+
+    fn history_next(&mut self) {
+        match self.history_index {
+            None => {}
+            Some(index) if index + 1 < self.history.len() => self.show_history(Some(index + 1)),
+            Some(_) => self.show_history(None),
+        }
+    }
+
+    fn show_history(&mut self, index: Option<usize>) {
+        self.history_index = index;
+        self.buffer = match index {
+            Some(index) => self.history[index].chars().collect(),
+            None => std::mem::take(&mut self.draft),
+        };
+        self.cursor = self.buffer.len();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::empty())
+    }
+
+    fn ctrl(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL)
+    }
+
+    fn type_str(editor: &mut LineEditor, text: &str) {
+        for c in text.chars() {
+            editor.apply(key(KeyCode::Char(c)));
+        }
+    }
+
+    #[test]
+    fn test_editing_and_history() {
+        let mut editor = LineEditor::new();
+        editor.add_history("show my tasks");
+        editor.add_history("show my tasks");
+        editor.add_history("list agents");
+        assert_eq!(editor.history.len(), 2);
+
+        type_str(&mut editor, "sow");
+        editor.apply(ctrl('a'));
+        editor.apply(key(KeyCode::Right));
+        type_str(&mut editor, "h");
+        editor.apply(key(KeyCode::End));
+        type_str(&mut editor, " it");
+        assert_eq!(editor.buffer.iter().collect::<String>(), "show it");
+
+        editor.apply(key(KeyCode::Up));
+        editor.apply(key(KeyCode::Up));
+        assert_eq!(editor.buffer.iter().collect::<String>(), "show my tasks");
+        editor.apply(key(KeyCode::Down));
+        editor.apply(key(KeyCode::Down));
+        assert_eq!(editor.buffer.iter().collect::<String>(), "show it");
+
+        editor.apply(key(KeyCode::Backspace));
+        assert_eq!(
+            editor.apply(key(KeyCode::Enter)),
+            KeyOutcome::Submit("show i".to_string())
+        );
+        assert_eq!(editor.apply(ctrl('d')), KeyOutcome::Eof);
+    }
+}
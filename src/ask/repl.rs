@@ -0,0 +1,335 @@
+//! Interactive natural language query loop for `engram ask repl`
+//!
+//! Storage is opened once for the whole session and a
+//! [`ConversationContext`] carries the task and agent of earlier turns into
+//! follow-up queries. On a terminal lines are read with history and editing;
+//! otherwise stdin is read one query per line so sessions can be scripted.
+//! When a query is answered with "did you mean" suggestions, typing the
+//! number of one runs it.
+
+use crate::error::EngramError;
+use crate::nlq::{ConversationContext, IntentCandidate, NLQEngine, ProcessedQuery};
+use crate::storage::Storage;
+use std::io::{BufRead, Write};
+
+const HELP: &str = "Commands:
+  /json     toggle raw JSON output
+  /explain  show the intent and entities of the last query
+  /help     show this help
+  <n>       run suggestion n from the last answer
+  /exit     leave the REPL (or press Ctrl-D)";
+
+/// Last query the session executed, kept for `/explain`
+struct LastQuery {
+    query: String,
+    resolved: String,
+    processed: ProcessedQuery,
+}
+
+/// State of one REPL session
+pub struct ReplSession {
+    engine: NLQEngine,
+    conversation: ConversationContext,
+    json: bool,
+    last: Option<LastQuery>,
+    suggestions: Vec<IntentCandidate>,
+}
+
+impl ReplSession {
+    pub fn new(json: bool) -> Self {
+        Self {
+            engine: NLQEngine::new(),
+            conversation: ConversationContext::new(),
+            json,
+            last: None,
+            suggestions: Vec::new(),
+        }
+    }
+
+    /// Handle one input line; returns `false` when the session should end
+    ///
+    /// Query failures are reported to `out` and do not end the session.
+    pub async fn handle_line(
+        &mut self,
+        line: &str,
+        storage: &dyn Storage,
+        out: &mut impl Write,
+    ) -> Result<bool, EngramError> {
+        let line = line.trim();
+        if let Some(example) = self.suggestion(line).map(str::to_string) {
+            if !self.json {
+                writeln!(out, "> {}", example)?;
+            }
+            self.run_query(&example, storage, out).await?;
+            return Ok(true);
+        }
+
+        match line {
+            "" => {}
+            "/exit" | "/quit" => return Ok(false),
+            "/help" => writeln!(out, "{}", HELP)?,
+            "/json" => {
+                self.json = !self.json;
+                let mode = if self.json { "on" } else { "off" };
+                writeln!(out, "JSON output {}", mode)?;
+            }
+            "/explain" => self.explain(out)?,
+            _ if line.starts_with('/') => {
+                writeln!(out, "Unknown command: {} (try /help)", line)?;
+            }
+            query => self.run_query(query, storage, out).await?,
+        }
+        Ok(true)
+    }
+
+    /// Example phrasing of the suggestion `line` picks by number, if any
+    fn suggestion(&self, line: &str) -> Option<&str> {
+        let index = line.parse::<usize>().ok()?.checked_sub(1)?;
+        self.suggestions.get(index).map(|s| s.example.as_str())
+    }
+
+    async fn run_query(
+        &mut self,
+        query: &str,
+        storage: &dyn Storage,
+        out: &mut impl Write,
+    ) -> Result<(), EngramError> {
+        let start_time = std::time::Instant::now();
+        let resolved = self.conversation.resolve(query);
+
+        let result = match self.engine.analyze(&resolved, None) {
+            Ok(processed) => {
+                let result = self
+                    .engine
+                    .execute_processed(&processed, storage, false, None)
+                    .await;
+                if let Ok(result) = &result {
+                    self.conversation.remember(&processed, &result.data);
+                }
+                self.suggestions = processed.suggestions.clone();
+                self.last = Some(LastQuery {
+                    query: query.to_string(),
+                    resolved,
+                    processed,
+                });
+                result
+            }
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Ok(result) if self.json => {
+                let json_output = serde_json::json!({
+                    "success": result.success,
+                    "query": query,
+                    "response": result.formatted_response,
+                    "data": result.data,
+                    "execution_time_ms": start_time.elapsed().as_millis() as u64
+                });
+                writeln!(out, "{}", serde_json::to_string(&json_output)?)?;
+            }
+            Ok(result) => writeln!(out, "{}", result.formatted_response)?,
+            Err(e) if self.json => {
+                let error_output = serde_json::json!({
+                    "success": false,
+                    "query": query,
+                    "error": e.to_string()
+                });
+                writeln!(out, "{}", serde_json::to_string(&error_output)?)?;
+            }
+            Err(e) => writeln!(out, "Error processing query: {}", e)?,
+        }
+        Ok(())
+    }
+
+    fn explain(&self, out: &mut impl Write) -> Result<(), EngramError> {
+        let Some(last) = &self.last else {
+            writeln!(out, "No query to explain yet")?;
+            return Ok(());
+        };
+
+        if self.json {
+            let explanation = serde_json::json!({
+                "query": last.query,
+                "resolved_query": last.resolved,
+                "intent": last.processed.intent,
+                "confidence": last.processed.confidence,
+                "entities": last.processed.entities,
+                "suggestions": last.processed.suggestions,
+            });
+            writeln!(out, "{}", serde_json::to_string(&explanation)?)?;
+            return Ok(());
+        }
+
+        writeln!(out, "Query:    {}", last.query)?;
+        if last.resolved != last.query {
+            writeln!(out, "Resolved: {}", last.resolved)?;
+        }
+        writeln!(
+            out,
+            "Intent:   {:?} ({:.0}%)",
+            last.processed.intent,
+            last.processed.confidence * 100.0
+        )?;
+        if last.processed.entities.is_empty() {
+            writeln!(out, "Entities: none")?;
+        } else {
+            writeln!(out, "Entities:")?;
+            for entity in &last.processed.entities {
+                writeln!(
+                    out,
+                    "  {} = {} ({:.0}%)",
+                    entity.entity_type,
+                    entity.value,
+                    entity.confidence * 100.0
+                )?;
+            }
+        }
+        for (index, suggestion) in last.processed.suggestions.iter().enumerate() {
+            writeln!(
+                out,
+                "Suggestion {}: {:?} ({:.0}%) - {}",
+                index + 1,
+                suggestion.intent,
+                suggestion.confidence * 100.0,
+                suggestion.example
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Run the REPL until Ctrl-D, `/exit` or end of input
+pub async fn run_repl(storage: &dyn Storage, json: bool) -> Result<(), EngramError> {
+    let mut session = ReplSession::new(json);
+
+    #[cfg(feature = "tui")]
+    {
+        use std::io::IsTerminal;
+        if std::io::stdin().is_terminal() && std::io::stdout().is_terminal() {
+            return run_interactive(&mut session, storage).await;
+        }
+    }
+
+    let stdin = std::io::stdin();
+    run_lines(&mut session, stdin.lock(), storage, &mut std::io::stdout()).await
+}
+
+/// Feed `input` to the session one query per line, without prompts
+pub async fn run_lines(
+    session: &mut ReplSession,
+    input: impl BufRead,
+    storage: &dyn Storage,
+    out: &mut impl Write,
+) -> Result<(), EngramError> {
+    for line in input.lines() {
+        if !session.handle_line(&line?, storage, out).await? {
+            break;
+        }
+        out.flush()?;
+    }
+    Ok(())
+}
+
+#[cfg(feature = "tui")]
+async fn run_interactive(
+    session: &mut ReplSession,
+    storage: &dyn Storage,
+) -> Result<(), EngramError> {
+    use super::line_editor::LineEditor;
+
+    let history_path = dirs::home_dir().map(|home| home.join(".engram").join("ask_history"));
+    let mut editor = LineEditor::new();
+    if let Some(path) = &history_path {
+        editor.load_history(path);
+    }
+
+    println!("Engram ask REPL - type /help for commands, Ctrl-D to exit");
+    let mut stdout = std::io::stdout();
+    while let Some(line) = editor.read_line("ask> ")? {
+        editor.add_history(&line);
+        if !session.handle_line(&line, storage, &mut stdout).await? {
+            break;
+        }
+    }
+
+    if let Some(path) = &history_path {
+        editor.save_history(path)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{Entity, Task, TaskPriority};
+    use crate::storage::MemoryStorage;
+    use std::io::Cursor;
+
+    #[tokio::test]
+    async fn test_repl_carries_context_and_toggles_json() {
+        let mut storage = MemoryStorage::new("default");
+        let task = Task::new(
+            "Write docs".to_string(),
+            String::new(),
+            "default".to_string(),
+            TaskPriority::Medium,
+            None,
+        );
+        storage.store(&task.to_generic()).unwrap();
+
+        let input = format!(
+            "show task {}\nshow it\n/explain\n/json\nshow it\n/exit\nshow it\n",
+            task.id
+        );
+        let mut session = ReplSession::new(false);
+        let mut out = Vec::new();
+        run_lines(&mut session, Cursor::new(input), &storage, &mut out)
+            .await
+            .unwrap();
+
+        let output = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert!(output.contains(&format!("Resolved: show task {}", task.id)));
+        assert!(output.contains("Intent:   ShowTaskDetails ("));
+        assert!(output.contains(&format!("task_id = {}", task.id)));
+        assert!(output.contains("JSON output on"));
+
+        let json_line = lines.iter().rev().find(|l| l.starts_with('{')).unwrap();
+        let value: serde_json::Value = serde_json::from_str(json_line).unwrap();
+        assert_eq!(value["query"], "show it");
+        assert_eq!(value["data"]["task"]["id"], task.id.as_str());
+        // Nothing after /exit is processed
+        assert_eq!(lines.last(), Some(json_line));
+    }
+
+    #[tokio::test]
+    async fn test_picking_a_suggestion_by_number() {
+        let mut storage = MemoryStorage::new("default");
+        let task = Task::new(
+            "Write docs".to_string(),
+            String::new(),
+            "default".to_string(),
+            TaskPriority::Medium,
+            None,
+        );
+        storage.store(&task.to_generic()).unwrap();
+
+        let input = format!("alice tasks blocked by {}\n1\n", task.id);
+        let mut session = ReplSession::new(false);
+        let mut out = Vec::new();
+        run_lines(&mut session, Cursor::new(input), &storage, &mut out)
+            .await
+            .unwrap();
+
+        let output = String::from_utf8(out).unwrap();
+        let example = format!("show relationships of task {}", task.id);
+        assert!(output.contains("Did you mean:"), "{}", output);
+        assert!(output.contains(&format!("1. {}", example)), "{}", output);
+        assert!(output.contains(&format!("> {}", example)), "{}", output);
+        assert_eq!(
+            session.last.as_ref().map(|last| last.query.as_str()),
+            Some(example.as_str())
+        );
+    }
+}
@@ -0,0 +1,311 @@
+//! Git branch ↔ task bindings
+//!
+//! A task bound to a branch records it under the `branch` key of its
+//! metadata, which is the source of truth. `.engram/branches.json` keeps a
+//! branch → task lookup so the bound task of the current branch can be
+//! found without scanning every task; a stale entry falls back to a scan.
+//! A branch is bound to at most one task: binding a branch that another
+//! task holds is rejected until that task is unbound.
+
+use crate::entities::{Entity, Task};
+use crate::error::EngramError;
+use crate::storage::Storage;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::process::Command;
+
+/// Task metadata key holding the bound branch
+pub const BRANCH_METADATA: &str = "branch";
+
+/// Workspace-relative path of the branch lookup
+pub const BRANCH_INDEX_FILE: &str = ".engram/branches.json";
+
+/// Branch → task ID lookup persisted next to the workspace store
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BranchIndex {
+    #[serde(default)]
+    pub branches: BTreeMap<String, String>,
+}
+
+impl BranchIndex {
+    /// Read the lookup at `path`; a missing file is an empty lookup
+    pub fn load(path: &Path) -> Result<Self, EngramError> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)?;
+        serde_json::from_str(&content).map_err(|e| {
+            EngramError::Deserialization(format!("Invalid branch index {}: {}", path.display(), e))
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), EngramError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Name of the checked-out branch, or `None` outside a repository or on a
+/// detached HEAD
+pub fn current_branch() -> Option<String> {
+    current_branch_in(Path::new("."))
+}
+
+/// [`current_branch`] for the repository containing `root`
+pub fn current_branch_in(root: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(root)
+        .args(["rev-parse", "--abbrev-ref", "HEAD"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let branch = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!branch.is_empty() && branch != "HEAD").then_some(branch)
+}
+
+/// Create `branch` from HEAD and check it out
+pub fn create_and_checkout(branch: &str) -> Result<(), EngramError> {
+    let output = Command::new("git")
+        .args(["checkout", "-b", branch])
+        .output()
+        .map_err(|e| EngramError::Git(format!("Failed to run git checkout: {}", e)))?;
+    if !output.status.success() {
+        return Err(EngramError::Git(format!(
+            "git checkout -b {} failed: {}",
+            branch,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+/// Branch name for a new task: `task/<title-slug>-<short id>`
+pub fn branch_name_for(task: &Task) -> String {
+    let mut slug = String::new();
+    for c in task.title.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+        if slug.len() >= 40 {
+            break;
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    let short_id = &task.id[..8.min(task.id.len())];
+    if slug.is_empty() {
+        format!("task/{}", short_id)
+    } else {
+        format!("task/{}-{}", slug, short_id)
+    }
+}
+
+/// Branch the task is bound to, if any
+pub fn branch_of(task: &Task) -> Option<&str> {
+    task.metadata.get(BRANCH_METADATA)?.as_str()
+}
+
+fn load_task<S: Storage + ?Sized>(storage: &S, id: &str) -> Result<Task, EngramError> {
+    let generic = storage
+        .get(id, Task::entity_type())?
+        .ok_or_else(|| EngramError::not_found("task", id))?;
+    Task::from_generic(generic)
+}
+
+fn tasks_on_branch<S: Storage + ?Sized>(
+    storage: &S,
+    branch: &str,
+) -> Result<Vec<Task>, EngramError> {
+    Ok(storage
+        .get_all(Task::entity_type())?
+        .into_iter()
+        .filter_map(|generic| Task::from_generic(generic).ok())
+        .filter(|task| branch_of(task) == Some(branch))
+        .collect())
+}
+
+/// The task bound to `branch`. Errors if the store holds more than one.
+pub fn bound_task<S: Storage + ?Sized>(
+    storage: &S,
+    index: &BranchIndex,
+    branch: &str,
+) -> Result<Option<Task>, EngramError> {
+    if let Some(id) = index.branches.get(branch) {
+        if let Ok(task) = load_task(storage, id) {
+            if branch_of(&task) == Some(branch) {
+                return Ok(Some(task));
+            }
+        }
+    }
+    let mut tasks = tasks_on_branch(storage, branch)?;
+    if tasks.len() > 1 {
+        let ids: Vec<&str> = tasks.iter().map(|task| task.id.as_str()).collect();
+        return Err(EngramError::Validation(format!(
+            "Branch '{}' is bound to several tasks ({}); unbind all but one",
+            branch,
+            ids.join(", ")
+        )));
+    }
+    Ok(tasks.pop())
+}
+
+/// Bind `task_id` to `branch`, moving it off any branch it was bound to.
+/// Returns the previous branch.
+pub fn bind_branch<S: Storage + ?Sized>(
+    storage: &mut S,
+    index: &mut BranchIndex,
+    task_id: &str,
+    branch: &str,
+) -> Result<Option<String>, EngramError> {
+    let branch = branch.trim();
+    if branch.is_empty() {
+        return Err(EngramError::Validation(
+            "Branch name cannot be empty".to_string(),
+        ));
+    }
+    let mut task = load_task(storage, task_id)?;
+    if let Some(holder) = bound_task(storage, index, branch)? {
+        if holder.id != task.id {
+            return Err(EngramError::Validation(format!(
+                "Branch '{}' is already bound to task {}; unbind it first",
+                branch, holder.id
+            )));
+        }
+    }
+
+    let previous = branch_of(&task)
+        .filter(|previous| *previous != branch)
+        .map(str::to_string);
+    task.metadata
+        .insert(BRANCH_METADATA.to_string(), serde_json::json!(branch));
+    storage.store(&task.to_generic())?;
+
+    index.branches.retain(|_, id| *id != task.id);
+    index.branches.insert(branch.to_string(), task.id);
+    Ok(previous)
+}
+
+/// Remove the task's branch binding. Returns the branch it was bound to.
+pub fn unbind_branch<S: Storage + ?Sized>(
+    storage: &mut S,
+    index: &mut BranchIndex,
+    task_id: &str,
+) -> Result<Option<String>, EngramError> {
+    let mut task = load_task(storage, task_id)?;
+    let previous = task
+        .metadata
+        .remove(BRANCH_METADATA)
+        .and_then(|branch| branch.as_str().map(str::to_string));
+    if previous.is_some() {
+        storage.store(&task.to_generic())?;
+    }
+    index.branches.retain(|_, id| *id != task.id);
+    Ok(previous)
+}
+
+/// The task bound to the checked-out branch, using the workspace lookup
+pub fn current_branch_task<S: Storage + ?Sized>(storage: &S) -> Result<Option<Task>, EngramError> {
+    current_branch_task_in(storage, Path::new("."))
+}
+
+/// The task bound to the branch checked out in the workspace at `root`
+pub fn current_branch_task_in<S: Storage + ?Sized>(
+    storage: &S,
+    root: &Path,
+) -> Result<Option<Task>, EngramError> {
+    let Some(branch) = current_branch_in(root) else {
+        return Ok(None);
+    };
+    let index = BranchIndex::load(&root.join(BRANCH_INDEX_FILE))?;
+    bound_task(storage, &index, &branch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::TaskPriority;
+    use crate::storage::MemoryStorage;
+
+    fn store_task(storage: &mut MemoryStorage, title: &str) -> String {
+        let task = Task::new(
+            title.to_string(),
+            String::new(),
+            "agent".to_string(),
+            TaskPriority::Medium,
+            None,
+        );
+        storage.store(&task.to_generic()).unwrap();
+        task.id
+    }
+
+    #[test]
+    fn test_bind_rebind_unbind_and_ambiguity() {
+        let mut storage = MemoryStorage::new("default");
+        let mut index = BranchIndex::default();
+        let first = store_task(&mut storage, "First");
+        let second = store_task(&mut storage, "Second");
+
+        assert_eq!(
+            bind_branch(&mut storage, &mut index, &first, "feature/a").unwrap(),
+            None
+        );
+        let bound = bound_task(&storage, &index, "feature/a").unwrap().unwrap();
+        assert_eq!(bound.id, first);
+
+        // Another task cannot take the same branch
+        assert!(bind_branch(&mut storage, &mut index, &second, "feature/a").is_err());
+
+        // Rebinding moves the task to the new branch
+        let previous = bind_branch(&mut storage, &mut index, &first, "feature/b").unwrap();
+        assert_eq!(previous.as_deref(), Some("feature/a"));
+        assert!(bound_task(&storage, &index, "feature/a").unwrap().is_none());
+        assert_eq!(index.branches.len(), 1);
+
+        // Unbinding frees the branch for another task
+        let previous = unbind_branch(&mut storage, &mut index, &first).unwrap();
+        assert_eq!(previous.as_deref(), Some("feature/b"));
+        bind_branch(&mut storage, &mut index, &second, "feature/b").unwrap();
+
+        // The scan works without the lookup and rejects a double binding
+        // written behind its back
+        let empty = BranchIndex::default();
+        assert_eq!(
+            bound_task(&storage, &empty, "feature/b")
+                .unwrap()
+                .unwrap()
+                .id,
+            second
+        );
+        let mut task = load_task(&storage, &first).unwrap();
+        task.metadata
+            .insert(BRANCH_METADATA.to_string(), serde_json::json!("feature/b"));
+        storage.store(&task.to_generic()).unwrap();
+        assert!(bound_task(&storage, &empty, "feature/b").is_err());
+    }
+
+    #[test]
+    fn test_branch_name_for() {
+        let mut task = Task::new(
+            "Fix: login 500s on Safari!".to_string(),
+            String::new(),
+            "agent".to_string(),
+            TaskPriority::Medium,
+            None,
+        );
+        task.id = "0123456789abcdef".to_string();
+        assert_eq!(
+            branch_name_for(&task),
+            "task/fix-login-500s-on-safari-01234567"
+        );
+        task.title = "!!!".to_string();
+        assert_eq!(branch_name_for(&task), "task/01234567");
+    }
+}
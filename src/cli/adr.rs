@@ -12,9 +12,9 @@ pub enum AdrCommands {
         #[arg(long, short)]
         title: String,
 
-        /// ADR number
+        /// ADR number (defaults to the next free number)
         #[arg(long)]
-        number: u32,
+        number: Option<u32>,
 
         /// Context and problem statement
         #[arg(long)]
@@ -91,6 +91,12 @@ pub enum AdrCommands {
         /// Show all results (no limit)
         #[arg(long, conflicts_with = "limit")]
         all: bool,
+
+        #[command(flatten)]
+        sort: SortArgs,
+
+        #[command(flatten)]
+        count: CountArgs,
     },
     /// Accept an ADR
     Accept {
@@ -128,6 +134,16 @@ pub enum AdrCommands {
     },
 }
 
+/// The number the next ADR should take: one past the highest in use
+pub fn next_adr_number<S: Storage>(storage: &S) -> Result<u32, EngramError> {
+    Ok(storage
+        .get_all("adr")?
+        .iter()
+        .filter_map(|entity| entity.data.get("number").and_then(|v| v.as_u64()))
+        .max()
+        .map_or(1, |highest| highest as u32 + 1))
+}
+
 /// Create a new ADR
 pub fn create_adr<S: Storage>(
     storage: &mut S,
@@ -158,7 +174,7 @@ pub fn get_adr<S: Storage>(storage: &S, id: &str) -> Result<(), EngramError> {
         let adr = ADR::from_generic(generic)?;
         display_adr(&adr);
     } else {
-        println!("❌ ADR not found: {}", id);
+        return Err(EngramError::not_found("adr", id));
     }
     Ok(())
 }
@@ -242,7 +258,7 @@ pub fn update_adr<S: Storage>(
 
         println!("✅ ADR updated: {}", id);
     } else {
-        println!("❌ ADR not found: {}", id);
+        return Err(EngramError::not_found("adr", id));
     }
     Ok(())
 }
@@ -257,15 +273,16 @@ pub fn delete_adr<S: Storage>(storage: &mut S, id: &str) -> Result<(), EngramErr
         storage.store(&updated_generic)?;
         println!("✅ ADR deleted (deprecated): {}", id);
     } else {
-        println!("❌ ADR not found: {}", id);
+        return Err(EngramError::not_found("adr", id));
     }
     Ok(())
 }
 
-use crate::cli::utils::{create_table, truncate};
+use crate::cli::utils::{create_table, truncate, CountArgs, SortArgs};
 use prettytable::row;
 
 /// List ADRs
+#[allow(clippy::too_many_arguments)]
 pub fn list_adrs<S: Storage>(
     storage: &S,
     status: Option<String>,
@@ -273,6 +290,8 @@ pub fn list_adrs<S: Storage>(
     limit: usize,
     offset: usize,
     all: bool,
+    sort: &SortArgs,
+    count: &CountArgs,
 ) -> Result<(), EngramError> {
     use crate::storage::QueryFilter;
     use serde_json::Value;
@@ -287,6 +306,7 @@ pub fn list_adrs<S: Storage>(
         offset: Some(offset),
         ..Default::default()
     };
+    sort.apply(&mut filter);
 
     let mut field_filters = HashMap::new();
 
@@ -298,6 +318,10 @@ pub fn list_adrs<S: Storage>(
         filter.field_filters = field_filters;
     }
 
+    if count.write(&mut std::io::stdout(), storage, &filter)? {
+        return Ok(());
+    }
+
     let result = storage.query(&filter)?;
 
     if result.entities.is_empty() {
@@ -393,7 +417,7 @@ pub fn accept_adr<S: Storage>(
         println!("✅ ADR accepted: {}", id);
         display_adr(&adr);
     } else {
-        println!("❌ ADR not found: {}", id);
+        return Err(EngramError::not_found("adr", id));
     }
     Ok(())
 }
@@ -412,7 +436,7 @@ pub fn add_alternative<S: Storage>(
         storage.store(&updated_generic)?;
         println!("✅ Alternative added to ADR {}: {}", id, alt_id);
     } else {
-        println!("❌ ADR not found: {}", id);
+        return Err(EngramError::not_found("adr", id));
     }
     Ok(())
 }
@@ -431,7 +455,7 @@ pub fn add_stakeholder<S: Storage>(
         storage.store(&updated_generic)?;
         println!("✅ Stakeholder added to ADR {}: {}", id, stakeholder);
     } else {
-        println!("❌ ADR not found: {}", id);
+        return Err(EngramError::not_found("adr", id));
     }
     Ok(())
 }
@@ -550,7 +574,11 @@ mod tests {
         assert!(result.is_ok());
 
         let result = get_adr(&storage, "non-existent");
-        assert!(result.is_ok()); // Should assume not found message printed
+        assert!(matches!(
+            result,
+            Err(EngramError::NotFound { ref entity_type, ref id })
+                if entity_type == "adr" && id == "non-existent"
+        ));
     }
 
     #[test]
@@ -736,21 +764,21 @@ mod tests {
     fn test_accept_adr_not_found() {
         let mut storage = MemoryStorage::new("test-agent");
         let result = accept_adr(&mut storage, "missing", "D".to_string(), "C".to_string());
-        assert!(result.is_ok()); // Prints error
+        assert!(result.is_err_and(|e| matches!(e, EngramError::NotFound { .. })));
     }
 
     #[test]
     fn test_add_alternative_not_found() {
         let mut storage = MemoryStorage::new("test-agent");
         let result = add_alternative(&mut storage, "missing", "Alt".to_string());
-        assert!(result.is_ok());
+        assert!(result.is_err_and(|e| matches!(e, EngramError::NotFound { .. })));
     }
 
     #[test]
     fn test_add_stakeholder_not_found() {
         let mut storage = MemoryStorage::new("test-agent");
         let result = add_stakeholder(&mut storage, "missing", "Person".to_string());
-        assert!(result.is_ok());
+        assert!(result.is_err_and(|e| matches!(e, EngramError::NotFound { .. })));
     }
 
     #[test]
@@ -820,13 +848,13 @@ mod tests {
             None,
             None,
         );
-        assert!(result.is_ok()); // Prints error
+        assert!(result.is_err_and(|e| matches!(e, EngramError::NotFound { .. })));
     }
 
     #[test]
     fn test_delete_adr_not_found() {
         let mut storage = MemoryStorage::new("test-agent");
         let result = delete_adr(&mut storage, "non-existent-id");
-        assert!(result.is_ok()); // Prints error
+        assert!(result.is_err_and(|e| matches!(e, EngramError::NotFound { .. })));
     }
 }
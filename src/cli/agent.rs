@@ -0,0 +1,106 @@
+//! Agent command implementations
+
+use crate::analytics::{compute_agent_leaderboard, AgentLeaderboardEntry};
+use crate::cli::utils::{create_table, truncate};
+use crate::error::EngramError;
+use crate::storage::Storage;
+use clap::Subcommand;
+use prettytable::row;
+
+/// Agent commands
+#[derive(Debug, Subcommand)]
+pub enum AgentCommands {
+    /// Rank agents by completed tasks, gate pass rate, knowledge and sessions
+    ///
+    ///EXAMPLES:
+    ///  engram agent leaderboard
+    ///  engram agent leaderboard --days 7 --top 5 --format json
+    Leaderboard {
+        /// Time window in days
+        #[arg(long, default_value = "30")]
+        days: u32,
+
+        /// Number of agents to show
+        #[arg(long, default_value = "10")]
+        top: usize,
+
+        /// Output format (table, json)
+        #[arg(long, default_value = "table")]
+        format: String,
+    },
+}
+
+fn format_seconds(seconds: f64) -> String {
+    let seconds = seconds.round() as u64;
+    let hours = seconds / 3600;
+    let minutes = (seconds % 3600) / 60;
+    if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+/// Render leaderboard entries as a ranked table
+pub fn render_leaderboard(entries: &[AgentLeaderboardEntry]) -> String {
+    let mut table = create_table();
+    table.set_titles(row![
+        "#",
+        "Agent",
+        "Score",
+        "Tasks",
+        "Avg Task",
+        "Gate Pass",
+        "Knowledge",
+        "Sessions"
+    ]);
+    for (rank, entry) in entries.iter().enumerate() {
+        table.add_row(row![
+            rank + 1,
+            truncate(&entry.agent_id, 20),
+            format!("{:.2}", entry.composite_score),
+            entry.tasks_completed,
+            format_seconds(entry.avg_task_duration_seconds),
+            format!("{:.0}%", entry.quality_gate_pass_rate * 100.0),
+            entry.knowledge_items_created,
+            format!(
+                "{} ({:.0}% closed)",
+                entry.sessions_started,
+                entry.session_discipline * 100.0
+            )
+        ]);
+    }
+    table.to_string()
+}
+
+/// Handle `engram agent`
+pub fn handle_agent_command<S: Storage>(
+    storage: &S,
+    command: AgentCommands,
+) -> Result<(), EngramError> {
+    match command {
+        AgentCommands::Leaderboard { days, top, format } => {
+            let mut entries = compute_agent_leaderboard(storage, days)?;
+            entries.truncate(top);
+
+            match format.as_str() {
+                "json" => println!("{}", serde_json::to_string_pretty(&entries)?),
+                "table" => {
+                    if entries.is_empty() {
+                        println!("No agent activity in the last {} days", days);
+                    } else {
+                        println!("🏆 Agent Leaderboard (last {} days)", days);
+                        print!("{}", render_leaderboard(&entries));
+                    }
+                }
+                other => {
+                    return Err(EngramError::Validation(format!(
+                        "Invalid format '{}'. Expected table or json",
+                        other
+                    )))
+                }
+            }
+        }
+    }
+    Ok(())
+}
@@ -1,8 +1,11 @@
+use crate::analytics::{
+    generate_burndown, render_burndown_chart, render_burndown_csv, MilestoneScope,
+};
 use crate::cli::utils::{create_table, truncate};
 use crate::entities::bottleneck_report::BottleneckReport;
 use crate::entities::dora_metrics_report::DoraMetricsCalculator;
-use crate::entities::task_duration_report::TaskDurationReport;
-use crate::entities::Entity;
+use crate::entities::task_duration_report::{EstimateAccuracy, TaskDurationReport};
+use crate::entities::{Entity, GenericEntity};
 use crate::error::EngramError;
 use crate::storage::Storage;
 use clap::Subcommand;
@@ -24,6 +27,28 @@ pub enum AnalyticsCommands {
         #[arg(long, default_value = "10")]
         top: usize,
     },
+    /// Daily open vs done counts for a milestone's tasks
+    ///
+    ///EXAMPLES:
+    ///  engram analytics burndown --tag milestone-1
+    ///  engram analytics burndown --parent <TASK_ID> --days 14 --format csv
+    Burndown {
+        /// Tasks carrying this tag
+        #[arg(long, required_unless_present = "parent", conflicts_with = "parent")]
+        tag: Option<String>,
+
+        /// Children of this task
+        #[arg(long)]
+        parent: Option<String>,
+
+        /// Number of days to cover, ending today
+        #[arg(long, default_value = "30")]
+        days: u32,
+
+        /// Output format
+        #[arg(long, default_value = "table", value_parser = ["table", "csv", "json"])]
+        format: String,
+    },
 }
 
 pub fn handle_analytics_command<S: Storage>(
@@ -34,7 +59,53 @@ pub fn handle_analytics_command<S: Storage>(
         AnalyticsCommands::Dora { window_days } => run_dora(storage, window_days),
         AnalyticsCommands::Report {} => run_duration_report(storage),
         AnalyticsCommands::Bottleneck { top } => run_bottleneck(storage, top),
+        AnalyticsCommands::Burndown {
+            tag,
+            parent,
+            days,
+            format,
+        } => {
+            let scope = match (tag, parent) {
+                (Some(tag), _) => MilestoneScope::Tag(tag),
+                (None, Some(parent)) => MilestoneScope::Parent(parent),
+                (None, None) => {
+                    return Err(EngramError::Validation(
+                        "Specify --tag or --parent".to_string(),
+                    ))
+                }
+            };
+            run_burndown(storage, &scope, days, &format)
+        }
+    }
+}
+
+fn run_burndown<S: Storage>(
+    storage: &S,
+    scope: &MilestoneScope,
+    days: u32,
+    format: &str,
+) -> Result<(), EngramError> {
+    let points = generate_burndown(storage, scope, days, chrono::Utc::now())?;
+    match format {
+        "csv" => print!("{}", render_burndown_csv(&points)),
+        "json" => println!("{}", serde_json::to_string_pretty(&points)?),
+        _ => {
+            let label = match scope {
+                MilestoneScope::Tag(tag) => format!("tag '{}'", tag),
+                MilestoneScope::Parent(id) => format!("children of {}", id),
+            };
+            println!("Burndown for {} ({} days)", label, points.len());
+            let mut table = create_table();
+            table.set_titles(row!["Date", "Open", "Done", "Total"]);
+            for point in &points {
+                table.add_row(row![point.date, point.open, point.done, point.total()]);
+            }
+            table.printstd();
+            println!();
+            print!("{}", render_burndown_chart(&points, 50));
+        }
     }
+    Ok(())
 }
 
 fn run_dora<S: Storage>(storage: &mut S, window_days: i64) -> Result<(), EngramError> {
@@ -93,12 +164,20 @@ fn run_dora<S: Storage>(storage: &mut S, window_days: i64) -> Result<(), EngramE
     println!("  Escalations analyzed:  {}", report.escalations_analyzed);
     println!("  Report ID: {}", report.id);
 
-    let generic = report.to_generic();
-    storage.store(&generic)?;
+    persist_report(storage, report.to_generic())?;
 
     Ok(())
 }
 
+/// Keep a computed report for later comparison. Reports are a cache, so a
+/// read-only workspace skips them instead of failing the command.
+fn persist_report<S: Storage>(storage: &mut S, report: GenericEntity) -> Result<(), EngramError> {
+    if storage.is_read_only() {
+        return Ok(());
+    }
+    storage.store(&report)
+}
+
 fn dora_rating_deployment_freq(freq: f64) -> &'static str {
     if freq >= 1.0 {
         "Elite"
@@ -199,15 +278,46 @@ fn run_duration_report<S: Storage>(storage: &mut S) -> Result<(), EngramError> {
         }
     }
 
+    print_estimate_accuracy("agent", &report.estimate_accuracy_by_agent);
+    print_estimate_accuracy("priority", &report.estimate_accuracy_by_priority);
+
     println!();
     println!("  Report ID: {}", report.id);
 
-    let generic = report.to_generic();
-    storage.store(&generic)?;
+    persist_report(storage, report.to_generic())?;
 
     Ok(())
 }
 
+fn print_estimate_accuracy(
+    label: &str,
+    accuracy: &std::collections::BTreeMap<String, EstimateAccuracy>,
+) {
+    if accuracy.is_empty() {
+        return;
+    }
+    println!();
+    println!("  Estimate accuracy by {}:", label);
+    let mut table = create_table();
+    table.set_titles(row![
+        label,
+        "Tasks",
+        "MAPE",
+        "Under-estimated",
+        "Over-estimated"
+    ]);
+    for (key, stats) in accuracy {
+        table.add_row(row![
+            truncate(key, 20),
+            stats.tasks,
+            format!("{:.1}%", stats.mean_absolute_percentage_error),
+            stats.under_estimated,
+            stats.over_estimated,
+        ]);
+    }
+    table.printstd();
+}
+
 fn run_bottleneck<S: Storage>(storage: &mut S, top: usize) -> Result<(), EngramError> {
     let repo_path = std::path::Path::new(".");
     let agent = "default";
@@ -263,8 +373,7 @@ fn run_bottleneck<S: Storage>(storage: &mut S, top: usize) -> Result<(), EngramE
     println!();
     println!("  Report ID: {}", report.id);
 
-    let generic = report.to_generic();
-    storage.store(&generic)?;
+    persist_report(storage, report.to_generic())?;
 
     Ok(())
 }
@@ -297,6 +406,8 @@ mod tests {
             agent: "default".to_string(),
             start_time: start,
             end_time: end,
+            due_at: None,
+            estimate_seconds: None,
             parent: None,
             children: Vec::new(),
             tags: Vec::new(),
@@ -999,4 +1110,27 @@ mod tests {
         assert_eq!(durations.len(), 1);
         assert!(durations[0]["duration_hours"].as_f64().unwrap() > 0.0);
     }
+
+    #[test]
+    fn test_reports_run_on_read_only_storage() {
+        use crate::storage::{ReadOnlyMode, ReadOnlyStorage};
+
+        let mut memory = make_storage();
+        let now = Utc::now();
+        let t = make_task(
+            "t1",
+            "Done",
+            TaskStatus::Done,
+            now - Duration::hours(2),
+            Some(now),
+            None,
+        );
+        memory.store(&t.to_generic()).unwrap();
+
+        let mut storage = ReadOnlyStorage::new(memory.clone(), ReadOnlyMode::Strict);
+        run_duration_report(&mut storage).unwrap();
+        run_bottleneck(&mut storage, 5).unwrap();
+        assert!(memory.get_all("task_duration_report").unwrap().is_empty());
+        assert!(memory.get_all("bottleneck_report").unwrap().is_empty());
+    }
 }
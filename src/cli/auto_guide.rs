@@ -3,85 +3,485 @@
 //! This module replicates the 'Auto-Guide' functionality from the TypeScript plugin,
 //! analyzing the current session state and recently modified tasks to suggest
 //! logical next steps to the user.
+//!
+//! Rules are evaluated after relevant commands and controlled by the
+//! `auto_guide` section of `engram.yaml`:
+//!
+//! ```yaml
+//! auto_guide:
+//!   enabled: true
+//!   categories: [reasoning, graph, session, priority]
+//!   quiet: false
+//!   repeat_after_hours: 24
+//! ```
+//!
+//! An empty `categories` list enables every category. `quiet` stops
+//! suggestions being printed after commands; `engram guide suggestions`
+//! still lists them.
 
+use crate::config::Config;
+use crate::entities::{
+    Entity, EntityRelationship, FatigueLevel, GenericEntity, GuideSuggestion, Reasoning, Session,
+    Task,
+};
 use crate::error::EngramError;
-use crate::storage::Storage;
+use crate::storage::{QueryFilter, Storage};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// Days a task may stay in progress without reasoning before it is flagged
+pub const STALE_IN_PROGRESS_DAYS: i64 = 3;
+
+/// Number of open, unlinked tasks that triggers a relationship suggestion
+pub const UNLINKED_TASK_THRESHOLD: usize = 5;
 
-/// Configuration for Auto-Guide
-#[derive(Debug, Clone)]
+/// Hours a session may stay active before ending it is suggested
+pub const LONG_SESSION_HOURS: i64 = 4;
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_repeat_after_hours() -> u64 {
+    24
+}
+
+/// Group of related suggestion rules
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SuggestionCategory {
+    /// Documenting decisions with reasoning entities
+    Reasoning,
+    /// Keeping the task graph connected
+    Graph,
+    /// Session hygiene
+    Session,
+    /// Pending high-priority work
+    Priority,
+}
+
+impl std::fmt::Display for SuggestionCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            SuggestionCategory::Reasoning => "reasoning",
+            SuggestionCategory::Graph => "graph",
+            SuggestionCategory::Session => "session",
+            SuggestionCategory::Priority => "priority",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Configuration for Auto-Guide, the `auto_guide` section of `engram.yaml`
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AutoGuideConfig {
+    #[serde(default = "default_enabled")]
     pub enabled: bool,
+
+    /// Categories to evaluate; empty means all
+    #[serde(default)]
+    pub categories: Vec<SuggestionCategory>,
+
+    /// Don't print suggestions after commands
+    #[serde(default)]
+    pub quiet: bool,
+
+    /// Don't repeat a suggestion within this many hours
+    #[serde(default = "default_repeat_after_hours")]
+    pub repeat_after_hours: u64,
 }
 
 impl Default for AutoGuideConfig {
     fn default() -> Self {
-        Self { enabled: true }
+        Self {
+            enabled: true,
+            categories: Vec::new(),
+            quiet: false,
+            repeat_after_hours: default_repeat_after_hours(),
+        }
     }
 }
 
-/// Generate a suggestion based on current context
-pub fn get_auto_guide_suggestion<S: Storage>(
-    storage: &S,
-    config: &AutoGuideConfig,
-    context: Option<&str>,
-) -> Result<Option<String>, EngramError> {
-    if !config.enabled {
-        return Ok(None);
+impl AutoGuideConfig {
+    /// Read the `auto_guide` section of `<workspace>/engram.yaml` (or
+    /// `engram.yml`). A missing file or section means the defaults.
+    pub fn load(workspace: &Path) -> Result<Self, EngramError> {
+        Config::load_section(workspace, "auto_guide")
+    }
+
+    pub fn from_yaml(content: &str) -> Result<Self, EngramError> {
+        Config::section_from_yaml(content, "auto_guide")
+    }
+
+    pub fn includes(&self, category: SuggestionCategory) -> bool {
+        self.categories.is_empty() || self.categories.contains(&category)
     }
+}
+
+/// A suggested next step and the command that resolves it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Suggestion {
+    /// Stable key used for rate limiting, e.g. `stale-in-progress:<task id>`
+    pub key: String,
+    pub category: SuggestionCategory,
+    pub message: String,
+    pub command: String,
+}
 
-    // 1. Try to find active session
-    // In a real implementation, we would track the current session ID in a local file
-    // For now, let's query the most recently modified session
-    let filter = crate::storage::QueryFilter {
-        entity_type: Some("session".to_string()),
-        limit: Some(1),
-        sort_by: Some("timestamp".to_string()),
-        sort_order: crate::storage::SortOrder::Desc,
+impl std::fmt::Display for Suggestion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: `{}`", self.message, self.command)
+    }
+}
+
+fn load<T: Entity>(storage: &dyn Storage) -> Result<Vec<T>, EngramError> {
+    Ok(storage
+        .get_all(T::entity_type())?
+        .into_iter()
+        .filter_map(|generic| T::from_generic(generic).ok())
+        .collect())
+}
+
+/// Entities of `entity_type` whose `status` is `status`
+fn with_status(
+    storage: &dyn Storage,
+    entity_type: &str,
+    status: &str,
+) -> Result<Vec<GenericEntity>, EngramError> {
+    let filter = QueryFilter {
+        entity_type: Some(entity_type.to_string()),
+        field_filters: HashMap::from([("status".to_string(), serde_json::json!(status))]),
+        ..Default::default()
+    };
+    Ok(storage.query(&filter)?.entities)
+}
+
+/// Whether any reasoning entity belongs to `task_id`
+fn has_reasoning(storage: &dyn Storage, task_id: &str) -> Result<bool, EngramError> {
+    let filter = QueryFilter {
+        entity_type: Some(Reasoning::entity_type().to_string()),
+        field_filters: HashMap::from([("task_id".to_string(), serde_json::json!(task_id))]),
         ..Default::default()
     };
+    Ok(storage.count(&filter)? > 0)
+}
+
+fn short_id(id: &str) -> &str {
+    &id[..8.min(id.len())]
+}
+
+/// Every suggestion whose rule currently applies, in priority order
+pub fn applicable_suggestions(
+    storage: &dyn Storage,
+    config: &AutoGuideConfig,
+    context: Option<&str>,
+    now: DateTime<Utc>,
+) -> Result<Vec<Suggestion>, EngramError> {
+    let mut suggestions = Vec::new();
+
+    // If we just committed to a task, suggest adding reasoning
+    if config.includes(SuggestionCategory::Reasoning)
+        && context.is_some_and(|c| c.contains("commit"))
+    {
+        suggestions.push(Suggestion {
+            key: "reasoning-after-commit".to_string(),
+            category: SuggestionCategory::Reasoning,
+            message: "Consider adding a reasoning node to document why you made these changes"
+                .to_string(),
+            command: "engram reasoning create --task-id <ID>".to_string(),
+        });
+    }
 
-    let _sessions = storage.query(&filter)?;
+    let in_progress = with_status(storage, Task::entity_type(), "inprogress")?;
 
-    // Heuristic 1: If we just committed to a task, suggest adding reasoning
-    if let Some(ctx) = context {
-        if ctx.contains("commit") {
-            return Ok(Some("Consider adding a reasoning node to document why you made these changes: `engram reasoning create --task-id <ID>`".to_string()));
+    // Long-running work with no documented reasoning. Time in progress runs
+    // from the last status change; tasks created in progress, or last
+    // changed before status changes were recorded, count from their start.
+    if config.includes(SuggestionCategory::Reasoning) {
+        let mut stale: Vec<(DateTime<Utc>, Task)> = in_progress
+            .iter()
+            .filter_map(|generic| {
+                let task = Task::from_generic(generic.clone()).ok()?;
+                let since = generic.status_changed_at().unwrap_or(task.start_time);
+                Some((since, task))
+            })
+            .filter(|(since, _)| now - *since >= Duration::days(STALE_IN_PROGRESS_DAYS))
+            .collect();
+        stale.sort_by_key(|(since, _)| *since);
+        for (since, task) in stale {
+            if has_reasoning(storage, &task.id)? {
+                continue;
+            }
+            suggestions.push(Suggestion {
+                key: format!("stale-in-progress:{}", task.id),
+                category: SuggestionCategory::Reasoning,
+                message: format!(
+                    "Task '{}' has been in progress for {} days without a reasoning entity",
+                    task.title,
+                    (now - since).num_days()
+                ),
+                command: format!(
+                    "engram reasoning create --task-id {} --title \"...\"",
+                    task.id
+                ),
+            });
         }
     }
 
-    // Heuristic 2: Check for open high-priority tasks
-    let task_filter = crate::storage::QueryFilter {
-        entity_type: Some("task".to_string()),
-        field_filters: {
-            let mut map = std::collections::HashMap::new();
-            map.insert("status".to_string(), serde_json::json!("todo"));
-            map.insert("priority".to_string(), serde_json::json!("high"));
-            map
-        },
-        limit: Some(1),
-        ..Default::default()
+    // Sessions left running, or worked long enough to be fatiguing
+    if config.includes(SuggestionCategory::Session) {
+        let mut sessions: Vec<Session> = with_status(storage, Session::entity_type(), "active")?
+            .into_iter()
+            .filter_map(|generic| Session::from_generic(generic).ok())
+            .collect();
+        sessions.sort_by_key(|session| session.start_time);
+        let mut long_running = Vec::new();
+        for session in sessions {
+            let fatigue = crate::cli::session::session_fatigue(storage, &session, now)?;
+            if let Some(recommendation) = fatigue
+                .recommendation
+                .filter(|_| fatigue.level == FatigueLevel::High)
+            {
+                suggestions.push(Suggestion {
+                    key: format!("fatigue:{}", session.id),
+                    category: SuggestionCategory::Session,
+                    message: recommendation,
+                    command: format!("engram session end --id {}", session.id),
+                });
+            } else if now - session.start_time >= Duration::hours(LONG_SESSION_HOURS) {
+                long_running.push(session);
+            }
+        }
+        for session in long_running {
+            suggestions.push(Suggestion {
+                key: format!("long-session:{}", session.id),
+                category: SuggestionCategory::Session,
+                message: format!(
+                    "Session '{}' has been active for {} hours — consider ending it",
+                    session.title,
+                    (now - session.start_time).num_hours()
+                ),
+                command: format!("engram session end --id {}", session.id),
+            });
+        }
+    }
+
+    // Open tasks that aren't linked to anything
+    if config.includes(SuggestionCategory::Graph) {
+        let linked: HashSet<String> = load::<EntityRelationship>(storage)?
+            .into_iter()
+            .flat_map(|rel| [rel.source_id, rel.target_id])
+            .collect();
+        let mut open = with_status(storage, Task::entity_type(), "todo")?;
+        open.extend(in_progress);
+        open.extend(with_status(storage, Task::entity_type(), "blocked")?);
+        let unlinked: Vec<&GenericEntity> = open
+            .iter()
+            .filter(|task| !linked.contains(&task.id))
+            .collect();
+        if unlinked.len() >= UNLINKED_TASK_THRESHOLD {
+            suggestions.push(Suggestion {
+                key: "unlinked-tasks".to_string(),
+                category: SuggestionCategory::Graph,
+                message: format!(
+                    "{} open tasks have no relationships (e.g. {})",
+                    unlinked.len(),
+                    short_id(&unlinked[0].id)
+                ),
+                command: "engram relationship create --source-id <ID> --source-type task \
+                          --target-id <ID> --target-type task --relationship-type depends-on"
+                    .to_string(),
+            });
+        }
+    }
+
+    // Open high-priority tasks
+    if config.includes(SuggestionCategory::Priority) {
+        let task_filter = QueryFilter {
+            entity_type: Some("task".to_string()),
+            field_filters: HashMap::from([
+                ("status".to_string(), serde_json::json!("todo")),
+                ("priority".to_string(), serde_json::json!("high")),
+            ]),
+            limit: Some(1),
+            ..Default::default()
+        };
+
+        let tasks = storage.query(&task_filter)?;
+        if let Some(task) = tasks.entities.first() {
+            let title = task
+                .data
+                .get("title")
+                .and_then(|v| v.as_str())
+                .unwrap_or("Unknown Task");
+            suggestions.push(Suggestion {
+                key: format!("high-priority-pending:{}", task.id),
+                category: SuggestionCategory::Priority,
+                message: format!("You have a high-priority task pending: '{}'", title),
+                command: "engram next".to_string(),
+            });
+        }
+    }
+
+    Ok(suggestions)
+}
+
+fn shown_suggestions(storage: &dyn Storage) -> Result<Vec<GuideSuggestion>, EngramError> {
+    load(storage)
+}
+
+/// Whether `key` was shown within the last `hours`
+fn shown_recently(shown: &[GuideSuggestion], key: &str, now: DateTime<Utc>, hours: u64) -> bool {
+    shown
+        .iter()
+        .any(|s| s.key == key && now - s.last_shown < Duration::hours(hours as i64))
+}
+
+/// Record that the suggestion `key` was shown at `now`
+pub fn record_suggestion_shown(
+    storage: &mut dyn Storage,
+    key: &str,
+    agent: &str,
+    now: DateTime<Utc>,
+) -> Result<(), EngramError> {
+    let record = match shown_suggestions(storage)?
+        .into_iter()
+        .find(|shown| shown.key == key)
+    {
+        Some(mut existing) => {
+            existing.record_shown(now);
+            existing
+        }
+        None => GuideSuggestion::new(key.to_string(), agent.to_string(), now),
     };
+    storage.store(&record.to_generic())
+}
 
-    let tasks = storage.query(&task_filter)?;
-    if let Some(task) = tasks.entities.first() {
-        let title = task
-            .data
-            .get("title")
-            .and_then(|v| v.as_str())
-            .unwrap_or("Unknown Task");
-        return Ok(Some(format!("You have a high-priority task pending: '{}'. Use `engram next` to start working on it.", title)));
+/// The first applicable suggestion not shown within `repeat_after_hours`.
+/// `None` when disabled or quiet. Nothing is recorded.
+fn next_suggestion(
+    storage: &dyn Storage,
+    config: &AutoGuideConfig,
+    context: Option<&str>,
+    now: DateTime<Utc>,
+) -> Result<Option<Suggestion>, EngramError> {
+    if !config.enabled || config.quiet {
+        return Ok(None);
     }
+    let shown = shown_suggestions(storage)?;
+    Ok(applicable_suggestions(storage, config, context, now)?
+        .into_iter()
+        .find(|s| !shown_recently(&shown, &s.key, now, config.repeat_after_hours)))
+}
 
-    // Heuristic 3: General nudge
+/// The first applicable suggestion not shown within `repeat_after_hours`,
+/// recorded as shown. `None` when disabled or quiet.
+pub fn suggest_after_command(
+    storage: &mut dyn Storage,
+    config: &AutoGuideConfig,
+    context: Option<&str>,
+    agent: &str,
+    now: DateTime<Utc>,
+) -> Result<Option<Suggestion>, EngramError> {
+    let suggestion = next_suggestion(storage, config, context, now)?;
+    if let Some(suggestion) = &suggestion {
+        record_suggestion_shown(storage, &suggestion.key, agent, now)?;
+    }
+    Ok(suggestion)
+}
+
+/// Print the next auto-guide suggestion after a mutating command. Failures
+/// are logged, never surfaced, so they can't disrupt the command itself.
+///
+/// Skipped on read-only and dry-run storage, where recording the suggestion
+/// as shown would be a write the user did not ask for.
+pub fn check_auto_guide<S: Storage>(storage: &mut S, context: &str) {
+    if storage.is_read_only() {
+        return;
+    }
+    let now = Utc::now();
+    let result = AutoGuideConfig::load(Path::new("."))
+        .and_then(|config| next_suggestion(storage, &config, Some(context), now));
+    let suggestion = match result {
+        Ok(Some(suggestion)) => suggestion,
+        Ok(None) => return,
+        Err(e) => {
+            tracing::warn!("Auto-guide check failed: {}", e);
+            return;
+        }
+    };
+
+    eprintln!(
+        "\n💡 \x1b[1m\x1b[36mEngram Suggestion:\x1b[0m {}",
+        suggestion
+    );
+    // Only a suggestion that was actually printed counts towards rate limiting
+    let agent = crate::cli::identity::current_agent();
+    if let Err(e) = record_suggestion_shown(storage, &suggestion.key, &agent, now) {
+        tracing::warn!("Auto-guide check failed: {}", e);
+    }
+}
+
+/// Generate a suggestion based on current context
+pub fn get_auto_guide_suggestion<S: Storage>(
+    storage: &S,
+    config: &AutoGuideConfig,
+    context: Option<&str>,
+) -> Result<Option<String>, EngramError> {
+    if !config.enabled || config.quiet {
+        return Ok(None);
+    }
+
+    let now = Utc::now();
+    let shown = shown_suggestions(storage)?;
+    if let Some(suggestion) = applicable_suggestions(storage, config, context, now)?
+        .into_iter()
+        .find(|s| !shown_recently(&shown, &s.key, now, config.repeat_after_hours))
+    {
+        return Ok(Some(suggestion.to_string()));
+    }
+
+    // General nudge
     Ok(Some(
         "Tip: Keep your task graph connected by using `engram relationship create`.".to_string(),
     ))
 }
 
+/// Handle `engram guide suggestions`: every applicable suggestion, ignoring
+/// quiet mode and rate limiting
+pub fn list_suggestions<S: Storage>(
+    storage: &S,
+    config: &AutoGuideConfig,
+    json: bool,
+) -> Result<(), EngramError> {
+    let suggestions = applicable_suggestions(storage, config, None, Utc::now())?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&suggestions)?);
+        return Ok(());
+    }
+    if suggestions.is_empty() {
+        println!("✅ No suggestions right now.");
+        return Ok(());
+    }
+    println!("💡 {} suggestion(s):", suggestions.len());
+    for (i, suggestion) in suggestions.iter().enumerate() {
+        println!(
+            "\n{}. [{}] {}\n   → {}",
+            i + 1,
+            suggestion.category,
+            suggestion.message,
+            suggestion.command
+        );
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::entities::GenericEntity;
+    use crate::entities::{TaskPriority, TaskStatus};
     use crate::storage::MemoryStorage;
     use crate::storage::Storage;
     use chrono::Utc;
@@ -90,7 +490,10 @@ mod tests {
     #[test]
     fn test_auto_guide_disabled() {
         let storage = MemoryStorage::new("test");
-        let config = AutoGuideConfig { enabled: false };
+        let config = AutoGuideConfig {
+            enabled: false,
+            ..Default::default()
+        };
         let result = get_auto_guide_suggestion(&storage, &config, None).unwrap();
         assert_eq!(result, None);
     }
@@ -131,6 +534,24 @@ mod tests {
         assert!(msg.contains("Critical Bug"));
     }
 
+    #[test]
+    fn test_check_auto_guide_writes_nothing_under_dry_run() {
+        let mut memory = MemoryStorage::new("test");
+        memory
+            .store(&GenericEntity {
+                id: "task-1".to_string(),
+                entity_type: "task".to_string(),
+                agent: "test".to_string(),
+                timestamp: Utc::now(),
+                data: json!({"title": "Critical Bug", "status": "todo", "priority": "high"}),
+            })
+            .unwrap();
+
+        let mut storage = crate::storage::DryRunStorage::new(memory);
+        check_auto_guide(&mut storage, "task");
+        assert!(storage.changes().is_empty());
+    }
+
     #[test]
     fn test_auto_guide_default_nudge() {
         let storage = MemoryStorage::new("test");
@@ -141,4 +562,127 @@ mod tests {
         assert!(result.is_some());
         assert!(result.unwrap().contains("Keep your task graph connected"));
     }
+
+    #[test]
+    fn test_stale_in_progress_counts_from_status_change() {
+        let now = Utc::now();
+        let mut storage = MemoryStorage::new("test");
+        let mut task = Task::new(
+            "Old task".to_string(),
+            String::new(),
+            "test".to_string(),
+            TaskPriority::Medium,
+            None,
+        );
+        task.start_time = now - Duration::days(30);
+        storage.store(&task.to_generic()).unwrap();
+        task.start();
+        storage.store(&task.to_generic()).unwrap();
+
+        let config =
+            AutoGuideConfig::from_yaml("auto_guide:\n  categories: [reasoning]\n").unwrap();
+        // Created a month ago but only just started
+        assert!(applicable_suggestions(&storage, &config, None, now)
+            .unwrap()
+            .is_empty());
+        let later = now + Duration::days(STALE_IN_PROGRESS_DAYS) + Duration::hours(1);
+        let suggestions = applicable_suggestions(&storage, &config, None, later).unwrap();
+        assert_eq!(suggestions.len(), 1);
+        assert!(suggestions[0].message.contains("in progress for 3 days"));
+
+        let reasoning = Reasoning::new("Why".to_string(), task.id.clone(), "test".to_string());
+        storage.store(&reasoning.to_generic()).unwrap();
+        assert!(applicable_suggestions(&storage, &config, None, later)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_rules_categories_and_rate_limit() {
+        let now = Utc::now();
+        let mut storage = MemoryStorage::new("test");
+        for i in 0..UNLINKED_TASK_THRESHOLD {
+            let mut task = Task::new(
+                format!("Task {}", i),
+                String::new(),
+                "test".to_string(),
+                TaskPriority::Medium,
+                None,
+            );
+            task.id = format!("task-{}", i);
+            if i == 0 {
+                task.status = TaskStatus::InProgress;
+                task.start_time = now - Duration::days(STALE_IN_PROGRESS_DAYS + 1);
+            }
+            storage.store(&task.to_generic()).unwrap();
+        }
+        let mut session = Session::new("Long haul".to_string(), "test".to_string(), Vec::new());
+        session.id = "session-1".to_string();
+        session.start_time = now - Duration::hours(LONG_SESSION_HOURS + 1);
+        storage.store(&session.to_generic()).unwrap();
+
+        let config = AutoGuideConfig::default();
+        let keys: Vec<String> = applicable_suggestions(&storage, &config, None, now)
+            .unwrap()
+            .into_iter()
+            .map(|s| s.key)
+            .collect();
+        assert_eq!(
+            keys,
+            [
+                "stale-in-progress:task-0",
+                "fatigue:session-1",
+                "unlinked-tasks"
+            ]
+        );
+
+        let config = AutoGuideConfig::from_yaml(
+            "auto_guide:\n  categories: [session]\n  repeat_after_hours: 6\n",
+        )
+        .unwrap();
+        let first = suggest_after_command(&mut storage, &config, None, "test", now).unwrap();
+        assert_eq!(first.unwrap().command, "engram session end --id session-1");
+        assert_eq!(
+            suggest_after_command(&mut storage, &config, None, "test", now).unwrap(),
+            None
+        );
+        let later = now + Duration::hours(7);
+        assert!(
+            suggest_after_command(&mut storage, &config, None, "test", later)
+                .unwrap()
+                .is_some()
+        );
+
+        let quiet = AutoGuideConfig::from_yaml("auto_guide:\n  quiet: true\n").unwrap();
+        assert_eq!(
+            suggest_after_command(&mut storage, &quiet, None, "test", later).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_fatigue_suggestion_replaces_long_session() {
+        let now = Utc::now();
+        let mut storage = MemoryStorage::new("test");
+
+        let mut paused = Session::new("Mostly paused".to_string(), "test".to_string(), Vec::new());
+        paused.id = "session-paused".to_string();
+        paused.start_time = now - Duration::hours(LONG_SESSION_HOURS + 1);
+        paused.paused_seconds = (LONG_SESSION_HOURS * 3600) as u64;
+        storage.store(&paused.to_generic()).unwrap();
+
+        let mut tiring = Session::new("Tiring".to_string(), "test".to_string(), Vec::new());
+        tiring.id = "session-tiring".to_string();
+        tiring.start_time = now - Duration::hours(3);
+        storage.store(&tiring.to_generic()).unwrap();
+
+        let config = AutoGuideConfig::from_yaml("auto_guide:\n  categories: [session]\n").unwrap();
+        let suggestions = applicable_suggestions(&storage, &config, None, now).unwrap();
+        let keys: Vec<&str> = suggestions.iter().map(|s| s.key.as_str()).collect();
+        assert_eq!(
+            keys,
+            ["fatigue:session-tiring", "long-session:session-paused"]
+        );
+        assert!(suggestions[0].message.contains("low-complexity task"));
+    }
 }
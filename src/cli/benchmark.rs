@@ -0,0 +1,450 @@
+//! Storage and validation micro-benchmarks for the current workspace
+//!
+//! `engram benchmark` times the hot storage paths and commit validation so
+//! that performance regressions can be caught by comparing against a
+//! previously saved report. The workspace is copied into a scratch repository
+//! first, so benchmark writes never reach the real workspace refs.
+
+use crate::cli::identity::current_agent;
+use crate::cli::utils::create_table;
+use crate::entities::{Entity, Task, TaskPriority};
+use crate::error::EngramError;
+use crate::storage::{copy_to, GitRefsStorage, RelationshipStorage, Storage};
+use crate::validation::CommitValidator;
+use chrono::{DateTime, Utc};
+use prettytable::{row, Cell, Row};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+/// Relative slowdown of `mean_ms` that counts as a regression
+pub const REGRESSION_THRESHOLD: f64 = 0.20;
+
+/// Number of existing entities sampled by the `get` benchmark
+const GET_SAMPLE_SIZE: usize = 10;
+
+/// Word used by the `text_search` benchmark
+const SEARCH_TERM: &str = "task";
+
+/// Timing summary for one benchmarked operation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkResult {
+    pub operation: String,
+    pub mean_ms: f64,
+    pub p50_ms: f64,
+    pub p99_ms: f64,
+    pub throughput_per_sec: f64,
+}
+
+impl BenchmarkResult {
+    fn from_samples(operation: &str, mut samples: Vec<f64>) -> Self {
+        samples.sort_by(|a, b| a.total_cmp(b));
+        let mean_ms = if samples.is_empty() {
+            0.0
+        } else {
+            samples.iter().sum::<f64>() / samples.len() as f64
+        };
+        Self {
+            operation: operation.to_string(),
+            mean_ms,
+            p50_ms: percentile(&samples, 0.50),
+            p99_ms: percentile(&samples, 0.99),
+            throughput_per_sec: if mean_ms > 0.0 { 1000.0 / mean_ms } else { 0.0 },
+        }
+    }
+}
+
+/// Full benchmark run, serialisable for later comparison
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkReport {
+    pub timestamp: DateTime<Utc>,
+    pub git_hash: String,
+    pub iterations: u32,
+    pub results: Vec<BenchmarkResult>,
+}
+
+impl BenchmarkReport {
+    /// Load a report previously written with `--output`
+    pub fn load(path: &Path) -> Result<Self, EngramError> {
+        let content = std::fs::read_to_string(path)?;
+        serde_json::from_str(&content).map_err(|e| {
+            EngramError::Deserialization(format!(
+                "Invalid benchmark report {}: {}",
+                path.display(),
+                e
+            ))
+        })
+    }
+}
+
+/// An operation whose mean latency grew beyond [`REGRESSION_THRESHOLD`]
+#[derive(Debug, Clone, Serialize)]
+pub struct Regression {
+    pub operation: String,
+    pub previous_mean_ms: f64,
+    pub current_mean_ms: f64,
+    pub change: f64,
+}
+
+/// Nearest-rank percentile over already sorted samples
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+fn time_ms<T>(f: impl FnOnce() -> T) -> (T, f64) {
+    let start = Instant::now();
+    let value = f();
+    (value, start.elapsed().as_secs_f64() * 1000.0)
+}
+
+fn current_git_hash() -> String {
+    std::process::Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Run every benchmark `iterations` times against `storage`
+pub fn run_benchmark<S: Storage + RelationshipStorage + Clone>(
+    storage: &mut S,
+    iterations: u32,
+) -> Result<BenchmarkReport, EngramError> {
+    let iterations = iterations.max(1);
+    let agent = current_agent();
+    let mut results = Vec::new();
+
+    // store: create a task, then delete it again so the workspace is unchanged
+    let mut samples = Vec::with_capacity(iterations as usize);
+    for i in 0..iterations {
+        let task = Task::new(
+            format!("benchmark task {}", i),
+            "Temporary task created by engram benchmark".to_string(),
+            agent.clone(),
+            TaskPriority::Low,
+            None,
+        );
+        let generic = task.to_generic();
+        let (stored, elapsed) = time_ms(|| storage.store(&generic));
+        stored?;
+        storage.delete(&generic.id, Task::entity_type())?;
+        samples.push(elapsed);
+    }
+    results.push(BenchmarkResult::from_samples("store", samples));
+
+    // get: spread sample of existing entities, falling back to a probe task
+    let probe = Task::new(
+        "benchmark probe".to_string(),
+        "Temporary task created by engram benchmark".to_string(),
+        agent.clone(),
+        TaskPriority::Low,
+        None,
+    )
+    .to_generic();
+    storage.store(&probe)?;
+
+    let ids = storage.list_ids(Task::entity_type())?;
+    let step = (ids.len() / GET_SAMPLE_SIZE).max(1);
+    let sample_ids: Vec<&String> = ids.iter().step_by(step).take(GET_SAMPLE_SIZE).collect();
+
+    let mut samples = Vec::with_capacity(iterations as usize);
+    for i in 0..iterations as usize {
+        let id = sample_ids[i % sample_ids.len()];
+        let (found, elapsed) = time_ms(|| storage.get(id, Task::entity_type()));
+        found?;
+        samples.push(elapsed);
+    }
+    results.push(BenchmarkResult::from_samples("get", samples));
+
+    let mut samples = Vec::with_capacity(iterations as usize);
+    for _ in 0..iterations {
+        let (found, elapsed) = time_ms(|| storage.text_search(SEARCH_TERM, None, Some(50)));
+        found?;
+        samples.push(elapsed);
+    }
+    results.push(BenchmarkResult::from_samples("text_search", samples));
+
+    let mut samples = Vec::with_capacity(iterations as usize);
+    for _ in 0..iterations {
+        let (found, elapsed) = time_ms(|| storage.query_by_agent(&agent, None));
+        found?;
+        samples.push(elapsed);
+    }
+    results.push(BenchmarkResult::from_samples("query_by_agent", samples));
+
+    let mut validator = CommitValidator::new(storage.clone())?;
+    let message = format!("feat: benchmark commit validation [{}]", probe.id);
+    let mut samples = Vec::with_capacity(iterations as usize);
+    for _ in 0..iterations {
+        validator.clear_cache();
+        let (_, elapsed) = time_ms(|| validator.validate_commit(&message, &[]));
+        samples.push(elapsed);
+    }
+    results.push(BenchmarkResult::from_samples("validate_commit", samples));
+
+    storage.delete(&probe.id, Task::entity_type())?;
+
+    Ok(BenchmarkReport {
+        timestamp: Utc::now(),
+        git_hash: current_git_hash(),
+        iterations,
+        results,
+    })
+}
+
+/// Operations whose mean latency regressed by more than 20% against `previous`
+pub fn compare_reports(previous: &BenchmarkReport, current: &BenchmarkReport) -> Vec<Regression> {
+    current
+        .results
+        .iter()
+        .filter_map(|result| {
+            let old = previous
+                .results
+                .iter()
+                .find(|r| r.operation == result.operation)?;
+            if old.mean_ms <= 0.0 {
+                return None;
+            }
+            let change = (result.mean_ms - old.mean_ms) / old.mean_ms;
+            (change > REGRESSION_THRESHOLD).then(|| Regression {
+                operation: result.operation.clone(),
+                previous_mean_ms: old.mean_ms,
+                current_mean_ms: result.mean_ms,
+                change,
+            })
+        })
+        .collect()
+}
+
+fn print_report(report: &BenchmarkReport, previous: Option<&BenchmarkReport>) {
+    println!("⏱️  Engram Benchmark");
+    println!("===================");
+    println!("  Git:        {}", report.git_hash);
+    println!("  Iterations: {}", report.iterations);
+    println!();
+
+    let mut table = create_table();
+    table.set_titles(row![
+        "Operation",
+        "Mean (ms)",
+        "p50 (ms)",
+        "p99 (ms)",
+        "Ops/s",
+        "Δ mean"
+    ]);
+    for result in &report.results {
+        let delta = previous
+            .and_then(|p| p.results.iter().find(|r| r.operation == result.operation))
+            .filter(|old| old.mean_ms > 0.0)
+            .map(|old| (result.mean_ms - old.mean_ms) / old.mean_ms);
+        let delta_cell = match delta {
+            Some(change) => {
+                let cell = Cell::new(&format!("{:+.1}%", change * 100.0));
+                if change > REGRESSION_THRESHOLD {
+                    cell.style_spec("Fr")
+                } else {
+                    cell
+                }
+            }
+            None => Cell::new("-"),
+        };
+        table.add_row(Row::new(vec![
+            Cell::new(&result.operation),
+            Cell::new(&format!("{:.3}", result.mean_ms)),
+            Cell::new(&format!("{:.3}", result.p50_ms)),
+            Cell::new(&format!("{:.3}", result.p99_ms)),
+            Cell::new(&format!("{:.1}", result.throughput_per_sec)),
+            delta_cell,
+        ]));
+    }
+    table.printstd();
+}
+
+/// Temporary repository holding a copy of the workspace, removed on drop
+struct ScratchWorkspace {
+    path: PathBuf,
+}
+
+impl ScratchWorkspace {
+    /// Copy every entity of `source` into a fresh repository under the
+    /// system temp directory
+    fn seed_from(source: &dyn Storage) -> Result<(Self, GitRefsStorage), EngramError> {
+        let path = std::env::temp_dir().join(format!("engram-benchmark-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&path)?;
+        let scratch = Self { path };
+
+        let mut storage = GitRefsStorage::new(&scratch.path.to_string_lossy(), &current_agent())?;
+        copy_to(source, &mut storage, None, None)?;
+        Ok((scratch, storage))
+    }
+}
+
+impl Drop for ScratchWorkspace {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
+
+/// Handle `engram benchmark`
+///
+/// `source` is only read: the benchmarks run against a scratch copy of it.
+pub fn handle_benchmark_command(
+    source: &dyn Storage,
+    iterations: u32,
+    output: Option<String>,
+    compare: Option<String>,
+    fail_on_regression: bool,
+    json: bool,
+) -> Result<(), EngramError> {
+    let previous = compare
+        .as_deref()
+        .map(|path| BenchmarkReport::load(Path::new(path)))
+        .transpose()?;
+
+    let (_scratch, mut storage) = ScratchWorkspace::seed_from(source)?;
+    let report = run_benchmark(&mut storage, iterations)?;
+
+    if let Some(path) = &output {
+        std::fs::write(path, serde_json::to_string_pretty(&report)?)?;
+    }
+
+    let regressions = previous
+        .as_ref()
+        .map(|prev| compare_reports(prev, &report))
+        .unwrap_or_default();
+
+    if json {
+        let output = serde_json::json!({
+            "report": report,
+            "regressions": regressions,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else {
+        print_report(&report, previous.as_ref());
+        if let Some(path) = &output {
+            println!("📝 Report saved to {}", path);
+        }
+        for regression in &regressions {
+            println!(
+                "⚠️  Regression: {} {:.3}ms → {:.3}ms ({:+.1}%)",
+                regression.operation,
+                regression.previous_mean_ms,
+                regression.current_mean_ms,
+                regression.change * 100.0
+            );
+        }
+    }
+
+    if fail_on_regression && !regressions.is_empty() {
+        std::process::exit(2);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::MemoryStorage;
+
+    #[test]
+    fn test_benchmark_memory_storage() {
+        let mut storage = MemoryStorage::new("test-agent");
+        let report = run_benchmark(&mut storage, 5).unwrap();
+
+        let operations: Vec<&str> = report
+            .results
+            .iter()
+            .map(|r| r.operation.as_str())
+            .collect();
+        assert_eq!(
+            operations,
+            vec![
+                "store",
+                "get",
+                "text_search",
+                "query_by_agent",
+                "validate_commit"
+            ]
+        );
+        for result in &report.results {
+            assert!(result.p50_ms <= result.p99_ms, "{:?}", result);
+        }
+        assert_eq!(report.iterations, 5);
+        // Benchmark tasks are cleaned up afterwards
+        assert!(storage.list_ids("task").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_scratch_workspace_leaves_source_untouched() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut source =
+            GitRefsStorage::new(temp_dir.path().to_str().unwrap(), "test-agent").unwrap();
+        let task = Task::new(
+            "existing task".to_string(),
+            "Seeded into the scratch copy".to_string(),
+            "test-agent".to_string(),
+            TaskPriority::Low,
+            None,
+        );
+        source.store(&task.to_generic()).unwrap();
+        let refs_before: Vec<String> = git2::Repository::open(temp_dir.path())
+            .unwrap()
+            .references_glob("refs/engram/**")
+            .unwrap()
+            .names()
+            .map(|name| name.unwrap().to_string())
+            .collect();
+
+        let scratch_path = {
+            let (scratch, mut storage) = ScratchWorkspace::seed_from(&source).unwrap();
+            assert!(storage.get(&task.id, "task").unwrap().is_some());
+            run_benchmark(&mut storage, 3).unwrap();
+            scratch.path.clone()
+        };
+
+        let refs_after: Vec<String> = git2::Repository::open(temp_dir.path())
+            .unwrap()
+            .references_glob("refs/engram/**")
+            .unwrap()
+            .names()
+            .map(|name| name.unwrap().to_string())
+            .collect();
+        assert_eq!(refs_before, refs_after);
+        assert!(!scratch_path.exists());
+    }
+
+    #[test]
+    fn test_percentile() {
+        let samples = vec![1.0, 2.0, 3.0, 4.0, 100.0];
+        assert_eq!(percentile(&samples, 0.50), 3.0);
+        assert_eq!(percentile(&samples, 0.99), 100.0);
+        assert_eq!(percentile(&[], 0.50), 0.0);
+    }
+
+    #[test]
+    fn test_compare_reports_flags_regressions() {
+        let make = |store: f64, get: f64| BenchmarkReport {
+            timestamp: Utc::now(),
+            git_hash: "abc".to_string(),
+            iterations: 5,
+            results: vec![
+                BenchmarkResult::from_samples("store", vec![store]),
+                BenchmarkResult::from_samples("get", vec![get]),
+            ],
+        };
+        let previous = make(10.0, 10.0);
+        let current = make(13.0, 11.0);
+
+        let regressions = compare_reports(&previous, &current);
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0].operation, "store");
+        assert!((regressions[0].change - 0.3).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,228 @@
+//! Dynamic shell completion of flag values from workspace data
+//!
+//! Static completion of subcommands and flag names comes from clap; this
+//! module supplies the values for flags whose valid inputs live in storage
+//! (agents, workflows, entity types) or in entity enums (task status,
+//! relationship type). Shells call back into `engram completions values
+//! <flag>` while the user is typing.
+
+use crate::cli::Cli;
+use crate::entities::{EntityRelationType, TaskStatus};
+use crate::error::EngramError;
+use crate::storage::Storage;
+use clap::{CommandFactory, Subcommand};
+
+/// Flags whose values are completed from workspace data
+pub const CONTEXTUAL_FLAGS: [&str; 5] = [
+    "--status",
+    "--agent",
+    "--type",
+    "--relationship-type",
+    "--workflow-id",
+];
+
+/// Completion commands
+#[derive(Debug, Subcommand)]
+pub enum CompletionsCommands {
+    /// Print the dynamic fish completions for flag values
+    Fish,
+    /// Print completion candidates for a flag, one per line
+    #[command(hide = true)]
+    Values {
+        /// Flag to complete, e.g. --agent
+        #[arg(allow_hyphen_values = true)]
+        flag: String,
+    },
+}
+
+/// Value accepted by `--status` for each task status
+fn task_status_value(status: &TaskStatus) -> &'static str {
+    match status {
+        TaskStatus::Todo => "todo",
+        TaskStatus::InProgress => "in_progress",
+        TaskStatus::Done => "done",
+        TaskStatus::Blocked => "blocked",
+        TaskStatus::Cancelled => "cancelled",
+    }
+}
+
+/// Value accepted by `--relationship-type`; custom types have no fixed name
+fn relationship_type_value(rel_type: &EntityRelationType) -> Option<&'static str> {
+    match rel_type {
+        EntityRelationType::DependsOn => Some("depends_on"),
+        EntityRelationType::Contains => Some("contains"),
+        EntityRelationType::References => Some("references"),
+        EntityRelationType::Fulfills => Some("fulfills"),
+        EntityRelationType::Implements => Some("implements"),
+        EntityRelationType::Supersedes => Some("supersedes"),
+        EntityRelationType::AssociatedWith => Some("associated_with"),
+        EntityRelationType::Influences => Some("influences"),
+        EntityRelationType::Custom(_) => None,
+    }
+}
+
+/// Completes flag values from live storage data
+pub struct ContextualCompleter;
+
+impl ContextualCompleter {
+    /// Completion candidates for `flag`, sorted; empty for flags it does not know
+    pub fn completions_for(flag: &str, storage: &dyn Storage) -> Result<Vec<String>, EngramError> {
+        let mut values: Vec<String> = match flag {
+            "--status" => [
+                TaskStatus::Todo,
+                TaskStatus::InProgress,
+                TaskStatus::Done,
+                TaskStatus::Blocked,
+                TaskStatus::Cancelled,
+            ]
+            .iter()
+            .map(|s| task_status_value(s).to_string())
+            .collect(),
+            "--relationship-type" => [
+                EntityRelationType::DependsOn,
+                EntityRelationType::Contains,
+                EntityRelationType::References,
+                EntityRelationType::Fulfills,
+                EntityRelationType::Implements,
+                EntityRelationType::Supersedes,
+                EntityRelationType::AssociatedWith,
+                EntityRelationType::Influences,
+            ]
+            .iter()
+            .filter_map(relationship_type_value)
+            .map(str::to_string)
+            .collect(),
+            "--agent" => storage.get_stats()?.entities_by_agent.into_keys().collect(),
+            "--type" => storage.get_stats()?.entities_by_type.into_keys().collect(),
+            "--workflow-id" => storage.list_ids("workflow")?,
+            _ => Vec::new(),
+        };
+
+        // Enum-backed lists keep their declaration order
+        if !matches!(flag, "--status" | "--relationship-type") {
+            values.sort();
+            values.dedup();
+        }
+        Ok(values)
+    }
+
+    /// Subcommand paths (e.g. `["task", "update"]`) that accept `flag`
+    pub fn commands_with_flag(flag: &str) -> Vec<Vec<String>> {
+        let long = flag.trim_start_matches("--");
+        let mut paths = Vec::new();
+        collect_commands_with_flag(&Cli::command(), long, &mut Vec::new(), &mut paths);
+        paths
+    }
+
+    /// Fish completion script calling back into engram for flag values
+    pub fn fish_script() -> String {
+        let mut script = String::from(
+            "# Dynamic flag values for engram, generated by `engram completions fish`\n\
+             function __engram_using\n    \
+                 set -l cmd (commandline -opc)\n    \
+                 test (count $cmd) -gt (count $argv); or return 1\n    \
+                 test \"$cmd[2..(math (count $argv) + 1)]\" = \"$argv\"\n\
+             end\n\n",
+        );
+
+        for flag in CONTEXTUAL_FLAGS {
+            let long = flag.trim_start_matches("--");
+            for path in Self::commands_with_flag(flag) {
+                script.push_str(&format!(
+                    "complete -c engram --condition \"__engram_using {}\" --long-option {} --no-files --arguments \"(engram completions values {} 2>/dev/null)\"\n",
+                    path.join(" "),
+                    long,
+                    flag
+                ));
+            }
+        }
+
+        script
+    }
+}
+
+fn collect_commands_with_flag(
+    command: &clap::Command,
+    long: &str,
+    path: &mut Vec<String>,
+    out: &mut Vec<Vec<String>>,
+) {
+    let has_flag = command
+        .get_arguments()
+        .any(|arg| !arg.is_global_set() && arg.get_long() == Some(long));
+    if has_flag && !path.is_empty() {
+        out.push(path.clone());
+    }
+
+    for sub in command.get_subcommands() {
+        path.push(sub.get_name().to_string());
+        collect_commands_with_flag(sub, long, path, out);
+        path.pop();
+    }
+}
+
+/// Handle `engram completions`
+pub fn handle_completions_command<S: Storage>(
+    storage: &S,
+    command: CompletionsCommands,
+) -> Result<(), EngramError> {
+    match command {
+        CompletionsCommands::Fish => print!("{}", ContextualCompleter::fish_script()),
+        CompletionsCommands::Values { flag } => {
+            for value in ContextualCompleter::completions_for(&flag, storage)? {
+                println!("{}", value);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{Entity, Task, TaskPriority};
+    use crate::storage::MemoryStorage;
+
+    fn store_task(storage: &mut MemoryStorage, agent: &str) {
+        let task = Task::new(
+            "Completion task".to_string(),
+            "For completion tests".to_string(),
+            agent.to_string(),
+            TaskPriority::Low,
+            None,
+        );
+        storage.store(&task.to_generic()).unwrap();
+    }
+
+    #[test]
+    fn test_agent_completions_list_known_agents() {
+        let mut storage = MemoryStorage::new("test-agent");
+        for agent in ["alice", "bob", "carol"] {
+            store_task(&mut storage, agent);
+        }
+        store_task(&mut storage, "alice");
+
+        let values = ContextualCompleter::completions_for("--agent", &storage).unwrap();
+        assert_eq!(values, vec!["alice", "bob", "carol"]);
+    }
+
+    #[test]
+    fn test_status_completions_match_task_update() {
+        let storage = MemoryStorage::new("test-agent");
+        let values = ContextualCompleter::completions_for("--status", &storage).unwrap();
+        assert_eq!(
+            values,
+            vec!["todo", "in_progress", "done", "blocked", "cancelled"]
+        );
+        assert!(ContextualCompleter::completions_for("--unknown", &storage)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_fish_script_targets_task_update_status() {
+        let script = ContextualCompleter::fish_script();
+        assert!(script.contains("--condition \"__engram_using task update\" --long-option status"));
+        assert!(script.contains("(engram completions values --status 2>/dev/null)"));
+    }
+}
@@ -1,7 +1,14 @@
-use crate::entities::{Compliance, Entity};
+use crate::cli::identity::resolve_agent;
+use crate::engines::rule_engine::RuleExecutionEngine;
+use crate::entities::{
+    Compliance, ComplianceEvidence, Entity, EvidenceKind, ExecutionResult, Rule, RuleStatus,
+    RuleType, Task,
+};
 use crate::error::EngramError;
 use crate::storage::Storage;
 use clap::Subcommand;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
 
 /// Compliance commands
 #[derive(Debug, Subcommand)]
@@ -38,6 +45,10 @@ pub enum ComplianceCommands {
         #[arg(long)]
         category: Option<String>,
 
+        /// Only items needing re-certification after a standard version bump
+        #[arg(long)]
+        stale: bool,
+
         /// Limit results
         #[arg(long, short)]
         limit: Option<usize>,
@@ -49,6 +60,9 @@ pub enum ComplianceCommands {
         /// Offset for pagination
         #[arg(long, short)]
         offset: Option<usize>,
+
+        #[command(flatten)]
+        sort: SortArgs,
     },
     /// Show compliance requirement details
     Show {
@@ -76,6 +90,60 @@ pub enum ComplianceCommands {
         #[arg(long, short)]
         id: String,
     },
+    /// Attach evidence (a commit, file, gate result or URL) to a requirement
+    AddEvidence {
+        /// Requirement ID
+        #[arg(help = "Compliance requirement ID")]
+        id: String,
+
+        /// Evidence kind (commit, file, gate, url)
+        #[arg(long)]
+        kind: EvidenceKind,
+
+        /// Commit SHA, file path, execution result ID or URL
+        #[arg(long = "ref")]
+        reference: String,
+
+        /// Standard requirement ID the evidence supports
+        #[arg(long)]
+        requirement: Option<String>,
+
+        /// Agent attaching the evidence
+        #[arg(long, short)]
+        agent: Option<String>,
+    },
+    /// Remove evidence from a requirement, keeping an audit record
+    RemoveEvidence {
+        /// Requirement ID
+        #[arg(help = "Compliance requirement ID")]
+        id: String,
+
+        /// Evidence ID (or unique prefix)
+        #[arg(help = "Evidence ID to remove")]
+        evidence_id: String,
+
+        /// Why the evidence is being removed
+        #[arg(long)]
+        reason: String,
+
+        /// Agent removing the evidence
+        #[arg(long, short)]
+        agent: Option<String>,
+    },
+    /// Check tasks against all active compliance rules
+    Check {
+        /// Only check this task
+        #[arg(long)]
+        task_id: Option<String>,
+
+        /// Output format
+        #[arg(long, default_value = "table", value_parser = ["table", "json"])]
+        format: String,
+
+        /// Exit with code 2 if any violation is found
+        #[arg(long)]
+        fail_on_violation: bool,
+    },
 }
 
 /// Create compliance requirement
@@ -86,12 +154,7 @@ pub fn create_compliance<S: Storage>(
     category: String,
     agent: Option<String>,
 ) -> Result<(), EngramError> {
-    let compliance = Compliance::new(
-        title,
-        description,
-        category,
-        agent.unwrap_or_else(|| "default".to_string()),
-    );
+    let compliance = Compliance::new(title, description, category, resolve_agent(agent));
 
     let generic = compliance.to_generic();
     storage.store(&generic)?;
@@ -102,32 +165,39 @@ pub fn create_compliance<S: Storage>(
     Ok(())
 }
 
-use crate::cli::utils::{create_table, truncate};
+use crate::cli::utils::{create_table, truncate, SortArgs};
 use prettytable::row;
 
 /// List compliance requirements
+#[allow(clippy::too_many_arguments)]
 pub fn list_compliance<S: Storage>(
     storage: &S,
     agent: Option<&str>,
     category: Option<&str>,
+    stale: bool,
     limit: Option<usize>,
     all: bool,
     offset: Option<usize>,
+    sort: &SortArgs,
 ) -> Result<(), EngramError> {
-    let mut compliance_items =
-        storage.query_by_agent(agent.unwrap_or("default"), Some("compliance"))?;
+    let mut compliance_items = storage.query_by_agent(&resolve_agent(agent), Some("compliance"))?;
 
     // Filter by category if specified
     if let Some(category_filter) = category {
+        let category_filter = category_filter.to_lowercase();
         compliance_items.retain(|generic_item| {
-            if let Ok(compliance_obj) = Compliance::from_generic(generic_item.clone()) {
-                compliance_obj.category.to_lowercase() == category_filter.to_lowercase()
-            } else {
-                false
-            }
+            Compliance::deserialize(&generic_item.data)
+                .is_ok_and(|c| c.category.to_lowercase() == category_filter)
         });
     }
 
+    if stale {
+        compliance_items.retain(|generic_item| {
+            Compliance::deserialize(&generic_item.data).is_ok_and(|c| c.stale)
+        });
+    }
+
+    compliance_items = sort.sort_generic("compliance", compliance_items)?;
     let total_count = compliance_items.len();
 
     if let Some(off) = offset {
@@ -157,8 +227,9 @@ pub fn list_compliance<S: Storage>(
         "ID", "Status", "Category", "Title", "Agent", "Updated"
     ]);
 
-    for generic_item in &compliance_items {
-        if let Ok(compliance) = Compliance::from_generic(generic_item.clone()) {
+    let shown = compliance_items.len();
+    for generic_item in compliance_items {
+        if let Ok(compliance) = Compliance::from_generic(generic_item) {
             let status_icon = match compliance.status {
                 crate::entities::ComplianceStatus::Compliant => "✅ Compliant",
                 crate::entities::ComplianceStatus::NonCompliant => "❌ Non-Compliant",
@@ -179,13 +250,130 @@ pub fn list_compliance<S: Storage>(
 
     table.printstd();
 
-    if total_count > compliance_items.len() {
+    if total_count > shown {
         println!("(More results available — use --all, --offset N, or --limit N)");
     }
 
     Ok(())
 }
 
+/// A task failing a compliance rule
+#[derive(Debug, Clone, Serialize)]
+pub struct ComplianceViolation {
+    pub task_id: String,
+    pub rule_id: String,
+    pub rule_title: String,
+    pub violation_message: String,
+}
+
+/// Result of checking tasks against compliance rules
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ComplianceCheckReport {
+    pub checked_tasks: u32,
+    pub violations: Vec<ComplianceViolation>,
+    /// Tasks that satisfied every rule
+    pub passed: Vec<String>,
+}
+
+/// Evaluate every active `compliance` rule against every task (or only
+/// `task_id`). A task violates a rule when the rule's condition is false or
+/// cannot be evaluated for it.
+pub fn check_task_compliance(
+    storage: &dyn Storage,
+    task_id: Option<&str>,
+) -> Result<ComplianceCheckReport, EngramError> {
+    let rules: Vec<Rule> = storage
+        .get_all(Rule::entity_type())?
+        .into_iter()
+        .filter_map(|e| Rule::from_generic(e).ok())
+        .filter(|r| r.rule_type == RuleType::Compliance && r.status == RuleStatus::Active)
+        .collect();
+
+    let tasks = match task_id {
+        Some(id) => vec![storage
+            .get(id, Task::entity_type())?
+            .ok_or_else(|| EngramError::not_found("task", id))?],
+        None => storage.get_all(Task::entity_type())?,
+    };
+
+    let engine = RuleExecutionEngine::new();
+    let mut report = ComplianceCheckReport::default();
+    for task in &tasks {
+        report.checked_tasks += 1;
+        let before = report.violations.len();
+        for rule in &rules {
+            let message = match engine.evaluate_condition(rule, task) {
+                Ok(true) => continue,
+                Ok(false) if rule.description.is_empty() => {
+                    format!("Condition not met: {}", condition_text(rule))
+                }
+                Ok(false) => rule.description.clone(),
+                Err(e) => format!("Could not evaluate condition: {}", e),
+            };
+            report.violations.push(ComplianceViolation {
+                task_id: task.id.clone(),
+                rule_id: rule.id.clone(),
+                rule_title: rule.title.clone(),
+                violation_message: message,
+            });
+        }
+        if report.violations.len() == before {
+            report.passed.push(task.id.clone());
+        }
+    }
+    Ok(report)
+}
+
+fn condition_text(rule: &Rule) -> String {
+    rule.condition
+        .get("expression")
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+        .unwrap_or_else(|| rule.condition.to_string())
+}
+
+/// Handle `engram compliance check`
+pub fn run_compliance_check(
+    storage: &dyn Storage,
+    task_id: Option<&str>,
+    format: &str,
+    fail_on_violation: bool,
+) -> Result<(), EngramError> {
+    let report = check_task_compliance(storage, task_id)?;
+
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else if report.violations.is_empty() {
+        println!(
+            "✅ {} task(s) checked, no compliance violations",
+            report.checked_tasks
+        );
+    } else {
+        println!(
+            "❌ {} violation(s) across {} task(s) checked ({} passed):",
+            report.violations.len(),
+            report.checked_tasks,
+            report.passed.len()
+        );
+        let mut table = create_table();
+        table.set_titles(row!["Task", "Rule", "Violation"]);
+        for violation in &report.violations {
+            table.add_row(row![
+                &violation.task_id[..8.min(violation.task_id.len())],
+                truncate(&violation.rule_title, 30),
+                truncate(&violation.violation_message, 60)
+            ]);
+        }
+        table.printstd();
+    }
+
+    if fail_on_violation && !report.violations.is_empty() {
+        std::process::exit(2);
+    }
+
+    Ok(())
+}
+
 /// Show compliance requirement details
 pub fn show_compliance<S: Storage>(storage: &S, id: &str) -> Result<(), EngramError> {
     // Try to find by exact ID first
@@ -220,7 +408,7 @@ pub fn show_compliance<S: Storage>(storage: &S, id: &str) -> Result<(), EngramEr
         let compliance = Compliance::from_generic(generic_item)?;
         display_compliance(&compliance);
     } else {
-        println!("❌ Compliance requirement '{}' not found", id);
+        return Err(EngramError::not_found("compliance", id));
     }
 
     Ok(())
@@ -269,12 +457,122 @@ pub fn update_compliance<S: Storage>(
         println!("✅ Compliance requirement updated:");
         display_compliance(&compliance);
     } else {
-        println!("❌ Compliance requirement '{}' not found", id);
+        return Err(EngramError::not_found("compliance", id));
     }
 
     Ok(())
 }
 
+/// Check that an evidence reference resolves: commits must exist in the
+/// repository at `repo_path` (the SHA is expanded to its full form), gate
+/// references must name a stored `ExecutionResult`, and URLs must be http(s)
+pub fn resolve_evidence_reference<S: Storage>(
+    storage: &S,
+    repo_path: &Path,
+    kind: EvidenceKind,
+    reference: &str,
+) -> Result<String, EngramError> {
+    let reference = reference.trim();
+    if reference.is_empty() {
+        return Err(EngramError::Validation(
+            "Evidence reference cannot be empty".to_string(),
+        ));
+    }
+
+    match kind {
+        EvidenceKind::Commit => {
+            let repo = git2::Repository::discover(repo_path).map_err(|e| {
+                EngramError::Git(format!("Cannot open repository for commit evidence: {}", e))
+            })?;
+            let commit = repo
+                .revparse_single(reference)
+                .and_then(|object| object.peel_to_commit())
+                .map_err(|_| {
+                    EngramError::Validation(format!(
+                        "Commit '{}' does not exist in the repository",
+                        reference
+                    ))
+                })?;
+            Ok(commit.id().to_string())
+        }
+        EvidenceKind::Gate => {
+            if !storage.exists(reference, ExecutionResult::entity_type())? {
+                return Err(EngramError::Validation(format!(
+                    "Gate evidence '{}' is not a stored execution result",
+                    reference
+                )));
+            }
+            Ok(reference.to_string())
+        }
+        EvidenceKind::Url => {
+            if !(reference.starts_with("http://") || reference.starts_with("https://")) {
+                return Err(EngramError::Validation(format!(
+                    "URL evidence '{}' must start with http:// or https://",
+                    reference
+                )));
+            }
+            Ok(reference.to_string())
+        }
+        EvidenceKind::File => Ok(reference.to_string()),
+    }
+}
+
+/// Attach evidence to a compliance requirement
+pub fn add_compliance_evidence<S: Storage>(
+    storage: &mut S,
+    repo_path: &Path,
+    id: &str,
+    kind: EvidenceKind,
+    reference: &str,
+    requirement: Option<String>,
+    agent: Option<String>,
+) -> Result<ComplianceEvidence, EngramError> {
+    let generic = storage
+        .get(id, "compliance")?
+        .ok_or_else(|| EngramError::not_found("compliance", id))?;
+    let mut compliance = Compliance::from_generic(generic)?;
+
+    let reference = resolve_evidence_reference(storage, repo_path, kind, reference)?;
+    let mut evidence = ComplianceEvidence::new(kind, reference, resolve_agent(agent));
+    evidence.requirement_id = requirement;
+    compliance.add_evidence(evidence.clone());
+    storage.store(&compliance.to_generic())?;
+
+    println!(
+        "✅ Added {} evidence {} to '{}'",
+        evidence.kind, evidence.reference, compliance.title
+    );
+    Ok(evidence)
+}
+
+/// Remove evidence from a compliance requirement, recording the reason
+pub fn remove_compliance_evidence<S: Storage>(
+    storage: &mut S,
+    id: &str,
+    evidence_id: &str,
+    reason: &str,
+    agent: Option<String>,
+) -> Result<(), EngramError> {
+    if reason.trim().is_empty() {
+        return Err(EngramError::Validation(
+            "A reason is required to remove evidence".to_string(),
+        ));
+    }
+    let generic = storage
+        .get(id, "compliance")?
+        .ok_or_else(|| EngramError::not_found("compliance", id))?;
+    let mut compliance = Compliance::from_generic(generic)?;
+
+    let removed = compliance.remove_evidence(evidence_id, &resolve_agent(agent), reason)?;
+    storage.store(&compliance.to_generic())?;
+
+    println!(
+        "✅ Removed {} evidence {} from '{}'",
+        removed.kind, removed.reference, compliance.title
+    );
+    Ok(())
+}
+
 /// Delete compliance requirement
 pub fn delete_compliance<S: Storage>(storage: &mut S, id: &str) -> Result<(), EngramError> {
     storage.delete(id, "compliance")?;
@@ -312,12 +610,36 @@ fn display_compliance(compliance: &Compliance) {
         println!("Tags: {}", compliance.tags.join(", "));
     }
 
+    let mut checked: Vec<_> = compliance.standard_versions.iter().collect();
+    checked.sort();
+    for (standard_id, version) in checked {
+        match compliance.stale_note(standard_id) {
+            Some(note) => println!("Checked: {} (stale: {})", standard_id, note),
+            None => println!("Checked: {} v{}", standard_id, version),
+        }
+    }
+
     if !compliance.violations.is_empty() {
         println!("Violations: {} found", compliance.violations.len());
     }
 
     if !compliance.evidence.is_empty() {
         println!("Evidence: {} items", compliance.evidence.len());
+        for evidence in &compliance.evidence {
+            println!(
+                "  - [{}] {} {} (by {}, {}){}",
+                &evidence.id[..8.min(evidence.id.len())],
+                evidence.kind,
+                evidence.reference,
+                evidence.added_by,
+                evidence.timestamp.format("%Y-%m-%d %H:%M"),
+                evidence
+                    .requirement_id
+                    .as_deref()
+                    .map(|r| format!(" for requirement {}", r))
+                    .unwrap_or_default()
+            );
+        }
     }
 }
 
@@ -374,10 +696,30 @@ mod tests {
         .unwrap();
 
         // List all for agent1
-        list_compliance(&storage, Some("agent1"), None, None, false, None).unwrap();
+        list_compliance(
+            &storage,
+            Some("agent1"),
+            None,
+            false,
+            None,
+            false,
+            None,
+            &SortArgs::default(),
+        )
+        .unwrap();
 
         // Filter by category
-        list_compliance(&storage, Some("agent1"), Some("cat1"), None, false, None).unwrap();
+        list_compliance(
+            &storage,
+            Some("agent1"),
+            Some("cat1"),
+            false,
+            None,
+            false,
+            None,
+            &SortArgs::default(),
+        )
+        .unwrap();
     }
 
     #[test]
@@ -398,7 +740,8 @@ mod tests {
         let id = &items[0].id;
 
         assert!(show_compliance(&storage, id).is_ok());
-        assert!(show_compliance(&storage, "invalid").is_ok()); // Prints error but returns Ok
+        assert!(show_compliance(&storage, "invalid")
+            .is_err_and(|e| matches!(e, EngramError::NotFound { .. })));
     }
 
     #[test]
@@ -457,9 +800,7 @@ mod tests {
     fn test_update_compliance_not_found() {
         let mut storage = create_test_storage();
         let result = update_compliance(&mut storage, "non-existent-id", "status", "compliant");
-        // update_compliance returns Ok even if not found (prints error),
-        // but we should verify it doesn't panic
-        assert!(result.is_ok());
+        assert!(matches!(result, Err(EngramError::NotFound { .. })));
     }
 
     #[test]
@@ -508,16 +849,7 @@ mod tests {
     fn test_delete_compliance_not_found() {
         let mut storage = create_test_storage();
         let result = delete_compliance(&mut storage, "non-existent-id");
-        // delete_compliance tries to delete, if not found, underlying storage might return error or Ok depending on implementation.
-        // MemoryStorage delete returns Ok if not found (or should we check?).
-        // Actually storage.delete returns Result.
-        // Let's check MemoryStorage implementation. If it returns error on not found, we expect error.
-        // Assuming typical delete behavior (idempotent or error if missing).
-        // Based on other tests, delete might return error if not found?
-        // Let's assume it returns Ok or Err. If it returns Err, we catch it.
-        // In this specific implementation: storage.delete(id, "compliance")?
-        // If MemoryStorage::delete returns NotFound, then this returns Err.
-        assert!(result.is_err());
+        assert!(matches!(result, Err(EngramError::NotFound { .. })));
     }
 
     #[test]
@@ -544,7 +876,197 @@ mod tests {
         // Since we can't easily capture output of list_compliance (it prints),
         // we can't verify what was printed, but we can verify it runs without error.
         // A better test would refactor list_compliance to return items, but for now:
-        let result = list_compliance(&storage, Some("agent1"), None, Some(1), false, None);
+        let result = list_compliance(
+            &storage,
+            Some("agent1"),
+            None,
+            false,
+            Some(1),
+            false,
+            None,
+            &SortArgs::default(),
+        );
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_check_task_compliance_reports_violation() {
+        use crate::cli::create_rule;
+        use crate::entities::TaskPriority;
+
+        let mut storage = create_test_storage();
+        create_rule(
+            &mut storage,
+            "Critical work is not low priority".to_string(),
+            None,
+            "compliance".to_string(),
+            "high".to_string(),
+            Some("task".to_string()),
+            r#"{"expression": "priority != low", "applies_when": "tags contains critical"}"#
+                .to_string(),
+            r#"{"type": "log"}"#.to_string(),
+            Some("default".to_string()),
+            None,
+        )
+        .unwrap();
+
+        let mut ids = Vec::new();
+        for (priority, tags) in [
+            (TaskPriority::Low, vec!["critical"]),
+            (TaskPriority::Low, vec!["docs"]),
+            (TaskPriority::High, vec!["critical"]),
+        ] {
+            let mut task = Task::new(
+                "Task".to_string(),
+                String::new(),
+                "default".to_string(),
+                priority,
+                None,
+            );
+            task.tags = tags.into_iter().map(str::to_string).collect();
+            storage.store(&task.to_generic()).unwrap();
+            ids.push(task.id);
+        }
+
+        let report = check_task_compliance(&storage, None).unwrap();
+        assert_eq!(report.checked_tasks, 3);
+        assert_eq!(report.violations.len(), 1);
+        assert_eq!(report.violations[0].task_id, ids[0]);
+        assert_eq!(
+            report.violations[0].rule_title,
+            "Critical work is not low priority"
+        );
+        assert_eq!(report.passed.len(), 2);
+
+        let single = check_task_compliance(&storage, Some(&ids[2])).unwrap();
+        assert_eq!(single.checked_tasks, 1);
+        assert!(single.violations.is_empty());
+        assert!(check_task_compliance(&storage, Some("missing")).is_err());
+    }
+
+    fn repo_with_commit(dir: &Path) -> String {
+        let repo = git2::Repository::init(dir).unwrap();
+        let signature = git2::Signature::now("Test", "test@example.com").unwrap();
+        let tree_id = repo.index().unwrap().write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "init", &tree, &[])
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn test_add_compliance_evidence_validates_references() {
+        let dir = tempfile::tempdir().unwrap();
+        let sha = repo_with_commit(dir.path());
+        let mut storage = create_test_storage();
+        let compliance = Compliance::new(
+            "SOC2".to_string(),
+            "Change management".to_string(),
+            "audit".to_string(),
+            "default".to_string(),
+        );
+        storage.store(&compliance.to_generic()).unwrap();
+        let id = compliance.id.as_str();
+
+        let evidence = add_compliance_evidence(
+            &mut storage,
+            dir.path(),
+            id,
+            EvidenceKind::Commit,
+            &sha[..7],
+            None,
+            Some("alice".to_string()),
+        )
+        .unwrap();
+        assert_eq!(evidence.reference, sha);
+        assert_eq!(evidence.added_by, "alice");
+
+        let missing_commit = add_compliance_evidence(
+            &mut storage,
+            dir.path(),
+            id,
+            EvidenceKind::Commit,
+            "deadbeef",
+            None,
+            None,
+        );
+        assert!(matches!(missing_commit, Err(EngramError::Validation(_))));
+        let missing_gate = add_compliance_evidence(
+            &mut storage,
+            dir.path(),
+            id,
+            EvidenceKind::Gate,
+            "no-such-run",
+            None,
+            None,
+        );
+        assert!(matches!(missing_gate, Err(EngramError::Validation(_))));
+
+        let run = ExecutionResult::new(
+            "task-1".to_string(),
+            "development".to_string(),
+            "cargo-test".to_string(),
+            "cargo test".to_string(),
+            "default".to_string(),
+        );
+        storage.store(&run.to_generic()).unwrap();
+        add_compliance_evidence(
+            &mut storage,
+            dir.path(),
+            id,
+            EvidenceKind::Gate,
+            &run.id,
+            Some("req-1".to_string()),
+            None,
+        )
+        .unwrap();
+
+        let stored =
+            Compliance::from_generic(storage.get(id, "compliance").unwrap().unwrap()).unwrap();
+        assert_eq!(stored.evidence.len(), 2);
+        assert_eq!(stored.evidence[1].requirement_id.as_deref(), Some("req-1"));
+        assert!(show_compliance(&storage, id).is_ok());
+    }
+
+    #[test]
+    fn test_remove_compliance_evidence_requires_reason() {
+        let mut storage = create_test_storage();
+        let mut compliance = Compliance::new(
+            "SOC2".to_string(),
+            "Change management".to_string(),
+            "audit".to_string(),
+            "default".to_string(),
+        );
+        let evidence = ComplianceEvidence::new(
+            EvidenceKind::File,
+            "docs/policy.md".to_string(),
+            "alice".to_string(),
+        );
+        let evidence_id = evidence.id.clone();
+        compliance.add_evidence(evidence);
+        storage.store(&compliance.to_generic()).unwrap();
+
+        assert!(matches!(
+            remove_compliance_evidence(&mut storage, &compliance.id, &evidence_id, " ", None),
+            Err(EngramError::Validation(_))
+        ));
+        remove_compliance_evidence(
+            &mut storage,
+            &compliance.id,
+            &evidence_id,
+            "superseded policy",
+            Some("bob".to_string()),
+        )
+        .unwrap();
+
+        let stored =
+            Compliance::from_generic(storage.get(&compliance.id, "compliance").unwrap().unwrap())
+                .unwrap();
+        assert!(stored.evidence.is_empty());
+        let removals = stored.metadata[crate::entities::EVIDENCE_REMOVALS_KEY]
+            .as_array()
+            .unwrap();
+        assert_eq!(removals[0]["reason"], "superseded policy");
+        assert_eq!(removals[0]["removed_by"], "bob");
+    }
 }
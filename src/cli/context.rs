@@ -1,12 +1,17 @@
 //! Context command implementations
 
-use crate::entities::{Context, ContextRelevance, Entity};
+use crate::cli::identity::resolve_agent;
+use crate::cli::mentions::{autolink_mentions, print_mentions};
+use crate::entities::{
+    hash_context_source, Context, ContextRelevance, ContextSourceStatus, Entity,
+};
 use crate::error::EngramError;
 use crate::storage::Storage;
 use clap::Subcommand;
 use serde::Deserialize;
 use std::fs;
 use std::io::{self, Read};
+use tracing::instrument;
 
 /// Context input structure for JSON
 #[derive(Debug, Deserialize)]
@@ -76,6 +81,14 @@ pub enum ContextCommands {
         /// JSON file path (requires --json)
         #[arg(long, requires = "json")]
         json_file: Option<String>,
+
+        /// Create even if an open context with a similar title exists
+        #[arg(long)]
+        allow_duplicate: bool,
+
+        /// Do not link entities mentioned in the content (`@T-42`, `[[id]]`)
+        #[arg(long)]
+        no_autolink: bool,
     },
     /// List contexts
     List {
@@ -98,6 +111,12 @@ pub enum ContextCommands {
         /// Offset for pagination
         #[arg(long, short)]
         offset: Option<usize>,
+
+        #[command(flatten)]
+        sort: SortArgs,
+
+        #[command(flatten)]
+        count: CountArgs,
     },
     /// Show context details
     Show {
@@ -114,6 +133,10 @@ pub enum ContextCommands {
         /// New content
         #[arg(long, short)]
         content: String,
+
+        /// Do not link entities mentioned in the content (`@T-42`, `[[id]]`)
+        #[arg(long)]
+        no_autolink: bool,
     },
     /// Delete a context
     Delete {
@@ -121,6 +144,18 @@ pub enum ContextCommands {
         #[arg(help = "Context ID to delete")]
         id: String,
     },
+    /// Re-read a context's source file, show the diff and update its content
+    Refresh {
+        /// Context ID
+        #[arg(help = "Context ID to refresh")]
+        id: String,
+    },
+    /// List contexts whose source files changed or disappeared since capture
+    Stale {
+        /// Output format (text, json)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
 }
 
 /// Helper function to read from stdin
@@ -141,6 +176,7 @@ fn read_file(path: &str) -> Result<String, EngramError> {
 fn create_context_from_input<S: Storage>(
     storage: &mut S,
     input: ContextInput,
+    autolink: bool,
 ) -> Result<(), EngramError> {
     // Parse relevance level
     let relevance = match input.relevance.as_deref().unwrap_or("medium") {
@@ -155,7 +191,7 @@ fn create_context_from_input<S: Storage>(
         }
     };
 
-    let agent = input.agent.unwrap_or_else(|| "default".to_string());
+    let agent = resolve_agent(input.agent);
 
     let mut context = Context::new(
         input.title,
@@ -166,12 +202,17 @@ fn create_context_from_input<S: Storage>(
     );
 
     context.source_id = input.source_id;
+    context.capture_source();
 
     // Convert to generic entity
     let generic_entity = context.to_generic();
 
     // Store
     storage.store(&generic_entity)?;
+    tracing::info!(context_id = %context.id, agent = %agent, "context created");
+    if autolink {
+        autolink_mentions(storage, &generic_entity, &[&context.content])?;
+    }
 
     println!("Context '{}' created successfully", context.id);
     println!("ID: {}", context.id);
@@ -181,6 +222,7 @@ fn create_context_from_input<S: Storage>(
 }
 
 /// Create a new context with flexible input
+#[instrument(skip_all, fields(entity_type = "context", operation = "create"))]
 pub fn create_context<S: Storage>(
     storage: &mut S,
     title: Option<String>,
@@ -197,6 +239,8 @@ pub fn create_context<S: Storage>(
     content_file: Option<String>,
     json: bool,
     json_file: Option<String>,
+    allow_duplicate: bool,
+    autolink: bool,
 ) -> Result<(), EngramError> {
     // Handle JSON input first (overrides all other inputs)
     if json {
@@ -229,7 +273,14 @@ pub fn create_context<S: Storage>(
             ))
         })?;
 
-        return create_context_from_input(storage, context_input);
+        check_duplicate(
+            storage,
+            "context",
+            &context_input.title,
+            Some(context_input.content.as_deref().unwrap_or_default()),
+            allow_duplicate,
+        )?;
+        return create_context_from_input(storage, context_input, autolink);
     }
 
     // Resolve title from various sources
@@ -269,7 +320,9 @@ pub fn create_context<S: Storage>(
         }
     };
 
-    let final_agent = agent.unwrap_or_else(|| "default".to_string());
+    check_duplicate(storage, "context", &final_title, None, allow_duplicate)?;
+
+    let final_agent = resolve_agent(agent);
 
     let mut context = Context::new(
         final_title,
@@ -280,12 +333,17 @@ pub fn create_context<S: Storage>(
     );
 
     context.source_id = source_id;
+    context.capture_source();
 
     // Convert to generic entity
     let generic_entity = context.to_generic();
 
     // Store
     storage.store(&generic_entity)?;
+    tracing::info!(context_id = %context.id, agent = %final_agent, "context created");
+    if autolink {
+        autolink_mentions(storage, &generic_entity, &[&context.content])?;
+    }
 
     println!("Context '{}' created successfully", context.id);
     println!("ID: {}", context.id);
@@ -296,10 +354,12 @@ pub fn create_context<S: Storage>(
     Ok(())
 }
 
-use crate::cli::utils::{create_table, truncate};
+use crate::cli::utils::{check_duplicate, create_table, truncate, CountArgs, SortArgs};
 use prettytable::row;
 
 /// List contexts
+#[instrument(skip_all, fields(entity_type = "context", operation = "list"))]
+#[allow(clippy::too_many_arguments)]
 pub fn list_contexts<S: Storage>(
     storage: &S,
     agent: Option<&str>,
@@ -307,6 +367,8 @@ pub fn list_contexts<S: Storage>(
     limit: Option<usize>,
     all: bool,
     offset: Option<usize>,
+    sort: &SortArgs,
+    count: &CountArgs,
 ) -> Result<(), EngramError> {
     let mut filter = crate::storage::QueryFilter {
         entity_type: Some("context".to_string()),
@@ -315,6 +377,7 @@ pub fn list_contexts<S: Storage>(
         offset,
         ..Default::default()
     };
+    sort.apply(&mut filter);
 
     // Add relevance filter if specified
     if let Some(rel) = relevance {
@@ -324,6 +387,10 @@ pub fn list_contexts<S: Storage>(
         );
     }
 
+    if count.write(&mut std::io::stdout(), storage, &filter)? {
+        return Ok(());
+    }
+
     let result = storage.query(&filter)?;
 
     if result.entities.is_empty() {
@@ -365,11 +432,13 @@ pub fn list_contexts<S: Storage>(
 }
 
 /// Show context details
+#[instrument(skip_all, fields(entity_type = "context", operation = "show", id = %id))]
 pub fn show_context<S: Storage>(storage: &S, id: &str) -> Result<(), EngramError> {
     let entity = storage.get(id, "context")?;
 
     match entity {
         Some(generic_entity) => {
+            let modification = generic_entity.modification();
             let context = Context::from_generic(generic_entity)?;
 
             println!("Context Details:");
@@ -377,6 +446,9 @@ pub fn show_context<S: Storage>(storage: &S, id: &str) -> Result<(), EngramError
             println!("ID: {}", context.id);
             println!("Title: {}", context.title);
             println!("Agent: {}", context.agent);
+            if let Some(modified) = modification.summary() {
+                println!("Last Modified By: {}", modified);
+            }
             println!("Relevance: {:?}", context.relevance);
             println!(
                 "Source: {}",
@@ -389,6 +461,9 @@ pub fn show_context<S: Storage>(storage: &S, id: &str) -> Result<(), EngramError
             if let Some(ref source_id) = context.source_id {
                 println!("Source ID: {}", source_id);
             }
+            if let Some(status) = context.source_status() {
+                println!("Source Status: {}", status);
+            }
             println!(
                 "Created: {}",
                 context.timestamp().format("%Y-%m-%d %H:%M:%S UTC")
@@ -397,12 +472,10 @@ pub fn show_context<S: Storage>(storage: &S, id: &str) -> Result<(), EngramError
             println!("Content:");
             println!("--------");
             println!("{}", context.content);
+            print_mentions(storage, &context.id)?;
         }
         None => {
-            return Err(EngramError::NotFound(format!(
-                "Context with ID '{}' not found",
-                id
-            )));
+            return Err(EngramError::not_found("context", id));
         }
     }
 
@@ -410,10 +483,12 @@ pub fn show_context<S: Storage>(storage: &S, id: &str) -> Result<(), EngramError
 }
 
 /// Update context
+#[instrument(skip_all, fields(entity_type = "context", operation = "update", id = %id))]
 pub fn update_context<S: Storage>(
     storage: &mut S,
     id: &str,
     content: &str,
+    autolink: bool,
 ) -> Result<(), EngramError> {
     let entity = storage.get(id, "context")?;
 
@@ -426,6 +501,9 @@ pub fn update_context<S: Storage>(
 
             let updated_entity = context.to_generic();
             storage.store(&updated_entity)?;
+            if autolink {
+                autolink_mentions(storage, &updated_entity, &[&context.content])?;
+            }
 
             println!("Context '{}' updated successfully", context.id);
             println!("Title: {}", context.title);
@@ -435,10 +513,7 @@ pub fn update_context<S: Storage>(
             );
         }
         None => {
-            return Err(EngramError::NotFound(format!(
-                "Context with ID '{}' not found",
-                id
-            )));
+            return Err(EngramError::not_found("context", id));
         }
     }
 
@@ -446,6 +521,7 @@ pub fn update_context<S: Storage>(
 }
 
 /// Delete context
+#[instrument(skip_all, fields(entity_type = "context", operation = "delete", id = %id))]
 pub fn delete_context<S: Storage>(storage: &mut S, id: &str) -> Result<(), EngramError> {
     let entity = storage.get(id, "context")?;
 
@@ -459,16 +535,117 @@ pub fn delete_context<S: Storage>(storage: &mut S, id: &str) -> Result<(), Engra
             println!("ID: {}", context.id);
         }
         None => {
-            return Err(EngramError::NotFound(format!(
-                "Context with ID '{}' not found",
-                id
-            )));
+            return Err(EngramError::not_found("context", id));
         }
     }
 
     Ok(())
 }
 
+/// Re-read a context's source file, print a diff and store the new content
+#[instrument(skip_all, fields(entity_type = "context", operation = "refresh", id = %id))]
+pub fn refresh_context<S: Storage>(storage: &mut S, id: &str) -> Result<(), EngramError> {
+    let generic_entity = storage
+        .get(id, "context")?
+        .ok_or_else(|| EngramError::not_found("context", id))?;
+    let mut context = Context::from_generic(generic_entity)?;
+
+    if context.source.is_empty() {
+        return Err(EngramError::Validation(format!(
+            "Context '{}' has no source to refresh from",
+            context.id
+        )));
+    }
+    let content = fs::read_to_string(&context.source)
+        .map_err(|_| EngramError::not_found("context source", context.source.clone()))?;
+    let hash = hash_context_source(&content);
+
+    if content == context.content && context.source_hash.as_deref() == Some(hash.as_str()) {
+        println!(
+            "Context '{}' is up to date with {}",
+            context.id, context.source
+        );
+        return Ok(());
+    }
+
+    let diff = similar::TextDiff::from_lines(&context.content, &content)
+        .unified_diff()
+        .context_radius(3)
+        .header(&format!("{} (captured)", context.source), &context.source)
+        .to_string();
+    if !diff.is_empty() {
+        print!("{}", diff);
+    }
+
+    context.update_content(content);
+    context.source_hash = Some(hash);
+    storage.store(&context.to_generic())?;
+    tracing::info!(context_id = %context.id, "context refreshed from source");
+
+    println!("Context '{}' refreshed from {}", context.id, context.source);
+    Ok(())
+}
+
+/// Contexts captured from files whose source has changed or disappeared
+pub fn stale_contexts<S: Storage>(
+    storage: &S,
+) -> Result<Vec<(Context, ContextSourceStatus)>, EngramError> {
+    let mut stale: Vec<_> = storage
+        .get_all("context")?
+        .into_iter()
+        .filter_map(|entity| Context::from_generic(entity).ok())
+        .filter_map(|context| match context.source_status()? {
+            ContextSourceStatus::Fresh => None,
+            status => Some((context, status)),
+        })
+        .collect();
+    stale.sort_by(|a, b| a.0.source.cmp(&b.0.source));
+    Ok(stale)
+}
+
+/// List stale contexts
+#[instrument(skip_all, fields(entity_type = "context", operation = "stale"))]
+pub fn list_stale_contexts<S: Storage>(storage: &S, format: &str) -> Result<(), EngramError> {
+    let stale = stale_contexts(storage)?;
+
+    if format == "json" {
+        let items: Vec<_> = stale
+            .iter()
+            .map(|(context, status)| {
+                serde_json::json!({
+                    "id": context.id,
+                    "title": context.title,
+                    "source": context.source,
+                    "status": status,
+                    "captured_at": context.updated_at,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&items)?);
+        return Ok(());
+    }
+
+    if stale.is_empty() {
+        println!("All file-sourced contexts are up to date");
+        return Ok(());
+    }
+
+    let mut table = create_table();
+    table.set_titles(row!["ID", "Title", "Source", "Status"]);
+    for (context, status) in &stale {
+        table.add_row(row![
+            &context.id[..8],
+            truncate(&context.title, 40),
+            truncate(&context.source, 40),
+            status
+        ]);
+    }
+    table.printstd();
+    println!("Run 'engram context refresh <id>' to update a context from its source");
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -496,6 +673,8 @@ mod tests {
             None,
             false,
             None,
+            false,
+            true,
         );
         assert!(result.is_ok());
 
@@ -528,6 +707,8 @@ mod tests {
             None,
             false,
             None,
+            false,
+            true,
         );
         assert!(matches!(result, Err(EngramError::Validation(_))));
 
@@ -547,6 +728,8 @@ mod tests {
             None,
             false,
             None,
+            false,
+            true,
         );
         assert!(matches!(result, Err(EngramError::Validation(_))));
     }
@@ -572,6 +755,8 @@ mod tests {
             None,
             true,                                    // enable JSON mode
             Some(tmp.to_string_lossy().to_string()), // provide invalid JSON file
+            false,
+            true,
         );
         assert!(matches!(result, Err(EngramError::Validation(_))));
         let _ = std::fs::remove_file(&tmp);
@@ -580,15 +765,15 @@ mod tests {
     #[test]
     fn test_update_context_not_found() {
         let mut storage = create_test_storage();
-        let result = update_context(&mut storage, "missing-id", "New content");
-        assert!(matches!(result, Err(EngramError::NotFound(_))));
+        let result = update_context(&mut storage, "missing-id", "New content", true);
+        assert!(matches!(result, Err(EngramError::NotFound { .. })));
     }
 
     #[test]
     fn test_delete_context_not_found() {
         let mut storage = create_test_storage();
         let result = delete_context(&mut storage, "missing-id");
-        assert!(matches!(result, Err(EngramError::NotFound(_))));
+        assert!(matches!(result, Err(EngramError::NotFound { .. })));
     }
 
     #[test]
@@ -609,13 +794,15 @@ mod tests {
             None,
             false,
             None,
+            false,
+            true,
         )
         .unwrap();
 
         let contexts = storage.query_by_agent("default", Some("context")).unwrap();
         let id = &contexts[0].id;
 
-        update_context(&mut storage, id, "Updated content").unwrap();
+        update_context(&mut storage, id, "Updated content", true).unwrap();
 
         let updated_entity = storage.get(id, "context").unwrap().unwrap();
         let context = Context::from_generic(updated_entity).unwrap();
@@ -640,6 +827,8 @@ mod tests {
             None,
             false,
             None,
+            false,
+            true,
         )
         .unwrap();
 
@@ -656,7 +845,7 @@ mod tests {
     fn test_show_context_not_found() {
         let storage = create_test_storage();
         let result = show_context(&storage, "non-existent-id");
-        assert!(matches!(result, Err(EngramError::NotFound(_))));
+        assert!(matches!(result, Err(EngramError::NotFound { .. })));
     }
 
     #[test]
@@ -677,6 +866,8 @@ mod tests {
             None,
             false,
             None,
+            false,
+            true,
         )
         .unwrap();
         create_context(
@@ -694,14 +885,36 @@ mod tests {
             None,
             false,
             None,
+            false,
+            true,
         )
         .unwrap();
 
         // Test listing all
-        list_contexts(&storage, None, None, None, false, None).unwrap();
+        list_contexts(
+            &storage,
+            None,
+            None,
+            None,
+            false,
+            None,
+            &SortArgs::default(),
+            &CountArgs::default(),
+        )
+        .unwrap();
 
         // Test filtering by relevance
-        list_contexts(&storage, None, Some("high"), None, false, None).unwrap();
+        list_contexts(
+            &storage,
+            None,
+            Some("high"),
+            None,
+            false,
+            None,
+            &SortArgs::default(),
+            &CountArgs::default(),
+        )
+        .unwrap();
     }
 
     #[test]
@@ -722,6 +935,8 @@ mod tests {
             None,
             false,
             None,
+            false,
+            true,
         )
         .unwrap();
 
@@ -744,7 +959,7 @@ mod tests {
             tags: None,
         };
 
-        create_context_from_input(&mut storage, input).unwrap();
+        create_context_from_input(&mut storage, input, true).unwrap();
 
         let contexts = storage.query_by_agent("bot", Some("context")).unwrap();
         assert_eq!(contexts.len(), 1);
@@ -766,7 +981,61 @@ mod tests {
             tags: None,
         };
 
-        let result = create_context_from_input(&mut storage, input);
+        let result = create_context_from_input(&mut storage, input, true);
         assert!(matches!(result, Err(EngramError::Validation(_))));
     }
+
+    #[test]
+    fn test_refresh_and_stale_contexts() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("requirements.md");
+        std::fs::write(&path, "line one\n").unwrap();
+        let source = path.to_string_lossy().to_string();
+
+        let mut storage = create_test_storage();
+        create_context(
+            &mut storage,
+            Some("Requirements".to_string()),
+            Some("line one\n".to_string()),
+            Some(source.clone()),
+            "high",
+            None,
+            None,
+            None,
+            false,
+            None,
+            false,
+            None,
+            false,
+            None,
+            false,
+            true,
+        )
+        .unwrap();
+        let id = storage.query_by_agent("default", Some("context")).unwrap()[0]
+            .id
+            .clone();
+        assert!(stale_contexts(&storage).unwrap().is_empty());
+
+        std::fs::write(&path, "line one\nline two\n").unwrap();
+        let stale = stale_contexts(&storage).unwrap();
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0].1, ContextSourceStatus::Changed);
+
+        refresh_context(&mut storage, &id).unwrap();
+        let refreshed =
+            Context::from_generic(storage.get(&id, "context").unwrap().unwrap()).unwrap();
+        assert_eq!(refreshed.content, "line one\nline two\n");
+        assert!(stale_contexts(&storage).unwrap().is_empty());
+
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            stale_contexts(&storage).unwrap()[0].1,
+            ContextSourceStatus::Missing
+        );
+        assert!(matches!(
+            refresh_context(&mut storage, &id),
+            Err(EngramError::NotFound { .. })
+        ));
+    }
 }
@@ -1,24 +1,342 @@
+//! Conversion from external formats for `engram convert`
+//!
+//! `--from openspec` reads an OpenAPI 3.x document (YAML or JSON) and creates
+//! one task per operation. Every component the operation references through
+//! `$ref`, directly or via other components, becomes a context linked from
+//! the task with `References`. Components shared by several operations are
+//! converted once.
+
+use crate::cli::identity::resolve_agent;
+use crate::entities::{
+    Context, ContextRelevance, Entity, EntityRelationType, EntityRelationship, GenericEntity, Task,
+    TaskPriority,
+};
+use crate::error::EngramError;
+use crate::storage::Storage;
 use clap::Subcommand;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::Path;
+use uuid::Uuid;
+
+/// Task metadata key holding the HTTP method and path of the operation
+pub const ENDPOINT_METADATA: &str = "endpoint";
+
+/// Prefix of `$ref` pointers into the document's components
+const COMPONENTS_REF_PREFIX: &str = "#/components/";
 
 /// Convert commands
 #[derive(Subcommand)]
 pub enum ConvertCommands {
     /// Convert from external format
     Convert {
-        /// Source format (openspec, beads, github)
+        /// Source format (openspec)
         #[arg(long, short = 'o')]
         from: String,
 
         /// Source file path
         #[arg(long, short = 'f')]
         file: String,
+
+        /// Agent to create entities as
+        #[arg(long, short)]
+        agent: Option<String>,
     },
 }
 
+/// Result of a conversion
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ConversionReport {
+    pub operations_processed: usize,
+    pub tasks_created: usize,
+    pub contexts_created: usize,
+    pub relationships_created: usize,
+    /// `METHOD path` of operations skipped for lacking an `operationId`
+    pub skipped_operations: Vec<String>,
+    pub warnings: Vec<String>,
+    pub task_ids: Vec<String>,
+}
+
+/// The subset of an OpenAPI 3.x document the converter reads
+#[derive(Debug, Deserialize)]
+struct OpenApiDocument {
+    #[serde(default)]
+    openapi: Option<String>,
+    #[serde(default)]
+    paths: BTreeMap<String, PathItem>,
+    #[serde(default)]
+    components: BTreeMap<String, BTreeMap<String, Value>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PathItem {
+    #[serde(default)]
+    parameters: Vec<Value>,
+    get: Option<Operation>,
+    put: Option<Operation>,
+    post: Option<Operation>,
+    delete: Option<Operation>,
+    options: Option<Operation>,
+    head: Option<Operation>,
+    patch: Option<Operation>,
+    trace: Option<Operation>,
+}
+
+impl PathItem {
+    /// Operations in the order the specification lists the methods
+    fn operations(&self) -> impl Iterator<Item = (&'static str, &Operation)> {
+        [
+            ("GET", &self.get),
+            ("PUT", &self.put),
+            ("POST", &self.post),
+            ("DELETE", &self.delete),
+            ("OPTIONS", &self.options),
+            ("HEAD", &self.head),
+            ("PATCH", &self.patch),
+            ("TRACE", &self.trace),
+        ]
+        .into_iter()
+        .filter_map(|(method, operation)| operation.as_ref().map(|op| (method, op)))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Operation {
+    operation_id: Option<String>,
+    summary: Option<String>,
+    description: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    parameters: Vec<Value>,
+    request_body: Option<Value>,
+    #[serde(default)]
+    responses: Value,
+}
+
+/// Handle `engram convert`
+pub fn handle_convert_command<S: Storage>(
+    storage: &mut S,
+    from: &str,
+    file: &str,
+    agent: Option<String>,
+    json: bool,
+) -> Result<(), EngramError> {
+    let report = match from {
+        "openspec" | "openapi" => convert_openapi(storage, Path::new(file), &resolve_agent(agent))?,
+        other => {
+            return Err(EngramError::InvalidOperation(format!(
+                "Conversion from '{}' is not supported; supported formats: openspec",
+                other
+            )))
+        }
+    };
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    println!(
+        "✅ Converted {} operation(s): {} task(s), {} context(s), {} relationship(s)",
+        report.operations_processed,
+        report.tasks_created,
+        report.contexts_created,
+        report.relationships_created
+    );
+    if !report.skipped_operations.is_empty() {
+        println!("Skipped (missing operationId):");
+        for endpoint in &report.skipped_operations {
+            println!("  • {}", endpoint);
+        }
+    }
+    for warning in &report.warnings {
+        println!("⚠️  {}", warning);
+    }
+    Ok(())
+}
+
+/// Create tasks and schema contexts from an OpenAPI 3.x file
+pub fn convert_openapi<S: Storage>(
+    storage: &mut S,
+    path: &Path,
+    agent: &str,
+) -> Result<ConversionReport, EngramError> {
+    let text = std::fs::read_to_string(path)?;
+    let document: OpenApiDocument = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("json") => serde_json::from_str(&text)
+            .map_err(|e| EngramError::Deserialization(format!("Invalid OpenAPI JSON: {}", e)))?,
+        _ => serde_yaml::from_str(&text)?,
+    };
+    match document.openapi.as_deref() {
+        Some(version) if version.starts_with("3.") => {}
+        version => {
+            return Err(EngramError::Validation(format!(
+                "Only OpenAPI 3.x documents are supported (found version {})",
+                version.unwrap_or("none")
+            )))
+        }
+    }
+
+    let source = path.display().to_string();
+    let mut report = ConversionReport::default();
+    let mut contexts: BTreeMap<String, Context> = BTreeMap::new();
+    let mut missing: BTreeSet<String> = BTreeSet::new();
+    let mut tasks: Vec<Task> = Vec::new();
+    let mut relationships: Vec<EntityRelationship> = Vec::new();
+
+    for (route, item) in &document.paths {
+        for (method, operation) in item.operations() {
+            report.operations_processed += 1;
+            let endpoint = format!("{} {}", method, route);
+            let Some(operation_id) = operation.operation_id.clone() else {
+                report.skipped_operations.push(endpoint);
+                continue;
+            };
+
+            let description = operation
+                .summary
+                .clone()
+                .or_else(|| operation.description.clone())
+                .unwrap_or_else(|| endpoint.clone());
+            let mut task = Task::new(
+                operation_id,
+                description,
+                agent.to_string(),
+                TaskPriority::Medium,
+                None,
+            );
+            for tag in &operation.tags {
+                task.add_tag(tag.clone());
+            }
+            task.metadata
+                .insert(ENDPOINT_METADATA.to_string(), Value::String(endpoint));
+
+            let mut refs = BTreeSet::new();
+            for value in item
+                .parameters
+                .iter()
+                .chain(&operation.parameters)
+                .chain(&operation.request_body)
+                .chain(std::iter::once(&operation.responses))
+            {
+                collect_component_refs(value, &document.components, &mut refs, &mut missing);
+            }
+
+            for reference in refs {
+                let Some(component) = lookup_component(&document.components, &reference) else {
+                    continue;
+                };
+                let context = contexts
+                    .entry(reference.clone())
+                    .or_insert_with(|| component_context(&reference, component, &source, agent));
+                relationships.push(EntityRelationship::new(
+                    Uuid::new_v4().to_string(),
+                    agent.to_string(),
+                    task.id.clone(),
+                    Task::entity_type().to_string(),
+                    context.id.clone(),
+                    Context::entity_type().to_string(),
+                    EntityRelationType::References,
+                ));
+            }
+
+            report.task_ids.push(task.id.clone());
+            tasks.push(task);
+        }
+    }
+
+    report.warnings.extend(
+        missing
+            .into_iter()
+            .map(|reference| format!("Unresolved reference {}", reference)),
+    );
+    report.tasks_created = tasks.len();
+    report.contexts_created = contexts.len();
+    report.relationships_created = relationships.len();
+
+    let mut entities: Vec<GenericEntity> = contexts.values().map(Context::to_generic).collect();
+    entities.extend(tasks.iter().map(Task::to_generic));
+    entities.extend(relationships.iter().map(EntityRelationship::to_generic));
+    storage.bulk_store(&entities)?;
+    Ok(report)
+}
+
+/// Resolve `#/components/{kind}/{name}` against the document's components
+fn lookup_component<'a>(
+    components: &'a BTreeMap<String, BTreeMap<String, Value>>,
+    reference: &str,
+) -> Option<&'a Value> {
+    let (kind, name) = reference
+        .strip_prefix(COMPONENTS_REF_PREFIX)?
+        .split_once('/')?;
+    components.get(kind)?.get(name)
+}
+
+/// Gather every component `$ref` reachable from `value`, following
+/// references inside components so nested schemas are included
+fn collect_component_refs(
+    value: &Value,
+    components: &BTreeMap<String, BTreeMap<String, Value>>,
+    refs: &mut BTreeSet<String>,
+    missing: &mut BTreeSet<String>,
+) {
+    match value {
+        Value::Object(map) => {
+            if let Some(reference) = map.get("$ref").and_then(Value::as_str) {
+                if reference.starts_with(COMPONENTS_REF_PREFIX) && refs.insert(reference.into()) {
+                    match lookup_component(components, reference) {
+                        Some(component) => {
+                            collect_component_refs(component, components, refs, missing)
+                        }
+                        None => {
+                            missing.insert(reference.to_string());
+                        }
+                    }
+                }
+            }
+            for nested in map.values() {
+                collect_component_refs(nested, components, refs, missing);
+            }
+        }
+        Value::Array(items) => {
+            for nested in items {
+                collect_component_refs(nested, components, refs, missing);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn component_context(reference: &str, component: &Value, source: &str, agent: &str) -> Context {
+    let name = reference
+        .strip_prefix(COMPONENTS_REF_PREFIX)
+        .unwrap_or(reference);
+    let content = serde_yaml::to_string(component).unwrap_or_else(|_| component.to_string());
+    let mut context = Context::new(
+        format!("OpenAPI {}", name),
+        content,
+        source.to_string(),
+        ContextRelevance::Medium,
+        agent.to_string(),
+    );
+    context.source_id = Some(reference.to_string());
+    context.tags.push("openapi".to_string());
+    context.metadata = HashMap::from([(
+        "openapi_ref".to_string(),
+        Value::String(reference.to_string()),
+    )]);
+    context
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::storage::MemoryStorage;
     use clap::Parser;
+    use tempfile::TempDir;
 
     #[derive(Parser)]
     struct Cli {
@@ -39,10 +357,163 @@ mod tests {
         let cli = Cli::try_parse_from(args).unwrap();
 
         match cli.command {
-            ConvertCommands::Convert { from, file } => {
+            ConvertCommands::Convert { from, file, agent } => {
                 assert_eq!(from, "github");
                 assert_eq!(file, "issues.json");
+                assert_eq!(agent, None);
             }
         }
     }
+
+    const SPEC: &str = r##"
+openapi: 3.0.3
+info:
+  title: Pets
+  version: 1.0.0
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      summary: List all pets
+      tags: [pets]
+      parameters:
+        - $ref: "#/components/parameters/Limit"
+      responses:
+        "200":
+          description: A page of pets
+          content:
+            application/json:
+              schema:
+                type: array
+                items:
+                  $ref: "#/components/schemas/Pet"
+    post:
+      summary: Create a pet
+      responses:
+        "201":
+          description: Created
+  /pets/{petId}:
+    parameters:
+      - $ref: "#/components/parameters/PetId"
+    get:
+      operationId: showPetById
+      summary: Info for a specific pet
+      tags: [pets, detail]
+      responses:
+        "200":
+          description: The pet
+          content:
+            application/json:
+              schema:
+                $ref: "#/components/schemas/Pet"
+components:
+  parameters:
+    Limit:
+      name: limit
+      in: query
+      schema:
+        type: integer
+    PetId:
+      name: petId
+      in: path
+      required: true
+      schema:
+        type: string
+  schemas:
+    Pet:
+      type: object
+      properties:
+        id:
+          type: integer
+        owner:
+          $ref: "#/components/schemas/Owner"
+    Owner:
+      type: object
+      properties:
+        name:
+          type: string
+"##;
+
+    fn task(storage: &MemoryStorage, title: &str) -> Task {
+        storage
+            .get_all("task")
+            .unwrap()
+            .into_iter()
+            .filter_map(|e| Task::from_generic(e).ok())
+            .find(|t| t.title == title)
+            .unwrap()
+    }
+
+    fn referenced_contexts(storage: &MemoryStorage, task_id: &str) -> BTreeSet<String> {
+        let contexts: HashMap<String, String> = storage
+            .get_all("context")
+            .unwrap()
+            .into_iter()
+            .filter_map(|e| Context::from_generic(e).ok())
+            .map(|c| (c.id, c.source_id.unwrap()))
+            .collect();
+        storage
+            .get_all("relationship")
+            .unwrap()
+            .into_iter()
+            .filter_map(|e| EntityRelationship::from_generic(e).ok())
+            .filter(|r| {
+                r.source_id == task_id && r.relationship_type == EntityRelationType::References
+            })
+            .map(|r| contexts[&r.target_id].clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_convert_openapi_creates_tasks_and_contexts() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("api.yaml");
+        std::fs::write(&path, SPEC).unwrap();
+        let mut storage = MemoryStorage::new("default");
+
+        let report = convert_openapi(&mut storage, &path, "converter").unwrap();
+        assert_eq!(report.operations_processed, 3);
+        assert_eq!(report.tasks_created, 2);
+        assert_eq!(report.contexts_created, 4);
+        assert_eq!(report.relationships_created, 6);
+        assert_eq!(report.skipped_operations, vec!["POST /pets"]);
+        assert!(report.warnings.is_empty());
+
+        let list = task(&storage, "listPets");
+        assert_eq!(list.description, "List all pets");
+        assert_eq!(list.tags, vec!["pets"]);
+        assert_eq!(list.agent, "converter");
+        assert_eq!(list.metadata[ENDPOINT_METADATA], "GET /pets");
+        assert_eq!(
+            referenced_contexts(&storage, &list.id),
+            BTreeSet::from([
+                "#/components/parameters/Limit".to_string(),
+                "#/components/schemas/Owner".to_string(),
+                "#/components/schemas/Pet".to_string(),
+            ])
+        );
+
+        let show = task(&storage, "showPetById");
+        assert_eq!(show.tags, vec!["pets", "detail"]);
+        assert_eq!(show.metadata[ENDPOINT_METADATA], "GET /pets/{petId}");
+        assert_eq!(
+            referenced_contexts(&storage, &show.id),
+            BTreeSet::from([
+                "#/components/parameters/PetId".to_string(),
+                "#/components/schemas/Owner".to_string(),
+                "#/components/schemas/Pet".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_convert_rejects_swagger_2() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("api.json");
+        std::fs::write(&path, r#"{"swagger": "2.0", "paths": {}}"#).unwrap();
+        let mut storage = MemoryStorage::new("default");
+
+        let err = convert_openapi(&mut storage, &path, "converter").unwrap_err();
+        assert!(err.to_string().contains("OpenAPI 3.x"));
+    }
 }
@@ -0,0 +1,42 @@
+//! Developer command implementations
+
+use crate::error::EngramError;
+use crate::storage::{MemoryStorage, Storage};
+use clap::Subcommand;
+use std::path::{Path, PathBuf};
+
+/// Developer commands
+#[derive(Debug, Subcommand)]
+pub enum DevCommands {
+    /// Load a TOML test fixture into the workspace
+    ///
+    ///EXAMPLES:
+    ///  engram dev load-fixture --file scenario.toml
+    LoadFixture {
+        /// TOML fixture with an `[[entities]]` array
+        #[arg(long)]
+        file: PathBuf,
+    },
+}
+
+/// Store every entity from a TOML fixture, returning how many were loaded
+pub fn load_fixture(storage: &mut dyn Storage, path: &Path) -> Result<usize, EngramError> {
+    let fixture = MemoryStorage::from_toml(&std::fs::read_to_string(path)?)?;
+    let entities = fixture.all_entities();
+    storage.bulk_store(&entities)?;
+    Ok(entities.len())
+}
+
+/// Handle `engram dev`
+pub fn handle_dev_command(
+    storage: &mut dyn Storage,
+    command: DevCommands,
+) -> Result<(), EngramError> {
+    match command {
+        DevCommands::LoadFixture { file } => {
+            let count = load_fixture(storage, &file)?;
+            println!("📦 Loaded {} entities from {}", count, file.display());
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,153 @@
+//! `engram diff` command implementation
+
+use crate::cli::utils::{create_table, truncate};
+use crate::entities::{
+    diff_entities, display_diff_value, EntityDiff, EntityRegistry, GenericEntity,
+};
+use crate::error::EngramError;
+use crate::storage::Storage;
+use prettytable::row;
+use serde_json::Value;
+use std::io::IsTerminal;
+
+/// Find an entity by ID without knowing its type
+pub fn find_entity_by_id(storage: &dyn Storage, id: &str) -> Result<GenericEntity, EngramError> {
+    let registry = EntityRegistry::with_builtin_types();
+    let mut types = registry.list_types();
+    types.sort_unstable();
+    for entity_type in types {
+        if let Some(entity) = storage.get(id, entity_type)? {
+            return Ok(entity);
+        }
+    }
+    Err(EngramError::not_found("entity", id))
+}
+
+fn short_id(id: &str) -> &str {
+    &id[..8.min(id.len())]
+}
+
+/// Render a diff as `-`/`+` lines per field
+pub fn render_unified_diff(
+    a: &GenericEntity,
+    b: &GenericEntity,
+    diff: &EntityDiff,
+    color: bool,
+) -> String {
+    render_labeled_diff(
+        &format!("{} {}", a.entity_type, short_id(&a.id)),
+        &format!("{} {}", b.entity_type, short_id(&b.id)),
+        diff,
+        color,
+    )
+}
+
+/// Render a diff under `---`/`+++` labels, colorized when `color` is set
+pub fn render_labeled_diff(old: &str, new: &str, diff: &EntityDiff, color: bool) -> String {
+    let mut out = format!("--- {}\n+++ {}\n", old, new);
+    out.push_str(&diff.render(color));
+    if !diff.unchanged_fields.is_empty() {
+        out.push_str(&format!(
+            "({} unchanged field(s))\n",
+            diff.unchanged_fields.len()
+        ));
+    }
+    out
+}
+
+pub fn print_table_diff(diff: &EntityDiff) {
+    let mut table = create_table();
+    table.set_titles(row!["Field", "Old", "New"]);
+    for (field, value) in &diff.removed_fields {
+        table.add_row(row![field, truncate(&display_diff_value(value), 40), "—"]);
+    }
+    for (field, change) in &diff.modified_fields {
+        table.add_row(row![
+            field,
+            truncate(&display_diff_value(&change.old), 40),
+            truncate(&display_diff_value(&change.new), 40)
+        ]);
+    }
+    for (field, change) in &diff.list_changes {
+        let items = |values: &[Value], sign: &str| {
+            values
+                .iter()
+                .map(|v| format!("{}{}", sign, display_diff_value(v)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        table.add_row(row![
+            field,
+            truncate(&items(&change.removed, "-"), 40),
+            truncate(&items(&change.added, "+"), 40)
+        ]);
+    }
+    for (field, value) in &diff.added_fields {
+        table.add_row(row![field, "—", truncate(&display_diff_value(value), 40)]);
+    }
+    table.printstd();
+    println!("{} unchanged field(s)", diff.unchanged_fields.len());
+}
+
+/// Handle `engram diff`
+pub fn handle_diff_command(
+    storage: &dyn Storage,
+    id_a: &str,
+    id_b: &str,
+    format: &str,
+) -> Result<(), EngramError> {
+    let a = find_entity_by_id(storage, id_a)?;
+    let b = find_entity_by_id(storage, id_b)?;
+    if a.entity_type != b.entity_type {
+        eprintln!("⚠️  Comparing a {} with a {}", a.entity_type, b.entity_type);
+    }
+    let diff = diff_entities(&a, &b);
+
+    match format {
+        "json" => println!("{}", serde_json::to_string_pretty(&diff)?),
+        "table" => print_table_diff(&diff),
+        _ => print!(
+            "{}",
+            render_unified_diff(&a, &b, &diff, std::io::stdout().is_terminal())
+        ),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{Entity, Task, TaskPriority};
+    use crate::storage::MemoryStorage;
+
+    #[test]
+    fn test_unified_diff_lines() {
+        let mut storage = MemoryStorage::new("default");
+        let mut low = Task::new(
+            "Write docs".to_string(),
+            String::new(),
+            "tester".to_string(),
+            TaskPriority::Low,
+            None,
+        );
+        low.id = "aaaaaaaa-1".to_string();
+        let mut high = low.clone();
+        high.id = "bbbbbbbb-2".to_string();
+        high.priority = TaskPriority::High;
+        storage.store(&low.to_generic()).unwrap();
+        storage.store(&high.to_generic()).unwrap();
+
+        let a = find_entity_by_id(&storage, &low.id).unwrap();
+        let b = find_entity_by_id(&storage, &high.id).unwrap();
+        assert_eq!(a.entity_type, "task");
+        let rendered = render_unified_diff(&a, &b, &diff_entities(&a, &b), false);
+        assert!(rendered.starts_with("--- task aaaaaaaa\n+++ task bbbbbbbb\n"));
+        assert!(rendered.contains("@@ priority @@\n- low\n+ high\n"));
+        assert!(rendered.contains("@@ id @@\n- aaaaaaaa-1\n+ bbbbbbbb-2\n"));
+
+        assert!(matches!(
+            find_entity_by_id(&storage, "missing"),
+            Err(EngramError::NotFound { .. })
+        ));
+    }
+}
@@ -415,7 +415,7 @@ fn handle_fetch_command<S: Storage>(
     let entities = storage.get_all(entity_type)?;
 
     if entities.is_empty() {
-        return Err(EngramError::NotFound(format!(
+        return Err(EngramError::InvalidOperation(format!(
             "No {} entities found",
             topic
         )));
@@ -616,10 +616,7 @@ fn handle_chunk_command<S: Storage>(
                     }
                 }
             }
-            return Err(EngramError::NotFound(format!(
-                "Chunk '{}' not found in topic '{}'.",
-                chunk_id, topic
-            )));
+            return Err(EngramError::not_found(format!("{} chunk", topic), chunk_id));
         }
     }
     Ok(())
@@ -1073,10 +1070,10 @@ pub fn search_refs(
     };
 
     if !root.exists() {
-        return Err(EngramError::NotFound(format!(
-            "Directory not found: {}",
-            root.display()
-        )));
+        return Err(EngramError::not_found(
+            "directory",
+            root.display().to_string(),
+        ));
     }
 
     let project_type = detect_project_type(&root);
@@ -1558,7 +1555,7 @@ mod tests {
     #[test]
     fn test_search_refs_nonexistent_dir() {
         let result = search_refs("test", Some("/nonexistent/path"), false, false, 3, 50);
-        assert!(matches!(result, Err(EngramError::NotFound(_))));
+        assert!(matches!(result, Err(EngramError::NotFound { .. })));
     }
 
     #[test]
@@ -0,0 +1,809 @@
+//! Workspace diagnostics for the `engram doctor` command
+//!
+//! Runs a series of independent checks against a workspace directory and
+//! reports what is wrong together with the command that fixes it.
+
+use crate::cli::utils::create_table;
+use crate::config::Config;
+use crate::entities::{Entity, Knowledge};
+use crate::error::EngramError;
+use crate::storage::{
+    EncryptionConfig, GitRefsStorage, Storage, WorkspaceKey, ENCRYPTION_KEY_ENV_VAR,
+};
+use crate::validation::HookManager;
+use prettytable::{Cell, Row};
+use serde::Serialize;
+use std::fs;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::Path;
+use std::time::Duration;
+
+/// Minimum supported git version (major, minor)
+const MIN_GIT_VERSION: (u32, u32) = (2, 28);
+
+/// Timeout for remote connectivity probes
+const REMOTE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Outcome of a single diagnostic check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+impl std::fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CheckStatus::Pass => write!(f, "PASS"),
+            CheckStatus::Warn => write!(f, "WARN"),
+            CheckStatus::Fail => write!(f, "FAIL"),
+        }
+    }
+}
+
+/// Result of one diagnostic check
+#[derive(Debug, Clone, Serialize)]
+pub struct DiagnosticCheck {
+    pub name: String,
+    pub status: CheckStatus,
+    pub message: String,
+    pub fix_command: Option<String>,
+}
+
+impl DiagnosticCheck {
+    fn pass(name: &str, message: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status: CheckStatus::Pass,
+            message: message.into(),
+            fix_command: None,
+        }
+    }
+
+    fn warn(name: &str, message: impl Into<String>, fix_command: Option<&str>) -> Self {
+        Self {
+            name: name.to_string(),
+            status: CheckStatus::Warn,
+            message: message.into(),
+            fix_command: fix_command.map(str::to_string),
+        }
+    }
+
+    fn fail(name: &str, message: impl Into<String>, fix_command: Option<&str>) -> Self {
+        Self {
+            name: name.to_string(),
+            status: CheckStatus::Fail,
+            message: message.into(),
+            fix_command: fix_command.map(str::to_string),
+        }
+    }
+}
+
+/// Full diagnostics report for a workspace
+#[derive(Debug, Clone, Serialize)]
+pub struct DiagnosticReport {
+    pub workspace_dir: String,
+    pub checks: Vec<DiagnosticCheck>,
+}
+
+impl DiagnosticReport {
+    /// Look up a check by name
+    pub fn check(&self, name: &str) -> Option<&DiagnosticCheck> {
+        self.checks.iter().find(|c| c.name == name)
+    }
+
+    /// True when no check failed
+    pub fn is_healthy(&self) -> bool {
+        self.checks.iter().all(|c| c.status != CheckStatus::Fail)
+    }
+}
+
+/// Run every diagnostic check against `workspace_dir`
+pub fn run_diagnostics(workspace_dir: &Path) -> Result<DiagnosticReport, EngramError> {
+    let repo = git2::Repository::open(workspace_dir).ok();
+
+    let checks = vec![
+        check_git_binary(),
+        check_engram_dir(workspace_dir),
+        check_refs_structure(repo.as_ref()),
+        check_config(workspace_dir),
+        check_encryption(workspace_dir),
+        check_agents(workspace_dir),
+        check_hook(workspace_dir)?,
+        check_entity_integrity(repo.as_ref()),
+        check_knowledge_confidence(repo.as_ref()),
+        check_remotes(workspace_dir, repo.as_ref()),
+    ];
+
+    Ok(DiagnosticReport {
+        workspace_dir: workspace_dir.to_string_lossy().to_string(),
+        checks,
+    })
+}
+
+fn parse_git_version(output: &str) -> Option<(u32, u32)> {
+    let version = output.split_whitespace().nth(2)?;
+    let mut parts = version.split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    Some((major, minor))
+}
+
+fn check_git_binary() -> DiagnosticCheck {
+    const NAME: &str = "git_binary";
+    let output = match std::process::Command::new("git").arg("--version").output() {
+        Ok(output) if output.status.success() => output,
+        _ => {
+            return DiagnosticCheck::fail(NAME, "git executable not found on PATH", None);
+        }
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    match parse_git_version(&stdout) {
+        Some(version) if version >= MIN_GIT_VERSION => DiagnosticCheck::pass(NAME, stdout),
+        Some((major, minor)) => DiagnosticCheck::fail(
+            NAME,
+            format!(
+                "git {}.{} is too old (need >= {}.{})",
+                major, minor, MIN_GIT_VERSION.0, MIN_GIT_VERSION.1
+            ),
+            None,
+        ),
+        None => DiagnosticCheck::warn(
+            NAME,
+            format!("Could not parse git version from '{}'", stdout),
+            None,
+        ),
+    }
+}
+
+fn check_engram_dir(workspace_dir: &Path) -> DiagnosticCheck {
+    const NAME: &str = "engram_dir";
+    let engram_dir = workspace_dir.join(".engram");
+    if !engram_dir.is_dir() {
+        return DiagnosticCheck::fail(
+            NAME,
+            ".engram directory is missing",
+            Some("engram setup workspace"),
+        );
+    }
+
+    let missing: Vec<&str> = ["agents", "workspaces", "templates"]
+        .into_iter()
+        .filter(|sub| !engram_dir.join(sub).is_dir())
+        .collect();
+    if missing.is_empty() {
+        DiagnosticCheck::pass(NAME, ".engram directory is present")
+    } else {
+        DiagnosticCheck::warn(
+            NAME,
+            format!("Missing subdirectories: {}", missing.join(", ")),
+            Some("engram setup workspace"),
+        )
+    }
+}
+
+/// All references under `refs/engram/`
+fn engram_refs(repo: &git2::Repository) -> Result<Vec<git2::Reference<'_>>, git2::Error> {
+    Ok(repo
+        .references()?
+        .flatten()
+        .filter(|r| r.name().is_some_and(|n| n.starts_with("refs/engram/")))
+        .collect())
+}
+
+fn check_refs_structure(repo: Option<&git2::Repository>) -> DiagnosticCheck {
+    const NAME: &str = "engram_refs";
+    let Some(repo) = repo else {
+        return DiagnosticCheck::fail(NAME, "Not a git repository", Some("git init"));
+    };
+
+    let refs = match engram_refs(repo) {
+        Ok(refs) => refs,
+        Err(e) => {
+            return DiagnosticCheck::fail(NAME, format!("Cannot read refs: {}", e), None);
+        }
+    };
+
+    let mut total = 0usize;
+    let mut malformed = Vec::new();
+    for reference in &refs {
+        total += 1;
+        let name = reference.name().unwrap_or("<non-utf8>").to_string();
+        // refs/engram/{entity_type}/{entity_id} at minimum
+        if name.trim_start_matches("refs/engram/").split('/').count() < 2 {
+            malformed.push(name);
+        }
+    }
+
+    if !malformed.is_empty() {
+        DiagnosticCheck::fail(
+            NAME,
+            format!("Malformed engram refs: {}", malformed.join(", ")),
+            None,
+        )
+    } else if total == 0 {
+        DiagnosticCheck::warn(NAME, "No engram refs found (empty workspace)", None)
+    } else {
+        DiagnosticCheck::pass(NAME, format!("{} engram refs found", total))
+    }
+}
+
+fn check_config(workspace_dir: &Path) -> DiagnosticCheck {
+    const NAME: &str = "config";
+    let Some(path) = Config::workspace_config_file(workspace_dir) else {
+        return DiagnosticCheck::pass(NAME, "No engram.yaml found, using defaults");
+    };
+
+    match Config::load_from_file(&path.to_string_lossy()).and_then(|c| c.validate()) {
+        Ok(()) => DiagnosticCheck::pass(NAME, format!("{} is valid", path.display())),
+        Err(e) => DiagnosticCheck::fail(NAME, format!("{}: {}", path.display(), e), None),
+    }
+}
+
+fn check_encryption(workspace_dir: &Path) -> DiagnosticCheck {
+    const NAME: &str = "encryption";
+    let config = match EncryptionConfig::load(workspace_dir) {
+        Ok(config) => config,
+        Err(e) => return DiagnosticCheck::fail(NAME, e.to_string(), None),
+    };
+    if config.fields.is_empty() {
+        return DiagnosticCheck::pass(NAME, "No encrypted fields configured");
+    }
+
+    match WorkspaceKey::load(workspace_dir, &config) {
+        Ok(Some(key)) => DiagnosticCheck::pass(
+            NAME,
+            format!(
+                "{} field(s) encrypted with key {}",
+                config.fields.len(),
+                key.fingerprint()
+            ),
+        ),
+        Ok(None) => DiagnosticCheck::fail(
+            NAME,
+            format!(
+                "{} field(s) configured for encryption but no key is available",
+                config.fields.len()
+            ),
+            Some(&format!(
+                "export {}=<base64 key> (or set encryption.key_file)",
+                ENCRYPTION_KEY_ENV_VAR
+            )),
+        ),
+        Err(e) => DiagnosticCheck::fail(NAME, format!("Unusable encryption key: {}", e), None),
+    }
+}
+
+fn check_agents(workspace_dir: &Path) -> DiagnosticCheck {
+    const NAME: &str = "agents";
+    let agents_dir = workspace_dir.join(".engram").join("agents");
+    let count = fs::read_dir(&agents_dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|e| {
+                    matches!(
+                        e.path().extension().and_then(|ext| ext.to_str()),
+                        Some("yaml") | Some("yml")
+                    )
+                })
+                .count()
+        })
+        .unwrap_or(0);
+
+    if count == 0 {
+        DiagnosticCheck::fail(
+            NAME,
+            "No agents configured",
+            Some("engram setup agent --name <name>"),
+        )
+    } else {
+        DiagnosticCheck::pass(NAME, format!("{} agent(s) configured", count))
+    }
+}
+
+fn check_hook(workspace_dir: &Path) -> Result<DiagnosticCheck, EngramError> {
+    const NAME: &str = "commit_hook";
+    const FIX: &str = "engram validate hook install";
+    let manager = HookManager::new(workspace_dir)?;
+    if !manager.is_installed()? {
+        return Ok(DiagnosticCheck::fail(
+            NAME,
+            "Engram commit-msg hook is not installed",
+            Some(FIX),
+        ));
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let hook_path = workspace_dir.join(".git").join("hooks").join("commit-msg");
+        let mode = fs::metadata(&hook_path)
+            .map_err(EngramError::Io)?
+            .permissions()
+            .mode();
+        if mode & 0o111 == 0 {
+            return Ok(DiagnosticCheck::fail(
+                NAME,
+                "Commit-msg hook is not executable",
+                Some(FIX),
+            ));
+        }
+    }
+
+    Ok(DiagnosticCheck::pass(NAME, "Commit-msg hook installed"))
+}
+
+fn check_entity_integrity(repo: Option<&git2::Repository>) -> DiagnosticCheck {
+    const NAME: &str = "entity_integrity";
+    let Some(repo) = repo else {
+        return DiagnosticCheck::warn(NAME, "Skipped: not a git repository", None);
+    };
+
+    let refs = match engram_refs(repo) {
+        Ok(refs) => refs,
+        Err(e) => {
+            return DiagnosticCheck::fail(NAME, format!("Cannot read refs: {}", e), None);
+        }
+    };
+
+    let mut checked = 0usize;
+    let mut corrupted = Vec::new();
+    for reference in &refs {
+        let name = reference.name().unwrap_or("<non-utf8>").to_string();
+        let Some(oid) = reference.target() else {
+            continue;
+        };
+        // Only blob refs hold entity JSON; commits and trees are skipped
+        let Ok(blob) = repo.find_blob(oid) else {
+            continue;
+        };
+        checked += 1;
+        if serde_json::from_slice::<serde_json::Value>(blob.content()).is_err() {
+            corrupted.push(name);
+        }
+    }
+
+    if corrupted.is_empty() {
+        DiagnosticCheck::pass(NAME, format!("{} entities decoded", checked))
+    } else {
+        DiagnosticCheck::fail(
+            NAME,
+            format!(
+                "{} corrupted entities: {}",
+                corrupted.len(),
+                corrupted.join(", ")
+            ),
+            None,
+        )
+    }
+}
+
+/// Ids of knowledge entities whose stored confidence lies outside [0.0, 1.0]
+fn out_of_range_knowledge(repo: &git2::Repository) -> Result<Vec<String>, git2::Error> {
+    let mut ids = Vec::new();
+    for reference in engram_refs(repo)? {
+        let Some(id) = reference
+            .name()
+            .and_then(|n| n.strip_prefix("refs/engram/knowledge/"))
+        else {
+            continue;
+        };
+        // Version sidecars live under refs/engram/knowledge/v<N>/<id>
+        if id.contains('/') {
+            continue;
+        }
+        let Some(blob) = reference.target().and_then(|oid| repo.find_blob(oid).ok()) else {
+            continue;
+        };
+        let confidence = serde_json::from_slice::<serde_json::Value>(blob.content())
+            .ok()
+            .and_then(|value| value.pointer("/data/confidence").and_then(|c| c.as_f64()));
+        if confidence.is_some_and(|c| !(0.0..=1.0).contains(&c)) {
+            ids.push(id.to_string());
+        }
+    }
+    Ok(ids)
+}
+
+fn check_knowledge_confidence(repo: Option<&git2::Repository>) -> DiagnosticCheck {
+    const NAME: &str = "knowledge_confidence";
+    let Some(repo) = repo else {
+        return DiagnosticCheck::warn(NAME, "Skipped: not a git repository", None);
+    };
+
+    match out_of_range_knowledge(repo) {
+        Ok(ids) if ids.is_empty() => {
+            DiagnosticCheck::pass(NAME, "All knowledge confidence values are in range")
+        }
+        Ok(ids) => DiagnosticCheck::warn(
+            NAME,
+            format!(
+                "{} knowledge item(s) with confidence outside [0.0, 1.0]: {}",
+                ids.len(),
+                ids.join(", ")
+            ),
+            Some("engram doctor --fix"),
+        ),
+        Err(e) => DiagnosticCheck::fail(NAME, format!("Cannot read refs: {}", e), None),
+    }
+}
+
+/// Clamp out-of-range knowledge confidence values, returning how many were fixed
+fn clamp_knowledge_confidence(workspace_dir: &Path) -> Result<usize, EngramError> {
+    let repo = git2::Repository::open(workspace_dir)?;
+    let ids = out_of_range_knowledge(&repo)?;
+    if ids.is_empty() {
+        return Ok(0);
+    }
+
+    let mut storage = GitRefsStorage::new(
+        &workspace_dir.to_string_lossy(),
+        &crate::cli::identity::current_agent(),
+    )?;
+    for id in &ids {
+        let Some(entity) = storage.get(id, Knowledge::entity_type())? else {
+            continue;
+        };
+        let mut knowledge = Knowledge::from_generic(entity)?;
+        knowledge.update_content(knowledge.content.clone(), knowledge.confidence);
+        storage.store(&knowledge.to_generic())?;
+    }
+    Ok(ids.len())
+}
+
+/// Extract (host, port) from a git remote URL; `None` for local remotes
+fn remote_host_port(url: &str) -> Option<(String, u16)> {
+    if let Some((scheme, rest)) = url.split_once("://") {
+        let default_port = match scheme {
+            "https" => 443,
+            "http" => 80,
+            "ssh" | "git+ssh" => 22,
+            "git" => 9418,
+            _ => return None,
+        };
+        let authority = rest.split('/').next()?;
+        let host_port = authority.rsplit('@').next()?;
+        return match host_port.rsplit_once(':') {
+            Some((host, port)) => Some((host.to_string(), port.parse().ok()?)),
+            None => Some((host_port.to_string(), default_port)),
+        };
+    }
+
+    // scp-like syntax: [user@]host:path
+    let (authority, _) = url.split_once(':')?;
+    if authority.contains('/') {
+        return None;
+    }
+    let host = authority.rsplit('@').next()?;
+    Some((host.to_string(), 22))
+}
+
+fn configured_remote_urls(workspace_dir: &Path, repo: Option<&git2::Repository>) -> Vec<String> {
+    let mut urls = Vec::new();
+
+    if let Some(repo) = repo {
+        if let Ok(names) = repo.remotes() {
+            for name in names.iter().flatten() {
+                if let Ok(remote) = repo.find_remote(name) {
+                    if let Some(url) = remote.url() {
+                        urls.push(url.to_string());
+                    }
+                }
+            }
+        }
+    }
+
+    let config_url = fs::read_to_string(workspace_dir.join("engram.yaml"))
+        .ok()
+        .and_then(|content| serde_yaml::from_str::<serde_yaml::Value>(&content).ok())
+        .and_then(|value| {
+            value
+                .get("git")
+                .and_then(|git| git.get("remote_url"))
+                .and_then(|url| url.as_str())
+                .map(str::to_string)
+        });
+    if let Some(url) = config_url {
+        if !urls.contains(&url) {
+            urls.push(url);
+        }
+    }
+
+    urls
+}
+
+fn check_remotes(workspace_dir: &Path, repo: Option<&git2::Repository>) -> DiagnosticCheck {
+    const NAME: &str = "remote_connectivity";
+    let urls = configured_remote_urls(workspace_dir, repo);
+    if urls.is_empty() {
+        return DiagnosticCheck::pass(NAME, "No remotes configured");
+    }
+
+    let mut unreachable = Vec::new();
+    for url in &urls {
+        let Some((host, port)) = remote_host_port(url) else {
+            continue;
+        };
+        let reachable = (host.as_str(), port)
+            .to_socket_addrs()
+            .ok()
+            .and_then(|mut addrs| addrs.next())
+            .map(|addr| TcpStream::connect_timeout(&addr, REMOTE_TIMEOUT).is_ok())
+            .unwrap_or(false);
+        if !reachable {
+            unreachable.push(url.clone());
+        }
+    }
+
+    if unreachable.is_empty() {
+        DiagnosticCheck::pass(NAME, format!("{} remote(s) reachable", urls.len()))
+    } else {
+        DiagnosticCheck::warn(
+            NAME,
+            format!("Unreachable remotes: {}", unreachable.join(", ")),
+            None,
+        )
+    }
+}
+
+/// Attempt automatic remediation for fixable checks, returning what was done
+pub fn apply_fixes(
+    workspace_dir: &Path,
+    report: &DiagnosticReport,
+) -> Result<Vec<String>, EngramError> {
+    let mut applied = Vec::new();
+
+    for check in &report.checks {
+        if check.status == CheckStatus::Pass {
+            continue;
+        }
+        match check.name.as_str() {
+            "engram_dir" => {
+                let engram_dir = workspace_dir.join(".engram");
+                for sub in ["agents", "workspaces", "templates"] {
+                    fs::create_dir_all(engram_dir.join(sub)).map_err(EngramError::Io)?;
+                }
+                applied.push("Created .engram directory structure".to_string());
+            }
+            "commit_hook" if workspace_dir.join(".git").is_dir() => {
+                HookManager::new(workspace_dir)?.install()?;
+                applied.push("Installed commit-msg hook".to_string());
+            }
+            "knowledge_confidence" => {
+                let fixed = clamp_knowledge_confidence(workspace_dir)?;
+                if fixed > 0 {
+                    applied.push(format!(
+                        "Clamped confidence of {} knowledge item(s) into [0.0, 1.0]",
+                        fixed
+                    ));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(applied)
+}
+
+/// Print a colour-coded diagnostics table
+pub fn print_report(report: &DiagnosticReport) {
+    let mut table = create_table();
+    table.set_titles(prettytable::row!["Check", "Status", "Message", "Fix"]);
+
+    for check in &report.checks {
+        let style = match check.status {
+            CheckStatus::Pass => "Fg",
+            CheckStatus::Warn => "Fy",
+            CheckStatus::Fail => "Fr",
+        };
+        table.add_row(Row::new(vec![
+            Cell::new(&check.name),
+            Cell::new(&check.status.to_string()).style_spec(style),
+            Cell::new(&check.message),
+            Cell::new(check.fix_command.as_deref().unwrap_or("")),
+        ]));
+    }
+
+    table.printstd();
+}
+
+/// Handle `engram doctor`
+pub fn handle_doctor_command(fix: bool, json: bool) -> Result<(), EngramError> {
+    let workspace_dir = Path::new(".");
+    let mut report = run_diagnostics(workspace_dir)?;
+
+    if fix {
+        let applied = apply_fixes(workspace_dir, &report)?;
+        if !json {
+            for action in &applied {
+                println!("🔧 {}", action);
+            }
+        }
+        if !applied.is_empty() {
+            report = run_diagnostics(workspace_dir)?;
+        }
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_report(&report);
+        if report.is_healthy() {
+            println!("✅ Workspace looks healthy");
+        } else if !fix {
+            println!("💡 Run 'engram doctor --fix' to attempt automatic remediation");
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_encryption_key_availability() {
+        let temp = TempDir::new().unwrap();
+        assert_eq!(check_encryption(temp.path()).status, CheckStatus::Pass);
+
+        std::fs::write(
+            temp.path().join("engram.yaml"),
+            "encryption:\n  fields: [context.content]\n  key_file: engram.key\n",
+        )
+        .unwrap();
+        let missing = check_encryption(temp.path());
+        assert_eq!(missing.status, CheckStatus::Fail);
+        assert!(missing.fix_command.is_some());
+
+        let key = WorkspaceKey::generate().unwrap();
+        std::fs::write(temp.path().join("engram.key"), key.encode()).unwrap();
+        let found = check_encryption(temp.path());
+        assert_eq!(found.status, CheckStatus::Pass);
+        assert!(found.message.contains(key.fingerprint()));
+    }
+
+    #[test]
+    fn test_missing_hook_suggests_install() {
+        let temp = TempDir::new().unwrap();
+        git2::Repository::init(temp.path()).unwrap();
+
+        let report = run_diagnostics(temp.path()).unwrap();
+        let hook = report.check("commit_hook").unwrap();
+
+        assert_eq!(hook.status, CheckStatus::Fail);
+        assert_eq!(
+            hook.fix_command.as_deref(),
+            Some("engram validate hook install")
+        );
+        assert!(!report.is_healthy());
+    }
+
+    #[test]
+    fn test_fix_installs_hook_and_dirs() {
+        let temp = TempDir::new().unwrap();
+        git2::Repository::init(temp.path()).unwrap();
+
+        let report = run_diagnostics(temp.path()).unwrap();
+        let applied = apply_fixes(temp.path(), &report).unwrap();
+        assert_eq!(applied.len(), 2);
+
+        let report = run_diagnostics(temp.path()).unwrap();
+        assert_eq!(
+            report.check("commit_hook").unwrap().status,
+            CheckStatus::Pass
+        );
+        assert_eq!(
+            report.check("engram_dir").unwrap().status,
+            CheckStatus::Pass
+        );
+    }
+
+    #[test]
+    fn test_invalid_config_fails() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("engram.yaml"), "app: [unterminated").unwrap();
+
+        let report = run_diagnostics(temp.path()).unwrap();
+        assert_eq!(report.check("config").unwrap().status, CheckStatus::Fail);
+        assert_eq!(
+            report.check("engram_refs").unwrap().status,
+            CheckStatus::Fail
+        );
+    }
+
+    #[test]
+    fn test_corrupted_entity_detected() {
+        let temp = TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp.path()).unwrap();
+        let good = repo.blob(br#"{"id":"a"}"#).unwrap();
+        let bad = repo.blob(b"not json").unwrap();
+        repo.reference("refs/engram/task/a", good, true, "test")
+            .unwrap();
+        repo.reference("refs/engram/task/b", bad, true, "test")
+            .unwrap();
+
+        let report = run_diagnostics(temp.path()).unwrap();
+        let integrity = report.check("entity_integrity").unwrap();
+        assert_eq!(integrity.status, CheckStatus::Fail);
+        assert!(integrity.message.contains("refs/engram/task/b"));
+    }
+
+    #[test]
+    fn test_fix_clamps_out_of_range_knowledge_confidence() {
+        let temp = TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp.path()).unwrap();
+
+        let knowledge = Knowledge::new(
+            "Rate limit".to_string(),
+            "100 req/s".to_string(),
+            crate::entities::KnowledgeType::Fact,
+            0.5,
+            "agent".to_string(),
+        );
+        let mut storage = GitRefsStorage::new(&temp.path().to_string_lossy(), "agent").unwrap();
+        storage.store(&knowledge.to_generic()).unwrap();
+
+        // Rewrite the stored blob the way an older engram would have left it
+        let ref_name = format!("refs/engram/knowledge/{}", knowledge.id);
+        let oid = repo.refname_to_id(&ref_name).unwrap();
+        let mut value: serde_json::Value =
+            serde_json::from_slice(repo.find_blob(oid).unwrap().content()).unwrap();
+        value["data"]["confidence"] = serde_json::json!(7.0);
+        let bad = repo.blob(&serde_json::to_vec(&value).unwrap()).unwrap();
+        repo.reference(&ref_name, bad, true, "test").unwrap();
+
+        let report = run_diagnostics(temp.path()).unwrap();
+        let check = report.check("knowledge_confidence").unwrap();
+        assert_eq!(check.status, CheckStatus::Warn);
+        assert!(check.message.contains(&knowledge.id));
+        assert_eq!(check.fix_command.as_deref(), Some("engram doctor --fix"));
+
+        let applied = apply_fixes(temp.path(), &report).unwrap();
+        assert!(applied
+            .iter()
+            .any(|a| a.contains("Clamped confidence of 1")));
+
+        let report = run_diagnostics(temp.path()).unwrap();
+        assert_eq!(
+            report.check("knowledge_confidence").unwrap().status,
+            CheckStatus::Pass
+        );
+        let storage = GitRefsStorage::new(&temp.path().to_string_lossy(), "agent").unwrap();
+        let entity = storage.get(&knowledge.id, "knowledge").unwrap().unwrap();
+        assert_eq!(Knowledge::from_generic(entity).unwrap().confidence, 1.0);
+    }
+
+    #[test]
+    fn test_parse_git_version() {
+        assert_eq!(parse_git_version("git version 2.39.2"), Some((2, 39)));
+        assert_eq!(
+            parse_git_version("git version 2.39.3 (Apple Git-146)"),
+            Some((2, 39))
+        );
+        assert_eq!(parse_git_version("garbage"), None);
+    }
+
+    #[test]
+    fn test_remote_host_port() {
+        assert_eq!(
+            remote_host_port("https://github.com/vincents-ai/engram.git"),
+            Some(("github.com".to_string(), 443))
+        );
+        assert_eq!(
+            remote_host_port("git@github.com:vincents-ai/engram.git"),
+            Some(("github.com".to_string(), 22))
+        );
+        assert_eq!(
+            remote_host_port("ssh://git@example.com:2222/repo.git"),
+            Some(("example.com".to_string(), 2222))
+        );
+        assert_eq!(remote_host_port("/srv/git/repo.git"), None);
+        assert_eq!(remote_host_port("file:///srv/git/repo.git"), None);
+    }
+}
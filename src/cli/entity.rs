@@ -0,0 +1,280 @@
+//! Entity-level commands that work on any entity type
+
+use crate::cli::diff::{find_entity_by_id, render_labeled_diff};
+use crate::entities::{diff_entities, EntityDiff, GenericEntity, JsonPatchOp};
+use crate::error::EngramError;
+use crate::storage::{GitRefsStorage, Storage};
+use clap::Subcommand;
+use std::io::IsTerminal;
+
+/// Entity commands
+#[derive(Debug, Subcommand)]
+pub enum EntityCommands {
+    /// Apply an RFC 6902 JSON Patch to a stored entity
+    ///
+    /// Paths address the whole entity, so fields of the entity payload live
+    /// under `/data`.
+    ///
+    ///EXAMPLES:
+    ///  engram entity patch <id> --type task --patch '[{"op":"replace","path":"/data/status","value":"done"}]'
+    ///  engram entity patch <id> --patch '[{"op":"add","path":"/data/tags/-","value":"urgent"}]'
+    Patch {
+        /// Entity ID
+        id: String,
+
+        /// Entity type; looked up from the ID when omitted
+        #[arg(long = "type")]
+        entity_type: Option<String>,
+
+        /// JSON array of patch operations
+        #[arg(long)]
+        patch: String,
+    },
+    /// Field-level diff of two entities, or of two stored versions of one
+    ///
+    /// List fields are compared as sets. Version numbers come from the
+    /// entity's version history (git-backed storage only).
+    ///
+    ///EXAMPLES:
+    ///  engram entity diff task <id-a> <id-b>
+    ///  engram entity diff task <id> --versions 3..5
+    ///  engram entity diff context <id> --versions 1..2 --json
+    Diff {
+        /// Entity type of both sides
+        entity_type: String,
+
+        /// Entity ID (the old side)
+        id_a: String,
+
+        /// Entity ID of the new side
+        #[arg(required_unless_present = "versions", conflicts_with = "versions")]
+        id_b: Option<String>,
+
+        /// Compare two versions of `id_a`, as `<old>..<new>`
+        #[arg(long)]
+        versions: Option<String>,
+    },
+}
+
+/// The two sides of an entity diff with their labels
+pub struct EntityComparison {
+    pub old_label: String,
+    pub new_label: String,
+    pub diff: EntityDiff,
+}
+
+fn short_id(id: &str) -> &str {
+    &id[..8.min(id.len())]
+}
+
+fn get_entity(
+    storage: &dyn Storage,
+    entity_type: &str,
+    id: &str,
+) -> Result<GenericEntity, EngramError> {
+    storage
+        .get(id, entity_type)?
+        .ok_or_else(|| EngramError::not_found(entity_type, id))
+}
+
+/// Parse a `<old>..<new>` version range
+fn parse_version_range(range: &str) -> Result<(u64, u64), EngramError> {
+    let invalid = || {
+        EngramError::Validation(format!(
+            "Invalid version range '{}'; expected <old>..<new>, e.g. 3..5",
+            range
+        ))
+    };
+    let (old, new) = range.split_once("..").ok_or_else(invalid)?;
+    let old: u64 = old.trim().parse().map_err(|_| invalid())?;
+    let new: u64 = new.trim().parse().map_err(|_| invalid())?;
+    if old == 0 || new == 0 {
+        return Err(invalid());
+    }
+    Ok((old, new))
+}
+
+/// Compare two entities of `entity_type`, or versions `range` of `id_a`
+pub fn compare_entities(
+    storage: &dyn Storage,
+    entity_type: &str,
+    id_a: &str,
+    id_b: Option<&str>,
+    range: Option<&str>,
+) -> Result<EntityComparison, EngramError> {
+    let label = |entity: &GenericEntity| format!("{} {}", entity_type, short_id(&entity.id));
+    let (old, new, old_label, new_label) = match (id_b, range) {
+        (Some(id_b), None) => {
+            let old = get_entity(storage, entity_type, id_a)?;
+            let new = get_entity(storage, entity_type, id_b)?;
+            let (old_label, new_label) = (label(&old), label(&new));
+            (old, new, old_label, new_label)
+        }
+        (None, Some(range)) => {
+            let (from, to) = parse_version_range(range)?;
+            let git = storage
+                .as_any()
+                .downcast_ref::<GitRefsStorage>()
+                .ok_or_else(|| {
+                    EngramError::InvalidOperation(
+                        "Version history is only kept by git-backed storage".to_string(),
+                    )
+                })?;
+            let current = get_entity(storage, entity_type, id_a)?;
+            let version = |n: u64| {
+                git.entity_version(entity_type, &current.id, n)?
+                    .ok_or_else(|| {
+                        EngramError::not_found(entity_type, format!("{} v{}", current.id, n))
+                    })
+            };
+            let (old, new) = (version(from)?, version(to)?);
+            let base = label(&current);
+            (
+                old,
+                new,
+                format!("{} v{}", base, from),
+                format!("{} v{}", base, to),
+            )
+        }
+        _ => {
+            return Err(EngramError::Validation(
+                "Give a second entity ID or --versions, not both".to_string(),
+            ))
+        }
+    };
+    Ok(EntityComparison {
+        old_label,
+        new_label,
+        diff: diff_entities(&old, &new),
+    })
+}
+
+/// Handle `engram entity`
+pub fn handle_entity_command(
+    storage: &mut dyn Storage,
+    command: EntityCommands,
+    json: bool,
+) -> Result<(), EngramError> {
+    match command {
+        EntityCommands::Patch {
+            id,
+            entity_type,
+            patch,
+        } => {
+            let ops: Vec<JsonPatchOp> = serde_json::from_str(&patch).map_err(|e| {
+                EngramError::Validation(format!("Invalid JSON Patch document: {}", e))
+            })?;
+            let entity_type = match entity_type {
+                Some(entity_type) => entity_type,
+                None => find_entity_by_id(storage, &id)?.entity_type,
+            };
+            let op_count = ops.len();
+            storage.patch(&id, &entity_type, ops)?;
+
+            if json {
+                let patched = storage.get(&id, &entity_type)?;
+                println!("{}", serde_json::to_string_pretty(&patched)?);
+            } else {
+                println!(
+                    "✅ Patched {} {} ({} operation(s))",
+                    entity_type, id, op_count
+                );
+            }
+        }
+        EntityCommands::Diff {
+            entity_type,
+            id_a,
+            id_b,
+            versions,
+        } => {
+            let comparison = compare_entities(
+                storage,
+                &entity_type,
+                &id_a,
+                id_b.as_deref(),
+                versions.as_deref(),
+            )?;
+            if json {
+                let output = serde_json::json!({
+                    "old": comparison.old_label,
+                    "new": comparison.new_label,
+                    "diff": comparison.diff,
+                });
+                println!("{}", serde_json::to_string_pretty(&output)?);
+            } else {
+                print!(
+                    "{}",
+                    render_labeled_diff(
+                        &comparison.old_label,
+                        &comparison.new_label,
+                        &comparison.diff,
+                        std::io::stdout().is_terminal(),
+                    )
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{Entity, ListChange, Task, TaskPriority};
+    use serde_json::json;
+
+    fn task(title: &str) -> Task {
+        Task::new(
+            title.to_string(),
+            String::new(),
+            "default".to_string(),
+            TaskPriority::Medium,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_compare_entities_and_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut storage = GitRefsStorage::new(dir.path().to_str().unwrap(), "default").unwrap();
+        let mut first = task("Ship");
+        first.tags = vec!["api".to_string()];
+        storage.store(&first.to_generic()).unwrap();
+        first.tags.push("urgent".to_string());
+        first.title = "Ship it".to_string();
+        storage.store(&first.to_generic()).unwrap();
+        let second = task("Other");
+        storage.store(&second.to_generic()).unwrap();
+
+        let short = &first.id[..8];
+        let versions = compare_entities(&storage, "task", &first.id, None, Some("1..2")).unwrap();
+        assert_eq!(versions.old_label, format!("task {} v1", short));
+        assert_eq!(versions.new_label, format!("task {} v2", short));
+        assert_eq!(
+            versions.diff.list_changes,
+            vec![(
+                "tags".to_string(),
+                ListChange {
+                    added: vec![json!("urgent")],
+                    removed: vec![],
+                }
+            )]
+        );
+        assert!(versions
+            .diff
+            .modified_fields
+            .iter()
+            .any(|(field, change)| field == "title" && change.new == json!("Ship it")));
+
+        let entities =
+            compare_entities(&storage, "task", &first.id, Some(&second.id), None).unwrap();
+        assert_eq!(entities.new_label, format!("task {}", &second.id[..8]));
+        assert!(!entities.diff.is_empty());
+
+        assert!(matches!(
+            compare_entities(&storage, "task", &first.id, None, Some("1..9")),
+            Err(EngramError::NotFound { .. })
+        ));
+        assert!(compare_entities(&storage, "task", &first.id, None, Some("2-1")).is_err());
+    }
+}
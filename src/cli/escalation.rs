@@ -1,16 +1,19 @@
 //! Escalation command implementations
 
+use crate::cli::identity::resolve_agent;
 use crate::entities::{
-    Entity, EscalationOperationType, EscalationPriority, EscalationRequest, EscalationStatus,
-    OperationContext, ReviewDecision, ReviewerInfo,
+    Entity, EscalationOperationType, EscalationPriority, EscalationRequest, EscalationStatistics,
+    EscalationStatus, OperationContext, ReviewDecision, ReviewerInfo,
 };
 use crate::error::EngramError;
-use crate::storage::Storage;
+use crate::notify::{self, NotificationEvent};
+use crate::storage::{QueryFilter, SortOrder, Storage, TimeRange};
 use clap::Subcommand;
-use serde::Deserialize;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::io::{self, Read, Write};
+use std::path::Path;
 
 /// Escalation input structure for JSON
 #[derive(Debug, Deserialize)]
@@ -68,6 +71,14 @@ pub enum EscalationCommands {
         #[arg(long)]
         reviewer: Option<String>,
 
+        /// Notify reviewers through the configured notification sinks (default)
+        #[arg(long, overrides_with = "no_notify")]
+        notify: bool,
+
+        /// Don't send notifications for this escalation
+        #[arg(long, overrides_with = "notify")]
+        no_notify: bool,
+
         #[arg(long, conflicts_with_all = ["agent"])]
         stdin: bool,
 
@@ -110,6 +121,9 @@ pub enum EscalationCommands {
         /// Output in JSON format
         #[arg(long)]
         json: bool,
+
+        #[command(flatten)]
+        sort: SortArgs,
     },
     /// Get escalation request details
     Get {
@@ -243,6 +257,30 @@ pub enum EscalationCommands {
         #[arg(long)]
         json: bool,
     },
+    /// Write pending escalations to an editable YAML file for batch review
+    ExportPending {
+        /// Output file
+        #[arg(long, default_value = "pending.yaml")]
+        out: String,
+    },
+    /// Apply the decisions recorded in an exported YAML file
+    Apply {
+        /// YAML file produced by export-pending
+        #[arg()]
+        file: String,
+
+        /// Reviewer ID (defaults to the current agent)
+        #[arg(long)]
+        reviewer_id: Option<String>,
+
+        /// Reviewer name (defaults to the reviewer ID)
+        #[arg(long)]
+        reviewer_name: Option<String>,
+
+        /// Output in JSON format
+        #[arg(long)]
+        json: bool,
+    },
     /// Show escalation statistics
     Stats {
         /// Agent ID to show stats for
@@ -259,6 +297,23 @@ pub enum EscalationCommands {
     },
 }
 
+/// Tell reviewers about a new escalation through the notification sinks.
+/// Critical escalations are also recorded as workspace alerts. Failures are
+/// logged and reported, never returned.
+fn notify_escalation_created(escalation: &EscalationRequest) {
+    let event = NotificationEvent::escalation_created(escalation);
+    let report = notify::dispatch(&event);
+    for (sink, error) in &report.failures {
+        eprintln!("⚠️  Failed to notify via {}: {}", sink, error);
+    }
+    if escalation.priority == EscalationPriority::Critical {
+        if let Err(e) = notify::record_alert(std::path::Path::new("."), &event) {
+            tracing::warn!(error = %e, "Failed to record critical escalation alert");
+            eprintln!("⚠️  Failed to record critical alert: {}", e);
+        }
+    }
+}
+
 /// Create a new escalation request
 pub fn create_escalation<S: Storage>(
     storage: &mut S,
@@ -273,15 +328,14 @@ pub fn create_escalation<S: Storage>(
     stdin: bool,
     file: Option<String>,
     json: bool,
+    notify: bool,
 ) -> Result<(), EngramError> {
     let escalation_input = if stdin {
         read_escalation_input_from_stdin()?
     } else if let Some(file_path) = file {
         read_escalation_input_from_file(&file_path)?
     } else {
-        let agent_id = agent
-            .clone()
-            .ok_or_else(|| EngramError::Validation("Agent is required".to_string()))?;
+        let agent_id = resolve_agent(agent.clone());
         let operation_type = operation_type
             .ok_or_else(|| EngramError::Validation("Operation type is required".to_string()))?;
         let operation = operation
@@ -310,7 +364,7 @@ pub fn create_escalation<S: Storage>(
             .priority
             .unwrap_or_else(|| "normal".to_string()),
     )?;
-    let agent = agent.unwrap_or_else(|| "default".to_string());
+    let agent = resolve_agent(agent);
 
     let operation_context = OperationContext {
         operation: escalation_input.operation,
@@ -339,6 +393,9 @@ pub fn create_escalation<S: Storage>(
     }
 
     storage.store(&escalation.to_generic())?;
+    if notify {
+        notify_escalation_created(&escalation);
+    }
 
     if json {
         println!(
@@ -363,10 +420,11 @@ pub fn create_escalation<S: Storage>(
     Ok(())
 }
 
-use crate::cli::utils::{create_table, truncate};
+use crate::cli::utils::{create_table, truncate, SortArgs};
 use prettytable::row;
 
 /// List escalation requests
+#[allow(clippy::too_many_arguments)]
 pub fn list_escalations<S: Storage>(
     storage: &S,
     agent_id: Option<String>,
@@ -377,6 +435,7 @@ pub fn list_escalations<S: Storage>(
     actionable_only: bool,
     agent: Option<String>,
     json: bool,
+    sort: &SortArgs,
 ) -> Result<(), EngramError> {
     let ids = storage.list_ids("escalation_request")?;
     let mut escalations = Vec::new();
@@ -444,6 +503,8 @@ pub fn list_escalations<S: Storage>(
         }
     }
 
+    escalations = sort.sort(escalations)?;
+
     if json {
         let generic_escalations: Vec<_> = escalations.iter().map(|e| e.to_generic()).collect();
         println!("{}", serde_json::to_string_pretty(&generic_escalations)?);
@@ -583,10 +644,7 @@ pub fn get_escalation<S: Storage>(storage: &S, id: String, json: bool) -> Result
             }
         }
         None => {
-            return Err(EngramError::NotFound(format!(
-                "Escalation request with ID {} not found",
-                id
-            )));
+            return Err(EngramError::not_found("escalation_request", id));
         }
     }
 
@@ -608,24 +666,7 @@ pub fn review_escalation<S: Storage>(
     file: Option<String>,
     json: bool,
 ) -> Result<(), EngramError> {
-    let mut escalation = match storage.get(&id, "escalation_request")? {
-        Some(entity) => EscalationRequest::from_generic(entity)
-            .map_err(|e| EngramError::Validation(e.to_string()))?,
-        None => {
-            return Err(EngramError::NotFound(format!(
-                "Escalation request with ID {} not found",
-                id
-            )));
-        }
-    };
-
-    // Check if request is still actionable
-    if !escalation.is_actionable() {
-        return Err(EngramError::InvalidOperation(format!(
-            "Escalation request {} is not actionable (status: {:?})",
-            id, escalation.status
-        )));
-    }
+    let escalation = load_actionable_escalation(storage, &id)?;
 
     let review_input = if stdin {
         read_review_input_from_stdin()?
@@ -654,6 +695,61 @@ pub fn review_escalation<S: Storage>(
         }
     };
 
+    let escalation = record_review(storage, escalation, review_input)?;
+    let decision_status = escalation.status.clone();
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&escalation.to_generic())?
+        );
+    } else {
+        println!("✅ Escalation request reviewed successfully:");
+        println!("  ID: {}", escalation.id);
+        println!("  Decision: {:?}", decision_status);
+        println!(
+            "  Reviewer: {}",
+            escalation.reviewer.as_ref().unwrap().reviewer_name
+        );
+
+        if let Some(duration) = escalation.decision.as_ref().unwrap().approval_duration {
+            println!("  Valid for: {} seconds", duration);
+        }
+    }
+
+    Ok(())
+}
+
+/// Load an escalation request that is still pending and unexpired
+fn load_actionable_escalation<S: Storage>(
+    storage: &S,
+    id: &str,
+) -> Result<EscalationRequest, EngramError> {
+    let escalation = match storage.get(id, "escalation_request")? {
+        Some(entity) => EscalationRequest::from_generic(entity)
+            .map_err(|e| EngramError::Validation(e.to_string()))?,
+        None => {
+            return Err(EngramError::not_found("escalation_request", id));
+        }
+    };
+
+    // Check if request is still actionable
+    if !escalation.is_actionable() {
+        return Err(EngramError::InvalidOperation(format!(
+            "Escalation request {} is not actionable (status: {:?})",
+            id, escalation.status
+        )));
+    }
+
+    Ok(escalation)
+}
+
+/// Record a reviewer's decision on an actionable escalation and store it
+fn record_review<S: Storage>(
+    storage: &mut S,
+    mut escalation: EscalationRequest,
+    review_input: ReviewInput,
+) -> Result<EscalationRequest, EngramError> {
     let decision_status = parse_escalation_status(&review_input.status)?;
 
     // Create reviewer info
@@ -666,7 +762,7 @@ pub fn review_escalation<S: Storage>(
 
     // Create decision
     let decision = ReviewDecision {
-        status: decision_status.clone(),
+        status: decision_status,
         reason: review_input.reason,
         conditions: review_input.conditions.unwrap_or_default(),
         approval_duration: review_input.approval_duration,
@@ -679,26 +775,253 @@ pub fn review_escalation<S: Storage>(
     escalation.record_decision(decision);
 
     storage.store(&escalation.to_generic())?;
+    Ok(escalation)
+}
 
-    if json {
-        println!(
-            "{}",
-            serde_json::to_string_pretty(&escalation.to_generic())?
-        );
-    } else {
-        println!("✅ Escalation request reviewed successfully:");
-        println!("  ID: {}", escalation.id);
-        println!("  Decision: {:?}", decision_status);
-        println!(
-            "  Reviewer: {}",
-            escalation.reviewer.as_ref().unwrap().reviewer_name
-        );
+/// Decision a reviewer writes against an exported escalation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BatchDecision {
+    Approve,
+    Deny,
+    Skip,
+}
 
-        if let Some(duration) = escalation.decision.as_ref().unwrap().approval_duration {
-            println!("  Valid for: {} seconds", duration);
+/// One pending escalation in a batch review file. Everything above
+/// `decision` is informational; only the decision fields are read back.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchReviewItem {
+    pub id: String,
+    #[serde(default)]
+    pub agent_id: String,
+    #[serde(default)]
+    pub operation_type: String,
+    #[serde(default)]
+    pub operation: String,
+    #[serde(default)]
+    pub priority: String,
+    #[serde(default)]
+    pub justification: String,
+    #[serde(default)]
+    pub block_reason: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub risk: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub impact_if_denied: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<String>,
+    pub decision: BatchDecision,
+    #[serde(default)]
+    pub reason: Option<String>,
+    /// Approval duration in seconds
+    #[serde(default)]
+    pub duration: Option<u64>,
+}
+
+/// File written by `escalation export-pending` and read by `escalation apply`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchReviewFile {
+    pub escalations: Vec<BatchReviewItem>,
+}
+
+/// Outcome of one item in an applied batch review
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchReviewResult {
+    pub id: String,
+    pub decision: BatchDecision,
+    pub outcome: String,
+}
+
+const BATCH_REVIEW_HEADER: &str = "\
+# Pending escalations for batch review.
+# Set `decision` to approve, deny or skip for each entry; `reason` and
+# `duration` (seconds, approvals only) are optional. Apply with:
+#   engram escalation apply <this file>
+";
+
+/// Write every actionable escalation to `out`, highest priority first.
+/// Returns the number exported.
+pub fn export_pending_escalations<S: Storage>(
+    storage: &S,
+    out: &Path,
+) -> Result<usize, EngramError> {
+    let mut pending: Vec<EscalationRequest> = storage
+        .get_all("escalation_request")?
+        .into_iter()
+        .filter_map(|entity| EscalationRequest::from_generic(entity).ok())
+        .filter(EscalationRequest::is_actionable)
+        .collect();
+    pending.sort_by(|a, b| {
+        priority_rank(&b.priority)
+            .cmp(&priority_rank(&a.priority))
+            .then(a.created_at.cmp(&b.created_at))
+    });
+
+    let file = BatchReviewFile {
+        escalations: pending
+            .into_iter()
+            .map(|escalation| BatchReviewItem {
+                id: escalation.id,
+                agent_id: escalation.agent_id,
+                operation_type: format!("{:?}", escalation.operation_type),
+                operation: escalation.operation_context.operation,
+                priority: format!("{:?}", escalation.priority),
+                justification: escalation.justification,
+                block_reason: escalation.operation_context.block_reason,
+                risk: escalation.operation_context.risk_assessment,
+                impact_if_denied: escalation.impact_if_denied,
+                expires_at: Some(escalation.expires_at.to_rfc3339()),
+                decision: BatchDecision::Skip,
+                reason: None,
+                duration: None,
+            })
+            .collect(),
+    };
+
+    let yaml = serde_yaml::to_string(&file)?;
+    fs::write(out, format!("{}{}", BATCH_REVIEW_HEADER, yaml))?;
+    Ok(file.escalations.len())
+}
+
+fn priority_rank(priority: &EscalationPriority) -> u8 {
+    match priority {
+        EscalationPriority::Low => 0,
+        EscalationPriority::Normal => 1,
+        EscalationPriority::High => 2,
+        EscalationPriority::Critical => 3,
+    }
+}
+
+/// Check every decision in a batch file before any is applied, collecting
+/// all problems into one error
+fn validate_batch<S: Storage>(storage: &S, file: &BatchReviewFile) -> Result<(), EngramError> {
+    let mut problems = Vec::new();
+    let mut seen = HashSet::new();
+    for item in &file.escalations {
+        if !seen.insert(item.id.as_str()) {
+            problems.push(format!("{}: listed more than once", item.id));
+            continue;
+        }
+        if item.decision == BatchDecision::Skip {
+            continue;
+        }
+        if item.duration.is_some() && item.decision != BatchDecision::Approve {
+            problems.push(format!("{}: duration is only valid for approvals", item.id));
+        }
+        if item.duration == Some(0) {
+            problems.push(format!("{}: duration must be positive", item.id));
+        }
+        if let Err(e) = load_actionable_escalation(storage, &item.id) {
+            problems.push(format!("{}: {}", item.id, e));
         }
     }
 
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(EngramError::Validation(format!(
+            "No decisions were applied; fix these entries first:\n  {}",
+            problems.join("\n  ")
+        )))
+    }
+}
+
+/// Validate and apply all decisions in a batch review file. Skipped entries
+/// are left untouched.
+pub fn apply_escalation_decisions<S: Storage>(
+    storage: &mut S,
+    path: &Path,
+    reviewer_id: &str,
+    reviewer_name: &str,
+) -> Result<Vec<BatchReviewResult>, EngramError> {
+    let content = fs::read_to_string(path)?;
+    let file: BatchReviewFile = serde_yaml::from_str(&content).map_err(|e| {
+        EngramError::Validation(format!(
+            "No decisions were applied; {} is not a valid batch review file: {}",
+            path.display(),
+            e
+        ))
+    })?;
+    validate_batch(storage, &file)?;
+
+    let mut results = Vec::with_capacity(file.escalations.len());
+    for item in file.escalations {
+        let (status, default_reason) = match item.decision {
+            BatchDecision::Skip => {
+                results.push(BatchReviewResult {
+                    id: item.id,
+                    decision: item.decision,
+                    outcome: "skipped".to_string(),
+                });
+                continue;
+            }
+            BatchDecision::Approve => ("approved", "Approved via batch review"),
+            BatchDecision::Deny => ("denied", "Denied via batch review"),
+        };
+        let review_input = ReviewInput {
+            status: status.to_string(),
+            reason: item.reason.unwrap_or_else(|| default_reason.to_string()),
+            conditions: None,
+            approval_duration: item.duration,
+            create_policy: None,
+            notes: None,
+            reviewer_id: reviewer_id.to_string(),
+            reviewer_name: reviewer_name.to_string(),
+            reviewer_email: None,
+        };
+        let outcome = load_actionable_escalation(storage, &item.id)
+            .and_then(|escalation| record_review(storage, escalation, review_input));
+        results.push(BatchReviewResult {
+            id: item.id,
+            decision: item.decision,
+            outcome: match outcome {
+                Ok(_) => status.to_string(),
+                Err(e) => format!("failed: {}", e),
+            },
+        });
+    }
+
+    Ok(results)
+}
+
+/// Apply a batch review file and report the result of each entry
+pub fn apply_escalation_file<S: Storage>(
+    storage: &mut S,
+    path: &Path,
+    reviewer_id: Option<String>,
+    reviewer_name: Option<String>,
+    json: bool,
+) -> Result<(), EngramError> {
+    let reviewer_id = resolve_agent(reviewer_id);
+    let reviewer_name = reviewer_name.unwrap_or_else(|| reviewer_id.clone());
+    let results = apply_escalation_decisions(storage, path, &reviewer_id, &reviewer_name)?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&results)?);
+        return Ok(());
+    }
+
+    for result in &results {
+        let icon = match result.outcome.as_str() {
+            "approved" => "✅",
+            "denied" => "🚫",
+            "skipped" => "⏭️",
+            _ => "❌",
+        };
+        println!("  {} {} {}", icon, result.id, result.outcome);
+    }
+    let applied = results
+        .iter()
+        .filter(|r| r.outcome == "approved" || r.outcome == "denied")
+        .count();
+    println!(
+        "Applied {} decision(s), skipped {}",
+        applied,
+        results
+            .iter()
+            .filter(|r| r.decision == BatchDecision::Skip)
+            .count()
+    );
     Ok(())
 }
 
@@ -728,10 +1051,7 @@ pub fn cancel_escalation<S: Storage>(
         Some(entity) => EscalationRequest::from_generic(entity)
             .map_err(|e| EngramError::Validation(e.to_string()))?,
         None => {
-            return Err(EngramError::NotFound(format!(
-                "Escalation request with ID {} not found",
-                id
-            )));
+            return Err(EngramError::not_found("escalation_request", id));
         }
     };
 
@@ -769,6 +1089,7 @@ pub fn cleanup_escalations<S: Storage>(
                     if apply {
                         escalation.mark_expired();
                         storage.store(&escalation.to_generic())?;
+                        notify::dispatch(&NotificationEvent::sla_breached(&escalation));
                         updated_count += 1;
                     }
                 }
@@ -820,6 +1141,83 @@ pub fn cleanup_escalations<S: Storage>(
     Ok(())
 }
 
+/// Page size used when streaming escalations for statistics
+const STATS_PAGE_SIZE: usize = 100;
+
+/// Aggregate escalations created in the last `days` days as of `now`
+///
+/// Escalations are fetched a page at a time through `Storage::query`. The
+/// entity timestamp is the last update, which is never earlier than the
+/// creation time, so the time range narrows the scan and `created_at` is
+/// checked afterwards.
+pub fn collect_escalation_stats<S: Storage>(
+    storage: &S,
+    agent_id: Option<&str>,
+    days: u64,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Result<EscalationStatistics, EngramError> {
+    let cutoff = now - chrono::Duration::days(days as i64);
+    let mut filter = QueryFilter {
+        entity_type: Some(EscalationRequest::entity_type().to_string()),
+        time_range: Some(TimeRange {
+            start: cutoff,
+            end: now,
+        }),
+        sort_by: Some("created_at".to_string()),
+        sort_order: SortOrder::Asc,
+        limit: Some(STATS_PAGE_SIZE),
+        offset: Some(0),
+        ..Default::default()
+    };
+    if let Some(agent_id) = agent_id {
+        filter
+            .field_filters
+            .insert("agent_id".to_string(), serde_json::json!(agent_id));
+    }
+
+    let mut error = None;
+    let mut done = false;
+    let pages = std::iter::from_fn(|| {
+        if done {
+            return None;
+        }
+        match storage.query(&filter) {
+            Ok(page) => {
+                done = !page.has_more || page.entities.is_empty();
+                filter.offset = Some(filter.offset.unwrap_or(0) + page.entities.len());
+                Some(page.entities)
+            }
+            Err(e) => {
+                error = Some(e);
+                None
+            }
+        }
+    });
+    let escalations = pages
+        .flatten()
+        .filter_map(|entity| EscalationRequest::from_generic(entity).ok())
+        .filter(|escalation| escalation.created_at >= cutoff);
+
+    let stats = EscalationStatistics::from_escalations(escalations, now);
+    match error {
+        Some(e) => Err(e),
+        None => Ok(stats),
+    }
+}
+
+fn format_seconds(seconds: Option<u64>) -> String {
+    match seconds {
+        Some(s) if s >= 3600 => format!("{:.1}h", s as f64 / 3600.0),
+        Some(s) if s >= 60 => format!("{:.1}m", s as f64 / 60.0),
+        Some(s) => format!("{}s", s),
+        None => "—".to_string(),
+    }
+}
+
+fn format_rate(rate: Option<f64>) -> String {
+    rate.map_or_else(|| "—".to_string(), |r| format!("{:.0}%", r * 100.0))
+}
+
 /// Show escalation statistics
 pub fn show_escalation_stats<S: Storage>(
     storage: &S,
@@ -827,103 +1225,90 @@ pub fn show_escalation_stats<S: Storage>(
     days: u64,
     json: bool,
 ) -> Result<(), EngramError> {
-    let ids = storage.list_ids("escalation_request")?;
-    let cutoff_date = chrono::Utc::now() - chrono::Duration::days(days as i64);
-
-    let mut total_requests = 0;
-    let mut status_counts = HashMap::new();
-    let mut priority_counts = HashMap::new();
-    let mut operation_type_counts = HashMap::new();
-    let mut agent_requests = Vec::new();
+    let stats = collect_escalation_stats(storage, agent_id.as_deref(), days, chrono::Utc::now())?;
 
-    for id in ids {
-        if let Ok(Some(entity)) = storage.get(&id, "escalation_request") {
-            if let Ok(escalation) = EscalationRequest::from_generic(entity) {
-                // Skip requests outside the time window
-                if escalation.created_at < cutoff_date {
-                    continue;
-                }
-
-                // Apply agent filter
-                if let Some(filter_agent_id) = &agent_id {
-                    if escalation.agent_id != *filter_agent_id {
-                        continue;
-                    }
-                    agent_requests.push(escalation.clone());
-                } else {
-                    total_requests += 1;
-                    *status_counts
-                        .entry(format!("{:?}", escalation.status))
-                        .or_insert(0) += 1;
-                    *priority_counts
-                        .entry(format!("{:?}", escalation.priority))
-                        .or_insert(0) += 1;
-                    *operation_type_counts
-                        .entry(format!("{:?}", escalation.operation_type))
-                        .or_insert(0) += 1;
-                    agent_requests.push(escalation);
-                }
-            }
+    if json {
+        let mut value = serde_json::to_value(&stats)?;
+        if let Some(object) = value.as_object_mut() {
+            object.insert("time_period_days".to_string(), serde_json::json!(days));
+            object.insert("agent_filter".to_string(), serde_json::json!(agent_id));
         }
+        println!("{}", serde_json::to_string_pretty(&value)?);
+        return Ok(());
     }
 
-    if json {
-        let stats = serde_json::json!({
-            "time_period_days": days,
-            "total_requests": if agent_id.is_some() { agent_requests.len() } else { total_requests },
-            "status_distribution": status_counts,
-            "priority_distribution": priority_counts,
-            "operation_type_distribution": operation_type_counts,
-            "agent_filter": agent_id,
-            "requests": agent_requests.iter().map(|e| serde_json::json!({
-                "id": e.id,
-                "agent_id": e.agent_id,
-                "status": format!("{:?}", e.status),
-                "priority": format!("{:?}", e.priority),
-                "operation_type": format!("{:?}", e.operation_type),
-                "created_at": e.created_at
-            })).collect::<Vec<_>>()
-        });
-        println!("{}", serde_json::to_string_pretty(&stats)?);
-    } else {
-        if let Some(filter_agent_id) = agent_id {
-            println!(
-                "🚨 Escalation Stats for Agent: {} (last {} days)",
-                filter_agent_id, days
-            );
-            if agent_requests.is_empty() {
-                println!("  No escalation requests found for this agent.");
-            } else {
-                println!("  Total requests: {}", agent_requests.len());
-                for request in agent_requests {
-                    println!(
-                        "  • {} - {:?} ({:?}, {:?})",
-                        request.operation_context.operation,
-                        request.status,
-                        request.priority,
-                        request.operation_type
-                    );
-                }
-            }
-        } else {
-            println!("🚨 Escalation Statistics (last {} days):", days);
-            println!("  Total requests: {}", total_requests);
+    match &agent_id {
+        Some(agent_id) => println!(
+            "🚨 Escalation Stats for Agent: {} (last {} days)",
+            agent_id, days
+        ),
+        None => println!("🚨 Escalation Statistics (last {} days):", days),
+    }
+    if stats.total_requests == 0 {
+        println!("  No escalation requests found.");
+        return Ok(());
+    }
 
-            println!("  Status distribution:");
-            for (status, count) in status_counts {
-                println!("    {}: {}", status, count);
-            }
+    println!("  Total requests: {}", stats.total_requests);
+    println!(
+        "  Pending: {}  Approved: {}  Denied: {}  Expired: {}  Cancelled: {}",
+        stats.pending_count,
+        stats.approved_count,
+        stats.denied_count,
+        stats.expired_count,
+        stats.cancelled_count
+    );
+    println!(
+        "  Time to review: median {}, p90 {} ({} reviewed)",
+        format_seconds(stats.median_review_seconds),
+        format_seconds(stats.p90_review_seconds),
+        stats.reviewed_count
+    );
+    println!("  Approval rate: {}", format_rate(stats.approval_rate));
+    println!(
+        "  Auto-timeout rate: {}",
+        format_rate(stats.auto_timeout_rate)
+    );
 
-            println!("  Priority distribution:");
-            for (priority, count) in priority_counts {
-                println!("    {}: {}", priority, count);
-            }
+    if !stats.by_operation_type.is_empty() {
+        println!();
+        println!("  By operation type:");
+        let mut table = create_table();
+        table.set_titles(row![
+            "Operation Type",
+            "Approved",
+            "Denied",
+            "Approval Rate"
+        ]);
+        for (op_type, op) in &stats.by_operation_type {
+            table.add_row(row![
+                op_type,
+                op.approved,
+                op.denied,
+                format_rate(op.approval_rate)
+            ]);
+        }
+        table.printstd();
+    }
 
-            println!("  Operation type distribution:");
-            for (op_type, count) in operation_type_counts {
-                println!("    {}: {}", op_type, count);
-            }
+    if !stats.by_reviewer.is_empty() {
+        println!();
+        println!("  By reviewer:");
+        let mut table = create_table();
+        table.set_titles(row![
+            "Reviewer", "Reviewed", "Approved", "Denied", "Median", "P90"
+        ]);
+        for (reviewer_id, reviewer) in &stats.by_reviewer {
+            table.add_row(row![
+                truncate(&format!("{} ({})", reviewer.reviewer_name, reviewer_id), 30),
+                reviewer.reviewed,
+                reviewer.approved,
+                reviewer.denied,
+                format_seconds(reviewer.median_review_seconds),
+                format_seconds(reviewer.p90_review_seconds)
+            ]);
         }
+        table.printstd();
     }
 
     Ok(())
@@ -943,10 +1328,7 @@ pub fn approve_escalation<S: Storage>(
         Some(entity) => EscalationRequest::from_generic(entity)
             .map_err(|e| EngramError::Validation(e.to_string()))?,
         None => {
-            return Err(EngramError::NotFound(format!(
-                "Escalation request with ID {} not found",
-                id
-            )));
+            return Err(EngramError::not_found("escalation_request", id));
         }
     };
 
@@ -1006,10 +1388,7 @@ pub fn deny_escalation<S: Storage>(
         Some(entity) => EscalationRequest::from_generic(entity)
             .map_err(|e| EngramError::Validation(e.to_string()))?,
         None => {
-            return Err(EngramError::NotFound(format!(
-                "Escalation request with ID {} not found",
-                id
-            )));
+            return Err(EngramError::not_found("escalation_request", id));
         }
     };
 
@@ -1165,6 +1544,7 @@ mod tests {
             false,
             None,
             false,
+            false,
         );
 
         assert!(result.is_ok());
@@ -1195,6 +1575,7 @@ mod tests {
             false,
             None,
             false,
+            false,
         )
         .unwrap();
 
@@ -1227,6 +1608,7 @@ mod tests {
             false,
             None,
             false,
+            false,
         )
         .unwrap();
 
@@ -1274,6 +1656,7 @@ mod tests {
             false,
             None,
             false,
+            false,
         )
         .unwrap();
 
@@ -1314,6 +1697,7 @@ mod tests {
             false,
             None,
             false,
+            false,
         )
         .unwrap();
 
@@ -1327,6 +1711,7 @@ mod tests {
             false,
             None,
             false,
+            &SortArgs::default(),
         );
         assert!(result.is_ok());
     }
@@ -1375,7 +1760,7 @@ mod tests {
         );
         assert!(result.is_err());
         match result {
-            Err(EngramError::NotFound(_)) => {}
+            Err(EngramError::NotFound { .. }) => {}
             _ => panic!("Expected NotFound error"),
         }
     }
@@ -1397,6 +1782,7 @@ mod tests {
             false,
             None,
             false,
+            false,
         )
         .unwrap();
 
@@ -1451,4 +1837,166 @@ mod tests {
         let result = cancel_escalation(&mut storage, "non-existent".to_string(), None, true, false);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_collect_escalation_stats_pages_through_window() {
+        let mut storage = MemoryStorage::new("test-agent");
+        let now = chrono::Utc::now();
+        let escalation = |agent_id: &str, age_days: i64| {
+            let mut request = EscalationRequest::new(
+                agent_id.to_string(),
+                EscalationOperationType::NetworkAccess,
+                OperationContext {
+                    operation: "curl".to_string(),
+                    parameters: HashMap::new(),
+                    resource: None,
+                    block_reason: "Network access restricted".to_string(),
+                    alternatives: vec![],
+                    risk_assessment: None,
+                },
+                "Need to fetch data".to_string(),
+                EscalationPriority::Normal,
+                "test-agent".to_string(),
+            );
+            request.created_at = now - chrono::Duration::days(age_days);
+            request.updated_at = request.created_at;
+            request.expires_at = now + chrono::Duration::days(1);
+            request
+        };
+
+        for _ in 0..STATS_PAGE_SIZE + 5 {
+            storage
+                .store(&escalation("agent-1", 1).to_generic())
+                .unwrap();
+        }
+        storage
+            .store(&escalation("agent-1", 40).to_generic())
+            .unwrap();
+        storage
+            .store(&escalation("agent-2", 1).to_generic())
+            .unwrap();
+
+        // Created before the window but updated inside it
+        let mut reviewed = escalation("agent-1", 40);
+        reviewed.status = EscalationStatus::Approved;
+        reviewed.updated_at = now - chrono::Duration::days(1);
+        reviewed.reviewed_at = Some(reviewed.updated_at);
+        storage.store(&reviewed.to_generic()).unwrap();
+
+        let stats = collect_escalation_stats(&storage, Some("agent-1"), 30, now).unwrap();
+        assert_eq!(stats.total_requests, STATS_PAGE_SIZE + 5);
+        assert_eq!(stats.pending_count, STATS_PAGE_SIZE + 5);
+        assert_eq!(stats.approved_count, 0);
+        assert_eq!(stats.median_review_seconds, None);
+
+        let stats = collect_escalation_stats(&storage, None, 30, now).unwrap();
+        assert_eq!(stats.total_requests, STATS_PAGE_SIZE + 6);
+    }
+
+    fn pending_escalation(operation: &str, priority: EscalationPriority) -> EscalationRequest {
+        EscalationRequest::new(
+            "agent-1".to_string(),
+            EscalationOperationType::CommandExecution,
+            OperationContext {
+                operation: operation.to_string(),
+                parameters: HashMap::new(),
+                resource: None,
+                block_reason: "Command not allowed".to_string(),
+                alternatives: vec![],
+                risk_assessment: Some("low".to_string()),
+            },
+            "Needed for the build".to_string(),
+            priority,
+            "test-agent".to_string(),
+        )
+    }
+
+    fn stored_status(storage: &MemoryStorage, id: &str) -> EscalationStatus {
+        EscalationRequest::from_generic(storage.get(id, "escalation_request").unwrap().unwrap())
+            .unwrap()
+            .status
+    }
+
+    #[test]
+    fn test_export_and_apply_batch_review() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pending.yaml");
+        let mut storage = MemoryStorage::new("test-agent");
+        let approve = pending_escalation("make deploy", EscalationPriority::Normal);
+        let deny = pending_escalation("rm -rf build", EscalationPriority::Critical);
+        let skip = pending_escalation("cargo publish", EscalationPriority::Low);
+        for escalation in [&approve, &deny, &skip] {
+            storage.store(&escalation.to_generic()).unwrap();
+        }
+
+        assert_eq!(export_pending_escalations(&storage, &path).unwrap(), 3);
+        let mut file: BatchReviewFile =
+            serde_yaml::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(file.escalations[0].id, deny.id);
+        assert!(file
+            .escalations
+            .iter()
+            .all(|item| item.decision == BatchDecision::Skip));
+
+        for item in &mut file.escalations {
+            if item.id == approve.id {
+                item.decision = BatchDecision::Approve;
+                item.duration = Some(3600);
+            } else if item.id == deny.id {
+                item.decision = BatchDecision::Deny;
+                item.reason = Some("too destructive".to_string());
+            }
+        }
+        fs::write(&path, serde_yaml::to_string(&file).unwrap()).unwrap();
+
+        let results = apply_escalation_decisions(&mut storage, &path, "lead", "Lead").unwrap();
+        assert_eq!(results.len(), 3);
+        assert!(results.iter().all(|r| !r.outcome.starts_with("failed")));
+        assert_eq!(
+            stored_status(&storage, &approve.id),
+            EscalationStatus::Approved
+        );
+        assert_eq!(stored_status(&storage, &deny.id), EscalationStatus::Denied);
+        assert_eq!(stored_status(&storage, &skip.id), EscalationStatus::Pending);
+    }
+
+    #[test]
+    fn test_apply_batch_rejects_malformed_decisions_before_applying() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pending.yaml");
+        let mut storage = MemoryStorage::new("test-agent");
+        let first = pending_escalation("make deploy", EscalationPriority::Normal);
+        let second = pending_escalation("rm -rf build", EscalationPriority::Normal);
+        storage.store(&first.to_generic()).unwrap();
+        storage.store(&second.to_generic()).unwrap();
+
+        // A valid approval next to a denial carrying a duration
+        fs::write(
+            &path,
+            format!(
+                "escalations:\n- id: {}\n  decision: approve\n- id: {}\n  decision: deny\n  duration: 60\n",
+                first.id, second.id
+            ),
+        )
+        .unwrap();
+        let result = apply_escalation_decisions(&mut storage, &path, "lead", "Lead");
+        assert!(matches!(result, Err(EngramError::Validation(_))));
+        assert_eq!(
+            stored_status(&storage, &first.id),
+            EscalationStatus::Pending
+        );
+
+        // An unknown decision value fails to parse
+        fs::write(
+            &path,
+            format!("escalations:\n- id: {}\n  decision: maybe\n", first.id),
+        )
+        .unwrap();
+        let result = apply_escalation_decisions(&mut storage, &path, "lead", "Lead");
+        assert!(matches!(result, Err(EngramError::Validation(_))));
+        assert_eq!(
+            stored_status(&storage, &first.id),
+            EscalationStatus::Pending
+        );
+    }
 }
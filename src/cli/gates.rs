@@ -0,0 +1,342 @@
+//! Quality gate command implementations
+
+use crate::cli::identity::resolve_agent;
+use crate::entities::{Entity, Task};
+use crate::error::EngramError;
+use crate::storage::Storage;
+use crate::validation::quality_gates::{
+    configured_gate_levels, GateContext, GateSelection, GateStrictness, ProgressiveEngine,
+    ProgressiveRun,
+};
+use clap::Subcommand;
+use std::collections::HashMap;
+use std::process::Command;
+
+/// Quality gate commands
+#[derive(Debug, Subcommand)]
+pub enum GatesCommands {
+    /// Select gates for a task by complexity and run them
+    ///
+    ///EXAMPLES:
+    ///  engram gates run --task <TASK_ID>
+    ///  engram gates run --task <TASK_ID> --level strict
+    Run {
+        /// Task the gates run for
+        #[arg(long, short)]
+        task: String,
+
+        /// Gate level: auto, basic, standard or strict
+        #[arg(long, short, default_value = "auto")]
+        level: String,
+
+        /// Changed files (comma-separated); defaults to staged files
+        #[arg(long, value_delimiter = ',')]
+        files: Option<Vec<String>>,
+
+        /// Agent recorded on execution results
+        #[arg(long, short)]
+        agent: Option<String>,
+    },
+    /// Show the level, complexity signals and gates a run would use
+    ///
+    ///EXAMPLES:
+    ///  engram gates plan --task <TASK_ID>
+    ///  engram gates plan --task <TASK_ID> --files src/lib.rs,tests/lib.rs
+    Plan {
+        /// Task to plan gates for
+        #[arg(long, short)]
+        task: String,
+
+        /// Gate level: auto, basic, standard or strict
+        #[arg(long, short, default_value = "auto")]
+        level: String,
+
+        /// Changed files (comma-separated); defaults to staged files
+        #[arg(long, value_delimiter = ',')]
+        files: Option<Vec<String>>,
+    },
+}
+
+/// Staged files, or none outside a git checkout
+fn staged_files() -> Vec<String> {
+    Command::new("git")
+        .args(["diff", "--cached", "--name-only"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .filter(|l| !l.trim().is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn parse_level(level: &str) -> Result<GateStrictness, EngramError> {
+    GateStrictness::parse(level).ok_or_else(|| {
+        EngramError::Validation(format!(
+            "Invalid gate level '{}'. Expected auto, basic, standard or strict",
+            level
+        ))
+    })
+}
+
+/// Load the task and build an engine plus context for it
+fn prepare<S: Storage>(
+    storage: S,
+    task_id: &str,
+    files: Option<Vec<String>>,
+) -> Result<(ProgressiveEngine<S>, GateContext), EngramError> {
+    let generic = storage
+        .get(task_id, "task")?
+        .ok_or_else(|| EngramError::not_found("task", task_id))?;
+    let task = Task::from_generic(generic)?;
+    let levels = configured_gate_levels(&storage)?;
+
+    let context = GateContext {
+        task,
+        changed_files: files.unwrap_or_else(staged_files),
+        commit_message: None,
+        branch_name: None,
+        metadata: HashMap::new(),
+    };
+    Ok((
+        ProgressiveEngine::new(storage).with_gate_levels(levels),
+        context,
+    ))
+}
+
+fn print_plan(task_id: &str, selection: &GateSelection) {
+    let signals = &selection.signals;
+    println!(
+        "Gate plan for task {} (level: {}, complexity: {:?}, score: {})",
+        task_id, selection.level, signals.level, signals.score
+    );
+    println!("Signals:");
+    println!(
+        "  lines changed:  {} (+{} -{})",
+        signals.lines_changed(),
+        signals.lines_added,
+        signals.lines_removed
+    );
+    println!(
+        "  files changed:  {} (src {}, tests {}, docs {}, other {})",
+        signals.files_changed,
+        signals.source_files,
+        signals.test_files,
+        signals.doc_files,
+        signals.other_files
+    );
+    println!("  breaking:       {}", signals.breaking);
+    println!("  security:       {}", signals.security);
+    println!("Gates:");
+    for gate in &selection.gates {
+        let kind = if gate.required {
+            "required"
+        } else {
+            "optional"
+        };
+        println!("  {} [{}]: {}", gate.name, kind, gate.command);
+    }
+}
+
+fn print_run(run: &ProgressiveRun) {
+    println!(
+        "Quality gates for task {} (level: {}, complexity: {:?})",
+        run.task_id, run.level, run.complexity
+    );
+    for result in &run.results {
+        let mark = if result.success { "✅" } else { "❌" };
+        println!(
+            "  {} {} ({} ms)",
+            mark, result.gate_type, result.execution_time_ms
+        );
+        for recommendation in &result.recommendations {
+            println!("     {}", recommendation);
+        }
+    }
+}
+
+/// Handle `engram gates`
+pub fn handle_gates_command<S: Storage>(
+    storage: S,
+    command: GatesCommands,
+    json: bool,
+) -> Result<(), EngramError> {
+    match command {
+        GatesCommands::Run {
+            task,
+            level,
+            files,
+            agent,
+        } => {
+            let strictness = parse_level(&level)?;
+            let (mut engine, context) = prepare(storage, &task, files)?;
+
+            let agent = resolve_agent(agent);
+            let run = engine.run(&context, strictness, &agent)?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&run)?);
+            } else {
+                print_run(&run);
+            }
+
+            if !run.passed() {
+                let failed: Vec<&str> = run
+                    .results
+                    .iter()
+                    .filter(|r| !r.success)
+                    .map(|r| r.gate_type.as_str())
+                    .collect();
+                return Err(EngramError::Validation(format!(
+                    "Quality gates failed: {}",
+                    failed.join(", ")
+                )));
+            }
+        }
+        GatesCommands::Plan { task, level, files } => {
+            let strictness = parse_level(&level)?;
+            let (engine, context) = prepare(storage, &task, files)?;
+            let selection = engine.select(&context, strictness);
+
+            if json {
+                let gates: Vec<_> = selection
+                    .gates
+                    .iter()
+                    .map(|g| {
+                        serde_json::json!({
+                            "name": g.name,
+                            "command": g.command,
+                            "required": g.required,
+                        })
+                    })
+                    .collect();
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({
+                        "task_id": context.task.id,
+                        "level": selection.level,
+                        "signals": selection.signals,
+                        "gates": gates,
+                    }))?
+                );
+            } else {
+                print_plan(&context.task.id, &selection);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::progressive_config::*;
+    use crate::entities::TaskPriority;
+    use crate::storage::MemoryStorage;
+    use std::time::Duration;
+
+    fn run(storage: MemoryStorage, task: &str, level: &str) -> Result<(), EngramError> {
+        handle_gates_command(
+            storage,
+            GatesCommands::Run {
+                task: task.to_string(),
+                level: level.to_string(),
+                files: Some(vec![]),
+                agent: Some("default".to_string()),
+            },
+            false,
+        )
+    }
+
+    fn plan(storage: MemoryStorage, task: &str, level: &str) -> Result<(), EngramError> {
+        handle_gates_command(
+            storage,
+            GatesCommands::Plan {
+                task: task.to_string(),
+                level: level.to_string(),
+                files: Some(vec!["docs/notes.md".to_string()]),
+            },
+            false,
+        )
+    }
+
+    /// Storage with an active gate config whose one level runs `command`
+    fn storage_with_gate(command: &str) -> MemoryStorage {
+        let mut storage = MemoryStorage::new("default");
+        let mut config = ProgressiveGateConfig::new("quick".to_string(), "default".to_string());
+        config.add_gate_level(GateLevel {
+            name: "quick".to_string(),
+            threshold: ChangeThreshold {
+                max_lines_changed: 500,
+                max_files_affected: 10,
+                max_complexity_delta: 1.0,
+                allowed_change_types: vec![ChangeType::Feature],
+                risk_level_limit: ProgressiveRiskLevel::Low,
+                file_patterns: vec![],
+            },
+            required_gates: vec![GateDefinition {
+                name: "quick-check".to_string(),
+                command: command.to_string(),
+                timeout: Duration::from_secs(30),
+                required: true,
+                condition: None,
+                environment: HashMap::new(),
+                retry_policy: RetryPolicy::default(),
+            }],
+            optional_gates: vec![],
+            max_execution_time: Duration::from_secs(60),
+            parallelization: ParallelizationStrategy::Sequential,
+            failure_handling: FailureHandling::default(),
+            enabled: true,
+            priority: 0,
+        });
+        storage.store(&config.to_generic()).unwrap();
+        storage
+    }
+
+    #[test]
+    fn test_gates_run_rejects_unknown_level_and_task() {
+        let mut storage = storage_with_gate("echo ok");
+        let task = Task::new(
+            "Gate me".to_string(),
+            "fix typo".to_string(),
+            "default".to_string(),
+            TaskPriority::Medium,
+            None,
+        );
+        storage.store(&task.to_generic()).unwrap();
+
+        let err = run(storage.clone(), &task.id, "paranoid").unwrap_err();
+        assert!(err.to_string().contains("Invalid gate level"));
+        assert!(run(storage.clone(), "missing", "auto").is_err());
+        assert!(run(storage, &task.id, "auto").is_ok());
+
+        let mut failing = storage_with_gate("false");
+        failing.store(&task.to_generic()).unwrap();
+        let err = run(failing, &task.id, "auto").unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Quality gates failed: quick-check"));
+    }
+
+    #[test]
+    fn test_gates_plan_rejects_unknown_level_and_task() {
+        let mut storage = MemoryStorage::new("default");
+        let task = Task::new(
+            "Gate me".to_string(),
+            "fix typo".to_string(),
+            "default".to_string(),
+            TaskPriority::Medium,
+            None,
+        );
+        storage.store(&task.to_generic()).unwrap();
+
+        let err = plan(storage.clone(), &task.id, "paranoid").unwrap_err();
+        assert!(err.to_string().contains("Invalid gate level"));
+        assert!(plan(storage.clone(), "missing", "auto").is_err());
+        assert!(plan(storage, &task.id, "standard").is_ok());
+    }
+}
@@ -68,7 +68,7 @@ pub fn handle_git_command(args: Vec<String>) -> Result<(), EngramError> {
                 // We try to initialize storage. If it fails (e.g. not an engram repo yet),
                 // we might want to warn or skip. But assuming 'engram git' is used in an engram repo.
                 match crate::storage::GitRefsStorage::new(&current_dir, "engram-cli") {
-                    Ok(storage) => {
+                    Ok(mut storage) => {
                         match crate::validation::CommitValidator::new(storage.clone()) {
                             Ok(mut validator) => {
                                 // Get staged files for validation
@@ -95,23 +95,11 @@ pub fn handle_git_command(args: Vec<String>) -> Result<(), EngramError> {
                                 }
 
                                 // Validation passed
-                                // Check for auto-guide suggestions
-                                match crate::cli::auto_guide::get_auto_guide_suggestion(
-                                    &storage,
-                                    &crate::cli::auto_guide::AutoGuideConfig::default(),
-                                    Some("commit"),
-                                ) {
-                                    Ok(Some(suggestion)) => {
-                                        println!(
-                                            "\n💡 \x1b[1m\x1b[36mEngram Suggestion:\x1b[0m {}",
-                                            suggestion
-                                        );
-                                    }
-                                    Ok(None) => {}
-                                    Err(_) => {
-                                        // Silently fail to not disrupt flow
-                                    }
+                                for warning in &result.warnings {
+                                    eprintln!("⚠️ {}", warning);
                                 }
+                                // Check for auto-guide suggestions
+                                crate::cli::auto_guide::check_auto_guide(&mut storage, "commit");
                             }
                             Err(e) => {
                                 // If we can't create validator, that's a problem but maybe not blocking?
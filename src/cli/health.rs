@@ -2047,6 +2047,8 @@ mod tests {
             agent: agent.to_string(),
             start_time: chrono::Utc::now(),
             end_time: None,
+            due_at: None,
+            estimate_seconds: None,
             parent: None,
             children: Vec::new(),
             tags: Vec::new(),
@@ -2069,6 +2071,7 @@ mod tests {
             content: "content".to_string(),
             source: "test".to_string(),
             source_id: None,
+            source_hash: None,
             relevance: ContextRelevance::Medium,
             agent: agent.to_string(),
             created_at: chrono::Utc::now(),
@@ -9,6 +9,16 @@ pub enum HelpCommands {
     GettingStarted,
     /// Show examples
     Examples,
+    /// List every currently applicable auto-guide suggestion
+    ///
+    ///EXAMPLES:
+    ///  engram guide suggestions
+    ///  engram guide suggestions --json
+    Suggestions {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 #[cfg(test)]
@@ -22,5 +32,6 @@ mod tests {
         let _ = HelpCommands::Onboarding;
         let _ = HelpCommands::GettingStarted;
         let _ = HelpCommands::Examples;
+        let _ = HelpCommands::Suggestions { json: false };
     }
 }
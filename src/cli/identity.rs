@@ -0,0 +1,97 @@
+//! Current agent identity resolution
+//!
+//! Commands that take an agent fall back to the current identity when none
+//! is given, and accept the literal `me` as an alias for it. The identity is
+//! resolved in order: `ENGRAM_AGENT` environment variable, then
+//! `workspace.default_agent` from the engram config file, then `"default"`.
+
+use crate::config::Config;
+
+/// Environment variable that overrides the configured identity
+pub const IDENTITY_ENV_VAR: &str = "ENGRAM_AGENT";
+
+/// Literal accepted wherever an agent name is expected
+pub const ME: &str = "me";
+
+/// Identity used when neither the environment nor config provide one
+pub const DEFAULT_IDENTITY: &str = "default";
+
+/// Resolve the identity from explicit sources (env > config > "default")
+pub fn resolve_identity(env_value: Option<&str>, config: Option<&Config>) -> String {
+    if let Some(agent) = env_value.map(str::trim).filter(|a| !a.is_empty()) {
+        return agent.to_string();
+    }
+
+    if let Some(agent) = config
+        .map(|c| c.workspace.default_agent.trim())
+        .filter(|a| !a.is_empty())
+    {
+        return agent.to_string();
+    }
+
+    DEFAULT_IDENTITY.to_string()
+}
+
+/// The current agent identity for this invocation
+pub fn current_agent() -> String {
+    let env_value = std::env::var(IDENTITY_ENV_VAR).ok();
+    let config = Config::find_config_file().and_then(|path| Config::load_from_file(&path).ok());
+    resolve_identity(env_value.as_deref(), config.as_ref())
+}
+
+/// Resolve an optional agent argument: missing or `me` become the current identity
+pub fn resolve_agent<A: AsRef<str>>(agent: Option<A>) -> String {
+    match agent {
+        Some(a) if a.as_ref() != ME => a.as_ref().to_string(),
+        _ => current_agent(),
+    }
+}
+
+/// Resolve an optional agent filter: `me` becomes the current identity, missing stays unfiltered
+pub fn resolve_agent_filter<A: AsRef<str>>(agent: Option<A>) -> Option<String> {
+    agent.map(|a| resolve_agent(Some(a)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_with_agent(agent: &str) -> Config {
+        let mut config = Config::default();
+        config.workspace.default_agent = agent.to_string();
+        config
+    }
+
+    #[test]
+    fn test_env_takes_precedence_over_config() {
+        let config = config_with_agent("from-config");
+        assert_eq!(
+            resolve_identity(Some("from-env"), Some(&config)),
+            "from-env"
+        );
+    }
+
+    #[test]
+    fn test_config_used_without_env() {
+        let config = config_with_agent("from-config");
+        assert_eq!(resolve_identity(None, Some(&config)), "from-config");
+        assert_eq!(resolve_identity(Some("  "), Some(&config)), "from-config");
+    }
+
+    #[test]
+    fn test_default_when_nothing_set() {
+        assert_eq!(resolve_identity(None, None), DEFAULT_IDENTITY);
+        let config = config_with_agent("");
+        assert_eq!(resolve_identity(None, Some(&config)), DEFAULT_IDENTITY);
+    }
+
+    #[test]
+    fn test_explicit_agent_passes_through() {
+        assert_eq!(resolve_agent(Some("alice")), "alice");
+        assert_eq!(
+            resolve_agent_filter(Some("alice".to_string())),
+            Some("alice".to_string())
+        );
+        assert_eq!(resolve_agent_filter(None::<&str>), None);
+    }
+}
@@ -2,14 +2,19 @@
 //!
 //! This module provides the `engram import` command which parses Engram Markdown
 //! (EMD) files and auto-creates entities with relationships based on pattern matching.
+//! Tasks can also be imported from spreadsheet exports with `engram import csv`
+//! and from Jira with `engram import jira`.
 
-use crate::entities::{Context, Entity, Reasoning, Task};
+use crate::cli::identity::resolve_agent;
+use crate::cli::jira::{import_jira_issues, JiraImportConfig};
+use crate::entities::{Context, Entity, Reasoning, Task, TaskPriority};
 use crate::error::EngramError;
 use crate::storage::{RelationshipStorage, Storage};
 use clap::Subcommand;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
 /// Import commands
@@ -25,20 +30,103 @@ pub enum ImportCommands {
         #[arg(long, short = 'v')]
         verbose: bool,
 
-        /// Preview changes without creating entities
-        #[arg(long)]
-        dry_run: bool,
-
         /// Overwrite existing entities
         #[arg(long)]
         force: bool,
 
+        /// Output results as JSON
+        #[arg(long, short = 'j')]
+        json: bool,
+    },
+    /// Import tasks from a CSV file with a header row
+    ///
+    ///EXAMPLES:
+    ///  engram import csv backlog.csv
+    ///  engram import csv backlog.csv --map title=Summary,description=Details,priority=Prio,tags=Labels --tag-sep ";"
+    Csv {
+        /// CSV file to import
+        file: PathBuf,
+
+        /// Column mapping as field=Header pairs; fields not listed use the
+        /// header of the same name. Fields: title, description, priority, tags
+        #[arg(long)]
+        map: Option<String>,
+
+        /// Separator between tags within the tags column
+        #[arg(long, default_value = ",")]
+        tag_sep: String,
+
+        /// Import nothing if any row is invalid
+        #[arg(long)]
+        strict: bool,
+
+        /// Import rows whose title matches an existing task
+        #[arg(long)]
+        allow_duplicates: bool,
+
+        /// Agent recorded on the imported tasks
+        #[arg(long)]
+        agent: Option<String>,
+
+        /// Output results as JSON
+        #[arg(long, short = 'j')]
+        json: bool,
+    },
+    /// Import Jira issues from a JSON export or saved REST search response
+    ///
+    /// Re-importing updates tasks matched by Jira key instead of duplicating them.
+    ///
+    ///EXAMPLES:
+    ///  engram import jira jira-export.json
+    ///  engram import jira search.json --config jira-status.yaml
+    Jira {
+        /// Jira JSON file to import
+        file: PathBuf,
+
+        /// YAML file with status mapping, dependency link types and epic link field
+        #[arg(long)]
+        config: Option<PathBuf>,
+
+        /// Agent recorded on newly imported tasks
+        #[arg(long)]
+        agent: Option<String>,
+
         /// Output results as JSON
         #[arg(long, short = 'j')]
         json: bool,
     },
 }
 
+/// Task fields a CSV column can be mapped to
+pub const CSV_TASK_FIELDS: [&str; 4] = ["title", "description", "priority", "tags"];
+
+/// Options for [`import_tasks_from_csv`]
+#[derive(Debug, Clone)]
+pub struct CsvImportOptions {
+    /// `field=Header` pairs separated by commas
+    pub mapping: Option<String>,
+    pub tag_separator: String,
+    pub strict: bool,
+    pub allow_duplicates: bool,
+    pub agent: String,
+}
+
+/// A row that was not imported, by CSV line number
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CsvRowIssue {
+    pub line: u64,
+    pub message: String,
+}
+
+/// Result of a CSV task import
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CsvImportReport {
+    pub rows_processed: usize,
+    pub created_ids: Vec<String>,
+    pub duplicates_skipped: Vec<CsvRowIssue>,
+    pub errors: Vec<CsvRowIssue>,
+}
+
 /// Document types supported by import
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub enum DocType {
@@ -133,11 +221,10 @@ pub fn handle_import_command<S: Storage + RelationshipStorage>(
         ImportCommands::Import {
             file,
             verbose,
-            dry_run,
             force: _,
             json,
         } => {
-            let result = import_file(&file, verbose, dry_run, storage)?;
+            let result = import_file(&file, verbose, storage)?;
 
             if json {
                 let json_output = serde_json::json!({
@@ -175,14 +262,268 @@ pub fn handle_import_command<S: Storage + RelationshipStorage>(
 
             Ok(())
         }
+        ImportCommands::Csv {
+            file,
+            map,
+            tag_sep,
+            strict,
+            allow_duplicates,
+            agent,
+            json,
+        } => {
+            let options = CsvImportOptions {
+                mapping: map,
+                tag_separator: tag_sep,
+                strict,
+                allow_duplicates,
+                agent: resolve_agent(agent),
+            };
+            let report = import_tasks_from_csv(&file, &options, storage)?;
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+                return Ok(());
+            }
+
+            println!(
+                "✅ Imported {} task(s) from {} row(s)",
+                report.created_ids.len(),
+                report.rows_processed
+            );
+            for duplicate in &report.duplicates_skipped {
+                println!("⚠️  Line {}: {}", duplicate.line, duplicate.message);
+            }
+            if !report.errors.is_empty() {
+                println!("❌ Skipped {} invalid row(s):", report.errors.len());
+                for error in &report.errors {
+                    println!("  Line {}: {}", error.line, error.message);
+                }
+            }
+            Ok(())
+        }
+        ImportCommands::Jira {
+            file,
+            config,
+            agent,
+            json,
+        } => {
+            let config = match config {
+                Some(path) => JiraImportConfig::from_file(&path)?,
+                None => JiraImportConfig::default(),
+            };
+            let report = import_jira_issues(&file, &config, &resolve_agent(agent), storage)?;
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+                return Ok(());
+            }
+
+            println!(
+                "✅ Imported {} Jira issue(s): {} created, {} updated, {} relationship(s)",
+                report.issues_processed,
+                report.created.len(),
+                report.updated.len(),
+                report.relationships_created
+            );
+            for warning in &report.warnings {
+                println!("⚠️  {}", warning);
+            }
+            Ok(())
+        }
     }
 }
 
+/// Resolve `field=Header` pairs against the CSV headers. Fields without an
+/// explicit mapping use a header of the same name, matched case-insensitively.
+fn resolve_csv_mapping(
+    mapping: Option<&str>,
+    headers: &csv::StringRecord,
+) -> Result<BTreeMap<&'static str, usize>, EngramError> {
+    let find_header = |name: &str| headers.iter().position(|h| h.eq_ignore_ascii_case(name));
+
+    let mut explicit = BTreeMap::new();
+    for pair in mapping
+        .unwrap_or_default()
+        .split(',')
+        .filter(|p| !p.trim().is_empty())
+    {
+        let (field, header) = pair.split_once('=').ok_or_else(|| {
+            EngramError::Validation(format!(
+                "Invalid mapping '{}': expected field=Header",
+                pair.trim()
+            ))
+        })?;
+        let field = CSV_TASK_FIELDS
+            .iter()
+            .find(|f| f.eq_ignore_ascii_case(field.trim()))
+            .ok_or_else(|| {
+                EngramError::Validation(format!(
+                    "Unknown task field '{}' in mapping. Valid fields: {}",
+                    field.trim(),
+                    CSV_TASK_FIELDS.join(", ")
+                ))
+            })?;
+        let column = find_header(header.trim()).ok_or_else(|| {
+            EngramError::Validation(format!(
+                "Column '{}' mapped to {} is not in the CSV header. Columns: {}",
+                header.trim(),
+                field,
+                headers.iter().collect::<Vec<_>>().join(", ")
+            ))
+        })?;
+        explicit.insert(*field, column);
+    }
+
+    let mut columns = BTreeMap::new();
+    for field in CSV_TASK_FIELDS {
+        if let Some(column) = explicit.get(field).copied().or_else(|| find_header(field)) {
+            columns.insert(field, column);
+        }
+    }
+    if !columns.contains_key("title") {
+        return Err(EngramError::Validation(
+            "No title column: map one with --map title=<Header>".to_string(),
+        ));
+    }
+    Ok(columns)
+}
+
+/// Parse a priority case-insensitively; an empty cell means medium
+fn parse_csv_priority(value: &str) -> Result<TaskPriority, String> {
+    match value.trim().to_lowercase().as_str() {
+        "" | "medium" => Ok(TaskPriority::Medium),
+        "low" => Ok(TaskPriority::Low),
+        "high" => Ok(TaskPriority::High),
+        "critical" => Ok(TaskPriority::Critical),
+        other => Err(format!(
+            "Unknown priority '{}' (expected low, medium, high or critical)",
+            other
+        )),
+    }
+}
+
+/// Import tasks from a CSV file with a header row
+///
+/// Invalid rows are reported by line number and skipped, or abort the whole
+/// import with `strict`. Titles matching an existing task, or an earlier
+/// row, are skipped unless `allow_duplicates`. Valid rows are stored in one
+/// `bulk_store`.
+pub fn import_tasks_from_csv(
+    path: &Path,
+    options: &CsvImportOptions,
+    storage: &mut dyn Storage,
+) -> Result<CsvImportReport, EngramError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_path(path)
+        .map_err(|e| {
+            EngramError::Validation(format!("Cannot read CSV {}: {}", path.display(), e))
+        })?;
+    let headers = reader
+        .headers()
+        .map_err(|e| EngramError::Validation(format!("Cannot read CSV header: {}", e)))?
+        .clone();
+    let columns = resolve_csv_mapping(options.mapping.as_deref(), &headers)?;
+    let cell = |record: &csv::StringRecord, field: &str| {
+        columns
+            .get(field)
+            .and_then(|&column| record.get(column))
+            .unwrap_or_default()
+            .to_string()
+    };
+
+    let mut titles: HashSet<String> = storage
+        .get_all(Task::entity_type())?
+        .into_iter()
+        .filter_map(|e| e.data.get("title")?.as_str().map(str::to_string))
+        .collect();
+
+    let mut report = CsvImportReport::default();
+    let mut tasks = Vec::new();
+    for (index, record) in reader.records().enumerate() {
+        report.rows_processed += 1;
+        let record = match record {
+            Ok(record) => record,
+            Err(e) => {
+                let line = e.position().map_or(index as u64 + 2, |p| p.line());
+                report.errors.push(CsvRowIssue {
+                    line,
+                    message: e.to_string(),
+                });
+                continue;
+            }
+        };
+        let line = record.position().map_or(index as u64 + 2, |p| p.line());
+
+        let title = cell(&record, "title");
+        if title.is_empty() {
+            report.errors.push(CsvRowIssue {
+                line,
+                message: "Title is empty".to_string(),
+            });
+            continue;
+        }
+        let priority = match parse_csv_priority(&cell(&record, "priority")) {
+            Ok(priority) => priority,
+            Err(message) => {
+                report.errors.push(CsvRowIssue { line, message });
+                continue;
+            }
+        };
+        if !options.allow_duplicates && !titles.insert(title.clone()) {
+            report.duplicates_skipped.push(CsvRowIssue {
+                line,
+                message: format!("Skipped duplicate title '{}'", title),
+            });
+            continue;
+        }
+
+        let mut task = Task::new(
+            title,
+            cell(&record, "description"),
+            options.agent.clone(),
+            priority,
+            None,
+        );
+        task.tags = cell(&record, "tags")
+            .split(options.tag_separator.as_str())
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(str::to_string)
+            .collect();
+        if let Err(e) = task.validate_entity() {
+            report.errors.push(CsvRowIssue {
+                line,
+                message: e.to_string(),
+            });
+            continue;
+        }
+        tasks.push(task);
+    }
+
+    if options.strict && !report.errors.is_empty() {
+        let details: Vec<String> = report
+            .errors
+            .iter()
+            .map(|e| format!("line {}: {}", e.line, e.message))
+            .collect();
+        return Err(EngramError::Validation(format!(
+            "CSV import aborted, {} invalid row(s): {}",
+            report.errors.len(),
+            details.join("; ")
+        )));
+    }
+
+    let generics: Vec<_> = tasks.iter().map(|t| t.to_generic()).collect();
+    storage.bulk_store(&generics)?;
+    report.created_ids = tasks.into_iter().map(|t| t.id).collect();
+    Ok(report)
+}
+
 /// Main import function
 fn import_file<S: Storage + RelationshipStorage>(
     file: &PathBuf,
     verbose: bool,
-    dry_run: bool,
     storage: &mut S,
 ) -> Result<ImportResult, EngramError> {
     let mut result = ImportResult {
@@ -247,21 +588,6 @@ fn import_file<S: Storage + RelationshipStorage>(
         println!("Found {} UUID patterns for linking", uuid_patterns.len());
     }
 
-    // For dry run, just show what would be created
-    if dry_run {
-        println!("\n[DRY RUN] Would create:");
-        println!("  - {} findings", findings.len());
-        println!("  - {} task references", tasks.len());
-        println!("  - {} reasoning sections", reasoning_sections.len());
-        println!("  - {} relationship patterns", uuid_patterns.len());
-
-        for finding in &findings {
-            println!("    - Finding: {}", finding.title);
-        }
-
-        return Ok(result);
-    }
-
     // Create entities (storage operations would go here)
     // For now, just count what we would create
     result.entities_created = findings.len() + reasoning_sections.len();
@@ -795,4 +1121,81 @@ title: Test
         let uuid_none = extract_task_id_from_content(content_none);
         assert!(uuid_none.is_none());
     }
+
+    fn csv_options(strict: bool) -> CsvImportOptions {
+        CsvImportOptions {
+            mapping: Some(
+                "title=Summary,description=Details,priority=Prio,tags=Labels".to_string(),
+            ),
+            tag_separator: ";".to_string(),
+            strict,
+            allow_duplicates: false,
+            agent: "importer".to_string(),
+        }
+    }
+
+    const BACKLOG_CSV: &str = "Summary,Details,Prio,Labels\n\
+        Login page,Build the form,HIGH,auth; ui\n\
+        ,No title,low,\n\
+        Existing task,Already tracked,medium,\n\
+        Rate limits,Throttle the API,urgent,api\n\
+        Logout,,,auth\n";
+
+    #[test]
+    fn test_csv_import_maps_columns_and_reports_rows() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("backlog.csv");
+        fs::write(&path, BACKLOG_CSV).unwrap();
+
+        let mut storage = crate::storage::MemoryStorage::new("default");
+        let existing = Task::new(
+            "Existing task".to_string(),
+            String::new(),
+            "default".to_string(),
+            TaskPriority::Low,
+            None,
+        );
+        storage.store(&existing.to_generic()).unwrap();
+
+        let report = import_tasks_from_csv(&path, &csv_options(false), &mut storage).unwrap();
+        assert_eq!(report.rows_processed, 5);
+        assert_eq!(report.created_ids.len(), 2);
+        assert_eq!(
+            report.errors.iter().map(|e| e.line).collect::<Vec<_>>(),
+            [3, 5]
+        );
+        assert!(report.errors[1].message.contains("urgent"));
+        assert_eq!(report.duplicates_skipped[0].line, 4);
+
+        let login = Task::from_generic(
+            storage
+                .get(&report.created_ids[0], "task")
+                .unwrap()
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(login.title, "Login page");
+        assert_eq!(login.priority, TaskPriority::High);
+        assert_eq!(login.tags, ["auth", "ui"]);
+        assert_eq!(login.agent, "importer");
+
+        // Strict mode imports nothing when any row is invalid
+        let mut empty = crate::storage::MemoryStorage::new("default");
+        let err = import_tasks_from_csv(&path, &csv_options(true), &mut empty).unwrap_err();
+        assert!(err.to_string().contains("line 3"));
+        assert!(empty.get_all("task").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_csv_mapping_validation() {
+        let headers = csv::StringRecord::from(vec!["Title", "Summary", "Notes"]);
+        let columns = resolve_csv_mapping(Some("description=notes"), &headers).unwrap();
+        assert_eq!(columns["title"], 0);
+        assert_eq!(columns["description"], 2);
+        assert!(!columns.contains_key("priority"));
+
+        assert!(resolve_csv_mapping(Some("owner=Summary"), &headers).is_err());
+        assert!(resolve_csv_mapping(Some("title=Missing"), &headers).is_err());
+        assert!(resolve_csv_mapping(None, &csv::StringRecord::from(vec!["Name"])).is_err());
+    }
 }
@@ -1,7 +1,37 @@
 //! Info command for displaying storage and workspace information
 
+use crate::archive::{read_zip, ZipWriter};
+use crate::entities::{Entity, EscalationRequest, GenericEntity};
 use crate::error::EngramError;
-use crate::storage::Storage;
+use crate::notify;
+use crate::redaction::{RedactionStats, Redactor};
+use crate::storage::{collect_entities, GitIdentity, GitRefsStorage, QueryFilter, Storage};
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+/// Snapshot layout version written to `metadata.json`
+pub const SNAPSHOT_FORMAT_VERSION: &str = "1";
+
+const ENTITIES_FILE: &str = "entities.jsonl";
+const RELATIONSHIPS_FILE: &str = "relationships.jsonl";
+const METADATA_FILE: &str = "metadata.json";
+const GIT_BUNDLE_FILE: &str = ".engram_git_bundle";
+
+/// Result of exporting or importing a workspace snapshot
+#[derive(Debug, Clone, Serialize)]
+pub struct SnapshotReport {
+    /// Entities in the archive, relationships included
+    pub entities_included: usize,
+    pub relationships_included: usize,
+    pub git_history_included: bool,
+    pub file_size_bytes: u64,
+    pub format_version: String,
+    /// Redactions per detector, when the export was redacted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub redactions: Option<RedactionStats>,
+}
 
 /// Display workspace and storage information
 pub fn info<S: Storage>(storage: &S) -> Result<(), EngramError> {
@@ -10,6 +40,29 @@ pub fn info<S: Storage>(storage: &S) -> Result<(), EngramError> {
     println!("╚════════════════════════════════════════════════════════════╝");
     println!();
 
+    // Critical escalations still awaiting review
+    let escalations: Vec<EscalationRequest> = storage
+        .get_all("escalation_request")
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|generic| EscalationRequest::from_generic(generic).ok())
+        .collect();
+    let alerts = notify::open_escalation_alerts(
+        notify::read_alerts(Path::new(".")).unwrap_or_default(),
+        &escalations,
+    );
+    if !alerts.is_empty() {
+        println!("🚨 Critical Alerts");
+        println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+        for alert in &alerts {
+            println!("  {}", alert.title);
+            if let Some(command) = alert.metadata.get("review_command") {
+                println!("    → {}", command);
+            }
+        }
+        println!();
+    }
+
     // Storage backend info
     println!("📦 Storage Backend");
     println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
@@ -48,9 +101,9 @@ pub fn info<S: Storage>(storage: &S) -> Result<(), EngramError> {
     ];
 
     for entity_type in entity_types {
-        if let Ok(ids) = storage.list_ids(entity_type) {
-            if !ids.is_empty() {
-                println!("  {}: {}", entity_type.replace("_", " "), ids.len());
+        if let Ok(count) = storage.count(&type_filter(entity_type)) {
+            if count > 0 {
+                println!("  {}: {}", entity_type.replace("_", " "), count);
             }
         }
     }
@@ -60,11 +113,24 @@ pub fn info<S: Storage>(storage: &S) -> Result<(), EngramError> {
     println!("👥 Agents");
     println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
 
-    let agent_entities = storage.list_ids("agent").unwrap_or_default();
-    if agent_entities.is_empty() {
+    let agent_count = storage.count(&type_filter("agent")).unwrap_or_default();
+    if agent_count == 0 {
         println!("  No agents configured");
     } else {
-        println!("  {} agent(s) configured", agent_entities.len());
+        println!("  {} agent(s) configured", agent_count);
+    }
+    println!();
+
+    // Git identity recorded on writes
+    println!("🪪 Git Identity");
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    match GitIdentity::discover(Path::new(".")) {
+        Some(identity) => println!("  Writes attributed to: {}", identity),
+        None => {
+            println!("  ⚠️  No git identity configured; writes carry no author");
+            println!("    → git config user.name \"Your Name\"");
+            println!("    → git config user.email you@example.com");
+        }
     }
     println!();
 
@@ -74,11 +140,298 @@ pub fn info<S: Storage>(storage: &S) -> Result<(), EngramError> {
     Ok(())
 }
 
+/// Every entity of one type, for `Storage::count`
+fn type_filter(entity_type: &str) -> QueryFilter {
+    QueryFilter {
+        entity_type: Some(entity_type.to_string()),
+        limit: None,
+        offset: None,
+        ..Default::default()
+    }
+}
+
+fn to_jsonl(entities: &[&GenericEntity]) -> Result<Vec<u8>, EngramError> {
+    let mut out = Vec::new();
+    for entity in entities {
+        serde_json::to_writer(&mut out, entity)?;
+        out.push(b'\n');
+    }
+    Ok(out)
+}
+
+fn from_jsonl(name: &str, data: &[u8]) -> Result<Vec<GenericEntity>, EngramError> {
+    String::from_utf8_lossy(data)
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .enumerate()
+        .map(|(i, line)| {
+            serde_json::from_str(line)
+                .map_err(|e| EngramError::Validation(format!("{} line {}: {}", name, i + 1, e)))
+        })
+        .collect()
+}
+
+fn git_bundle(workspace: &Path) -> Result<Vec<u8>, EngramError> {
+    let bundle = std::env::temp_dir().join(format!("engram-{}.bundle", uuid::Uuid::new_v4()));
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(workspace)
+        .args(["bundle", "create"])
+        .arg(&bundle)
+        .arg("--all")
+        .output()?;
+    let contents = if output.status.success() {
+        fs::read(&bundle).map_err(EngramError::from)
+    } else {
+        Err(EngramError::Git(format!(
+            "git bundle create failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )))
+    };
+    let _ = fs::remove_file(&bundle);
+    contents
+}
+
+/// Write every entity of `storage` into a self-contained ZIP archive at `output`
+///
+/// The archive holds `entities.jsonl`, `relationships.jsonl` and
+/// `metadata.json`, plus `.engram_git_bundle` (all refs, via `git bundle`)
+/// when `include_git_history` is set and the storage is git-backed. With a
+/// `redactor`, entity data is redacted before it is written; the git bundle
+/// is raw history and cannot be redacted, so the two are mutually exclusive.
+pub fn export_workspace_snapshot(
+    storage: &dyn Storage,
+    output: &Path,
+    include_git_history: bool,
+    redactor: Option<&Redactor>,
+) -> Result<SnapshotReport, EngramError> {
+    if redactor.is_some() && include_git_history {
+        return Err(EngramError::InvalidOperation(
+            "A redacted snapshot cannot include git history".to_string(),
+        ));
+    }
+    let mut entities = collect_entities(storage, None)?;
+    let redactions = redactor.map(|redactor| {
+        let mut stats = RedactionStats::default();
+        for entity in &mut entities {
+            redactor.redact_value(&mut entity.data, &mut stats);
+        }
+        stats
+    });
+    let (relationships, others): (Vec<&GenericEntity>, Vec<&GenericEntity>) = entities
+        .iter()
+        .partition(|entity| entity.entity_type == "relationship");
+
+    let bundle = if include_git_history {
+        let git = storage
+            .as_any()
+            .downcast_ref::<GitRefsStorage>()
+            .ok_or_else(|| {
+                EngramError::InvalidOperation(
+                    "Git history can only be exported from git-backed storage".to_string(),
+                )
+            })?;
+        Some(git_bundle(git.workspace_path())?)
+    } else {
+        None
+    };
+
+    let stats = storage.get_stats()?;
+    let metadata = serde_json::json!({
+        "format_version": SNAPSHOT_FORMAT_VERSION,
+        "engram_version": env!("CARGO_PKG_VERSION"),
+        "created_at": chrono::Utc::now().to_rfc3339(),
+        "entity_count": others.len(),
+        "relationship_count": relationships.len(),
+        "includes_git_history": bundle.is_some(),
+        "redacted": redactions.is_some(),
+        "stats": {
+            "total_entities": stats.total_entities,
+            "entities_by_type": stats.entities_by_type,
+            "entities_by_agent": stats.entities_by_agent,
+        },
+    });
+
+    let mut zip = ZipWriter::new();
+    zip.add_file(ENTITIES_FILE, &to_jsonl(&others)?)?;
+    zip.add_file(RELATIONSHIPS_FILE, &to_jsonl(&relationships)?)?;
+    zip.add_file(METADATA_FILE, &serde_json::to_vec_pretty(&metadata)?)?;
+    if let Some(bundle) = &bundle {
+        zip.add_file(GIT_BUNDLE_FILE, bundle)?;
+    }
+    let bytes = zip.finish()?;
+    fs::write(output, &bytes)?;
+
+    Ok(SnapshotReport {
+        entities_included: entities.len(),
+        relationships_included: relationships.len(),
+        git_history_included: bundle.is_some(),
+        file_size_bytes: bytes.len() as u64,
+        format_version: SNAPSHOT_FORMAT_VERSION.to_string(),
+        redactions,
+    })
+}
+
+/// Store every entity from a snapshot written by [`export_workspace_snapshot`]
+///
+/// Entities keep their IDs, so importing into the source workspace updates
+/// them in place. With `agent` every entity is reassigned to that agent. A
+/// git bundle in the archive is left alone; restore it with `git fetch`.
+pub fn import_workspace_snapshot(
+    storage: &mut dyn Storage,
+    input: &Path,
+    agent: Option<&str>,
+) -> Result<SnapshotReport, EngramError> {
+    let bytes = fs::read(input)?;
+    let files = read_zip(&bytes)?;
+    let file = |name: &str| files.iter().find(|(n, _)| n == name).map(|(_, d)| d);
+
+    let metadata: serde_json::Value =
+        serde_json::from_slice(file(METADATA_FILE).ok_or_else(|| {
+            EngramError::Validation(format!("Snapshot has no {}", METADATA_FILE))
+        })?)?;
+    let format_version = metadata["format_version"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string();
+    if format_version != SNAPSHOT_FORMAT_VERSION {
+        return Err(EngramError::Validation(format!(
+            "Unsupported snapshot format version '{}'",
+            format_version
+        )));
+    }
+
+    let mut entities = match file(ENTITIES_FILE) {
+        Some(data) => from_jsonl(ENTITIES_FILE, data)?,
+        None => Vec::new(),
+    };
+    let relationships = match file(RELATIONSHIPS_FILE) {
+        Some(data) => from_jsonl(RELATIONSHIPS_FILE, data)?,
+        None => Vec::new(),
+    };
+    let relationships_included = relationships.len();
+    // Relationships last, so their endpoints exist first
+    entities.extend(relationships);
+
+    if let Some(agent) = agent {
+        for entity in &mut entities {
+            entity.agent = agent.to_string();
+            if let Some(data_agent) = entity.data.get_mut("agent") {
+                *data_agent = serde_json::Value::String(agent.to_string());
+            }
+        }
+    }
+
+    storage.bulk_store(&entities)?;
+
+    Ok(SnapshotReport {
+        entities_included: entities.len(),
+        relationships_included,
+        git_history_included: file(GIT_BUNDLE_FILE).is_some(),
+        file_size_bytes: bytes.len() as u64,
+        format_version,
+        redactions: None,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::storage::MemoryStorage;
 
+    fn seed(storage: &mut MemoryStorage) {
+        for (id, entity_type) in [("t1", "task"), ("t2", "task"), ("c1", "context")] {
+            storage
+                .store(&GenericEntity {
+                    id: id.to_string(),
+                    entity_type: entity_type.to_string(),
+                    agent: "alice".to_string(),
+                    timestamp: chrono::Utc::now(),
+                    data: serde_json::json!({"id": id, "title": id, "agent": "alice"}),
+                })
+                .unwrap();
+        }
+        storage
+            .store(&GenericEntity {
+                id: "r1".to_string(),
+                entity_type: "relationship".to_string(),
+                agent: "alice".to_string(),
+                timestamp: chrono::Utc::now(),
+                data: serde_json::json!({"id": "r1", "source_id": "t1", "target_id": "c1"}),
+            })
+            .unwrap();
+    }
+
+    fn counts(storage: &MemoryStorage) -> Vec<(String, usize)> {
+        ["task", "context", "relationship"]
+            .iter()
+            .map(|t| (t.to_string(), storage.list_ids(t).unwrap().len()))
+            .collect()
+    }
+
+    #[test]
+    fn test_snapshot_export_import_round_trip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let archive = dir.path().join("snapshot.zip");
+        let mut storage = MemoryStorage::new("alice");
+        seed(&mut storage);
+        let before = counts(&storage);
+
+        let exported = export_workspace_snapshot(&storage, &archive, false, None).unwrap();
+        assert_eq!(exported.entities_included, 4);
+        assert_eq!(exported.relationships_included, 1);
+        assert_eq!(exported.format_version, SNAPSHOT_FORMAT_VERSION);
+        assert_eq!(
+            exported.file_size_bytes,
+            fs::metadata(&archive).unwrap().len()
+        );
+
+        let mut cleared = MemoryStorage::new("bob");
+        assert!(counts(&cleared).iter().all(|(_, n)| *n == 0));
+
+        let imported = import_workspace_snapshot(&mut cleared, &archive, Some("bob")).unwrap();
+        assert_eq!(imported.entities_included, 4);
+        assert_eq!(counts(&cleared), before);
+
+        let task = cleared.get("t1", "task").unwrap().unwrap();
+        assert_eq!(task.agent, "bob");
+        assert_eq!(task.data["agent"], "bob");
+    }
+
+    #[test]
+    fn test_redacted_snapshot_export() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let archive = dir.path().join("snapshot.zip");
+        let mut storage = MemoryStorage::new("alice");
+        seed(&mut storage);
+        let mut context = storage.get("c1", "context").unwrap().unwrap();
+        context.data["content"] = serde_json::json!("mail ops@example.com");
+        storage.store(&context).unwrap();
+
+        let redactor = Redactor::new(&Default::default()).unwrap();
+        let exported =
+            export_workspace_snapshot(&storage, &archive, false, Some(&redactor)).unwrap();
+        assert_eq!(exported.redactions.unwrap().counts["email"], 1);
+
+        let mut restored = MemoryStorage::new("alice");
+        import_workspace_snapshot(&mut restored, &archive, None).unwrap();
+        let context = restored.get("c1", "context").unwrap().unwrap();
+        assert_eq!(context.data["content"], "mail [REDACTED:email]");
+        // The source workspace is untouched
+        let original = storage.get("c1", "context").unwrap().unwrap();
+        assert_eq!(original.data["content"], "mail ops@example.com");
+
+        assert!(export_workspace_snapshot(&storage, &archive, true, Some(&redactor)).is_err());
+    }
+
+    #[test]
+    fn test_snapshot_git_history_needs_git_storage() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = MemoryStorage::new("alice");
+        let result = export_workspace_snapshot(&storage, &dir.path().join("s.zip"), true, None);
+        assert!(matches!(result, Err(EngramError::InvalidOperation(_))));
+    }
+
     #[test]
     fn test_info_execution() {
         // Just verify that the info function runs without panicking on an empty storage
@@ -0,0 +1,701 @@
+//! Jira import for `engram import jira`
+//!
+//! Reads a Jira JSON export or a saved REST search response and turns issues
+//! into tasks. Epics and parent issues become parent tasks linked with
+//! `Contains`, and issue links become `DependsOn` or `References`
+//! relationships. The Jira key is kept in task metadata, so importing the
+//! same export again updates tasks instead of duplicating them.
+
+use crate::entities::{
+    Entity, EntityRelationType, EntityRelationship, GenericEntity, Task, TaskPriority, TaskStatus,
+};
+use crate::error::EngramError;
+use crate::storage::Storage;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use uuid::Uuid;
+
+/// Task metadata key holding the Jira issue key
+pub const JIRA_KEY_METADATA: &str = "jira_key";
+
+/// Task metadata key holding the Jira issue type
+pub const JIRA_ISSUE_TYPE_METADATA: &str = "jira_issue_type";
+
+/// Mapping settings read from the `--config` YAML file
+///
+/// ```yaml
+/// status:
+///   "Code Review": inprogress
+///   "Won't Do": cancelled
+/// dependency_link_types: [Blocks]
+/// epic_link_field: customfield_10014
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct JiraImportConfig {
+    /// Jira status name to task status, matched case-insensitively. Statuses
+    /// not listed fall back to the Jira status category.
+    pub status: HashMap<String, TaskStatus>,
+    /// Link types imported as `DependsOn`; other link types become `References`
+    pub dependency_link_types: Vec<String>,
+    /// Field holding the epic key on classic Jira projects
+    pub epic_link_field: String,
+}
+
+impl Default for JiraImportConfig {
+    fn default() -> Self {
+        Self {
+            status: HashMap::new(),
+            dependency_link_types: vec!["Blocks".to_string()],
+            epic_link_field: "customfield_10014".to_string(),
+        }
+    }
+}
+
+impl JiraImportConfig {
+    /// Load a config file
+    pub fn from_file(path: &Path) -> Result<Self, EngramError> {
+        let content = std::fs::read_to_string(path)?;
+        serde_yaml::from_str(&content).map_err(|e| {
+            EngramError::Validation(format!("Invalid Jira config {}: {}", path.display(), e))
+        })
+    }
+
+    fn map_status(&self, status: Option<&JiraStatus>) -> TaskStatus {
+        let Some(status) = status else {
+            return TaskStatus::Todo;
+        };
+        if let Some(mapped) = self
+            .status
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(&status.name))
+            .map(|(_, mapped)| mapped.clone())
+        {
+            return mapped;
+        }
+        let category = status
+            .status_category
+            .as_ref()
+            .map(|c| c.key.as_str())
+            .unwrap_or_default();
+        match category {
+            "done" => TaskStatus::Done,
+            "indeterminate" => TaskStatus::InProgress,
+            _ => TaskStatus::Todo,
+        }
+    }
+
+    fn is_dependency_link(&self, link_type: &str) -> bool {
+        self.dependency_link_types
+            .iter()
+            .any(|t| t.eq_ignore_ascii_case(link_type))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum JiraExport {
+    Search { issues: Vec<JiraIssue> },
+    Issues(Vec<JiraIssue>),
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraIssue {
+    key: String,
+    #[serde(default)]
+    fields: JiraFields,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct JiraFields {
+    summary: Option<String>,
+    /// Atlassian Document Format (REST v3) or plain text (REST v2)
+    description: Option<Value>,
+    priority: Option<JiraNamed>,
+    #[serde(default)]
+    labels: Vec<String>,
+    status: Option<JiraStatus>,
+    issuetype: Option<JiraNamed>,
+    parent: Option<JiraIssueRef>,
+    #[serde(default)]
+    issuelinks: Vec<JiraIssueLink>,
+    created: Option<String>,
+    #[serde(flatten)]
+    other: HashMap<String, Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraNamed {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraStatus {
+    name: String,
+    #[serde(rename = "statusCategory")]
+    status_category: Option<JiraStatusCategory>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraStatusCategory {
+    key: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraIssueRef {
+    key: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraIssueLink {
+    #[serde(rename = "type")]
+    link_type: JiraNamed,
+    #[serde(rename = "inwardIssue")]
+    inward_issue: Option<JiraIssueRef>,
+    #[serde(rename = "outwardIssue")]
+    outward_issue: Option<JiraIssueRef>,
+}
+
+impl JiraFields {
+    /// Key of the parent issue or, on classic projects, the linked epic
+    fn parent_key(&self, config: &JiraImportConfig) -> Option<String> {
+        self.parent.as_ref().map(|p| p.key.clone()).or_else(|| {
+            self.other
+                .get(&config.epic_link_field)
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        })
+    }
+}
+
+/// Result of a Jira import
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct JiraImportReport {
+    pub issues_processed: usize,
+    /// Jira keys of newly created tasks
+    pub created: Vec<String>,
+    /// Jira keys of tasks that already existed and were updated
+    pub updated: Vec<String>,
+    pub relationships_created: usize,
+    pub warnings: Vec<String>,
+}
+
+fn map_priority(priority: Option<&JiraNamed>) -> TaskPriority {
+    match priority.map(|p| p.name.to_lowercase()).as_deref() {
+        Some("highest" | "blocker" | "critical") => TaskPriority::Critical,
+        Some("high" | "major") => TaskPriority::High,
+        Some("low" | "lowest" | "minor" | "trivial") => TaskPriority::Low,
+        _ => TaskPriority::Medium,
+    }
+}
+
+fn parse_jira_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .or_else(|_| DateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f%z"))
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+/// Convert a Jira description to markdown. Plain strings are returned as-is;
+/// Atlassian Document Format is converted for common block and inline nodes,
+/// and the text of unknown nodes is kept.
+pub fn adf_to_markdown(description: &Value) -> String {
+    match description {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        node => {
+            let mut blocks = Vec::new();
+            for child in children(node) {
+                let block = adf_block(child, "");
+                if !block.is_empty() {
+                    blocks.push(block);
+                }
+            }
+            blocks.join("\n\n")
+        }
+    }
+}
+
+fn children(node: &Value) -> &[Value] {
+    node.get("content")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+fn attr<'a>(node: &'a Value, name: &str) -> Option<&'a Value> {
+    node.get("attrs").and_then(|attrs| attrs.get(name))
+}
+
+fn adf_inline(nodes: &[Value]) -> String {
+    let mut out = String::new();
+    for node in nodes {
+        match node.get("type").and_then(Value::as_str).unwrap_or_default() {
+            "text" => {
+                let mut text = node
+                    .get("text")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string();
+                let marks = node.get("marks").and_then(Value::as_array);
+                for mark in marks.into_iter().flatten() {
+                    text = match mark.get("type").and_then(Value::as_str) {
+                        Some("strong") => format!("**{}**", text),
+                        Some("em") => format!("*{}*", text),
+                        Some("code") => format!("`{}`", text),
+                        Some("strike") => format!("~~{}~~", text),
+                        Some("link") => match attr(mark, "href").and_then(Value::as_str) {
+                            Some(href) => format!("[{}]({})", text, href),
+                            None => text,
+                        },
+                        _ => text,
+                    };
+                }
+                out.push_str(&text);
+            }
+            "hardBreak" => out.push('\n'),
+            "mention" | "emoji" => {
+                let text = attr(node, "text")
+                    .or_else(|| attr(node, "shortName"))
+                    .and_then(Value::as_str)
+                    .unwrap_or_default();
+                out.push_str(text);
+            }
+            "inlineCard" => {
+                if let Some(url) = attr(node, "url").and_then(Value::as_str) {
+                    out.push_str(url);
+                }
+            }
+            _ => out.push_str(&adf_inline(children(node))),
+        }
+    }
+    out
+}
+
+fn adf_block(node: &Value, indent: &str) -> String {
+    match node.get("type").and_then(Value::as_str).unwrap_or_default() {
+        "paragraph" => adf_inline(children(node)),
+        "heading" => {
+            let level = attr(node, "level").and_then(Value::as_u64).unwrap_or(1);
+            format!(
+                "{} {}",
+                "#".repeat(level.clamp(1, 6) as usize),
+                adf_inline(children(node))
+            )
+        }
+        "bulletList" | "orderedList" => {
+            let ordered = node.get("type").and_then(Value::as_str) == Some("orderedList");
+            let mut lines = Vec::new();
+            for (index, item) in children(node).iter().enumerate() {
+                let marker = if ordered {
+                    format!("{}. ", index + 1)
+                } else {
+                    "- ".to_string()
+                };
+                let nested = format!("{}{}", indent, " ".repeat(marker.len()));
+                let mut item_blocks = children(item).iter().map(|child| {
+                    match child.get("type").and_then(Value::as_str) {
+                        Some("bulletList" | "orderedList") => adf_block(child, &nested),
+                        _ => adf_block(child, indent),
+                    }
+                });
+                let first = item_blocks.next().unwrap_or_default();
+                lines.push(format!("{}{}{}", indent, marker, first));
+                lines.extend(item_blocks);
+            }
+            lines.join("\n")
+        }
+        "codeBlock" => {
+            let language = attr(node, "language")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            format!("```{}\n{}\n```", language, adf_inline(children(node)))
+        }
+        "blockquote" => children(node)
+            .iter()
+            .map(|child| adf_block(child, ""))
+            .flat_map(|block| {
+                block
+                    .lines()
+                    .map(|line| format!("> {}", line))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        "rule" => "---".to_string(),
+        "text" | "hardBreak" | "mention" | "emoji" | "inlineCard" => {
+            adf_inline(std::slice::from_ref(node))
+        }
+        _ => children(node)
+            .iter()
+            .map(|child| adf_block(child, indent))
+            .filter(|block| !block.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n"),
+    }
+}
+
+fn relationship_key(source: &str, target: &str, relationship_type: &EntityRelationType) -> String {
+    format!("{}|{}|{}", source, target, relationship_type)
+}
+
+/// Import issues from a Jira export into tasks and relationships
+///
+/// Tasks are matched to issues by the `jira_key` metadata; matched tasks are
+/// updated in place and keep their ID, agent and engram-only fields.
+/// Relationships that already exist are not stored again.
+pub fn import_jira_issues(
+    path: &Path,
+    config: &JiraImportConfig,
+    agent: &str,
+    storage: &mut dyn Storage,
+) -> Result<JiraImportReport, EngramError> {
+    let content = std::fs::read_to_string(path)?;
+    let issues = match serde_json::from_str::<JiraExport>(&content).map_err(|e| {
+        EngramError::Validation(format!("Invalid Jira export {}: {}", path.display(), e))
+    })? {
+        JiraExport::Search { issues } | JiraExport::Issues(issues) => issues,
+    };
+
+    let mut existing: HashMap<String, Task> = HashMap::new();
+    for generic in storage.get_all(Task::entity_type())? {
+        if let Ok(task) = Task::from_generic(generic) {
+            if let Some(key) = task.metadata.get(JIRA_KEY_METADATA).and_then(Value::as_str) {
+                existing.insert(key.to_string(), task);
+            }
+        }
+    }
+
+    let mut report = JiraImportReport::default();
+    let mut tasks: HashMap<String, Task> = HashMap::new();
+    let mut order = Vec::new();
+    for issue in &issues {
+        report.issues_processed += 1;
+        let fields = &issue.fields;
+        let title = fields
+            .summary
+            .clone()
+            .filter(|s| !s.trim().is_empty())
+            .unwrap_or_else(|| issue.key.clone());
+        let description = fields
+            .description
+            .as_ref()
+            .map(adf_to_markdown)
+            .unwrap_or_default();
+
+        let mut task = match existing.remove(&issue.key) {
+            Some(mut task) => {
+                task.title = title;
+                task.description = description;
+                report.updated.push(issue.key.clone());
+                task
+            }
+            None => {
+                let mut task = Task::new(
+                    title,
+                    description,
+                    agent.to_string(),
+                    TaskPriority::Medium,
+                    None,
+                );
+                if let Some(created) = fields.created.as_deref().and_then(parse_jira_timestamp) {
+                    task.start_time = created;
+                }
+                task.metadata.insert(
+                    JIRA_KEY_METADATA.to_string(),
+                    Value::String(issue.key.clone()),
+                );
+                report.created.push(issue.key.clone());
+                task
+            }
+        };
+        task.priority = map_priority(fields.priority.as_ref());
+        task.status = config.map_status(fields.status.as_ref());
+        task.tags = Vec::new();
+        for label in &fields.labels {
+            task.add_tag(label.clone());
+        }
+        if let Some(issue_type) = &fields.issuetype {
+            task.metadata.insert(
+                JIRA_ISSUE_TYPE_METADATA.to_string(),
+                Value::String(issue_type.name.clone()),
+            );
+        }
+        order.push(issue.key.clone());
+        tasks.insert(issue.key.clone(), task);
+    }
+
+    // Tasks from earlier imports can still be parents or link targets
+    let mut ids: HashMap<String, String> = existing
+        .iter()
+        .map(|(key, task)| (key.clone(), task.id.clone()))
+        .collect();
+    ids.extend(
+        tasks
+            .iter()
+            .map(|(key, task)| (key.clone(), task.id.clone())),
+    );
+
+    let mut known_relationships: HashSet<String> = HashSet::new();
+    for generic in storage.get_all("relationship")? {
+        if let Ok(relationship) = EntityRelationship::from_generic(generic) {
+            known_relationships.insert(relationship_key(
+                &relationship.source_id,
+                &relationship.target_id,
+                &relationship.relationship_type,
+            ));
+        }
+    }
+    let mut relationships: Vec<EntityRelationship> = Vec::new();
+    let mut link = |source_id: &str, target_id: &str, relationship_type: EntityRelationType| {
+        if source_id == target_id
+            || !known_relationships.insert(relationship_key(
+                source_id,
+                target_id,
+                &relationship_type,
+            ))
+        {
+            return;
+        }
+        relationships.push(EntityRelationship::new(
+            Uuid::new_v4().to_string(),
+            agent.to_string(),
+            source_id.to_string(),
+            Task::entity_type().to_string(),
+            target_id.to_string(),
+            Task::entity_type().to_string(),
+            relationship_type,
+        ));
+    };
+
+    let mut parents: Vec<(String, String)> = Vec::new();
+    for issue in &issues {
+        let child_id = &ids[&issue.key];
+        if let Some(parent_key) = issue.fields.parent_key(config) {
+            match ids.get(&parent_key) {
+                Some(parent_id) => {
+                    link(parent_id, child_id, EntityRelationType::Contains);
+                    parents.push((parent_key, issue.key.clone()));
+                }
+                None => report.warnings.push(format!(
+                    "{}: parent {} is not in the export",
+                    issue.key, parent_key
+                )),
+            }
+        }
+
+        for issue_link in &issue.fields.issuelinks {
+            let dependency = config.is_dependency_link(&issue_link.link_type.name);
+            // Each link is listed on both issues; orient it the same way from
+            // either side so the second sighting is recognized as known
+            let (source_key, target_key) =
+                match (&issue_link.outward_issue, &issue_link.inward_issue) {
+                    (Some(outward), _) => (issue.key.as_str(), outward.key.as_str()),
+                    (None, Some(inward)) => (inward.key.as_str(), issue.key.as_str()),
+                    (None, None) => continue,
+                };
+            let (Some(source_id), Some(target_id)) = (ids.get(source_key), ids.get(target_key))
+            else {
+                report.warnings.push(format!(
+                    "{}: linked issue {} is not in the export",
+                    issue.key,
+                    if ids.contains_key(source_key) {
+                        target_key
+                    } else {
+                        source_key
+                    }
+                ));
+                continue;
+            };
+            if dependency {
+                // "A blocks B": B depends on A
+                link(target_id, source_id, EntityRelationType::DependsOn);
+            } else {
+                link(source_id, target_id, EntityRelationType::References);
+            }
+        }
+    }
+
+    for (parent_key, child_key) in parents {
+        let parent_id = ids[&parent_key].clone();
+        let child_id = ids[&child_key].clone();
+        if let Some(child) = tasks.get_mut(&child_key) {
+            child.parent = Some(parent_id);
+        }
+        if let Some(parent) = tasks.get_mut(&parent_key) {
+            parent.add_child(child_id);
+        } else if let Some(parent) = existing.get_mut(&parent_key) {
+            parent.add_child(child_id);
+            order.push(parent_key);
+        }
+    }
+
+    let mut stored = HashSet::new();
+    let mut entities: Vec<GenericEntity> = order
+        .iter()
+        .filter(|key| stored.insert(key.as_str()))
+        .filter_map(|key| tasks.get(key).or_else(|| existing.get(key)))
+        .map(Task::to_generic)
+        .collect();
+    report.relationships_created = relationships.len();
+    entities.extend(relationships.iter().map(EntityRelationship::to_generic));
+    storage.bulk_store(&entities)?;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::MemoryStorage;
+    use tempfile::TempDir;
+
+    fn export() -> Value {
+        serde_json::json!({
+            "startAt": 0,
+            "total": 3,
+            "issues": [
+                {
+                    "key": "PROJ-1",
+                    "fields": {
+                        "summary": "Checkout epic",
+                        "issuetype": { "name": "Epic" },
+                        "status": { "name": "In Progress", "statusCategory": { "key": "indeterminate" } },
+                        "priority": { "name": "Highest" }
+                    }
+                },
+                {
+                    "key": "PROJ-2",
+                    "fields": {
+                        "summary": "Payment form",
+                        "description": {
+                            "type": "doc",
+                            "version": 1,
+                            "content": [
+                                { "type": "heading", "attrs": { "level": 2 }, "content": [{ "type": "text", "text": "Goal" }] },
+                                { "type": "paragraph", "content": [
+                                    { "type": "text", "text": "Use " },
+                                    { "type": "text", "text": "Stripe", "marks": [{ "type": "strong" }] },
+                                    { "type": "text", "text": " elements" }
+                                ] },
+                                { "type": "bulletList", "content": [
+                                    { "type": "listItem", "content": [{ "type": "paragraph", "content": [{ "type": "text", "text": "card" }] }] },
+                                    { "type": "listItem", "content": [{ "type": "paragraph", "content": [{ "type": "text", "text": "docs", "marks": [{ "type": "link", "attrs": { "href": "https://example.com" } }] }] }] }
+                                ] }
+                            ]
+                        },
+                        "labels": ["frontend", "payments"],
+                        "status": { "name": "Code Review", "statusCategory": { "key": "indeterminate" } },
+                        "priority": { "name": "Low" },
+                        "parent": { "key": "PROJ-1" },
+                        "created": "2024-01-15T10:30:00.000+0000",
+                        "issuelinks": [
+                            { "type": { "name": "Blocks" }, "inwardIssue": { "key": "PROJ-3" } }
+                        ]
+                    }
+                },
+                {
+                    "key": "PROJ-3",
+                    "fields": {
+                        "summary": "Payment API",
+                        "status": { "name": "Done", "statusCategory": { "key": "done" } },
+                        "customfield_10014": "PROJ-1",
+                        "issuelinks": [
+                            { "type": { "name": "Blocks" }, "outwardIssue": { "key": "PROJ-2" } },
+                            { "type": { "name": "Relates" }, "outwardIssue": { "key": "PROJ-9" } }
+                        ]
+                    }
+                }
+            ]
+        })
+    }
+
+    fn task_by_key(storage: &MemoryStorage, key: &str) -> Task {
+        storage
+            .get_all("task")
+            .unwrap()
+            .into_iter()
+            .filter_map(|g| Task::from_generic(g).ok())
+            .find(|t| t.metadata.get(JIRA_KEY_METADATA) == Some(&Value::String(key.to_string())))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_adf_to_markdown() {
+        let description = &export()["issues"][1]["fields"]["description"];
+        assert_eq!(
+            adf_to_markdown(description),
+            "## Goal\n\nUse **Stripe** elements\n\n- card\n- [docs](https://example.com)"
+        );
+        assert_eq!(
+            adf_to_markdown(&Value::String("h1. Wiki".into())),
+            "h1. Wiki"
+        );
+    }
+
+    #[test]
+    fn test_import_jira_is_idempotent() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("jira.json");
+        std::fs::write(&path, export().to_string()).unwrap();
+        let mut config = JiraImportConfig::default();
+        config
+            .status
+            .insert("code review".to_string(), TaskStatus::Blocked);
+        let mut storage = MemoryStorage::new("default");
+
+        let report = import_jira_issues(&path, &config, "importer", &mut storage).unwrap();
+        assert_eq!(report.created, vec!["PROJ-1", "PROJ-2", "PROJ-3"]);
+        assert_eq!(report.relationships_created, 3);
+        assert_eq!(report.warnings.len(), 1);
+        assert!(report.warnings[0].contains("PROJ-9"));
+
+        let epic = task_by_key(&storage, "PROJ-1");
+        let form = task_by_key(&storage, "PROJ-2");
+        let api = task_by_key(&storage, "PROJ-3");
+        assert_eq!(epic.priority, TaskPriority::Critical);
+        assert_eq!(epic.status, TaskStatus::InProgress);
+        assert_eq!(form.status, TaskStatus::Blocked);
+        assert_eq!(form.priority, TaskPriority::Low);
+        assert_eq!(form.tags, vec!["frontend", "payments"]);
+        assert_eq!(form.start_time.format("%Y-%m-%d").to_string(), "2024-01-15");
+        assert_eq!(api.status, TaskStatus::Done);
+        assert_eq!(form.parent.as_deref(), Some(epic.id.as_str()));
+        assert_eq!(api.parent.as_deref(), Some(epic.id.as_str()));
+        assert_eq!(epic.children.len(), 2);
+
+        let relationships: Vec<EntityRelationship> = storage
+            .get_all("relationship")
+            .unwrap()
+            .into_iter()
+            .map(|g| EntityRelationship::from_generic(g).unwrap())
+            .collect();
+        assert!(relationships.iter().any(|r| r.source_id == form.id
+            && r.target_id == api.id
+            && r.relationship_type == EntityRelationType::DependsOn));
+        assert_eq!(
+            relationships
+                .iter()
+                .filter(|r| r.relationship_type == EntityRelationType::Contains)
+                .count(),
+            2
+        );
+
+        // Re-running updates the same tasks and adds no relationships
+        let mut renamed = export();
+        renamed["issues"][2]["fields"]["summary"] = Value::String("Payments API".into());
+        std::fs::write(&path, renamed.to_string()).unwrap();
+        let report = import_jira_issues(&path, &config, "importer", &mut storage).unwrap();
+        assert!(report.created.is_empty());
+        assert_eq!(report.updated.len(), 3);
+        assert_eq!(report.relationships_created, 0);
+        assert_eq!(storage.get_all("task").unwrap().len(), 3);
+        assert_eq!(storage.get_all("relationship").unwrap().len(), 3);
+        let api_again = task_by_key(&storage, "PROJ-3");
+        assert_eq!(api_again.id, api.id);
+        assert_eq!(api_again.title, "Payments API");
+        assert_eq!(task_by_key(&storage, "PROJ-1").children.len(), 2);
+    }
+}
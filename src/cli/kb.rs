@@ -0,0 +1,199 @@
+//! Knowledge base command implementations
+
+use crate::cli::identity::resolve_agent_filter;
+use crate::entities::{
+    build_knowledge_base, collect_kb_items, search_knowledge_base, KbItem, KnowledgeBaseIndex,
+};
+use crate::error::EngramError;
+use crate::storage::Storage;
+use clap::Subcommand;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Knowledge base commands
+#[derive(Debug, Subcommand)]
+pub enum KbCommands {
+    /// Build a structured knowledge base from knowledge, context and reasoning
+    ///
+    ///EXAMPLES:
+    ///  engram kb build --output kb.md
+    ///  engram kb build --output kb.md --agent default --title "Payments KB"
+    Build {
+        /// Markdown output file (prints to stdout when omitted)
+        #[arg(long, short)]
+        output: Option<String>,
+
+        /// Only include entities from this agent
+        #[arg(long, short)]
+        agent: Option<String>,
+
+        /// Knowledge base title
+        #[arg(long, short, default_value = "Knowledge Base")]
+        title: String,
+    },
+    /// Search the knowledge base
+    ///
+    ///EXAMPLES:
+    ///  engram kb search "OAuth implementation"
+    ///  engram kb search "connection pool" --agent default --limit 5
+    Search {
+        /// Search terms
+        query: String,
+
+        /// Only include entities from this agent
+        #[arg(long, short)]
+        agent: Option<String>,
+
+        /// Maximum number of results
+        #[arg(long, short, default_value = "10")]
+        limit: usize,
+    },
+}
+
+/// Render an index as a Markdown document
+pub fn render_markdown(index: &KnowledgeBaseIndex, items: &[KbItem]) -> String {
+    let by_id: HashMap<&str, &KbItem> = items.iter().map(|i| (i.id.as_str(), i)).collect();
+    let mut doc = String::new();
+
+    let _ = writeln!(doc, "# {}\n", index.title);
+    let _ = writeln!(
+        doc,
+        "_Generated {} from {} entities in {} sections._\n",
+        index.created_at.format("%Y-%m-%d %H:%M UTC"),
+        index.total_entities,
+        index.sections.len()
+    );
+
+    let _ = writeln!(doc, "## Contents\n");
+    for section in &index.sections {
+        let count =
+            section.knowledge_items.len() + section.contexts.len() + section.reasoning_refs.len();
+        let _ = writeln!(doc, "- [{}](#{}) ({})", section.tag, section.tag, count);
+    }
+
+    for section in &index.sections {
+        let _ = writeln!(doc, "\n## {}", section.tag);
+        for (heading, ids) in [
+            ("Knowledge", &section.knowledge_items),
+            ("Context", &section.contexts),
+            ("Reasoning", &section.reasoning_refs),
+        ] {
+            if ids.is_empty() {
+                continue;
+            }
+            let _ = writeln!(doc, "\n### {}\n", heading);
+            for id in ids {
+                let Some(item) = by_id.get(id.as_str()) else {
+                    continue;
+                };
+                let _ = write!(doc, "- **{}** (`{}`)", item.title, id);
+                let summary = item.body.lines().next().unwrap_or("").trim();
+                if !summary.is_empty() {
+                    let _ = write!(doc, ": {}", summary);
+                }
+                doc.push('\n');
+            }
+        }
+    }
+
+    doc
+}
+
+/// Handle `engram kb`
+pub fn handle_kb_command<S: Storage>(
+    storage: &S,
+    command: KbCommands,
+    json: bool,
+) -> Result<(), EngramError> {
+    match command {
+        KbCommands::Build {
+            output,
+            agent,
+            title,
+        } => {
+            let agent = resolve_agent_filter(agent);
+            let index = build_knowledge_base(storage, agent.as_deref(), &title)?;
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&index)?);
+                return Ok(());
+            }
+
+            let items = collect_kb_items(storage, agent.as_deref())?;
+            let markdown = render_markdown(&index, &items);
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, markdown)?;
+                    println!(
+                        "Knowledge base written to {} ({} entities, {} sections)",
+                        path,
+                        index.total_entities,
+                        index.sections.len()
+                    );
+                }
+                None => print!("{}", markdown),
+            }
+        }
+        KbCommands::Search {
+            query,
+            agent,
+            limit,
+        } => {
+            let agent = resolve_agent_filter(agent);
+            let index = build_knowledge_base(storage, agent.as_deref(), "search")?;
+            let items = collect_kb_items(storage, agent.as_deref())?;
+            let mut hits = search_knowledge_base(&items, &index, &query);
+            hits.truncate(limit);
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&hits)?);
+            } else if hits.is_empty() {
+                println!("No knowledge base entries match '{}'", query);
+            } else {
+                for hit in &hits {
+                    println!(
+                        "[{}] {:?} {} ({})",
+                        hit.section,
+                        hit.kind,
+                        hit.title,
+                        &hit.id[..hit.id.len().min(8)]
+                    );
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{Entity, Knowledge, KnowledgeType};
+    use crate::storage::MemoryStorage;
+
+    #[test]
+    fn test_render_markdown_lists_sections_and_items() {
+        let mut storage = MemoryStorage::new("default");
+        let mut k = Knowledge::new(
+            "Token lifetime".to_string(),
+            "Access tokens expire after 1h".to_string(),
+            KnowledgeType::Fact,
+            0.9,
+            "default".to_string(),
+        );
+        k.add_tag("auth".to_string());
+        storage.store(&k.to_generic()).unwrap();
+
+        let index = build_knowledge_base(&storage, None, "Team KB").unwrap();
+        let items = collect_kb_items(&storage, None).unwrap();
+        let doc = render_markdown(&index, &items);
+
+        assert!(doc.starts_with("# Team KB\n"));
+        assert!(doc.contains("- [auth](#auth) (1)"));
+        assert!(doc.contains("## auth\n\n### Knowledge\n"));
+        assert!(doc.contains(&format!(
+            "- **Token lifetime** (`{}`): Access tokens expire after 1h",
+            k.id
+        )));
+    }
+}
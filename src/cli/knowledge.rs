@@ -1,6 +1,9 @@
 //! Knowledge command implementations
 
-use crate::entities::{Entity, Knowledge, KnowledgeType};
+use crate::cli::identity::resolve_agent;
+use crate::entities::{
+    merge_knowledge_items, Entity, Knowledge, KnowledgeMergeConfig, KnowledgeType,
+};
 use crate::error::EngramError;
 use crate::storage::Storage;
 use clap::Subcommand;
@@ -50,6 +53,10 @@ pub enum KnowledgeCommands {
         #[arg(long, short = 'f', default_value = "0.8")]
         confidence: f64,
 
+        /// Clamp an out-of-range confidence into [0.0, 1.0] instead of rejecting it
+        #[arg(long)]
+        clamp: bool,
+
         /// Source of this knowledge
         #[arg(long, short)]
         source: Option<String>,
@@ -85,6 +92,10 @@ pub enum KnowledgeCommands {
         /// JSON file path (requires --json)
         #[arg(long, requires = "json")]
         json_file: Option<String>,
+
+        /// Create even if open knowledge with a similar title exists
+        #[arg(long)]
+        allow_duplicate: bool,
     },
     /// List knowledge items
     ///
@@ -117,6 +128,9 @@ pub enum KnowledgeCommands {
         /// Offset for pagination
         #[arg(long, short)]
         offset: Option<usize>,
+
+        #[command(flatten)]
+        sort: SortArgs,
     },
     /// Show knowledge details
     ///
@@ -146,6 +160,35 @@ pub enum KnowledgeCommands {
         /// New value
         #[arg(long, short)]
         value: String,
+
+        /// Clamp an out-of-range confidence into [0.0, 1.0] instead of rejecting it
+        #[arg(long)]
+        clamp: bool,
+    },
+    /// Record a verification of a knowledge item and update its confidence
+    ///
+    ///EXAMPLES:
+    ///  engram knowledge verify <UUID> --confidence 0.95 --source "Checked against API docs v3"
+    ///  engram knowledge verify <UUID> -f 0.4 -s "Load test showed 80 req/s" -a reviewer
+    Verify {
+        /// Knowledge item ID
+        id: String,
+
+        /// Confidence after verification (0.0 to 1.0)
+        #[arg(long, short = 'f')]
+        confidence: f64,
+
+        /// What the knowledge was verified against
+        #[arg(long, short)]
+        source: String,
+
+        /// Verifying agent
+        #[arg(long, short)]
+        agent: Option<String>,
+
+        /// Clamp an out-of-range confidence into [0.0, 1.0] instead of rejecting it
+        #[arg(long)]
+        clamp: bool,
     },
     /// Delete knowledge item
     Delete {
@@ -153,6 +196,34 @@ pub enum KnowledgeCommands {
         #[arg(long, short)]
         id: String,
     },
+    /// Merge near-duplicate knowledge items into one
+    ///
+    /// Relationships of the merged items move to the primary; the merged
+    /// items are kept with `merged_into` set and a low confidence.
+    ///
+    ///EXAMPLES:
+    ///  engram knowledge merge <PRIMARY> <UUID> <UUID>
+    ///  engram knowledge merge <PRIMARY> <UUID> --confidence average --content concatenate --tags intersection
+    Merge {
+        /// Knowledge item that is kept
+        primary_id: String,
+
+        /// Knowledge items merged into the primary
+        #[arg(required = true)]
+        secondary_ids: Vec<String>,
+
+        /// How to combine confidence
+        #[arg(long, default_value = "max", value_parser = ["max", "average", "weighted"])]
+        confidence: String,
+
+        /// How to combine content
+        #[arg(long, default_value = "keep-primary", value_parser = ["keep-primary", "concatenate", "longest"])]
+        content: String,
+
+        /// How to combine tags
+        #[arg(long, default_value = "union", value_parser = ["union", "intersection"])]
+        tags: String,
+    },
 }
 
 /// Read from stdin
@@ -191,20 +262,14 @@ fn parse_knowledge_type(type_str: &str) -> Result<KnowledgeType, EngramError> {
 fn create_knowledge_from_input<S: Storage>(
     storage: &mut S,
     input: KnowledgeInput,
+    clamp: bool,
 ) -> Result<(), EngramError> {
-    let agent = input.agent.unwrap_or_else(|| "default".to_string());
+    let agent = resolve_agent(input.agent);
     let content = input.content.unwrap_or_default();
-    let confidence = input.confidence.unwrap_or(0.8);
+    let confidence = Knowledge::normalize_confidence(input.confidence.unwrap_or(0.8), clamp)?;
     let knowledge_type_str = input.knowledge_type.unwrap_or_else(|| "fact".to_string());
     let knowledge_type = parse_knowledge_type(&knowledge_type_str)?;
 
-    // Validate confidence
-    if confidence < 0.0 || confidence > 1.0 {
-        return Err(EngramError::Validation(
-            "Confidence must be between 0.0 and 1.0".to_string(),
-        ));
-    }
-
     let mut knowledge = Knowledge::new(input.title, content, knowledge_type, confidence, agent);
 
     // Set optional fields
@@ -232,6 +297,7 @@ pub fn create_knowledge<S: Storage>(
     content: Option<String>,
     knowledge_type: String,
     confidence: f64,
+    clamp: bool,
     source: Option<String>,
     agent: Option<String>,
     tags: Option<String>,
@@ -241,6 +307,7 @@ pub fn create_knowledge<S: Storage>(
     content_file: Option<String>,
     json: bool,
     json_file: Option<String>,
+    allow_duplicate: bool,
 ) -> Result<(), EngramError> {
     // Handle JSON input first
     if json {
@@ -273,7 +340,14 @@ pub fn create_knowledge<S: Storage>(
             ))
         })?;
 
-        return create_knowledge_from_input(storage, input);
+        check_duplicate(
+            storage,
+            "knowledge",
+            &input.title,
+            Some(input.content.as_deref().unwrap_or_default()),
+            allow_duplicate,
+        )?;
+        return create_knowledge_from_input(storage, input, clamp);
     }
 
     // Resolve title
@@ -298,17 +372,14 @@ pub fn create_knowledge<S: Storage>(
         content.unwrap_or_default()
     };
 
+    check_duplicate(storage, "knowledge", &final_title, None, allow_duplicate)?;
+
     // Parse knowledge type
     let knowledge_type_enum = parse_knowledge_type(&knowledge_type)?;
 
-    // Validate confidence
-    if confidence < 0.0 || confidence > 1.0 {
-        return Err(EngramError::Validation(
-            "Confidence must be between 0.0 and 1.0".to_string(),
-        ));
-    }
+    let confidence = Knowledge::normalize_confidence(confidence, clamp)?;
 
-    let agent_name = agent.unwrap_or_else(|| "default".to_string());
+    let agent_name = resolve_agent(agent);
 
     let mut knowledge = Knowledge::new(
         final_title,
@@ -336,9 +407,24 @@ pub fn create_knowledge<S: Storage>(
     Ok(())
 }
 
-use crate::cli::utils::{create_table, truncate};
+use crate::cli::utils::{check_duplicate, create_table, truncate, SortArgs};
 use prettytable::row;
 
+/// Width of the confidence bar in cells
+const CONFIDENCE_BAR_WIDTH: usize = 10;
+
+/// Render a confidence as a bar with its percentage, e.g. `████████░░ 80%`
+fn confidence_bar(confidence: f64) -> String {
+    let confidence = confidence.clamp(0.0, 1.0);
+    let filled = (confidence * CONFIDENCE_BAR_WIDTH as f64).round() as usize;
+    format!(
+        "{}{} {:.0}%",
+        "█".repeat(filled),
+        "░".repeat(CONFIDENCE_BAR_WIDTH - filled),
+        confidence * 100.0
+    )
+}
+
 /// List knowledge items
 pub fn list_knowledge<S: Storage>(
     storage: &S,
@@ -347,6 +433,7 @@ pub fn list_knowledge<S: Storage>(
     limit: Option<usize>,
     all: bool,
     offset: Option<usize>,
+    sort: &SortArgs,
 ) -> Result<(), EngramError> {
     let ids = storage.list_ids(Knowledge::entity_type())?;
 
@@ -373,6 +460,7 @@ pub fn list_knowledge<S: Storage>(
         }
     }
 
+    items = sort.sort(items)?;
     let total_count = items.len();
 
     if let Some(off) = offset {
@@ -403,7 +491,7 @@ pub fn list_knowledge<S: Storage>(
             &knowledge.id[..8],
             truncate(&knowledge.title, 40),
             type_str,
-            format!("{:.2}", knowledge.confidence),
+            confidence_bar(knowledge.confidence),
             truncate(&knowledge.agent, 15),
             truncate(&source_str, 20),
             knowledge.updated_at.format("%Y-%m-%d")
@@ -427,8 +515,9 @@ pub fn list_knowledge<S: Storage>(
 pub fn show_knowledge<S: Storage>(storage: &S, id: &str) -> Result<(), EngramError> {
     let entity = storage
         .get(id, Knowledge::entity_type())?
-        .ok_or_else(|| EngramError::NotFound(format!("Knowledge not found: {}", id)))?;
+        .ok_or_else(|| EngramError::not_found("knowledge", id))?;
 
+    let modification = entity.modification();
     let knowledge =
         Knowledge::from_generic(entity).map_err(|e| EngramError::Validation(e.to_string()))?;
 
@@ -438,8 +527,11 @@ pub fn show_knowledge<S: Storage>(storage: &S, id: &str) -> Result<(), EngramErr
     println!("Title: {}", knowledge.title);
     println!("Content: {}", knowledge.content);
     println!("Type: {:?}", knowledge.knowledge_type);
-    println!("Confidence: {:.2}", knowledge.confidence);
+    println!("Confidence: {}", confidence_bar(knowledge.confidence));
     println!("Agent: {}", knowledge.agent);
+    if let Some(modified) = modification.summary() {
+        println!("Last Modified By: {}", modified);
+    }
     println!("Created: {}", knowledge.created_at);
     println!("Updated: {}", knowledge.updated_at);
 
@@ -468,6 +560,21 @@ pub fn show_knowledge<S: Storage>(storage: &S, id: &str) -> Result<(), EngramErr
         println!("Last Used: {}", last_used);
     }
 
+    let verifications = knowledge.verifications();
+    if !verifications.is_empty() {
+        println!("Verifications:");
+        for verification in &verifications {
+            println!(
+                "  {} by {}: {:.0}% -> {:.0}% ({})",
+                verification.verified_at.format("%Y-%m-%d %H:%M"),
+                verification.verified_by,
+                verification.previous_confidence * 100.0,
+                verification.confidence * 100.0,
+                verification.source
+            );
+        }
+    }
+
     Ok(())
 }
 
@@ -477,10 +584,11 @@ pub fn update_knowledge<S: Storage>(
     id: &str,
     field: &str,
     value: &str,
+    clamp: bool,
 ) -> Result<(), EngramError> {
     let entity = storage
         .get(id, Knowledge::entity_type())?
-        .ok_or_else(|| EngramError::NotFound(format!("Knowledge not found: {}", id)))?;
+        .ok_or_else(|| EngramError::not_found("knowledge", id))?;
 
     let mut knowledge =
         Knowledge::from_generic(entity).map_err(|e| EngramError::Validation(e.to_string()))?;
@@ -493,11 +601,7 @@ pub fn update_knowledge<S: Storage>(
             let confidence: f64 = value
                 .parse()
                 .map_err(|_| EngramError::Validation("Confidence must be a number".to_string()))?;
-            if confidence < 0.0 || confidence > 1.0 {
-                return Err(EngramError::Validation(
-                    "Confidence must be between 0.0 and 1.0".to_string(),
-                ));
-            }
+            let confidence = Knowledge::normalize_confidence(confidence, clamp)?;
             knowledge.update_content(knowledge.content.clone(), confidence);
         }
         "type" => {
@@ -523,6 +627,67 @@ pub fn update_knowledge<S: Storage>(
     Ok(())
 }
 
+/// Record a verification of a knowledge item and update its confidence
+pub fn verify_knowledge<S: Storage>(
+    storage: &mut S,
+    id: &str,
+    confidence: f64,
+    source: String,
+    agent: Option<String>,
+    clamp: bool,
+) -> Result<(), EngramError> {
+    let confidence = Knowledge::normalize_confidence(confidence, clamp)?;
+    if source.trim().is_empty() {
+        return Err(EngramError::Validation(
+            "Verification source cannot be empty".to_string(),
+        ));
+    }
+
+    let entity = storage
+        .get(id, Knowledge::entity_type())?
+        .ok_or_else(|| EngramError::not_found("knowledge", id))?;
+
+    let mut knowledge =
+        Knowledge::from_generic(entity).map_err(|e| EngramError::Validation(e.to_string()))?;
+
+    let previous = knowledge.confidence;
+    knowledge.record_verification(confidence, resolve_agent(agent), source);
+    storage.store(&knowledge.to_generic())?;
+
+    println!(
+        "Knowledge verified: {} ({} -> {})",
+        id,
+        confidence_bar(previous),
+        confidence_bar(knowledge.confidence)
+    );
+    Ok(())
+}
+
+/// Merge knowledge items into `primary_id`
+pub fn merge_knowledge<S: Storage>(
+    storage: &mut S,
+    primary_id: &str,
+    secondary_ids: &[String],
+    confidence: &str,
+    content: &str,
+    tags: &str,
+) -> Result<(), EngramError> {
+    let config = KnowledgeMergeConfig {
+        confidence_strategy: confidence.parse().map_err(EngramError::Validation)?,
+        content_strategy: content.parse().map_err(EngramError::Validation)?,
+        tag_strategy: tags.parse().map_err(EngramError::Validation)?,
+    };
+    let merged = merge_knowledge_items(storage, primary_id, secondary_ids, config)?;
+    println!(
+        "Merged {} knowledge item(s) into {} ({}, used {} times)",
+        secondary_ids.len(),
+        merged.id,
+        confidence_bar(merged.confidence),
+        merged.usage_count
+    );
+    Ok(())
+}
+
 /// Delete knowledge item
 pub fn delete_knowledge<S: Storage>(storage: &mut S, id: &str) -> Result<(), EngramError> {
     storage.delete(id, Knowledge::entity_type())?;
@@ -548,6 +713,7 @@ mod tests {
             Some("Water is wet".to_string()),
             "fact".to_string(),
             0.9,
+            false,
             Some("Observation".to_string()),
             None,
             None,
@@ -557,6 +723,7 @@ mod tests {
             None,
             false,
             None,
+            false,
         );
         assert!(result.is_ok());
 
@@ -581,6 +748,7 @@ mod tests {
             None,
             "fact".to_string(),
             0.8,
+            false,
             None,
             None,
             None,
@@ -590,6 +758,7 @@ mod tests {
             None,
             false,
             None,
+            false,
         );
         assert!(matches!(result, Err(EngramError::Validation(_))));
 
@@ -600,6 +769,7 @@ mod tests {
             None,
             "invalid_type".to_string(),
             0.8,
+            false,
             None,
             None,
             None,
@@ -609,6 +779,7 @@ mod tests {
             None,
             false,
             None,
+            false,
         );
         assert!(matches!(result, Err(EngramError::Validation(_))));
 
@@ -619,6 +790,7 @@ mod tests {
             None,
             "fact".to_string(),
             1.5,
+            false,
             None,
             None,
             None,
@@ -628,6 +800,7 @@ mod tests {
             None,
             false,
             None,
+            false,
         );
         assert!(matches!(result, Err(EngramError::Validation(_))));
     }
@@ -641,6 +814,7 @@ mod tests {
             None,
             "fact".to_string(),
             0.8,
+            false,
             None,
             None,
             None,
@@ -650,6 +824,7 @@ mod tests {
             None,
             false,
             None,
+            false,
         )
         .unwrap();
 
@@ -657,13 +832,13 @@ mod tests {
         let id = &ids[0];
 
         // Update content
-        update_knowledge(&mut storage, id, "content", "New content").unwrap();
+        update_knowledge(&mut storage, id, "content", "New content", false).unwrap();
         let entity = storage.get(id, "knowledge").unwrap().unwrap();
         let knowledge = Knowledge::from_generic(entity).unwrap();
         assert_eq!(knowledge.content, "New content");
 
         // Update confidence
-        update_knowledge(&mut storage, id, "confidence", "0.95").unwrap();
+        update_knowledge(&mut storage, id, "confidence", "0.95", false).unwrap();
         let entity = storage.get(id, "knowledge").unwrap().unwrap();
         let knowledge = Knowledge::from_generic(entity).unwrap();
         assert_eq!(knowledge.confidence, 0.95);
@@ -678,6 +853,7 @@ mod tests {
             None,
             "fact".to_string(),
             0.8,
+            false,
             None,
             None,
             None,
@@ -687,6 +863,7 @@ mod tests {
             None,
             false,
             None,
+            false,
         )
         .unwrap();
 
@@ -708,6 +885,7 @@ mod tests {
             None,
             "fact".to_string(),
             0.8,
+            false,
             None,
             None,
             None,
@@ -717,6 +895,7 @@ mod tests {
             None,
             false,
             None,
+            false,
         )
         .unwrap();
 
@@ -726,6 +905,7 @@ mod tests {
             None,
             "rule".to_string(),
             0.8,
+            false,
             None,
             None,
             None,
@@ -735,13 +915,21 @@ mod tests {
             None,
             false,
             None,
+            false,
         )
         .unwrap();
 
         // Just verify it runs without error (output is to stdout)
-        assert!(
-            list_knowledge(&storage, None, Some("fact".to_string()), None, false, None).is_ok()
-        );
+        assert!(list_knowledge(
+            &storage,
+            None,
+            Some("fact".to_string()),
+            None,
+            false,
+            None,
+            &SortArgs::default()
+        )
+        .is_ok());
     }
 
     #[test]
@@ -753,6 +941,7 @@ mod tests {
             Some("Content to show".to_string()),
             "fact".to_string(),
             0.8,
+            false,
             None,
             None,
             None,
@@ -762,6 +951,7 @@ mod tests {
             None,
             false,
             None,
+            false,
         )
         .unwrap();
 
@@ -775,14 +965,14 @@ mod tests {
     fn test_show_knowledge_not_found() {
         let storage = create_test_storage();
         let result = show_knowledge(&storage, "missing-id");
-        assert!(matches!(result, Err(EngramError::NotFound(_))));
+        assert!(matches!(result, Err(EngramError::NotFound { .. })));
     }
 
     #[test]
     fn test_update_knowledge_not_found() {
         let mut storage = create_test_storage();
-        let result = update_knowledge(&mut storage, "missing-id", "content", "new content");
-        assert!(matches!(result, Err(EngramError::NotFound(_))));
+        let result = update_knowledge(&mut storage, "missing-id", "content", "new content", false);
+        assert!(matches!(result, Err(EngramError::NotFound { .. })));
     }
 
     #[test]
@@ -794,6 +984,7 @@ mod tests {
             None,
             "fact".to_string(),
             0.8,
+            false,
             None,
             None,
             None,
@@ -803,13 +994,14 @@ mod tests {
             None,
             false,
             None,
+            false,
         )
         .unwrap();
 
         let ids = storage.list_ids("knowledge").unwrap();
         let id = &ids[0];
 
-        let result = update_knowledge(&mut storage, id, "invalid_field", "value");
+        let result = update_knowledge(&mut storage, id, "invalid_field", "value", false);
         assert!(matches!(result, Err(EngramError::Validation(_))));
     }
 
@@ -822,6 +1014,7 @@ mod tests {
             None,
             "fact".to_string(),
             0.8,
+            false,
             None,
             None,
             None,
@@ -831,13 +1024,14 @@ mod tests {
             None,
             false,
             None,
+            false,
         )
         .unwrap();
 
         let ids = storage.list_ids("knowledge").unwrap();
         let id = &ids[0];
 
-        let result = update_knowledge(&mut storage, id, "confidence", "2.0");
+        let result = update_knowledge(&mut storage, id, "confidence", "2.0", false);
         assert!(matches!(result, Err(EngramError::Validation(_))));
     }
 
@@ -858,7 +1052,7 @@ mod tests {
         // But wait, the MemoryStorage delete implementation:
         //      if self.data.remove(key).is_none() { return Err(EngramError::NotFound(...)); }
         // Yes, it returns NotFound.
-        assert!(matches!(result, Err(EngramError::NotFound(_))));
+        assert!(matches!(result, Err(EngramError::NotFound { .. })));
     }
 
     #[test]
@@ -870,6 +1064,7 @@ mod tests {
             None,
             "fact".to_string(),
             0.8,
+            false,
             None,
             None,
             None,
@@ -879,13 +1074,14 @@ mod tests {
             None,
             false,
             None,
+            false,
         )
         .unwrap();
 
         let ids = storage.list_ids("knowledge").unwrap();
         let id = &ids[0];
 
-        let result = update_knowledge(&mut storage, id, "type", "invalid_type");
+        let result = update_knowledge(&mut storage, id, "type", "invalid_type", false);
         assert!(matches!(result, Err(EngramError::Validation(_))));
     }
 
@@ -898,6 +1094,7 @@ mod tests {
             None,
             "fact".to_string(),
             0.8,
+            false,
             None,
             None,
             None,
@@ -907,13 +1104,14 @@ mod tests {
             None,
             false,
             None,
+            false,
         )
         .unwrap();
 
         let ids = storage.list_ids("knowledge").unwrap();
         let id = &ids[0];
 
-        update_knowledge(&mut storage, id, "source", "New Source").unwrap();
+        update_knowledge(&mut storage, id, "source", "New Source", false).unwrap();
         let entity = storage.get(id, "knowledge").unwrap().unwrap();
         let knowledge = Knowledge::from_generic(entity).unwrap();
         assert_eq!(knowledge.source, Some("New Source".to_string()));
@@ -928,6 +1126,7 @@ mod tests {
             None,
             "fact".to_string(),
             0.8,
+            false,
             None,
             None,
             None,
@@ -937,13 +1136,98 @@ mod tests {
             None,
             false,
             None,
+            false,
         )
         .unwrap();
 
         let ids = storage.list_ids("knowledge").unwrap();
         let id = &ids[0];
 
-        let result = update_knowledge(&mut storage, id, "confidence", "not_a_number");
+        let result = update_knowledge(&mut storage, id, "confidence", "not_a_number", false);
+        assert!(matches!(result, Err(EngramError::Validation(_))));
+    }
+
+    #[test]
+    fn test_create_knowledge_clamps_confidence_on_request() {
+        let mut storage = create_test_storage();
+        let create = |storage: &mut MemoryStorage, confidence: f64, clamp: bool| {
+            create_knowledge(
+                storage,
+                Some("Clamped".to_string()),
+                None,
+                "fact".to_string(),
+                confidence,
+                clamp,
+                None,
+                None,
+                None,
+                false,
+                None,
+                false,
+                None,
+                false,
+                None,
+                false,
+            )
+        };
+
+        assert!(matches!(
+            create(&mut storage, 7.0, false),
+            Err(EngramError::Validation(_))
+        ));
+        assert!(storage.list_ids("knowledge").unwrap().is_empty());
+
+        create(&mut storage, 7.0, true).unwrap();
+        let ids = storage.list_ids("knowledge").unwrap();
+        let entity = storage.get(&ids[0], "knowledge").unwrap().unwrap();
+        assert_eq!(Knowledge::from_generic(entity).unwrap().confidence, 1.0);
+    }
+
+    #[test]
+    fn test_verify_knowledge_records_history() {
+        let mut storage = create_test_storage();
+        let knowledge = Knowledge::new(
+            "Rate limit".to_string(),
+            "100 req/s".to_string(),
+            KnowledgeType::Fact,
+            0.6,
+            "default".to_string(),
+        );
+        storage.store(&knowledge.to_generic()).unwrap();
+
+        verify_knowledge(
+            &mut storage,
+            &knowledge.id,
+            0.95,
+            "API docs".to_string(),
+            Some("reviewer".to_string()),
+            false,
+        )
+        .unwrap();
+
+        let entity = storage.get(&knowledge.id, "knowledge").unwrap().unwrap();
+        let verified = Knowledge::from_generic(entity).unwrap();
+        assert_eq!(verified.confidence, 0.95);
+        let history = verified.verifications();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].verified_by, "reviewer");
+        assert_eq!(history[0].previous_confidence, 0.6);
+
+        let result = verify_knowledge(
+            &mut storage,
+            &knowledge.id,
+            1.2,
+            "API docs".to_string(),
+            None,
+            false,
+        );
         assert!(matches!(result, Err(EngramError::Validation(_))));
     }
+
+    #[test]
+    fn test_confidence_bar() {
+        assert_eq!(confidence_bar(0.8), "████████░░ 80%");
+        assert_eq!(confidence_bar(0.0), "░░░░░░░░░░ 0%");
+        assert_eq!(confidence_bar(1.0), "██████████ 100%");
+    }
 }
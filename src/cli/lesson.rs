@@ -1,5 +1,6 @@
 //! Lesson command implementations
 
+use crate::cli::identity::resolve_agent;
 use crate::entities::{Entity, Lesson, LessonCategory, LessonSeverity};
 use crate::error::EngramError;
 use crate::storage::Storage;
@@ -75,6 +76,9 @@ pub enum LessonCommands {
         /// Offset for pagination
         #[arg(long, short)]
         offset: Option<usize>,
+
+        #[command(flatten)]
+        sort: SortArgs,
     },
     /// Show lesson details
     Show {
@@ -156,7 +160,7 @@ pub fn create_lesson<S: Storage>(
 ) -> Result<(), EngramError> {
     let cat = parse_category(&category)?;
     let sev = parse_severity(&severity)?;
-    let agent_name = agent.unwrap_or_else(|| "default".to_string());
+    let agent_name = resolve_agent(agent);
 
     let mut lesson = Lesson::new(
         title,
@@ -184,10 +188,11 @@ pub fn create_lesson<S: Storage>(
     Ok(())
 }
 
-use crate::cli::utils::{create_table, truncate};
+use crate::cli::utils::{create_table, truncate, SortArgs};
 use prettytable::row;
 
 /// List lessons
+#[allow(clippy::too_many_arguments)]
 pub fn list_lessons<S: Storage>(
     storage: &S,
     agent: Option<String>,
@@ -197,6 +202,7 @@ pub fn list_lessons<S: Storage>(
     limit: Option<usize>,
     all: bool,
     offset: Option<usize>,
+    sort: &SortArgs,
 ) -> Result<(), EngramError> {
     let ids = storage.list_ids(Lesson::entity_type())?;
 
@@ -230,6 +236,7 @@ pub fn list_lessons<S: Storage>(
         }
     }
 
+    items = sort.sort(items)?;
     let total_count = items.len();
 
     if let Some(off) = offset {
@@ -285,8 +292,9 @@ pub fn list_lessons<S: Storage>(
 pub fn show_lesson<S: Storage>(storage: &S, id: &str) -> Result<(), EngramError> {
     let entity = storage
         .get(id, Lesson::entity_type())?
-        .ok_or_else(|| EngramError::NotFound(format!("Lesson not found: {}", id)))?;
+        .ok_or_else(|| EngramError::not_found("lesson", id))?;
 
+    let modification = entity.modification();
     let lesson =
         Lesson::from_generic(entity).map_err(|e| EngramError::Validation(e.to_string()))?;
 
@@ -298,6 +306,9 @@ pub fn show_lesson<S: Storage>(storage: &S, id: &str) -> Result<(), EngramError>
     println!("Category: {}", lesson.category);
     println!("Severity: {}", lesson.severity);
     println!("Agent: {}", lesson.agent);
+    if let Some(modified) = modification.summary() {
+        println!("Last Modified By: {}", modified);
+    }
     println!("Created: {}", lesson.created_at);
     println!("Updated: {}", lesson.updated_at);
     println!();
@@ -329,7 +340,7 @@ pub fn update_lesson<S: Storage>(
 ) -> Result<(), EngramError> {
     let entity = storage
         .get(id, Lesson::entity_type())?
-        .ok_or_else(|| EngramError::NotFound(format!("Lesson not found: {}", id)))?;
+        .ok_or_else(|| EngramError::not_found("lesson", id))?;
 
     let mut lesson =
         Lesson::from_generic(entity).map_err(|e| EngramError::Validation(e.to_string()))?;
@@ -485,7 +496,7 @@ mod tests {
     fn test_show_lesson_not_found() {
         let storage = create_test_storage();
         let result = show_lesson(&storage, "missing-id");
-        assert!(matches!(result, Err(EngramError::NotFound(_))));
+        assert!(matches!(result, Err(EngramError::NotFound { .. })));
     }
 
     #[test]
@@ -516,7 +527,7 @@ mod tests {
     fn test_update_lesson_not_found() {
         let mut storage = create_test_storage();
         let result = update_lesson(&mut storage, "missing-id", None, None, None, None);
-        assert!(matches!(result, Err(EngramError::NotFound(_))));
+        assert!(matches!(result, Err(EngramError::NotFound { .. })));
     }
 
     #[test]
@@ -558,7 +569,7 @@ mod tests {
     fn test_delete_lesson_not_found() {
         let mut storage = create_test_storage();
         let result = delete_lesson(&mut storage, "missing-id");
-        assert!(matches!(result, Err(EngramError::NotFound(_))));
+        assert!(matches!(result, Err(EngramError::NotFound { .. })));
     }
 
     #[test]
@@ -579,7 +590,18 @@ mod tests {
         )
         .unwrap();
 
-        assert!(list_lessons(&storage, None, None, None, None, None, false, None).is_ok());
+        assert!(list_lessons(
+            &storage,
+            None,
+            None,
+            None,
+            None,
+            None,
+            false,
+            None,
+            &SortArgs::default()
+        )
+        .is_ok());
     }
 
     #[test]
@@ -609,7 +631,8 @@ mod tests {
             None,
             None,
             false,
-            None
+            None,
+            &SortArgs::default(),
         )
         .is_ok());
     }
@@ -617,7 +640,18 @@ mod tests {
     #[test]
     fn test_list_lessons_empty() {
         let storage = create_test_storage();
-        assert!(list_lessons(&storage, None, None, None, None, None, false, None).is_ok());
+        assert!(list_lessons(
+            &storage,
+            None,
+            None,
+            None,
+            None,
+            None,
+            false,
+            None,
+            &SortArgs::default()
+        )
+        .is_ok());
     }
 
     #[test]
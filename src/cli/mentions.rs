@@ -0,0 +1,282 @@
+//! Entity mentions inside free text
+//!
+//! Content may reference other entities as `[[<alias-or-id>]]` or `@T-42`.
+//! A mention resolves to an entity by full ID, by unique ID prefix, or by the
+//! `alias` / `jira_key` metadata of a task, context, reasoning, knowledge or
+//! ADR. Resolved mentions become `References` relationships from the
+//! containing entity; unresolved ones are reported but never fail the write.
+
+use crate::entities::{
+    Entity, EntityRegistry, EntityRelationType, EntityRelationship, GenericEntity,
+};
+use crate::error::EngramError;
+use crate::storage::{resolve_id_prefix, Storage};
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Relationship metadata key holding the mention text that created the link
+pub const MENTION_METADATA: &str = "mention";
+
+/// Entity types whose metadata aliases a mention can name
+const ALIASED_TYPES: [&str; 5] = ["task", "context", "reasoning", "knowledge", "adr"];
+
+/// Shortest mention tried as an ID prefix, so `@me` never matches an ID
+const MIN_PREFIX_LEN: usize = 8;
+
+/// Mentions in `text`, in order of first appearance, without duplicates
+pub fn parse_mentions(text: &str) -> Vec<String> {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    let re = RE.get_or_init(|| {
+        Regex::new(
+            r"\[\[([^\[\]\n]+)\]\]|(?:^|[^\w@.])@([A-Za-z0-9](?:[A-Za-z0-9_.-]*[A-Za-z0-9])?)",
+        )
+        .expect("mention regex compiles")
+    });
+
+    let mut mentions: Vec<String> = Vec::new();
+    for captures in re.captures_iter(text) {
+        let mention = captures
+            .get(1)
+            .or_else(|| captures.get(2))
+            .map(|m| m.as_str().trim().to_string())
+            .unwrap_or_default();
+        if !mention.is_empty() && !mentions.contains(&mention) {
+            mentions.push(mention);
+        }
+    }
+    mentions
+}
+
+/// A mention matched to a stored entity
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedMention {
+    pub mention: String,
+    pub id: String,
+    pub entity_type: String,
+}
+
+fn metadata_alias_matches(entity: &GenericEntity, mention: &str) -> bool {
+    let Some(metadata) = entity.data.get("metadata") else {
+        return false;
+    };
+    ["alias", "jira_key"].iter().any(|key| {
+        metadata
+            .get(key)
+            .and_then(|value| value.as_str())
+            .is_some_and(|alias| alias.eq_ignore_ascii_case(mention))
+    })
+}
+
+/// Find the entity `mention` names, if exactly one can be identified
+pub fn resolve_mention(
+    storage: &dyn Storage,
+    mention: &str,
+) -> Result<Option<ResolvedMention>, EngramError> {
+    let resolved = |id: String, entity_type: &str| ResolvedMention {
+        mention: mention.to_string(),
+        id,
+        entity_type: entity_type.to_string(),
+    };
+    let registry = EntityRegistry::with_builtin_types();
+    let entity_types = registry.list_types();
+
+    for entity_type in &entity_types {
+        if storage.exists(mention, entity_type)? {
+            return Ok(Some(resolved(mention.to_string(), entity_type)));
+        }
+    }
+
+    if mention.len() >= MIN_PREFIX_LEN {
+        let mut matches = Vec::new();
+        for entity_type in &entity_types {
+            if let Ok(id) = resolve_id_prefix(storage, entity_type, mention) {
+                matches.push(resolved(id, entity_type));
+            }
+        }
+        if matches.len() == 1 {
+            return Ok(matches.pop());
+        }
+    }
+
+    let mut matches = Vec::new();
+    for entity_type in ALIASED_TYPES {
+        for entity in storage.get_all(entity_type)? {
+            if metadata_alias_matches(&entity, mention) {
+                matches.push(resolved(entity.id, entity_type));
+            }
+        }
+    }
+    Ok(if matches.len() == 1 {
+        matches.pop()
+    } else {
+        None
+    })
+}
+
+/// Outcome of [`autolink_mentions`]
+#[derive(Debug, Default)]
+pub struct MentionLinks {
+    /// Mentions that now have a `References` relationship, new or existing
+    pub linked: Vec<ResolvedMention>,
+    /// Mentions that matched no entity, or more than one
+    pub unresolved: Vec<String>,
+}
+
+/// Link `source` to every entity mentioned in `texts` with a `References`
+/// relationship, skipping links that already exist and self-mentions.
+/// Unresolved mentions are printed as warnings.
+pub fn autolink_mentions<S: Storage>(
+    storage: &mut S,
+    source: &GenericEntity,
+    texts: &[&str],
+) -> Result<MentionLinks, EngramError> {
+    let mut mentions: Vec<String> = Vec::new();
+    for text in texts {
+        for mention in parse_mentions(text) {
+            if !mentions.contains(&mention) {
+                mentions.push(mention);
+            }
+        }
+    }
+    let mut links = MentionLinks::default();
+    if mentions.is_empty() {
+        return Ok(links);
+    }
+
+    let existing: Vec<EntityRelationship> = storage
+        .get_all(EntityRelationship::entity_type())?
+        .into_iter()
+        .filter_map(|e| EntityRelationship::from_generic(e).ok())
+        .filter(|r| {
+            r.source_id == source.id && r.relationship_type == EntityRelationType::References
+        })
+        .collect();
+
+    for mention in mentions {
+        let Some(target) = resolve_mention(storage, &mention)? else {
+            eprintln!("⚠️  Unresolved mention '{}' was not linked", mention);
+            links.unresolved.push(mention);
+            continue;
+        };
+        if target.id == source.id {
+            continue;
+        }
+        let already_linked = existing.iter().any(|r| r.target_id == target.id)
+            || links.linked.iter().any(|l| l.id == target.id);
+        if !already_linked {
+            let mut relationship = EntityRelationship::new(
+                uuid::Uuid::new_v4().to_string(),
+                source.agent.clone(),
+                source.id.clone(),
+                source.entity_type.clone(),
+                target.id.clone(),
+                target.entity_type.clone(),
+                EntityRelationType::References,
+            );
+            relationship.metadata.insert(
+                MENTION_METADATA.to_string(),
+                serde_json::Value::String(mention.clone()),
+            );
+            storage.store(&relationship.to_generic())?;
+        }
+        links.linked.push(target);
+    }
+    Ok(links)
+}
+
+/// Entities `id` mentions, as (id, entity type, title) in link order
+pub fn mentioned_entities<S: Storage>(
+    storage: &S,
+    id: &str,
+) -> Result<Vec<(String, String, String)>, EngramError> {
+    let mut relationships: Vec<EntityRelationship> = storage
+        .get_all(EntityRelationship::entity_type())?
+        .into_iter()
+        .filter_map(|e| EntityRelationship::from_generic(e).ok())
+        .filter(|r| {
+            r.source_id == id
+                && r.relationship_type == EntityRelationType::References
+                && r.metadata.contains_key(MENTION_METADATA)
+        })
+        .collect();
+    relationships.sort_by_key(|r| r.timestamp);
+
+    let mut mentioned = Vec::new();
+    for relationship in relationships {
+        let title = storage
+            .get(&relationship.target_id, &relationship.target_type)?
+            .and_then(|entity| {
+                ["title", "name"]
+                    .iter()
+                    .find_map(|key| entity.data.get(key)?.as_str().map(str::to_string))
+            })
+            .unwrap_or_else(|| "(deleted)".to_string());
+        mentioned.push((relationship.target_id, relationship.target_type, title));
+    }
+    Ok(mentioned)
+}
+
+/// Print a "Mentions" section for `id`; prints nothing without mentions
+pub fn print_mentions<S: Storage>(storage: &S, id: &str) -> Result<(), EngramError> {
+    let mentioned = mentioned_entities(storage, id)?;
+    if mentioned.is_empty() {
+        return Ok(());
+    }
+    println!();
+    println!("Mentions:");
+    println!("---------");
+    for (target_id, entity_type, title) in mentioned {
+        println!("  [{}] {} {}", entity_type, target_id, title);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{Context, ContextRelevance, Task, TaskPriority};
+    use crate::storage::MemoryStorage;
+
+    #[test]
+    fn test_parse_mentions() {
+        let text = "See @T-42, [[login flow]] and @T-42 again. Mail bob@example.com; [[ x ]]";
+        assert_eq!(parse_mentions(text), ["T-42", "login flow", "x"]);
+        assert!(parse_mentions("no mentions @ all").is_empty());
+    }
+
+    #[test]
+    fn test_autolink_mentions_dedupes_and_warns() {
+        let mut storage = MemoryStorage::new("default");
+        let mut task = Task::new(
+            "Fix login timeout".to_string(),
+            String::new(),
+            "agent".to_string(),
+            TaskPriority::High,
+            None,
+        );
+        task.metadata
+            .insert("alias".to_string(), serde_json::json!("T-42"));
+        storage.store(&task.to_generic()).unwrap();
+        let context = Context::new(
+            "Session notes".to_string(),
+            format!("See @t-42 and [[{}]]; also @missing", task.id),
+            String::new(),
+            ContextRelevance::Medium,
+            "agent".to_string(),
+        );
+        let source = context.to_generic();
+        storage.store(&source).unwrap();
+
+        let links = autolink_mentions(&mut storage, &source, &[&context.content]).unwrap();
+        assert_eq!(links.linked.len(), 2);
+        assert!(links.linked.iter().all(|l| l.id == task.id));
+        assert_eq!(links.unresolved, ["missing"]);
+
+        autolink_mentions(&mut storage, &source, &[&context.content]).unwrap();
+        let mentioned = mentioned_entities(&storage, &context.id).unwrap();
+        assert_eq!(
+            mentioned,
+            [(task.id.clone(), "task".to_string(), task.title.clone())]
+        );
+    }
+}
@@ -4,25 +4,40 @@
 //! for all entity types and operations.
 
 pub mod adr;
+pub mod agent;
 pub mod analytics;
 pub mod auto_guide;
+pub mod benchmark;
+pub mod completions;
 pub mod compliance;
 pub mod context;
 pub mod convert;
+pub mod dev;
+pub mod diff;
 pub mod doc;
+pub mod doctor;
+pub mod entity;
 pub mod escalation;
+pub mod gates;
 pub mod git;
 pub mod health;
 pub mod help;
+pub mod identity;
 pub mod import;
 pub mod info;
+pub mod jira;
+pub mod kb;
 pub mod knowledge;
 pub mod lesson;
+pub mod mentions;
+pub mod notify;
 pub mod perkeep;
 pub mod persona;
 pub mod prompts;
 pub mod reasoning;
+pub mod redact;
 pub mod relationship;
+pub mod report;
 pub mod rule;
 #[cfg(feature = "sandbox")]
 pub mod sandbox;
@@ -32,31 +47,44 @@ pub mod setup;
 pub mod skills;
 pub mod standard;
 pub mod state_reflection;
+pub mod stats;
+pub mod status;
+pub mod storage;
 pub mod sync;
 pub mod task;
 pub mod theory;
 pub mod utils;
 pub mod validation;
 pub mod workflow;
+pub mod workspace;
 
 pub use adr::*;
+pub use agent::AgentCommands;
 pub use analytics::*;
+pub use completions::CompletionsCommands;
 pub use compliance::*;
 pub use context::*;
 pub use convert::*;
+pub use dev::DevCommands;
 pub use doc::*;
+pub use entity::EntityCommands;
 pub use escalation::*;
+pub use gates::GatesCommands;
 pub use health::HealthCommands;
 pub use help::*;
 pub use import::*;
 pub use info::*;
+pub use kb::KbCommands;
 pub use knowledge::*;
 pub use lesson::*;
+pub use notify::NotifyCommands;
 pub use perkeep::*;
 pub use persona::*;
 pub use prompts::*;
 pub use reasoning::*;
+pub use redact::RedactCommands;
 pub use relationship::*;
+pub use report::ReportCommands;
 pub use rule::*;
 #[cfg(feature = "sandbox")]
 pub use sandbox::*;
@@ -66,14 +94,19 @@ pub use setup::*;
 pub use skills::*;
 pub use standard::*;
 pub use state_reflection::*;
+pub use stats::StatsCommands;
+pub use storage::StorageCommands;
 pub use sync::SyncCommands;
 pub use task::*;
 pub use theory::*;
 pub use validation::*;
 pub use workflow::*;
+pub use workspace::WorkspaceCommands;
 
 use crate::ask::AskCommands;
+use crate::storage::ReadOnlyMode;
 use clap::{Parser, Subcommand};
+use std::path::PathBuf;
 
 /// Main CLI structure
 #[derive(Parser)]
@@ -90,6 +123,31 @@ pub struct Cli {
 
     #[arg(long, global = true)]
     pub json: bool,
+
+    /// Preview changes: storage writes are recorded and reported instead of applied
+    #[arg(long, global = true)]
+    pub dry_run: bool,
+
+    /// Never write to the workspace: writes are skipped with a warning, or
+    /// fail with `--read-only=strict`. Defaults to `ENGRAM_READ_ONLY`
+    #[arg(
+        long,
+        global = true,
+        value_name = "MODE",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "warn"
+    )]
+    pub read_only: Option<ReadOnlyMode>,
+
+    /// Run against a registered workspace name or a workspace path instead
+    /// of the current directory
+    #[arg(long, global = true, value_name = "NAME|PATH")]
+    pub workspace: Option<String>,
+
+    /// Threads used to read entities in large scans; defaults to one per CPU
+    #[arg(long, global = true, value_name = "N")]
+    pub threads: Option<usize>,
 }
 
 /// Available CLI commands
@@ -100,15 +158,19 @@ pub enum Commands {
         #[command(subcommand)]
         command: SetupCommands,
     },
-    /// Convert from other formats (EXPERIMENTAL - Not yet implemented)
+    /// Convert from other formats (currently OpenAPI 3.x via `--from openspec`)
     Convert {
-        /// Source format (openspec, beads, github)
+        /// Source format (openspec)
         #[arg(long, short = 'o')]
         from: String,
 
         /// Source file path
         #[arg(long, short = 'f')]
         file: String,
+
+        /// Agent to create entities as
+        #[arg(long, short)]
+        agent: Option<String>,
     },
     /// Documentation management
     Doc {
@@ -247,11 +309,102 @@ pub enum Commands {
         /// Scope to tasks with a specific tag
         #[arg(long)]
         tag: Option<String>,
+
+        /// Replace likely secrets in the prompt with `[REDACTED:<kind>]`
+        #[arg(long)]
+        redact: bool,
+    },
+    /// Display workspace and storage information, or export/import a
+    /// workspace snapshot archive
+    ///
+    ///EXAMPLES:
+    ///  engram info
+    ///  engram info --export snapshot.zip --include-git-history
+    ///  engram info --export snapshot.zip --redact
+    ///  engram info --import snapshot.zip --agent alice
+    Info {
+        /// Write a ZIP snapshot of every entity to this file
+        #[arg(long, value_name = "FILE", conflicts_with = "import")]
+        export: Option<PathBuf>,
+
+        /// Include a git bundle of the repository in the snapshot
+        #[arg(long, requires = "export")]
+        include_git_history: bool,
+
+        /// Replace likely secrets in exported entities with `[REDACTED:<kind>]`
+        #[arg(long, requires = "export")]
+        redact: bool,
+
+        /// Store every entity from a ZIP snapshot
+        #[arg(long, value_name = "FILE")]
+        import: Option<PathBuf>,
+
+        /// Reassign imported entities to this agent
+        #[arg(long, requires = "import")]
+        agent: Option<String>,
+    },
+    /// Measure storage and validation performance on this workspace
+    Benchmark {
+        /// Number of iterations per operation
+        #[arg(long, default_value = "100")]
+        iterations: u32,
+
+        /// Write the report as JSON to this file
+        #[arg(long)]
+        output: Option<String>,
+
+        /// Compare against a previously saved report
+        #[arg(long)]
+        compare: Option<String>,
+
+        /// Exit with code 2 when a regression (>20% slower) is found
+        #[arg(long, requires = "compare")]
+        fail_on_regression: bool,
+    },
+    /// Diagnose common configuration and storage problems
+    Doctor {
+        /// Attempt automatic remediation of fixable issues
+        #[arg(long)]
+        fix: bool,
+    },
+    /// Check whether every subsystem is operational
+    ///
+    ///EXAMPLES:
+    ///  engram status
+    ///  engram status --json --fail-on-unhealthy
+    Status {
+        /// Exit with code 2 if any subsystem is unhealthy
+        #[arg(long)]
+        fail_on_unhealthy: bool,
+    },
+    /// Shell completion helpers for flag values
+    Completions {
+        #[command(subcommand)]
+        command: CompletionsCommands,
+    },
+    /// Migrate from dual-repository to Git refs storage, or copy entities
+    /// between storage backends
+    ///
+    ///EXAMPLES:
+    ///  engram migration --dry-run
+    ///  engram migration --from git --to git:../other-workspace --verify
+    Migration {
+        /// Source backend to copy from (git, git:<path>)
+        #[arg(long, requires = "to")]
+        from: Option<String>,
+
+        /// Destination backend to copy to (git:<path>)
+        #[arg(long, requires = "from")]
+        to: Option<String>,
+
+        /// Only back up the .engram directory
+        #[arg(long, conflicts_with = "from")]
+        backup_only: bool,
+
+        /// After copying, compare content hashes of source and destination
+        #[arg(long, requires = "to")]
+        verify: bool,
     },
-    /// Display workspace and storage information
-    Info,
-    /// Migrate from dual-repository to Git refs storage
-    Migration,
     /// Perkeep backup and restore operations
     Perkeep {
         #[command(subcommand)]
@@ -297,13 +450,92 @@ pub enum Commands {
         #[command(subcommand)]
         command: HealthCommands,
     },
+    /// Build and search a structured knowledge base
+    Kb {
+        #[command(subcommand)]
+        command: KbCommands,
+    },
+    /// Run quality gates selected by task complexity
+    Gates {
+        #[command(subcommand)]
+        command: GatesCommands,
+    },
+    /// Agent productivity views
+    Agent {
+        #[command(subcommand)]
+        command: AgentCommands,
+    },
+    /// Notification sinks
+    Notify {
+        #[command(subcommand)]
+        command: NotifyCommands,
+    },
+    /// Registry of named workspaces used by `--workspace`
+    Workspace {
+        #[command(subcommand)]
+        command: WorkspaceCommands,
+    },
+    /// Workspace statistics and growth reports
+    Stats {
+        #[command(subcommand)]
+        command: StatsCommands,
+    },
+    /// Compare two entities field by field
+    ///
+    ///EXAMPLES:
+    ///  engram diff <id_a> <id_b>
+    ///  engram diff <id_a> <id_b> --format table
+    Diff {
+        /// ID of the first (old) entity
+        id_a: String,
+
+        /// ID of the second (new) entity
+        id_b: String,
+
+        /// Output format
+        #[arg(long, default_value = "unified", value_parser = ["unified", "table", "json"])]
+        format: String,
+    },
+    /// Operations on any stored entity
+    Entity {
+        #[command(subcommand)]
+        command: EntityCommands,
+    },
+    /// Shareable activity reports
+    Report {
+        #[command(subcommand)]
+        command: ReportCommands,
+    },
+    /// Find likely secrets in stored entities
+    Redact {
+        #[command(subcommand)]
+        command: RedactCommands,
+    },
+    /// Storage integrity checks and repair
+    Storage {
+        #[command(subcommand)]
+        command: StorageCommands,
+    },
+    /// Developer tooling such as loading test fixtures
+    Dev {
+        #[command(subcommand)]
+        command: DevCommands,
+    },
 }
 
 /// Setup commands
 #[derive(Subcommand)]
 pub enum SetupCommands {
     /// Initialize workspace
-    Workspace,
+    Workspace {
+        /// Provision a built-in template: minimal, standard or strict
+        #[arg(long, conflicts_with = "template_file")]
+        template: Option<String>,
+
+        /// Provision a custom YAML template
+        #[arg(long)]
+        template_file: Option<PathBuf>,
+    },
     /// Initialize agent profile
     Agent {
         /// Agent name
@@ -1,5 +1,8 @@
+use crate::api::Engram;
+use crate::cli::identity::{resolve_agent, resolve_agent_filter};
 use crate::entities::task::{Task, TaskPriority, TaskStatus};
 use crate::entities::Entity;
+use crate::redaction::{RedactionStats, Redactor};
 use crate::storage::Storage;
 use crate::EngramError;
 use chrono::Utc;
@@ -14,6 +17,28 @@ pub fn interpolate(template: &str, context: &HashMap<String, String>) -> String
     result
 }
 
+/// Distinct `{{name}}` placeholder names in `template`, in order of first use
+pub fn template_placeholders(template: &str) -> Vec<String> {
+    let pattern = regex::Regex::new(r"\{\{([A-Za-z0-9_.-]+)\}\}").expect("valid placeholder regex");
+    let mut names: Vec<String> = Vec::new();
+    for capture in pattern.captures_iter(template) {
+        let name = capture[1].to_string();
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+/// Placeholders in `template` that [`interpolate`] would leave untouched
+pub fn unresolved_placeholders(template: &str, context: &HashMap<String, String>) -> Vec<String> {
+    template_placeholders(template)
+        .into_iter()
+        .filter(|name| !context.contains_key(name))
+        .collect()
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct NextScope {
     pub parent: Option<String>,
     pub agent: Option<String>,
@@ -123,10 +148,19 @@ pub fn handle_next_command<S: Storage>(
     scope_agent: Option<String>,
     session: Option<String>,
     tag: Option<String>,
+    redact: bool,
 ) -> Result<(), EngramError> {
+    let agent = resolve_agent(agent);
+    let redactor = if redact {
+        Some(Redactor::load(std::path::Path::new("."))?)
+    } else {
+        None
+    };
+    let mut redactions = RedactionStats::default();
+    let unscoped = parent.is_none() && scope_agent.is_none() && session.is_none() && tag.is_none();
     let scope = NextScope {
         parent,
-        agent: scope_agent,
+        agent: resolve_agent_filter(scope_agent),
         session,
         tag,
     };
@@ -136,10 +170,23 @@ pub fn handle_next_command<S: Storage>(
         if let Some(entity) = storage.get(&task_id, "task")? {
             Task::from_generic(entity).map_err(|e| EngramError::Validation(e.to_string()))?
         } else {
-            return Err(EngramError::NotFound(format!("Task {} not found", task_id)));
+            return Err(EngramError::not_found("task", task_id));
         }
     } else {
-        if let Some(t) = find_next_task(storage, "default", &scope)? {
+        // Without an explicit scope, an open task bound to the current
+        // branch comes first
+        let bound = if unscoped {
+            crate::branch::current_branch_task(storage)?.filter(|task| {
+                task.status != TaskStatus::Done && task.status != TaskStatus::Cancelled
+            })
+        } else {
+            None
+        };
+        if let Some(t) = bound {
+            t
+        } else if let Some(t) =
+            Engram::with_storage(&mut *storage, agent.clone()).next_task_in(&agent, &scope)?
+        {
             t
         } else {
             println!("No pending tasks found.");
@@ -147,8 +194,14 @@ pub fn handle_next_command<S: Storage>(
         }
     };
 
-    // 2. Load associated Workflow (if any)
-    let workflow = if let Some(workflow_id) = &task.workflow_id {
+    // 2. Load associated Workflow (if any); a running instance attached to
+    // the task takes precedence over the task's own workflow fields
+    let instance = crate::cli::workflow::find_active_instance(storage, &task.id)?;
+    let workflow_id = instance
+        .as_ref()
+        .map(|instance| &instance.workflow_id)
+        .or(task.workflow_id.as_ref());
+    let workflow = if let Some(workflow_id) = workflow_id {
         if let Some(entity) = storage.get(workflow_id, "workflow")? {
             Some(
                 Workflow::from_generic(entity)
@@ -166,6 +219,13 @@ pub fn handle_next_command<S: Storage>(
     prompt_context.insert("TASK_ID".to_string(), task.id.clone());
     prompt_context.insert("TASK_TITLE".to_string(), task.title.clone());
     prompt_context.insert("TASK_DESCRIPTION".to_string(), task.description.clone());
+    let estimate = task
+        .estimate_seconds
+        .map(crate::cli::utils::format_duration_secs);
+    prompt_context.insert(
+        "TASK_ESTIMATE".to_string(),
+        estimate.clone().unwrap_or_default(),
+    );
 
     // Load related Context entities
     let mut context_content = String::new();
@@ -178,8 +238,8 @@ pub fn handle_next_command<S: Storage>(
     }
     prompt_context.insert("CONTEXT".to_string(), context_content);
 
-    // 4. Select Prompts
-    let (system_prompt, user_prompt) = if let Some(ref wf) = workflow {
+    // 4. Select Prompts (an instance's stage prompt is appended below instead)
+    let (system_prompt, user_prompt) = if let (Some(ref wf), None) = (&workflow, &instance) {
         if let Some(state_name) = &task.workflow_state {
             if let Some(state) = wf.states.iter().find(|s| &s.name == state_name) {
                 if let Some(prompts) = &state.prompts {
@@ -218,45 +278,61 @@ pub fn handle_next_command<S: Storage>(
     };
 
     // 5. Resolve persona system prompt prefix (if agent config specifies one)
-    let persona_prefix = agent
-        .as_deref()
-        .and_then(|agent_name| {
-            let agent_file =
-                std::path::PathBuf::from(".engram/agents").join(format!("{}.yaml", agent_name));
-            std::fs::read_to_string(&agent_file).ok()
-        })
-        .and_then(|yaml| {
-            serde_yaml::from_str::<crate::config::agent_config::AgentConfig>(&yaml).ok()
-        })
-        .and_then(|cfg| cfg.persona)
-        .and_then(|persona_name| {
-            let result = crate::personas::find_persona(&persona_name);
-            if result.is_none() {
-                eprintln!(
-                    "⚠️  Persona '{}' not found in storage or embedded set",
-                    persona_name
-                );
-            }
-            result
-        })
-        .map(|(_, def)| def.instructions)
-        .unwrap_or_default();
+    let persona_prefix = std::fs::read_to_string(
+        std::path::PathBuf::from(".engram/agents").join(format!("{}.yaml", agent)),
+    )
+    .ok()
+    .and_then(|yaml| serde_yaml::from_str::<crate::config::agent_config::AgentConfig>(&yaml).ok())
+    .and_then(|cfg| cfg.persona)
+    .and_then(|persona_name| {
+        let result = crate::personas::find_persona(&persona_name);
+        if result.is_none() {
+            eprintln!(
+                "⚠️  Persona '{}' not found in storage or embedded set",
+                persona_name
+            );
+        }
+        result
+    })
+    .map(|(_, def)| def.instructions)
+    .unwrap_or_default();
 
     // 6. Interpolate
-    let interpolated_system = interpolate(&system_prompt, &prompt_context);
+    let stage_prompt = match (&workflow, &instance) {
+        (Some(wf), Some(instance)) => {
+            crate::cli::workflow::render_state_prompt(wf, instance, &prompt_context)
+        }
+        _ => None,
+    };
+    let mut interpolated_system = interpolate(&system_prompt, &prompt_context);
+    if let Some(system) = stage_prompt.as_ref().and_then(|p| p.system.as_ref()) {
+        interpolated_system.push_str(&format!("\n\n{}", system));
+    }
     let final_system = if persona_prefix.is_empty() {
         interpolated_system
     } else {
         format!("{}\n\n{}", persona_prefix, interpolated_system)
     };
-    let final_user = interpolate(&user_prompt, &prompt_context);
+    let mut final_user = interpolate(&user_prompt, &prompt_context);
+    if let Some(estimate) = &estimate {
+        final_user.push_str(&format!("\nEstimate: {}", estimate));
+    }
+    if let (Some(user), Some(instance)) = (
+        stage_prompt.as_ref().and_then(|p| p.user.as_ref()),
+        &instance,
+    ) {
+        final_user.push_str(&format!(
+            "\n\n**Workflow stage ({})**:\n{}",
+            instance.current_state, user
+        ));
+    }
 
     let task_management_instructions = format!(
         r#"
 ## Task Management with Engram
 
 **Current Task**: {} ({})
-**Status**: {:?} | **Priority**: {:?}
+**Status**: {:?} | **Priority**: {:?} | **Estimate**: {}
 
 ### Required Workflow Actions:
 
@@ -300,12 +376,17 @@ engram relationship connected --entity-id {}
         task.id,
         task.status,
         task.priority,
+        estimate.as_deref().unwrap_or("none"),
         task.id,
         task.id,
         task.id,
         if workflow.is_some() {
+            let instance_id = instance
+                .as_ref()
+                .map_or("<instance-id>", |instance| instance.id.as_str());
             format!(
-                "This task is part of a workflow. Use:\n   engram workflow status <instance-id>\n   engram workflow transition <instance-id> --transition <name> --agent default"
+                "This task is part of a workflow. Use:\n   engram workflow status {}\n   engram workflow prompt {}\n   engram workflow transition {} --transition <name> --agent default",
+                instance_id, instance_id, instance_id
             )
         } else {
             "No active workflow for this task.".to_string()
@@ -316,17 +397,41 @@ engram relationship connected --entity-id {}
         task.id
     );
 
-    // 6. Detect active session
+    // 6. Detect active session and how fatiguing it has become
     let active_session = find_active_session(storage)?;
+    let fatigue = match &active_session {
+        Some(sess) => Some(crate::cli::session::session_fatigue(
+            storage,
+            sess,
+            Utc::now(),
+        )?),
+        None => None,
+    };
+    if let Some(recommendation) = fatigue
+        .as_ref()
+        .filter(|f| f.is_high_risk())
+        .and_then(|f| f.recommendation.as_ref())
+    {
+        final_user.push_str(&format!("\n\n**Session fatigue:** {}", recommendation));
+    }
 
     // 7. Output
     if format == "json" {
         let mut output = serde_json::json!({
             "task_id": task.id,
+            "estimate_seconds": task.estimate_seconds,
             "system_prompt": final_system,
             "user_prompt": final_user,
             "task_management": task_management_instructions
         });
+        if let Some(ref instance) = instance {
+            output["workflow_instance"] = serde_json::json!({
+                "instance_id": instance.id,
+                "workflow_id": instance.workflow_id,
+                "current_state": instance.current_state,
+                "stage_prompt": stage_prompt,
+            });
+        }
         if let Some(ref sess) = active_session {
             let elapsed = Utc::now()
                 .signed_duration_since(sess.start_time)
@@ -346,8 +451,14 @@ engram relationship connected --entity-id {}
             if !sess.outcomes.is_empty() {
                 session_json["outcomes"] = serde_json::json!(sess.outcomes);
             }
+            if let Some(ref fatigue) = fatigue {
+                session_json["fatigue"] = serde_json::to_value(fatigue)?;
+            }
             output["session"] = session_json;
         }
+        if let Some(redactor) = &redactor {
+            redactor.redact_value(&mut output, &mut redactions);
+        }
         println!("{}", serde_json::to_string_pretty(&output).unwrap());
     } else {
         let mut output_parts = Vec::new();
@@ -390,6 +501,19 @@ engram relationship connected --entity-id {}
                 }
             }
 
+            if let Some(ref fatigue) = fatigue {
+                session_header.push_str(&format!(
+                    "\n\nFatigue: {} ({}m active of {}m optimal, {} task(s) completed)",
+                    fatigue.level,
+                    fatigue.active_minutes,
+                    fatigue.optimal_session_minutes,
+                    fatigue.tasks_completed
+                ));
+                if let Some(ref recommendation) = fatigue.recommendation {
+                    session_header.push_str(&format!("\n{}", recommendation));
+                }
+            }
+
             output_parts.push(session_header);
         }
 
@@ -398,9 +522,16 @@ engram relationship connected --entity-id {}
             final_system, final_user, task_management_instructions
         ));
 
-        println!("{}", output_parts.join("\n\n---\n\n"));
+        let mut output = output_parts.join("\n\n---\n\n");
+        if let Some(redactor) = &redactor {
+            output = redactor.redact(&output, &mut redactions);
+        }
+        println!("{}", output);
     }
 
+    if redactor.is_some() {
+        eprintln!("{}", redactions.summary());
+    }
     Ok(())
 }
 
@@ -529,6 +660,8 @@ mod tests {
             agent: "test-agent".to_string(),
             start_time: Utc::now(),
             end_time: None,
+            due_at: None,
+            estimate_seconds: None,
             parent: None,
             children: vec![],
             context_ids: vec![],
@@ -554,6 +687,21 @@ mod tests {
         assert_eq!(result, "Hello Alice, working on 123");
     }
 
+    #[test]
+    fn test_unresolved_placeholders() {
+        let template =
+            "{{task.title}} for {{AGENT_NAME}} ({{task.title}}, {{ticket-id}}) {{not a var}}";
+        assert_eq!(
+            template_placeholders(template),
+            vec!["task.title", "AGENT_NAME", "ticket-id"]
+        );
+        let context = HashMap::from([("task.title".to_string(), "Ship".to_string())]);
+        assert_eq!(
+            unresolved_placeholders(template, &context),
+            vec!["AGENT_NAME", "ticket-id"]
+        );
+    }
+
     #[test]
     fn test_find_next_task_selection() {
         let t1 = create_test_task("1", TaskStatus::Todo, TaskPriority::High);
@@ -621,8 +769,9 @@ mod tests {
             None,
             None,
             None,
+            false,
         );
-        assert!(matches!(result, Err(EngramError::NotFound(_))));
+        assert!(matches!(result, Err(EngramError::NotFound { .. })));
     }
 
     #[test]
@@ -638,6 +787,7 @@ mod tests {
             None,
             None,
             None,
+            false,
         );
         assert!(result.is_ok());
     }
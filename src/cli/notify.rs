@@ -0,0 +1,83 @@
+//! Notification command implementations
+
+use crate::error::EngramError;
+use crate::notify::{
+    dispatch_with, DispatchReport, NotificationConfig, NotificationEvent, NotificationPriority,
+};
+use clap::Subcommand;
+use std::path::Path;
+
+/// Notification commands
+#[derive(Debug, Subcommand)]
+pub enum NotifyCommands {
+    /// Send a test event to every configured notification sink
+    ///
+    ///EXAMPLES:
+    ///  engram notify test
+    ///  engram notify test --event-type sla_breach --priority critical
+    Test {
+        /// Event type to send, for checking sink filters
+        #[arg(long, default_value = "test")]
+        event_type: String,
+
+        /// Priority (low, medium, high, critical)
+        #[arg(long, default_value = "medium")]
+        priority: String,
+
+        /// Message body
+        #[arg(long, default_value = "Test notification from engram")]
+        message: String,
+    },
+}
+
+fn print_report(report: &DispatchReport) {
+    for sink in &report.delivered {
+        println!("  ✅ {}", sink);
+    }
+    for sink in &report.filtered {
+        println!("  ⏭️  {} (filtered out)", sink);
+    }
+    for (sink, error) in &report.failures {
+        println!("  ❌ {}: {}", sink, error);
+    }
+}
+
+/// Handle `engram notify`
+pub fn handle_notify_command(command: NotifyCommands) -> Result<(), EngramError> {
+    match command {
+        NotifyCommands::Test {
+            event_type,
+            priority,
+            message,
+        } => {
+            let priority = NotificationPriority::parse(&priority).ok_or_else(|| {
+                EngramError::Validation(format!(
+                    "Invalid priority '{}'. Expected low, medium, high or critical",
+                    priority
+                ))
+            })?;
+            let config = NotificationConfig::load(Path::new("."))?;
+            if config.sinks.is_empty() {
+                println!("No notification sinks configured (notifications.sinks in engram.yaml)");
+                return Ok(());
+            }
+
+            let event = NotificationEvent::new(&event_type, priority, "Engram test", message);
+            println!(
+                "📣 Sending '{}' event to {} sink(s):",
+                event_type,
+                config.sinks.len()
+            );
+            let report = dispatch_with(&config, &event);
+            print_report(&report);
+
+            if !report.failures.is_empty() {
+                return Err(EngramError::InvalidOperation(format!(
+                    "{} notification sink(s) failed",
+                    report.failures.len()
+                )));
+            }
+        }
+    }
+    Ok(())
+}
@@ -1,7 +1,11 @@
 //! Perkeep CLI commands for backup and restore
 
+use crate::cli::session::parse_since;
 use crate::error::EngramError;
-use crate::perkeep::{EngramBackupMetadata, PerkeepClient, PerkeepConfig, SchemaObject};
+use crate::perkeep::{
+    EngramBackupMetadata, PerkeepClient, PerkeepConfig, SchemaObject, AGENT_ATTR, ENTITY_TYPE_ATTR,
+};
+use crate::redaction::{RedactionStats, Redactor};
 use crate::storage::Storage;
 use clap::Subcommand;
 use serde_json::Value;
@@ -22,6 +26,10 @@ pub enum PerkeepCommands {
         /// Backup description
         #[arg(long)]
         description: Option<String>,
+
+        /// Replace likely secrets in backed-up entities with `[REDACTED:<kind>]`
+        #[arg(long)]
+        redact: bool,
     },
 
     /// Restore entities from Perkeep
@@ -33,17 +41,29 @@ pub enum PerkeepCommands {
         /// Restore to specific agent
         #[arg(long, short)]
         agent: Option<String>,
-
-        /// Dry run (don't actually restore)
-        #[arg(long)]
-        dry_run: bool,
     },
 
-    /// List available backups
+    /// List available backups, or backed-up entities when filtered
+    ///
+    ///EXAMPLES:
+    ///  engram perkeep list --detailed
+    ///  engram perkeep list --type task --agent default --since 2024-01-01
     List {
         /// Show detailed information
         #[arg(long)]
         detailed: bool,
+
+        /// Only list backed-up entities of this type
+        #[arg(long = "type")]
+        entity_type: Option<String>,
+
+        /// Only list backed-up entities from this agent
+        #[arg(long)]
+        agent: Option<String>,
+
+        /// Only list entities with a timestamp after this (7d, 24h, 2024-01-01)
+        #[arg(long)]
+        since: Option<String>,
     },
 
     /// Check Perkeep server health
@@ -71,7 +91,14 @@ pub async fn perkeep_backup<S: Storage>(
     entity_type: Option<String>,
     include_relationships: bool,
     description: Option<String>,
+    redact: bool,
 ) -> Result<(), EngramError> {
+    let redactor = if redact {
+        Some(Redactor::load(std::path::Path::new("."))?)
+    } else {
+        None
+    };
+    let mut redactions = RedactionStats::default();
     let client = PerkeepClient::new(PerkeepConfig::default()).map_err(|e| {
         EngramError::InvalidOperation(format!("Failed to create Perkeep client: {}", e))
     })?;
@@ -115,12 +142,11 @@ pub async fn perkeep_backup<S: Storage>(
         let ids = storage.list_ids(et)?;
 
         for id in &ids {
-            if let Ok(Some(entity)) = storage.get(&id, et) {
-                let blob_data = serde_json::to_vec(&entity).map_err(|e| {
-                    EngramError::InvalidOperation(format!("Failed to serialize entity: {}", e))
-                })?;
-
-                let blobref = client.upload_blob(&blob_data).await.map_err(|e| {
+            if let Ok(Some(mut entity)) = storage.get(id, et) {
+                if let Some(redactor) = &redactor {
+                    redactor.redact_value(&mut entity.data, &mut redactions);
+                }
+                let blobref = client.upload_entity(&entity).await.map_err(|e| {
                     EngramError::InvalidOperation(format!("Failed to upload {} {}: {}", et, id, e))
                 })?;
 
@@ -140,15 +166,8 @@ pub async fn perkeep_backup<S: Storage>(
         let rel_ids = storage.list_ids("relationship")?;
 
         for id in &rel_ids {
-            if let Ok(Some(entity)) = storage.get(&id, "relationship") {
-                let blob_data = serde_json::to_vec(&entity).map_err(|e| {
-                    EngramError::InvalidOperation(format!(
-                        "Failed to serialize relationship: {}",
-                        e
-                    ))
-                })?;
-
-                let blobref = client.upload_blob(&blob_data).await.map_err(|e| {
+            if let Ok(Some(entity)) = storage.get(id, "relationship") {
+                let blobref = client.upload_entity(&entity).await.map_err(|e| {
                     EngramError::InvalidOperation(format!(
                         "Failed to upload relationship {}: {}",
                         id, e
@@ -163,6 +182,10 @@ pub async fn perkeep_backup<S: Storage>(
         println!("      ✓ {} relationships", rel_ids.len());
     }
 
+    if redactor.is_some() {
+        println!("{}", redactions.summary());
+    }
+
     // Create backup metadata
     let metadata = EngramBackupMetadata::new(
         entity_count,
@@ -221,7 +244,6 @@ pub async fn perkeep_restore<S: Storage>(
     storage: &mut S,
     blobref: Option<String>,
     agent: Option<String>,
-    dry_run: bool,
 ) -> Result<(), EngramError> {
     let client = PerkeepClient::new(PerkeepConfig::default()).map_err(|e| {
         EngramError::InvalidOperation(format!("Failed to create Perkeep client: {}", e))
@@ -251,7 +273,7 @@ pub async fn perkeep_restore<S: Storage>(
                 })?;
 
             if matches.is_empty() {
-                return Err(EngramError::NotFound(
+                return Err(EngramError::InvalidOperation(
                     "No backups found in Perkeep".to_string(),
                 ));
             }
@@ -263,10 +285,6 @@ pub async fn perkeep_restore<S: Storage>(
     println!("🔐 Restoring from Perkeep...");
     println!("   Backup blobref: {}", blobref);
 
-    if dry_run {
-        println!("\n⚠️ Dry run mode - no changes will be made");
-    }
-
     // Fetch backup metadata
     let backup_data = client
         .fetch_blob(&blobref)
@@ -276,10 +294,7 @@ pub async fn perkeep_restore<S: Storage>(
     let backup_data = match backup_data {
         Some(data) => data,
         None => {
-            return Err(EngramError::NotFound(format!(
-                "Backup blob not found: {}",
-                blobref
-            )));
+            return Err(EngramError::not_found("backup", blobref));
         }
     };
 
@@ -293,17 +308,6 @@ pub async fn perkeep_restore<S: Storage>(
     println!("   Entities: {}", metadata.entity_count);
     println!("   Total size: {} bytes", metadata.total_size);
 
-    if dry_run {
-        println!("\n🪧 Would restore {} entities:", metadata.entity_count);
-        for (key, _) in metadata.entity_blob_refs.iter().take(10) {
-            println!("   - {}", key);
-        }
-        if metadata.entity_blob_refs.len() > 10 {
-            println!("   ... and {} more", metadata.entity_blob_refs.len() - 10);
-        }
-        return Ok(());
-    }
-
     // Restore entities
     println!("\n📦 Restoring entities...");
 
@@ -361,8 +365,13 @@ pub async fn perkeep_restore<S: Storage>(
     Ok(())
 }
 
-/// List available backups
-pub async fn perkeep_list(detailed: bool) -> Result<(), EngramError> {
+/// List available backups, or backed-up entities when any filter is given
+pub async fn perkeep_list(
+    detailed: bool,
+    entity_type: Option<String>,
+    agent: Option<String>,
+    since: Option<String>,
+) -> Result<(), EngramError> {
     let client = PerkeepClient::new(PerkeepConfig::default()).map_err(|e| {
         EngramError::InvalidOperation(format!("Failed to create Perkeep client: {}", e))
     })?;
@@ -378,6 +387,47 @@ pub async fn perkeep_list(detailed: bool) -> Result<(), EngramError> {
         ));
     }
 
+    if entity_type.is_some() || agent.is_some() || since.is_some() {
+        let since = since.as_deref().map(parse_since).transpose()?;
+        let mut filters = std::collections::HashMap::new();
+        if let Some(entity_type) = entity_type {
+            filters.insert(ENTITY_TYPE_ATTR.to_string(), entity_type);
+        }
+        if let Some(agent) = agent {
+            filters.insert(AGENT_ATTR.to_string(), agent);
+        }
+
+        let mut summaries = client.search_by_metadata(&filters).await?;
+        // Perkeep attribute search has no range operators, so `--since` is applied here
+        if let Some(since) = since {
+            summaries.retain(|summary| {
+                chrono::DateTime::parse_from_rfc3339(&summary.timestamp)
+                    .is_ok_and(|timestamp| timestamp >= since)
+            });
+        }
+
+        if summaries.is_empty() {
+            println!("\n📭 No matching entities found in Perkeep.");
+            return Ok(());
+        }
+        println!("\n📦 Backed-up Entities:");
+        println!("=====================");
+        for (i, summary) in summaries.iter().enumerate() {
+            println!(
+                "{}. {} {} ({}, {})",
+                i + 1,
+                summary.entity_type,
+                summary.entity_id,
+                summary.agent,
+                summary.timestamp
+            );
+            if detailed {
+                println!("   Blobref: {}", summary.blobref);
+            }
+        }
+        return Ok(());
+    }
+
     // Search for backups
     let backups = client
         .search_blobs("camliType:engram.net/backup")
@@ -440,16 +490,21 @@ mod tests {
     fn test_perkeep_commands_variants() {
         // Just verify variants exist and can be instantiated
         let _ = PerkeepCommands::Health;
-        let _ = PerkeepCommands::List { detailed: false };
+        let _ = PerkeepCommands::List {
+            detailed: false,
+            entity_type: None,
+            agent: None,
+            since: None,
+        };
         let _ = PerkeepCommands::Backup {
             entity_type: None,
             include_relationships: true,
             description: None,
+            redact: false,
         };
         let _ = PerkeepCommands::Restore {
             blobref: Some("test".to_string()),
             agent: None,
-            dry_run: true,
         };
         let _ = PerkeepCommands::Config {
             server: Some("http://localhost".to_string()),
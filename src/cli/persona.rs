@@ -1,5 +1,6 @@
 //! Persona command implementations
 
+use crate::cli::identity::resolve_agent;
 use crate::entities::{Entity, Persona};
 use crate::error::EngramError;
 use crate::storage::Storage;
@@ -80,6 +81,9 @@ pub enum PersonaCommands {
         /// Offset for pagination
         #[arg(long, short)]
         offset: Option<usize>,
+
+        #[command(flatten)]
+        sort: SortArgs,
     },
     /// Show persona details (accepts slug or UUID prefix)
     Show {
@@ -184,7 +188,7 @@ fn resolve_persona<S: Storage>(storage: &S, id: &str) -> Result<Persona, EngramE
         }
     }
 
-    Err(EngramError::NotFound(format!("Persona not found: {}", id)))
+    Err(EngramError::not_found("persona", id))
 }
 
 // ── CRUD functions ────────────────────────────────────────────────────────────
@@ -212,7 +216,7 @@ pub fn create_persona<S: Storage>(
         )));
     }
 
-    let agent_name = agent.unwrap_or_else(|| "default".to_string());
+    let agent_name = resolve_agent(agent);
     let mut persona = Persona::new(slug, title, description, instructions, domain, agent_name);
 
     if let Some(base) = base_persona {
@@ -257,10 +261,11 @@ pub fn create_persona<S: Storage>(
     Ok(())
 }
 
-use crate::cli::utils::{create_table, truncate};
+use crate::cli::utils::{create_table, truncate, SortArgs};
 use prettytable::row;
 
 /// List personas
+#[allow(clippy::too_many_arguments)]
 pub fn list_personas<S: Storage>(
     storage: &S,
     agent: Option<String>,
@@ -269,6 +274,7 @@ pub fn list_personas<S: Storage>(
     limit: Option<usize>,
     all: bool,
     offset: Option<usize>,
+    sort: &SortArgs,
 ) -> Result<(), EngramError> {
     let ids = storage.list_ids(Persona::entity_type())?;
 
@@ -297,6 +303,7 @@ pub fn list_personas<S: Storage>(
         }
     }
 
+    items = sort.sort(items)?;
     let total_count = items.len();
 
     if let Some(off) = offset {
@@ -757,7 +764,7 @@ mod tests {
     fn test_show_persona_not_found() {
         let storage = create_test_storage();
         let result = show_persona(&storage, "missing-slug");
-        assert!(matches!(result, Err(EngramError::NotFound(_))));
+        assert!(matches!(result, Err(EngramError::NotFound { .. })));
     }
 
     #[test]
@@ -824,7 +831,7 @@ mod tests {
             None,
             None,
         );
-        assert!(matches!(result, Err(EngramError::NotFound(_))));
+        assert!(matches!(result, Err(EngramError::NotFound { .. })));
     }
 
     #[test]
@@ -844,13 +851,23 @@ mod tests {
     fn test_delete_persona_not_found() {
         let mut storage = create_test_storage();
         let result = delete_persona(&mut storage, "missing-id");
-        assert!(matches!(result, Err(EngramError::NotFound(_))));
+        assert!(matches!(result, Err(EngramError::NotFound { .. })));
     }
 
     #[test]
     fn test_list_personas_empty() {
         let storage = create_test_storage();
-        assert!(list_personas(&storage, None, None, None, None, false, None).is_ok());
+        assert!(list_personas(
+            &storage,
+            None,
+            None,
+            None,
+            None,
+            false,
+            None,
+            &SortArgs::default()
+        )
+        .is_ok());
     }
 
     #[test]
@@ -880,7 +897,8 @@ mod tests {
             None,
             None,
             false,
-            None
+            None,
+            &SortArgs::default(),
         )
         .is_ok());
     }
@@ -1,10 +1,16 @@
 //! Prompts management commands
 //!
-//! Provides commands for listing and showing prompts from ENGRAM_PROMPTS_PATH.
+//! Provides commands for listing, showing and rendering prompts from
+//! ENGRAM_PROMPTS_PATH.
 
+use crate::cli::next::{interpolate, unresolved_placeholders};
+use crate::entities::{Context, Entity, EntityRelationship, Task};
+use crate::error::EngramError;
+use crate::storage::Storage;
 use clap::Subcommand;
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Prompts commands
 #[derive(Debug, Subcommand)]
@@ -29,6 +35,35 @@ pub enum PromptsCommands {
         #[arg(help = "Prompt name or path")]
         name: String,
     },
+    /// Render a prompt template with task fields and variables
+    ///
+    /// Supports `{{task.id}}`, `{{task.title}}`, `{{task.description}}`,
+    /// `{{task.status}}`, `{{task.priority}}`, `{{contexts}}` (linked
+    /// contexts) and any `--var` name.
+    ///
+    ///EXAMPLES:
+    ///  engram prompts render agents/reviewer.md --task <UUID>
+    ///  engram prompts render handoff --task <UUID> --var team=payments --out prompt.md
+    Render {
+        /// Prompt name or path
+        name: String,
+
+        /// Task whose fields and linked contexts fill the template
+        #[arg(long)]
+        task: Option<String>,
+
+        /// Template variable as key=value (repeatable)
+        #[arg(long = "var", value_parser = parse_template_var)]
+        vars: Vec<(String, String)>,
+
+        /// Leave unresolved placeholders in place instead of failing
+        #[arg(long)]
+        allow_missing: bool,
+
+        /// Write the rendered prompt to this file instead of stdout
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
     /// Validate all prompts for evidence-based validation requirements
     Validate {
         /// Category to validate (agents, ai, compliance)
@@ -416,6 +451,147 @@ pub fn show_prompt(name: &str, root: Option<PathBuf>) -> Result<(), std::io::Err
     Ok(())
 }
 
+fn parse_template_var(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_string(), value.to_string()))
+        }
+        _ => Err(format!("Invalid variable '{}': expected key=value", s)),
+    }
+}
+
+/// Locate a prompt file by path relative to the prompts root, or by a file
+/// name fragment inside one of its category directories
+pub fn find_prompt_file(name: &str, root: Option<PathBuf>) -> Option<PathBuf> {
+    let prompts_path = root.unwrap_or_else(get_prompts_path);
+    let direct = prompts_path.join(name);
+    if direct.is_file() {
+        return Some(direct);
+    }
+
+    let search_name = name.to_lowercase();
+    let mut categories: Vec<PathBuf> = fs::read_dir(&prompts_path)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    categories.sort();
+    for category in categories {
+        let mut files: Vec<PathBuf> = fs::read_dir(&category)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file())
+            .collect();
+        files.sort();
+        if let Some(found) = files.into_iter().find(|path| {
+            path.file_name()
+                .map(|n| n.to_string_lossy().to_lowercase().contains(&search_name))
+                .unwrap_or(false)
+        }) {
+            return Some(found);
+        }
+    }
+    None
+}
+
+/// Template values for `task_id`: `task.*` fields and `contexts`, the
+/// contexts listed on the task or linked to it by a relationship
+pub fn task_prompt_context(
+    storage: &dyn Storage,
+    task_id: &str,
+) -> Result<HashMap<String, String>, EngramError> {
+    let task = storage
+        .get(task_id, Task::entity_type())?
+        .ok_or_else(|| EngramError::not_found("task", task_id))
+        .and_then(Task::from_generic)?;
+
+    let mut context_ids = task.context_ids.clone();
+    for relationship in storage
+        .get_all(EntityRelationship::entity_type())?
+        .into_iter()
+        .filter_map(|generic| EntityRelationship::from_generic(generic).ok())
+        .filter(|r| r.active)
+    {
+        let linked = if relationship.source_id == task.id && relationship.target_type == "context" {
+            Some(relationship.target_id)
+        } else if relationship.target_id == task.id && relationship.source_type == "context" {
+            Some(relationship.source_id)
+        } else {
+            None
+        };
+        if let Some(id) = linked.filter(|id| !context_ids.contains(id)) {
+            context_ids.push(id);
+        }
+    }
+    let mut contexts = Vec::new();
+    for id in &context_ids {
+        if let Some(entity) = storage.get(id, Context::entity_type())? {
+            let context = Context::from_generic(entity)?;
+            contexts.push(format!("- {}: {}", context.title, context.content));
+        }
+    }
+
+    Ok(HashMap::from([
+        ("task.id".to_string(), task.id.clone()),
+        ("task.title".to_string(), task.title.clone()),
+        ("task.description".to_string(), task.description.clone()),
+        (
+            "task.status".to_string(),
+            format!("{:?}", task.status).to_lowercase(),
+        ),
+        (
+            "task.priority".to_string(),
+            format!("{:?}", task.priority).to_lowercase(),
+        ),
+        ("contexts".to_string(), contexts.join("\n")),
+    ]))
+}
+
+/// Fill `template` from `context`. Unresolved placeholders are an error
+/// listing them all, unless `allow_missing` leaves them in place.
+pub fn render_prompt_template(
+    template: &str,
+    context: &HashMap<String, String>,
+    allow_missing: bool,
+) -> Result<String, EngramError> {
+    let missing = unresolved_placeholders(template, context);
+    if !missing.is_empty() && !allow_missing {
+        return Err(EngramError::Validation(format!(
+            "Unresolved placeholders: {}",
+            missing
+                .iter()
+                .map(|name| format!("{{{{{}}}}}", name))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )));
+    }
+    Ok(interpolate(template, context))
+}
+
+/// Render the prompt `name`, printing it or writing it to `out`
+pub fn render_prompt(
+    name: &str,
+    root: Option<PathBuf>,
+    context: &HashMap<String, String>,
+    allow_missing: bool,
+    out: Option<&Path>,
+) -> Result<(), EngramError> {
+    let path =
+        find_prompt_file(name, root).ok_or_else(|| EngramError::not_found("prompt", name))?;
+    let rendered = render_prompt_template(&fs::read_to_string(&path)?, context, allow_missing)?;
+    match out {
+        Some(out) => {
+            fs::write(out, rendered)?;
+            println!("Rendered {} to {}", path.display(), out.display());
+        }
+        None => print!("{}", rendered),
+    }
+    Ok(())
+}
+
 /// Validate all prompts for evidence-based validation requirements
 pub fn validate_prompts(
     category: Option<&str>,
@@ -573,4 +749,88 @@ mod tests {
         );
         assert!(result_cat.is_ok());
     }
+
+    #[test]
+    fn test_render_prompt_with_task_and_vars() {
+        use crate::entities::{EntityRelationType, TaskPriority};
+        use crate::storage::MemoryStorage;
+
+        let mut storage = MemoryStorage::new("default");
+        let mut task = Task::new(
+            "Ship checkout".to_string(),
+            "Card payments only".to_string(),
+            "tester".to_string(),
+            TaskPriority::High,
+            None,
+        );
+        let listed = Context::new(
+            "Scope".to_string(),
+            "No wallets".to_string(),
+            "docs".to_string(),
+            crate::entities::ContextRelevance::High,
+            "tester".to_string(),
+        );
+        let linked = Context::new(
+            "Deadline".to_string(),
+            "Friday".to_string(),
+            "docs".to_string(),
+            crate::entities::ContextRelevance::High,
+            "tester".to_string(),
+        );
+        task.context_ids.push(listed.id.clone());
+        for generic in [task.to_generic(), listed.to_generic(), linked.to_generic()] {
+            storage.store(&generic).unwrap();
+        }
+        let relationship = EntityRelationship::new(
+            "rel-1".to_string(),
+            "tester".to_string(),
+            task.id.clone(),
+            "task".to_string(),
+            linked.id.clone(),
+            "context".to_string(),
+            EntityRelationType::References,
+        );
+        storage.store(&relationship.to_generic()).unwrap();
+
+        let mut context = task_prompt_context(&storage, &task.id).unwrap();
+        context.insert("team".to_string(), "payments".to_string());
+        let template =
+            "{{task.title}} ({{task.priority}}) for {{team}}\n{{contexts}}\n{{reviewer}}";
+
+        match render_prompt_template(template, &context, false) {
+            Err(EngramError::Validation(message)) => assert!(message.contains("{{reviewer}}")),
+            other => panic!("expected unresolved placeholder error, got {:?}", other),
+        }
+        let rendered = render_prompt_template(template, &context, true).unwrap();
+        assert_eq!(
+            rendered,
+            "Ship checkout (high) for payments\n- Scope: No wallets\n- Deadline: Friday\n{{reviewer}}"
+        );
+
+        let root = TempDir::new().unwrap();
+        fs::create_dir(root.path().join("agents")).unwrap();
+        fs::write(
+            root.path().join("agents/handoff.md"),
+            "Hand off {{task.title}}",
+        )
+        .unwrap();
+        let found = find_prompt_file("handoff", Some(root.path().to_path_buf())).unwrap();
+        assert!(found.ends_with("agents/handoff.md"));
+        let out = root.path().join("rendered.md");
+        render_prompt(
+            "agents/handoff.md",
+            Some(root.path().to_path_buf()),
+            &context,
+            false,
+            Some(&out),
+        )
+        .unwrap();
+        assert_eq!(fs::read_to_string(out).unwrap(), "Hand off Ship checkout");
+
+        assert_eq!(
+            parse_template_var("team=a=b").unwrap(),
+            ("team".to_string(), "a=b".to_string())
+        );
+        assert!(parse_template_var("novalue").is_err());
+    }
 }
@@ -1,8 +1,11 @@
 //! Reasoning command implementations
 
-use crate::entities::{Entity, Reasoning};
+use crate::cli::adr::next_adr_number;
+use crate::cli::identity::resolve_agent;
+use crate::cli::mentions::{autolink_mentions, print_mentions};
+use crate::entities::{Entity, EntityRelationType, EntityRelationship, Reasoning, ADR};
 use crate::error::EngramError;
-use crate::storage::Storage;
+use crate::storage::{RelationshipStorage, Storage};
 use clap::Subcommand;
 use serde::Deserialize;
 use std::fs;
@@ -69,6 +72,10 @@ pub enum ReasoningCommands {
         /// JSON file path (requires --json)
         #[arg(long, requires = "json")]
         json_file: Option<String>,
+
+        /// Do not link entities mentioned in the text (`@T-42`, `[[id]]`)
+        #[arg(long)]
+        no_autolink: bool,
     },
     /// Add a reasoning step
     AddStep {
@@ -103,6 +110,10 @@ pub enum ReasoningCommands {
         /// Read conclusion from file
         #[arg(long, conflicts_with_all = ["conclusion", "conclusion_stdin"])]
         conclusion_file: Option<String>,
+
+        /// Do not link entities mentioned in the text (`@T-42`, `[[id]]`)
+        #[arg(long)]
+        no_autolink: bool,
     },
     /// Set final conclusion
     Conclude {
@@ -125,6 +136,10 @@ pub enum ReasoningCommands {
         /// Read conclusion from file
         #[arg(long, conflicts_with_all = ["conclusion", "conclusion_stdin"])]
         conclusion_file: Option<String>,
+
+        /// Do not link entities mentioned in the text (`@T-42`, `[[id]]`)
+        #[arg(long)]
+        no_autolink: bool,
     },
     /// List reasoning chains
     List {
@@ -147,6 +162,44 @@ pub enum ReasoningCommands {
         /// Offset for pagination
         #[arg(long, short)]
         offset: Option<usize>,
+
+        #[command(flatten)]
+        sort: SortArgs,
+
+        #[command(flatten)]
+        count: CountArgs,
+    },
+    /// Record an alternative that was considered and rejected
+    AddAlternative {
+        /// Reasoning ID
+        #[arg(help = "Reasoning ID to add alternative to")]
+        id: String,
+
+        /// Alternative description
+        #[arg(long, short)]
+        description: String,
+
+        /// Advantage of the alternative (repeatable)
+        #[arg(long = "pro")]
+        pros: Vec<String>,
+
+        /// Drawback of the alternative (repeatable)
+        #[arg(long = "con")]
+        cons: Vec<String>,
+
+        /// Why the alternative was not chosen
+        #[arg(long)]
+        rejected_reason: Option<String>,
+    },
+    /// Create a proposed ADR from the reasoning's conclusion and alternatives
+    PromoteToAdr {
+        /// Reasoning ID
+        #[arg(help = "Reasoning ID to promote")]
+        id: String,
+
+        /// Agent recorded on the ADR
+        #[arg(long, short)]
+        agent: Option<String>,
     },
     /// Show reasoning details
     Show {
@@ -178,7 +231,7 @@ fn create_reasoning_from_input<S: Storage>(
     storage: &mut S,
     input: ReasoningInput,
 ) -> Result<(), EngramError> {
-    let agent = input.agent.unwrap_or_else(|| "default".to_string());
+    let agent = resolve_agent(input.agent);
 
     let reasoning = Reasoning::new(input.title, input.task_id, agent.clone());
 
@@ -206,6 +259,7 @@ pub fn create_reasoning<S: Storage>(
     content_file: Option<String>,
     json: bool,
     json_file: Option<String>,
+    autolink: bool,
 ) -> Result<(), EngramError> {
     if json {
         let json_content = if let Some(ref file_path) = json_file {
@@ -255,7 +309,7 @@ pub fn create_reasoning<S: Storage>(
     let final_task_id = task_id
         .ok_or_else(|| EngramError::Validation("Task ID required: use --task-id".to_string()))?;
 
-    let final_agent = agent.unwrap_or_else(|| "default".to_string());
+    let final_agent = resolve_agent(agent);
 
     let mut reasoning = Reasoning::new(final_title, final_task_id, final_agent.clone());
 
@@ -280,6 +334,9 @@ pub fn create_reasoning<S: Storage>(
 
     let generic_entity = reasoning.to_generic();
     storage.store(&generic_entity)?;
+    if autolink {
+        autolink_mentions(storage, &generic_entity, &[&reasoning.conclusion])?;
+    }
 
     println!("Reasoning '{}' created successfully", reasoning.id);
     println!("ID: {}", reasoning.id);
@@ -300,6 +357,7 @@ pub fn add_reasoning_step<S: Storage>(
     description_file: Option<String>,
     conclusion_stdin: bool,
     conclusion_file: Option<String>,
+    autolink: bool,
 ) -> Result<(), EngramError> {
     let final_description = if description_stdin {
         read_stdin()?
@@ -343,15 +401,21 @@ pub fn add_reasoning_step<S: Storage>(
 
             let updated_entity = reasoning.to_generic();
             storage.store(&updated_entity)?;
+            if autolink {
+                if let Some(step) = reasoning.steps.last() {
+                    autolink_mentions(
+                        storage,
+                        &updated_entity,
+                        &[&step.description, &step.conclusion],
+                    )?;
+                }
+            }
 
             println!("Added step to reasoning '{}' successfully", reasoning.title);
             println!("Step count: {}", reasoning.steps.len());
         }
         None => {
-            return Err(EngramError::NotFound(format!(
-                "Reasoning with ID '{}' not found",
-                id
-            )));
+            return Err(EngramError::not_found("reasoning", id));
         }
     }
 
@@ -365,6 +429,7 @@ pub fn conclude_reasoning<S: Storage>(
     confidence: f64,
     conclusion_stdin: bool,
     conclusion_file: Option<String>,
+    autolink: bool,
 ) -> Result<(), EngramError> {
     let final_conclusion = if conclusion_stdin {
         read_stdin()?
@@ -395,24 +460,25 @@ pub fn conclude_reasoning<S: Storage>(
 
             let updated_entity = reasoning.to_generic();
             storage.store(&updated_entity)?;
+            if autolink {
+                autolink_mentions(storage, &updated_entity, &[&reasoning.conclusion])?;
+            }
 
             println!("Reasoning '{}' concluded successfully", reasoning.title);
             println!("Final confidence: {}", reasoning.confidence);
         }
         None => {
-            return Err(EngramError::NotFound(format!(
-                "Reasoning with ID '{}' not found",
-                id
-            )));
+            return Err(EngramError::not_found("reasoning", id));
         }
     }
 
     Ok(())
 }
 
-use crate::cli::utils::{create_table, truncate};
+use crate::cli::utils::{create_table, truncate, CountArgs, SortArgs};
 use prettytable::row;
 
+#[allow(clippy::too_many_arguments)]
 pub fn list_reasoning<S: Storage>(
     storage: &S,
     agent: Option<&str>,
@@ -420,6 +486,8 @@ pub fn list_reasoning<S: Storage>(
     limit: Option<usize>,
     all: bool,
     offset: Option<usize>,
+    sort: &SortArgs,
+    count: &CountArgs,
 ) -> Result<(), EngramError> {
     let mut filter = crate::storage::QueryFilter {
         entity_type: Some("reasoning".to_string()),
@@ -428,6 +496,7 @@ pub fn list_reasoning<S: Storage>(
         offset,
         ..Default::default()
     };
+    sort.apply(&mut filter);
 
     if let Some(tid) = task_id {
         filter.field_filters.insert(
@@ -436,6 +505,10 @@ pub fn list_reasoning<S: Storage>(
         );
     }
 
+    if count.write(&mut std::io::stdout(), storage, &filter)? {
+        return Ok(());
+    }
+
     let result = storage.query(&filter)?;
 
     if result.entities.is_empty() {
@@ -485,6 +558,7 @@ pub fn show_reasoning<S: Storage>(storage: &S, id: &str) -> Result<(), EngramErr
 
     match entity {
         Some(generic_entity) => {
+            let modification = generic_entity.modification();
             let reasoning = Reasoning::from_generic(generic_entity)
                 .map_err(|e| EngramError::Validation(e.to_string()))?;
 
@@ -494,6 +568,9 @@ pub fn show_reasoning<S: Storage>(storage: &S, id: &str) -> Result<(), EngramErr
             println!("Title: {}", reasoning.title);
             println!("Task ID: {}", reasoning.task_id);
             println!("Agent: {}", reasoning.agent);
+            if let Some(modified) = modification.summary() {
+                println!("Last Modified By: {}", modified);
+            }
             println!(
                 "Created: {}",
                 reasoning.created_at.format("%Y-%m-%d %H:%M:%S UTC")
@@ -527,21 +604,157 @@ pub fn show_reasoning<S: Storage>(storage: &S, id: &str) -> Result<(), EngramErr
                 println!("  Overall Confidence: {:.2}", reasoning.confidence);
             }
 
+            if !reasoning.alternatives.is_empty() {
+                println!();
+                println!("Alternatives Considered: {}", reasoning.alternatives.len());
+                for (i, alt) in reasoning.alternatives.iter().enumerate() {
+                    println!("  {}. {}", i + 1, alt.description);
+                    if !alt.pros.is_empty() {
+                        println!("     Pros: {}", alt.pros.join("; "));
+                    }
+                    if !alt.cons.is_empty() {
+                        println!("     Cons: {}", alt.cons.join("; "));
+                    }
+                    if let Some(ref reason) = alt.rejection_reason {
+                        println!("     Rejected: {}", reason);
+                    }
+                }
+            }
+
+            if let Some(adr_id) = reasoning.metadata.get(ADR_ID_KEY).and_then(|v| v.as_str()) {
+                println!("Promoted To ADR: {}", adr_id);
+            }
+            print_mentions(storage, &reasoning.id)?;
+
             if !reasoning.tags.is_empty() {
                 println!("Tags: {}", reasoning.tags.join(", "));
             }
         }
         None => {
-            return Err(EngramError::NotFound(format!(
-                "Reasoning with ID '{}' not found",
-                id
-            )));
+            return Err(EngramError::not_found("reasoning", id));
         }
     }
 
     Ok(())
 }
 
+/// Reasoning metadata key holding the ID of the ADR it was promoted to
+const ADR_ID_KEY: &str = "adr_id";
+
+pub fn add_reasoning_alternative<S: Storage>(
+    storage: &mut S,
+    id: &str,
+    description: String,
+    pros: Vec<String>,
+    cons: Vec<String>,
+    rejected_reason: Option<String>,
+) -> Result<(), EngramError> {
+    if description.trim().is_empty() {
+        return Err(EngramError::Validation(
+            "Alternative description cannot be empty".to_string(),
+        ));
+    }
+
+    let generic_entity = storage
+        .get(id, "reasoning")?
+        .ok_or_else(|| EngramError::not_found("reasoning", id))?;
+    let mut reasoning = Reasoning::from_generic(generic_entity)
+        .map_err(|e| EngramError::Validation(e.to_string()))?;
+
+    let alternative_id = reasoning.add_alternative(description, pros, cons, rejected_reason);
+    storage.store(&reasoning.to_generic())?;
+
+    println!(
+        "Added alternative to reasoning '{}' successfully",
+        reasoning.title
+    );
+    println!("Alternative ID: {}", alternative_id);
+    println!("Alternatives: {}", reasoning.alternatives.len());
+
+    Ok(())
+}
+
+/// Create a proposed ADR from a concluded reasoning chain, numbered after the
+/// existing ADRs and linked back to the reasoning. Returns the ADR ID.
+pub fn promote_reasoning_to_adr<S: Storage + RelationshipStorage>(
+    storage: &mut S,
+    id: &str,
+    agent: Option<String>,
+) -> Result<String, EngramError> {
+    let generic_entity = storage
+        .get(id, "reasoning")?
+        .ok_or_else(|| EngramError::not_found("reasoning", id))?;
+    let mut reasoning = Reasoning::from_generic(generic_entity)
+        .map_err(|e| EngramError::Validation(e.to_string()))?;
+
+    if reasoning.conclusion.is_empty() {
+        return Err(EngramError::Validation(format!(
+            "Reasoning '{}' has no conclusion; run 'engram reasoning conclude {}' first",
+            reasoning.id, reasoning.id
+        )));
+    }
+    if let Some(adr_id) = reasoning.metadata.get(ADR_ID_KEY).and_then(|v| v.as_str()) {
+        if storage.exists(adr_id, "adr")? {
+            return Err(EngramError::AlreadyExists(format!(
+                "Reasoning '{}' was already promoted to ADR '{}'",
+                reasoning.id, adr_id
+            )));
+        }
+    }
+
+    let mut context = format!(
+        "Promoted from reasoning '{}' ({}) for task {}.",
+        reasoning.title, reasoning.id, reasoning.task_id
+    );
+    if !reasoning.steps.is_empty() {
+        context.push('\n');
+        for step in &reasoning.steps {
+            context.push_str(&format!("\n- {}: {}", step.description, step.conclusion));
+        }
+    }
+
+    let mut adr = ADR::new(
+        reasoning.title.clone(),
+        next_adr_number(storage)?,
+        resolve_agent(agent),
+        context,
+    );
+    adr.decision = reasoning.conclusion.clone();
+    adr.alternatives = reasoning.alternatives.clone();
+    adr.tags = reasoning.tags.clone();
+    adr.metadata.insert(
+        "reasoning_id".to_string(),
+        serde_json::Value::String(reasoning.id.clone()),
+    );
+    storage.store(&adr.to_generic())?;
+
+    storage.store_relationship(&EntityRelationship::new(
+        uuid::Uuid::new_v4().to_string(),
+        adr.agent.clone(),
+        adr.id.clone(),
+        ADR::entity_type().to_string(),
+        reasoning.id.clone(),
+        Reasoning::entity_type().to_string(),
+        EntityRelationType::References,
+    ))?;
+
+    reasoning.metadata.insert(
+        ADR_ID_KEY.to_string(),
+        serde_json::Value::String(adr.id.clone()),
+    );
+    storage.store(&reasoning.to_generic())?;
+    tracing::info!(reasoning_id = %reasoning.id, adr_id = %adr.id, number = adr.number, "reasoning promoted to ADR");
+
+    println!(
+        "Reasoning '{}' promoted to ADR-{:03}",
+        reasoning.title, adr.number
+    );
+    println!("ADR ID: {}", adr.id);
+    println!("Alternatives carried over: {}", adr.alternatives.len());
+
+    Ok(adr.id)
+}
+
 pub fn delete_reasoning<S: Storage>(storage: &mut S, id: &str) -> Result<(), EngramError> {
     let entity = storage.get(id, "reasoning")?;
 
@@ -557,10 +770,7 @@ pub fn delete_reasoning<S: Storage>(storage: &mut S, id: &str) -> Result<(), Eng
             println!("Task ID: {}", reasoning.task_id);
         }
         None => {
-            return Err(EngramError::NotFound(format!(
-                "Reasoning with ID '{}' not found",
-                id
-            )));
+            return Err(EngramError::not_found("reasoning", id));
         }
     }
 
@@ -593,6 +803,7 @@ mod tests {
             None,
             false,
             None,
+            true,
         );
         assert!(result.is_ok());
 
@@ -624,6 +835,7 @@ mod tests {
             None,
             false,
             None,
+            true,
         );
         assert!(matches!(result, Err(EngramError::Validation(_))));
 
@@ -642,6 +854,7 @@ mod tests {
             None,
             false,
             None,
+            true,
         );
         assert!(matches!(result, Err(EngramError::Validation(_))));
     }
@@ -663,6 +876,7 @@ mod tests {
             None,
             false,
             None,
+            true,
         )
         .unwrap();
 
@@ -681,6 +895,7 @@ mod tests {
             None,
             false,
             None,
+            true,
         );
         assert!(result.is_ok());
 
@@ -708,6 +923,7 @@ mod tests {
             None,
             false,
             None,
+            true,
         )
         .unwrap();
 
@@ -723,6 +939,7 @@ mod tests {
             0.95,
             false,
             None,
+            true,
         );
         assert!(result.is_ok());
 
@@ -749,6 +966,7 @@ mod tests {
             None,
             false,
             None,
+            true,
         )
         .unwrap();
 
@@ -776,8 +994,9 @@ mod tests {
             None,
             false,
             None,
+            true,
         );
-        assert!(matches!(result, Err(EngramError::NotFound(_))));
+        assert!(matches!(result, Err(EngramError::NotFound { .. })));
     }
 
     #[test]
@@ -797,6 +1016,7 @@ mod tests {
             None,
             false,
             None,
+            true,
         )
         .unwrap();
 
@@ -815,6 +1035,7 @@ mod tests {
             None,
             false,
             None,
+            true,
         );
         assert!(matches!(result, Err(EngramError::Validation(_))));
     }
@@ -836,6 +1057,7 @@ mod tests {
             None,
             false,
             None,
+            true,
         )
         .unwrap();
 
@@ -854,6 +1076,7 @@ mod tests {
             None,
             false,
             None,
+            true,
         );
         assert!(matches!(result, Err(EngramError::Validation(_))));
     }
@@ -868,8 +1091,9 @@ mod tests {
             0.9,
             false,
             None,
+            true,
         );
-        assert!(matches!(result, Err(EngramError::NotFound(_))));
+        assert!(matches!(result, Err(EngramError::NotFound { .. })));
     }
 
     #[test]
@@ -889,6 +1113,7 @@ mod tests {
             None,
             false,
             None,
+            true,
         )
         .unwrap();
 
@@ -904,6 +1129,7 @@ mod tests {
             -0.1, // Invalid confidence
             false,
             None,
+            true,
         );
         assert!(matches!(result, Err(EngramError::Validation(_))));
     }
@@ -912,14 +1138,14 @@ mod tests {
     fn test_delete_reasoning_not_found() {
         let mut storage = create_test_storage();
         let result = delete_reasoning(&mut storage, "non-existent-id");
-        assert!(matches!(result, Err(EngramError::NotFound(_))));
+        assert!(matches!(result, Err(EngramError::NotFound { .. })));
     }
 
     #[test]
     fn test_show_reasoning_not_found() {
         let storage = create_test_storage();
         let result = show_reasoning(&storage, "non-existent-id");
-        assert!(matches!(result, Err(EngramError::NotFound(_))));
+        assert!(matches!(result, Err(EngramError::NotFound { .. })));
     }
 
     #[test]
@@ -939,6 +1165,7 @@ mod tests {
             None,
             false,
             None,
+            true,
         )
         .unwrap();
 
@@ -956,17 +1183,48 @@ mod tests {
             None,
             false,
             None,
+            true,
         )
         .unwrap();
 
         // No filters
-        assert!(list_reasoning(&storage, None, None, None, false, None).is_ok());
+        assert!(list_reasoning(
+            &storage,
+            None,
+            None,
+            None,
+            false,
+            None,
+            &SortArgs::default(),
+            &CountArgs::default()
+        )
+        .is_ok());
 
         // Filter by agent
-        assert!(list_reasoning(&storage, Some("agent1"), None, None, false, None).is_ok());
+        assert!(list_reasoning(
+            &storage,
+            Some("agent1"),
+            None,
+            None,
+            false,
+            None,
+            &SortArgs::default(),
+            &CountArgs::default()
+        )
+        .is_ok());
 
         // Filter by task
-        assert!(list_reasoning(&storage, None, Some("task-2"), None, false, None).is_ok());
+        assert!(list_reasoning(
+            &storage,
+            None,
+            Some("task-2"),
+            None,
+            false,
+            None,
+            &SortArgs::default(),
+            &CountArgs::default()
+        )
+        .is_ok());
     }
 
     #[test]
@@ -986,6 +1244,7 @@ mod tests {
             None,
             false,
             None,
+            true,
         )
         .unwrap();
 
@@ -1014,7 +1273,73 @@ mod tests {
             None,
             false,
             None,
+            true,
         );
         assert!(matches!(result, Err(EngramError::Validation(_))));
     }
+
+    #[test]
+    fn test_promote_reasoning_to_adr() {
+        let mut storage = create_test_storage();
+        let existing = ADR::new(
+            "Existing decision".to_string(),
+            3,
+            "default".to_string(),
+            "ctx".to_string(),
+        );
+        storage.store(&existing.to_generic()).unwrap();
+
+        let mut reasoning = Reasoning::new(
+            "Choose a cache".to_string(),
+            "task-1".to_string(),
+            "default".to_string(),
+        );
+        reasoning.add_step("Compare latency".to_string(), "LRU wins".to_string(), 0.8);
+        storage.store(&reasoning.to_generic()).unwrap();
+
+        let unconcluded = promote_reasoning_to_adr(&mut storage, &reasoning.id, None);
+        assert!(matches!(unconcluded, Err(EngramError::Validation(_))));
+
+        add_reasoning_alternative(
+            &mut storage,
+            &reasoning.id,
+            "Redis".to_string(),
+            vec!["shared".to_string()],
+            vec!["extra service".to_string()],
+            Some("operational cost".to_string()),
+        )
+        .unwrap();
+        conclude_reasoning(
+            &mut storage,
+            &reasoning.id,
+            Some("Use an in-process LRU cache".to_string()),
+            0.9,
+            false,
+            None,
+            true,
+        )
+        .unwrap();
+
+        let adr_id = promote_reasoning_to_adr(&mut storage, &reasoning.id, None).unwrap();
+        let adr = ADR::from_generic(storage.get(&adr_id, "adr").unwrap().unwrap()).unwrap();
+        assert_eq!(adr.number, 4);
+        assert_eq!(adr.decision, "Use an in-process LRU cache");
+        assert_eq!(adr.alternatives.len(), 1);
+        assert_eq!(
+            adr.alternatives[0].rejection_reason.as_deref(),
+            Some("operational cost")
+        );
+        assert!(adr.context.contains("Compare latency: LRU wins"));
+
+        let relationships = storage.get_entity_relationships(&adr_id).unwrap();
+        assert_eq!(relationships.len(), 1);
+        assert_eq!(relationships[0].target_id, reasoning.id);
+        assert_eq!(
+            relationships[0].relationship_type,
+            EntityRelationType::References
+        );
+
+        let again = promote_reasoning_to_adr(&mut storage, &reasoning.id, None);
+        assert!(matches!(again, Err(EngramError::AlreadyExists(_))));
+    }
 }
@@ -1,3 +1,6 @@
+use crate::api::Engram;
+use crate::cli::identity::resolve_agent;
+use crate::cli::utils::SortArgs;
 use crate::entities::{
     Entity, EntityRelationType, EntityRelationship, RelationshipDirection, RelationshipFilter,
     RelationshipStrength,
@@ -5,6 +8,9 @@ use crate::entities::{
 use crate::error::EngramError;
 use crate::storage::{RelationshipStorage, Storage, TraversalAlgorithm};
 use clap::Subcommand;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tracing::instrument;
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Subcommand)]
@@ -77,6 +83,9 @@ pub enum RelationshipCommands {
         /// Filter by agent
         #[arg(long)]
         agent: Option<String>,
+
+        #[command(flatten)]
+        sort: SortArgs,
     },
 
     /// Show relationship details
@@ -130,9 +139,69 @@ pub enum RelationshipCommands {
 
     /// Show relationship statistics
     Stats {},
+
+    /// Import relationships from a CSV file
+    ///
+    /// Columns: source_id, source_type, target_id, target_type,
+    /// relationship_type, and optionally direction, strength, description.
+    ///
+    ///EXAMPLES:
+    ///  engram relationship import --file rels.csv
+    ///  engram --dry-run relationship import --file rels.csv --agent planner
+    Import {
+        /// CSV file with a header row
+        #[arg(long)]
+        file: PathBuf,
+
+        /// Agent recorded as the creator of the relationships
+        #[arg(long)]
+        agent: Option<String>,
+    },
+}
+
+impl RelationshipCommands {
+    /// Operation name recorded on the command's tracing span
+    fn operation(&self) -> &'static str {
+        match self {
+            RelationshipCommands::Create { .. } => "create",
+            RelationshipCommands::List { .. } => "list",
+            RelationshipCommands::Get { .. } => "get",
+            RelationshipCommands::Delete { .. } => "delete",
+            RelationshipCommands::FindPath { .. } => "find_path",
+            RelationshipCommands::Connected { .. } => "connected",
+            RelationshipCommands::Stats { .. } => "stats",
+            RelationshipCommands::Import { .. } => "import",
+        }
+    }
+}
+
+/// One row of a relationship import CSV
+#[derive(Debug, Deserialize)]
+struct RelationshipCsvRow {
+    source_id: String,
+    source_type: String,
+    target_id: String,
+    target_type: String,
+    relationship_type: String,
+    #[serde(default)]
+    direction: Option<String>,
+    #[serde(default)]
+    strength: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
 }
 
-fn parse_relationship_type(s: &str) -> Result<EntityRelationType, String> {
+/// Outcome of `import_relationships_from_csv`
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RelationshipImportReport {
+    pub rows_processed: usize,
+    pub relationships_created: usize,
+    pub rows_skipped: usize,
+    /// (row number counting from 1 after the header, error)
+    pub errors: Vec<(usize, String)>,
+}
+
+pub(crate) fn parse_relationship_type(s: &str) -> Result<EntityRelationType, String> {
     match s.to_lowercase().as_str() {
         "depends_on" | "depends-on" => Ok(EntityRelationType::DependsOn),
         "contains" => Ok(EntityRelationType::Contains),
@@ -190,7 +259,8 @@ fn parse_algorithm(s: &str) -> Result<TraversalAlgorithm, String> {
     }
 }
 
-pub fn handle_relationship_command<S: RelationshipStorage>(
+#[instrument(skip_all, fields(entity_type = "relationship", operation = command.operation()))]
+pub fn handle_relationship_command<S: RelationshipStorage + 'static>(
     storage: &mut S,
     command: RelationshipCommands,
 ) -> Result<(), EngramError> {
@@ -226,6 +296,7 @@ pub fn handle_relationship_command<S: RelationshipStorage>(
             direction,
             active_only,
             agent,
+            sort,
         } => list_relationships(
             storage,
             entity_id,
@@ -235,6 +306,7 @@ pub fn handle_relationship_command<S: RelationshipStorage>(
             direction,
             active_only,
             agent,
+            &sort,
         ),
 
         RelationshipCommands::Get { id } => show_relationship(storage, &id),
@@ -255,9 +327,104 @@ pub fn handle_relationship_command<S: RelationshipStorage>(
         } => show_connected(storage, &entity_id, &algorithm, max_depth),
 
         RelationshipCommands::Stats {} => show_stats(storage),
+
+        RelationshipCommands::Import { file, agent } => {
+            let report = import_relationships_from_csv(&file, storage, &resolve_agent(agent))?;
+            println!(
+                "✅ Imported {} relationship(s) from {} row(s)",
+                report.relationships_created, report.rows_processed
+            );
+            if report.rows_skipped > 0 {
+                println!("⚠️  Skipped {} row(s):", report.rows_skipped);
+                for (row, error) in &report.errors {
+                    println!("  Row {}: {}", row, error);
+                }
+            }
+            Ok(())
+        }
     }
 }
 
+fn relationship_from_row(
+    storage: &dyn Storage,
+    row: RelationshipCsvRow,
+    agent: &str,
+) -> Result<EntityRelationship, String> {
+    if row.relationship_type.trim().is_empty() {
+        return Err("relationship_type is empty".to_string());
+    }
+    let relationship_type = parse_relationship_type(row.relationship_type.trim())?;
+    let direction = parse_direction(row.direction.as_deref().unwrap_or("unidirectional"))?;
+    let strength = parse_strength(row.strength.as_deref().unwrap_or("medium"))?;
+
+    for (id, entity_type) in [
+        (&row.source_id, &row.source_type),
+        (&row.target_id, &row.target_type),
+    ] {
+        match storage.exists(id, entity_type) {
+            Ok(true) => {}
+            Ok(false) => return Err(format!("{} '{}' not found", entity_type, id)),
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+
+    let mut relationship = EntityRelationship::new(
+        Uuid::new_v4().to_string(),
+        agent.to_string(),
+        row.source_id,
+        row.source_type,
+        row.target_id,
+        row.target_type,
+        relationship_type,
+    )
+    .with_direction(direction)
+    .with_strength(strength);
+    if let Some(description) = row.description.filter(|d| !d.is_empty()) {
+        relationship = relationship.with_description(description);
+    }
+    relationship.validate_entity().map_err(|e| e.to_string())?;
+    Ok(relationship)
+}
+
+/// Import relationships from a CSV file with a header row
+///
+/// Rows that fail to parse, reference missing entities or fail validation
+/// are skipped and reported; the rest are stored in one `bulk_store`.
+#[instrument(skip_all, fields(entity_type = "relationship", operation = "import", path = %path.display()))]
+pub fn import_relationships_from_csv(
+    path: &Path,
+    storage: &mut dyn Storage,
+    agent: &str,
+) -> Result<RelationshipImportReport, EngramError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_path(path)
+        .map_err(|e| {
+            EngramError::Validation(format!("Cannot read CSV {}: {}", path.display(), e))
+        })?;
+
+    let mut report = RelationshipImportReport::default();
+    let mut relationships = Vec::new();
+    for (index, record) in reader.deserialize::<RelationshipCsvRow>().enumerate() {
+        let row = index + 1;
+        report.rows_processed += 1;
+        let result = record
+            .map_err(|e| e.to_string())
+            .and_then(|record| relationship_from_row(storage, record, agent));
+        match result {
+            Ok(relationship) => relationships.push(relationship.to_generic()),
+            Err(error) => {
+                report.rows_skipped += 1;
+                report.errors.push((row, error));
+            }
+        }
+    }
+
+    storage.bulk_store(&relationships)?;
+    report.relationships_created = relationships.len();
+    Ok(report)
+}
+
 fn create_relationship<S: Storage>(
     storage: &mut S,
     source_id: String,
@@ -278,7 +445,7 @@ fn create_relationship<S: Storage>(
 
     let mut relationship = EntityRelationship::new(
         id,
-        agent,
+        agent.clone(),
         source_id,
         source_type,
         target_id,
@@ -292,12 +459,7 @@ fn create_relationship<S: Storage>(
         relationship = relationship.with_description(desc);
     }
 
-    relationship
-        .validate_entity()
-        .map_err(|e| EngramError::Validation(e.to_string()))?;
-
-    let generic = relationship.to_generic();
-    storage.store(&generic)?;
+    let relationship = Engram::with_storage(storage, agent).add_relationship(relationship)?;
 
     println!("✅ Relationship created successfully");
     println!("📋 ID: {}", relationship.id);
@@ -309,6 +471,7 @@ fn create_relationship<S: Storage>(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn list_relationships<S: Storage>(
     _storage: &S,
     entity_id: Option<String>,
@@ -318,6 +481,7 @@ fn list_relationships<S: Storage>(
     direction: Option<String>,
     active_only: bool,
     agent: Option<String>,
+    sort: &SortArgs,
 ) -> Result<(), EngramError> {
     let mut filter = RelationshipFilter::new();
 
@@ -344,7 +508,7 @@ fn list_relationships<S: Storage>(
         filter.agent = Some(ag);
     }
 
-    let relationships = _storage.get_all("relationship")?;
+    let relationships = sort.sort_generic("relationship", _storage.get_all("relationship")?)?;
 
     println!("🔗 Entity Relationships");
     println!("======================");
@@ -458,10 +622,7 @@ fn show_relationship<S: Storage>(storage: &S, id: &str) -> Result<(), EngramErro
         }
         None => {
             println!("❌ Relationship not found: {}", id);
-            Err(EngramError::NotFound(format!(
-                "Relationship with ID '{}' not found",
-                id
-            )))
+            Err(EngramError::not_found("relationship", id))
         }
     }
 }
@@ -481,10 +642,7 @@ fn delete_relationship<S: Storage>(
         }
         None => {
             println!("❌ Relationship not found: {}", id);
-            Err(EngramError::NotFound(format!(
-                "Relationship with ID '{}' not found",
-                id
-            )))
+            Err(EngramError::not_found("relationship", id))
         }
     }
 }
@@ -673,6 +831,7 @@ mod tests {
             None,
             false,
             None,
+            &SortArgs::default(),
         );
         assert!(list_result.is_ok());
 
@@ -741,4 +900,74 @@ mod tests {
         let result = delete_relationship(&mut storage, "non-existent", "agent");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_import_relationships_from_csv() {
+        use crate::entities::{Context, ContextRelevance, Task, TaskPriority};
+
+        let mut storage = MemoryStorage::new("default");
+        let task = Task::new(
+            "Task".to_string(),
+            String::new(),
+            "default".to_string(),
+            TaskPriority::Medium,
+            None,
+        );
+        let other = Task::new(
+            "Other".to_string(),
+            String::new(),
+            "default".to_string(),
+            TaskPriority::Medium,
+            None,
+        );
+        let context = Context::new(
+            "Context".to_string(),
+            "Background".to_string(),
+            "notes".to_string(),
+            ContextRelevance::Medium,
+            "default".to_string(),
+        );
+        for generic in [task.to_generic(), other.to_generic(), context.to_generic()] {
+            storage.store(&generic).unwrap();
+        }
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("rels.csv");
+        std::fs::write(
+            &path,
+            format!(
+                "source_id,source_type,target_id,target_type,relationship_type,direction,strength,description\n\
+                 {t},task,{o},task,depends_on,,,\n\
+                 {t},task,{c},context,references,bidirectional,strong,Background reading\n\
+                 {o},task,{c},context,associated-with,,0.3,\n\
+                 {o},task,missing,context,references,,,\n",
+                t = task.id,
+                o = other.id,
+                c = context.id
+            ),
+        )
+        .unwrap();
+
+        let report = import_relationships_from_csv(&path, &mut storage, "importer").unwrap();
+        assert_eq!(report.rows_processed, 4);
+        assert_eq!(report.relationships_created, 3);
+        assert_eq!(report.rows_skipped, 1);
+        assert_eq!(report.errors[0].0, 4);
+        assert!(report.errors[0].1.contains("missing"));
+
+        let stored: Vec<EntityRelationship> = storage
+            .get_all("relationship")
+            .unwrap()
+            .into_iter()
+            .map(|e| EntityRelationship::from_generic(e).unwrap())
+            .collect();
+        assert_eq!(stored.len(), 3);
+        assert!(stored.iter().all(|r| r.agent == "importer"));
+        let reference = stored
+            .iter()
+            .find(|r| r.relationship_type == EntityRelationType::References)
+            .unwrap();
+        assert_eq!(reference.direction, RelationshipDirection::Bidirectional);
+        assert_eq!(reference.description.as_deref(), Some("Background reading"));
+    }
 }
@@ -0,0 +1,74 @@
+//! Report command implementations
+
+use crate::analytics::{generate_markdown_report, MarkdownReportOptions};
+use crate::cli::identity::resolve_agent_filter;
+use crate::cli::session::parse_since;
+use crate::error::EngramError;
+use crate::redaction::{RedactionStats, Redactor};
+use crate::storage::Storage;
+use chrono::Utc;
+use clap::Subcommand;
+use std::path::{Path, PathBuf};
+
+/// Report commands
+#[derive(Debug, Subcommand)]
+pub enum ReportCommands {
+    /// Markdown digest of completed and in-progress tasks, new ADRs and notable knowledge
+    ///
+    ///EXAMPLES:
+    ///  engram report markdown
+    ///  engram report markdown --since 14d --agent me --out report.md
+    ///  engram report markdown --redact
+    Markdown {
+        /// Start of the report window (7d, 24h, 2024-01-01)
+        #[arg(long, default_value = "7d")]
+        since: String,
+
+        /// Only include entities from this agent (`me` is accepted)
+        #[arg(long)]
+        agent: Option<String>,
+
+        /// Write the report to this file instead of stdout
+        #[arg(long)]
+        out: Option<PathBuf>,
+
+        /// Replace likely secrets with `[REDACTED:<kind>]`
+        #[arg(long)]
+        redact: bool,
+    },
+}
+
+/// Handle `engram report`
+pub fn handle_report_command(
+    storage: &dyn Storage,
+    command: ReportCommands,
+) -> Result<(), EngramError> {
+    match command {
+        ReportCommands::Markdown {
+            since,
+            agent,
+            out,
+            redact,
+        } => {
+            let options = MarkdownReportOptions {
+                since: parse_since(&since)?,
+                now: Utc::now(),
+                agent: resolve_agent_filter(agent),
+            };
+            let mut report = generate_markdown_report(storage, &options)?;
+            if redact {
+                let mut stats = RedactionStats::default();
+                report = Redactor::load(Path::new("."))?.redact(&report, &mut stats);
+                eprintln!("{}", stats.summary());
+            }
+            match out {
+                Some(path) => {
+                    std::fs::write(&path, report)?;
+                    println!("📝 Report written to {}", path.display());
+                }
+                None => print!("{}", report),
+            }
+        }
+    }
+    Ok(())
+}
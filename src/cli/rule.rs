@@ -1,7 +1,19 @@
-use crate::entities::{Entity, Rule, RulePriority, RuleStatus, RuleType};
+use crate::cli::relationship::parse_relationship_type;
+use crate::engines::rule_engine::{
+    create_entity_spec, rule_expressions, EntityCreation, RuleBatchSummary, RuleExecutionEngine,
+    RuleTrace, SPAWNED_ENTITIES_KEY,
+};
+use crate::entities::{
+    Context, ContextRelevance, Entity, EntityRelationship, EscalationOperationType,
+    EscalationPriority, EscalationRequest, GenericEntity, OperationContext, Rule, RulePriority,
+    RuleStatus, RuleType, Task, TaskPriority,
+};
 use crate::error::EngramError;
 use crate::storage::Storage;
 use clap::Subcommand;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
 
 /// Rule commands
 #[derive(Debug, Subcommand)]
@@ -16,7 +28,7 @@ pub enum RuleCommands {
         #[arg(long)]
         description: Option<String>,
 
-        /// Rule type (validation, transformation, enforcement, notification)
+        /// Rule type (validation, transformation, enforcement, notification, compliance)
         #[arg(long, default_value = "validation")]
         rule_type: String,
 
@@ -32,13 +44,20 @@ pub enum RuleCommands {
         #[arg(long, default_value = "{}")]
         condition: String,
 
-        /// Rule action (JSON)
+        /// Rule action (JSON), e.g. {"type": "create_entity", "entity_type":
+        /// "task", "fields": {"title": "Follow up {{source.title}}"},
+        /// "relationship": "depends_on"}
         #[arg(long, default_value = "{}")]
         action: String,
 
         /// Agent to assign
         #[arg(long, short)]
         agent: Option<String>,
+
+        /// Exclusive group: only the highest-priority matching rule in a
+        /// group fires
+        #[arg(long)]
+        exclusive_group: Option<String>,
     },
     /// Get rule details
     Get {
@@ -83,6 +102,10 @@ pub enum RuleCommands {
         /// Rule status (active, inactive, deprecated)
         #[arg(long)]
         status: Option<String>,
+
+        /// Exclusive group (empty string clears it)
+        #[arg(long)]
+        exclusive_group: Option<String>,
     },
     /// Delete rule
     Delete {
@@ -123,6 +146,12 @@ pub enum RuleCommands {
         /// Show all results (no limit)
         #[arg(long, conflicts_with = "limit")]
         all: bool,
+
+        #[command(flatten)]
+        sort: SortArgs,
+
+        #[command(flatten)]
+        count: CountArgs,
     },
     /// Execute rule
     Execute {
@@ -138,6 +167,59 @@ pub enum RuleCommands {
         #[arg(long)]
         entity_type: String,
     },
+    /// Run all active rules against an entity in priority order, applying
+    /// field updates and reporting suppressed actions
+    RunAll {
+        /// Target entity ID
+        #[arg(long)]
+        entity_id: String,
+
+        /// Target entity type
+        #[arg(long)]
+        entity_type: String,
+
+        /// Report what would happen without storing field updates
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Output format
+        #[arg(long, default_value = "table", value_parser = ["table", "json"])]
+        format: String,
+    },
+    /// Evaluate a rule against an entity and show each condition's trace,
+    /// without running its action
+    Test {
+        /// Rule ID
+        #[arg(help = "Rule ID to test")]
+        id: String,
+
+        /// JSON file holding an entity, or just its data fields
+        #[arg(
+            long,
+            required_unless_present = "entity_id",
+            conflicts_with = "entity_id"
+        )]
+        fixture: Option<PathBuf>,
+
+        /// Stored entity to test against
+        #[arg(long)]
+        entity_id: Option<String>,
+
+        /// Type of the stored entity (defaults to the rule's entity types)
+        #[arg(long, requires = "entity_id")]
+        entity_type: Option<String>,
+
+        /// Output format
+        #[arg(long, default_value = "table", value_parser = ["table", "json"])]
+        format: String,
+    },
+    /// Report rule conditions that cannot parse or reference fields missing
+    /// from the rule's entity types
+    Lint {
+        /// Output format
+        #[arg(long, default_value = "table", value_parser = ["table", "json"])]
+        format: String,
+    },
 }
 
 /// Create a new rule
@@ -151,15 +233,17 @@ pub fn create_rule<S: Storage>(
     condition: String,
     action: String,
     agent: Option<String>,
+    exclusive_group: Option<String>,
 ) -> Result<(), EngramError> {
     let rule_type = match rule_type.to_lowercase().as_str() {
         "validation" => RuleType::Validation,
         "transformation" => RuleType::Transformation,
         "enforcement" => RuleType::Enforcement,
         "notification" => RuleType::Notification,
+        "compliance" => RuleType::Compliance,
         _ => {
             println!(
-                "❌ Invalid rule type. Use: validation, transformation, enforcement, notification"
+                "❌ Invalid rule type. Use: validation, transformation, enforcement, notification, compliance"
             );
             return Ok(());
         }
@@ -181,6 +265,7 @@ pub fn create_rule<S: Storage>(
 
     let action_json: serde_json::Value = serde_json::from_str(&action)
         .map_err(|e| EngramError::Validation(format!("Invalid JSON in action: {}", e)))?;
+    create_entity_spec(&action_json).map_err(EngramError::Validation)?;
 
     let mut rule = Rule::new(
         title,
@@ -200,6 +285,7 @@ pub fn create_rule<S: Storage>(
             .collect();
         rule.entity_types = types;
     }
+    rule.exclusive_group = exclusive_group.filter(|group| !group.is_empty());
 
     let generic = rule.to_generic();
     storage.store(&generic)?;
@@ -217,7 +303,7 @@ pub fn get_rule<S: Storage>(storage: &S, id: &str) -> Result<(), EngramError> {
             Rule::from_generic(generic).map_err(|e| EngramError::Validation(e.to_string()))?;
         display_rule(&rule);
     } else {
-        println!("❌ Rule not found: {}", id);
+        return Err(EngramError::not_found("rule", id));
     }
     Ok(())
 }
@@ -234,6 +320,7 @@ pub fn update_rule<S: Storage>(
     condition: Option<String>,
     action: Option<String>,
     status: Option<String>,
+    exclusive_group: Option<String>,
 ) -> Result<(), EngramError> {
     if let Some(generic) = storage.get(id, "rule")? {
         let mut rule =
@@ -257,8 +344,9 @@ pub fn update_rule<S: Storage>(
                 "transformation" => RuleType::Transformation,
                 "enforcement" => RuleType::Enforcement,
                 "notification" => RuleType::Notification,
+                "compliance" => RuleType::Compliance,
                 _ => {
-                    println!("❌ Invalid rule type. Use: validation, transformation, enforcement, notification");
+                    println!("❌ Invalid rule type. Use: validation, transformation, enforcement, notification, compliance");
                     return Ok(());
                 }
             };
@@ -303,10 +391,16 @@ pub fn update_rule<S: Storage>(
         if let Some(action_str) = action {
             let action_json: serde_json::Value = serde_json::from_str(&action_str)
                 .map_err(|e| EngramError::Validation(format!("Invalid JSON in action: {}", e)))?;
+            create_entity_spec(&action_json).map_err(EngramError::Validation)?;
             rule.action = action_json;
             updated = true;
         }
 
+        if let Some(group) = exclusive_group {
+            rule.exclusive_group = Some(group).filter(|group| !group.is_empty());
+            updated = true;
+        }
+
         if let Some(status_str) = status {
             let new_status = match status_str.to_lowercase().as_str() {
                 "active" => RuleStatus::Active,
@@ -332,7 +426,7 @@ pub fn update_rule<S: Storage>(
 
         println!("✅ Rule updated: {}", id);
     } else {
-        println!("❌ Rule not found: {}", id);
+        return Err(EngramError::not_found("rule", id));
     }
     Ok(())
 }
@@ -347,15 +441,16 @@ pub fn delete_rule<S: Storage>(storage: &mut S, id: &str) -> Result<(), EngramEr
         storage.store(&updated_generic)?;
         println!("✅ Rule deleted (deactivated): {}", id);
     } else {
-        println!("❌ Rule not found: {}", id);
+        return Err(EngramError::not_found("rule", id));
     }
     Ok(())
 }
 
-use crate::cli::utils::{create_table, truncate};
+use crate::cli::utils::{create_table, truncate, CountArgs, SortArgs};
 use prettytable::row;
 
 /// List rules
+#[allow(clippy::too_many_arguments)]
 pub fn list_rules<S: Storage>(
     storage: &S,
     rule_type: Option<String>,
@@ -366,6 +461,8 @@ pub fn list_rules<S: Storage>(
     limit: usize,
     offset: usize,
     all: bool,
+    sort: &SortArgs,
+    count: &CountArgs,
 ) -> Result<(), EngramError> {
     use crate::storage::QueryFilter;
     use serde_json::Value;
@@ -380,6 +477,7 @@ pub fn list_rules<S: Storage>(
         offset: Some(offset),
         ..Default::default()
     };
+    sort.apply(&mut filter);
 
     let mut field_filters = HashMap::new();
 
@@ -406,6 +504,10 @@ pub fn list_rules<S: Storage>(
         filter.field_filters = field_filters;
     }
 
+    if count.write(&mut std::io::stdout(), storage, &filter)? {
+        return Ok(());
+    }
+
     let result = storage.query(&filter)?;
 
     if result.entities.is_empty() {
@@ -516,13 +618,537 @@ pub fn execute_rule<S: Storage>(
             let updated_generic = rule.to_generic();
             storage.store(&updated_generic)?;
         } else {
+            return Err(EngramError::not_found(entity_type, entity_id));
+        }
+    } else {
+        return Err(EngramError::not_found("rule", id));
+    }
+    Ok(())
+}
+
+/// A new entity of `entity_type` with `fields` laid over its defaults,
+/// validated as that type
+fn build_created_entity(
+    entity_type: &str,
+    fields: &serde_json::Map<String, serde_json::Value>,
+    agent: &str,
+) -> Result<GenericEntity, EngramError> {
+    fn validated<T: Entity>(generic: GenericEntity) -> Result<GenericEntity, EngramError> {
+        let entity = T::from_generic(generic)?;
+        entity.validate_entity()?;
+        Ok(entity.to_generic())
+    }
+
+    let mut generic = match entity_type {
+        "task" => Task::new(
+            String::new(),
+            String::new(),
+            agent.to_string(),
+            TaskPriority::Medium,
+            None,
+        )
+        .to_generic(),
+        "context" => Context::new(
+            String::new(),
+            String::new(),
+            "rule".to_string(),
+            ContextRelevance::Medium,
+            agent.to_string(),
+        )
+        .to_generic(),
+        "escalation_request" => EscalationRequest::new(
+            agent.to_string(),
+            EscalationOperationType::Custom("rule".to_string()),
+            OperationContext {
+                operation: "rule".to_string(),
+                parameters: std::collections::HashMap::new(),
+                resource: None,
+                block_reason: "Raised by rule".to_string(),
+                alternatives: Vec::new(),
+                risk_assessment: None,
+            },
+            String::new(),
+            EscalationPriority::Normal,
+            agent.to_string(),
+        )
+        .to_generic(),
+        other => {
+            return Err(EngramError::Validation(format!(
+                "Rules cannot create '{}' entities",
+                other
+            )))
+        }
+    };
+    if let Some(data) = generic.data.as_object_mut() {
+        for (field, value) in fields {
+            if field != "id" {
+                data.insert(field.clone(), value.clone());
+            }
+        }
+    }
+    match entity_type {
+        "task" => validated::<Task>(generic),
+        "context" => validated::<Context>(generic),
+        _ => validated::<EscalationRequest>(generic),
+    }
+}
+
+/// Store the entity `creation` asks for, link it from `source` and record
+/// it in the source's spawned-entity metadata. Returns the new entity's ID.
+fn store_creation<S: Storage>(
+    storage: &mut S,
+    source: &mut GenericEntity,
+    creation: &EntityCreation,
+    agent: &str,
+) -> Result<String, EngramError> {
+    let entity = build_created_entity(&creation.entity_type, &creation.fields, agent)?;
+    storage.store(&entity)?;
+
+    let relationship_type =
+        parse_relationship_type(&creation.relationship).map_err(EngramError::Validation)?;
+    let mut relationship = EntityRelationship::new(
+        uuid::Uuid::new_v4().to_string(),
+        agent.to_string(),
+        source.id.clone(),
+        source.entity_type.clone(),
+        entity.id.clone(),
+        entity.entity_type.clone(),
+        relationship_type,
+    );
+    relationship.metadata.insert(
+        "rule_id".to_string(),
+        serde_json::Value::String(creation.rule_id.clone()),
+    );
+    storage.store(&relationship.to_generic())?;
+
+    if let Some(data) = source.data.as_object_mut() {
+        let metadata = data
+            .entry("metadata")
+            .or_insert_with(|| serde_json::json!({}));
+        if !metadata.is_object() {
+            *metadata = serde_json::json!({});
+        }
+        let spawned = metadata
+            .as_object_mut()
+            .expect("metadata is an object")
+            .entry(SPAWNED_ENTITIES_KEY)
+            .or_insert_with(|| serde_json::json!({}));
+        if !spawned.is_object() {
+            *spawned = serde_json::json!({});
+        }
+        spawned
+            .as_object_mut()
+            .expect("spawned entities is an object")
+            .insert(
+                creation.rule_id.clone(),
+                serde_json::Value::String(entity.id.clone()),
+            );
+    }
+    Ok(entity.id)
+}
+
+/// Run every active rule against a stored entity, storing the resulting
+/// field updates and the entities `create_entity` actions ask for unless
+/// `dry_run` is set
+pub fn run_all_rules<S: Storage>(
+    storage: &mut S,
+    entity_id: &str,
+    entity_type: &str,
+    dry_run: bool,
+) -> Result<RuleBatchSummary, EngramError> {
+    let mut entity = storage
+        .get(entity_id, entity_type)?
+        .ok_or_else(|| EngramError::not_found(entity_type, entity_id))?;
+    let rules = storage
+        .get_all(Rule::entity_type())?
+        .into_iter()
+        .filter_map(|generic| Rule::from_generic(generic).ok())
+        .collect();
+
+    let agent = crate::cli::identity::current_agent();
+    let mut summary = RuleExecutionEngine::new().execute_rules(rules, &entity, &agent);
+    if dry_run || (summary.updates.is_empty() && summary.creations.is_empty()) {
+        return Ok(summary);
+    }
+    summary.apply_updates(&mut entity);
+    for creation in &mut summary.creations {
+        let id = store_creation(storage, &mut entity, creation, &agent)?;
+        creation.created_id = Some(id);
+    }
+    storage.store(&entity)?;
+    Ok(summary)
+}
+
+/// Handle `engram rule run-all`
+pub fn run_all_rules_command<S: Storage>(
+    storage: &mut S,
+    entity_id: &str,
+    entity_type: &str,
+    dry_run: bool,
+    format: &str,
+) -> Result<(), EngramError> {
+    let summary = run_all_rules(storage, entity_id, entity_type, dry_run)?;
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(&summary)?);
+        return Ok(());
+    }
+
+    println!(
+        "⚙️  Ran {} rule(s) against {} ({})",
+        summary.results.len(),
+        entity_id,
+        entity_type
+    );
+    for result in &summary.results {
+        let status = if result.actions_executed {
+            "✅ fired"
+        } else if result.condition_satisfied {
+            "⛔ suppressed"
+        } else if !result.errors.is_empty() {
+            "❌ error"
+        } else {
+            "⏸️  not matched"
+        };
+        println!("  {} {}", status, result.rule_id);
+        for action in &result.actions_taken {
+            println!("      {}", action);
+        }
+        for error in &result.errors {
+            println!("      {}", error);
+        }
+    }
+    if !summary.suppressed.is_empty() {
+        println!("Suppressed actions:");
+        for suppressed in &summary.suppressed {
+            println!(
+                "  {} (kept {}): {}",
+                suppressed.rule_id, suppressed.winning_rule_id, suppressed.reason
+            );
+        }
+    }
+    if !summary.updates.is_empty() {
+        let verb = if dry_run { "Would update" } else { "Updated" };
+        for (field, update) in &summary.updates {
             println!(
-                "❌ Target entity not found: {} ({})",
-                entity_id, entity_type
+                "{} {} = {} (rule {})",
+                verb, field, update.value, update.rule_id
             );
         }
+    }
+    for creation in &summary.creations {
+        match &creation.created_id {
+            Some(id) => println!(
+                "Created {} {} ({} from {}, rule {})",
+                creation.entity_type, id, creation.relationship, entity_id, creation.rule_id
+            ),
+            None => {
+                println!("Would create (rule {}):", creation.rule_id);
+                for line in creation.describe() {
+                    println!("  {}", line);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn load_rule<S: Storage>(storage: &S, id: &str) -> Result<Rule, EngramError> {
+    let generic = storage
+        .get(id, "rule")?
+        .ok_or_else(|| EngramError::not_found("rule", id))?;
+    Rule::from_generic(generic).map_err(|e| EngramError::Validation(e.to_string()))
+}
+
+/// Read a test fixture: a serialized entity, or a bare object taken as the
+/// data of an entity of the rule's first entity type
+pub fn load_rule_fixture(path: &Path, rule: &Rule) -> Result<GenericEntity, EngramError> {
+    let content = std::fs::read_to_string(path)?;
+    let value: serde_json::Value = serde_json::from_str(&content).map_err(|e| {
+        EngramError::Validation(format!("Invalid fixture {}: {}", path.display(), e))
+    })?;
+    if let Ok(entity) = GenericEntity::from_value(value.clone()) {
+        return Ok(entity);
+    }
+    if !value.is_object() {
+        return Err(EngramError::Validation(format!(
+            "Fixture {} must be a JSON object",
+            path.display()
+        )));
+    }
+    Ok(GenericEntity {
+        id: "fixture".to_string(),
+        entity_type: rule
+            .entity_types
+            .first()
+            .cloned()
+            .unwrap_or_else(|| "fixture".to_string()),
+        agent: crate::cli::identity::current_agent(),
+        timestamp: chrono::Utc::now(),
+        data: value,
+    })
+}
+
+/// Trace a rule against a fixture file or a stored entity
+pub fn trace_rule<S: Storage>(
+    storage: &S,
+    id: &str,
+    fixture: Option<&Path>,
+    entity_id: Option<&str>,
+    entity_type: Option<&str>,
+) -> Result<RuleTrace, EngramError> {
+    let rule = load_rule(storage, id)?;
+    let entity = match (fixture, entity_id) {
+        (Some(path), _) => load_rule_fixture(path, &rule)?,
+        (None, Some(entity_id)) => {
+            let types: Vec<&str> = match entity_type {
+                Some(t) => vec![t],
+                None => rule.entity_types.iter().map(String::as_str).collect(),
+            };
+            if types.is_empty() {
+                return Err(EngramError::Validation(
+                    "Rule has no entity types; pass --entity-type".to_string(),
+                ));
+            }
+            let mut found = None;
+            for t in types {
+                if let Some(entity) = storage.get(entity_id, t)? {
+                    found = Some(entity);
+                    break;
+                }
+            }
+            found.ok_or_else(|| EngramError::not_found("entity", entity_id))?
+        }
+        (None, None) => {
+            return Err(EngramError::Validation(
+                "Pass --fixture or --entity-id".to_string(),
+            ))
+        }
+    };
+    Ok(RuleExecutionEngine::new().trace_rule(&rule, &entity))
+}
+
+/// Handle `engram rule test`
+pub fn test_rule<S: Storage>(
+    storage: &S,
+    id: &str,
+    fixture: Option<&Path>,
+    entity_id: Option<&str>,
+    entity_type: Option<&str>,
+    format: &str,
+) -> Result<(), EngramError> {
+    let trace = trace_rule(storage, id, fixture, entity_id, entity_type)?;
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(&trace)?);
+        return Ok(());
+    }
+
+    println!(
+        "🧪 Rule {} against {} ({})",
+        trace.rule_id, trace.entity_id, trace.entity_type
+    );
+    if !trace.applies_to_type {
+        println!(
+            "  ⏭️  Rule does not apply to '{}' entities",
+            trace.entity_type
+        );
+    }
+    for (label, expression) in [
+        ("applies_when", &trace.scope),
+        ("condition", &trace.condition),
+    ] {
+        let Some(expression) = expression else {
+            continue;
+        };
+        println!("  {}: {}", label, expression.expression);
+        if let Some(parsed) = &expression.parsed {
+            let found = expression
+                .value_found
+                .as_ref()
+                .map(|v| v.to_string())
+                .unwrap_or_else(|| "<missing>".to_string());
+            println!(
+                "    {} = {}  {}  {}",
+                parsed.variable, found, parsed.operator, parsed.expected
+            );
+        }
+        match expression.outcome() {
+            Ok(result) => println!("    → {}", result),
+            Err(e) => println!("    → error: {}", e),
+        }
+    }
+    if trace.condition.is_none() {
+        match &trace.condition_satisfied {
+            Ok(result) => println!("  condition: literal → {}", result),
+            Err(e) => println!("  condition: error: {}", e),
+        }
+    }
+
+    if trace.would_fire {
+        println!("✅ Action would fire:");
+        for action in &trace.actions {
+            println!("    {}", action);
+        }
     } else {
-        println!("❌ Rule not found: {}", id);
+        println!("⏸️  Action would not fire");
+    }
+    Ok(())
+}
+
+/// A problem found by `engram rule lint`
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleLintFinding {
+    pub rule_id: String,
+    pub rule_title: String,
+    pub entity_type: Option<String>,
+    pub expression: Option<String>,
+    pub problem: String,
+}
+
+/// Result of `engram rule lint`
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RuleLintReport {
+    pub rules_checked: usize,
+    pub findings: Vec<RuleLintFinding>,
+    /// Declared entity types with no stored entities to learn fields from
+    pub unchecked_types: Vec<String>,
+}
+
+/// Fields seen on stored entities of one type
+#[derive(Default)]
+struct KnownFields {
+    variables: BTreeSet<String>,
+    /// Top-level data fields holding objects, whose keys vary per entity
+    object_fields: BTreeSet<String>,
+}
+
+impl KnownFields {
+    fn contains(&self, variable: &str) -> bool {
+        self.variables.contains(variable)
+            || variable
+                .split_once('.')
+                .is_some_and(|(head, _)| self.object_fields.contains(head))
+    }
+}
+
+/// Parse every stored rule's condition and check the variables it
+/// references against the fields of stored entities of the rule's
+/// declared entity types
+pub fn lint_rules<S: Storage>(storage: &S) -> Result<RuleLintReport, EngramError> {
+    let engine = RuleExecutionEngine::new();
+    let mut report = RuleLintReport::default();
+    let mut fields: BTreeMap<String, Option<KnownFields>> = BTreeMap::new();
+
+    for generic in storage.get_all(Rule::entity_type())? {
+        report.rules_checked += 1;
+        let rule = match Rule::from_generic(generic.clone()) {
+            Ok(rule) => rule,
+            Err(e) => {
+                report.findings.push(RuleLintFinding {
+                    rule_id: generic.id.clone(),
+                    rule_title: String::new(),
+                    entity_type: None,
+                    expression: None,
+                    problem: format!("Cannot parse rule: {}", e),
+                });
+                continue;
+            }
+        };
+
+        for (label, expression, parsed) in rule_expressions(&rule) {
+            let finding = |entity_type: Option<&String>, problem: String| RuleLintFinding {
+                rule_id: rule.id.clone(),
+                rule_title: rule.title.clone(),
+                entity_type: entity_type.cloned(),
+                expression: Some(expression.to_string()),
+                problem,
+            };
+            let parsed = match parsed {
+                Ok(parsed) => parsed,
+                Err(e) => {
+                    report
+                        .findings
+                        .push(finding(None, format!("{}: {}", label, e)));
+                    continue;
+                }
+            };
+            for entity_type in &rule.entity_types {
+                let known = fields.entry(entity_type.clone()).or_insert_with(|| {
+                    let entities = storage.get_all(entity_type).ok()?;
+                    if entities.is_empty() {
+                        return None;
+                    }
+                    let mut known = KnownFields::default();
+                    for entity in &entities {
+                        known
+                            .variables
+                            .extend(engine.entity_variables(entity).into_keys());
+                        if let Some(data) = entity.data.as_object() {
+                            known.object_fields.extend(
+                                data.iter()
+                                    .filter(|(_, v)| v.is_object())
+                                    .map(|(k, _)| k.clone()),
+                            );
+                        }
+                    }
+                    Some(known)
+                });
+                match known {
+                    Some(known) if !known.contains(&parsed.variable) => {
+                        report.findings.push(finding(
+                            Some(entity_type),
+                            format!(
+                                "{}: field '{}' does not exist on {} entities",
+                                label, parsed.variable, entity_type
+                            ),
+                        ));
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    report.unchecked_types = fields
+        .into_iter()
+        .filter(|(_, known)| known.is_none())
+        .map(|(entity_type, _)| entity_type)
+        .collect();
+    Ok(report)
+}
+
+/// Handle `engram rule lint`
+pub fn run_rule_lint<S: Storage>(storage: &S, format: &str) -> Result<(), EngramError> {
+    let report = lint_rules(storage)?;
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    if report.findings.is_empty() {
+        println!(
+            "✅ {} rule(s) checked, no problems found",
+            report.rules_checked
+        );
+    } else {
+        println!(
+            "❌ {} problem(s) in {} rule(s) checked:",
+            report.findings.len(),
+            report.rules_checked
+        );
+        for finding in &report.findings {
+            println!(
+                "  {} {}: {}",
+                &finding.rule_id[..8.min(finding.rule_id.len())],
+                finding.rule_title,
+                finding.problem
+            );
+        }
+    }
+    if !report.unchecked_types.is_empty() {
+        println!(
+            "ℹ️  No stored entities to check fields against for: {}",
+            report.unchecked_types.join(", ")
+        );
     }
     Ok(())
 }
@@ -536,6 +1162,9 @@ fn display_rule(rule: &Rule) {
     println!("📊 Status: {:?}", rule.status);
     println!("⚡ Priority: {:?}", rule.priority);
     println!("🤖 Agent: {}", rule.agent);
+    if let Some(group) = &rule.exclusive_group {
+        println!("🔀 Exclusive Group: {}", group);
+    }
     if !rule.entity_types.is_empty() {
         println!("🎯 Entity Types: {:?}", rule.entity_types);
     }
@@ -571,6 +1200,7 @@ mod tests {
             r#"{"field": "status", "operator": "eq", "value": "done"}"#.to_string(),
             r#"{"type": "notify", "message": "Task done"}"#.to_string(),
             Some("agent1".to_string()),
+            None,
         )
         .unwrap();
 
@@ -594,6 +1224,7 @@ mod tests {
             "{}".to_string(),
             "{}".to_string(),
             Some("agent1".to_string()),
+            None,
         )
         .unwrap();
 
@@ -611,6 +1242,7 @@ mod tests {
             None,
             None,
             Some("deprecated".to_string()),
+            None,
         )
         .unwrap();
 
@@ -635,6 +1267,7 @@ mod tests {
             "{}".to_string(),
             "{}".to_string(),
             Some("agent1".to_string()),
+            None,
         )
         .unwrap();
 
@@ -662,10 +1295,24 @@ mod tests {
             "{}".to_string(),
             "{}".to_string(),
             None,
+            None,
         )
         .unwrap();
 
-        list_rules(&storage, None, None, None, None, None, 10, 0, false).unwrap();
+        list_rules(
+            &storage,
+            None,
+            None,
+            None,
+            None,
+            None,
+            10,
+            0,
+            false,
+            &SortArgs::default(),
+            &CountArgs::default(),
+        )
+        .unwrap();
         list_rules(
             &storage,
             Some("validation".to_string()),
@@ -676,6 +1323,8 @@ mod tests {
             10,
             0,
             false,
+            &SortArgs::default(),
+            &CountArgs::default(),
         )
         .unwrap();
     }
@@ -693,6 +1342,7 @@ mod tests {
             "{}".to_string(),
             "{}".to_string(),
             None,
+            None,
         )
         .unwrap();
 
@@ -714,6 +1364,7 @@ mod tests {
             "{}".to_string(),
             "{}".to_string(),
             None,
+            None,
         )
         .unwrap();
 
@@ -734,6 +1385,7 @@ mod tests {
             "{invalid}".to_string(),
             "{}".to_string(),
             None,
+            None,
         );
         assert!(result.is_err());
     }
@@ -751,15 +1403,17 @@ mod tests {
             None,
             None,
             None,
+            None,
             None
         )
-        .is_ok());
+        .is_err_and(|e| matches!(e, EngramError::NotFound { .. })));
     }
 
     #[test]
     fn test_delete_rule_not_found() {
         let mut storage = create_test_storage();
-        assert!(delete_rule(&mut storage, "non-existent").is_ok());
+        assert!(delete_rule(&mut storage, "non-existent")
+            .is_err_and(|e| matches!(e, EngramError::NotFound { .. })));
     }
 
     #[test]
@@ -771,7 +1425,106 @@ mod tests {
             "entity_id".to_string(),
             "task".to_string()
         )
-        .is_ok());
+        .is_err_and(|e| matches!(e, EngramError::NotFound { .. })));
+    }
+
+    fn store_rule(storage: &mut MemoryStorage, condition: &str) -> String {
+        create_rule(
+            storage,
+            "Escalate".to_string(),
+            None,
+            "validation".to_string(),
+            "high".to_string(),
+            Some("task".to_string()),
+            condition.to_string(),
+            r#"{"type": "notification", "message": "High priority"}"#.to_string(),
+            Some("agent1".to_string()),
+            None,
+        )
+        .unwrap();
+        storage.get_all("rule").unwrap()[0].id.clone()
+    }
+
+    fn store_task(storage: &mut MemoryStorage, priority: &str) -> String {
+        let entity = GenericEntity {
+            id: uuid::Uuid::new_v4().to_string(),
+            entity_type: "task".to_string(),
+            agent: "agent1".to_string(),
+            timestamp: chrono::Utc::now(),
+            data: serde_json::json!({"title": "T", "priority": priority}),
+        };
+        storage.store(&entity).unwrap();
+        entity.id
+    }
+
+    #[test]
+    fn test_trace_rule_against_fixture_and_entity() {
+        let mut storage = create_test_storage();
+        let rule_id = store_rule(&mut storage, r#"{"expression": "priority equals high"}"#);
+
+        let dir = tempfile::tempdir().unwrap();
+        let fixture = dir.path().join("fixture.json");
+        std::fs::write(&fixture, r#"{"priority": "high"}"#).unwrap();
+        let trace = trace_rule(&storage, &rule_id, Some(&fixture), None, None).unwrap();
+        assert_eq!(trace.entity_type, "task");
+        assert!(trace.would_fire);
+
+        let task_id = store_task(&mut storage, "low");
+        let trace = trace_rule(&storage, &rule_id, None, Some(&task_id), None).unwrap();
+        assert_eq!(trace.entity_id, task_id);
+        assert!(!trace.would_fire);
+
+        assert!(trace_rule(&storage, &rule_id, None, Some("missing"), None).is_err());
+    }
+
+    #[test]
+    fn test_run_all_rules_stores_winning_update() {
+        let mut storage = create_test_storage();
+        for (priority, status) in [("low", "in_progress"), ("critical", "blocked")] {
+            create_rule(
+                &mut storage,
+                format!("Set {}", status),
+                None,
+                "transformation".to_string(),
+                priority.to_string(),
+                Some("task".to_string()),
+                r#"{"expression": "priority equals high"}"#.to_string(),
+                format!(
+                    r#"{{"type": "update_entity", "field": "status", "value": "{}"}}"#,
+                    status
+                ),
+                Some("agent1".to_string()),
+                None,
+            )
+            .unwrap();
+        }
+        let task_id = store_task(&mut storage, "high");
+
+        let summary = run_all_rules(&mut storage, &task_id, "task", true).unwrap();
+        assert_eq!(summary.suppressed.len(), 1);
+        let task = storage.get(&task_id, "task").unwrap().unwrap();
+        assert!(task.data.get("status").is_none());
+
+        run_all_rules(&mut storage, &task_id, "task", false).unwrap();
+        let task = storage.get(&task_id, "task").unwrap().unwrap();
+        assert_eq!(task.data["status"], "blocked");
+    }
+
+    #[test]
+    fn test_lint_rules_reports_unknown_fields() {
+        let mut storage = create_test_storage();
+        store_rule(&mut storage, r#"{"expression": "severity equals high"}"#);
+
+        let report = lint_rules(&storage).unwrap();
+        assert!(report.findings.is_empty());
+        assert_eq!(report.unchecked_types, vec!["task".to_string()]);
+
+        store_task(&mut storage, "high");
+        let report = lint_rules(&storage).unwrap();
+        assert_eq!(report.rules_checked, 1);
+        assert_eq!(report.findings.len(), 1);
+        assert!(report.findings[0].problem.contains("severity"));
+        assert!(report.unchecked_types.is_empty());
     }
 
     #[test]
@@ -787,6 +1540,7 @@ mod tests {
             "{}".to_string(),
             "{}".to_string(),
             None,
+            None,
         )
         .unwrap();
 
@@ -805,6 +1559,7 @@ mod tests {
             None,
             None,
             None,
+            None,
         )
         .unwrap();
         let updated = Rule::from_generic(storage.get(id, "rule").unwrap().unwrap()).unwrap();
@@ -822,6 +1577,7 @@ mod tests {
             None,
             None,
             None,
+            None,
         )
         .unwrap();
         let updated = Rule::from_generic(storage.get(id, "rule").unwrap().unwrap()).unwrap();
@@ -839,9 +1595,65 @@ mod tests {
             None,
             None,
             Some("invalid".to_string()),
+            None,
         )
         .unwrap();
         let updated = Rule::from_generic(storage.get(id, "rule").unwrap().unwrap()).unwrap();
         assert!(matches!(updated.status, RuleStatus::Active)); // Should remain unchanged
     }
+
+    #[test]
+    fn test_run_all_rules_creates_entity_once() {
+        let mut storage = create_test_storage();
+        create_rule(
+            &mut storage,
+            "Follow up".to_string(),
+            None,
+            "enforcement".to_string(),
+            "high".to_string(),
+            Some("task".to_string()),
+            r#"{"expression": "priority equals high"}"#.to_string(),
+            r#"{"type": "create_entity", "entity_type": "task",
+                "fields": {"title": "Review {{source.title}}"},
+                "relationship": "depends_on"}"#
+                .to_string(),
+            Some("agent1".to_string()),
+            None,
+        )
+        .unwrap();
+        let task_id = store_task(&mut storage, "high");
+
+        let summary = run_all_rules(&mut storage, &task_id, "task", true).unwrap();
+        assert_eq!(summary.creations.len(), 1);
+        assert_eq!(storage.get_all("task").unwrap().len(), 1);
+
+        let summary = run_all_rules(&mut storage, &task_id, "task", false).unwrap();
+        let created_id = summary.creations[0].created_id.clone().unwrap();
+        let created =
+            Task::from_generic(storage.get(&created_id, "task").unwrap().unwrap()).unwrap();
+        assert_eq!(created.title, "Review T");
+        let relationships = storage.get_all("relationship").unwrap();
+        assert_eq!(relationships.len(), 1);
+        let relationship = EntityRelationship::from_generic(relationships[0].clone()).unwrap();
+        assert_eq!(relationship.source_id, task_id);
+        assert_eq!(relationship.target_id, created_id);
+
+        let summary = run_all_rules(&mut storage, &task_id, "task", false).unwrap();
+        assert!(summary.creations.is_empty());
+        assert_eq!(storage.get_all("task").unwrap().len(), 2);
+
+        let invalid = create_rule(
+            &mut storage,
+            "Bad".to_string(),
+            None,
+            "enforcement".to_string(),
+            "high".to_string(),
+            None,
+            "{}".to_string(),
+            r#"{"type": "create_entity"}"#.to_string(),
+            None,
+            None,
+        );
+        assert!(invalid.is_err());
+    }
 }
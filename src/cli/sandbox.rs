@@ -1,6 +1,9 @@
 //! Sandbox command implementations
 
-use crate::entities::{AgentSandbox, Entity, SandboxLevel};
+use crate::cli::identity::{resolve_agent, resolve_agent_filter};
+use crate::entities::{
+    AgentSandbox, Entity, OperationType, PathPermission, ResourceLimits, SandboxLevel, SandboxUsage,
+};
 use crate::error::EngramError;
 use crate::feedback::StructuredFeedback;
 use crate::storage::Storage;
@@ -74,6 +77,9 @@ pub enum SandboxCommands {
         /// Output in JSON format
         #[arg(long)]
         json: bool,
+
+        #[command(flatten)]
+        sort: SortArgs,
     },
     /// Get sandbox configuration details
     Get {
@@ -103,6 +109,18 @@ pub enum SandboxCommands {
         #[arg(long, conflicts_with = "stdin")]
         file: Option<String>,
 
+        /// Allow the path operation under a glob, e.g. 'src/**' (repeatable)
+        #[arg(long = "allow-path", value_name = "GLOB")]
+        allow_paths: Vec<String>,
+
+        /// Deny the path operation under a glob, e.g. '.github/**' (repeatable)
+        #[arg(long = "deny-path", value_name = "GLOB")]
+        deny_paths: Vec<String>,
+
+        /// Operation the path globs apply to (file_write, file_delete, system_file_access)
+        #[arg(long, default_value = "file_write")]
+        path_operation: String,
+
         /// Output in JSON format
         #[arg(long)]
         json: bool,
@@ -131,6 +149,10 @@ pub enum SandboxCommands {
         #[arg(long, short)]
         resource_type: Option<String>,
 
+        /// File path the operation targets, checked against path rules
+        #[arg(long)]
+        path: Option<String>,
+
         /// Read validation request from stdin as JSON
         #[arg(long, conflicts_with_all = ["agent_id", "operation"])]
         stdin: bool,
@@ -159,6 +181,37 @@ pub enum SandboxCommands {
         #[arg(long)]
         json: bool,
     },
+    /// Replay past requests against a hypothetical sandbox level without side effects
+    ///
+    ///EXAMPLES:
+    ///  engram sandbox simulate --agent alice --level restricted
+    ///  engram sandbox simulate --agent alice --level isolated --from-audit 7d --json
+    ///  engram sandbox simulate --agent alice --level restricted --requests requests.jsonl
+    Simulate {
+        /// Agent whose requests are replayed (`me` is accepted)
+        #[arg(long, short)]
+        agent: Option<String>,
+
+        /// Hypothetical sandbox level
+        #[arg(long, short)]
+        level: String,
+
+        /// Replay audit log entries recorded within this window (30d, 24h, 2024-01-01)
+        #[arg(long, default_value = "30d")]
+        from_audit: String,
+
+        /// Replay SandboxRequests from this JSONL file instead of the audit log
+        #[arg(long, conflicts_with = "from_audit")]
+        requests: Option<String>,
+
+        /// Number of most affected operations to report
+        #[arg(long, default_value_t = 5)]
+        top: usize,
+
+        /// Output in JSON format
+        #[arg(long)]
+        json: bool,
+    },
     /// Reset sandbox configuration to defaults
     Reset {
         /// Agent ID to reset
@@ -190,11 +243,8 @@ pub fn create_sandbox<S: Storage>(
     } else if let Some(file_path) = file {
         read_sandbox_input_from_file(&file_path)?
     } else {
-        let agent_id =
-            agent.ok_or_else(|| EngramError::Validation("Agent is required".to_string()))?;
-
         SandboxInput {
-            agent_id,
+            agent_id: resolve_agent(agent),
             sandbox_level: level,
             created_by,
             agent: None,
@@ -205,7 +255,7 @@ pub fn create_sandbox<S: Storage>(
     let created_by = sandbox_input
         .created_by
         .unwrap_or_else(|| "default".to_string());
-    let agent = sandbox_input.agent.unwrap_or_else(|| "default".to_string());
+    let agent = resolve_agent(sandbox_input.agent);
 
     let sandbox = AgentSandbox::new(sandbox_input.agent_id, sandbox_level, created_by, agent);
 
@@ -224,9 +274,18 @@ pub fn create_sandbox<S: Storage>(
     Ok(())
 }
 
-use crate::cli::utils::{create_table, truncate};
+use crate::cli::session::parse_since;
+use crate::cli::utils::{create_table, truncate, SortArgs};
+use crate::sandbox::permission_engine::compile_path_glob;
 use crate::sandbox::preflight::run_preflight_checks;
+use crate::sandbox::{
+    read_audit_log, simulate_requests, PermissionEngine, SandboxAuditEntry, SandboxDecision,
+    SandboxEngine, SandboxRequest, SimulationReport, SANDBOX_AUDIT_FILE,
+};
+use crate::storage::MemoryStorage;
+use chrono::{DateTime, Utc};
 use prettytable::row;
+use std::path::Path;
 
 /// List sandbox configurations
 pub fn list_sandboxes<S: Storage>(
@@ -235,7 +294,9 @@ pub fn list_sandboxes<S: Storage>(
     level: Option<String>,
     agent: Option<String>,
     json: bool,
+    sort: &SortArgs,
 ) -> Result<(), EngramError> {
+    let agent_id = resolve_agent_filter(agent_id);
     let ids = storage.list_ids("agent_sandbox")?;
     let mut sandboxes = Vec::new();
 
@@ -271,6 +332,8 @@ pub fn list_sandboxes<S: Storage>(
         }
     }
 
+    sandboxes = sort.sort(sandboxes)?;
+
     if json {
         let generic_sandboxes: Vec<_> = sandboxes.iter().map(|s| s.to_generic()).collect();
         println!("{}", serde_json::to_string_pretty(&generic_sandboxes)?);
@@ -326,16 +389,89 @@ pub fn get_sandbox<S: Storage>(storage: &S, id: String, json: bool) -> Result<()
             }
         }
         None => {
-            return Err(EngramError::NotFound(format!(
-                "Sandbox with ID {} not found",
-                id
-            )));
+            return Err(EngramError::not_found("sandbox", id));
         }
     }
 
     Ok(())
 }
 
+/// Path globs to add to a sandbox for one operation
+#[derive(Debug, Clone, Default)]
+pub struct PathRuleUpdate {
+    pub operation: String,
+    pub allow: Vec<String>,
+    pub deny: Vec<String>,
+}
+
+fn parse_path_operation(operation: &str) -> Result<OperationType, EngramError> {
+    match operation {
+        "file_write" => Ok(OperationType::FileWrite),
+        "file_delete" => Ok(OperationType::FileDelete),
+        "system_file_access" => Ok(OperationType::SystemFileAccess),
+        other => Err(EngramError::Validation(format!(
+            "Invalid path operation '{}'. Use file_write, file_delete or system_file_access",
+            other
+        ))),
+    }
+}
+
+/// Merge `update` into the sandbox's path rules, skipping duplicate globs
+fn apply_path_rules(sandbox: &mut AgentSandbox, update: PathRuleUpdate) -> Result<(), EngramError> {
+    if update.allow.is_empty() && update.deny.is_empty() {
+        return Ok(());
+    }
+    let operation = parse_path_operation(&update.operation)?;
+    for pattern in update.allow.iter().chain(&update.deny) {
+        compile_path_glob(pattern).map_err(|e| {
+            EngramError::Validation(format!("Invalid path glob '{}': {}", pattern, e))
+        })?;
+    }
+
+    let rules = &mut sandbox.permissions.path_permissions;
+    let index = match rules.iter().position(|rule| rule.operation == operation) {
+        Some(index) => index,
+        None => {
+            rules.push(PathPermission {
+                operation,
+                allow: Vec::new(),
+                deny: Vec::new(),
+            });
+            rules.len() - 1
+        }
+    };
+    let rule = &mut rules[index];
+    for pattern in update.allow {
+        if !rule.allow.contains(&pattern) {
+            rule.allow.push(pattern);
+        }
+    }
+    for pattern in update.deny {
+        if !rule.deny.contains(&pattern) {
+            rule.deny.push(pattern);
+        }
+    }
+    Ok(())
+}
+
+/// Overlay the fields given in `update` onto `current`
+fn merge_resource_limits(
+    current: &ResourceLimits,
+    update: serde_json::Value,
+) -> Result<ResourceLimits, EngramError> {
+    let serde_json::Value::Object(fields) = update else {
+        return Err(EngramError::Validation(
+            "resource_limits must be a JSON object".to_string(),
+        ));
+    };
+    let mut merged = serde_json::to_value(current)?;
+    for (key, value) in fields {
+        merged[key] = value;
+    }
+    serde_json::from_value(merged)
+        .map_err(|e| EngramError::Validation(format!("Invalid resource_limits: {}", e)))
+}
+
 /// Update sandbox configuration
 pub fn update_sandbox<S: Storage>(
     storage: &mut S,
@@ -343,17 +479,13 @@ pub fn update_sandbox<S: Storage>(
     level: Option<String>,
     stdin: bool,
     file: Option<String>,
+    path_rules: PathRuleUpdate,
     json: bool,
 ) -> Result<(), EngramError> {
     let mut sandbox = match storage.get(&id, "agent_sandbox")? {
         Some(entity) => AgentSandbox::from_generic(entity)
             .map_err(|e| EngramError::Validation(e.to_string()))?,
-        None => {
-            return Err(EngramError::NotFound(format!(
-                "Sandbox with ID {} not found",
-                id
-            )))
-        }
+        None => return Err(EngramError::not_found("sandbox", id)),
     };
 
     if stdin || file.is_some() {
@@ -366,9 +498,13 @@ pub fn update_sandbox<S: Storage>(
         if let Some(new_level) = update_input.sandbox_level {
             sandbox.sandbox_level = parse_sandbox_level(&new_level)?;
         }
+        if let Some(limits) = update_input.resource_limits {
+            sandbox.resource_limits = merge_resource_limits(&sandbox.resource_limits, limits)?;
+        }
     } else if let Some(new_level) = level {
         sandbox.sandbox_level = parse_sandbox_level(&new_level)?;
     }
+    apply_path_rules(&mut sandbox, path_rules)?;
 
     sandbox.last_modified = chrono::Utc::now();
 
@@ -380,6 +516,14 @@ pub fn update_sandbox<S: Storage>(
         println!("✅ Sandbox updated successfully:");
         println!("  ID: {}", sandbox.id);
         println!("  Level: {:?}", sandbox.sandbox_level);
+        for rule in &sandbox.permissions.path_permissions {
+            println!(
+                "  Paths ({:?}): allow [{}], deny [{}]",
+                rule.operation,
+                rule.allow.join(", "),
+                rule.deny.join(", ")
+            );
+        }
     }
 
     Ok(())
@@ -408,51 +552,105 @@ pub fn delete_sandbox<S: Storage>(
     Ok(())
 }
 
-/// Validate an operation against sandbox constraints (simplified implementation)
-pub fn validate_operation<S: Storage>(
-    _storage: &S,
+/// Build a validation request from command-line flags; a missing agent is
+/// the current identity
+fn request_from_args(
     agent_id: Option<String>,
     operation: Option<String>,
     resource_type: Option<String>,
+) -> Result<SandboxValidationRequest, EngramError> {
+    let agent_id = resolve_agent(agent_id);
+    let operation =
+        operation.ok_or_else(|| EngramError::Validation("Operation is required".to_string()))?;
+    let resource_type = resource_type
+        .ok_or_else(|| EngramError::Validation("Resource type is required".to_string()))?;
+
+    Ok(SandboxValidationRequest {
+        agent_id,
+        operation,
+        resource_type,
+        parameters: serde_json::Value::Object(serde_json::Map::new()),
+    })
+}
+
+/// Validate an operation against the agent's sandbox permissions
+///
+/// Agents without a sandbox are checked against the default standard one.
+/// A denied operation is reported, not returned as an error.
+#[allow(clippy::too_many_arguments)]
+pub async fn validate_operation<S: Storage>(
+    storage: &S,
+    agent_id: Option<String>,
+    operation: Option<String>,
+    resource_type: Option<String>,
+    path: Option<String>,
     stdin: bool,
     file: Option<String>,
     json: bool,
 ) -> Result<(), EngramError> {
-    let validation_request = if stdin {
+    let mut validation_request = if stdin {
         read_validation_request_from_stdin()?
     } else if let Some(file_path) = file {
         read_validation_request_from_file(&file_path)?
     } else {
-        let agent_id =
-            agent_id.ok_or_else(|| EngramError::Validation("Agent ID is required".to_string()))?;
-        let operation = operation
-            .ok_or_else(|| EngramError::Validation("Operation is required".to_string()))?;
-        let resource_type = resource_type
-            .ok_or_else(|| EngramError::Validation("Resource type is required".to_string()))?;
-
-        SandboxValidationRequest {
-            agent_id,
-            operation,
-            resource_type,
-            parameters: serde_json::Value::Object(serde_json::Map::new()),
-        }
+        request_from_args(agent_id, operation, resource_type)?
     };
+    if let Some(path) = path {
+        if !validation_request.parameters.is_object() {
+            validation_request.parameters = serde_json::Value::Object(serde_json::Map::new());
+        }
+        validation_request.parameters["path"] = serde_json::Value::String(path);
+    }
 
-    // Simplified validation - just allow for now
-    let result = serde_json::json!({
-        "status": "allowed",
-        "agent_id": validation_request.agent_id,
-        "operation": validation_request.operation,
-        "resource_type": validation_request.resource_type
-    });
+    let sandbox = match find_agent_sandbox(storage, &validation_request.agent_id)? {
+        Some(sandbox) => sandbox,
+        None => AgentSandbox::new(
+            validation_request.agent_id.clone(),
+            SandboxLevel::Standard,
+            "system".to_string(),
+            "default".to_string(),
+        ),
+    };
+    let request = SandboxRequest {
+        agent_id: validation_request.agent_id.clone(),
+        operation: validation_request.operation.clone(),
+        resource_type: validation_request.resource_type.clone(),
+        parameters: validation_request.parameters.clone(),
+        timestamp: Utc::now(),
+        session_id: None,
+    };
+    let denial = PermissionEngine::new()
+        .with_workspace_root(std::env::current_dir()?)
+        .validate_operation(&request, &sandbox.permissions)
+        .await
+        .err()
+        .map(|e| e.to_string());
+    let path = request.parameters.get("path").and_then(|v| v.as_str());
 
     if json {
+        let result = serde_json::json!({
+            "status": if denial.is_some() { "denied" } else { "allowed" },
+            "agent_id": validation_request.agent_id,
+            "operation": validation_request.operation,
+            "resource_type": validation_request.resource_type,
+            "path": path,
+            "reason": denial,
+        });
         println!("{}", serde_json::to_string_pretty(&result)?);
     } else {
-        println!("✅ Operation allowed:");
+        match &denial {
+            Some(_) => println!("❌ Operation denied:"),
+            None => println!("✅ Operation allowed:"),
+        }
         println!("  Agent: {}", validation_request.agent_id);
         println!("  Operation: {}", validation_request.operation);
         println!("  Resource: {}", validation_request.resource_type);
+        if let Some(path) = path {
+            println!("  Path: {}", path);
+        }
+        if let Some(reason) = &denial {
+            println!("  Reason: {}", reason);
+        }
     }
 
     Ok(())
@@ -464,6 +662,7 @@ pub fn show_stats<S: Storage>(
     agent_id: Option<String>,
     json: bool,
 ) -> Result<(), EngramError> {
+    let agent_id = resolve_agent_filter(agent_id);
     let ids = storage.list_ids("agent_sandbox")?;
     let mut total_sandboxes = 0;
     let mut level_counts = std::collections::HashMap::new();
@@ -530,6 +729,7 @@ pub fn reset_sandbox<S: Storage>(
     force: bool,
     json: bool,
 ) -> Result<(), EngramError> {
+    let agent_id = resolve_agent(Some(agent_id));
     if !force {
         print!(
             "Are you sure you want to reset sandbox configuration for agent {}? (y/N): ",
@@ -563,6 +763,7 @@ pub fn reset_sandbox<S: Storage>(
         // Reset to standard level with default configuration
         sandbox.sandbox_level = SandboxLevel::Standard;
         sandbox.violation_count = 0;
+        sandbox.usage = SandboxUsage::default();
         sandbox.last_modified = chrono::Utc::now();
         sandbox.metadata.clear();
 
@@ -596,6 +797,171 @@ pub fn reset_sandbox<S: Storage>(
     Ok(())
 }
 
+fn find_agent_sandbox<S: Storage>(
+    storage: &S,
+    agent_id: &str,
+) -> Result<Option<AgentSandbox>, EngramError> {
+    for id in storage.list_ids("agent_sandbox")? {
+        if let Some(entity) = storage.get(&id, "agent_sandbox")? {
+            if let Ok(sandbox) = AgentSandbox::from_generic(entity) {
+                if sandbox.agent_id == agent_id {
+                    return Ok(Some(sandbox));
+                }
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Requests and recorded decisions to replay for `agent_id`
+///
+/// With `requests`, each request's baseline decision comes from the agent's
+/// current sandbox (or the default standard sandbox) rather than history.
+async fn load_simulation_entries<S: Storage>(
+    storage: &S,
+    engine: &mut SandboxEngine,
+    agent_id: &str,
+    audit_log: &Path,
+    since: DateTime<Utc>,
+    requests: Option<&Path>,
+) -> Result<Vec<SandboxAuditEntry>, EngramError> {
+    let Some(path) = requests else {
+        let mut entries = read_audit_log(audit_log)?;
+        entries.retain(|e| e.request.agent_id == agent_id && e.request.timestamp >= since);
+        return Ok(entries);
+    };
+
+    let current = match find_agent_sandbox(storage, agent_id)? {
+        Some(sandbox) => sandbox,
+        None => AgentSandbox::new(
+            agent_id.to_string(),
+            SandboxLevel::Standard,
+            "system".to_string(),
+            "default".to_string(),
+        ),
+    };
+    let mut entries = Vec::new();
+    for (index, line) in fs::read_to_string(path)?.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let request: SandboxRequest = serde_json::from_str(line).map_err(|e| {
+            EngramError::Validation(format!("{}:{}: {}", path.display(), index + 1, e))
+        })?;
+        if request.agent_id != agent_id {
+            continue;
+        }
+        let decision = engine
+            .simulate_request(&request, &current)
+            .await
+            .map_err(|e| EngramError::InvalidOperation(e.to_string()))?;
+        entries.push(SandboxAuditEntry {
+            request,
+            decision,
+            reason: None,
+            recorded_at: Utc::now(),
+        });
+    }
+    Ok(entries)
+}
+
+/// Replay an agent's requests against a hypothetical sandbox level
+///
+/// Reads the workspace audit log (or `requests`) and never writes to
+/// `storage`: no sandboxes are created and no escalations are filed.
+pub async fn simulate_sandbox<S: Storage>(
+    storage: &S,
+    agent: Option<String>,
+    level: &str,
+    from_audit: &str,
+    requests: Option<&Path>,
+    top: usize,
+) -> Result<SimulationReport, EngramError> {
+    let agent_id = resolve_agent(agent);
+    let sandbox = AgentSandbox::new(
+        agent_id.clone(),
+        parse_sandbox_level(level)?,
+        "simulation".to_string(),
+        agent_id.clone(),
+    );
+    // Simulation never touches the engine's storage; give it a throwaway one
+    let mut engine = SandboxEngine::new(Box::new(MemoryStorage::new(&agent_id)));
+
+    let entries = load_simulation_entries(
+        storage,
+        &mut engine,
+        &agent_id,
+        Path::new(SANDBOX_AUDIT_FILE),
+        parse_since(from_audit)?,
+        requests,
+    )
+    .await?;
+    simulate_requests(&mut engine, &entries, &sandbox, top)
+        .await
+        .map_err(|e| EngramError::InvalidOperation(e.to_string()))
+}
+
+/// Print a [`SimulationReport`]
+pub fn print_simulation_report(
+    writer: &mut dyn Write,
+    report: &SimulationReport,
+    json: bool,
+) -> Result<(), EngramError> {
+    if json {
+        writeln!(writer, "{}", serde_json::to_string_pretty(report)?)?;
+        return Ok(());
+    }
+
+    writeln!(
+        writer,
+        "🧪 Sandbox simulation for {} at level {:?}",
+        report.agent_id, report.sandbox_level
+    )?;
+    if report.total_requests == 0 {
+        writeln!(writer, "No recorded requests to replay.")?;
+        return Ok(());
+    }
+
+    let mut table = create_table();
+    table.set_titles(row!["Decision", "Historical", "Simulated"]);
+    for decision in [
+        SandboxDecision::Allow,
+        SandboxDecision::Deny,
+        SandboxDecision::Escalate,
+        SandboxDecision::Defer,
+    ] {
+        let count = |counts: &std::collections::BTreeMap<SandboxDecision, usize>| {
+            counts.get(&decision).copied().unwrap_or(0)
+        };
+        table.add_row(row![
+            decision,
+            count(&report.historical),
+            count(&report.simulated)
+        ]);
+    }
+    table.print(writer)?;
+
+    writeln!(
+        writer,
+        "\n{} of {} decision(s) would change",
+        report.changed, report.total_requests
+    )?;
+    for change in &report.changes {
+        writeln!(
+            writer,
+            "  {} → {}: {}",
+            change.from, change.to, change.count
+        )?;
+    }
+    if !report.top_operations.is_empty() {
+        writeln!(writer, "\nMost affected operations:")?;
+        for op in &report.top_operations {
+            writeln!(writer, "  {} ({})", op.operation, op.changed)?;
+        }
+    }
+    Ok(())
+}
+
 pub fn check_preflight(json: bool) -> Result<(), EngramError> {
     let workspace_dir = std::env::current_dir().map_err(|e| {
         EngramError::Validation(format!("Cannot determine workspace directory: {}", e))
@@ -764,19 +1130,32 @@ mod tests {
         .unwrap();
 
         // Test listing all
-        let result = list_sandboxes(&storage, None, None, None, true);
+        let result = list_sandboxes(&storage, None, None, None, true, &SortArgs::default());
         assert!(result.is_ok());
 
         // Test filter by agent_id
         // Since list_sandboxes just prints to stdout, we can't easily capture output here to assert count
         // but we can verify it doesn't panic.
         // In a real refactor, list_sandboxes should probably return the list.
-        let result_filtered =
-            list_sandboxes(&storage, Some("agent1".to_string()), None, None, true);
+        let result_filtered = list_sandboxes(
+            &storage,
+            Some("agent1".to_string()),
+            None,
+            None,
+            true,
+            &SortArgs::default(),
+        );
         assert!(result_filtered.is_ok());
 
         // Test filter by level
-        let result_level = list_sandboxes(&storage, None, Some("isolated".to_string()), None, true);
+        let result_level = list_sandboxes(
+            &storage,
+            None,
+            Some("isolated".to_string()),
+            None,
+            true,
+            &SortArgs::default(),
+        );
         assert!(result_level.is_ok());
     }
 
@@ -801,7 +1180,7 @@ mod tests {
         assert!(result.is_ok());
 
         let result_missing = get_sandbox(&storage, "nonexistent".to_string(), true);
-        assert!(matches!(result_missing, Err(EngramError::NotFound(_))));
+        assert!(matches!(result_missing, Err(EngramError::NotFound { .. })));
     }
 
     #[test]
@@ -827,6 +1206,7 @@ mod tests {
             Some("unrestricted".to_string()),
             false,
             None,
+            PathRuleUpdate::default(),
             true,
         );
         assert!(result.is_ok());
@@ -835,6 +1215,34 @@ mod tests {
         let entity = storage.get(&id, "agent_sandbox").unwrap().unwrap();
         let sandbox = AgentSandbox::from_generic(entity).unwrap();
         assert_eq!(sandbox.sandbox_level, SandboxLevel::Unrestricted);
+
+        // Path globs merge into the rule for their operation
+        for allow in ["src/**", "tests/**", "src/**"] {
+            let rules = PathRuleUpdate {
+                operation: "file_write".to_string(),
+                allow: vec![allow.to_string()],
+                deny: vec![".github/**".to_string()],
+            };
+            update_sandbox(&mut storage, id.clone(), None, false, None, rules, true).unwrap();
+        }
+        let entity = storage.get(&id, "agent_sandbox").unwrap().unwrap();
+        let sandbox = AgentSandbox::from_generic(entity).unwrap();
+        assert_eq!(
+            sandbox.permissions.path_permissions,
+            vec![PathPermission {
+                operation: OperationType::FileWrite,
+                allow: vec!["src/**".to_string(), "tests/**".to_string()],
+                deny: vec![".github/**".to_string()],
+            }]
+        );
+
+        let rules = PathRuleUpdate {
+            operation: "file_read".to_string(),
+            allow: vec!["src/**".to_string()],
+            deny: vec![],
+        };
+        let result = update_sandbox(&mut storage, id.clone(), None, false, None, rules, true);
+        assert!(matches!(result, Err(EngramError::Validation(_))));
     }
 
     #[test]
@@ -862,18 +1270,20 @@ mod tests {
         assert!(result_check.is_none());
     }
 
-    #[test]
-    fn test_validate_operation() {
+    #[tokio::test]
+    async fn test_validate_operation() {
         let storage = MemoryStorage::new("test_agent");
         let result = validate_operation(
             &storage,
             Some("agent1".to_string()),
             Some("read".to_string()),
             Some("file".to_string()),
+            None,
             false,
             None,
             true,
-        );
+        )
+        .await;
         assert!(result.is_ok());
     }
 
@@ -991,9 +1401,10 @@ mod tests {
             Some("standard".to_string()),
             false,
             None,
+            PathRuleUpdate::default(),
             true,
         );
-        assert!(matches!(result, Err(EngramError::NotFound(_))));
+        assert!(matches!(result, Err(EngramError::NotFound { .. })));
     }
 
     #[test]
@@ -1020,25 +1431,31 @@ mod tests {
             Some("super_secure_level".to_string()),
             false,
             None,
+            PathRuleUpdate::default(),
             true,
         );
         assert!(matches!(result, Err(EngramError::Validation(_))));
     }
 
-    #[test]
-    fn test_validate_operation_missing_fields() {
+    #[tokio::test]
+    async fn test_validate_operation_missing_fields() {
         let storage = MemoryStorage::new("test_agent");
-        // Missing agent_id
+        // Missing agent_id falls back to the current identity
+        let request =
+            request_from_args(None, Some("op".to_string()), Some("res".to_string())).unwrap();
+        assert_eq!(request.agent_id, resolve_agent(None::<&str>));
         let result = validate_operation(
             &storage,
             None,
             Some("op".to_string()),
             Some("res".to_string()),
+            None,
             false,
             None,
             true,
-        );
-        assert!(matches!(result, Err(EngramError::Validation(_))));
+        )
+        .await;
+        assert!(result.is_ok());
 
         // Missing operation
         let result = validate_operation(
@@ -1046,10 +1463,61 @@ mod tests {
             Some("agent1".to_string()),
             None,
             Some("res".to_string()),
+            None,
             false,
             None,
             true,
-        );
+        )
+        .await;
         assert!(matches!(result, Err(EngramError::Validation(_))));
     }
+
+    #[tokio::test]
+    async fn test_simulate_sandbox_from_requests_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("requests.jsonl");
+        let request = |op: &str| {
+            serde_json::json!({
+                "agent_id": "agent1",
+                "operation": op,
+                "resource_type": "file",
+                "parameters": {},
+                "timestamp": "2026-01-01T00:00:00Z",
+                "session_id": null,
+            })
+            .to_string()
+        };
+        fs::write(
+            &path,
+            [
+                request("list_files"),
+                request("write_file"),
+                request("list_files"),
+            ]
+            .join("\n"),
+        )
+        .unwrap();
+
+        let storage = MemoryStorage::new("test_agent");
+        let report = simulate_sandbox(
+            &storage,
+            Some("agent1".to_string()),
+            "isolated",
+            "30d",
+            Some(&path),
+            5,
+        )
+        .await
+        .unwrap();
+        assert_eq!(report.total_requests, 3);
+        assert_eq!(report.sandbox_level, SandboxLevel::Isolated);
+        assert_eq!(report.historical[&SandboxDecision::Allow], 3);
+        assert!(storage.list_ids("agent_sandbox").unwrap().is_empty());
+
+        let mut out = Vec::new();
+        print_simulation_report(&mut out, &report, false).unwrap();
+        assert!(String::from_utf8(out)
+            .unwrap()
+            .contains("decision(s) would change"));
+    }
 }
@@ -1,5 +1,6 @@
+use crate::config::AgentConfig;
 use crate::entities::dora_metrics_report::DoraMetricsCalculator;
-use crate::entities::session::{DoraMetrics, SpaceMetrics};
+use crate::entities::session::{DoraMetrics, FatigueAnalysis, FatigueLevel, SpaceMetrics};
 use crate::entities::{Entity, Session, SessionStatus};
 use crate::error::EngramError;
 use crate::storage::Storage;
@@ -11,9 +12,9 @@ use clap::Subcommand;
 pub enum SessionCommands {
     /// Start a new session
     Start {
-        /// Agent name
+        /// Agent name (defaults to the current identity; `me` is accepted)
         #[arg(long, short)]
-        name: String,
+        name: Option<String>,
 
         /// Auto-detect current task
         #[arg(long)]
@@ -29,6 +30,18 @@ pub enum SessionCommands {
         #[arg(long)]
         metrics: bool,
     },
+    /// Pause a session; paused time doesn't count towards fatigue
+    Pause {
+        /// Session ID
+        #[arg(long, short)]
+        id: String,
+    },
+    /// Resume a paused session
+    Resume {
+        /// Session ID
+        #[arg(long, short)]
+        id: String,
+    },
     /// End current session
     End {
         /// Session ID
@@ -61,6 +74,9 @@ pub enum SessionCommands {
         /// Offset for pagination
         #[arg(long, short)]
         offset: Option<usize>,
+
+        #[command(flatten)]
+        sort: SortArgs,
     },
     /// Detect zombie sessions (started but never ended beyond a threshold)
     Zombies {
@@ -90,6 +106,35 @@ pub enum SessionCommands {
         #[arg(long, conflicts_with = "limit")]
         all: bool,
     },
+    /// Compare two sessions' entities and productivity metrics
+    ///
+    ///EXAMPLES:
+    ///  engram session compare <id_a> <id_b>
+    ///  engram session compare <id_a> <id_b> --format json
+    Compare {
+        /// First (baseline) session ID
+        id_a: String,
+
+        /// Second session ID
+        id_b: String,
+
+        /// Output format: table, json
+        #[arg(long, default_value = "table")]
+        format: String,
+    },
+}
+
+impl SessionCommands {
+    /// Whether the command writes to the workspace
+    pub fn is_mutating(&self) -> bool {
+        matches!(
+            self,
+            SessionCommands::Start { .. }
+                | SessionCommands::Pause { .. }
+                | SessionCommands::Resume { .. }
+                | SessionCommands::End { .. }
+        )
+    }
 }
 
 /// Start a new session
@@ -143,7 +188,7 @@ pub fn show_session_status<S: Storage>(
 ) -> Result<(), EngramError> {
     let generic = storage
         .get(&session_id, Session::entity_type())?
-        .ok_or_else(|| EngramError::NotFound(format!("Session not found: {}", session_id)))?;
+        .ok_or_else(|| EngramError::not_found("session", session_id))?;
 
     let session =
         Session::from_generic(generic).map_err(|e| EngramError::Validation(e.to_string()))?;
@@ -191,6 +236,23 @@ pub fn show_session_status<S: Storage>(
     if show_metrics {
         println!("\n--- Metrics ---");
 
+        let fatigue = session_fatigue(storage, &session, Utc::now())?;
+        let icon = match fatigue.level {
+            FatigueLevel::Low => "🟢",
+            FatigueLevel::Moderate => "🟡",
+            FatigueLevel::High => "🔴",
+        };
+        println!("\nFatigue:");
+        println!("  Level:           {} {}", icon, fatigue.level);
+        println!(
+            "  Active Time:     {}m of {}m optimal",
+            fatigue.active_minutes, fatigue.optimal_session_minutes
+        );
+        println!("  Tasks Completed: {}", fatigue.tasks_completed);
+        if let Some(ref recommendation) = fatigue.recommendation {
+            println!("  Recommendation:  {}", recommendation);
+        }
+
         if let Some(ref space) = session.space_metrics {
             println!("\nSPACE Framework:");
             println!("  Satisfaction: {:.2}", space.satisfaction_score);
@@ -220,6 +282,65 @@ pub fn show_session_status<S: Storage>(
     Ok(())
 }
 
+/// Estimate fatigue for `session` from its active time and the session
+/// tasks already done, using the thresholds in the agent's profile
+pub fn session_fatigue<S: Storage + ?Sized>(
+    storage: &S,
+    session: &Session,
+    now: chrono::DateTime<Utc>,
+) -> Result<FatigueAnalysis, EngramError> {
+    let mut tasks_completed = 0;
+    for task_id in &session.task_ids {
+        if let Some(generic) = storage.get(task_id, Task::entity_type())? {
+            if Task::from_generic(generic).is_ok_and(|task| task.status == TaskStatus::Done) {
+                tasks_completed += 1;
+            }
+        }
+    }
+    let thresholds = AgentConfig::load_for_agent(std::path::Path::new("."), &session.agent)
+        .map(|config| config.fatigue)
+        .unwrap_or_default();
+    Ok(FatigueAnalysis::compute(
+        session,
+        tasks_completed,
+        &thresholds,
+        now,
+    ))
+}
+
+/// Pause or resume a session
+pub fn set_session_paused<S: Storage>(
+    storage: &mut S,
+    session_id: &str,
+    paused: bool,
+) -> Result<(), EngramError> {
+    let generic = storage
+        .get(session_id, Session::entity_type())?
+        .ok_or_else(|| EngramError::not_found("session", session_id))?;
+    let mut session =
+        Session::from_generic(generic).map_err(|e| EngramError::Validation(e.to_string()))?;
+
+    match (paused, &session.status) {
+        (true, SessionStatus::Active | SessionStatus::Reflecting) => session.pause(),
+        (false, SessionStatus::Paused) => session.resume(),
+        (_, status) => {
+            return Err(EngramError::Validation(format!(
+                "Cannot {} a session that is {:?}",
+                if paused { "pause" } else { "resume" },
+                status
+            )))
+        }
+    }
+    storage.store(&session.to_generic())?;
+
+    println!(
+        "Session {}: {}",
+        if paused { "paused" } else { "resumed" },
+        session.id
+    );
+    Ok(())
+}
+
 /// End a session
 pub fn end_session<S: Storage>(
     storage: &mut S,
@@ -228,7 +349,7 @@ pub fn end_session<S: Storage>(
 ) -> Result<(), EngramError> {
     let generic = storage
         .get(&session_id, Session::entity_type())?
-        .ok_or_else(|| EngramError::NotFound(format!("Session not found: {}", session_id)))?;
+        .ok_or_else(|| EngramError::not_found("session", session_id))?;
 
     let mut session =
         Session::from_generic(generic).map_err(|e| EngramError::Validation(e.to_string()))?;
@@ -353,11 +474,14 @@ fn calculate_basic_dora_metrics<S: Storage>(storage: &mut S, session: &Session)
     }
 }
 
-use crate::cli::utils::{create_table, truncate};
+use crate::cli::utils::{create_table, truncate, SortArgs};
+use crate::entities::{EntityRegistry, ExecutionResult, GenericEntity, Task, TaskStatus};
 use chrono::{DateTime, NaiveDate, NaiveDateTime};
 use prettytable::row;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
-fn parse_since(input: &str) -> Result<DateTime<Utc>, EngramError> {
+pub(crate) fn parse_since(input: &str) -> Result<DateTime<Utc>, EngramError> {
     let input = input.trim();
 
     if let Some(rest) = input.strip_suffix('h') {
@@ -395,6 +519,7 @@ fn parse_since(input: &str) -> Result<DateTime<Utc>, EngramError> {
 }
 
 /// List sessions
+#[allow(clippy::too_many_arguments)]
 pub fn list_sessions<S: Storage>(
     writer: &mut dyn std::io::Write,
     storage: &S,
@@ -403,6 +528,7 @@ pub fn list_sessions<S: Storage>(
     limit: Option<usize>,
     all: bool,
     offset: Option<usize>,
+    sort: &SortArgs,
 ) -> Result<(), EngramError> {
     let since_time = since_filter.as_deref().map(parse_since).transpose()?;
 
@@ -429,6 +555,7 @@ pub fn list_sessions<S: Storage>(
 
     sessions.sort_by(|a, b| b.start_time.cmp(&a.start_time));
 
+    sessions = sort.sort(sessions)?;
     let total_count = sessions.len();
 
     if let Some(off) = offset {
@@ -799,6 +926,269 @@ pub fn summarize_sessions<S: Storage>(
     Ok(())
 }
 
+/// Productivity snapshot of one session, used by [`compare_sessions`]
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionSummary {
+    pub id: String,
+    pub title: String,
+    pub agent: String,
+    pub duration_minutes: f64,
+    pub tasks_completed: u32,
+    pub knowledge_created: u32,
+    /// Passed over passed + failed execution results; skipped results are ignored
+    pub quality_gate_pass_rate: f64,
+    /// SPACE overall score, 0 when the session has no metrics yet
+    pub space_overall_score: f64,
+}
+
+/// Side-by-side comparison of two sessions
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionComparison {
+    pub session_a: SessionSummary,
+    pub session_b: SessionSummary,
+    pub entities_only_in_a: Vec<String>,
+    pub entities_only_in_b: Vec<String>,
+    pub entities_in_both: Vec<String>,
+    /// Percentage change from session A to session B, keyed `<metric>_delta`
+    pub metric_deltas: HashMap<String, f64>,
+}
+
+fn load_session(storage: &dyn Storage, id: &str) -> Result<Session, EngramError> {
+    let generic = storage
+        .get(id, Session::entity_type())?
+        .ok_or_else(|| EngramError::not_found("session", id))?;
+    Session::from_generic(generic)
+}
+
+/// Entities linked to a session or created by its agent while it was open
+fn session_entities(
+    storage: &dyn Storage,
+    session: &Session,
+) -> Result<BTreeMap<String, GenericEntity>, EngramError> {
+    let end = session.end_time.unwrap_or_else(Utc::now);
+    let linked: HashSet<&String> = session
+        .task_ids
+        .iter()
+        .chain(&session.context_ids)
+        .chain(&session.knowledge_ids)
+        .chain(&session.theory_ids)
+        .chain(&session.reflection_ids)
+        .collect();
+
+    let mut entities = BTreeMap::new();
+    for entity_type in EntityRegistry::with_builtin_types().list_types() {
+        if entity_type == Session::entity_type() {
+            continue;
+        }
+        for generic in storage.get_all(entity_type)? {
+            let created_during = generic.agent == session.agent
+                && generic.timestamp >= session.start_time
+                && generic.timestamp <= end;
+            if created_during || linked.contains(&generic.id) {
+                entities.insert(generic.id.clone(), generic);
+            }
+        }
+    }
+    Ok(entities)
+}
+
+fn summarize_session(
+    session: &Session,
+    entities: &BTreeMap<String, GenericEntity>,
+) -> SessionSummary {
+    let end = session.end_time.unwrap_or_else(Utc::now);
+    let (mut tasks_completed, mut knowledge_created, mut passed, mut failed) = (0, 0, 0, 0);
+    for generic in entities.values() {
+        match generic.entity_type.as_str() {
+            "task"
+                if Task::from_generic(generic.clone())
+                    .is_ok_and(|t| t.status == TaskStatus::Done) =>
+            {
+                tasks_completed += 1;
+            }
+            "knowledge" => knowledge_created += 1,
+            "execution_result" => {
+                if let Ok(result) = ExecutionResult::from_generic(generic.clone()) {
+                    if result.passed() {
+                        passed += 1;
+                    } else if result.failed() {
+                        failed += 1;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    SessionSummary {
+        id: session.id.clone(),
+        title: session.title.clone(),
+        agent: session.agent.clone(),
+        duration_minutes: (end - session.start_time).num_seconds().max(0) as f64 / 60.0,
+        tasks_completed,
+        knowledge_created,
+        quality_gate_pass_rate: if passed + failed == 0 {
+            0.0
+        } else {
+            passed as f64 / (passed + failed) as f64
+        },
+        space_overall_score: session
+            .space_metrics
+            .as_ref()
+            .map_or(0.0, |m| m.overall_score),
+    }
+}
+
+/// Percentage change from `a` to `b`; growth from zero counts as 100%
+fn percent_change(a: f64, b: f64) -> f64 {
+    if a == 0.0 {
+        if b == 0.0 {
+            0.0
+        } else {
+            100.0
+        }
+    } else {
+        (b - a) / a.abs() * 100.0
+    }
+}
+
+/// Compare the entity sets and productivity metrics of two sessions
+pub fn compare_sessions(
+    storage: &dyn Storage,
+    session_a_id: &str,
+    session_b_id: &str,
+) -> Result<SessionComparison, EngramError> {
+    let session_a = load_session(storage, session_a_id)?;
+    let session_b = load_session(storage, session_b_id)?;
+    let entities_a = session_entities(storage, &session_a)?;
+    let entities_b = session_entities(storage, &session_b)?;
+
+    let only = |x: &BTreeMap<String, GenericEntity>, y: &BTreeMap<String, GenericEntity>| {
+        x.keys()
+            .filter(|id| !y.contains_key(*id))
+            .cloned()
+            .collect::<Vec<_>>()
+    };
+    let entities_only_in_a = only(&entities_a, &entities_b);
+    let entities_only_in_b = only(&entities_b, &entities_a);
+    let entities_in_both = entities_a
+        .keys()
+        .filter(|id| entities_b.contains_key(*id))
+        .cloned()
+        .collect();
+
+    let summary_a = summarize_session(&session_a, &entities_a);
+    let summary_b = summarize_session(&session_b, &entities_b);
+    let metric_deltas = [
+        (
+            "duration_minutes",
+            summary_a.duration_minutes,
+            summary_b.duration_minutes,
+        ),
+        (
+            "tasks_completed",
+            summary_a.tasks_completed as f64,
+            summary_b.tasks_completed as f64,
+        ),
+        (
+            "knowledge_created",
+            summary_a.knowledge_created as f64,
+            summary_b.knowledge_created as f64,
+        ),
+        (
+            "quality_gate_pass_rate",
+            summary_a.quality_gate_pass_rate,
+            summary_b.quality_gate_pass_rate,
+        ),
+        (
+            "space_overall_score",
+            summary_a.space_overall_score,
+            summary_b.space_overall_score,
+        ),
+    ]
+    .into_iter()
+    .map(|(name, a, b)| (format!("{}_delta", name), percent_change(a, b)))
+    .collect();
+
+    Ok(SessionComparison {
+        session_a: summary_a,
+        session_b: summary_b,
+        entities_only_in_a,
+        entities_only_in_b,
+        entities_in_both,
+        metric_deltas,
+    })
+}
+
+/// Render a [`SessionComparison`] as a table or JSON
+pub fn print_session_comparison(
+    writer: &mut dyn std::io::Write,
+    comparison: &SessionComparison,
+    format: &str,
+) -> Result<(), EngramError> {
+    if format == "json" {
+        writeln!(writer, "{}", serde_json::to_string_pretty(comparison)?)?;
+        return Ok(());
+    }
+
+    let (a, b) = (&comparison.session_a, &comparison.session_b);
+    let delta = |name: &str| {
+        comparison
+            .metric_deltas
+            .get(&format!("{}_delta", name))
+            .map_or("-".to_string(), |d| format!("{:+.1}%", d))
+    };
+    let mut table = create_table();
+    table.set_titles(row![
+        "Metric",
+        &a.id[..8.min(a.id.len())],
+        &b.id[..8.min(b.id.len())],
+        "Change"
+    ]);
+    table.add_row(row!["Agent", a.agent, b.agent, ""]);
+    table.add_row(row![
+        "Duration (min)",
+        format!("{:.0}", a.duration_minutes),
+        format!("{:.0}", b.duration_minutes),
+        delta("duration_minutes")
+    ]);
+    table.add_row(row![
+        "Tasks completed",
+        a.tasks_completed,
+        b.tasks_completed,
+        delta("tasks_completed")
+    ]);
+    table.add_row(row![
+        "Knowledge created",
+        a.knowledge_created,
+        b.knowledge_created,
+        delta("knowledge_created")
+    ]);
+    table.add_row(row![
+        "Quality gate pass rate",
+        format!("{:.0}%", a.quality_gate_pass_rate * 100.0),
+        format!("{:.0}%", b.quality_gate_pass_rate * 100.0),
+        delta("quality_gate_pass_rate")
+    ]);
+    table.add_row(row![
+        "SPACE overall",
+        format!("{:.1}", a.space_overall_score),
+        format!("{:.1}", b.space_overall_score),
+        delta("space_overall_score")
+    ]);
+    table.print(writer)?;
+
+    writeln!(writer)?;
+    writeln!(
+        writer,
+        "Entities: {} only in A, {} only in B, {} in both",
+        comparison.entities_only_in_a.len(),
+        comparison.entities_only_in_b.len(),
+        comparison.entities_in_both.len()
+    )?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -808,6 +1198,51 @@ mod tests {
         MemoryStorage::new("default")
     }
 
+    #[test]
+    fn test_session_fatigue_counts_completed_tasks() {
+        let mut storage = create_test_storage();
+        let session_id = start_session(&mut storage, "agent1".to_string(), false).unwrap();
+        let mut session =
+            Session::from_generic(storage.get(&session_id, "session").unwrap().unwrap()).unwrap();
+        session.start_time = Utc::now() - Duration::minutes(100);
+        for status in [TaskStatus::Done, TaskStatus::Done, TaskStatus::Todo] {
+            let mut task = Task::new(
+                "T".to_string(),
+                "D".to_string(),
+                "agent1".to_string(),
+                crate::entities::TaskPriority::Medium,
+                None,
+            );
+            task.status = status;
+            storage.store(&task.to_generic()).unwrap();
+            session.add_task(task.id);
+        }
+
+        let fatigue = session_fatigue(&storage, &session, Utc::now()).unwrap();
+        assert_eq!(fatigue.tasks_completed, 2);
+        // 100 active minutes + 2 x 10 for completed tasks, against 90 optimal
+        assert_eq!(fatigue.level, FatigueLevel::Moderate);
+    }
+
+    #[test]
+    fn test_pause_and_resume_session() {
+        let mut storage = create_test_storage();
+        let session_id = start_session(&mut storage, "agent1".to_string(), false).unwrap();
+
+        set_session_paused(&mut storage, &session_id, true).unwrap();
+        let session =
+            Session::from_generic(storage.get(&session_id, "session").unwrap().unwrap()).unwrap();
+        assert_eq!(session.status, SessionStatus::Paused);
+        assert!(session.paused_at.is_some());
+        assert!(set_session_paused(&mut storage, &session_id, true).is_err());
+
+        set_session_paused(&mut storage, &session_id, false).unwrap();
+        let session =
+            Session::from_generic(storage.get(&session_id, "session").unwrap().unwrap()).unwrap();
+        assert_eq!(session.status, SessionStatus::Active);
+        assert!(session.paused_at.is_none());
+    }
+
     #[test]
     fn test_start_session() {
         let mut storage = create_test_storage();
@@ -852,7 +1287,17 @@ mod tests {
         start_session(&mut storage, "agent2".to_string(), false).unwrap();
 
         let mut buffer = Vec::new();
-        list_sessions(&mut buffer, &storage, None, None, None, false, None).unwrap();
+        list_sessions(
+            &mut buffer,
+            &storage,
+            None,
+            None,
+            None,
+            false,
+            None,
+            &SortArgs::default(),
+        )
+        .unwrap();
         let output = String::from_utf8(buffer).unwrap();
 
         assert!(output.contains("Found 2 sessions"));
@@ -868,6 +1313,7 @@ mod tests {
             None,
             false,
             None,
+            &SortArgs::default(),
         )
         .unwrap();
         let output_filtered = String::from_utf8(buffer_filtered).unwrap();
@@ -904,7 +1350,122 @@ mod tests {
     fn test_end_session_not_found() {
         let mut storage = create_test_storage();
         let result = end_session(&mut storage, "non-existent".to_string(), false);
-        assert!(matches!(result, Err(EngramError::NotFound(_))));
+        assert!(matches!(result, Err(EngramError::NotFound { .. })));
+    }
+
+    #[test]
+    fn test_compare_sessions_entity_sets_and_deltas() {
+        use crate::entities::{Knowledge, KnowledgeType, TaskPriority, ValidationStatus};
+
+        let mut storage = create_test_storage();
+        let now = Utc::now();
+        let session = |agent: &str, hours_ago: i64| {
+            let mut session = Session::new(format!("{} sprint", agent), agent.to_string(), vec![]);
+            session.start_time = now - Duration::hours(hours_ago);
+            session.end_time = Some(session.start_time + Duration::hours(1));
+            session
+        };
+        let mut session_a = session("alice", 5);
+        let session_b = session("bob", 2);
+        let task = |title: &str, agent: &str, start, done: bool| {
+            let mut task = Task::new(
+                title.to_string(),
+                String::new(),
+                agent.to_string(),
+                TaskPriority::Medium,
+                None,
+            );
+            task.start_time = start;
+            if done {
+                task.status = TaskStatus::Done;
+            }
+            task
+        };
+
+        let a_done = task(
+            "A done",
+            "alice",
+            session_a.start_time + Duration::minutes(10),
+            true,
+        );
+        let a_open = task(
+            "A open",
+            "alice",
+            session_a.start_time + Duration::minutes(20),
+            false,
+        );
+        let shared = task(
+            "Shared",
+            "bob",
+            session_b.start_time + Duration::minutes(5),
+            true,
+        );
+        let b_done = task(
+            "B done",
+            "bob",
+            session_b.start_time + Duration::minutes(15),
+            true,
+        );
+        let outside = task("Later", "alice", now, true);
+        session_a.task_ids.push(shared.id.clone());
+        let mut knowledge = Knowledge::new(
+            "B insight".to_string(),
+            "content".to_string(),
+            KnowledgeType::Pattern,
+            0.9,
+            "bob".to_string(),
+        );
+        knowledge.created_at = session_b.start_time + Duration::minutes(30);
+        let mut gate = ExecutionResult::new(
+            b_done.id.clone(),
+            "test".to_string(),
+            "unit".to_string(),
+            "cargo test".to_string(),
+            "bob".to_string(),
+        );
+        gate.timestamp = session_b.start_time + Duration::minutes(40);
+        gate.validation_status = ValidationStatus::Passed;
+
+        for generic in [
+            session_a.to_generic(),
+            session_b.to_generic(),
+            a_done.to_generic(),
+            a_open.to_generic(),
+            shared.to_generic(),
+            b_done.to_generic(),
+            outside.to_generic(),
+            knowledge.to_generic(),
+            gate.to_generic(),
+        ] {
+            storage.store(&generic).unwrap();
+        }
+
+        let comparison = compare_sessions(&storage, &session_a.id, &session_b.id).unwrap();
+        let mut expected_a = vec![a_done.id.clone(), a_open.id.clone()];
+        expected_a.sort();
+        assert_eq!(comparison.entities_only_in_a, expected_a);
+        assert_eq!(comparison.entities_in_both, vec![shared.id.clone()]);
+        let mut expected_b = vec![b_done.id.clone(), knowledge.id.clone(), gate.id.clone()];
+        expected_b.sort();
+        assert_eq!(comparison.entities_only_in_b, expected_b);
+
+        assert_eq!(comparison.session_a.tasks_completed, 2);
+        assert_eq!(comparison.session_b.tasks_completed, 2);
+        assert_eq!(comparison.session_b.knowledge_created, 1);
+        assert_eq!(comparison.session_b.quality_gate_pass_rate, 1.0);
+        assert!((comparison.session_a.duration_minutes - 60.0).abs() < 1e-9);
+        assert_eq!(comparison.metric_deltas["tasks_completed_delta"], 0.0);
+        assert_eq!(comparison.metric_deltas["knowledge_created_delta"], 100.0);
+
+        let mut buffer = Vec::new();
+        print_session_comparison(&mut buffer, &comparison, "json").unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&buffer).unwrap();
+        assert_eq!(json["session_a"]["agent"], "alice");
+
+        assert!(matches!(
+            compare_sessions(&storage, &session_a.id, "missing"),
+            Err(EngramError::NotFound { .. })
+        ));
     }
 
     fn create_old_session(storage: &mut MemoryStorage, agent: &str, hours_ago: i64) -> String {
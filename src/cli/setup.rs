@@ -1,68 +1,821 @@
 //! Setup command implementations
 
+use crate::config::{AgentConfig, Config};
+use crate::entities::progressive_config::{
+    ChangeThreshold, ChangeType, FailureHandling, GateDefinition, GateLevel,
+    ParallelizationStrategy, ProgressiveRiskLevel, RetryPolicy,
+};
+use crate::entities::{
+    Context, ContextRelevance, Entity, ProgressiveGateConfig, Rule, RulePriority, RuleType,
+    StateType, TransitionType, Workflow, WorkflowState, WorkflowTransition,
+};
 use crate::error::EngramError;
-use serde::Serialize;
+use crate::storage::{GitRefsStorage, QueryFilter, Storage};
+use crate::validation::{HookManager, ValidationConfig};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::env;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Programmatic workspace setup, used by `engram setup workspace` and by
+/// library consumers that want a workspace without shelling out
+///
+/// ```no_run
+/// use engram::cli::WorkspaceInitializer;
+/// use engram::config::AgentConfig;
+/// use std::path::Path;
+///
+/// let workspace = WorkspaceInitializer::new(Path::new("/tmp/project"))
+///     .with_agent("default", AgentConfig::default())
+///     .initialize()?;
+/// workspace.verify()?;
+/// # Ok::<(), engram::EngramError>(())
+/// ```
+pub struct WorkspaceInitializer {
+    root: PathBuf,
+    name: String,
+    default_agent: Option<String>,
+    agents: BTreeMap<String, AgentConfig>,
+    sync_strategy: String,
+    validation: Option<ValidationConfig>,
+    workflow_files: Vec<PathBuf>,
+    default_contexts: bool,
+}
 
-/// Setup workspace command
-pub fn setup_workspace(root_dir: Option<PathBuf>) -> Result<(), EngramError> {
-    let engram_dir = root_dir
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join(".engram");
-    fs::create_dir_all(&engram_dir).map_err(EngramError::Io)?;
-
-    // Create subdirectories
-    let subdirs = ["agents", "workspaces", "templates"];
-    for subdir in &subdirs {
-        fs::create_dir_all(engram_dir.join(subdir)).map_err(EngramError::Io)?;
-    }
-
-    // Create default configuration
-    let config = WorkspaceSetup {
-        agents: std::collections::HashMap::from([
-            (
-                "coder".to_string(),
-                AgentSetup {
-                    agent_type: "implementation".to_string(),
-                    description: "Handles code changes and technical implementation tasks"
-                        .to_string(),
-                },
-            ),
-            (
-                "reviewer".to_string(),
-                AgentSetup {
-                    agent_type: "quality_assurance".to_string(),
-                    description: "Reviews code for quality and standards compliance".to_string(),
-                },
-            ),
-            (
-                "planner".to_string(),
-                AgentSetup {
-                    agent_type: "architecture".to_string(),
-                    description: "Handles system design, planning, and architectural decisions"
-                        .to_string(),
+impl WorkspaceInitializer {
+    /// Start a workspace rooted at `path`
+    pub fn new(path: &Path) -> Self {
+        Self {
+            root: path.to_path_buf(),
+            name: "default".to_string(),
+            default_agent: None,
+            agents: BTreeMap::new(),
+            sync_strategy: "merge_with_conflict_resolution".to_string(),
+            validation: None,
+            workflow_files: Vec::new(),
+            default_contexts: true,
+        }
+    }
+
+    /// Start a workspace in the current directory from a loaded `Config`
+    pub fn from_config(config: &Config) -> Self {
+        let mut initializer = Self::new(Path::new("."));
+        initializer.name = config.workspace.name.clone();
+        initializer.sync_strategy = config.workspace.sync_strategy.clone();
+        initializer.default_agent = Some(config.workspace.default_agent.clone());
+        for (name, agent) in config.workspace.agents.iter().chain(&config.agents) {
+            initializer = initializer.with_agent(name, agent.clone());
+        }
+        initializer
+    }
+
+    /// Change the workspace root
+    pub fn with_root(mut self, path: &Path) -> Self {
+        self.root = path.to_path_buf();
+        self
+    }
+
+    /// Add an agent; an empty `name` in the config is filled from `name`
+    pub fn with_agent(mut self, name: &str, mut config: AgentConfig) -> Self {
+        if config.name.is_empty() {
+            config.name = name.to_string();
+        }
+        self.agents.insert(name.to_string(), config);
+        self
+    }
+
+    /// Agent that owns the storage; defaults to the first agent by name
+    pub fn with_default_agent(mut self, name: &str) -> Self {
+        self.default_agent = Some(name.to_string());
+        self
+    }
+
+    /// Save a validation config to `.engram/validation.yaml`
+    pub fn with_validation(mut self, config: ValidationConfig) -> Self {
+        self.validation = Some(config);
+        self
+    }
+
+    /// Store the workflow defined in a YAML or JSON file; relative paths are
+    /// resolved against the workspace root
+    pub fn with_workflow_from_file(mut self, path: impl AsRef<Path>) -> Self {
+        self.workflow_files.push(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Skip seeding the workspace overview context
+    pub fn without_default_contexts(mut self) -> Self {
+        self.default_contexts = false;
+        self
+    }
+
+    fn storage_agent(&self) -> String {
+        self.default_agent
+            .clone()
+            .or_else(|| self.agents.keys().next().cloned())
+            .unwrap_or_else(|| "default".to_string())
+    }
+
+    fn load_workflow(&self, path: &Path) -> Result<Workflow, EngramError> {
+        load_workflow_file(&self.root.join(path))
+    }
+
+    /// Create the `.engram` layout, git repository and storage, then store
+    /// the configured workflows
+    pub fn initialize(self) -> Result<InitializedWorkspace, EngramError> {
+        // Parse workflows first so a bad file leaves nothing half-written
+        let workflows = self
+            .workflow_files
+            .iter()
+            .map(|path| self.load_workflow(path))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let engram_dir = self.root.join(".engram");
+        for subdir in ["agents", "workspaces", "templates"] {
+            fs::create_dir_all(engram_dir.join(subdir)).map_err(EngramError::Io)?;
+        }
+
+        let config = WorkspaceSetup {
+            agents: self
+                .agents
+                .iter()
+                .map(|(name, agent)| {
+                    (
+                        name.clone(),
+                        AgentSetup {
+                            agent_type: agent.agent_type.clone(),
+                            description: agent.specialization.clone().unwrap_or_default(),
+                        },
+                    )
+                })
+                .collect(),
+            workspaces: BTreeMap::from([(
+                self.name.clone(),
+                WorkspaceEntry {
+                    agents: self.agents.keys().cloned().collect(),
+                    sync_strategy: self.sync_strategy.clone(),
                 },
+            )]),
+        };
+        let config_path = engram_dir.join("config.yaml");
+        let config_yaml = serde_yaml::to_string(&config)
+            .map_err(|e| EngramError::Validation(format!("Failed to serialize config: {}", e)))?;
+        fs::write(&config_path, config_yaml).map_err(EngramError::Io)?;
+
+        if let Some(validation) = &self.validation {
+            validation.save_to_file(ValidationConfig::workspace_path(&self.root))?;
+        }
+
+        let agent = self.storage_agent();
+        let mut storage = GitRefsStorage::new(&self.root.to_string_lossy(), &agent)?;
+        let mut workflow_ids = Vec::new();
+        for workflow in workflows {
+            storage.store(&workflow.to_generic())?;
+            workflow_ids.push(workflow.id);
+        }
+
+        let mut workspace = InitializedWorkspace {
+            root: self.root,
+            config_path,
+            storage,
+            agent,
+            agents: self.agents.into_keys().collect(),
+            workflow_ids,
+        };
+        if self.default_contexts {
+            workspace.create_default_contexts()?;
+        }
+        Ok(workspace)
+    }
+}
+
+/// A workspace created by [`WorkspaceInitializer`]
+pub struct InitializedWorkspace {
+    root: PathBuf,
+    config_path: PathBuf,
+    storage: GitRefsStorage,
+    agent: String,
+    agents: Vec<String>,
+    workflow_ids: Vec<String>,
+}
+
+/// Source recorded on contexts seeded by [`InitializedWorkspace::create_default_contexts`]
+const SETUP_CONTEXT_SOURCE: &str = "engram-setup";
+
+impl InitializedWorkspace {
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn config_path(&self) -> &Path {
+        &self.config_path
+    }
+
+    pub fn storage(&self) -> &GitRefsStorage {
+        &self.storage
+    }
+
+    pub fn storage_mut(&mut self) -> &mut GitRefsStorage {
+        &mut self.storage
+    }
+
+    /// Consume the workspace, keeping only its storage
+    pub fn into_storage(self) -> GitRefsStorage {
+        self.storage
+    }
+
+    /// IDs of the workflows stored during initialization
+    pub fn workflow_ids(&self) -> &[String] {
+        &self.workflow_ids
+    }
+
+    /// Seed a workspace overview context; does nothing if one already exists.
+    /// Returns the IDs of the contexts created.
+    pub fn create_default_contexts(&mut self) -> Result<Vec<String>, EngramError> {
+        let existing = self.storage.query(&QueryFilter {
+            entity_type: Some(Context::entity_type().to_string()),
+            field_filters: HashMap::from([(
+                "source".to_string(),
+                serde_json::Value::String(SETUP_CONTEXT_SOURCE.to_string()),
+            )]),
+            limit: Some(1),
+            ..Default::default()
+        })?;
+        if !existing.entities.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let agents = if self.agents.is_empty() {
+            "none".to_string()
+        } else {
+            self.agents.join(", ")
+        };
+        let mut context = Context::new(
+            "Workspace overview".to_string(),
+            format!(
+                "Engram workspace at {}.\nAgents: {}\nWorkflows: {}",
+                self.root.display(),
+                agents,
+                self.workflow_ids.len()
             ),
-        ]),
-        workspaces: std::collections::HashMap::from([(
-            "default".to_string(),
-            WorkspaceEntry {
-                agents: vec!["coder".to_string(), "reviewer".to_string()],
-                sync_strategy: "merge_with_conflict_resolution".to_string(),
-            },
-        )]),
+            SETUP_CONTEXT_SOURCE.to_string(),
+            ContextRelevance::Medium,
+            self.agent.clone(),
+        );
+        context.tags.push("workspace".to_string());
+        self.storage.store(&context.to_generic())?;
+        Ok(vec![context.id])
+    }
+
+    /// Install the engram commit-msg hook into the workspace repository
+    pub fn install_hooks(&self) -> Result<(), EngramError> {
+        HookManager::new(&self.root)?.install()
+    }
+
+    /// Check the layout, repository and stored workflows are all in place
+    pub fn verify(&self) -> Result<(), EngramError> {
+        let mut problems = Vec::new();
+        if !self.config_path.exists() {
+            problems.push(format!("missing {}", self.config_path.display()));
+        }
+        if !self.root.join(".git").exists() {
+            problems.push("not a git repository".to_string());
+        }
+        for id in &self.workflow_ids {
+            if self.storage.get(id, Workflow::entity_type())?.is_none() {
+                problems.push(format!("workflow {} not stored", id));
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(EngramError::Validation(format!(
+                "Workspace {} is incomplete: {}",
+                self.root.display(),
+                problems.join("; ")
+            )))
+        }
+    }
+}
+
+/// Workflow entry in a [`WorkspaceTemplate`]: a path to a workflow YAML or
+/// JSON file (the format `with_workflow_from_file` reads), or the workflow
+/// itself inline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TemplateWorkflow {
+    File(PathBuf),
+    Inline(Box<Workflow>),
+}
+
+/// Rule provisioned by a [`WorkspaceTemplate`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateRule {
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    pub rule_type: RuleType,
+    pub priority: RulePriority,
+    #[serde(default)]
+    pub entity_types: Vec<String>,
+    pub condition: serde_json::Value,
+    pub action: serde_json::Value,
+}
+
+/// Workflows, rules, validation config and quality gates provisioned on top
+/// of a base workspace by `engram setup workspace --template`
+///
+/// Template files are YAML. `validation` uses the `.engram/validation.yaml`
+/// format and `quality_gates` the gate levels of a progressive gate config.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WorkspaceTemplate {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub workflows: Vec<TemplateWorkflow>,
+    #[serde(default)]
+    pub rules: Vec<TemplateRule>,
+    #[serde(default)]
+    pub validation: Option<ValidationConfig>,
+    #[serde(default)]
+    pub quality_gates: Vec<GateLevel>,
+    #[serde(default)]
+    pub install_hooks: bool,
+}
+
+/// Names accepted by [`WorkspaceTemplate::builtin`]
+pub const BUILTIN_TEMPLATES: [&str; 3] = ["minimal", "standard", "strict"];
+
+impl WorkspaceTemplate {
+    /// One of the [`BUILTIN_TEMPLATES`]: `minimal` is the feature workflow
+    /// alone, `standard` adds rules and relationship validation, `strict`
+    /// adds quality gates, stale-context warnings and the commit-msg hook
+    pub fn builtin(name: &str) -> Result<Self, EngramError> {
+        let mut template = Self {
+            name: name.to_string(),
+            workflows: vec![TemplateWorkflow::Inline(Box::new(
+                feature_development_workflow(),
+            ))],
+            ..Default::default()
+        };
+        match name {
+            "minimal" => {}
+            "standard" => {
+                template.rules = standard_rules();
+                template.validation = Some(ValidationConfig::default());
+            }
+            "strict" => {
+                template.rules = standard_rules();
+                template.validation = Some(ValidationConfig {
+                    min_reasoning_steps: 1,
+                    warn_stale_context: true,
+                    ..Default::default()
+                });
+                template.quality_gates = vec![strict_gate_level()];
+                template.install_hooks = true;
+            }
+            other => {
+                return Err(EngramError::Validation(format!(
+                    "Unknown workspace template '{}' (expected one of: {})",
+                    other,
+                    BUILTIN_TEMPLATES.join(", ")
+                )))
+            }
+        }
+        Ok(template)
+    }
+
+    /// Read a YAML template. Workflow files are loaded straight away,
+    /// relative to the template's directory, so a bad template fails before
+    /// anything is written.
+    pub fn from_file(path: &Path) -> Result<Self, EngramError> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            EngramError::Validation(format!("Cannot read template {}: {}", path.display(), e))
+        })?;
+        let mut template: Self = serde_yaml::from_str(&content)?;
+        if template.name.is_empty() {
+            template.name = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "custom".to_string());
+        }
+        let base = path.parent().unwrap_or_else(|| Path::new("."));
+        for workflow in &mut template.workflows {
+            if let TemplateWorkflow::File(file) = workflow {
+                *workflow =
+                    TemplateWorkflow::Inline(Box::new(load_workflow_file(&base.join(file))?));
+            }
+        }
+        if let Some(validation) = &template.validation {
+            validation.validate()?;
+        }
+        Ok(template)
+    }
+}
+
+/// What [`InitializedWorkspace::apply_template`] provisioned; anything that
+/// was already present is listed under `skipped` instead
+#[derive(Debug, Default, Serialize)]
+pub struct TemplateReport {
+    pub workflows: Vec<String>,
+    pub rules: Vec<String>,
+    pub validation_updated: bool,
+    pub quality_gates: Option<String>,
+    pub hooks_installed: bool,
+    pub skipped: Vec<String>,
+}
+
+impl InitializedWorkspace {
+    /// Provision a template on top of this workspace. Workflows and rules
+    /// are matched by title, validation flags are only ever switched on, and
+    /// gates are not added when an active gate config exists, so applying
+    /// the same template twice changes nothing.
+    pub fn apply_template(
+        &mut self,
+        template: &WorkspaceTemplate,
+    ) -> Result<TemplateReport, EngramError> {
+        let mut report = TemplateReport::default();
+
+        let existing_workflows: Vec<Workflow> = self
+            .storage
+            .get_all(Workflow::entity_type())?
+            .into_iter()
+            .filter_map(|e| Workflow::from_generic(e).ok())
+            .collect();
+        for entry in &template.workflows {
+            let mut workflow = match entry {
+                TemplateWorkflow::File(path) => load_workflow_file(&self.root.join(path))?,
+                TemplateWorkflow::Inline(workflow) => (**workflow).clone(),
+            };
+            if existing_workflows
+                .iter()
+                .any(|w| w.title == workflow.title || w.id == workflow.id)
+            {
+                report
+                    .skipped
+                    .push(format!("workflow '{}'", workflow.title));
+                continue;
+            }
+            workflow.agent = self.agent.clone();
+            self.storage.store(&workflow.to_generic())?;
+            self.workflow_ids.push(workflow.id.clone());
+            report.workflows.push(workflow.title);
+        }
+
+        let existing_rules: Vec<String> = self
+            .storage
+            .get_all(Rule::entity_type())?
+            .into_iter()
+            .filter_map(|e| Rule::from_generic(e).ok())
+            .map(|rule| rule.title)
+            .collect();
+        for template_rule in &template.rules {
+            if existing_rules.contains(&template_rule.title) {
+                report
+                    .skipped
+                    .push(format!("rule '{}'", template_rule.title));
+                continue;
+            }
+            let mut rule = Rule::new(
+                template_rule.title.clone(),
+                template_rule.description.clone(),
+                template_rule.rule_type.clone(),
+                template_rule.priority.clone(),
+                self.agent.clone(),
+                template_rule.condition.clone(),
+                template_rule.action.clone(),
+            );
+            rule.entity_types = template_rule.entity_types.clone();
+            rule.tags.push(format!("template:{}", template.name));
+            self.storage.store(&rule.to_generic())?;
+            report.rules.push(rule.title);
+        }
+
+        if let Some(validation) = &template.validation {
+            let path = ValidationConfig::workspace_path(&self.root);
+            if path.exists() {
+                let mut existing = ValidationConfig::load_from_file(&path)?;
+                if merge_validation(&mut existing, validation) {
+                    existing.save_to_file(&path)?;
+                    report.validation_updated = true;
+                } else {
+                    report.skipped.push("validation config".to_string());
+                }
+            } else {
+                validation.save_to_file(&path)?;
+                report.validation_updated = true;
+            }
+        }
+
+        if !template.quality_gates.is_empty() {
+            let active = self
+                .storage
+                .get_all(ProgressiveGateConfig::entity_type())?
+                .into_iter()
+                .filter_map(|e| ProgressiveGateConfig::from_generic(e).ok())
+                .find(|c| c.active);
+            match active {
+                Some(config) => report
+                    .skipped
+                    .push(format!("quality gates (active config '{}')", config.name)),
+                None => {
+                    let mut config = ProgressiveGateConfig::new(
+                        format!("{} template", template.name),
+                        self.agent.clone(),
+                    );
+                    config.description =
+                        format!("Provisioned by the '{}' workspace template", template.name);
+                    for level in &template.quality_gates {
+                        config.add_gate_level(level.clone());
+                    }
+                    self.storage.store(&config.to_generic())?;
+                    report.quality_gates = Some(config.name);
+                }
+            }
+        }
+
+        if template.install_hooks {
+            let mut hooks = HookManager::new(&self.root)?;
+            if hooks.is_installed()? {
+                report.skipped.push("commit-msg hook".to_string());
+            } else {
+                hooks.install()?;
+                report.hooks_installed = true;
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+/// Switch on every requirement `template` enables; returns whether
+/// `existing` changed
+fn merge_validation(existing: &mut ValidationConfig, template: &ValidationConfig) -> bool {
+    let mut changed = false;
+    for (flag, wanted) in [
+        (&mut existing.enabled, template.enabled),
+        (
+            &mut existing.require_task_reference,
+            template.require_task_reference,
+        ),
+        (
+            &mut existing.require_reasoning_relationship,
+            template.require_reasoning_relationship,
+        ),
+        (
+            &mut existing.require_context_relationship,
+            template.require_context_relationship,
+        ),
+        (
+            &mut existing.require_file_scope_match,
+            template.require_file_scope_match,
+        ),
+        (
+            &mut existing.warn_stale_context,
+            template.warn_stale_context,
+        ),
+    ] {
+        if wanted && !*flag {
+            *flag = true;
+            changed = true;
+        }
+    }
+    if template.min_reasoning_steps > existing.min_reasoning_steps {
+        existing.min_reasoning_steps = template.min_reasoning_steps;
+        changed = true;
+    }
+    if existing.max_files_per_commit.is_none() && template.max_files_per_commit.is_some() {
+        existing.max_files_per_commit = template.max_files_per_commit;
+        changed = true;
+    }
+    changed
+}
+
+/// Backlog → in progress → review → done, with review able to send work back
+fn feature_development_workflow() -> Workflow {
+    let state =
+        |id: &str, state_type: StateType, description: &str, is_final: bool| WorkflowState {
+            id: id.to_string(),
+            name: id.to_string(),
+            state_type,
+            description: description.to_string(),
+            is_final,
+            prompts: None,
+            guards: vec![],
+            post_functions: vec![],
+            commit_policy: None,
+        };
+    let transition = |name: &str, from: &str, to: &str, description: &str| WorkflowTransition {
+        id: name.to_string(),
+        name: name.to_string(),
+        from_state: from.to_string(),
+        to_state: to.to_string(),
+        transition_type: TransitionType::Manual,
+        description: description.to_string(),
+        conditions: vec![],
+        actions: vec![],
+        trigger: None,
+    };
+
+    let mut workflow = Workflow::new(
+        "Feature development".to_string(),
+        "Standard lifecycle for feature work: plan, implement, review, ship".to_string(),
+        String::new(),
+    );
+    workflow.add_state(state(
+        "backlog",
+        StateType::Start,
+        "Planned, not started",
+        false,
+    ));
+    workflow.add_state(state(
+        "in_progress",
+        StateType::InProgress,
+        "Being implemented",
+        false,
+    ));
+    workflow.add_state(state("review", StateType::Review, "Awaiting review", false));
+    workflow.add_state(state("done", StateType::Done, "Reviewed and merged", true));
+    workflow.add_transition(transition(
+        "start",
+        "backlog",
+        "in_progress",
+        "Begin implementation",
+    ));
+    workflow.add_transition(transition(
+        "submit_for_review",
+        "in_progress",
+        "review",
+        "Implementation ready for review",
+    ));
+    workflow.add_transition(transition(
+        "request_changes",
+        "review",
+        "in_progress",
+        "Review found issues",
+    ));
+    workflow.add_transition(transition("approve", "review", "done", "Review passed"));
+    workflow.set_initial_state("backlog".to_string());
+    workflow.add_final_state("done".to_string());
+    workflow.add_entity_type("task".to_string());
+    workflow.activate();
+    workflow
+}
+
+fn standard_rules() -> Vec<TemplateRule> {
+    vec![
+        TemplateRule {
+            title: "Escalate blocked tasks".to_string(),
+            description: "Notify the team when a task is blocked".to_string(),
+            rule_type: RuleType::Notification,
+            priority: RulePriority::High,
+            entity_types: vec!["task".to_string()],
+            condition: serde_json::json!({"expression": "status == blocked"}),
+            action: serde_json::json!({
+                "type": "notify",
+                "priority": "high",
+                "title": "Blocked task",
+                "message": "A task is blocked and needs attention",
+            }),
+        },
+        TemplateRule {
+            title: "Tasks have a priority".to_string(),
+            description: "Every task must carry a priority".to_string(),
+            rule_type: RuleType::Validation,
+            priority: RulePriority::Medium,
+            entity_types: vec!["task".to_string()],
+            condition: serde_json::Value::Bool(true),
+            action: serde_json::json!({"type": "validate", "field": "priority"}),
+        },
+    ]
+}
+
+/// Single catch-all level running a whitespace check on staged changes
+fn strict_gate_level() -> GateLevel {
+    GateLevel {
+        name: "strict".to_string(),
+        threshold: ChangeThreshold {
+            max_lines_changed: u32::MAX,
+            max_files_affected: u32::MAX,
+            max_complexity_delta: f32::MAX,
+            allowed_change_types: vec![
+                ChangeType::Documentation,
+                ChangeType::Comments,
+                ChangeType::Formatting,
+                ChangeType::BugFix,
+                ChangeType::Refactoring,
+                ChangeType::FeatureMinor,
+                ChangeType::Feature,
+                ChangeType::Enhancement,
+                ChangeType::BreakingChange,
+                ChangeType::SecurityCritical,
+                ChangeType::Performance,
+                ChangeType::Configuration,
+            ],
+            risk_level_limit: ProgressiveRiskLevel::Critical,
+            file_patterns: vec![],
+        },
+        required_gates: vec![GateDefinition {
+            name: "whitespace".to_string(),
+            command: "git diff --cached --check".to_string(),
+            timeout: Duration::from_secs(60),
+            required: true,
+            condition: None,
+            environment: HashMap::new(),
+            retry_policy: RetryPolicy::default(),
+        }],
+        optional_gates: vec![],
+        max_execution_time: Duration::from_secs(300),
+        parallelization: ParallelizationStrategy::Sequential,
+        failure_handling: FailureHandling::default(),
+        enabled: true,
+        priority: 0,
+    }
+}
+
+fn load_workflow_file(path: &Path) -> Result<Workflow, EngramError> {
+    let content = fs::read_to_string(path).map_err(|e| {
+        EngramError::Validation(format!("Cannot read workflow {}: {}", path.display(), e))
+    })?;
+    let workflow: Workflow = if path.extension().is_some_and(|ext| ext == "json") {
+        serde_json::from_str(&content)?
+    } else {
+        serde_yaml::from_str(&content)?
     };
+    workflow.validate_entity()?;
+    crate::engines::validate_workflow_references(&workflow)
+        .map_err(|e| EngramError::Validation(format!("Workflow {}: {}", path.display(), e)))?;
+    Ok(workflow)
+}
 
-    let config_path = engram_dir.join("config.yaml");
-    let config_yaml = serde_yaml::to_string(&config)
-        .map_err(|e| EngramError::Validation(format!("Failed to serialize config: {}", e)))?;
+/// Setup workspace command; `template` is provisioned after base
+/// initialization and may be re-applied to an existing workspace
+pub fn setup_workspace(
+    root_dir: Option<PathBuf>,
+    template: Option<WorkspaceTemplate>,
+) -> Result<(), EngramError> {
+    let agent = |agent_type: &str, description: &str| AgentConfig {
+        agent_type: agent_type.to_string(),
+        specialization: Some(description.to_string()),
+        ..Default::default()
+    };
 
-    fs::write(&config_path, config_yaml).map_err(EngramError::Io)?;
+    let mut workspace = WorkspaceInitializer::new(&root_dir.unwrap_or_else(|| PathBuf::from(".")))
+        .with_agent(
+            "coder",
+            agent(
+                "implementation",
+                "Handles code changes and technical implementation tasks",
+            ),
+        )
+        .with_agent(
+            "reviewer",
+            agent(
+                "quality_assurance",
+                "Reviews code for quality and standards compliance",
+            ),
+        )
+        .with_agent(
+            "planner",
+            agent(
+                "architecture",
+                "Handles system design, planning, and architectural decisions",
+            ),
+        )
+        .with_default_agent("coder")
+        .initialize()?;
 
     println!("✅ Workspace initialized for Engram team collaboration");
-    println!("📝 Configuration created at: {:?}", config_path);
+    println!("📝 Configuration created at: {:?}", workspace.config_path());
+
+    if let Some(template) = template {
+        let report = workspace.apply_template(&template)?;
+        println!("📦 Applied template '{}'", template.name);
+        for title in &report.workflows {
+            println!("  + workflow: {}", title);
+        }
+        for title in &report.rules {
+            println!("  + rule: {}", title);
+        }
+        if report.validation_updated {
+            println!(
+                "  + validation config: {:?}",
+                ValidationConfig::workspace_path(workspace.root())
+            );
+        }
+        if let Some(name) = &report.quality_gates {
+            println!("  + quality gates: {}", name);
+        }
+        if report.hooks_installed {
+            println!("  + commit-msg hook installed");
+        }
+        for item in &report.skipped {
+            println!("  = already present: {}", item);
+        }
+    }
 
     Ok(())
 }
@@ -278,8 +1031,8 @@ fn copy_dir_recursive(source: &PathBuf, target: &PathBuf) -> Result<(), EngramEr
 /// Workspace setup configuration structure
 #[derive(Debug, Serialize)]
 struct WorkspaceSetup {
-    agents: std::collections::HashMap<String, AgentSetup>,
-    workspaces: std::collections::HashMap<String, WorkspaceEntry>,
+    agents: BTreeMap<String, AgentSetup>,
+    workspaces: BTreeMap<String, WorkspaceEntry>,
 }
 
 /// Agent setup configuration
@@ -330,7 +1083,7 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         let root = temp_dir.path().to_path_buf();
 
-        setup_workspace(Some(root.clone())).unwrap();
+        setup_workspace(Some(root.clone()), None).unwrap();
 
         let engram_dir = root.join(".engram");
         assert!(engram_dir.exists());
@@ -345,6 +1098,167 @@ mod tests {
         assert!(config_content.contains("workspaces:"));
     }
 
+    #[test]
+    fn test_workspace_initializer() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let workspace = WorkspaceInitializer::new(temp_dir.path())
+            .with_agent("test", AgentConfig::default())
+            .initialize()
+            .unwrap();
+
+        assert!(temp_dir.path().join(".engram/config.yaml").exists());
+        assert!(temp_dir.path().join(".git").exists());
+        workspace.verify().unwrap();
+
+        let stats = workspace.storage().get_stats().unwrap();
+        assert!(stats.total_entities > 0);
+    }
+
+    #[test]
+    fn test_workspace_initializer_workflow_and_validation() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut workflow = Workflow::new(
+            "CI".to_string(),
+            "Build and test".to_string(),
+            "test".to_string(),
+        );
+        workflow.initial_state = "build".to_string();
+        fs::write(
+            temp_dir.path().join("ci.yaml"),
+            serde_yaml::to_string(&workflow).unwrap(),
+        )
+        .unwrap();
+
+        let mut workspace = WorkspaceInitializer::new(temp_dir.path())
+            .with_agent("test", AgentConfig::default())
+            .with_validation(ValidationConfig::default())
+            .with_workflow_from_file("ci.yaml")
+            .initialize()
+            .unwrap();
+
+        assert_eq!(workspace.workflow_ids(), [workflow.id.clone()]);
+        assert!(ValidationConfig::workspace_path(temp_dir.path()).exists());
+        workspace.verify().unwrap();
+
+        // The overview context was seeded by initialize, so nothing new
+        assert!(workspace.create_default_contexts().unwrap().is_empty());
+
+        workspace.install_hooks().unwrap();
+        assert!(temp_dir.path().join(".git/hooks/commit-msg").exists());
+    }
+
+    #[test]
+    fn test_workspace_initializer_rejects_missing_workflow() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let result = WorkspaceInitializer::new(temp_dir.path())
+            .with_workflow_from_file("missing.yaml")
+            .initialize();
+
+        assert!(matches!(result, Err(EngramError::Validation(_))));
+        assert!(!temp_dir.path().join(".engram").exists());
+    }
+
+    #[test]
+    fn test_apply_template_is_idempotent() {
+        let temp_dir = TempDir::new().unwrap();
+        let template = WorkspaceTemplate::builtin("strict").unwrap();
+        let mut workspace = WorkspaceInitializer::new(temp_dir.path())
+            .with_agent("test", AgentConfig::default())
+            .initialize()
+            .unwrap();
+
+        let report = workspace.apply_template(&template).unwrap();
+        assert_eq!(report.workflows, ["Feature development"]);
+        assert_eq!(report.rules.len(), 2);
+        assert!(report.validation_updated);
+        assert_eq!(report.quality_gates.as_deref(), Some("strict template"));
+        assert!(report.hooks_installed);
+        workspace.verify().unwrap();
+
+        let validation = ValidationConfig::load_for_workspace(temp_dir.path()).unwrap();
+        assert!(validation.warn_stale_context);
+        assert_eq!(
+            crate::validation::quality_gates::configured_gate_levels(workspace.storage())
+                .unwrap()
+                .len(),
+            1
+        );
+
+        let again = workspace.apply_template(&template).unwrap();
+        assert!(again.workflows.is_empty());
+        assert!(again.rules.is_empty());
+        assert!(!again.validation_updated);
+        assert!(again.quality_gates.is_none());
+        assert!(!again.hooks_installed);
+        assert_eq!(again.skipped.len(), 6);
+        assert_eq!(
+            workspace
+                .storage()
+                .get_all(Workflow::entity_type())
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_template_file_merges_validation() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut workflow = Workflow::new(
+            "Hotfix".to_string(),
+            "Ship urgent fixes".to_string(),
+            "test".to_string(),
+        );
+        workflow.initial_state = "fix".to_string();
+        fs::create_dir(temp_dir.path().join("templates")).unwrap();
+        fs::write(
+            temp_dir.path().join("templates/hotfix.yaml"),
+            serde_yaml::to_string(&workflow).unwrap(),
+        )
+        .unwrap();
+        let validation = ValidationConfig {
+            require_file_scope_match: true,
+            min_reasoning_steps: 2,
+            ..Default::default()
+        };
+        fs::write(
+            temp_dir.path().join("templates/team.yaml"),
+            format!(
+                "workflows:\n  - hotfix.yaml\nvalidation:\n{}",
+                serde_yaml::to_string(&validation)
+                    .unwrap()
+                    .lines()
+                    .map(|line| format!("  {}\n", line))
+                    .collect::<String>()
+            ),
+        )
+        .unwrap();
+
+        let template =
+            WorkspaceTemplate::from_file(&temp_dir.path().join("templates/team.yaml")).unwrap();
+        assert_eq!(template.name, "team");
+
+        let existing = ValidationConfig {
+            require_file_scope_match: false,
+            ..Default::default()
+        };
+        let mut workspace = WorkspaceInitializer::new(temp_dir.path())
+            .with_validation(existing)
+            .initialize()
+            .unwrap();
+        let report = workspace.apply_template(&template).unwrap();
+
+        assert_eq!(report.workflows, ["Hotfix"]);
+        assert!(report.validation_updated);
+        let merged = ValidationConfig::load_for_workspace(temp_dir.path()).unwrap();
+        assert!(merged.require_file_scope_match);
+        assert_eq!(merged.min_reasoning_steps, 2);
+
+        assert!(WorkspaceTemplate::builtin("lenient").is_err());
+    }
+
     #[test]
     fn test_setup_agent() {
         let temp_dir = TempDir::new().unwrap();
@@ -1,6 +1,6 @@
 use crate::error::EngramError;
 use clap::Subcommand;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Skills commands
 #[derive(Debug, Subcommand)]
@@ -36,6 +36,28 @@ pub enum SkillsCommands {
         #[arg(help = "Skill name or path")]
         name: String,
     },
+    /// Install a skill pack from a git URL or local directory
+    Install {
+        /// Git URL or path to a directory containing skill-pack.toml
+        source: String,
+        /// Install under this name instead of the pack's manifest name
+        #[arg(long)]
+        name: Option<String>,
+        /// Skills directory to install into (default: the listed skills path)
+        #[arg(long, short)]
+        dir: Option<String>,
+    },
+    /// Update installed skill packs from their recorded source
+    Update {
+        /// Installed pack to update (default: all installed packs)
+        name: Option<String>,
+        /// Skills directory holding the installed packs
+        #[arg(long, short)]
+        dir: Option<String>,
+        /// Show the diff summary without applying it
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
 
 /// Get skills path from environment or default
@@ -153,6 +175,19 @@ pub fn scan_skills_from_dir(source_dir: &PathBuf) -> Result<Vec<(String, String)
 use crate::cli::utils::{create_table, truncate};
 use prettytable::row;
 
+/// Staging directories and provenance files start with a dot and are not skills
+fn is_hidden(entry: &std::fs::DirEntry) -> bool {
+    entry.file_name().to_string_lossy().starts_with('.')
+}
+
+/// `("installed", version)` for packs with a provenance record, `("bundled", "-")` otherwise
+fn skill_origin(skill_dir: &Path) -> (String, String) {
+    match read_provenance(skill_dir) {
+        Some(provenance) => ("installed".to_string(), provenance.version),
+        None => ("bundled".to_string(), "-".to_string()),
+    }
+}
+
 /// List all skills in skills directory
 pub fn list_skills(
     writer: &mut dyn std::io::Write,
@@ -202,10 +237,14 @@ pub fn list_skills(
 
     match format {
         "short" | "s" => {
-            table.set_titles(row!["Skill Name"]);
+            table.set_titles(row!["Skill Name", "Source", "Version"]);
             for entry in entries.flatten() {
+                if is_hidden(&entry) {
+                    continue;
+                }
                 if entry.path().is_dir() {
-                    table.add_row(row![entry.file_name().to_string_lossy()]);
+                    let (source, version) = skill_origin(&entry.path());
+                    table.add_row(row![entry.file_name().to_string_lossy(), source, version]);
                     found_any = true;
                 } else if verbose {
                     writeln!(
@@ -222,8 +261,11 @@ pub fn list_skills(
             }
         }
         "full" | "f" => {
-            table.set_titles(row!["Skill Name", "Description"]);
+            table.set_titles(row!["Skill Name", "Source", "Version", "Description"]);
             for entry in entries.flatten() {
+                if is_hidden(&entry) {
+                    continue;
+                }
                 if entry.path().is_dir() {
                     let file_name = entry.file_name();
                     let name = file_name.to_string_lossy();
@@ -241,7 +283,13 @@ pub fn list_skills(
                         }
                     };
 
-                    table.add_row(row![truncate(&name, 30), truncate(&description, 50)]);
+                    let (source, version) = skill_origin(&entry.path());
+                    table.add_row(row![
+                        truncate(&name, 30),
+                        source,
+                        version,
+                        truncate(&description, 50)
+                    ]);
                     found_any = true;
                 } else if verbose {
                     writeln!(
@@ -530,6 +578,381 @@ pub fn handle_skills_command(
     Ok(())
 }
 
+/// Manifest every installable skill pack carries at its root
+pub const SKILL_PACK_MANIFEST: &str = "skill-pack.toml";
+
+/// Provenance record written into each installed pack directory
+pub const SKILL_PACK_PROVENANCE: &str = ".engram-pack.json";
+
+/// `skill-pack.toml`: the pack's name, version and the files it ships
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SkillPackManifest {
+    pub name: String,
+    pub version: String,
+    pub files: Vec<String>,
+}
+
+/// Where an installed pack came from, so `skills update` can fetch it again
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SkillPackProvenance {
+    /// Git URL or absolute directory path the pack was installed from
+    pub source: String,
+    /// `git` or `directory`
+    pub kind: String,
+    /// Name declared in the pack manifest (may differ from the install name)
+    pub pack_name: String,
+    pub version: String,
+    /// Commit the pack was cloned at, for git sources
+    pub revision: Option<String>,
+    pub installed_at: chrono::DateTime<chrono::Utc>,
+}
+
+fn read_provenance(skill_dir: &Path) -> Option<SkillPackProvenance> {
+    let content = std::fs::read_to_string(skill_dir.join(SKILL_PACK_PROVENANCE)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn is_git_source(source: &str) -> bool {
+    if Path::new(source).is_dir() {
+        return false;
+    }
+    ["http://", "https://", "ssh://", "git://", "file://", "git@"]
+        .iter()
+        .any(|prefix| source.starts_with(prefix))
+        || source.ends_with(".git")
+}
+
+fn validate_skill_name(name: &str) -> Result<(), EngramError> {
+    let valid = !name.is_empty()
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.');
+    if valid {
+        Ok(())
+    } else {
+        Err(EngramError::Validation(format!(
+            "Invalid skill name '{}': use letters, digits, '-', '_' or '.'",
+            name
+        )))
+    }
+}
+
+/// Read and check a pack's manifest: name and version are set and every
+/// listed file is a relative path that exists inside the pack.
+pub fn validate_skill_pack(pack_root: &Path) -> Result<SkillPackManifest, EngramError> {
+    let manifest_path = pack_root.join(SKILL_PACK_MANIFEST);
+    let content = std::fs::read_to_string(&manifest_path).map_err(|_| {
+        EngramError::Validation(format!(
+            "Skill pack has no {} at {:?}",
+            SKILL_PACK_MANIFEST, pack_root
+        ))
+    })?;
+    let manifest: SkillPackManifest = toml::from_str(&content)
+        .map_err(|e| EngramError::Validation(format!("Invalid {}: {}", SKILL_PACK_MANIFEST, e)))?;
+
+    validate_skill_name(&manifest.name)?;
+    if manifest.version.trim().is_empty() {
+        return Err(EngramError::Validation(format!(
+            "Skill pack '{}' has an empty version",
+            manifest.name
+        )));
+    }
+    if manifest.files.is_empty() {
+        return Err(EngramError::Validation(format!(
+            "Skill pack '{}' lists no files",
+            manifest.name
+        )));
+    }
+    for file in &manifest.files {
+        let relative = Path::new(file);
+        let escapes = relative.is_absolute()
+            || relative
+                .components()
+                .any(|c| !matches!(c, std::path::Component::Normal(_)));
+        if escapes {
+            return Err(EngramError::Validation(format!(
+                "Skill pack '{}' lists '{}' outside the pack",
+                manifest.name, file
+            )));
+        }
+        if !pack_root.join(relative).is_file() {
+            return Err(EngramError::Validation(format!(
+                "Skill pack '{}' lists '{}' but the file is missing",
+                manifest.name, file
+            )));
+        }
+    }
+
+    Ok(manifest)
+}
+
+/// A fetched copy of a pack's source, removed on drop when it was cloned
+struct FetchedPack {
+    root: PathBuf,
+    kind: &'static str,
+    source: String,
+    revision: Option<String>,
+    staging: Option<PathBuf>,
+}
+
+impl Drop for FetchedPack {
+    fn drop(&mut self) {
+        if let Some(staging) = &self.staging {
+            let _ = std::fs::remove_dir_all(staging);
+        }
+    }
+}
+
+fn fetch_skill_pack(source: &str, skills_dir: &Path) -> Result<FetchedPack, EngramError> {
+    if !is_git_source(source) {
+        let root = std::fs::canonicalize(source).map_err(|_| {
+            EngramError::Validation(format!("Skill pack source not found: {}", source))
+        })?;
+        return Ok(FetchedPack {
+            source: root.to_string_lossy().to_string(),
+            root,
+            kind: "directory",
+            revision: None,
+            staging: None,
+        });
+    }
+
+    std::fs::create_dir_all(skills_dir)?;
+    let staging = skills_dir.join(format!(".staging-{}", uuid::Uuid::new_v4()));
+    let mut fetched = FetchedPack {
+        root: staging.clone(),
+        kind: "git",
+        source: source.to_string(),
+        revision: None,
+        staging: Some(staging.clone()),
+    };
+    let repo = git2::Repository::clone(source, &staging)
+        .map_err(|e| EngramError::Git(format!("Failed to clone {}: {}", source, e.message())))?;
+    fetched.revision = repo
+        .head()
+        .ok()
+        .and_then(|head| head.peel_to_commit().ok())
+        .map(|commit| commit.id().to_string());
+    Ok(fetched)
+}
+
+/// Copy the manifest and the files it lists into `target`, then record provenance
+fn write_skill_pack(
+    fetched: &FetchedPack,
+    manifest: &SkillPackManifest,
+    target: &Path,
+) -> Result<SkillPackProvenance, EngramError> {
+    std::fs::create_dir_all(target)?;
+    std::fs::copy(
+        fetched.root.join(SKILL_PACK_MANIFEST),
+        target.join(SKILL_PACK_MANIFEST),
+    )?;
+    for file in &manifest.files {
+        let destination = target.join(file);
+        if let Some(parent) = destination.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::copy(fetched.root.join(file), &destination)?;
+    }
+
+    let provenance = SkillPackProvenance {
+        source: fetched.source.clone(),
+        kind: fetched.kind.to_string(),
+        pack_name: manifest.name.clone(),
+        version: manifest.version.clone(),
+        revision: fetched.revision.clone(),
+        installed_at: chrono::Utc::now(),
+    };
+    std::fs::write(
+        target.join(SKILL_PACK_PROVENANCE),
+        serde_json::to_string_pretty(&provenance)?,
+    )?;
+    Ok(provenance)
+}
+
+/// Install a skill pack from a git URL or directory into `skills_dir`.
+/// Names already present, bundled or installed, are rejected.
+pub fn install_skill_pack(
+    writer: &mut dyn std::io::Write,
+    source: &str,
+    alias: Option<&str>,
+    skills_dir: &Path,
+) -> Result<SkillPackProvenance, EngramError> {
+    writeln!(writer, "📂 Fetching skill pack from: {}", source)?;
+    let fetched = fetch_skill_pack(source, skills_dir)?;
+    let manifest = validate_skill_pack(&fetched.root)?;
+
+    let install_name = alias.unwrap_or(&manifest.name);
+    validate_skill_name(install_name)?;
+    let target = skills_dir.join(install_name);
+    if target.exists() {
+        let existing = if read_provenance(&target).is_some() {
+            "an installed pack"
+        } else {
+            "a bundled skill"
+        };
+        return Err(EngramError::Validation(format!(
+            "Skill '{}' conflicts with {} in {:?}. Install under another name with --name <alias>, \
+             or run 'engram skills update {}' to refresh an installed pack",
+            install_name, existing, skills_dir, install_name
+        )));
+    }
+
+    let provenance = write_skill_pack(&fetched, &manifest, &target)?;
+    writeln!(
+        writer,
+        "✅ Installed skill pack '{}' v{} as '{}' ({} files)",
+        manifest.name,
+        manifest.version,
+        install_name,
+        manifest.files.len()
+    )?;
+    Ok(provenance)
+}
+
+/// Per-file changes between an installed pack and its latest source
+#[derive(Debug, Default, PartialEq)]
+pub struct SkillPackDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    /// (file, lines inserted, lines deleted)
+    pub modified: Vec<(String, usize, usize)>,
+}
+
+impl SkillPackDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+}
+
+fn diff_skill_pack(
+    installed: &Path,
+    installed_files: &[String],
+    latest: &Path,
+    latest_files: &[String],
+) -> SkillPackDiff {
+    use similar::{ChangeTag, TextDiff};
+
+    let mut diff = SkillPackDiff::default();
+    for file in latest_files {
+        if !installed_files.contains(file) {
+            diff.added.push(file.clone());
+            continue;
+        }
+        let old = std::fs::read_to_string(installed.join(file)).unwrap_or_default();
+        let new = std::fs::read_to_string(latest.join(file)).unwrap_or_default();
+        if old == new {
+            continue;
+        }
+        let text_diff = TextDiff::from_lines(&old, &new);
+        let (mut inserted, mut deleted) = (0, 0);
+        for change in text_diff.iter_all_changes() {
+            match change.tag() {
+                ChangeTag::Insert => inserted += 1,
+                ChangeTag::Delete => deleted += 1,
+                ChangeTag::Equal => {}
+            }
+        }
+        diff.modified.push((file.clone(), inserted, deleted));
+    }
+    diff.removed = installed_files
+        .iter()
+        .filter(|file| !latest_files.contains(file))
+        .cloned()
+        .collect();
+    diff
+}
+
+/// Re-fetch installed packs from their recorded source, print a diff summary
+/// and apply it unless `dry_run`. Returns the number of packs updated.
+pub fn update_skill_packs(
+    writer: &mut dyn std::io::Write,
+    name: Option<&str>,
+    skills_dir: &Path,
+    dry_run: bool,
+) -> Result<usize, EngramError> {
+    let mut packs = Vec::new();
+    if let Some(name) = name {
+        let target = skills_dir.join(name);
+        if !target.is_dir() {
+            return Err(EngramError::Validation(format!(
+                "Skill '{}' not found in {:?}",
+                name, skills_dir
+            )));
+        }
+        let provenance = read_provenance(&target).ok_or_else(|| {
+            EngramError::Validation(format!(
+                "Skill '{}' is bundled, not an installed pack; use 'engram skills setup --force' to refresh it",
+                name
+            ))
+        })?;
+        packs.push((name.to_string(), provenance));
+    } else if skills_dir.is_dir() {
+        for entry in std::fs::read_dir(skills_dir)?.flatten() {
+            if is_hidden(&entry) {
+                continue;
+            }
+            if let Some(provenance) = read_provenance(&entry.path()) {
+                packs.push((entry.file_name().to_string_lossy().to_string(), provenance));
+            }
+        }
+        packs.sort_by(|a, b| a.0.cmp(&b.0));
+    }
+
+    if packs.is_empty() {
+        writeln!(writer, "No installed skill packs in {:?}", skills_dir)?;
+        return Ok(0);
+    }
+
+    let mut updated = 0;
+    for (install_name, provenance) in packs {
+        let target = skills_dir.join(&install_name);
+        let fetched = fetch_skill_pack(&provenance.source, skills_dir)?;
+        let manifest = validate_skill_pack(&fetched.root)?;
+        let installed_files = validate_skill_pack(&target)
+            .map(|m| m.files)
+            .unwrap_or_default();
+        let diff = diff_skill_pack(&target, &installed_files, &fetched.root, &manifest.files);
+
+        if diff.is_empty() && manifest.version == provenance.version {
+            writeln!(
+                writer,
+                "✅ Skill pack '{}' is up to date (v{})",
+                install_name, provenance.version
+            )?;
+            continue;
+        }
+
+        writeln!(
+            writer,
+            "📝 Skill pack '{}': v{} → v{}",
+            install_name, provenance.version, manifest.version
+        )?;
+        for file in &diff.added {
+            writeln!(writer, "   + {}", file)?;
+        }
+        for (file, inserted, deleted) in &diff.modified {
+            writeln!(writer, "   ~ {} (+{} -{})", file, inserted, deleted)?;
+        }
+        for file in &diff.removed {
+            writeln!(writer, "   - {}", file)?;
+        }
+
+        if dry_run {
+            writeln!(writer, "   (dry run, not applied)")?;
+            continue;
+        }
+
+        std::fs::remove_dir_all(&target)?;
+        write_skill_pack(&fetched, &manifest, &target)?;
+        writeln!(writer, "🔄 Updated skill pack: {}", install_name)?;
+        updated += 1;
+    }
+
+    Ok(updated)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1374,4 +1797,124 @@ mod tests {
         assert!(output.contains("Found 3 skills"));
         assert!(output.contains("Installed: 3"));
     }
+
+    fn write_pack(dir: &Path, name: &str, version: &str, skill: &str) {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(
+            dir.join(SKILL_PACK_MANIFEST),
+            format!(
+                "name = \"{}\"\nversion = \"{}\"\nfiles = [\"SKILL.md\"]\n",
+                name, version
+            ),
+        )
+        .unwrap();
+        fs::write(dir.join("SKILL.md"), skill).unwrap();
+    }
+
+    #[test]
+    fn test_install_skill_pack_from_directory() {
+        let temp = TempDir::new().unwrap();
+        let pack = temp.path().join("pack");
+        let skills_dir = temp.path().join("skills");
+        write_pack(&pack, "team-review", "1.0.0", "# Review\n");
+
+        let mut buf = Vec::new();
+        let provenance =
+            install_skill_pack(&mut buf, pack.to_str().unwrap(), None, &skills_dir).unwrap();
+
+        assert_eq!(provenance.kind, "directory");
+        assert_eq!(provenance.version, "1.0.0");
+        assert!(skills_dir.join("team-review/SKILL.md").exists());
+        assert_eq!(
+            read_provenance(&skills_dir.join("team-review")),
+            Some(provenance)
+        );
+    }
+
+    #[test]
+    fn test_install_skill_pack_rejects_name_conflict() {
+        let temp = TempDir::new().unwrap();
+        let pack = temp.path().join("pack");
+        let skills_dir = temp.path().join("skills");
+        write_pack(&pack, "engram-testing", "1.0.0", "# Testing\n");
+        fs::create_dir_all(skills_dir.join("engram-testing")).unwrap();
+
+        let mut buf = Vec::new();
+        let err =
+            install_skill_pack(&mut buf, pack.to_str().unwrap(), None, &skills_dir).unwrap_err();
+        assert!(err.to_string().contains("--name"));
+
+        install_skill_pack(
+            &mut buf,
+            pack.to_str().unwrap(),
+            Some("team-testing"),
+            &skills_dir,
+        )
+        .unwrap();
+        assert!(skills_dir.join("team-testing/SKILL.md").exists());
+    }
+
+    #[test]
+    fn test_validate_skill_pack_rejects_bad_manifests() {
+        let temp = TempDir::new().unwrap();
+        let pack = temp.path().join("pack");
+        fs::create_dir_all(&pack).unwrap();
+        assert!(validate_skill_pack(&pack).is_err());
+
+        fs::write(
+            pack.join(SKILL_PACK_MANIFEST),
+            "name = \"p\"\nversion = \"1\"\nfiles = [\"../escape.md\"]\n",
+        )
+        .unwrap();
+        assert!(validate_skill_pack(&pack).is_err());
+
+        fs::write(
+            pack.join(SKILL_PACK_MANIFEST),
+            "name = \"p\"\nversion = \"1\"\nfiles = [\"missing.md\"]\n",
+        )
+        .unwrap();
+        assert!(validate_skill_pack(&pack).is_err());
+    }
+
+    #[test]
+    fn test_update_skill_packs_shows_diff_and_applies() {
+        let temp = TempDir::new().unwrap();
+        let pack = temp.path().join("pack");
+        let skills_dir = temp.path().join("skills");
+        write_pack(&pack, "team-review", "1.0.0", "# Review\n");
+        install_skill_pack(&mut Vec::new(), pack.to_str().unwrap(), None, &skills_dir).unwrap();
+
+        write_pack(&pack, "team-review", "1.1.0", "# Review\nCheck tests.\n");
+
+        let mut buf = Vec::new();
+        let updated = update_skill_packs(&mut buf, None, &skills_dir, true).unwrap();
+        let output = String::from_utf8(buf).unwrap();
+        assert_eq!(updated, 0);
+        assert!(output.contains("v1.0.0 → v1.1.0"));
+        assert!(output.contains("~ SKILL.md (+1 -0)"));
+
+        let updated =
+            update_skill_packs(&mut Vec::new(), Some("team-review"), &skills_dir, false).unwrap();
+        assert_eq!(updated, 1);
+        let content = fs::read_to_string(skills_dir.join("team-review/SKILL.md")).unwrap();
+        assert!(content.contains("Check tests."));
+    }
+
+    #[test]
+    fn test_list_skills_distinguishes_bundled_and_installed() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().to_path_buf();
+        let skills_dir = root.join("engram/skills");
+        let pack = temp.path().join("pack");
+        fs::create_dir_all(skills_dir.join("engram-bundled")).unwrap();
+        write_pack(&pack, "team-review", "2.3.0", "# Review\n");
+        install_skill_pack(&mut Vec::new(), pack.to_str().unwrap(), None, &skills_dir).unwrap();
+
+        let mut buf = Vec::new();
+        list_skills(&mut buf, "short", false, Some(root)).unwrap();
+        let output = String::from_utf8(buf).unwrap();
+        assert!(output.contains("bundled"));
+        assert!(output.contains("installed"));
+        assert!(output.contains("2.3.0"));
+    }
 }
@@ -1,8 +1,11 @@
-use crate::entities::{Entity, Standard, StandardCategory, StandardRequirement, StandardStatus};
+use crate::entities::{
+    Compliance, Entity, Standard, StandardCategory, StandardRequirement, StandardStatus,
+};
 use crate::error::EngramError;
-use crate::storage::Storage;
+use crate::storage::{RelationshipStorage, Storage};
 use chrono::Utc;
 use clap::Subcommand;
+use serde::Serialize;
 
 /// Standard commands
 #[derive(Debug, Subcommand)]
@@ -104,6 +107,12 @@ pub enum StandardCommands {
         /// Show all results (no limit)
         #[arg(long, conflicts_with = "limit")]
         all: bool,
+
+        #[command(flatten)]
+        sort: SortArgs,
+
+        #[command(flatten)]
+        count: CountArgs,
     },
     /// Add requirement to standard
     AddRequirement {
@@ -131,8 +140,37 @@ pub enum StandardCommands {
         #[arg(long, action)]
         evidence_required: bool,
     },
+    /// Check a standard's requirements against compliance evidence
+    Check {
+        /// Standard ID
+        #[arg(help = "Standard ID to check")]
+        id: String,
+
+        /// Output format
+        #[arg(long, default_value = "table", value_parser = ["table", "json"])]
+        format: String,
+
+        /// Exit with code 2 if an evidence-required requirement has no evidence
+        #[arg(long)]
+        fail_on_missing: bool,
+    },
+    /// List compliance items and tasks last checked against an older
+    /// version of a standard
+    Recertify {
+        /// Standard ID
+        #[arg(help = "Standard ID to re-certify against")]
+        id: String,
+
+        /// Output format
+        #[arg(long, default_value = "table", value_parser = ["table", "json"])]
+        format: String,
+    },
 }
 
+/// Metadata key on the compliance record written by `engram standard check`,
+/// holding the ID of the standard it records
+pub const STANDARD_CHECK_KEY: &str = "standard_check";
+
 /// Create a new standard
 pub fn create_standard<S: Storage>(
     storage: &mut S,
@@ -189,7 +227,7 @@ pub fn get_standard<S: Storage>(storage: &S, id: &str) -> Result<(), EngramError
             Standard::from_generic(generic).map_err(|e| EngramError::Validation(e.to_string()))?;
         display_standard(&standard);
     } else {
-        println!("❌ Standard not found: {}", id);
+        return Err(EngramError::not_found("standard", id));
     }
     Ok(())
 }
@@ -211,6 +249,7 @@ pub fn update_standard<S: Storage>(
             Standard::from_generic(generic).map_err(|e| EngramError::Validation(e.to_string()))?;
 
         let mut updated = false;
+        let previous_version = standard.version.clone();
 
         if let Some(title) = title {
             standard.title = title;
@@ -288,12 +327,42 @@ pub fn update_standard<S: Storage>(
         storage.store(&updated_generic)?;
 
         println!("✅ Standard updated: {}", id);
+        if standard.version != previous_version {
+            let stale = mark_compliance_stale(storage, &standard.id, &standard.version)?;
+            if !stale.is_empty() {
+                println!(
+                    "⚠️  {} compliance item(s) need re-certification against version {}",
+                    stale.len(),
+                    standard.version
+                );
+            }
+        }
     } else {
-        println!("❌ Standard not found: {}", id);
+        return Err(EngramError::not_found("standard", id));
     }
     Ok(())
 }
 
+/// Mark compliance items checked against another version of `standard_id`
+/// as stale, returning their IDs
+pub fn mark_compliance_stale<S: Storage>(
+    storage: &mut S,
+    standard_id: &str,
+    version: &str,
+) -> Result<Vec<String>, EngramError> {
+    let mut marked = Vec::new();
+    for generic in storage.get_all(Compliance::entity_type())? {
+        let Ok(mut compliance) = Compliance::from_generic(generic) else {
+            continue;
+        };
+        if compliance.mark_stale_for(standard_id, version) {
+            storage.store(&compliance.to_generic())?;
+            marked.push(compliance.id);
+        }
+    }
+    Ok(marked)
+}
+
 /// Delete standard
 pub fn delete_standard<S: Storage>(storage: &mut S, id: &str) -> Result<(), EngramError> {
     if let Some(generic) = storage.get(id, "standard")? {
@@ -304,15 +373,16 @@ pub fn delete_standard<S: Storage>(storage: &mut S, id: &str) -> Result<(), Engr
         storage.store(&updated_generic)?;
         println!("✅ Standard deleted (deprecated): {}", id);
     } else {
-        println!("❌ Standard not found: {}", id);
+        return Err(EngramError::not_found("standard", id));
     }
     Ok(())
 }
 
-use crate::cli::utils::{create_table, truncate};
+use crate::cli::utils::{create_table, truncate, CountArgs, SortArgs};
 use prettytable::row;
 
 /// List standards
+#[allow(clippy::too_many_arguments)]
 pub fn list_standards<S: Storage>(
     writer: &mut dyn std::io::Write,
     storage: &S,
@@ -322,6 +392,8 @@ pub fn list_standards<S: Storage>(
     limit: usize,
     offset: usize,
     all: bool,
+    sort: &SortArgs,
+    count: &CountArgs,
 ) -> Result<(), EngramError> {
     use crate::storage::QueryFilter;
     use serde_json::Value;
@@ -336,6 +408,7 @@ pub fn list_standards<S: Storage>(
         offset: Some(offset),
         ..Default::default()
     };
+    sort.apply(&mut filter);
 
     let mut field_filters = HashMap::new();
 
@@ -351,6 +424,10 @@ pub fn list_standards<S: Storage>(
         filter.field_filters = field_filters;
     }
 
+    if count.write(writer, storage, &filter)? {
+        return Ok(());
+    }
+
     let result = storage.query(&filter)?;
 
     if result.entities.is_empty() {
@@ -495,8 +572,316 @@ pub fn add_requirement<S: Storage>(
 
         println!("✅ Requirement added to standard: {}", id);
     } else {
-        println!("❌ Standard not found: {}", id);
+        return Err(EngramError::not_found("standard", id));
+    }
+    Ok(())
+}
+
+/// Outcome of checking one standard requirement
+#[derive(Debug, Clone, Serialize)]
+pub struct RequirementCheck {
+    pub requirement_id: String,
+    pub title: String,
+    pub mandatory: bool,
+    pub evidence_required: bool,
+    /// `kind:reference` of each supporting evidence entry
+    pub evidence: Vec<String>,
+    pub satisfied: bool,
+}
+
+/// Result of `engram standard check`
+#[derive(Debug, Clone, Serialize)]
+pub struct StandardCheckReport {
+    pub standard_id: String,
+    pub title: String,
+    pub version: String,
+    pub requirements: Vec<RequirementCheck>,
+    /// Compliance items that track the standard or supplied evidence
+    pub compliance_ids: Vec<String>,
+}
+
+impl StandardCheckReport {
+    /// Requirements that need evidence and have none
+    pub fn missing_evidence(&self) -> impl Iterator<Item = &RequirementCheck> {
+        self.requirements.iter().filter(|r| !r.satisfied)
+    }
+}
+
+/// Judge each requirement of a standard against the evidence on compliance
+/// items: an `evidence_required` requirement is satisfied once any item
+/// carries evidence for it (see [`Compliance::evidence_for`])
+pub fn check_standard<S: Storage>(
+    storage: &S,
+    id: &str,
+) -> Result<StandardCheckReport, EngramError> {
+    let generic = storage
+        .get(id, "standard")?
+        .ok_or_else(|| EngramError::not_found("standard", id))?;
+    let standard =
+        Standard::from_generic(generic).map_err(|e| EngramError::Validation(e.to_string()))?;
+    let compliance: Vec<Compliance> = storage
+        .get_all(Compliance::entity_type())?
+        .into_iter()
+        .filter_map(|e| Compliance::from_generic(e).ok())
+        .collect();
+
+    let requirements: Vec<RequirementCheck> = standard
+        .requirements
+        .iter()
+        .map(|requirement| {
+            let evidence: Vec<String> = compliance
+                .iter()
+                .flat_map(|item| item.evidence_for(&standard.id, &requirement.id))
+                .map(|e| format!("{}:{}", e.kind, e.reference))
+                .collect();
+            RequirementCheck {
+                requirement_id: requirement.id.clone(),
+                title: requirement.title.clone(),
+                mandatory: requirement.mandatory,
+                evidence_required: requirement.evidence_required,
+                satisfied: !requirement.evidence_required || !evidence.is_empty(),
+                evidence,
+            }
+        })
+        .collect();
+
+    let compliance_ids = compliance
+        .iter()
+        .filter(|item| {
+            item.tracks_standard(&standard.id)
+                || standard
+                    .requirements
+                    .iter()
+                    .any(|r| !item.evidence_for(&standard.id, &r.id).is_empty())
+        })
+        .map(|item| item.id.clone())
+        .collect();
+
+    Ok(StandardCheckReport {
+        standard_id: standard.id,
+        title: standard.title,
+        version: standard.version,
+        requirements,
+        compliance_ids,
+    })
+}
+
+/// Store the outcome of a check: the standard's compliance record (created
+/// on first check) is marked compliant or non-compliant, and it and every
+/// item in the report are stamped with the checked version. Returns the
+/// record's ID.
+pub fn record_standard_check<S: Storage>(
+    storage: &mut S,
+    report: &StandardCheckReport,
+) -> Result<String, EngramError> {
+    let existing = storage
+        .get_all(Compliance::entity_type())?
+        .into_iter()
+        .filter_map(|e| Compliance::from_generic(e).ok())
+        .find(|c| {
+            c.metadata.get(STANDARD_CHECK_KEY).and_then(|v| v.as_str())
+                == Some(report.standard_id.as_str())
+        });
+    let mut record = existing.unwrap_or_else(|| {
+        let mut record = Compliance::new(
+            format!("Standard check: {}", report.title),
+            format!("Result of `engram standard check {}`", report.standard_id),
+            "standard-check".to_string(),
+            crate::cli::identity::current_agent(),
+        );
+        record.add_related_standard(report.standard_id.clone());
+        record.metadata.insert(
+            STANDARD_CHECK_KEY.to_string(),
+            serde_json::Value::String(report.standard_id.clone()),
+        );
+        record
+    });
+
+    if report.missing_evidence().next().is_none() {
+        record.mark_compliant();
+    } else {
+        record.status = crate::entities::ComplianceStatus::NonCompliant;
+    }
+    record.record_standard_check(&report.standard_id, &report.version);
+    storage.store(&record.to_generic())?;
+
+    for id in &report.compliance_ids {
+        if *id == record.id {
+            continue;
+        }
+        let Some(generic) = storage.get(id, Compliance::entity_type())? else {
+            continue;
+        };
+        let mut item = Compliance::from_generic(generic)
+            .map_err(|e| EngramError::Validation(e.to_string()))?;
+        item.record_standard_check(&report.standard_id, &report.version);
+        storage.store(&item.to_generic())?;
+    }
+    Ok(record.id)
+}
+
+/// Handle `engram standard check`
+pub fn run_standard_check<S: Storage>(
+    storage: &mut S,
+    id: &str,
+    format: &str,
+    fail_on_missing: bool,
+) -> Result<(), EngramError> {
+    let report = check_standard(storage, id)?;
+    let missing = report.missing_evidence().count();
+    let record_id = record_standard_check(storage, &report)?;
+
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!("📋 Standard: {} ({})", report.title, report.standard_id);
+        for requirement in &report.requirements {
+            let status = if !requirement.satisfied {
+                "❌ missing evidence"
+            } else if requirement.evidence_required {
+                "✅ evidenced"
+            } else {
+                "➖ no evidence required"
+            };
+            println!(
+                "  {} {}{}",
+                status,
+                requirement.title,
+                if requirement.mandatory {
+                    " (mandatory)"
+                } else {
+                    ""
+                }
+            );
+            for evidence in &requirement.evidence {
+                println!("      {}", evidence);
+            }
+        }
+        if missing == 0 {
+            println!("✅ All evidence requirements met");
+        } else {
+            println!("❌ {} requirement(s) missing evidence", missing);
+        }
+        println!(
+            "🔏 Recorded check against version {} as {}",
+            report.version, record_id
+        );
+    }
+
+    if fail_on_missing && missing > 0 {
+        std::process::exit(2);
+    }
+    Ok(())
+}
+
+/// A compliance item needing re-certification against a standard
+#[derive(Debug, Clone, Serialize)]
+pub struct RecertificationItem {
+    pub compliance_id: String,
+    pub title: String,
+    pub checked_version: String,
+    pub note: Option<String>,
+    /// Tasks related to the compliance item
+    pub tasks: Vec<String>,
+}
+
+/// Result of `engram standard recertify`
+#[derive(Debug, Clone, Serialize)]
+pub struct RecertificationReport {
+    pub standard_id: String,
+    pub title: String,
+    pub version: String,
+    pub items: Vec<RecertificationItem>,
+}
+
+/// Compliance items last checked against a version of the standard other
+/// than its current one, with the tasks related to each
+pub fn standard_recertification<S: Storage + RelationshipStorage>(
+    storage: &S,
+    id: &str,
+) -> Result<RecertificationReport, EngramError> {
+    let generic = storage
+        .get(id, "standard")?
+        .ok_or_else(|| EngramError::not_found("standard", id))?;
+    let standard =
+        Standard::from_generic(generic).map_err(|e| EngramError::Validation(e.to_string()))?;
+
+    let mut items = Vec::new();
+    for generic in storage.get_all(Compliance::entity_type())? {
+        let Ok(compliance) = Compliance::from_generic(generic) else {
+            continue;
+        };
+        let Some(checked) = compliance.standard_versions.get(&standard.id) else {
+            continue;
+        };
+        if *checked == standard.version {
+            continue;
+        }
+        let tasks = storage
+            .get_entity_relationships(&compliance.id)?
+            .into_iter()
+            .filter_map(|r| {
+                if r.source_id == compliance.id && r.target_type == "task" {
+                    Some(r.target_id)
+                } else if r.target_id == compliance.id && r.source_type == "task" {
+                    Some(r.source_id)
+                } else {
+                    None
+                }
+            })
+            .collect();
+        items.push(RecertificationItem {
+            note: compliance.stale_note(&standard.id).map(str::to_string),
+            checked_version: checked.clone(),
+            compliance_id: compliance.id,
+            title: compliance.title,
+            tasks,
+        });
+    }
+    items.sort_by(|a, b| a.compliance_id.cmp(&b.compliance_id));
+
+    Ok(RecertificationReport {
+        standard_id: standard.id,
+        title: standard.title,
+        version: standard.version,
+        items,
+    })
+}
+
+/// Handle `engram standard recertify`
+pub fn run_standard_recertify<S: Storage + RelationshipStorage>(
+    storage: &S,
+    id: &str,
+    format: &str,
+) -> Result<(), EngramError> {
+    let report = standard_recertification(storage, id)?;
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    println!(
+        "📋 Standard: {} ({}) at version {}",
+        report.title, report.standard_id, report.version
+    );
+    if report.items.is_empty() {
+        println!("✅ Nothing needs re-certification");
+        return Ok(());
     }
+    println!("🔁 {} item(s) need re-certification:", report.items.len());
+    for item in &report.items {
+        println!(
+            "  {} {} (checked against {})",
+            item.compliance_id, item.title, item.checked_version
+        );
+        for task in &item.tasks {
+            println!("      task {}", task);
+        }
+    }
+    println!(
+        "Run `engram standard check {}` once they are re-certified",
+        report.standard_id
+    );
     Ok(())
 }
 
@@ -613,7 +998,7 @@ mod tests {
         assert!(result.is_ok());
 
         let result = get_standard(&storage, "non-existent");
-        assert!(result.is_ok()); // Should just print error message
+        assert!(result.is_err_and(|e| matches!(e, EngramError::NotFound { .. })));
     }
 
     #[test]
@@ -711,7 +1096,18 @@ mod tests {
 
         // List all
         let mut buffer = Vec::new();
-        let result = list_standards(&mut buffer, &storage, None, None, None, 10, 0, false);
+        let result = list_standards(
+            &mut buffer,
+            &storage,
+            None,
+            None,
+            None,
+            10,
+            0,
+            false,
+            &SortArgs::default(),
+            &CountArgs::default(),
+        );
         assert!(result.is_ok());
 
         // Filter by category
@@ -725,6 +1121,8 @@ mod tests {
             10,
             0,
             false,
+            &SortArgs::default(),
+            &CountArgs::default(),
         );
         assert!(result.is_ok());
     }
@@ -837,14 +1235,14 @@ mod tests {
             None,
             None,
         );
-        assert!(result.is_ok());
+        assert!(result.is_err_and(|e| matches!(e, EngramError::NotFound { .. })));
     }
 
     #[test]
     fn test_delete_standard_not_found() {
         let mut storage = MemoryStorage::new("test-agent");
         let result = delete_standard(&mut storage, "non-existent");
-        assert!(result.is_ok());
+        assert!(result.is_err_and(|e| matches!(e, EngramError::NotFound { .. })));
     }
 
     #[test]
@@ -859,7 +1257,7 @@ mod tests {
             "high".to_string(),
             false,
         );
-        assert!(result.is_ok());
+        assert!(result.is_err_and(|e| matches!(e, EngramError::NotFound { .. })));
     }
 
     #[test]
@@ -895,4 +1293,162 @@ mod tests {
         let standard = Standard::from_generic(generic).unwrap();
         assert!(standard.requirements.is_empty());
     }
+
+    #[test]
+    fn test_check_standard_uses_compliance_evidence() {
+        use crate::entities::{ComplianceEvidence, EvidenceKind};
+
+        let mut storage = MemoryStorage::new("test-agent");
+        create_standard(
+            &mut storage,
+            "Release".to_string(),
+            None,
+            "process".to_string(),
+            "1.0".to_string(),
+            None,
+            None,
+        )
+        .unwrap();
+        let query_result = storage.query_by_type("standard", None, None, None).unwrap();
+        let id = query_result.entities[0].id.clone();
+        for (title, evidence_required) in [("Signed off", true), ("Changelog", false)] {
+            add_requirement(
+                &mut storage,
+                &id,
+                title.to_string(),
+                "Desc".to_string(),
+                true,
+                "high".to_string(),
+                evidence_required,
+            )
+            .unwrap();
+        }
+
+        let report = check_standard(&storage, &id).unwrap();
+        assert_eq!(report.missing_evidence().count(), 1);
+        assert!(report.requirements[1].satisfied);
+
+        let mut compliance = Compliance::new(
+            "Release audit".to_string(),
+            "Desc".to_string(),
+            "process".to_string(),
+            "test-agent".to_string(),
+        );
+        let mut evidence = ComplianceEvidence::new(
+            EvidenceKind::Url,
+            "https://reviews/42".to_string(),
+            "test-agent".to_string(),
+        );
+        evidence.requirement_id = Some(report.requirements[0].requirement_id.clone());
+        compliance.add_evidence(evidence);
+        storage.store(&compliance.to_generic()).unwrap();
+
+        let report = check_standard(&storage, &id).unwrap();
+        assert_eq!(report.missing_evidence().count(), 0);
+        assert_eq!(
+            report.requirements[0].evidence,
+            vec!["url:https://reviews/42"]
+        );
+        assert!(check_standard(&storage, "missing").is_err());
+    }
+
+    #[test]
+    fn test_version_bump_marks_checked_compliance_stale() {
+        use crate::entities::{EntityRelationType, EntityRelationship};
+
+        let mut storage = MemoryStorage::new("test-agent");
+        create_standard(
+            &mut storage,
+            "Release".to_string(),
+            None,
+            "process".to_string(),
+            "1.0".to_string(),
+            None,
+            None,
+        )
+        .unwrap();
+        let id = storage.get_all("standard").unwrap()[0].id.clone();
+
+        let mut audit = Compliance::new(
+            "Release audit".to_string(),
+            "Desc".to_string(),
+            "process".to_string(),
+            "test-agent".to_string(),
+        );
+        audit.add_related_standard(id.clone());
+        storage.store(&audit.to_generic()).unwrap();
+        let untracked = Compliance::new(
+            "Unrelated".to_string(),
+            "Desc".to_string(),
+            "process".to_string(),
+            "test-agent".to_string(),
+        );
+        storage.store(&untracked.to_generic()).unwrap();
+        storage
+            .store_relationship(&EntityRelationship::new(
+                "rel-1".to_string(),
+                "test-agent".to_string(),
+                "task-1".to_string(),
+                "task".to_string(),
+                audit.id.clone(),
+                "compliance".to_string(),
+                EntityRelationType::Implements,
+            ))
+            .unwrap();
+
+        run_standard_check(&mut storage, &id, "json", false).unwrap();
+        let compliance = |storage: &MemoryStorage, id: &str| {
+            Compliance::from_generic(storage.get(id, "compliance").unwrap().unwrap()).unwrap()
+        };
+        assert_eq!(
+            compliance(&storage, &audit.id).standard_versions[&id],
+            "1.0"
+        );
+        assert!(compliance(&storage, &untracked.id)
+            .standard_versions
+            .is_empty());
+        assert_eq!(storage.get_all("compliance").unwrap().len(), 3);
+        assert!(standard_recertification(&storage, &id)
+            .unwrap()
+            .items
+            .is_empty());
+
+        update_standard(
+            &mut storage,
+            &id,
+            None,
+            None,
+            None,
+            Some("2.0".to_string()),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        let stale_audit = compliance(&storage, &audit.id);
+        assert!(stale_audit.stale);
+        assert!(stale_audit.stale_note(&id).unwrap().contains("2.0"));
+        assert!(!compliance(&storage, &untracked.id).stale);
+
+        let report = standard_recertification(&storage, &id).unwrap();
+        assert_eq!(report.version, "2.0");
+        // The audit plus the check record itself
+        assert_eq!(report.items.len(), 2);
+        let item = report
+            .items
+            .iter()
+            .find(|i| i.compliance_id == audit.id)
+            .unwrap();
+        assert_eq!(item.checked_version, "1.0");
+        assert_eq!(item.tasks, vec!["task-1"]);
+
+        run_standard_check(&mut storage, &id, "json", false).unwrap();
+        assert!(!compliance(&storage, &audit.id).stale);
+        assert_eq!(storage.get_all("compliance").unwrap().len(), 3);
+        assert!(standard_recertification(&storage, &id)
+            .unwrap()
+            .items
+            .is_empty());
+    }
 }
@@ -1,5 +1,6 @@
 //! State Reflection command implementations (Naur, 1985 - Cognitive Dissonance Detection)
 
+use crate::cli::identity::resolve_agent;
 use crate::entities::{Entity, StateReflection, TriggerType};
 use crate::error::EngramError;
 use crate::storage::Storage;
@@ -84,6 +85,9 @@ pub enum StateReflectionCommands {
         /// Offset for pagination
         #[arg(long, short)]
         offset: Option<usize>,
+
+        #[command(flatten)]
+        sort: SortArgs,
     },
     /// Show state reflection details
     Show {
@@ -194,7 +198,7 @@ pub fn create_reflection<S: Storage>(
         let input: StateReflectionInput = serde_json::from_str(&json_str)
             .map_err(|e| EngramError::Validation(format!("Invalid JSON: {}", e)))?;
 
-        let agent_name = input.agent.unwrap_or_else(|| "default".to_string());
+        let agent_name = resolve_agent(input.agent);
         let mut reflection = StateReflection::new(
             input.theory_id,
             input.trigger_context_id,
@@ -226,7 +230,7 @@ pub fn create_reflection<S: Storage>(
             .ok_or_else(|| EngramError::Validation("Context ID is required".to_string()))?;
         let observed = observed_state
             .ok_or_else(|| EngramError::Validation("Observed state is required".to_string()))?;
-        let agent_name = agent.unwrap_or_else(|| "default".to_string());
+        let agent_name = resolve_agent(agent);
 
         let mut reflection = StateReflection::new(theory, context, observed, agent_name);
 
@@ -249,10 +253,11 @@ pub fn create_reflection<S: Storage>(
     Ok(())
 }
 
-use crate::cli::utils::{create_table, truncate};
+use crate::cli::utils::{create_table, truncate, SortArgs};
 use prettytable::row;
 
 /// List state reflections
+#[allow(clippy::too_many_arguments)]
 pub fn list_reflections<S: Storage>(
     storage: &S,
     theory_id: Option<String>,
@@ -261,6 +266,7 @@ pub fn list_reflections<S: Storage>(
     limit: Option<usize>,
     all: bool,
     offset: Option<usize>,
+    sort: &SortArgs,
 ) -> Result<(), EngramError> {
     let ids = storage.list_ids(StateReflection::entity_type())?;
 
@@ -295,6 +301,7 @@ pub fn list_reflections<S: Storage>(
         }
     }
 
+    items = sort.sort(items)?;
     let total_count = items.len();
 
     if let Some(off) = offset {
@@ -363,8 +370,9 @@ pub fn list_reflections<S: Storage>(
 pub fn show_reflection<S: Storage>(storage: &S, id: &str) -> Result<(), EngramError> {
     let entity = storage
         .get(id, StateReflection::entity_type())?
-        .ok_or_else(|| EngramError::NotFound(format!("State reflection not found: {}", id)))?;
+        .ok_or_else(|| EngramError::not_found("state_reflection", id))?;
 
+    let modification = entity.modification();
     let reflection = StateReflection::from_generic(entity)
         .map_err(|e: EngramError| EngramError::Validation(e.to_string()))?;
 
@@ -374,6 +382,9 @@ pub fn show_reflection<S: Storage>(storage: &S, id: &str) -> Result<(), EngramEr
     println!("Theory ID: {}", reflection.theory_id);
     println!("Trigger Context ID: {}", reflection.trigger_context_id);
     println!("Agent: {}", reflection.agent);
+    if let Some(modified) = modification.summary() {
+        println!("Last Modified By: {}", modified);
+    }
     println!("Timestamp: {}", reflection.timestamp);
     println!("Severity: {}", reflection.severity());
     println!("Dissonance Score: {:.2}", reflection.dissonance_score);
@@ -418,7 +429,7 @@ pub fn record_dissonance<S: Storage>(
 ) -> Result<(), EngramError> {
     let entity = storage
         .get(id, StateReflection::entity_type())?
-        .ok_or_else(|| EngramError::NotFound(format!("State reflection not found: {}", id)))?;
+        .ok_or_else(|| EngramError::not_found("state_reflection", id))?;
 
     let mut reflection = StateReflection::from_generic(entity)
         .map_err(|e: EngramError| EngramError::Validation(e.to_string()))?;
@@ -445,7 +456,7 @@ pub fn propose_update<S: Storage>(
 ) -> Result<(), EngramError> {
     let entity = storage
         .get(id, StateReflection::entity_type())?
-        .ok_or_else(|| EngramError::NotFound(format!("State reflection not found: {}", id)))?;
+        .ok_or_else(|| EngramError::not_found("state_reflection", id))?;
 
     let mut reflection = StateReflection::from_generic(entity)
         .map_err(|e: EngramError| EngramError::Validation(e.to_string()))?;
@@ -467,7 +478,7 @@ pub fn resolve_reflection<S: Storage>(
 ) -> Result<(), EngramError> {
     let entity = storage
         .get(id, StateReflection::entity_type())?
-        .ok_or_else(|| EngramError::NotFound(format!("State reflection not found: {}", id)))?;
+        .ok_or_else(|| EngramError::not_found("state_reflection", id))?;
 
     let mut reflection = StateReflection::from_generic(entity)
         .map_err(|e: EngramError| EngramError::Validation(e.to_string()))?;
@@ -496,7 +507,7 @@ pub fn requires_mutation<S: Storage>(
 ) -> Result<(), EngramError> {
     let entity = storage
         .get(id, StateReflection::entity_type())?
-        .ok_or_else(|| EngramError::NotFound(format!("State reflection not found: {}", id)))?;
+        .ok_or_else(|| EngramError::not_found("state_reflection", id))?;
 
     let reflection = StateReflection::from_generic(entity)
         .map_err(|e: EngramError| EngramError::Validation(e.to_string()))?;
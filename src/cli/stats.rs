@@ -0,0 +1,308 @@
+//! Workspace statistics command implementations
+
+use crate::cli::utils::{create_table, truncate};
+use crate::error::EngramError;
+use crate::storage::{collect_entities, GitRefsStorage, Storage};
+use chrono::{DateTime, Duration, Utc};
+use clap::Subcommand;
+use prettytable::row;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+/// Growth periods reported, in days
+pub const GROWTH_PERIODS_DAYS: [i64; 3] = [7, 30, 90];
+
+/// Statistics commands
+#[derive(Debug, Subcommand)]
+pub enum StatsCommands {
+    /// Entity counts, growth over time, largest entities and agent volumes
+    ///
+    ///EXAMPLES:
+    ///  engram stats entities
+    ///  engram stats entities --top 5 --format json
+    Entities {
+        /// Number of largest entities to list
+        #[arg(long, default_value_t = 20)]
+        top: usize,
+
+        /// Output format
+        #[arg(long, default_value = "table", value_parser = ["table", "json"])]
+        format: String,
+    },
+}
+
+/// Entities created within each growth period
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct GrowthCounts {
+    pub total: usize,
+    pub last_7_days: usize,
+    pub last_30_days: usize,
+    pub last_90_days: usize,
+}
+
+impl GrowthCounts {
+    fn record(&mut self, age: Duration) {
+        self.total += 1;
+        for (days, count) in GROWTH_PERIODS_DAYS.iter().zip([
+            &mut self.last_7_days,
+            &mut self.last_30_days,
+            &mut self.last_90_days,
+        ]) {
+            if age <= Duration::days(*days) {
+                *count += 1;
+            }
+        }
+    }
+}
+
+/// Growth and size figures for one entity type
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EntityTypeGrowth {
+    #[serde(flatten)]
+    pub counts: GrowthCounts,
+    pub size_bytes: u64,
+}
+
+/// One entity's stored size
+#[derive(Debug, Clone, Serialize)]
+pub struct EntitySize {
+    pub id: String,
+    pub entity_type: String,
+    pub agent: String,
+    pub size_bytes: u64,
+}
+
+/// Report for `engram stats entities`
+#[derive(Debug, Clone, Serialize)]
+pub struct EntityGrowthReport {
+    pub generated_at: DateTime<Utc>,
+    pub total_entities: usize,
+    pub total_size_bytes: u64,
+    pub by_type: BTreeMap<String, EntityTypeGrowth>,
+    /// Largest entities first
+    pub largest: Vec<EntitySize>,
+    pub by_agent: BTreeMap<String, GrowthCounts>,
+}
+
+/// Build the growth report as of `now`. Entity ages come from entity
+/// timestamps. Sizes are the stored blob sizes on git-backed storage and
+/// the serialized JSON size elsewhere.
+pub fn entity_growth_report(
+    storage: &dyn Storage,
+    now: DateTime<Utc>,
+    top: usize,
+) -> Result<EntityGrowthReport, EngramError> {
+    let stats = storage.get_stats()?;
+    let blob_sizes: Option<HashMap<(String, String), u64>> = storage
+        .as_any()
+        .downcast_ref::<GitRefsStorage>()
+        .map(|git| git.entity_blob_sizes())
+        .transpose()?;
+
+    let mut by_type: BTreeMap<String, EntityTypeGrowth> = stats
+        .entities_by_type
+        .keys()
+        .map(|entity_type| (entity_type.clone(), EntityTypeGrowth::default()))
+        .collect();
+    let mut by_agent: BTreeMap<String, GrowthCounts> = BTreeMap::new();
+    let mut sizes = Vec::new();
+
+    for entity in collect_entities(storage, None)? {
+        let age = now - entity.timestamp;
+        let size_bytes = blob_sizes
+            .as_ref()
+            .and_then(|s| s.get(&(entity.entity_type.clone(), entity.id.clone())))
+            .copied()
+            .unwrap_or_else(|| entity.approximate_size() as u64);
+
+        let growth = by_type.entry(entity.entity_type.clone()).or_default();
+        growth.counts.record(age);
+        growth.size_bytes += size_bytes;
+        by_agent
+            .entry(entity.agent.clone())
+            .or_default()
+            .record(age);
+        sizes.push(EntitySize {
+            id: entity.id,
+            entity_type: entity.entity_type,
+            agent: entity.agent,
+            size_bytes,
+        });
+    }
+
+    by_type.retain(|_, growth| growth.counts.total > 0);
+    sizes.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes).then(a.id.cmp(&b.id)));
+    let total_size_bytes = sizes.iter().map(|s| s.size_bytes).sum();
+    let total_entities = sizes.len();
+    sizes.truncate(top);
+
+    Ok(EntityGrowthReport {
+        generated_at: now,
+        total_entities,
+        total_size_bytes,
+        by_type,
+        largest: sizes,
+        by_agent,
+    })
+}
+
+fn format_bytes(bytes: u64) -> String {
+    match bytes {
+        b if b >= 1 << 20 => format!("{:.1} MiB", b as f64 / (1 << 20) as f64),
+        b if b >= 1 << 10 => format!("{:.1} KiB", b as f64 / (1 << 10) as f64),
+        b => format!("{} B", b),
+    }
+}
+
+fn print_growth_report(report: &EntityGrowthReport) {
+    println!(
+        "📊 {} entities, {}",
+        report.total_entities,
+        format_bytes(report.total_size_bytes)
+    );
+    if report.total_entities == 0 {
+        return;
+    }
+
+    println!("\nBy type:");
+    let mut table = create_table();
+    table.set_titles(row!["Type", "Total", "7d", "30d", "90d", "Size"]);
+    for (entity_type, growth) in &report.by_type {
+        table.add_row(row![
+            entity_type,
+            growth.counts.total,
+            growth.counts.last_7_days,
+            growth.counts.last_30_days,
+            growth.counts.last_90_days,
+            format_bytes(growth.size_bytes)
+        ]);
+    }
+    table.printstd();
+
+    println!("\nBy agent:");
+    let mut table = create_table();
+    table.set_titles(row!["Agent", "Total", "7d", "30d", "90d"]);
+    for (agent, counts) in &report.by_agent {
+        table.add_row(row![
+            truncate(agent, 30),
+            counts.total,
+            counts.last_7_days,
+            counts.last_30_days,
+            counts.last_90_days
+        ]);
+    }
+    table.printstd();
+
+    println!("\nLargest entities:");
+    let mut table = create_table();
+    table.set_titles(row!["ID", "Type", "Agent", "Size"]);
+    for entity in &report.largest {
+        table.add_row(row![
+            &entity.id[..8.min(entity.id.len())],
+            entity.entity_type,
+            truncate(&entity.agent, 20),
+            format_bytes(entity.size_bytes)
+        ]);
+    }
+    table.printstd();
+}
+
+/// Handle `engram stats`
+pub fn handle_stats_command(
+    storage: &dyn Storage,
+    command: StatsCommands,
+) -> Result<(), EngramError> {
+    match command {
+        StatsCommands::Entities { top, format } => {
+            let report = entity_growth_report(storage, Utc::now(), top)?;
+            if format == "json" {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                print_growth_report(&report);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{Entity, GenericEntity, Task, TaskPriority};
+    use crate::storage::MemoryStorage;
+    use tempfile::TempDir;
+
+    fn entity(entity_type: &str, agent: &str, age_days: i64, now: DateTime<Utc>) -> GenericEntity {
+        GenericEntity {
+            id: uuid::Uuid::new_v4().to_string(),
+            entity_type: entity_type.to_string(),
+            agent: agent.to_string(),
+            timestamp: now - Duration::days(age_days),
+            data: serde_json::json!({ "title": "x".repeat(age_days as usize) }),
+        }
+    }
+
+    #[test]
+    fn test_growth_report_periods_and_largest() {
+        let now = Utc::now();
+        let mut storage = MemoryStorage::new("default");
+        for (entity_type, agent, age) in [
+            ("task", "alice", 1),
+            ("task", "alice", 20),
+            ("task", "bob", 60),
+            ("context", "bob", 200),
+        ] {
+            storage
+                .store(&entity(entity_type, agent, age, now))
+                .unwrap();
+        }
+
+        let report = entity_growth_report(&storage, now, 2).unwrap();
+        assert_eq!(report.total_entities, 4);
+        assert_eq!(
+            report.by_type["task"].counts,
+            GrowthCounts {
+                total: 3,
+                last_7_days: 1,
+                last_30_days: 2,
+                last_90_days: 3,
+            }
+        );
+        assert_eq!(report.by_type["context"].counts.last_90_days, 0);
+        assert_eq!(report.by_agent["alice"].last_30_days, 2);
+        assert_eq!(report.by_agent["bob"].total, 2);
+
+        // The 200-day-old context has the longest title
+        assert_eq!(report.largest.len(), 2);
+        assert_eq!(report.largest[0].entity_type, "context");
+        assert!(report.largest[0].size_bytes >= report.largest[1].size_bytes);
+    }
+
+    #[test]
+    fn test_growth_report_uses_git_blob_sizes() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut storage = GitRefsStorage::new(temp_dir.path().to_str().unwrap(), "tester").unwrap();
+        let now = Utc::now();
+        let stored = Task::new(
+            "Measure me".to_string(),
+            String::new(),
+            "tester".to_string(),
+            TaskPriority::Medium,
+            None,
+        )
+        .to_generic();
+        storage.store(&stored).unwrap();
+
+        let sizes = storage.entity_blob_sizes().unwrap();
+        let size = sizes[&("task".to_string(), stored.id.clone())];
+        assert!(size > 0);
+
+        let stats = storage.get_stats().unwrap();
+        assert_eq!(stats.entities_by_type["task"], 1);
+        assert_eq!(stats.total_storage_size, size);
+
+        let report = entity_growth_report(&storage, now, 20).unwrap();
+        assert_eq!(report.total_size_bytes, size);
+        assert_eq!(report.by_type["task"].counts.last_7_days, 1);
+    }
+}
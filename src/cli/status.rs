@@ -0,0 +1,48 @@
+//! `engram status`: one-shot health report across all subsystems
+
+use crate::cli::utils::{create_table, truncate};
+use crate::config::Config;
+use crate::error::EngramError;
+use crate::health::{any_unhealthy, check_all_health, HealthState};
+use crate::storage::Storage;
+use prettytable::row;
+
+/// Run every subsystem health check and print the results
+///
+/// With `fail_on_unhealthy` the process exits with code 2 if any subsystem
+/// is unhealthy, after the report has been printed.
+pub async fn handle_status_command(
+    storage: &dyn Storage,
+    config: &Config,
+    fail_on_unhealthy: bool,
+    json: bool,
+) -> Result<(), EngramError> {
+    let results = check_all_health(storage, config).await;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&results)?);
+    } else {
+        let mut table = create_table();
+        table.set_titles(row!["Subsystem", "Status", "Latency (ms)", "Details"]);
+        for result in &results {
+            let status = match result.status {
+                HealthState::Healthy => format!("✅ {}", result.status),
+                HealthState::Degraded => format!("⚠️  {}", result.status),
+                HealthState::Unhealthy => format!("❌ {}", result.status),
+            };
+            table.add_row(row![
+                result.name,
+                status,
+                result.latency_ms,
+                truncate(&result.details, 70)
+            ]);
+        }
+        table.printstd();
+    }
+
+    if fail_on_unhealthy && any_unhealthy(&results) {
+        std::process::exit(2);
+    }
+
+    Ok(())
+}
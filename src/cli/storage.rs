@@ -0,0 +1,109 @@
+//! Storage maintenance command implementations
+
+use crate::cli::utils::create_table;
+use crate::error::EngramError;
+use crate::storage::{FsckReport, GitRefsStorage, ReadOnlyStorage};
+use clap::Subcommand;
+use prettytable::{Cell, Row};
+
+/// Storage maintenance commands
+#[derive(Debug, Subcommand)]
+pub enum StorageCommands {
+    /// Check every entity ref for unreadable or invalid blobs
+    ///
+    ///EXAMPLES:
+    ///  engram storage fsck
+    ///  engram storage fsck --repair
+    Fsck {
+        /// Move broken entity refs to refs/engram/corrupt/ so queries skip them
+        #[arg(long)]
+        repair: bool,
+    },
+}
+
+/// Handle `engram storage`
+pub fn handle_storage_command(
+    storage: &ReadOnlyStorage<GitRefsStorage>,
+    command: StorageCommands,
+    json: bool,
+) -> Result<(), EngramError> {
+    match command {
+        StorageCommands::Fsck { repair } => {
+            if repair && storage.mode().is_enabled() {
+                return Err(EngramError::InvalidOperation(
+                    "Workspace is read-only; refused fsck --repair".to_string(),
+                ));
+            }
+            let report = storage.inner().fsck(repair)?;
+            print_fsck_report(&report, json)?;
+            if !report.is_clean() && !report.repaired {
+                return Err(EngramError::Validation(format!(
+                    "{} corrupt entity ref(s) found; run `engram storage fsck --repair` to quarantine them",
+                    report.corrupt.len()
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn print_fsck_report(report: &FsckReport, json: bool) -> Result<(), EngramError> {
+    if json {
+        println!("{}", serde_json::to_string_pretty(report)?);
+        return Ok(());
+    }
+    if report.is_clean() {
+        println!(
+            "✅ {} entity ref(s) checked, no problems found",
+            report.checked
+        );
+        return Ok(());
+    }
+
+    let mut table = create_table();
+    table.set_titles(Row::new(vec![
+        Cell::new("Type"),
+        Cell::new("ID"),
+        Cell::new("Problem"),
+        Cell::new("Action"),
+    ]));
+    for entry in &report.corrupt {
+        let action = match (&entry.quarantined_to, report.repaired) {
+            (Some(quarantine_ref), _) => format!("moved to {}", quarantine_ref),
+            (None, true) => "ref removed (blob missing)".to_string(),
+            (None, false) => "-".to_string(),
+        };
+        table.add_row(Row::new(vec![
+            Cell::new(&entry.entity_type),
+            Cell::new(&entry.entity_id),
+            Cell::new(&entry.problem),
+            Cell::new(&action),
+        ]));
+    }
+    table.printstd();
+    println!(
+        "\n{} of {} entity ref(s) corrupt",
+        report.corrupt.len(),
+        report.checked
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::ReadOnlyMode;
+
+    #[test]
+    fn test_fsck_repair_refused_when_read_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let inner = GitRefsStorage::new(dir.path().to_str().unwrap(), "test").unwrap();
+        let storage = ReadOnlyStorage::new(inner, ReadOnlyMode::Strict);
+
+        let result = handle_storage_command(&storage, StorageCommands::Fsck { repair: true }, true);
+        assert!(matches!(result, Err(EngramError::InvalidOperation(_))));
+        assert!(
+            handle_storage_command(&storage, StorageCommands::Fsck { repair: false }, true).is_ok()
+        );
+    }
+}
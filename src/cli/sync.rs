@@ -1,11 +1,17 @@
-use crate::entities::GenericEntity;
+use crate::cli::diff::render_labeled_diff;
+use crate::cli::identity::resolve_agent;
+use crate::entities::{diff_entities, EntityDiff, GenericEntity};
 use crate::error::EngramError;
-use crate::storage::{ConflictResolution, RemoteAuth, Storage, SyncResult};
+use crate::storage::{
+    ConflictResolution, IntelligentMergeResolver, RemoteAuth, Storage, SyncResult,
+    WorkspaceEncryption,
+};
 use chrono::Utc;
 use git2::{Cred, FetchOptions, PushOptions, RemoteCallbacks, Repository};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
+use std::io::IsTerminal;
 use std::path::Path;
 
 #[derive(clap::Subcommand)]
@@ -17,9 +23,6 @@ pub enum SyncCommands {
 
         #[arg(long, short, default_value = "merge_with_conflict_resolution")]
         strategy: String,
-
-        #[arg(long, default_value_t = false)]
-        dry_run: bool,
     },
     /// Add remote repository
     AddRemote {
@@ -62,8 +65,6 @@ pub enum SyncCommands {
         password: Option<String>,
         #[arg(long)]
         ssh_key: Option<String>,
-        #[arg(long, default_value_t = false)]
-        dry_run: bool,
     },
     /// Push to remote  
     Push {
@@ -81,8 +82,6 @@ pub enum SyncCommands {
         password: Option<String>,
         #[arg(long)]
         ssh_key: Option<String>,
-        #[arg(long, default_value_t = false)]
-        dry_run: bool,
     },
     /// Create a new branch for agent isolation
     CreateBranch {
@@ -125,8 +124,6 @@ pub enum SyncCommands {
         password: Option<String>,
         #[arg(long)]
         ssh_key: Option<String>,
-        #[arg(long, default_value_t = false)]
-        dry_run: bool,
     },
     /// Resolve conflicts detected by pull
     Resolve {
@@ -136,6 +133,37 @@ pub enum SyncCommands {
         #[arg(long)]
         strategy: Option<String>,
     },
+    /// Show field-level diffs of the conflicts detected by pull, without
+    /// resolving them
+    ///
+    ///EXAMPLES:
+    ///  engram sync diff --remote origin
+    ///  engram sync diff --remote origin --json
+    Diff {
+        #[arg(long)]
+        remote: String,
+        /// Output as JSON
+        #[arg(long, default_value_t = false)]
+        json: bool,
+    },
+    /// Three-way merge two diverged versions of an entity and store the result
+    ///
+    ///EXAMPLES:
+    ///  engram sync merge --base base.json --ours ours.json --theirs theirs.json
+    Merge {
+        /// Common ancestor version (entity JSON file)
+        #[arg(long)]
+        base: String,
+        /// Local version (entity JSON file)
+        #[arg(long)]
+        ours: String,
+        /// Remote version (entity JSON file)
+        #[arg(long)]
+        theirs: String,
+        /// Merge strategy; only intelligent-merge supports three-way merges
+        #[arg(long, default_value = "intelligent-merge")]
+        strategy: String,
+    },
 }
 
 /// Result of a pull-then-push (both) operation
@@ -217,16 +245,12 @@ pub fn sync_agents<S: Storage>(
     storage: &mut S,
     agents: Vec<String>,
     strategy: MergeStrategy,
-    dry_run: bool,
 ) -> Result<SyncResult, EngramError> {
     let start_time = Utc::now();
 
     println!("🔄 Starting synchronization...");
     println!("🤖 Agents: {}", agents.join(", "));
     println!("📋 Strategy: {:?}", strategy);
-    if dry_run {
-        println!("🔍 Mode: Dry run (no changes will be made)");
-    }
     println!();
 
     if agents.is_empty() {
@@ -264,7 +288,7 @@ pub fn sync_agents<S: Storage>(
     let mut errors = Vec::new();
 
     for entity_type in entity_types {
-        match sync_entity_type(storage, entity_type, &agents, &strategy, dry_run) {
+        match sync_entity_type(storage, entity_type, &agents, &strategy) {
             Ok((synced, merged, conflicts)) => {
                 total_synced += synced;
                 total_merged += merged;
@@ -285,7 +309,7 @@ pub fn sync_agents<S: Storage>(
         }
     }
 
-    if !dry_run && total_synced > 0 {
+    if total_synced > 0 {
         storage.sync()?;
     }
 
@@ -321,7 +345,6 @@ fn sync_entity_type<S: Storage>(
     entity_type: &str,
     agents: &[String],
     strategy: &MergeStrategy,
-    dry_run: bool,
 ) -> Result<(usize, usize, Vec<ConflictResolution>), EngramError> {
     println!("\n🔍 Synchronizing {} entities...", entity_type);
 
@@ -370,10 +393,8 @@ fn sync_entity_type<S: Storage>(
         );
     }
 
-    if !dry_run {
-        for entity in &merged_entities {
-            storage.store(entity)?;
-        }
+    for entity in &merged_entities {
+        storage.store(entity)?;
     }
 
     Ok((merged_entities.len(), merged_count, conflicts))
@@ -469,6 +490,11 @@ fn intelligent_merge_entity(
         }
     }
 
+    let existing_modification = existing.modification();
+    if existing_modification.is_later_than(&merged.modification()) {
+        merged.set_modification(&existing_modification);
+    }
+
     Ok(merged)
 }
 
@@ -1489,6 +1515,27 @@ pub struct ConflictEntry {
     pub remote_content: Vec<u8>,
 }
 
+impl ConflictEntry {
+    /// Field-level diff from the local to the remote content, with
+    /// encrypted fields decrypted where `encryption` holds the key
+    pub fn diff(&self, encryption: &WorkspaceEncryption) -> Result<EntityDiff, EngramError> {
+        let parse = |content: &[u8]| -> Result<GenericEntity, EngramError> {
+            let mut entity = GenericEntity::from_value(serde_json::from_slice(content)?)?;
+            let _ = encryption.decrypt_entity(&mut entity);
+            Ok(entity)
+        };
+        Ok(diff_entities(
+            &parse(&self.local_content)?,
+            &parse(&self.remote_content)?,
+        ))
+    }
+
+    fn render_diff(&self, encryption: &WorkspaceEncryption, color: bool) -> Option<String> {
+        let diff = self.diff(encryption).ok()?;
+        Some(render_labeled_diff("local", "remote", &diff, color))
+    }
+}
+
 /// Auto-resolve strategy for non-interactive conflict resolution
 #[derive(Debug, Clone, PartialEq)]
 pub enum ResolveStrategy {
@@ -1639,6 +1686,54 @@ pub fn detect_conflicts(
     Ok(conflicts)
 }
 
+/// Print the field-level diff of every conflict detected for a remote.
+/// Returns the number of conflicts.
+pub fn diff_conflicts(remote_name: &str, json: bool) -> Result<usize, EngramError> {
+    let repo = Repository::open(".")
+        .map_err(|e| EngramError::Git(format!("Failed to open repository: {}", e)))?;
+    let conflicts = detect_conflicts(&repo, remote_name)?;
+    let encryption = WorkspaceEncryption::load(Path::new(".")).unwrap_or_default();
+
+    if json {
+        let entries = conflicts
+            .iter()
+            .map(|conflict| {
+                Ok(serde_json::json!({
+                    "entity_type": conflict.entity_type,
+                    "uuid": conflict.uuid,
+                    "version": conflict.version,
+                    "diff": conflict.diff(&encryption)?,
+                }))
+            })
+            .collect::<Result<Vec<_>, EngramError>>()?;
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+        return Ok(conflicts.len());
+    }
+
+    if conflicts.is_empty() {
+        println!("No conflicts for remote '{}'.", remote_name);
+        return Ok(0);
+    }
+    let color = std::io::stdout().is_terminal();
+    for conflict in &conflicts {
+        println!(
+            "CONFLICT  {}/{} (version {})",
+            conflict.entity_type, conflict.uuid, conflict.version
+        );
+        match conflict.render_diff(&encryption, color) {
+            Some(diff) => print!("{}", diff),
+            None => println!("  (content is not a readable entity)"),
+        }
+        println!();
+    }
+    println!(
+        "{} conflict(s); resolve with: engram sync resolve --remote {}",
+        conflicts.len(),
+        remote_name
+    );
+    Ok(conflicts.len())
+}
+
 /// Resolve conflicts for a given remote
 /// Strategy: Some("local") or Some("remote") for non-interactive; None for interactive stdin
 pub fn resolve_conflicts(
@@ -1668,6 +1763,7 @@ pub fn resolve_conflicts(
         println!("No conflicts to resolve for remote '{}'.", remote_name);
         return Ok(0);
     }
+    let encryption = WorkspaceEncryption::load(Path::new(".")).unwrap_or_default();
 
     println!(
         "Found {} conflict(s) for remote '{}':",
@@ -1681,15 +1777,20 @@ pub fn resolve_conflicts(
         println!("CONFLICT  {}/{}", conflict.entity_type, conflict.uuid);
         println!("  Version: {}", conflict.version);
 
-        // Show a brief diff: print first 200 bytes of each side
-        let local_preview = String::from_utf8_lossy(
-            &conflict.local_content[..conflict.local_content.len().min(200)],
-        );
-        let remote_preview = String::from_utf8_lossy(
-            &conflict.remote_content[..conflict.remote_content.len().min(200)],
-        );
-        println!("  Local  : {}", local_preview);
-        println!("  Remote : {}", remote_preview);
+        // Show the field-level diff, or the first 200 bytes of each side
+        // when the content does not parse as an entity
+        if let Some(diff) = conflict.render_diff(&encryption, std::io::stdout().is_terminal()) {
+            print!("{}", diff);
+        } else {
+            let local_preview = String::from_utf8_lossy(
+                &conflict.local_content[..conflict.local_content.len().min(200)],
+            );
+            let remote_preview = String::from_utf8_lossy(
+                &conflict.remote_content[..conflict.remote_content.len().min(200)],
+            );
+            println!("  Local  : {}", local_preview);
+            println!("  Remote : {}", remote_preview);
+        }
 
         let winner_content: &[u8] = match &strategy {
             Some(ResolveStrategy::Local) => {
@@ -1854,20 +1955,21 @@ pub fn handle_import_git_remotes() -> Result<(), EngramError> {
 }
 
 /// Handle sync commands
+///
+/// Local agent sync writes through `storage`, so a dry run wraps it in
+/// `DryRunStorage`; `dry_run` is only consulted for remote pull and push.
 pub fn handle_sync_command<S: Storage>(
     storage: &mut S,
     command: &SyncCommands,
+    dry_run: bool,
 ) -> Result<(), EngramError> {
     match command {
-        SyncCommands::Sync {
-            agents,
-            strategy,
-            dry_run,
-        } => {
+        SyncCommands::Sync { agents, strategy } => {
             let agent_list: Vec<String> = agents
                 .split(',')
-                .map(|s| s.trim().to_string())
+                .map(|s| s.trim())
                 .filter(|s| !s.is_empty())
+                .map(|s| resolve_agent(Some(s)))
                 .collect();
 
             if agent_list.is_empty() {
@@ -1877,7 +1979,7 @@ pub fn handle_sync_command<S: Storage>(
             }
 
             let merge_strategy = MergeStrategy::from_str(strategy)?;
-            let _result = sync_agents(storage, agent_list, merge_strategy, *dry_run)?;
+            let _result = sync_agents(storage, agent_list, merge_strategy)?;
 
             println!("\n🎉 Synchronization completed successfully!");
             Ok(())
@@ -1921,7 +2023,6 @@ pub fn handle_sync_command<S: Storage>(
             username,
             password,
             ssh_key,
-            dry_run,
         } => {
             let auth = RemoteAuth {
                 auth_type: auth_type.clone().unwrap_or_else(|| "none".to_string()),
@@ -1929,7 +2030,7 @@ pub fn handle_sync_command<S: Storage>(
                 password: password.clone(),
                 key_path: ssh_key.clone(),
             };
-            pull_from_remote(remote.clone(), auth, *dry_run)?;
+            pull_from_remote(remote.clone(), auth, dry_run)?;
             Ok(())
         }
         SyncCommands::Push {
@@ -1940,7 +2041,6 @@ pub fn handle_sync_command<S: Storage>(
             username,
             password,
             ssh_key,
-            dry_run,
         } => {
             let auth = RemoteAuth {
                 auth_type: auth_type.clone().unwrap_or_else(|| "none".to_string()),
@@ -1948,7 +2048,7 @@ pub fn handle_sync_command<S: Storage>(
                 password: password.clone(),
                 key_path: ssh_key.clone(),
             };
-            push_to_remote(remote.clone(), auth, *dry_run)?;
+            push_to_remote(remote.clone(), auth, dry_run)?;
             Ok(())
         }
         SyncCommands::CreateBranch { name, agent, from } => {
@@ -1964,7 +2064,6 @@ pub fn handle_sync_command<S: Storage>(
             username,
             password,
             ssh_key,
-            dry_run,
         } => {
             let auth = RemoteAuth {
                 auth_type: auth_type.clone().unwrap_or_else(|| "none".to_string()),
@@ -1972,7 +2071,7 @@ pub fn handle_sync_command<S: Storage>(
                 password: password.clone(),
                 key_path: ssh_key.clone(),
             };
-            sync_both(remote.clone(), auth, *dry_run)?;
+            sync_both(remote.clone(), auth, dry_run)?;
             Ok(())
         }
         SyncCommands::Resolve { remote, strategy } => {
@@ -1983,7 +2082,81 @@ pub fn handle_sync_command<S: Storage>(
             resolve_conflicts(remote.clone(), strat)?;
             Ok(())
         }
+        SyncCommands::Diff { remote, json } => {
+            diff_conflicts(remote, *json)?;
+            Ok(())
+        }
+        SyncCommands::Merge {
+            base,
+            ours,
+            theirs,
+            strategy,
+        } => {
+            merge_entity_files(
+                storage,
+                base,
+                ours,
+                theirs,
+                &MergeStrategy::from_str(strategy)?,
+            )?;
+            Ok(())
+        }
+    }
+}
+
+fn read_entity_file(path: &str) -> Result<GenericEntity, EngramError> {
+    let content = fs::read_to_string(path)?;
+    GenericEntity::from_value(serde_json::from_str(&content)?)
+}
+
+/// Three-way merge entity JSON files and store the merged entity
+///
+/// Conflicting fields are kept as `__conflict_*` metadata markers on the
+/// stored entity so they can be reviewed and fixed up by hand.
+pub fn merge_entity_files<S: Storage>(
+    storage: &mut S,
+    base: &str,
+    ours: &str,
+    theirs: &str,
+    strategy: &MergeStrategy,
+) -> Result<ConflictResolution, EngramError> {
+    if *strategy != MergeStrategy::IntelligentMerge {
+        return Err(EngramError::Validation(format!(
+            "Strategy {:?} does not support three-way merges; use intelligent-merge",
+            strategy
+        )));
+    }
+    let outcome = IntelligentMergeResolver::new().merge(
+        &read_entity_file(base)?,
+        &read_entity_file(ours)?,
+        &read_entity_file(theirs)?,
+    )?;
+    storage.store(&outcome.merged)?;
+
+    let resolution = outcome.resolution();
+    if outcome.needs_review() {
+        println!(
+            "⚠️  Merged {} {} with {} conflict(s) needing manual review:",
+            resolution.entity_type,
+            resolution.entity_id,
+            resolution.conflicts_detected.len()
+        );
+        print!(
+            "{}",
+            render_labeled_diff(
+                &format!("ours {}", ours),
+                &format!("theirs {}", theirs),
+                &outcome.conflict_diff(),
+                std::io::stdout().is_terminal(),
+            )
+        );
+    } else {
+        println!(
+            "✅ Merged {} {} cleanly",
+            resolution.entity_type, resolution.entity_id
+        );
     }
+    Ok(resolution)
 }
 
 /// Create a new branch for agent isolation
@@ -2269,7 +2442,7 @@ mod tests {
     #[test]
     fn test_sync_agents_empty() {
         let mut storage = MemoryStorage::new("test-agent");
-        let result = sync_agents(&mut storage, vec![], MergeStrategy::LatestWins, false);
+        let result = sync_agents(&mut storage, vec![], MergeStrategy::LatestWins);
         assert!(result.is_err());
     }
 
@@ -2280,13 +2453,70 @@ mod tests {
             &mut storage,
             vec!["agent1".to_string()],
             MergeStrategy::LatestWins,
-            false,
         );
         assert!(result.is_ok());
         let sync_result = result.unwrap();
         assert_eq!(sync_result.entities_synced, 0);
     }
 
+    #[test]
+    fn test_merge_entity_files_stores_merged_entity() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |name: &str, data: serde_json::Value| {
+            let path = dir.path().join(name);
+            let entity = serde_json::json!({
+                "id": "task-1",
+                "entity_type": "task",
+                "agent": "alice",
+                "timestamp": "2026-01-01T00:00:00Z",
+                "data": data,
+            });
+            fs::write(&path, entity.to_string()).unwrap();
+            path.to_string_lossy().to_string()
+        };
+        let base = write("base.json", serde_json::json!({"a": 1, "b": 1}));
+        let ours = write("ours.json", serde_json::json!({"a": 2, "b": 1}));
+        let theirs = write("theirs.json", serde_json::json!({"a": 1, "b": 2}));
+
+        let mut storage = MemoryStorage::new("test-agent");
+        let resolution = merge_entity_files(
+            &mut storage,
+            &base,
+            &ours,
+            &theirs,
+            &MergeStrategy::IntelligentMerge,
+        )
+        .unwrap();
+        assert!(resolution.conflicts_detected.is_empty());
+        let mut stored = storage.get("task-1", "task").unwrap().unwrap();
+        assert!(stored.modification().last_modified_at.is_some());
+        stored
+            .data
+            .as_object_mut()
+            .unwrap()
+            .remove("last_modified_at");
+        assert_eq!(
+            stored.data,
+            serde_json::json!({
+                "a": 2,
+                "b": 2,
+                "last_modified_by": "test-agent",
+                "modification_count": 1
+            })
+        );
+
+        assert!(matches!(
+            merge_entity_files(
+                &mut storage,
+                &base,
+                &ours,
+                &theirs,
+                &MergeStrategy::LatestWins
+            ),
+            Err(EngramError::Validation(_))
+        ));
+    }
+
     #[test]
     fn test_remote_config_project_id_field() {
         // Serialise with project_id None — field must be absent from JSON (serde skip_serializing_if)
@@ -2648,6 +2878,42 @@ mod tests {
         assert!(is_conflict);
     }
 
+    /// Conflicting blobs diff field by field, with lists as sets
+    #[test]
+    fn test_conflict_entry_diff() {
+        let blob = |status: &str, tags: serde_json::Value| {
+            serde_json::to_vec(&serde_json::json!({
+                "id": "t1",
+                "entity_type": "task",
+                "agent": "alice",
+                "timestamp": "2024-01-01T00:00:00Z",
+                "data": {"status": status, "tags": tags},
+            }))
+            .unwrap()
+        };
+        let conflict = ConflictEntry {
+            entity_type: "task".to_string(),
+            uuid: "t1".to_string(),
+            version: 2,
+            local_content: blob("done", serde_json::json!(["a"])),
+            remote_content: blob("blocked", serde_json::json!(["a", "b"])),
+        };
+        let encryption = WorkspaceEncryption::default();
+        let diff = conflict.diff(&encryption).unwrap();
+        assert_eq!(diff.modified_fields.len(), 1);
+        assert_eq!(diff.list_changes[0].1.added, vec![serde_json::json!("b")]);
+        assert_eq!(
+            conflict.render_diff(&encryption, false).unwrap(),
+            "--- local\n+++ remote\n@@ status @@\n- done\n+ blocked\n@@ tags @@\n+ b\n"
+        );
+
+        let unreadable = ConflictEntry {
+            local_content: b"content-A".to_vec(),
+            ..conflict
+        };
+        assert!(unreadable.render_diff(&encryption, false).is_none());
+    }
+
     /// detect_conflicts: same version same content is NOT a conflict
     #[test]
     fn test_conflict_detection_same_content_not_conflict() {
@@ -1,13 +1,23 @@
 //! Task command implementations
 
-use crate::entities::{Entity, StaleTaskReport, Task, TaskPriority};
+use crate::api::{Engram, TaskDraft, TaskFilter};
+use crate::cli::identity::resolve_agent;
+use crate::engines::RecurringTaskManager;
+use crate::entities::{
+    fallback_duration_seconds, CriticalPathResult, DependencyGraph, Entity, EntityRelationType,
+    EntityRelationship, Modification, RecurrenceTrigger, RecurringTaskConfig, SearchHistory,
+    StaleTaskReport, Task, TaskPriority, TaskStatus,
+};
 use crate::error::EngramError;
 use crate::feedback::StructuredFeedback;
 use crate::storage::{RelationshipStorage, Storage};
+use chrono::{DateTime, Utc};
 use clap::Subcommand;
 use serde::Deserialize;
+use std::collections::HashSet;
 use std::fs;
 use std::io::{self, Read, Write};
+use tracing::instrument;
 
 /// Task input structure for JSON
 #[derive(Debug, Deserialize)]
@@ -18,6 +28,11 @@ pub struct TaskInput {
     pub agent: Option<String>,
     pub parent: Option<String>,
     pub tags: Option<Vec<String>>,
+    #[serde(default)]
+    pub due_at: Option<DateTime<Utc>>,
+    /// Estimated effort, e.g. `2h` or `3d`
+    #[serde(default)]
+    pub estimate: Option<String>,
 }
 
 /// Task commands
@@ -49,6 +64,10 @@ pub enum TaskCommands {
         #[arg(long)]
         tags: Option<String>,
 
+        /// Estimated effort (e.g. 45m, 2h, 3d)
+        #[arg(long)]
+        estimate: Option<String>,
+
         /// Output format (json, text)
         #[arg(long, default_value = "text")]
         output: String,
@@ -77,13 +96,25 @@ pub enum TaskCommands {
         /// JSON file path
         #[arg(long, requires = "json")]
         json_file: Option<String>,
+
+        /// Create even if an open task with a similar title exists
+        #[arg(long)]
+        allow_duplicate: bool,
+
+        /// Create and check out a branch named after the task, bound to it
+        #[arg(long)]
+        branch: bool,
     },
     /// List tasks
     List {
-        /// Filter by agent
+        /// Filter by agent (defaults to the current identity; `me` is accepted)
         #[arg(long, short)]
         agent: Option<String>,
 
+        /// List tasks from every agent instead of only the current identity
+        #[arg(long, conflicts_with = "agent")]
+        all_agents: bool,
+
         /// Filter by status
         #[arg(long, short)]
         status: Option<String>,
@@ -116,17 +147,59 @@ pub enum TaskCommands {
         #[arg(long, default_value = "24", requires = "stale")]
         stale_threshold: i64,
 
+        /// Only tasks whose title or description contains this text; `?` lists your most used search terms
+        #[arg(long)]
+        search: Option<String>,
+
+        /// Also match words within a typo of the --search text
+        #[arg(long, requires = "search")]
+        fuzzy: bool,
+
+        /// Show previous search terms matching --search above the results
+        #[arg(long, requires = "search")]
+        suggest: bool,
+
+        /// Delete your recorded search terms
+        #[arg(long, conflicts_with = "search")]
+        clear_search_history: bool,
+
         /// Output format (text, json)
         #[arg(long, default_value = "text")]
         output: String,
+
+        #[command(flatten)]
+        sort: SortArgs,
+
+        #[command(flatten)]
+        count: CountArgs,
     },
-    /// Show task details
+    /// Show task details with linked entities, dependencies, commits, gate results and notes
     Show {
         /// Task ID
-        #[arg(help = "Task ID to show")]
-        id: String,
+        #[arg(help = "Task ID to show (default: the task bound to the current branch)")]
+        id: Option<String>,
+
+        /// Omit linked contexts, reasoning and related entities
+        #[arg(long)]
+        no_links: bool,
+
+        /// Omit dependency tasks
+        #[arg(long)]
+        no_dependencies: bool,
+
+        /// Omit commits referencing the task
+        #[arg(long)]
+        no_commits: bool,
+
+        /// Omit quality gate results
+        #[arg(long)]
+        no_gates: bool,
+
+        /// Omit unresolved notes
+        #[arg(long)]
+        no_notes: bool,
     },
-    /// Update task status
+    /// Update task status or estimate
     Update {
         /// Task ID
         #[arg(help = "Task ID to update")]
@@ -136,9 +209,14 @@ pub enum TaskCommands {
         #[arg(
             long,
             short,
+            required_unless_present = "estimate",
             help = "New status: todo, in_progress, done, blocked, cancelled"
         )]
-        status: String,
+        status: Option<String>,
+
+        /// Estimated effort (e.g. 45m, 2h, 3d); `none` clears it
+        #[arg(long)]
+        estimate: Option<String>,
 
         /// Outcome (when completing task)
         #[arg(long)]
@@ -168,10 +246,6 @@ pub enum TaskCommands {
         #[arg(long)]
         status: Option<String>,
 
-        /// Preview what would be archived without making changes
-        #[arg(long)]
-        dry_run: bool,
-
         /// Output format (text, json)
         #[arg(long, default_value = "text")]
         output: String,
@@ -220,6 +294,200 @@ pub enum TaskCommands {
         #[arg(long)]
         no_fail_fast: bool,
     },
+    /// Manage auto-repeating tasks
+    Recurring {
+        #[command(subcommand)]
+        command: RecurringTaskCommands,
+    },
+    /// Show the longest dependency chain ending at a task as a timeline
+    CriticalPath {
+        /// Terminal task UUID
+        id: String,
+
+        /// Output format (text, json)
+        #[arg(long, default_value = "text")]
+        output: String,
+    },
+    /// Render task schedules as a Gantt chart
+    ///
+    ///EXAMPLES:
+    ///  engram task gantt
+    ///  engram task gantt --agent me --days 14 --unit hour
+    ///  engram --json task gantt
+    Gantt {
+        /// Only include tasks assigned to this agent (`me` is accepted)
+        #[arg(long, short)]
+        agent: Option<String>,
+
+        /// Only include tasks active within the last N days
+        #[arg(long, default_value_t = 30)]
+        days: i64,
+
+        /// Column unit (hour, day, week)
+        #[arg(long, default_value = "day")]
+        unit: String,
+    },
+    /// Raise the priority of tasks idle past the `aging` policy in
+    /// engram.yaml, one level at a time
+    ///
+    ///EXAMPLES:
+    ///  engram task age
+    ///  engram task age --apply
+    Age {
+        /// Store the bumps; without this only reports what would change
+        #[arg(long)]
+        apply: bool,
+    },
+    /// Bind a task to a git branch so `next`, `task show` and commit
+    /// validation default to it while that branch is checked out
+    ///
+    ///EXAMPLES:
+    ///  engram task bind-branch <TASK_ID>
+    ///  engram task bind-branch <TASK_ID> --branch feature/login
+    BindBranch {
+        /// Task to bind
+        id: String,
+
+        /// Branch to bind (default: the current branch)
+        #[arg(long)]
+        branch: Option<String>,
+    },
+    /// Remove a task's branch binding
+    UnbindBranch {
+        /// Task to unbind (default: the task bound to the current branch)
+        id: Option<String>,
+    },
+    /// Merge a duplicate task into another, keeping both histories
+    Merge {
+        /// Task that survives the merge
+        #[arg(required_unless_present = "auto_detect_duplicates")]
+        primary_id: Option<String>,
+
+        /// Task folded into the primary and then cancelled
+        #[arg(required_unless_present = "auto_detect_duplicates")]
+        secondary_id: Option<String>,
+
+        /// Description handling (keep-primary, keep-longer-description, concatenate-descriptions)
+        #[arg(long, default_value = "keep-primary", value_parser = parse_merge_strategy)]
+        strategy: TaskMergeStrategy,
+
+        /// List tasks with near-identical titles as merge candidates instead of merging
+        #[arg(long, conflicts_with_all = ["primary_id", "secondary_id"])]
+        auto_detect_duplicates: bool,
+    },
+}
+
+impl TaskCommands {
+    /// Whether the command writes to the workspace; listing, reports and
+    /// duplicate detection only read
+    pub fn is_mutating(&self) -> bool {
+        match self {
+            TaskCommands::Create { .. }
+            | TaskCommands::Update { .. }
+            | TaskCommands::Archive { .. }
+            | TaskCommands::ArchiveBulk { .. }
+            | TaskCommands::Resolve { .. }
+            | TaskCommands::CreateBatch { .. }
+            | TaskCommands::BindBranch { .. }
+            | TaskCommands::UnbindBranch { .. } => true,
+            TaskCommands::Merge {
+                auto_detect_duplicates,
+                ..
+            } => !auto_detect_duplicates,
+            TaskCommands::Recurring { command } => !matches!(command, RecurringTaskCommands::List),
+            TaskCommands::List { .. }
+            | TaskCommands::Show { .. }
+            | TaskCommands::CriticalPath { .. }
+            | TaskCommands::Gantt { .. }
+            | TaskCommands::Age { .. } => false,
+        }
+    }
+}
+
+/// How `merge_tasks` combines the two descriptions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskMergeStrategy {
+    /// Keep the primary description untouched
+    KeepPrimary,
+    /// Keep whichever description is longer
+    KeepLongerDescription,
+    /// Append the secondary description to the primary one
+    ConcatenateDescriptions,
+}
+
+fn parse_merge_strategy(s: &str) -> Result<TaskMergeStrategy, String> {
+    match s.to_lowercase().replace('_', "-").as_str() {
+        "keep-primary" | "primary" => Ok(TaskMergeStrategy::KeepPrimary),
+        "keep-longer-description" | "keep-longer" | "longer" => {
+            Ok(TaskMergeStrategy::KeepLongerDescription)
+        }
+        "concatenate-descriptions" | "concatenate" | "concat" => {
+            Ok(TaskMergeStrategy::ConcatenateDescriptions)
+        }
+        _ => Err(format!(
+            "Invalid merge strategy: {}. Use: keep-primary, keep-longer-description, or concatenate-descriptions",
+            s
+        )),
+    }
+}
+
+/// `task list --status` value; case-insensitive, `in_progress` and
+/// `in-progress` name the same status as `inprogress`
+fn parse_status_filter(s: &str) -> Result<TaskStatus, EngramError> {
+    match s.to_lowercase().replace(['_', '-'], "").as_str() {
+        "todo" => Ok(TaskStatus::Todo),
+        "inprogress" => Ok(TaskStatus::InProgress),
+        "done" => Ok(TaskStatus::Done),
+        "blocked" => Ok(TaskStatus::Blocked),
+        "cancelled" => Ok(TaskStatus::Cancelled),
+        _ => Err(EngramError::Validation(format!(
+            "Invalid status: '{}'. Valid values: todo, in_progress, done, blocked, cancelled",
+            s
+        ))),
+    }
+}
+
+/// Recurring task commands
+#[derive(Subcommand)]
+pub enum RecurringTaskCommands {
+    /// Register a recurrence for an existing task
+    ///
+    ///EXAMPLES:
+    ///  engram task recurring create --base-task <UUID> --schedule "0 9 * * MON"
+    ///  engram task recurring create --base-task <UUID> --on-completion --max-instances 4
+    Create {
+        /// Task copied for each new instance
+        #[arg(long)]
+        base_task: String,
+
+        /// Cron schedule in UTC (minute hour day-of-month month day-of-week)
+        #[arg(
+            long,
+            conflicts_with = "on_completion",
+            required_unless_present = "on_completion"
+        )]
+        schedule: Option<String>,
+
+        /// Create the next instance when the current one is marked done
+        #[arg(long)]
+        on_completion: bool,
+
+        /// Stop after this many instances
+        #[arg(long)]
+        max_instances: Option<u32>,
+
+        /// Copy the base task's tags onto each instance (default: true)
+        #[arg(long)]
+        carry_over_labels: Option<bool>,
+
+        /// Agent registering the recurrence
+        #[arg(long, short)]
+        agent: Option<String>,
+    },
+    /// List recurring tasks and when they next trigger
+    List,
+    /// Create any instances that are due now
+    Tick,
 }
 
 /// Read content from stdin with a prompt
@@ -249,6 +517,7 @@ fn read_file(path: &str) -> Result<String, EngramError> {
 }
 
 /// Create task command
+#[instrument(skip_all, fields(entity_type = "task", operation = "create"))]
 pub fn create_task<S: Storage>(
     storage: &mut S,
     title: Option<String>,
@@ -265,6 +534,9 @@ pub fn create_task<S: Storage>(
     json: bool,
     json_file: Option<String>,
     output_format: String,
+    allow_duplicate: bool,
+    estimate: Option<String>,
+    branch: bool,
 ) -> Result<(), EngramError> {
     // Handle JSON input first (overrides all other inputs)
     if json {
@@ -297,6 +569,14 @@ pub fn create_task<S: Storage>(
             ))
         })?;
 
+        check_duplicate(
+            storage,
+            "task",
+            &task_input.title,
+            Some(task_input.description.as_deref().unwrap_or_default()),
+            allow_duplicate,
+        )?;
+
         // Re-implementing logic here to support output format
         let priority_enum = match task_input.priority.as_deref().unwrap_or("medium") {
             "low" => TaskPriority::Low,
@@ -306,30 +586,27 @@ pub fn create_task<S: Storage>(
             _ => TaskPriority::Medium,
         };
 
-        let mut task = Task::new(
-            task_input.title,
-            task_input.description.unwrap_or_default(),
-            task_input.agent.unwrap_or_else(|| "default".to_string()),
-            priority_enum,
-            None,
-        );
-
-        if let Some(parent_id) = task_input.parent {
-            task.parent = Some(parent_id);
-        }
-
-        if let Some(tags_vec) = task_input.tags {
-            task.tags = tags_vec;
-        }
-
-        let generic = task.to_generic();
-        storage.store(&generic)?;
+        let agent = resolve_agent(task_input.agent);
+        let task = Engram::with_storage(&mut *storage, agent).create_task(TaskDraft {
+            title: task_input.title,
+            description: task_input.description.unwrap_or_default(),
+            priority: priority_enum,
+            agent: None,
+            parent: task_input.parent,
+            tags: task_input.tags.unwrap_or_default(),
+            due_at: task_input.due_at,
+            estimate_seconds: task_input
+                .estimate
+                .as_deref()
+                .map(parse_duration_secs)
+                .transpose()?,
+        })?;
 
         if output_format == "json" {
             println!("{}", serde_json::to_string_pretty(&task).unwrap());
         } else {
             println!("✅ Task created:");
-            display_task(&task);
+            display_task(&task, None);
         }
         return Ok(());
     }
@@ -358,6 +635,8 @@ pub fn create_task<S: Storage>(
         description
     };
 
+    check_duplicate(storage, "task", &final_title, None, allow_duplicate)?;
+
     let priority_enum = match priority {
         "low" => TaskPriority::Low,
         "medium" => TaskPriority::Medium,
@@ -366,37 +645,114 @@ pub fn create_task<S: Storage>(
         _ => TaskPriority::Medium,
     };
 
-    let mut task = Task::new(
-        final_title,
-        description_val.unwrap_or_default(),
-        agent.unwrap_or_else(|| "default".to_string()),
-        priority_enum,
-        None,
-    );
-
-    if let Some(parent_id) = parent {
-        task.parent = Some(parent_id);
-    }
-
-    if let Some(tags_str) = tags {
-        task.tags = tags_str.split(',').map(|s| s.trim().to_string()).collect();
-    }
+    let agent = resolve_agent(agent);
+    let mut task = Engram::with_storage(&mut *storage, agent).create_task(TaskDraft {
+        description: description_val.unwrap_or_default(),
+        priority: priority_enum,
+        parent,
+        tags: tags
+            .map(|tags_str| tags_str.split(',').map(|s| s.trim().to_string()).collect())
+            .unwrap_or_default(),
+        estimate_seconds: estimate.as_deref().map(parse_duration_secs).transpose()?,
+        ..TaskDraft::new(final_title)
+    })?;
 
-    let generic = task.to_generic();
-    storage.store(&generic)?;
+    let branch_name = if branch {
+        let name = crate::branch::branch_name_for(&task);
+        crate::branch::create_and_checkout(&name)?;
+        let index_path = std::path::Path::new(crate::branch::BRANCH_INDEX_FILE);
+        let mut index = crate::branch::BranchIndex::load(index_path)?;
+        crate::branch::bind_branch(storage, &mut index, &task.id, &name)?;
+        index.save(index_path)?;
+        task.metadata.insert(
+            crate::branch::BRANCH_METADATA.to_string(),
+            serde_json::json!(name),
+        );
+        Some(name)
+    } else {
+        None
+    };
 
     if output_format == "json" {
         println!("{}", serde_json::to_string_pretty(&task).unwrap());
     } else {
         println!("✅ Task created:");
-        display_task(&task);
+        display_task(&task, None);
+        if let Some(name) = branch_name {
+            println!("🌿 Checked out branch {}", name);
+        }
+    }
+
+    Ok(())
+}
+
+/// Resolve an optional task ID, defaulting to the task bound to the
+/// current git branch
+pub fn resolve_task_or_branch<S: Storage + ?Sized>(
+    storage: &S,
+    id: Option<String>,
+) -> Result<String, EngramError> {
+    if let Some(id) = id {
+        return Ok(id);
+    }
+    let branch = crate::branch::current_branch().ok_or_else(|| {
+        EngramError::Validation("No task ID given and no git branch checked out".to_string())
+    })?;
+    match crate::branch::current_branch_task(storage)? {
+        Some(task) => Ok(task.id),
+        None => Err(EngramError::Validation(format!(
+            "No task ID given and branch '{}' has no bound task (see `engram task bind-branch`)",
+            branch
+        ))),
+    }
+}
+
+/// Handle `engram task bind-branch`
+pub fn bind_branch_command<S: Storage>(
+    storage: &mut S,
+    id: &str,
+    branch: Option<String>,
+) -> Result<(), EngramError> {
+    let branch = match branch {
+        Some(branch) => branch,
+        None => crate::branch::current_branch().ok_or_else(|| {
+            EngramError::Validation("Not on a git branch; pass --branch to name one".to_string())
+        })?,
+    };
+    let index_path = std::path::Path::new(crate::branch::BRANCH_INDEX_FILE);
+    let mut index = crate::branch::BranchIndex::load(index_path)?;
+    let previous = crate::branch::bind_branch(storage, &mut index, id, &branch)?;
+    index.save(index_path)?;
+    match previous {
+        Some(previous) => println!(
+            "🌿 Task {} moved from branch {} to {}",
+            id, previous, branch
+        ),
+        None => println!("🌿 Task {} bound to branch {}", id, branch),
     }
+    Ok(())
+}
 
+/// Handle `engram task unbind-branch`
+pub fn unbind_branch_command<S: Storage>(
+    storage: &mut S,
+    id: Option<String>,
+) -> Result<(), EngramError> {
+    let id = resolve_task_or_branch(storage, id)?;
+    let index_path = std::path::Path::new(crate::branch::BRANCH_INDEX_FILE);
+    let mut index = crate::branch::BranchIndex::load(index_path)?;
+    let previous = crate::branch::unbind_branch(storage, &mut index, &id)?;
+    index.save(index_path)?;
+    match previous {
+        Some(branch) => println!("Task {} unbound from branch {}", id, branch),
+        None => println!("Task {} was not bound to a branch", id),
+    }
     Ok(())
 }
 
 /// Create multiple tasks in a batch
 #[allow(clippy::too_many_arguments)]
+#[instrument(skip_all, fields(entity_type = "task", operation = "create_batch"))]
 pub fn create_task_batch<S: Storage>(
     storage: &mut S,
     file: Option<String>,
@@ -433,6 +789,8 @@ pub fn create_task_batch<S: Storage>(
                 agent: None,
                 parent: None,
                 tags: None,
+                due_at: None,
+                estimate: None,
             })
             .collect()
     } else {
@@ -484,7 +842,7 @@ pub fn create_task_batch<S: Storage>(
         let mut task = Task::new(
             input.title.clone(),
             input.description.unwrap_or_default(),
-            input.agent.unwrap_or_else(|| "default".to_string()),
+            resolve_agent(input.agent),
             priority_enum,
             None,
         );
@@ -495,6 +853,12 @@ pub fn create_task_batch<S: Storage>(
         if let Some(tags_vec) = input.tags {
             task.tags = tags_vec;
         }
+        task.due_at = input.due_at;
+        task.estimate_seconds = input
+            .estimate
+            .as_deref()
+            .map(parse_duration_secs)
+            .transpose()?;
 
         let generic = task.to_generic();
         match storage.store(&generic) {
@@ -572,10 +936,18 @@ pub fn create_task_batch<S: Storage>(
     Ok(())
 }
 
-use crate::cli::utils::{create_table, truncate};
+use crate::cli::utils::{
+    check_duplicate, create_table, format_duration_secs, parse_duration_secs, truncate, CountArgs,
+    SortArgs,
+};
 use prettytable::row;
 
 /// List tasks command
+///
+/// `agent` is used as-is; `None` lists tasks from every agent. Callers resolve
+/// the current identity (see [`crate::cli::identity`]) before calling.
+#[allow(clippy::too_many_arguments)]
+#[instrument(skip_all, fields(entity_type = "task", operation = "list"))]
 pub fn list_tasks<S: Storage>(
     storage: &S,
     agent: Option<&str>,
@@ -587,54 +959,36 @@ pub fn list_tasks<S: Storage>(
     offset: Option<usize>,
     stale: bool,
     stale_threshold: i64,
+    search: Option<&str>,
+    fuzzy: bool,
+    sort: &SortArgs,
+    count: &CountArgs,
     output_format: &str,
 ) -> Result<(), EngramError> {
     if stale {
         return list_stale_tasks(storage, agent, stale_threshold, output_format);
     }
 
-    let effective_limit = if all { None } else { limit };
-    let mut filter = crate::storage::QueryFilter {
-        entity_type: Some("task".to_string()),
-        agent: Some(agent.unwrap_or("default").to_string()),
-        limit: effective_limit,
+    let task_filter = TaskFilter {
+        agent: agent.map(str::to_string),
+        status: status.map(parse_status_filter).transpose()?,
+        workflow_instance_id: workflow_instance_id.map(str::to_string),
+        workflow_state: workflow_state.map(str::to_string),
+        search: search.map(str::to_string),
+        fuzzy,
+        sort_by: sort.sort.clone(),
+        sort_order: sort.sort_order(),
+        limit: if all { None } else { limit },
         offset,
-        ..Default::default()
     };
+    let filter = task_filter.to_query_filter();
 
-    if let Some(wf_id) = workflow_instance_id {
-        filter.field_filters.insert(
-            "workflow_id".to_string(),
-            serde_json::Value::String(wf_id.to_string()),
-        );
-    }
-
-    let result = storage.query(&filter)?;
-
-    let mut tasks: Vec<_> = result.entities;
-    if let Some(status_filter) = status {
-        tasks.retain(|generic_task| {
-            if let Ok(task_obj) = Task::from_generic(generic_task.clone()) {
-                format!("{:?}", task_obj.status).to_lowercase() == status_filter.to_lowercase()
-            } else {
-                false
-            }
-        });
+    if count.write(&mut std::io::stdout(), storage, &filter)? {
+        return Ok(());
     }
 
-    if let Some(wf_state) = workflow_state {
-        tasks.retain(|generic_task| {
-            if let Ok(task_obj) = Task::from_generic(generic_task.clone()) {
-                task_obj
-                    .workflow_state
-                    .as_deref()
-                    .map(|s| s == wf_state)
-                    .unwrap_or(false)
-            } else {
-                false
-            }
-        });
-    }
+    let result = task_filter.query(storage)?;
+    let tasks = result.entities;
 
     if tasks.is_empty() {
         println!("No tasks found");
@@ -647,36 +1001,49 @@ pub fn list_tasks<S: Storage>(
         tasks.len()
     );
 
+    let critical = critical_task_ids(storage);
+
     let mut table = create_table();
     table.set_titles(row![
-        "ID", "Status", "Priority", "Title", "Agent", "Created"
+        "ID", "Status", "Priority", "Estimate", "Title", "Agent", "Created"
     ]);
 
-    for generic_task in &tasks {
-        if let Ok(task) = Task::from_generic(generic_task.clone()) {
-            let status_emoji = match task.status {
-                crate::entities::TaskStatus::Todo => "📝 Todo",
-                crate::entities::TaskStatus::InProgress => "🚧 In Progress",
-                crate::entities::TaskStatus::Done => "✅ Done",
-                crate::entities::TaskStatus::Blocked => "⛔ Blocked",
-                crate::entities::TaskStatus::Cancelled => "❌ Cancelled",
-            };
+    for task in tasks {
+        let status_emoji = match task.status {
+            crate::entities::TaskStatus::Todo => "📝 Todo",
+            crate::entities::TaskStatus::InProgress => "🚧 In Progress",
+            crate::entities::TaskStatus::Done => "✅ Done",
+            crate::entities::TaskStatus::Blocked => "⛔ Blocked",
+            crate::entities::TaskStatus::Cancelled => "❌ Cancelled",
+        };
 
-            let priority_str = format!("{:?}", task.priority);
+        let priority_str = format!("{:?}", task.priority);
 
-            table.add_row(row![
-                &task.id[..8],
-                status_emoji,
-                priority_str,
-                truncate(&task.title, 40),
-                truncate(&task.agent, 10),
-                task.start_time.format("%Y-%m-%d")
-            ]);
-        }
+        let title = if critical.contains(&task.id) {
+            format!("⚡ {}", truncate(&task.title, 38))
+        } else {
+            truncate(&task.title, 40)
+        };
+
+        table.add_row(row![
+            &task.id[..8],
+            status_emoji,
+            priority_str,
+            task.estimate_seconds
+                .map(format_duration_secs)
+                .unwrap_or_else(|| "-".to_string()),
+            title,
+            truncate(&task.agent, 10),
+            task.start_time.format("%Y-%m-%d")
+        ]);
     }
 
     table.printstd();
 
+    if !critical.is_empty() {
+        println!("⚡ on a critical path");
+    }
+
     if result.has_more {
         println!("(More results available — use --all, --offset N, or --limit N)");
     }
@@ -684,172 +1051,1381 @@ pub fn list_tasks<S: Storage>(
     Ok(())
 }
 
-fn list_stale_tasks<S: Storage>(
-    storage: &S,
-    _agent: Option<&str>,
-    stale_threshold: i64,
-    output_format: &str,
-) -> Result<(), EngramError> {
-    let report = StaleTaskReport::detect(storage, stale_threshold)?;
-
-    match output_format {
-        "json" => {
-            println!("{}", report.to_pretty_json());
-        }
-        _ => {
-            println!("{}", report.summary());
-            println!();
-
-            if report.stale_tasks.is_empty() {
-                println!("  No stale tasks found.");
-            } else {
-                let mut table = create_table();
-                table.set_titles(row![
-                    "ID",
-                    "Age (h)",
-                    "Last Commit",
-                    "Title",
-                    "Agent",
-                    "Reason"
-                ]);
+/// `--search` value that lists recorded search terms instead of tasks
+pub const SEARCH_HISTORY_QUERY: &str = "?";
 
-                for entry in &report.stale_tasks {
-                    let last_commit_str = entry
-                        .last_git_commit
-                        .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
-                        .unwrap_or_else(|| "never".to_string());
+/// Search terms shown by `--search ?` and `--suggest`
+pub const MAX_SEARCH_SUGGESTIONS: usize = 10;
 
-                    table.add_row(row![
-                        &entry.task_id[..8],
-                        format!("{:.1}", entry.age_hours),
-                        last_commit_str,
-                        truncate(&entry.title, 35),
-                        truncate(&entry.agent, 10),
-                        truncate(&entry.staleness_reason, 40),
-                    ]);
-                }
+/// Records an agent's `task list --search` terms as `search_history`
+/// entities and suggests previous terms, most used first
+pub struct SearchSuggestionEngine {
+    agent: String,
+}
 
-                table.printstd();
-            }
+impl SearchSuggestionEngine {
+    #[instrument(
+        level = "trace",
+        skip_all,
+        fields(entity_type = "search_history", operation = "new")
+    )]
+    pub fn new(agent: impl Into<String>) -> Self {
+        Self {
+            agent: agent.into(),
         }
     }
 
-    Ok(())
-}
+    fn normalize(term: &str) -> String {
+        term.trim().to_lowercase()
+    }
 
-pub fn show_task<S: Storage + RelationshipStorage + 'static>(
-    storage: &S,
-    id: &str,
-) -> Result<(), EngramError> {
-    if let Some(generic_task) = storage.get(id, "task")? {
-        if let Ok(task_obj) = Task::from_generic(generic_task) {
-            println!("📋 Task Details:");
-            display_task(&task_obj);
-
-            // ── Related entities via relationship graph ──────────────────────
-            let relationships = storage.get_entity_relationships(id).unwrap_or_default();
-            if !relationships.is_empty() {
-                println!("🔗 Related Entities:");
-                println!("====================");
-                for rel in &relationships {
-                    let direction = if rel.source_id == id { "→" } else { "←" };
-                    let other_id = if rel.source_id == id {
-                        &rel.target_id
-                    } else {
-                        &rel.source_id
-                    };
-                    let rel_type = format!("{}", rel.relationship_type);
+    /// The agent's recorded terms, most used first, then most recent
+    #[instrument(skip_all, fields(entity_type = "search_history", operation = "history", agent = %self.agent))]
+    pub fn history<S: Storage + ?Sized>(
+        &self,
+        storage: &S,
+    ) -> Result<Vec<SearchHistory>, EngramError> {
+        let mut history: Vec<SearchHistory> = storage
+            .get_all(SearchHistory::entity_type())?
+            .into_iter()
+            .filter(|generic| generic.agent == self.agent)
+            .filter_map(|generic| SearchHistory::from_generic(generic).ok())
+            .collect();
+        history.sort_by(|a, b| {
+            b.count
+                .cmp(&a.count)
+                .then(b.last_used.cmp(&a.last_used))
+                .then(a.term.cmp(&b.term))
+        });
+        Ok(history)
+    }
 
-                    // Try to resolve entity title/summary
-                    let label = resolve_entity_label(storage, other_id);
-                    println!("  {} [{}] {} {}", direction, rel_type, other_id, label);
-                }
-                println!();
+    /// Count a use of `term`. Blank terms and the history query are ignored.
+    #[instrument(skip_all, fields(entity_type = "search_history", operation = "record", agent = %self.agent))]
+    pub fn record<S: Storage + ?Sized>(
+        &self,
+        storage: &mut S,
+        term: &str,
+    ) -> Result<Option<SearchHistory>, EngramError> {
+        let term = Self::normalize(term);
+        if term.is_empty() || term == SEARCH_HISTORY_QUERY {
+            return Ok(None);
+        }
+        let entry = match self.history(storage)?.into_iter().find(|h| h.term == term) {
+            Some(mut existing) => {
+                existing.record_use();
+                existing
             }
+            None => SearchHistory::new(term, self.agent.clone()),
+        };
+        storage.store(&entry.to_generic())?;
+        Ok(Some(entry))
+    }
 
-            // ── Associated workflow instances ────────────────────────────────
-            println!("🔄 Associated Workflows:");
-            println!("=======================");
+    /// The `limit` most used terms
+    #[instrument(skip_all, fields(entity_type = "search_history", operation = "top", agent = %self.agent))]
+    pub fn top<S: Storage + ?Sized>(
+        &self,
+        storage: &S,
+        limit: usize,
+    ) -> Result<Vec<SearchHistory>, EngramError> {
+        let mut history = self.history(storage)?;
+        history.truncate(limit);
+        Ok(history)
+    }
 
-            let instances: Vec<_> = storage
-                .get_all("workflow_instance")
-                .unwrap_or_else(|_| Vec::new())
-                .into_iter()
-                .filter_map(|e| crate::entities::WorkflowInstance::from_generic(e).ok())
-                .filter(|instance| instance.context.entity_id.as_deref() == Some(id))
-                .collect();
+    /// Previous terms containing `partial`, other than `partial` itself
+    #[instrument(skip_all, fields(entity_type = "search_history", operation = "suggest", agent = %self.agent))]
+    pub fn suggest<S: Storage + ?Sized>(
+        &self,
+        storage: &S,
+        partial: &str,
+        limit: usize,
+    ) -> Result<Vec<SearchHistory>, EngramError> {
+        let partial = Self::normalize(partial);
+        let mut matches: Vec<SearchHistory> = self
+            .history(storage)?
+            .into_iter()
+            .filter(|h| h.term != partial && h.term.contains(&partial))
+            .collect();
+        matches.truncate(limit);
+        Ok(matches)
+    }
 
-            if instances.is_empty() {
-                println!("  No active workflows associated with this task.");
-            } else {
-                for instance in &instances {
-                    let status_icon = match instance.status {
-                        crate::engines::workflow_engine::WorkflowStatus::Running => "🟢",
-                        crate::engines::workflow_engine::WorkflowStatus::Completed => "🎯",
-                        crate::engines::workflow_engine::WorkflowStatus::Failed(_) => "💥",
-                        crate::engines::workflow_engine::WorkflowStatus::Suspended(_) => "⏸️",
-                        crate::engines::workflow_engine::WorkflowStatus::Cancelled => "❌",
-                    };
-
-                    println!(
-                        "  {} Workflow: {} [{}]",
-                        status_icon, instance.workflow_id, instance.status
-                    );
-                    println!(
-                        "     State: {} | Started: {}",
-                        instance.current_state,
-                        instance.started_at.format("%Y-%m-%d %H:%M")
-                    );
-                    println!("     Instance ID: {}", instance.id);
-                    println!();
-                }
-            }
-        } else {
-            return Err(EngramError::Validation("Invalid task type".to_string()));
+    /// Delete all of the agent's recorded terms, returning how many were removed
+    #[instrument(skip_all, fields(entity_type = "search_history", operation = "clear", agent = %self.agent))]
+    pub fn clear<S: Storage + ?Sized>(&self, storage: &mut S) -> Result<usize, EngramError> {
+        let history = self.history(storage)?;
+        for entry in &history {
+            storage.delete(&entry.id, SearchHistory::entity_type())?;
         }
-    } else {
-        return Err(EngramError::NotFound(format!("Task '{}' not found", id)));
+        Ok(history.len())
     }
+}
 
-    Ok(())
+fn print_search_terms(terms: &[SearchHistory]) {
+    for entry in terms {
+        println!(
+            "  {:<30} {:>4}x  last {}",
+            truncate(&entry.term, 30),
+            entry.count,
+            entry.last_used.format("%Y-%m-%d")
+        );
+    }
 }
 
-/// Attempt to resolve a human-readable label for any entity ID.
-fn resolve_entity_label<S: Storage>(storage: &S, id: &str) -> String {
-    // Try each known entity type and extract a title field
-    for entity_type in &[
-        "reasoning",
-        "context",
-        "adr",
-        "task",
-        "rule",
-        "standard",
-        "knowledge",
-    ] {
-        if let Ok(Some(generic)) = storage.get(id, entity_type) {
-            let title = generic
-                .data
-                .get("title")
-                .and_then(|v| v.as_str())
-                .map(|s| format!("— {}", s))
-                .unwrap_or_default();
-            return format!("({}){}", entity_type, title);
+/// Search history side of `engram task list`: clears or lists recorded terms,
+/// or records `search` and prints suggestions. Returns `true` when the task
+/// list itself should be skipped.
+#[instrument(skip_all, fields(entity_type = "task", operation = "search_history", agent = %agent))]
+pub fn handle_search_history<S: Storage>(
+    storage: &mut S,
+    agent: &str,
+    search: Option<&str>,
+    suggest: bool,
+    clear: bool,
+) -> Result<bool, EngramError> {
+    let engine = SearchSuggestionEngine::new(agent);
+    if clear {
+        let removed = engine.clear(storage)?;
+        println!("🧹 Cleared {} search history entries", removed);
+        return Ok(true);
+    }
+    let Some(search) = search else {
+        return Ok(false);
+    };
+    if search.trim() == SEARCH_HISTORY_QUERY {
+        let top = engine.top(storage, MAX_SEARCH_SUGGESTIONS)?;
+        if top.is_empty() {
+            println!("No search history");
+        } else {
+            println!("🔎 Most used searches:");
+            print_search_terms(&top);
         }
+        return Ok(true);
     }
-    String::new()
+    if suggest {
+        let suggestions = engine.suggest(storage, search, MAX_SEARCH_SUGGESTIONS)?;
+        if !suggestions.is_empty() {
+            println!("💡 Did you mean:");
+            print_search_terms(&suggestions);
+            println!();
+        }
+    }
+    engine.record(storage, search)?;
+    Ok(false)
 }
 
-/// Update task command
-pub fn update_task<S: Storage>(
-    storage: &mut S,
-    id: &str,
+/// IDs of tasks on the critical path of any terminal task in the dependency
+/// graph. Empty when there are no dependencies or the graph has a cycle.
+fn critical_task_ids<S: Storage>(storage: &S) -> std::collections::HashSet<String> {
+    let mut critical = std::collections::HashSet::new();
+    let Ok(graph) = DependencyGraph::load(storage) else {
+        return critical;
+    };
+    if !graph.has_dependencies() {
+        return critical;
+    }
+    let fallback = fallback_duration_seconds(storage)
+        .unwrap_or(crate::entities::DEFAULT_TASK_DURATION_SECONDS);
+    for terminal in graph.terminal_tasks() {
+        if let Ok(result) = graph.critical_path(&terminal, fallback) {
+            critical.extend(result.path);
+        }
+    }
+    critical
+}
+
+const GANTT_WIDTH: usize = 40;
+
+fn format_seconds(seconds: f64) -> String {
+    let seconds = seconds.max(0.0).round() as u64;
+    if seconds < 60 {
+        return format!("{}s", seconds);
+    }
+    let hours = seconds / 3600;
+    let minutes = (seconds % 3600) / 60;
+    if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+/// Render a critical path as a Gantt-style timeline. Critical tasks are
+/// drawn with `█`, others with `▒` followed by `·` for their slack.
+#[instrument(
+    level = "trace",
+    skip_all,
+    fields(entity_type = "task", operation = "render_critical_path")
+)]
+pub fn render_critical_path(result: &CriticalPathResult, graph: &DependencyGraph) -> String {
+    use std::fmt::Write as _;
+
+    let total = result.total_estimated_duration_seconds.max(1.0);
+    let scale = |seconds: f64| ((seconds / total) * GANTT_WIDTH as f64).round() as usize;
+
+    let mut ids: Vec<&String> = result.earliest_start.keys().collect();
+    ids.sort_by(|a, b| {
+        result.earliest_start[*a]
+            .partial_cmp(&result.earliest_start[*b])
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| result.is_critical(b).cmp(&result.is_critical(a)))
+            .then_with(|| a.cmp(b))
+    });
+
+    let mut out = String::new();
+    let terminal = result.path.last().map(String::as_str).unwrap_or("");
+    let _ = writeln!(
+        out,
+        "Critical path to {} ({}): {} total",
+        graph
+            .task(terminal)
+            .map(|t| t.title.as_str())
+            .unwrap_or(terminal),
+        &terminal[..terminal.len().min(8)],
+        format_seconds(result.total_estimated_duration_seconds)
+    );
+    for id in ids {
+        let start = scale(result.earliest_start[id]).min(GANTT_WIDTH);
+        let len = scale(result.durations[id])
+            .max(1)
+            .min(GANTT_WIDTH + 1 - start.min(GANTT_WIDTH));
+        let slack = result.slack.get(id).copied().unwrap_or(0.0);
+        let critical = result.is_critical(id);
+        let (marker, fill) = if critical { ('*', '█') } else { (' ', '▒') };
+        let slack_len = scale(slack).min(GANTT_WIDTH.saturating_sub(start + len));
+
+        let mut bar = " ".repeat(start);
+        bar.extend(std::iter::repeat_n(fill, len));
+        bar.extend(std::iter::repeat_n('·', slack_len));
+        let pad = (GANTT_WIDTH + 1).saturating_sub(start + len + slack_len);
+        bar.push_str(&" ".repeat(pad));
+
+        let title = graph.task(id).map(|t| t.title.as_str()).unwrap_or("?");
+        let _ = write!(
+            out,
+            "{} {:<8} {:<24} |{}| {}",
+            marker,
+            &id[..id.len().min(8)],
+            truncate(title, 24),
+            bar,
+            format_seconds(result.durations[id])
+        );
+        if slack > 0.0 {
+            let _ = write!(out, " (slack {})", format_seconds(slack));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Show the critical path ending at `id`
+#[instrument(skip_all, fields(entity_type = "task", operation = "critical_path", id = %id))]
+pub fn show_critical_path<S: Storage>(
+    storage: &S,
+    id: &str,
+    output_format: &str,
+) -> Result<(), EngramError> {
+    let graph = DependencyGraph::load(storage)?;
+    let result = graph.critical_path(id, fallback_duration_seconds(storage)?)?;
+
+    match output_format {
+        "json" => println!("{}", serde_json::to_string_pretty(&result)?),
+        _ => print!("{}", render_critical_path(&result, &graph)),
+    }
+    Ok(())
+}
+
+/// Time covered by one unit of a task Gantt chart
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GanttUnit {
+    Hour,
+    Day,
+    Week,
+}
+
+impl std::str::FromStr for GanttUnit {
+    type Err = EngramError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "hour" | "hours" => Ok(GanttUnit::Hour),
+            "day" | "days" => Ok(GanttUnit::Day),
+            "week" | "weeks" => Ok(GanttUnit::Week),
+            _ => Err(EngramError::Validation(format!(
+                "Invalid Gantt unit '{}'. Use hour, day or week",
+                s
+            ))),
+        }
+    }
+}
+
+impl GanttUnit {
+    fn duration(self) -> chrono::Duration {
+        match self {
+            GanttUnit::Hour => chrono::Duration::hours(1),
+            GanttUnit::Day => chrono::Duration::days(1),
+            GanttUnit::Week => chrono::Duration::weeks(1),
+        }
+    }
+
+    /// Start of the unit containing `time`; weeks start on Monday
+    fn floor(self, time: DateTime<Utc>) -> DateTime<Utc> {
+        use chrono::{Datelike, Timelike};
+        let hour = time
+            .with_minute(0)
+            .and_then(|t| t.with_second(0))
+            .and_then(|t| t.with_nanosecond(0))
+            .unwrap_or(time);
+        match self {
+            GanttUnit::Hour => hour,
+            GanttUnit::Day => hour.with_hour(0).unwrap_or(hour),
+            GanttUnit::Week => {
+                let day = hour.with_hour(0).unwrap_or(hour);
+                day - chrono::Duration::days(day.weekday().num_days_from_monday() as i64)
+            }
+        }
+    }
+
+    fn label(self, time: DateTime<Utc>) -> String {
+        match self {
+            GanttUnit::Hour => time.format("%H:00").to_string(),
+            GanttUnit::Day | GanttUnit::Week => time.format("%m-%d").to_string(),
+        }
+    }
+}
+
+/// One task's span in a Gantt chart
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct GanttBar {
+    pub id: String,
+    pub title: String,
+    pub status: TaskStatus,
+    pub agent: String,
+    pub start: String,
+    /// Completion time, else due time, else now for open tasks
+    pub end: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due_at: Option<String>,
+}
+
+/// Gantt chart data for programmatic consumption
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct GanttData {
+    pub tasks: Vec<GanttBar>,
+    pub date_range: (String, String),
+}
+
+/// Width of the id and title columns before a Gantt row's bar
+const GANTT_LABEL_WIDTH: usize = 8 + 1 + 24 + 1;
+
+fn gantt_end(task: &Task, now: DateTime<Utc>) -> DateTime<Utc> {
+    task.end_time
+        .or(task.due_at)
+        .unwrap_or(now)
+        .max(task.start_time)
+}
+
+fn sorted_by_start(tasks: &[Task]) -> Vec<&Task> {
+    let mut sorted: Vec<&Task> = tasks.iter().collect();
+    sorted.sort_by(|a, b| a.start_time.cmp(&b.start_time).then(a.title.cmp(&b.title)));
+    sorted
+}
+
+/// Gantt data for `tasks`, sorted by start time
+#[instrument(
+    level = "trace",
+    skip_all,
+    fields(entity_type = "task", operation = "gantt_data")
+)]
+pub fn gantt_data(tasks: &[Task], now: DateTime<Utc>) -> GanttData {
+    let sorted = sorted_by_start(tasks);
+    let start = sorted.iter().map(|t| t.start_time).min().unwrap_or(now);
+    let end = sorted
+        .iter()
+        .map(|t| gantt_end(t, now))
+        .max()
+        .unwrap_or(now);
+    GanttData {
+        tasks: sorted
+            .into_iter()
+            .map(|task| GanttBar {
+                id: task.id.clone(),
+                title: task.title.clone(),
+                status: task.status.clone(),
+                agent: task.agent.clone(),
+                start: task.start_time.to_rfc3339(),
+                end: gantt_end(task, now).to_rfc3339(),
+                due_at: task.due_at.map(|d| d.to_rfc3339()),
+            })
+            .collect(),
+        date_range: (start.to_rfc3339(), end.to_rfc3339()),
+    }
+}
+
+/// Render `tasks` as an ASCII Gantt chart at most `width` columns wide
+///
+/// Each row spans a task's start to its completion (or due) time. The part
+/// of the span already in the past is drawn with `░` and the part still
+/// ahead with `█`.
+#[instrument(
+    level = "trace",
+    skip_all,
+    fields(entity_type = "task", operation = "render_gantt")
+)]
+pub fn render_gantt(tasks: &[Task], width: u16, unit: GanttUnit) -> String {
+    render_gantt_at(tasks, width, unit, Utc::now())
+}
+
+fn render_gantt_at(tasks: &[Task], width: u16, unit: GanttUnit, now: DateTime<Utc>) -> String {
+    use std::fmt::Write as _;
+
+    if tasks.is_empty() {
+        return "No tasks to chart\n".to_string();
+    }
+    let sorted = sorted_by_start(tasks);
+    let range_start = unit.floor(sorted.iter().map(|t| t.start_time).min().unwrap_or(now));
+    let latest = sorted
+        .iter()
+        .map(|t| gantt_end(t, now))
+        .max()
+        .unwrap_or(now);
+    let mut range_end = unit.floor(latest);
+    if range_end < latest || range_end == range_start {
+        range_end += unit.duration();
+    }
+
+    let unit_seconds = unit.duration().num_seconds() as f64;
+    let units = ((range_end - range_start).num_seconds() as f64 / unit_seconds).round();
+    let columns = (width as usize)
+        .saturating_sub(GANTT_LABEL_WIDTH + 2)
+        .max(10);
+    // Whole columns per unit keep bar lengths proportional when the range fits
+    let per_unit = if units <= columns as f64 {
+        (columns as f64 / units).floor()
+    } else {
+        columns as f64 / units
+    };
+    let chart_width = (units * per_unit).round() as usize;
+    let column = |time: DateTime<Utc>| {
+        let offset = (time - range_start).num_seconds() as f64 / unit_seconds;
+        ((offset * per_unit).round() as usize).min(chart_width)
+    };
+
+    let mut out = format!(
+        "Tasks from {} to {} (1 {} = {} column(s))\n",
+        range_start.format("%Y-%m-%d %H:%M"),
+        range_end.format("%Y-%m-%d %H:%M"),
+        format!("{:?}", unit).to_lowercase(),
+        if per_unit >= 1.0 {
+            format!("{}", per_unit)
+        } else {
+            format!("{:.2}", per_unit)
+        }
+    );
+
+    let mut header = vec![' '; chart_width];
+    let mut tick = range_start;
+    let mut next_free = 0;
+    while tick < range_end {
+        let col = column(tick);
+        let label = unit.label(tick);
+        if col >= next_free && col + label.len() <= chart_width {
+            for (i, ch) in label.chars().enumerate() {
+                header[col + i] = ch;
+            }
+            next_free = col + label.len() + 1;
+        }
+        tick += unit.duration();
+    }
+    let _ = writeln!(
+        out,
+        "{} |{}|",
+        " ".repeat(GANTT_LABEL_WIDTH - 1),
+        header.into_iter().collect::<String>()
+    );
+
+    let now_col = if now <= range_start {
+        0
+    } else {
+        column(now.min(range_end))
+    };
+    for task in sorted {
+        let start = column(task.start_time).min(chart_width.saturating_sub(1));
+        let end = column(gantt_end(task, now)).max(start + 1).min(chart_width);
+        let mut bar = " ".repeat(start);
+        bar.extend((start..end).map(|col| if col < now_col { '░' } else { '█' }));
+        bar.push_str(&" ".repeat(chart_width - end));
+        let _ = writeln!(
+            out,
+            "{:<8} {:<24} |{}|",
+            &task.id[..task.id.len().min(8)],
+            truncate(&task.title, 24),
+            bar
+        );
+    }
+    out
+}
+
+/// Show a Gantt chart of tasks active within the last `days` days
+#[instrument(skip_all, fields(entity_type = "task", operation = "gantt"))]
+pub fn show_gantt<S: Storage>(
+    storage: &S,
+    agent: Option<String>,
+    days: i64,
+    unit: &str,
+    json: bool,
+) -> Result<(), EngramError> {
+    let unit: GanttUnit = unit.parse()?;
+    let agent = crate::cli::identity::resolve_agent_filter(agent);
+    let now = Utc::now();
+    let cutoff = now - chrono::Duration::days(days);
+    let tasks: Vec<Task> = storage
+        .get_all(Task::entity_type())?
+        .into_iter()
+        .filter_map(|generic| Task::from_generic(generic).ok())
+        .filter(|task| agent.as_ref().is_none_or(|agent| task.agent == *agent))
+        .filter(|task| task.status != TaskStatus::Cancelled && gantt_end(task, now) >= cutoff)
+        .collect();
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&gantt_data(&tasks, now))?
+        );
+    } else {
+        let width = terminal_size::terminal_size()
+            .map(|(terminal_size::Width(w), _)| w)
+            .unwrap_or(80);
+        print!("{}", render_gantt_at(&tasks, width, unit, now));
+    }
+    Ok(())
+}
+
+fn list_stale_tasks<S: Storage>(
+    storage: &S,
+    _agent: Option<&str>,
+    stale_threshold: i64,
+    output_format: &str,
+) -> Result<(), EngramError> {
+    let report = StaleTaskReport::detect(storage, stale_threshold)?;
+
+    match output_format {
+        "json" => {
+            println!("{}", report.to_pretty_json());
+        }
+        _ => {
+            println!("{}", report.summary());
+            println!();
+
+            if report.stale_tasks.is_empty() {
+                println!("  No stale tasks found.");
+            } else {
+                let mut table = create_table();
+                table.set_titles(row![
+                    "ID",
+                    "Age (h)",
+                    "Last Commit",
+                    "Title",
+                    "Agent",
+                    "Reason"
+                ]);
+
+                for entry in &report.stale_tasks {
+                    let last_commit_str = entry
+                        .last_git_commit
+                        .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+                        .unwrap_or_else(|| "never".to_string());
+
+                    table.add_row(row![
+                        &entry.task_id[..8],
+                        format!("{:.1}", entry.age_hours),
+                        last_commit_str,
+                        truncate(&entry.title, 35),
+                        truncate(&entry.agent, 10),
+                        truncate(&entry.staleness_reason, 40),
+                    ]);
+                }
+
+                table.printstd();
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Handle `engram task age`: report the tasks the workspace aging policy
+/// bumps, storing the bumps when `apply` is set
+pub fn age_tasks_command<S: Storage>(
+    storage: &mut S,
+    apply: bool,
+    json: bool,
+) -> Result<(), EngramError> {
+    use crate::aging::{age_tasks, priority_label, AgingPolicy};
+
+    let policy = AgingPolicy::load(std::path::Path::new("."))?;
+    if !policy.enabled {
+        println!("Priority aging is disabled (set aging.enabled in engram.yaml)");
+        return Ok(());
+    }
+    let bumps = age_tasks(storage, &policy, Utc::now(), apply)?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&bumps)?);
+        return Ok(());
+    }
+    if bumps.is_empty() {
+        println!(
+            "No tasks idle for {} day(s) below {} priority",
+            policy.after_days,
+            priority_label(&policy.max_priority)
+        );
+        return Ok(());
+    }
+
+    let mut table = create_table();
+    table.set_titles(row!["ID", "Idle (days)", "From", "To", "Title"]);
+    for bump in &bumps {
+        table.add_row(row![
+            &bump.task_id[..8.min(bump.task_id.len())],
+            bump.idle_days,
+            priority_label(&bump.from),
+            priority_label(&bump.to),
+            truncate(&bump.title, 40),
+        ]);
+    }
+    table.printstd();
+    if apply {
+        println!("⬆️  Bumped {} task(s)", bumps.len());
+    } else {
+        println!(
+            "{} task(s) would be bumped; run with --apply to store",
+            bumps.len()
+        );
+    }
+    Ok(())
+}
+
+/// Register a recurrence for an existing task
+#[instrument(skip_all, fields(entity_type = "recurring_task", operation = "create", base_task = %base_task))]
+pub fn create_recurring_task<S: Storage>(
+    storage: &mut S,
+    base_task: &str,
+    schedule: Option<String>,
+    max_instances: Option<u32>,
+    carry_over_labels: bool,
+    agent: Option<String>,
+) -> Result<(), EngramError> {
+    if storage.get(base_task, Task::entity_type())?.is_none() {
+        return Err(EngramError::not_found("task", base_task));
+    }
+
+    let trigger = match schedule {
+        Some(expression) => RecurrenceTrigger::OnSchedule(expression),
+        None => RecurrenceTrigger::OnCompletion,
+    };
+    let config = RecurringTaskConfig::new(
+        base_task.to_string(),
+        trigger,
+        max_instances,
+        carry_over_labels,
+        resolve_agent(agent),
+    )?;
+    storage.store(&config.to_generic())?;
+
+    println!("Recurring task created: {}", config.id);
+    println!("  Base task: {}", config.base_task_id);
+    println!("  Schedule: {}", config.schedule);
+    if let Some(next) = config.next_trigger {
+        println!("  Next trigger: {}", next.format("%Y-%m-%d %H:%M UTC"));
+    }
+    Ok(())
+}
+
+/// List recurring tasks and when they next trigger
+#[instrument(skip_all, fields(entity_type = "recurring_task", operation = "list"))]
+pub fn list_recurring_tasks<S: Storage>(storage: &S) -> Result<(), EngramError> {
+    let mut configs: Vec<RecurringTaskConfig> = storage
+        .get_all(RecurringTaskConfig::entity_type())?
+        .into_iter()
+        .filter_map(|e| RecurringTaskConfig::from_generic(e).ok())
+        .collect();
+    if configs.is_empty() {
+        println!("No recurring tasks configured.");
+        return Ok(());
+    }
+    configs.sort_by_key(|c| c.next_trigger);
+
+    let mut table = create_table();
+    table.set_titles(row![
+        "ID",
+        "Base Task",
+        "Schedule",
+        "Next Trigger",
+        "Instances",
+        "Labels"
+    ]);
+    for config in &configs {
+        let base_title = storage
+            .get(&config.base_task_id, Task::entity_type())?
+            .and_then(|e| Task::from_generic(e).ok())
+            .map(|t| t.title)
+            .unwrap_or_else(|| config.base_task_id.clone());
+        let next = if config.is_exhausted() {
+            "finished".to_string()
+        } else {
+            match (&config.auto_create_on, config.next_trigger) {
+                (RecurrenceTrigger::OnCompletion, _) => "when current is done".to_string(),
+                (_, Some(next)) => next.format("%Y-%m-%d %H:%M UTC").to_string(),
+                (_, None) => "never".to_string(),
+            }
+        };
+        let instances = match config.max_instances {
+            Some(max) => format!("{}/{}", config.instance_ids.len(), max),
+            None => config.instance_ids.len().to_string(),
+        };
+        table.add_row(row![
+            &config.id[..8],
+            truncate(&base_title, 30),
+            config.schedule,
+            next,
+            instances,
+            if config.carry_over_labels {
+                "yes"
+            } else {
+                "no"
+            }
+        ]);
+    }
+    table.printstd();
+    Ok(())
+}
+
+/// Create due recurring task instances ahead of a mutating task command
+///
+/// A no-op on read-only or dry-run storage, so commands that must not write
+/// never create instances as a side effect. `task recurring tick` uses
+/// [`tick_recurring_tasks`] instead.
+pub fn check_recurring_tasks<S: Storage>(storage: &mut S) -> Vec<Task> {
+    if storage.is_read_only() {
+        return Vec::new();
+    }
+    tick_recurring_tasks(storage)
+}
+
+/// Create due recurring task instances, reporting each one on stderr
+///
+/// Failures are logged rather than returned so a broken recurrence never
+/// blocks the command the user asked for.
+#[instrument(skip_all, fields(entity_type = "recurring_task", operation = "check"))]
+pub fn tick_recurring_tasks<S: Storage>(storage: &mut S) -> Vec<Task> {
+    match RecurringTaskManager::tick(storage, chrono::Utc::now()) {
+        Ok(created) => {
+            for task in &created {
+                eprintln!("🔁 Created recurring task: {} ({})", task.title, task.id);
+            }
+            created
+        }
+        Err(e) => {
+            tracing::warn!("Recurring task check failed: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// Sections of `task show` that can be suppressed from the command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TaskShowSections {
+    pub links: bool,
+    pub dependencies: bool,
+    pub commits: bool,
+    pub gates: bool,
+    pub notes: bool,
+}
+
+impl Default for TaskShowSections {
+    fn default() -> Self {
+        Self {
+            links: true,
+            dependencies: true,
+            commits: true,
+            gates: true,
+            notes: true,
+        }
+    }
+}
+
+/// One resolved section of `task show`. A resolution failure is recorded in
+/// `error` instead of failing the whole command.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct TaskSection<T> {
+    pub items: Vec<T>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl<T> TaskSection<T> {
+    fn resolve<E: std::fmt::Display>(result: Result<Vec<T>, E>) -> Self {
+        match result {
+            Ok(items) => Self { items, error: None },
+            Err(error) => Self {
+                items: Vec::new(),
+                error: Some(error.to_string()),
+            },
+        }
+    }
+
+    /// Resolve a section that needs the task's relationships, which are
+    /// loaded once and shared between sections.
+    fn from_relationships(
+        relationships: &Result<Vec<EntityRelationship>, String>,
+        resolve: impl FnOnce(&[EntityRelationship]) -> Result<Vec<T>, EngramError>,
+    ) -> Self {
+        match relationships {
+            Ok(rels) => Self::resolve(resolve(rels)),
+            Err(error) => Self {
+                items: Vec::new(),
+                error: Some(error.clone()),
+            },
+        }
+    }
+}
+
+/// A context or reasoning entity linked to a task.
+#[derive(Debug, Clone, serde::Serialize, PartialEq)]
+pub struct LinkedEntity {
+    pub id: String,
+    pub title: Option<String>,
+}
+
+/// A task the shown task depends on.
+#[derive(Debug, Clone, serde::Serialize, PartialEq)]
+pub struct TaskDependency {
+    pub id: String,
+    pub title: Option<String>,
+    pub status: Option<String>,
+}
+
+/// A git commit whose message references the task.
+#[derive(Debug, Clone, serde::Serialize, PartialEq)]
+pub struct TaskCommit {
+    pub hash: String,
+    pub date: String,
+    pub subject: String,
+}
+
+/// The most recent execution of a quality gate for the task.
+#[derive(Debug, Clone, serde::Serialize, PartialEq)]
+pub struct TaskGateResult {
+    pub id: String,
+    pub quality_gate: String,
+    pub status: String,
+    pub exit_code: i32,
+    pub duration_ms: u64,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// A note recorded on the task that has not been marked resolved.
+#[derive(Debug, Clone, serde::Serialize, PartialEq)]
+pub struct TaskNote {
+    pub text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+}
+
+/// Everything `task show` reports about a task.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct TaskDetails {
+    pub task: Task,
+    pub modification: Modification,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contexts: Option<TaskSection<LinkedEntity>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reasoning: Option<TaskSection<LinkedEntity>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dependencies: Option<TaskSection<TaskDependency>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commits: Option<TaskSection<TaskCommit>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gate_results: Option<TaskSection<TaskGateResult>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<TaskSection<TaskNote>>,
+}
+
+/// Maximum number of referencing commits listed by `task show`.
+const TASK_SHOW_COMMIT_LIMIT: usize = 10;
+
+/// Resolve the details of a task. Only a missing or malformed task is an
+/// error; every other section degrades to an error message on its own.
+pub fn task_details<S: Storage + RelationshipStorage>(
+    storage: &S,
+    id: &str,
+    sections: TaskShowSections,
+) -> Result<TaskDetails, EngramError> {
+    let generic_task = storage
+        .get(id, "task")?
+        .ok_or_else(|| EngramError::not_found("task", id))?;
+    let modification = generic_task.modification();
+    let task = Task::from_generic(generic_task)
+        .map_err(|_| EngramError::Validation("Invalid task type".to_string()))?;
+
+    let relationships = storage
+        .get_entity_relationships(id)
+        .map_err(|e| e.to_string());
+    let contexts = sections.links.then(|| {
+        TaskSection::from_relationships(&relationships, |rels| {
+            linked_contexts(storage, &task, rels)
+        })
+    });
+    let reasoning = sections.links.then(|| {
+        TaskSection::from_relationships(&relationships, |rels| {
+            linked_reasoning(storage, &task, rels)
+        })
+    });
+    let dependencies = sections.dependencies.then(|| {
+        TaskSection::from_relationships(&relationships, |rels| {
+            task_dependencies(storage, &task.id, rels)
+        })
+    });
+    let commits = sections
+        .commits
+        .then(|| TaskSection::resolve(task_commits(&task.id, TASK_SHOW_COMMIT_LIMIT)));
+    let gate_results = sections
+        .gates
+        .then(|| TaskSection::resolve(latest_gate_results(storage, &task.id)));
+    let notes = sections
+        .notes
+        .then(|| TaskSection::resolve(unresolved_notes(&task)));
+
+    Ok(TaskDetails {
+        task,
+        modification,
+        contexts,
+        reasoning,
+        dependencies,
+        commits,
+        gate_results,
+        notes,
+    })
+}
+
+fn entity_title<S: Storage>(storage: &S, id: &str, entity_type: &str) -> Option<String> {
+    storage
+        .get(id, entity_type)
+        .ok()
+        .flatten()
+        .and_then(|generic| {
+            generic
+                .data
+                .get("title")
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+        })
+}
+
+/// IDs of entities of `entity_type` linked to `task_id` in either direction.
+fn related_ids(
+    relationships: &[EntityRelationship],
+    task_id: &str,
+    entity_type: &str,
+) -> Vec<String> {
+    relationships
+        .iter()
+        .filter_map(|rel| {
+            if rel.source_id == task_id && rel.target_type == entity_type {
+                Some(rel.target_id.clone())
+            } else if rel.target_id == task_id && rel.source_type == entity_type {
+                Some(rel.source_id.clone())
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+fn linked_contexts<S: Storage>(
+    storage: &S,
+    task: &Task,
+    relationships: &[EntityRelationship],
+) -> Result<Vec<LinkedEntity>, EngramError> {
+    let mut ids = task.context_ids.clone();
+    ids.extend(related_ids(relationships, &task.id, "context"));
+    let mut seen = HashSet::new();
+    Ok(ids
+        .into_iter()
+        .filter(|id| seen.insert(id.clone()))
+        .map(|id| LinkedEntity {
+            title: entity_title(storage, &id, "context"),
+            id,
+        })
+        .collect())
+}
+
+fn linked_reasoning<S: Storage>(
+    storage: &S,
+    task: &Task,
+    relationships: &[EntityRelationship],
+) -> Result<Vec<LinkedEntity>, EngramError> {
+    let mut linked: Vec<LinkedEntity> = storage
+        .get_all("reasoning")?
+        .into_iter()
+        .filter_map(|generic| crate::entities::Reasoning::from_generic(generic).ok())
+        .filter(|reasoning| reasoning.task_id == task.id)
+        .map(|reasoning| LinkedEntity {
+            id: reasoning.id,
+            title: Some(reasoning.title),
+        })
+        .collect();
+    for id in related_ids(relationships, &task.id, "reasoning") {
+        if !linked.iter().any(|entity| entity.id == id) {
+            linked.push(LinkedEntity {
+                title: entity_title(storage, &id, "reasoning"),
+                id,
+            });
+        }
+    }
+    Ok(linked)
+}
+
+fn task_dependencies<S: Storage>(
+    storage: &S,
+    task_id: &str,
+    relationships: &[EntityRelationship],
+) -> Result<Vec<TaskDependency>, EngramError> {
+    relationships
+        .iter()
+        .filter(|rel| {
+            rel.source_id == task_id
+                && rel.target_type == "task"
+                && rel.relationship_type == EntityRelationType::DependsOn
+        })
+        .map(|rel| {
+            let dependency = storage
+                .get(&rel.target_id, "task")?
+                .and_then(|generic| Task::from_generic(generic).ok());
+            Ok(TaskDependency {
+                id: rel.target_id.clone(),
+                title: dependency.as_ref().map(|task| task.title.clone()),
+                status: dependency.map(|task| format!("{:?}", task.status).to_lowercase()),
+            })
+        })
+        .collect()
+}
+
+/// Commits in the current repository whose message mentions the task ID or
+/// its eight-character short form.
+fn task_commits(task_id: &str, limit: usize) -> Result<Vec<TaskCommit>, EngramError> {
+    let short_id: String = task_id.chars().take(8).collect();
+    let output = std::process::Command::new("git")
+        .args([
+            "log",
+            &format!("--max-count={}", limit),
+            "--format=%H%x09%aI%x09%s",
+            "--fixed-strings",
+            &format!("--grep={}", task_id),
+            &format!("--grep={}", short_id),
+        ])
+        .output()
+        .map_err(|e| EngramError::Git(format!("Failed to run git log: {}", e)))?;
+    if !output.status.success() {
+        return Err(EngramError::Git(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(3, '\t');
+            Some(TaskCommit {
+                hash: parts.next()?.to_string(),
+                date: parts.next()?.to_string(),
+                subject: parts.next().unwrap_or_default().to_string(),
+            })
+        })
+        .collect())
+}
+
+/// The latest execution result of each quality gate run for the task.
+fn latest_gate_results<S: Storage>(
+    storage: &S,
+    task_id: &str,
+) -> Result<Vec<TaskGateResult>, EngramError> {
+    let mut latest: std::collections::BTreeMap<String, crate::entities::ExecutionResult> =
+        std::collections::BTreeMap::new();
+    for result in storage
+        .get_all("execution_result")?
+        .into_iter()
+        .filter_map(|generic| crate::entities::ExecutionResult::from_generic(generic).ok())
+        .filter(|result| result.task_id == task_id)
+    {
+        match latest.get(&result.quality_gate) {
+            Some(existing) if existing.timestamp >= result.timestamp => {}
+            _ => {
+                latest.insert(result.quality_gate.clone(), result);
+            }
+        }
+    }
+    Ok(latest
+        .into_values()
+        .map(|result| TaskGateResult {
+            status: match &result.validation_status {
+                crate::entities::ValidationStatus::Passed => "passed".to_string(),
+                crate::entities::ValidationStatus::Failed { reason } => {
+                    format!("failed: {}", reason)
+                }
+                crate::entities::ValidationStatus::Skipped { reason } => {
+                    format!("skipped: {}", reason)
+                }
+            },
+            id: result.id,
+            quality_gate: result.quality_gate,
+            exit_code: result.exit_code,
+            duration_ms: result.duration_ms,
+            timestamp: result.timestamp,
+        })
+        .collect())
+}
+
+/// Notes are kept in the task's `notes` metadata as plain strings or as
+/// `{"text", "author", "resolved"}` objects; plain strings count as unresolved.
+fn unresolved_notes(task: &Task) -> Result<Vec<TaskNote>, EngramError> {
+    let Some(notes) = task.metadata.get("notes") else {
+        return Ok(Vec::new());
+    };
+    let notes = notes.as_array().ok_or_else(|| {
+        EngramError::Validation("Task metadata 'notes' must be an array".to_string())
+    })?;
+    Ok(notes
+        .iter()
+        .filter_map(|note| match note {
+            serde_json::Value::String(text) => Some(TaskNote {
+                text: text.clone(),
+                author: None,
+            }),
+            serde_json::Value::Object(fields) => {
+                if fields.get("resolved").and_then(|v| v.as_bool()) == Some(true) {
+                    return None;
+                }
+                Some(TaskNote {
+                    text: fields.get("text")?.as_str()?.to_string(),
+                    author: fields
+                        .get("author")
+                        .and_then(|v| v.as_str())
+                        .map(str::to_string),
+                })
+            }
+            _ => None,
+        })
+        .collect())
+}
+
+fn print_section_error(error: &Option<String>) {
+    if let Some(error) = error {
+        println!("  ⚠️ Could not resolve: {}", error);
+    }
+}
+
+fn print_linked(heading: &str, section: &TaskSection<LinkedEntity>) {
+    if section.items.is_empty() && section.error.is_none() {
+        return;
+    }
+    println!("{}", heading);
+    println!("{}", "=".repeat(heading.chars().count()));
+    for entity in &section.items {
+        println!(
+            "  {} {}",
+            entity.id,
+            entity.title.as_deref().unwrap_or("(unresolved)")
+        );
+    }
+    print_section_error(&section.error);
+    println!();
+}
+
+/// Show task command
+#[instrument(skip_all, fields(entity_type = "task", operation = "show", id = %id))]
+pub fn show_task<S: Storage + RelationshipStorage + 'static>(
+    storage: &S,
+    id: &str,
+    sections: TaskShowSections,
+    json: bool,
+) -> Result<(), EngramError> {
+    let details = task_details(storage, id, sections)?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&details)?);
+        return Ok(());
+    }
+
+    println!("📋 Task Details:");
+    display_task(&details.task, Some(&details.modification));
+
+    // ── Related entities via relationship graph ──────────────────────────
+    if sections.links {
+        let relationships = storage.get_entity_relationships(id).unwrap_or_default();
+        if !relationships.is_empty() {
+            println!("🔗 Related Entities:");
+            println!("====================");
+            for rel in &relationships {
+                let direction = if rel.source_id == id { "→" } else { "←" };
+                let other_id = if rel.source_id == id {
+                    &rel.target_id
+                } else {
+                    &rel.source_id
+                };
+                let rel_type = format!("{}", rel.relationship_type);
+
+                // Try to resolve entity title/summary
+                let label = resolve_entity_label(storage, other_id);
+                println!("  {} [{}] {} {}", direction, rel_type, other_id, label);
+            }
+            println!();
+        }
+    }
+    if let Some(contexts) = &details.contexts {
+        print_linked("📚 Linked Contexts:", contexts);
+    }
+    if let Some(reasoning) = &details.reasoning {
+        print_linked("🧠 Linked Reasoning:", reasoning);
+    }
+
+    if let Some(dependencies) = &details.dependencies {
+        println!("⛓️ Dependencies:");
+        println!("===============");
+        if dependencies.items.is_empty() && dependencies.error.is_none() {
+            println!("  None");
+        }
+        for dependency in &dependencies.items {
+            println!(
+                "  {} [{}] {}",
+                dependency.id,
+                dependency.status.as_deref().unwrap_or("missing"),
+                dependency.title.as_deref().unwrap_or("")
+            );
+        }
+        print_section_error(&dependencies.error);
+        println!();
+    }
+
+    if let Some(commits) = &details.commits {
+        println!("📝 Recent Commits:");
+        println!("==================");
+        if commits.items.is_empty() && commits.error.is_none() {
+            println!("  No commits reference this task.");
+        }
+        for commit in &commits.items {
+            let short_hash: String = commit.hash.chars().take(8).collect();
+            println!("  {} {} {}", short_hash, commit.date, commit.subject);
+        }
+        print_section_error(&commits.error);
+        println!();
+    }
+
+    if let Some(gate_results) = &details.gate_results {
+        println!("🚦 Quality Gates:");
+        println!("=================");
+        if gate_results.items.is_empty() && gate_results.error.is_none() {
+            println!("  No quality gate results recorded.");
+        }
+        for gate in &gate_results.items {
+            let icon = if gate.status == "passed" {
+                "✅"
+            } else {
+                "❌"
+            };
+            println!(
+                "  {} {} — {} (exit {}, {}ms, {})",
+                icon,
+                gate.quality_gate,
+                gate.status,
+                gate.exit_code,
+                gate.duration_ms,
+                gate.timestamp.format("%Y-%m-%d %H:%M")
+            );
+        }
+        print_section_error(&gate_results.error);
+        println!();
+    }
+
+    if let Some(notes) = &details.notes {
+        if !notes.items.is_empty() || notes.error.is_some() {
+            println!("🗒️ Unresolved Notes:");
+            println!("====================");
+            for note in &notes.items {
+                match &note.author {
+                    Some(author) => println!("  - {} ({})", note.text, author),
+                    None => println!("  - {}", note.text),
+                }
+            }
+            print_section_error(&notes.error);
+            println!();
+        }
+    }
+
+    Ok(())
+}
+
+/// Attempt to resolve a human-readable label for any entity ID.
+fn resolve_entity_label<S: Storage>(storage: &S, id: &str) -> String {
+    // Try each known entity type and extract a title field
+    for entity_type in &[
+        "reasoning",
+        "context",
+        "adr",
+        "task",
+        "rule",
+        "standard",
+        "knowledge",
+    ] {
+        if let Ok(Some(generic)) = storage.get(id, entity_type) {
+            let title = generic
+                .data
+                .get("title")
+                .and_then(|v| v.as_str())
+                .map(|s| format!("— {}", s))
+                .unwrap_or_default();
+            return format!("({}){}", entity_type, title);
+        }
+    }
+    String::new()
+}
+
+/// Set or clear (`none`) a task's estimated effort
+pub fn set_task_estimate<S: Storage>(
+    storage: &mut S,
+    id: &str,
+    estimate: &str,
+) -> Result<(), EngramError> {
+    let generic = storage
+        .get(id, Task::entity_type())?
+        .ok_or_else(|| EngramError::not_found("task", id))?;
+    let mut task = Task::from_generic(generic)?;
+    task.estimate_seconds = if estimate.eq_ignore_ascii_case("none") {
+        None
+    } else {
+        Some(parse_duration_secs(estimate)?)
+    };
+    storage.store(&task.to_generic())?;
+    match task.estimate_seconds {
+        Some(seconds) => println!("⏱️  Estimate for {}: {}", id, format_duration_secs(seconds)),
+        None => println!("⏱️  Estimate cleared for {}", id),
+    }
+    Ok(())
+}
+
+/// Update task command
+#[instrument(skip_all, fields(entity_type = "task", operation = "update", id = %id, status = %status))]
+pub fn update_task<S: Storage>(
+    storage: &mut S,
+    id: &str,
     status: &str,
     outcome: Option<&str>,
     reason: Option<&str>,
 ) -> Result<(), EngramError> {
     let existing_generic = storage
         .get(id, "task")?
-        .ok_or_else(|| EngramError::NotFound(format!("Task '{}' not found", id)))?;
+        .ok_or_else(|| EngramError::not_found("task", id))?;
 
     if let Ok(task) = Task::from_generic(existing_generic) {
         let mut updated_task = task;
@@ -890,9 +2466,10 @@ pub fn update_task<S: Storage>(
 
         let updated_generic = updated_task.to_generic();
         storage.store(&updated_generic)?;
+        tracing::info!(task_id = %id, status = ?updated_task.status, "task updated");
 
         println!("✅ Task updated:");
-        display_task(&updated_task);
+        display_task(&updated_task, None);
 
         Ok(())
     } else {
@@ -901,6 +2478,7 @@ pub fn update_task<S: Storage>(
 }
 
 /// Archive task command (soft delete - preserves data but marks as archived)
+#[instrument(skip_all, fields(entity_type = "task", operation = "archive", id = %id))]
 pub fn archive_task<S: Storage>(
     storage: &mut S,
     id: &str,
@@ -908,7 +2486,7 @@ pub fn archive_task<S: Storage>(
 ) -> Result<(), EngramError> {
     let existing_generic = storage
         .get(id, "task")?
-        .ok_or_else(|| EngramError::NotFound(format!("Task '{}' not found", id)))?;
+        .ok_or_else(|| EngramError::not_found("task", id))?;
 
     if let Ok(task) = Task::from_generic(existing_generic) {
         let mut updated_task = task;
@@ -934,11 +2512,11 @@ pub fn archive_task<S: Storage>(
 }
 
 /// Bulk archive tasks matching filters
+#[instrument(skip_all, fields(entity_type = "task", operation = "archive_bulk"))]
 pub fn archive_tasks_bulk<S: Storage>(
     storage: &mut S,
     older_than: Option<u64>,
     status_filter: Option<&str>,
-    dry_run: bool,
     output_format: &str,
 ) -> Result<(), EngramError> {
     if older_than.is_none() && status_filter.is_none() {
@@ -998,11 +2576,7 @@ pub fn archive_tasks_bulk<S: Storage>(
 
     if output_format == "json" || output_format == "text" {
         if output_format == "text" {
-            println!(
-                "{} {} task(s) matching filters:",
-                if dry_run { "DRY RUN:" } else { "Archiving" },
-                matched.len()
-            );
+            println!("Archiving {} task(s) matching filters:", matched.len());
             let mut table = create_table();
             table.set_titles(row!["ID", "Status", "Title"]);
             for (id, title, status) in &matched {
@@ -1012,27 +2586,6 @@ pub fn archive_tasks_bulk<S: Storage>(
         }
     }
 
-    if dry_run {
-        if output_format == "json" {
-            let tasks_json: Vec<serde_json::Value> = matched
-                .iter()
-                .map(|(id, title, status)| {
-                    serde_json::json!({"id": id, "title": title, "status": status})
-                })
-                .collect();
-            println!(
-                "{}",
-                serde_json::json!({"dry_run": true, "would_archive": matched.len(), "tasks": tasks_json})
-            );
-        } else {
-            println!(
-                "\nDRY RUN: No changes made. {} task(s) would be archived.",
-                matched.len()
-            );
-        }
-        return Ok(());
-    }
-
     for (id, _title, _status) in &matched {
         if let Ok(existing) = storage.get(id, "task") {
             if let Some(generic) = existing {
@@ -1084,6 +2637,7 @@ pub fn archive_tasks_bulk<S: Storage>(
 }
 
 /// Resolve a blocked task
+#[instrument(skip_all, fields(entity_type = "task", operation = "resolve", id = %id))]
 pub fn resolve_task<S: Storage>(
     storage: &mut S,
     id: &str,
@@ -1091,7 +2645,7 @@ pub fn resolve_task<S: Storage>(
 ) -> Result<(), EngramError> {
     let existing_generic = storage
         .get(id, "task")?
-        .ok_or_else(|| EngramError::NotFound(format!("Task '{}' not found", id)))?;
+        .ok_or_else(|| EngramError::not_found("task", id))?;
 
     if let Ok(mut task) = Task::from_generic(existing_generic) {
         if task.status != crate::entities::TaskStatus::Blocked {
@@ -1163,7 +2717,7 @@ pub fn resolve_task<S: Storage>(
         storage.store(&updated_generic)?;
 
         println!("✅ Task unblocked and set to In Progress.");
-        display_task(&task);
+        display_task(&task, None);
 
         Ok(())
     } else {
@@ -1171,8 +2725,215 @@ pub fn resolve_task<S: Storage>(
     }
 }
 
+/// Title similarity above which `find_duplicate_tasks` reports a pair
+pub const DUPLICATE_TITLE_SIMILARITY: f64 = 0.8;
+
+fn load_task(storage: &dyn Storage, id: &str) -> Result<Task, EngramError> {
+    let generic = storage
+        .get(id, "task")?
+        .ok_or_else(|| EngramError::not_found("task", id))?;
+    Task::from_generic(generic)
+}
+
+/// Fold `secondary_id` into `primary_id`
+///
+/// Relationships touching the secondary task are copied onto the primary
+/// (skipping ones the primary already has and links between the two), tags
+/// are unioned and the description is combined according to `strategy`. The
+/// secondary task is kept for history but cancelled with an outcome pointing
+/// at the primary.
+#[instrument(skip_all, fields(entity_type = "task", operation = "merge", primary_id = %primary_id, secondary_id = %secondary_id))]
+pub fn merge_tasks(
+    storage: &mut dyn Storage,
+    primary_id: &str,
+    secondary_id: &str,
+    strategy: TaskMergeStrategy,
+) -> Result<Task, EngramError> {
+    if primary_id == secondary_id {
+        return Err(EngramError::Validation(
+            "Cannot merge a task into itself".to_string(),
+        ));
+    }
+
+    let mut primary = load_task(storage, primary_id)?;
+    let mut secondary = load_task(storage, secondary_id)?;
+    if secondary.status == TaskStatus::Cancelled && secondary.metadata.contains_key("merged_into") {
+        return Err(EngramError::InvalidOperation(format!(
+            "Task '{}' has already been merged",
+            secondary_id
+        )));
+    }
+
+    let relationships: Vec<EntityRelationship> = storage
+        .get_all(EntityRelationship::entity_type())?
+        .into_iter()
+        .filter_map(|e| EntityRelationship::from_generic(e).ok())
+        .collect();
+    let mut existing: HashSet<(String, String, EntityRelationType)> = relationships
+        .iter()
+        .filter(|r| r.source_id == primary_id || r.target_id == primary_id)
+        .map(|r| {
+            (
+                r.source_id.clone(),
+                r.target_id.clone(),
+                r.relationship_type.clone(),
+            )
+        })
+        .collect();
+
+    for relationship in &relationships {
+        let mut copy = relationship.clone();
+        if copy.source_id == secondary_id {
+            copy.source_id = primary_id.to_string();
+        }
+        if copy.target_id == secondary_id {
+            copy.target_id = primary_id.to_string();
+        }
+        if (copy.source_id == relationship.source_id && copy.target_id == relationship.target_id)
+            || copy.source_id == copy.target_id
+        {
+            continue;
+        }
+        let key = (
+            copy.source_id.clone(),
+            copy.target_id.clone(),
+            copy.relationship_type.clone(),
+        );
+        if !existing.insert(key) {
+            continue;
+        }
+        copy.id = uuid::Uuid::new_v4().to_string();
+        copy.timestamp = chrono::Utc::now();
+        copy.metadata.insert(
+            "copied_from".to_string(),
+            serde_json::Value::String(relationship.id.clone()),
+        );
+        storage.store(&copy.to_generic())?;
+    }
+
+    for tag in &secondary.tags {
+        primary.add_tag(tag.clone());
+    }
+
+    match strategy {
+        TaskMergeStrategy::KeepPrimary => {}
+        TaskMergeStrategy::KeepLongerDescription => {
+            if secondary.description.len() > primary.description.len() {
+                primary.description = secondary.description.clone();
+            }
+        }
+        TaskMergeStrategy::ConcatenateDescriptions => {
+            if primary.description.trim().is_empty() {
+                primary.description = secondary.description.clone();
+            } else if !secondary.description.trim().is_empty() {
+                primary.description =
+                    format!("{}\n\n{}", primary.description, secondary.description);
+            }
+        }
+    }
+
+    primary.metadata.insert(
+        "merged_from".to_string(),
+        serde_json::Value::String(secondary_id.to_string()),
+    );
+    secondary.status = TaskStatus::Cancelled;
+    secondary.end_time = Some(chrono::Utc::now());
+    secondary.outcome = Some(format!("Merged into {}", primary_id));
+    secondary.metadata.insert(
+        "merged_into".to_string(),
+        serde_json::Value::String(primary_id.to_string()),
+    );
+
+    storage.store(&primary.to_generic())?;
+    storage.store(&secondary.to_generic())?;
+    Ok(primary)
+}
+
+/// Jaccard similarity of the lowercase word sets of two titles
+#[instrument(
+    level = "trace",
+    skip_all,
+    fields(entity_type = "task", operation = "title_similarity")
+)]
+pub fn title_similarity(a: &str, b: &str) -> f64 {
+    let words = |s: &str| -> HashSet<String> {
+        s.split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .map(str::to_lowercase)
+            .collect()
+    };
+    let (a, b) = (words(a), words(b));
+    if a.is_empty() && b.is_empty() {
+        return 0.0;
+    }
+    a.intersection(&b).count() as f64 / a.union(&b).count() as f64
+}
+
+/// Pairs of open tasks whose titles are more similar than `threshold`, most
+/// similar first
+#[instrument(skip_all, fields(entity_type = "task", operation = "find_duplicates"))]
+pub fn find_duplicate_tasks(
+    storage: &dyn Storage,
+    threshold: f64,
+) -> Result<Vec<(Task, Task, f64)>, EngramError> {
+    let tasks: Vec<Task> = storage
+        .get_all("task")?
+        .into_iter()
+        .filter_map(|e| Task::from_generic(e).ok())
+        .filter(|t| !matches!(t.status, TaskStatus::Cancelled | TaskStatus::Done))
+        .collect();
+
+    let mut pairs = Vec::new();
+    for (i, a) in tasks.iter().enumerate() {
+        for b in &tasks[i + 1..] {
+            let similarity = title_similarity(&a.title, &b.title);
+            if similarity > threshold {
+                pairs.push((a.clone(), b.clone(), similarity));
+            }
+        }
+    }
+    pairs.sort_by(|x, y| y.2.total_cmp(&x.2));
+    Ok(pairs)
+}
+
+/// Handle `engram task merge`
+#[instrument(skip_all, fields(entity_type = "task", operation = "merge_command"))]
+pub fn handle_task_merge(
+    storage: &mut dyn Storage,
+    primary_id: Option<&str>,
+    secondary_id: Option<&str>,
+    strategy: TaskMergeStrategy,
+    auto_detect_duplicates: bool,
+) -> Result<(), EngramError> {
+    if auto_detect_duplicates {
+        let pairs = find_duplicate_tasks(storage, DUPLICATE_TITLE_SIMILARITY)?;
+        if pairs.is_empty() {
+            println!("No duplicate tasks found.");
+            return Ok(());
+        }
+        println!("🔍 Merge candidates ({}):", pairs.len());
+        for (a, b, similarity) in &pairs {
+            println!("  {:.0}% similar", similarity * 100.0);
+            println!("    {}  {}", a.id, a.title);
+            println!("    {}  {}", b.id, b.title);
+            println!("    engram task merge {} {}", a.id, b.id);
+        }
+        return Ok(());
+    }
+
+    let (Some(primary_id), Some(secondary_id)) = (primary_id, secondary_id) else {
+        return Err(EngramError::Validation(
+            "Both a primary and a secondary task ID are required".to_string(),
+        ));
+    };
+    let task = merge_tasks(storage, primary_id, secondary_id, strategy)?;
+    println!("✅ Merged task {} into {}", secondary_id, primary_id);
+    display_task(&task, None);
+    Ok(())
+}
+
 /// Display task information
-fn display_task(task: &Task) {
+fn display_task(task: &Task, modification: Option<&Modification>) {
     println!("  ID: {}", task.id);
     println!("  Title: {}", task.title);
     println!("  Description: {}", task.description);
@@ -1183,7 +2944,13 @@ fn display_task(task: &Task) {
         }
     }
     println!("  Priority: {:?}", task.priority);
+    if let Some(estimate) = task.estimate_seconds {
+        println!("  Estimate: {}", format_duration_secs(estimate));
+    }
     println!("  Agent: {}", task.agent);
+    if let Some(modified) = modification.and_then(Modification::summary) {
+        println!("  Last Modified By: {}", modified);
+    }
     println!(
         "  Created: {}",
         task.start_time.format("%Y-%m-%d %H:%M:%S UTC")
@@ -1206,10 +2973,120 @@ fn display_task(task: &Task) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::storage::MemoryStorage;
+    use crate::storage::{DryRunStorage, MemoryStorage};
+
+    fn create_test_storage() -> MemoryStorage {
+        MemoryStorage::new("default")
+    }
+
+    #[test]
+    fn test_check_recurring_tasks_skips_dry_run_storage() {
+        let mut memory = create_test_storage();
+        let base = Task::new(
+            "Weekly report".to_string(),
+            String::new(),
+            "default".to_string(),
+            TaskPriority::Medium,
+            None,
+        );
+        memory.store(&base.to_generic()).unwrap();
+        let mut config = RecurringTaskConfig::new(
+            base.id.clone(),
+            RecurrenceTrigger::OnSchedule("0 9 * * MON".to_string()),
+            None,
+            true,
+            "default".to_string(),
+        )
+        .unwrap();
+        config.next_trigger = Some(Utc::now() - chrono::Duration::hours(1));
+        memory.store(&config.to_generic()).unwrap();
+
+        let mut dry_run = DryRunStorage::new(memory.clone());
+        assert!(check_recurring_tasks(&mut dry_run).is_empty());
+        assert!(dry_run.changes().is_empty());
+
+        // The explicit tick still previews the instance under --dry-run
+        assert_eq!(tick_recurring_tasks(&mut dry_run).len(), 1);
+        assert_eq!(memory.list_ids("task").unwrap(), vec![base.id.clone()]);
+
+        assert_eq!(check_recurring_tasks(&mut memory).len(), 1);
+    }
+
+    #[test]
+    fn test_task_commands_is_mutating() {
+        assert!(TaskCommands::Archive {
+            id: "t".to_string(),
+            reason: None,
+        }
+        .is_mutating());
+        assert!(TaskCommands::Recurring {
+            command: RecurringTaskCommands::Tick,
+        }
+        .is_mutating());
+        assert!(!TaskCommands::Recurring {
+            command: RecurringTaskCommands::List,
+        }
+        .is_mutating());
+    }
+
+    #[test]
+    fn test_render_gantt_bar_lengths_follow_day_ratios() {
+        use chrono::TimeZone;
+
+        let day0 = Utc.with_ymd_and_hms(2026, 3, 2, 0, 0, 0).unwrap();
+        let task = |id: &str, title: &str, start_day: i64, end_day: i64| {
+            let mut task = Task::new(
+                title.to_string(),
+                String::new(),
+                "tester".to_string(),
+                TaskPriority::Medium,
+                None,
+            );
+            task.id = id.to_string();
+            task.start_time = day0 + chrono::Duration::days(start_day);
+            task.due_at = Some(day0 + chrono::Duration::days(end_day));
+            task
+        };
+        let mut done = task("cccc0003", "Release", 3, 20);
+        done.status = TaskStatus::Done;
+        done.end_time = Some(day0 + chrono::Duration::days(9));
+        let tasks = vec![
+            done,
+            task("bbbb0002", "Build", 1, 5),
+            task("aaaa0001", "Design", 0, 2),
+        ];
 
-    fn create_test_storage() -> MemoryStorage {
-        MemoryStorage::new("default")
+        // 80 columns leave 44 for the chart: 9 days at 4 columns each
+        let before = day0 - chrono::Duration::days(1);
+        let chart = render_gantt_at(&tasks, 80, GanttUnit::Day, before);
+        let rows: Vec<&str> = chart.lines().skip(2).collect();
+        assert_eq!(rows.len(), 3);
+        assert!(rows[0].starts_with("aaaa0001 Design"));
+        assert!(rows[2].starts_with("cccc0003 Release"));
+        let bar = |row: &str| row.chars().filter(|c| *c == '█' || *c == '░').count();
+        assert_eq!(
+            rows.iter().map(|r| bar(r)).collect::<Vec<_>>(),
+            vec![8, 16, 24]
+        );
+        assert!(chart.lines().nth(1).unwrap().contains("03-02"));
+        assert!(chart.lines().all(|line| line.chars().count() <= 80));
+
+        // One day in, the first day of every started task is elapsed
+        let chart = render_gantt_at(&tasks, 80, GanttUnit::Day, day0 + chrono::Duration::days(2));
+        let design = chart.lines().nth(2).unwrap();
+        assert_eq!(design.matches('░').count(), 8);
+        let build = chart.lines().nth(3).unwrap();
+        assert_eq!(build.matches('░').count(), 4);
+        assert_eq!(build.matches('█').count(), 12);
+
+        let data = gantt_data(&tasks, before);
+        assert_eq!(data.tasks[0].id, "aaaa0001");
+        assert_eq!(data.date_range.0, day0.to_rfc3339());
+        assert_eq!(
+            data.date_range.1,
+            (day0 + chrono::Duration::days(9)).to_rfc3339()
+        );
+        assert!("fortnight".parse::<GanttUnit>().is_err());
     }
 
     #[test]
@@ -1230,6 +3107,9 @@ mod tests {
             false,
             None,
             "text".to_string(),
+            false,
+            None,
+            false,
         );
         assert!(result.is_ok());
 
@@ -1242,6 +3122,93 @@ mod tests {
         assert_eq!(task.priority, TaskPriority::Medium);
     }
 
+    #[test]
+    fn test_task_estimate_create_and_update() {
+        let mut storage = create_test_storage();
+        create_task(
+            &mut storage,
+            Some("Estimated".to_string()),
+            None,
+            "medium",
+            None,
+            None,
+            None,
+            false,
+            None,
+            false,
+            None,
+            false,
+            None,
+            "text".to_string(),
+            false,
+            Some("2h".to_string()),
+            false,
+        )
+        .unwrap();
+        let id = storage.get_all("task").unwrap()[0].id.clone();
+        let task = Task::from_generic(storage.get(&id, "task").unwrap().unwrap()).unwrap();
+        assert_eq!(task.estimate_seconds, Some(7_200));
+
+        set_task_estimate(&mut storage, &id, "1d 4h").unwrap();
+        let task = Task::from_generic(storage.get(&id, "task").unwrap().unwrap()).unwrap();
+        assert_eq!(task.estimate_seconds, Some(100_800));
+
+        assert!(set_task_estimate(&mut storage, &id, "soon").is_err());
+        set_task_estimate(&mut storage, &id, "none").unwrap();
+        let task = Task::from_generic(storage.get(&id, "task").unwrap().unwrap()).unwrap();
+        assert_eq!(task.estimate_seconds, None);
+    }
+
+    #[test]
+    fn test_create_task_rejects_duplicates() {
+        let mut storage = create_test_storage();
+        let create = |storage: &mut MemoryStorage,
+                      title: &str,
+                      json_file: Option<String>,
+                      allow_duplicate: bool| {
+            create_task(
+                storage,
+                json_file.is_none().then(|| title.to_string()),
+                Some("Sessions expire early".to_string()),
+                "medium",
+                None,
+                None,
+                None,
+                false,
+                None,
+                false,
+                None,
+                json_file.is_some(),
+                json_file,
+                "text".to_string(),
+                allow_duplicate,
+                None,
+                false,
+            )
+        };
+
+        create(&mut storage, "Fix login timeout", None, false).unwrap();
+        assert!(matches!(
+            create(&mut storage, "fix login timeout.", None, false),
+            Err(EngramError::AlreadyExists(_))
+        ));
+        create(&mut storage, "fix login timeout.", None, true).unwrap();
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let payload = temp_dir.path().join("task.json");
+        std::fs::write(
+            &payload,
+            r#"{"title": "Fix login timeout", "description": "Sessions expire early"}"#,
+        )
+        .unwrap();
+        let json_file = Some(payload.to_string_lossy().to_string());
+        assert!(matches!(
+            create(&mut storage, "", json_file, true),
+            Err(EngramError::AlreadyExists(_))
+        ));
+        assert_eq!(storage.get_all("task").unwrap().len(), 2);
+    }
+
     #[test]
     fn test_create_task_with_priority() {
         let mut storage = create_test_storage();
@@ -1270,6 +3237,9 @@ mod tests {
                 false,
                 None,
                 "text".to_string(),
+                false,
+                None,
+                false,
             )
             .unwrap();
 
@@ -1303,33 +3273,164 @@ mod tests {
             false,
             None,
             "text".to_string(),
+            false,
+            None,
+            false,
         )
         .unwrap();
 
         let tasks = storage.query_by_agent("default", Some("task")).unwrap();
         let id = &tasks[0].id;
-        assert!(show_task(&storage, id).is_ok());
+        assert!(show_task(&storage, id, TaskShowSections::default(), false).is_ok());
     }
 
     #[test]
     fn test_show_task_not_found() {
         let storage = create_test_storage();
-        let result = show_task(&storage, "missing-id");
-        assert!(matches!(result, Err(EngramError::NotFound(_))));
+        let result = show_task(&storage, "missing-id", TaskShowSections::default(), false);
+        assert!(matches!(result, Err(EngramError::NotFound { .. })));
+    }
+
+    #[test]
+    fn test_task_details_resolves_sections() {
+        use crate::entities::{
+            Context, ContextRelevance, ExecutionResult, Reasoning, ValidationStatus,
+        };
+
+        let mut storage = create_test_storage();
+        let dependency = Task::new(
+            "Dependency".to_string(),
+            String::new(),
+            "default".to_string(),
+            TaskPriority::Medium,
+            None,
+        );
+        let context = Context::new(
+            "Design notes".to_string(),
+            "content".to_string(),
+            "test".to_string(),
+            ContextRelevance::Medium,
+            "default".to_string(),
+        );
+        let mut task = Task::new(
+            "Shown".to_string(),
+            String::new(),
+            "default".to_string(),
+            TaskPriority::High,
+            None,
+        );
+        task.context_ids.push(context.id.clone());
+        task.metadata.insert(
+            "notes".to_string(),
+            serde_json::json!([
+                "check the migration",
+                {"text": "done already", "resolved": true},
+                {"text": "ask about limits", "author": "alice"}
+            ]),
+        );
+        let reasoning = Reasoning::new(
+            "Why this approach".to_string(),
+            task.id.clone(),
+            "default".to_string(),
+        );
+        storage.store(&dependency.to_generic()).unwrap();
+        storage.store(&context.to_generic()).unwrap();
+        storage.store(&task.to_generic()).unwrap();
+        storage.store(&reasoning.to_generic()).unwrap();
+        let relationship = EntityRelationship::new(
+            uuid::Uuid::new_v4().to_string(),
+            "default".to_string(),
+            task.id.clone(),
+            "task".to_string(),
+            dependency.id.clone(),
+            "task".to_string(),
+            EntityRelationType::DependsOn,
+        );
+        storage.store_relationship(&relationship).unwrap();
+
+        let mut older = ExecutionResult::new(
+            task.id.clone(),
+            "review".to_string(),
+            "tests".to_string(),
+            "cargo test".to_string(),
+            "default".to_string(),
+        );
+        older.timestamp -= chrono::Duration::hours(1);
+        let mut latest = older.clone();
+        latest.id = uuid::Uuid::new_v4().to_string();
+        latest.timestamp += chrono::Duration::minutes(30);
+        latest.exit_code = 101;
+        latest.validation_status = ValidationStatus::Failed {
+            reason: "2 tests failed".to_string(),
+        };
+        storage.store(&older.to_generic()).unwrap();
+        storage.store(&latest.to_generic()).unwrap();
+
+        let details = task_details(&storage, &task.id, TaskShowSections::default()).unwrap();
+
+        let contexts = details.contexts.unwrap();
+        assert_eq!(contexts.items.len(), 1);
+        assert_eq!(contexts.items[0].title.as_deref(), Some("Design notes"));
+        let reasoning_items = details.reasoning.unwrap().items;
+        assert_eq!(reasoning_items[0].id, reasoning.id);
+        let dependencies = details.dependencies.unwrap().items;
+        assert_eq!(dependencies.len(), 1);
+        assert_eq!(dependencies[0].status.as_deref(), Some("todo"));
+        let gates = details.gate_results.unwrap().items;
+        assert_eq!(gates.len(), 1);
+        assert_eq!(gates[0].id, latest.id);
+        assert_eq!(gates[0].status, "failed: 2 tests failed");
+        let notes = details.notes.unwrap().items;
+        assert_eq!(notes.len(), 2);
+        assert_eq!(notes[1].author.as_deref(), Some("alice"));
+        assert!(details.commits.is_some());
+    }
+
+    #[test]
+    fn test_task_details_suppresses_sections_and_isolates_failures() {
+        let mut storage = create_test_storage();
+        let mut task = Task::new(
+            "Shown".to_string(),
+            String::new(),
+            "default".to_string(),
+            TaskPriority::Low,
+            None,
+        );
+        task.metadata
+            .insert("notes".to_string(), serde_json::json!("not a list"));
+        storage.store(&task.to_generic()).unwrap();
+
+        let sections = TaskShowSections {
+            commits: false,
+            gates: false,
+            ..TaskShowSections::default()
+        };
+        let details = task_details(&storage, &task.id, sections).unwrap();
+        assert!(details.commits.is_none());
+        assert!(details.gate_results.is_none());
+        let notes = details.notes.unwrap();
+        assert!(notes.items.is_empty());
+        assert!(notes.error.is_some());
+
+        let json =
+            serde_json::to_value(task_details(&storage, &task.id, sections).unwrap()).unwrap();
+        assert!(json.get("commits").is_none());
+        assert!(json["notes"]["error"].is_string());
+        assert_eq!(json["task"]["title"], "Shown");
     }
 
     #[test]
     fn test_update_task_not_found() {
         let mut storage = create_test_storage();
         let result = update_task(&mut storage, "missing-id", "done", None, None);
-        assert!(matches!(result, Err(EngramError::NotFound(_))));
+        assert!(matches!(result, Err(EngramError::NotFound { .. })));
     }
 
     #[test]
     fn test_archive_task_not_found() {
         let mut storage = create_test_storage();
         let result = archive_task(&mut storage, "missing-id", None);
-        assert!(matches!(result, Err(EngramError::NotFound(_))));
+        assert!(matches!(result, Err(EngramError::NotFound { .. })));
     }
     #[test]
     fn test_create_task_validation() {
@@ -1351,6 +3452,9 @@ mod tests {
             false,
             None,
             "text".to_string(),
+            false,
+            None,
+            false,
         );
         assert!(matches!(result, Err(EngramError::Validation(_))));
     }
@@ -1373,6 +3477,9 @@ mod tests {
             false,
             None,
             "text".to_string(),
+            false,
+            None,
+            false,
         )
         .unwrap();
 
@@ -1425,6 +3532,9 @@ mod tests {
             false,
             None,
             "text".to_string(),
+            false,
+            None,
+            false,
         )
         .unwrap();
 
@@ -1453,6 +3563,9 @@ mod tests {
             false,
             None,
             "text".to_string(),
+            false,
+            None,
+            false,
         )
         .unwrap();
 
@@ -1489,6 +3602,9 @@ mod tests {
             false,
             None,
             "text".to_string(),
+            false,
+            None,
+            false,
         )
         .unwrap();
 
@@ -1507,6 +3623,9 @@ mod tests {
             false,
             None,
             "text".to_string(),
+            false,
+            None,
+            false,
         )
         .unwrap();
 
@@ -1522,6 +3641,10 @@ mod tests {
             None,
             false,
             24,
+            None,
+            false,
+            &SortArgs::default(),
+            &CountArgs::default(),
             "text",
         );
         assert!(result.is_ok());
@@ -1544,6 +3667,10 @@ mod tests {
             None,
             false,
             24,
+            None,
+            false,
+            &SortArgs::default(),
+            &CountArgs::default(),
             "text",
         );
         assert!(result.is_ok());
@@ -1569,6 +3696,9 @@ mod tests {
             false,
             None,
             "text".to_string(),
+            false,
+            None,
+            false,
         )
         .unwrap();
 
@@ -1645,6 +3775,9 @@ mod tests {
             false,
             None,
             "text".to_string(),
+            false,
+            None,
+            false,
         )
         .unwrap();
 
@@ -1671,7 +3804,7 @@ mod tests {
     #[test]
     fn test_archive_bulk_no_filters() {
         let mut storage = create_test_storage();
-        let result = archive_tasks_bulk(&mut storage, None, None, false, "text");
+        let result = archive_tasks_bulk(&mut storage, None, None, "text");
         assert!(matches!(result, Err(EngramError::Validation(_))));
     }
 
@@ -1694,6 +3827,9 @@ mod tests {
             false,
             None,
             "text".to_string(),
+            false,
+            None,
+            false,
         )
         .unwrap();
         create_task(
@@ -1711,6 +3847,9 @@ mod tests {
             false,
             None,
             "text".to_string(),
+            false,
+            None,
+            false,
         )
         .unwrap();
 
@@ -1728,7 +3867,7 @@ mod tests {
 
         update_task(&mut storage, &done_id, "done", Some("Finished"), None).unwrap();
 
-        archive_tasks_bulk(&mut storage, None, Some("done"), false, "text").unwrap();
+        archive_tasks_bulk(&mut storage, None, Some("done"), "text").unwrap();
 
         let archived = Task::from_generic(storage.get(&done_id, "task").unwrap().unwrap()).unwrap();
         assert!(archived.metadata.contains_key("archived_at"));
@@ -1765,6 +3904,9 @@ mod tests {
             false,
             None,
             "text".to_string(),
+            false,
+            None,
+            false,
         )
         .unwrap();
 
@@ -1773,7 +3915,9 @@ mod tests {
 
         update_task(&mut storage, &task_id, "done", Some("Finished"), None).unwrap();
 
-        archive_tasks_bulk(&mut storage, None, Some("done"), true, "text").unwrap();
+        let mut dry_run = DryRunStorage::new(storage.clone());
+        archive_tasks_bulk(&mut dry_run, None, Some("done"), "text").unwrap();
+        assert_eq!(dry_run.summary()["task"].stores, 1);
 
         let task = Task::from_generic(storage.get(&task_id, "task").unwrap().unwrap()).unwrap();
         assert!(!task.metadata.contains_key("archived_at"));
@@ -1798,13 +3942,16 @@ mod tests {
             false,
             None,
             "text".to_string(),
+            false,
+            None,
+            false,
         )
         .unwrap();
 
         let tasks = storage.query_by_agent("default", Some("task")).unwrap();
         let task_id = tasks[0].id.clone();
 
-        archive_tasks_bulk(&mut storage, Some(0), None, false, "text").unwrap();
+        archive_tasks_bulk(&mut storage, Some(0), None, "text").unwrap();
 
         let task = Task::from_generic(storage.get(&task_id, "task").unwrap().unwrap()).unwrap();
         assert!(task.metadata.contains_key("archived_at"));
@@ -1829,6 +3976,9 @@ mod tests {
             false,
             None,
             "text".to_string(),
+            false,
+            None,
+            false,
         )
         .unwrap();
 
@@ -1836,12 +3986,12 @@ mod tests {
         let task_id = tasks[0].id.clone();
 
         update_task(&mut storage, &task_id, "done", Some("Done"), None).unwrap();
-        archive_tasks_bulk(&mut storage, Some(0), Some("done"), false, "text").unwrap();
+        archive_tasks_bulk(&mut storage, Some(0), Some("done"), "text").unwrap();
 
         let archived = Task::from_generic(storage.get(&task_id, "task").unwrap().unwrap()).unwrap();
         let first_archived_at = archived.metadata.get("archived_at").unwrap().clone();
 
-        archive_tasks_bulk(&mut storage, Some(0), Some("done"), false, "text").unwrap();
+        archive_tasks_bulk(&mut storage, Some(0), Some("done"), "text").unwrap();
 
         let still_archived =
             Task::from_generic(storage.get(&task_id, "task").unwrap().unwrap()).unwrap();
@@ -1868,10 +4018,13 @@ mod tests {
             false,
             None,
             "text".to_string(),
+            false,
+            None,
+            false,
         )
         .unwrap();
 
-        let result = archive_tasks_bulk(&mut storage, None, Some("done"), false, "text");
+        let result = archive_tasks_bulk(&mut storage, None, Some("done"), "text");
         assert!(result.is_ok());
     }
 
@@ -1894,6 +4047,9 @@ mod tests {
             false,
             None,
             "text".to_string(),
+            false,
+            None,
+            false,
         )
         .unwrap();
         create_task(
@@ -1911,6 +4067,9 @@ mod tests {
             false,
             None,
             "text".to_string(),
+            false,
+            None,
+            false,
         )
         .unwrap();
         create_task(
@@ -1928,6 +4087,9 @@ mod tests {
             false,
             None,
             "text".to_string(),
+            false,
+            None,
+            false,
         )
         .unwrap();
 
@@ -1940,7 +4102,7 @@ mod tests {
             }
         }
 
-        archive_tasks_bulk(&mut storage, Some(0), Some("done"), false, "text").unwrap();
+        archive_tasks_bulk(&mut storage, Some(0), Some("done"), "text").unwrap();
 
         for t in &tasks {
             let task = Task::from_generic(storage.get(&t.id, "task").unwrap().unwrap()).unwrap();
@@ -1971,10 +4133,13 @@ mod tests {
             false,
             None,
             "text".to_string(),
+            false,
+            None,
+            false,
         )
         .unwrap();
 
-        let result = archive_tasks_bulk(&mut storage, Some(0), None, true, "json");
+        let result = archive_tasks_bulk(&mut storage, Some(0), None, "json");
         assert!(result.is_ok());
     }
 
@@ -2012,6 +4177,10 @@ mod tests {
             None,
             false,
             24,
+            None,
+            false,
+            &SortArgs::default(),
+            &CountArgs::default(),
             "text",
         );
         assert!(result.is_ok());
@@ -2052,6 +4221,10 @@ mod tests {
             None,
             false,
             24,
+            None,
+            false,
+            &SortArgs::default(),
+            &CountArgs::default(),
             "text",
         );
         assert!(result.is_ok());
@@ -2102,8 +4275,298 @@ mod tests {
             None,
             false,
             24,
+            None,
+            false,
+            &SortArgs::default(),
+            &CountArgs::default(),
             "text",
         );
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_render_critical_path_marks_critical_tasks() {
+        let mut graph = DependencyGraph::new();
+        let mut ids = Vec::new();
+        for (title, seconds) in [("Design", 3600), ("Build", 7200)] {
+            let mut task = Task::new(
+                title.to_string(),
+                String::new(),
+                "default".to_string(),
+                TaskPriority::Medium,
+                None,
+            );
+            task.metadata.insert(
+                crate::entities::ESTIMATED_DURATION_METADATA_KEY.to_string(),
+                serde_json::json!(seconds),
+            );
+            ids.push(task.id.clone());
+            graph.add_task(task);
+        }
+        graph.add_dependency(&ids[1], &ids[0]);
+
+        let result = graph.critical_path(&ids[1], 3600.0).unwrap();
+        let rendered = render_critical_path(&result, &graph);
+        let lines: Vec<&str> = rendered.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].contains("Critical path to Build"));
+        assert!(lines[0].ends_with("3h 0m total"));
+        assert!(lines[1].starts_with(&format!("* {}", &ids[0][..8])));
+        assert!(lines[2].contains("2h 0m"));
+        assert!(lines[2].contains('█'));
+    }
+
+    fn link(storage: &mut MemoryStorage, source: &str, target: &str, kind: EntityRelationType) {
+        let relationship = EntityRelationship::new(
+            uuid::Uuid::new_v4().to_string(),
+            "default".to_string(),
+            source.to_string(),
+            "task".to_string(),
+            target.to_string(),
+            "context".to_string(),
+            kind,
+        );
+        storage.store(&relationship.to_generic()).unwrap();
+    }
+
+    fn task_relationships(storage: &MemoryStorage, id: &str) -> Vec<EntityRelationship> {
+        storage
+            .get_all("relationship")
+            .unwrap()
+            .into_iter()
+            .filter_map(|e| EntityRelationship::from_generic(e).ok())
+            .filter(|r| r.source_id == id || r.target_id == id)
+            .collect()
+    }
+
+    #[test]
+    fn test_merge_tasks_copies_relationships_and_cancels_secondary() {
+        let mut storage = create_test_storage();
+        let mut primary = Task::new(
+            "Fix login bug".to_string(),
+            "Short".to_string(),
+            "default".to_string(),
+            TaskPriority::High,
+            None,
+        );
+        primary.tags = vec!["auth".to_string()];
+        let mut secondary = Task::new(
+            "Fix the login bug".to_string(),
+            "A much longer description".to_string(),
+            "default".to_string(),
+            TaskPriority::Medium,
+            None,
+        );
+        secondary.tags = vec!["auth".to_string(), "urgent".to_string()];
+        storage.store(&primary.to_generic()).unwrap();
+        storage.store(&secondary.to_generic()).unwrap();
+
+        link(
+            &mut storage,
+            &primary.id,
+            "ctx-a",
+            EntityRelationType::References,
+        );
+        link(
+            &mut storage,
+            &primary.id,
+            "ctx-b",
+            EntityRelationType::DependsOn,
+        );
+        link(
+            &mut storage,
+            &secondary.id,
+            "ctx-c",
+            EntityRelationType::References,
+        );
+        link(
+            &mut storage,
+            &secondary.id,
+            "ctx-d",
+            EntityRelationType::References,
+        );
+
+        let merged = merge_tasks(
+            &mut storage,
+            &primary.id,
+            &secondary.id,
+            TaskMergeStrategy::KeepLongerDescription,
+        )
+        .unwrap();
+        assert_eq!(merged.description, "A much longer description");
+        assert_eq!(merged.tags, vec!["auth", "urgent"]);
+        assert_eq!(merged.metadata["merged_from"], secondary.id.as_str());
+
+        let relationships = task_relationships(&storage, &primary.id);
+        let targets: HashSet<&str> = relationships.iter().map(|r| r.target_id.as_str()).collect();
+        assert_eq!(relationships.len(), 4);
+        assert_eq!(targets.len(), 4);
+
+        let stored =
+            Task::from_generic(storage.get(&secondary.id, "task").unwrap().unwrap()).unwrap();
+        assert_eq!(stored.status, TaskStatus::Cancelled);
+        assert_eq!(stored.outcome, Some(format!("Merged into {}", primary.id)));
+        assert_eq!(task_relationships(&storage, &secondary.id).len(), 2);
+
+        // Merging again is rejected rather than duplicating links
+        assert!(merge_tasks(
+            &mut storage,
+            &primary.id,
+            &secondary.id,
+            TaskMergeStrategy::KeepPrimary
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_merge_tasks_deduplicates_shared_relationships() {
+        let mut storage = create_test_storage();
+        let primary = Task::new(
+            "A".to_string(),
+            "first".to_string(),
+            "default".to_string(),
+            TaskPriority::Medium,
+            None,
+        );
+        let secondary = Task::new(
+            "B".to_string(),
+            "second".to_string(),
+            "default".to_string(),
+            TaskPriority::Medium,
+            None,
+        );
+        storage.store(&primary.to_generic()).unwrap();
+        storage.store(&secondary.to_generic()).unwrap();
+        link(
+            &mut storage,
+            &primary.id,
+            "ctx-a",
+            EntityRelationType::References,
+        );
+        link(
+            &mut storage,
+            &secondary.id,
+            "ctx-a",
+            EntityRelationType::References,
+        );
+        link(
+            &mut storage,
+            &secondary.id,
+            &primary.id,
+            EntityRelationType::DependsOn,
+        );
+
+        let merged = merge_tasks(
+            &mut storage,
+            &primary.id,
+            &secondary.id,
+            TaskMergeStrategy::ConcatenateDescriptions,
+        )
+        .unwrap();
+        assert_eq!(merged.description, "first\n\nsecond");
+        // The shared link is not duplicated and the link between the two is not turned into a self-loop
+        assert_eq!(task_relationships(&storage, &primary.id).len(), 2);
+    }
+
+    #[test]
+    fn test_find_duplicate_tasks_by_title_similarity() {
+        assert_eq!(title_similarity("Fix login bug", "fix LOGIN bug"), 1.0);
+        assert!(title_similarity("Fix login bug", "Write docs") < 0.1);
+
+        let mut storage = create_test_storage();
+        for title in [
+            "Update the release notes for v2",
+            "Update release notes for v2",
+            "Refactor storage layer",
+        ] {
+            let task = Task::new(
+                title.to_string(),
+                String::new(),
+                "default".to_string(),
+                TaskPriority::Medium,
+                None,
+            );
+            storage.store(&task.to_generic()).unwrap();
+        }
+
+        let pairs = find_duplicate_tasks(&storage, DUPLICATE_TITLE_SIMILARITY).unwrap();
+        assert_eq!(pairs.len(), 1);
+        assert!(pairs[0].0.title.contains("release notes"));
+        assert!(pairs[0].1.title.contains("release notes"));
+    }
+
+    #[test]
+    fn test_search_suggestions_ordered_by_count() {
+        let mut storage = create_test_storage();
+        let engine = SearchSuggestionEngine::new("alice");
+        for (term, uses) in [
+            ("implement", 3),
+            ("impl trait", 5),
+            ("bug", 4),
+            ("Improve docs", 1),
+            ("impl", 2),
+        ] {
+            for _ in 0..uses {
+                engine.record(&mut storage, term).unwrap();
+            }
+        }
+        // Another agent's history stays separate
+        SearchSuggestionEngine::new("bob")
+            .record(&mut storage, "implement")
+            .unwrap();
+        assert!(engine.record(&mut storage, "  ").unwrap().is_none());
+        assert!(engine
+            .record(&mut storage, SEARCH_HISTORY_QUERY)
+            .unwrap()
+            .is_none());
+
+        let top = engine.top(&storage, MAX_SEARCH_SUGGESTIONS).unwrap();
+        let ranked: Vec<(&str, u64)> = top.iter().map(|h| (h.term.as_str(), h.count)).collect();
+        assert_eq!(
+            ranked,
+            vec![
+                ("impl trait", 5),
+                ("bug", 4),
+                ("implement", 3),
+                ("impl", 2),
+                ("improve docs", 1),
+            ]
+        );
+
+        let suggestions: Vec<String> = engine
+            .suggest(&storage, "IMPL", MAX_SEARCH_SUGGESTIONS)
+            .unwrap()
+            .into_iter()
+            .map(|h| h.term)
+            .collect();
+        assert_eq!(suggestions, vec!["impl trait", "implement"]);
+
+        assert_eq!(engine.clear(&mut storage).unwrap(), 5);
+        assert!(engine.history(&storage).unwrap().is_empty());
+        assert_eq!(
+            SearchSuggestionEngine::new("bob")
+                .history(&storage)
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_handle_search_history_records_and_skips_listing() {
+        let mut storage = create_test_storage();
+        assert!(!handle_search_history(&mut storage, "alice", Some("login"), true, false).unwrap());
+        assert!(handle_search_history(&mut storage, "alice", Some("?"), false, false).unwrap());
+        assert!(!handle_search_history(&mut storage, "alice", None, false, false).unwrap());
+
+        let history = SearchSuggestionEngine::new("alice")
+            .history(&storage)
+            .unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].term, "login");
+
+        assert!(handle_search_history(&mut storage, "alice", None, false, true).unwrap());
+        assert!(storage.get_all("search_history").unwrap().is_empty());
+    }
 }
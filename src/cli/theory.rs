@@ -1,6 +1,7 @@
 //! Theory command implementations (Naur, 1985 - Programming as Theory Building)
 
-use crate::cli::utils::{create_table, truncate};
+use crate::cli::identity::resolve_agent;
+use crate::cli::utils::{create_table, truncate, SortArgs};
 use crate::entities::{Entity, Theory};
 use crate::error::EngramError;
 use crate::storage::Storage;
@@ -57,6 +58,9 @@ pub enum TheoryCommands {
         /// Offset for pagination
         #[arg(long, short)]
         offset: Option<usize>,
+
+        #[command(flatten)]
+        sort: SortArgs,
     },
     Show {
         #[arg(long, short)]
@@ -106,7 +110,7 @@ fn create_theory_from_input<S: Storage>(
     storage: &mut S,
     input: TheoryInput,
 ) -> Result<(), EngramError> {
-    let agent = input.agent.unwrap_or_else(|| "default".to_string());
+    let agent = resolve_agent(input.agent);
     let mut theory = if let Some(task_id) = input.task_id {
         Theory::for_task(input.domain_name, agent, task_id)
     } else {
@@ -177,7 +181,7 @@ pub fn create_theory<S: Storage>(
         )
     })?;
 
-    let agent_name = agent.unwrap_or_else(|| "default".to_string());
+    let agent_name = resolve_agent(agent);
     let theory = if let Some(task_id) = task {
         Theory::for_task(domain_name, agent_name, task_id)
     } else {
@@ -198,6 +202,7 @@ pub fn list_theories<S: Storage>(
     limit: Option<usize>,
     all: bool,
     offset: Option<usize>,
+    sort: &SortArgs,
 ) -> Result<(), EngramError> {
     let ids = storage.list_ids(Theory::entity_type())?;
 
@@ -227,6 +232,7 @@ pub fn list_theories<S: Storage>(
         }
     }
 
+    items = sort.sort(items)?;
     let total_count = items.len();
 
     if let Some(off) = offset {
@@ -287,8 +293,9 @@ pub fn show_theory<S: Storage>(
 ) -> Result<(), EngramError> {
     let entity = storage
         .get(id, Theory::entity_type())?
-        .ok_or_else(|| EngramError::NotFound(format!("Theory not found: {}", id)))?;
+        .ok_or_else(|| EngramError::not_found("theory", id))?;
 
+    let modification = entity.modification();
     let theory = Theory::from_generic(entity)
         .map_err(|e: EngramError| EngramError::Validation(e.to_string()))?;
 
@@ -297,6 +304,9 @@ pub fn show_theory<S: Storage>(
     println!("ID: {}", theory.id);
     println!("Domain: {}", theory.domain_name);
     println!("Agent: {}", theory.agent);
+    if let Some(modified) = modification.summary() {
+        println!("Last Modified By: {}", modified);
+    }
     println!("Created: {}", theory.created_at);
     println!("Last Updated: {}", theory.last_updated);
     println!("Iteration Count: {}", theory.iteration_count);
@@ -359,7 +369,7 @@ pub fn update_theory<S: Storage>(
 ) -> Result<(), EngramError> {
     let entity = storage
         .get(id, Theory::entity_type())?
-        .ok_or_else(|| EngramError::NotFound(format!("Theory not found: {}", id)))?;
+        .ok_or_else(|| EngramError::not_found("theory", id))?;
 
     let mut theory = Theory::from_generic(entity)
         .map_err(|e: EngramError| EngramError::Validation(e.to_string()))?;
@@ -419,7 +429,7 @@ pub fn apply_reflection<S: Storage>(
 ) -> Result<(), EngramError> {
     let entity = storage
         .get(theory_id, Theory::entity_type())?
-        .ok_or_else(|| EngramError::NotFound(format!("Theory not found: {}", theory_id)))?;
+        .ok_or_else(|| EngramError::not_found("theory", theory_id))?;
 
     let mut theory = Theory::from_generic(entity)
         .map_err(|e: EngramError| EngramError::Validation(e.to_string()))?;
@@ -500,14 +510,24 @@ mod tests {
         )
         .unwrap();
 
-        assert!(list_theories(&storage, None, None, None, false, None).is_ok());
+        assert!(list_theories(
+            &storage,
+            None,
+            None,
+            None,
+            false,
+            None,
+            &SortArgs::default()
+        )
+        .is_ok());
         assert!(list_theories(
             &storage,
             Some("agent1".to_string()),
             None,
             None,
             false,
-            None
+            None,
+            &SortArgs::default(),
         )
         .is_ok());
     }
@@ -536,7 +556,7 @@ mod tests {
     fn test_show_theory_not_found() {
         let storage = create_test_storage();
         let result = show_theory(&storage, "missing-id", false);
-        assert!(matches!(result, Err(EngramError::NotFound(_))));
+        assert!(matches!(result, Err(EngramError::NotFound { .. })));
     }
 
     #[test]
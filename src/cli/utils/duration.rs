@@ -0,0 +1,107 @@
+//! Human duration syntax shared by estimates, due dates and claims
+//!
+//! A duration is one or more `<number><unit>` parts, e.g. `90m`, `2h`,
+//! `1.5d` or `1w 2d`. Units are `s`, `m`, `h`, `d` (24 hours) and `w`
+//! (7 days); a bare number counts seconds.
+
+use crate::error::EngramError;
+
+const UNITS: [(char, u64); 5] = [
+    ('w', 7 * 86_400),
+    ('d', 86_400),
+    ('h', 3_600),
+    ('m', 60),
+    ('s', 1),
+];
+
+/// Parse duration syntax into whole seconds
+pub fn parse_duration_secs(input: &str) -> Result<u64, EngramError> {
+    let invalid = || {
+        EngramError::Validation(format!(
+            "Invalid duration '{}': use e.g. 45m, 2h, 3d or 1w 2d",
+            input
+        ))
+    };
+    let compact: String = input.chars().filter(|c| !c.is_whitespace()).collect();
+    if compact.is_empty() {
+        return Err(invalid());
+    }
+    if let Ok(seconds) = compact.parse::<u64>() {
+        return Ok(seconds);
+    }
+
+    let mut total = 0.0;
+    let mut number = String::new();
+    for c in compact.to_lowercase().chars() {
+        if c.is_ascii_digit() || c == '.' {
+            number.push(c);
+            continue;
+        }
+        let (_, unit_secs) = UNITS
+            .iter()
+            .find(|(unit, _)| *unit == c)
+            .ok_or_else(invalid)?;
+        let value: f64 = number.parse().map_err(|_| invalid())?;
+        total += value * *unit_secs as f64;
+        number.clear();
+    }
+    if !number.is_empty() {
+        return Err(invalid());
+    }
+    Ok(total.round() as u64)
+}
+
+/// Render seconds in the largest units that fit, at most two parts:
+/// `2h`, `1d 4h`, `45m`, `30s`
+pub fn format_duration_secs(seconds: u64) -> String {
+    if seconds == 0 {
+        return "0s".to_string();
+    }
+    let mut parts = Vec::new();
+    let mut remaining = seconds;
+    for (unit, unit_secs) in UNITS {
+        if parts.len() == 2 {
+            break;
+        }
+        let count = remaining / unit_secs;
+        if count > 0 {
+            parts.push(format!("{}{}", count, unit));
+            remaining -= count * unit_secs;
+        } else if !parts.is_empty() {
+            break;
+        }
+    }
+    parts.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration_secs() {
+        assert_eq!(parse_duration_secs("90").unwrap(), 90);
+        assert_eq!(parse_duration_secs("45m").unwrap(), 2_700);
+        assert_eq!(parse_duration_secs("2h").unwrap(), 7_200);
+        assert_eq!(parse_duration_secs("3d").unwrap(), 259_200);
+        assert_eq!(parse_duration_secs("1.5h").unwrap(), 5_400);
+        assert_eq!(parse_duration_secs("1w 2d").unwrap(), 777_600);
+        assert_eq!(parse_duration_secs("1H30M").unwrap(), 5_400);
+        for bad in ["", "h", "2x", "2h30", "1..5h"] {
+            assert!(parse_duration_secs(bad).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_format_duration_secs() {
+        assert_eq!(format_duration_secs(0), "0s");
+        assert_eq!(format_duration_secs(7_200), "2h");
+        assert_eq!(format_duration_secs(100_800), "1d 4h");
+        assert_eq!(format_duration_secs(90_061), "1d 1h");
+        assert_eq!(format_duration_secs(86_460), "1d");
+        assert_eq!(
+            parse_duration_secs(&format_duration_secs(5_400)).unwrap(),
+            5_400
+        );
+    }
+}
@@ -1,4 +1,177 @@
+pub mod duration;
+
+pub use duration::*;
+
+use crate::dedup::{self, DedupConfig};
+use crate::entities::{Entity, GenericEntity};
+use crate::error::EngramError;
+use crate::storage::{query::apply_filter, QueryFilter, SortOrder, Storage};
 use prettytable::{format, Table};
+use std::collections::HashMap;
+
+/// `--sort` / `--order` flags shared by the list commands
+#[derive(Debug, Clone, Default, clap::Args)]
+pub struct SortArgs {
+    /// Sort by a data field; use dots for nested fields (e.g. `metadata.owner`)
+    #[arg(long)]
+    pub sort: Option<String>,
+
+    /// Sort direction (default: asc with --sort, newest first without)
+    #[arg(long, value_parser = ["asc", "desc"])]
+    pub order: Option<String>,
+}
+
+impl SortArgs {
+    /// Requested direction; ascending when only `--sort` is given
+    pub fn sort_order(&self) -> Option<SortOrder> {
+        match self.order.as_deref() {
+            Some("desc") => Some(SortOrder::Desc),
+            Some(_) => Some(SortOrder::Asc),
+            None if self.sort.is_some() => Some(SortOrder::Asc),
+            None => None,
+        }
+    }
+
+    /// Apply the flags to a list query, leaving its default order alone
+    /// when neither is given
+    pub fn apply(&self, filter: &mut QueryFilter) {
+        if let Some(key) = &self.sort {
+            filter.sort_by = Some(key.clone());
+        }
+        if let Some(order) = self.sort_order() {
+            filter.sort_order = order;
+        }
+    }
+
+    /// Reorder entities a list command loaded itself rather than through
+    /// `Storage::query`, with the same key semantics and errors
+    pub fn sort_generic(
+        &self,
+        entity_type: &str,
+        entities: Vec<GenericEntity>,
+    ) -> Result<Vec<GenericEntity>, EngramError> {
+        let Some(sort_order) = self.sort_order() else {
+            return Ok(entities);
+        };
+        let filter = QueryFilter {
+            entity_type: Some(entity_type.to_string()),
+            sort_by: self.sort.clone(),
+            sort_order,
+            limit: None,
+            offset: None,
+            ..Default::default()
+        };
+        Ok(apply_filter(entities, &filter)?.entities)
+    }
+
+    /// [`sort_generic`](Self::sort_generic) for typed entities
+    pub fn sort<T: Entity>(&self, items: Vec<T>) -> Result<Vec<T>, EngramError> {
+        if self.sort_order().is_none() {
+            return Ok(items);
+        }
+        let generic = items.iter().map(Entity::to_generic).collect();
+        let order = self.sort_generic(T::entity_type(), generic)?;
+
+        let mut by_id: HashMap<String, T> = items
+            .into_iter()
+            .map(|item| (item.id().to_string(), item))
+            .collect();
+        Ok(order
+            .into_iter()
+            .filter_map(|entity| by_id.remove(&entity.id))
+            .collect())
+    }
+}
+
+/// `--count-only` flags shared by the list commands backed by `Storage::count`
+#[derive(Debug, Clone, Default, clap::Args)]
+pub struct CountArgs {
+    /// Print only the number of matching entities
+    #[arg(long)]
+    pub count_only: bool,
+
+    /// Print the count as `{"count": N}`
+    #[arg(long, requires = "count_only")]
+    pub json: bool,
+}
+
+impl CountArgs {
+    /// With `--count-only`, print how many entities match `filter` and
+    /// return true; otherwise do nothing and return false
+    pub fn write<S: Storage + ?Sized>(
+        &self,
+        writer: &mut dyn std::io::Write,
+        storage: &S,
+        filter: &QueryFilter,
+    ) -> Result<bool, EngramError> {
+        if !self.count_only {
+            return Ok(false);
+        }
+        let count = storage.count(filter)?;
+        if self.json {
+            writeln!(writer, "{}", serde_json::json!({ "count": count }))?;
+        } else {
+            writeln!(writer, "{}", count)?;
+        }
+        Ok(true)
+    }
+}
+
+/// Refuse to create an entity that duplicates an open one of the same type.
+/// A JSON `payload_body` identical to an existing entity is always rejected;
+/// similar titles are listed and then need `allow_duplicate` or, at a
+/// terminal, confirmation.
+pub fn check_duplicate<S: Storage>(
+    storage: &S,
+    entity_type: &str,
+    title: &str,
+    payload_body: Option<&str>,
+    allow_duplicate: bool,
+) -> Result<(), EngramError> {
+    use std::io::{BufRead, IsTerminal, Write};
+
+    if let Some(body) = payload_body {
+        let hash = dedup::content_hash(title, body);
+        if let Some(id) = dedup::find_exact_duplicate(storage, entity_type, &hash)? {
+            return Err(EngramError::AlreadyExists(format!(
+                "Identical {} already exists: {}",
+                entity_type, id
+            )));
+        }
+    }
+
+    let config = DedupConfig::load(std::path::Path::new("."))?;
+    if allow_duplicate || !config.enabled {
+        return Ok(());
+    }
+    let similar = dedup::find_similar(storage, entity_type, title, config.threshold)?;
+    if similar.is_empty() {
+        return Ok(());
+    }
+
+    eprintln!("⚠️  Similar {} already exist:", entity_type);
+    for entity in &similar {
+        eprintln!(
+            "  {} {:>3.0}%  {}",
+            entity.id,
+            entity.similarity * 100.0,
+            entity.title
+        );
+    }
+    if std::io::stdin().is_terminal() && std::io::stderr().is_terminal() {
+        eprint!("Create anyway? [y/N] ");
+        std::io::stderr().flush()?;
+        let mut answer = String::new();
+        std::io::stdin().lock().read_line(&mut answer)?;
+        if matches!(answer.trim(), "y" | "Y" | "yes") {
+            return Ok(());
+        }
+    }
+    Err(EngramError::AlreadyExists(format!(
+        "{} '{}' looks like a duplicate of {} (use --allow-duplicate to create it anyway)",
+        entity_type, title, similar[0].id
+    )))
+}
 
 /// Create a standard table format for CLI output
 pub fn create_table() -> Table {
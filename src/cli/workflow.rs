@@ -1,7 +1,10 @@
+use crate::cli::utils::{CountArgs, SortArgs};
+use crate::engines::action_registry::{ActionRegistry, GuardRegistry, TypeSpec};
 use crate::engines::rule_engine::RuleValue;
-use crate::engines::workflow_engine::WorkflowAutomationEngine;
+use crate::engines::workflow_engine::{WorkflowAutomationEngine, WorkflowEventType};
 use crate::entities::{
-    Entity, StateType, TransitionType, Workflow, WorkflowState, WorkflowStatus, WorkflowTransition,
+    Entity, PromptTemplate, StateType, TransitionAction, TransitionCondition, TransitionType,
+    Workflow, WorkflowInstance, WorkflowState, WorkflowStatus, WorkflowTransition,
 };
 use crate::error::EngramError;
 use crate::storage::Storage;
@@ -89,6 +92,12 @@ pub enum WorkflowCommands {
         /// Show all results (no limit)
         #[arg(long, conflicts_with = "limit")]
         all: bool,
+
+        #[command(flatten)]
+        sort: SortArgs,
+
+        #[command(flatten)]
+        count: CountArgs,
     },
     /// Add state to workflow
     AddState {
@@ -111,6 +120,15 @@ pub enum WorkflowCommands {
         /// Whether this is a final state
         #[arg(long, action)]
         is_final: bool,
+
+        /// Instructions shown by `engram next` while an instance is in this
+        /// state; `{{variable}}` placeholders are filled from the instance
+        #[arg(long, conflicts_with = "prompt_file")]
+        prompt: Option<String>,
+
+        /// Read the state prompt from a file
+        #[arg(long, conflicts_with = "prompt")]
+        prompt_file: Option<String>,
     },
     /// Add transition to workflow
     AddTransition {
@@ -137,6 +155,16 @@ pub enum WorkflowCommands {
         /// Transition description
         #[arg(long)]
         description: String,
+
+        /// Action to run on the transition, as TYPE or TYPE=JSON-PARAMS
+        /// (repeatable; see `workflow query-actions` for the available types)
+        #[arg(long = "action")]
+        actions: Vec<String>,
+
+        /// Check that must pass before the transition, as TYPE=JSON-PARAMS
+        /// (repeatable), e.g. command_guard='{"command":"cargo","args":["test"]}'
+        #[arg(long = "condition")]
+        conditions: Vec<String>,
     },
     /// Activate workflow
     Activate {
@@ -194,6 +222,30 @@ pub enum WorkflowCommands {
         #[arg(help = "Workflow instance ID")]
         instance_id: String,
     },
+    /// Show the prompt for a workflow instance's current state
+    Prompt {
+        /// Workflow instance ID
+        #[arg(help = "Workflow instance ID")]
+        instance_id: String,
+    },
+    /// Page through the full execution history of a workflow instance
+    ///
+    ///EXAMPLES:
+    ///  engram workflow history <INSTANCE_ID>
+    ///  engram workflow history <INSTANCE_ID> --since 2024-01-01T12:00:00 --limit 20
+    History {
+        /// Workflow instance ID
+        #[arg(help = "Workflow instance ID")]
+        instance_id: String,
+
+        /// Maximum number of events to show
+        #[arg(long, default_value = "50")]
+        limit: usize,
+
+        /// Only show events at or after this time (2024-01-01, 2024-01-01T12:00:00, 24h, 7d)
+        #[arg(long)]
+        since: Option<String>,
+    },
     /// List active workflow instances
     Instances {
         /// Filter by workflow ID
@@ -270,6 +322,20 @@ pub enum WorkflowCommands {
         #[arg(long)]
         state_id: Option<String>,
     },
+    /// Audit execution history across all instances of a workflow
+    Audit {
+        /// Workflow ID
+        #[arg(help = "Workflow definition ID")]
+        workflow_id: String,
+
+        /// Only include instances started within this many days
+        #[arg(long, default_value = "30")]
+        days: u32,
+
+        /// Output format (table, json)
+        #[arg(long, default_value = "table")]
+        format: String,
+    },
 }
 
 /// Create a new workflow
@@ -311,7 +377,7 @@ pub fn get_workflow<S: Storage>(storage: &S, id: &str) -> Result<(), EngramError
             Workflow::from_generic(generic).map_err(|e| EngramError::Validation(e.to_string()))?;
         display_workflow(&workflow);
     } else {
-        println!("❌ Workflow not found: {}", id);
+        return Err(EngramError::not_found("workflow", id));
     }
     Ok(())
 }
@@ -383,7 +449,7 @@ pub fn update_workflow<S: Storage>(
 
         println!("✅ Workflow updated: {}", id);
     } else {
-        println!("❌ Workflow not found: {}", id);
+        return Err(EngramError::not_found("workflow", id));
     }
     Ok(())
 }
@@ -399,12 +465,13 @@ pub fn delete_workflow<S: Storage>(storage: &mut S, id: &str) -> Result<(), Engr
         storage.store(&updated_generic)?;
         println!("✅ Workflow deleted (archived): {}", id);
     } else {
-        println!("❌ Workflow not found: {}", id);
+        return Err(EngramError::not_found("workflow", id));
     }
     Ok(())
 }
 
 /// List workflows
+#[allow(clippy::too_many_arguments)]
 pub fn list_workflows<S: Storage>(
     writer: &mut dyn std::io::Write,
     storage: &S,
@@ -413,6 +480,8 @@ pub fn list_workflows<S: Storage>(
     limit: usize,
     offset: usize,
     all: bool,
+    sort: &SortArgs,
+    count: &CountArgs,
 ) -> Result<(), EngramError> {
     use crate::cli::utils::{create_table, truncate};
     use crate::storage::QueryFilter;
@@ -429,6 +498,7 @@ pub fn list_workflows<S: Storage>(
         offset: Some(offset),
         ..Default::default()
     };
+    sort.apply(&mut filter);
 
     let mut field_filters = HashMap::new();
 
@@ -440,6 +510,10 @@ pub fn list_workflows<S: Storage>(
         filter.field_filters = field_filters;
     }
 
+    if count.write(writer, storage, &filter)? {
+        return Ok(());
+    }
+
     let result = storage.query(&filter)?;
 
     if result.entities.is_empty() {
@@ -541,6 +615,7 @@ pub fn list_workflows<S: Storage>(
 }
 
 /// Add state to workflow
+#[allow(clippy::too_many_arguments)]
 pub fn add_state<S: Storage>(
     storage: &mut S,
     id: &str,
@@ -548,7 +623,14 @@ pub fn add_state<S: Storage>(
     state_type: String,
     description: String,
     is_final: bool,
+    prompt: Option<String>,
+    prompt_file: Option<String>,
 ) -> Result<(), EngramError> {
+    let prompt = match prompt_file {
+        Some(path) => Some(std::fs::read_to_string(&path).map_err(EngramError::Io)?),
+        None => prompt,
+    };
+
     if let Some(generic) = storage.get(id, "workflow")? {
         let mut workflow =
             Workflow::from_generic(generic).map_err(|e| EngramError::Validation(e.to_string()))?;
@@ -573,7 +655,10 @@ pub fn add_state<S: Storage>(
             is_final,
             guards: Vec::new(),
             post_functions: Vec::new(),
-            prompts: None,
+            prompts: prompt.map(|user| PromptTemplate {
+                system: None,
+                user: Some(user),
+            }),
             commit_policy: None,
         };
 
@@ -593,12 +678,37 @@ pub fn add_state<S: Storage>(
 
         println!("✅ State added to workflow {}: {} ({})", id, name, state_id);
     } else {
-        println!("❌ Workflow not found: {}", id);
+        return Err(EngramError::not_found("workflow", id));
     }
     Ok(())
 }
 
+/// Split a `TYPE[=JSON]` reference into its type and JSON parameters
+fn parse_typed_reference(
+    spec: &str,
+) -> Result<(String, serde_json::Map<String, serde_json::Value>), EngramError> {
+    let (type_name, params) = match spec.split_once('=') {
+        Some((type_name, json)) => {
+            let params = serde_json::from_str::<serde_json::Value>(json).map_err(|e| {
+                EngramError::Validation(format!("Invalid JSON parameters in '{}': {}", spec, e))
+            })?;
+            match params {
+                serde_json::Value::Object(map) => (type_name, map),
+                _ => {
+                    return Err(EngramError::Validation(format!(
+                        "Parameters in '{}' must be a JSON object",
+                        spec
+                    )))
+                }
+            }
+        }
+        None => (spec, serde_json::Map::new()),
+    };
+    Ok((type_name.trim().to_string(), params))
+}
+
 /// Add transition to workflow
+#[allow(clippy::too_many_arguments)]
 pub fn add_transition<S: Storage>(
     storage: &mut S,
     id: &str,
@@ -607,7 +717,36 @@ pub fn add_transition<S: Storage>(
     to_state: String,
     transition_type: String,
     description: String,
+    actions: Vec<String>,
+    conditions: Vec<String>,
 ) -> Result<(), EngramError> {
+    let action_registry = ActionRegistry::builtin();
+    let guard_registry = GuardRegistry::builtin();
+    let mut transition_actions = Vec::new();
+    for spec in &actions {
+        let (action_type, parameters) = parse_typed_reference(spec)?;
+        let action = TransitionAction {
+            id: Uuid::new_v4().to_string(),
+            name: action_type.clone(),
+            action_type,
+            parameters: parameters.into_iter().collect(),
+            on_failure: None,
+        };
+        action_registry.validate_action(&action)?;
+        transition_actions.push(action);
+    }
+    let mut transition_conditions = Vec::new();
+    for spec in &conditions {
+        let (condition_type, logic) = parse_typed_reference(spec)?;
+        let condition = TransitionCondition {
+            id: Uuid::new_v4().to_string(),
+            condition_type,
+            logic: serde_json::Value::Object(logic),
+        };
+        guard_registry.validate_condition(&condition)?;
+        transition_conditions.push(condition);
+    }
+
     if let Some(generic) = storage.get(id, "workflow")? {
         let mut workflow =
             Workflow::from_generic(generic).map_err(|e| EngramError::Validation(e.to_string()))?;
@@ -632,8 +771,8 @@ pub fn add_transition<S: Storage>(
             to_state,
             transition_type,
             description,
-            conditions: Vec::new(),
-            actions: Vec::new(),
+            conditions: transition_conditions,
+            actions: transition_actions,
             trigger: None,
         };
 
@@ -648,7 +787,7 @@ pub fn add_transition<S: Storage>(
             id, name, transition_id
         );
     } else {
-        println!("❌ Workflow not found: {}", id);
+        return Err(EngramError::not_found("workflow", id));
     }
     Ok(())
 }
@@ -663,7 +802,7 @@ pub fn activate_workflow<S: Storage>(storage: &mut S, id: &str) -> Result<(), En
         storage.store(&updated_generic)?;
         println!("✅ Workflow activated: {}", id);
     } else {
-        println!("❌ Workflow not found: {}", id);
+        return Err(EngramError::not_found("workflow", id));
     }
     Ok(())
 }
@@ -869,8 +1008,11 @@ pub fn execute_workflow_transition<S: Storage + 'static>(
             }
         }
     } else {
-        println!("❌ Failed to execute transition");
-        println!("💬 Message: {}", result.message);
+        return Err(EngramError::conflict(
+            "workflow_instance",
+            instance_id,
+            result.message,
+        ));
     }
 
     Ok(())
@@ -883,79 +1025,218 @@ pub fn get_workflow_instance_status<S: Storage + 'static>(
 ) -> Result<(), EngramError> {
     let engine = WorkflowAutomationEngine::new(storage);
 
-    match engine.get_instance_status(&instance_id) {
-        Ok(instance) => {
-            println!("📋 Workflow Instance: {}", instance.id);
-            println!("🔗 Workflow ID: {}", instance.workflow_id);
-            println!("🔄 Current State: {}", instance.current_state);
-            println!("📊 Status: {}", instance.status);
+    let instance = engine.get_instance_status(&instance_id)?;
+    println!("📋 Workflow Instance: {}", instance.id);
+    println!("🔗 Workflow ID: {}", instance.workflow_id);
+    println!("🔄 Current State: {}", instance.current_state);
+    println!("📊 Status: {}", instance.status);
+    println!(
+        "🕐 Started: {}",
+        instance.started_at.format("%Y-%m-%d %H:%M:%S")
+    );
+    println!(
+        "🔄 Updated: {}",
+        instance.updated_at.format("%Y-%m-%d %H:%M:%S")
+    );
+
+    if let Some(completed) = instance.completed_at {
+        println!("🎯 Completed: {}", completed.format("%Y-%m-%d %H:%M:%S"));
+    }
+
+    println!("👤 Executing Agent: {}", instance.context.executing_agent);
+
+    if let Some(entity_id) = &instance.context.entity_id {
+        println!("🏷️ Associated Entity: {}", entity_id);
+        if let Some(entity_type) = &instance.context.entity_type {
+            println!("📦 Entity Type: {}", entity_type);
+        }
+    }
+
+    if !instance.context.variables.is_empty() {
+        println!("📋 Variables:");
+        for (key, value) in &instance.context.variables {
+            println!("  • {} = {:?}", key, value);
+        }
+    }
+
+    if !instance.execution_history.is_empty() {
+        println!(
+            "📚 Execution History ({} events):",
+            instance.total_event_count()
+        );
+        for (i, event) in instance.execution_history.iter().rev().take(5).enumerate() {
+            let event_icon = event_icon(&event.event_type);
+
             println!(
-                "🕐 Started: {}",
-                instance.started_at.format("%Y-%m-%d %H:%M:%S")
+                "  {}. {} {} - {} ({})",
+                i + 1,
+                event_icon,
+                event.timestamp.format("%H:%M:%S"),
+                event.message,
+                event.agent
             );
+        }
+
+        if instance.total_event_count() > 5 {
             println!(
-                "🔄 Updated: {}",
-                instance.updated_at.format("%Y-%m-%d %H:%M:%S")
+                "    ... and {} more events (engram workflow history {})",
+                instance.total_event_count() - 5,
+                instance.id
             );
+        }
+    }
 
-            if let Some(completed) = instance.completed_at {
-                println!("🎯 Completed: {}", completed.format("%Y-%m-%d %H:%M:%S"));
-            }
+    Ok(())
+}
 
-            println!("👤 Executing Agent: {}", instance.context.executing_agent);
+/// Template variables an instance provides to its state prompts: the
+/// instance variables and metadata, plus `instance_id`, `workflow_id`,
+/// `current_state`, `entity_id` and `entity_type`
+pub fn instance_prompt_variables(instance: &WorkflowInstance) -> HashMap<String, String> {
+    let mut variables: HashMap<String, String> = instance.context.metadata.clone();
+    for (key, value) in &instance.context.variables {
+        variables.insert(key.clone(), value.to_string());
+    }
+    variables.insert("instance_id".to_string(), instance.id.clone());
+    variables.insert("workflow_id".to_string(), instance.workflow_id.clone());
+    variables.insert("current_state".to_string(), instance.current_state.clone());
+    if let Some(entity_id) = &instance.context.entity_id {
+        variables.insert("entity_id".to_string(), entity_id.clone());
+    }
+    if let Some(entity_type) = &instance.context.entity_type {
+        variables.insert("entity_type".to_string(), entity_type.clone());
+    }
+    variables
+}
 
-            if let Some(entity_id) = &instance.context.entity_id {
-                println!("🏷️ Associated Entity: {}", entity_id);
-                if let Some(entity_type) = &instance.context.entity_type {
-                    println!("📦 Entity Type: {}", entity_type);
-                }
-            }
+/// Prompt of `instance`'s current state with `extra` and the instance
+/// variables substituted, or `None` when the state defines no prompt
+pub fn render_state_prompt(
+    workflow: &Workflow,
+    instance: &WorkflowInstance,
+    extra: &HashMap<String, String>,
+) -> Option<PromptTemplate> {
+    let state = workflow
+        .states
+        .iter()
+        .find(|s| s.name == instance.current_state || s.id == instance.current_state)?;
+    let prompts = state.prompts.as_ref()?;
+
+    let mut variables = extra.clone();
+    variables.extend(instance_prompt_variables(instance));
+    let render = |text: &Option<String>| {
+        text.as_deref()
+            .map(|text| crate::cli::next::interpolate(text, &variables))
+    };
+    Some(PromptTemplate {
+        system: render(&prompts.system),
+        user: render(&prompts.user),
+    })
+}
 
-            if !instance.context.variables.is_empty() {
-                println!("📋 Variables:");
-                for (key, value) in &instance.context.variables {
-                    println!("  • {} = {:?}", key, value);
-                }
+/// Most recently updated running instance attached to `entity_id`
+pub fn find_active_instance<S: Storage>(
+    storage: &S,
+    entity_id: &str,
+) -> Result<Option<WorkflowInstance>, EngramError> {
+    Ok(storage
+        .get_all(WorkflowInstance::entity_type())?
+        .into_iter()
+        .filter_map(|e| WorkflowInstance::from_generic(e).ok())
+        .filter(|instance| {
+            instance.status == crate::engines::workflow_engine::WorkflowStatus::Running
+                && instance.context.entity_id.as_deref() == Some(entity_id)
+        })
+        .max_by_key(|instance| instance.updated_at))
+}
+
+/// Print the rendered prompt of a workflow instance's current state
+pub fn show_workflow_prompt<S: Storage>(storage: &S, instance_id: &str) -> Result<(), EngramError> {
+    let instance = storage
+        .get(instance_id, WorkflowInstance::entity_type())?
+        .map(WorkflowInstance::from_generic)
+        .transpose()?
+        .ok_or_else(|| EngramError::not_found("workflow_instance", instance_id))?;
+    let workflow = storage
+        .get(&instance.workflow_id, Workflow::entity_type())?
+        .map(Workflow::from_generic)
+        .transpose()?
+        .ok_or_else(|| EngramError::not_found("workflow", instance.workflow_id.clone()))?;
+
+    match render_state_prompt(&workflow, &instance, &HashMap::new()) {
+        Some(prompt) => {
+            if let Some(system) = prompt.system {
+                println!("{}", system);
+                println!();
+            }
+            if let Some(user) = prompt.user {
+                println!("{}", user);
             }
+        }
+        None => println!(
+            "State '{}' of workflow '{}' has no prompt (set one with `engram workflow add-state --prompt`)",
+            instance.current_state, workflow.title
+        ),
+    }
+    Ok(())
+}
 
-            if !instance.execution_history.is_empty() {
-                println!(
-                    "📚 Execution History ({} events):",
-                    instance.execution_history.len()
-                );
-                for (i, event) in instance.execution_history.iter().rev().take(5).enumerate() {
-                    let event_icon = match event.event_type {
-                        crate::engines::workflow_engine::WorkflowEventType::Started => "🚀",
-                        crate::engines::workflow_engine::WorkflowEventType::Transitioned => "🔄",
-                        crate::engines::workflow_engine::WorkflowEventType::ActionExecuted => "⚡",
-                        crate::engines::workflow_engine::WorkflowEventType::Completed => "🎯",
-                        crate::engines::workflow_engine::WorkflowEventType::Cancelled => "❌",
-                        crate::engines::workflow_engine::WorkflowEventType::Failed => "💥",
-                        _ => "📝",
-                    };
+fn event_icon(event_type: &WorkflowEventType) -> &'static str {
+    match event_type {
+        WorkflowEventType::Started => "🚀",
+        WorkflowEventType::Transitioned => "🔄",
+        WorkflowEventType::ActionExecuted => "⚡",
+        WorkflowEventType::Completed => "🎯",
+        WorkflowEventType::Cancelled => "❌",
+        WorkflowEventType::Failed => "💥",
+        _ => "📝",
+    }
+}
 
-                    println!(
-                        "  {}. {} {} - {} ({})",
-                        i + 1,
-                        event_icon,
-                        event.timestamp.format("%H:%M:%S"),
-                        event.message,
-                        event.agent
-                    );
-                }
+/// Show a page of an instance's execution history, oldest first
+pub fn show_workflow_history<S: Storage + 'static>(
+    storage: S,
+    instance_id: String,
+    limit: usize,
+    since: Option<String>,
+) -> Result<(), EngramError> {
+    let since = since
+        .as_deref()
+        .map(crate::cli::session::parse_since)
+        .transpose()?;
+    let engine = WorkflowAutomationEngine::new(storage);
+    let history = engine.get_execution_history(&instance_id)?;
+    let total = history.len();
 
-                if instance.execution_history.len() > 5 {
-                    println!(
-                        "    ... and {} more events",
-                        instance.execution_history.len() - 5
-                    );
-                }
-            }
-        }
-        Err(e) => {
-            println!("❌ Workflow instance not found: {}", instance_id);
-            return Err(e);
-        }
+    let events: Vec<_> = history
+        .into_iter()
+        .filter(|event| since.is_none_or(|since| event.timestamp >= since))
+        .collect();
+    let matching = events.len();
+
+    println!(
+        "📚 Execution history for {} ({} of {} events)",
+        instance_id,
+        matching.min(limit),
+        total
+    );
+    for event in events.iter().take(limit) {
+        println!(
+            "  {} {} {:?} - {} ({})",
+            event_icon(&event.event_type),
+            event.timestamp.format("%Y-%m-%d %H:%M:%S"),
+            event.event_type,
+            event.message,
+            event.agent
+        );
+    }
+
+    if let Some(next) = events.get(limit) {
+        println!(
+            "  ... {} more; continue with --since {}",
+            matching - limit,
+            next.timestamp.format("%Y-%m-%dT%H:%M:%S")
+        );
     }
 
     Ok(())
@@ -969,7 +1250,11 @@ pub fn list_workflow_instances<S: Storage + 'static>(
     running_only: bool,
 ) -> Result<(), EngramError> {
     let engine = WorkflowAutomationEngine::new(storage);
-    let instances = engine.list_active_instances();
+    let instances = if running_only {
+        engine.list_active_instances()
+    } else {
+        engine.list_instances()
+    };
 
     let filtered_instances: Vec<_> = instances
         .into_iter()
@@ -1082,8 +1367,11 @@ pub fn cancel_workflow_instance<S: Storage + 'static>(
             }
         }
     } else {
-        println!("❌ Failed to cancel workflow instance");
-        println!("💬 Message: {}", result.message);
+        return Err(EngramError::conflict(
+            "workflow_instance",
+            instance_id,
+            result.message,
+        ));
     }
 
     Ok(())
@@ -1217,6 +1505,9 @@ pub fn query_workflow_actions<S: Storage>(
     workflow_id: String,
     state_id: Option<String>,
 ) -> Result<(), EngramError> {
+    let action_registry = ActionRegistry::builtin();
+    let guard_registry = GuardRegistry::builtin();
+
     if let Some(generic) = storage.get(&workflow_id, "workflow")? {
         let workflow =
             Workflow::from_generic(generic).map_err(|e| EngramError::Validation(e.to_string()))?;
@@ -1232,8 +1523,11 @@ pub fn query_workflow_actions<S: Storage>(
         };
 
         if states_to_show.is_empty() {
-            if state_id.is_some() {
-                println!("❌ State not found in workflow");
+            if let Some(sid) = state_id {
+                return Err(EngramError::not_found(
+                    format!("state in workflow {}", workflow.id),
+                    sid,
+                ));
             } else {
                 println!("ℹ️  No states defined in workflow");
             }
@@ -1249,9 +1543,13 @@ pub fn query_workflow_actions<S: Storage>(
                 println!("   🛡️  Guards ({}):", state.guards.len());
                 for guard in &state.guards {
                     println!("      • {} ({})", guard.guard_type, guard.id);
+                    println!("        Parameters: {}", guard.condition);
                     if !guard.error_message.is_empty() {
                         println!("        Error: {}", guard.error_message);
                     }
+                    if let Err(e) = guard_registry.validate_guard(guard) {
+                        println!("        ⚠️  {}", e);
+                    }
                 }
             }
 
@@ -1263,6 +1561,9 @@ pub fn query_workflow_actions<S: Storage>(
                         "      • {} - {} ({})",
                         func.name, func.function_type, func.id
                     );
+                    if let Err(e) = action_registry.validate_function(func) {
+                        println!("        ⚠️  {}", e);
+                    }
                 }
             }
 
@@ -1294,6 +1595,10 @@ pub fn query_workflow_actions<S: Storage>(
                     println!("     📋 Conditions ({}):", transition.conditions.len());
                     for condition in &transition.conditions {
                         println!("        • {} ({})", condition.condition_type, condition.id);
+                        println!("          Parameters: {}", condition.logic);
+                        if let Err(e) = guard_registry.validate_condition(condition) {
+                            println!("          ⚠️  {}", e);
+                        }
                     }
                 }
 
@@ -1312,6 +1617,9 @@ pub fn query_workflow_actions<S: Storage>(
                                 println!("          Command: {}", cmd);
                             }
                         }
+                        if let Err(e) = action_registry.validate_action(action) {
+                            println!("          ⚠️  {}", e);
+                        }
                     }
                 }
 
@@ -1319,11 +1627,89 @@ pub fn query_workflow_actions<S: Storage>(
             }
         }
 
+        print_type_specs("🧰 Available actions", &action_registry.actions);
+        print_type_specs("🛡️  Available state guards", &guard_registry.guards);
+        print_type_specs("📋 Available transition checks", &guard_registry.checks);
+
         println!(
             "💡 Use 'engram workflow execute-action --action-type <type> ...' to test actions"
         );
     } else {
-        println!("❌ Workflow not found: {}", workflow_id);
+        return Err(EngramError::not_found("workflow", workflow_id));
+    }
+
+    Ok(())
+}
+
+fn print_type_specs(heading: &str, specs: &[TypeSpec]) {
+    println!("{}:", heading);
+    for spec in specs {
+        println!("   • {} - {}", spec.name, spec.description);
+        for param in &spec.parameters {
+            println!(
+                "       {}{}: {} - {}",
+                param.name,
+                if param.required { "" } else { "?" },
+                param.param_type.label(),
+                param.description
+            );
+        }
+    }
+    println!();
+}
+
+/// Audit execution history across all instances of a workflow
+pub fn audit_workflow<S: Storage>(
+    storage: &S,
+    workflow_id: &str,
+    days: u32,
+    format: &str,
+) -> Result<(), EngramError> {
+    let report = crate::analytics::generate_workflow_audit_report(storage, workflow_id, days)?;
+
+    match format {
+        "json" => {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        }
+        "table" => {
+            println!("📋 Workflow Audit: {} (last {} days)", workflow_id, days);
+            println!("====================");
+            println!("Instances:  {}", report.total_instances);
+            println!("Completed:  {}", report.completed_instances);
+            println!("Failed:     {}", report.failed_instances);
+            println!("Avg duration: {:.1}s", report.average_duration_seconds);
+            if let Some(ref slowest) = report.slowest_instance_id {
+                println!("Slowest instance: {}", slowest);
+            }
+            if let Some(ref state) = report.most_common_failure_state {
+                println!("Most common failure state: {}", state);
+            }
+            println!();
+
+            println!("🔄 Transition heatmap (from → to):");
+            print!(
+                "{}",
+                crate::analytics::render_transition_matrix(&report.transition_heatmap)
+            );
+            println!();
+
+            if !report.agent_usage.is_empty() {
+                let mut agents: Vec<_> = report.agent_usage.iter().collect();
+                agents.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+                let mut table = crate::cli::utils::create_table();
+                table.set_titles(prettytable::row!["Agent", "Instances"]);
+                for (agent, count) in agents {
+                    table.add_row(prettytable::row![agent, count]);
+                }
+                table.printstd();
+            }
+        }
+        _ => {
+            return Err(EngramError::Validation(format!(
+                "Invalid format '{}'. Use table or json.",
+                format
+            )));
+        }
     }
 
     Ok(())
@@ -1360,6 +1746,8 @@ mod tests {
             "invalid_type".to_string(),
             "Desc".to_string(),
             false,
+            None,
+            None,
         )
         .unwrap();
 
@@ -1369,6 +1757,66 @@ mod tests {
         assert_eq!(workflow.states.len(), 0);
     }
 
+    #[test]
+    fn test_state_prompt_for_active_instance() {
+        use crate::engines::workflow_engine::{WorkflowExecutionContext, WorkflowStatus};
+
+        let mut storage = MemoryStorage::new("default");
+        let id = create_test_workflow(&mut storage, "Workflow");
+        add_state(
+            &mut storage,
+            &id,
+            "red".to_string(),
+            "in_progress".to_string(),
+            "Write tests".to_string(),
+            false,
+            Some("Write failing tests for {{TASK_TITLE}} in {{module}} first".to_string()),
+            None,
+        )
+        .unwrap();
+
+        let now = chrono::Utc::now();
+        let instance = WorkflowInstance {
+            id: "instance-1".to_string(),
+            workflow_id: id.clone(),
+            current_state: "red".to_string(),
+            context: WorkflowExecutionContext {
+                variables: HashMap::from([(
+                    "module".to_string(),
+                    RuleValue::String("auth".to_string()),
+                )]),
+                entity_id: Some("task-1".to_string()),
+                entity_type: Some("task".to_string()),
+                executing_agent: "test-agent".to_string(),
+                permissions: Vec::new(),
+                metadata: HashMap::new(),
+            },
+            status: WorkflowStatus::Running,
+            started_at: now,
+            updated_at: now,
+            completed_at: None,
+            execution_history: Vec::new(),
+            step_count: 0,
+            archived_event_count: 0,
+        };
+        storage.store(&instance.to_generic()).unwrap();
+
+        let active = find_active_instance(&storage, "task-1").unwrap().unwrap();
+        assert_eq!(active.id, "instance-1");
+        assert!(find_active_instance(&storage, "task-2").unwrap().is_none());
+
+        let workflow =
+            Workflow::from_generic(storage.get(&id, "workflow").unwrap().unwrap()).unwrap();
+        let extra = HashMap::from([("TASK_TITLE".to_string(), "Login".to_string())]);
+        let prompt = render_state_prompt(&workflow, &active, &extra).unwrap();
+        assert_eq!(
+            prompt.user.as_deref(),
+            Some("Write failing tests for Login in auth first")
+        );
+        assert!(show_workflow_prompt(&storage, "instance-1").is_ok());
+        assert!(show_workflow_prompt(&storage, "missing").is_err());
+    }
+
     #[test]
     fn test_add_transition_not_found() {
         let mut storage = MemoryStorage::new("default");
@@ -1379,9 +1827,11 @@ mod tests {
             "s1".to_string(),
             "s2".to_string(),
             "manual".to_string(),
-            "Desc".to_string()
+            "Desc".to_string(),
+            Vec::new(),
+            Vec::new(),
         )
-        .is_ok());
+        .is_err_and(|e| matches!(e, EngramError::NotFound { .. })));
     }
 
     #[test]
@@ -1397,6 +1847,8 @@ mod tests {
             "s2".to_string(),
             "invalid_type".to_string(),
             "Desc".to_string(),
+            Vec::new(),
+            Vec::new(),
         )
         .unwrap();
 
@@ -1405,10 +1857,59 @@ mod tests {
         assert_eq!(workflow.transitions.len(), 0);
     }
 
+    #[test]
+    fn test_add_transition_validates_actions_and_conditions() {
+        let mut storage = MemoryStorage::new("default");
+        let id = create_test_workflow(&mut storage, "Workflow");
+        let add = |storage: &mut MemoryStorage, actions: Vec<&str>, conditions: Vec<&str>| {
+            add_transition(
+                storage,
+                &id,
+                "Submit".to_string(),
+                "s1".to_string(),
+                "s2".to_string(),
+                "manual".to_string(),
+                "Desc".to_string(),
+                actions.into_iter().map(String::from).collect(),
+                conditions.into_iter().map(String::from).collect(),
+            )
+        };
+
+        let err = add(&mut storage, vec![r#"notify={"message":"hi"}"#], vec![])
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Unknown action type 'notify'"), "{}", err);
+        assert!(add(
+            &mut storage,
+            vec![],
+            vec![r#"command_guard={"args":["test"]}"#]
+        )
+        .is_err());
+
+        add(
+            &mut storage,
+            vec![r#"notification={"message":"Ready for review"}"#],
+            vec![r#"command_guard={"command":"cargo","args":["test"]}"#],
+        )
+        .unwrap();
+        let generic = storage.get(&id, "workflow").unwrap().unwrap();
+        let workflow = Workflow::from_generic(generic).unwrap();
+        assert_eq!(workflow.transitions.len(), 1);
+        assert_eq!(
+            workflow.transitions[0].actions[0].action_type,
+            "notification"
+        );
+        assert_eq!(
+            workflow.transitions[0].conditions[0].logic["command"],
+            "cargo"
+        );
+    }
+
     #[test]
     fn test_activate_workflow_not_found() {
         let mut storage = MemoryStorage::new("default");
-        assert!(activate_workflow(&mut storage, "non-existent").is_ok());
+        assert!(activate_workflow(&mut storage, "non-existent")
+            .is_err_and(|e| matches!(e, EngramError::NotFound { .. })));
     }
 
     #[test]
@@ -1423,13 +1924,14 @@ mod tests {
             None,
             None
         )
-        .is_ok());
+        .is_err_and(|e| matches!(e, EngramError::NotFound { .. })));
     }
 
     #[test]
     fn test_delete_workflow_not_found() {
         let mut storage = MemoryStorage::new("default");
-        assert!(delete_workflow(&mut storage, "non-existent").is_ok());
+        assert!(delete_workflow(&mut storage, "non-existent")
+            .is_err_and(|e| matches!(e, EngramError::NotFound { .. })));
     }
 
     #[test]
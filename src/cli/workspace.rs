@@ -0,0 +1,293 @@
+//! Workspace registry command implementations
+//!
+//! The registry at `~/.engram/workspaces.yaml` maps short names to workspace
+//! roots so any command can target a workspace with `--workspace <name>`
+//! instead of being run from its directory.
+
+use crate::error::EngramError;
+use clap::Subcommand;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Workspace registry commands
+#[derive(Debug, Subcommand)]
+pub enum WorkspaceCommands {
+    /// Register a workspace root under a name
+    ///
+    ///EXAMPLES:
+    ///  engram workspace register api ~/src/api
+    ///  engram workspace register api . --force
+    Register {
+        /// Name used with `--workspace`
+        name: String,
+
+        /// Workspace root directory
+        path: PathBuf,
+
+        /// Replace an existing registration with the same name
+        #[arg(long)]
+        force: bool,
+    },
+    /// List registered workspaces
+    List,
+    /// Remove a workspace from the registry (its files are left untouched)
+    Remove {
+        /// Registered name
+        name: String,
+    },
+    /// Show which workspace a command run from here would use
+    Current,
+}
+
+/// Named workspace roots, stored in `~/.engram/workspaces.yaml`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WorkspaceRegistry {
+    #[serde(default)]
+    pub workspaces: BTreeMap<String, PathBuf>,
+}
+
+impl WorkspaceRegistry {
+    /// Location of the user-level registry
+    pub fn default_path() -> Result<PathBuf, EngramError> {
+        dirs::home_dir()
+            .map(|home| home.join(".engram").join("workspaces.yaml"))
+            .ok_or_else(|| {
+                EngramError::Validation("Cannot locate home directory for workspaces.yaml".into())
+            })
+    }
+
+    /// Load the registry, or an empty one if the file does not exist yet
+    pub fn load(path: &Path) -> Result<Self, EngramError> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)?;
+        Ok(serde_yaml::from_str(&content)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), EngramError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_yaml::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// Register `path` as `name`. The path must be an existing directory and
+    /// is stored canonicalized.
+    pub fn register(
+        &mut self,
+        name: &str,
+        path: &Path,
+        force: bool,
+    ) -> Result<PathBuf, EngramError> {
+        if name.trim().is_empty() {
+            return Err(EngramError::Validation(
+                "Workspace name cannot be empty".to_string(),
+            ));
+        }
+        if !path.is_dir() {
+            return Err(EngramError::not_found(
+                "directory",
+                path.display().to_string(),
+            ));
+        }
+        if let Some(existing) = self.workspaces.get(name) {
+            if !force {
+                return Err(EngramError::AlreadyExists(format!(
+                    "Workspace '{}' is already registered at {} (use --force to replace it)",
+                    name,
+                    existing.display()
+                )));
+            }
+        }
+        let root = path.canonicalize()?;
+        self.workspaces.insert(name.to_string(), root.clone());
+        Ok(root)
+    }
+
+    pub fn remove(&mut self, name: &str) -> Result<PathBuf, EngramError> {
+        self.workspaces
+            .remove(name)
+            .ok_or_else(|| EngramError::not_found("workspace", name))
+    }
+
+    /// Resolve `--workspace`: a registered name first, then a directory path
+    pub fn resolve(&self, spec: &str) -> Result<PathBuf, EngramError> {
+        if let Some(root) = self.workspaces.get(spec) {
+            if !root.is_dir() {
+                return Err(EngramError::conflict("workspace", spec, format!("registered at {}, which no longer exists (update it with `engram workspace register {} <path> --force` or drop it with `engram workspace remove {}`)", root.display(), spec, spec)));
+            }
+            return Ok(root.clone());
+        }
+        let path = Path::new(spec);
+        if path.is_dir() {
+            return Ok(path.canonicalize()?);
+        }
+        Err(EngramError::not_found("workspace", spec))
+    }
+
+    /// Registered name for `root`, if any
+    pub fn name_for(&self, root: &Path) -> Option<&str> {
+        let root = root.canonicalize().ok()?;
+        self.workspaces
+            .iter()
+            .find(|(_, path)| **path == root)
+            .map(|(name, _)| name.as_str())
+    }
+}
+
+/// The workspace a command would use, as reported by `engram workspace current`
+#[derive(Debug, Serialize)]
+pub struct CurrentWorkspace {
+    pub root: PathBuf,
+    pub name: Option<String>,
+    pub initialized: bool,
+    /// Nearest initialized ancestor when `root` itself is not a workspace
+    pub enclosing: Option<PathBuf>,
+}
+
+/// Describe the workspace rooted at `dir`. Storage is always opened in the
+/// working directory, so an uninitialized `dir` inside another workspace is
+/// reported along with that enclosing workspace.
+pub fn current_workspace(registry: &WorkspaceRegistry, dir: &Path) -> CurrentWorkspace {
+    let root = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+    let initialized = root.join(".engram").is_dir();
+    let enclosing = if initialized {
+        None
+    } else {
+        root.ancestors()
+            .skip(1)
+            .find(|ancestor| ancestor.join(".engram").is_dir())
+            .map(Path::to_path_buf)
+    };
+    CurrentWorkspace {
+        name: registry.name_for(&root).map(str::to_string),
+        root,
+        initialized,
+        enclosing,
+    }
+}
+
+/// Change into the workspace named or located by `spec`, so every command
+/// that opens `.` uses it
+pub fn enter_workspace(spec: &str) -> Result<PathBuf, EngramError> {
+    let registry = WorkspaceRegistry::load(&WorkspaceRegistry::default_path()?)?;
+    let root = registry.resolve(spec)?;
+    std::env::set_current_dir(&root)?;
+    Ok(root)
+}
+
+/// Handle `engram workspace`
+pub fn handle_workspace_command(command: WorkspaceCommands, json: bool) -> Result<(), EngramError> {
+    let registry_path = WorkspaceRegistry::default_path()?;
+    let mut registry = WorkspaceRegistry::load(&registry_path)?;
+
+    match command {
+        WorkspaceCommands::Register { name, path, force } => {
+            let root = registry.register(&name, &path, force)?;
+            registry.save(&registry_path)?;
+            println!("✅ Registered workspace '{}' at {}", name, root.display());
+            if !root.join(".engram").is_dir() {
+                println!("⚠️  No .engram directory yet; run `engram setup workspace` there");
+            }
+        }
+        WorkspaceCommands::List => {
+            if json {
+                println!("{}", serde_json::to_string_pretty(&registry)?);
+            } else if registry.workspaces.is_empty() {
+                println!("No workspaces registered (engram workspace register <name> <path>)");
+            } else {
+                for (name, root) in &registry.workspaces {
+                    let status = if !root.is_dir() {
+                        " (missing)"
+                    } else if !root.join(".engram").is_dir() {
+                        " (not initialized)"
+                    } else {
+                        ""
+                    };
+                    println!("{:<20} {}{}", name, root.display(), status);
+                }
+            }
+        }
+        WorkspaceCommands::Remove { name } => {
+            let root = registry.remove(&name)?;
+            registry.save(&registry_path)?;
+            println!("🗑️  Removed workspace '{}' ({})", name, root.display());
+        }
+        WorkspaceCommands::Current => {
+            let current = current_workspace(&registry, &std::env::current_dir()?);
+            if json {
+                println!("{}", serde_json::to_string_pretty(&current)?);
+                return Ok(());
+            }
+            match &current.name {
+                Some(name) => println!("Workspace: {} ({})", name, current.root.display()),
+                None => println!("Workspace: {} (unregistered)", current.root.display()),
+            }
+            if !current.initialized {
+                println!("⚠️  No .engram directory here; commands would start a new workspace");
+                if let Some(enclosing) = &current.enclosing {
+                    let hint = registry
+                        .name_for(enclosing)
+                        .map(|name| format!("--workspace {}", name))
+                        .unwrap_or_else(|| format!("--workspace {}", enclosing.display()));
+                    println!(
+                        "   Inside workspace {}; run from there or pass {}",
+                        enclosing.display(),
+                        hint
+                    );
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_register_resolve_and_remove() {
+        let temp_dir = TempDir::new().unwrap();
+        let registry_path = temp_dir.path().join("home/.engram/workspaces.yaml");
+        let project = temp_dir.path().join("project");
+        fs::create_dir_all(project.join(".engram")).unwrap();
+
+        let mut registry = WorkspaceRegistry::load(&registry_path).unwrap();
+        registry.register("api", &project, false).unwrap();
+        assert!(matches!(
+            registry.register("api", &project, false),
+            Err(EngramError::AlreadyExists(_))
+        ));
+        registry.save(&registry_path).unwrap();
+
+        let registry = WorkspaceRegistry::load(&registry_path).unwrap();
+        let root = project.canonicalize().unwrap();
+        assert_eq!(registry.resolve("api").unwrap(), root);
+        assert_eq!(registry.resolve(&project.to_string_lossy()).unwrap(), root);
+        assert!(matches!(
+            registry.resolve("web"),
+            Err(EngramError::NotFound { .. })
+        ));
+
+        let current = current_workspace(&registry, &project.join(".engram"));
+        assert_eq!(current.name, None);
+        assert!(!current.initialized);
+        assert_eq!(current.enclosing, Some(root.clone()));
+        assert_eq!(registry.name_for(&project), Some("api"));
+
+        fs::remove_dir_all(&project).unwrap();
+        let err = registry.resolve("api").unwrap_err().to_string();
+        assert!(err.contains("no longer exists"));
+
+        let mut registry = registry;
+        assert_eq!(registry.remove("api").unwrap(), root);
+        assert!(registry.remove("api").is_err());
+    }
+}
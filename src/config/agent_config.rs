@@ -3,9 +3,10 @@
 //! Provides agent profile management and type definitions.
 
 use serde::{Deserialize, Serialize};
+use std::path::Path;
 
 /// Agent configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct AgentConfig {
     pub name: String,
     pub agent_type: String,
@@ -13,4 +14,47 @@ pub struct AgentConfig {
     pub email: Option<String>,
     /// Persona slug to use as system prompt (e.g. "01-the-one" or "the-architect")
     pub persona: Option<String>,
+    /// When a session counts as fatiguing for this agent
+    #[serde(default)]
+    pub fatigue: FatigueThresholds,
+}
+
+impl AgentConfig {
+    /// Read the profile for `agent` from `<workspace>/.engram/agents/<agent>.yaml`,
+    /// or `None` when there isn't a readable one
+    pub fn load_for_agent(workspace: &Path, agent: &str) -> Option<Self> {
+        let path = workspace
+            .join(".engram")
+            .join("agents")
+            .join(format!("{}.yaml", agent));
+        let yaml = std::fs::read_to_string(path).ok()?;
+        serde_yaml::from_str(&yaml).ok()
+    }
+}
+
+/// Per-agent fatigue thresholds, the `fatigue` section of an agent profile.
+/// Session load is active minutes plus `minutes_per_completed_task` for each
+/// task finished, measured against `optimal_session_minutes`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FatigueThresholds {
+    /// Session length the agent works best within
+    pub optimal_session_minutes: u64,
+    /// Extra load counted for each completed task
+    pub minutes_per_completed_task: u64,
+    /// Load, as a fraction of the optimal length, at which risk is moderate
+    pub moderate_ratio: f64,
+    /// Load, as a fraction of the optimal length, at which risk is high
+    pub high_ratio: f64,
+}
+
+impl Default for FatigueThresholds {
+    fn default() -> Self {
+        Self {
+            optimal_session_minutes: 90,
+            minutes_per_completed_task: 10,
+            moderate_ratio: 1.0,
+            high_ratio: 1.5,
+        }
+    }
 }
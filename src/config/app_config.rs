@@ -230,6 +230,7 @@ mod tests {
             specialization: None,
             email: None,
             persona: None,
+            fatigue: Default::default(),
         };
         other.agents.insert("test-agent".to_string(), agent);
 
@@ -335,6 +336,7 @@ mod tests {
             specialization: Some("spec1".to_string()),
             email: Some("a1@test.com".to_string()),
             persona: None,
+            fatigue: Default::default(),
         };
         other.agents.insert("agent1".to_string(), agent1);
 
@@ -344,6 +346,7 @@ mod tests {
             specialization: None,
             email: None,
             persona: None,
+            fatigue: Default::default(),
         };
         base.agents.insert("agent2".to_string(), agent2);
 
@@ -12,8 +12,13 @@ pub use app_config::*;
 pub use workspace_config::*;
 
 use crate::error::{ConfigError, EngramError};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Config files looked up in a workspace root, in order of preference
+pub const WORKSPACE_CONFIG_FILES: [&str; 2] = ["engram.yaml", "engram.yml"];
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -217,6 +222,38 @@ impl ConfigFeatures {
 }
 
 impl Config {
+    /// The first of [`WORKSPACE_CONFIG_FILES`] present in `workspace`
+    pub fn workspace_config_file(workspace: &Path) -> Option<PathBuf> {
+        WORKSPACE_CONFIG_FILES
+            .iter()
+            .map(|name| workspace.join(name))
+            .find(|path| path.exists())
+    }
+
+    /// Read the `section` of the workspace config file. A missing file or
+    /// section yields `T::default()`.
+    pub fn load_section<T>(workspace: &Path, section: &str) -> Result<T, EngramError>
+    where
+        T: DeserializeOwned + Default,
+    {
+        match Self::workspace_config_file(workspace) {
+            Some(path) => Self::section_from_yaml(&std::fs::read_to_string(path)?, section),
+            None => Ok(T::default()),
+        }
+    }
+
+    /// Parse the `section` of a config document, or `T::default()` when absent
+    pub fn section_from_yaml<T>(content: &str, section: &str) -> Result<T, EngramError>
+    where
+        T: DeserializeOwned + Default,
+    {
+        let value: serde_yaml::Value = serde_yaml::from_str(content)?;
+        match value.get(section) {
+            Some(section) => Ok(serde_yaml::from_value(section.clone())?),
+            None => Ok(T::default()),
+        }
+    }
+
     /// Load configuration from file
     pub fn load_from_file(path: &str) -> Result<Self, EngramError> {
         let content = std::fs::read_to_string(path).map_err(|e| {
@@ -504,6 +541,7 @@ mod tests {
                 specialization: None,
                 email: None,
                 persona: None,
+                fatigue: Default::default(),
             },
         );
         other.agents = other_agents;
@@ -622,6 +660,7 @@ mod tests {
                 specialization: None,
                 email: None,
                 persona: None,
+                fatigue: Default::default(),
             },
         );
 
@@ -634,6 +673,7 @@ mod tests {
                 specialization: None,
                 email: None,
                 persona: None,
+                fatigue: Default::default(),
             },
         );
 
@@ -692,4 +732,20 @@ mod tests {
         assert!(!features.enterprise);
         assert!(!features.nix_sandbox);
     }
+
+    #[test]
+    fn test_load_section_prefers_engram_yaml() {
+        let dir = tempfile::tempdir().unwrap();
+        let section: HashMap<String, u32> = Config::load_section(dir.path(), "limits").unwrap();
+        assert!(section.is_empty());
+
+        std::fs::write(dir.path().join("engram.yml"), "limits:\n  a: 1\n").unwrap();
+        let section: HashMap<String, u32> = Config::load_section(dir.path(), "limits").unwrap();
+        assert_eq!(section.get("a"), Some(&1));
+
+        std::fs::write(dir.path().join("engram.yaml"), "other: {}\n").unwrap();
+        let section: HashMap<String, u32> = Config::load_section(dir.path(), "limits").unwrap();
+        assert!(section.is_empty());
+        assert!(Config::load_section::<HashMap<String, u32>>(dir.path(), "other").is_ok());
+    }
 }
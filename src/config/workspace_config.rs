@@ -112,6 +112,7 @@ mod tests {
                 specialization: None,
                 email: None,
                 persona: None,
+                fatigue: Default::default(),
             },
         );
 
@@ -124,6 +125,7 @@ mod tests {
                 specialization: None,
                 email: None,
                 persona: None,
+                fatigue: Default::default(),
             },
         );
 
@@ -168,6 +170,7 @@ mod tests {
                 specialization: None,
                 email: None,
                 persona: None,
+                fatigue: Default::default(),
             },
         );
 
@@ -179,6 +182,7 @@ mod tests {
                 specialization: Some("new".to_string()),
                 email: None,
                 persona: None,
+                fatigue: Default::default(),
             },
         );
 
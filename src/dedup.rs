@@ -0,0 +1,290 @@
+//! Duplicate detection for entities about to be created
+//!
+//! Titles are compared after normalization (case, punctuation and spacing
+//! folded), scoring the better of word overlap and edit distance. Exact
+//! duplicates are detected by a content hash over the title and body, and
+//! [`find_similar_semantic`] adds embedding similarity when a provider is
+//! available.
+
+use crate::config::Config;
+use crate::entities::GenericEntity;
+use crate::error::EngramError;
+use crate::storage::Storage;
+use crate::vector::{cosine_similarity, EmbeddingProvider};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Similarity at or above which an existing entity counts as a duplicate
+pub const DEFAULT_THRESHOLD: f64 = 0.85;
+
+/// Statuses of entities that no longer count as duplicates
+const CLOSED_STATUSES: [&str; 5] = ["done", "cancelled", "archived", "deprecated", "superseded"];
+
+/// `dedup` section of `engram.yaml`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DedupConfig {
+    /// Check titles before creating tasks, contexts and knowledge
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    /// Minimum title similarity (0.0-1.0) reported as a duplicate
+    #[serde(default = "default_threshold")]
+    pub threshold: f64,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_threshold() -> f64 {
+    DEFAULT_THRESHOLD
+}
+
+impl Default for DedupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            threshold: DEFAULT_THRESHOLD,
+        }
+    }
+}
+
+impl DedupConfig {
+    /// Read the `dedup` section of `<workspace>/engram.yaml` (or
+    /// `engram.yml`). A missing file or section means the defaults.
+    pub fn load(workspace: &Path) -> Result<Self, EngramError> {
+        Self::validated(Config::load_section(workspace, "dedup")?)
+    }
+
+    pub fn from_yaml(content: &str) -> Result<Self, EngramError> {
+        Self::validated(Config::section_from_yaml(content, "dedup")?)
+    }
+
+    fn validated(config: Self) -> Result<Self, EngramError> {
+        if !(0.0..=1.0).contains(&config.threshold) {
+            return Err(EngramError::Validation(format!(
+                "dedup.threshold must be between 0.0 and 1.0, got {}",
+                config.threshold
+            )));
+        }
+        Ok(config)
+    }
+}
+
+/// An existing entity resembling the one being created
+#[derive(Debug, Clone, Serialize)]
+pub struct SimilarEntity {
+    pub id: String,
+    pub entity_type: String,
+    pub title: String,
+    pub similarity: f64,
+}
+
+/// Lowercase alphanumeric words separated by single spaces
+pub fn normalize_title(title: &str) -> String {
+    title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Similarity of two titles in 0.0-1.0: 1.0 when they normalize to the same
+/// text, otherwise the better of word-set overlap and edit distance
+pub fn title_similarity(a: &str, b: &str) -> f64 {
+    let (a, b) = (normalize_title(a), normalize_title(b));
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    if a == b {
+        return 1.0;
+    }
+    let words_a: HashSet<&str> = a.split(' ').collect();
+    let words_b: HashSet<&str> = b.split(' ').collect();
+    let overlap =
+        words_a.intersection(&words_b).count() as f64 / words_a.union(&words_b).count() as f64;
+    overlap.max(strsim::normalized_levenshtein(&a, &b))
+}
+
+/// Hash of an entity's normalized title and trimmed body (description or
+/// content), identifying exact duplicates
+pub fn content_hash(title: &str, body: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(normalize_title(title).as_bytes());
+    hasher.update([0]);
+    hasher.update(body.trim().as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+fn text_field<'a>(entity: &'a GenericEntity, field: &str) -> &'a str {
+    entity
+        .data
+        .get(field)
+        .and_then(|value| value.as_str())
+        .unwrap_or_default()
+}
+
+fn is_open(entity: &GenericEntity) -> bool {
+    !CLOSED_STATUSES.contains(&text_field(entity, "status"))
+}
+
+fn body(entity: &GenericEntity) -> &str {
+    match text_field(entity, "description") {
+        "" => text_field(entity, "content"),
+        description => description,
+    }
+}
+
+fn open_entities(
+    storage: &dyn Storage,
+    entity_type: &str,
+) -> Result<Vec<GenericEntity>, EngramError> {
+    Ok(storage
+        .get_all(entity_type)?
+        .into_iter()
+        .filter(is_open)
+        .collect())
+}
+
+/// Open entities of `entity_type` whose title is at least `threshold`
+/// similar to `title`, most similar first
+pub fn find_similar(
+    storage: &dyn Storage,
+    entity_type: &str,
+    title: &str,
+    threshold: f64,
+) -> Result<Vec<SimilarEntity>, EngramError> {
+    let mut similar: Vec<SimilarEntity> = open_entities(storage, entity_type)?
+        .into_iter()
+        .filter_map(|entity| {
+            let similarity = title_similarity(title, text_field(&entity, "title"));
+            (similarity >= threshold).then(|| SimilarEntity {
+                title: text_field(&entity, "title").to_string(),
+                id: entity.id,
+                entity_type: entity.entity_type,
+                similarity,
+            })
+        })
+        .collect();
+    similar.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+    Ok(similar)
+}
+
+/// ID of an open entity of `entity_type` with exactly this content hash
+pub fn find_exact_duplicate(
+    storage: &dyn Storage,
+    entity_type: &str,
+    hash: &str,
+) -> Result<Option<String>, EngramError> {
+    Ok(open_entities(storage, entity_type)?
+        .into_iter()
+        .find(|entity| content_hash(text_field(entity, "title"), body(entity)) == hash)
+        .map(|entity| entity.id))
+}
+
+/// Like [`find_similar`], scoring titles by embedding cosine similarity
+/// instead of text similarity
+pub async fn find_similar_semantic(
+    storage: &dyn Storage,
+    provider: &dyn EmbeddingProvider,
+    entity_type: &str,
+    title: &str,
+    threshold: f64,
+) -> Result<Vec<SimilarEntity>, EngramError> {
+    let entities = open_entities(storage, entity_type)?;
+    if entities.is_empty() {
+        return Ok(Vec::new());
+    }
+    let query = provider.embed(title).await?;
+    let titles: Vec<&str> = entities
+        .iter()
+        .map(|entity| text_field(entity, "title"))
+        .collect();
+    let embeddings = provider.embed_batch(&titles).await?;
+
+    let mut similar: Vec<SimilarEntity> = entities
+        .iter()
+        .zip(embeddings)
+        .filter_map(|(entity, embedding)| {
+            let similarity = cosine_similarity(&query, &embedding) as f64;
+            (similarity >= threshold).then(|| SimilarEntity {
+                id: entity.id.clone(),
+                entity_type: entity.entity_type.clone(),
+                title: text_field(entity, "title").to_string(),
+                similarity,
+            })
+        })
+        .collect();
+    similar.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+    Ok(similar)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{Entity, Task, TaskPriority, TaskStatus};
+    use crate::storage::MemoryStorage;
+
+    fn store_task(storage: &mut MemoryStorage, title: &str, status: TaskStatus) -> Task {
+        let mut task = Task::new(
+            title.to_string(),
+            "Sessions expire early".to_string(),
+            "agent".to_string(),
+            TaskPriority::Medium,
+            None,
+        );
+        task.status = status;
+        storage.store(&task.to_generic()).unwrap();
+        task
+    }
+
+    #[test]
+    fn test_find_similar_ignores_closed_entities() {
+        let mut storage = MemoryStorage::new("default");
+        let open = store_task(&mut storage, "Fix login timeout", TaskStatus::Todo);
+        store_task(&mut storage, "Fix login timeout!", TaskStatus::Done);
+        store_task(&mut storage, "Write release notes", TaskStatus::Todo);
+
+        let similar = find_similar(&storage, "task", "fix  Login-timeout", 0.85).unwrap();
+        assert_eq!(similar.len(), 1);
+        assert_eq!(similar[0].id, open.id);
+        assert_eq!(similar[0].similarity, 1.0);
+
+        assert!(title_similarity("Fix login timeout", "Fix the login timeout") >= 0.75);
+        assert!(find_similar(&storage, "context", "Fix login timeout", 0.5)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_exact_duplicate_by_content_hash() {
+        let mut storage = MemoryStorage::new("default");
+        let task = store_task(&mut storage, "Fix login timeout", TaskStatus::InProgress);
+
+        let same = content_hash("fix login timeout", "Sessions expire early\n");
+        assert_eq!(
+            find_exact_duplicate(&storage, "task", &same).unwrap(),
+            Some(task.id)
+        );
+        let different = content_hash("Fix login timeout", "Tokens expire early");
+        assert_eq!(
+            find_exact_duplicate(&storage, "task", &different).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_config_threshold_from_yaml() {
+        let config = DedupConfig::from_yaml("dedup:\n  threshold: 0.6\n").unwrap();
+        assert!(config.enabled);
+        assert_eq!(config.threshold, 0.6);
+        assert_eq!(
+            DedupConfig::from_yaml("quotas: {}\n").unwrap().threshold,
+            0.85
+        );
+        assert!(DedupConfig::from_yaml("dedup:\n  threshold: 2\n").is_err());
+    }
+}
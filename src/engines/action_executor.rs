@@ -4,6 +4,7 @@
 //! including external commands, notifications, and custom actions.
 
 use crate::error::EngramError;
+use crate::notify::{self, NotificationEvent, NotificationPriority};
 use crate::sandbox::ephemeral_env::NixSandboxConfig;
 use crate::sandbox::NixSandbox;
 use crate::Result;
@@ -30,6 +31,7 @@ pub enum ActionType {
     ExternalCommand,
     Notification,
     UpdateEntity,
+    HttpRequest,
     Custom,
 }
 
@@ -84,6 +86,7 @@ impl ActionExecutor {
             "external_command" => self.execute_external_command(parameters),
             "notification" => self.execute_notification(parameters),
             "update_entity" => self.execute_update_entity(parameters),
+            "http_request" => self.execute_http_request(parameters),
             _ => Err(EngramError::Validation(format!(
                 "Unknown action type: {}",
                 action_type
@@ -323,17 +326,52 @@ impl ActionExecutor {
             .and_then(|v| v.as_str())
             .ok_or_else(|| EngramError::Validation("Missing 'message' parameter".to_string()))?;
 
-        // For now, just log the notification
-        // In the future, this could send emails, Slack messages, etc.
+        let priority = parameters
+            .get("priority")
+            .and_then(|v| v.as_str())
+            .and_then(NotificationPriority::parse)
+            .unwrap_or(NotificationPriority::Medium);
+        let title = parameters
+            .get("title")
+            .and_then(|v| v.as_str())
+            .unwrap_or("Workflow notification");
+
         tracing::info!("Workflow notification: {}", message);
+        let report = notify::dispatch(&NotificationEvent::new(
+            notify::WORKFLOW_NOTIFICATION,
+            priority,
+            title,
+            message,
+        ));
+
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "sinks_delivered".to_string(),
+            report.delivered.len().to_string(),
+        );
+        metadata.insert(
+            "sinks_failed".to_string(),
+            report.failures.len().to_string(),
+        );
 
         Ok(ActionResult {
             success: true,
             message: format!("Notification sent: {}", message),
             output: None,
-            error: None,
+            error: if report.failures.is_empty() {
+                None
+            } else {
+                Some(
+                    report
+                        .failures
+                        .iter()
+                        .map(|(sink, e)| format!("{}: {}", sink, e))
+                        .collect::<Vec<_>>()
+                        .join("; "),
+                )
+            },
             exit_code: None,
-            metadata: HashMap::new(),
+            metadata,
         })
     }
 
@@ -371,6 +409,96 @@ impl ActionExecutor {
             metadata: HashMap::new(),
         })
     }
+
+    /// Execute an HTTP request action
+    fn execute_http_request(
+        &self,
+        parameters: &HashMap<String, serde_json::Value>,
+    ) -> Result<ActionResult> {
+        let url = parameters
+            .get("url")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| EngramError::Validation("Missing 'url' parameter".to_string()))?
+            .to_string();
+        let method = parameters
+            .get("method")
+            .and_then(|v| v.as_str())
+            .unwrap_or("GET");
+        let method = reqwest::Method::from_bytes(method.to_uppercase().as_bytes())
+            .map_err(|_| EngramError::Validation(format!("Invalid HTTP method '{}'", method)))?;
+        let headers: Vec<(String, String)> = parameters
+            .get("headers")
+            .and_then(|v| v.as_object())
+            .map(|obj| {
+                obj.iter()
+                    .filter_map(|(k, v)| v.as_str().map(|s| (k.clone(), s.to_string())))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let body = parameters.get("body").cloned();
+        let timeout = Duration::from_secs(
+            parameters
+                .get("timeout_seconds")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(30),
+        );
+        let expected_status = parameters
+            .get("expected_status")
+            .and_then(|v| v.as_u64())
+            .map(|code| code as u16);
+
+        // The blocking client must not run on an async runtime thread
+        let request_url = url.clone();
+        let response = std::thread::spawn(move || -> std::result::Result<_, String> {
+            let client = reqwest::blocking::Client::builder()
+                .timeout(timeout)
+                .build()
+                .map_err(|e| e.to_string())?;
+            let mut request = client.request(method, &request_url);
+            for (name, value) in headers {
+                request = request.header(name, value);
+            }
+            request = match body {
+                Some(serde_json::Value::String(text)) => request.body(text),
+                Some(value) => request.json(&value),
+                None => request,
+            };
+            let response = request.send().map_err(|e| e.to_string())?;
+            let status = response.status();
+            let text = response.text().unwrap_or_default();
+            Ok((status, text))
+        })
+        .join()
+        .map_err(|_| EngramError::Validation("HTTP request thread panicked".to_string()))?;
+
+        let mut metadata = HashMap::new();
+        metadata.insert("url".to_string(), url.clone());
+        match response {
+            Ok((status, text)) => {
+                metadata.insert("status".to_string(), status.as_u16().to_string());
+                let success = match expected_status {
+                    Some(expected) => status.as_u16() == expected,
+                    None => status.is_success(),
+                };
+                Ok(ActionResult {
+                    success,
+                    message: format!("HTTP request to {} returned {}", url, status),
+                    output: Some(text),
+                    error: (!success).then(|| format!("Unexpected status {}", status)),
+                    exit_code: None,
+                    metadata,
+                })
+            }
+            Err(e) => Ok(ActionResult {
+                success: false,
+                message: format!("HTTP request to {} failed", url),
+                output: None,
+                error: Some(e),
+                exit_code: None,
+                metadata,
+            }),
+        }
+    }
 }
 
 #[cfg(test)]
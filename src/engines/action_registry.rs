@@ -0,0 +1,464 @@
+//! Registry of built-in workflow actions, guards and checks
+//!
+//! Describes every action type the [`ActionExecutor`](super::ActionExecutor)
+//! can run and every guard and transition condition the workflow engine
+//! evaluates, with the parameters each accepts. Workflow definitions are
+//! validated against it so a misspelled type or parameter is rejected when
+//! the workflow is built instead of being silently ignored at run time.
+
+use crate::entities::{StateFunction, StateGuard, TransitionAction, TransitionCondition, Workflow};
+use crate::error::EngramError;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// JSON type a parameter must have
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParamType {
+    String,
+    Integer,
+    Boolean,
+    StringList,
+    StringMap,
+    Any,
+}
+
+impl ParamType {
+    pub fn matches(self, value: &serde_json::Value) -> bool {
+        match self {
+            ParamType::String => value.is_string(),
+            ParamType::Integer => value.is_i64() || value.is_u64(),
+            ParamType::Boolean => value.is_boolean(),
+            ParamType::StringList => value
+                .as_array()
+                .is_some_and(|items| items.iter().all(|item| item.is_string())),
+            ParamType::StringMap => value
+                .as_object()
+                .is_some_and(|map| map.values().all(|item| item.is_string())),
+            ParamType::Any => true,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ParamType::String => "string",
+            ParamType::Integer => "integer",
+            ParamType::Boolean => "boolean",
+            ParamType::StringList => "list of strings",
+            ParamType::StringMap => "map of strings",
+            ParamType::Any => "any",
+        }
+    }
+}
+
+/// One parameter of an action, guard or check
+#[derive(Debug, Clone, Serialize)]
+pub struct ParamSpec {
+    pub name: &'static str,
+    pub param_type: ParamType,
+    pub required: bool,
+    pub description: &'static str,
+}
+
+const fn param(
+    name: &'static str,
+    param_type: ParamType,
+    required: bool,
+    description: &'static str,
+) -> ParamSpec {
+    ParamSpec {
+        name,
+        param_type,
+        required,
+        description,
+    }
+}
+
+/// A registered action, guard or check type
+#[derive(Debug, Clone, Serialize)]
+pub struct TypeSpec {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub parameters: Vec<ParamSpec>,
+}
+
+impl TypeSpec {
+    /// Check `params` against this type's schema
+    pub fn validate(
+        &self,
+        kind: &str,
+        params: &serde_json::Map<String, serde_json::Value>,
+    ) -> Result<(), EngramError> {
+        for spec in &self.parameters {
+            match params.get(spec.name) {
+                None if spec.required => {
+                    return Err(EngramError::Validation(format!(
+                        "{} '{}' requires parameter '{}' ({})",
+                        kind,
+                        self.name,
+                        spec.name,
+                        spec.param_type.label()
+                    )))
+                }
+                Some(value) if !spec.param_type.matches(value) => {
+                    return Err(EngramError::Validation(format!(
+                        "{} '{}' parameter '{}' must be a {}, got {}",
+                        kind,
+                        self.name,
+                        spec.name,
+                        spec.param_type.label(),
+                        value
+                    )))
+                }
+                _ => {}
+            }
+        }
+        for name in params.keys() {
+            if !self.parameters.iter().any(|spec| spec.name == name) {
+                let known: Vec<&str> = self.parameters.iter().map(|spec| spec.name).collect();
+                return Err(EngramError::Validation(format!(
+                    "{} '{}' has no parameter '{}'{}",
+                    kind,
+                    self.name,
+                    name,
+                    suggestion(name, &known)
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Closest known name to `name`, formatted as a hint, or the full list of
+/// names when nothing is close
+fn suggestion(name: &str, known: &[&str]) -> String {
+    let closest = known
+        .iter()
+        .map(|candidate| (candidate, strsim::normalized_levenshtein(name, candidate)))
+        .filter(|(_, score)| *score >= 0.5)
+        .max_by(|a, b| a.1.total_cmp(&b.1));
+    match closest {
+        Some((candidate, _)) => format!(" (did you mean '{}'?)", candidate),
+        None if known.is_empty() => String::new(),
+        None => format!(" (available: {})", known.join(", ")),
+    }
+}
+
+fn lookup<'a>(specs: &'a [TypeSpec], kind: &str, name: &str) -> Result<&'a TypeSpec, EngramError> {
+    specs.iter().find(|spec| spec.name == name).ok_or_else(|| {
+        let known: Vec<&str> = specs.iter().map(|spec| spec.name).collect();
+        EngramError::Validation(format!(
+            "Unknown {} type '{}'{}",
+            kind,
+            name,
+            suggestion(name, &known)
+        ))
+    })
+}
+
+fn as_params(
+    map: &HashMap<String, serde_json::Value>,
+) -> serde_json::Map<String, serde_json::Value> {
+    map.iter().map(|(k, v)| (k.clone(), v.clone())).collect()
+}
+
+fn as_object(
+    kind: &str,
+    name: &str,
+    value: &serde_json::Value,
+) -> Result<serde_json::Map<String, serde_json::Value>, EngramError> {
+    value.as_object().cloned().ok_or_else(|| {
+        EngramError::Validation(format!(
+            "{} '{}' parameters must be a JSON object, got {}",
+            kind, name, value
+        ))
+    })
+}
+
+/// Action types runnable as transition actions and state post-functions
+#[derive(Debug, Clone, Serialize)]
+pub struct ActionRegistry {
+    pub actions: Vec<TypeSpec>,
+}
+
+impl ActionRegistry {
+    pub fn builtin() -> Self {
+        use ParamType::*;
+        Self {
+            actions: vec![
+                TypeSpec {
+                    name: "external_command",
+                    description: "Run a command, optionally inside the Nix sandbox",
+                    parameters: vec![
+                        param("command", String, true, "Executable to run"),
+                        param("args", StringList, false, "Command arguments"),
+                        param("working_directory", String, false, "Directory to run in"),
+                        param(
+                            "environment",
+                            StringMap,
+                            false,
+                            "Extra environment variables",
+                        ),
+                        param("timeout_seconds", Integer, false, "Timeout (default 300)"),
+                        param("capture_output", Boolean, false, "Capture stdout/stderr"),
+                        param(
+                            "nix_packages",
+                            StringList,
+                            false,
+                            "Packages for the Nix sandbox",
+                        ),
+                    ],
+                },
+                TypeSpec {
+                    name: "notification",
+                    description: "Send a notification through the configured sinks",
+                    parameters: vec![
+                        param("message", String, true, "Notification body"),
+                        param("title", String, false, "Notification title"),
+                        param("priority", String, false, "low, medium, high or critical"),
+                    ],
+                },
+                TypeSpec {
+                    name: "update_entity",
+                    description: "Record an update to an entity",
+                    parameters: vec![
+                        param("entity_id", String, true, "Entity to update"),
+                        param("entity_type", String, true, "Type of the entity"),
+                    ],
+                },
+                TypeSpec {
+                    name: "http_request",
+                    description: "Send an HTTP request, failing on an unexpected status",
+                    parameters: vec![
+                        param("url", String, true, "Request URL"),
+                        param("method", String, false, "HTTP method (default GET)"),
+                        param("headers", StringMap, false, "Request headers"),
+                        param("body", Any, false, "Body; objects are sent as JSON"),
+                        param("timeout_seconds", Integer, false, "Timeout (default 30)"),
+                        param(
+                            "expected_status",
+                            Integer,
+                            false,
+                            "Required status (default 2xx)",
+                        ),
+                    ],
+                },
+            ],
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Result<&TypeSpec, EngramError> {
+        lookup(&self.actions, "action", name)
+    }
+
+    pub fn validate_action(&self, action: &TransitionAction) -> Result<(), EngramError> {
+        self.get(&action.action_type)?
+            .validate("Action", &as_params(&action.parameters))
+    }
+
+    pub fn validate_function(&self, function: &StateFunction) -> Result<(), EngramError> {
+        self.get(&function.function_type)?
+            .validate("Post-function", &as_params(&function.parameters))
+    }
+}
+
+/// State guard and transition condition (check) types
+#[derive(Debug, Clone, Serialize)]
+pub struct GuardRegistry {
+    /// Guards evaluated before entering a state
+    pub guards: Vec<TypeSpec>,
+    /// Conditions evaluated before taking a transition
+    pub checks: Vec<TypeSpec>,
+}
+
+impl GuardRegistry {
+    pub fn builtin() -> Self {
+        use ParamType::*;
+        Self {
+            guards: vec![TypeSpec {
+                name: "permission",
+                description: "The executing agent must hold a permission",
+                parameters: vec![param("permission", String, true, "Required permission")],
+            }],
+            checks: vec![
+                TypeSpec {
+                    name: "field",
+                    description: "An instance variable must equal a value",
+                    parameters: vec![
+                        param("field", String, true, "Instance variable name"),
+                        param("equals", Any, false, "Expected value"),
+                    ],
+                },
+                TypeSpec {
+                    name: "rule",
+                    description: "A rule expression over the instance variables must hold",
+                    parameters: vec![param("expression", String, true, "Rule expression")],
+                },
+                TypeSpec {
+                    name: "command_guard",
+                    description: "A command must exit with the expected code",
+                    parameters: vec![
+                        param("command", String, true, "Executable to run"),
+                        param("args", StringList, false, "Command arguments"),
+                        param("working_directory", String, false, "Directory to run in"),
+                        param(
+                            "environment",
+                            StringMap,
+                            false,
+                            "Extra environment variables",
+                        ),
+                        param(
+                            "inject_env_vars",
+                            StringList,
+                            false,
+                            "Variables passed through",
+                        ),
+                        param("timeout_seconds", Integer, false, "Timeout (default 300)"),
+                        param(
+                            "expected_exit_code",
+                            Integer,
+                            false,
+                            "Passing exit code (default 0)",
+                        ),
+                    ],
+                },
+            ],
+        }
+    }
+
+    pub fn guard(&self, name: &str) -> Result<&TypeSpec, EngramError> {
+        lookup(&self.guards, "guard", name)
+    }
+
+    pub fn check(&self, name: &str) -> Result<&TypeSpec, EngramError> {
+        lookup(&self.checks, "check", name)
+    }
+
+    pub fn validate_guard(&self, guard: &StateGuard) -> Result<(), EngramError> {
+        let params = as_object("Guard", &guard.guard_type, &guard.condition)?;
+        self.guard(&guard.guard_type)?.validate("Guard", &params)
+    }
+
+    pub fn validate_condition(&self, condition: &TransitionCondition) -> Result<(), EngramError> {
+        let params = as_object("Check", &condition.condition_type, &condition.logic)?;
+        self.check(&condition.condition_type)?
+            .validate("Check", &params)
+    }
+}
+
+/// Check every guard, post-function, condition and action referenced by
+/// `workflow` against the built-in registries
+pub fn validate_workflow_references(workflow: &Workflow) -> Result<(), EngramError> {
+    let actions = ActionRegistry::builtin();
+    let guards = GuardRegistry::builtin();
+    let located =
+        |place: String| move |e: EngramError| EngramError::Validation(format!("{}: {}", place, e));
+
+    for state in &workflow.states {
+        for guard in &state.guards {
+            guards
+                .validate_guard(guard)
+                .map_err(located(format!("State '{}'", state.name)))?;
+        }
+        for function in &state.post_functions {
+            actions
+                .validate_function(function)
+                .map_err(located(format!("State '{}'", state.name)))?;
+        }
+    }
+    for transition in &workflow.transitions {
+        for condition in &transition.conditions {
+            guards
+                .validate_condition(condition)
+                .map_err(located(format!("Transition '{}'", transition.name)))?;
+        }
+        for action in &transition.actions {
+            actions
+                .validate_action(action)
+                .map_err(located(format!("Transition '{}'", transition.name)))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn action(action_type: &str, parameters: serde_json::Value) -> TransitionAction {
+        TransitionAction {
+            id: "a1".to_string(),
+            name: "action".to_string(),
+            action_type: action_type.to_string(),
+            parameters: serde_json::from_value(parameters).unwrap(),
+            on_failure: None,
+        }
+    }
+
+    #[test]
+    fn test_action_validation() {
+        let registry = ActionRegistry::builtin();
+        assert!(registry
+            .validate_action(&action("notification", json!({"message": "Ready"})))
+            .is_ok());
+
+        let err = registry
+            .validate_action(&action("notifcation", json!({"message": "Ready"})))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("did you mean 'notification'"), "{}", err);
+
+        let err = registry
+            .validate_action(&action(
+                "external_command",
+                json!({"command": "make", "args": "all"}),
+            ))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("must be a list of strings"), "{}", err);
+
+        let err = registry
+            .validate_action(&action(
+                "http_request",
+                json!({"url": "http://x", "methd": "POST"}),
+            ))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("did you mean 'method'"), "{}", err);
+
+        assert!(registry
+            .validate_action(&action("update_entity", json!({"entity_id": "t1"})))
+            .is_err());
+    }
+
+    #[test]
+    fn test_condition_and_guard_validation() {
+        let registry = GuardRegistry::builtin();
+        let condition = |condition_type: &str, logic| TransitionCondition {
+            id: "c1".to_string(),
+            condition_type: condition_type.to_string(),
+            logic,
+        };
+        assert!(registry
+            .validate_condition(&condition(
+                "command_guard",
+                json!({"command": "cargo", "args": ["test"]})
+            ))
+            .is_ok());
+        assert!(registry
+            .validate_condition(&condition("field", json!("approved")))
+            .is_err());
+        assert!(registry
+            .validate_condition(&condition("approval", json!({})))
+            .is_err());
+
+        let guard = StateGuard {
+            id: "g1".to_string(),
+            guard_type: "permission".to_string(),
+            condition: json!({"permission": 3}),
+            error_message: String::new(),
+        };
+        assert!(registry.validate_guard(&guard).is_err());
+    }
+}
@@ -4,9 +4,13 @@
 //! and system automation.
 
 pub mod action_executor;
+pub mod action_registry;
+pub mod recurring_task_manager;
 pub mod rule_engine;
 pub mod workflow_engine;
 
 pub use action_executor::*;
+pub use action_registry::*;
+pub use recurring_task_manager::*;
 pub use rule_engine::*;
 pub use workflow_engine::*;
@@ -0,0 +1,410 @@
+//! Recurring task scheduling
+//!
+//! [`RecurringTaskManager::tick`] walks every stored [`RecurringTaskConfig`]
+//! and creates a new instance of its base task when one is due: at the next
+//! cron time for scheduled recurrences, or once the latest instance is done
+//! for completion-triggered ones. Missed scheduled runs are not backfilled;
+//! a tick creates at most one instance per config and schedules the next
+//! run after `now`.
+
+use crate::entities::{Entity, RecurrenceTrigger, RecurringTaskConfig, Task, TaskStatus};
+use crate::error::EngramError;
+use crate::storage::Storage;
+use chrono::{DateTime, Datelike, Duration, TimeZone, Timelike, Utc};
+
+/// Metadata key linking an instance back to its recurrence
+pub const RECURRING_TASK_METADATA_KEY: &str = "recurring_task_id";
+
+/// How far ahead `next_after` searches before giving up (e.g. `0 0 30 2 *`)
+const MAX_LOOKAHEAD_DAYS: i64 = 366 * 5;
+
+const MONTH_NAMES: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+const WEEKDAY_NAMES: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// Parsed 5-field cron expression (`minute hour day-of-month month day-of-week`)
+///
+/// Supports `*`, single values, ranges, lists and `/step`, plus `JAN`-`DEC`
+/// and `SUN`-`SAT` names. Times are evaluated in UTC. As in cron, when both
+/// day fields are restricted a day matches if either does.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    day_of_month_restricted: bool,
+    day_of_week_restricted: bool,
+}
+
+fn parse_value(value: &str, min: u32, max: u32, names: &[&str]) -> Result<u32, String> {
+    let upper = value.to_ascii_uppercase();
+    let parsed = match names.iter().position(|n| *n == upper) {
+        // Month names start at 1, weekday names at 0
+        Some(i) => i as u32 + min,
+        None => value
+            .parse::<u32>()
+            .map_err(|_| format!("'{}' is not a number", value))?,
+    };
+    if parsed < min || parsed > max {
+        return Err(format!("{} is outside {}-{}", parsed, min, max));
+    }
+    Ok(parsed)
+}
+
+/// Bitmask of the values selected by one cron field
+fn parse_field(field: &str, min: u32, max: u32, names: &[&str]) -> Result<u64, String> {
+    let mut mask = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .ok()
+                    .filter(|s| *s > 0)
+                    .ok_or_else(|| format!("invalid step '{}'", step))?;
+                (range, step)
+            }
+            None => (part, 1),
+        };
+
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (
+                parse_value(start, min, max, names)?,
+                parse_value(end, min, max, names)?,
+            )
+        } else {
+            let value = parse_value(range, min, max, names)?;
+            // `5/15` means "from 5 to the end, every 15"
+            (value, if part.contains('/') { max } else { value })
+        };
+        if start > end {
+            return Err(format!("range {}-{} is reversed", start, end));
+        }
+
+        for value in (start..=end).step_by(step as usize) {
+            mask |= 1 << value;
+        }
+    }
+    Ok(mask)
+}
+
+impl CronSchedule {
+    /// Parse a 5-field cron expression
+    pub fn parse(expression: &str) -> Result<Self, EngramError> {
+        let invalid = |detail: String| {
+            EngramError::Validation(format!(
+                "Invalid cron expression '{}': {}",
+                expression, detail
+            ))
+        };
+
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let [minute, hour, day_of_month, month, day_of_week] = fields[..] else {
+            return Err(invalid(format!("expected 5 fields, got {}", fields.len())));
+        };
+
+        let mut days_of_week = parse_field(day_of_week, 0, 7, &WEEKDAY_NAMES).map_err(invalid)?;
+        // 7 is an alias for Sunday
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week = (days_of_week | 1) & !(1 << 7);
+        }
+
+        Ok(Self {
+            minutes: parse_field(minute, 0, 59, &[]).map_err(invalid)?,
+            hours: parse_field(hour, 0, 23, &[]).map_err(invalid)?,
+            days_of_month: parse_field(day_of_month, 1, 31, &[]).map_err(invalid)?,
+            months: parse_field(month, 1, 12, &MONTH_NAMES).map_err(invalid)?,
+            days_of_week,
+            day_of_month_restricted: day_of_month != "*",
+            day_of_week_restricted: day_of_week != "*",
+        })
+    }
+
+    fn matches_day(&self, time: DateTime<Utc>) -> bool {
+        let dom = self.days_of_month & (1 << time.day()) != 0;
+        let dow = self.days_of_week & (1 << time.weekday().num_days_from_sunday()) != 0;
+        if self.day_of_month_restricted && self.day_of_week_restricted {
+            dom || dow
+        } else {
+            dom && dow
+        }
+    }
+
+    /// First matching minute strictly after `after`, if any within five years
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let mut time = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let limit = after + Duration::days(MAX_LOOKAHEAD_DAYS);
+
+        while time <= limit {
+            if self.months & (1 << time.month()) == 0 {
+                let (year, month) = if time.month() == 12 {
+                    (time.year() + 1, 1)
+                } else {
+                    (time.year(), time.month() + 1)
+                };
+                time = Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0).single()?;
+            } else if !self.matches_day(time) {
+                time = time.with_hour(0)?.with_minute(0)? + Duration::days(1);
+            } else if self.hours & (1 << time.hour()) == 0 {
+                time = time.with_minute(0)? + Duration::hours(1);
+            } else if self.minutes & (1 << time.minute()) == 0 {
+                time += Duration::minutes(1);
+            } else {
+                return Some(time);
+            }
+        }
+        None
+    }
+}
+
+/// Creates due instances of recurring tasks
+pub struct RecurringTaskManager;
+
+impl RecurringTaskManager {
+    /// Create every instance due at `now`, returning the new tasks
+    pub fn tick<S: Storage + ?Sized>(
+        storage: &mut S,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<Task>, EngramError> {
+        let mut created = Vec::new();
+
+        for entity in storage.get_all(RecurringTaskConfig::entity_type())? {
+            let mut config = RecurringTaskConfig::from_generic(entity)?;
+            if config.is_exhausted() {
+                continue;
+            }
+
+            let Some(base) = storage
+                .get(&config.base_task_id, Task::entity_type())?
+                .map(Task::from_generic)
+                .transpose()?
+            else {
+                tracing::warn!(
+                    "Recurring task {} refers to missing base task {}",
+                    config.id,
+                    config.base_task_id
+                );
+                continue;
+            };
+
+            if !Self::is_due(storage, &config, now)? {
+                continue;
+            }
+
+            let instance = Self::spawn_instance(&base, &config);
+            storage.store(&instance.to_generic())?;
+
+            config.instance_ids.push(instance.id.clone());
+            if let RecurrenceTrigger::OnSchedule(expression) = &config.auto_create_on {
+                config.next_trigger = CronSchedule::parse(expression)?.next_after(now);
+            }
+            storage.store(&config.to_generic())?;
+
+            created.push(instance);
+        }
+
+        Ok(created)
+    }
+
+    fn is_due<S: Storage + ?Sized>(
+        storage: &S,
+        config: &RecurringTaskConfig,
+        now: DateTime<Utc>,
+    ) -> Result<bool, EngramError> {
+        match config.auto_create_on {
+            RecurrenceTrigger::OnSchedule(_) => Ok(config.next_trigger.is_some_and(|t| t <= now)),
+            RecurrenceTrigger::OnCompletion => {
+                let current = config.instance_ids.last().unwrap_or(&config.base_task_id);
+                Ok(storage
+                    .get(current, Task::entity_type())?
+                    .map(Task::from_generic)
+                    .transpose()?
+                    .is_some_and(|task| task.status == TaskStatus::Done))
+            }
+        }
+    }
+
+    fn spawn_instance(base: &Task, config: &RecurringTaskConfig) -> Task {
+        let mut instance = Task::new(
+            base.title.clone(),
+            base.description.clone(),
+            base.agent.clone(),
+            base.priority.clone(),
+            base.workflow_id.clone(),
+        );
+        instance.parent = base.parent.clone();
+        if config.carry_over_labels {
+            instance.tags = base.tags.clone();
+        }
+        instance.metadata.insert(
+            RECURRING_TASK_METADATA_KEY.to_string(),
+            serde_json::Value::String(config.id.clone()),
+        );
+        instance
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::TaskPriority;
+    use crate::storage::MemoryStorage;
+
+    fn utc(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, mo, d, h, mi, 0).unwrap()
+    }
+
+    fn base_task(storage: &mut MemoryStorage) -> Task {
+        let mut task = Task::new(
+            "Weekly dependency check".to_string(),
+            "Run cargo outdated".to_string(),
+            "default".to_string(),
+            TaskPriority::High,
+            None,
+        );
+        task.tags = vec!["maintenance".to_string(), "deps".to_string()];
+        storage.store(&task.to_generic()).unwrap();
+        task
+    }
+
+    #[test]
+    fn test_cron_next_after() {
+        // 2024-06-05 is a Wednesday
+        let monday_nine = CronSchedule::parse("0 9 * * MON").unwrap();
+        assert_eq!(
+            monday_nine.next_after(utc(2024, 6, 5, 12, 0)),
+            Some(utc(2024, 6, 10, 9, 0))
+        );
+        assert_eq!(
+            monday_nine.next_after(utc(2024, 6, 10, 9, 0)),
+            Some(utc(2024, 6, 17, 9, 0))
+        );
+
+        let every_quarter_hour = CronSchedule::parse("*/15 * * * *").unwrap();
+        assert_eq!(
+            every_quarter_hour.next_after(utc(2024, 12, 31, 23, 50)),
+            Some(utc(2025, 1, 1, 0, 0))
+        );
+
+        let sunday = CronSchedule::parse("30 8 * * 7").unwrap();
+        assert_eq!(
+            sunday.next_after(utc(2024, 6, 5, 0, 0)),
+            Some(utc(2024, 6, 9, 8, 30))
+        );
+
+        let leap_day = CronSchedule::parse("0 0 29 FEB *").unwrap();
+        assert_eq!(
+            leap_day.next_after(utc(2024, 3, 1, 0, 0)),
+            Some(utc(2028, 2, 29, 0, 0))
+        );
+        assert_eq!(
+            CronSchedule::parse("0 0 30 2 *")
+                .unwrap()
+                .next_after(utc(2024, 1, 1, 0, 0)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_cron_rejects_invalid_expressions() {
+        for expression in [
+            "0 9 * *",
+            "60 * * * *",
+            "* * * * FUNDAY",
+            "5-1 * * * *",
+            "*/0 * * * *",
+        ] {
+            let err = CronSchedule::parse(expression).unwrap_err();
+            assert!(
+                err.to_string().contains("Invalid cron expression"),
+                "{}: {}",
+                expression,
+                err
+            );
+        }
+    }
+
+    #[test]
+    fn test_tick_creates_instance_when_schedule_is_due() {
+        let mut storage = MemoryStorage::new("default");
+        let base = base_task(&mut storage);
+
+        let mut config = RecurringTaskConfig::new(
+            base.id.clone(),
+            RecurrenceTrigger::OnSchedule("0 9 * * MON".to_string()),
+            None,
+            true,
+            "default".to_string(),
+        )
+        .unwrap();
+        let now = Utc::now();
+        config.next_trigger = Some(now - Duration::hours(1));
+        storage.store(&config.to_generic()).unwrap();
+
+        let created = RecurringTaskManager::tick(&mut storage, now).unwrap();
+        assert_eq!(created.len(), 1);
+
+        let stored = storage
+            .get(&created[0].id, "task")
+            .unwrap()
+            .map(Task::from_generic)
+            .unwrap()
+            .unwrap();
+        assert_ne!(stored.id, base.id);
+        assert_eq!(stored.title, base.title);
+        assert_eq!(stored.priority, base.priority);
+        assert_eq!(stored.tags, base.tags);
+        assert_eq!(stored.status, TaskStatus::Todo);
+
+        let config = RecurringTaskConfig::from_generic(
+            storage.get(&config.id, "recurring_task").unwrap().unwrap(),
+        )
+        .unwrap();
+        assert_eq!(config.instance_ids, vec![stored.id]);
+        assert!(config.next_trigger.unwrap() > now);
+
+        // Nothing is due again until the next Monday
+        assert!(RecurringTaskManager::tick(&mut storage, now)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_tick_on_completion_respects_max_instances() {
+        let mut storage = MemoryStorage::new("default");
+        let base = base_task(&mut storage);
+        let config = RecurringTaskConfig::new(
+            base.id.clone(),
+            RecurrenceTrigger::OnCompletion,
+            Some(1),
+            false,
+            "default".to_string(),
+        )
+        .unwrap();
+        storage.store(&config.to_generic()).unwrap();
+
+        assert!(RecurringTaskManager::tick(&mut storage, Utc::now())
+            .unwrap()
+            .is_empty());
+
+        let mut done = base.clone();
+        done.complete("Checked".to_string());
+        storage.store(&done.to_generic()).unwrap();
+
+        let created = RecurringTaskManager::tick(&mut storage, Utc::now()).unwrap();
+        assert_eq!(created.len(), 1);
+        assert!(created[0].tags.is_empty());
+
+        let mut instance = created[0].clone();
+        instance.complete("Checked again".to_string());
+        storage.store(&instance.to_generic()).unwrap();
+        assert!(RecurringTaskManager::tick(&mut storage, Utc::now())
+            .unwrap()
+            .is_empty());
+    }
+}
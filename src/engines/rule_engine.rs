@@ -3,13 +3,16 @@
 //! Provides business rule enforcement, validation, and automated
 //! rule execution with conditions, actions, and audit trails.
 
-use crate::entities::{GenericEntity, Rule};
+use crate::entities::{GenericEntity, Rule, RuleStatus};
 use crate::error::EngramError;
+use crate::notify::{self, NotificationEvent, NotificationPriority};
 use crate::storage::Storage;
 use chrono::{DateTime, Utc};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
+use std::sync::OnceLock;
 
 /// Rule condition for evaluation
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -69,6 +72,193 @@ pub struct RuleExecutionResult {
     pub execution_duration_ms: u64,
 }
 
+/// A field value set by an `update_entity` action
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FieldUpdate {
+    pub rule_id: String,
+    pub value: serde_json::Value,
+}
+
+/// A matching rule whose action was not run because a higher-priority rule
+/// took precedence
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SuppressedAction {
+    pub rule_id: String,
+    pub winning_rule_id: String,
+    pub reason: String,
+}
+
+/// Outcome of running every applicable rule against one entity
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RuleBatchSummary {
+    pub entity_id: String,
+    /// Results in execution order: priority, then creation time
+    pub results: Vec<RuleExecutionResult>,
+    /// Fields set by `update_entity` actions and the rule that set each
+    pub updates: BTreeMap<String, FieldUpdate>,
+    pub suppressed: Vec<SuppressedAction>,
+    /// Entities requested by `create_entity` actions that have not already
+    /// been created from this entity
+    #[serde(default)]
+    pub creations: Vec<EntityCreation>,
+}
+
+/// Source metadata key mapping rule IDs to the entity each rule's
+/// `create_entity` action created, so the action only fires once
+pub const SPAWNED_ENTITIES_KEY: &str = "spawned_entities";
+
+/// Entity types a `create_entity` action can create
+pub const CREATABLE_ENTITY_TYPES: [&str; 3] = ["task", "context", "escalation_request"];
+
+/// Relationship type used when a `create_entity` action names none
+pub const DEFAULT_SPAWN_RELATIONSHIP: &str = "references";
+
+/// An entity a `create_entity` action asks for, with its field template
+/// rendered against the triggering entity
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EntityCreation {
+    pub rule_id: String,
+    pub entity_type: String,
+    pub fields: serde_json::Map<String, serde_json::Value>,
+    /// Relationship type from the source entity to the created one
+    pub relationship: String,
+    pub source_id: String,
+    pub source_type: String,
+    /// ID of the stored entity, once the caller has created it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_id: Option<String>,
+}
+
+impl EntityCreation {
+    /// Describe the creation, one line per templated field
+    pub fn describe(&self) -> Vec<String> {
+        let mut lines = vec![format!(
+            "Create {} linked from {} ({})",
+            self.entity_type, self.source_id, self.relationship
+        )];
+        lines.extend(
+            self.fields
+                .iter()
+                .map(|(field, value)| format!("  {} = {}", field, value)),
+        );
+        lines
+    }
+}
+
+impl RuleBatchSummary {
+    /// Write the batch's field updates into `entity`'s data
+    pub fn apply_updates(&self, entity: &mut GenericEntity) {
+        if let Some(data) = entity.data.as_object_mut() {
+            for (field, update) in &self.updates {
+                data.insert(field.clone(), update.value.clone());
+            }
+        }
+    }
+}
+
+/// Operators understood by rule condition expressions
+pub const EXPRESSION_OPERATORS: [&str; 15] = [
+    "equals",
+    "==",
+    "not_equals",
+    "!=",
+    "greater_than",
+    ">",
+    "greater_than_or_equal",
+    ">=",
+    "less_than",
+    "<",
+    "less_than_or_equal",
+    "<=",
+    "contains",
+    "starts_with",
+    "ends_with",
+];
+
+/// A condition expression split into `<variable> <operator> <expected>`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ParsedExpression {
+    pub variable: String,
+    pub operator: String,
+    pub expected: String,
+}
+
+impl ParsedExpression {
+    /// Parse an expression, rejecting ones too short to evaluate or using an
+    /// unknown operator
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        let parts: Vec<&str> = expression.split_whitespace().collect();
+        if parts.len() < 3 {
+            return Err(format!("Invalid expression: {}", expression));
+        }
+        if !EXPRESSION_OPERATORS.contains(&parts[1]) {
+            return Err(format!("Unknown operator: {}", parts[1]));
+        }
+        Ok(Self {
+            variable: parts[0].to_string(),
+            operator: parts[1].to_string(),
+            expected: parts[2..].join(" "),
+        })
+    }
+}
+
+/// How one expression evaluated: the value found for its variable, the
+/// comparison made and its outcome
+#[derive(Debug, Clone, Serialize)]
+pub struct ExpressionTrace {
+    pub expression: String,
+    pub parsed: Option<ParsedExpression>,
+    /// Value of the variable in the entity, if present
+    pub value_found: Option<RuleValue>,
+    pub result: Option<bool>,
+    pub error: Option<String>,
+}
+
+impl ExpressionTrace {
+    /// The outcome as `evaluate_expression` reports it
+    pub fn outcome(&self) -> Result<bool, String> {
+        match (&self.result, &self.error) {
+            (Some(result), _) => Ok(*result),
+            (None, Some(error)) => Err(error.clone()),
+            (None, None) => Err(format!("Invalid expression: {}", self.expression)),
+        }
+    }
+}
+
+/// Step-by-step evaluation of a rule against one entity, without running
+/// its action
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleTrace {
+    pub rule_id: String,
+    pub entity_id: String,
+    pub entity_type: String,
+    /// Whether the rule's `entity_types` include the entity's type
+    pub applies_to_type: bool,
+    /// Evaluation of the condition's `applies_when` scope, if it has one
+    pub scope: Option<ExpressionTrace>,
+    /// Evaluation of the condition expression; `None` when the condition is
+    /// a literal (`true`, `null`, an object without `expression`)
+    pub condition: Option<ExpressionTrace>,
+    /// Outcome of the condition itself, ignoring scope
+    pub condition_satisfied: Result<bool, String>,
+    /// Whether executing the rule against the entity would run its action
+    pub would_fire: bool,
+    /// What the action would do
+    pub actions: Vec<String>,
+}
+
+impl RuleTrace {
+    /// Whether the entity is in scope: the rule applies to its type and the
+    /// `applies_when` scope, if any, holds
+    pub fn in_scope(&self) -> bool {
+        self.applies_to_type
+            && self
+                .scope
+                .as_ref()
+                .is_none_or(|scope| scope.outcome().unwrap_or(false))
+    }
+}
+
 /// Rule execution engine
 pub struct RuleExecutionEngine {}
 
@@ -135,37 +325,185 @@ impl RuleExecutionEngine {
         storage: &S,
         entity: &GenericEntity,
         agent: &str,
-    ) -> Result<Vec<RuleExecutionResult>, EngramError> {
-        let mut context = RuleExecutionContext {
-            variables: HashMap::new(),
-            current_entity: Some(entity.clone()),
-            executing_agent: agent.to_string(),
-            execution_time: Utc::now(),
-            metadata: HashMap::new(),
-        };
+    ) -> Result<RuleBatchSummary, EngramError> {
+        let rules = storage
+            .query_by_agent(agent, Some("rule"))?
+            .into_iter()
+            .filter_map(|generic_rule| serde_json::from_value::<Rule>(generic_rule.data).ok())
+            .collect();
+
+        Ok(self.execute_rules(rules, entity, agent))
+    }
+
+    /// Run every active rule that applies to `entity`, highest priority
+    /// first and oldest first within a priority. Each rule sees the entity
+    /// as it was when the batch started. Entities requested by
+    /// `create_entity` actions are collected for the caller to store. A matching rule is suppressed when
+    /// an earlier rule in its `exclusive_group` already fired, or when its
+    /// `update_entity` action would set a field an earlier rule set to a
+    /// different value.
+    pub fn execute_rules(
+        &self,
+        mut rules: Vec<Rule>,
+        entity: &GenericEntity,
+        agent: &str,
+    ) -> RuleBatchSummary {
+        order_rules(&mut rules);
 
-        self.populate_entity_variables(&mut context, entity);
+        let mut summary = RuleBatchSummary {
+            entity_id: entity.id.clone(),
+            ..Default::default()
+        };
+        let mut fired_groups: HashMap<&str, &str> = HashMap::new();
 
-        let rules = storage.query_by_agent(agent, Some("rule"))?;
+        for rule in &rules {
+            if rule.status != RuleStatus::Active {
+                continue;
+            }
+            let trace = self.trace_rule(rule, entity);
+            if !trace.in_scope() {
+                continue;
+            }
 
-        let mut results = Vec::new();
+            let mut context = RuleExecutionContext {
+                variables: HashMap::new(),
+                current_entity: Some(entity.clone()),
+                executing_agent: agent.to_string(),
+                execution_time: Utc::now(),
+                metadata: HashMap::new(),
+            };
+            self.populate_entity_variables(&mut context, entity);
+
+            if trace.would_fire {
+                if let Some(suppressed) = self.suppression(rule, &summary, &fired_groups) {
+                    summary.results.push(RuleExecutionResult {
+                        rule_id: rule.id.clone(),
+                        condition_satisfied: true,
+                        actions_executed: false,
+                        context,
+                        errors: Vec::new(),
+                        actions_taken: Vec::new(),
+                        execution_duration_ms: 0,
+                    });
+                    summary.suppressed.push(suppressed);
+                    continue;
+                }
+            }
 
-        for generic_rule in rules {
-            if let Ok(rule_json) = serde_json::to_string(&generic_rule.data) {
-                if let Ok(rule) = serde_json::from_str::<Rule>(&rule_json) {
-                    if self.rule_applies_to_entity(&rule, entity) {
-                        match self.execute_rule(&rule, &mut context) {
-                            Ok(result) => results.push(result),
-                            Err(e) => {
-                                eprintln!("Failed to execute rule {}: {}", rule.id, e);
+            match self.execute_rule(rule, &mut context) {
+                Ok(mut result) => {
+                    if result.actions_executed {
+                        if let Some(group) = &rule.exclusive_group {
+                            fired_groups.entry(group).or_insert(&rule.id);
+                        }
+                        if let Ok(Some(creation)) = entity_creation(rule, entity) {
+                            match spawned_entity(entity, &rule.id) {
+                                Some(existing) => {
+                                    result.actions_taken = vec![format!(
+                                        "Skipped: already created {} {}",
+                                        creation.entity_type, existing
+                                    )];
+                                }
+                                None => summary.creations.push(creation),
                             }
                         }
+                        for (field, value) in update_entity_fields(&rule.action) {
+                            summary.updates.entry(field).or_insert(FieldUpdate {
+                                rule_id: rule.id.clone(),
+                                value,
+                            });
+                        }
                     }
+                    summary.results.push(result);
                 }
+                Err(e) => {
+                    eprintln!("Failed to execute rule {}: {}", rule.id, e);
+                }
+            }
+        }
+
+        summary
+    }
+
+    fn suppression(
+        &self,
+        rule: &Rule,
+        summary: &RuleBatchSummary,
+        fired_groups: &HashMap<&str, &str>,
+    ) -> Option<SuppressedAction> {
+        if let Some(group) = &rule.exclusive_group {
+            if let Some(winner) = fired_groups.get(group.as_str()) {
+                return Some(SuppressedAction {
+                    rule_id: rule.id.clone(),
+                    winning_rule_id: winner.to_string(),
+                    reason: format!("exclusive group '{}' already fired", group),
+                });
             }
         }
+        update_entity_fields(&rule.action)
+            .into_iter()
+            .find_map(|(field, value)| {
+                let existing = summary.updates.get(&field)?;
+                (existing.value != value).then(|| SuppressedAction {
+                    rule_id: rule.id.clone(),
+                    winning_rule_id: existing.rule_id.clone(),
+                    reason: format!(
+                        "conflicting update {} = {} (kept {})",
+                        field, value, existing.value
+                    ),
+                })
+            })
+    }
+
+    /// Evaluate a rule's condition against an entity without running its
+    /// action. Rules that do not apply to the entity evaluate to `true`:
+    /// those scoped to other entity types, and those whose condition object
+    /// has an `applies_when` expression the entity does not satisfy.
+    pub fn evaluate_condition(&self, rule: &Rule, entity: &GenericEntity) -> Result<bool, String> {
+        let trace = self.trace_rule(rule, entity);
+        if !trace.in_scope() {
+            return Ok(true);
+        }
+        trace.condition_satisfied
+    }
 
-        Ok(results)
+    /// Evaluate a rule against an entity in trace mode, recording each
+    /// expression's inputs and outcome. Nothing is executed.
+    pub fn trace_rule(&self, rule: &Rule, entity: &GenericEntity) -> RuleTrace {
+        let mut context = RuleExecutionContext {
+            variables: HashMap::new(),
+            current_entity: Some(entity.clone()),
+            executing_agent: entity.agent.clone(),
+            execution_time: Utc::now(),
+            metadata: HashMap::new(),
+        };
+        self.populate_entity_variables(&mut context, entity);
+
+        let scope = rule
+            .condition
+            .get("applies_when")
+            .and_then(|v| v.as_str())
+            .map(|scope| self.trace_expression(scope, &context));
+        let condition = condition_expression(&rule.condition)
+            .map(|expression| self.trace_expression(expression, &context));
+        let condition_satisfied = match &condition {
+            Some(trace) => trace.outcome(),
+            None => self.evaluate_rule_condition(&rule.condition, &context),
+        };
+
+        let mut trace = RuleTrace {
+            rule_id: rule.id.clone(),
+            entity_id: entity.id.clone(),
+            entity_type: entity.entity_type.clone(),
+            applies_to_type: self.rule_applies_to_entity(rule, entity),
+            scope,
+            condition,
+            condition_satisfied,
+            would_fire: false,
+            actions: describe_rule_action(rule, entity),
+        };
+        trace.would_fire = trace.in_scope() && trace.condition_satisfied == Ok(true);
+        trace
     }
 
     fn evaluate_rule_condition(
@@ -217,6 +555,68 @@ impl RuleExecutionEngine {
                                 }
                             }
                         }
+                        "notify" => {
+                            if let Some(serde_json::Value::String(message)) = obj.get("message") {
+                                let priority = obj
+                                    .get("priority")
+                                    .and_then(|v| v.as_str())
+                                    .and_then(NotificationPriority::parse)
+                                    .unwrap_or(NotificationPriority::Medium);
+                                let title = obj
+                                    .get("title")
+                                    .and_then(|v| v.as_str())
+                                    .unwrap_or("Rule notification");
+                                let mut event = NotificationEvent::new(
+                                    notify::RULE_ACTION,
+                                    priority,
+                                    title,
+                                    message.clone(),
+                                );
+                                if let Some(entity) = &context.current_entity {
+                                    event = event.with_entity(entity.id.clone());
+                                }
+                                let report = notify::dispatch(&event);
+                                action_descriptions.push(format!(
+                                    "Notified {} sink(s): {}",
+                                    report.delivered.len(),
+                                    message
+                                ));
+                            }
+                        }
+                        "update_entity" => {
+                            for (field, value) in update_entity_fields(action) {
+                                if let Some(data) = context
+                                    .current_entity
+                                    .as_mut()
+                                    .and_then(|entity| entity.data.as_object_mut())
+                                {
+                                    data.insert(field.clone(), value.clone());
+                                }
+                                if let Some(rule_value) = Self::scalar_value(&value) {
+                                    context.variables.insert(field.clone(), rule_value);
+                                }
+                                action_descriptions.push(format!("Set {} = {}", field, value));
+                            }
+                        }
+                        "create_entity" => {
+                            let spec = create_entity_spec(action)?
+                                .ok_or("create_entity action has no spec")?;
+                            let fields = match &context.current_entity {
+                                Some(source) => render_source_template(
+                                    &serde_json::Value::Object(spec.fields),
+                                    source,
+                                ),
+                                None => serde_json::Value::Object(spec.fields),
+                            };
+                            let title = ["title", "name"]
+                                .iter()
+                                .find_map(|key| fields.get(key)?.as_str())
+                                .unwrap_or_default();
+                            action_descriptions.push(format!(
+                                "Create {} '{}' ({})",
+                                spec.entity_type, title, spec.relationship
+                            ));
+                        }
                         "validate" => {
                             if let Some(serde_json::Value::String(field)) = obj.get("field") {
                                 if !context.variables.contains_key(field) {
@@ -244,20 +644,50 @@ impl RuleExecutionEngine {
         expression: &str,
         context: &RuleExecutionContext,
     ) -> Result<bool, String> {
-        let parts: Vec<&str> = expression.split_whitespace().collect();
-        if parts.len() < 3 {
-            return Err(format!("Invalid expression: {}", expression));
-        }
-
-        let variable_name = parts[0];
-        let operator = parts[1];
-        let expected_value = parts[2..].join(" ");
+        self.trace_expression(expression, context).outcome()
+    }
 
-        let variable_value = context
-            .variables
-            .get(variable_name)
-            .ok_or_else(|| format!("Variable '{}' not found", variable_name))?;
+    /// Evaluate an expression, keeping the parsed parts and the value found
+    /// for its variable alongside the outcome
+    pub fn trace_expression(
+        &self,
+        expression: &str,
+        context: &RuleExecutionContext,
+    ) -> ExpressionTrace {
+        let mut trace = ExpressionTrace {
+            expression: expression.to_string(),
+            parsed: None,
+            value_found: None,
+            result: None,
+            error: None,
+        };
+        let parsed = match ParsedExpression::parse(expression) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                trace.error = Some(e);
+                return trace;
+            }
+        };
+        trace.value_found = context.variables.get(&parsed.variable).cloned();
+        let outcome = match &trace.value_found {
+            Some(value) => self.compare(value, &parsed.operator, &parsed.expected),
+            None => Err(format!("Variable '{}' not found", parsed.variable)),
+        };
+        match outcome {
+            Ok(result) => trace.result = Some(result),
+            Err(e) => trace.error = Some(e),
+        }
+        trace.parsed = Some(parsed);
+        trace
+    }
 
+    fn compare(
+        &self,
+        variable_value: &RuleValue,
+        operator: &str,
+        expected_value: &str,
+    ) -> Result<bool, String> {
+        let expected_value = expected_value.to_string();
         match operator {
             "equals" | "==" => {
                 let expected = self.parse_value(&expected_value)?;
@@ -312,6 +742,21 @@ impl RuleExecutionEngine {
         rule.entity_types.is_empty() || rule.entity_types.contains(&entity.entity_type)
     }
 
+    /// Variables a condition can reference for `entity`: `id`,
+    /// `entity_type`, `agent`, `timestamp` and its data fields, with nested
+    /// objects flattened to dotted paths
+    pub fn entity_variables(&self, entity: &GenericEntity) -> HashMap<String, RuleValue> {
+        let mut context = RuleExecutionContext {
+            variables: HashMap::new(),
+            current_entity: None,
+            executing_agent: entity.agent.clone(),
+            execution_time: Utc::now(),
+            metadata: HashMap::new(),
+        };
+        self.populate_entity_variables(&mut context, entity);
+        context.variables
+    }
+
     fn populate_entity_variables(
         &self,
         context: &mut RuleExecutionContext,
@@ -368,7 +813,10 @@ impl RuleExecutionEngine {
                         serde_json::Value::Object(_) => {
                             self.extract_variables_from_json(value, &var_name, variables);
                         }
-                        serde_json::Value::Array(_) => {}
+                        serde_json::Value::Array(items) => {
+                            let values = items.iter().filter_map(Self::scalar_value).collect();
+                            variables.insert(var_name, RuleValue::Array(values));
+                        }
                     }
                 }
             }
@@ -376,6 +824,17 @@ impl RuleExecutionEngine {
         }
     }
 
+    /// Scalar JSON values as rule values, for `contains` checks on arrays
+    fn scalar_value(value: &serde_json::Value) -> Option<RuleValue> {
+        match value {
+            serde_json::Value::String(s) => Some(RuleValue::String(s.clone())),
+            serde_json::Value::Number(n) => n.as_f64().map(RuleValue::Number),
+            serde_json::Value::Bool(b) => Some(RuleValue::Boolean(*b)),
+            serde_json::Value::Null => Some(RuleValue::Null),
+            _ => None,
+        }
+    }
+
     fn parse_value(&self, value_str: &str) -> Result<RuleValue, String> {
         if let Ok(num) = value_str.parse::<f64>() {
             return Ok(RuleValue::Number(num));
@@ -414,6 +873,255 @@ impl RuleExecutionEngine {
     }
 }
 
+/// Sort rules into execution order: highest priority first, then oldest
+/// first, with the ID as a final tie-break
+pub fn order_rules(rules: &mut [Rule]) {
+    rules.sort_by(|a, b| {
+        b.priority
+            .cmp(&a.priority)
+            .then(a.created_at.cmp(&b.created_at))
+            .then_with(|| a.id.cmp(&b.id))
+    });
+}
+
+/// Fields an `update_entity` action sets, from either `field`/`value` or a
+/// `fields` object
+pub fn update_entity_fields(action: &serde_json::Value) -> Vec<(String, serde_json::Value)> {
+    if action.get("type").and_then(|v| v.as_str()) != Some("update_entity") {
+        return Vec::new();
+    }
+    let mut fields = Vec::new();
+    if let (Some(field), Some(value)) = (
+        action.get("field").and_then(|v| v.as_str()),
+        action.get("value"),
+    ) {
+        fields.push((field.to_string(), value.clone()));
+    }
+    if let Some(map) = action.get("fields").and_then(|v| v.as_object()) {
+        fields.extend(map.iter().map(|(k, v)| (k.clone(), v.clone())));
+    }
+    fields
+}
+
+/// Entity type, field template and relationship of a `create_entity` action
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreateEntitySpec {
+    pub entity_type: String,
+    pub fields: serde_json::Map<String, serde_json::Value>,
+    pub relationship: String,
+}
+
+/// Parse a `create_entity` action; `Ok(None)` for any other action and an
+/// error when the action is malformed or names an unsupported entity type
+pub fn create_entity_spec(action: &serde_json::Value) -> Result<Option<CreateEntitySpec>, String> {
+    if action.get("type").and_then(|v| v.as_str()) != Some("create_entity") {
+        return Ok(None);
+    }
+    let entity_type = action
+        .get("entity_type")
+        .and_then(|v| v.as_str())
+        .ok_or("create_entity action requires an 'entity_type'")?;
+    if !CREATABLE_ENTITY_TYPES.contains(&entity_type) {
+        return Err(format!(
+            "create_entity cannot create '{}' (supported: {})",
+            entity_type,
+            CREATABLE_ENTITY_TYPES.join(", ")
+        ));
+    }
+    let fields = match action.get("fields") {
+        None => serde_json::Map::new(),
+        Some(serde_json::Value::Object(fields)) => fields.clone(),
+        Some(_) => return Err("create_entity 'fields' must be an object".to_string()),
+    };
+    let relationship = match action.get("relationship") {
+        None => DEFAULT_SPAWN_RELATIONSHIP.to_string(),
+        Some(serde_json::Value::String(relationship)) if !relationship.trim().is_empty() => {
+            relationship.trim().to_string()
+        }
+        Some(_) => {
+            return Err("create_entity 'relationship' must be a non-empty string".to_string())
+        }
+    };
+    Ok(Some(CreateEntitySpec {
+        entity_type: entity_type.to_string(),
+        fields,
+        relationship,
+    }))
+}
+
+/// The entity `rule`'s `create_entity` action asks for when triggered by
+/// `source`; `Ok(None)` when the rule has another kind of action
+pub fn entity_creation(
+    rule: &Rule,
+    source: &GenericEntity,
+) -> Result<Option<EntityCreation>, String> {
+    let Some(spec) = create_entity_spec(&rule.action)? else {
+        return Ok(None);
+    };
+    let fields = match render_source_template(&serde_json::Value::Object(spec.fields), source) {
+        serde_json::Value::Object(fields) => fields,
+        _ => serde_json::Map::new(),
+    };
+    Ok(Some(EntityCreation {
+        rule_id: rule.id.clone(),
+        entity_type: spec.entity_type,
+        fields,
+        relationship: spec.relationship,
+        source_id: source.id.clone(),
+        source_type: source.entity_type.clone(),
+        created_id: None,
+    }))
+}
+
+/// Fill `{{source.<path>}}` placeholders in every string of `template` from
+/// `source`, where the path is `id`, `entity_type`, `agent` or a dotted path
+/// into its data. A string that is only a placeholder takes the field's JSON
+/// value; unknown paths are left as written.
+pub fn render_source_template(
+    template: &serde_json::Value,
+    source: &GenericEntity,
+) -> serde_json::Value {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    let re = RE.get_or_init(|| {
+        Regex::new(r"\{\{\s*source\.([\w.]+)\s*\}\}").expect("template regex compiles")
+    });
+    let lookup = |path: &str| -> Option<serde_json::Value> {
+        match path {
+            "id" => Some(serde_json::Value::String(source.id.clone())),
+            "entity_type" => Some(serde_json::Value::String(source.entity_type.clone())),
+            "agent" => Some(serde_json::Value::String(source.agent.clone())),
+            _ => path
+                .split('.')
+                .try_fold(&source.data, |value, key| value.get(key))
+                .cloned(),
+        }
+    };
+
+    match template {
+        serde_json::Value::String(text) => {
+            if let Some(captures) = re.captures(text) {
+                if captures[0].len() == text.len() {
+                    if let Some(value) = lookup(&captures[1]) {
+                        return value;
+                    }
+                }
+            }
+            let rendered = re.replace_all(text, |captures: &regex::Captures| {
+                match lookup(&captures[1]) {
+                    Some(serde_json::Value::String(s)) => s,
+                    Some(value) => value.to_string(),
+                    None => captures[0].to_string(),
+                }
+            });
+            serde_json::Value::String(rendered.into_owned())
+        }
+        serde_json::Value::Array(items) => serde_json::Value::Array(
+            items
+                .iter()
+                .map(|item| render_source_template(item, source))
+                .collect(),
+        ),
+        serde_json::Value::Object(map) => serde_json::Value::Object(
+            map.iter()
+                .map(|(key, value)| (key.clone(), render_source_template(value, source)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+/// ID of the entity `rule_id`'s `create_entity` action already created from
+/// `source`, read from the source's metadata
+pub fn spawned_entity(source: &GenericEntity, rule_id: &str) -> Option<String> {
+    source
+        .data
+        .get("metadata")?
+        .get(SPAWNED_ENTITIES_KEY)?
+        .get(rule_id)?
+        .as_str()
+        .map(str::to_string)
+}
+
+/// The expression a rule condition evaluates: the condition itself when it
+/// is a string, or its `expression` field when it is an object
+pub fn condition_expression(condition: &serde_json::Value) -> Option<&str> {
+    match condition {
+        serde_json::Value::String(expression) => Some(expression),
+        serde_json::Value::Object(obj) => obj.get("expression").and_then(|v| v.as_str()),
+        _ => None,
+    }
+}
+
+/// Every expression in a rule's condition, labelled `condition` or
+/// `applies_when`, parsed without evaluating
+pub fn rule_expressions(
+    rule: &Rule,
+) -> Vec<(&'static str, &str, Result<ParsedExpression, String>)> {
+    let mut expressions = Vec::new();
+    if let Some(scope) = rule.condition.get("applies_when").and_then(|v| v.as_str()) {
+        expressions.push(("applies_when", scope, ParsedExpression::parse(scope)));
+    }
+    if let Some(expression) = condition_expression(&rule.condition) {
+        expressions.push(("condition", expression, ParsedExpression::parse(expression)));
+    }
+    expressions
+}
+
+/// Describe what a rule action would do, without doing it
+pub fn describe_action(action: &serde_json::Value) -> Vec<String> {
+    let text = |obj: &serde_json::Map<String, serde_json::Value>, key: &str| {
+        obj.get(key)
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string()
+    };
+    match action {
+        serde_json::Value::String(action) => vec![format!("Execute: {}", action)],
+        serde_json::Value::Object(obj) => match obj.get("type").and_then(|v| v.as_str()) {
+            Some("log") => vec![format!("Log: {}", text(obj, "message"))],
+            Some("set_metadata") => vec![format!(
+                "Set metadata {} = {}",
+                text(obj, "key"),
+                text(obj, "value")
+            )],
+            Some("notify") => vec![format!("Notify: {}", text(obj, "message"))],
+            Some("validate") => vec![format!("Validate field: {}", text(obj, "field"))],
+            Some("create_entity") => match create_entity_spec(action) {
+                Ok(Some(spec)) => vec![format!(
+                    "Create {} with fields {} ({})",
+                    spec.entity_type,
+                    serde_json::Value::Object(spec.fields),
+                    spec.relationship
+                )],
+                Ok(None) => Vec::new(),
+                Err(e) => vec![format!("Invalid create_entity action: {}", e)],
+            },
+            Some("update_entity") => update_entity_fields(action)
+                .into_iter()
+                .map(|(field, value)| format!("Set {} = {}", field, value))
+                .collect(),
+            Some(other) => vec![format!("Unknown action: {}", other)],
+            None => Vec::new(),
+        },
+        _ => vec!["Execute unknown action".to_string()],
+    }
+}
+
+/// Describe what `rule`'s action would do to `entity`, rendering
+/// `create_entity` templates and noting entities already created
+pub fn describe_rule_action(rule: &Rule, entity: &GenericEntity) -> Vec<String> {
+    match entity_creation(rule, entity) {
+        Ok(Some(creation)) => match spawned_entity(entity, &rule.id) {
+            Some(existing) => vec![format!(
+                "Skip: already created {} {}",
+                creation.entity_type, existing
+            )],
+            None => creation.describe(),
+        },
+        _ => describe_action(&rule.action),
+    }
+}
+
 impl fmt::Display for RuleValue {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -495,6 +1203,7 @@ mod tests {
             execution_history: vec![],
             tags: vec!["test".to_string()],
             related_rules: vec![],
+            exclusive_group: None,
             metadata: HashMap::new(),
         }
     }
@@ -563,6 +1272,156 @@ mod tests {
         assert!(!result.unwrap());
     }
 
+    #[test]
+    fn test_trace_rule_reports_each_expression() {
+        let engine = RuleExecutionEngine::new();
+        let mut rule = create_test_rule();
+        rule.condition = json!({
+            "applies_when": "status equals pending",
+            "expression": "priority equals high"
+        });
+
+        let trace = engine.trace_rule(&rule, &create_test_entity());
+        assert!(trace.applies_to_type);
+        assert!(trace.in_scope());
+        let condition = trace.condition.as_ref().unwrap();
+        assert_eq!(condition.parsed.as_ref().unwrap().variable, "priority");
+        assert!(matches!(
+            condition.value_found,
+            Some(RuleValue::String(ref v)) if v == "high"
+        ));
+        assert_eq!(condition.outcome(), Ok(true));
+        assert!(trace.would_fire);
+        assert!(!trace.actions.is_empty());
+
+        let mut entity = create_test_entity();
+        entity.data["priority"] = json!("low");
+        let trace = engine.trace_rule(&rule, &entity);
+        assert_eq!(trace.condition.unwrap().outcome(), Ok(false));
+        assert!(!trace.would_fire);
+
+        let trace = engine.trace_expression(
+            "missing equals x",
+            &RuleExecutionContext {
+                variables: HashMap::new(),
+                current_entity: None,
+                executing_agent: "test-agent".to_string(),
+                execution_time: Utc::now(),
+                metadata: HashMap::new(),
+            },
+        );
+        assert!(trace.value_found.is_none());
+        assert!(trace.outcome().is_err());
+    }
+
+    fn status_rule(id: &str, priority: RulePriority, status: &str, age_secs: i64) -> Rule {
+        let mut rule = create_test_rule();
+        rule.id = id.to_string();
+        rule.priority = priority;
+        rule.created_at = Utc::now() - chrono::Duration::seconds(age_secs);
+        rule.condition = json!({"expression": "priority equals high"});
+        rule.action = json!({"type": "update_entity", "field": "status", "value": status});
+        rule
+    }
+
+    #[test]
+    fn test_execute_rules_resolves_conflicting_updates_by_priority() {
+        let engine = RuleExecutionEngine::new();
+        let rules = vec![
+            status_rule(
+                "medium-in-progress",
+                RulePriority::Medium,
+                "in_progress",
+                30,
+            ),
+            status_rule("critical-blocked", RulePriority::Critical, "blocked", 10),
+            status_rule("high-in-progress", RulePriority::High, "in_progress", 20),
+        ];
+
+        let summary = engine.execute_rules(rules, &create_test_entity(), "test-agent");
+
+        let order: Vec<&str> = summary.results.iter().map(|r| r.rule_id.as_str()).collect();
+        assert_eq!(
+            order,
+            vec!["critical-blocked", "high-in-progress", "medium-in-progress"]
+        );
+        assert_eq!(
+            summary.updates["status"],
+            FieldUpdate {
+                rule_id: "critical-blocked".to_string(),
+                value: json!("blocked"),
+            }
+        );
+        assert_eq!(summary.suppressed.len(), 2);
+        assert!(summary
+            .suppressed
+            .iter()
+            .all(|s| s.winning_rule_id == "critical-blocked"));
+        assert!(summary.results[0].actions_executed);
+        assert!(!summary.results[1].actions_executed);
+
+        let mut entity = create_test_entity();
+        summary.apply_updates(&mut entity);
+        assert_eq!(entity.data["status"], json!("blocked"));
+    }
+
+    #[test]
+    fn test_execute_rules_orders_equal_priority_by_creation_time() {
+        let engine = RuleExecutionEngine::new();
+        let rules = vec![
+            status_rule("newer", RulePriority::High, "in_progress", 10),
+            status_rule("older", RulePriority::High, "blocked", 60),
+            status_rule("newest", RulePriority::High, "blocked", 5),
+        ];
+
+        let summary = engine.execute_rules(rules, &create_test_entity(), "test-agent");
+
+        assert_eq!(summary.updates["status"].rule_id, "older");
+        // Agreeing with the winner is not a conflict
+        assert_eq!(summary.suppressed.len(), 1);
+        assert_eq!(summary.suppressed[0].rule_id, "newer");
+    }
+
+    #[test]
+    fn test_execute_rules_exclusive_group_fires_first_match_only() {
+        let engine = RuleExecutionEngine::new();
+        let mut rules = Vec::new();
+        for (id, priority, expression) in [
+            ("low", RulePriority::Low, "priority equals high"),
+            (
+                "critical-unmatched",
+                RulePriority::Critical,
+                "priority equals low",
+            ),
+            ("medium", RulePriority::Medium, "priority equals high"),
+        ] {
+            let mut rule = create_test_rule();
+            rule.id = id.to_string();
+            rule.priority = priority;
+            rule.condition = json!({ "expression": expression });
+            rule.exclusive_group = Some("triage".to_string());
+            rules.push(rule);
+        }
+        let mut inactive = create_test_rule();
+        inactive.id = "inactive".to_string();
+        inactive.status = RuleStatus::Inactive;
+        rules.push(inactive);
+
+        let summary = engine.execute_rules(rules, &create_test_entity(), "test-agent");
+
+        let fired: Vec<&str> = summary
+            .results
+            .iter()
+            .filter(|r| r.actions_executed)
+            .map(|r| r.rule_id.as_str())
+            .collect();
+        assert_eq!(fired, vec!["medium"]);
+        assert_eq!(summary.suppressed.len(), 1);
+        assert_eq!(summary.suppressed[0].rule_id, "low");
+        assert!(summary.suppressed[0].reason.contains("triage"));
+        assert!(summary.results.iter().all(|r| r.rule_id != "inactive"));
+    }
+
     #[test]
     fn test_execute_rule_success() {
         let engine = RuleExecutionEngine::new();
@@ -1073,6 +1932,27 @@ mod tests {
             .is_err());
     }
 
+    #[test]
+    fn test_evaluate_condition_respects_applies_when() {
+        let engine = RuleExecutionEngine::new();
+        let mut rule = create_test_rule();
+        rule.condition = json!({
+            "expression": "priority != low",
+            "applies_when": "tags contains critical"
+        });
+
+        let mut entity = create_test_entity();
+        entity.data = json!({"priority": "low", "tags": ["critical", "backend"]});
+        assert_eq!(engine.evaluate_condition(&rule, &entity), Ok(false));
+
+        entity.data = json!({"priority": "low", "tags": ["backend"]});
+        assert_eq!(engine.evaluate_condition(&rule, &entity), Ok(true));
+
+        entity.entity_type = "context".to_string();
+        entity.data = json!({"priority": "low", "tags": ["critical"]});
+        assert_eq!(engine.evaluate_condition(&rule, &entity), Ok(true));
+    }
+
     // ── Variable resolution ──
 
     #[test]
@@ -1473,4 +2353,48 @@ mod tests {
     fn test_builder_default() {
         let _engine = RuleEngineBuilder::default().build();
     }
+
+    #[test]
+    fn test_create_entity_renders_template_once() {
+        let engine = RuleExecutionEngine::new();
+        let mut rule = create_test_rule();
+        rule.action = json!({
+            "type": "create_entity",
+            "entity_type": "task",
+            "fields": {
+                "title": "Follow up on {{source.title}} ({{source.id}})",
+                "tags": "{{source.tags}}",
+                "description": "{{source.missing}}"
+            },
+            "relationship": "depends_on"
+        });
+        let mut entity = create_test_entity();
+        entity.data["tags"] = json!(["urgent"]);
+
+        let summary = engine.execute_rules(vec![rule.clone()], &entity, "test-agent");
+        assert_eq!(summary.creations.len(), 1);
+        let creation = &summary.creations[0];
+        assert_eq!(creation.relationship, "depends_on");
+        assert_eq!(
+            creation.fields["title"],
+            "Follow up on Test Task (test-entity-1)"
+        );
+        assert_eq!(creation.fields["tags"], json!(["urgent"]));
+        assert_eq!(creation.fields["description"], "{{source.missing}}");
+
+        entity.data["metadata"] = json!({SPAWNED_ENTITIES_KEY: {"test-rule-1": "task-9"}});
+        let summary = engine.execute_rules(vec![rule.clone()], &entity, "test-agent");
+        assert!(summary.creations.is_empty());
+        assert_eq!(
+            summary.results[0].actions_taken,
+            ["Skipped: already created task task-9"]
+        );
+        assert_eq!(
+            engine.trace_rule(&rule, &entity).actions,
+            ["Skip: already created task task-9"]
+        );
+
+        rule.action = json!({"type": "create_entity", "entity_type": "rule"});
+        assert!(create_entity_spec(&rule.action).is_err());
+    }
 }
@@ -5,12 +5,16 @@
 
 use crate::engines::action_executor::{ActionExecutor, ActionResult};
 use crate::engines::rule_engine::{RuleExecutionContext, RuleExecutionEngine, RuleValue};
-use crate::entities::{Entity, Task, TriggerCondition, Workflow, WorkflowInstance};
+use crate::entities::{
+    full_execution_history, store_workflow_instance, Entity, Task, TriggerCondition, Workflow,
+    WorkflowInstance, DEFAULT_MAX_EMBEDDED_EVENTS,
+};
 use crate::error::EngramError;
+use crate::health::{EngineHealthCheck, HealthState, HealthStatus};
 use crate::storage::{QueryFilter, Storage};
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::time::Duration as StdDuration;
 use uuid::Uuid;
@@ -133,8 +137,12 @@ pub struct WorkflowAutomationEngine<S: Storage> {
     storage: S,
     rule_engine: RuleExecutionEngine,
     action_executor: ActionExecutor,
+    /// Working copies of instances being mutated; every mutation is written
+    /// back through `storage`, which is reread before each operation
     active_instances: HashMap<String, WorkflowInstance>,
     max_execution_steps: u64,
+    /// Events kept embedded in an instance before older ones are archived
+    max_history_events: usize,
 }
 
 /// Builder for workflow automation engine
@@ -143,6 +151,7 @@ pub struct WorkflowEngineBuilder<S: Storage> {
     rule_engine: Option<RuleExecutionEngine>,
     action_executor: Option<ActionExecutor>,
     max_execution_steps: u64,
+    max_history_events: usize,
 }
 
 impl<S: Storage> WorkflowEngineBuilder<S> {
@@ -152,6 +161,7 @@ impl<S: Storage> WorkflowEngineBuilder<S> {
             rule_engine: None,
             action_executor: None,
             max_execution_steps: 1000,
+            max_history_events: DEFAULT_MAX_EMBEDDED_EVENTS,
         }
     }
 
@@ -175,6 +185,13 @@ impl<S: Storage> WorkflowEngineBuilder<S> {
         self
     }
 
+    /// Execution events kept embedded per instance; older events are
+    /// archived as `workflow_event` entities when the instance is stored
+    pub fn with_max_history_events(mut self, max_events: usize) -> Self {
+        self.max_history_events = max_events;
+        self
+    }
+
     pub fn build(self) -> Result<WorkflowAutomationEngine<S>, EngramError> {
         let storage = self
             .storage
@@ -191,6 +208,7 @@ impl<S: Storage> WorkflowEngineBuilder<S> {
             action_executor,
             active_instances: HashMap::new(),
             max_execution_steps: self.max_execution_steps,
+            max_history_events: self.max_history_events,
         })
     }
 }
@@ -203,6 +221,7 @@ impl<S: Storage> WorkflowAutomationEngine<S> {
             action_executor: ActionExecutor::new(true),
             active_instances: HashMap::new(),
             max_execution_steps: 1000,
+            max_history_events: DEFAULT_MAX_EMBEDDED_EVENTS,
         }
     }
 
@@ -351,7 +370,7 @@ impl<S: Storage> WorkflowAutomationEngine<S> {
             metadata: HashMap::new(),
         };
 
-        let instance = WorkflowInstance {
+        let mut instance = WorkflowInstance {
             id: instance_id.clone(),
             workflow_id,
             current_state: initial_state_name.clone(),
@@ -362,12 +381,11 @@ impl<S: Storage> WorkflowAutomationEngine<S> {
             completed_at: None,
             execution_history: vec![start_event.clone()],
             step_count: 0,
+            archived_event_count: 0,
         };
 
-        self.active_instances
-            .insert(instance_id.clone(), instance.clone());
-
-        self.storage.store(&instance.to_generic())?;
+        store_workflow_instance(&mut self.storage, &mut instance, self.max_history_events)?;
+        self.active_instances.insert(instance_id.clone(), instance);
 
         Ok(WorkflowExecutionResult {
             success: true,
@@ -500,7 +518,7 @@ impl<S: Storage> WorkflowAutomationEngine<S> {
                 {
                     let instance = self.active_instances.get_mut(instance_id).unwrap();
                     instance.updated_at = Utc::now();
-                    self.storage.store(&instance.to_generic())?;
+                    store_workflow_instance(&mut self.storage, instance, self.max_history_events)?;
                 }
 
                 return Ok(WorkflowExecutionResult {
@@ -610,7 +628,7 @@ impl<S: Storage> WorkflowAutomationEngine<S> {
                 let instance = self.active_instances.get_mut(instance_id).unwrap();
                 instance.execution_history.push(fail_event.clone());
                 instance.updated_at = Utc::now();
-                self.storage.store(&instance.to_generic())?;
+                store_workflow_instance(&mut self.storage, instance, self.max_history_events)?;
             }
 
             let mut all_events = action_events;
@@ -666,7 +684,7 @@ impl<S: Storage> WorkflowAutomationEngine<S> {
 
         {
             let instance = self.active_instances.get_mut(instance_id).unwrap();
-            self.storage.store(&instance.to_generic())?;
+            store_workflow_instance(&mut self.storage, instance, self.max_history_events)?;
         }
 
         let mut all_events = condition_events;
@@ -694,24 +712,43 @@ impl<S: Storage> WorkflowAutomationEngine<S> {
         self.action_executor.execute_action(action_type, parameters)
     }
 
+    /// Current persisted state of an instance
     pub fn get_instance_status(&self, instance_id: &str) -> Result<WorkflowInstance, EngramError> {
-        if let Some(instance) = self.active_instances.get(instance_id) {
-            return Ok(instance.clone());
-        }
-
         if let Some(generic) = self.storage.get(instance_id, "workflow_instance")? {
             return WorkflowInstance::from_generic(generic)
                 .map_err(|e| EngramError::Validation(e.to_string()));
         }
 
-        Err(EngramError::NotFound(format!(
-            "Workflow instance {} not found",
-            instance_id
-        )))
+        Err(EngramError::not_found("workflow_instance", instance_id))
     }
 
+    /// Running instances, read from storage
     pub fn list_active_instances(&self) -> Vec<WorkflowInstance> {
-        match self.storage.get_all("workflow_instance") {
+        let filter = QueryFilter {
+            entity_type: Some(WorkflowInstance::entity_type().to_string()),
+            field_filters: HashMap::from([(
+                "status".to_string(),
+                serde_json::to_value(WorkflowStatus::Running)
+                    .expect("WorkflowStatus serialization should not fail"),
+            )]),
+            limit: None,
+            offset: None,
+            ..Default::default()
+        };
+
+        match self.storage.query(&filter) {
+            Ok(result) => result
+                .entities
+                .into_iter()
+                .filter_map(|e| WorkflowInstance::from_generic(e).ok())
+                .collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// All instances regardless of status, read from storage
+    pub fn list_instances(&self) -> Vec<WorkflowInstance> {
+        match self.storage.get_all(WorkflowInstance::entity_type()) {
             Ok(entities) => entities
                 .into_iter()
                 .filter_map(|e| WorkflowInstance::from_generic(e).ok())
@@ -823,7 +860,7 @@ impl<S: Storage> WorkflowAutomationEngine<S> {
         instance.updated_at = Utc::now();
         instance.execution_history.push(suspend_event.clone());
 
-        self.storage.store(&instance.to_generic())?;
+        store_workflow_instance(&mut self.storage, instance, self.max_history_events)?;
 
         Ok(WorkflowExecutionResult {
             success: true,
@@ -869,7 +906,7 @@ impl<S: Storage> WorkflowAutomationEngine<S> {
         instance.updated_at = Utc::now();
         instance.execution_history.push(resume_event.clone());
 
-        self.storage.store(&instance.to_generic())?;
+        store_workflow_instance(&mut self.storage, instance, self.max_history_events)?;
 
         Ok(WorkflowExecutionResult {
             success: true,
@@ -882,21 +919,27 @@ impl<S: Storage> WorkflowAutomationEngine<S> {
     }
 
     fn ensure_instance_loaded(&mut self, instance_id: &str) -> Result<(), EngramError> {
-        if self.active_instances.contains_key(instance_id) {
+        self.load_instances(&[instance_id.to_string()])
+    }
+
+    /// (Re)load `instance_ids` from storage with a single batched read.
+    ///
+    /// Storage is the source of truth: another engine or process may have
+    /// advanced an instance since it was last cached here.
+    pub fn load_instances(&mut self, instance_ids: &[String]) -> Result<(), EngramError> {
+        if instance_ids.is_empty() {
             return Ok(());
         }
-        if let Some(generic) = self.storage.get(instance_id, "workflow_instance")? {
+
+        let loaded = self.storage.get_many(instance_ids, "workflow_instance")?;
+        for (instance_id, generic) in instance_ids.iter().cloned().zip(loaded) {
+            let generic = generic
+                .ok_or_else(|| EngramError::not_found("workflow_instance", instance_id.clone()))?;
             let instance = WorkflowInstance::from_generic(generic)
                 .map_err(|e| EngramError::Validation(e.to_string()))?;
-            self.active_instances
-                .insert(instance_id.to_string(), instance);
-            Ok(())
-        } else {
-            Err(EngramError::NotFound(format!(
-                "Workflow instance {} not found",
-                instance_id
-            )))
+            self.active_instances.insert(instance_id, instance);
         }
+        Ok(())
     }
 
     pub fn cancel_workflow(
@@ -925,7 +968,7 @@ impl<S: Storage> WorkflowAutomationEngine<S> {
         instance.completed_at = Some(Utc::now());
         instance.execution_history.push(cancel_event.clone());
 
-        self.storage.store(&instance.to_generic())?;
+        store_workflow_instance(&mut self.storage, instance, self.max_history_events)?;
 
         Ok(WorkflowExecutionResult {
             success: true,
@@ -950,17 +993,18 @@ impl<S: Storage> WorkflowAutomationEngine<S> {
         }
 
         instance.updated_at = Utc::now();
-        self.storage.store(&instance.to_generic())?;
+        store_workflow_instance(&mut self.storage, instance, self.max_history_events)?;
 
         Ok(())
     }
 
+    /// Full execution history, including events archived out of the instance
     pub fn get_execution_history(
         &self,
         instance_id: &str,
     ) -> Result<Vec<WorkflowExecutionEvent>, EngramError> {
         let instance = self.get_instance_status(instance_id)?;
-        Ok(instance.execution_history)
+        full_execution_history(&self.storage, &instance)
     }
 
     fn evaluate_transition_condition(
@@ -1374,14 +1418,62 @@ impl<S: Storage> WorkflowAutomationEngine<S> {
     }
 
     fn load_workflow_definition(&self, workflow_id: &str) -> Result<Workflow, EngramError> {
-        let generic = self.storage.get(workflow_id, "workflow")?.ok_or_else(|| {
-            EngramError::NotFound(format!("Workflow definition {} not found", workflow_id))
-        })?;
+        let generic = self
+            .storage
+            .get(workflow_id, "workflow")?
+            .ok_or_else(|| EngramError::not_found("workflow", workflow_id))?;
 
         Workflow::from_generic(generic).map_err(|e| EngramError::Validation(e.to_string()))
     }
 }
 
+impl<S: Storage> EngineHealthCheck for WorkflowAutomationEngine<S> {
+    /// Malformed definitions and running instances whose definition is gone
+    /// cannot advance, so either degrades the engine
+    fn health_check(&self) -> Result<HealthStatus, EngramError> {
+        let definitions = self.storage.get_all("workflow")?;
+        let total = definitions.len();
+        let workflow_ids: HashSet<String> = definitions
+            .into_iter()
+            .filter_map(|e| Workflow::from_generic(e).ok())
+            .map(|workflow| workflow.id)
+            .collect();
+        let running = self.list_active_instances();
+        let orphaned = running
+            .iter()
+            .filter(|instance| !workflow_ids.contains(&instance.workflow_id))
+            .count();
+
+        let mut problems = Vec::new();
+        if workflow_ids.len() < total {
+            problems.push(format!(
+                "{} malformed workflow definition(s)",
+                total - workflow_ids.len()
+            ));
+        }
+        if orphaned > 0 {
+            problems.push(format!(
+                "{} running instance(s) reference missing workflows",
+                orphaned
+            ));
+        }
+
+        Ok(if problems.is_empty() {
+            HealthStatus::new(
+                "workflow",
+                HealthState::Healthy,
+                format!(
+                    "{} workflow(s), {} running instance(s)",
+                    total,
+                    running.len()
+                ),
+            )
+        } else {
+            HealthStatus::new("workflow", HealthState::Degraded, problems.join("; "))
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1773,6 +1865,110 @@ mod tests {
         assert_eq!(engine.list_active_instances().len(), 2);
     }
 
+    #[test]
+    fn test_list_active_instances_excludes_finished() {
+        let mut engine = create_test_engine();
+        let workflow_id = create_test_workflow_in_storage(&mut engine);
+        let running = engine
+            .start_workflow(
+                workflow_id.clone(),
+                None,
+                None,
+                "a1".to_string(),
+                HashMap::new(),
+            )
+            .unwrap();
+        let cancelled = engine
+            .start_workflow(workflow_id, None, None, "a2".to_string(), HashMap::new())
+            .unwrap();
+        engine
+            .cancel_workflow(&cancelled.instance_id, "a2".to_string(), "done".to_string())
+            .unwrap();
+
+        let active = engine.list_active_instances();
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].id, running.instance_id);
+        assert_eq!(engine.list_instances().len(), 2);
+    }
+
+    #[test]
+    fn test_history_compacts_past_threshold() {
+        let storage = MemoryStorage::new("test-agent");
+        let mut engine = WorkflowEngineBuilder::new()
+            .with_storage(storage.clone())
+            .with_max_history_events(2)
+            .build()
+            .unwrap();
+        let workflow_id = create_test_workflow_in_storage(&mut engine);
+        let started = engine
+            .start_workflow(workflow_id, None, None, "a1".to_string(), HashMap::new())
+            .unwrap();
+        for transition in ["start", "complete"] {
+            engine
+                .execute_transition(
+                    &started.instance_id,
+                    transition.to_string(),
+                    "a1".to_string(),
+                )
+                .unwrap();
+        }
+
+        let instance = engine.get_instance_status(&started.instance_id).unwrap();
+        assert_eq!(instance.execution_history.len(), 2);
+        assert!(instance.archived_event_count > 0);
+
+        let history = engine.get_execution_history(&started.instance_id).unwrap();
+        assert_eq!(history.len() as u64, instance.total_event_count());
+        assert!(matches!(history[0].event_type, WorkflowEventType::Started));
+        assert_eq!(
+            crate::entities::load_archived_events(&storage, &started.instance_id)
+                .unwrap()
+                .len() as u64,
+            instance.archived_event_count
+        );
+    }
+
+    #[test]
+    fn test_instances_persist_across_engines() {
+        let storage = MemoryStorage::new("test-agent");
+        let mut engine = WorkflowAutomationEngine::new(storage.clone());
+        let workflow_id = create_test_workflow_in_storage(&mut engine);
+        let started = engine
+            .start_workflow(workflow_id, None, None, "a1".to_string(), HashMap::new())
+            .unwrap();
+        drop(engine);
+
+        let mut fresh = WorkflowAutomationEngine::new(storage.clone());
+        assert_eq!(fresh.list_active_instances().len(), 1);
+        let result = fresh
+            .execute_transition(&started.instance_id, "start".to_string(), "a1".to_string())
+            .unwrap();
+        assert!(result.success);
+        assert_eq!(result.current_state, "in_progress");
+
+        // A third engine sees the transition, and a stale cache does not
+        // override it
+        let reread = WorkflowAutomationEngine::new(storage);
+        let instance = reread.get_instance_status(&started.instance_id).unwrap();
+        assert_eq!(instance.current_state, "in_progress");
+        assert_eq!(instance.step_count, 1);
+
+        fresh
+            .execute_transition(
+                &started.instance_id,
+                "complete".to_string(),
+                "a1".to_string(),
+            )
+            .unwrap();
+        assert_eq!(
+            reread
+                .get_instance_status(&started.instance_id)
+                .unwrap()
+                .current_state,
+            "completed"
+        );
+    }
+
     fn create_workflow_with_actions(
         engine: &mut WorkflowAutomationEngine<MemoryStorage>,
         actions: Vec<crate::entities::TransitionAction>,
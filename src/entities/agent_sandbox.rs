@@ -39,6 +39,9 @@ pub struct PermissionSet {
     pub quality_gate_permissions: Vec<QualityGatePermission>,
     /// Workflow permissions
     pub workflow_permissions: WorkflowPermissions,
+    /// Path globs scoping file writes, deletions and system file access
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub path_permissions: Vec<PathPermission>,
 }
 
 /// Resource limits for an agent
@@ -65,6 +68,33 @@ pub struct ResourceLimits {
     /// Maximum network requests per minute
     #[validate(range(min = 1, max = 1000))]
     pub max_network_requests_per_minute: u32,
+    /// Maximum number of entities the agent may create
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_entities_created: Option<u64>,
+    /// Maximum total bytes of entity data the agent may write
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_total_bytes_written: Option<u64>,
+    /// Maximum serialized size of a single entity in bytes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_single_entity_bytes: Option<u64>,
+}
+
+impl ResourceLimits {
+    /// Whether any limit on entity storage is configured
+    pub fn limits_storage(&self) -> bool {
+        self.max_entities_created.is_some()
+            || self.max_total_bytes_written.is_some()
+            || self.max_single_entity_bytes.is_some()
+    }
+}
+
+/// Storage consumed by an agent, persisted with its sandbox
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SandboxUsage {
+    /// Entities created since usage was last reset
+    pub entities_created: u64,
+    /// Serialized bytes written since usage was last reset
+    pub bytes_written: u64,
 }
 
 /// Command filtering configuration
@@ -173,6 +203,19 @@ pub struct PathRestriction {
     pub escalation_allowed: bool,
 }
 
+/// Path globs an operation is scoped to
+///
+/// Deny globs take precedence; when `allow` is non-empty, paths must match
+/// one of its globs.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PathPermission {
+    pub operation: OperationType,
+    #[serde(default)]
+    pub allow: Vec<String>,
+    #[serde(default)]
+    pub deny: Vec<String>,
+}
+
 /// File operations
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
@@ -295,6 +338,10 @@ pub struct AgentSandbox {
     #[serde(rename = "violation_count")]
     pub violation_count: u32,
 
+    /// Storage consumed against the resource limits
+    #[serde(rename = "usage", default)]
+    pub usage: SandboxUsage,
+
     /// Associated agent
     #[serde(rename = "agent")]
     pub agent: String,
@@ -333,6 +380,7 @@ impl AgentSandbox {
             created_at: now,
             last_modified: now,
             violation_count: 0,
+            usage: SandboxUsage::default(),
             agent,
             metadata: HashMap::new(),
         }
@@ -408,6 +456,7 @@ impl AgentSandbox {
                 can_execute_workflows: false,
                 restricted_workflow_types: vec!["security".to_string()],
             },
+            path_permissions: Vec::new(),
         };
 
         let resource_limits = ResourceLimits {
@@ -418,6 +467,9 @@ impl AgentSandbox {
             max_concurrent_operations: 2,
             max_file_size_mb: 10,
             max_network_requests_per_minute: 0,
+            max_entities_created: None,
+            max_total_bytes_written: None,
+            max_single_entity_bytes: None,
         };
 
         let command_filter = CommandFilter {
@@ -506,6 +558,7 @@ impl AgentSandbox {
                 can_execute_workflows: true,
                 restricted_workflow_types: vec!["deployment".to_string()],
             },
+            path_permissions: Vec::new(),
         };
 
         let resource_limits = ResourceLimits {
@@ -516,6 +569,9 @@ impl AgentSandbox {
             max_concurrent_operations: 5,
             max_file_size_mb: 50,
             max_network_requests_per_minute: 10,
+            max_entities_created: None,
+            max_total_bytes_written: None,
+            max_single_entity_bytes: None,
         };
 
         let command_filter = CommandFilter {
@@ -587,6 +643,7 @@ impl AgentSandbox {
                 can_execute_workflows: true,
                 restricted_workflow_types: vec!["production".to_string()],
             },
+            path_permissions: Vec::new(),
         };
 
         let resource_limits = ResourceLimits {
@@ -597,6 +654,9 @@ impl AgentSandbox {
             max_concurrent_operations: 10,
             max_file_size_mb: 100,
             max_network_requests_per_minute: 50,
+            max_entities_created: None,
+            max_total_bytes_written: None,
+            max_single_entity_bytes: None,
         };
 
         let command_filter = CommandFilter {
@@ -665,6 +725,7 @@ impl AgentSandbox {
                 can_execute_workflows: false,
                 restricted_workflow_types: vec!["*".to_string()],
             },
+            path_permissions: Vec::new(),
         };
 
         let resource_limits = ResourceLimits {
@@ -675,6 +736,9 @@ impl AgentSandbox {
             max_concurrent_operations: 1,
             max_file_size_mb: 1,
             max_network_requests_per_minute: 0,
+            max_entities_created: None,
+            max_total_bytes_written: None,
+            max_single_entity_bytes: None,
         };
 
         let command_filter = CommandFilter {
@@ -732,6 +796,7 @@ impl AgentSandbox {
                 can_execute_workflows: true,
                 restricted_workflow_types: vec![],
             },
+            path_permissions: Vec::new(),
         };
 
         let resource_limits = ResourceLimits {
@@ -742,6 +807,9 @@ impl AgentSandbox {
             max_concurrent_operations: 50,
             max_file_size_mb: 1024,
             max_network_requests_per_minute: 1000,
+            max_entities_created: None,
+            max_total_bytes_written: None,
+            max_single_entity_bytes: None,
         };
 
         let command_filter = CommandFilter {
@@ -881,6 +949,10 @@ impl Entity for AgentSandbox {
             "violation_count".to_string(),
             serde_json::to_value(&self.violation_count).unwrap(),
         );
+        data.insert(
+            "usage".to_string(),
+            serde_json::to_value(&self.usage).unwrap(),
+        );
         data.insert(
             "agent".to_string(),
             serde_json::to_value(&self.agent).unwrap(),
@@ -929,6 +1001,9 @@ impl Default for ResourceLimits {
             max_concurrent_operations: 5,
             max_file_size_mb: 50,
             max_network_requests_per_minute: 20,
+            max_entities_created: None,
+            max_total_bytes_written: None,
+            max_single_entity_bytes: None,
         }
     }
 }
@@ -942,6 +1017,7 @@ impl Default for PermissionSet {
             network_access: NetworkPolicy::InternalOnly,
             quality_gate_permissions: vec![],
             workflow_permissions: WorkflowPermissions::default(),
+            path_permissions: Vec::new(),
         }
     }
 }
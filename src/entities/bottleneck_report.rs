@@ -344,6 +344,8 @@ mod tests {
             agent: "test-agent".to_string(),
             start_time: start,
             end_time: end,
+            due_at: None,
+            estimate_seconds: None,
             parent: None,
             children: vec![],
             context_ids: vec![],
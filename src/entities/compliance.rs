@@ -134,6 +134,21 @@ pub struct Compliance {
     )]
     pub related_standards: Vec<String>,
 
+    /// Version of each standard this item was last checked against, keyed
+    /// by standard ID
+    #[serde(
+        rename = "standard_versions",
+        skip_serializing_if = "HashMap::is_empty",
+        default
+    )]
+    pub standard_versions: HashMap<String, String>,
+
+    /// Set when a standard this item was checked against has changed
+    /// version since; the reasons are kept under [`STALE_STANDARDS_KEY`]
+    /// in `metadata`
+    #[serde(rename = "stale", default)]
+    pub stale: bool,
+
     /// Tags for categorization
     #[serde(rename = "tags", skip_serializing_if = "Vec::is_empty", default)]
     pub tags: Vec<String>,
@@ -147,34 +162,100 @@ pub struct Compliance {
     pub metadata: HashMap<String, serde_json::Value>,
 }
 
+/// What a piece of compliance evidence points at
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum EvidenceKind {
+    /// A commit in the workspace repository, by SHA
+    Commit,
+    /// A file path in the workspace
+    File,
+    /// A stored quality gate `ExecutionResult`, by ID
+    Gate,
+    /// An external URL
+    Url,
+}
+
+impl std::fmt::Display for EvidenceKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EvidenceKind::Commit => write!(f, "commit"),
+            EvidenceKind::File => write!(f, "file"),
+            EvidenceKind::Gate => write!(f, "gate"),
+            EvidenceKind::Url => write!(f, "url"),
+        }
+    }
+}
+
+impl std::str::FromStr for EvidenceKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "commit" => Ok(Self::Commit),
+            "file" => Ok(Self::File),
+            "gate" => Ok(Self::Gate),
+            "url" => Ok(Self::Url),
+            other => Err(format!(
+                "Unknown evidence kind '{}' (expected commit, file, gate or url)",
+                other
+            )),
+        }
+    }
+}
+
 /// Evidence supporting compliance
-#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, PartialEq)]
 pub struct ComplianceEvidence {
     /// Evidence identifier
     #[serde(rename = "id")]
     pub id: String,
 
-    /// Evidence description
-    #[serde(rename = "description")]
-    pub description: String,
+    /// What the reference points at
+    #[serde(rename = "kind")]
+    pub kind: EvidenceKind,
 
-    /// Evidence type (document, test, review, etc.)
-    #[serde(rename = "evidence_type")]
-    pub evidence_type: String,
+    /// Commit SHA, file path, execution result ID or URL
+    #[serde(rename = "reference")]
+    pub reference: String,
 
-    /// Evidence location or URL
-    #[serde(rename = "location")]
-    pub location: Option<String>,
+    /// Standard requirement this evidence supports
+    #[serde(
+        rename = "requirement_id",
+        skip_serializing_if = "Option::is_none",
+        default
+    )]
+    pub requirement_id: Option<String>,
 
-    /// Timestamp when evidence was collected
-    #[serde(rename = "collected_at")]
-    pub collected_at: DateTime<Utc>,
+    /// Agent that attached the evidence
+    #[serde(rename = "added_by")]
+    pub added_by: String,
 
-    /// Validator or reviewer
-    #[serde(rename = "reviewer")]
-    pub reviewer: Option<String>,
+    /// When the evidence was attached
+    #[serde(rename = "timestamp")]
+    pub timestamp: DateTime<Utc>,
 }
 
+impl ComplianceEvidence {
+    /// Create evidence attached now by `added_by`
+    pub fn new(kind: EvidenceKind, reference: String, added_by: String) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            kind,
+            reference,
+            requirement_id: None,
+            added_by,
+            timestamp: Utc::now(),
+        }
+    }
+}
+
+/// Metadata key holding the audit trail of removed evidence
+pub const EVIDENCE_REMOVALS_KEY: &str = "evidence_removals";
+
+/// Metadata key holding, per standard ID, why an item needs re-certification
+pub const STALE_STANDARDS_KEY: &str = "stale_standards";
+
 /// Compliance violation
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
 pub struct ComplianceViolation {
@@ -229,6 +310,8 @@ impl Compliance {
             evidence: Vec::new(),
             violations: Vec::new(),
             related_standards: Vec::new(),
+            standard_versions: HashMap::new(),
+            stale: false,
             tags: Vec::new(),
             metadata: HashMap::new(),
         }
@@ -272,6 +355,72 @@ impl Compliance {
         self.updated_at = Utc::now();
     }
 
+    /// Remove evidence by ID (or unique ID prefix), recording the removed
+    /// entry, who removed it and why under [`EVIDENCE_REMOVALS_KEY`] in
+    /// `metadata`
+    pub fn remove_evidence(
+        &mut self,
+        evidence_id: &str,
+        removed_by: &str,
+        reason: &str,
+    ) -> crate::Result<ComplianceEvidence> {
+        let matches: Vec<usize> = self
+            .evidence
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.id == evidence_id || e.id.starts_with(evidence_id))
+            .map(|(i, _)| i)
+            .collect();
+        let index = match matches.as_slice() {
+            [index] => *index,
+            [] => return Err(crate::EngramError::not_found("evidence", evidence_id)),
+            _ => {
+                return Err(crate::EngramError::Validation(format!(
+                    "Evidence ID '{}' is ambiguous on compliance '{}'",
+                    evidence_id, self.id
+                )))
+            }
+        };
+
+        let removed = self.evidence.remove(index);
+        let entry = serde_json::json!({
+            "evidence": removed,
+            "removed_by": removed_by,
+            "removed_at": Utc::now(),
+            "reason": reason,
+        });
+        let removals = self
+            .metadata
+            .entry(EVIDENCE_REMOVALS_KEY.to_string())
+            .or_insert_with(|| serde_json::Value::Array(Vec::new()));
+        if !removals.is_array() {
+            *removals = serde_json::Value::Array(Vec::new());
+        }
+        if let serde_json::Value::Array(entries) = removals {
+            entries.push(entry);
+        }
+        self.updated_at = Utc::now();
+        Ok(removed)
+    }
+
+    /// Evidence supporting a requirement of `standard_id`: entries attached
+    /// to that requirement, plus untargeted entries when this item tracks
+    /// the standard
+    pub fn evidence_for(
+        &self,
+        standard_id: &str,
+        requirement_id: &str,
+    ) -> Vec<&ComplianceEvidence> {
+        let tracks_standard = self.related_standards.iter().any(|s| s == standard_id);
+        self.evidence
+            .iter()
+            .filter(|e| match &e.requirement_id {
+                Some(id) => id == requirement_id,
+                None => tracks_standard,
+            })
+            .collect()
+    }
+
     /// Add a related standard
     pub fn add_related_standard(&mut self, standard_id: String) {
         if !self.related_standards.contains(&standard_id) {
@@ -279,6 +428,67 @@ impl Compliance {
         }
     }
 
+    /// Whether this item tracks `standard_id`, either as a related standard
+    /// or by having been checked against it
+    pub fn tracks_standard(&self, standard_id: &str) -> bool {
+        self.related_standards.iter().any(|s| s == standard_id)
+            || self.standard_versions.contains_key(standard_id)
+    }
+
+    /// Record a check against `version` of `standard_id`, re-certifying the
+    /// item for that standard
+    pub fn record_standard_check(&mut self, standard_id: &str, version: &str) {
+        self.standard_versions
+            .insert(standard_id.to_string(), version.to_string());
+        if let Some(serde_json::Value::Object(notes)) = self.metadata.get_mut(STALE_STANDARDS_KEY) {
+            notes.remove(standard_id);
+            if notes.is_empty() {
+                self.metadata.remove(STALE_STANDARDS_KEY);
+            }
+        }
+        self.stale = self.metadata.contains_key(STALE_STANDARDS_KEY);
+        self.updated_at = Utc::now();
+    }
+
+    /// Flag the item for re-certification because `standard_id` moved to
+    /// `version`. Returns false, changing nothing, when the item was never
+    /// checked against the standard or was checked against `version`.
+    pub fn mark_stale_for(&mut self, standard_id: &str, version: &str) -> bool {
+        let Some(checked) = self.standard_versions.get(standard_id) else {
+            return false;
+        };
+        if checked == version {
+            return false;
+        }
+        let note = format!(
+            "Checked against version {}; standard is now at version {} ({})",
+            checked,
+            version,
+            Utc::now().format("%Y-%m-%d")
+        );
+        let notes = self
+            .metadata
+            .entry(STALE_STANDARDS_KEY.to_string())
+            .or_insert_with(|| serde_json::Value::Object(Default::default()));
+        if !notes.is_object() {
+            *notes = serde_json::Value::Object(Default::default());
+        }
+        if let serde_json::Value::Object(notes) = notes {
+            notes.insert(standard_id.to_string(), serde_json::Value::String(note));
+        }
+        self.stale = true;
+        self.updated_at = Utc::now();
+        true
+    }
+
+    /// Why this item needs re-certification against `standard_id`, if it does
+    pub fn stale_note(&self, standard_id: &str) -> Option<&str> {
+        self.metadata
+            .get(STALE_STANDARDS_KEY)?
+            .get(standard_id)?
+            .as_str()
+    }
+
     /// Set due date
     pub fn set_due_date(&mut self, due_date: DateTime<Utc>) {
         self.due_date = Some(due_date);
@@ -433,4 +643,99 @@ mod tests {
         compliance.description = "Valid".to_string();
         assert!(compliance.validate_entity().is_ok());
     }
+
+    #[test]
+    fn test_remove_evidence_records_reason() {
+        let mut compliance = Compliance::new(
+            "SOC2".to_string(),
+            "Change management".to_string(),
+            "audit".to_string(),
+            "agent".to_string(),
+        );
+        let evidence = ComplianceEvidence::new(
+            EvidenceKind::Url,
+            "https://ci/1".to_string(),
+            "alice".to_string(),
+        );
+        let evidence_id = evidence.id.clone();
+        compliance.add_evidence(evidence);
+
+        assert!(compliance
+            .remove_evidence("missing", "bob", "typo")
+            .is_err());
+        let removed = compliance
+            .remove_evidence(&evidence_id[..8], "bob", "wrong build")
+            .unwrap();
+        assert_eq!(removed.id, evidence_id);
+        assert!(compliance.evidence.is_empty());
+
+        let removals = compliance.metadata[EVIDENCE_REMOVALS_KEY]
+            .as_array()
+            .unwrap();
+        assert_eq!(removals.len(), 1);
+        assert_eq!(removals[0]["reason"], "wrong build");
+        assert_eq!(removals[0]["removed_by"], "bob");
+        assert_eq!(removals[0]["evidence"]["reference"], "https://ci/1");
+
+        let round_tripped = Compliance::from_generic(compliance.to_generic()).unwrap();
+        assert_eq!(
+            round_tripped.metadata[EVIDENCE_REMOVALS_KEY],
+            compliance.metadata[EVIDENCE_REMOVALS_KEY]
+        );
+    }
+
+    #[test]
+    fn test_evidence_for_requirement() {
+        let mut compliance = Compliance::new(
+            "SOC2".to_string(),
+            "Change management".to_string(),
+            "audit".to_string(),
+            "agent".to_string(),
+        );
+        let mut targeted =
+            ComplianceEvidence::new(EvidenceKind::Gate, "run-1".to_string(), "alice".to_string());
+        targeted.requirement_id = Some("req-1".to_string());
+        compliance.add_evidence(targeted);
+        compliance.add_evidence(ComplianceEvidence::new(
+            EvidenceKind::File,
+            "docs/policy.md".to_string(),
+            "alice".to_string(),
+        ));
+
+        assert_eq!(compliance.evidence_for("std-1", "req-1").len(), 1);
+        assert!(compliance.evidence_for("std-1", "req-2").is_empty());
+
+        compliance.add_related_standard("std-1".to_string());
+        assert_eq!(compliance.evidence_for("std-1", "req-1").len(), 2);
+        assert_eq!(compliance.evidence_for("std-1", "req-2").len(), 1);
+    }
+
+    #[test]
+    fn test_stale_until_rechecked() {
+        let mut compliance = Compliance::new(
+            "SOC2".to_string(),
+            "Change management".to_string(),
+            "audit".to_string(),
+            "agent".to_string(),
+        );
+        assert!(!compliance.mark_stale_for("std-1", "2.0"));
+
+        compliance.record_standard_check("std-1", "1.0");
+        compliance.record_standard_check("std-2", "1.0");
+        assert!(compliance.tracks_standard("std-1"));
+        assert!(!compliance.mark_stale_for("std-1", "1.0"));
+
+        assert!(compliance.mark_stale_for("std-1", "2.0"));
+        assert!(compliance.mark_stale_for("std-2", "1.1"));
+        assert!(compliance.stale);
+        assert!(compliance.stale_note("std-1").unwrap().contains("1.0"));
+
+        compliance.record_standard_check("std-1", "2.0");
+        assert!(compliance.stale);
+        assert!(compliance.stale_note("std-1").is_none());
+        compliance.record_standard_check("std-2", "1.1");
+        assert!(!compliance.stale);
+        assert!(!compliance.metadata.contains_key(STALE_STANDARDS_KEY));
+        assert_eq!(compliance.standard_versions["std-1"], "2.0");
+    }
 }
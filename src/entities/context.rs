@@ -3,7 +3,9 @@
 use super::{Entity, GenericEntity};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
+use std::path::Path;
 use uuid::Uuid;
 use validator::Validate;
 
@@ -40,6 +42,14 @@ pub struct Context {
     #[serde(rename = "source_id")]
     pub source_id: Option<String>,
 
+    /// SHA-256 of the source file when `source` is a file path, taken at capture
+    #[serde(
+        rename = "source_hash",
+        skip_serializing_if = "Option::is_none",
+        default
+    )]
+    pub source_hash: Option<String>,
+
     /// Relevance level
     #[serde(rename = "relevance")]
     pub relevance: ContextRelevance,
@@ -93,6 +103,7 @@ impl Context {
             content,
             source,
             source_id: None,
+            source_hash: None,
             relevance,
             agent,
             created_at: now,
@@ -120,6 +131,55 @@ impl Context {
     pub fn set_source_id(&mut self, source_id: String) {
         self.source_id = Some(source_id);
     }
+
+    /// Record the hash of the source file if `source` names a readable file
+    pub fn capture_source(&mut self) {
+        self.source_hash = fs_read(&self.source).map(|content| hash_context_source(&content));
+    }
+
+    /// Whether the source file still matches the captured hash; `None` when
+    /// the context was not captured from a file
+    pub fn source_status(&self) -> Option<ContextSourceStatus> {
+        let captured = self.source_hash.as_deref()?;
+        Some(match fs_read(&self.source) {
+            None => ContextSourceStatus::Missing,
+            Some(content) if hash_context_source(&content) == captured => {
+                ContextSourceStatus::Fresh
+            }
+            Some(_) => ContextSourceStatus::Changed,
+        })
+    }
+}
+
+/// Freshness of a context captured from a file
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ContextSourceStatus {
+    Fresh,
+    Changed,
+    Missing,
+}
+
+impl std::fmt::Display for ContextSourceStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ContextSourceStatus::Fresh => write!(f, "fresh"),
+            ContextSourceStatus::Changed => write!(f, "changed"),
+            ContextSourceStatus::Missing => write!(f, "missing"),
+        }
+    }
+}
+
+/// Hex SHA-256 of source file content
+pub fn hash_context_source(content: &str) -> String {
+    hex::encode(Sha256::digest(content.as_bytes()))
+}
+
+fn fs_read(path: &str) -> Option<String> {
+    if path.is_empty() || !Path::new(path).is_file() {
+        return None;
+    }
+    std::fs::read_to_string(path).ok()
 }
 
 impl Entity for Context {
@@ -266,4 +326,36 @@ mod tests {
         context.add_related_entity(entity_id.to_string());
         assert!(context.related_entities.contains(&entity_id.to_string()));
     }
+
+    #[test]
+    fn test_source_status_tracks_file_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("requirements.md");
+        std::fs::write(&path, "v1").unwrap();
+
+        let mut context = Context::new(
+            "Requirements".to_string(),
+            "v1".to_string(),
+            path.to_string_lossy().to_string(),
+            ContextRelevance::High,
+            "agent".to_string(),
+        );
+        context.capture_source();
+        assert_eq!(
+            context.source_hash.as_deref(),
+            Some(hash_context_source("v1").as_str())
+        );
+        assert_eq!(context.source_status(), Some(ContextSourceStatus::Fresh));
+
+        std::fs::write(&path, "v2").unwrap();
+        assert_eq!(context.source_status(), Some(ContextSourceStatus::Changed));
+
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(context.source_status(), Some(ContextSourceStatus::Missing));
+
+        let mut manual = context.clone();
+        manual.source = "manual".to_string();
+        manual.capture_source();
+        assert_eq!(manual.source_status(), None);
+    }
 }
@@ -3,7 +3,7 @@
 use super::{Entity, GenericEntity};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use uuid::Uuid;
 use validator::Validate;
 
@@ -315,6 +315,24 @@ impl EscalationRequest {
         }
     }
 
+    /// Whether the request was approved and the approval has not lapsed at `now`
+    ///
+    /// Approvals without a duration never lapse.
+    pub fn approval_active(&self, now: DateTime<Utc>) -> bool {
+        if self.status != EscalationStatus::Approved {
+            return false;
+        }
+        match (&self.decision, self.reviewed_at) {
+            (Some(decision), reviewed_at) => match decision.approval_duration {
+                None => true,
+                Some(seconds) => reviewed_at.is_some_and(|reviewed_at| {
+                    now < reviewed_at + chrono::Duration::seconds(seconds as i64)
+                }),
+            },
+            (None, _) => false,
+        }
+    }
+
     /// Check if the request is actionable (pending and not expired)
     pub fn is_actionable(&self) -> bool {
         self.status == EscalationStatus::Pending && !self.is_expired()
@@ -396,6 +414,185 @@ impl std::fmt::Display for EscalationRequest {
     }
 }
 
+/// Review outcomes for one escalation operation type
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct OperationApprovalStats {
+    pub approved: usize,
+    pub denied: usize,
+    /// `approved / (approved + denied)`, or `None` before any decision
+    pub approval_rate: Option<f64>,
+}
+
+/// Decisions and review latency for one reviewer
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ReviewerStats {
+    pub reviewer_name: String,
+    pub reviewed: usize,
+    pub approved: usize,
+    pub denied: usize,
+    pub median_review_seconds: Option<u64>,
+    pub p90_review_seconds: Option<u64>,
+}
+
+/// Statistics about escalation requests
+///
+/// Requests still awaiting review count towards `pending_count` only and are
+/// left out of every latency figure. Pending requests whose deadline has
+/// passed are counted as expired, even if nobody has marked them yet.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EscalationStatistics {
+    pub total_requests: usize,
+    pub pending_count: usize,
+    pub approved_count: usize,
+    pub denied_count: usize,
+    pub expired_count: usize,
+    pub cancelled_count: usize,
+    pub reviewed_count: usize,
+    pub total_response_time_seconds: u64,
+    pub average_response_time_seconds: u64,
+    pub total_approval_duration_seconds: u64,
+    pub average_approval_duration_seconds: u64,
+    pub median_review_seconds: Option<u64>,
+    pub p90_review_seconds: Option<u64>,
+    /// `approved / (approved + denied)`, or `None` before any decision
+    pub approval_rate: Option<f64>,
+    /// Share of no-longer-pending requests that expired without a review
+    pub auto_timeout_rate: Option<f64>,
+    /// Keyed by the `Debug` name of the operation type
+    pub by_operation_type: BTreeMap<String, OperationApprovalStats>,
+    /// Keyed by reviewer id
+    pub by_reviewer: BTreeMap<String, ReviewerStats>,
+}
+
+impl EscalationStatistics {
+    /// Aggregate `escalations` as of `now`
+    ///
+    /// Takes any iterator so callers can page through storage instead of
+    /// loading every request up front.
+    pub fn from_escalations<I>(escalations: I, now: DateTime<Utc>) -> Self
+    where
+        I: IntoIterator<Item = EscalationRequest>,
+    {
+        let mut stats = Self::default();
+        let mut latencies = Vec::new();
+        let mut reviewer_latencies: HashMap<String, Vec<u64>> = HashMap::new();
+
+        for escalation in escalations {
+            stats.total_requests += 1;
+            match escalation.status {
+                EscalationStatus::Pending if escalation.expires_at < now => {
+                    stats.expired_count += 1
+                }
+                EscalationStatus::Pending => {
+                    stats.pending_count += 1;
+                    continue;
+                }
+                EscalationStatus::Approved => {
+                    stats.approved_count += 1;
+                    if let Some(duration) = escalation
+                        .decision
+                        .as_ref()
+                        .and_then(|d| d.approval_duration)
+                    {
+                        stats.total_approval_duration_seconds += duration;
+                    }
+                }
+                EscalationStatus::Denied => stats.denied_count += 1,
+                EscalationStatus::Expired => stats.expired_count += 1,
+                EscalationStatus::Cancelled => stats.cancelled_count += 1,
+            }
+
+            let decided = matches!(
+                escalation.status,
+                EscalationStatus::Approved | EscalationStatus::Denied
+            );
+            if decided {
+                let approved = escalation.status == EscalationStatus::Approved;
+                let op = stats
+                    .by_operation_type
+                    .entry(format!("{:?}", escalation.operation_type))
+                    .or_default();
+                if approved {
+                    op.approved += 1;
+                } else {
+                    op.denied += 1;
+                }
+            }
+
+            let Some(reviewed_at) = escalation.reviewed_at else {
+                continue;
+            };
+            let latency = (reviewed_at - escalation.created_at).num_seconds().max(0) as u64;
+            stats.reviewed_count += 1;
+            stats.total_response_time_seconds += latency;
+            latencies.push(latency);
+
+            if let Some(reviewer) = &escalation.reviewer {
+                let entry = stats
+                    .by_reviewer
+                    .entry(reviewer.reviewer_id.clone())
+                    .or_default();
+                entry.reviewer_name = reviewer.reviewer_name.clone();
+                entry.reviewed += 1;
+                match escalation.status {
+                    EscalationStatus::Approved => entry.approved += 1,
+                    EscalationStatus::Denied => entry.denied += 1,
+                    _ => {}
+                }
+                reviewer_latencies
+                    .entry(reviewer.reviewer_id.clone())
+                    .or_default()
+                    .push(latency);
+            }
+        }
+
+        if stats.reviewed_count > 0 {
+            stats.average_response_time_seconds =
+                stats.total_response_time_seconds / stats.reviewed_count as u64;
+        }
+        if stats.approved_count > 0 {
+            stats.average_approval_duration_seconds =
+                stats.total_approval_duration_seconds / stats.approved_count as u64;
+        }
+
+        stats.median_review_seconds = percentile(&mut latencies, 50);
+        stats.p90_review_seconds = percentile(&mut latencies, 90);
+        stats.approval_rate = ratio(
+            stats.approved_count,
+            stats.approved_count + stats.denied_count,
+        );
+        stats.auto_timeout_rate = ratio(
+            stats.expired_count,
+            stats.total_requests - stats.pending_count,
+        );
+        for op in stats.by_operation_type.values_mut() {
+            op.approval_rate = ratio(op.approved, op.approved + op.denied);
+        }
+        for (id, reviewer) in stats.by_reviewer.iter_mut() {
+            if let Some(latencies) = reviewer_latencies.get_mut(id) {
+                reviewer.median_review_seconds = percentile(latencies, 50);
+                reviewer.p90_review_seconds = percentile(latencies, 90);
+            }
+        }
+
+        stats
+    }
+}
+
+fn ratio(part: usize, whole: usize) -> Option<f64> {
+    (whole > 0).then(|| part as f64 / whole as f64)
+}
+
+/// Nearest-rank percentile; sorts `values` in place
+fn percentile(values: &mut [u64], pct: usize) -> Option<u64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_unstable();
+    let rank = (pct * values.len()).div_ceil(100).max(1);
+    Some(values[rank - 1])
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -482,4 +679,94 @@ mod tests {
         req.mark_expired();
         assert_eq!(req.status, EscalationStatus::Expired);
     }
+
+    #[test]
+    fn test_statistics_latency_and_rates() {
+        let now = Utc::now();
+        let reviewed = |op_type, minutes: i64, reviewer: &str, status| {
+            let mut req = EscalationRequest::new(
+                "agent-007".to_string(),
+                op_type,
+                create_test_context(),
+                "Need access".to_string(),
+                EscalationPriority::Normal,
+                "system".to_string(),
+            );
+            req.created_at = now - chrono::Duration::hours(2);
+            req.reviewer = Some(ReviewerInfo {
+                reviewer_id: reviewer.to_string(),
+                reviewer_name: reviewer.to_uppercase(),
+                reviewer_email: None,
+                department: None,
+            });
+            req.status = status;
+            req.reviewed_at = Some(req.created_at + chrono::Duration::minutes(minutes));
+            req
+        };
+        let pending = EscalationRequest::new(
+            "agent-007".to_string(),
+            EscalationOperationType::NetworkAccess,
+            create_test_context(),
+            "Need access".to_string(),
+            EscalationPriority::Normal,
+            "system".to_string(),
+        );
+        let mut lapsed = pending.clone();
+        lapsed.expires_at = now - chrono::Duration::minutes(1);
+
+        let stats = EscalationStatistics::from_escalations(
+            vec![
+                reviewed(
+                    EscalationOperationType::FileSystemAccess,
+                    1,
+                    "alice",
+                    EscalationStatus::Approved,
+                ),
+                reviewed(
+                    EscalationOperationType::FileSystemAccess,
+                    2,
+                    "alice",
+                    EscalationStatus::Approved,
+                ),
+                reviewed(
+                    EscalationOperationType::NetworkAccess,
+                    10,
+                    "bob",
+                    EscalationStatus::Denied,
+                ),
+                reviewed(
+                    EscalationOperationType::NetworkAccess,
+                    60,
+                    "bob",
+                    EscalationStatus::Approved,
+                ),
+                pending,
+                lapsed,
+            ],
+            now,
+        );
+
+        assert_eq!(stats.total_requests, 6);
+        assert_eq!(stats.pending_count, 1);
+        assert_eq!(stats.expired_count, 1);
+        assert_eq!(stats.reviewed_count, 4);
+        assert_eq!(stats.median_review_seconds, Some(120));
+        assert_eq!(stats.p90_review_seconds, Some(3600));
+        assert_eq!(stats.approval_rate, Some(0.75));
+        assert_eq!(stats.auto_timeout_rate, Some(0.2));
+
+        let network = &stats.by_operation_type["NetworkAccess"];
+        assert_eq!((network.approved, network.denied), (1, 1));
+        assert_eq!(network.approval_rate, Some(0.5));
+        assert_eq!(
+            stats.by_operation_type["FileSystemAccess"].approval_rate,
+            Some(1.0)
+        );
+
+        let bob = &stats.by_reviewer["bob"];
+        assert_eq!(bob.reviewer_name, "BOB");
+        assert_eq!((bob.reviewed, bob.approved, bob.denied), (2, 1, 1));
+        assert_eq!(bob.median_review_seconds, Some(600));
+        assert_eq!(bob.p90_review_seconds, Some(3600));
+    }
 }
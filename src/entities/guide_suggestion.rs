@@ -0,0 +1,100 @@
+//! Guide suggestion entity implementation
+//!
+//! Records when each auto-guide suggestion was last shown, so the same
+//! suggestion is not repeated within the configured interval.
+
+use super::{Entity, GenericEntity};
+use crate::error::EngramError;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// An auto-guide suggestion that has been shown, with how often and when last
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuideSuggestion {
+    /// Unique identifier
+    #[serde(rename = "id")]
+    pub id: String,
+
+    /// Suggestion key, e.g. `stale-in-progress:<task id>`
+    #[serde(rename = "key")]
+    pub key: String,
+
+    /// Number of times the suggestion was shown
+    #[serde(rename = "count")]
+    pub count: u64,
+
+    /// When the suggestion was last shown
+    #[serde(rename = "last_shown")]
+    pub last_shown: DateTime<Utc>,
+
+    /// Agent the suggestion was shown to
+    #[serde(rename = "agent")]
+    pub agent: String,
+}
+
+impl GuideSuggestion {
+    pub fn new(key: String, agent: String, shown_at: DateTime<Utc>) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            key,
+            count: 1,
+            last_shown: shown_at,
+            agent,
+        }
+    }
+
+    /// Count another showing of the suggestion
+    pub fn record_shown(&mut self, shown_at: DateTime<Utc>) {
+        self.count += 1;
+        self.last_shown = shown_at;
+    }
+}
+
+impl Entity for GuideSuggestion {
+    fn entity_type() -> &'static str {
+        "guide_suggestion"
+    }
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn agent(&self) -> &str {
+        &self.agent
+    }
+
+    fn timestamp(&self) -> DateTime<Utc> {
+        self.last_shown
+    }
+
+    fn validate_entity(&self) -> crate::Result<()> {
+        if self.key.trim().is_empty() {
+            return Err(EngramError::Validation(
+                "Guide suggestion key cannot be empty".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn to_generic(&self) -> GenericEntity {
+        GenericEntity {
+            id: self.id.clone(),
+            entity_type: Self::entity_type().to_string(),
+            agent: self.agent.clone(),
+            timestamp: self.last_shown,
+            data: serde_json::to_value(self)
+                .expect("GuideSuggestion serialization should not fail"),
+        }
+    }
+
+    fn from_generic(entity: GenericEntity) -> crate::Result<Self> {
+        serde_json::from_value(entity.data).map_err(|e| {
+            EngramError::Deserialization(format!("Failed to deserialize guide suggestion: {}", e))
+        })
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
@@ -23,6 +23,19 @@ pub enum KnowledgeType {
     Autocomplete,
 }
 
+/// Metadata key holding the verification history of a knowledge item
+pub const VERIFICATIONS_METADATA_KEY: &str = "verifications";
+
+/// A recorded re-check of a knowledge item against a source
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct KnowledgeVerification {
+    pub verified_at: DateTime<Utc>,
+    pub verified_by: String,
+    pub source: String,
+    pub previous_confidence: f64,
+    pub confidence: f64,
+}
+
 /// Knowledge entity representing stored information
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
 pub struct Knowledge {
@@ -124,6 +137,23 @@ impl Knowledge {
         }
     }
 
+    /// Check that a confidence value lies in [0.0, 1.0]
+    ///
+    /// Out-of-range values are clamped when `clamp` is set and rejected
+    /// otherwise. NaN is always rejected.
+    pub fn normalize_confidence(confidence: f64, clamp: bool) -> crate::Result<f64> {
+        if (0.0..=1.0).contains(&confidence) {
+            Ok(confidence)
+        } else if clamp && !confidence.is_nan() {
+            Ok(confidence.clamp(0.0, 1.0))
+        } else {
+            Err(crate::EngramError::Validation(format!(
+                "Confidence must be between 0.0 and 1.0, got {}",
+                confidence
+            )))
+        }
+    }
+
     /// Update knowledge content
     pub fn update_content(&mut self, content: String, confidence: f64) {
         self.content = content;
@@ -162,6 +192,35 @@ impl Knowledge {
     pub fn set_source(&mut self, source: String) {
         self.source = Some(source);
     }
+
+    /// Record a verification against `source` and adopt its confidence
+    pub fn record_verification(&mut self, confidence: f64, verified_by: String, source: String) {
+        let now = Utc::now();
+        let verification = KnowledgeVerification {
+            verified_at: now,
+            verified_by,
+            source,
+            previous_confidence: self.confidence,
+            confidence: confidence.clamp(0.0, 1.0),
+        };
+        self.confidence = verification.confidence;
+        self.updated_at = now;
+
+        let mut history = self.verifications();
+        history.push(verification);
+        self.metadata.insert(
+            VERIFICATIONS_METADATA_KEY.to_string(),
+            serde_json::to_value(history).unwrap_or_default(),
+        );
+    }
+
+    /// Verification history, oldest first
+    pub fn verifications(&self) -> Vec<KnowledgeVerification> {
+        self.metadata
+            .get(VERIFICATIONS_METADATA_KEY)
+            .and_then(|value| serde_json::from_value(value.clone()).ok())
+            .unwrap_or_default()
+    }
 }
 
 impl Entity for Knowledge {
@@ -210,11 +269,7 @@ impl Entity for Knowledge {
             ));
         }
 
-        if self.confidence < 0.0 || self.confidence > 1.0 {
-            return Err(crate::EngramError::Validation(
-                "Confidence must be between 0.0 and 1.0".to_string(),
-            ));
-        }
+        Self::normalize_confidence(self.confidence, false)?;
 
         Ok(())
     }
@@ -243,6 +298,277 @@ impl Entity for Knowledge {
     }
 }
 
+/// Metadata key recording which item a merged knowledge item was folded into
+pub const MERGED_INTO_METADATA_KEY: &str = "merged_into";
+
+/// Metadata key listing the items merged into a primary knowledge item
+pub const MERGED_FROM_METADATA_KEY: &str = "merged_from";
+
+/// Confidence given to knowledge items after they are merged into another
+pub const MERGED_KNOWLEDGE_CONFIDENCE: f64 = 0.1;
+
+/// How the merged item's confidence is derived
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConfidenceStrategy {
+    #[default]
+    MaxConfidence,
+    AverageConfidence,
+    /// Average weighted by usage count; plain average when nothing was used
+    WeightedByUsage,
+}
+
+/// How the merged item's content is derived
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ContentStrategy {
+    #[default]
+    KeepPrimary,
+    /// Primary content followed by each distinct secondary content
+    Concatenate,
+    /// The longest content, preferring the primary on ties
+    PickLongest,
+}
+
+/// How the merged item's tags are derived
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TagStrategy {
+    #[default]
+    Union,
+    Intersection,
+}
+
+impl std::str::FromStr for ConfidenceStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "max" => Ok(Self::MaxConfidence),
+            "average" => Ok(Self::AverageConfidence),
+            "weighted" => Ok(Self::WeightedByUsage),
+            other => Err(format!(
+                "Unknown confidence strategy '{}' (expected max, average or weighted)",
+                other
+            )),
+        }
+    }
+}
+
+impl std::str::FromStr for ContentStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "keep-primary" => Ok(Self::KeepPrimary),
+            "concatenate" => Ok(Self::Concatenate),
+            "longest" => Ok(Self::PickLongest),
+            other => Err(format!(
+                "Unknown content strategy '{}' (expected keep-primary, concatenate or longest)",
+                other
+            )),
+        }
+    }
+}
+
+impl std::str::FromStr for TagStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "union" => Ok(Self::Union),
+            "intersection" => Ok(Self::Intersection),
+            other => Err(format!(
+                "Unknown tag strategy '{}' (expected union or intersection)",
+                other
+            )),
+        }
+    }
+}
+
+/// Options for [`merge_knowledge_items`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KnowledgeMergeConfig {
+    pub confidence_strategy: ConfidenceStrategy,
+    pub content_strategy: ContentStrategy,
+    pub tag_strategy: TagStrategy,
+}
+
+fn merged_confidence(items: &[&Knowledge], strategy: ConfidenceStrategy) -> f64 {
+    let average = items.iter().map(|k| k.confidence).sum::<f64>() / items.len() as f64;
+    match strategy {
+        ConfidenceStrategy::MaxConfidence => items.iter().map(|k| k.confidence).fold(0.0, f64::max),
+        ConfidenceStrategy::AverageConfidence => average,
+        ConfidenceStrategy::WeightedByUsage => {
+            let usage: u64 = items.iter().map(|k| k.usage_count).sum();
+            if usage == 0 {
+                average
+            } else {
+                items
+                    .iter()
+                    .map(|k| k.confidence * k.usage_count as f64)
+                    .sum::<f64>()
+                    / usage as f64
+            }
+        }
+    }
+}
+
+fn merged_content(items: &[&Knowledge], strategy: ContentStrategy) -> String {
+    match strategy {
+        ContentStrategy::KeepPrimary => items[0].content.clone(),
+        ContentStrategy::Concatenate => {
+            let mut parts: Vec<&str> = Vec::new();
+            for item in items {
+                let content = item.content.trim();
+                if !content.is_empty() && !parts.contains(&content) {
+                    parts.push(content);
+                }
+            }
+            parts.join("\n\n")
+        }
+        ContentStrategy::PickLongest => items
+            .iter()
+            .fold(items[0], |longest, item| {
+                if item.content.chars().count() > longest.content.chars().count() {
+                    item
+                } else {
+                    longest
+                }
+            })
+            .content
+            .clone(),
+    }
+}
+
+fn merged_tags(items: &[&Knowledge], strategy: TagStrategy) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for item in items {
+        for tag in &item.tags {
+            let keep = match strategy {
+                TagStrategy::Union => true,
+                TagStrategy::Intersection => items.iter().all(|other| other.tags.contains(tag)),
+            };
+            if keep && !tags.contains(tag) {
+                tags.push(tag.clone());
+            }
+        }
+    }
+    tags
+}
+
+/// Fold `secondary_ids` into the knowledge item `primary_id`
+///
+/// The primary gets the merged confidence, content and tags, the union of
+/// contexts and related items, and the summed usage count. Relationships
+/// touching a secondary are re-pointed to the primary, dropping any that
+/// would then link the primary to itself. Secondaries are kept, marked with
+/// `merged_into` and demoted to [`MERGED_KNOWLEDGE_CONFIDENCE`].
+pub fn merge_knowledge_items(
+    storage: &mut dyn crate::storage::Storage,
+    primary_id: &str,
+    secondary_ids: &[String],
+    merge_config: KnowledgeMergeConfig,
+) -> crate::Result<Knowledge> {
+    if secondary_ids.is_empty() {
+        return Err(crate::EngramError::Validation(
+            "At least one knowledge item to merge is required".to_string(),
+        ));
+    }
+    if secondary_ids.iter().any(|id| id == primary_id) {
+        return Err(crate::EngramError::Validation(
+            "Cannot merge a knowledge item into itself".to_string(),
+        ));
+    }
+
+    let load = |storage: &dyn crate::storage::Storage, id: &str| -> crate::Result<Knowledge> {
+        let entity = storage
+            .get(id, Knowledge::entity_type())?
+            .ok_or_else(|| crate::EngramError::not_found("knowledge", id))?;
+        Knowledge::from_generic(entity)
+    };
+    let mut primary = load(storage, primary_id)?;
+    let mut secondaries = Vec::new();
+    for id in secondary_ids {
+        if !secondaries.iter().any(|k: &Knowledge| &k.id == id) {
+            secondaries.push(load(storage, id)?);
+        }
+    }
+
+    let items: Vec<&Knowledge> = std::iter::once(&primary).chain(&secondaries).collect();
+    let confidence = merged_confidence(&items, merge_config.confidence_strategy);
+    let content = merged_content(&items, merge_config.content_strategy);
+    let tags = merged_tags(&items, merge_config.tag_strategy);
+    let usage_count = items.iter().map(|k| k.usage_count).sum();
+    let last_used = items.iter().filter_map(|k| k.last_used).max();
+    let merged_ids: std::collections::HashSet<&str> =
+        secondaries.iter().map(|k| k.id.as_str()).collect();
+
+    primary.confidence = confidence;
+    primary.content = content;
+    primary.tags = tags;
+    primary.usage_count = usage_count;
+    primary.last_used = last_used;
+    for secondary in &secondaries {
+        for context in &secondary.contexts {
+            primary.add_context(context.clone());
+        }
+        for related in &secondary.related_knowledge {
+            primary.add_related_knowledge(related.clone());
+        }
+    }
+    primary
+        .related_knowledge
+        .retain(|id| id != primary_id && !merged_ids.contains(id.as_str()));
+    let mut merged_from: Vec<serde_json::Value> = primary
+        .metadata
+        .get(MERGED_FROM_METADATA_KEY)
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+    merged_from.extend(
+        secondaries
+            .iter()
+            .map(|k| serde_json::Value::String(k.id.clone())),
+    );
+    primary.metadata.insert(
+        MERGED_FROM_METADATA_KEY.to_string(),
+        serde_json::Value::Array(merged_from),
+    );
+    primary.updated_at = Utc::now();
+
+    for generic in storage.get_all(super::EntityRelationship::entity_type())? {
+        let Ok(mut relationship) = super::EntityRelationship::from_generic(generic) else {
+            continue;
+        };
+        let source_merged = merged_ids.contains(relationship.source_id.as_str());
+        let target_merged = merged_ids.contains(relationship.target_id.as_str());
+        if !source_merged && !target_merged {
+            continue;
+        }
+        if source_merged {
+            relationship.source_id = primary.id.clone();
+        }
+        if target_merged {
+            relationship.target_id = primary.id.clone();
+        }
+        if relationship.source_id == relationship.target_id {
+            storage.delete(&relationship.id, super::EntityRelationship::entity_type())?;
+        } else {
+            storage.store(&relationship.to_generic())?;
+        }
+    }
+
+    for secondary in &mut secondaries {
+        secondary.metadata.insert(
+            MERGED_INTO_METADATA_KEY.to_string(),
+            serde_json::Value::String(primary.id.clone()),
+        );
+        secondary.confidence = MERGED_KNOWLEDGE_CONFIDENCE;
+        secondary.updated_at = Utc::now();
+        storage.store(&secondary.to_generic())?;
+    }
+    storage.store(&primary.to_generic())?;
+    Ok(primary)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -300,7 +626,158 @@ mod tests {
         knowledge.confidence = 1.5; // Invalid confidence
         assert!(knowledge.validate_entity().is_err());
 
+        knowledge.confidence = f64::NAN;
+        assert!(knowledge.validate_entity().is_err());
+
         knowledge.confidence = 0.5;
         assert!(knowledge.validate_entity().is_ok());
     }
+
+    #[test]
+    fn test_normalize_confidence() {
+        assert_eq!(Knowledge::normalize_confidence(0.95, false).unwrap(), 0.95);
+        assert_eq!(Knowledge::normalize_confidence(7.0, true).unwrap(), 1.0);
+        assert_eq!(Knowledge::normalize_confidence(-0.2, true).unwrap(), 0.0);
+
+        let err = Knowledge::normalize_confidence(7.0, false).unwrap_err();
+        assert!(err.to_string().contains("got 7"), "{}", err);
+        assert!(Knowledge::normalize_confidence(f64::NAN, true).is_err());
+    }
+
+    #[test]
+    fn test_record_verification() {
+        let mut knowledge = Knowledge::new(
+            "Rate limit".to_string(),
+            "100 req/s".to_string(),
+            KnowledgeType::Fact,
+            0.6,
+            "agent".to_string(),
+        );
+
+        knowledge.record_verification(0.95, "reviewer".to_string(), "API docs".to_string());
+        knowledge.record_verification(0.9, "reviewer".to_string(), "load test".to_string());
+
+        assert_eq!(knowledge.confidence, 0.9);
+        let history = knowledge.verifications();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].previous_confidence, 0.6);
+        assert_eq!(history[0].confidence, 0.95);
+        assert_eq!(history[1].source, "load test");
+
+        let restored = Knowledge::from_generic(knowledge.to_generic()).unwrap();
+        assert_eq!(restored.verifications(), history);
+    }
+
+    fn item(title: &str, confidence: f64, usage: u64, tags: &[&str]) -> Knowledge {
+        let mut knowledge = Knowledge::new(
+            title.to_string(),
+            format!("{} content", title),
+            KnowledgeType::Pattern,
+            confidence,
+            "agent".to_string(),
+        );
+        knowledge.usage_count = usage;
+        knowledge.tags = tags.iter().map(|t| t.to_string()).collect();
+        knowledge
+    }
+
+    #[test]
+    fn test_merge_knowledge_items() {
+        use crate::entities::{EntityRelationType, EntityRelationship};
+        use crate::storage::{MemoryStorage, Storage};
+
+        let mut storage = MemoryStorage::new("default");
+        let primary = item("Retry with jitter", 0.9, 4, &["net", "retry"]);
+        let second = item("Jittered retries", 0.6, 2, &["retry"]);
+        let third = item("Backoff retries need randomness", 0.3, 1, &["retry", "ops"]);
+        for k in [&primary, &second, &third] {
+            storage.store(&k.to_generic()).unwrap();
+        }
+        let relationship = |id: &str, source: &str, target: &str| {
+            EntityRelationship::new(
+                id.to_string(),
+                "agent".to_string(),
+                source.to_string(),
+                "knowledge".to_string(),
+                target.to_string(),
+                "knowledge".to_string(),
+                EntityRelationType::References,
+            )
+        };
+        for rel in [
+            relationship("rel-out", &second.id, "other-knowledge"),
+            relationship("rel-in", "some-task", &third.id),
+            relationship("rel-self", &primary.id, &second.id),
+        ] {
+            storage.store(&rel.to_generic()).unwrap();
+        }
+
+        let config = KnowledgeMergeConfig {
+            confidence_strategy: ConfidenceStrategy::AverageConfidence,
+            content_strategy: ContentStrategy::PickLongest,
+            tag_strategy: TagStrategy::Intersection,
+        };
+        let merged = merge_knowledge_items(
+            &mut storage,
+            &primary.id,
+            &[second.id.clone(), third.id.clone()],
+            config,
+        )
+        .unwrap();
+
+        assert!((merged.confidence - 0.6).abs() < 1e-9);
+        assert_eq!(merged.usage_count, 7);
+        assert_eq!(merged.content, "Backoff retries need randomness content");
+        assert_eq!(merged.tags, vec!["retry"]);
+
+        let stored =
+            Knowledge::from_generic(storage.get(&primary.id, "knowledge").unwrap().unwrap())
+                .unwrap();
+        assert_eq!(stored.usage_count, 7);
+        for id in [&second.id, &third.id] {
+            let secondary =
+                Knowledge::from_generic(storage.get(id, "knowledge").unwrap().unwrap()).unwrap();
+            assert_eq!(
+                secondary.metadata[MERGED_INTO_METADATA_KEY],
+                serde_json::json!(primary.id)
+            );
+            assert_eq!(secondary.confidence, MERGED_KNOWLEDGE_CONFIDENCE);
+        }
+
+        let rel = |id: &str| {
+            storage
+                .get(id, "relationship")
+                .unwrap()
+                .map(|g| EntityRelationship::from_generic(g).unwrap())
+        };
+        assert_eq!(rel("rel-out").unwrap().source_id, primary.id);
+        assert_eq!(rel("rel-in").unwrap().target_id, primary.id);
+        assert!(rel("rel-self").is_none());
+
+        assert!(matches!(
+            merge_knowledge_items(&mut storage, &primary.id, &[primary.id.clone()], config),
+            Err(crate::EngramError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_merge_strategies() {
+        let a = item("a", 0.8, 0, &["x"]);
+        let b = item("bb", 0.4, 0, &["y"]);
+        let items = [&a, &b];
+        assert_eq!(
+            merged_confidence(&items, ConfidenceStrategy::MaxConfidence),
+            0.8
+        );
+        // No usage recorded: weighted falls back to the plain average
+        assert!(
+            (merged_confidence(&items, ConfidenceStrategy::WeightedByUsage) - 0.6).abs() < 1e-9
+        );
+        assert_eq!(
+            merged_content(&items, ContentStrategy::Concatenate),
+            "a content\n\nbb content"
+        );
+        assert_eq!(merged_tags(&items, TagStrategy::Union), vec!["x", "y"]);
+        assert!(merged_tags(&items, TagStrategy::Intersection).is_empty());
+    }
 }
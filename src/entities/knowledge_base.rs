@@ -0,0 +1,457 @@
+//! Knowledge base index built from accumulated entities
+//!
+//! Knowledge, Context and Reasoning entities are grouped into sections by
+//! tag taxonomy. Each item lands in exactly one section: the root of its
+//! first tag (`auth/oauth` files under `auth`). Untagged items inherit the
+//! section of the nearest tagged entity in the relationship graph, where
+//! tasks and sessions also count as tagged neighbours, and fall back to
+//! [`UNTAGGED_SECTION`] when nothing tagged is reachable.
+
+use super::{
+    Context, Entity, EntityRelationship, GenericEntity, Knowledge, Reasoning, Session, Task,
+};
+use crate::storage::Storage;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+/// Section holding items with no reachable tag
+pub const UNTAGGED_SECTION: &str = "untagged";
+
+/// Structured knowledge base assembled from stored entities
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KnowledgeBaseIndex {
+    pub title: String,
+    pub created_at: DateTime<Utc>,
+    pub sections: Vec<KbSection>,
+    pub total_entities: usize,
+}
+
+/// Items sharing one root tag
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct KbSection {
+    pub tag: String,
+    pub knowledge_items: Vec<String>,
+    pub contexts: Vec<String>,
+    pub reasoning_refs: Vec<String>,
+}
+
+/// Kind of entity included in a knowledge base
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KbItemKind {
+    Knowledge,
+    Context,
+    Reasoning,
+}
+
+/// A knowledge base entry with the text used for rendering and search
+#[derive(Debug, Clone)]
+pub struct KbItem {
+    pub id: String,
+    pub kind: KbItemKind,
+    pub title: String,
+    pub body: String,
+    pub tags: Vec<String>,
+}
+
+/// A search match within a knowledge base index
+#[derive(Debug, Clone, Serialize)]
+pub struct KbSearchHit {
+    pub section: String,
+    pub kind: KbItemKind,
+    pub id: String,
+    pub title: String,
+    pub score: usize,
+}
+
+impl KnowledgeBaseIndex {
+    /// Section an entity was filed under
+    pub fn section_of(&self, id: &str) -> Option<&KbSection> {
+        self.sections.iter().find(|s| {
+            s.knowledge_items.iter().any(|i| i == id)
+                || s.contexts.iter().any(|i| i == id)
+                || s.reasoning_refs.iter().any(|i| i == id)
+        })
+    }
+}
+
+/// Root of a tag in the taxonomy, e.g. `Auth/OAuth` -> `auth`
+fn taxonomy_root(tag: &str) -> Option<String> {
+    let root = tag.split(['/', ':']).next()?.trim().to_lowercase();
+    (!root.is_empty()).then_some(root)
+}
+
+fn primary_section(tags: &[String]) -> Option<String> {
+    tags.iter().find_map(|t| taxonomy_root(t))
+}
+
+fn decode_all<E: Entity>(storage: &dyn Storage, agent: Option<&str>) -> crate::Result<Vec<E>> {
+    Ok(storage
+        .get_all(E::entity_type())?
+        .into_iter()
+        .filter(|e| agent.is_none_or(|a| e.agent == a))
+        .filter_map(|e: GenericEntity| E::from_generic(e).ok())
+        .collect())
+}
+
+/// Knowledge, Context and Reasoning entries, optionally limited to one agent
+pub fn collect_kb_items(storage: &dyn Storage, agent: Option<&str>) -> crate::Result<Vec<KbItem>> {
+    let mut items = Vec::new();
+    for k in decode_all::<Knowledge>(storage, agent)? {
+        items.push(KbItem {
+            id: k.id,
+            kind: KbItemKind::Knowledge,
+            title: k.title,
+            body: k.content,
+            tags: k.tags,
+        });
+    }
+    for c in decode_all::<Context>(storage, agent)? {
+        items.push(KbItem {
+            id: c.id,
+            kind: KbItemKind::Context,
+            title: c.title,
+            body: c.content,
+            tags: c.tags,
+        });
+    }
+    for r in decode_all::<Reasoning>(storage, agent)? {
+        items.push(KbItem {
+            id: r.id,
+            kind: KbItemKind::Reasoning,
+            title: r.title,
+            body: r.conclusion,
+            tags: r.tags,
+        });
+    }
+    Ok(items)
+}
+
+/// Undirected link graph over every entity that can relate KB items
+struct LinkGraph {
+    edges: HashMap<String, Vec<String>>,
+    sections: HashMap<String, String>,
+}
+
+impl LinkGraph {
+    fn link(&mut self, a: &str, b: &str) {
+        if a == b {
+            return;
+        }
+        self.edges
+            .entry(a.to_string())
+            .or_default()
+            .push(b.to_string());
+        self.edges
+            .entry(b.to_string())
+            .or_default()
+            .push(a.to_string());
+    }
+
+    fn tag(&mut self, id: &str, tags: &[String]) {
+        if let Some(section) = primary_section(tags) {
+            self.sections.insert(id.to_string(), section);
+        }
+    }
+
+    fn build(storage: &dyn Storage, items: &[KbItem]) -> crate::Result<Self> {
+        let mut graph = Self {
+            edges: HashMap::new(),
+            sections: HashMap::new(),
+        };
+
+        for item in items {
+            graph.tag(&item.id, &item.tags);
+        }
+        for k in decode_all::<Knowledge>(storage, None)? {
+            for related in &k.related_knowledge {
+                graph.link(&k.id, related);
+            }
+        }
+        for c in decode_all::<Context>(storage, None)? {
+            for related in &c.related_entities {
+                graph.link(&c.id, related);
+            }
+        }
+        for r in decode_all::<Reasoning>(storage, None)? {
+            for linked in std::iter::once(&r.task_id)
+                .chain(&r.context_ids)
+                .chain(&r.knowledge_ids)
+            {
+                graph.link(&r.id, linked);
+            }
+        }
+        for t in decode_all::<Task>(storage, None)? {
+            graph.tag(&t.id, &t.tags);
+            for linked in t.context_ids.iter().chain(&t.knowledge) {
+                graph.link(&t.id, linked);
+            }
+        }
+        for s in decode_all::<Session>(storage, None)? {
+            graph.tag(&s.id, &s.tags);
+            for linked in s
+                .task_ids
+                .iter()
+                .chain(&s.context_ids)
+                .chain(&s.knowledge_ids)
+            {
+                graph.link(&s.id, linked);
+            }
+        }
+        for rel in decode_all::<EntityRelationship>(storage, None)? {
+            if rel.active {
+                graph.link(&rel.source_id, &rel.target_id);
+            }
+        }
+
+        // Sorted neighbours keep the nearest-tag choice deterministic
+        for neighbours in graph.edges.values_mut() {
+            neighbours.sort();
+            neighbours.dedup();
+        }
+        Ok(graph)
+    }
+
+    /// Section of `id`, or of the nearest tagged entity reachable from it
+    fn section_for(&self, id: &str) -> Option<String> {
+        let mut visited = HashSet::from([id.to_string()]);
+        let mut queue = VecDeque::from([id.to_string()]);
+        while let Some(current) = queue.pop_front() {
+            if let Some(section) = self.sections.get(&current) {
+                return Some(section.clone());
+            }
+            for next in self.edges.get(&current).into_iter().flatten() {
+                if visited.insert(next.clone()) {
+                    queue.push_back(next.clone());
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Build a knowledge base index, optionally limited to one agent's entities
+pub fn build_knowledge_base(
+    storage: &dyn Storage,
+    agent: Option<&str>,
+    title: &str,
+) -> crate::Result<KnowledgeBaseIndex> {
+    let items = collect_kb_items(storage, agent)?;
+    let graph = LinkGraph::build(storage, &items)?;
+
+    let mut sections: BTreeMap<String, KbSection> = BTreeMap::new();
+    for item in &items {
+        let tag = graph
+            .section_for(&item.id)
+            .unwrap_or_else(|| UNTAGGED_SECTION.to_string());
+        let section = sections.entry(tag.clone()).or_insert_with(|| KbSection {
+            tag,
+            ..Default::default()
+        });
+        match item.kind {
+            KbItemKind::Knowledge => section.knowledge_items.push(item.id.clone()),
+            KbItemKind::Context => section.contexts.push(item.id.clone()),
+            KbItemKind::Reasoning => section.reasoning_refs.push(item.id.clone()),
+        }
+    }
+
+    // Alphabetical sections with the untagged catch-all last
+    let untagged = sections.remove(UNTAGGED_SECTION);
+    let mut sections: Vec<KbSection> = sections.into_values().chain(untagged).collect();
+    for section in &mut sections {
+        section.knowledge_items.sort();
+        section.contexts.sort();
+        section.reasoning_refs.sort();
+    }
+
+    Ok(KnowledgeBaseIndex {
+        title: title.to_string(),
+        created_at: Utc::now(),
+        sections,
+        total_entities: items.len(),
+    })
+}
+
+/// Search the items of an index, best matches first
+///
+/// Each query term scores one point per match in the body or tags and two
+/// in the title or section tag; items matching no term are dropped.
+pub fn search_knowledge_base(
+    items: &[KbItem],
+    index: &KnowledgeBaseIndex,
+    query: &str,
+) -> Vec<KbSearchHit> {
+    let terms: Vec<String> = query.split_whitespace().map(|t| t.to_lowercase()).collect();
+    if terms.is_empty() {
+        return Vec::new();
+    }
+
+    let mut hits: Vec<KbSearchHit> = items
+        .iter()
+        .filter_map(|item| {
+            let section = index.section_of(&item.id)?;
+            let title = item.title.to_lowercase();
+            let body = item.body.to_lowercase();
+            let tags = item.tags.join(" ").to_lowercase();
+            let score: usize = terms
+                .iter()
+                .map(|term| {
+                    let mut score = 0;
+                    if title.contains(term.as_str()) || section.tag.contains(term.as_str()) {
+                        score += 2;
+                    }
+                    if body.contains(term.as_str()) || tags.contains(term.as_str()) {
+                        score += 1;
+                    }
+                    score
+                })
+                .sum();
+            (score > 0).then(|| KbSearchHit {
+                section: section.tag.clone(),
+                kind: item.kind,
+                id: item.id.clone(),
+                title: item.title.clone(),
+                score,
+            })
+        })
+        .collect();
+
+    hits.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.title.cmp(&b.title)));
+    hits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{ContextRelevance, EntityRelationType, KnowledgeType, TaskPriority};
+    use crate::storage::MemoryStorage;
+
+    fn knowledge(storage: &mut MemoryStorage, title: &str, tags: &[&str]) -> String {
+        let mut k = Knowledge::new(
+            title.to_string(),
+            format!("{} details", title),
+            KnowledgeType::Fact,
+            0.8,
+            "default".to_string(),
+        );
+        tags.iter().for_each(|t| k.add_tag(t.to_string()));
+        storage.store(&k.to_generic()).unwrap();
+        k.id
+    }
+
+    fn context(storage: &mut MemoryStorage, title: &str, tags: &[&str]) -> String {
+        let mut c = Context::new(
+            title.to_string(),
+            format!("{} notes", title),
+            "test".to_string(),
+            ContextRelevance::Medium,
+            "default".to_string(),
+        );
+        c.tags = tags.iter().map(|t| t.to_string()).collect();
+        storage.store(&c.to_generic()).unwrap();
+        c.id
+    }
+
+    fn reasoning(storage: &mut MemoryStorage, title: &str, task_id: &str) -> String {
+        let r = Reasoning::new(
+            title.to_string(),
+            task_id.to_string(),
+            "default".to_string(),
+        );
+        storage.store(&r.to_generic()).unwrap();
+        r.id
+    }
+
+    #[test]
+    fn test_every_entity_lands_in_exactly_one_section() {
+        let mut storage = MemoryStorage::new("default");
+
+        let mut task = Task::new(
+            "Implement OAuth".to_string(),
+            "Login via provider".to_string(),
+            "default".to_string(),
+            TaskPriority::High,
+            None,
+        );
+        task.tags = vec!["auth".to_string()];
+        storage.store(&task.to_generic()).unwrap();
+
+        let mut ids = vec![
+            knowledge(&mut storage, "OAuth token lifetime", &["auth/oauth"]),
+            knowledge(&mut storage, "Session cookies", &["Auth:cookies"]),
+            knowledge(&mut storage, "Index hints", &["database"]),
+            knowledge(&mut storage, "Loose fact", &[]),
+            context(&mut storage, "Provider docs", &["auth"]),
+            context(&mut storage, "Schema notes", &["database", "auth"]),
+            context(&mut storage, "Meeting notes", &[]),
+            reasoning(&mut storage, "Why PKCE", &task.id),
+            reasoning(&mut storage, "Orphan decision", "missing-task"),
+        ];
+
+        // Untagged context linked to a database item through a relationship
+        let linked = context(&mut storage, "Query plan", &[]);
+        let rel = EntityRelationship::new(
+            "rel-1".to_string(),
+            "default".to_string(),
+            linked.clone(),
+            "context".to_string(),
+            ids[2].clone(),
+            "knowledge".to_string(),
+            EntityRelationType::References,
+        );
+        storage.store(&rel.to_generic()).unwrap();
+        ids.push(linked.clone());
+
+        let index = build_knowledge_base(&storage, None, "Project KB").unwrap();
+        assert_eq!(index.total_entities, 10);
+
+        for id in &ids {
+            let count: usize = index
+                .sections
+                .iter()
+                .map(|s| {
+                    s.knowledge_items
+                        .iter()
+                        .chain(&s.contexts)
+                        .chain(&s.reasoning_refs)
+                        .filter(|i| *i == id)
+                        .count()
+                })
+                .sum();
+            assert_eq!(count, 1, "{} appears {} times", id, count);
+        }
+
+        let tags: Vec<&str> = index.sections.iter().map(|s| s.tag.as_str()).collect();
+        assert_eq!(tags, vec!["auth", "database", UNTAGGED_SECTION]);
+        assert_eq!(index.section_of(&ids[7]).unwrap().tag, "auth");
+        assert_eq!(index.section_of(&linked).unwrap().tag, "database");
+        assert_eq!(index.section_of(&ids[3]).unwrap().tag, UNTAGGED_SECTION);
+    }
+
+    #[test]
+    fn test_agent_filter_and_search() {
+        let mut storage = MemoryStorage::new("default");
+        knowledge(&mut storage, "OAuth implementation notes", &["auth"]);
+        knowledge(&mut storage, "Connection pooling", &["database"]);
+        let mut other = Knowledge::new(
+            "OAuth elsewhere".to_string(),
+            "Not ours".to_string(),
+            KnowledgeType::Fact,
+            0.5,
+            "other".to_string(),
+        );
+        other.add_tag("auth".to_string());
+        storage.store(&other.to_generic()).unwrap();
+
+        let index = build_knowledge_base(&storage, Some("default"), "KB").unwrap();
+        assert_eq!(index.total_entities, 2);
+
+        let items = collect_kb_items(&storage, Some("default")).unwrap();
+        let hits = search_knowledge_base(&items, &index, "OAuth implementation");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].title, "OAuth implementation notes");
+        assert_eq!(hits[0].section, "auth");
+        assert!(search_knowledge_base(&items, &index, "   ").is_empty());
+    }
+}
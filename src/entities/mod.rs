@@ -12,21 +12,26 @@ pub mod doc_fragment;
 pub mod dora_metrics_report;
 pub mod escalation_request;
 pub mod execution_result;
+pub mod guide_suggestion;
 pub mod knowledge;
+pub mod knowledge_base;
 pub mod lesson;
 pub mod persona;
 pub mod progressive_config;
 pub mod reasoning;
 pub mod relationship;
 pub mod rule;
+pub mod search_history;
 pub mod session;
 pub mod stale_task_report;
 pub mod standard;
 pub mod state_reflection;
 pub mod task;
 pub mod task_duration_report;
+pub mod task_tree;
 pub mod theory;
 pub mod workflow;
+pub mod workflow_event;
 pub mod workflow_instance;
 
 // Re-export all entity types
@@ -39,21 +44,26 @@ pub use doc_fragment::*;
 pub use dora_metrics_report::*;
 pub use escalation_request::*;
 pub use execution_result::*;
+pub use guide_suggestion::*;
 pub use knowledge::*;
+pub use knowledge_base::*;
 pub use lesson::*;
 pub use persona::*;
 pub use progressive_config::*;
 pub use reasoning::*;
 pub use relationship::*;
 pub use rule::*;
+pub use search_history::*;
 pub use session::*;
 pub use stale_task_report::*;
 pub use standard::*;
 pub use state_reflection::*;
 pub use task::*;
 pub use task_duration_report::*;
+pub use task_tree::*;
 pub use theory::*;
 pub use workflow::*;
+pub use workflow_event::*;
 pub use workflow_instance::*;
 
 use serde::{Deserialize, Serialize};
@@ -147,7 +157,7 @@ pub trait Entity: Serialize + for<'de> Deserialize<'de> + Send + Sync {
 }
 
 /// Generic entity representation for dynamic handling
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GenericEntity {
     pub id: String,
     #[serde(alias = "type")]
@@ -167,6 +177,581 @@ impl GenericEntity {
             ))
         })
     }
+
+    /// Size in bytes of the entity serialized as compact JSON, roughly what
+    /// a storage backend writes for it
+    pub fn approximate_size(&self) -> usize {
+        serde_json::to_vec(self)
+            .map(|bytes| bytes.len())
+            .unwrap_or(0)
+    }
+
+    /// Who last stored this entity and how often it has been stored
+    pub fn modification(&self) -> Modification {
+        Modification {
+            last_modified_by: self
+                .data
+                .get(LAST_MODIFIED_BY_FIELD)
+                .and_then(|v| v.as_str())
+                .map(str::to_string),
+            modification_count: self
+                .data
+                .get(MODIFICATION_COUNT_FIELD)
+                .and_then(|v| v.as_u64())
+                .unwrap_or(0),
+            last_modified_at: self
+                .data
+                .get(LAST_MODIFIED_AT_FIELD)
+                .and_then(|v| v.as_str())
+                .and_then(|at| at.parse().ok()),
+        }
+    }
+
+    /// Replace the modification fields with `modification`; no-op for
+    /// entities whose data is not an object
+    pub fn set_modification(&mut self, modification: &Modification) {
+        let Some(data) = self.data.as_object_mut() else {
+            return;
+        };
+        match &modification.last_modified_by {
+            Some(agent) => data.insert(
+                LAST_MODIFIED_BY_FIELD.to_string(),
+                serde_json::Value::String(agent.clone()),
+            ),
+            None => data.remove(LAST_MODIFIED_BY_FIELD),
+        };
+        if modification.modification_count > 0 {
+            data.insert(
+                MODIFICATION_COUNT_FIELD.to_string(),
+                serde_json::Value::from(modification.modification_count),
+            );
+        } else {
+            data.remove(MODIFICATION_COUNT_FIELD);
+        }
+        match &modification.last_modified_at {
+            Some(at) => data.insert(
+                LAST_MODIFIED_AT_FIELD.to_string(),
+                serde_json::Value::String(at.to_rfc3339()),
+            ),
+            None => data.remove(LAST_MODIFIED_AT_FIELD),
+        };
+    }
+
+    /// When the entity's `status` field last changed, if it has changed
+    /// since status changes were tracked
+    pub fn status_changed_at(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.data
+            .get(STATUS_CHANGED_AT_FIELD)
+            .and_then(|v| v.as_str())
+            .and_then(|at| at.parse().ok())
+    }
+
+    /// Stamp [`STATUS_CHANGED_AT_FIELD`] for a store over `previous`: the
+    /// current time when the status differs from the stored one, otherwise
+    /// the stored stamp. A first store is left alone, since the entity has
+    /// had its status since it was created, as are entities without a
+    /// `status`.
+    fn record_status_change(&mut self, previous: Option<&GenericEntity>) {
+        let (Some(data), Some(previous)) = (self.data.as_object_mut(), previous) else {
+            return;
+        };
+        let Some(status) = data.get("status") else {
+            return;
+        };
+        let stamp = if previous.data.get("status") == Some(status) {
+            match previous.data.get(STATUS_CHANGED_AT_FIELD) {
+                Some(stored) => stored.clone(),
+                None => return,
+            }
+        } else {
+            serde_json::Value::String(chrono::Utc::now().to_rfc3339())
+        };
+        data.insert(STATUS_CHANGED_AT_FIELD.to_string(), stamp);
+    }
+
+    /// Stamp the modification fields for a store by `agent` over `previous`
+    ///
+    /// An entity already carrying a higher count than the stored one (copied
+    /// or synced from another workspace) keeps its metadata; otherwise the
+    /// count moves one past the stored count, `agent` becomes the last
+    /// modifier and the current time the last modification time. The status
+    /// change time is stamped either way. Entities whose data is not an
+    /// object are left alone.
+    pub fn record_modification(&mut self, agent: &str, previous: Option<&GenericEntity>) {
+        self.record_status_change(previous);
+        let stored = previous.map_or(0, |p| p.modification().modification_count);
+        if self.modification().modification_count > stored {
+            return;
+        }
+        self.set_modification(&Modification {
+            last_modified_by: Some(agent.to_string()),
+            modification_count: stored + 1,
+            last_modified_at: Some(chrono::Utc::now()),
+        });
+    }
+}
+
+/// Data field holding the agent that last stored an entity
+pub const LAST_MODIFIED_BY_FIELD: &str = "last_modified_by";
+
+/// Data field holding how many times an entity has been stored
+pub const MODIFICATION_COUNT_FIELD: &str = "modification_count";
+
+/// Data field holding when an entity was last stored, as RFC 3339
+pub const LAST_MODIFIED_AT_FIELD: &str = "last_modified_at";
+
+/// Data field holding when an entity's `status` last changed, as RFC 3339
+pub const STATUS_CHANGED_AT_FIELD: &str = "status_changed_at";
+
+/// Data field holding the git identity (`Name <email>`) that first stored
+/// an entity, where the backend knows one
+pub const CREATED_BY_IDENTITY_FIELD: &str = "created_by_identity";
+
+/// Modification metadata maintained by the storage layer on every store
+///
+/// `agent` on an entity is its creator; these record later writers too.
+/// Entities stored before the fields existed read as never modified.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Modification {
+    #[serde(default)]
+    pub last_modified_by: Option<String>,
+    #[serde(default)]
+    pub modification_count: u64,
+    #[serde(default)]
+    pub last_modified_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl Modification {
+    /// Whether `self` is more recent than `other`: the higher count wins
+    pub fn is_later_than(&self, other: &Modification) -> bool {
+        self.modification_count > other.modification_count
+    }
+
+    /// One-line summary for `show` output, if the entity has been stored
+    /// since modification tracking was added
+    pub fn summary(&self) -> Option<String> {
+        let agent = self.last_modified_by.as_deref()?;
+        Some(format!(
+            "{} ({} modification{})",
+            agent,
+            self.modification_count,
+            if self.modification_count == 1 {
+                ""
+            } else {
+                "s"
+            }
+        ))
+    }
+}
+
+/// An old and a new value of one field
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OldNew<T> {
+    pub old: T,
+    pub new: T,
+}
+
+/// Items added to and removed from a list field, compared as sets
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ListChange {
+    pub added: Vec<serde_json::Value>,
+    pub removed: Vec<serde_json::Value>,
+}
+
+impl ListChange {
+    pub fn between(old: &[serde_json::Value], new: &[serde_json::Value]) -> Self {
+        Self {
+            added: new.iter().filter(|v| !old.contains(v)).cloned().collect(),
+            removed: old.iter().filter(|v| !new.contains(v)).cloned().collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Field-by-field differences between the data of two entities
+///
+/// Nested objects present on both sides are compared one level deep and
+/// reported as `parent.child`; anything deeper is compared as a whole value.
+/// Lists on both sides (tags, related entities, ...) are compared as sets,
+/// so reordering a list is not a change.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct EntityDiff {
+    /// Fields only in the second entity
+    pub added_fields: Vec<(String, serde_json::Value)>,
+    /// Fields only in the first entity
+    pub removed_fields: Vec<(String, serde_json::Value)>,
+    pub modified_fields: Vec<(String, OldNew<serde_json::Value>)>,
+    pub list_changes: Vec<(String, ListChange)>,
+    pub unchanged_fields: Vec<String>,
+}
+
+/// Strings print without quotes; everything else as compact JSON
+pub fn display_diff_value(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+impl EntityDiff {
+    /// Whether the two entities have identical data
+    pub fn is_empty(&self) -> bool {
+        self.added_fields.is_empty()
+            && self.removed_fields.is_empty()
+            && self.modified_fields.is_empty()
+            && self.list_changes.is_empty()
+    }
+
+    /// One `@@ field @@` hunk per changed field with `-` old and `+` new
+    /// lines, list fields listing only the items added or removed. With
+    /// `color`, hunks are cyan, removals red and additions green.
+    pub fn render(&self, color: bool) -> String {
+        let paint = |code: &str, line: String| {
+            if color {
+                format!("\x1b[{}m{}\x1b[0m\n", code, line)
+            } else {
+                format!("{}\n", line)
+            }
+        };
+        let header = |field: &str| paint("36", format!("@@ {} @@", field));
+        let old =
+            |value: &serde_json::Value| paint("31", format!("- {}", display_diff_value(value)));
+        let new =
+            |value: &serde_json::Value| paint("32", format!("+ {}", display_diff_value(value)));
+
+        let mut out = String::new();
+        for (field, value) in &self.removed_fields {
+            out.push_str(&header(field));
+            out.push_str(&old(value));
+        }
+        for (field, change) in &self.modified_fields {
+            out.push_str(&header(field));
+            out.push_str(&old(&change.old));
+            out.push_str(&new(&change.new));
+        }
+        for (field, change) in &self.list_changes {
+            out.push_str(&header(field));
+            change
+                .removed
+                .iter()
+                .for_each(|value| out.push_str(&old(value)));
+            change
+                .added
+                .iter()
+                .for_each(|value| out.push_str(&new(value)));
+        }
+        for (field, value) in &self.added_fields {
+            out.push_str(&header(field));
+            out.push_str(&new(value));
+        }
+        out
+    }
+
+    fn compare(
+        &mut self,
+        prefix: &str,
+        a: &serde_json::Map<String, serde_json::Value>,
+        b: &serde_json::Map<String, serde_json::Value>,
+        depth: usize,
+    ) {
+        let keys: std::collections::BTreeSet<&String> = a.keys().chain(b.keys()).collect();
+        for key in keys {
+            let field = format!("{}{}", prefix, key);
+            match (a.get(key), b.get(key)) {
+                (Some(old), None) => self.removed_fields.push((field, old.clone())),
+                (None, Some(new)) => self.added_fields.push((field, new.clone())),
+                (Some(old), Some(new)) if old == new => self.unchanged_fields.push(field),
+                (Some(serde_json::Value::Object(old)), Some(serde_json::Value::Object(new)))
+                    if depth == 0 =>
+                {
+                    self.compare(&format!("{}.", field), old, new, depth + 1)
+                }
+                (Some(serde_json::Value::Array(old)), Some(serde_json::Value::Array(new))) => {
+                    let change = ListChange::between(old, new);
+                    if change.is_empty() {
+                        self.unchanged_fields.push(field);
+                    } else {
+                        self.list_changes.push((field, change));
+                    }
+                }
+                (Some(old), Some(new)) => self.modified_fields.push((
+                    field,
+                    OldNew {
+                        old: old.clone(),
+                        new: new.clone(),
+                    },
+                )),
+                (None, None) => {}
+            }
+        }
+    }
+}
+
+/// Compare the data of `a` (old) and `b` (new) field by field
+pub fn diff_entities(a: &GenericEntity, b: &GenericEntity) -> EntityDiff {
+    let empty = serde_json::Map::new();
+    let mut diff = EntityDiff::default();
+    diff.compare(
+        "",
+        a.data.as_object().unwrap_or(&empty),
+        b.data.as_object().unwrap_or(&empty),
+        0,
+    );
+    diff
+}
+
+/// One RFC 6902 JSON Patch operation
+///
+/// Paths are JSON Pointers (RFC 6901) into the serialized [`GenericEntity`],
+/// e.g. `/data/status` or `/agent`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum JsonPatchOp {
+    Add {
+        path: String,
+        value: serde_json::Value,
+    },
+    Remove {
+        path: String,
+    },
+    Replace {
+        path: String,
+        value: serde_json::Value,
+    },
+    Move {
+        from: String,
+        path: String,
+    },
+    Copy {
+        from: String,
+        path: String,
+    },
+    Test {
+        path: String,
+        value: serde_json::Value,
+    },
+}
+
+/// Operations that turn `a` into `b`
+///
+/// Objects are compared key by key and arrays of equal length element by
+/// element; arrays whose length changed are replaced whole. A value removed
+/// in one place and added unchanged in another becomes a single `move`.
+pub fn diff_to_patch(a: &GenericEntity, b: &GenericEntity) -> Vec<JsonPatchOp> {
+    let a = serde_json::to_value(a).unwrap_or_default();
+    let b = serde_json::to_value(b).unwrap_or_default();
+    let mut ops = Vec::new();
+    diff_values("", &a, &b, &mut ops);
+    collapse_moves(ops)
+}
+
+/// Append the operations turning `a` into `b`, keeping the old value of
+/// every removal so moves can be recognised afterwards
+fn diff_values<'a>(
+    path: &str,
+    a: &'a serde_json::Value,
+    b: &serde_json::Value,
+    ops: &mut Vec<(JsonPatchOp, Option<&'a serde_json::Value>)>,
+) {
+    use serde_json::Value;
+
+    match (a, b) {
+        _ if a == b => {}
+        (Value::Object(old), Value::Object(new)) => {
+            for (key, old_value) in old {
+                let child = format!("{}/{}", path, escape_pointer(key));
+                match new.get(key) {
+                    Some(new_value) => diff_values(&child, old_value, new_value, ops),
+                    None => ops.push((JsonPatchOp::Remove { path: child }, Some(old_value))),
+                }
+            }
+            for (key, new_value) in new {
+                if !old.contains_key(key) {
+                    let path = format!("{}/{}", path, escape_pointer(key));
+                    let value = new_value.clone();
+                    ops.push((JsonPatchOp::Add { path, value }, None));
+                }
+            }
+        }
+        (Value::Array(old), Value::Array(new)) if old.len() == new.len() => {
+            for (i, (old_value, new_value)) in old.iter().zip(new).enumerate() {
+                diff_values(&format!("{}/{}", path, i), old_value, new_value, ops);
+            }
+        }
+        _ => {
+            let path = path.to_string();
+            ops.push((
+                JsonPatchOp::Replace {
+                    path,
+                    value: b.clone(),
+                },
+                None,
+            ));
+        }
+    }
+}
+
+/// Turn each `add` whose value was removed elsewhere into a `move`
+///
+/// [`diff_values`] never removes array elements, so no path shifts: the
+/// `move` can take the place of the `add` and the `remove` is dropped.
+fn collapse_moves(ops: Vec<(JsonPatchOp, Option<&serde_json::Value>)>) -> Vec<JsonPatchOp> {
+    let mut moved_from = vec![false; ops.len()];
+    let mut moves = vec![None; ops.len()];
+    for (i, (op, _)) in ops.iter().enumerate() {
+        let JsonPatchOp::Add { path, value } = op else {
+            continue;
+        };
+        let source = ops.iter().enumerate().position(|(j, (other, old))| {
+            !moved_from[j] && matches!(other, JsonPatchOp::Remove { .. }) && *old == Some(value)
+        });
+        if let Some(j) = source {
+            if let JsonPatchOp::Remove { path: from } = &ops[j].0 {
+                moved_from[j] = true;
+                moves[i] = Some(JsonPatchOp::Move {
+                    from: from.clone(),
+                    path: path.clone(),
+                });
+            }
+        }
+    }
+
+    ops.into_iter()
+        .zip(moves)
+        .enumerate()
+        .filter(|(j, _)| !moved_from[*j])
+        .map(|(_, ((op, _), moved))| moved.unwrap_or(op))
+        .collect()
+}
+
+/// Apply `patch` to `entity`
+///
+/// Operations are applied in order to the serialized entity; if any of them
+/// fails, including a failed `test`, `entity` is left unchanged.
+pub fn apply_patch(entity: &mut GenericEntity, patch: &[JsonPatchOp]) -> crate::Result<()> {
+    let mut document = serde_json::to_value(&*entity)?;
+    for op in patch {
+        apply_op(&mut document, op)?;
+    }
+    *entity = serde_json::from_value(document).map_err(|e| {
+        crate::EngramError::Validation(format!("Patched entity is not valid: {}", e))
+    })?;
+    Ok(())
+}
+
+fn apply_op(document: &mut serde_json::Value, op: &JsonPatchOp) -> crate::Result<()> {
+    match op {
+        JsonPatchOp::Add { path, value } => add_value(document, path, value.clone()),
+        JsonPatchOp::Remove { path } => remove_value(document, path).map(|_| ()),
+        JsonPatchOp::Replace { path, value } => {
+            *pointer_mut(document, path)? = value.clone();
+            Ok(())
+        }
+        JsonPatchOp::Move { from, path } => {
+            if path.starts_with(&format!("{}/", from)) {
+                return Err(patch_error(path, "cannot move a value into itself"));
+            }
+            let value = remove_value(document, from)?;
+            add_value(document, path, value)
+        }
+        JsonPatchOp::Copy { from, path } => {
+            let value = document
+                .pointer(from)
+                .cloned()
+                .ok_or_else(|| patch_error(from, "path does not exist"))?;
+            add_value(document, path, value)
+        }
+        JsonPatchOp::Test { path, value } => match document.pointer(path) {
+            Some(actual) if actual == value => Ok(()),
+            Some(actual) => Err(patch_error(
+                path,
+                &format!("test failed: expected {}, found {}", value, actual),
+            )),
+            None => Err(patch_error(path, "path does not exist")),
+        },
+    }
+}
+
+fn patch_error(path: &str, reason: &str) -> crate::EngramError {
+    crate::EngramError::InvalidOperation(format!("Patch failed at '{}': {}", path, reason))
+}
+
+fn pointer_mut<'a>(
+    document: &'a mut serde_json::Value,
+    path: &str,
+) -> crate::Result<&'a mut serde_json::Value> {
+    document
+        .pointer_mut(path)
+        .ok_or_else(|| patch_error(path, "path does not exist"))
+}
+
+/// Split a pointer into its parent pointer and unescaped last token
+fn split_pointer(path: &str) -> crate::Result<(&str, String)> {
+    match path.rfind('/') {
+        Some(i) => Ok((&path[..i], unescape_pointer(&path[i + 1..]))),
+        None => Err(patch_error(path, "path must start with '/'")),
+    }
+}
+
+fn add_value(
+    document: &mut serde_json::Value,
+    path: &str,
+    value: serde_json::Value,
+) -> crate::Result<()> {
+    if path.is_empty() {
+        *document = value;
+        return Ok(());
+    }
+    let (parent, token) = split_pointer(path)?;
+    match pointer_mut(document, parent)? {
+        serde_json::Value::Object(map) => {
+            map.insert(token, value);
+            Ok(())
+        }
+        serde_json::Value::Array(items) => {
+            let index = if token == "-" {
+                items.len()
+            } else {
+                array_index(path, &token, items.len() + 1)?
+            };
+            items.insert(index, value);
+            Ok(())
+        }
+        _ => Err(patch_error(path, "parent is not an object or array")),
+    }
+}
+
+fn remove_value(document: &mut serde_json::Value, path: &str) -> crate::Result<serde_json::Value> {
+    let (parent, token) = split_pointer(path)?;
+    match pointer_mut(document, parent)? {
+        serde_json::Value::Object(map) => map
+            .remove(&token)
+            .ok_or_else(|| patch_error(path, "path does not exist")),
+        serde_json::Value::Array(items) => {
+            let index = array_index(path, &token, items.len())?;
+            Ok(items.remove(index))
+        }
+        _ => Err(patch_error(path, "parent is not an object or array")),
+    }
+}
+
+/// Parse an array index token, which must be below `bound`
+fn array_index(path: &str, token: &str, bound: usize) -> crate::Result<usize> {
+    match token.parse::<usize>() {
+        Ok(index) if index < bound && (token == "0" || !token.starts_with('0')) => Ok(index),
+        _ => Err(patch_error(path, "invalid array index")),
+    }
+}
+
+fn escape_pointer(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+fn unescape_pointer(token: &str) -> String {
+    token.replace("~1", "/").replace("~0", "~")
 }
 
 #[cfg(test)]
@@ -188,22 +773,360 @@ mod tests {
             serde_json::from_str(json).expect("Should deserialize with 'entity_type' field");
         assert_eq!(entity.entity_type, "task");
     }
+
+    fn generic(entity_type: &str, data: serde_json::Value) -> GenericEntity {
+        GenericEntity {
+            id: "invalid-1".to_string(),
+            entity_type: entity_type.to_string(),
+            agent: "test-agent".to_string(),
+            timestamp: chrono::Utc::now(),
+            data,
+        }
+    }
+
+    fn assert_rejected(registry: &EntityRegistry, entity: &GenericEntity, field: &str) {
+        match registry.validate(entity) {
+            Err(crate::EngramError::Validation(message)) => {
+                assert!(
+                    message.starts_with(&format!("Invalid {} 'invalid-1'", entity.entity_type)),
+                    "{}",
+                    message
+                );
+                assert!(message.contains(field), "{}", message);
+            }
+            other => panic!("{} should be rejected, got {:?}", entity.entity_type, other),
+        }
+    }
+
+    #[test]
+    fn test_registry_rejects_invalid_payload_for_every_builtin_type() {
+        let registry = EntityRegistry::with_builtin_types();
+        let cases = [
+            ("task", "title"),
+            ("context", "content"),
+            ("reasoning", "task_id"),
+            ("knowledge", "content"),
+            ("session", "title"),
+            ("compliance", "category"),
+            ("relationship", "source_id"),
+            ("theory", "domain_name"),
+            ("state_reflection", "observed_state"),
+            ("rule", "title"),
+            ("standard", "title"),
+            ("adr", "title"),
+            ("workflow", "title"),
+            ("workflow_instance", "workflow_id"),
+            ("workflow_event", "instance_id"),
+            ("agent_sandbox", "agent_id"),
+            ("escalation_request", "agent_id"),
+            ("execution_result", "command"),
+            ("progressive_gate_config", "name"),
+            ("doc_fragment", "topic"),
+            ("lesson", "mistake"),
+            ("persona", "slug"),
+            ("bottleneck_report", "project_path"),
+            ("dora_metrics_report", "project_path"),
+            ("task_duration_report", "project_path"),
+            ("flakiness_blacklist", "gate_name"),
+            ("recurring_task", "base_task_id"),
+            ("search_history", "term"),
+            ("guide_suggestion", "key"),
+        ];
+
+        let mut types = registry.list_types();
+        types.sort_unstable();
+        let mut covered: Vec<&str> = cases.iter().map(|(t, _)| *t).collect();
+        covered.sort_unstable();
+        assert_eq!(types, covered, "every registered type needs a case");
+
+        for (entity_type, field) in cases {
+            let entity = generic(entity_type, serde_json::json!({ field: 42 }));
+            assert_rejected(&registry, &entity, field);
+        }
+    }
+
+    #[test]
+    fn test_diff_entities_categorises_fields() {
+        let mut low = Task::new(
+            "Write docs".to_string(),
+            "Describe the API".to_string(),
+            "test-agent".to_string(),
+            TaskPriority::Low,
+            None,
+        );
+        low.metadata
+            .insert("estimate".to_string(), serde_json::json!({ "hours": 2 }));
+        let mut high = low.clone();
+        high.priority = TaskPriority::High;
+        high.metadata
+            .insert("estimate".to_string(), serde_json::json!({ "hours": 5 }));
+        high.metadata
+            .insert("owner".to_string(), serde_json::json!("bob"));
+
+        let mut a = low.to_generic();
+        a.data.as_object_mut().unwrap().remove("title");
+        let b = high.to_generic();
+
+        let diff = diff_entities(&a, &b);
+        assert_eq!(
+            diff.added_fields,
+            vec![
+                ("metadata.owner".to_string(), serde_json::json!("bob")),
+                ("title".to_string(), serde_json::json!("Write docs")),
+            ]
+        );
+        assert!(diff.removed_fields.is_empty());
+        assert_eq!(
+            diff.modified_fields,
+            vec![
+                (
+                    "metadata.estimate".to_string(),
+                    OldNew {
+                        old: serde_json::json!({ "hours": 2 }),
+                        new: serde_json::json!({ "hours": 5 }),
+                    }
+                ),
+                (
+                    "priority".to_string(),
+                    OldNew {
+                        old: serde_json::json!("low"),
+                        new: serde_json::json!("high"),
+                    }
+                ),
+            ]
+        );
+        assert!(diff.unchanged_fields.contains(&"description".to_string()));
+        assert!(diff.unchanged_fields.contains(&"id".to_string()));
+        assert!(!diff.is_empty());
+
+        let reverse = diff_entities(&b, &a);
+        assert_eq!(reverse.removed_fields.len(), 2);
+        assert!(diff_entities(&b, &b).is_empty());
+    }
+
+    #[test]
+    fn test_diff_lists_as_sets_and_render() {
+        let entity = |tags: serde_json::Value| GenericEntity {
+            id: "t1".to_string(),
+            entity_type: "task".to_string(),
+            agent: "test-agent".to_string(),
+            timestamp: chrono::Utc::now(),
+            data: serde_json::json!({ "tags": tags, "status": "todo" }),
+        };
+        let a = entity(serde_json::json!(["api", "backend", "stale"]));
+        let reordered = entity(serde_json::json!(["stale", "api", "backend"]));
+        assert!(diff_entities(&a, &reordered).is_empty());
+
+        let mut b = entity(serde_json::json!(["backend", "api", "urgent"]));
+        b.data["status"] = serde_json::json!("done");
+        let diff = diff_entities(&a, &b);
+        assert_eq!(
+            diff.list_changes,
+            vec![(
+                "tags".to_string(),
+                ListChange {
+                    added: vec![serde_json::json!("urgent")],
+                    removed: vec![serde_json::json!("stale")],
+                }
+            )]
+        );
+        assert_eq!(
+            diff.render(false),
+            "@@ status @@\n- todo\n+ done\n@@ tags @@\n- stale\n+ urgent\n"
+        );
+        assert!(diff.render(true).contains("\x1b[32m+ urgent\x1b[0m"));
+    }
+
+    #[test]
+    fn test_registry_runs_validate_entity_after_deserializing() {
+        let registry = EntityRegistry::with_builtin_types();
+
+        let mut task = Task::new(
+            "Valid".to_string(),
+            "Description".to_string(),
+            "test-agent".to_string(),
+            TaskPriority::Low,
+            None,
+        );
+        assert!(registry.validate(&task.to_generic()).unwrap());
+
+        task.title.clear();
+        let mut entity = task.to_generic();
+        entity.id = "invalid-1".to_string();
+        assert_rejected(&registry, &entity, "title cannot be empty");
+
+        entity.data["title"] = serde_json::json!("Valid");
+        entity.data["status"] = serde_json::json!("pending");
+        assert_rejected(&registry, &entity, "status: unknown variant `pending`");
+
+        let mut knowledge = Knowledge::new(
+            "Fact".to_string(),
+            "Content".to_string(),
+            KnowledgeType::Fact,
+            0.5,
+            "test-agent".to_string(),
+        );
+        knowledge.confidence = 1.5;
+        let mut entity = knowledge.to_generic();
+        entity.id = "invalid-1".to_string();
+        assert_rejected(&registry, &entity, "Confidence must be between 0.0 and 1.0");
+    }
+
+    #[test]
+    fn test_registry_skips_unregistered_types() {
+        let registry = EntityRegistry::with_builtin_types();
+        let entity = generic("note", serde_json::json!({"anything": true}));
+        assert!(!registry.is_registered("note"));
+        assert!(!registry.validate(&entity).unwrap());
+    }
+
+    #[test]
+    fn test_diff_to_patch_round_trips_task() {
+        let original = Task::new(
+            "Write docs".to_string(),
+            "Describe the API".to_string(),
+            "test-agent".to_string(),
+            TaskPriority::Low,
+            None,
+        );
+        let mut updated = original.clone();
+        updated.status = TaskStatus::Done;
+        updated.tags = vec!["docs".to_string()];
+        updated.outcome = Some("Published".to_string());
+        updated.metadata.insert(
+            "estimate".to_string(),
+            serde_json::json!({ "hours": 2, "unit": "h/~" }),
+        );
+
+        let a = original.to_generic();
+        let mut b = updated.to_generic();
+        b.agent = "reviewer".to_string();
+
+        let patch = diff_to_patch(&a, &b);
+        assert!(patch.contains(&JsonPatchOp::Replace {
+            path: "/data/status".to_string(),
+            value: serde_json::json!("done"),
+        }));
+        assert!(patch.contains(&JsonPatchOp::Replace {
+            path: "/agent".to_string(),
+            value: serde_json::json!("reviewer"),
+        }));
+
+        let mut patched = a.clone();
+        apply_patch(&mut patched, &patch).unwrap();
+        assert_eq!(patched, b);
+        assert!(diff_to_patch(&b, &b).is_empty());
+    }
+
+    #[test]
+    fn test_diff_to_patch_detects_moves() {
+        let mut a = generic(
+            "note",
+            serde_json::json!({ "draft": { "body": "text" }, "n": 1 }),
+        );
+        let mut b = a.clone();
+        b.data = serde_json::json!({ "final": { "body": "text" }, "n": 1 });
+
+        let patch = diff_to_patch(&a, &b);
+        assert_eq!(
+            patch,
+            vec![JsonPatchOp::Move {
+                from: "/data/draft".to_string(),
+                path: "/data/final".to_string(),
+            }]
+        );
+        apply_patch(&mut a, &patch).unwrap();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_apply_patch_failure_leaves_entity_unchanged() {
+        let mut entity = generic("note", serde_json::json!({ "tags": ["a"], "n": 1 }));
+        let before = entity.clone();
+
+        let patch: Vec<JsonPatchOp> = serde_json::from_str(
+            r#"[
+                {"op": "add", "path": "/data/tags/-", "value": "b"},
+                {"op": "test", "path": "/data/n", "value": 2}
+            ]"#,
+        )
+        .unwrap();
+        assert!(apply_patch(&mut entity, &patch).is_err());
+        assert_eq!(entity, before);
+
+        let missing = [JsonPatchOp::Remove {
+            path: "/data/missing".to_string(),
+        }];
+        assert!(apply_patch(&mut entity, &missing).is_err());
+
+        let ops: Vec<JsonPatchOp> = serde_json::from_str(
+            r#"[
+                {"op": "add", "path": "/data/tags/0", "value": "first"},
+                {"op": "copy", "from": "/data/n", "path": "/data/m"},
+                {"op": "test", "path": "/data/tags", "value": ["first", "a"]}
+            ]"#,
+        )
+        .unwrap();
+        apply_patch(&mut entity, &ops).unwrap();
+        assert_eq!(
+            entity.data,
+            serde_json::json!({ "tags": ["first", "a"], "n": 1, "m": 1 })
+        );
+    }
 }
 
 /// Registry for entity types
 pub struct EntityRegistry {
     entities: HashMap<String, EntityFactory>,
+    validators: HashMap<String, EntityValidator>,
 }
 
 type EntityFactory = Box<dyn Fn(GenericEntity) -> crate::Result<GenericEntity> + Send + Sync>;
+type EntityValidator = Box<dyn Fn(GenericEntity) -> crate::Result<()> + Send + Sync>;
 
 impl EntityRegistry {
     pub fn new() -> Self {
         Self {
             entities: HashMap::new(),
+            validators: HashMap::new(),
         }
     }
 
+    /// Registry with every built-in entity type registered
+    pub fn with_builtin_types() -> Self {
+        let mut registry = Self::new();
+        registry.register::<Task>();
+        registry.register::<RecurringTaskConfig>();
+        registry.register::<Context>();
+        registry.register::<Reasoning>();
+        registry.register::<Knowledge>();
+        registry.register::<Session>();
+        registry.register::<Compliance>();
+        registry.register::<EntityRelationship>();
+        registry.register::<Theory>();
+        registry.register::<StateReflection>();
+        registry.register::<Rule>();
+        registry.register::<Standard>();
+        registry.register::<ADR>();
+        registry.register::<Workflow>();
+        registry.register::<WorkflowInstance>();
+        registry.register::<WorkflowEvent>();
+        registry.register::<AgentSandbox>();
+        registry.register::<EscalationRequest>();
+        registry.register::<ExecutionResult>();
+        registry.register::<ProgressiveGateConfig>();
+        registry.register::<DocFragment>();
+        registry.register::<Lesson>();
+        registry.register::<Persona>();
+        registry.register::<BottleneckReport>();
+        registry.register::<DoraMetricsReport>();
+        registry.register::<TaskDurationReport>();
+        registry.register::<SearchHistory>();
+        registry.register::<GuideSuggestion>();
+        registry.register::<crate::validation::FlakinessBlacklistEntry>();
+        registry
+    }
+
     pub fn register<T>(&mut self)
     where
         T: Entity + 'static + for<'de> Deserialize<'de> + Serialize,
@@ -211,7 +1134,21 @@ impl EntityRegistry {
         let factory = Box::new(|entity: GenericEntity| -> crate::Result<GenericEntity> {
             T::from_generic(entity.clone()).map(|t| t.to_generic())
         });
+        // Deserializes like `from_generic`, but keeps the path to the failing field
+        let validator = Box::new(|entity: GenericEntity| -> crate::Result<()> {
+            let typed: T = serde_path_to_error::deserialize(entity.data).map_err(|e| {
+                let path = e.path().to_string();
+                crate::EngramError::Validation(if path == "." {
+                    e.inner().to_string()
+                } else {
+                    format!("{}: {}", path, e.inner())
+                })
+            })?;
+            typed.validate_entity()
+        });
         self.entities.insert(T::entity_type().to_string(), factory);
+        self.validators
+            .insert(T::entity_type().to_string(), validator);
     }
 
     pub fn create(&self, entity: GenericEntity) -> crate::Result<GenericEntity> {
@@ -221,6 +1158,33 @@ impl EntityRegistry {
         factory(entity)
     }
 
+    pub fn is_registered(&self, entity_type: &str) -> bool {
+        self.entities.contains_key(entity_type)
+    }
+
+    /// Check `entity` against its registered type before it is persisted
+    ///
+    /// Returns `Ok(false)` without checking anything when the type is not
+    /// registered. Failures are reported as `EngramError::Validation` naming
+    /// the entity and the offending field.
+    pub fn validate(&self, entity: &GenericEntity) -> crate::Result<bool> {
+        let Some(validator) = self.validators.get(&entity.entity_type) else {
+            return Ok(false);
+        };
+
+        validator(entity.clone()).map_err(|e| {
+            let detail = match e {
+                crate::EngramError::Validation(message) => message,
+                other => other.to_string(),
+            };
+            crate::EngramError::Validation(format!(
+                "Invalid {} '{}': {}",
+                entity.entity_type, entity.id, detail
+            ))
+        })?;
+        Ok(true)
+    }
+
     pub fn list_types(&self) -> Vec<&str> {
         self.entities.keys().map(|k| k.as_str()).collect()
     }
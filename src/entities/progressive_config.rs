@@ -24,8 +24,8 @@ pub struct GateLevel {
     #[validate(length(min = 1))]
     pub name: String,
     pub threshold: ChangeThreshold,
-    pub required_gates: Vec<QualityGate>,
-    pub optional_gates: Vec<QualityGate>,
+    pub required_gates: Vec<GateDefinition>,
+    pub optional_gates: Vec<GateDefinition>,
     #[serde(with = "duration_serde")]
     pub max_execution_time: Duration,
     pub parallelization: ParallelizationStrategy,
@@ -44,8 +44,10 @@ pub struct ChangeThreshold {
     pub file_patterns: Vec<FilePattern>,
 }
 
+/// Serialized gate definition within a [`GateLevel`]. Converted into a
+/// runnable `validation::quality_gates::QualityGate` for execution.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct QualityGate {
+pub struct GateDefinition {
     pub name: String,
     pub command: String,
     #[serde(with = "duration_serde")]
@@ -56,6 +58,12 @@ pub struct QualityGate {
     pub retry_policy: RetryPolicy,
 }
 
+#[deprecated(
+    since = "0.6.4",
+    note = "renamed to `GateDefinition`; runnable gates live in `validation::quality_gates::QualityGate`"
+)]
+pub type QualityGate = GateDefinition;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ParallelizationStrategy {
     Sequential,
@@ -1,6 +1,6 @@
 //! Reasoning chain entity implementation
 
-use super::{Entity, GenericEntity};
+use super::{Alternative, Entity, GenericEntity};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -58,6 +58,14 @@ pub struct Reasoning {
     #[serde(rename = "conclusion")]
     pub conclusion: String,
 
+    /// Options considered and rejected on the way to the conclusion
+    #[serde(
+        rename = "alternatives",
+        skip_serializing_if = "Vec::is_empty",
+        default
+    )]
+    pub alternatives: Vec<Alternative>,
+
     /// Overall confidence
     #[serde(rename = "confidence")]
     pub confidence: f64,
@@ -105,6 +113,7 @@ impl Reasoning {
             task_id,
             steps: Vec::new(),
             conclusion: String::new(),
+            alternatives: Vec::new(),
             confidence: 0.0,
             agent,
             created_at: now,
@@ -136,6 +145,25 @@ impl Reasoning {
         }
     }
 
+    /// Record an alternative that was considered, returning its ID
+    pub fn add_alternative(
+        &mut self,
+        description: String,
+        pros: Vec<String>,
+        cons: Vec<String>,
+        rejection_reason: Option<String>,
+    ) -> String {
+        let id = Uuid::new_v4().to_string();
+        self.alternatives.push(Alternative {
+            id: id.clone(),
+            description,
+            pros,
+            cons,
+            rejection_reason,
+        });
+        id
+    }
+
     /// Set final conclusion
     pub fn set_conclusion(&mut self, conclusion: String, confidence: f64) {
         self.conclusion = conclusion;
@@ -285,4 +313,24 @@ mod tests {
         reasoning.task_id = "task-1".to_string();
         assert!(reasoning.validate_entity().is_ok());
     }
+
+    #[test]
+    fn test_reasoning_alternatives_round_trip() {
+        let mut reasoning = Reasoning::new(
+            "Pick a queue".to_string(),
+            "task-1".to_string(),
+            "agent".to_string(),
+        );
+        let id = reasoning.add_alternative(
+            "Kafka".to_string(),
+            vec!["durable".to_string()],
+            vec!["heavy".to_string()],
+            Some("overkill for one consumer".to_string()),
+        );
+
+        let restored = Reasoning::from_generic(reasoning.to_generic()).unwrap();
+        assert_eq!(restored.alternatives.len(), 1);
+        assert_eq!(restored.alternatives[0].id, id);
+        assert_eq!(restored.alternatives[0].cons, vec!["heavy".to_string()]);
+    }
 }
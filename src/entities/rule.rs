@@ -52,8 +52,8 @@ pub enum RuleStatus {
     Deprecated,
 }
 
-/// Rule priority levels
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+/// Rule priority levels, ordered lowest to highest
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 #[serde(rename_all = "lowercase")]
 pub enum RulePriority {
     Low,
@@ -70,6 +70,8 @@ pub enum RuleType {
     Transformation,
     Enforcement,
     Notification,
+    /// Requirement checked by `engram compliance check`
+    Compliance,
 }
 
 /// Rule entity for system rules and policies
@@ -143,6 +145,15 @@ pub struct Rule {
     )]
     pub related_rules: Vec<String>,
 
+    /// Rules sharing a group are mutually exclusive: when several match an
+    /// entity, only the first in priority order fires
+    #[serde(
+        rename = "exclusive_group",
+        skip_serializing_if = "Option::is_none",
+        default
+    )]
+    pub exclusive_group: Option<String>,
+
     /// Additional metadata
     #[serde(
         rename = "metadata",
@@ -217,6 +228,7 @@ impl Rule {
             execution_history: Vec::new(),
             tags: Vec::new(),
             related_rules: Vec::new(),
+            exclusive_group: None,
             metadata: HashMap::new(),
         }
     }
@@ -0,0 +1,99 @@
+//! Search history entity implementation
+//!
+//! Each `engram task list --search` term is kept per agent with a use count,
+//! so frequently repeated searches can be offered as suggestions.
+
+use super::{Entity, GenericEntity};
+use crate::error::EngramError;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A search term an agent has used, with how often and when it was last used
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchHistory {
+    /// Unique identifier
+    #[serde(rename = "id")]
+    pub id: String,
+
+    /// Normalized (trimmed, lowercase) search term
+    #[serde(rename = "term")]
+    pub term: String,
+
+    /// Number of searches using this term
+    #[serde(rename = "count")]
+    pub count: u64,
+
+    /// When the term was last searched
+    #[serde(rename = "last_used")]
+    pub last_used: DateTime<Utc>,
+
+    /// Agent that searched
+    #[serde(rename = "agent")]
+    pub agent: String,
+}
+
+impl SearchHistory {
+    pub fn new(term: String, agent: String) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            term,
+            count: 1,
+            last_used: Utc::now(),
+            agent,
+        }
+    }
+
+    /// Count another use of the term
+    pub fn record_use(&mut self) {
+        self.count += 1;
+        self.last_used = Utc::now();
+    }
+}
+
+impl Entity for SearchHistory {
+    fn entity_type() -> &'static str {
+        "search_history"
+    }
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn agent(&self) -> &str {
+        &self.agent
+    }
+
+    fn timestamp(&self) -> DateTime<Utc> {
+        self.last_used
+    }
+
+    fn validate_entity(&self) -> crate::Result<()> {
+        if self.term.trim().is_empty() {
+            return Err(EngramError::Validation(
+                "Search history term cannot be empty".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn to_generic(&self) -> GenericEntity {
+        GenericEntity {
+            id: self.id.clone(),
+            entity_type: Self::entity_type().to_string(),
+            agent: self.agent.clone(),
+            timestamp: self.last_used,
+            data: serde_json::to_value(self).expect("SearchHistory serialization should not fail"),
+        }
+    }
+
+    fn from_generic(entity: GenericEntity) -> crate::Result<Self> {
+        serde_json::from_value(entity.data).map_err(|e| {
+            EngramError::Deserialization(format!("Failed to deserialize search history: {}", e))
+        })
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
@@ -1,6 +1,7 @@
 //! Session entity implementation
 
 use super::{Entity, GenericEntity};
+use crate::config::FatigueThresholds;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -51,6 +52,14 @@ pub struct Session {
     #[serde(rename = "duration_seconds")]
     pub duration_seconds: Option<u64>,
 
+    /// When the current pause began, while paused
+    #[serde(rename = "paused_at", skip_serializing_if = "Option::is_none", default)]
+    pub paused_at: Option<DateTime<Utc>>,
+
+    /// Total seconds spent in completed pauses
+    #[serde(rename = "paused_seconds", skip_serializing_if = "is_zero", default)]
+    pub paused_seconds: u64,
+
     /// Tasks worked on during session
     #[serde(rename = "task_ids", skip_serializing_if = "Vec::is_empty", default)]
     pub task_ids: Vec<String>,
@@ -116,6 +125,91 @@ pub struct Session {
     pub metadata: HashMap<String, serde_json::Value>,
 }
 
+fn is_zero(value: &u64) -> bool {
+    *value == 0
+}
+
+/// How fatiguing a session has become
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FatigueLevel {
+    Low,
+    Moderate,
+    High,
+}
+
+impl std::fmt::Display for FatigueLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            FatigueLevel::Low => "low",
+            FatigueLevel::Moderate => "moderate",
+            FatigueLevel::High => "high",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Fatigue estimate for a session, from its active time and completed
+/// tasks measured against the agent's [`FatigueThresholds`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FatigueAnalysis {
+    pub session_id: String,
+    /// Time since the session started, excluding pauses
+    pub active_minutes: u64,
+    pub tasks_completed: usize,
+    pub optimal_session_minutes: u64,
+    /// Session load as a fraction of the optimal length
+    pub load_ratio: f64,
+    pub level: FatigueLevel,
+    pub recommendation: Option<String>,
+}
+
+impl FatigueAnalysis {
+    pub fn compute(
+        session: &Session,
+        tasks_completed: usize,
+        thresholds: &FatigueThresholds,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let active_minutes = session.active_seconds(now) / 60;
+        let load = active_minutes + tasks_completed as u64 * thresholds.minutes_per_completed_task;
+        let load_ratio = load as f64 / thresholds.optimal_session_minutes.max(1) as f64;
+        let level = if load_ratio >= thresholds.high_ratio {
+            FatigueLevel::High
+        } else if load_ratio >= thresholds.moderate_ratio {
+            FatigueLevel::Moderate
+        } else {
+            FatigueLevel::Low
+        };
+        let recommendation = match level {
+            FatigueLevel::High => Some(format!(
+                "Fatigue risk is high after {} active minutes and {} completed task(s) \
+                 (optimal session: {} minutes). End the session with \
+                 `engram session end --id {}`, or pick a low-complexity task.",
+                active_minutes, tasks_completed, thresholds.optimal_session_minutes, session.id
+            )),
+            FatigueLevel::Moderate => Some(format!(
+                "Session is past its optimal length of {} minutes; plan to wrap up soon.",
+                thresholds.optimal_session_minutes
+            )),
+            FatigueLevel::Low => None,
+        };
+        Self {
+            session_id: session.id.clone(),
+            active_minutes,
+            tasks_completed,
+            optimal_session_minutes: thresholds.optimal_session_minutes,
+            load_ratio,
+            level,
+            recommendation,
+        }
+    }
+
+    pub fn is_high_risk(&self) -> bool {
+        self.level == FatigueLevel::High
+    }
+}
+
 /// SPACE framework metrics
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
 pub struct SpaceMetrics {
@@ -176,6 +270,8 @@ impl Session {
             start_time: now,
             end_time: None,
             duration_seconds: None,
+            paused_at: None,
+            paused_seconds: 0,
             task_ids: Vec::new(),
             context_ids: Vec::new(),
             knowledge_ids: Vec::new(),
@@ -193,6 +289,7 @@ impl Session {
 
     /// Complete the session
     pub fn complete(&mut self, outcomes: Vec<String>) {
+        self.end_pause(Utc::now());
         self.status = SessionStatus::Completed;
         self.end_time = Some(Utc::now());
         self.outcomes = outcomes;
@@ -202,20 +299,46 @@ impl Session {
     /// Pause the session
     pub fn pause(&mut self) {
         self.status = SessionStatus::Paused;
+        if self.paused_at.is_none() {
+            self.paused_at = Some(Utc::now());
+        }
     }
 
     /// Resume the session
     pub fn resume(&mut self) {
         self.status = SessionStatus::Active;
+        self.end_pause(Utc::now());
     }
 
     /// Cancel the session
     pub fn cancel(&mut self) {
+        self.end_pause(Utc::now());
         self.status = SessionStatus::Cancelled;
         self.end_time = Some(Utc::now());
         self.calculate_duration();
     }
 
+    fn end_pause(&mut self, now: DateTime<Utc>) {
+        if let Some(paused_at) = self.paused_at.take() {
+            self.paused_seconds += now.signed_duration_since(paused_at).num_seconds().max(0) as u64;
+        }
+    }
+
+    /// Seconds the session has been worked on as of `now`: time since it
+    /// started, up to its end, less time spent paused
+    pub fn active_seconds(&self, now: DateTime<Utc>) -> u64 {
+        let until = self.end_time.unwrap_or(now);
+        let elapsed = until
+            .signed_duration_since(self.start_time)
+            .num_seconds()
+            .max(0) as u64;
+        let current_pause = self
+            .paused_at
+            .map(|paused_at| until.signed_duration_since(paused_at).num_seconds().max(0) as u64)
+            .unwrap_or(0);
+        elapsed.saturating_sub(self.paused_seconds + current_pause)
+    }
+
     /// Calculate duration in seconds
     fn calculate_duration(&mut self) {
         if let Some(end_time) = self.end_time {
@@ -413,6 +536,55 @@ mod tests {
         assert!(session.end_time.is_some());
     }
 
+    #[test]
+    fn test_active_seconds_excludes_pauses() {
+        let mut session = Session::new("Test".to_string(), "agent".to_string(), vec![]);
+        let now = Utc::now();
+        session.start_time = now - chrono::Duration::minutes(120);
+        session.paused_seconds = 30 * 60;
+        session.paused_at = Some(now - chrono::Duration::minutes(10));
+
+        assert_eq!(session.active_seconds(now), 80 * 60);
+
+        session.resume();
+        assert!(session.paused_at.is_none());
+        assert!(session.paused_seconds >= 40 * 60);
+    }
+
+    #[test]
+    fn test_fatigue_analysis_levels() {
+        let thresholds = FatigueThresholds::default();
+        let mut session = Session::new("Test".to_string(), "agent".to_string(), vec![]);
+        let now = Utc::now();
+
+        session.start_time = now - chrono::Duration::minutes(30);
+        let analysis = FatigueAnalysis::compute(&session, 1, &thresholds, now);
+        assert_eq!(analysis.active_minutes, 30);
+        assert_eq!(analysis.level, FatigueLevel::Low);
+        assert!(analysis.recommendation.is_none());
+
+        session.start_time = now - chrono::Duration::minutes(100);
+        let analysis = FatigueAnalysis::compute(&session, 0, &thresholds, now);
+        assert_eq!(analysis.level, FatigueLevel::Moderate);
+
+        let analysis = FatigueAnalysis::compute(&session, 4, &thresholds, now);
+        assert!(analysis.is_high_risk());
+        assert!(analysis
+            .recommendation
+            .unwrap()
+            .contains("engram session end"));
+
+        session.paused_seconds = 60 * 60;
+        let analysis = FatigueAnalysis::compute(&session, 4, &thresholds, now);
+        assert_eq!(analysis.level, FatigueLevel::Low);
+
+        let strict = FatigueThresholds {
+            optimal_session_minutes: 20,
+            ..FatigueThresholds::default()
+        };
+        assert!(FatigueAnalysis::compute(&session, 0, &strict, now).is_high_risk());
+    }
+
     #[test]
     fn test_metrics_collection() {
         let mut session = Session::new("Metrics Test".to_string(), "agent".to_string(), vec![]);
@@ -63,6 +63,18 @@ pub struct Task {
     #[serde(rename = "end_time")]
     pub end_time: Option<DateTime<Utc>>,
 
+    /// Planned completion time
+    #[serde(rename = "due_at", skip_serializing_if = "Option::is_none", default)]
+    pub due_at: Option<DateTime<Utc>>,
+
+    /// Estimated effort in seconds
+    #[serde(
+        rename = "estimate_seconds",
+        skip_serializing_if = "Option::is_none",
+        default
+    )]
+    pub estimate_seconds: Option<u64>,
+
     /// Parent task ID
     #[serde(rename = "parent", skip_serializing_if = "Option::is_none")]
     pub parent: Option<String>,
@@ -129,6 +141,8 @@ impl Task {
             agent,
             start_time: now,
             end_time: None,
+            due_at: None,
+            estimate_seconds: None,
             parent: None,
             children: Vec::new(),
             tags: Vec::new(),
@@ -239,6 +253,149 @@ impl Entity for Task {
     }
 }
 
+/// When a recurring task produces its next instance
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RecurrenceTrigger {
+    /// On a 5-field cron schedule, evaluated in UTC
+    OnSchedule(String),
+    /// When the current instance is marked done
+    OnCompletion,
+}
+
+/// Configuration for a task that repeats from a base task
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecurringTaskConfig {
+    /// Unique identifier
+    pub id: String,
+
+    /// Task copied for each new instance
+    pub base_task_id: String,
+
+    /// Human-readable schedule: the cron expression or `on-completion`
+    pub schedule: String,
+
+    /// What triggers a new instance
+    pub auto_create_on: RecurrenceTrigger,
+
+    /// Stop creating instances after this many
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub max_instances: Option<u32>,
+
+    /// Copy the base task's tags onto each instance
+    pub carry_over_labels: bool,
+
+    /// Agent that registered the recurrence
+    pub agent: String,
+
+    /// Creation timestamp
+    pub created_at: DateTime<Utc>,
+
+    /// Next time a scheduled instance is due
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub next_trigger: Option<DateTime<Utc>>,
+
+    /// Instances created so far, oldest first
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub instance_ids: Vec<String>,
+}
+
+impl RecurringTaskConfig {
+    /// Create a recurrence for `base_task_id`; scheduled triggers get their first due time
+    pub fn new(
+        base_task_id: String,
+        auto_create_on: RecurrenceTrigger,
+        max_instances: Option<u32>,
+        carry_over_labels: bool,
+        agent: String,
+    ) -> crate::Result<Self> {
+        let now = Utc::now();
+        let (schedule, next_trigger) = match &auto_create_on {
+            RecurrenceTrigger::OnSchedule(expression) => (
+                expression.clone(),
+                crate::engines::CronSchedule::parse(expression)?.next_after(now),
+            ),
+            RecurrenceTrigger::OnCompletion => ("on-completion".to_string(), None),
+        };
+        Ok(Self {
+            id: Uuid::new_v4().to_string(),
+            base_task_id,
+            schedule,
+            auto_create_on,
+            max_instances,
+            carry_over_labels,
+            agent,
+            created_at: now,
+            next_trigger,
+            instance_ids: Vec::new(),
+        })
+    }
+
+    /// True once `max_instances` instances exist
+    pub fn is_exhausted(&self) -> bool {
+        self.max_instances
+            .is_some_and(|max| self.instance_ids.len() >= max as usize)
+    }
+}
+
+impl Entity for RecurringTaskConfig {
+    fn entity_type() -> &'static str {
+        "recurring_task"
+    }
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn agent(&self) -> &str {
+        &self.agent
+    }
+
+    fn timestamp(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    fn validate_entity(&self) -> crate::Result<()> {
+        if self.base_task_id.is_empty() {
+            return Err(crate::EngramError::Validation(
+                "Recurring task base_task_id cannot be empty".to_string(),
+            ));
+        }
+
+        if let RecurrenceTrigger::OnSchedule(expression) = &self.auto_create_on {
+            crate::engines::CronSchedule::parse(expression)?;
+        }
+
+        Ok(())
+    }
+
+    fn to_generic(&self) -> GenericEntity {
+        GenericEntity {
+            id: self.id.clone(),
+            entity_type: Self::entity_type().to_string(),
+            agent: self.agent.clone(),
+            timestamp: self.created_at,
+            data: serde_json::to_value(self).unwrap_or_default(),
+        }
+    }
+
+    fn from_generic(entity: GenericEntity) -> crate::Result<Self> {
+        serde_json::from_value(entity.data).map_err(|e| {
+            crate::EngramError::Deserialization(format!(
+                "Failed to deserialize RecurringTaskConfig: {}",
+                e
+            ))
+        })
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any
+    where
+        Self: Sized,
+    {
+        self
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,7 +1,7 @@
 use super::{Entity, GenericEntity};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use uuid::Uuid;
 use validator::Validate;
 
@@ -44,6 +44,22 @@ pub struct TaskDurationReport {
     #[serde(rename = "max_duration_hours")]
     pub max_duration_hours: f64,
 
+    /// Estimate accuracy of completed, estimated tasks per agent
+    #[serde(
+        rename = "estimate_accuracy_by_agent",
+        skip_serializing_if = "BTreeMap::is_empty",
+        default
+    )]
+    pub estimate_accuracy_by_agent: BTreeMap<String, EstimateAccuracy>,
+
+    /// Estimate accuracy of completed, estimated tasks per priority
+    #[serde(
+        rename = "estimate_accuracy_by_priority",
+        skip_serializing_if = "BTreeMap::is_empty",
+        default
+    )]
+    pub estimate_accuracy_by_priority: BTreeMap<String, EstimateAccuracy>,
+
     #[serde(
         rename = "metadata",
         skip_serializing_if = "HashMap::is_empty",
@@ -74,6 +90,47 @@ pub struct TaskDurationEntry {
 
     #[serde(rename = "end_time")]
     pub end_time: Option<DateTime<Utc>>,
+
+    #[serde(
+        rename = "estimate_hours",
+        skip_serializing_if = "Option::is_none",
+        default
+    )]
+    pub estimate_hours: Option<f64>,
+}
+
+/// How completed tasks' actual durations compared to their estimates
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EstimateAccuracy {
+    /// Completed tasks with an estimate and a non-zero duration
+    pub tasks: u64,
+    /// Mean absolute percentage error of the estimates against actuals
+    pub mean_absolute_percentage_error: f64,
+    /// Tasks that took longer than estimated
+    pub under_estimated: u64,
+    /// Tasks that finished sooner than estimated
+    pub over_estimated: u64,
+    #[serde(skip)]
+    absolute_percentage_error_sum: f64,
+}
+
+impl EstimateAccuracy {
+    /// Add a task that was estimated at `estimate_secs` and took `actual_secs`
+    pub fn record(&mut self, estimate_secs: f64, actual_secs: f64) {
+        if actual_secs <= 0.0 {
+            return;
+        }
+        self.tasks += 1;
+        self.absolute_percentage_error_sum +=
+            (actual_secs - estimate_secs).abs() / actual_secs * 100.0;
+        self.mean_absolute_percentage_error =
+            self.absolute_percentage_error_sum / self.tasks as f64;
+        if actual_secs > estimate_secs {
+            self.under_estimated += 1;
+        } else if actual_secs < estimate_secs {
+            self.over_estimated += 1;
+        }
+    }
 }
 
 impl TaskDurationReport {
@@ -90,11 +147,13 @@ impl TaskDurationReport {
             mean_duration_hours: 0.0,
             min_duration_hours: 0.0,
             max_duration_hours: 0.0,
+            estimate_accuracy_by_agent: BTreeMap::new(),
+            estimate_accuracy_by_priority: BTreeMap::new(),
             metadata: HashMap::new(),
         }
     }
 
-    pub fn compute<S: crate::storage::Storage>(
+    pub fn compute<S: crate::storage::Storage + ?Sized>(
         storage: &S,
         repo_path: &std::path::Path,
         agent: &str,
@@ -130,11 +189,27 @@ impl TaskDurationReport {
                     duration_hours,
                     start_time: task.start_time,
                     end_time: task.end_time,
+                    estimate_hours: task.estimate_seconds.map(|secs| secs as f64 / 3600.0),
                 });
 
                 if status_str == "done" {
                     report.completed_tasks += 1;
                     durations.push(duration_hours);
+
+                    if let (Some(estimate), Some(_)) = (task.estimate_seconds, task.end_time) {
+                        let actual_secs = duration_hours * 3600.0;
+                        let priority = format!("{:?}", task.priority).to_lowercase();
+                        report
+                            .estimate_accuracy_by_agent
+                            .entry(task.agent.clone())
+                            .or_default()
+                            .record(estimate as f64, actual_secs);
+                        report
+                            .estimate_accuracy_by_priority
+                            .entry(priority)
+                            .or_default()
+                            .record(estimate as f64, actual_secs);
+                    }
                 }
             }
         }
@@ -349,6 +424,8 @@ mod tests {
             agent: "test-agent".to_string(),
             start_time: start,
             end_time: end,
+            due_at: None,
+            estimate_seconds: None,
             parent: None,
             children: vec![],
             context_ids: vec![],
@@ -393,6 +470,7 @@ mod tests {
             duration_hours: 2.5,
             start_time: Utc::now(),
             end_time: Some(Utc::now()),
+            estimate_hours: None,
         };
         let json = serde_json::to_string(&entry).unwrap();
         let restored: TaskDurationEntry = serde_json::from_str(&json).unwrap();
@@ -602,4 +680,46 @@ mod tests {
         assert_eq!(entry.status, "done");
         assert_eq!(entry.agent, "test-agent");
     }
+
+    #[test]
+    fn test_compute_estimate_accuracy() {
+        let base = Utc::now();
+        let mut quick = make_task(
+            "t1",
+            TaskStatus::Done,
+            base - chrono::Duration::hours(1),
+            Some(base),
+        );
+        quick.estimate_seconds = Some(2 * 3600);
+        let mut slow = make_task(
+            "t2",
+            TaskStatus::Done,
+            base - chrono::Duration::hours(4),
+            Some(base),
+        );
+        slow.estimate_seconds = Some(2 * 3600);
+        slow.priority = TaskPriority::High;
+        let mut open = make_task("t3", TaskStatus::InProgress, base, None);
+        open.estimate_seconds = Some(3600);
+        let unestimated = make_task(
+            "t4",
+            TaskStatus::Done,
+            base - chrono::Duration::hours(1),
+            Some(base),
+        );
+        let storage = MockStorage {
+            tasks: vec![quick, slow, open, unestimated],
+        };
+        let report =
+            TaskDurationReport::compute(&storage, std::path::Path::new("/repo"), "agent").unwrap();
+
+        let by_agent = &report.estimate_accuracy_by_agent["test-agent"];
+        assert_eq!(by_agent.tasks, 2);
+        assert_eq!(by_agent.over_estimated, 1);
+        assert_eq!(by_agent.under_estimated, 1);
+        // |1h - 2h| / 1h = 100%, |4h - 2h| / 4h = 50%
+        assert!((by_agent.mean_absolute_percentage_error - 75.0).abs() < 0.1);
+        assert_eq!(report.estimate_accuracy_by_priority["high"].tasks, 1);
+        assert_eq!(report.estimate_accuracy_by_priority["medium"].tasks, 1);
+    }
 }
@@ -0,0 +1,332 @@
+//! Task dependency graph and critical path analysis
+//!
+//! Dependencies are `depends_on` relationships between tasks: a task that
+//! depends on another cannot start until that one finishes.
+
+use super::{Entity, EntityRelationType, EntityRelationship, Task, TaskDurationReport};
+use crate::error::EngramError;
+use crate::storage::Storage;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Task metadata key holding the estimated duration in seconds
+pub const ESTIMATED_DURATION_METADATA_KEY: &str = "estimated_duration_seconds";
+
+/// Duration assumed for tasks with no estimate and no duration history
+pub const DEFAULT_TASK_DURATION_SECONDS: f64 = 3600.0;
+
+/// Longest dependency chain ending at a terminal task
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CriticalPathResult {
+    /// Task IDs from the first task to the terminal task
+    pub path: Vec<String>,
+    pub total_estimated_duration_seconds: f64,
+    /// Free float per task: how long it can slip without delaying a dependent
+    pub slack: HashMap<String, f64>,
+    /// Earliest start offset per task, in seconds from the project start
+    pub earliest_start: HashMap<String, f64>,
+    /// Duration used per task, in seconds
+    pub durations: HashMap<String, f64>,
+}
+
+impl CriticalPathResult {
+    pub fn is_critical(&self, task_id: &str) -> bool {
+        self.path.iter().any(|id| id == task_id)
+    }
+}
+
+/// Tasks and the `depends_on` edges between them
+#[derive(Debug, Clone, Default)]
+pub struct DependencyGraph {
+    tasks: HashMap<String, Task>,
+    /// task id -> ids of tasks it depends on
+    dependencies: HashMap<String, Vec<String>>,
+}
+
+impl DependencyGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load every task and active task-to-task `depends_on` relationship
+    pub fn load(storage: &dyn Storage) -> Result<Self, EngramError> {
+        let mut graph = Self::new();
+        for generic in storage.get_all(Task::entity_type())? {
+            if let Ok(task) = Task::from_generic(generic) {
+                graph.add_task(task);
+            }
+        }
+        for generic in storage.get_all(EntityRelationship::entity_type())? {
+            let Ok(rel) = serde_json::from_value::<EntityRelationship>(generic.data) else {
+                continue;
+            };
+            if rel.active
+                && rel.relationship_type == EntityRelationType::DependsOn
+                && rel.source_type == "task"
+                && rel.target_type == "task"
+            {
+                graph.add_dependency(&rel.source_id, &rel.target_id);
+            }
+        }
+        Ok(graph)
+    }
+
+    pub fn add_task(&mut self, task: Task) {
+        self.tasks.insert(task.id.clone(), task);
+    }
+
+    /// Record that `task_id` cannot start before `depends_on_id` finishes
+    pub fn add_dependency(&mut self, task_id: &str, depends_on_id: &str) {
+        let deps = self.dependencies.entry(task_id.to_string()).or_default();
+        if !deps.iter().any(|d| d == depends_on_id) {
+            deps.push(depends_on_id.to_string());
+        }
+    }
+
+    pub fn has_dependencies(&self) -> bool {
+        self.dependencies.values().any(|d| !d.is_empty())
+    }
+
+    pub fn dependencies_of(&self, task_id: &str) -> &[String] {
+        self.dependencies
+            .get(task_id)
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+
+    /// Tasks that have dependencies but nothing depending on them
+    pub fn terminal_tasks(&self) -> Vec<String> {
+        let depended_on: HashSet<&String> = self.dependencies.values().flatten().collect();
+        let mut terminals: Vec<String> = self
+            .dependencies
+            .iter()
+            .filter(|(id, deps)| !deps.is_empty() && !depended_on.contains(id))
+            .map(|(id, _)| id.clone())
+            .collect();
+        terminals.sort();
+        terminals
+    }
+
+    pub fn task(&self, task_id: &str) -> Option<&Task> {
+        self.tasks.get(task_id)
+    }
+
+    /// Estimated duration from task metadata, else `fallback_seconds`
+    pub fn duration_of(&self, task_id: &str, fallback_seconds: f64) -> f64 {
+        self.tasks
+            .get(task_id)
+            .and_then(|t| t.metadata.get(ESTIMATED_DURATION_METADATA_KEY))
+            .and_then(|v| v.as_f64())
+            .filter(|d| d.is_finite() && *d >= 0.0)
+            .unwrap_or(fallback_seconds)
+    }
+
+    /// Task IDs the terminal task transitively depends on (itself included),
+    /// ordered so every task comes after its dependencies
+    fn upstream_order(&self, terminal_task_id: &str) -> Result<Vec<String>, EngramError> {
+        fn visit(
+            graph: &DependencyGraph,
+            id: &str,
+            visiting: &mut HashSet<String>,
+            done: &mut HashSet<String>,
+            order: &mut Vec<String>,
+        ) -> Result<(), EngramError> {
+            if done.contains(id) {
+                return Ok(());
+            }
+            if !visiting.insert(id.to_string()) {
+                return Err(EngramError::Validation(format!(
+                    "Dependency cycle detected at task {}",
+                    id
+                )));
+            }
+            for dep in graph.dependencies_of(id) {
+                visit(graph, dep, visiting, done, order)?;
+            }
+            visiting.remove(id);
+            done.insert(id.to_string());
+            order.push(id.to_string());
+            Ok(())
+        }
+
+        let mut order = Vec::new();
+        visit(
+            self,
+            terminal_task_id,
+            &mut HashSet::new(),
+            &mut HashSet::new(),
+            &mut order,
+        )?;
+        Ok(order)
+    }
+
+    /// Longest path through the dependencies of `terminal_task_id`
+    pub fn critical_path(
+        &self,
+        terminal_task_id: &str,
+        fallback_seconds: f64,
+    ) -> Result<CriticalPathResult, EngramError> {
+        if !self.tasks.contains_key(terminal_task_id) {
+            return Err(EngramError::not_found("task", terminal_task_id));
+        }
+
+        let order = self.upstream_order(terminal_task_id)?;
+        let durations: HashMap<String, f64> = order
+            .iter()
+            .map(|id| (id.clone(), self.duration_of(id, fallback_seconds)))
+            .collect();
+
+        let mut earliest_start: HashMap<String, f64> = HashMap::new();
+        let mut earliest_finish: HashMap<String, f64> = HashMap::new();
+        let mut predecessor: HashMap<String, String> = HashMap::new();
+        for id in &order {
+            let mut start = 0.0;
+            for dep in self.dependencies_of(id) {
+                let finish = earliest_finish[dep];
+                if finish > start || !predecessor.contains_key(id) {
+                    start = start.max(finish);
+                    predecessor.insert(id.clone(), dep.clone());
+                }
+            }
+            earliest_start.insert(id.clone(), start);
+            earliest_finish.insert(id.clone(), start + durations[id]);
+        }
+
+        let mut path = vec![terminal_task_id.to_string()];
+        while let Some(prev) = predecessor.get(path.last().unwrap()) {
+            path.push(prev.clone());
+        }
+        path.reverse();
+
+        let mut slack = HashMap::new();
+        for id in &order {
+            let next_start = order
+                .iter()
+                .filter(|other| self.dependencies_of(other).contains(id))
+                .map(|other| earliest_start[other])
+                .fold(f64::INFINITY, f64::min);
+            let free_float = if next_start.is_finite() {
+                next_start - earliest_finish[id]
+            } else {
+                0.0
+            };
+            slack.insert(id.clone(), free_float);
+        }
+
+        Ok(CriticalPathResult {
+            path,
+            total_estimated_duration_seconds: earliest_finish[terminal_task_id],
+            slack,
+            earliest_start,
+            durations,
+        })
+    }
+}
+
+/// Duration assumed for tasks without an estimate: the mean completed-task
+/// duration, or [`DEFAULT_TASK_DURATION_SECONDS`] without history
+pub fn fallback_duration_seconds(storage: &dyn Storage) -> Result<f64, EngramError> {
+    let report = TaskDurationReport::compute(storage, std::path::Path::new("."), "default")?;
+    if report.completed_tasks > 0 && report.mean_duration_hours > 0.0 {
+        Ok(report.mean_duration_hours * 3600.0)
+    } else {
+        Ok(DEFAULT_TASK_DURATION_SECONDS)
+    }
+}
+
+/// Critical path ending at `terminal_task_id`
+pub fn critical_path(
+    storage: &dyn Storage,
+    terminal_task_id: &str,
+) -> Result<CriticalPathResult, EngramError> {
+    let graph = DependencyGraph::load(storage)?;
+    graph.critical_path(terminal_task_id, fallback_duration_seconds(storage)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::TaskPriority;
+    use crate::storage::MemoryStorage;
+
+    fn store_task(storage: &mut MemoryStorage, title: &str, seconds: Option<u64>) -> String {
+        let mut task = Task::new(
+            title.to_string(),
+            String::new(),
+            "test-agent".to_string(),
+            TaskPriority::Medium,
+            None,
+        );
+        if let Some(seconds) = seconds {
+            task.metadata.insert(
+                ESTIMATED_DURATION_METADATA_KEY.to_string(),
+                serde_json::json!(seconds),
+            );
+        }
+        storage.store(&task.to_generic()).unwrap();
+        task.id
+    }
+
+    fn store_dependency(storage: &mut MemoryStorage, task_id: &str, depends_on: &str) {
+        let rel = EntityRelationship::new(
+            uuid::Uuid::new_v4().to_string(),
+            "test-agent".to_string(),
+            task_id.to_string(),
+            "task".to_string(),
+            depends_on.to_string(),
+            "task".to_string(),
+            EntityRelationType::DependsOn,
+        );
+        storage.store(&rel.to_generic()).unwrap();
+    }
+
+    #[test]
+    fn test_critical_path_diamond() {
+        let mut storage = MemoryStorage::new("test-agent");
+        let a = store_task(&mut storage, "A", Some(1000));
+        let b = store_task(&mut storage, "B", Some(2000));
+        let c = store_task(&mut storage, "C", Some(5000));
+        let d = store_task(&mut storage, "D", None);
+        store_dependency(&mut storage, &b, &a);
+        store_dependency(&mut storage, &c, &a);
+        store_dependency(&mut storage, &d, &b);
+        store_dependency(&mut storage, &d, &c);
+
+        let result = critical_path(&storage, &d).unwrap();
+
+        assert_eq!(result.path, vec![a.clone(), c.clone(), d.clone()]);
+        assert_eq!(
+            result.total_estimated_duration_seconds,
+            1000.0 + 5000.0 + DEFAULT_TASK_DURATION_SECONDS
+        );
+        assert_eq!(result.slack[&a], 0.0);
+        assert_eq!(result.slack[&b], 3000.0);
+        assert_eq!(result.slack[&c], 0.0);
+        assert_eq!(result.slack[&d], 0.0);
+        assert!(!result.is_critical(&b));
+    }
+
+    #[test]
+    fn test_critical_path_rejects_cycles() {
+        let mut graph = DependencyGraph::new();
+        for title in ["A", "B"] {
+            let task = Task::new(
+                title.to_string(),
+                String::new(),
+                "test-agent".to_string(),
+                TaskPriority::Low,
+                None,
+            );
+            graph.add_task(task);
+        }
+        let ids: Vec<String> = graph.tasks.keys().cloned().collect();
+        graph.add_dependency(&ids[0], &ids[1]);
+        graph.add_dependency(&ids[1], &ids[0]);
+
+        let err = graph
+            .critical_path(&ids[0], DEFAULT_TASK_DURATION_SECONDS)
+            .unwrap_err();
+        assert!(err.to_string().contains("cycle"));
+        assert_eq!(graph.terminal_tasks(), Vec::<String>::new());
+    }
+}
@@ -0,0 +1,220 @@
+//! Workflow event entity implementation
+//!
+//! Long-running workflow instances keep only their most recent execution
+//! events embedded. Older events are spilled into `workflow_event` entities
+//! linked to the instance by ID, so the full history stays queryable without
+//! the instance entity growing without bound.
+
+use super::{Entity, GenericEntity, WorkflowInstance};
+use crate::engines::workflow_engine::WorkflowExecutionEvent;
+use crate::error::EngramError;
+use crate::storage::{QueryFilter, Storage};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Execution events kept embedded in a workflow instance before older ones
+/// are spilled into `workflow_event` entities
+pub const DEFAULT_MAX_EMBEDDED_EVENTS: usize = 100;
+
+/// A workflow execution event moved out of its instance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowEvent {
+    /// Unique identifier (the wrapped event's ID)
+    #[serde(rename = "id")]
+    pub id: String,
+
+    /// Workflow instance the event belongs to
+    #[serde(rename = "instance_id")]
+    pub instance_id: String,
+
+    /// Workflow definition of the instance
+    #[serde(rename = "workflow_id")]
+    pub workflow_id: String,
+
+    /// Position in the instance's full history, starting at 0
+    #[serde(rename = "sequence")]
+    pub sequence: u64,
+
+    /// The archived event
+    #[serde(rename = "event")]
+    pub event: WorkflowExecutionEvent,
+}
+
+impl Entity for WorkflowEvent {
+    fn entity_type() -> &'static str {
+        "workflow_event"
+    }
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn agent(&self) -> &str {
+        &self.event.agent
+    }
+
+    fn timestamp(&self) -> DateTime<Utc> {
+        self.event.timestamp
+    }
+
+    fn validate_entity(&self) -> crate::Result<()> {
+        if self.id.is_empty() {
+            return Err(EngramError::Validation(
+                "Workflow event ID cannot be empty".to_string(),
+            ));
+        }
+        if self.instance_id.is_empty() {
+            return Err(EngramError::Validation(
+                "Workflow instance ID cannot be empty".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn to_generic(&self) -> GenericEntity {
+        GenericEntity {
+            id: self.id.clone(),
+            entity_type: Self::entity_type().to_string(),
+            agent: self.event.agent.clone(),
+            timestamp: self.event.timestamp,
+            data: serde_json::to_value(self).expect("WorkflowEvent serialization should not fail"),
+        }
+    }
+
+    fn from_generic(entity: GenericEntity) -> crate::Result<Self> {
+        serde_json::from_value(entity.data).map_err(|e| {
+            EngramError::Deserialization(format!("Failed to deserialize workflow event: {}", e))
+        })
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Store an instance, first spilling all but the `max_embedded` most recent
+/// events into `workflow_event` entities
+pub fn store_workflow_instance<S: Storage + ?Sized>(
+    storage: &mut S,
+    instance: &mut WorkflowInstance,
+    max_embedded: usize,
+) -> Result<(), EngramError> {
+    for event in instance.compact_history(max_embedded) {
+        storage.store(&event.to_generic())?;
+    }
+    storage.store(&instance.to_generic())
+}
+
+/// Spilled events of an instance, oldest first
+pub fn load_archived_events<S: Storage + ?Sized>(
+    storage: &S,
+    instance_id: &str,
+) -> Result<Vec<WorkflowEvent>, EngramError> {
+    let filter = QueryFilter {
+        entity_type: Some(WorkflowEvent::entity_type().to_string()),
+        field_filters: HashMap::from([(
+            "instance_id".to_string(),
+            serde_json::Value::String(instance_id.to_string()),
+        )]),
+        limit: None,
+        offset: None,
+        ..Default::default()
+    };
+
+    let mut events: Vec<WorkflowEvent> = storage
+        .query(&filter)?
+        .entities
+        .into_iter()
+        .filter_map(|e| WorkflowEvent::from_generic(e).ok())
+        .collect();
+    events.sort_by_key(|e| e.sequence);
+    Ok(events)
+}
+
+/// Full execution history of an instance: spilled events followed by the
+/// embedded ones, oldest first
+pub fn full_execution_history<S: Storage + ?Sized>(
+    storage: &S,
+    instance: &WorkflowInstance,
+) -> Result<Vec<WorkflowExecutionEvent>, EngramError> {
+    if instance.archived_event_count == 0 {
+        return Ok(instance.execution_history.clone());
+    }
+
+    let mut history: Vec<WorkflowExecutionEvent> = load_archived_events(storage, &instance.id)?
+        .into_iter()
+        .map(|e| e.event)
+        .collect();
+    history.extend(instance.execution_history.iter().cloned());
+    Ok(history)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::workflow_engine::{
+        WorkflowEventType, WorkflowExecutionContext, WorkflowStatus,
+    };
+    use crate::storage::MemoryStorage;
+
+    fn make_instance(events: usize) -> WorkflowInstance {
+        let now = Utc::now();
+        WorkflowInstance {
+            id: "instance-1".to_string(),
+            workflow_id: "workflow-1".to_string(),
+            current_state: "start".to_string(),
+            context: WorkflowExecutionContext {
+                entity_id: None,
+                entity_type: None,
+                executing_agent: "agent-1".to_string(),
+                variables: HashMap::new(),
+                metadata: HashMap::new(),
+                permissions: Vec::new(),
+            },
+            status: WorkflowStatus::Running,
+            started_at: now,
+            updated_at: now,
+            completed_at: None,
+            execution_history: (0..events)
+                .map(|i| WorkflowExecutionEvent {
+                    id: format!("event-{}", i),
+                    timestamp: now + chrono::Duration::seconds(i as i64),
+                    event_type: WorkflowEventType::Transitioned,
+                    from_state: None,
+                    to_state: None,
+                    transition_id: None,
+                    agent: "agent-1".to_string(),
+                    message: format!("step {}", i),
+                    metadata: HashMap::new(),
+                })
+                .collect(),
+            step_count: 0,
+            archived_event_count: 0,
+        }
+    }
+
+    #[test]
+    fn test_store_spills_events_over_threshold() {
+        let mut storage = MemoryStorage::new("agent-1");
+        let mut instance = make_instance(7);
+
+        store_workflow_instance(&mut storage, &mut instance, 3).unwrap();
+        assert_eq!(instance.execution_history.len(), 3);
+        assert_eq!(instance.archived_event_count, 4);
+        assert_eq!(instance.execution_history[0].id, "event-4");
+
+        // Below the threshold nothing moves
+        store_workflow_instance(&mut storage, &mut instance, 3).unwrap();
+        assert_eq!(instance.archived_event_count, 4);
+
+        let archived = load_archived_events(&storage, "instance-1").unwrap();
+        let sequences: Vec<u64> = archived.iter().map(|e| e.sequence).collect();
+        assert_eq!(sequences, vec![0, 1, 2, 3]);
+
+        let history = full_execution_history(&storage, &instance).unwrap();
+        let ids: Vec<String> = history.iter().map(|e| e.id.clone()).collect();
+        let expected: Vec<String> = (0..7).map(|i| format!("event-{}", i)).collect();
+        assert_eq!(ids, expected);
+    }
+}
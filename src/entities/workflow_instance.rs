@@ -1,6 +1,6 @@
 //! Workflow Instance entity implementation
 
-use super::{Entity, GenericEntity};
+use super::{Entity, GenericEntity, WorkflowEvent};
 use crate::engines::workflow_engine::{
     WorkflowExecutionContext, WorkflowExecutionEvent, WorkflowStatus,
 };
@@ -49,6 +49,39 @@ pub struct WorkflowInstance {
     /// Number of transitions executed so far
     #[serde(rename = "step_count", default)]
     pub step_count: u64,
+
+    /// Number of older events moved out of `execution_history` into
+    /// `workflow_event` entities
+    #[serde(rename = "archived_event_count", default)]
+    pub archived_event_count: u64,
+}
+
+impl WorkflowInstance {
+    /// Events recorded over the instance's lifetime, embedded or archived
+    pub fn total_event_count(&self) -> u64 {
+        self.archived_event_count + self.execution_history.len() as u64
+    }
+
+    /// Move all but the `max_embedded` most recent events out of
+    /// `execution_history`, returning them as `workflow_event` entities
+    pub fn compact_history(&mut self, max_embedded: usize) -> Vec<WorkflowEvent> {
+        let excess = self.execution_history.len().saturating_sub(max_embedded);
+        let first_sequence = self.archived_event_count;
+        let spilled: Vec<WorkflowEvent> = self
+            .execution_history
+            .drain(..excess)
+            .enumerate()
+            .map(|(i, event)| WorkflowEvent {
+                id: event.id.clone(),
+                instance_id: self.id.clone(),
+                workflow_id: self.workflow_id.clone(),
+                sequence: first_sequence + i as u64,
+                event,
+            })
+            .collect();
+        self.archived_event_count += spilled.len() as u64;
+        spilled
+    }
 }
 
 impl Entity for WorkflowInstance {
@@ -150,6 +183,7 @@ mod tests {
             completed_at: None,
             execution_history: vec![],
             step_count: 0,
+            archived_event_count: 0,
         };
 
         assert_eq!(instance.id, id);
@@ -184,6 +218,7 @@ mod tests {
             completed_at: None,
             execution_history: vec![],
             step_count: 0,
+            archived_event_count: 0,
         };
 
         // Valid instance
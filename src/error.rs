@@ -29,14 +29,135 @@ pub enum EngramError {
     #[error("YAML error: {0}")]
     Yaml(#[from] serde_yaml::Error),
 
-    #[error("Not found: {0}")]
-    NotFound(String),
+    #[error("{entity_type} '{id}' not found")]
+    NotFound { entity_type: String, id: String },
+
+    /// The entity exists but its current state rules out the operation
+    #[error("{entity_type} '{id}': {reason}")]
+    Conflict {
+        entity_type: String,
+        id: String,
+        reason: String,
+    },
 
     #[error("Already exists: {0}")]
     AlreadyExists(String),
 
     #[error("Invalid operation: {0}")]
     InvalidOperation(String),
+
+    #[error("Workspace locked: {0}")]
+    Locked(String),
+
+    #[error(
+        "Quota exceeded: agent '{agent}' may create {limit} {entity_type} entities per hour; \
+         the window resets at {resets}. To request a higher quota run: \
+         engram escalation create --agent {agent} --operation-type resource_limit \
+         --operation \"create {entity_type}\" --justification \"...\"",
+        resets = .resets_at.format("%Y-%m-%d %H:%M:%S UTC")
+    )]
+    QuotaExceeded {
+        agent: String,
+        entity_type: String,
+        limit: u32,
+        resets_at: chrono::DateTime<chrono::Utc>,
+    },
+
+    #[error(
+        "Sandbox limit exceeded for agent '{agent}': {reason}. Escalation {escalation_id} \
+         is pending review; once it is approved (engram escalation approve {escalation_id}) \
+         retry the command"
+    )]
+    SandboxLimitExceeded {
+        agent: String,
+        reason: String,
+        escalation_id: String,
+    },
+}
+
+/// Process exit code for [`EngramError::NotFound`]
+pub const EXIT_NOT_FOUND: i32 = 3;
+
+/// Process exit code for [`EngramError::Conflict`]
+pub const EXIT_CONFLICT: i32 = 4;
+
+impl EngramError {
+    pub fn not_found(entity_type: impl Into<String>, id: impl Into<String>) -> Self {
+        EngramError::NotFound {
+            entity_type: entity_type.into(),
+            id: id.into(),
+        }
+    }
+
+    pub fn conflict(
+        entity_type: impl Into<String>,
+        id: impl Into<String>,
+        reason: impl Into<String>,
+    ) -> Self {
+        EngramError::Conflict {
+            entity_type: entity_type.into(),
+            id: id.into(),
+            reason: reason.into(),
+        }
+    }
+
+    /// Exit code the CLI ends with: [`EXIT_NOT_FOUND`], [`EXIT_CONFLICT`],
+    /// or 1 for every other error. 2 is left to checks that fail on findings.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            EngramError::NotFound { .. } => EXIT_NOT_FOUND,
+            EngramError::Conflict { .. } => EXIT_CONFLICT,
+            _ => 1,
+        }
+    }
+
+    /// Error object printed under `--json`: `{"error": message}`, plus a
+    /// `kind` and the entity's `entity_type` and `id` for missing or
+    /// conflicting entities
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut object = serde_json::json!({ "error": self.to_string() });
+        match self {
+            EngramError::NotFound { entity_type, id } => {
+                object["kind"] = "not_found".into();
+                object["entity_type"] = entity_type.as_str().into();
+                object["id"] = id.as_str().into();
+            }
+            EngramError::Conflict {
+                entity_type,
+                id,
+                reason,
+            } => {
+                object["kind"] = "conflict".into();
+                object["entity_type"] = entity_type.as_str().into();
+                object["id"] = id.as_str().into();
+                object["reason"] = reason.as_str().into();
+            }
+            _ => {}
+        }
+        object
+    }
+
+    /// Emit this error as a log event at a level matching its severity
+    ///
+    /// Missing entities and refused operations are usually the caller's
+    /// mistake and log at `warn`, validation failures are expected feedback
+    /// and log at `info`, everything else (git, storage, I/O) logs at `error`.
+    pub fn log(&self) {
+        match self {
+            EngramError::Validation(_) => tracing::info!(error = %self, "validation failed"),
+            EngramError::NotFound { .. }
+            | EngramError::Conflict { .. }
+            | EngramError::AlreadyExists(_)
+            | EngramError::InvalidOperation(_)
+            | EngramError::Locked(_)
+            | EngramError::QuotaExceeded { .. }
+            | EngramError::SandboxLimitExceeded { .. } => {
+                tracing::warn!(error = %self, "operation refused")
+            }
+            EngramError::Git(_) => tracing::error!(error = %self, "git operation failed"),
+            _ => tracing::error!(error = %self, "operation failed"),
+        }
+    }
 }
 
 impl From<git2::Error> for EngramError {
@@ -76,3 +197,35 @@ pub enum ConfigError {
 
 /// Result type alias for convenience
 pub type Result<T> = std::result::Result<T, EngramError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_structured_errors_carry_exit_code_and_json_context() {
+        let missing = EngramError::not_found("workflow", "wf-1");
+        assert_eq!(missing.to_string(), "workflow 'wf-1' not found");
+        assert_eq!(missing.exit_code(), EXIT_NOT_FOUND);
+        assert_eq!(
+            missing.to_json(),
+            serde_json::json!({
+                "error": "workflow 'wf-1' not found",
+                "kind": "not_found",
+                "entity_type": "workflow",
+                "id": "wf-1",
+            })
+        );
+
+        let conflict = EngramError::conflict("workflow_instance", "inst-1", "already completed");
+        assert_eq!(conflict.exit_code(), EXIT_CONFLICT);
+        assert_eq!(conflict.to_json()["reason"], "already completed");
+
+        let other = EngramError::Validation("bad".to_string());
+        assert_eq!(other.exit_code(), 1);
+        assert_eq!(
+            other.to_json(),
+            serde_json::json!({ "error": "Entity validation error: bad" })
+        );
+    }
+}
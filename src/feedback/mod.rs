@@ -294,6 +294,8 @@ mod tests {
             validated_relationships: vec!["rel-1".into()],
             validated_files: vec!["foo.rs".into()],
             validation_time_ms: 5,
+            warnings: vec![],
+            phase_timings: Default::default(),
         };
         assert_eq!(r.status_code(), FeedbackStatus::Success);
         assert!(r.summary().contains("passed"));
@@ -314,6 +316,8 @@ mod tests {
             validated_relationships: vec![],
             validated_files: vec![],
             validation_time_ms: 1,
+            warnings: vec![],
+            phase_timings: Default::default(),
         };
         assert_eq!(r.status_code(), FeedbackStatus::Failed);
         assert!(r.summary().contains("1 error(s)"));
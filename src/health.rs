@@ -0,0 +1,216 @@
+//! Subsystem health checks for `engram status`
+//!
+//! Each engine implements [`EngineHealthCheck`]. [`check_all_health`]
+//! instantiates the engines and collects one [`HealthStatus`] per subsystem.
+//! Engines own their storage, so each one runs against an in-memory snapshot
+//! of just the entity types it reads. A check can therefore never modify the
+//! workspace, and a failed read marks only the affected subsystem unhealthy.
+
+use crate::config::Config;
+use crate::engines::workflow_engine::WorkflowAutomationEngine;
+use crate::error::EngramError;
+use crate::nlq::NLQEngine;
+use crate::perkeep::{PerkeepClient, PerkeepConfig};
+use crate::storage::{MemoryStorage, Storage};
+use crate::validation::quality_gates::QualityGatesExecutor;
+use serde::Serialize;
+use std::time::Instant;
+
+/// Overall state of a subsystem
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthState {
+    Healthy,
+    /// Working, but with problems an operator should look at
+    Degraded,
+    Unhealthy,
+}
+
+impl std::fmt::Display for HealthState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HealthState::Healthy => write!(f, "healthy"),
+            HealthState::Degraded => write!(f, "degraded"),
+            HealthState::Unhealthy => write!(f, "unhealthy"),
+        }
+    }
+}
+
+/// Result of checking one subsystem
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthStatus {
+    pub name: String,
+    pub status: HealthState,
+    pub details: String,
+    pub latency_ms: u64,
+}
+
+impl HealthStatus {
+    pub fn new(name: &str, status: HealthState, details: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status,
+            details: details.into(),
+            latency_ms: 0,
+        }
+    }
+}
+
+/// A subsystem that can report whether it is operational
+pub trait EngineHealthCheck {
+    /// Check the subsystem; an error means the check itself could not run
+    /// and is reported as [`HealthState::Unhealthy`]
+    fn health_check(&self) -> crate::Result<HealthStatus>;
+}
+
+/// Entity types the sandbox engine reads
+#[cfg(feature = "sandbox")]
+const SANDBOX_ENTITY_TYPES: [&str; 1] = ["agent_sandbox"];
+
+/// Entity types the workflow engine reads
+const WORKFLOW_ENTITY_TYPES: [&str; 2] = ["workflow", "workflow_instance"];
+
+/// Entity types the quality gates executor reads
+const QUALITY_GATE_ENTITY_TYPES: [&str; 1] = ["flakiness_blacklist"];
+
+/// Check every subsystem against `storage`
+///
+/// Results are in a fixed order: config, workflow, sandbox (when the sandbox
+/// feature is enabled), nlq, quality_gates, perkeep.
+pub async fn check_all_health(storage: &dyn Storage, config: &Config) -> Vec<HealthStatus> {
+    let mut results = vec![timed("config", || {
+        config.validate()?;
+        Ok(HealthStatus::new(
+            "config",
+            HealthState::Healthy,
+            format!("storage: {}", config.storage.storage_type),
+        ))
+    })];
+
+    results.push(timed("workflow", || {
+        let snapshot = snapshot(storage, &WORKFLOW_ENTITY_TYPES)?;
+        WorkflowAutomationEngine::new(snapshot).health_check()
+    }));
+
+    #[cfg(feature = "sandbox")]
+    results.push(timed("sandbox", || {
+        let snapshot = snapshot(storage, &SANDBOX_ENTITY_TYPES)?;
+        crate::sandbox::SandboxEngine::new(Box::new(snapshot)).health_check()
+    }));
+
+    results.push(timed("nlq", || NLQEngine::new().health_check()));
+
+    results.push(timed("quality_gates", || {
+        let snapshot = snapshot(storage, &QUALITY_GATE_ENTITY_TYPES)?;
+        QualityGatesExecutor::new(snapshot).health_check()
+    }));
+
+    results.push(check_perkeep().await);
+    results
+}
+
+/// Whether any subsystem in `results` is unhealthy
+pub fn any_unhealthy(results: &[HealthStatus]) -> bool {
+    results.iter().any(|r| r.status == HealthState::Unhealthy)
+}
+
+/// Run `check`, record its latency and turn errors into an unhealthy status
+fn timed(name: &str, check: impl FnOnce() -> crate::Result<HealthStatus>) -> HealthStatus {
+    let start = Instant::now();
+    let mut status =
+        check().unwrap_or_else(|e| HealthStatus::new(name, HealthState::Unhealthy, e.to_string()));
+    status.latency_ms = start.elapsed().as_millis() as u64;
+    status
+}
+
+/// Copy every entity of `entity_types` into a fresh memory store
+fn snapshot(storage: &dyn Storage, entity_types: &[&str]) -> Result<MemoryStorage, EngramError> {
+    let mut snapshot = MemoryStorage::new("health");
+    for entity_type in entity_types {
+        snapshot.bulk_store(&storage.get_all(entity_type)?)?;
+    }
+    Ok(snapshot)
+}
+
+/// Perkeep is optional, so it is only probed when `PERKEEP_SERVER` is set;
+/// an unreachable server degrades backups without affecting anything else
+async fn check_perkeep() -> HealthStatus {
+    let start = Instant::now();
+    let mut status = if std::env::var("PERKEEP_SERVER").is_err() {
+        HealthStatus::new(
+            "perkeep",
+            HealthState::Healthy,
+            "not configured (PERKEEP_SERVER unset)",
+        )
+    } else {
+        let config = PerkeepConfig::default();
+        let server = config.server_url.clone();
+        match PerkeepClient::new(config) {
+            Ok(client) => match client.health_check().await {
+                Ok(true) => HealthStatus::new(
+                    "perkeep",
+                    HealthState::Healthy,
+                    format!("{} reachable", server),
+                ),
+                Ok(false) => HealthStatus::new(
+                    "perkeep",
+                    HealthState::Degraded,
+                    format!("{} unreachable", server),
+                ),
+                Err(e) => HealthStatus::new("perkeep", HealthState::Degraded, e.to_string()),
+            },
+            Err(e) => HealthStatus::new("perkeep", HealthState::Unhealthy, e.to_string()),
+        }
+    };
+    status.latency_ms = start.elapsed().as_millis() as u64;
+    status
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::GenericEntity;
+
+    fn status<'a>(results: &'a [HealthStatus], name: &str) -> &'a HealthStatus {
+        results.iter().find(|r| r.name == name).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_check_all_health_empty_workspace() {
+        let storage = MemoryStorage::new("default");
+        let results = check_all_health(&storage, &Config::default()).await;
+
+        for name in ["config", "workflow", "nlq", "quality_gates"] {
+            assert_eq!(
+                status(&results, name).status,
+                HealthState::Healthy,
+                "{}",
+                name
+            );
+        }
+        assert!(!any_unhealthy(&results));
+    }
+
+    #[cfg(feature = "sandbox")]
+    #[tokio::test]
+    async fn test_check_all_health_reports_unhealthy_sandbox() {
+        let mut storage = MemoryStorage::new("default");
+        storage
+            .store(&GenericEntity {
+                id: "broken".to_string(),
+                entity_type: "agent_sandbox".to_string(),
+                agent: "default".to_string(),
+                timestamp: chrono::Utc::now(),
+                data: serde_json::json!({"agent_id": 7}),
+            })
+            .unwrap();
+
+        let results = check_all_health(&storage, &Config::default()).await;
+
+        let sandbox = status(&results, "sandbox");
+        assert_eq!(sandbox.status, HealthState::Unhealthy);
+        assert!(sandbox.details.contains("broken"));
+        assert_eq!(status(&results, "workflow").status, HealthState::Healthy);
+        assert!(any_unhealthy(&results));
+    }
+}
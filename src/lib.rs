@@ -4,22 +4,32 @@
 //! a distributed memory system with Git-based storage, CLI interface,
 //! and extensible architecture for AI agents.
 
+pub mod aging;
+pub mod analytics;
+pub mod api;
+pub mod archive;
 pub mod ask;
+pub mod branch;
 pub mod cli;
 pub mod config;
+pub mod dedup;
 pub mod engines;
 pub mod entities;
 pub mod error;
 pub mod feedback;
+pub mod health;
 pub mod locus_cli;
 pub mod locus_handlers;
 pub mod locus_integration;
 #[cfg(feature = "tui")]
 pub mod locus_tui;
+pub mod logging;
 pub mod migration;
 pub mod nlq;
+pub mod notify;
 pub mod perkeep;
 pub mod personas;
+pub mod redaction;
 #[cfg(feature = "sandbox")]
 pub mod sandbox;
 pub mod storage;
@@ -32,6 +42,7 @@ use std::result::Result as StdResult;
 /// Common result type used throughout the application
 pub type Result<T> = StdResult<T, error::EngramError>;
 
+pub use api::{Engram, TaskDraft, TaskFilter};
 pub use config::Config;
 pub use entities::doc_fragment::DocFragment;
 /// Re-export commonly used types
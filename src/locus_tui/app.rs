@@ -3,6 +3,7 @@ use crate::entities::{
     ExecutionResult, Knowledge, Persona, ProgressiveGateConfig, Reasoning, Rule, Session, Standard,
     StateReflection, Task, TaskStatus, Theory, Workflow, WorkflowInstance, ADR,
 };
+use crate::notify::NotificationEvent;
 use std::collections::HashMap;
 use std::time::Instant;
 
@@ -366,6 +367,8 @@ pub struct AppState {
     pub state_reflections_selected: usize,
     pub all_escalations: Vec<EscalationRequest>,
     pub escalations_selected: usize,
+    /// Critical escalation alerts still awaiting review, shown in the header
+    pub critical_alerts: Vec<NotificationEvent>,
     pub all_sandboxes: Vec<AgentSandbox>,
     pub sandboxes_selected: usize,
     pub all_execution_results: Vec<ExecutionResult>,
@@ -437,6 +440,7 @@ impl AppState {
             state_reflections_selected: 0,
             all_escalations: Vec::new(),
             escalations_selected: 0,
+            critical_alerts: Vec::new(),
             all_sandboxes: Vec::new(),
             sandboxes_selected: 0,
             all_execution_results: Vec::new(),
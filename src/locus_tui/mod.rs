@@ -207,6 +207,10 @@ impl<S: Storage + RelationshipStorage + Send + 'static> LocusTuiApp<S> {
         self.app_state.all_state_reflections =
             self.backend.list_state_reflections().unwrap_or_default();
         self.app_state.all_escalations = self.backend.list_escalations().unwrap_or_default();
+        self.app_state.critical_alerts = crate::notify::open_escalation_alerts(
+            crate::notify::read_alerts(std::path::Path::new(".")).unwrap_or_default(),
+            &self.app_state.all_escalations,
+        );
         self.app_state.all_sandboxes = self.backend.list_sandboxes().unwrap_or_default();
         self.app_state.all_execution_results =
             self.backend.list_execution_results().unwrap_or_default();
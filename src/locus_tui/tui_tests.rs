@@ -404,6 +404,25 @@ mod tests {
 
     // ── Dashboard view render tests ───────────────────────────────────────────
 
+    #[test]
+    fn test_title_bar_surfaces_critical_alerts() {
+        let mut app = make_loaded_app();
+        let content = render_to_string(&mut app);
+        assert!(!content.contains("critical escalation(s) awaiting review"));
+
+        app.app_state.critical_alerts = vec![crate::notify::NotificationEvent::new(
+            crate::notify::ESCALATION_CREATED,
+            crate::notify::NotificationPriority::Critical,
+            "Escalation from agent-1",
+            "agent-1 requests approval",
+        )];
+        let content = render_to_string(&mut app);
+        assert!(
+            content.contains("1 critical escalation(s) awaiting review"),
+            "expected alert banner in: {content}"
+        );
+    }
+
     #[test]
     fn test_dashboard_view_renders_title_bar() {
         let mut app = make_loaded_app();
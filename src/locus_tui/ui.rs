@@ -61,7 +61,19 @@ pub fn draw<S: Storage + RelationshipStorage>(
     let title_text = format!(
         "Engram Locus  [{view_name}]  Tasks: {task_count}  Workflows: {workflow_count}  Tab:next  q:quit  t:theme"
     );
-    let title = Paragraph::new(title_text).style(Style::default().fg(theme.title()));
+    let mut title_lines = vec![Line::from(title_text)];
+    if !app_state.critical_alerts.is_empty() {
+        title_lines.push(Line::from(Span::styled(
+            format!(
+                "🚨 {} critical escalation(s) awaiting review — see the Escalations view",
+                app_state.critical_alerts.len()
+            ),
+            Style::default()
+                .fg(theme.status_err())
+                .add_modifier(Modifier::BOLD),
+        )));
+    }
+    let title = Paragraph::new(title_lines).style(Style::default().fg(theme.title()));
     f.render_widget(title, chunks[0]);
 
     // Border style derived from the active theme
@@ -0,0 +1,252 @@
+//! Diagnostic logging for CLI runs
+//!
+//! Logs go to stderr so they never mix with command output. `ENGRAM_LOG`
+//! takes an [`EnvFilter`] directive such as `info` or
+//! `engram::storage=debug,engram::cli=info`; logging is off when it is unset.
+//! `ENGRAM_LOG_FORMAT=json` switches to one JSON object per line, including
+//! the fields of every enclosing span.
+
+use chrono::Utc;
+use serde_json::{Map, Value};
+use std::fmt;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields, FormattedFields, MakeWriter};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::EnvFilter;
+
+/// Environment variable holding the log filter directives
+pub const LOG_ENV: &str = "ENGRAM_LOG";
+
+/// Environment variable selecting the log format (`json` or plain text)
+pub const LOG_FORMAT_ENV: &str = "ENGRAM_LOG_FORMAT";
+
+/// Filter built from `ENGRAM_LOG`, or one that disables logging
+pub fn env_filter() -> EnvFilter {
+    EnvFilter::try_from_env(LOG_ENV).unwrap_or_else(|_| EnvFilter::new("off"))
+}
+
+/// Whether `ENGRAM_LOG_FORMAT` asks for JSON lines
+pub fn json_format_requested() -> bool {
+    std::env::var(LOG_FORMAT_ENV)
+        .map(|format| format.eq_ignore_ascii_case("json"))
+        .unwrap_or(false)
+}
+
+/// Build the subscriber configured by the environment, writing to `writer`
+pub fn subscriber<W>(writer: W, ansi: bool) -> Box<dyn Subscriber + Send + Sync>
+where
+    W: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+{
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(env_filter())
+        .with_writer(writer);
+
+    if json_format_requested() {
+        Box::new(
+            builder
+                .event_format(JsonFormat)
+                .fmt_fields(JsonFields)
+                .finish(),
+        )
+    } else {
+        Box::new(builder.with_ansi(ansi).finish())
+    }
+}
+
+/// Formats each event as a single JSON object
+///
+/// `{"timestamp", "level", "target", "fields": {..}, "spans": [{"name", ..}]}`
+/// with spans ordered from the outermost inwards.
+pub struct JsonFormat;
+
+impl<S, N> FormatEvent<S, N> for JsonFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let metadata = event.metadata();
+        let mut fields = JsonVisitor::default();
+        event.record(&mut fields);
+
+        let mut spans = Vec::new();
+        if let Some(scope) = ctx.event_scope() {
+            for span in scope.from_root() {
+                let mut object = span
+                    .extensions()
+                    .get::<FormattedFields<N>>()
+                    .and_then(|formatted| serde_json::from_str(&formatted.fields).ok())
+                    .unwrap_or_else(Map::new);
+                object.insert("name".to_string(), Value::from(span.name()));
+                spans.push(Value::Object(object));
+            }
+        }
+
+        let line = serde_json::json!({
+            "timestamp": Utc::now().to_rfc3339(),
+            "level": metadata.level().as_str(),
+            "target": metadata.target(),
+            "fields": Value::Object(fields.0),
+            "spans": spans,
+        });
+        writeln!(writer, "{}", line)
+    }
+}
+
+/// Stores span fields as a JSON object so [`JsonFormat`] can embed them
+pub struct JsonFields;
+
+impl<'writer> FormatFields<'writer> for JsonFields {
+    fn format_fields<R: tracing_subscriber::field::RecordFields>(
+        &self,
+        mut writer: Writer<'writer>,
+        fields: R,
+    ) -> fmt::Result {
+        let mut visitor = JsonVisitor::default();
+        fields.record(&mut visitor);
+        write!(writer, "{}", Value::Object(visitor.0))
+    }
+
+    fn add_fields(
+        &self,
+        current: &'writer mut FormattedFields<Self>,
+        fields: &tracing::span::Record<'_>,
+    ) -> fmt::Result {
+        let mut visitor = JsonVisitor(serde_json::from_str(&current.fields).unwrap_or_default());
+        fields.record(&mut visitor);
+        current.fields = Value::Object(visitor.0).to_string();
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct JsonVisitor(Map<String, Value>);
+
+impl Visit for JsonVisitor {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0.insert(
+            field.name().to_string(),
+            Value::from(format!("{:?}", value)),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::task::create_task;
+    use crate::storage::MemoryStorage;
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+
+    /// Collects log output so tests can inspect it
+    #[derive(Clone, Default)]
+    struct Capture(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Capture {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Capture {
+        fn output(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    fn create_logged_task() -> String {
+        let capture = Capture::default();
+        let writer = capture.clone();
+        let subscriber = subscriber(move || writer.clone(), false);
+
+        tracing::subscriber::with_default(subscriber, || {
+            let mut storage = MemoryStorage::new("default");
+            create_task(
+                &mut storage,
+                Some("Write docs".to_string()),
+                None,
+                "high",
+                Some("alice".to_string()),
+                None,
+                None,
+                false,
+                None,
+                false,
+                None,
+                false,
+                None,
+                "json".to_string(),
+                false,
+                None,
+                false,
+            )
+            .unwrap();
+        });
+        capture.output()
+    }
+
+    // Both formats are covered in one test because they share process-wide
+    // environment variables
+    #[test]
+    fn test_task_creation_emits_events() {
+        std::env::set_var(LOG_ENV, "info");
+
+        std::env::remove_var(LOG_FORMAT_ENV);
+        let plain = create_logged_task();
+        assert!(plain.contains("task created"), "{}", plain);
+        assert!(plain.contains("operation=\"create\""), "{}", plain);
+
+        std::env::set_var(LOG_FORMAT_ENV, "json");
+        let json = create_logged_task();
+        std::env::remove_var(LOG_FORMAT_ENV);
+
+        let event: Value = json
+            .lines()
+            .map(|line| serde_json::from_str::<Value>(line).unwrap())
+            .find(|event| event["fields"]["message"] == "task created")
+            .expect("task created event");
+        assert_eq!(event["level"], "INFO");
+        assert_eq!(event["target"], "engram::api");
+        assert_eq!(event["fields"]["agent"], "alice");
+        assert_eq!(event["spans"][0]["name"], "create_task");
+        assert_eq!(event["spans"][0]["entity_type"], "task");
+        assert_eq!(event["spans"][0]["operation"], "create");
+
+        std::env::set_var(LOG_ENV, "warn");
+        assert!(create_logged_task().is_empty());
+        std::env::remove_var(LOG_ENV);
+    }
+}
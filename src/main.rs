@@ -1,101 +1,204 @@
 //! Main entry point for Engram CLI
 
 use clap::Parser;
+#[cfg(feature = "sandbox")]
+use engram::sandbox::SandboxStorage;
 use engram::{
     ask::handle_ask_command,
     cli::{self, handle_relationship_command, handle_validation_command},
     error::EngramError,
+    logging,
     migration::Migration,
-    storage::GitRefsStorage,
+    storage::{
+        CachedStorage, DryRunStorage, GitRefsStorage, QuotaConfig, QuotaStorage, ReadOnlyMode,
+        ReadOnlyStorage,
+    },
+    Config,
 };
+use std::io::IsTerminal;
+use std::path::Path;
+
+/// Open the workspace behind a read-only guard, with an entity cache
+/// shared by everything the command reads. In read-only mode the
+/// repository is opened without initialising anything. Writes are
+/// attributed to the current agent identity.
+fn open_workspace(
+    read_only: Option<ReadOnlyMode>,
+) -> Result<CachedStorage<ReadOnlyStorage<GitRefsStorage>>, EngramError> {
+    let mode = ReadOnlyMode::resolve(read_only)?;
+    let agent = cli::identity::current_agent();
+    let storage = if mode.is_enabled() {
+        GitRefsStorage::open_read_only(".", &agent)?
+    } else {
+        GitRefsStorage::new(".", &agent)?
+    };
+    Ok(CachedStorage::new(ReadOnlyStorage::new(storage, mode)))
+}
+
+/// Open the workspace storage as `$storage` and run `$body`. Creation quotas
+/// from `engram.yaml` are always enforced, as are sandbox storage limits when
+/// the sandbox feature is enabled. Under `--dry-run` the storage is also
+/// wrapped in `DryRunStorage`, so writes are reported afterwards instead of
+/// applied.
+macro_rules! with_storage {
+    ($args:expr, $storage:ident => $body:block) => {{
+        let inner = open_workspace($args.read_only)?;
+        let quotas = QuotaConfig::load(Path::new("."))?;
+        if $args.dry_run {
+            let $storage = QuotaStorage::new(DryRunStorage::new(inner), quotas);
+            #[cfg(feature = "sandbox")]
+            let $storage = SandboxStorage::new($storage);
+            let mut $storage = $storage;
+            $body
+            #[cfg(feature = "sandbox")]
+            let $storage = $storage.into_inner();
+            $storage.inner().print_report($args.json);
+        } else {
+            let $storage = QuotaStorage::new(inner, quotas);
+            #[cfg(feature = "sandbox")]
+            let $storage = SandboxStorage::new($storage);
+            let mut $storage = $storage;
+            $body
+        }
+    }};
+}
+
+/// Install the global log subscriber configured by `ENGRAM_LOG` and
+/// `ENGRAM_LOG_FORMAT`; see [`engram::logging`]
+fn init_logging() {
+    let subscriber = logging::subscriber(std::io::stderr, std::io::stderr().is_terminal());
+    let _ = tracing::subscriber::set_global_default(subscriber);
+}
 
 #[tokio::main]
 async fn main() {
+    init_logging();
+
     let args: Vec<String> = std::env::args().collect();
     let json_mode = args.iter().any(|arg| arg == "--json");
 
     if let Err(e) = run().await {
+        e.log();
         if json_mode {
-            let error_msg = serde_json::json!({
-                "error": e.to_string()
-            });
-            println!("{}", error_msg);
+            println!("{}", e.to_json());
         } else {
             eprintln!("Error: {}", e);
         }
-        std::process::exit(1);
+        std::process::exit(e.exit_code());
     }
 }
 
 async fn run() -> Result<(), EngramError> {
     let args = cli::Cli::parse();
+    if let Some(threads) = args.threads {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build_global()
+            .map_err(|e| EngramError::InvalidOperation(format!("--threads {}: {}", threads, e)))?;
+    }
+    if let Some(workspace) = &args.workspace {
+        cli::workspace::enter_workspace(workspace)?;
+    }
 
     match args.command {
         cli::Commands::Setup { command } => handle_setup_command(command)?,
-        cli::Commands::Convert { from, file } => handle_convert_command(&from, &file)?,
+        cli::Commands::Convert { from, file, agent } => {
+            with_storage!(args, storage => {
+                cli::handle_convert_command(&mut storage, &from, &file, agent, args.json)?;
+            });
+        }
         cli::Commands::Doc { command } => {
-            let mut storage = GitRefsStorage::new(".", "default")?;
-            cli::handle_doc_command(command, &mut storage)?;
+            with_storage!(args, storage => {
+                cli::handle_doc_command(command, &mut storage)?;
+            });
         }
         cli::Commands::Import { command } => {
-            let mut storage = GitRefsStorage::new(".", "default")?;
-            cli::handle_import_command(command, &mut storage)?;
+            with_storage!(args, storage => {
+                cli::handle_import_command(command, &mut storage)?;
+            });
         }
         cli::Commands::Test => handle_test_command()?,
         cli::Commands::Task { command } => {
-            let mut storage = GitRefsStorage::new(".", "default")?;
-            handle_task_command(command, &mut storage)?;
+            with_storage!(args, storage => {
+                if command.is_mutating()
+                    && !matches!(command, cli::TaskCommands::Recurring { .. })
+                {
+                    cli::check_recurring_tasks(&mut storage);
+                }
+                let mutating = command.is_mutating();
+                handle_task_command(command, &mut storage, args.json)?;
+                if mutating && !args.json {
+                    cli::auto_guide::check_auto_guide(&mut storage, "task");
+                }
+            });
         }
         cli::Commands::Context { command } => {
-            let mut storage = GitRefsStorage::new(".", "default")?;
-            handle_context_command(command, &mut storage)?;
+            with_storage!(args, storage => {
+                handle_context_command(command, &mut storage)?;
+            });
         }
         cli::Commands::Ask { command } => {
-            handle_ask_command(command).await?;
+            let storage = open_workspace(args.read_only)?;
+            handle_ask_command(command, &storage).await?;
         }
         cli::Commands::Reasoning { command } => {
-            let mut storage = GitRefsStorage::new(".", "default")?;
-            handle_reasoning_command(command, &mut storage)?;
+            with_storage!(args, storage => {
+                handle_reasoning_command(command, &mut storage)?;
+            });
         }
         cli::Commands::Knowledge { command } => {
-            let mut storage = GitRefsStorage::new(".", "default")?;
-            handle_knowledge_command(command, &mut storage)?;
+            with_storage!(args, storage => {
+                handle_knowledge_command(command, &mut storage)?;
+            });
         }
         cli::Commands::Lesson { command } => {
-            let mut storage = GitRefsStorage::new(".", "default")?;
-            handle_lesson_command(command, &mut storage)?;
+            with_storage!(args, storage => {
+                handle_lesson_command(command, &mut storage)?;
+            });
         }
         cli::Commands::Persona { command } => {
-            let mut storage = GitRefsStorage::new(".", "default")?;
-            handle_persona_command(command, &mut storage)?;
+            with_storage!(args, storage => {
+                handle_persona_command(command, &mut storage)?;
+            });
         }
         cli::Commands::Session { command } => {
-            let mut storage = GitRefsStorage::new(".", "default")?;
-            handle_session_command(command, &mut storage)?;
+            with_storage!(args, storage => {
+                let mutating = command.is_mutating();
+                handle_session_command(command, &mut storage)?;
+                if mutating && !args.json {
+                    cli::auto_guide::check_auto_guide(&mut storage, "session");
+                }
+            });
         }
         cli::Commands::Compliance { command } => {
-            let mut storage = GitRefsStorage::new(".", "default")?;
-            handle_compliance_command(command, &mut storage)?;
+            with_storage!(args, storage => {
+                handle_compliance_command(command, &mut storage)?;
+            });
         }
         cli::Commands::Rule { command } => {
-            let mut storage = GitRefsStorage::new(".", "default")?;
-            handle_rule_command(command, &mut storage)?;
+            with_storage!(args, storage => {
+                handle_rule_command(command, &mut storage)?;
+            });
         }
         cli::Commands::Standard { command } => {
-            let mut storage = GitRefsStorage::new(".", "default")?;
-            handle_standard_command(command, &mut storage)?;
+            with_storage!(args, storage => {
+                handle_standard_command(command, &mut storage)?;
+            });
         }
         cli::Commands::Adr { command } => {
-            let mut storage = GitRefsStorage::new(".", "default")?;
-            handle_adr_command(command, &mut storage)?;
+            with_storage!(args, storage => {
+                handle_adr_command(command, &mut storage)?;
+            });
         }
         cli::Commands::Workflow { command } => {
-            let mut storage = GitRefsStorage::new(".", "default")?;
-            handle_workflow_command(command, &mut storage)?;
+            with_storage!(args, storage => {
+                handle_workflow_command(command, &mut storage)?;
+            });
         }
         cli::Commands::Relationship { command } => {
-            let mut storage = GitRefsStorage::new(".", "default")?;
-            handle_relationship_command(&mut storage, command)?;
+            with_storage!(args, storage => {
+                handle_relationship_command(&mut storage, command)?;
+            });
         }
         cli::Commands::Git { command } => {
             engram::cli::git::handle_git_command(match command {
@@ -103,20 +206,23 @@ async fn run() -> Result<(), EngramError> {
             })?;
         }
         cli::Commands::Validate { command } => {
-            let storage = GitRefsStorage::new(".", "default")?;
-            handle_validation_command(command, storage)?;
+            let storage = open_workspace(args.read_only)?;
+            handle_validation_command(command, storage, args.dry_run, args.json)?;
         }
         cli::Commands::Sandbox { command } => {
-            let mut storage = GitRefsStorage::new(".", "default")?;
-            handle_sandbox_command(command, &mut storage)?;
+            with_storage!(args, storage => {
+                handle_sandbox_command(command, &mut storage).await?;
+            });
         }
         cli::Commands::Escalation { command } => {
-            let mut storage = GitRefsStorage::new(".", "default")?;
-            handle_escalation_command(command, &mut storage)?;
+            with_storage!(args, storage => {
+                handle_escalation_command(command, &mut storage)?;
+            });
         }
         cli::Commands::Sync { command } => {
-            let mut storage = GitRefsStorage::new(".", "default")?;
-            engram::cli::sync::handle_sync_command(&mut storage, &command)?;
+            with_storage!(args, storage => {
+                engram::cli::sync::handle_sync_command(&mut storage, &command, args.dry_run)?;
+            });
         }
         cli::Commands::Next {
             id,
@@ -126,25 +232,97 @@ async fn run() -> Result<(), EngramError> {
             scope_agent,
             session,
             tag,
+            redact,
         } => {
-            let mut storage = GitRefsStorage::new(".", "default")?;
-            engram::cli::next::handle_next_command(
-                &mut storage,
-                id,
-                format,
-                agent,
-                parent,
-                scope_agent,
-                session,
-                tag,
+            with_storage!(args, storage => {
+                engram::cli::next::handle_next_command(
+                    &mut storage,
+                    id,
+                    format,
+                    agent,
+                    parent,
+                    scope_agent,
+                    session,
+                    tag,
+                    redact,
+                )?;
+            });
+        }
+        cli::Commands::Info {
+            export,
+            include_git_history,
+            redact,
+            import,
+            agent,
+        } => {
+            if let Some(output) = export {
+                let storage = open_workspace(args.read_only)?;
+                let redactor = if redact {
+                    Some(engram::redaction::Redactor::load(std::path::Path::new(
+                        ".",
+                    ))?)
+                } else {
+                    None
+                };
+                let report = cli::info::export_workspace_snapshot(
+                    &storage,
+                    &output,
+                    include_git_history,
+                    redactor.as_ref(),
+                )?;
+                print_snapshot_report("Exported", &output, &report, args.json)?;
+            } else if let Some(input) = import {
+                with_storage!(args, storage => {
+                    let report =
+                        cli::info::import_workspace_snapshot(&mut storage, &input, agent.as_deref())?;
+                    print_snapshot_report("Imported", &input, &report, args.json)?;
+                });
+            } else {
+                let storage = open_workspace(args.read_only)?;
+                cli::info::info(&storage)?;
+            }
+        }
+        cli::Commands::Benchmark {
+            iterations,
+            output,
+            compare,
+            fail_on_regression,
+        } => {
+            let storage = open_workspace(args.read_only)?;
+            cli::benchmark::handle_benchmark_command(
+                &storage,
+                iterations,
+                output,
+                compare,
+                fail_on_regression,
+                args.json,
             )?;
         }
-        cli::Commands::Info => {
-            let storage = GitRefsStorage::new(".", "default")?;
-            cli::info::info(&storage)?;
+        cli::Commands::Doctor { fix } => {
+            cli::doctor::handle_doctor_command(fix, args.json)?;
+        }
+        cli::Commands::Status { fail_on_unhealthy } => {
+            let storage = open_workspace(args.read_only)?;
+            let config = Config::load_with_defaults()?;
+            cli::status::handle_status_command(&storage, &config, fail_on_unhealthy, args.json)
+                .await?;
+        }
+        cli::Commands::Completions { command } => {
+            let storage = open_workspace(args.read_only)?;
+            cli::completions::handle_completions_command(&storage, command)?;
+        }
+        cli::Commands::Migration {
+            from,
+            to,
+            backup_only,
+            verify,
+        } => match (from, to) {
+            (Some(from), Some(to)) => handle_copy_command(&from, &to, args.dry_run, verify)?,
+            _ => handle_migration_command(args.dry_run, backup_only)?,
+        },
+        cli::Commands::Guide { command } => {
+            handle_help_command(command, args.read_only, args.json)?
         }
-        cli::Commands::Migration => handle_migration_command()?,
-        cli::Commands::Guide { command } => handle_help_command(command)?,
         cli::Commands::Skills { command } => match command {
             cli::SkillsCommands::Setup {
                 force,
@@ -166,6 +344,28 @@ async fn run() -> Result<(), EngramError> {
             cli::SkillsCommands::Show { name } => {
                 cli::show_skill(&mut std::io::stdout(), &name, None)?;
             }
+            cli::SkillsCommands::Install { source, name, dir } => {
+                let skills_dir = dir
+                    .map(std::path::PathBuf::from)
+                    .unwrap_or_else(|| cli::get_skills_path(None));
+                cli::install_skill_pack(
+                    &mut std::io::stdout(),
+                    &source,
+                    name.as_deref(),
+                    &skills_dir,
+                )?;
+            }
+            cli::SkillsCommands::Update { name, dir, dry_run } => {
+                let skills_dir = dir
+                    .map(std::path::PathBuf::from)
+                    .unwrap_or_else(|| cli::get_skills_path(None));
+                cli::update_skill_packs(
+                    &mut std::io::stdout(),
+                    name.as_deref(),
+                    &skills_dir,
+                    dry_run,
+                )?;
+            }
         },
         cli::Commands::Prompts { command } => match command {
             cli::PromptsCommands::List {
@@ -178,6 +378,23 @@ async fn run() -> Result<(), EngramError> {
             cli::PromptsCommands::Show { name } => {
                 cli::show_prompt(&name, None)?;
             }
+            cli::PromptsCommands::Render {
+                name,
+                task,
+                vars,
+                allow_missing,
+                out,
+            } => {
+                let mut context = match task {
+                    Some(task_id) => {
+                        let storage = open_workspace(args.read_only)?;
+                        cli::task_prompt_context(&storage, &task_id)?
+                    }
+                    None => std::collections::HashMap::new(),
+                };
+                context.extend(vars);
+                cli::render_prompt(&name, None, &context, allow_missing, out.as_deref())?;
+            }
             cli::PromptsCommands::Validate { category, fix } => {
                 cli::validate_prompts(category.as_deref(), fix, None)?;
             }
@@ -186,63 +403,123 @@ async fn run() -> Result<(), EngramError> {
             cli::handle_schema_command(command)?;
         }
         cli::Commands::Theory { command } => {
-            let mut storage = GitRefsStorage::new(".", "default")?;
-            handle_theory_command(command, &mut storage)?;
+            with_storage!(args, storage => {
+                handle_theory_command(command, &mut storage)?;
+            });
         }
         cli::Commands::Reflect { command } => {
-            let mut storage = GitRefsStorage::new(".", "default")?;
-            handle_reflection_command(command, &mut storage)?;
+            with_storage!(args, storage => {
+                handle_reflection_command(command, &mut storage)?;
+            });
         }
         cli::Commands::Analytics { command } => {
-            let mut storage = GitRefsStorage::new(".", "default")?;
+            let mut storage = open_workspace(args.read_only)?;
             cli::handle_analytics_command(&mut storage, command)?;
         }
         cli::Commands::Health { command } => {
-            let mut storage = GitRefsStorage::new(".", "default")?;
-            cli::health::handle_health_command(&mut storage, command)?;
+            with_storage!(args, storage => {
+                cli::health::handle_health_command(&mut storage, command)?;
+            });
+        }
+        cli::Commands::Kb { command } => {
+            let storage = open_workspace(args.read_only)?;
+            cli::kb::handle_kb_command(&storage, command, args.json)?;
+        }
+        cli::Commands::Gates { command } => {
+            let storage = open_workspace(args.read_only)?;
+            cli::gates::handle_gates_command(storage, command, args.json)?;
+        }
+        cli::Commands::Agent { command } => {
+            let storage = open_workspace(args.read_only)?;
+            cli::agent::handle_agent_command(&storage, command)?;
+        }
+        cli::Commands::Notify { command } => {
+            cli::notify::handle_notify_command(command)?;
+        }
+        cli::Commands::Workspace { command } => {
+            cli::workspace::handle_workspace_command(command, args.json)?;
+        }
+        cli::Commands::Stats { command } => {
+            let storage = open_workspace(args.read_only)?;
+            cli::stats::handle_stats_command(&storage, command)?;
+        }
+        cli::Commands::Diff { id_a, id_b, format } => {
+            let storage = open_workspace(args.read_only)?;
+            cli::diff::handle_diff_command(&storage, &id_a, &id_b, &format)?;
+        }
+        cli::Commands::Entity { command } => {
+            with_storage!(args, storage => {
+                cli::entity::handle_entity_command(&mut storage, command, args.json)?;
+            });
+        }
+        cli::Commands::Report { command } => {
+            let storage = open_workspace(args.read_only)?;
+            cli::report::handle_report_command(&storage, command)?;
+        }
+        cli::Commands::Redact { command } => {
+            let storage = open_workspace(args.read_only)?;
+            cli::redact::handle_redact_command(&storage, command, args.json)?;
+        }
+        cli::Commands::Storage { command } => {
+            let storage = open_workspace(args.read_only)?;
+            cli::storage::handle_storage_command(storage.inner(), command, args.json)?;
+        }
+        cli::Commands::Dev { command } => {
+            with_storage!(args, storage => {
+                cli::dev::handle_dev_command(&mut storage, command)?;
+            });
         }
         cli::Commands::Perkeep { command } => {
             use engram::cli::perkeep::{
                 perkeep_backup, perkeep_health, perkeep_list, perkeep_restore,
             };
-            let mut storage = GitRefsStorage::new(".", "default")?;
-            match command {
-                cli::PerkeepCommands::Backup {
-                    entity_type,
-                    include_relationships,
-                    description,
-                } => {
-                    perkeep_backup(&storage, entity_type, include_relationships, description)
+            with_storage!(args, storage => {
+                match command {
+                    cli::PerkeepCommands::Backup {
+                        entity_type,
+                        include_relationships,
+                        description,
+                        redact,
+                    } => {
+                        perkeep_backup(
+                            &storage,
+                            entity_type,
+                            include_relationships,
+                            description,
+                            redact,
+                        )
                         .await?;
-                }
-                cli::PerkeepCommands::Restore {
-                    blobref,
-                    agent,
-                    dry_run,
-                } => {
-                    perkeep_restore(&mut storage, blobref, agent, dry_run).await?;
-                }
-                cli::PerkeepCommands::List { detailed } => {
-                    perkeep_list(detailed).await?;
-                }
-                cli::PerkeepCommands::Health => {
-                    perkeep_health().await?;
-                }
-                cli::PerkeepCommands::Config {
-                    server,
-                    auth_token,
-                    save: _,
-                } => {
-                    println!("Perkeep configuration");
-                    if let Some(server) = server {
-                        println!("   Server: {}", server);
                     }
-                    if let Some(_auth_token) = auth_token {
-                        println!("   Auth token: [REDACTED]");
+                    cli::PerkeepCommands::Restore { blobref, agent } => {
+                        perkeep_restore(&mut storage, blobref, agent).await?;
+                    }
+                    cli::PerkeepCommands::List {
+                        detailed,
+                        entity_type,
+                        agent,
+                        since,
+                    } => {
+                        perkeep_list(detailed, entity_type, agent, since).await?;
+                    }
+                    cli::PerkeepCommands::Health => {
+                        perkeep_health().await?;
+                    }
+                    cli::PerkeepCommands::Config {
+                        server,
+                        auth_token,
+                        save: _,
+                    } => {
+                        println!("Perkeep configuration");
+                        if let Some(server) = server {
+                            println!("   Server: {}", server);
+                        }
+                        if let Some(_auth_token) = auth_token {
+                            println!("   Auth token: [REDACTED]");
+                        }
+                        println!("Note: Configuration via environment variables PERKEEP_SERVER and PERKEEP_AUTH_TOKEN");
                     }
-                    println!("Note: Configuration via environment variables PERKEEP_SERVER and PERKEEP_AUTH_TOKEN");
                 }
-            }
+            });
         }
     }
 
@@ -252,7 +529,17 @@ async fn run() -> Result<(), EngramError> {
 /// Handle setup commands
 fn handle_setup_command(command: cli::SetupCommands) -> Result<(), EngramError> {
     match command {
-        cli::SetupCommands::Workspace => cli::setup_workspace(None)?,
+        cli::SetupCommands::Workspace {
+            template,
+            template_file,
+        } => {
+            let template = match (template, template_file) {
+                (Some(name), _) => Some(cli::WorkspaceTemplate::builtin(&name)?),
+                (None, Some(path)) => Some(cli::WorkspaceTemplate::from_file(&path)?),
+                (None, None) => None,
+            };
+            cli::setup_workspace(None, template)?
+        }
         cli::SetupCommands::Agent {
             name,
             agent_type,
@@ -285,13 +572,6 @@ fn handle_setup_command(command: cli::SetupCommands) -> Result<(), EngramError>
     Ok(())
 }
 
-/// Handle convert command
-fn handle_convert_command(from: &str, file: &str) -> Result<(), EngramError> {
-    println!("Converting from {} file: {}", from, file);
-    println!("Conversion functionality will be implemented in a future version");
-    Ok(())
-}
-
 /// Handle test command
 fn handle_test_command() -> Result<(), EngramError> {
     println!("Engram Test Suite");
@@ -323,6 +603,7 @@ fn handle_task_command<
 >(
     command: cli::TaskCommands,
     storage: &mut S,
+    json: bool,
 ) -> Result<(), EngramError> {
     match command {
         cli::TaskCommands::Create {
@@ -339,6 +620,9 @@ fn handle_task_command<
             description_file,
             json,
             json_file,
+            allow_duplicate,
+            estimate,
+            branch,
         } => {
             cli::create_task(
                 storage,
@@ -355,10 +639,14 @@ fn handle_task_command<
                 json,
                 json_file,
                 output,
+                allow_duplicate,
+                estimate,
+                branch,
             )?;
         }
         cli::TaskCommands::List {
             agent,
+            all_agents,
             status,
             workflow_instance_id,
             workflow_state,
@@ -367,8 +655,28 @@ fn handle_task_command<
             offset,
             stale,
             stale_threshold,
+            search,
+            fuzzy,
+            suggest,
+            clear_search_history,
             output,
+            sort,
+            count,
         } => {
+            if cli::handle_search_history(
+                storage,
+                &cli::identity::resolve_agent(None::<&str>),
+                search.as_deref(),
+                suggest,
+                clear_search_history,
+            )? {
+                return Ok(());
+            }
+            let agent = if all_agents {
+                None
+            } else {
+                Some(cli::identity::resolve_agent(agent))
+            };
             cli::list_tasks(
                 storage,
                 agent.as_deref(),
@@ -380,19 +688,59 @@ fn handle_task_command<
                 offset,
                 stale,
                 stale_threshold,
+                search.as_deref(),
+                fuzzy,
+                &sort,
+                &count,
                 &output,
             )?;
         }
-        cli::TaskCommands::Show { id } => {
-            cli::show_task(storage, &id)?;
+        cli::TaskCommands::Show {
+            id,
+            no_links,
+            no_dependencies,
+            no_commits,
+            no_gates,
+            no_notes,
+        } => {
+            let sections = cli::TaskShowSections {
+                links: !no_links,
+                dependencies: !no_dependencies,
+                commits: !no_commits,
+                gates: !no_gates,
+                notes: !no_notes,
+            };
+            let id = cli::resolve_task_or_branch(storage, id)?;
+            cli::show_task(storage, &id, sections, json)?;
+        }
+        cli::TaskCommands::CriticalPath { id, output } => {
+            cli::show_critical_path(storage, &id, &output)?;
+        }
+        cli::TaskCommands::Gantt { agent, days, unit } => {
+            cli::show_gantt(storage, agent, days, &unit, json)?;
+        }
+        cli::TaskCommands::Age { apply } => {
+            cli::age_tasks_command(storage, apply, json)?;
+        }
+        cli::TaskCommands::BindBranch { id, branch } => {
+            cli::bind_branch_command(storage, &id, branch)?;
+        }
+        cli::TaskCommands::UnbindBranch { id } => {
+            cli::unbind_branch_command(storage, id)?;
         }
         cli::TaskCommands::Update {
             id,
             status,
             outcome,
             reason,
+            estimate,
         } => {
-            cli::update_task(storage, &id, &status, outcome.as_deref(), reason.as_deref())?;
+            if let Some(estimate) = estimate {
+                cli::set_task_estimate(storage, &id, &estimate)?;
+            }
+            if let Some(status) = status {
+                cli::update_task(storage, &id, &status, outcome.as_deref(), reason.as_deref())?;
+            }
         }
         cli::TaskCommands::Archive { id, reason } => {
             cli::archive_task(storage, &id, reason.as_deref())?;
@@ -400,10 +748,9 @@ fn handle_task_command<
         cli::TaskCommands::ArchiveBulk {
             older_than,
             status,
-            dry_run,
             output,
         } => {
-            cli::archive_tasks_bulk(storage, older_than, status.as_deref(), dry_run, &output)?;
+            cli::archive_tasks_bulk(storage, older_than, status.as_deref(), &output)?;
         }
         cli::TaskCommands::Resolve { id, message } => {
             cli::resolve_task(storage, &id, message.as_deref())?;
@@ -430,6 +777,45 @@ fn handle_task_command<
                 no_fail_fast,
             )?;
         }
+        cli::TaskCommands::Merge {
+            primary_id,
+            secondary_id,
+            strategy,
+            auto_detect_duplicates,
+        } => {
+            cli::handle_task_merge(
+                storage,
+                primary_id.as_deref(),
+                secondary_id.as_deref(),
+                strategy,
+                auto_detect_duplicates,
+            )?;
+        }
+        cli::TaskCommands::Recurring { command } => match command {
+            cli::RecurringTaskCommands::Create {
+                base_task,
+                schedule,
+                on_completion: _,
+                max_instances,
+                carry_over_labels,
+                agent,
+            } => {
+                cli::create_recurring_task(
+                    storage,
+                    &base_task,
+                    schedule,
+                    max_instances,
+                    carry_over_labels.unwrap_or(true),
+                    agent,
+                )?;
+            }
+            cli::RecurringTaskCommands::List => cli::list_recurring_tasks(storage)?,
+            cli::RecurringTaskCommands::Tick => {
+                if cli::tick_recurring_tasks(storage).is_empty() {
+                    println!("No recurring tasks are due.");
+                }
+            }
+        },
     }
     Ok(())
 }
@@ -454,6 +840,8 @@ fn handle_context_command<S: engram::storage::Storage>(
             content_file,
             json,
             json_file,
+            allow_duplicate,
+            no_autolink,
         } => {
             cli::create_context(
                 storage,
@@ -470,6 +858,8 @@ fn handle_context_command<S: engram::storage::Storage>(
                 content_file,
                 json,
                 json_file,
+                allow_duplicate,
+                !no_autolink,
             )?;
         }
         cli::ContextCommands::List {
@@ -478,6 +868,8 @@ fn handle_context_command<S: engram::storage::Storage>(
             limit,
             all,
             offset,
+            sort,
+            count,
         } => {
             cli::list_contexts(
                 storage,
@@ -486,23 +878,35 @@ fn handle_context_command<S: engram::storage::Storage>(
                 limit,
                 all,
                 offset,
+                &sort,
+                &count,
             )?;
         }
         cli::ContextCommands::Show { id } => {
             cli::show_context(storage, &id)?;
         }
-        cli::ContextCommands::Update { id, content } => {
-            cli::update_context(storage, &id, &content)?;
+        cli::ContextCommands::Update {
+            id,
+            content,
+            no_autolink,
+        } => {
+            cli::update_context(storage, &id, &content, !no_autolink)?;
         }
         cli::ContextCommands::Delete { id } => {
             cli::delete_context(storage, &id)?;
         }
+        cli::ContextCommands::Refresh { id } => {
+            cli::refresh_context(storage, &id)?;
+        }
+        cli::ContextCommands::Stale { format } => {
+            cli::list_stale_contexts(storage, &format)?;
+        }
     }
     Ok(())
 }
 
 /// Handle reasoning commands
-fn handle_reasoning_command<S: engram::storage::Storage>(
+fn handle_reasoning_command<S: engram::storage::Storage + engram::storage::RelationshipStorage>(
     command: engram::cli::ReasoningCommands,
     storage: &mut S,
 ) -> Result<(), EngramError> {
@@ -520,6 +924,7 @@ fn handle_reasoning_command<S: engram::storage::Storage>(
             content_file,
             json,
             json_file,
+            no_autolink,
         } => {
             cli::create_reasoning(
                 storage,
@@ -535,6 +940,7 @@ fn handle_reasoning_command<S: engram::storage::Storage>(
                 content_file,
                 json,
                 json_file,
+                !no_autolink,
             )?;
         }
         cli::ReasoningCommands::AddStep {
@@ -546,6 +952,7 @@ fn handle_reasoning_command<S: engram::storage::Storage>(
             description_file,
             conclusion_stdin,
             conclusion_file,
+            no_autolink,
         } => {
             cli::add_reasoning_step(
                 storage,
@@ -557,6 +964,7 @@ fn handle_reasoning_command<S: engram::storage::Storage>(
                 description_file,
                 conclusion_stdin,
                 conclusion_file,
+                !no_autolink,
             )?;
         }
         cli::ReasoningCommands::Conclude {
@@ -565,6 +973,7 @@ fn handle_reasoning_command<S: engram::storage::Storage>(
             confidence,
             conclusion_stdin,
             conclusion_file,
+            no_autolink,
         } => {
             cli::conclude_reasoning(
                 storage,
@@ -573,6 +982,7 @@ fn handle_reasoning_command<S: engram::storage::Storage>(
                 confidence,
                 conclusion_stdin,
                 conclusion_file,
+                !no_autolink,
             )?;
         }
         cli::ReasoningCommands::List {
@@ -581,6 +991,8 @@ fn handle_reasoning_command<S: engram::storage::Storage>(
             limit,
             all,
             offset,
+            sort,
+            count,
         } => {
             cli::list_reasoning(
                 storage,
@@ -589,8 +1001,22 @@ fn handle_reasoning_command<S: engram::storage::Storage>(
                 limit,
                 all,
                 offset,
+                &sort,
+                &count,
             )?;
         }
+        cli::ReasoningCommands::AddAlternative {
+            id,
+            description,
+            pros,
+            cons,
+            rejected_reason,
+        } => {
+            cli::add_reasoning_alternative(storage, &id, description, pros, cons, rejected_reason)?;
+        }
+        cli::ReasoningCommands::PromoteToAdr { id, agent } => {
+            cli::promote_reasoning_to_adr(storage, &id, agent)?;
+        }
         cli::ReasoningCommands::Show { id } => {
             cli::show_reasoning(storage, &id)?;
         }
@@ -639,9 +1065,10 @@ fn handle_lesson_command<S: engram::storage::Storage>(
             limit,
             all,
             offset,
+            sort,
         } => {
             cli::list_lessons(
-                storage, agent, category, domain, severity, limit, all, offset,
+                storage, agent, category, domain, severity, limit, all, offset, &sort,
             )?;
         }
         cli::LessonCommands::Show { id } => {
@@ -704,8 +1131,9 @@ fn handle_persona_command<S: engram::storage::Storage>(
             limit,
             all,
             offset,
+            sort,
         } => {
-            cli::list_personas(storage, agent, domain, tag, limit, all, offset)?;
+            cli::list_personas(storage, agent, domain, tag, limit, all, offset, &sort)?;
         }
         cli::PersonaCommands::Show { id } => {
             cli::show_persona(storage, &id)?;
@@ -760,6 +1188,7 @@ fn handle_knowledge_command<S: engram::storage::Storage>(
             content,
             knowledge_type,
             confidence,
+            clamp,
             source,
             agent,
             tags,
@@ -769,6 +1198,7 @@ fn handle_knowledge_command<S: engram::storage::Storage>(
             content_file,
             json,
             json_file,
+            allow_duplicate,
         } => {
             cli::create_knowledge(
                 storage,
@@ -776,6 +1206,7 @@ fn handle_knowledge_command<S: engram::storage::Storage>(
                 content,
                 knowledge_type,
                 confidence,
+                clamp,
                 source,
                 agent,
                 tags,
@@ -785,6 +1216,7 @@ fn handle_knowledge_command<S: engram::storage::Storage>(
                 content_file,
                 json,
                 json_file,
+                allow_duplicate,
             )?;
         }
         cli::KnowledgeCommands::List {
@@ -793,18 +1225,49 @@ fn handle_knowledge_command<S: engram::storage::Storage>(
             limit,
             all,
             offset,
+            sort,
         } => {
-            cli::list_knowledge(storage, agent, kind, limit, all, offset)?;
+            cli::list_knowledge(storage, agent, kind, limit, all, offset, &sort)?;
         }
         cli::KnowledgeCommands::Show { id } => {
             cli::show_knowledge(storage, &id)?;
         }
-        cli::KnowledgeCommands::Update { id, field, value } => {
-            cli::update_knowledge(storage, &id, &field, &value)?;
+        cli::KnowledgeCommands::Update {
+            id,
+            field,
+            value,
+            clamp,
+        } => {
+            cli::update_knowledge(storage, &id, &field, &value, clamp)?;
+        }
+        cli::KnowledgeCommands::Verify {
+            id,
+            confidence,
+            source,
+            agent,
+            clamp,
+        } => {
+            cli::verify_knowledge(storage, &id, confidence, source, agent, clamp)?;
         }
         cli::KnowledgeCommands::Delete { id } => {
             cli::delete_knowledge(storage, &id)?;
         }
+        cli::KnowledgeCommands::Merge {
+            primary_id,
+            secondary_ids,
+            confidence,
+            content,
+            tags,
+        } => {
+            cli::merge_knowledge(
+                storage,
+                &primary_id,
+                &secondary_ids,
+                &confidence,
+                &content,
+                &tags,
+            )?;
+        }
     }
     Ok(())
 }
@@ -818,11 +1281,21 @@ fn handle_session_command<S: engram::storage::Storage>(
 
     match command {
         engram::cli::SessionCommands::Start { name, auto_detect } => {
-            start_session(storage, name, auto_detect)?;
+            start_session(
+                storage,
+                engram::cli::identity::resolve_agent(name),
+                auto_detect,
+            )?;
         }
         engram::cli::SessionCommands::Status { id, metrics } => {
             show_session_status(storage, id, metrics)?;
         }
+        engram::cli::SessionCommands::Pause { id } => {
+            set_session_paused(storage, &id, true)?;
+        }
+        engram::cli::SessionCommands::Resume { id } => {
+            set_session_paused(storage, &id, false)?;
+        }
         engram::cli::SessionCommands::End {
             id,
             generate_summary,
@@ -835,15 +1308,17 @@ fn handle_session_command<S: engram::storage::Storage>(
             limit,
             all,
             offset,
+            sort,
         } => {
             list_sessions(
                 &mut std::io::stdout(),
                 storage,
-                agent,
+                engram::cli::identity::resolve_agent_filter(agent),
                 since,
                 limit,
                 all,
                 offset,
+                &sort,
             )?;
         }
         engram::cli::SessionCommands::Zombies {
@@ -858,7 +1333,18 @@ fn handle_session_command<S: engram::storage::Storage>(
             limit,
             all,
         } => {
-            summarize_sessions(&mut std::io::stdout(), storage, agent, since, limit, all)?;
+            summarize_sessions(
+                &mut std::io::stdout(),
+                storage,
+                engram::cli::identity::resolve_agent_filter(agent),
+                since,
+                limit,
+                all,
+            )?;
+        }
+        engram::cli::SessionCommands::Compare { id_a, id_b, format } => {
+            let comparison = compare_sessions(storage, &id_a, &id_b)?;
+            print_session_comparison(&mut std::io::stdout(), &comparison, &format)?;
         }
     }
 
@@ -866,7 +1352,7 @@ fn handle_session_command<S: engram::storage::Storage>(
 }
 
 /// Handle compliance commands
-fn handle_compliance_command<S: engram::storage::Storage>(
+fn handle_compliance_command<S: engram::storage::Storage + 'static>(
     command: engram::cli::ComplianceCommands,
     storage: &mut S,
 ) -> Result<(), EngramError> {
@@ -883,17 +1369,21 @@ fn handle_compliance_command<S: engram::storage::Storage>(
         cli::ComplianceCommands::List {
             agent,
             category,
+            stale,
             limit,
             all,
             offset,
+            sort,
         } => {
             cli::list_compliance(
                 storage,
                 agent.as_deref(),
                 category.as_deref(),
+                stale,
                 limit,
                 all,
                 offset,
+                &sort,
             )?;
         }
         cli::ComplianceCommands::Show { id } => {
@@ -905,6 +1395,38 @@ fn handle_compliance_command<S: engram::storage::Storage>(
         cli::ComplianceCommands::Delete { id } => {
             cli::delete_compliance(storage, &id)?;
         }
+        cli::ComplianceCommands::AddEvidence {
+            id,
+            kind,
+            reference,
+            requirement,
+            agent,
+        } => {
+            cli::add_compliance_evidence(
+                storage,
+                Path::new("."),
+                &id,
+                kind,
+                &reference,
+                requirement,
+                agent,
+            )?;
+        }
+        cli::ComplianceCommands::RemoveEvidence {
+            id,
+            evidence_id,
+            reason,
+            agent,
+        } => {
+            cli::remove_compliance_evidence(storage, &id, &evidence_id, &reason, agent)?;
+        }
+        cli::ComplianceCommands::Check {
+            task_id,
+            format,
+            fail_on_violation,
+        } => {
+            cli::run_compliance_check(storage, task_id.as_deref(), &format, fail_on_violation)?;
+        }
     }
     Ok(())
 }
@@ -924,6 +1446,7 @@ fn handle_rule_command<S: engram::storage::Storage>(
             condition,
             action,
             agent,
+            exclusive_group,
         } => {
             cli::create_rule(
                 storage,
@@ -935,6 +1458,7 @@ fn handle_rule_command<S: engram::storage::Storage>(
                 condition,
                 action,
                 agent,
+                exclusive_group,
             )?;
         }
         cli::RuleCommands::Get { id } => {
@@ -950,6 +1474,7 @@ fn handle_rule_command<S: engram::storage::Storage>(
             condition,
             action,
             status,
+            exclusive_group,
         } => {
             cli::update_rule(
                 storage,
@@ -962,6 +1487,7 @@ fn handle_rule_command<S: engram::storage::Storage>(
                 condition,
                 action,
                 status,
+                exclusive_group,
             )?;
         }
         cli::RuleCommands::Delete { id } => {
@@ -976,6 +1502,8 @@ fn handle_rule_command<S: engram::storage::Storage>(
             limit,
             offset,
             all,
+            sort,
+            count,
         } => {
             cli::list_rules(
                 storage,
@@ -987,6 +1515,8 @@ fn handle_rule_command<S: engram::storage::Storage>(
                 limit,
                 offset,
                 all,
+                &sort,
+                &count,
             )?;
         }
         cli::RuleCommands::Execute {
@@ -996,12 +1526,39 @@ fn handle_rule_command<S: engram::storage::Storage>(
         } => {
             cli::execute_rule(storage, &id, entity_id, entity_type)?;
         }
+        cli::RuleCommands::RunAll {
+            entity_id,
+            entity_type,
+            dry_run,
+            format,
+        } => {
+            cli::run_all_rules_command(storage, &entity_id, &entity_type, dry_run, &format)?;
+        }
+        cli::RuleCommands::Test {
+            id,
+            fixture,
+            entity_id,
+            entity_type,
+            format,
+        } => {
+            cli::test_rule(
+                storage,
+                &id,
+                fixture.as_deref(),
+                entity_id.as_deref(),
+                entity_type.as_deref(),
+                &format,
+            )?;
+        }
+        cli::RuleCommands::Lint { format } => {
+            cli::run_rule_lint(storage, &format)?;
+        }
     }
     Ok(())
 }
 
 /// Handle standard commands
-fn handle_standard_command<S: engram::storage::Storage>(
+fn handle_standard_command<S: engram::storage::Storage + engram::storage::RelationshipStorage>(
     command: engram::cli::StandardCommands,
     storage: &mut S,
 ) -> Result<(), EngramError> {
@@ -1059,6 +1616,8 @@ fn handle_standard_command<S: engram::storage::Storage>(
             limit,
             offset,
             all,
+            sort,
+            count,
         } => {
             cli::list_standards(
                 &mut std::io::stdout(),
@@ -1069,6 +1628,8 @@ fn handle_standard_command<S: engram::storage::Storage>(
                 limit,
                 offset,
                 all,
+                &sort,
+                &count,
             )?;
         }
         cli::StandardCommands::AddRequirement {
@@ -1089,6 +1650,16 @@ fn handle_standard_command<S: engram::storage::Storage>(
                 evidence_required,
             )?;
         }
+        cli::StandardCommands::Check {
+            id,
+            format,
+            fail_on_missing,
+        } => {
+            cli::run_standard_check(storage, &id, &format, fail_on_missing)?;
+        }
+        cli::StandardCommands::Recertify { id, format } => {
+            cli::run_standard_recertify(storage, &id, &format)?;
+        }
     }
     Ok(())
 }
@@ -1105,6 +1676,10 @@ fn handle_adr_command<S: engram::storage::Storage>(
             context,
             agent,
         } => {
+            let number = match number {
+                Some(number) => number,
+                None => cli::next_adr_number(storage)?,
+            };
             cli::create_adr(storage, title, number, context, agent)?;
         }
         cli::AdrCommands::Get { id } => {
@@ -1141,8 +1716,10 @@ fn handle_adr_command<S: engram::storage::Storage>(
             limit,
             offset,
             all,
+            sort,
+            count,
         } => {
-            cli::list_adrs(storage, status, search, limit, offset, all)?;
+            cli::list_adrs(storage, status, search, limit, offset, all, &sort, &count)?;
         }
         cli::AdrCommands::Accept {
             id,
@@ -1162,7 +1739,7 @@ fn handle_adr_command<S: engram::storage::Storage>(
 }
 
 /// Handle workflow commands
-fn handle_workflow_command<S: engram::storage::Storage>(
+fn handle_workflow_command<S: engram::storage::Storage + Clone + 'static>(
     command: engram::cli::WorkflowCommands,
     storage: &mut S,
 ) -> Result<(), EngramError> {
@@ -1205,6 +1782,8 @@ fn handle_workflow_command<S: engram::storage::Storage>(
             limit,
             offset,
             all,
+            sort,
+            count,
         } => {
             cli::list_workflows(
                 &mut std::io::stdout(),
@@ -1214,6 +1793,8 @@ fn handle_workflow_command<S: engram::storage::Storage>(
                 limit,
                 offset,
                 all,
+                &sort,
+                &count,
             )?;
         }
         cli::WorkflowCommands::AddState {
@@ -1222,8 +1803,19 @@ fn handle_workflow_command<S: engram::storage::Storage>(
             state_type,
             description,
             is_final,
+            prompt,
+            prompt_file,
         } => {
-            cli::add_state(storage, &id, name, state_type, description, is_final)?;
+            cli::add_state(
+                storage,
+                &id,
+                name,
+                state_type,
+                description,
+                is_final,
+                prompt,
+                prompt_file,
+            )?;
         }
         cli::WorkflowCommands::AddTransition {
             id,
@@ -1232,6 +1824,8 @@ fn handle_workflow_command<S: engram::storage::Storage>(
             to_state,
             transition_type,
             description,
+            actions,
+            conditions,
         } => {
             cli::add_transition(
                 storage,
@@ -1241,6 +1835,8 @@ fn handle_workflow_command<S: engram::storage::Storage>(
                 to_state,
                 transition_type,
                 description,
+                actions,
+                conditions,
             )?;
         }
         cli::WorkflowCommands::Activate { id } => {
@@ -1254,7 +1850,7 @@ fn handle_workflow_command<S: engram::storage::Storage>(
             variables,
             context_file,
         } => {
-            let storage_for_workflow = GitRefsStorage::new(".", "default")?;
+            let storage_for_workflow = storage.clone();
             cli::start_workflow_instance(
                 storage_for_workflow,
                 workflow_id,
@@ -1271,7 +1867,7 @@ fn handle_workflow_command<S: engram::storage::Storage>(
             agent,
             context_file,
         } => {
-            let storage_for_workflow = GitRefsStorage::new(".", "default")?;
+            let storage_for_workflow = storage.clone();
             cli::execute_workflow_transition(
                 storage_for_workflow,
                 instance_id,
@@ -1281,15 +1877,26 @@ fn handle_workflow_command<S: engram::storage::Storage>(
             )?;
         }
         cli::WorkflowCommands::Status { instance_id } => {
-            let storage_for_workflow = GitRefsStorage::new(".", "default")?;
+            let storage_for_workflow = storage.clone();
             cli::get_workflow_instance_status(storage_for_workflow, instance_id)?;
         }
+        cli::WorkflowCommands::Prompt { instance_id } => {
+            cli::show_workflow_prompt(storage, &instance_id)?;
+        }
+        cli::WorkflowCommands::History {
+            instance_id,
+            limit,
+            since,
+        } => {
+            let storage_for_workflow = storage.clone();
+            cli::show_workflow_history(storage_for_workflow, instance_id, limit, since)?;
+        }
         cli::WorkflowCommands::Instances {
             workflow_id,
             agent,
             running_only,
         } => {
-            let storage_for_workflow = GitRefsStorage::new(".", "default")?;
+            let storage_for_workflow = storage.clone();
             cli::list_workflow_instances(storage_for_workflow, workflow_id, agent, running_only)?;
         }
         cli::WorkflowCommands::Cancel {
@@ -1297,7 +1904,7 @@ fn handle_workflow_command<S: engram::storage::Storage>(
             agent,
             reason,
         } => {
-            let storage_for_workflow = GitRefsStorage::new(".", "default")?;
+            let storage_for_workflow = storage.clone();
             cli::cancel_workflow_instance(storage_for_workflow, instance_id, agent, reason)?;
         }
         cli::WorkflowCommands::ExecuteAction {
@@ -1311,7 +1918,7 @@ fn handle_workflow_command<S: engram::storage::Storage>(
             entity_id,
             entity_type,
         } => {
-            let storage_for_workflow = GitRefsStorage::new(".", "default")?;
+            let storage_for_workflow = storage.clone();
             cli::execute_action(
                 storage_for_workflow,
                 action_type,
@@ -1331,16 +1938,19 @@ fn handle_workflow_command<S: engram::storage::Storage>(
         } => {
             cli::query_workflow_actions(storage, workflow_id, state_id)?;
         }
+        cli::WorkflowCommands::Audit {
+            workflow_id,
+            days,
+            format,
+        } => {
+            cli::audit_workflow(storage, &workflow_id, days, &format)?;
+        }
     }
     Ok(())
 }
 
 /// Handle migration command
-fn handle_migration_command() -> Result<(), EngramError> {
-    let args: Vec<String> = std::env::args().collect();
-    let dry_run = args.contains(&String::from("--dry-run"));
-    let backup_only = args.contains(&String::from("--backup-only"));
-
+fn handle_migration_command(dry_run: bool, backup_only: bool) -> Result<(), EngramError> {
     if backup_only {
         println!("📦 Creating backup of .engram directory...");
         let migration = Migration::new(".", "default", true, backup_only)?;
@@ -1385,8 +1995,98 @@ fn handle_migration_command() -> Result<(), EngramError> {
     Ok(())
 }
 
+/// Print the outcome of `engram info --export` / `--import`
+fn print_snapshot_report(
+    action: &str,
+    path: &std::path::Path,
+    report: &cli::info::SnapshotReport,
+    json: bool,
+) -> Result<(), EngramError> {
+    if json {
+        println!("{}", serde_json::to_string_pretty(report)?);
+        return Ok(());
+    }
+    println!("📦 {} snapshot {}", action, path.display());
+    println!(
+        "  Entities: {} ({} relationships)",
+        report.entities_included, report.relationships_included
+    );
+    if report.git_history_included {
+        println!("  Git history: included");
+    }
+    println!("  Size: {} bytes", report.file_size_bytes);
+    println!("  Format version: {}", report.format_version);
+    if let Some(redactions) = &report.redactions {
+        println!("{}", redactions.summary());
+    }
+    Ok(())
+}
+
+/// Copy entities between storage backends
+fn handle_copy_command(
+    from: &str,
+    to: &str,
+    dry_run: bool,
+    verify: bool,
+) -> Result<(), EngramError> {
+    let src = engram::migration::open_backend(from, "default")?;
+    let mut dst = engram::migration::open_backend(to, "default")?;
+
+    println!("🚚 Copying entities from {} to {}", from, to);
+    if dry_run {
+        let mut preview = DryRunStorage::new(dst);
+        let report = engram::storage::copy_to(src.as_ref(), &mut preview, None, None)?;
+        println!("  ✅ Would copy: {}", report.entities_copied);
+        println!("  ⏭️  Already at destination: {}", report.entities_skipped);
+        preview.print_report(false);
+        return Ok(());
+    }
+
+    let report = engram::storage::copy_to(
+        src.as_ref(),
+        dst.as_mut(),
+        None,
+        Some(Box::new(|done, total| {
+            eprint!("\r  {}/{} entities", done, total);
+            if done == total {
+                eprintln!();
+            }
+        })),
+    )?;
+
+    println!("\n🏁 Copy Summary:");
+    println!("  ✅ Copied: {}", report.entities_copied);
+    println!(
+        "  ⏭️  Skipped (already present): {}",
+        report.entities_skipped
+    );
+    if !report.errors.is_empty() {
+        println!("  ❌ Failed: {}", report.errors.len());
+        for (id, error) in &report.errors {
+            println!("     {}: {}", id, error);
+        }
+    }
+
+    if verify {
+        let mismatches = engram::storage::verify_copy(src.as_ref(), dst.as_ref(), None)?;
+        if mismatches.is_empty() {
+            println!("🔒 Verified: every source entity matches its copy");
+        } else {
+            for (id, problem) in &mismatches {
+                println!("  ⚠️  {}: {}", id, problem);
+            }
+            return Err(EngramError::Validation(format!(
+                "Verification failed for {} entities",
+                mismatches.len()
+            )));
+        }
+    }
+
+    Ok(())
+}
+
 /// Handle sandbox commands
-fn handle_sandbox_command<S: engram::storage::Storage>(
+async fn handle_sandbox_command<S: engram::storage::Storage>(
     command: engram::cli::SandboxCommands,
     storage: &mut S,
 ) -> Result<(), EngramError> {
@@ -1408,8 +2108,9 @@ fn handle_sandbox_command<S: engram::storage::Storage>(
             level,
             agent,
             json,
+            sort,
         } => {
-            list_sandboxes(storage, agent_id, level, agent, json)?;
+            list_sandboxes(storage, agent_id, level, agent, json, &sort)?;
         }
         engram::cli::SandboxCommands::Get { id, json } => {
             get_sandbox(storage, id, json)?;
@@ -1419,9 +2120,17 @@ fn handle_sandbox_command<S: engram::storage::Storage>(
             level,
             stdin,
             file,
+            allow_paths,
+            deny_paths,
+            path_operation,
             json,
         } => {
-            update_sandbox(storage, id, level, stdin, file, json)?;
+            let path_rules = engram::cli::PathRuleUpdate {
+                operation: path_operation,
+                allow: allow_paths,
+                deny: deny_paths,
+            };
+            update_sandbox(storage, id, level, stdin, file, path_rules, json)?;
         }
         engram::cli::SandboxCommands::Delete { id, force } => {
             delete_sandbox(storage, id, force)?;
@@ -1430,6 +2139,7 @@ fn handle_sandbox_command<S: engram::storage::Storage>(
             agent_id,
             operation,
             resource_type,
+            path,
             stdin,
             file,
             json,
@@ -1439,10 +2149,12 @@ fn handle_sandbox_command<S: engram::storage::Storage>(
                 agent_id,
                 operation,
                 resource_type,
+                path,
                 stdin,
                 file,
                 json,
-            )?;
+            )
+            .await?;
         }
         engram::cli::SandboxCommands::Stats { agent_id, json } => {
             show_stats(storage, agent_id, json)?;
@@ -1457,6 +2169,25 @@ fn handle_sandbox_command<S: engram::storage::Storage>(
         } => {
             reset_sandbox(storage, agent_id, force, json)?;
         }
+        engram::cli::SandboxCommands::Simulate {
+            agent,
+            level,
+            from_audit,
+            requests,
+            top,
+            json,
+        } => {
+            let report = simulate_sandbox(
+                storage,
+                agent,
+                &level,
+                &from_audit,
+                requests.as_deref().map(std::path::Path::new),
+                top,
+            )
+            .await?;
+            print_simulation_report(&mut std::io::stdout(), &report, json)?;
+        }
     }
 
     Ok(())
@@ -1479,6 +2210,8 @@ fn handle_escalation_command<S: engram::storage::Storage>(
             priority,
             impact,
             reviewer,
+            notify: _,
+            no_notify,
             stdin,
             file,
             json,
@@ -1496,6 +2229,7 @@ fn handle_escalation_command<S: engram::storage::Storage>(
                 stdin,
                 file,
                 json,
+                !no_notify,
             )?;
         }
         engram::cli::EscalationCommands::List {
@@ -1507,6 +2241,7 @@ fn handle_escalation_command<S: engram::storage::Storage>(
             actionable_only,
             agent,
             json,
+            sort,
         } => {
             list_escalations(
                 storage,
@@ -1518,6 +2253,7 @@ fn handle_escalation_command<S: engram::storage::Storage>(
                 actionable_only,
                 agent,
                 json,
+                &sort,
             )?;
         }
         engram::cli::EscalationCommands::Get { id, json } => {
@@ -1562,6 +2298,24 @@ fn handle_escalation_command<S: engram::storage::Storage>(
         engram::cli::EscalationCommands::Cleanup { apply, json } => {
             cleanup_escalations(storage, apply, json)?;
         }
+        engram::cli::EscalationCommands::ExportPending { out } => {
+            let count = export_pending_escalations(storage, std::path::Path::new(&out))?;
+            println!("Exported {} pending escalation(s) to {}", count, out);
+        }
+        engram::cli::EscalationCommands::Apply {
+            file,
+            reviewer_id,
+            reviewer_name,
+            json,
+        } => {
+            apply_escalation_file(
+                storage,
+                std::path::Path::new(&file),
+                reviewer_id,
+                reviewer_name,
+                json,
+            )?;
+        }
         engram::cli::EscalationCommands::Stats {
             agent_id,
             days,
@@ -1601,8 +2355,17 @@ fn handle_escalation_command<S: engram::storage::Storage>(
     Ok(())
 }
 /// Handle help command
-fn handle_help_command(command: Option<cli::HelpCommands>) -> Result<(), EngramError> {
+fn handle_help_command(
+    command: Option<cli::HelpCommands>,
+    read_only: Option<ReadOnlyMode>,
+    json_output: bool,
+) -> Result<(), EngramError> {
     match command {
+        Some(cli::HelpCommands::Suggestions { json }) => {
+            let storage = open_workspace(read_only)?;
+            let config = cli::auto_guide::AutoGuideConfig::load(Path::new("."))?;
+            cli::auto_guide::list_suggestions(&storage, &config, json || json_output)?;
+        }
         Some(cli::HelpCommands::Onboarding) => {
             println!("ENGRAM - Task Memory System for LLM Coding Agents");
             println!("==================================================");
@@ -1776,8 +2539,9 @@ fn handle_theory_command<S: engram::storage::Storage>(
             limit,
             all,
             offset,
+            sort,
         } => {
-            list_theories(storage, agent, domain, limit, all, offset)?;
+            list_theories(storage, agent, domain, limit, all, offset, &sort)?;
         }
         cli::TheoryCommands::Show { id, show_metrics } => {
             show_theory(storage, &id, show_metrics)?;
@@ -1841,6 +2605,7 @@ fn handle_reflection_command<S: engram::storage::Storage>(
             limit,
             all,
             offset,
+            sort,
         } => {
             list_reflections(
                 storage,
@@ -1850,6 +2615,7 @@ fn handle_reflection_command<S: engram::storage::Storage>(
                 limit,
                 all,
                 offset,
+                &sort,
             )?;
         }
         cli::StateReflectionCommands::Show { id } => {
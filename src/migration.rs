@@ -2,6 +2,22 @@
 //!
 //! This module provides tools for migrating data from the dual-repository
 //! architecture (.engram/ directory) to the Git refs storage architecture.
+//!
+//! # On-disk differences
+//!
+//! The directory backend kept a separate repository in `.engram/` with one
+//! file per entity at `.engram/<entity_type>/<entity_id>.json`, holding a
+//! [`MemoryEntity`]. [`GitRefsStorage`] writes into the workspace's own
+//! repository instead: each entity is a blob holding a
+//! [`GenericEntity`](crate::entities::GenericEntity), pointed to by
+//! `refs/engram/<entity_type>/<entity_id>`, with no working-tree files.
+//!
+//! - The entity type comes from the directory name, not the file contents.
+//! - `.engram/session/` and dot-directories are not migrated.
+//! - `.engram/` is left in place and copied to `.engram_backup_<timestamp>/`;
+//!   remove it once the migrated refs have been checked.
+//! - Refs are not pushed by a default `git push`; share them with
+//!   `engram sync`.
 
 use crate::error::EngramError;
 use crate::storage::{memory_entity::MemoryEntity, GitRefsStorage, Storage};
@@ -102,7 +118,7 @@ impl Migration {
         let mut entity_dirs = Vec::new();
 
         let entries = fs::read_dir(&self.source_path).map_err(|e| {
-            EngramError::NotFound(format!("Failed to read source directory: {}", e))
+            EngramError::InvalidOperation(format!("Failed to read source directory: {}", e))
         })?;
 
         for entry in entries {
@@ -261,8 +277,9 @@ impl Migration {
         let engram_path = PathBuf::from(workspace_path).join(".engram");
 
         if !engram_path.exists() {
-            return Err(EngramError::NotFound(
-                "No .engram directory found. Nothing to migrate.".to_string(),
+            return Err(EngramError::not_found(
+                "directory",
+                engram_path.display().to_string(),
             ));
         }
 
@@ -290,9 +307,30 @@ impl Migration {
     }
 }
 
+/// Open a storage backend from a `--from`/`--to` spec: `git` (the current
+/// workspace) or `git:<path>`
+pub fn open_backend(spec: &str, agent: &str) -> Result<Box<dyn Storage>, EngramError> {
+    let (kind, path) = match spec.split_once(':') {
+        Some((kind, path)) => (kind, path),
+        None => (spec, "."),
+    };
+    match kind {
+        "git" => Ok(Box::new(GitRefsStorage::new(path, agent)?)),
+        "sqlite" => Err(EngramError::InvalidOperation(
+            "SQLite entity storage is not available in this build; supported backends: git, git:<path>"
+                .to_string(),
+        )),
+        other => Err(EngramError::Validation(format!(
+            "Unknown storage backend '{}'. Expected git or git:<path>",
+            other
+        ))),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::entities::{Context, ContextRelevance, Entity, Session, Task, TaskPriority};
     use crate::storage::memory_entity::MemoryEntity;
     use std::collections::HashMap;
 
@@ -306,9 +344,41 @@ mod tests {
     }
 
     fn create_valid_memory_entity_json(id: &str, entity_type: &str) -> String {
-        let mut data = HashMap::new();
-        data.insert("title".to_string(), serde_json::json!("Test Entity"));
-        data.insert("value".to_string(), serde_json::json!(42));
+        // Registered types are validated on store, so they need real entity data
+        let typed = match entity_type {
+            "task" => Some(
+                Task::new(
+                    "Test Entity".to_string(),
+                    "Migrated task".to_string(),
+                    "test-agent".to_string(),
+                    TaskPriority::Medium,
+                    None,
+                )
+                .to_generic(),
+            ),
+            "context" => Some(
+                Context::new(
+                    "Test Entity".to_string(),
+                    "Migrated context".to_string(),
+                    "test".to_string(),
+                    ContextRelevance::Medium,
+                    "test-agent".to_string(),
+                )
+                .to_generic(),
+            ),
+            "session" => Some(
+                Session::new("Test Entity".to_string(), "test-agent".to_string(), vec![])
+                    .to_generic(),
+            ),
+            _ => None,
+        };
+        let data: HashMap<String, serde_json::Value> = match typed {
+            Some(generic) => serde_json::from_value(generic.data).unwrap(),
+            None => HashMap::from([
+                ("title".to_string(), serde_json::json!("Test Entity")),
+                ("value".to_string(), serde_json::json!(42)),
+            ]),
+        };
         let entity = MemoryEntity::new(
             id.to_string(),
             entity_type.to_string(),
@@ -675,11 +745,10 @@ mod tests {
         setup_git_repo(tmp.path());
 
         let result = Migration::validate_migration_readiness(workspace);
-        assert!(result.is_err());
-        assert!(result
-            .unwrap_err()
-            .to_string()
-            .contains("No .engram directory found"));
+        assert!(matches!(
+            result,
+            Err(EngramError::NotFound { ref entity_type, .. }) if entity_type == "directory"
+        ));
     }
 
     #[test]
@@ -722,6 +791,15 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_open_backend_specs() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let spec = format!("git:{}", tmp.path().display());
+        assert!(open_backend(&spec, "test-agent").is_ok());
+        assert!(open_backend("sqlite:engram.db", "test-agent").is_err());
+        assert!(open_backend("postgres", "test-agent").is_err());
+    }
+
     #[test]
     fn test_migration_stats_default() {
         let stats = MigrationStats::default();
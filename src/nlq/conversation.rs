@@ -0,0 +1,150 @@
+//! Conversation state carried between natural language queries
+//!
+//! Each query is classified on its own, so a follow-up such as "what depends
+//! on it?" has nothing to refer to. [`ConversationContext`] remembers the
+//! task and agent the previous turns were about and rewrites references to
+//! them into explicit entities before the next query is analyzed.
+
+use crate::nlq::ProcessedQuery;
+use regex::Regex;
+use serde_json::Value;
+
+/// Task and agent the conversation is currently about
+#[derive(Debug, Default)]
+pub struct ConversationContext {
+    task_id: Option<String>,
+    agent: Option<String>,
+}
+
+impl ConversationContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Task referenced by the most recent turn that named one
+    pub fn task_id(&self) -> Option<&str> {
+        self.task_id.as_deref()
+    }
+
+    /// Agent named by the most recent turn that named one
+    pub fn agent(&self) -> Option<&str> {
+        self.agent.as_deref()
+    }
+
+    /// Rewrite references to earlier turns into explicit entities
+    ///
+    /// "it", "that task" and "this task" become `task <id>`; "that agent",
+    /// "this agent" and "the same agent" become `agent <name>`. Queries that
+    /// name their own task or agent are left alone.
+    pub fn resolve(&self, query: &str) -> String {
+        let mut resolved = query.to_string();
+
+        if let Some(task_id) = &self.task_id {
+            if !resolved.contains(task_id.as_str()) && !contains_uuid(&resolved) {
+                let re =
+                    Regex::new(r"(?i)\b(?:(?:that|this|the same) task|it)\b").expect("valid regex");
+                resolved = re
+                    .replace_all(&resolved, format!("task {}", task_id).as_str())
+                    .into_owned();
+            }
+        }
+
+        if let Some(agent) = &self.agent {
+            let re = Regex::new(r"(?i)\b(?:that|this|the same) agent\b").expect("valid regex");
+            resolved = re
+                .replace_all(&resolved, format!("agent {}", agent).as_str())
+                .into_owned();
+        }
+
+        resolved
+    }
+
+    /// Remember what a finished turn was about
+    ///
+    /// A task is taken from the query itself or, failing that, from a result
+    /// holding exactly one task. Agents are only taken from an explicit
+    /// `agent <name>` in the query.
+    pub fn remember(&mut self, query: &ProcessedQuery, data: &Value) {
+        let task_id = query
+            .entities
+            .iter()
+            .find(|e| e.entity_type == "task_id")
+            .map(|e| e.value.clone())
+            .or_else(|| single_task_id(data));
+        if task_id.is_some() {
+            self.task_id = task_id;
+        }
+
+        let explicit_agent = Regex::new(r"(?i)\bagent\s+([a-zA-Z0-9_-]+)").expect("valid regex");
+        if let Some(captures) = explicit_agent.captures(&query.original_query) {
+            self.agent = Some(captures[1].to_string());
+        }
+    }
+}
+
+fn contains_uuid(text: &str) -> bool {
+    Regex::new(r"[a-f0-9]{8}-[a-f0-9]{4}-[a-f0-9]{4}-[a-f0-9]{4}-[a-f0-9]{12}")
+        .expect("valid regex")
+        .is_match(text)
+}
+
+fn single_task_id(data: &Value) -> Option<String> {
+    if let Some(id) = data.pointer("/task/id").and_then(Value::as_str) {
+        return Some(id.to_string());
+    }
+    match data.get("tasks").and_then(Value::as_array) {
+        Some(tasks) if tasks.len() == 1 => tasks[0]
+            .get("id")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nlq::NLQEngine;
+    use serde_json::json;
+
+    const TASK_ID: &str = "0b0e8a57-7a3c-4a55-9a38-5f2f4c1e9d11";
+
+    #[test]
+    fn test_follow_up_refers_to_previous_task_and_agent() {
+        let engine = NLQEngine::new();
+        let mut conversation = ConversationContext::new();
+
+        let first = engine
+            .analyze(&format!("show task {} for agent alice", TASK_ID), None)
+            .unwrap();
+        conversation.remember(&first, &json!({}));
+        assert_eq!(conversation.task_id(), Some(TASK_ID));
+        assert_eq!(conversation.agent(), Some("alice"));
+
+        assert_eq!(
+            conversation.resolve("what depends on it?"),
+            format!("what depends on task {}?", TASK_ID)
+        );
+        assert_eq!(
+            conversation.resolve("list tasks for that agent"),
+            "list tasks for agent alice"
+        );
+        // A query naming its own task is not rewritten
+        let other = "show task 11111111-2222-3333-4444-555555555555";
+        assert_eq!(conversation.resolve(other), other);
+    }
+
+    #[test]
+    fn test_remembers_single_task_result() {
+        let engine = NLQEngine::new();
+        let mut conversation = ConversationContext::new();
+        let query = engine.analyze("show my tasks", None).unwrap();
+
+        conversation.remember(&query, &json!({"tasks": [{"id": "a"}, {"id": "b"}]}));
+        assert_eq!(conversation.task_id(), None);
+
+        conversation.remember(&query, &json!({"tasks": [{"id": TASK_ID}]}));
+        assert_eq!(conversation.task_id(), Some(TASK_ID));
+        assert_eq!(conversation.agent(), None);
+    }
+}
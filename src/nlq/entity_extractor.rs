@@ -3,8 +3,25 @@ use crate::nlq::ExtractedEntity;
 use regex::Regex;
 use std::collections::HashMap;
 
+/// Words that make "the ... task" a position rather than a title
+const NON_TITLE_WORDS: [&str; 9] = [
+    "same",
+    "next",
+    "last",
+    "current",
+    "first",
+    "previous",
+    "whole",
+    "entire",
+    "following",
+];
+
 pub struct EntityExtractor {
     extractors: HashMap<String, Vec<Regex>>,
+    word: Regex,
+    id_prefix: Regex,
+    quoted_title: Regex,
+    described_title: Regex,
 }
 
 impl EntityExtractor {
@@ -55,7 +72,14 @@ impl EntityExtractor {
             ],
         );
 
-        Self { extractors }
+        Self {
+            extractors,
+            word: Regex::new(r"[A-Za-z0-9-]+").unwrap(),
+            id_prefix: Regex::new(r"^[a-f0-9]{8}[a-f0-9-]*$").unwrap(),
+            quoted_title: Regex::new(r#"(?:^|[\s(])["']([^"']+)["']"#).unwrap(),
+            described_title: Regex::new(r"(?i)\bthe\s+([a-z0-9_-]+(?:\s+[a-z0-9_-]+)?)\s+task\b")
+                .unwrap(),
+        }
     }
 
     pub fn extract(&self, query: &str) -> Result<Vec<ExtractedEntity>, EngramError> {
@@ -75,10 +99,58 @@ impl EntityExtractor {
                 }
             }
         }
+        entities.extend(self.extract_task_references(query));
 
         Ok(entities)
     }
 
+    /// Task references that only storage can turn into IDs
+    ///
+    /// `task_id_prefix` entities are hex tokens of at least 8 characters
+    /// that are shorter than a full UUID, scored by how much of one they
+    /// cover. `title` entities come from quoted strings and from phrases
+    /// like "the login task"; quoted ones are more certain.
+    fn extract_task_references(&self, query: &str) -> Vec<ExtractedEntity> {
+        let mut references = Vec::new();
+
+        for token in self.word.find_iter(query) {
+            let value = token.as_str();
+            if value.len() < 36
+                && self.id_prefix.is_match(value)
+                && value.chars().any(|c| c.is_ascii_digit())
+            {
+                references.push(ExtractedEntity {
+                    entity_type: "task_id_prefix".to_string(),
+                    value: value.to_string(),
+                    confidence: 0.5 + 0.4 * value.len() as f64 / 36.0,
+                    position: Some((token.start(), token.end())),
+                });
+            }
+        }
+
+        for (regex, confidence) in [(&self.quoted_title, 0.7), (&self.described_title, 0.5)] {
+            for captures in regex.captures_iter(query) {
+                let Some(matched) = captures.get(1) else {
+                    continue;
+                };
+                let title = matched.as_str().trim();
+                let first_word = title.split_whitespace().next().unwrap_or_default();
+                if title.is_empty() || NON_TITLE_WORDS.contains(&first_word.to_lowercase().as_str())
+                {
+                    continue;
+                }
+                references.push(ExtractedEntity {
+                    entity_type: "title".to_string(),
+                    value: title.to_string(),
+                    confidence,
+                    position: Some((matched.start(), matched.end())),
+                });
+            }
+        }
+
+        references
+    }
+
     pub fn extract_specific(
         &self,
         query: &str,
@@ -99,6 +171,12 @@ impl EntityExtractor {
                     }
                 }
             }
+        } else {
+            entities.extend(
+                self.extract_task_references(query)
+                    .into_iter()
+                    .filter(|e| e.entity_type == entity_type),
+            );
         }
 
         Ok(entities)
@@ -160,4 +238,43 @@ mod tests {
         assert_eq!(entities[0].entity_type, "agent");
         assert_eq!(entities[0].value, "bob");
     }
+
+    #[test]
+    fn test_task_reference_extraction() {
+        let extractor = EntityExtractor::new();
+        let full = "550e8400-e29b-41d4-a716-446655440000";
+
+        let entities = extractor
+            .extract(&format!("what depends on task 550e8400 and {}", full))
+            .unwrap();
+        let prefixes: Vec<_> = entities
+            .iter()
+            .filter(|e| e.entity_type == "task_id_prefix")
+            .collect();
+        assert_eq!(prefixes.len(), 1);
+        assert_eq!(prefixes[0].value, "550e8400");
+        assert_eq!(prefixes[0].position, Some((21, 29)));
+        let longer = extractor
+            .extract_specific("show task 550e8400-e29b", "task_id_prefix")
+            .unwrap();
+        assert!(longer[0].confidence > prefixes[0].confidence);
+        // Plain words made of hex letters are not prefixes
+        assert!(extractor
+            .extract_specific("show task deadbeefcafe", "task_id_prefix")
+            .unwrap()
+            .is_empty());
+
+        let titles = extractor
+            .extract_specific("what's blocking \"fix login\" and the deploy task", "title")
+            .unwrap();
+        let values: Vec<(&str, f64)> = titles
+            .iter()
+            .map(|e| (e.value.as_str(), e.confidence))
+            .collect();
+        assert_eq!(values, [("fix login", 0.7), ("deploy", 0.5)]);
+        assert!(extractor
+            .extract_specific("show the same task", "title")
+            .unwrap()
+            .is_empty());
+    }
 }
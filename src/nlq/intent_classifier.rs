@@ -1,11 +1,54 @@
 use crate::error::EngramError;
-use crate::nlq::QueryIntent;
+use crate::nlq::{ExtractedEntity, QueryIntent};
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Queries classified with less confidence than this are answered with
+/// suggestions instead of a possibly wrong result
+pub const SUGGESTION_THRESHOLD: f64 = 0.5;
+
+/// Structured intents, most specific first
+const INTENT_ORDER: [QueryIntent; 9] = [
+    QueryIntent::ShowTaskDetails,
+    QueryIntent::FindRelationships,
+    QueryIntent::SearchContext,
+    QueryIntent::AnalyzeWorkflow,
+    QueryIntent::SearchSkills,
+    QueryIntent::ListSkills,
+    QueryIntent::SearchPrompts,
+    QueryIntent::ListPrompts,
+    QueryIntent::ListTasks, // More general, check last
+];
+
+/// Confidence of a free-text search that no structured intent competes with
+const FULL_TEXT_CONFIDENCE: f64 = 0.6;
+
+/// Weight of each keyword hinting at an intent no pattern matched
+const KEYWORD_WEIGHT: f64 = 0.25;
+
+/// Weight of each kind of extracted entity an intent can use
+const ENTITY_WEIGHT: f64 = 0.15;
+
+/// Penalty for an intent missing an entity it cannot run without
+const MISSING_ENTITY_PENALTY: f64 = 0.3;
+
+/// Rival interpretations weaker than this don't lower the confidence of the
+/// chosen one, so a single stray keyword isn't treated as ambiguity
+const RIVAL_THRESHOLD: f64 = 0.35;
+
+/// One possible reading of a query, with a phrasing that classifies as it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IntentCandidate {
+    pub intent: QueryIntent,
+    pub confidence: f64,
+    pub example: String,
+}
+
 /// Intent classifier using pattern matching for natural language queries
 pub struct IntentClassifier {
     patterns: HashMap<QueryIntent, Vec<Regex>>,
+    keywords: HashMap<QueryIntent, Vec<Regex>>,
 }
 
 impl IntentClassifier {
@@ -38,6 +81,8 @@ impl IntentClassifier {
             vec![
                 Regex::new(r"(?i)^(show|get|details?\s+of)\s+task\s+").unwrap(),
                 Regex::new(r"(?i)^what\s+(is|about)\s+task\s+").unwrap(),
+                Regex::new(r"(?i)^(show|get|details?\s+of)\s+the\s+[\w-]+(\s+[\w-]+)?\s+task\b")
+                    .unwrap(),
             ],
         );
 
@@ -47,6 +92,7 @@ impl IntentClassifier {
             vec![
                 Regex::new(r"(?i)what\s+tasks?\s+(depend\s+on|are\s+related\s+to)").unwrap(),
                 Regex::new(r"(?i)(dependencies|dependents)\s+(of|for)\s+").unwrap(),
+                Regex::new(r"(?i)^what\s+(depends\s+on|blocks)\s+").unwrap(),
                 Regex::new(r"(?i)^(show|find|get)\s+(relationship|dependencies|dependents)")
                     .unwrap(),
             ],
@@ -117,7 +163,40 @@ impl IntentClassifier {
             ],
         );
 
-        Self { patterns }
+        // Keywords that hint at an intent when none of its patterns match
+        let keyword = |word: &str| Regex::new(&format!(r"(?i)\b{}\b", word)).unwrap();
+        let mut keywords = HashMap::new();
+        keywords.insert(
+            QueryIntent::ListTasks,
+            vec![keyword("tasks"), keyword("todo"), keyword("assigned")],
+        );
+        keywords.insert(
+            QueryIntent::ShowTaskDetails,
+            vec![keyword("details?"), keyword("describe")],
+        );
+        keywords.insert(
+            QueryIntent::FindRelationships,
+            vec![
+                keyword(r"depend\w*"),
+                keyword(r"blocked\s+by"),
+                keyword("blocks"),
+                keyword(r"related|relationships?"),
+            ],
+        );
+        keywords.insert(
+            QueryIntent::SearchContext,
+            vec![keyword("context"), keyword("background"), keyword("notes")],
+        );
+        keywords.insert(
+            QueryIntent::AnalyzeWorkflow,
+            vec![keyword("workflows?"), keyword("stages?")],
+        );
+        keywords.insert(QueryIntent::ListSkills, vec![keyword("skills")]);
+        keywords.insert(QueryIntent::SearchSkills, vec![keyword("skill")]);
+        keywords.insert(QueryIntent::ListPrompts, vec![keyword("prompts")]);
+        keywords.insert(QueryIntent::SearchPrompts, vec![keyword("prompt")]);
+
+        Self { patterns, keywords }
     }
 
     /// Classify the intent of a natural language query
@@ -125,19 +204,7 @@ impl IntentClassifier {
         let trimmed_query = query.trim();
 
         // Check patterns in order of specificity (most specific first)
-        let intent_order = vec![
-            QueryIntent::ShowTaskDetails,
-            QueryIntent::FindRelationships,
-            QueryIntent::SearchContext,
-            QueryIntent::AnalyzeWorkflow,
-            QueryIntent::SearchSkills,
-            QueryIntent::ListSkills,
-            QueryIntent::SearchPrompts,
-            QueryIntent::ListPrompts,
-            QueryIntent::ListTasks, // More general, check last
-        ];
-
-        for intent in intent_order {
+        for intent in INTENT_ORDER {
             if let Some(regexes) = self.patterns.get(&intent) {
                 for regex in regexes {
                     if regex.is_match(trimmed_query) {
@@ -158,21 +225,150 @@ impl IntentClassifier {
 
     /// Get confidence score for a classification (0.0 to 1.0)
     pub fn get_confidence(&self, query: &str, intent: &QueryIntent) -> f64 {
-        let trimmed_query = query.trim();
+        self.score(query, intent, &[])
+    }
 
-        if let Some(regexes) = self.patterns.get(intent) {
-            for regex in regexes {
-                if regex.is_match(trimmed_query) {
-                    // Simple confidence based on pattern specificity
-                    return if regex.as_str().len() > 20 { 0.9 } else { 0.7 };
-                }
-            }
+    /// Confidence (0.0 to 1.0) that `intent` is what `query` asks for
+    ///
+    /// A matching pattern scores by how many words it covers; otherwise
+    /// keywords hinting at the intent count. Extracted entities the intent
+    /// can use raise the score, a missing task ID lowers it, and so does
+    /// the strongest other intent the query hints at without matching its
+    /// patterns. Overlapping patterns are settled by [`Self::classify`]'s
+    /// order instead.
+    pub fn score(&self, query: &str, intent: &QueryIntent, entities: &[ExtractedEntity]) -> f64 {
+        let query = query.trim();
+        let own = match intent {
+            QueryIntent::Unknown => return 0.0,
+            QueryIntent::FullTextSearch => FULL_TEXT_CONFIDENCE,
+            _ => self.signal(query, intent, entities),
+        };
+        let rival = INTENT_ORDER
+            .iter()
+            .filter(|other| *other != intent && self.pattern_strength(query, other) == 0.0)
+            .map(|other| self.signal(query, other, entities))
+            .filter(|score| *score >= RIVAL_THRESHOLD)
+            .fold(0.0, f64::max);
+
+        (own - rival).clamp(0.0, 1.0)
+    }
+
+    /// Structured interpretations of `query`, most likely first
+    ///
+    /// Only intents the query hints at are included, and only when an
+    /// example phrasing can be built from the extracted entities.
+    pub fn candidates(&self, query: &str, entities: &[ExtractedEntity]) -> Vec<IntentCandidate> {
+        let query = query.trim();
+        let mut candidates: Vec<IntentCandidate> = INTENT_ORDER
+            .iter()
+            .filter(|intent| self.signal(query, intent, entities) > 0.0)
+            .filter_map(|intent| {
+                Some(IntentCandidate {
+                    intent: intent.clone(),
+                    confidence: self.signal(query, intent, entities).clamp(0.0, 1.0),
+                    example: example_phrasing(intent, query, entities)?,
+                })
+            })
+            .collect();
+        candidates.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+        candidates
+    }
+
+    /// Unadjusted evidence for `intent`; zero when nothing in the query
+    /// points at it
+    fn signal(&self, query: &str, intent: &QueryIntent, entities: &[ExtractedEntity]) -> f64 {
+        let pattern = self.pattern_strength(query, intent);
+        let evidence = if pattern > 0.0 {
+            pattern
+        } else {
+            let hits = self
+                .keywords
+                .get(intent)
+                .into_iter()
+                .flatten()
+                .filter(|regex| regex.is_match(query))
+                .count()
+                .min(2);
+            KEYWORD_WEIGHT * hits as f64
+        };
+        if evidence == 0.0 {
+            return 0.0;
         }
 
-        0.0
+        let supporting = supporting_entities(intent)
+            .iter()
+            .filter(|kind| entities.iter().any(|e| e.entity_type == **kind))
+            .count()
+            .min(2);
+        // The supporting entities of intents needing a task are references to it
+        let missing = requires_task_id(intent) && supporting == 0;
+
+        evidence + ENTITY_WEIGHT * supporting as f64
+            - if missing { MISSING_ENTITY_PENALTY } else { 0.0 }
+    }
+
+    /// Strength of the best matching pattern for `intent`, growing with the
+    /// number of words it covers; zero when none match
+    fn pattern_strength(&self, query: &str, intent: &QueryIntent) -> f64 {
+        self.patterns
+            .get(intent)
+            .into_iter()
+            .flatten()
+            .filter_map(|regex| regex.find(query))
+            .map(|m| 0.5 + 0.1 * m.as_str().split_whitespace().count().min(4) as f64)
+            .fold(0.0, f64::max)
+    }
+}
+
+/// Entity types an intent makes use of
+fn supporting_entities(intent: &QueryIntent) -> &'static [&'static str] {
+    match intent {
+        QueryIntent::ListTasks => &["agent", "status", "priority", "time_period"],
+        QueryIntent::ShowTaskDetails | QueryIntent::FindRelationships => {
+            &["task_id", "task_id_prefix", "title"]
+        }
+        _ => &[],
     }
 }
 
+fn requires_task_id(intent: &QueryIntent) -> bool {
+    matches!(
+        intent,
+        QueryIntent::ShowTaskDetails | QueryIntent::FindRelationships
+    )
+}
+
+fn entity_value<'a>(entities: &'a [ExtractedEntity], entity_type: &str) -> Option<&'a str> {
+    entities
+        .iter()
+        .find(|e| e.entity_type == entity_type)
+        .map(|e| e.value.as_str())
+}
+
+/// A query that classifies as `intent`, filled in from `entities`
+fn example_phrasing(
+    intent: &QueryIntent,
+    query: &str,
+    entities: &[ExtractedEntity],
+) -> Option<String> {
+    let task_id = entity_value(entities, "task_id");
+    Some(match intent {
+        QueryIntent::ListTasks => match entity_value(entities, "agent") {
+            Some(agent) => format!("list tasks for agent {}", agent),
+            None => "list tasks".to_string(),
+        },
+        QueryIntent::ShowTaskDetails => format!("show task {}", task_id?),
+        QueryIntent::FindRelationships => format!("show relationships of task {}", task_id?),
+        QueryIntent::SearchContext => format!("find context about {}", query),
+        QueryIntent::AnalyzeWorkflow => "show workflow status".to_string(),
+        QueryIntent::ListSkills => "list skills".to_string(),
+        QueryIntent::SearchSkills => format!("find a skill for {}", query),
+        QueryIntent::ListPrompts => "list prompts".to_string(),
+        QueryIntent::SearchPrompts => format!("find a prompt for {}", query),
+        QueryIntent::FullTextSearch | QueryIntent::Unknown => return None,
+    })
+}
+
 impl Default for IntentClassifier {
     fn default() -> Self {
         Self::new()
@@ -258,4 +454,74 @@ mod tests {
         let no_confidence = classifier.get_confidence("show my tasks", &QueryIntent::SearchContext);
         assert_eq!(no_confidence, 0.0);
     }
+
+    fn entities(query: &str) -> Vec<ExtractedEntity> {
+        crate::nlq::EntityExtractor::new().extract(query).unwrap()
+    }
+
+    #[test]
+    fn test_clear_queries_score_above_threshold() {
+        let classifier = IntentClassifier::new();
+        let task = "task 0b0e8a57-7a3c-4a55-9a38-5f2f4c1e9d11";
+
+        for query in [
+            "show my tasks".to_string(),
+            "list tasks for agent alice".to_string(),
+            format!("show {}", task),
+            format!("what tasks depend on {}", task),
+            "find context about authentication".to_string(),
+            "authentication API design decisions".to_string(),
+        ] {
+            let intent = classifier.classify(&query).unwrap();
+            let score = classifier.score(&query, &intent, &entities(&query));
+            assert!(score >= SUGGESTION_THRESHOLD, "{}: {}", query, score);
+        }
+
+        // An agent filter is evidence for listing tasks
+        let plain = classifier.score("list tasks", &QueryIntent::ListTasks, &[]);
+        let query = "list tasks for agent alice";
+        let filtered = classifier.score(query, &QueryIntent::ListTasks, &entities(query));
+        assert!(filtered > plain);
+    }
+
+    #[test]
+    fn test_ambiguous_queries_produce_suggestions() {
+        let classifier = IntentClassifier::new();
+        let task_id = "0b0e8a57-7a3c-4a55-9a38-5f2f4c1e9d11";
+
+        // Falls through to full text search, but mentions a blocking task
+        let query = format!("alice tasks blocked by {}", task_id);
+        let intent = classifier.classify(&query).unwrap();
+        assert_eq!(intent, QueryIntent::FullTextSearch);
+        assert!(classifier.score(&query, &intent, &entities(&query)) < SUGGESTION_THRESHOLD);
+        let candidates = classifier.candidates(&query, &entities(&query));
+        let examples: Vec<&str> = candidates.iter().map(|c| c.example.as_str()).collect();
+        assert_eq!(
+            examples[..2],
+            [
+                format!("show relationships of task {}", task_id).as_str(),
+                "list tasks"
+            ]
+        );
+
+        // Matches the task list pattern, but also asks about dependencies
+        let query = format!("tasks for alice that depend on {}", task_id);
+        let intent = classifier.classify(&query).unwrap();
+        assert_eq!(intent, QueryIntent::ListTasks);
+        assert!(classifier.score(&query, &intent, &entities(&query)) < SUGGESTION_THRESHOLD);
+        let candidates = classifier.candidates(&query, &entities(&query));
+        assert_eq!(candidates[0].example, "list tasks for agent alice");
+        assert_eq!(
+            candidates[1].example,
+            format!("show relationships of task {}", task_id)
+        );
+
+        // Every suggestion classifies as the intent it stands for
+        for candidate in candidates {
+            assert_eq!(
+                classifier.classify(&candidate.example).unwrap(),
+                candidate.intent
+            );
+        }
+    }
 }
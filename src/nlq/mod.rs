@@ -3,6 +3,7 @@
 //! Provides conversational access to Engram's memory system through pattern matching
 //! and entity extraction. Maps natural language queries to structured Engram operations.
 
+pub mod conversation;
 pub mod deep_walk;
 pub mod entity_extractor;
 pub mod intent_classifier;
@@ -11,14 +12,18 @@ pub mod response_formatter;
 pub mod skills_prompts_handler;
 
 use crate::error::EngramError;
+use crate::health::{EngineHealthCheck, HealthState, HealthStatus};
 use crate::storage::Storage;
+use futures::stream::{self, Stream};
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 
+pub use conversation::ConversationContext;
 pub use deep_walk::{ConnectedEntity, DeepWalkResult, DeepWalker};
 pub use entity_extractor::EntityExtractor;
-pub use intent_classifier::IntentClassifier;
+pub use intent_classifier::{IntentCandidate, IntentClassifier, SUGGESTION_THRESHOLD};
 pub use query_mapper::QueryMapper;
-pub use response_formatter::ResponseFormatter;
+pub use response_formatter::{OutputMode, ResponseFormatter};
 pub use skills_prompts_handler::{
     list_prompts, list_skills, search_prompts, search_skills, PromptInfo, PromptsQuery, SkillInfo,
     SkillsQuery,
@@ -40,6 +45,10 @@ pub struct ProcessedQuery {
     pub entities: Vec<ExtractedEntity>,
     pub context: Option<String>,
     pub confidence: f64,
+    /// Likely interpretations offered instead of an answer when the intent
+    /// is uncertain
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suggestions: Vec<IntentCandidate>,
 }
 
 /// Supported query intents
@@ -77,6 +86,43 @@ pub struct QueryResult {
     pub execution_time_ms: u64,
 }
 
+/// Stage a streamed query chunk belongs to
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChunkType {
+    IntentClassified,
+    EntitiesExtracted,
+    QueryExecuting,
+    ResponseChunk,
+    Done,
+}
+
+/// One piece of a streamed query response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryStreamChunk {
+    pub chunk_type: ChunkType,
+    pub content: String,
+}
+
+impl QueryStreamChunk {
+    fn new(chunk_type: ChunkType, content: impl Into<String>) -> Self {
+        Self {
+            chunk_type,
+            content: content.into(),
+        }
+    }
+}
+
+/// Progress of a streamed query
+enum StreamStage {
+    Classify(Option<String>),
+    Extract(QueryIntent, Option<String>),
+    Announce(ProcessedQuery),
+    Execute(ProcessedQuery),
+    Respond(VecDeque<String>),
+    Finished,
+}
+
 impl NLQEngine {
     /// Create a new NLQ engine instance
     pub fn new() -> Self {
@@ -88,6 +134,12 @@ impl NLQEngine {
         }
     }
 
+    /// Render responses in `mode` instead of plain text
+    pub fn with_output_mode(mut self, mode: OutputMode) -> Self {
+        self.response_formatter = ResponseFormatter::with_mode(mode);
+        self
+    }
+
     /// Process a natural language query and return results
     pub async fn process_query(
         &self,
@@ -110,47 +162,196 @@ impl NLQEngine {
     ) -> Result<QueryResult, EngramError> {
         let start_time = std::time::Instant::now();
 
-        // Step 1: Classify intent
-        let intent = self.intent_classifier.classify(query)?;
+        // Steps 1-3: Classify intent and extract entities
+        let processed_query = self.analyze(query, context)?;
 
-        // Step 2: Extract entities
+        let mut result = self
+            .execute_processed(&processed_query, storage, deep, max_depth)
+            .await?;
+        result.execution_time_ms = start_time.elapsed().as_millis() as u64;
+        Ok(result)
+    }
+
+    /// Classify `query` and extract its entities without touching storage
+    pub fn analyze(
+        &self,
+        query: &str,
+        context: Option<String>,
+    ) -> Result<ProcessedQuery, EngramError> {
+        let intent = self.intent_classifier.classify(query)?;
         let entities = self.entity_extractor.extract(query)?;
 
-        // Step 3: Create processed query
-        let processed_query = ProcessedQuery {
+        Ok(self.processed_query(query, intent, entities, context))
+    }
+
+    /// Score the classification, attaching the top two candidate
+    /// interpretations when it falls below [`SUGGESTION_THRESHOLD`]
+    fn processed_query(
+        &self,
+        query: &str,
+        intent: QueryIntent,
+        entities: Vec<ExtractedEntity>,
+        context: Option<String>,
+    ) -> ProcessedQuery {
+        let confidence = self.intent_classifier.score(query, &intent, &entities);
+        let mut suggestions = Vec::new();
+        if confidence < SUGGESTION_THRESHOLD {
+            suggestions = self.intent_classifier.candidates(query, &entities);
+            suggestions.truncate(2);
+        }
+
+        ProcessedQuery {
             original_query: query.to_string(),
-            intent: intent.clone(),
+            intent,
             entities,
             context,
-            confidence: 0.8,
-        };
+            confidence,
+            suggestions,
+        }
+    }
+
+    /// Run the mapped storage query, or skip it when the query is answered
+    /// with suggestions
+    async fn run_query(
+        &self,
+        processed_query: &ProcessedQuery,
+        storage: &dyn Storage,
+    ) -> Result<serde_json::Value, EngramError> {
+        if processed_query.suggestions.is_empty() {
+            self.query_mapper
+                .execute_query(processed_query, storage)
+                .await
+        } else {
+            Ok(serde_json::json!({
+                "confidence": processed_query.confidence,
+                "suggestions": processed_query.suggestions,
+            }))
+        }
+    }
+
+    /// Run an already analyzed query against `storage` and format the response
+    pub async fn execute_processed(
+        &self,
+        processed_query: &ProcessedQuery,
+        storage: &dyn Storage,
+        deep: bool,
+        max_depth: Option<usize>,
+    ) -> Result<QueryResult, EngramError> {
+        let start_time = std::time::Instant::now();
 
         // Step 4: Map to storage query and execute
-        let data = self
-            .query_mapper
-            .execute_query(&processed_query, storage)
-            .await?;
+        let data = self.run_query(processed_query, storage).await?;
+        let answered = processed_query.suggestions.is_empty();
 
         // Step 5: Deep walk if requested
-        let data = if deep {
+        let data = if deep && answered {
             self.perform_deep_walk(&data, storage, max_depth)?
         } else {
             data
         };
 
         // Step 6: Format response
-        let formatted_response = self.response_formatter.format(&processed_query, &data)?;
+        let formatted_response = self.response_formatter.format(processed_query, &data)?;
 
         let execution_time = start_time.elapsed().as_millis() as u64;
 
         Ok(QueryResult {
-            success: true,
+            success: answered,
             data,
             formatted_response,
             execution_time_ms: execution_time,
         })
     }
 
+    /// Process a query as a stream of chunks: the classified intent, the
+    /// extracted entities, a marker before the query runs, the formatted
+    /// response piece by piece, then a final `Done` with the execution time.
+    ///
+    /// Concatenating the `ResponseChunk` contents gives the same text as
+    /// [`QueryResult::formatted_response`] from [`Self::process_query`].
+    /// The stream ends after the first error.
+    pub fn process_query_stream<'a>(
+        &'a self,
+        query: &'a str,
+        context: Option<String>,
+        storage: &'a dyn Storage,
+    ) -> impl Stream<Item = Result<QueryStreamChunk, EngramError>> + 'a {
+        let start_time = std::time::Instant::now();
+
+        stream::unfold(
+            StreamStage::Classify(context),
+            move |mut stage| async move {
+                let step = loop {
+                    stage = match stage {
+                        StreamStage::Classify(context) => {
+                            break self.intent_classifier.classify(query).map(|intent| {
+                                let chunk = QueryStreamChunk::new(
+                                    ChunkType::IntentClassified,
+                                    format!("{:?}", intent),
+                                );
+                                (chunk, StreamStage::Extract(intent, context))
+                            });
+                        }
+                        StreamStage::Extract(intent, context) => {
+                            break self.entity_extractor.extract(query).and_then(|entities| {
+                                let chunk = QueryStreamChunk::new(
+                                    ChunkType::EntitiesExtracted,
+                                    serde_json::to_string(&entities)?,
+                                );
+                                let processed_query =
+                                    self.processed_query(query, intent, entities, context);
+                                Ok((chunk, StreamStage::Announce(processed_query)))
+                            });
+                        }
+                        StreamStage::Announce(processed_query) => {
+                            let chunk = QueryStreamChunk::new(
+                                ChunkType::QueryExecuting,
+                                format!("{:?}", processed_query.intent),
+                            );
+                            break Ok((chunk, StreamStage::Execute(processed_query)));
+                        }
+                        StreamStage::Execute(processed_query) => {
+                            let chunks =
+                                self.run_query(&processed_query, storage)
+                                    .await
+                                    .and_then(|data| {
+                                        self.response_formatter
+                                            .format_chunks(&processed_query, &data)
+                                    });
+                            match chunks {
+                                Ok(chunks) => StreamStage::Respond(chunks.into()),
+                                Err(e) => break Err(e),
+                            }
+                        }
+                        StreamStage::Respond(mut chunks) => {
+                            let chunk = match chunks.pop_front() {
+                                Some(content) => {
+                                    QueryStreamChunk::new(ChunkType::ResponseChunk, content)
+                                }
+                                None => {
+                                    let elapsed = start_time.elapsed().as_millis().to_string();
+                                    QueryStreamChunk::new(ChunkType::Done, elapsed)
+                                }
+                            };
+                            let next = if chunk.chunk_type == ChunkType::Done {
+                                StreamStage::Finished
+                            } else {
+                                StreamStage::Respond(chunks)
+                            };
+                            break Ok((chunk, next));
+                        }
+                        StreamStage::Finished => return None,
+                    };
+                };
+
+                Some(match step {
+                    Ok((chunk, next)) => (Ok(chunk), next),
+                    Err(e) => (Err(e), StreamStage::Finished),
+                })
+            },
+        )
+    }
+
     fn perform_deep_walk(
         &self,
         data: &serde_json::Value,
@@ -210,6 +411,29 @@ impl NLQEngine {
     }
 }
 
+/// Query the health check runs through the classifier and extractor
+const HEALTH_PROBE_QUERY: &str = "show my tasks";
+
+impl EngineHealthCheck for NLQEngine {
+    fn health_check(&self) -> crate::Result<HealthStatus> {
+        let intent = self.intent_classifier.classify(HEALTH_PROBE_QUERY)?;
+        self.entity_extractor.extract(HEALTH_PROBE_QUERY)?;
+
+        Ok(if intent == QueryIntent::ListTasks {
+            HealthStatus::new("nlq", HealthState::Healthy, "intent classifier ready")
+        } else {
+            HealthStatus::new(
+                "nlq",
+                HealthState::Degraded,
+                format!(
+                    "probe query '{}' classified as {:?}, expected ListTasks",
+                    HEALTH_PROBE_QUERY, intent
+                ),
+            )
+        })
+    }
+}
+
 impl Default for NLQEngine {
     fn default() -> Self {
         Self::new()
@@ -227,6 +451,101 @@ mod tests {
         assert!(!patterns.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_ambiguous_query_returns_suggestions_instead_of_answer() {
+        use crate::entities::{Entity, Task, TaskPriority};
+        use crate::storage::MemoryStorage;
+
+        let mut storage = MemoryStorage::new("default");
+        let task = Task::new(
+            "Fix login".to_string(),
+            String::new(),
+            "alice".to_string(),
+            TaskPriority::Medium,
+            None,
+        );
+        storage.store(&task.to_generic()).unwrap();
+
+        let engine = NLQEngine::new();
+        let query = format!("tasks for alice that depend on {}", task.id);
+        let result = engine.process_query(&query, None, &storage).await.unwrap();
+
+        assert!(!result.success);
+        assert!(result.data.get("tasks").is_none());
+        assert_eq!(result.data["suggestions"].as_array().unwrap().len(), 2);
+        assert_eq!(
+            result.formatted_response,
+            format!(
+                "Not sure what you meant. Did you mean:\n  1. list tasks for agent alice\n  2. show relationships of task {}\n",
+                task.id
+            )
+        );
+
+        let table = NLQEngine::new().with_output_mode(OutputMode::Table);
+        let result = table.process_query(&query, None, &storage).await.unwrap();
+        assert!(result
+            .formatted_response
+            .starts_with("Not sure what you meant. Did you mean: 1. list tasks"));
+
+        let clear = engine
+            .process_query("list tasks for agent alice", None, &storage)
+            .await
+            .unwrap();
+        assert!(clear.success);
+        assert_eq!(clear.data["tasks"].as_array().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_process_query_stream_matches_formatted_response() {
+        use crate::entities::{Entity, Task, TaskPriority};
+        use crate::storage::MemoryStorage;
+        use futures::StreamExt;
+
+        let mut storage = MemoryStorage::new("default");
+        for title in ["Write docs", "Fix login"] {
+            let task = Task::new(
+                title.to_string(),
+                String::new(),
+                "default".to_string(),
+                TaskPriority::Medium,
+                None,
+            );
+            storage.store(&task.to_generic()).unwrap();
+        }
+
+        let engine = NLQEngine::new();
+        let expected = engine
+            .process_query("show my tasks", None, &storage)
+            .await
+            .unwrap();
+        let chunks: Vec<QueryStreamChunk> = engine
+            .process_query_stream("show my tasks", None, &storage)
+            .map(|chunk| chunk.unwrap())
+            .collect()
+            .await;
+
+        let types: Vec<ChunkType> = chunks.iter().map(|c| c.chunk_type).collect();
+        assert_eq!(
+            &types[..3],
+            &[
+                ChunkType::IntentClassified,
+                ChunkType::EntitiesExtracted,
+                ChunkType::QueryExecuting
+            ]
+        );
+        assert_eq!(types.last(), Some(&ChunkType::Done));
+        assert_eq!(chunks[0].content, "ListTasks");
+
+        let response: Vec<&str> = chunks
+            .iter()
+            .filter(|c| c.chunk_type == ChunkType::ResponseChunk)
+            .map(|c| c.content.as_str())
+            .collect();
+        // Header plus one chunk per task
+        assert_eq!(response.len(), 3);
+        assert_eq!(response.concat(), expected.formatted_response);
+    }
+
     #[test]
     fn test_query_intent_serialization() {
         let intent = QueryIntent::ListTasks;
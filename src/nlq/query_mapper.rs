@@ -4,11 +4,20 @@ use crate::nlq::{
     list_prompts, list_skills, search_prompts, search_skills, ExtractedEntity, ProcessedQuery,
     PromptsQuery, QueryIntent, SkillsQuery,
 };
-use crate::storage::{GitRefsStorage, RelationshipStorage, Storage};
+use crate::storage::{resolve_id_prefix, GitRefsStorage, RelationshipStorage, Storage};
 use serde_json::{json, Value};
 
 pub struct QueryMapper;
 
+/// Outcome of resolving the task a query refers to
+#[derive(Debug, PartialEq)]
+enum TaskReference {
+    Resolved(String),
+    /// The query names a task but it matches none or several
+    Unresolved(String),
+    Missing,
+}
+
 impl QueryMapper {
     pub fn new() -> Self {
         Self
@@ -239,7 +248,12 @@ impl QueryMapper {
         processed_query: &ProcessedQuery,
         storage: &dyn Storage,
     ) -> Result<Value, EngramError> {
-        if let Some(task_id) = self.extract_task_id(&processed_query.entities) {
+        let task_id = match self.resolve_task_reference(&processed_query.entities, storage)? {
+            TaskReference::Resolved(task_id) => Some(task_id),
+            TaskReference::Unresolved(error) => return Ok(json!({ "error": error })),
+            TaskReference::Missing => None,
+        };
+        if let Some(task_id) = task_id {
             if let Some(task_entity) = storage.get(&task_id, "task")? {
                 if let Ok(task) = crate::entities::Task::from_generic(task_entity) {
                     return Ok(json!({
@@ -268,7 +282,12 @@ impl QueryMapper {
         processed_query: &ProcessedQuery,
         storage: &dyn Storage,
     ) -> Result<Value, EngramError> {
-        if let Some(task_id) = self.extract_task_id(&processed_query.entities) {
+        let task_id = match self.resolve_task_reference(&processed_query.entities, storage)? {
+            TaskReference::Resolved(task_id) => Some(task_id),
+            TaskReference::Unresolved(error) => return Ok(json!({ "error": error })),
+            TaskReference::Missing => None,
+        };
+        if let Some(task_id) = task_id {
             if let Some(git_refs_storage) = storage.as_any().downcast_ref::<GitRefsStorage>() {
                 let relationships = git_refs_storage.get_entity_relationships(&task_id)?;
 
@@ -759,6 +778,71 @@ impl QueryMapper {
             .map(|e| e.value.clone())
     }
 
+    /// Resolve the task a query refers to: a full ID, then an ID prefix,
+    /// then the most confident title reference
+    fn resolve_task_reference(
+        &self,
+        entities: &[ExtractedEntity],
+        storage: &dyn Storage,
+    ) -> Result<TaskReference, EngramError> {
+        if let Some(task_id) = self.extract_task_id(entities) {
+            return Ok(TaskReference::Resolved(task_id));
+        }
+
+        if let Some(prefix) = entities.iter().find(|e| e.entity_type == "task_id_prefix") {
+            return match resolve_id_prefix(storage, "task", &prefix.value) {
+                Ok(task_id) => Ok(TaskReference::Resolved(task_id)),
+                Err(EngramError::NotFound { .. }) => Ok(TaskReference::Unresolved(format!(
+                    "no task matching '{}'",
+                    prefix.value
+                ))),
+                Err(EngramError::InvalidOperation(message)) => {
+                    Ok(TaskReference::Unresolved(message))
+                }
+                Err(e) => Err(e),
+            };
+        }
+
+        let title = entities
+            .iter()
+            .filter(|e| e.entity_type == "title")
+            .max_by(|a, b| a.confidence.total_cmp(&b.confidence));
+        let Some(title) = title else {
+            return Ok(TaskReference::Missing);
+        };
+
+        let needle = title.value.to_lowercase();
+        let mut matches: Vec<(String, String)> = storage
+            .text_search(&title.value, Some(&["task".to_string()]), None)?
+            .into_iter()
+            .filter_map(|entity| crate::entities::Task::from_generic(entity).ok())
+            .filter(|task| task.title.to_lowercase().contains(&needle))
+            .map(|task| (task.id, task.title))
+            .collect();
+        matches.sort_by(|a, b| a.1.cmp(&b.1));
+        if let Some(exact) = matches
+            .iter()
+            .position(|(_, task_title)| task_title.to_lowercase() == needle)
+        {
+            return Ok(TaskReference::Resolved(matches.swap_remove(exact).0));
+        }
+
+        Ok(match matches.len() {
+            0 => TaskReference::Unresolved(format!("no task matching '{}'", title.value)),
+            1 => TaskReference::Resolved(matches.remove(0).0),
+            n => TaskReference::Unresolved(format!(
+                "'{}' matches {} tasks: {}",
+                title.value,
+                n,
+                matches
+                    .iter()
+                    .map(|(_, task_title)| task_title.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+        })
+    }
+
     fn extract_search_term(&self, entities: &[ExtractedEntity], query: &str) -> String {
         let lower_query = query.to_lowercase();
 
@@ -846,4 +930,53 @@ mod tests {
         let extracted = mapper.extract_task_id(&entities);
         assert_eq!(extracted, Some(task_id.to_string()));
     }
+
+    #[tokio::test]
+    async fn test_task_references_resolve_before_execution() {
+        use crate::entities::{Task, TaskPriority};
+        use crate::nlq::NLQEngine;
+        use crate::storage::MemoryStorage;
+
+        let mut storage = MemoryStorage::new("default");
+        let mut ids = Vec::new();
+        for title in ["Fix login", "Fix login page", "Deploy release"] {
+            let task = Task::new(
+                title.to_string(),
+                String::new(),
+                "default".to_string(),
+                TaskPriority::Medium,
+                None,
+            );
+            storage.store(&task.to_generic()).unwrap();
+            ids.push(task.id);
+        }
+
+        let engine = NLQEngine::new();
+        let data = |query: String| {
+            let engine = &engine;
+            let storage = &storage;
+            async move {
+                engine
+                    .process_query(&query, None, storage)
+                    .await
+                    .unwrap()
+                    .data
+            }
+        };
+
+        let exact = data(r#"show task "fix login""#.to_string()).await;
+        assert_eq!(exact["task"]["id"], ids[0].as_str());
+        let described = data("show the deploy task".to_string()).await;
+        assert_eq!(described["task"]["id"], ids[2].as_str());
+        let prefix = data(format!("what depends on task {}", &ids[1][..8])).await;
+        assert_eq!(prefix["task_id"], ids[1].as_str());
+
+        let missing = data(r#"show task "payments""#.to_string()).await;
+        assert_eq!(missing["error"], "no task matching 'payments'");
+        let ambiguous = data(r#"what depends on "fix""#.to_string()).await;
+        assert_eq!(
+            ambiguous["error"],
+            "'fix' matches 2 tasks: Fix login, Fix login page"
+        );
+    }
 }
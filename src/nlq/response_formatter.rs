@@ -1,15 +1,218 @@
+use crate::cli::utils::create_table;
 use crate::error::EngramError;
-use crate::nlq::{ProcessedQuery, QueryIntent};
+use crate::nlq::{IntentCandidate, ProcessedQuery, QueryIntent};
+use prettytable::{Cell, Row};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::str::FromStr;
+
+/// How NLQ responses are rendered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputMode {
+    /// Numbered plain-text lists
+    #[default]
+    Plain,
+    /// Box-drawn tables like `engram task list`
+    Table,
+    /// Markdown tables for agent consumption
+    Markdown,
+    /// The raw result data as a single JSON line
+    Json,
+}
+
+impl FromStr for OutputMode {
+    type Err = EngramError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "plain" => Ok(OutputMode::Plain),
+            "table" => Ok(OutputMode::Table),
+            "markdown" | "md" => Ok(OutputMode::Markdown),
+            "json" => Ok(OutputMode::Json),
+            _ => Err(EngramError::InvalidOperation(format!(
+                "Unknown output format '{}'; expected plain, table, markdown or json",
+                s
+            ))),
+        }
+    }
+}
+
+/// Titled rows of one result section
+struct Section {
+    title: Option<String>,
+    headers: Vec<&'static str>,
+    rows: Vec<Vec<String>>,
+}
 
-pub struct ResponseFormatter;
+/// A response that lists entities, ready to render as a table or markdown
+struct Tabular {
+    heading: String,
+    sections: Vec<Section>,
+}
+
+impl Tabular {
+    fn new(heading: String) -> Self {
+        Self {
+            heading,
+            sections: Vec::new(),
+        }
+    }
+
+    /// Add a section unless it has no rows
+    fn section(
+        mut self,
+        title: Option<String>,
+        headers: Vec<&'static str>,
+        rows: Vec<Vec<String>>,
+    ) -> Self {
+        if !rows.is_empty() {
+            self.sections.push(Section {
+                title,
+                headers,
+                rows,
+            });
+        }
+        self
+    }
+
+    fn to_table(&self) -> String {
+        let mut output = format!("{}\n", self.heading);
+        for section in &self.sections {
+            if let Some(title) = &section.title {
+                output.push_str(&format!("\n{}:\n", title));
+            }
+            let mut table = create_table();
+            table.set_titles(Row::new(
+                section.headers.iter().map(|h| Cell::new(h)).collect(),
+            ));
+            for row in &section.rows {
+                table.add_row(Row::new(row.iter().map(|c| Cell::new(c)).collect()));
+            }
+            output.push_str(&table.to_string());
+        }
+        output
+    }
+
+    fn to_markdown(&self) -> String {
+        let mut output = format!("{}\n", self.heading);
+        for section in &self.sections {
+            if let Some(title) = &section.title {
+                output.push_str(&format!("\n### {}\n", title));
+            }
+            output.push('\n');
+            output.push_str(&markdown_row(section.headers.iter().copied()));
+            output.push_str(&markdown_row(section.headers.iter().map(|_| "---")));
+            for row in &section.rows {
+                output.push_str(&markdown_row(row.iter().map(String::as_str)));
+            }
+        }
+        output
+    }
+}
+
+fn markdown_row<'a>(cells: impl Iterator<Item = &'a str>) -> String {
+    let cells: Vec<String> = cells
+        .map(|cell| cell.replace('|', "\\|").replace('\n', " "))
+        .collect();
+    format!("| {} |\n", cells.join(" | "))
+}
+
+/// First 8 characters of an entity ID, as shown in listings
+fn short_id(value: &Value) -> String {
+    let id = value.as_str().unwrap_or("");
+    id[..8.min(id.len())].to_string()
+}
+
+fn text(value: &Value, default: &str) -> String {
+    value.as_str().unwrap_or(default).to_string()
+}
+
+/// Collapse a multi-line answer onto one line
+fn single_line(answer: &str) -> String {
+    answer
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Numbered "did you mean" list offered when the intent is uncertain
+fn format_suggestions(suggestions: &[IntentCandidate]) -> String {
+    let mut output = "Not sure what you meant. Did you mean:\n".to_string();
+    for (index, suggestion) in suggestions.iter().enumerate() {
+        output.push_str(&format!("  {}. {}\n", index + 1, suggestion.example));
+    }
+    output
+}
+
+/// Full text search sections: data key and display title
+const SEARCH_SECTIONS: [(&str, &str); 18] = [
+    ("tasks", "Tasks"),
+    ("contexts", "Context"),
+    ("reasoning", "Reasoning"),
+    ("knowledge", "Knowledge"),
+    ("rules", "Rules"),
+    ("standards", "Standards"),
+    ("adrs", "ADRs"),
+    ("theories", "Theories"),
+    ("compliance", "Compliance"),
+    ("sessions", "Sessions"),
+    ("state_reflections", "State Reflections"),
+    ("workflows", "Workflows"),
+    ("workflow_instances", "Workflow Instances"),
+    ("agent_sandboxes", "Agent Sandboxes"),
+    ("escalation_requests", "Escalation Requests"),
+    ("execution_results", "Execution Results"),
+    ("progressive_gate_configs", "Progressive Gate Configs"),
+    ("doc_fragments", "Doc Fragments"),
+];
+
+#[derive(Default)]
+pub struct ResponseFormatter {
+    mode: OutputMode,
+}
 
 impl ResponseFormatter {
     pub fn new() -> Self {
-        Self
+        Self::default()
     }
 
+    pub fn with_mode(mode: OutputMode) -> Self {
+        Self { mode }
+    }
+
+    pub fn mode(&self) -> OutputMode {
+        self.mode
+    }
+
+    /// Render `data` in the formatter's output mode
+    ///
+    /// Table and markdown modes apply to intents that list entities. Answers
+    /// without rows (errors, empty results, unknown queries) are a single
+    /// line in every mode.
     pub fn format(&self, query: &ProcessedQuery, data: &Value) -> Result<String, EngramError> {
+        if !query.suggestions.is_empty() && self.mode != OutputMode::Json {
+            let answer = format_suggestions(&query.suggestions);
+            return Ok(match self.mode {
+                OutputMode::Plain => answer,
+                _ => single_line(&answer),
+            });
+        }
+
+        match self.mode {
+            OutputMode::Plain => self.format_plain(query, data),
+            OutputMode::Json => Ok(serde_json::to_string(data)?),
+            OutputMode::Table | OutputMode::Markdown => match self.tabulate(&query.intent, data) {
+                Some(tabular) if self.mode == OutputMode::Table => Ok(tabular.to_table()),
+                Some(tabular) => Ok(tabular.to_markdown()),
+                None => Ok(single_line(&self.format_plain(query, data)?)),
+            },
+        }
+    }
+
+    fn format_plain(&self, query: &ProcessedQuery, data: &Value) -> Result<String, EngramError> {
         match &query.intent {
             QueryIntent::ListTasks => self.format_task_list(data),
             QueryIntent::ShowTaskDetails => self.format_task_details(data),
@@ -25,6 +228,27 @@ impl ResponseFormatter {
         }
     }
 
+    /// Format the response as chunks, one per formatted entity where the
+    /// intent lists entities. Concatenated, the chunks equal [`Self::format`].
+    pub fn format_chunks(
+        &self,
+        query: &ProcessedQuery,
+        data: &Value,
+    ) -> Result<Vec<String>, EngramError> {
+        match &query.intent {
+            QueryIntent::ListTasks
+                if self.mode == OutputMode::Plain && query.suggestions.is_empty() =>
+            {
+                self.task_list_chunks(data)
+            }
+            _ => Ok(self
+                .format(query, data)?
+                .split_inclusive('\n')
+                .map(str::to_string)
+                .collect()),
+        }
+    }
+
     // Skills/Prompts formatters
     fn format_skills_list(&self, data: &Value) -> Result<String, EngramError> {
         if let Some(skills) = data.get("skills").and_then(|v| v.as_array()) {
@@ -127,11 +351,16 @@ impl ResponseFormatter {
     }
 
     fn format_task_list(&self, data: &Value) -> Result<String, EngramError> {
+        Ok(self.task_list_chunks(data)?.concat())
+    }
+
+    /// Task list as a header chunk followed by one chunk per task
+    fn task_list_chunks(&self, data: &Value) -> Result<Vec<String>, EngramError> {
         if let Some(error) = data.get("error") {
-            return Ok(format!(
+            return Ok(vec![format!(
                 "Error: {}",
                 error.as_str().unwrap_or("Unknown error")
-            ));
+            )]);
         }
 
         let empty_vec = vec![];
@@ -140,17 +369,20 @@ impl ResponseFormatter {
         let agent = data["agent"].as_str().unwrap_or("default");
 
         if count == 0 {
-            return Ok(format!("No tasks found for agent '{}'", agent));
+            return Ok(vec![format!("No tasks found for agent '{}'", agent)]);
         }
 
-        let mut response = format!("Found {} task(s) for agent '{}':\n\n", count, agent);
+        let mut chunks = vec![format!(
+            "Found {} task(s) for agent '{}':\n\n",
+            count, agent
+        )];
 
         for (i, task) in tasks.iter().enumerate() {
             let title = task["title"].as_str().unwrap_or("Untitled");
             let status = task["status"].as_str().unwrap_or("Unknown");
             let priority = task["priority"].as_str().unwrap_or("Unknown");
 
-            response.push_str(&format!(
+            chunks.push(format!(
                 "{}. {} [{}] ({})\n",
                 i + 1,
                 title,
@@ -159,7 +391,7 @@ impl ResponseFormatter {
             ));
         }
 
-        Ok(response)
+        Ok(chunks)
     }
 
     fn format_task_details(&self, data: &Value) -> Result<String, EngramError> {
@@ -628,6 +860,252 @@ impl ResponseFormatter {
         Ok(section)
     }
 
+    /// Rows for intents that list entities; `None` when there is nothing to
+    /// tabulate
+    fn tabulate(&self, intent: &QueryIntent, data: &Value) -> Option<Tabular> {
+        if data.get("error").is_some() {
+            return None;
+        }
+
+        let empty_vec = vec![];
+        let list = |key: &str| data[key].as_array().unwrap_or(&empty_vec);
+
+        let tabular = match intent {
+            QueryIntent::ListTasks => Tabular::new(format!(
+                "Found {} task(s) for agent '{}':",
+                data["count"].as_u64().unwrap_or(0),
+                data["agent"].as_str().unwrap_or("default")
+            ))
+            .section(
+                None,
+                vec!["ID", "Status", "Priority", "Title"],
+                list("tasks")
+                    .iter()
+                    .map(|task| {
+                        vec![
+                            short_id(&task["id"]),
+                            text(&task["status"], "Unknown"),
+                            text(&task["priority"], "Unknown"),
+                            text(&task["title"], "Untitled"),
+                        ]
+                    })
+                    .collect(),
+            ),
+            QueryIntent::ShowTaskDetails => {
+                let task = data.get("task")?;
+                let mut rows = vec![
+                    vec!["ID".to_string(), text(&task["id"], "")],
+                    vec!["Title".to_string(), text(&task["title"], "Untitled")],
+                    vec!["Status".to_string(), text(&task["status"], "Unknown")],
+                    vec!["Priority".to_string(), text(&task["priority"], "Unknown")],
+                    vec!["Agent".to_string(), text(&task["agent"], "Unknown")],
+                ];
+                let description = text(&task["description"], "");
+                if !description.is_empty() {
+                    rows.push(vec!["Description".to_string(), description]);
+                }
+                if let Some(outcome) = task["outcome"].as_str() {
+                    rows.push(vec!["Outcome".to_string(), outcome.to_string()]);
+                }
+                Tabular::new("Task Details:".to_string()).section(
+                    None,
+                    vec!["Field", "Value"],
+                    rows,
+                )
+            }
+            QueryIntent::FindRelationships => Tabular::new(format!(
+                "Found {} relationship(s) for task {}:",
+                data["count"].as_u64().unwrap_or(0),
+                data["task_id"].as_str().unwrap_or("unknown")
+            ))
+            .section(
+                None,
+                vec!["Type", "Target", "Strength"],
+                list("relationships")
+                    .iter()
+                    .map(|rel| {
+                        vec![
+                            text(&rel["type"], "Unknown"),
+                            text(&rel["target"], "Unknown"),
+                            text(&rel["strength"], "Unknown"),
+                        ]
+                    })
+                    .collect(),
+            ),
+            QueryIntent::SearchContext => Tabular::new(format!(
+                "Found {} context item(s):",
+                data["count"].as_u64().unwrap_or(0)
+            ))
+            .section(
+                None,
+                vec!["ID", "Title", "Relevance"],
+                list("contexts")
+                    .iter()
+                    .map(|context| {
+                        let relevance = match &context["relevance"] {
+                            Value::Number(n) => format!("{:.2}", n.as_f64().unwrap_or(0.0)),
+                            other => text(other, "Unknown"),
+                        };
+                        vec![
+                            short_id(&context["id"]),
+                            text(&context["title"], "Untitled"),
+                            relevance,
+                        ]
+                    })
+                    .collect(),
+            ),
+            QueryIntent::AnalyzeWorkflow => Tabular::new(format!(
+                "Found {} workflow(s):",
+                data["count"].as_u64().unwrap_or(0)
+            ))
+            .section(
+                None,
+                vec!["ID", "Title", "State", "Status"],
+                list("workflows")
+                    .iter()
+                    .map(|workflow| {
+                        vec![
+                            short_id(&workflow["id"]),
+                            text(&workflow["title"], "Untitled"),
+                            text(&workflow["current_state"], "Unknown"),
+                            text(&workflow["status"], "Unknown"),
+                        ]
+                    })
+                    .collect(),
+            ),
+            QueryIntent::ListSkills | QueryIntent::SearchSkills => {
+                let skills = list("skills");
+                let heading = match data["query"].as_str() {
+                    Some(query) if *intent == QueryIntent::SearchSkills => {
+                        format!("Found {} skill(s) matching '{}':", skills.len(), query)
+                    }
+                    _ => format!("Found {} skill(s):", skills.len()),
+                };
+                Tabular::new(heading).section(
+                    None,
+                    vec!["Name", "Description"],
+                    skills
+                        .iter()
+                        .map(|skill| {
+                            vec![
+                                text(&skill["name"], "unknown"),
+                                text(&skill["description"], "(no description)"),
+                            ]
+                        })
+                        .collect(),
+                )
+            }
+            QueryIntent::ListPrompts | QueryIntent::SearchPrompts => {
+                let prompts = list("prompts");
+                let heading = match data["query"].as_str() {
+                    Some(query) if *intent == QueryIntent::SearchPrompts => {
+                        format!("Found {} prompt(s) matching '{}':", prompts.len(), query)
+                    }
+                    _ => format!("Found {} prompt(s):", prompts.len()),
+                };
+                Tabular::new(heading).section(
+                    None,
+                    vec!["Name", "Title"],
+                    prompts
+                        .iter()
+                        .map(|prompt| {
+                            vec![
+                                text(&prompt["name"], "unknown"),
+                                text(&prompt["title"], "(no title)"),
+                            ]
+                        })
+                        .collect(),
+                )
+            }
+            QueryIntent::FullTextSearch => self.tabulate_search(data),
+            QueryIntent::Unknown => return None,
+        };
+
+        if tabular.sections.is_empty() {
+            None
+        } else {
+            Some(tabular)
+        }
+    }
+
+    fn tabulate_search(&self, data: &Value) -> Tabular {
+        let empty_vec = vec![];
+        let mut tabular = Tabular::new(format!(
+            "Found {} result(s) for '{}':",
+            data["total_matches"].as_u64().unwrap_or(0),
+            data["query"].as_str().unwrap_or("")
+        ));
+
+        for (key, title) in SEARCH_SECTIONS {
+            let rows = data[key]
+                .as_array()
+                .unwrap_or(&empty_vec)
+                .iter()
+                .map(|item| {
+                    let mut title = text(&item["title"], "Untitled");
+                    match key {
+                        "adrs" => {
+                            title =
+                                format!("ADR-{}: {}", item["number"].as_u64().unwrap_or(0), title)
+                        }
+                        "doc_fragments" => {
+                            title = format!("{} (topic: {})", title, text(&item["topic"], ""));
+                            if item["stale"].as_bool().unwrap_or(false) {
+                                title.push_str(" [stale]");
+                            }
+                        }
+                        _ => {}
+                    }
+                    let mut row = vec![short_id(&item["id"]), title];
+                    if key == "tasks" {
+                        row.push(text(&item["status"], "Unknown"));
+                    }
+                    row
+                })
+                .collect();
+            let headers = if key == "tasks" {
+                vec!["ID", "Title", "Status"]
+            } else {
+                vec!["ID", "Title"]
+            };
+            tabular = tabular.section(Some(title.to_string()), headers, rows);
+        }
+
+        let deep_walk = &data["deep_walk"];
+        if deep_walk["total_connected"].as_u64().unwrap_or(0) > 0 {
+            let rows = deep_walk["connected_entities"]
+                .as_array()
+                .unwrap_or(&empty_vec)
+                .iter()
+                .map(|entity| {
+                    let direction = if entity["direction"] == "outbound" {
+                        "->"
+                    } else {
+                        "<-"
+                    };
+                    vec![
+                        short_id(&entity["entity_id"]),
+                        direction.to_string(),
+                        text(&entity["entity_type"], "unknown"),
+                        text(&entity["relationship_type"], "Unknown"),
+                        entity["depth"].as_u64().unwrap_or(0).to_string(),
+                    ]
+                })
+                .collect();
+            tabular = tabular.section(
+                Some(format!(
+                    "Connected Entities (depth {} from {} seeds)",
+                    deep_walk["max_depth"].as_u64().unwrap_or(2),
+                    deep_walk["seed_count"].as_u64().unwrap_or(0)
+                )),
+                vec!["ID", "Direction", "Type", "Relationship", "Depth"],
+                rows,
+            );
+        }
+
+        tabular
+    }
+
     fn format_unknown(&self, data: &Value) -> Result<String, EngramError> {
         if let Some(error) = data.get("error") {
             let error_msg = error.as_str().unwrap_or("Unknown error");
@@ -645,12 +1123,6 @@ impl ResponseFormatter {
     }
 }
 
-impl Default for ResponseFormatter {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -759,6 +1231,166 @@ mod tests {
         assert!(!result.contains("Connected Entities"));
     }
 
+    const MODES: [OutputMode; 4] = [
+        OutputMode::Plain,
+        OutputMode::Table,
+        OutputMode::Markdown,
+        OutputMode::Json,
+    ];
+
+    fn processed(intent: QueryIntent) -> ProcessedQuery {
+        ProcessedQuery {
+            original_query: String::new(),
+            intent,
+            entities: vec![],
+            context: None,
+            confidence: 0.8,
+            suggestions: vec![],
+        }
+    }
+
+    /// Representative query mapper results, one per intent
+    fn intent_fixtures() -> Vec<(&'static str, QueryIntent, Value)> {
+        vec![
+            (
+                "list_tasks",
+                QueryIntent::ListTasks,
+                json!({
+                    "tasks": [
+                        {"id": "1a2b3c4d-0000-0000-0000-000000000001", "title": "Write docs", "status": "Todo", "priority": "High"},
+                        {"id": "5e6f7a8b-0000-0000-0000-000000000002", "title": "Fix | login", "status": "InProgress", "priority": "Medium"}
+                    ],
+                    "count": 2,
+                    "agent": "alice"
+                }),
+            ),
+            (
+                "show_task_details",
+                QueryIntent::ShowTaskDetails,
+                json!({
+                    "task": {
+                        "id": "1a2b3c4d-0000-0000-0000-000000000001",
+                        "title": "Write docs",
+                        "description": "Cover the CLI",
+                        "status": "Done",
+                        "priority": "High",
+                        "agent": "alice",
+                        "outcome": "Published"
+                    }
+                }),
+            ),
+            (
+                "find_relationships",
+                QueryIntent::FindRelationships,
+                json!({
+                    "task_id": "1a2b3c4d",
+                    "relationships": [{"type": "DependsOn", "target": "5e6f7a8b", "strength": "Strong"}],
+                    "count": 1
+                }),
+            ),
+            (
+                "search_context",
+                QueryIntent::SearchContext,
+                json!({
+                    "contexts": [{"id": "9c8d7e6f-0000", "title": "Auth design", "relevance": "High"}],
+                    "count": 1
+                }),
+            ),
+            (
+                "analyze_workflow",
+                QueryIntent::AnalyzeWorkflow,
+                json!({
+                    "workflows": [{"id": "0f1e2d3c-0000", "title": "Release", "current_state": "review", "status": "Active"}],
+                    "count": 1
+                }),
+            ),
+            (
+                "list_skills",
+                QueryIntent::ListSkills,
+                json!({"skills": [{"name": "tdd", "description": "Test first"}], "count": 1}),
+            ),
+            (
+                "search_skills",
+                QueryIntent::SearchSkills,
+                json!({"query": "test", "skills": [{"name": "tdd", "description": "Test first"}]}),
+            ),
+            (
+                "list_prompts",
+                QueryIntent::ListPrompts,
+                json!({"prompts": [{"name": "review", "title": "Code review"}], "count": 1}),
+            ),
+            (
+                "search_prompts",
+                QueryIntent::SearchPrompts,
+                json!({"query": "review", "prompts": [{"name": "review", "title": "Code review"}]}),
+            ),
+            (
+                "full_text_search",
+                QueryIntent::FullTextSearch,
+                json!({
+                    "query": "auth",
+                    "total_matches": 2,
+                    "tasks": [{"id": "1a2b3c4d-0000", "title": "Auth login", "status": "Todo"}],
+                    "adrs": [{"id": "2b3c4d5e-0000", "title": "Use OAuth", "number": 7}],
+                    "deep_walk": {
+                        "seed_count": 1,
+                        "max_depth": 2,
+                        "total_connected": 1,
+                        "connected_entities": [
+                            {"entity_id": "ctx-abcdef12", "entity_type": "context", "depth": 1, "relationship_type": "Explains", "direction": "outbound"}
+                        ]
+                    }
+                }),
+            ),
+            (
+                "unknown",
+                QueryIntent::Unknown,
+                json!({"error": "Could not understand query", "suggestion": "Try 'show my tasks'"}),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_snapshot_every_intent_and_mode() {
+        for (name, intent, data) in intent_fixtures() {
+            let query = processed(intent);
+            for mode in MODES {
+                let output = ResponseFormatter::with_mode(mode)
+                    .format(&query, &data)
+                    .unwrap();
+                let mode_name = format!("{:?}", mode).to_lowercase();
+                insta::assert_snapshot!(format!("{}_{}", name, mode_name), output);
+            }
+        }
+    }
+
+    #[test]
+    fn test_answers_without_rows_are_single_line() {
+        let query = processed(QueryIntent::ListTasks);
+        let data = json!({"tasks": [], "count": 0, "agent": "alice"});
+        for mode in MODES {
+            let output = ResponseFormatter::with_mode(mode)
+                .format(&query, &data)
+                .unwrap();
+            assert_eq!(output.lines().count(), 1, "{:?}: {}", mode, output);
+        }
+
+        let unknown = ResponseFormatter::with_mode(OutputMode::Markdown)
+            .format(
+                &processed(QueryIntent::Unknown),
+                &json!({"error": "Could not understand query", "suggestion": "Try 'show my tasks'"}),
+            )
+            .unwrap();
+        assert_eq!(unknown, "Could not understand query Try 'show my tasks'");
+    }
+
+    #[test]
+    fn test_output_mode_from_str() {
+        assert_eq!("table".parse::<OutputMode>().unwrap(), OutputMode::Table);
+        assert_eq!("MD".parse::<OutputMode>().unwrap(), OutputMode::Markdown);
+        assert!("yaml".parse::<OutputMode>().is_err());
+    }
+
     #[test]
     fn test_format_no_deep_walk() {
         let formatter = ResponseFormatter::new();
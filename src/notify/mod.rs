@@ -0,0 +1,587 @@
+//! Notification dispatch for Engram
+//!
+//! Events such as escalation creation, SLA breaches, rule actions and
+//! workflow `notification` actions are routed through [`dispatch`] to the
+//! sinks configured under `notifications.sinks` in `engram.yaml`:
+//!
+//! ```yaml
+//! notifications:
+//!   sinks:
+//!     - type: file
+//!       path: .engram/notifications.jsonl
+//!     - type: command
+//!       command: ./scripts/page-oncall.sh
+//!       event_types: [sla_breach]
+//!       min_priority: high
+//!     - type: desktop
+//! ```
+//!
+//! Each sink is independent: a failing sink is reported but never stops the
+//! remaining sinks from firing.
+
+use crate::config::Config;
+use crate::entities::{EscalationPriority, EscalationRequest, EscalationStatus};
+use crate::error::EngramError;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Event type for newly created escalation requests
+pub const ESCALATION_CREATED: &str = "escalation_created";
+/// Event type for escalation requests that passed their deadline unanswered
+pub const SLA_BREACH: &str = "sla_breach";
+/// Event type for `notify` rule actions
+pub const RULE_ACTION: &str = "rule_action";
+/// Event type for workflow `notification` actions
+pub const WORKFLOW_NOTIFICATION: &str = "workflow_notification";
+
+/// Workspace file critical alerts are appended to, for `engram info` and Locus
+pub const ALERTS_FILE: &str = ".engram/alerts.jsonl";
+
+/// Notification priority, ordered from least to most urgent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NotificationPriority {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl NotificationPriority {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "low" => Some(Self::Low),
+            "medium" | "normal" => Some(Self::Medium),
+            "high" => Some(Self::High),
+            "critical" => Some(Self::Critical),
+            _ => None,
+        }
+    }
+}
+
+impl From<&EscalationPriority> for NotificationPriority {
+    fn from(priority: &EscalationPriority) -> Self {
+        match priority {
+            EscalationPriority::Low => Self::Low,
+            EscalationPriority::Normal => Self::Medium,
+            EscalationPriority::High => Self::High,
+            EscalationPriority::Critical => Self::Critical,
+        }
+    }
+}
+
+/// Something worth telling a human about
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationEvent {
+    pub event_type: String,
+    pub priority: NotificationPriority,
+    pub title: String,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entity_id: Option<String>,
+    pub timestamp: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, String>,
+}
+
+impl NotificationEvent {
+    pub fn new(
+        event_type: &str,
+        priority: NotificationPriority,
+        title: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            event_type: event_type.to_string(),
+            priority,
+            title: title.into(),
+            message: message.into(),
+            entity_id: None,
+            timestamp: Utc::now(),
+            metadata: HashMap::new(),
+        }
+    }
+
+    pub fn with_entity(mut self, entity_id: impl Into<String>) -> Self {
+        self.entity_id = Some(entity_id.into());
+        self
+    }
+
+    pub fn escalation_created(escalation: &EscalationRequest) -> Self {
+        let review_command = escalation_review_command(escalation);
+        let mut event = Self::new(
+            ESCALATION_CREATED,
+            NotificationPriority::from(&escalation.priority),
+            match &escalation.suggested_reviewer {
+                Some(reviewer) => format!(
+                    "Escalation from {} for review by {}",
+                    escalation.agent_id, reviewer
+                ),
+                None => format!("Escalation from {}", escalation.agent_id),
+            },
+            format!(
+                "{} requests approval for '{}': {}\nReview with: {}",
+                escalation.agent_id,
+                escalation.operation_context.operation,
+                escalation.justification,
+                review_command
+            ),
+        )
+        .with_entity(escalation.id.clone());
+        event.metadata = HashMap::from([
+            ("escalation_id".to_string(), escalation.id.clone()),
+            ("agent".to_string(), escalation.agent_id.clone()),
+            (
+                "operation".to_string(),
+                escalation.operation_context.operation.clone(),
+            ),
+            (
+                "priority".to_string(),
+                format!("{:?}", escalation.priority).to_lowercase(),
+            ),
+            ("review_command".to_string(), review_command),
+        ]);
+        if let Some(reviewer) = &escalation.suggested_reviewer {
+            event
+                .metadata
+                .insert("reviewer".to_string(), reviewer.clone());
+        }
+        event
+    }
+
+    pub fn sla_breached(escalation: &EscalationRequest) -> Self {
+        Self::new(
+            SLA_BREACH,
+            NotificationPriority::from(&escalation.priority).max(NotificationPriority::High),
+            format!("Escalation {} expired", escalation.id),
+            format!(
+                "Escalation from {} for '{}' passed its deadline ({}) without review",
+                escalation.agent_id,
+                escalation.operation_context.operation,
+                escalation.expires_at.format("%Y-%m-%d %H:%M:%S")
+            ),
+        )
+        .with_entity(escalation.id.clone())
+    }
+}
+
+/// The `engram escalation review` command that resolves `escalation`
+pub fn escalation_review_command(escalation: &EscalationRequest) -> String {
+    let mut command = format!(
+        "engram escalation review {} --status <approved|denied> --reason \"...\"",
+        escalation.id
+    );
+    if let Some(reviewer) = &escalation.suggested_reviewer {
+        command.push_str(&format!(" --reviewer-id {}", reviewer));
+    }
+    command
+}
+
+/// Where a sink delivers events
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SinkKind {
+    /// Append each event as a JSON line
+    File { path: PathBuf },
+    /// Run a command with the message on stdin
+    Command {
+        command: String,
+        #[serde(default)]
+        args: Vec<String>,
+    },
+    /// Desktop notification (needs the `desktop-notifications` feature)
+    Desktop,
+}
+
+/// A configured sink and the events it accepts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SinkConfig {
+    #[serde(flatten)]
+    pub kind: SinkKind,
+    /// Only these event types; empty accepts all
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub event_types: Vec<String>,
+    /// Only events at or above this priority
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_priority: Option<NotificationPriority>,
+}
+
+impl SinkConfig {
+    pub fn accepts(&self, event: &NotificationEvent) -> bool {
+        (self.event_types.is_empty() || self.event_types.contains(&event.event_type))
+            && self.min_priority.is_none_or(|min| event.priority >= min)
+    }
+
+    pub fn name(&self) -> String {
+        match &self.kind {
+            SinkKind::File { path } => format!("file:{}", path.display()),
+            SinkKind::Command { command, .. } => format!("command:{}", command),
+            SinkKind::Desktop => "desktop".to_string(),
+        }
+    }
+
+    /// Deliver one event
+    pub fn send(&self, event: &NotificationEvent) -> Result<(), EngramError> {
+        match &self.kind {
+            SinkKind::File { path } => send_to_file(path, event),
+            SinkKind::Command { command, args } => send_to_command(command, args, event),
+            SinkKind::Desktop => send_to_desktop(event),
+        }
+    }
+}
+
+fn send_to_file(path: &Path, event: &NotificationEvent) -> Result<(), EngramError> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    writeln!(file, "{}", serde_json::to_string(event)?)?;
+    Ok(())
+}
+
+fn send_to_command(
+    command: &str,
+    args: &[String],
+    event: &NotificationEvent,
+) -> Result<(), EngramError> {
+    let mut child = Command::new(command)
+        .args(args)
+        .env("ENGRAM_EVENT_TYPE", &event.event_type)
+        .env(
+            "ENGRAM_EVENT_PRIORITY",
+            serde_json::to_value(event.priority)?
+                .as_str()
+                .unwrap_or_default(),
+        )
+        .env("ENGRAM_EVENT_TITLE", &event.title)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // A command that exits without reading stdin is not a failure
+        let _ = stdin.write_all(event.message.as_bytes());
+    }
+
+    let output = child.wait_with_output()?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let mut message = format!("'{}' exited with {}", command, output.status);
+        if !stderr.trim().is_empty() {
+            message.push_str(&format!(": {}", stderr.trim()));
+        }
+        Err(EngramError::InvalidOperation(message))
+    }
+}
+
+#[cfg(feature = "desktop-notifications")]
+fn send_to_desktop(event: &NotificationEvent) -> Result<(), EngramError> {
+    let status = if cfg!(target_os = "macos") {
+        let script = format!(
+            "display notification {:?} with title {:?}",
+            event.message, event.title
+        );
+        Command::new("osascript").args(["-e", &script]).status()?
+    } else {
+        let urgency = match event.priority {
+            NotificationPriority::Low => "low",
+            NotificationPriority::Medium => "normal",
+            NotificationPriority::High | NotificationPriority::Critical => "critical",
+        };
+        Command::new("notify-send")
+            .args(["--urgency", urgency, "--app-name", "engram"])
+            .arg(&event.title)
+            .arg(&event.message)
+            .status()?
+    };
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(EngramError::InvalidOperation(format!(
+            "Desktop notifier exited with {}",
+            status
+        )))
+    }
+}
+
+#[cfg(not(feature = "desktop-notifications"))]
+fn send_to_desktop(_event: &NotificationEvent) -> Result<(), EngramError> {
+    Err(EngramError::InvalidOperation(
+        "Desktop notifications require the 'desktop-notifications' feature".to_string(),
+    ))
+}
+
+/// `notifications` section of `engram.yaml`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NotificationConfig {
+    #[serde(default)]
+    pub sinks: Vec<SinkConfig>,
+}
+
+impl NotificationConfig {
+    /// Read the `notifications` section of `<workspace>/engram.yaml`
+    /// (or `engram.yml`). A missing file or section means no sinks.
+    pub fn load(workspace: &Path) -> Result<Self, EngramError> {
+        Config::load_section(workspace, "notifications")
+    }
+
+    pub fn from_yaml(content: &str) -> Result<Self, EngramError> {
+        Config::section_from_yaml(content, "notifications")
+    }
+}
+
+/// Outcome of dispatching one event
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DispatchReport {
+    /// Sinks the event was delivered to
+    pub delivered: Vec<String>,
+    /// Sinks whose filters excluded the event
+    pub filtered: Vec<String>,
+    /// (sink, error) for sinks that failed
+    pub failures: Vec<(String, String)>,
+}
+
+/// Send `event` to every sink in `config` that accepts it
+pub fn dispatch_with(config: &NotificationConfig, event: &NotificationEvent) -> DispatchReport {
+    let mut report = DispatchReport::default();
+    for sink in &config.sinks {
+        let name = sink.name();
+        if !sink.accepts(event) {
+            report.filtered.push(name);
+            continue;
+        }
+        match sink.send(event) {
+            Ok(()) => report.delivered.push(name),
+            Err(e) => {
+                tracing::warn!(sink = %name, error = %e, "Notification sink failed");
+                report.failures.push((name, e.to_string()));
+            }
+        }
+    }
+    report
+}
+
+/// Send `event` to the sinks configured for the current workspace.
+///
+/// Never fails: an unreadable config or failing sinks are logged and
+/// reported, so callers can notify without affecting their own outcome.
+pub fn dispatch(event: &NotificationEvent) -> DispatchReport {
+    match NotificationConfig::load(Path::new(".")) {
+        Ok(config) => dispatch_with(&config, event),
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to load notification config");
+            DispatchReport {
+                failures: vec![("config".to_string(), e.to_string())],
+                ..Default::default()
+            }
+        }
+    }
+}
+
+/// Append `event` to the workspace [`ALERTS_FILE`]
+pub fn record_alert(workspace: &Path, event: &NotificationEvent) -> Result<(), EngramError> {
+    send_to_file(&workspace.join(ALERTS_FILE), event)
+}
+
+/// Alerts in the workspace [`ALERTS_FILE`]; unreadable lines are skipped
+pub fn read_alerts(workspace: &Path) -> Result<Vec<NotificationEvent>, EngramError> {
+    let path = workspace.join(ALERTS_FILE);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    Ok(std::fs::read_to_string(path)?
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Alerts for escalations in `escalations` that are still awaiting review
+pub fn open_escalation_alerts(
+    alerts: Vec<NotificationEvent>,
+    escalations: &[EscalationRequest],
+) -> Vec<NotificationEvent> {
+    alerts
+        .into_iter()
+        .filter(|alert| {
+            escalations.iter().any(|escalation| {
+                alert.entity_id.as_deref() == Some(escalation.id.as_str())
+                    && escalation.status == EscalationStatus::Pending
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_lines(path: &Path) -> Vec<NotificationEvent> {
+        std::fs::read_to_string(path)
+            .unwrap_or_default()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_config_from_yaml() {
+        let config = NotificationConfig::from_yaml(
+            r#"
+app:
+  name: engram
+notifications:
+  sinks:
+    - type: file
+      path: out.jsonl
+    - type: command
+      command: notify.sh
+      args: ["--loud"]
+      event_types: [sla_breach]
+      min_priority: high
+    - type: desktop
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.sinks.len(), 3);
+        assert!(matches!(config.sinks[0].kind, SinkKind::File { .. }));
+        assert_eq!(config.sinks[1].event_types, vec![SLA_BREACH]);
+        assert_eq!(
+            config.sinks[1].min_priority,
+            Some(NotificationPriority::High)
+        );
+        assert!(matches!(config.sinks[2].kind, SinkKind::Desktop));
+
+        let empty = NotificationConfig::from_yaml("app:\n  name: engram\n").unwrap();
+        assert!(empty.sinks.is_empty());
+    }
+
+    #[test]
+    fn test_dispatch_filters_and_isolates_failures() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let all = tmp.path().join("all.jsonl");
+        let urgent = tmp.path().join("urgent.jsonl");
+        let config = NotificationConfig {
+            sinks: vec![
+                SinkConfig {
+                    kind: SinkKind::Command {
+                        command: "engram-missing-notifier".to_string(),
+                        args: Vec::new(),
+                    },
+                    event_types: Vec::new(),
+                    min_priority: None,
+                },
+                SinkConfig {
+                    kind: SinkKind::File { path: all.clone() },
+                    event_types: Vec::new(),
+                    min_priority: None,
+                },
+                SinkConfig {
+                    kind: SinkKind::File {
+                        path: urgent.clone(),
+                    },
+                    event_types: vec![SLA_BREACH.to_string()],
+                    min_priority: Some(NotificationPriority::High),
+                },
+            ],
+        };
+
+        let routine = NotificationEvent::new(
+            RULE_ACTION,
+            NotificationPriority::Medium,
+            "Rule fired",
+            "routine",
+        );
+        let report = dispatch_with(&config, &routine);
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.delivered.len(), 1);
+        assert_eq!(report.filtered.len(), 1);
+
+        let breach = NotificationEvent::new(
+            SLA_BREACH,
+            NotificationPriority::Critical,
+            "Expired",
+            "too slow",
+        )
+        .with_entity("esc-1");
+        let report = dispatch_with(&config, &breach);
+        assert_eq!(report.delivered.len(), 2);
+
+        assert_eq!(read_lines(&all).len(), 2);
+        let urgent_events = read_lines(&urgent);
+        assert_eq!(urgent_events.len(), 1);
+        assert_eq!(urgent_events[0].entity_id.as_deref(), Some("esc-1"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_escalation_event_and_critical_alerts() {
+        let mut escalation = EscalationRequest::new(
+            "agent-1".to_string(),
+            crate::entities::EscalationOperationType::NetworkAccess,
+            crate::entities::OperationContext {
+                operation: "curl example.com".to_string(),
+                parameters: HashMap::new(),
+                resource: None,
+                block_reason: "Network restricted".to_string(),
+                alternatives: Vec::new(),
+                risk_assessment: None,
+            },
+            "Need the API schema".to_string(),
+            EscalationPriority::Critical,
+            "agent-1".to_string(),
+        );
+        escalation.suggested_reviewer = Some("alice".to_string());
+
+        let event = NotificationEvent::escalation_created(&escalation);
+        assert_eq!(event.priority, NotificationPriority::Critical);
+        assert_eq!(event.title, "Escalation from agent-1 for review by alice");
+        let command = format!(
+            "engram escalation review {} --status <approved|denied> --reason \"...\" --reviewer-id alice",
+            escalation.id
+        );
+        assert_eq!(event.metadata["review_command"], command);
+        assert!(event.message.ends_with(&command));
+        assert_eq!(event.metadata["operation"], "curl example.com");
+        assert_eq!(event.metadata["priority"], "critical");
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        record_alert(tmp.path(), &event).unwrap();
+        let alerts = read_alerts(tmp.path()).unwrap();
+        assert_eq!(
+            open_escalation_alerts(alerts.clone(), std::slice::from_ref(&escalation)).len(),
+            1
+        );
+        escalation.status = EscalationStatus::Approved;
+        assert!(open_escalation_alerts(alerts, &[escalation]).is_empty());
+    }
+
+    #[test]
+    fn test_command_sink_receives_message_on_stdin() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let out = tmp.path().join("stdin.txt");
+        let sink = SinkConfig {
+            kind: SinkKind::Command {
+                command: "sh".to_string(),
+                args: vec!["-c".to_string(), format!("cat > {}", out.display())],
+            },
+            event_types: Vec::new(),
+            min_priority: None,
+        };
+
+        let event = NotificationEvent::new("test", NotificationPriority::Low, "Hi", "hello sink");
+        sink.send(&event).unwrap();
+        assert_eq!(std::fs::read_to_string(out).unwrap(), "hello sink");
+    }
+}
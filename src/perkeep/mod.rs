@@ -25,13 +25,29 @@
 //!
 //! # List backups
 //! engram perkeep list
+//!
+//! # Find backed-up entities by type and agent
+//! engram perkeep list --type task --agent default --since 2024-01-01
 //! ```
 
+use crate::entities::GenericEntity;
 use crate::error::EngramError;
 use digest::Digest;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// `camliEtc` attribute holding the backed-up entity's type
+pub const ENTITY_TYPE_ATTR: &str = "engram_entity_type";
+
+/// `camliEtc` attribute holding the backed-up entity's agent
+pub const AGENT_ATTR: &str = "engram_agent";
+
+/// `camliEtc` attribute holding the backed-up entity's timestamp (RFC 3339)
+pub const TIMESTAMP_ATTR: &str = "engram_timestamp";
+
+/// `camliEtc` attribute holding the backed-up entity's ID
+pub const ENTITY_ID_ATTR: &str = "engram_entity_id";
+
 /// Perkeep client configuration
 #[derive(Debug, Clone)]
 pub struct PerkeepConfig {
@@ -279,6 +295,130 @@ mod tests {
         assert_eq!(metadata.total_size, 0);
     }
 
+    /// Answer each search with the canned response for its expression,
+    /// recording every expression received
+    fn mock_search_server(
+        responses: HashMap<String, serde_json::Value>,
+    ) -> (String, std::sync::Arc<std::sync::Mutex<Vec<String>>>) {
+        use std::io::{BufRead, BufReader, Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let received = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = received.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let mut reader = BufReader::new(stream);
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.trim().parse().unwrap();
+                        }
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+                let query: serde_json::Value = serde_json::from_slice(&body).unwrap();
+                let expression = query["expression"].as_str().unwrap().to_string();
+                let response = responses
+                    .get(&expression)
+                    .cloned()
+                    .unwrap_or_else(|| serde_json::json!({ "matches": [] }))
+                    .to_string();
+                recorded.lock().unwrap().push(expression);
+                let mut stream = reader.into_inner();
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    response.len(),
+                    response
+                )
+                .unwrap();
+            }
+        });
+        (url, received)
+    }
+
+    #[tokio::test]
+    async fn test_search_by_metadata_sends_attr_expression() {
+        let task_expression = "attr:engram_agent:default attr:engram_entity_type:task";
+        let (url, received) = mock_search_server(HashMap::from([(
+            task_expression.to_string(),
+            serde_json::json!({ "matches": [{
+                "blob": { "blobref": "sha256-abc", "size": 120, "sha256": "abc" },
+                "camliEtc": {
+                    "engram_entity_id": "task-1",
+                    "engram_entity_type": "task",
+                    "engram_agent": "default",
+                    "engram_timestamp": "2024-02-01T10:00:00+00:00"
+                }
+            }] }),
+        )]));
+        let client = PerkeepClient::new(PerkeepConfig {
+            server_url: url,
+            auth_token: None,
+            verify_tls: true,
+        })
+        .unwrap();
+
+        let filters = HashMap::from([
+            (ENTITY_TYPE_ATTR.to_string(), "task".to_string()),
+            (AGENT_ATTR.to_string(), "default".to_string()),
+        ]);
+        let summaries = client.search_by_metadata(&filters).await.unwrap();
+        assert_eq!(
+            summaries,
+            vec![BackupSummary {
+                blobref: "sha256-abc".to_string(),
+                entity_id: "task-1".to_string(),
+                entity_type: "task".to_string(),
+                agent: "default".to_string(),
+                timestamp: "2024-02-01T10:00:00+00:00".to_string(),
+            }]
+        );
+
+        let filters = HashMap::from([(AGENT_ATTR.to_string(), "someone-else".to_string())]);
+        assert!(client
+            .search_by_metadata(&filters)
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            *received.lock().unwrap(),
+            vec![task_expression, "attr:engram_agent:someone-else"]
+        );
+    }
+
+    #[test]
+    fn test_entity_schema_attributes() {
+        let entity = GenericEntity {
+            id: "task-1".to_string(),
+            entity_type: "task".to_string(),
+            agent: "default".to_string(),
+            timestamp: "2024-02-01T10:00:00Z".parse().unwrap(),
+            data: serde_json::json!({}),
+        };
+        let content = BlobRef {
+            blobref: "sha256-abc".to_string(),
+            size: 2,
+            sha256: "abc".to_string(),
+        };
+
+        let schema = SchemaObject::for_entity(&entity, content);
+        let attributes = schema.custom_attributes.unwrap();
+        assert_eq!(attributes[ENTITY_TYPE_ATTR], "task");
+        assert_eq!(attributes[AGENT_ATTR], "default");
+        assert_eq!(attributes[ENTITY_ID_ATTR], "task-1");
+        assert_eq!(attributes[TIMESTAMP_ATTR], "2024-02-01T10:00:00+00:00");
+        assert_eq!(schema.base_value_ref.unwrap().blobref, "sha256-abc");
+    }
+
     #[test]
     fn test_backup_metadata_timestamp_format() {
         let metadata = EngramBackupMetadata::new(
@@ -343,6 +483,73 @@ pub struct SchemaObject {
     pub custom_attributes: Option<HashMap<String, serde_json::Value>>,
 }
 
+impl SchemaObject {
+    /// Schema describing an entity blob, with `camliEtc` attributes that
+    /// make it discoverable through [`PerkeepClient::search_by_metadata`]
+    pub fn for_entity(entity: &GenericEntity, content: BlobRef) -> Self {
+        let attributes = HashMap::from([
+            (ENTITY_ID_ATTR.to_string(), entity.id.clone().into()),
+            (
+                ENTITY_TYPE_ATTR.to_string(),
+                entity.entity_type.clone().into(),
+            ),
+            (AGENT_ATTR.to_string(), entity.agent.clone().into()),
+            (
+                TIMESTAMP_ATTR.to_string(),
+                entity.timestamp.to_rfc3339().into(),
+            ),
+        ]);
+        Self {
+            camli_type: "engram.net/entity".to_string(),
+            size: Some(content.size),
+            base_value_ref: Some(content),
+            file_name: Some(format!("{}-{}.json", entity.entity_type, entity.id)),
+            mime_type: Some("application/json".to_string()),
+            title: None,
+            description: None,
+            creation_time: Some(entity.timestamp.to_rfc3339()),
+            custom_attributes: Some(attributes),
+        }
+    }
+}
+
+/// An entity backup found by [`PerkeepClient::search_by_metadata`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackupSummary {
+    /// Blob reference of the matching schema blob
+    pub blobref: String,
+
+    /// ID of the backed-up entity
+    pub entity_id: String,
+
+    /// Type of the backed-up entity
+    pub entity_type: String,
+
+    /// Agent that owns the entity
+    pub agent: String,
+
+    /// Entity timestamp (RFC 3339)
+    pub timestamp: String,
+}
+
+/// Build a Perkeep search expression such as
+/// `attr:engram_agent:default attr:engram_entity_type:task`, ordered by attribute
+pub fn metadata_expression(filters: &HashMap<String, String>) -> String {
+    let mut terms: Vec<String> = filters
+        .iter()
+        .map(|(attr, value)| format!("attr:{}:{}", attr, value))
+        .collect();
+    terms.sort();
+    terms.join(" ")
+}
+
+#[derive(Deserialize)]
+struct SearchMatch {
+    blob: BlobRef,
+    #[serde(default, rename = "camliEtc")]
+    attributes: HashMap<String, serde_json::Value>,
+}
+
 /// Perkeep client for API communication
 #[derive(Debug, Clone)]
 pub struct PerkeepClient {
@@ -437,6 +644,18 @@ impl PerkeepClient {
         self.upload_blob(&data).await
     }
 
+    /// Upload an entity blob plus a schema blob carrying its `engram_*`
+    /// attributes, returning the entity blob reference
+    pub async fn upload_entity(&self, entity: &GenericEntity) -> Result<BlobRef, EngramError> {
+        let data = serde_json::to_vec(entity).map_err(|e| {
+            EngramError::InvalidOperation(format!("Failed to serialize entity: {}", e))
+        })?;
+        let content = self.upload_blob(&data).await?;
+        self.upload_schema(&SchemaObject::for_entity(entity, content.clone()))
+            .await?;
+        Ok(content)
+    }
+
     /// Fetch a blob by reference
     pub async fn fetch_blob(&self, blobref: &str) -> Result<Option<Vec<u8>>, EngramError> {
         let url = self.blob_url(blobref);
@@ -467,8 +686,44 @@ impl PerkeepClient {
 
     /// Search for blobs with a query
     pub async fn search_blobs(&self, query: &str) -> Result<Vec<BlobRef>, EngramError> {
+        Ok(self
+            .search(query)
+            .await?
+            .into_iter()
+            .map(|m| m.blob)
+            .collect())
+    }
+
+    /// Search entity backups by `camliEtc` attribute, e.g.
+    /// `{"engram_entity_type": "task", "engram_agent": "default"}`
+    pub async fn search_by_metadata(
+        &self,
+        filters: &HashMap<String, String>,
+    ) -> Result<Vec<BackupSummary>, EngramError> {
+        let attribute = |m: &SearchMatch, name: &str| {
+            m.attributes
+                .get(name)
+                .and_then(|value| value.as_str())
+                .unwrap_or_default()
+                .to_string()
+        };
+        Ok(self
+            .search(&metadata_expression(filters))
+            .await?
+            .iter()
+            .map(|m| BackupSummary {
+                blobref: m.blob.blobref.clone(),
+                entity_id: attribute(m, ENTITY_ID_ATTR),
+                entity_type: attribute(m, ENTITY_TYPE_ATTR),
+                agent: attribute(m, AGENT_ATTR),
+                timestamp: attribute(m, TIMESTAMP_ATTR),
+            })
+            .collect())
+    }
+
+    async fn search(&self, expression: &str) -> Result<Vec<SearchMatch>, EngramError> {
         let search_query = serde_json::json!({
-            "expression": query
+            "expression": expression
         });
 
         let response = self
@@ -491,18 +746,14 @@ impl PerkeepClient {
 
         #[derive(Deserialize)]
         struct SearchResponse {
-            matches: Vec<Match>,
-        }
-        #[derive(Deserialize)]
-        struct Match {
-            blob: BlobRef,
+            matches: Vec<SearchMatch>,
         }
 
         let result: SearchResponse = response.json().await.map_err(|e| {
             EngramError::InvalidOperation(format!("Failed to parse search results: {}", e))
         })?;
 
-        Ok(result.matches.into_iter().map(|m| m.blob).collect())
+        Ok(result.matches)
     }
 
     /// Check if the Perkeep server is accessible
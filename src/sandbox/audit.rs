@@ -0,0 +1,60 @@
+//! Sandbox decision audit log
+//!
+//! Each validated request is appended as one JSON line together with the
+//! decision the engine made, so past traffic can later be replayed against a
+//! different sandbox configuration.
+
+use crate::error::EngramError;
+use crate::sandbox::{SandboxDecision, SandboxRequest, SandboxResponse};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::Path;
+
+/// Workspace-relative path of the sandbox audit log
+pub const SANDBOX_AUDIT_FILE: &str = ".engram/sandbox_audit.jsonl";
+
+/// One recorded sandbox decision
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SandboxAuditEntry {
+    pub request: SandboxRequest,
+    pub decision: SandboxDecision,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    pub recorded_at: DateTime<Utc>,
+}
+
+impl SandboxAuditEntry {
+    pub fn new(request: SandboxRequest, response: &SandboxResponse) -> Self {
+        Self {
+            request,
+            decision: response.decision(),
+            reason: response.reason().map(str::to_string),
+            recorded_at: Utc::now(),
+        }
+    }
+}
+
+/// Append `entry` to the audit log at `path`, creating parent directories
+pub fn append_audit_entry(path: &Path, entry: &SandboxAuditEntry) -> Result<(), EngramError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)?;
+    Ok(())
+}
+
+/// Entries in the audit log at `path`; unreadable lines are skipped
+pub fn read_audit_log(path: &Path) -> Result<Vec<SandboxAuditEntry>, EngramError> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    Ok(std::fs::read_to_string(path)?
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
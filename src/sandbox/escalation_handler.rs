@@ -5,6 +5,8 @@
 
 #![allow(clippy::collapsible_if, clippy::needless_borrows_for_generic_args)]
 
+pub use crate::entities::EscalationStatistics;
+
 use crate::entities::{
     Entity, EscalationOperationType, EscalationPriority, EscalationRequest, EscalationStatus,
     OperationContext, ReviewDecision, ReviewerInfo,
@@ -82,6 +84,10 @@ impl EscalationHandler {
             SandboxError::StorageError(format!("Failed to store escalation: {}", e))
         })?;
 
+        crate::notify::dispatch(&crate::notify::NotificationEvent::escalation_created(
+            &escalation,
+        ));
+
         // Cache for quick lookup
         let escalation_id = escalation.id.clone();
         self.escalation_cache
@@ -339,49 +345,17 @@ impl EscalationHandler {
             SandboxError::StorageError(format!("Failed to list escalations: {}", e))
         })?;
 
-        let mut stats = EscalationStatistics {
-            total_requests: all_ids.len(),
-            ..Default::default()
-        };
-
+        let mut escalations = Vec::with_capacity(all_ids.len());
         for id in all_ids {
             if let Ok(escalation) = self.get_escalation(&id).await {
-                match escalation.status {
-                    EscalationStatus::Pending => stats.pending_count += 1,
-                    EscalationStatus::Approved => {
-                        stats.approved_count += 1;
-                        if let Some(decision) = &escalation.decision {
-                            if let Some(duration) = decision.approval_duration {
-                                stats.total_approval_duration_seconds += duration;
-                            }
-                        }
-                    }
-                    EscalationStatus::Denied => stats.denied_count += 1,
-                    EscalationStatus::Expired => stats.expired_count += 1,
-                    EscalationStatus::Cancelled => stats.cancelled_count += 1,
-                }
-
-                // Calculate average response time for reviewed requests
-                if let Some(reviewed_at) = escalation.reviewed_at {
-                    let response_time = (reviewed_at - escalation.created_at).num_seconds();
-                    stats.total_response_time_seconds += response_time as u64;
-                    stats.reviewed_count += 1;
-                }
+                escalations.push(escalation);
             }
         }
 
-        // Calculate averages
-        if stats.reviewed_count > 0 {
-            stats.average_response_time_seconds =
-                stats.total_response_time_seconds / stats.reviewed_count as u64;
-        }
-
-        if stats.approved_count > 0 {
-            stats.average_approval_duration_seconds =
-                stats.total_approval_duration_seconds / stats.approved_count as u64;
-        }
-
-        Ok(stats)
+        Ok(EscalationStatistics::from_escalations(
+            escalations,
+            Utc::now(),
+        ))
     }
 
     /// Check if an agent has an active approval for a specific operation
@@ -394,24 +368,10 @@ impl EscalationHandler {
         let now = Utc::now();
 
         for escalation in agent_escalations {
-            if escalation.status == EscalationStatus::Approved
-                && escalation.operation_context.operation == operation
+            if escalation.operation_context.operation == operation
+                && escalation.approval_active(now)
             {
-                // Check if approval is still valid
-                if let Some(decision) = &escalation.decision {
-                    if let Some(duration_seconds) = decision.approval_duration {
-                        if let Some(reviewed_at) = escalation.reviewed_at {
-                            let expires_at =
-                                reviewed_at + chrono::Duration::seconds(duration_seconds as i64);
-                            if now < expires_at {
-                                return Ok(Some(escalation));
-                            }
-                        }
-                    } else {
-                        // Approval has no expiration
-                        return Ok(Some(escalation));
-                    }
-                }
+                return Ok(Some(escalation));
             }
         }
 
@@ -490,22 +450,6 @@ impl EscalationHandler {
     }
 }
 
-/// Statistics about escalation requests
-#[derive(Debug, Clone, Default)]
-pub struct EscalationStatistics {
-    pub total_requests: usize,
-    pub pending_count: usize,
-    pub approved_count: usize,
-    pub denied_count: usize,
-    pub expired_count: usize,
-    pub cancelled_count: usize,
-    pub reviewed_count: usize,
-    pub total_response_time_seconds: u64,
-    pub average_response_time_seconds: u64,
-    pub total_approval_duration_seconds: u64,
-    pub average_approval_duration_seconds: u64,
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -6,29 +6,37 @@
 //! - Command filtering
 //! - Escalation handling
 
+pub mod audit;
 pub mod command_validator;
 pub mod ephemeral_env;
 pub mod escalation_handler;
 pub mod permission_engine;
 pub mod preflight;
 pub mod resource_monitor;
+pub mod simulation;
+pub mod storage_guard;
 
 use crate::entities::agent_sandbox::OperationType;
 use crate::entities::{
     AgentSandbox, Entity, EscalationOperationType, EscalationPriority, EscalationRequest,
     OperationContext, SandboxLevel,
 };
+use crate::health::{EngineHealthCheck, HealthState, HealthStatus};
 use crate::storage::Storage;
 use chrono::{DateTime, Duration as ChronoDuration, Utc};
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::time::Instant;
 use thiserror::Error;
 
+pub use audit::{append_audit_entry, read_audit_log, SandboxAuditEntry, SANDBOX_AUDIT_FILE};
 pub use command_validator::CommandValidator;
 pub use ephemeral_env::{ExecutionResult, NixSandbox, NixSandboxConfig};
 pub use escalation_handler::{EscalationHandler, EscalationStatistics};
 pub use permission_engine::PermissionEngine;
-pub use resource_monitor::ResourceMonitor;
+pub use resource_monitor::{ResourceMonitor, STORE_ENTITY_OPERATION};
+pub use simulation::{simulate_requests, DecisionChange, SimulationReport};
+pub use storage_guard::SandboxStorage;
 
 /// Errors that can occur during sandbox operations
 #[derive(Error, Debug)]
@@ -56,7 +64,7 @@ pub enum SandboxError {
 pub type SandboxResult<T> = Result<T, SandboxError>;
 
 /// Request context for sandbox validation
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct SandboxRequest {
     pub agent_id: String,
     pub operation: String,
@@ -95,6 +103,66 @@ pub enum SandboxResponse {
     },
 }
 
+impl SandboxResponse {
+    /// The decision kind, without its details
+    pub fn decision(&self) -> SandboxDecision {
+        match self {
+            SandboxResponse::Allow { .. } => SandboxDecision::Allow,
+            SandboxResponse::Deny { .. } => SandboxDecision::Deny,
+            SandboxResponse::Escalate { .. } => SandboxDecision::Escalate,
+            SandboxResponse::Defer { .. } => SandboxDecision::Defer,
+        }
+    }
+
+    /// Why the operation was not allowed outright
+    pub fn reason(&self) -> Option<&str> {
+        match self {
+            SandboxResponse::Allow { .. } => None,
+            SandboxResponse::Deny { reason, .. }
+            | SandboxResponse::Escalate { reason, .. }
+            | SandboxResponse::Defer { reason, .. } => Some(reason),
+        }
+    }
+}
+
+/// Kind of decision made for a sandbox request
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum SandboxDecision {
+    Allow,
+    Deny,
+    Escalate,
+    Defer,
+}
+
+impl std::fmt::Display for SandboxDecision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            SandboxDecision::Allow => "allow",
+            SandboxDecision::Deny => "deny",
+            SandboxDecision::Escalate => "escalate",
+            SandboxDecision::Defer => "defer",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Outcome of evaluating a request before any side effects
+enum Evaluation {
+    Decided(SandboxResponse),
+    NeedsEscalation {
+        reason: String,
+        timeout: ChronoDuration,
+    },
+}
+
+fn escalation_timeout(sandbox: &AgentSandbox) -> ChronoDuration {
+    ChronoDuration::from_std(sandbox.escalation_policy.escalation_timeout)
+        .unwrap_or(ChronoDuration::minutes(10))
+}
+
 /// Main sandbox engine that orchestrates validation
 pub struct SandboxEngine {
     permission_engine: PermissionEngine,
@@ -102,6 +170,7 @@ pub struct SandboxEngine {
     command_validator: CommandValidator,
     storage: Box<dyn Storage>,
     start_time: Instant,
+    audit_log: Option<PathBuf>,
 }
 
 impl SandboxEngine {
@@ -113,9 +182,16 @@ impl SandboxEngine {
             command_validator: CommandValidator::new(),
             storage,
             start_time: Instant::now(),
+            audit_log: None,
         }
     }
 
+    /// Append every decision made by [`validate_request`](Self::validate_request) to `path`
+    pub fn with_audit_log(mut self, path: impl Into<PathBuf>) -> Self {
+        self.audit_log = Some(path.into());
+        self
+    }
+
     /// Validate a sandbox request against all constraints
     pub async fn validate_request(
         &mut self,
@@ -124,44 +200,102 @@ impl SandboxEngine {
         // Get sandbox configuration for the agent
         let sandbox = self.get_agent_sandbox(&request.agent_id).await?;
 
+        let response = match self.evaluate(&request, &sandbox).await? {
+            Evaluation::Decided(response) => response,
+            Evaluation::NeedsEscalation { reason, timeout } => {
+                let escalation_id = self
+                    .create_escalation_request(&request, &sandbox, &reason)
+                    .await?;
+                SandboxResponse::Escalate {
+                    reason,
+                    escalation_id,
+                    timeout,
+                }
+            }
+        };
+
+        if let Some(path) = &self.audit_log {
+            let entry = SandboxAuditEntry::new(request, &response);
+            if let Err(e) = append_audit_entry(path, &entry) {
+                tracing::warn!("Failed to record sandbox audit entry: {}", e);
+            }
+        }
+        Ok(response)
+    }
+
+    /// Decide `request` against `sandbox` without side effects
+    ///
+    /// Unlike [`validate_request`](Self::validate_request) this never looks up
+    /// or creates sandboxes, files escalations or writes the audit log, so it
+    /// can replay history against a hypothetical configuration.
+    pub async fn simulate_request(
+        &mut self,
+        request: &SandboxRequest,
+        sandbox: &AgentSandbox,
+    ) -> SandboxResult<SandboxDecision> {
+        Ok(match self.evaluate(request, sandbox).await? {
+            Evaluation::Decided(response) => response.decision(),
+            Evaluation::NeedsEscalation { .. } => SandboxDecision::Escalate,
+        })
+    }
+
+    async fn evaluate(
+        &mut self,
+        request: &SandboxRequest,
+        sandbox: &AgentSandbox,
+    ) -> SandboxResult<Evaluation> {
         // Step 1: Permission validation
         if let Err(e) = self
             .permission_engine
-            .validate_operation(&request, &sandbox.permissions)
+            .validate_operation(request, &sandbox.permissions)
             .await
         {
-            return Ok(SandboxResponse::Deny {
+            return Ok(Evaluation::Decided(SandboxResponse::Deny {
                 reason: format!("Permission denied: {}", e),
                 suggestion: Some(
                     "Request elevated permissions or contact administrator".to_string(),
                 ),
-            });
+            }));
         }
 
         // Step 2: Resource limits validation
-        if let Err(e) = self
+        match self
             .resource_monitor
-            .check_limits(&request.agent_id, &request, &sandbox.resource_limits)
+            .check_limits(
+                &request.agent_id,
+                request,
+                &sandbox.resource_limits,
+                &sandbox.usage,
+            )
             .await
         {
-            return Ok(SandboxResponse::Deny {
-                reason: format!("Resource limit exceeded: {}", e),
-                suggestion: Some("Reduce resource usage or request higher limits".to_string()),
-            });
+            Ok(()) => {}
+            Err(SandboxError::EscalationRequired(reason)) => {
+                return Ok(Evaluation::NeedsEscalation {
+                    reason: format!("Resource limit exceeded: {}", reason),
+                    timeout: escalation_timeout(sandbox),
+                });
+            }
+            Err(e) => {
+                return Ok(Evaluation::Decided(SandboxResponse::Deny {
+                    reason: format!("Resource limit exceeded: {}", e),
+                    suggestion: Some("Reduce resource usage or request higher limits".to_string()),
+                }));
+            }
         }
 
         // Step 3: Command filtering
         match self
             .command_validator
-            .validate_command(&request, &sandbox.command_filter)
+            .validate_command(request, &sandbox.command_filter)
             .await?
         {
             CommandValidationResult::Allow => {}
             CommandValidationResult::Block(reason) => {
-                return Ok(SandboxResponse::Deny {
+                return Ok(Evaluation::Decided(SandboxResponse::Deny {
                     reason: format!("Command blocked: {}", reason),
                     suggestion: Some("Use alternative commands or request permission".to_string()),
-                });
+                }));
             }
             CommandValidationResult::RequiresApproval => {
                 // Check escalation policy
@@ -171,27 +305,22 @@ impl SandboxEngine {
                     .iter()
                     .any(|op_type| self.matches_operation_type(&request.operation, op_type))
                 {
-                    let escalation_id = self.create_escalation_request(&request, &sandbox).await?;
-                    return Ok(SandboxResponse::Escalate {
+                    return Ok(Evaluation::NeedsEscalation {
                         reason: "Operation requires human approval".to_string(),
-                        escalation_id,
-                        timeout: ChronoDuration::from_std(
-                            sandbox.escalation_policy.escalation_timeout,
-                        )
-                        .unwrap_or(ChronoDuration::minutes(10)),
+                        timeout: escalation_timeout(sandbox),
                     });
                 }
             }
         }
 
         // Step 4: Check if monitoring is required
-        let monitoring_required = self.requires_monitoring(&request, &sandbox);
+        let monitoring_required = self.requires_monitoring(request, sandbox);
 
         // Operation is allowed
-        Ok(SandboxResponse::Allow {
-            conditions: self.get_operation_conditions(&request, &sandbox),
+        Ok(Evaluation::Decided(SandboxResponse::Allow {
+            conditions: self.get_operation_conditions(request, sandbox),
             monitoring_required,
-        })
+        }))
     }
 
     /// Get sandbox configuration for an agent
@@ -238,6 +367,7 @@ impl SandboxEngine {
         &mut self,
         request: &SandboxRequest,
         sandbox: &AgentSandbox,
+        reason: &str,
     ) -> SandboxResult<String> {
         let operation_type = self.infer_escalation_operation_type(&request.operation);
         let priority = self.infer_escalation_priority(sandbox, &request.operation);
@@ -249,10 +379,7 @@ impl SandboxEngine {
                 None => HashMap::new(),
             },
             resource: Some(request.resource_type.clone()),
-            block_reason: format!(
-                "Operation blocked by sandbox level {:?}",
-                sandbox.sandbox_level
-            ),
+            block_reason: format!("{} (sandbox level {:?})", reason, sandbox.sandbox_level),
             alternatives: self.suggest_alternatives(&request.operation),
             risk_assessment: Some(self.assess_risk(&request.operation)),
         };
@@ -277,12 +404,16 @@ impl SandboxEngine {
         self.storage.store(&generic_entity).map_err(|e| {
             SandboxError::StorageError(format!("Failed to store escalation: {}", e))
         })?;
+        crate::notify::dispatch(&crate::notify::NotificationEvent::escalation_created(
+            &escalation,
+        ));
 
         Ok(escalation_id)
     }
 
     fn infer_escalation_operation_type(&self, operation: &str) -> EscalationOperationType {
         match operation {
+            STORE_ENTITY_OPERATION => EscalationOperationType::ResourceLimitIncrease,
             op if op.contains("file") || op.contains("File") => {
                 EscalationOperationType::FileSystemAccess
             }
@@ -508,6 +639,41 @@ impl SandboxEngine {
     }
 }
 
+impl EngineHealthCheck for SandboxEngine {
+    /// Unreadable sandboxes are unhealthy: the engine skips them, so their
+    /// agents silently fall back to a default sandbox
+    fn health_check(&self) -> crate::Result<HealthStatus> {
+        let entities = self.storage.get_all("agent_sandbox")?;
+        let total = entities.len();
+        let broken: Vec<String> = entities
+            .into_iter()
+            .filter_map(|entity| {
+                let id = entity.id.clone();
+                AgentSandbox::from_generic(entity).err().map(|_| id)
+            })
+            .collect();
+
+        Ok(if broken.is_empty() {
+            HealthStatus::new(
+                "sandbox",
+                HealthState::Healthy,
+                format!("{} sandbox(es) loaded", total),
+            )
+        } else {
+            HealthStatus::new(
+                "sandbox",
+                HealthState::Unhealthy,
+                format!(
+                    "{} of {} sandbox(es) unreadable: {}",
+                    broken.len(),
+                    total,
+                    broken.join(", ")
+                ),
+            )
+        })
+    }
+}
+
 /// Command validation result
 #[derive(Debug, Clone)]
 pub enum CommandValidationResult {
@@ -635,6 +801,22 @@ mod tests {
         ));
     }
 
+    #[tokio::test]
+    async fn test_validate_records_audit_log() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(SANDBOX_AUDIT_FILE);
+        let mut e = SandboxEngine::new(create_test_storage()).with_audit_log(&path);
+        e.validate_request(tr("list_files")).await.unwrap();
+        e.validate_request(tr("delete_file")).await.unwrap();
+
+        let entries = read_audit_log(&path).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].decision, SandboxDecision::Allow);
+        assert_eq!(entries[1].request.operation, "delete_file");
+        assert_eq!(entries[1].decision, SandboxDecision::Deny);
+        assert!(entries[1].reason.is_some());
+    }
+
     #[tokio::test]
     async fn test_validate_no_session() {
         let mut e = SandboxEngine::new(create_test_storage());
@@ -1,18 +1,83 @@
-use crate::entities::{FileOperation, NetworkPolicy, PermissionSet};
+use crate::entities::{FileOperation, NetworkPolicy, OperationType, PermissionSet};
 use crate::sandbox::{SandboxError, SandboxRequest, SandboxResult};
+use globset::{GlobBuilder, GlobMatcher};
 use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+
+/// Compile a path glob; `*` stays within one path segment, `**` spans many
+pub fn compile_path_glob(pattern: &str) -> Result<GlobMatcher, globset::Error> {
+    Ok(GlobBuilder::new(pattern)
+        .literal_separator(true)
+        .build()?
+        .compile_matcher())
+}
+
+fn path_matches(pattern: &str, path: &str) -> bool {
+    compile_path_glob(pattern)
+        .map(|glob| glob.is_match(path))
+        .unwrap_or(false)
+}
+
+/// Resolve `.` and `..` segments without touching the filesystem
+///
+/// Returns `None` when a relative path climbs above its starting point.
+fn lexical_normalize(path: &Path) -> Option<PathBuf> {
+    let mut normalized = PathBuf::new();
+    let mut depth = 0usize;
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir if depth > 0 => {
+                normalized.pop();
+                depth -= 1;
+            }
+            // `/..` is `/`; anything relative that climbs out is rejected
+            Component::ParentDir if path.has_root() => {}
+            Component::ParentDir => return None,
+            Component::Normal(segment) => {
+                normalized.push(segment);
+                depth += 1;
+            }
+            Component::RootDir | Component::Prefix(_) => normalized.push(component),
+        }
+    }
+    Some(normalized)
+}
+
+fn operation_label(operation: &OperationType) -> &'static str {
+    match operation {
+        OperationType::FileWrite => "file_write",
+        OperationType::FileDelete => "file_delete",
+        OperationType::SystemFileAccess => "system_file_access",
+        OperationType::CommandExecution => "command_execution",
+        OperationType::NetworkAccess => "network_access",
+        OperationType::ConfigChange => "config_change",
+        OperationType::DatabaseOperation => "database_operation",
+        OperationType::PrivilegedOperation => "privileged_operation",
+    }
+}
 
 pub struct PermissionEngine {
     cached_permissions: HashMap<String, PermissionSet>,
+    workspace_root: Option<PathBuf>,
 }
 
 impl PermissionEngine {
     pub fn new() -> Self {
         Self {
             cached_permissions: HashMap::new(),
+            workspace_root: None,
         }
     }
 
+    /// Accept absolute file paths that fall inside `root`, matched relative to it
+    ///
+    /// Without a root every absolute path is rejected by path-scoped file rules.
+    pub fn with_workspace_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.workspace_root = Some(root.into());
+        self
+    }
+
     pub async fn validate_operation(
         &mut self,
         request: &SandboxRequest,
@@ -38,6 +103,7 @@ impl PermissionEngine {
                         "File write operations not permitted".to_string(),
                     ));
                 }
+                self.check_path_scope(request, permissions, OperationType::FileWrite)?;
             }
             "delete_file" | "move_file" => {
                 if !permissions
@@ -48,6 +114,12 @@ impl PermissionEngine {
                         "File deletion operations not permitted".to_string(),
                     ));
                 }
+                self.check_path_scope(request, permissions, OperationType::FileDelete)?;
+            }
+            // Entity writes are bounded by the storage resource limits instead
+            crate::sandbox::STORE_ENTITY_OPERATION => {}
+            "system_file_access" => {
+                self.check_path_scope(request, permissions, OperationType::SystemFileAccess)?;
             }
             "execute_command" => {
                 // Check if command is allowed via command permissions
@@ -129,6 +201,96 @@ impl PermissionEngine {
         Ok(())
     }
 
+    /// Check the request's `path` parameter against the path globs for `operation`
+    ///
+    /// Without path rules, file writes and deletions are unscoped while system
+    /// file access is denied outright.
+    fn check_path_scope(
+        &self,
+        request: &SandboxRequest,
+        permissions: &PermissionSet,
+        operation: OperationType,
+    ) -> SandboxResult<()> {
+        let label = operation_label(&operation);
+        let rules: Vec<_> = permissions
+            .path_permissions
+            .iter()
+            .filter(|rule| rule.operation == operation)
+            .collect();
+        if rules.is_empty() {
+            if operation == OperationType::SystemFileAccess {
+                return Err(SandboxError::PermissionDenied(
+                    "System file access not permitted".to_string(),
+                ));
+            }
+            return Ok(());
+        }
+
+        let path = request
+            .parameters
+            .get("path")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| {
+                SandboxError::PermissionDenied(format!(
+                    "{} is path-scoped but the request has no 'path' parameter",
+                    label
+                ))
+            })?;
+        let path = self.scoped_path(path, &operation)?;
+        let path = path.as_str();
+
+        for pattern in rules.iter().flat_map(|rule| &rule.deny) {
+            if path_matches(pattern, path) {
+                return Err(SandboxError::PermissionDenied(format!(
+                    "{} of '{}' denied by deny rule '{}'",
+                    label, path, pattern
+                )));
+            }
+        }
+
+        let allow: Vec<&str> = rules
+            .iter()
+            .flat_map(|rule| rule.allow.iter().map(String::as_str))
+            .collect();
+        let unscoped = allow.is_empty() && operation != OperationType::SystemFileAccess;
+        if !unscoped && !allow.iter().any(|pattern| path_matches(pattern, path)) {
+            return Err(SandboxError::PermissionDenied(format!(
+                "{} of '{}' not permitted: path matches no allow rule ({})",
+                label,
+                path,
+                allow.join(", ")
+            )));
+        }
+        Ok(())
+    }
+
+    /// Normalize `path` lexically into the form the path globs are written in
+    ///
+    /// Workspace file operations match workspace-relative paths: `..` may not
+    /// climb out of the workspace, and absolute paths must lie under the
+    /// workspace root. System file access matches normalized absolute paths.
+    fn scoped_path(&self, path: &str, operation: &OperationType) -> SandboxResult<String> {
+        let label = operation_label(operation);
+        let escapes = || {
+            SandboxError::PermissionDenied(format!(
+                "{} of '{}' denied: path escapes the workspace root",
+                label, path
+            ))
+        };
+        let normalized = lexical_normalize(Path::new(path)).ok_or_else(escapes)?;
+        if *operation == OperationType::SystemFileAccess || !normalized.has_root() {
+            return Ok(normalized.to_string_lossy().into_owned());
+        }
+
+        let root = self
+            .workspace_root
+            .as_deref()
+            .and_then(lexical_normalize)
+            .ok_or_else(escapes)?;
+        let relative = normalized.strip_prefix(&root).map_err(|_| escapes())?;
+        Ok(relative.to_string_lossy().into_owned())
+    }
+
     fn is_command_allowed(&self, command: &str, permissions: &PermissionSet) -> bool {
         // Check if command matches any allowed command patterns
         permissions
@@ -211,7 +373,9 @@ impl Default for PermissionEngine {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::entities::{CommandPattern, CommandPermission, RiskLevel, WorkflowPermissions};
+    use crate::entities::{
+        CommandPattern, CommandPermission, PathPermission, RiskLevel, WorkflowPermissions,
+    };
 
     fn create_test_permissions() -> PermissionSet {
         let mut allowed_file_ops = Vec::new(); // Changed to Vec
@@ -239,6 +403,7 @@ mod tests {
                 can_execute_workflows: true,
                 restricted_workflow_types: vec!["dangerous_workflow".to_string()], // Changed to Vec
             },
+            path_permissions: Vec::new(),
             forbidden_paths: vec![],          // Added forbidden_paths
             quality_gate_permissions: vec![], // Added quality_gate_permissions
         }
@@ -503,6 +668,7 @@ mod tests {
                 can_execute_workflows: false,
                 restricted_workflow_types: vec![],
             },
+            path_permissions: Vec::new(),
         };
         for op in &[
             "read_file",
@@ -849,4 +1015,152 @@ mod tests {
             assert!(e.validate_operation(&req, &p).await.is_err(), "{}", url);
         }
     }
+
+    #[tokio::test]
+    async fn test_path_scoped_file_permissions() {
+        let mut e = PermissionEngine::new();
+        let mut p = PermissionSet::default();
+        p.allowed_file_operations = vec![FileOperation::Write, FileOperation::Delete];
+        p.path_permissions = vec![
+            PathPermission {
+                operation: OperationType::FileWrite,
+                allow: vec!["src/**".into(), "tests/**".into()],
+                deny: vec![".github/**".into(), "src/*.lock".into()],
+            },
+            PathPermission {
+                operation: OperationType::SystemFileAccess,
+                allow: vec!["/etc/hosts".into()],
+                deny: vec![],
+            },
+        ];
+        let req = |op: &str, path: Option<&str>| SandboxRequest {
+            operation: op.into(),
+            parameters: path.map_or(serde_json::json!({}), |p| serde_json::json!({ "path": p })),
+            agent_id: "t".into(),
+            resource_type: "f".into(),
+            session_id: None,
+            timestamp: chrono::Utc::now(),
+        };
+
+        for path in ["src/sandbox/mod.rs", "./tests/cli.rs"] {
+            let r = req("write_file", Some(path));
+            assert!(e.validate_operation(&r, &p).await.is_ok(), "{}", path);
+        }
+        let err = e
+            .validate_operation(&req("write_file", Some(".github/workflows/ci.yml")), &p)
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("deny rule '.github/**'"),
+            "{}",
+            err
+        );
+        let err = e
+            .validate_operation(&req("modify_file", Some("README.md")), &p)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("no allow rule (src/**, tests/**)"));
+        assert!(e
+            .validate_operation(&req("create_file", None), &p)
+            .await
+            .is_err());
+
+        // Deletions have no rules of their own and stay unscoped
+        let r = req("delete_file", Some(".github/CODEOWNERS"));
+        assert!(e.validate_operation(&r, &p).await.is_ok());
+
+        let r = req("system_file_access", Some("/etc/hosts"));
+        assert!(e.validate_operation(&r, &p).await.is_ok());
+        let r = req("system_file_access", Some("/etc/shadow"));
+        assert!(e.validate_operation(&r, &p).await.is_err());
+        let r = req("system_file_access", Some("/etc/hosts"));
+        assert!(e
+            .validate_operation(&r, &PermissionSet::default())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_path_scope_normalizes_traversal_and_absolute_paths() {
+        let mut e = PermissionEngine::new().with_workspace_root("/work/repo");
+        let p = PermissionSet {
+            allowed_file_operations: vec![FileOperation::Write],
+            path_permissions: vec![
+                PathPermission {
+                    operation: OperationType::FileWrite,
+                    allow: vec!["src/**".into()],
+                    deny: vec![".github/**".into()],
+                },
+                PathPermission {
+                    operation: OperationType::SystemFileAccess,
+                    allow: vec!["/etc/hosts".into()],
+                    deny: vec![],
+                },
+            ],
+            ..Default::default()
+        };
+        let req = |op: &str, path: &str| SandboxRequest {
+            operation: op.into(),
+            parameters: serde_json::json!({ "path": path }),
+            agent_id: "t".into(),
+            resource_type: "f".into(),
+            session_id: None,
+            timestamp: chrono::Utc::now(),
+        };
+
+        for path in ["src/../.github/x", "src/./../.github/workflows/ci.yml"] {
+            let err = e
+                .validate_operation(&req("write_file", path), &p)
+                .await
+                .unwrap_err();
+            assert!(
+                err.to_string().contains("deny rule '.github/**'"),
+                "{}",
+                err
+            );
+        }
+        for path in ["../outside/src/x.rs", "src/../../repo/src/x.rs"] {
+            let err = e
+                .validate_operation(&req("write_file", path), &p)
+                .await
+                .unwrap_err();
+            assert!(
+                err.to_string().contains("escapes the workspace root"),
+                "{}",
+                err
+            );
+        }
+
+        // Absolute paths are matched relative to the workspace root
+        let r = req("write_file", "/work/repo/src/lib.rs");
+        assert!(e.validate_operation(&r, &p).await.is_ok());
+        let err = e
+            .validate_operation(&req("write_file", "/work/repo/.github/x"), &p)
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("deny rule '.github/**'"),
+            "{}",
+            err
+        );
+        for path in ["/tmp/src/x.rs", "/work/repo/../other/src/x.rs"] {
+            let err = e
+                .validate_operation(&req("write_file", path), &p)
+                .await
+                .unwrap_err();
+            assert!(
+                err.to_string().contains("escapes the workspace root"),
+                "{}",
+                err
+            );
+        }
+        let mut rootless = PermissionEngine::new();
+        let r = req("write_file", "/work/repo/src/lib.rs");
+        assert!(rootless.validate_operation(&r, &p).await.is_err());
+
+        let r = req("system_file_access", "/etc/../etc/hosts");
+        assert!(e.validate_operation(&r, &p).await.is_ok());
+        let r = req("system_file_access", "/etc/hosts/../shadow");
+        assert!(e.validate_operation(&r, &p).await.is_err());
+    }
 }
@@ -283,6 +283,9 @@ mod tests {
             max_concurrent_operations: 10,
             max_file_size_mb: 50,
             max_network_requests_per_minute: 30,
+            max_entities_created: None,
+            max_total_bytes_written: None,
+            max_single_entity_bytes: None,
         }
     }
 
@@ -1,8 +1,12 @@
-use crate::entities::ResourceLimits;
+use crate::entities::{ResourceLimits, SandboxUsage};
 use crate::sandbox::{SandboxError, SandboxRequest, SandboxResult};
 use std::collections::HashMap;
 use std::time::{Duration, Instant};
 
+/// Operation name for storing an entity; parameters carry `entity_bytes`
+/// and whether the entity is `new`
+pub const STORE_ENTITY_OPERATION: &str = "store_entity";
+
 pub struct ResourceMonitor {
     agent_usage: HashMap<String, AgentResourceUsage>,
 }
@@ -44,6 +48,7 @@ impl ResourceMonitor {
         agent_id: &str,
         request: &SandboxRequest,
         limits: &ResourceLimits,
+        usage: &SandboxUsage,
     ) -> SandboxResult<()> {
         // First update usage
         self.update_current_usage(agent_id).await?;
@@ -115,6 +120,8 @@ impl ResourceMonitor {
             }
         }
 
+        Self::check_storage_limits(request, limits, usage)?;
+
         // Check file size limits
         if let Some(file_size) = request.parameters.get("file_size_mb") {
             if let Some(size) = file_size.as_f64() {
@@ -130,6 +137,57 @@ impl ResourceMonitor {
         Ok(())
     }
 
+    /// Check an entity store against the storage limits given the agent's
+    /// persisted usage
+    ///
+    /// Exceeding a storage limit asks for escalation rather than denying.
+    pub fn check_storage_limits(
+        request: &SandboxRequest,
+        limits: &ResourceLimits,
+        usage: &SandboxUsage,
+    ) -> SandboxResult<()> {
+        if request.operation != STORE_ENTITY_OPERATION {
+            return Ok(());
+        }
+        let bytes = request
+            .parameters
+            .get("entity_bytes")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0);
+        let is_new = request
+            .parameters
+            .get("new")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+
+        if let Some(max) = limits.max_single_entity_bytes {
+            if bytes > max {
+                return Err(SandboxError::EscalationRequired(format!(
+                    "Entity size {} bytes exceeds limit {} bytes",
+                    bytes, max
+                )));
+            }
+        }
+        if let Some(max) = limits.max_entities_created {
+            if is_new && usage.entities_created >= max {
+                return Err(SandboxError::EscalationRequired(format!(
+                    "Entities created {} reached limit {}",
+                    usage.entities_created, max
+                )));
+            }
+        }
+        if let Some(max) = limits.max_total_bytes_written {
+            let total = usage.bytes_written + bytes;
+            if total > max {
+                return Err(SandboxError::EscalationRequired(format!(
+                    "Writing {} bytes would bring total written to {} bytes, over limit {} bytes",
+                    bytes, total, max
+                )));
+            }
+        }
+        Ok(())
+    }
+
     async fn update_current_usage(&mut self, agent_id: &str) -> SandboxResult<()> {
         // Get current usage metrics first (these only borrow self immutably)
         let memory_usage = self.get_memory_usage(agent_id).await?;
@@ -291,6 +349,9 @@ mod tests {
             max_concurrent_operations: 2,
             max_file_size_mb: 10,
             max_network_requests_per_minute: 5,
+            max_entities_created: None,
+            max_total_bytes_written: None,
+            max_single_entity_bytes: None,
         }
     }
 
@@ -310,7 +371,9 @@ mod tests {
         // Wait, check_limits checks `active_ops >= limits.max_concurrent_operations`.
         // If we have 2 running, we can't start a 3rd.
 
-        let result = monitor.check_limits(agent_id, &request, &limits).await;
+        let result = monitor
+            .check_limits(agent_id, &request, &limits, &SandboxUsage::default())
+            .await;
         assert!(result.is_err());
         match result {
             Err(SandboxError::ResourceLimitExceeded(msg)) => {
@@ -324,7 +387,7 @@ mod tests {
 
         // Should succeed now
         assert!(monitor
-            .check_limits(agent_id, &request, &limits)
+            .check_limits(agent_id, &request, &limits, &SandboxUsage::default())
             .await
             .is_ok());
     }
@@ -339,13 +402,15 @@ mod tests {
         // Make 5 successful requests
         for _ in 0..5 {
             assert!(monitor
-                .check_limits(agent_id, &request, &limits)
+                .check_limits(agent_id, &request, &limits, &SandboxUsage::default())
                 .await
                 .is_ok());
         }
 
         // 6th request should fail
-        let result = monitor.check_limits(agent_id, &request, &limits).await;
+        let result = monitor
+            .check_limits(agent_id, &request, &limits, &SandboxUsage::default())
+            .await;
         assert!(result.is_err());
         match result {
             Err(SandboxError::ResourceLimitExceeded(msg)) => {
@@ -373,7 +438,9 @@ mod tests {
             timestamp: Utc::now(),
         };
 
-        let result = monitor.check_limits(agent_id, &request, &limits).await;
+        let result = monitor
+            .check_limits(agent_id, &request, &limits, &SandboxUsage::default())
+            .await;
         assert!(result.is_err());
         match result {
             Err(SandboxError::ResourceLimitExceeded(msg)) => {
@@ -483,7 +550,8 @@ mod tests {
             .check_limits(
                 "t",
                 &create_test_request("read_file"),
-                &create_test_limits()
+                &create_test_limits(),
+                &SandboxUsage::default()
             )
             .await
             .is_ok());
@@ -502,7 +570,10 @@ mod tests {
             session_id: None,
             timestamp: Utc::now(),
         };
-        assert!(m.check_limits("t", &r, &create_test_limits()).await.is_ok());
+        assert!(m
+            .check_limits("t", &r, &create_test_limits(), &SandboxUsage::default())
+            .await
+            .is_ok());
     }
 
     #[tokio::test]
@@ -518,7 +589,10 @@ mod tests {
             session_id: None,
             timestamp: Utc::now(),
         };
-        assert!(m.check_limits("t", &r, &create_test_limits()).await.is_ok());
+        assert!(m
+            .check_limits("t", &r, &create_test_limits(), &SandboxUsage::default())
+            .await
+            .is_ok());
     }
 
     #[tokio::test]
@@ -0,0 +1,163 @@
+//! Dry-run replay of past sandbox decisions
+//!
+//! Replays recorded requests against a hypothetical sandbox configuration via
+//! [`SandboxEngine::simulate_request`] and reports which decisions would
+//! change, so a sandbox can be tightened without surprises.
+
+use crate::entities::{AgentSandbox, SandboxLevel};
+use crate::sandbox::{SandboxAuditEntry, SandboxDecision, SandboxEngine, SandboxResult};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+/// Number of requests whose decision moved from `from` to `to`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DecisionChange {
+    pub from: SandboxDecision,
+    pub to: SandboxDecision,
+    pub count: usize,
+}
+
+/// Operation whose decision changed, with how many requests were affected
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AffectedOperation {
+    pub operation: String,
+    pub changed: usize,
+}
+
+/// Result of replaying requests against a hypothetical sandbox
+#[derive(Debug, Clone, Serialize)]
+pub struct SimulationReport {
+    pub agent_id: String,
+    pub sandbox_level: SandboxLevel,
+    pub total_requests: usize,
+    pub historical: BTreeMap<SandboxDecision, usize>,
+    pub simulated: BTreeMap<SandboxDecision, usize>,
+    /// Requests whose decision would differ from the recorded one
+    pub changed: usize,
+    pub changes: Vec<DecisionChange>,
+    /// Operations with the most changed decisions, most affected first
+    pub top_operations: Vec<AffectedOperation>,
+}
+
+/// Replay `entries` against `sandbox` and compare with their recorded decisions
+///
+/// Only the `top` most affected operations are kept in the report.
+pub async fn simulate_requests(
+    engine: &mut SandboxEngine,
+    entries: &[SandboxAuditEntry],
+    sandbox: &AgentSandbox,
+    top: usize,
+) -> SandboxResult<SimulationReport> {
+    let mut historical = BTreeMap::new();
+    let mut simulated = BTreeMap::new();
+    let mut changes: BTreeMap<(SandboxDecision, SandboxDecision), usize> = BTreeMap::new();
+    let mut operations: HashMap<&str, usize> = HashMap::new();
+
+    for entry in entries {
+        let decision = engine.simulate_request(&entry.request, sandbox).await?;
+        *historical.entry(entry.decision).or_insert(0) += 1;
+        *simulated.entry(decision).or_insert(0) += 1;
+        if decision != entry.decision {
+            *changes.entry((entry.decision, decision)).or_insert(0) += 1;
+            *operations
+                .entry(entry.request.operation.as_str())
+                .or_insert(0) += 1;
+        }
+    }
+
+    let mut top_operations: Vec<AffectedOperation> = operations
+        .into_iter()
+        .map(|(operation, changed)| AffectedOperation {
+            operation: operation.to_string(),
+            changed,
+        })
+        .collect();
+    top_operations.sort_by(|a, b| {
+        b.changed
+            .cmp(&a.changed)
+            .then_with(|| a.operation.cmp(&b.operation))
+    });
+    top_operations.truncate(top);
+
+    Ok(SimulationReport {
+        agent_id: sandbox.agent_id.clone(),
+        sandbox_level: sandbox.sandbox_level.clone(),
+        total_requests: entries.len(),
+        historical,
+        simulated,
+        changed: changes.values().sum(),
+        changes: changes
+            .into_iter()
+            .map(|((from, to), count)| DecisionChange { from, to, count })
+            .collect(),
+        top_operations,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sandbox::SandboxRequest;
+    use crate::storage::{MemoryStorage, Storage};
+    use chrono::Utc;
+    use serde_json::json;
+
+    fn entry(operation: &str, decision: SandboxDecision) -> SandboxAuditEntry {
+        SandboxAuditEntry {
+            request: SandboxRequest {
+                agent_id: "agent-1".to_string(),
+                operation: operation.to_string(),
+                resource_type: "file".to_string(),
+                parameters: json!({}),
+                timestamp: Utc::now(),
+                session_id: None,
+            },
+            decision,
+            reason: None,
+            recorded_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_simulate_reports_changed_decisions_without_side_effects() {
+        let storage = MemoryStorage::new("agent-1");
+        let mut engine = SandboxEngine::new(Box::new(storage.clone()));
+        let entries = vec![
+            entry("list_files", SandboxDecision::Allow),
+            entry("delete_file", SandboxDecision::Allow),
+            entry("delete_file", SandboxDecision::Allow),
+            entry("unknown_op", SandboxDecision::Deny),
+        ];
+        let sandbox = AgentSandbox::new(
+            "agent-1".to_string(),
+            SandboxLevel::Restricted,
+            "simulation".to_string(),
+            "agent-1".to_string(),
+        );
+
+        let report = simulate_requests(&mut engine, &entries, &sandbox, 5)
+            .await
+            .unwrap();
+        assert_eq!(report.total_requests, 4);
+        assert_eq!(report.historical[&SandboxDecision::Allow], 3);
+        assert_eq!(report.changed, 2);
+        assert_eq!(
+            report.changes,
+            vec![DecisionChange {
+                from: SandboxDecision::Allow,
+                to: SandboxDecision::Deny,
+                count: 2,
+            }]
+        );
+        assert_eq!(
+            report.top_operations,
+            vec![AffectedOperation {
+                operation: "delete_file".to_string(),
+                changed: 2,
+            }]
+        );
+
+        assert!(storage.list_ids("agent_sandbox").unwrap().is_empty());
+        assert!(storage.list_ids("escalation_request").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,588 @@
+//! Sandbox storage limits enforced on entity writes
+//!
+//! [`SandboxStorage`] wraps another backend and checks every entity store
+//! against the writing agent's sandbox resource limits (`max_entities_created`,
+//! `max_total_bytes_written` and `max_single_entity_bytes`) with
+//! [`ResourceMonitor::check_storage_limits`]. Usage counters are persisted in
+//! the agent's sandbox, so limits hold across separate CLI invocations.
+//!
+//! A store over a limit files a resource-limit escalation instead of failing
+//! with a bare denial. Once a reviewer approves it, the agent's stores are no
+//! longer limited for as long as the approval lasts.
+
+use crate::entities::{
+    AgentSandbox, Entity, EntityRelationship, EscalationOperationType, EscalationPriority,
+    EscalationRequest, GenericEntity, JsonPatchOp, OperationContext, RelationshipFilter,
+};
+use crate::error::EngramError;
+use crate::sandbox::{ResourceMonitor, SandboxError, SandboxRequest, STORE_ENTITY_OPERATION};
+use crate::storage::{
+    apply_entity_patch, EntityPath, GitCommit, QueryFilter, QueryResult, RelationshipIndex,
+    RelationshipStats, RelationshipStorage, Storage, StorageStats, TraversalAlgorithm,
+};
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Entity types the sandbox itself writes; never limited
+const UNLIMITED_TYPES: [&str; 2] = ["agent_sandbox", "escalation_request"];
+
+/// Storage wrapper enforcing sandbox storage limits on entity writes
+#[derive(Clone)]
+pub struct SandboxStorage<S> {
+    inner: S,
+    /// Sandbox of each agent seen so far; `None` when it has none
+    sandboxes: Arc<Mutex<HashMap<String, Option<AgentSandbox>>>>,
+}
+
+impl<S: Storage> SandboxStorage<S> {
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            sandboxes: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// The wrapped backend
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    fn sandbox_for(&self, agent: &str) -> Result<Option<AgentSandbox>, EngramError> {
+        let mut sandboxes = self.sandboxes.lock().expect("sandbox cache poisoned");
+        if let Some(sandbox) = sandboxes.get(agent) {
+            return Ok(sandbox.clone());
+        }
+
+        let mut found = None;
+        for id in self.inner.list_ids("agent_sandbox")? {
+            if let Some(entity) = self.inner.get(&id, "agent_sandbox")? {
+                if let Ok(sandbox) = AgentSandbox::from_generic(entity) {
+                    if sandbox.agent_id == agent {
+                        found = Some(sandbox);
+                        break;
+                    }
+                }
+            }
+        }
+        sandboxes.insert(agent.to_string(), found.clone());
+        Ok(found)
+    }
+
+    /// Resource-limit escalations for storing entities filed by `agent`
+    fn store_escalations(&self, agent: &str) -> Result<Vec<EscalationRequest>, EngramError> {
+        Ok(self
+            .inner
+            .get_all("escalation_request")?
+            .into_iter()
+            .filter_map(|entity| EscalationRequest::from_generic(entity).ok())
+            .filter(|e| {
+                e.agent_id == agent && e.operation_context.operation == STORE_ENTITY_OPERATION
+            })
+            .collect())
+    }
+
+    /// File an escalation for `request`, reusing one that is still pending
+    fn escalate(
+        &mut self,
+        request: &SandboxRequest,
+        escalations: &[EscalationRequest],
+        reason: &str,
+    ) -> Result<String, EngramError> {
+        if let Some(pending) = escalations.iter().find(|e| e.is_actionable()) {
+            return Ok(pending.id.clone());
+        }
+
+        let context = OperationContext {
+            operation: STORE_ENTITY_OPERATION.to_string(),
+            parameters: request
+                .parameters
+                .as_object()
+                .map(|params| params.clone().into_iter().collect())
+                .unwrap_or_default(),
+            resource: Some(request.resource_type.clone()),
+            block_reason: reason.to_string(),
+            alternatives: vec![
+                "Store smaller entities".to_string(),
+                "Raise the limit with 'engram sandbox update --stdin'".to_string(),
+            ],
+            risk_assessment: None,
+        };
+        let escalation = EscalationRequest::new(
+            request.agent_id.clone(),
+            EscalationOperationType::ResourceLimitIncrease,
+            context,
+            format!(
+                "Agent {} exceeded a sandbox storage limit: {}",
+                request.agent_id, reason
+            ),
+            EscalationPriority::Normal,
+            "default".to_string(),
+        );
+        self.inner.store(&escalation.to_generic())?;
+        crate::notify::dispatch(&crate::notify::NotificationEvent::escalation_created(
+            &escalation,
+        ));
+        Ok(escalation.id)
+    }
+
+    /// Check `entities` against their agents' storage limits
+    ///
+    /// Returns the limited sandboxes with usage advanced as if every entity
+    /// had been stored.
+    fn check_stores(
+        &mut self,
+        entities: &[GenericEntity],
+    ) -> Result<Vec<AgentSandbox>, EngramError> {
+        let mut limited: Vec<AgentSandbox> = Vec::new();
+        for entity in entities {
+            if UNLIMITED_TYPES.contains(&entity.entity_type.as_str()) {
+                continue;
+            }
+            let index = match limited.iter().position(|s| s.agent_id == entity.agent) {
+                Some(index) => index,
+                None => match self.sandbox_for(&entity.agent)? {
+                    Some(sandbox) if sandbox.resource_limits.limits_storage() => {
+                        limited.push(sandbox);
+                        limited.len() - 1
+                    }
+                    _ => continue,
+                },
+            };
+
+            let bytes = serde_json::to_vec(entity)?.len() as u64;
+            let is_new = !self.inner.exists(&entity.id, &entity.entity_type)?;
+            let request = SandboxRequest {
+                agent_id: entity.agent.clone(),
+                operation: STORE_ENTITY_OPERATION.to_string(),
+                resource_type: entity.entity_type.clone(),
+                parameters: serde_json::json!({
+                    "entity_id": entity.id,
+                    "entity_bytes": bytes,
+                    "new": is_new,
+                }),
+                timestamp: Utc::now(),
+                session_id: None,
+            };
+
+            let sandbox = &limited[index];
+            match ResourceMonitor::check_storage_limits(
+                &request,
+                &sandbox.resource_limits,
+                &sandbox.usage,
+            ) {
+                Ok(()) => {}
+                Err(SandboxError::EscalationRequired(reason)) => {
+                    let escalations = self.store_escalations(&entity.agent)?;
+                    let now = Utc::now();
+                    if !escalations.iter().any(|e| e.approval_active(now)) {
+                        let escalation_id = self.escalate(&request, &escalations, &reason)?;
+                        return Err(EngramError::SandboxLimitExceeded {
+                            agent: entity.agent.clone(),
+                            reason,
+                            escalation_id,
+                        });
+                    }
+                }
+                Err(e) => return Err(EngramError::InvalidOperation(e.to_string())),
+            }
+
+            let usage = &mut limited[index].usage;
+            usage.bytes_written += bytes;
+            if is_new {
+                usage.entities_created += 1;
+            }
+        }
+        Ok(limited)
+    }
+
+    /// Persist the usage counters of `sandboxes`
+    fn record_usage(&mut self, sandboxes: Vec<AgentSandbox>) -> Result<(), EngramError> {
+        for sandbox in sandboxes {
+            self.inner.store(&sandbox.to_generic())?;
+            self.sandboxes
+                .lock()
+                .expect("sandbox cache poisoned")
+                .insert(sandbox.agent_id.clone(), Some(sandbox));
+        }
+        Ok(())
+    }
+
+    /// Forget cached sandboxes when one is written through this wrapper
+    fn invalidate(&self, entity_type: &str) {
+        if entity_type == "agent_sandbox" {
+            self.sandboxes
+                .lock()
+                .expect("sandbox cache poisoned")
+                .clear();
+        }
+    }
+}
+
+impl<S: Storage + 'static> Storage for SandboxStorage<S> {
+    fn store(&mut self, entity: &GenericEntity) -> Result<(), EngramError> {
+        let sandboxes = self.check_stores(std::slice::from_ref(entity))?;
+        self.inner.store(entity)?;
+        self.invalidate(&entity.entity_type);
+        self.record_usage(sandboxes)
+    }
+
+    /// Limits are checked against a preview of the patched entity; the patch
+    /// itself is applied atomically by the wrapped backend
+    fn patch(
+        &mut self,
+        id: &str,
+        entity_type: &str,
+        patch: Vec<JsonPatchOp>,
+    ) -> Result<(), EngramError> {
+        let mut preview = self
+            .inner
+            .get(id, entity_type)?
+            .ok_or_else(|| EngramError::not_found(entity_type, id))?;
+        apply_entity_patch(&mut preview, &patch)?;
+        let sandboxes = self.check_stores(std::slice::from_ref(&preview))?;
+        self.inner.patch(id, entity_type, patch)?;
+        self.invalidate(entity_type);
+        self.record_usage(sandboxes)
+    }
+
+    fn get(&self, id: &str, entity_type: &str) -> Result<Option<GenericEntity>, EngramError> {
+        self.inner.get(id, entity_type)
+    }
+
+    fn exists(&self, id: &str, entity_type: &str) -> Result<bool, EngramError> {
+        self.inner.exists(id, entity_type)
+    }
+
+    fn validate_for_store(&self, entity: &GenericEntity) -> Result<(), EngramError> {
+        self.inner.validate_for_store(entity)
+    }
+
+    fn get_many(
+        &self,
+        ids: &[String],
+        entity_type: &str,
+    ) -> Result<Vec<Option<GenericEntity>>, EngramError> {
+        self.inner.get_many(ids, entity_type)
+    }
+
+    fn query(&self, filter: &QueryFilter) -> Result<QueryResult, EngramError> {
+        self.inner.query(filter)
+    }
+
+    fn query_by_agent(
+        &self,
+        agent: &str,
+        entity_type: Option<&str>,
+    ) -> Result<Vec<GenericEntity>, EngramError> {
+        self.inner.query_by_agent(agent, entity_type)
+    }
+
+    fn query_by_time_range(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<GenericEntity>, EngramError> {
+        self.inner.query_by_time_range(start, end)
+    }
+
+    fn query_by_type(
+        &self,
+        entity_type: &str,
+        filters: Option<&HashMap<String, Value>>,
+        limit: Option<usize>,
+        offset: Option<usize>,
+    ) -> Result<QueryResult, EngramError> {
+        self.inner
+            .query_by_type(entity_type, filters, limit, offset)
+    }
+
+    fn text_search(
+        &self,
+        query: &str,
+        entity_types: Option<&[String]>,
+        limit: Option<usize>,
+    ) -> Result<Vec<GenericEntity>, EngramError> {
+        self.inner.text_search(query, entity_types, limit)
+    }
+
+    fn count(&self, filter: &QueryFilter) -> Result<usize, EngramError> {
+        self.inner.count(filter)
+    }
+
+    fn delete(&mut self, id: &str, entity_type: &str) -> Result<(), EngramError> {
+        self.inner.delete(id, entity_type)?;
+        self.invalidate(entity_type);
+        Ok(())
+    }
+
+    fn list_ids(&self, entity_type: &str) -> Result<Vec<String>, EngramError> {
+        self.inner.list_ids(entity_type)
+    }
+
+    fn get_all(&self, entity_type: &str) -> Result<Vec<GenericEntity>, EngramError> {
+        self.inner.get_all(entity_type)
+    }
+
+    fn sync(&mut self) -> Result<(), EngramError> {
+        self.inner.sync()
+    }
+
+    fn current_branch(&self) -> Result<String, EngramError> {
+        self.inner.current_branch()
+    }
+
+    fn create_branch(&mut self, branch_name: &str) -> Result<(), EngramError> {
+        self.inner.create_branch(branch_name)
+    }
+
+    fn switch_branch(&mut self, branch_name: &str) -> Result<(), EngramError> {
+        self.inner.switch_branch(branch_name)
+    }
+
+    fn merge_branches(&mut self, source: &str, target: &str) -> Result<(), EngramError> {
+        self.inner.merge_branches(source, target)
+    }
+
+    fn history(&self, limit: Option<usize>) -> Result<Vec<GitCommit>, EngramError> {
+        self.inner.history(limit)
+    }
+
+    fn bulk_store(&mut self, entities: &[GenericEntity]) -> Result<(), EngramError> {
+        let sandboxes = self.check_stores(entities)?;
+        self.inner.bulk_store(entities)?;
+        for entity in entities {
+            self.invalidate(&entity.entity_type);
+        }
+        self.record_usage(sandboxes)
+    }
+
+    fn get_stats(&self) -> Result<StorageStats, EngramError> {
+        self.inner.get_stats()
+    }
+
+    fn is_read_only(&self) -> bool {
+        self.inner.is_read_only()
+    }
+
+    /// The guard is transparent, so downcasts see the wrapped backend
+    fn as_any(&self) -> &dyn std::any::Any {
+        self.inner.as_any()
+    }
+}
+
+impl<S: RelationshipStorage + 'static> RelationshipStorage for SandboxStorage<S> {
+    fn store_relationship(&mut self, relationship: &EntityRelationship) -> Result<(), EngramError> {
+        let sandboxes = self.check_stores(std::slice::from_ref(&relationship.to_generic()))?;
+        self.inner.store_relationship(relationship)?;
+        self.record_usage(sandboxes)
+    }
+
+    fn get_relationship(&self, id: &str) -> Result<Option<EntityRelationship>, EngramError> {
+        self.inner.get_relationship(id)
+    }
+
+    fn query_relationships(
+        &self,
+        filter: &RelationshipFilter,
+    ) -> Result<Vec<EntityRelationship>, EngramError> {
+        self.inner.query_relationships(filter)
+    }
+
+    fn get_entity_relationships(
+        &self,
+        entity_id: &str,
+    ) -> Result<Vec<EntityRelationship>, EngramError> {
+        self.inner.get_entity_relationships(entity_id)
+    }
+
+    fn get_outbound_relationships(
+        &self,
+        entity_id: &str,
+    ) -> Result<Vec<EntityRelationship>, EngramError> {
+        self.inner.get_outbound_relationships(entity_id)
+    }
+
+    fn get_inbound_relationships(
+        &self,
+        entity_id: &str,
+    ) -> Result<Vec<EntityRelationship>, EngramError> {
+        self.inner.get_inbound_relationships(entity_id)
+    }
+
+    fn find_paths(
+        &self,
+        source_id: &str,
+        target_id: &str,
+        algorithm: TraversalAlgorithm,
+        max_depth: Option<usize>,
+    ) -> Result<Vec<EntityPath>, EngramError> {
+        self.inner
+            .find_paths(source_id, target_id, algorithm, max_depth)
+    }
+
+    fn get_connected_entities(
+        &self,
+        entity_id: &str,
+        algorithm: TraversalAlgorithm,
+        max_depth: Option<usize>,
+    ) -> Result<Vec<String>, EngramError> {
+        self.inner
+            .get_connected_entities(entity_id, algorithm, max_depth)
+    }
+
+    fn delete_relationship(&mut self, id: &str) -> Result<(), EngramError> {
+        self.inner.delete_relationship(id)
+    }
+
+    fn get_relationship_index(&self) -> Result<&RelationshipIndex, EngramError> {
+        self.inner.get_relationship_index()
+    }
+
+    fn rebuild_relationship_index(&mut self) -> Result<(), EngramError> {
+        self.inner.rebuild_relationship_index()
+    }
+
+    fn get_relationship_stats(&self) -> Result<RelationshipStats, EngramError> {
+        self.inner.get_relationship_stats()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{EscalationStatus, ReviewDecision, SandboxLevel};
+    use crate::storage::MemoryStorage;
+
+    fn entity(agent: &str, payload: &str) -> GenericEntity {
+        GenericEntity {
+            id: uuid::Uuid::new_v4().to_string(),
+            entity_type: "context".to_string(),
+            agent: agent.to_string(),
+            timestamp: DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+            data: serde_json::json!({ "content": payload }),
+        }
+    }
+
+    fn size(entity: &GenericEntity) -> u64 {
+        serde_json::to_vec(entity).unwrap().len() as u64
+    }
+
+    /// Memory storage holding a sandbox for `agent` adjusted by `limit`
+    fn limited_storage(agent: &str, limit: impl FnOnce(&mut AgentSandbox)) -> MemoryStorage {
+        let mut memory = MemoryStorage::new("default");
+        let mut sandbox = AgentSandbox::new(
+            agent.to_string(),
+            SandboxLevel::Standard,
+            "admin".to_string(),
+            "default".to_string(),
+        );
+        limit(&mut sandbox);
+        memory.store(&sandbox.to_generic()).unwrap();
+        memory
+    }
+
+    fn escalation(storage: &MemoryStorage, id: &str) -> EscalationRequest {
+        EscalationRequest::from_generic(storage.get(id, "escalation_request").unwrap().unwrap())
+            .unwrap()
+    }
+
+    fn limit_error(err: EngramError) -> (String, String) {
+        match err {
+            EngramError::SandboxLimitExceeded {
+                reason,
+                escalation_id,
+                ..
+            } => (reason, escalation_id),
+            other => panic!("expected a sandbox limit error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_max_entities_created_persists_across_wrappers() {
+        let memory = limited_storage("bot", |s| s.resource_limits.max_entities_created = Some(2));
+        let mut storage = SandboxStorage::new(memory.clone());
+        let mut first = entity("bot", "a");
+        storage.store(&first).unwrap();
+        storage.store(&entity("bot", "b")).unwrap();
+
+        // Updates and agents without storage limits are not counted
+        first.data = serde_json::json!({ "content": "a2" });
+        storage.store(&first).unwrap();
+        storage.store(&entity("human", "c")).unwrap();
+
+        // A fresh wrapper reads the persisted counters
+        let mut storage = SandboxStorage::new(memory.clone());
+        let (reason, escalation_id) = limit_error(storage.store(&entity("bot", "d")).unwrap_err());
+        assert_eq!(reason, "Entities created 2 reached limit 2");
+        let filed = escalation(&memory, &escalation_id);
+        assert_eq!(
+            filed.operation_type,
+            EscalationOperationType::ResourceLimitIncrease
+        );
+        assert_eq!(filed.status, EscalationStatus::Pending);
+
+        // Retrying reuses the pending escalation
+        let (_, retry_id) = limit_error(storage.store(&entity("bot", "e")).unwrap_err());
+        assert_eq!(retry_id, escalation_id);
+        assert_eq!(memory.list_ids("escalation_request").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_max_single_entity_bytes() {
+        let small = entity("bot", "small");
+        let max = size(&small) + 10;
+        let memory = limited_storage("bot", |s| {
+            s.resource_limits.max_single_entity_bytes = Some(max)
+        });
+        let mut storage = SandboxStorage::new(memory.clone());
+        storage.store(&small).unwrap();
+
+        let large = entity("bot", &"x".repeat(100));
+        let (reason, _) = limit_error(storage.store(&large).unwrap_err());
+        assert_eq!(
+            reason,
+            format!(
+                "Entity size {} bytes exceeds limit {} bytes",
+                size(&large),
+                max
+            )
+        );
+        assert_eq!(memory.list_ids("context").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_max_total_bytes_written_until_escalation_approved() {
+        let entities: Vec<_> = (0..3)
+            .map(|i| entity("bot", &format!("note {}", i)))
+            .collect();
+        let max = size(&entities[0]) * 2 + 10;
+        let memory = limited_storage("bot", |s| {
+            s.resource_limits.max_total_bytes_written = Some(max)
+        });
+        let mut storage = SandboxStorage::new(memory.clone());
+        storage.bulk_store(&entities[..2]).unwrap();
+        let (_, escalation_id) = limit_error(storage.store(&entities[2]).unwrap_err());
+
+        let sandbox = storage.sandbox_for("bot").unwrap().unwrap();
+        assert_eq!(sandbox.usage.entities_created, 2);
+        assert_eq!(sandbox.usage.bytes_written, size(&entities[0]) * 2);
+
+        let mut approved = escalation(&memory, &escalation_id);
+        approved.record_decision(ReviewDecision {
+            status: EscalationStatus::Approved,
+            reason: "Bulk import".to_string(),
+            conditions: Vec::new(),
+            approval_duration: Some(3600),
+            create_policy: false,
+            notes: None,
+        });
+        storage.store(&approved.to_generic()).unwrap();
+        storage.store(&entities[2]).unwrap();
+    }
+}
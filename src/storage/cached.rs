@@ -0,0 +1,434 @@
+//! Per-invocation entity cache
+//!
+//! [`CachedStorage`] wraps another backend and keeps the most recently read
+//! entities in an LRU keyed by (entity type, ID), so the code paths of one
+//! command that each load the same entity read it from the backend once.
+//! Writes through the wrapper invalidate the entries they touch, and branch
+//! switches, merges and syncs clear the cache. Hit and miss counts are
+//! logged at debug level (`ENGRAM_LOG=engram::storage=debug`) when the
+//! wrapper is dropped.
+
+use super::{
+    EntityPath, GitCommit, QueryFilter, QueryResult, RelationshipIndex, RelationshipStats,
+    RelationshipStorage, Storage, StorageStats, TraversalAlgorithm,
+};
+use crate::entities::{EntityRelationship, GenericEntity, JsonPatchOp, RelationshipFilter};
+use crate::error::EngramError;
+use lru::LruCache;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Entities kept by [`CachedStorage::new`]
+pub const DEFAULT_CACHE_CAPACITY: usize = 256;
+
+/// (entity type, entity ID)
+type CacheKey = (String, String);
+
+/// Cache lookups so far
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: usize,
+    pub misses: usize,
+}
+
+/// Storage wrapper caching entities read by ID. Clones share the cache, so
+/// a write through any of them invalidates it for all.
+pub struct CachedStorage<S> {
+    inner: S,
+    cache: Arc<Mutex<LruCache<CacheKey, GenericEntity>>>,
+    hits: Arc<AtomicUsize>,
+    misses: Arc<AtomicUsize>,
+}
+
+impl<S: Clone> Clone for CachedStorage<S> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            cache: self.cache.clone(),
+            hits: self.hits.clone(),
+            misses: self.misses.clone(),
+        }
+    }
+}
+
+impl<S: Storage> CachedStorage<S> {
+    pub fn new(inner: S) -> Self {
+        Self::with_capacity(inner, DEFAULT_CACHE_CAPACITY)
+    }
+
+    /// Cache at most `capacity` entities; a capacity of 0 is treated as 1
+    pub fn with_capacity(inner: S, capacity: usize) -> Self {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
+        Self {
+            inner,
+            cache: Arc::new(Mutex::new(LruCache::new(capacity))),
+            hits: Arc::new(AtomicUsize::new(0)),
+            misses: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// The wrapped backend
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    /// A poisoned lock only means another reader panicked mid-lookup; the
+    /// cached entities themselves are still whole
+    fn cache(&self) -> MutexGuard<'_, LruCache<CacheKey, GenericEntity>> {
+        self.cache
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn cached(&self, id: &str, entity_type: &str) -> Option<GenericEntity> {
+        let entity = self
+            .cache()
+            .get(&(entity_type.to_string(), id.to_string()))
+            .cloned();
+        let counter = if entity.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        entity
+    }
+
+    /// Remember an entity loaded by its full ID. Entities found by a short
+    /// ID are not cached, so a write to the full ID always invalidates.
+    fn remember(&self, id: &str, entity: &GenericEntity) {
+        if entity.id == id {
+            self.cache().put(
+                (entity.entity_type.clone(), entity.id.clone()),
+                entity.clone(),
+            );
+        }
+    }
+
+    fn invalidate(&self, id: &str, entity_type: &str) {
+        self.cache().pop(&(entity_type.to_string(), id.to_string()));
+    }
+
+    fn clear(&self) {
+        self.cache().clear();
+    }
+}
+
+impl<S> Drop for CachedStorage<S> {
+    fn drop(&mut self) {
+        // Log once, when the last clone goes
+        if Arc::strong_count(&self.cache) > 1 {
+            return;
+        }
+        tracing::debug!(
+            hits = self.hits.load(Ordering::Relaxed),
+            misses = self.misses.load(Ordering::Relaxed),
+            "entity cache"
+        );
+    }
+}
+
+impl<S: Storage + 'static> Storage for CachedStorage<S> {
+    fn store(&mut self, entity: &GenericEntity) -> Result<(), EngramError> {
+        self.invalidate(&entity.id, &entity.entity_type);
+        self.inner.store(entity)
+    }
+
+    fn patch(
+        &mut self,
+        id: &str,
+        entity_type: &str,
+        patch: Vec<JsonPatchOp>,
+    ) -> Result<(), EngramError> {
+        self.invalidate(id, entity_type);
+        self.inner.patch(id, entity_type, patch)
+    }
+
+    fn get(&self, id: &str, entity_type: &str) -> Result<Option<GenericEntity>, EngramError> {
+        if let Some(entity) = self.cached(id, entity_type) {
+            return Ok(Some(entity));
+        }
+        let entity = self.inner.get(id, entity_type)?;
+        if let Some(entity) = &entity {
+            self.remember(id, entity);
+        }
+        Ok(entity)
+    }
+
+    fn exists(&self, id: &str, entity_type: &str) -> Result<bool, EngramError> {
+        if self
+            .cache()
+            .contains(&(entity_type.to_string(), id.to_string()))
+        {
+            return Ok(true);
+        }
+        self.inner.exists(id, entity_type)
+    }
+
+    fn validate_for_store(&self, entity: &GenericEntity) -> Result<(), EngramError> {
+        self.inner.validate_for_store(entity)
+    }
+
+    fn get_many(
+        &self,
+        ids: &[String],
+        entity_type: &str,
+    ) -> Result<Vec<Option<GenericEntity>>, EngramError> {
+        let mut found: Vec<Option<GenericEntity>> =
+            ids.iter().map(|id| self.cached(id, entity_type)).collect();
+        let missing: Vec<String> = ids
+            .iter()
+            .zip(&found)
+            .filter(|(_, entity)| entity.is_none())
+            .map(|(id, _)| id.clone())
+            .collect();
+        if missing.is_empty() {
+            return Ok(found);
+        }
+
+        let mut loaded = self.inner.get_many(&missing, entity_type)?.into_iter();
+        for (id, slot) in ids.iter().zip(found.iter_mut()) {
+            if slot.is_none() {
+                *slot = loaded.next().flatten();
+                if let Some(entity) = slot {
+                    self.remember(id, entity);
+                }
+            }
+        }
+        Ok(found)
+    }
+
+    fn query(&self, filter: &QueryFilter) -> Result<QueryResult, EngramError> {
+        self.inner.query(filter)
+    }
+
+    fn query_by_agent(
+        &self,
+        agent: &str,
+        entity_type: Option<&str>,
+    ) -> Result<Vec<GenericEntity>, EngramError> {
+        self.inner.query_by_agent(agent, entity_type)
+    }
+
+    fn query_by_time_range(
+        &self,
+        start: chrono::DateTime<chrono::Utc>,
+        end: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<GenericEntity>, EngramError> {
+        self.inner.query_by_time_range(start, end)
+    }
+
+    fn query_by_type(
+        &self,
+        entity_type: &str,
+        filters: Option<&HashMap<String, Value>>,
+        limit: Option<usize>,
+        offset: Option<usize>,
+    ) -> Result<QueryResult, EngramError> {
+        self.inner
+            .query_by_type(entity_type, filters, limit, offset)
+    }
+
+    fn text_search(
+        &self,
+        query: &str,
+        entity_types: Option<&[String]>,
+        limit: Option<usize>,
+    ) -> Result<Vec<GenericEntity>, EngramError> {
+        self.inner.text_search(query, entity_types, limit)
+    }
+
+    fn count(&self, filter: &QueryFilter) -> Result<usize, EngramError> {
+        self.inner.count(filter)
+    }
+
+    fn delete(&mut self, id: &str, entity_type: &str) -> Result<(), EngramError> {
+        self.invalidate(id, entity_type);
+        self.inner.delete(id, entity_type)
+    }
+
+    fn list_ids(&self, entity_type: &str) -> Result<Vec<String>, EngramError> {
+        self.inner.list_ids(entity_type)
+    }
+
+    fn get_all(&self, entity_type: &str) -> Result<Vec<GenericEntity>, EngramError> {
+        self.inner.get_all(entity_type)
+    }
+
+    fn sync(&mut self) -> Result<(), EngramError> {
+        self.clear();
+        self.inner.sync()
+    }
+
+    fn current_branch(&self) -> Result<String, EngramError> {
+        self.inner.current_branch()
+    }
+
+    fn create_branch(&mut self, branch_name: &str) -> Result<(), EngramError> {
+        self.inner.create_branch(branch_name)
+    }
+
+    fn switch_branch(&mut self, branch_name: &str) -> Result<(), EngramError> {
+        self.clear();
+        self.inner.switch_branch(branch_name)
+    }
+
+    fn merge_branches(&mut self, source: &str, target: &str) -> Result<(), EngramError> {
+        self.clear();
+        self.inner.merge_branches(source, target)
+    }
+
+    fn history(&self, limit: Option<usize>) -> Result<Vec<GitCommit>, EngramError> {
+        self.inner.history(limit)
+    }
+
+    fn bulk_store(&mut self, entities: &[GenericEntity]) -> Result<(), EngramError> {
+        for entity in entities {
+            self.invalidate(&entity.id, &entity.entity_type);
+        }
+        self.inner.bulk_store(entities)
+    }
+
+    fn get_stats(&self) -> Result<StorageStats, EngramError> {
+        self.inner.get_stats()
+    }
+
+    fn is_read_only(&self) -> bool {
+        self.inner.is_read_only()
+    }
+
+    /// The cache is transparent, so downcasts see the wrapped backend
+    fn as_any(&self) -> &dyn std::any::Any {
+        self.inner.as_any()
+    }
+}
+
+impl<S: RelationshipStorage + 'static> RelationshipStorage for CachedStorage<S> {
+    fn store_relationship(&mut self, relationship: &EntityRelationship) -> Result<(), EngramError> {
+        self.invalidate(&relationship.id, "relationship");
+        self.inner.store_relationship(relationship)
+    }
+
+    fn get_relationship(&self, id: &str) -> Result<Option<EntityRelationship>, EngramError> {
+        self.inner.get_relationship(id)
+    }
+
+    fn query_relationships(
+        &self,
+        filter: &RelationshipFilter,
+    ) -> Result<Vec<EntityRelationship>, EngramError> {
+        self.inner.query_relationships(filter)
+    }
+
+    fn get_entity_relationships(
+        &self,
+        entity_id: &str,
+    ) -> Result<Vec<EntityRelationship>, EngramError> {
+        self.inner.get_entity_relationships(entity_id)
+    }
+
+    fn get_outbound_relationships(
+        &self,
+        entity_id: &str,
+    ) -> Result<Vec<EntityRelationship>, EngramError> {
+        self.inner.get_outbound_relationships(entity_id)
+    }
+
+    fn get_inbound_relationships(
+        &self,
+        entity_id: &str,
+    ) -> Result<Vec<EntityRelationship>, EngramError> {
+        self.inner.get_inbound_relationships(entity_id)
+    }
+
+    fn find_paths(
+        &self,
+        source_id: &str,
+        target_id: &str,
+        algorithm: TraversalAlgorithm,
+        max_depth: Option<usize>,
+    ) -> Result<Vec<EntityPath>, EngramError> {
+        self.inner
+            .find_paths(source_id, target_id, algorithm, max_depth)
+    }
+
+    fn get_connected_entities(
+        &self,
+        entity_id: &str,
+        algorithm: TraversalAlgorithm,
+        max_depth: Option<usize>,
+    ) -> Result<Vec<String>, EngramError> {
+        self.inner
+            .get_connected_entities(entity_id, algorithm, max_depth)
+    }
+
+    fn delete_relationship(&mut self, id: &str) -> Result<(), EngramError> {
+        self.invalidate(id, "relationship");
+        self.inner.delete_relationship(id)
+    }
+
+    fn get_relationship_index(&self) -> Result<&RelationshipIndex, EngramError> {
+        self.inner.get_relationship_index()
+    }
+
+    fn rebuild_relationship_index(&mut self) -> Result<(), EngramError> {
+        self.inner.rebuild_relationship_index()
+    }
+
+    fn get_relationship_stats(&self) -> Result<RelationshipStats, EngramError> {
+        self.inner.get_relationship_stats()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::MemoryStorage;
+
+    fn entity(title: &str) -> GenericEntity {
+        GenericEntity {
+            id: "ctx-1".to_string(),
+            entity_type: "context".to_string(),
+            agent: "default".to_string(),
+            timestamp: chrono::Utc::now(),
+            data: serde_json::json!({ "title": title }),
+        }
+    }
+
+    #[test]
+    fn test_repeated_gets_hit_until_a_store_invalidates() {
+        let mut memory = MemoryStorage::new("default");
+        memory.store(&entity("First")).unwrap();
+        let mut storage = CachedStorage::new(memory);
+
+        for _ in 0..3 {
+            let found = storage.get("ctx-1", "context").unwrap().unwrap();
+            assert_eq!(found.data["title"], "First");
+        }
+        assert_eq!(storage.stats(), CacheStats { hits: 2, misses: 1 });
+
+        storage.store(&entity("Second")).unwrap();
+        let found = storage.get("ctx-1", "context").unwrap().unwrap();
+        assert_eq!(found.data["title"], "Second");
+        assert_eq!(storage.stats(), CacheStats { hits: 2, misses: 2 });
+
+        let ids = vec!["ctx-1".to_string(), "missing".to_string()];
+        let found = storage.get_many(&ids, "context").unwrap();
+        assert_eq!(found[0].as_ref().unwrap().data["title"], "Second");
+        assert!(found[1].is_none());
+        assert_eq!(storage.stats(), CacheStats { hits: 3, misses: 3 });
+
+        storage.delete("ctx-1", "context").unwrap();
+        assert!(storage.get("ctx-1", "context").unwrap().is_none());
+    }
+}
@@ -0,0 +1,376 @@
+//! Conflict resolvers for multi-agent sync
+//!
+//! [`IntelligentMergeResolver`] performs a field-level three-way merge of two
+//! diverged versions of an entity against their common ancestor. Fields
+//! changed on only one side merge cleanly; fields changed on both sides to
+//! different values are kept as conflict markers in the entity's `metadata`
+//! and flagged for manual resolution. Modification metadata is never a
+//! conflict: the merged entity takes the later of the two sides.
+
+use super::{ConflictResolution, SyncStrategy};
+use crate::entities::{
+    display_diff_value, EntityDiff, GenericEntity, ListChange, OldNew, LAST_MODIFIED_BY_FIELD,
+    MODIFICATION_COUNT_FIELD,
+};
+use crate::error::EngramError;
+use serde_json::{Map, Value};
+
+/// Metadata key prefix for our side of a conflicting field
+pub const CONFLICT_OURS_PREFIX: &str = "__conflict_ours_";
+
+/// Metadata key prefix for their side of a conflicting field
+pub const CONFLICT_THEIRS_PREFIX: &str = "__conflict_theirs_";
+
+/// Kind of conflict found during a merge
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConflictType {
+    /// Both sides changed the same field to different values
+    ActionConflict,
+}
+
+/// A single conflicting field; nested fields use dotted paths
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldConflict {
+    pub field: String,
+    pub conflict_type: ConflictType,
+    /// `None` when our side removed the field
+    pub ours: Option<Value>,
+    /// `None` when their side removed the field
+    pub theirs: Option<Value>,
+}
+
+/// Result of a three-way merge
+#[derive(Debug, Clone)]
+pub struct MergeOutcome {
+    /// Merged entity; conflicting fields keep our value until resolved
+    pub merged: GenericEntity,
+    pub conflicts: Vec<FieldConflict>,
+}
+
+impl MergeOutcome {
+    /// Whether the merge needs human review
+    pub fn needs_review(&self) -> bool {
+        !self.conflicts.is_empty()
+    }
+
+    /// The conflicting fields as a diff from our side to theirs, for
+    /// rendering with [`EntityDiff::render`]
+    pub fn conflict_diff(&self) -> EntityDiff {
+        let mut diff = EntityDiff::default();
+        for conflict in &self.conflicts {
+            let field = conflict.field.clone();
+            match (&conflict.ours, &conflict.theirs) {
+                (Some(Value::Array(ours)), Some(Value::Array(theirs))) => diff
+                    .list_changes
+                    .push((field, ListChange::between(ours, theirs))),
+                (Some(ours), Some(theirs)) => diff.modified_fields.push((
+                    field,
+                    OldNew {
+                        old: ours.clone(),
+                        new: theirs.clone(),
+                    },
+                )),
+                (Some(ours), None) => diff.removed_fields.push((field, ours.clone())),
+                (None, Some(theirs)) => diff.added_fields.push((field, theirs.clone())),
+                (None, None) => {}
+            }
+        }
+        diff
+    }
+
+    /// Summarize the merge as a [`ConflictResolution`]
+    ///
+    /// Clean merges report `IntelligentMerge`; merges with conflicts report
+    /// `ManualResolution` with one entry per conflicting field.
+    pub fn resolution(&self) -> ConflictResolution {
+        let (strategy_used, winner) = if self.needs_review() {
+            (SyncStrategy::ManualResolution, "manual".to_string())
+        } else {
+            (SyncStrategy::IntelligentMerge, "merged".to_string())
+        };
+        ConflictResolution {
+            entity_id: self.merged.id.clone(),
+            entity_type: self.merged.entity_type.clone(),
+            strategy_used,
+            winner,
+            conflicts_detected: self
+                .conflicts
+                .iter()
+                .map(|c| {
+                    format!(
+                        "Field '{}' changed on both sides: {} vs {}",
+                        c.field,
+                        display(&c.ours),
+                        display(&c.theirs)
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+fn display(value: &Option<Value>) -> String {
+    value
+        .as_ref()
+        .map_or_else(|| "<removed>".to_string(), display_diff_value)
+}
+
+/// Field-level three-way merge resolver
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IntelligentMergeResolver;
+
+impl IntelligentMergeResolver {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Merge `ours` and `theirs` against their common ancestor `base`
+    pub fn merge(
+        &self,
+        base: &GenericEntity,
+        ours: &GenericEntity,
+        theirs: &GenericEntity,
+    ) -> Result<MergeOutcome, EngramError> {
+        for other in [base, theirs] {
+            if other.id != ours.id || other.entity_type != ours.entity_type {
+                return Err(EngramError::Validation(format!(
+                    "Cannot merge {} '{}' with {} '{}'",
+                    ours.entity_type, ours.id, other.entity_type, other.id
+                )));
+            }
+        }
+
+        let empty = Map::new();
+        let object = |entity: &GenericEntity| {
+            let mut object = entity.data.as_object().unwrap_or(&empty).clone();
+            object.remove(LAST_MODIFIED_BY_FIELD);
+            object.remove(MODIFICATION_COUNT_FIELD);
+            object
+        };
+        let mut conflicts = Vec::new();
+        let mut data = merge_objects(
+            &object(base),
+            &object(ours),
+            &object(theirs),
+            "",
+            &mut conflicts,
+        );
+
+        if !conflicts.is_empty() {
+            let metadata = data
+                .entry("metadata")
+                .or_insert_with(|| Value::Object(Map::new()));
+            if !metadata.is_object() {
+                *metadata = Value::Object(Map::new());
+            }
+            let metadata = metadata.as_object_mut().expect("metadata is an object");
+            for conflict in &conflicts {
+                let null = |v: &Option<Value>| v.clone().unwrap_or(Value::Null);
+                metadata.insert(
+                    format!("{}{}", CONFLICT_OURS_PREFIX, conflict.field),
+                    null(&conflict.ours),
+                );
+                metadata.insert(
+                    format!("{}{}", CONFLICT_THEIRS_PREFIX, conflict.field),
+                    null(&conflict.theirs),
+                );
+            }
+        }
+
+        let newer = if theirs.timestamp > ours.timestamp {
+            theirs
+        } else {
+            ours
+        };
+        let (ours_modification, theirs_modification) = (ours.modification(), theirs.modification());
+        let modification = if theirs_modification.is_later_than(&ours_modification) {
+            theirs_modification
+        } else if ours_modification.is_later_than(&theirs_modification) {
+            ours_modification
+        } else {
+            newer.modification()
+        };
+        let mut merged = GenericEntity {
+            id: ours.id.clone(),
+            entity_type: ours.entity_type.clone(),
+            agent: newer.agent.clone(),
+            timestamp: newer.timestamp,
+            data: Value::Object(data),
+        };
+        merged.set_modification(&modification);
+        Ok(MergeOutcome { merged, conflicts })
+    }
+}
+
+fn merge_objects(
+    base: &Map<String, Value>,
+    ours: &Map<String, Value>,
+    theirs: &Map<String, Value>,
+    prefix: &str,
+    conflicts: &mut Vec<FieldConflict>,
+) -> Map<String, Value> {
+    let mut keys: Vec<&String> = base
+        .keys()
+        .chain(ours.keys())
+        .chain(theirs.keys())
+        .collect();
+    keys.sort();
+    keys.dedup();
+
+    let mut merged = Map::new();
+    for key in keys {
+        let (b, o, t) = (base.get(key), ours.get(key), theirs.get(key));
+        let value = if o == t || t == b {
+            o.cloned()
+        } else if o == b {
+            t.cloned()
+        } else if let (Some(Value::Object(o)), Some(Value::Object(t))) = (o, t) {
+            let empty = Map::new();
+            let b = b.and_then(Value::as_object).unwrap_or(&empty);
+            let path = format!("{}{}.", prefix, key);
+            Some(Value::Object(merge_objects(b, o, t, &path, conflicts)))
+        } else {
+            conflicts.push(FieldConflict {
+                field: format!("{}{}", prefix, key),
+                conflict_type: ConflictType::ActionConflict,
+                ours: o.cloned(),
+                theirs: t.cloned(),
+            });
+            o.cloned()
+        };
+        if let Some(value) = value {
+            merged.insert(key.clone(), value);
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+    use serde_json::json;
+
+    fn version(agent: &str, minutes: i64, data: Value) -> GenericEntity {
+        GenericEntity {
+            id: "task-1".to_string(),
+            entity_type: "task".to_string(),
+            agent: agent.to_string(),
+            timestamp: Utc::now() + Duration::minutes(minutes),
+            data,
+        }
+    }
+
+    #[test]
+    fn test_three_way_merge_combines_one_sided_changes() {
+        let base = version(
+            "alice",
+            0,
+            json!({"title": "Draft", "priority": "low", "metadata": {"team": "core"}}),
+        );
+        let ours = version(
+            "alice",
+            1,
+            json!({"title": "Final", "priority": "low", "metadata": {"team": "core"}}),
+        );
+        let theirs = version(
+            "bob",
+            2,
+            json!({"title": "Draft", "priority": "high", "metadata": {"team": "core", "sprint": 3}}),
+        );
+
+        let outcome = IntelligentMergeResolver::new()
+            .merge(&base, &ours, &theirs)
+            .unwrap();
+        assert!(!outcome.needs_review());
+        assert_eq!(
+            outcome.merged.data,
+            json!({"title": "Final", "priority": "high", "metadata": {"team": "core", "sprint": 3}})
+        );
+        assert_eq!(outcome.merged.agent, "bob");
+        let resolution = outcome.resolution();
+        assert!(matches!(
+            resolution.strategy_used,
+            SyncStrategy::IntelligentMerge
+        ));
+        assert!(resolution.conflicts_detected.is_empty());
+    }
+
+    #[test]
+    fn test_three_way_merge_flags_action_conflicts() {
+        let base = version("alice", 0, json!({"status": "todo", "notes": "x"}));
+        let ours = version("alice", 1, json!({"status": "done", "notes": "x"}));
+        let theirs = version("bob", 2, json!({"status": "blocked"}));
+
+        let outcome = IntelligentMergeResolver::new()
+            .merge(&base, &ours, &theirs)
+            .unwrap();
+        assert_eq!(
+            outcome.conflicts,
+            vec![FieldConflict {
+                field: "status".to_string(),
+                conflict_type: ConflictType::ActionConflict,
+                ours: Some(json!("done")),
+                theirs: Some(json!("blocked")),
+            }]
+        );
+        assert_eq!(
+            outcome.merged.data,
+            json!({
+                "status": "done",
+                "metadata": {
+                    "__conflict_ours_status": "done",
+                    "__conflict_theirs_status": "blocked"
+                }
+            })
+        );
+        assert!(matches!(
+            outcome.resolution().strategy_used,
+            SyncStrategy::ManualResolution
+        ));
+        assert_eq!(
+            outcome.conflict_diff().render(false),
+            "@@ status @@\n- done\n+ blocked\n"
+        );
+
+        let mut other = theirs.clone();
+        other.id = "task-2".to_string();
+        assert!(matches!(
+            IntelligentMergeResolver::new().merge(&base, &ours, &other),
+            Err(EngramError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_three_way_merge_takes_later_modification() {
+        let base = version(
+            "alice",
+            0,
+            json!({"title": "Draft", "last_modified_by": "alice", "modification_count": 1}),
+        );
+        let ours = version(
+            "alice",
+            2,
+            json!({"title": "Final", "last_modified_by": "alice", "modification_count": 2}),
+        );
+        let theirs = version(
+            "bob",
+            1,
+            json!({"title": "Draft", "last_modified_by": "carol", "modification_count": 4}),
+        );
+
+        let outcome = IntelligentMergeResolver::new()
+            .merge(&base, &ours, &theirs)
+            .unwrap();
+        assert!(!outcome.needs_review());
+        assert_eq!(outcome.merged.data["title"], json!("Final"));
+        assert_eq!(
+            outcome.merged.modification(),
+            crate::entities::Modification {
+                last_modified_by: Some("carol".to_string()),
+                modification_count: 4,
+                last_modified_at: None,
+            }
+        );
+    }
+}
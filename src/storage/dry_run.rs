@@ -0,0 +1,555 @@
+//! Dry-run storage wrapper
+//!
+//! [`DryRunStorage`] wraps another backend and records stores and deletes
+//! instead of applying them. Reads go to the wrapped backend with the
+//! recorded changes overlaid, so a command sees its own would-be writes and
+//! behaves as it would for real. Branch and sync operations are skipped.
+
+use super::query::apply_filter;
+use super::{
+    EntityPath, GitCommit, QueryFilter, QueryResult, RelationshipIndex, RelationshipStats,
+    RelationshipStorage, Storage, StorageStats, TraversalAlgorithm,
+};
+use crate::entities::{Entity, EntityRelationship, GenericEntity, RelationshipFilter};
+use crate::error::EngramError;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Kind of write a dry run intercepted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlannedAction {
+    Store,
+    Delete,
+}
+
+/// A write that would have been applied
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PlannedChange {
+    pub action: PlannedAction,
+    pub entity_type: String,
+    pub entity_id: String,
+}
+
+/// Would-be changes for one entity type
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct PlannedCounts {
+    pub stores: usize,
+    pub deletes: usize,
+}
+
+#[derive(Debug, Default)]
+struct DryRunLog {
+    changes: Vec<PlannedChange>,
+    /// Latest state per (entity_type, id); `None` marks a delete
+    overlay: HashMap<(String, String), Option<GenericEntity>>,
+}
+
+/// Storage wrapper that records writes instead of applying them
+///
+/// Clones share the same log, so engines that take an owned storage can be
+/// handed a clone and their writes still show up in the report.
+#[derive(Debug)]
+pub struct DryRunStorage<S> {
+    inner: S,
+    log: Arc<Mutex<DryRunLog>>,
+}
+
+impl<S: Clone> Clone for DryRunStorage<S> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            log: Arc::clone(&self.log),
+        }
+    }
+}
+
+impl<S: Storage> DryRunStorage<S> {
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            log: Arc::new(Mutex::new(DryRunLog::default())),
+        }
+    }
+
+    /// The wrapped backend, without the recorded changes
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    fn log(&self) -> MutexGuard<'_, DryRunLog> {
+        self.log.lock().unwrap()
+    }
+
+    fn record(&self, action: PlannedAction, entity_type: &str, id: &str) {
+        self.log().changes.push(PlannedChange {
+            action,
+            entity_type: entity_type.to_string(),
+            entity_id: id.to_string(),
+        });
+    }
+
+    /// Every intercepted write, in order
+    pub fn changes(&self) -> Vec<PlannedChange> {
+        self.log().changes.clone()
+    }
+
+    /// Intercepted writes counted per entity type
+    pub fn summary(&self) -> BTreeMap<String, PlannedCounts> {
+        let mut summary: BTreeMap<String, PlannedCounts> = BTreeMap::new();
+        for change in &self.log().changes {
+            let counts = summary.entry(change.entity_type.clone()).or_default();
+            match change.action {
+                PlannedAction::Store => counts.stores += 1,
+                PlannedAction::Delete => counts.deletes += 1,
+            }
+        }
+        summary
+    }
+
+    /// Print the would-be changes, as JSON or a per-type summary
+    pub fn print_report(&self, json: bool) {
+        let changes = self.changes();
+        if json {
+            let report = serde_json::json!({
+                "dry_run": true,
+                "summary": self.summary(),
+                "changes": changes,
+            });
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&report).unwrap_or_default()
+            );
+            return;
+        }
+
+        println!();
+        if changes.is_empty() {
+            println!("🧪 Dry run: no changes would be made");
+            return;
+        }
+        println!("🧪 Dry run: {} change(s) not applied", changes.len());
+        for (entity_type, counts) in self.summary() {
+            println!(
+                "  {}: {} store(s), {} delete(s)",
+                entity_type, counts.stores, counts.deletes
+            );
+        }
+        for change in &changes {
+            let action = match change.action {
+                PlannedAction::Store => "store",
+                PlannedAction::Delete => "delete",
+            };
+            println!("    {} {} {}", action, change.entity_type, change.entity_id);
+        }
+    }
+
+    /// Overlay recorded changes onto entities read from the wrapped backend.
+    /// `include` decides which recorded stores not already in `entities` are added.
+    fn overlay(
+        &self,
+        entities: Vec<GenericEntity>,
+        include: impl Fn(&GenericEntity) -> bool,
+    ) -> Vec<GenericEntity> {
+        let log = self.log();
+        let mut seen = std::collections::HashSet::new();
+        let mut merged: Vec<GenericEntity> = entities
+            .into_iter()
+            .filter_map(|entity| {
+                let key = (entity.entity_type.clone(), entity.id.clone());
+                seen.insert(key.clone());
+                match log.overlay.get(&key) {
+                    Some(Some(updated)) => Some(updated.clone()),
+                    Some(None) => None,
+                    None => Some(entity),
+                }
+            })
+            .collect();
+        merged.extend(
+            log.overlay
+                .iter()
+                .filter(|(key, _)| !seen.contains(*key))
+                .filter_map(|(_, entity)| entity.clone())
+                .filter(|entity| include(entity)),
+        );
+        merged
+    }
+}
+
+impl<S: Storage + 'static> Storage for DryRunStorage<S> {
+    fn store(&mut self, entity: &GenericEntity) -> Result<(), EngramError> {
+        self.inner.validate_for_store(entity)?;
+        self.record(PlannedAction::Store, &entity.entity_type, &entity.id);
+        self.log().overlay.insert(
+            (entity.entity_type.clone(), entity.id.clone()),
+            Some(entity.clone()),
+        );
+        Ok(())
+    }
+
+    fn get(&self, id: &str, entity_type: &str) -> Result<Option<GenericEntity>, EngramError> {
+        let key = (entity_type.to_string(), id.to_string());
+        if let Some(entity) = self.log().overlay.get(&key) {
+            return Ok(entity.clone());
+        }
+        self.inner.get(id, entity_type)
+    }
+
+    fn validate_for_store(&self, entity: &GenericEntity) -> Result<(), EngramError> {
+        self.inner.validate_for_store(entity)
+    }
+
+    fn query(&self, filter: &QueryFilter) -> Result<QueryResult, EngramError> {
+        let unpaged = QueryFilter {
+            limit: None,
+            offset: None,
+            ..filter.clone()
+        };
+        let entities = self.inner.query(&unpaged)?.entities;
+        let entities = self.overlay(entities, |_| true);
+        apply_filter(entities, filter)
+    }
+
+    fn query_by_agent(
+        &self,
+        agent: &str,
+        entity_type: Option<&str>,
+    ) -> Result<Vec<GenericEntity>, EngramError> {
+        let entities = self.inner.query_by_agent(agent, entity_type)?;
+        Ok(self.overlay(entities, |e| {
+            e.agent == agent && entity_type.is_none_or(|t| e.entity_type == t)
+        }))
+    }
+
+    fn query_by_time_range(
+        &self,
+        start: chrono::DateTime<chrono::Utc>,
+        end: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<GenericEntity>, EngramError> {
+        let entities = self.inner.query_by_time_range(start, end)?;
+        Ok(self.overlay(entities, |e| e.timestamp >= start && e.timestamp <= end))
+    }
+
+    fn query_by_type(
+        &self,
+        entity_type: &str,
+        filters: Option<&HashMap<String, Value>>,
+        limit: Option<usize>,
+        offset: Option<usize>,
+    ) -> Result<QueryResult, EngramError> {
+        self.query(&QueryFilter {
+            entity_type: Some(entity_type.to_string()),
+            field_filters: filters.cloned().unwrap_or_default(),
+            limit,
+            offset,
+            ..Default::default()
+        })
+    }
+
+    fn text_search(
+        &self,
+        query: &str,
+        entity_types: Option<&[String]>,
+        limit: Option<usize>,
+    ) -> Result<Vec<GenericEntity>, EngramError> {
+        // Recorded stores are not searched; the wrapped backend's ranking is kept
+        let entities = self.inner.text_search(query, entity_types, limit)?;
+        Ok(self.overlay(entities, |_| false))
+    }
+
+    fn count(&self, filter: &QueryFilter) -> Result<usize, EngramError> {
+        Ok(self.query(filter)?.total_count)
+    }
+
+    fn delete(&mut self, id: &str, entity_type: &str) -> Result<(), EngramError> {
+        self.record(PlannedAction::Delete, entity_type, id);
+        self.log()
+            .overlay
+            .insert((entity_type.to_string(), id.to_string()), None);
+        Ok(())
+    }
+
+    fn list_ids(&self, entity_type: &str) -> Result<Vec<String>, EngramError> {
+        Ok(self
+            .get_all(entity_type)?
+            .into_iter()
+            .map(|entity| entity.id)
+            .collect())
+    }
+
+    fn get_all(&self, entity_type: &str) -> Result<Vec<GenericEntity>, EngramError> {
+        let entities = self.inner.get_all(entity_type)?;
+        Ok(self.overlay(entities, |e| e.entity_type == entity_type))
+    }
+
+    fn sync(&mut self) -> Result<(), EngramError> {
+        Ok(())
+    }
+
+    fn current_branch(&self) -> Result<String, EngramError> {
+        self.inner.current_branch()
+    }
+
+    fn create_branch(&mut self, _branch_name: &str) -> Result<(), EngramError> {
+        Ok(())
+    }
+
+    fn switch_branch(&mut self, _branch_name: &str) -> Result<(), EngramError> {
+        Ok(())
+    }
+
+    fn merge_branches(&mut self, _source: &str, _target: &str) -> Result<(), EngramError> {
+        Ok(())
+    }
+
+    fn history(&self, limit: Option<usize>) -> Result<Vec<GitCommit>, EngramError> {
+        self.inner.history(limit)
+    }
+
+    fn bulk_store(&mut self, entities: &[GenericEntity]) -> Result<(), EngramError> {
+        for entity in entities {
+            self.store(entity)?;
+        }
+        Ok(())
+    }
+
+    fn get_stats(&self) -> Result<StorageStats, EngramError> {
+        self.inner.get_stats()
+    }
+
+    fn is_read_only(&self) -> bool {
+        true
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Relationship reads see only the wrapped backend; relationship writes are
+/// recorded like any other entity
+impl<S: RelationshipStorage + 'static> RelationshipStorage for DryRunStorage<S> {
+    fn store_relationship(&mut self, relationship: &EntityRelationship) -> Result<(), EngramError> {
+        self.store(&relationship.to_generic())
+    }
+
+    fn get_relationship(&self, id: &str) -> Result<Option<EntityRelationship>, EngramError> {
+        match self.get(id, EntityRelationship::entity_type())? {
+            Some(generic) => Ok(Some(EntityRelationship::from_generic(generic)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn query_relationships(
+        &self,
+        filter: &RelationshipFilter,
+    ) -> Result<Vec<EntityRelationship>, EngramError> {
+        self.inner.query_relationships(filter)
+    }
+
+    fn get_entity_relationships(
+        &self,
+        entity_id: &str,
+    ) -> Result<Vec<EntityRelationship>, EngramError> {
+        self.inner.get_entity_relationships(entity_id)
+    }
+
+    fn get_outbound_relationships(
+        &self,
+        entity_id: &str,
+    ) -> Result<Vec<EntityRelationship>, EngramError> {
+        self.inner.get_outbound_relationships(entity_id)
+    }
+
+    fn get_inbound_relationships(
+        &self,
+        entity_id: &str,
+    ) -> Result<Vec<EntityRelationship>, EngramError> {
+        self.inner.get_inbound_relationships(entity_id)
+    }
+
+    fn find_paths(
+        &self,
+        source_id: &str,
+        target_id: &str,
+        algorithm: TraversalAlgorithm,
+        max_depth: Option<usize>,
+    ) -> Result<Vec<EntityPath>, EngramError> {
+        self.inner
+            .find_paths(source_id, target_id, algorithm, max_depth)
+    }
+
+    fn get_connected_entities(
+        &self,
+        entity_id: &str,
+        algorithm: TraversalAlgorithm,
+        max_depth: Option<usize>,
+    ) -> Result<Vec<String>, EngramError> {
+        self.inner
+            .get_connected_entities(entity_id, algorithm, max_depth)
+    }
+
+    fn delete_relationship(&mut self, id: &str) -> Result<(), EngramError> {
+        self.delete(id, EntityRelationship::entity_type())
+    }
+
+    fn get_relationship_index(&self) -> Result<&RelationshipIndex, EngramError> {
+        self.inner.get_relationship_index()
+    }
+
+    fn rebuild_relationship_index(&mut self) -> Result<(), EngramError> {
+        Ok(())
+    }
+
+    fn get_relationship_stats(&self) -> Result<RelationshipStats, EngramError> {
+        self.inner.get_relationship_stats()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{create_rule, create_task, execute_rule};
+    use crate::entities::Rule;
+    use crate::storage::MemoryStorage;
+
+    #[test]
+    fn test_task_create_writes_nothing() {
+        let memory = MemoryStorage::new("default");
+        let mut storage = DryRunStorage::new(memory.clone());
+
+        create_task(
+            &mut storage,
+            Some("Preview only".to_string()),
+            None,
+            "medium",
+            None,
+            None,
+            None,
+            false,
+            None,
+            false,
+            None,
+            false,
+            None,
+            "text".to_string(),
+            false,
+            None,
+            false,
+        )
+        .unwrap();
+
+        assert!(memory.get_all("task").unwrap().is_empty());
+        let changes = storage.changes();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].action, PlannedAction::Store);
+        assert_eq!(changes[0].entity_type, "task");
+
+        // The command's own reads see the would-be task
+        assert_eq!(storage.get_all("task").unwrap().len(), 1);
+        assert_eq!(storage.summary()["task"].stores, 1);
+    }
+
+    #[test]
+    fn test_rule_execute_writes_nothing() {
+        let mut memory = MemoryStorage::new("default");
+        create_rule(
+            &mut memory,
+            "Done check".to_string(),
+            None,
+            "validation".to_string(),
+            "medium".to_string(),
+            Some("task".to_string()),
+            r#"{"field": "status", "operator": "eq", "value": "done"}"#.to_string(),
+            r#"{"type": "notify", "message": "Task done"}"#.to_string(),
+            Some("default".to_string()),
+            None,
+        )
+        .unwrap();
+        create_task(
+            &mut memory,
+            Some("Target".to_string()),
+            None,
+            "medium",
+            None,
+            None,
+            None,
+            false,
+            None,
+            false,
+            None,
+            false,
+            None,
+            "text".to_string(),
+            false,
+            None,
+            false,
+        )
+        .unwrap();
+        let rule_id = memory.list_ids("rule").unwrap()[0].clone();
+        let task_id = memory.list_ids("task").unwrap()[0].clone();
+        let before = memory.get(&rule_id, "rule").unwrap().unwrap();
+
+        let mut storage = DryRunStorage::new(memory.clone());
+        execute_rule(&mut storage, &rule_id, task_id, "task".to_string()).unwrap();
+
+        let after = memory.get(&rule_id, "rule").unwrap().unwrap();
+        assert_eq!(before.data, after.data);
+        assert_eq!(
+            storage.changes(),
+            vec![PlannedChange {
+                action: PlannedAction::Store,
+                entity_type: "rule".to_string(),
+                entity_id: rule_id.clone(),
+            }]
+        );
+        let previewed = Rule::from_generic(storage.get(&rule_id, "rule").unwrap().unwrap());
+        assert!(!previewed.unwrap().execution_history.is_empty());
+    }
+
+    #[test]
+    fn test_delete_hides_entity_and_clones_share_log() {
+        let mut memory = MemoryStorage::new("default");
+        let entity = GenericEntity {
+            id: "ctx-1".to_string(),
+            entity_type: "context".to_string(),
+            agent: "default".to_string(),
+            timestamp: chrono::Utc::now(),
+            data: serde_json::json!({"title": "Keep me"}),
+        };
+        memory.store(&entity).unwrap();
+
+        let storage = DryRunStorage::new(memory.clone());
+        let mut clone = storage.clone();
+        clone.delete("ctx-1", "context").unwrap();
+
+        assert!(storage.get("ctx-1", "context").unwrap().is_none());
+        assert!(storage.list_ids("context").unwrap().is_empty());
+        assert!(memory.get("ctx-1", "context").unwrap().is_some());
+        assert_eq!(storage.summary()["context"].deletes, 1);
+    }
+
+    #[test]
+    fn test_store_runs_backend_validation() {
+        let dir = tempfile::tempdir().unwrap();
+        let git =
+            crate::storage::GitRefsStorage::new(dir.path().to_str().unwrap(), "default").unwrap();
+        let mut storage = DryRunStorage::new(git);
+
+        let task = crate::entities::Task::new(
+            "Invalid status".to_string(),
+            String::new(),
+            "default".to_string(),
+            crate::entities::TaskPriority::Low,
+            None,
+        );
+        let mut entity = task.to_generic();
+        entity.data["status"] = serde_json::json!("pending");
+
+        let err = storage.store(&entity).unwrap_err();
+        assert!(matches!(err, EngramError::Validation(_)), "{:?}", err);
+        assert!(storage.changes().is_empty());
+    }
+}
@@ -0,0 +1,393 @@
+//! Field-level encryption of sensitive entity data
+//!
+//! Opt-in through the `encryption` section of `engram.yaml`:
+//!
+//! ```yaml
+//! encryption:
+//!   fields:
+//!     - context.content
+//!     - reasoning.content
+//!   key_file: .engram/encryption.key   # optional; ENGRAM_ENCRYPTION_KEY wins
+//! ```
+//!
+//! The workspace key is 32 bytes, given base64- or hex-encoded in
+//! `ENGRAM_ENCRYPTION_KEY` or in the key file. Listed fields are encrypted
+//! with AES-256-GCM before an entity is written and decrypted when it is
+//! read, so callers only ever see plaintext. An encrypted value is stored as
+//! `enc:v1:<key fingerprint>:<base64 nonce+ciphertext+tag>`; the fingerprint
+//! names the key needed to read it. Reading a value without that key fails
+//! with an error naming the fingerprint.
+//!
+//! Text search decrypts like any other read, so it matches encrypted fields
+//! only while the key is available; the stored refs never contain the
+//! plaintext.
+
+use crate::config::Config;
+use crate::entities::GenericEntity;
+use crate::error::EngramError;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use openssl::symm::{decrypt_aead, encrypt_aead, Cipher};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+/// Environment variable holding the workspace encryption key
+pub const ENCRYPTION_KEY_ENV_VAR: &str = "ENGRAM_ENCRYPTION_KEY";
+
+/// Prefix marking an encrypted field value
+pub const ENCRYPTED_PREFIX: &str = "enc:v1:";
+
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+
+/// `encryption` section of `engram.yaml`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EncryptionConfig {
+    /// Fields to encrypt, as `<entity_type>.<field>`
+    #[serde(default)]
+    pub fields: Vec<String>,
+
+    /// Key file, relative to the workspace, used when the environment
+    /// variable is unset
+    #[serde(default)]
+    pub key_file: Option<PathBuf>,
+}
+
+impl EncryptionConfig {
+    /// Read the `encryption` section of `<workspace>/engram.yaml` (or
+    /// `engram.yml`). A missing file or section encrypts nothing.
+    pub fn load(workspace: &Path) -> Result<Self, EngramError> {
+        Self::validated(Config::load_section(workspace, "encryption")?)
+    }
+
+    pub fn from_yaml(content: &str) -> Result<Self, EngramError> {
+        Self::validated(Config::section_from_yaml(content, "encryption")?)
+    }
+
+    fn validated(config: Self) -> Result<Self, EngramError> {
+        for field in &config.fields {
+            if field
+                .split_once('.')
+                .is_none_or(|(t, f)| t.is_empty() || f.is_empty())
+            {
+                return Err(EngramError::Validation(format!(
+                    "encryption.fields entry '{}' must be <entity_type>.<field>",
+                    field
+                )));
+            }
+        }
+        Ok(config)
+    }
+
+    /// Fields of `entity_type` to encrypt
+    pub fn fields_for<'a>(&'a self, entity_type: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.fields.iter().filter_map(move |field| {
+            let (field_type, name) = field.split_once('.')?;
+            (field_type == entity_type).then_some(name)
+        })
+    }
+}
+
+/// A 256-bit workspace key
+#[derive(Clone)]
+pub struct WorkspaceKey {
+    bytes: [u8; KEY_LEN],
+    fingerprint: String,
+}
+
+impl std::fmt::Debug for WorkspaceKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WorkspaceKey")
+            .field("fingerprint", &self.fingerprint)
+            .finish()
+    }
+}
+
+impl WorkspaceKey {
+    pub fn from_bytes(bytes: [u8; KEY_LEN]) -> Self {
+        let fingerprint = hex::encode(&Sha256::digest(bytes)[..8]);
+        Self { bytes, fingerprint }
+    }
+
+    /// Parse a base64- or hex-encoded 32-byte key
+    pub fn parse(encoded: &str) -> Result<Self, EngramError> {
+        let encoded = encoded.trim();
+        let decoded = hex::decode(encoded)
+            .ok()
+            .filter(|bytes| bytes.len() == KEY_LEN)
+            .or_else(|| BASE64.decode(encoded).ok())
+            .ok_or_else(|| {
+                EngramError::Validation("Encryption key must be base64 or hex encoded".to_string())
+            })?;
+        let bytes: [u8; KEY_LEN] = decoded.try_into().map_err(|bytes: Vec<u8>| {
+            EngramError::Validation(format!(
+                "Encryption key must be {} bytes, got {}",
+                KEY_LEN,
+                bytes.len()
+            ))
+        })?;
+        Ok(Self::from_bytes(bytes))
+    }
+
+    /// A fresh random key
+    pub fn generate() -> Result<Self, EngramError> {
+        let mut bytes = [0u8; KEY_LEN];
+        openssl::rand::rand_bytes(&mut bytes).map_err(crypto_error)?;
+        Ok(Self::from_bytes(bytes))
+    }
+
+    /// The key from `ENGRAM_ENCRYPTION_KEY`, else from `key_file`
+    pub fn load(workspace: &Path, config: &EncryptionConfig) -> Result<Option<Self>, EngramError> {
+        if let Ok(encoded) = std::env::var(ENCRYPTION_KEY_ENV_VAR) {
+            if !encoded.trim().is_empty() {
+                return Self::parse(&encoded).map(Some);
+            }
+        }
+        let Some(key_file) = &config.key_file else {
+            return Ok(None);
+        };
+        let path = workspace.join(key_file);
+        if !path.exists() {
+            return Ok(None);
+        }
+        Self::parse(&std::fs::read_to_string(&path)?).map(Some)
+    }
+
+    /// Short hex identifier of the key, safe to print
+    pub fn fingerprint(&self) -> &str {
+        &self.fingerprint
+    }
+
+    /// Base64 form accepted by [`WorkspaceKey::parse`]
+    pub fn encode(&self) -> String {
+        BASE64.encode(self.bytes)
+    }
+
+    fn encrypt(&self, plaintext: &[u8]) -> Result<String, EngramError> {
+        let mut nonce = [0u8; NONCE_LEN];
+        openssl::rand::rand_bytes(&mut nonce).map_err(crypto_error)?;
+        let mut tag = [0u8; TAG_LEN];
+        let ciphertext = encrypt_aead(
+            Cipher::aes_256_gcm(),
+            &self.bytes,
+            Some(&nonce),
+            self.fingerprint.as_bytes(),
+            plaintext,
+            &mut tag,
+        )
+        .map_err(crypto_error)?;
+        let payload = [&nonce[..], &ciphertext, &tag].concat();
+        Ok(format!(
+            "{}{}:{}",
+            ENCRYPTED_PREFIX,
+            self.fingerprint,
+            BASE64.encode(payload)
+        ))
+    }
+
+    fn decrypt(&self, payload: &str) -> Option<Vec<u8>> {
+        let payload = BASE64.decode(payload).ok()?;
+        if payload.len() < NONCE_LEN + TAG_LEN {
+            return None;
+        }
+        let (nonce, rest) = payload.split_at(NONCE_LEN);
+        let (ciphertext, tag) = rest.split_at(rest.len() - TAG_LEN);
+        decrypt_aead(
+            Cipher::aes_256_gcm(),
+            &self.bytes,
+            Some(nonce),
+            self.fingerprint.as_bytes(),
+            ciphertext,
+            tag,
+        )
+        .ok()
+    }
+}
+
+fn crypto_error(error: openssl::error::ErrorStack) -> EngramError {
+    EngramError::InvalidOperation(format!("Encryption failed: {}", error))
+}
+
+/// Fingerprint and payload of an encrypted value, if `value` is one
+fn encrypted_parts(value: &Value) -> Option<(&str, &str)> {
+    value
+        .as_str()?
+        .strip_prefix(ENCRYPTED_PREFIX)?
+        .split_once(':')
+}
+
+/// Whether `value` is an encrypted field value
+pub fn is_encrypted(value: &Value) -> bool {
+    encrypted_parts(value).is_some()
+}
+
+/// Encryption settings and key of one workspace
+#[derive(Debug, Clone, Default)]
+pub struct WorkspaceEncryption {
+    pub config: EncryptionConfig,
+    pub key: Option<WorkspaceKey>,
+}
+
+impl WorkspaceEncryption {
+    pub fn new(config: EncryptionConfig, key: Option<WorkspaceKey>) -> Self {
+        Self { config, key }
+    }
+
+    /// Config and key of the workspace at `workspace`
+    pub fn load(workspace: &Path) -> Result<Self, EngramError> {
+        let config = EncryptionConfig::load(workspace)?;
+        let key = WorkspaceKey::load(workspace, &config)?;
+        Ok(Self { config, key })
+    }
+
+    /// Encrypt the configured fields of `entity` in place. Fails rather
+    /// than write plaintext when a configured field is present and no key
+    /// is available.
+    pub fn encrypt_entity(&self, entity: &mut GenericEntity) -> Result<(), EngramError> {
+        let entity_type = entity.entity_type.clone();
+        let Some(data) = entity.data.as_object_mut() else {
+            return Ok(());
+        };
+        for field in self.config.fields_for(&entity_type) {
+            let Some(value) = data.get_mut(field) else {
+                continue;
+            };
+            if value.is_null() || is_encrypted(value) {
+                continue;
+            }
+            let key = self.key.as_ref().ok_or_else(|| {
+                EngramError::InvalidOperation(format!(
+                    "{}.{} must be encrypted but no workspace key is available (set {} or encryption.key_file)",
+                    entity_type, field, ENCRYPTION_KEY_ENV_VAR
+                ))
+            })?;
+            let plaintext = serde_json::to_vec(value)?;
+            *value = Value::String(key.encrypt(&plaintext)?);
+        }
+        Ok(())
+    }
+
+    /// Decrypt every encrypted field of `entity` in place, whether or not
+    /// the field is still configured
+    pub fn decrypt_entity(&self, entity: &mut GenericEntity) -> Result<(), EngramError> {
+        let Some(data) = entity.data.as_object_mut() else {
+            return Ok(());
+        };
+        for (field, value) in data.iter_mut() {
+            let Some((fingerprint, payload)) = encrypted_parts(value) else {
+                continue;
+            };
+            let undecryptable = |reason: &str| {
+                EngramError::InvalidOperation(format!(
+                    "Cannot decrypt {}.{} of {}: {} (encrypted with key {})",
+                    entity.entity_type, field, entity.id, reason, fingerprint
+                ))
+            };
+            let key = match &self.key {
+                Some(key) if key.fingerprint() == fingerprint => key,
+                Some(key) => {
+                    return Err(undecryptable(&format!(
+                        "workspace key is {}",
+                        key.fingerprint()
+                    )))
+                }
+                None => {
+                    return Err(undecryptable(&format!(
+                        "no workspace key available, set {}",
+                        ENCRYPTION_KEY_ENV_VAR
+                    )))
+                }
+            };
+            let plaintext = key
+                .decrypt(payload)
+                .ok_or_else(|| undecryptable("ciphertext is corrupted"))?;
+            *value = serde_json::from_slice(&plaintext)
+                .map_err(|_| undecryptable("decrypted value is not JSON"))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use serde_json::json;
+
+    fn context(content: Value) -> GenericEntity {
+        GenericEntity {
+            id: "ctx-1".to_string(),
+            entity_type: "context".to_string(),
+            agent: "agent".to_string(),
+            timestamp: Utc::now(),
+            data: json!({"title": "Deploy", "content": content}),
+        }
+    }
+
+    fn encryption(key: Option<WorkspaceKey>) -> WorkspaceEncryption {
+        let config =
+            EncryptionConfig::from_yaml("encryption:\n  fields: [context.content]\n").unwrap();
+        WorkspaceEncryption::new(config, key)
+    }
+
+    #[test]
+    fn test_field_round_trip() {
+        let key = WorkspaceKey::generate().unwrap();
+        let encryption = encryption(Some(key.clone()));
+        let mut entity = context(json!("password=hunter2"));
+
+        encryption.encrypt_entity(&mut entity).unwrap();
+        let stored = entity.data["content"].as_str().unwrap().to_string();
+        assert!(stored.starts_with(&format!("{}{}:", ENCRYPTED_PREFIX, key.fingerprint())));
+        assert!(!stored.contains("hunter2"));
+        assert_eq!(entity.data["title"], "Deploy");
+
+        // Already-encrypted values are not wrapped twice
+        encryption.encrypt_entity(&mut entity).unwrap();
+        assert_eq!(entity.data["content"], stored);
+
+        encryption.decrypt_entity(&mut entity).unwrap();
+        assert_eq!(entity.data["content"], "password=hunter2");
+
+        // Non-string values keep their type
+        let mut entity = context(json!({"token": 42}));
+        encryption.encrypt_entity(&mut entity).unwrap();
+        encryption.decrypt_entity(&mut entity).unwrap();
+        assert_eq!(entity.data["content"], json!({"token": 42}));
+
+        let parsed = WorkspaceKey::parse(&key.encode()).unwrap();
+        assert_eq!(parsed.fingerprint(), key.fingerprint());
+    }
+
+    #[test]
+    fn test_wrong_or_missing_key_names_fingerprint() {
+        let key = WorkspaceKey::generate().unwrap();
+        let mut entity = context(json!("secret"));
+        encryption(Some(key.clone()))
+            .encrypt_entity(&mut entity)
+            .unwrap();
+
+        let other = WorkspaceKey::generate().unwrap();
+        let error = encryption(Some(other))
+            .decrypt_entity(&mut entity.clone())
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains(key.fingerprint()), "{}", error);
+
+        let error = encryption(None)
+            .decrypt_entity(&mut entity.clone())
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains(key.fingerprint()), "{}", error);
+
+        // Writing a configured field without a key is refused
+        assert!(encryption(None)
+            .encrypt_entity(&mut context(json!("secret")))
+            .is_err());
+
+        assert!(EncryptionConfig::from_yaml("encryption:\n  fields: [content]\n").is_err());
+        assert!(WorkspaceKey::parse("c2hvcnQ=").is_err());
+    }
+}
@@ -4,26 +4,40 @@
 //! eliminating the need for a separate .engram directory structure.
 //! Entities are stored as Git blobs and referenced by refs in the format:
 //! refs/engram/{entity_type}/{entity_id}
+//!
+//! Alongside each entity ref, `refs/engram/{entity_type}/by-time/...` names
+//! the entity's timestamp and agent so time-range and agent queries can skip
+//! non-matching blobs.
 
 #![allow(clippy::needless_borrows_for_generic_args)]
 
 use super::{
+    apply_entity_patch,
+    query::{apply_filter, fold_case, matches_filter, text_match, MatchTier, TextMatch},
     relationship_storage::{
         EntityPath, GraphAnalyzer, RelationshipIndex, RelationshipStats, RelationshipStorage,
         TraversalAlgorithm,
     },
-    GitCommit, MemoryEntity, QueryFilter, QueryResult, SortOrder, Storage, StorageStats,
+    workspace_lock::{lock_timeout_from_env, WorkspaceLock},
+    GitCommit, MemoryEntity, QueryFilter, QueryResult, Storage, StorageStats, TimeRange,
+    WorkspaceEncryption,
+};
+use crate::entities::{
+    EntityRegistry, EntityRelationship, GenericEntity, JsonPatchOp, RelationshipFilter,
+    CREATED_BY_IDENTITY_FIELD,
 };
-use crate::entities::{EntityRegistry, EntityRelationship, GenericEntity, RelationshipFilter};
 use crate::error::{EngramError, StorageError};
 use chrono::Utc;
 use git2::Repository;
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use sha2::{Digest, Sha512};
 use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 /// Git refs-based storage for entities
 ///
@@ -35,11 +49,20 @@ use std::sync::{Arc, Mutex};
 pub struct GitRefsStorage {
     repository: Arc<Mutex<Repository>>,
     workspace_path: PathBuf,
-    #[allow(dead_code)]
     entity_registry: Arc<EntityRegistry>,
     current_agent: String,
     relationship_index: Arc<Mutex<RelationshipIndex>>,
     pub project_id: String,
+    lock_timeout: Duration,
+    strict_entities: bool,
+    /// Field encryption applied on write and undone on read
+    encryption: Arc<WorkspaceEncryption>,
+    /// Entity blobs deserialized so far, shared between clones
+    blob_reads: Arc<AtomicUsize>,
+    /// Object database handles opened for blob reads, shared between clones
+    odb_handles: Arc<AtomicUsize>,
+    /// Pool for parallel blob reads; `None` uses the global rayon pool
+    read_pool: Option<Arc<rayon::ThreadPool>>,
 }
 
 impl std::fmt::Debug for GitRefsStorage {
@@ -61,6 +84,12 @@ impl Clone for GitRefsStorage {
             current_agent: self.current_agent.clone(),
             relationship_index: self.relationship_index.clone(),
             project_id: self.project_id.clone(),
+            lock_timeout: self.lock_timeout,
+            strict_entities: self.strict_entities,
+            encryption: self.encryption.clone(),
+            blob_reads: self.blob_reads.clone(),
+            odb_handles: self.odb_handles.clone(),
+            read_pool: self.read_pool.clone(),
         }
     }
 }
@@ -118,15 +147,8 @@ fn derive_project_id(repo: &git2::Repository) -> Result<String, EngramError> {
     Ok(hex::encode(digest)) // 128 hex chars
 }
 
-/// Ensure `refs/engram/config/workspace` exists in `repo`.
-///
-/// * If the ref already exists, read the JSON blob and return the stored `project_id`.
-/// * If the ref does not exist, derive a new `project_id`, write the JSON blob, create
-///   the ref, and return the new `project_id`.
-fn ensure_workspace_ref(
-    repo: &git2::Repository,
-    workspace_path: &std::path::Path,
-) -> Result<String, EngramError> {
+/// Read the `project_id` stored in `refs/engram/config/workspace`, if the ref exists.
+fn read_workspace_ref(repo: &git2::Repository) -> Result<Option<String>, EngramError> {
     match repo.find_reference("refs/engram/config/workspace") {
         Ok(r) => {
             let oid = r.target().ok_or_else(|| {
@@ -145,32 +167,9 @@ fn ensure_workspace_ref(
                 .and_then(|p| p.as_str())
                 .ok_or_else(|| EngramError::Git("workspace JSON missing project_id field".into()))?
                 .to_string();
-            Ok(pid)
-        }
-        Err(e) if e.code() == git2::ErrorCode::NotFound => {
-            let pid = derive_project_id(repo)?;
-            let json = serde_json::json!({
-                "project_id": &pid,
-                "name": workspace_path.to_string_lossy().as_ref()
-            })
-            .to_string();
-            let blob_oid = repo
-                .blob(json.as_bytes())
-                .map_err(|e| EngramError::Git(format!("Failed to create workspace blob: {}", e)))?;
-            repo.reference(
-                "refs/engram/config/workspace",
-                blob_oid,
-                true,
-                "engram: init workspace config",
-            )
-            .map_err(|e| {
-                EngramError::Git(format!(
-                    "Failed to write refs/engram/config/workspace: {}",
-                    e
-                ))
-            })?;
-            Ok(pid)
+            Ok(Some(pid))
         }
+        Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
         Err(e) => Err(EngramError::Git(format!(
             "Failed to read refs/engram/config/workspace: {}",
             e
@@ -178,54 +177,233 @@ fn ensure_workspace_ref(
     }
 }
 
-/// Return the next monotonic version number for a versioned sidecar ref.
+/// Ensure `refs/engram/config/workspace` exists in `repo`.
 ///
-/// Scans all refs matching `refs/engram/<entity_type>/v*/<entity_id>`, extracts
-/// the numeric version segment, and returns `max + 1`.  Returns `1` if no
-/// existing versioned refs are found for this entity.
-fn next_version(repo: &git2::Repository, entity_type: &str, entity_id: &str) -> u64 {
-    let prefix = format!("refs/engram/{}/v", entity_type);
-    let suffix = format!("/{}", entity_id);
-
-    let all_refs = match repo.references() {
-        Ok(r) => r,
-        Err(_) => return 1,
-    };
+/// * If the ref already exists, read the JSON blob and return the stored `project_id`.
+/// * If the ref does not exist, derive a new `project_id`, write the JSON blob, create
+///   the ref, and return the new `project_id`.
+fn ensure_workspace_ref(
+    repo: &git2::Repository,
+    workspace_path: &std::path::Path,
+) -> Result<String, EngramError> {
+    if let Some(pid) = read_workspace_ref(repo)? {
+        return Ok(pid);
+    }
 
-    let mut max_n: u64 = 0;
-    for r_result in all_refs {
-        let r = match r_result {
-            Ok(r) => r,
-            Err(_) => continue,
-        };
-        if let Some(name) = r.name() {
-            if name.starts_with(&prefix) && name.ends_with(&suffix) {
-                // Extract the middle segment between prefix and suffix
-                let after_prefix = &name[prefix.len()..];
-                let middle = &after_prefix[..after_prefix.len() - suffix.len()];
-                if let Ok(n) = middle.parse::<u64>() {
-                    if n > max_n {
-                        max_n = n;
-                    }
+    let pid = derive_project_id(repo)?;
+    let json = serde_json::json!({
+        "project_id": &pid,
+        "name": workspace_path.to_string_lossy().as_ref()
+    })
+    .to_string();
+    let blob_oid = repo
+        .blob(json.as_bytes())
+        .map_err(|e| EngramError::Git(format!("Failed to create workspace blob: {}", e)))?;
+    repo.reference(
+        "refs/engram/config/workspace",
+        blob_oid,
+        true,
+        "engram: init workspace config",
+    )
+    .map_err(|e| {
+        EngramError::Git(format!(
+            "Failed to write refs/engram/config/workspace: {}",
+            e
+        ))
+    })?;
+    Ok(pid)
+}
+
+/// Ref segment under `refs/engram/<entity_type>/` holding the time index.
+///
+/// Every entity has one index ref,
+/// `refs/engram/<entity_type>/by-time/<timestamp_ms>/a<agent_hex>/<entity_id>`,
+/// pointing at the same blob as its entity ref. Time-range and agent filters
+/// are answered from these names before any blob is read.
+const TIME_INDEX_SEGMENT: &str = "by-time";
+
+/// Blobs read before a scan is split across threads; smaller batches are
+/// read serially, where opening per-thread repository handles costs more
+/// than it saves
+const PARALLEL_READ_THRESHOLD: usize = 256;
+
+/// Namespace under `refs/engram/` holding entity refs quarantined by
+/// [`GitRefsStorage::fsck`], as `refs/engram/corrupt/<entity_type>/<entity_id>`.
+/// Quarantined blobs stay reachable for inspection but are never read as entities.
+pub const QUARANTINE_NAMESPACE: &str = "corrupt";
+
+/// Time index ref name for an entity
+fn time_index_ref(entity: &GenericEntity) -> String {
+    format!(
+        "refs/engram/{}/{}/{}/a{}/{}",
+        entity.entity_type,
+        TIME_INDEX_SEGMENT,
+        entity.timestamp.timestamp_millis(),
+        hex::encode(&entity.agent),
+        entity.id
+    )
+}
+
+/// Timestamp and agent decoded from a time index ref name
+struct TimeIndexEntry {
+    timestamp_ms: i64,
+    agent: String,
+    entity_id: String,
+}
+
+impl TimeIndexEntry {
+    /// Parse the part of an index ref name after `by-time/`
+    fn parse(key: &str) -> Option<Self> {
+        let mut parts = key.splitn(3, '/');
+        let timestamp_ms = parts.next()?.parse().ok()?;
+        let agent = hex::decode(parts.next()?.strip_prefix('a')?).ok()?;
+        Some(Self {
+            timestamp_ms,
+            agent: String::from_utf8(agent).ok()?,
+            entity_id: parts.next()?.to_string(),
+        })
+    }
+
+    /// Whether the entity can match the filter's agent and time range.
+    /// Timestamps are truncated to milliseconds, so this may admit entities
+    /// just outside the range; the full filter runs on the loaded entity.
+    fn may_match(&self, filter: &QueryFilter) -> bool {
+        if filter
+            .agent
+            .as_ref()
+            .is_some_and(|agent| *agent != self.agent)
+        {
+            return false;
+        }
+        match &filter.time_range {
+            Some(range) => {
+                self.timestamp_ms >= range.start.timestamp_millis()
+                    && self.timestamp_ms <= range.end.timestamp_millis()
+            }
+            None => true,
+        }
+    }
+
+    /// Whether the entity certainly matches the filter's agent and time
+    /// range: [`may_match`](Self::may_match) away from the millisecond
+    /// boundaries of the range
+    fn surely_matches(&self, filter: &QueryFilter) -> bool {
+        self.may_match(filter)
+            && filter.time_range.as_ref().is_none_or(|range| {
+                self.timestamp_ms > range.start.timestamp_millis()
+                    && self.timestamp_ms < range.end.timestamp_millis()
+            })
+    }
+}
+
+/// Entities of one type sorted by what their time index refs say about a
+/// filter's agent and time range
+#[derive(Default)]
+struct IndexedCandidates {
+    /// Certainly match
+    matching: Vec<String>,
+    /// Unindexed, or on a millisecond boundary of the time range
+    unverified: Vec<String>,
+}
+
+/// (entity type, entity ID)
+type EntityKey = (String, String);
+
+/// Version sidecars and time index refs already written, from one scan of
+/// `refs/engram/*`, so a batch of stores does not rescan per entity
+#[derive(Default)]
+struct ExistingRefs {
+    versions: HashMap<EntityKey, u64>,
+    time_index: HashMap<EntityKey, Vec<String>>,
+}
+
+impl ExistingRefs {
+    fn scan(repo: &git2::Repository) -> Result<Self, EngramError> {
+        let refs = repo
+            .references_glob("refs/engram/*")
+            .map_err(|e| EngramError::Git(format!("Failed to list references: {}", e)))?;
+
+        let mut existing = Self::default();
+        for reference in refs {
+            let reference = reference
+                .map_err(|e| EngramError::Git(format!("Failed to read reference: {}", e)))?;
+            let Some(name) = reference.name() else {
+                continue;
+            };
+            let Some((entity_type, rest)) = name
+                .strip_prefix("refs/engram/")
+                .and_then(|rest| rest.split_once('/'))
+            else {
+                continue;
+            };
+            if entity_type == "config"
+                || entity_type == "remote"
+                || entity_type == QUARANTINE_NAMESPACE
+            {
+                continue;
+            }
+
+            if let Some(key) = rest
+                .strip_prefix(TIME_INDEX_SEGMENT)
+                .and_then(|key| key.strip_prefix('/'))
+            {
+                if let Some(entry) = TimeIndexEntry::parse(key) {
+                    existing
+                        .time_index
+                        .entry((entity_type.to_string(), entry.entity_id))
+                        .or_default()
+                        .push(name.to_string());
                 }
+            } else if let Some((version, entity_id)) = rest.split_once('/') {
+                // Versioned sidecar: refs/engram/<type>/v<N>/<entity_id>
+                let Some(n) = version
+                    .strip_prefix('v')
+                    .and_then(|n| n.parse::<u64>().ok())
+                else {
+                    continue;
+                };
+                let max = existing
+                    .versions
+                    .entry((entity_type.to_string(), entity_id.to_string()))
+                    .or_insert(0);
+                *max = (*max).max(n);
             }
         }
+        Ok(existing)
+    }
+
+    /// Next monotonic version for an entity's sidecar, starting at 1
+    fn next_version(&mut self, entity: &GenericEntity) -> u64 {
+        let n = self
+            .versions
+            .entry((entity.entity_type.clone(), entity.id.clone()))
+            .or_insert(0);
+        *n += 1;
+        *n
     }
 
-    max_n + 1
+    /// Time index refs currently stored for an entity
+    fn take_time_index(&mut self, entity: &GenericEntity) -> Vec<String> {
+        self.time_index
+            .remove(&(entity.entity_type.clone(), entity.id.clone()))
+            .unwrap_or_default()
+    }
 }
 
 /// Write an immutable versioned sidecar ref for an entity.
 ///
 /// The sidecar is written to `refs/engram/<entity_type>/v<N>/<entity_id>` with
-/// `force = false` so that each version snapshot is never overwritten.
+/// `force = false` so that each version snapshot is never overwritten. It
+/// embeds the stored entity (`snapshot`, encrypted fields included) so that
+/// old versions stay readable after the entity ref moves on.
 fn write_version_sidecar(
     repo: &git2::Repository,
     entity: &GenericEntity,
     project_id: &str,
+    n: u64,
+    author: Option<&GitIdentity>,
+    snapshot: &MemoryEntity,
 ) -> Result<(), EngramError> {
-    let n = next_version(repo, &entity.entity_type, &entity.id);
-
     let json = serde_json::json!({
         "project_id": project_id,
         "entity_type": entity.entity_type,
@@ -233,6 +411,8 @@ fn write_version_sidecar(
         "version": n,
         "created_at": Utc::now().to_rfc3339(),
         "agent": entity.agent,
+        "author": author,
+        "entity": snapshot,
     });
 
     let blob_oid = repo
@@ -251,6 +431,144 @@ fn write_version_sidecar(
     Ok(())
 }
 
+/// Git author identity (`user.name` / `user.email`) a write is made under
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GitIdentity {
+    pub name: String,
+    pub email: String,
+}
+
+impl GitIdentity {
+    /// The repository's effective identity, or `None` when git has no
+    /// `user.name` / `user.email` configured for it
+    pub fn from_repo(repo: &git2::Repository) -> Option<Self> {
+        let signature = repo.signature().ok()?;
+        Some(Self {
+            name: signature.name()?.to_string(),
+            email: signature.email()?.to_string(),
+        })
+    }
+
+    /// Identity of the repository containing `path`, if any
+    pub fn discover(path: &Path) -> Option<Self> {
+        Self::from_repo(&Repository::discover(path).ok()?)
+    }
+}
+
+impl std::fmt::Display for GitIdentity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} <{}>", self.name, self.email)
+    }
+}
+
+/// One stored version of an entity, read from its version sidecar
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntityVersion {
+    pub version: u64,
+    pub created_at: String,
+    pub agent: String,
+    /// Git identity the version was written under; unset for versions
+    /// written before identities were recorded or without one configured
+    #[serde(default)]
+    pub author: Option<GitIdentity>,
+}
+
+/// Object database of a repository
+fn open_odb(repo: &Repository) -> Result<git2::Odb<'_>, EngramError> {
+    repo.odb()
+        .map_err(|e| EngramError::Git(format!("Failed to open object database: {}", e)))
+}
+
+/// Delete a ref if it exists
+fn delete_ref(repo: &git2::Repository, ref_name: &str) -> Result<(), EngramError> {
+    if let Ok(mut reference) = repo.find_reference(ref_name) {
+        reference
+            .delete()
+            .map_err(|e| EngramError::Git(format!("Failed to delete ref: {}", e)))?;
+    }
+    Ok(())
+}
+
+/// Delete an entity ref and its time index refs, keeping version sidecars
+fn delete_entity_refs(
+    repo: &git2::Repository,
+    entity_type: &str,
+    entity_id: &str,
+) -> Result<(), EngramError> {
+    delete_ref(repo, &format!("refs/engram/{}/{}", entity_type, entity_id))?;
+
+    let index_prefix = format!("refs/engram/{}/{}/", entity_type, TIME_INDEX_SEGMENT);
+    let index_refs: Vec<String> = repo
+        .references_glob(&format!("{}*", index_prefix))
+        .map_err(|e| EngramError::Git(format!("Failed to list references: {}", e)))?
+        .filter_map(|reference| reference.ok()?.name().map(str::to_string))
+        .filter(|name| {
+            TimeIndexEntry::parse(&name[index_prefix.len()..])
+                .is_some_and(|entry| entry.entity_id == entity_id)
+        })
+        .collect();
+    for index_ref in index_refs {
+        delete_ref(repo, &index_ref)?;
+    }
+    Ok(())
+}
+
+/// An entity ref whose blob cannot be served as an entity
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CorruptEntity {
+    pub ref_name: String,
+    pub entity_type: String,
+    pub entity_id: String,
+    pub problem: String,
+    /// Ref the blob was moved to by `--repair`; unset when it was not
+    /// repaired, or when the blob itself is missing and the ref was dropped
+    pub quarantined_to: Option<String>,
+}
+
+/// Result of [`GitRefsStorage::fsck`]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FsckReport {
+    /// Entity refs examined
+    pub checked: usize,
+    pub corrupt: Vec<CorruptEntity>,
+    /// Whether corrupt entries were moved out of the entity namespace
+    pub repaired: bool,
+}
+
+impl FsckReport {
+    pub fn is_clean(&self) -> bool {
+        self.corrupt.is_empty()
+    }
+}
+
+/// Carry `created_by_identity` over from the stored version, or stamp
+/// `author` when the entity is new. Entities whose data is not an object
+/// are left alone.
+fn stamp_created_by_identity(
+    entity: &mut GenericEntity,
+    previous: Option<&GenericEntity>,
+    author: Option<&GitIdentity>,
+) {
+    let identity = match previous {
+        Some(previous) => previous.data.get(CREATED_BY_IDENTITY_FIELD).cloned(),
+        None => author.map(|author| Value::String(author.to_string())),
+    };
+    let (Some(identity), Some(data)) = (identity, entity.data.as_object_mut()) else {
+        return;
+    };
+    data.entry(CREATED_BY_IDENTITY_FIELD.to_string())
+        .or_insert(identity);
+}
+
+/// Environment variable enabling `strict_entities`: unregistered entity types are rejected on store
+pub const STRICT_ENTITIES_ENV_VAR: &str = "ENGRAM_STRICT_ENTITIES";
+
+fn strict_entities_from_env() -> bool {
+    std::env::var(STRICT_ENTITIES_ENV_VAR)
+        .map(|v| matches!(v.trim().to_lowercase().as_str(), "1" | "true" | "yes"))
+        .unwrap_or(false)
+}
+
 impl GitRefsStorage {
     /// Create new Git refs storage instance
     pub fn new(workspace_path: &str, agent: &str) -> Result<Self, EngramError> {
@@ -264,35 +582,60 @@ impl GitRefsStorage {
 
         let project_id = ensure_workspace_ref(&repository, &workspace_path)
             .map_err(|e| EngramError::Git(format!("Failed to ensure workspace ref: {}", e)))?;
+        let encryption = WorkspaceEncryption::load(&workspace_path)?;
 
-        let mut registry = EntityRegistry::new();
-        registry.register::<crate::entities::Task>();
-        registry.register::<crate::entities::Context>();
-        registry.register::<crate::entities::Reasoning>();
-        registry.register::<crate::entities::Knowledge>();
-        registry.register::<crate::entities::Session>();
-        registry.register::<crate::entities::Compliance>();
-        registry.register::<crate::entities::EntityRelationship>();
-        registry.register::<crate::entities::Theory>();
-        registry.register::<crate::entities::StateReflection>();
-        registry.register::<crate::entities::Rule>();
-        registry.register::<crate::entities::Standard>();
-        registry.register::<crate::entities::ADR>();
-        registry.register::<crate::entities::Workflow>();
-        registry.register::<crate::entities::WorkflowInstance>();
-        registry.register::<crate::entities::AgentSandbox>();
-        registry.register::<crate::entities::EscalationRequest>();
-        registry.register::<crate::entities::ExecutionResult>();
-        registry.register::<crate::entities::ProgressiveGateConfig>();
-        registry.register::<crate::entities::DocFragment>();
+        let mut storage = GitRefsStorage {
+            repository: Arc::new(Mutex::new(repository)),
+            workspace_path,
+            entity_registry: Arc::new(EntityRegistry::with_builtin_types()),
+            current_agent: agent.to_string(),
+            relationship_index: Arc::new(Mutex::new(RelationshipIndex::new())),
+            project_id,
+            lock_timeout: lock_timeout_from_env(),
+            strict_entities: strict_entities_from_env(),
+            encryption: Arc::new(encryption),
+            blob_reads: Arc::new(AtomicUsize::new(0)),
+            odb_handles: Arc::new(AtomicUsize::new(0)),
+            read_pool: None,
+        };
+
+        storage.index_unindexed_entities()?;
+        storage.rebuild_relationship_index()?;
+
+        Ok(storage)
+    }
+
+    /// Open an existing workspace without writing to it
+    ///
+    /// Unlike [`GitRefsStorage::new`], the repository is never initialised and
+    /// the workspace config ref is never created. A workspace that has no
+    /// config ref yet gets an empty `project_id`, which is only needed for
+    /// writes.
+    pub fn open_read_only(workspace_path: &str, agent: &str) -> Result<Self, EngramError> {
+        let workspace_path = PathBuf::from(workspace_path);
+        let repository = Repository::open(&workspace_path).map_err(|e| {
+            EngramError::Git(format!(
+                "Cannot open {} read-only: {}",
+                workspace_path.display(),
+                e
+            ))
+        })?;
+        let project_id = read_workspace_ref(&repository)?.unwrap_or_default();
+        let encryption = WorkspaceEncryption::load(&workspace_path)?;
 
         let mut storage = GitRefsStorage {
             repository: Arc::new(Mutex::new(repository)),
             workspace_path,
-            entity_registry: Arc::new(registry),
+            entity_registry: Arc::new(EntityRegistry::with_builtin_types()),
             current_agent: agent.to_string(),
             relationship_index: Arc::new(Mutex::new(RelationshipIndex::new())),
             project_id,
+            lock_timeout: lock_timeout_from_env(),
+            strict_entities: strict_entities_from_env(),
+            encryption: Arc::new(encryption),
+            blob_reads: Arc::new(AtomicUsize::new(0)),
+            odb_handles: Arc::new(AtomicUsize::new(0)),
+            read_pool: None,
         };
 
         storage.rebuild_relationship_index()?;
@@ -300,171 +643,745 @@ impl GitRefsStorage {
         Ok(storage)
     }
 
+    /// Root of the workspace repository
+    pub fn workspace_path(&self) -> &Path {
+        &self.workspace_path
+    }
+
+    /// Git identity writes are currently attributed to, if configured
+    pub fn author_identity(&self) -> Option<GitIdentity> {
+        let repo = self.repository.lock().ok()?;
+        GitIdentity::from_repo(&repo)
+    }
+
+    /// Every stored version of an entity, oldest first, from its version
+    /// sidecars
+    pub fn entity_history(
+        &self,
+        entity_type: &str,
+        entity_id: &str,
+    ) -> Result<Vec<EntityVersion>, EngramError> {
+        let repo = self.repository.lock().map_err(|_| {
+            EngramError::Storage(StorageError::InvalidState(
+                "Repository lock failed".to_string(),
+            ))
+        })?;
+        let glob = format!("refs/engram/{}/v*/{}", entity_type, entity_id);
+        let references = repo
+            .references_glob(&glob)
+            .map_err(|e| EngramError::Git(format!("Failed to list version refs: {}", e)))?;
+
+        let mut versions = Vec::new();
+        for reference in references {
+            let reference = reference
+                .map_err(|e| EngramError::Git(format!("Failed to read version ref: {}", e)))?;
+            let Some(blob) = reference.target().and_then(|oid| repo.find_blob(oid).ok()) else {
+                continue;
+            };
+            if let Ok(version) = serde_json::from_slice::<EntityVersion>(blob.content()) {
+                versions.push(version);
+            }
+        }
+        versions.sort_by_key(|version| version.version);
+        Ok(versions)
+    }
+
+    /// Entity content as stored at `version`, from its version sidecar
+    ///
+    /// `None` when the version does not exist. Sidecars written before
+    /// snapshots were recorded carry no content and are an error.
+    pub fn entity_version(
+        &self,
+        entity_type: &str,
+        entity_id: &str,
+        version: u64,
+    ) -> Result<Option<GenericEntity>, EngramError> {
+        let repo = self.repository.lock().map_err(|_| {
+            EngramError::Storage(StorageError::InvalidState(
+                "Repository lock failed".to_string(),
+            ))
+        })?;
+        let ref_name = format!("refs/engram/{}/v{}/{}", entity_type, version, entity_id);
+        let Ok(oid) = repo.refname_to_id(&ref_name) else {
+            return Ok(None);
+        };
+        let blob = repo
+            .find_blob(oid)
+            .map_err(|e| EngramError::Git(format!("Failed to find blob {}: {}", oid, e)))?;
+        let mut sidecar: Value = serde_json::from_slice(blob.content())
+            .map_err(|e| EngramError::Deserialization(e.to_string()))?;
+        let snapshot = match sidecar.get_mut("entity").map(Value::take) {
+            Some(snapshot) if !snapshot.is_null() => snapshot,
+            _ => {
+                return Err(EngramError::InvalidOperation(format!(
+                    "Version {} of {} {} was stored without a content snapshot",
+                    version, entity_type, entity_id
+                )))
+            }
+        };
+        self.read_stored_entity(&serde_json::to_vec(&snapshot)?)
+            .map(Some)
+    }
+
+    /// Set how long writes wait for the workspace lock before failing
+    pub fn with_lock_timeout(mut self, timeout: Duration) -> Self {
+        self.lock_timeout = timeout;
+        self
+    }
+
+    /// Reject entity types that are not registered instead of storing them unchecked
+    pub fn with_strict_entities(mut self, strict: bool) -> Self {
+        self.strict_entities = strict;
+        self
+    }
+
+    /// Read blobs of large scans on a dedicated pool of `threads` threads
+    /// instead of the global rayon pool; `1` reads serially
+    pub fn with_read_threads(mut self, threads: usize) -> Result<Self, EngramError> {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .map_err(|e| {
+                EngramError::Storage(StorageError::InvalidState(format!(
+                    "Failed to start read pool: {}",
+                    e
+                )))
+            })?;
+        self.read_pool = Some(Arc::new(pool));
+        Ok(self)
+    }
+
+    /// Replace the encryption settings loaded from the workspace
+    pub fn with_encryption(mut self, encryption: WorkspaceEncryption) -> Self {
+        self.encryption = Arc::new(encryption);
+        self
+    }
+
+    /// Deserialize a stored entity blob and decrypt its encrypted fields
+    fn read_stored_entity(&self, content: &[u8]) -> Result<GenericEntity, EngramError> {
+        self.blob_reads.fetch_add(1, Ordering::Relaxed);
+        let mut entity = Self::parse_entity_blob(content)?;
+        self.encryption.decrypt_entity(&mut entity)?;
+        Ok(entity)
+    }
+
+    /// Take the workspace write lock; reads never call this
+    fn lock_workspace(&self) -> Result<WorkspaceLock, EngramError> {
+        WorkspaceLock::acquire(&self.workspace_path, self.lock_timeout)
+    }
+
     /// Get ref name for an entity
     fn get_entity_ref(&self, entity_type: &str, entity_id: &str) -> String {
         format!("refs/engram/{}/{}", entity_type, entity_id)
     }
 
-    /// Store entity as Git blob and create ref
-    fn store_entity_as_ref(&self, entity: &GenericEntity) -> Result<(), EngramError> {
+    /// Store entities as Git blobs with their refs, time index refs and
+    /// version sidecars, holding the workspace lock once for the batch.
+    /// Each entity's modification metadata is stamped against the stored
+    /// version with the storage's agent.
+    fn store_entities_as_refs(&self, entities: &[GenericEntity]) -> Result<(), EngramError> {
+        let _lock = self.lock_workspace()?;
         let repo = self.repository.lock().map_err(|_| {
             EngramError::Storage(StorageError::InvalidState(
                 "Repository lock failed".to_string(),
             ))
         })?;
+        self.write_entity_refs(&repo, entities)
+    }
 
-        let data_map = match &entity.data {
-            Value::Object(map) => map.iter().map(|(k, v)| (k.clone(), v.clone())).collect(),
-            _ => {
-                let mut map = HashMap::new();
-                map.insert("raw_data".to_string(), entity.data.clone());
-                map
+    /// Add a just-stored relationship entity to the in-memory relationship index
+    fn index_stored_relationship(&self, entity: &GenericEntity) -> Result<(), EngramError> {
+        if entity.entity_type == "relationship" {
+            if let Ok(relationship) =
+                serde_json::from_value::<EntityRelationship>(entity.data.clone())
+            {
+                let mut index = self.relationship_index.lock().map_err(|_| {
+                    EngramError::Storage(StorageError::InvalidState(
+                        "Index lock failed".to_string(),
+                    ))
+                })?;
+                index.add_relationship(&relationship);
             }
-        };
+        }
 
-        let memory_entity = MemoryEntity::new(
-            entity.id.clone(),
-            entity.entity_type.clone(),
-            entity.agent.clone(),
-            entity.timestamp,
-            data_map,
-        );
+        Ok(())
+    }
 
-        let json_content = serde_json::to_string_pretty(&memory_entity)?;
+    /// Body of [`Self::store_entities_as_refs`]; the caller holds the
+    /// workspace lock and the repository mutex
+    fn write_entity_refs(
+        &self,
+        repo: &Repository,
+        entities: &[GenericEntity],
+    ) -> Result<(), EngramError> {
+        let mut existing = ExistingRefs::scan(repo)?;
+        let author = GitIdentity::from_repo(repo);
 
-        let blob_oid = repo
-            .blob(json_content.as_bytes())
-            .map_err(|e| EngramError::Git(format!("Failed to create blob: {}", e)))?;
+        for entity in entities {
+            let ref_name = self.get_entity_ref(&entity.entity_type, &entity.id);
+            let previous = repo
+                .refname_to_id(&ref_name)
+                .ok()
+                .and_then(|oid| repo.find_blob(oid).ok())
+                .and_then(|blob| Self::parse_entity_blob(blob.content()).ok());
+            let mut entity = entity.clone();
+            entity.record_modification(&self.current_agent, previous.as_ref());
+            stamp_created_by_identity(&mut entity, previous.as_ref(), author.as_ref());
+            self.encryption.encrypt_entity(&mut entity)?;
+            let entity = &entity;
+
+            let data_map = match &entity.data {
+                Value::Object(map) => map.iter().map(|(k, v)| (k.clone(), v.clone())).collect(),
+                _ => {
+                    let mut map = HashMap::new();
+                    map.insert("raw_data".to_string(), entity.data.clone());
+                    map
+                }
+            };
 
-        let ref_name = self.get_entity_ref(&entity.entity_type, &entity.id);
-        repo.reference(
-            &ref_name,
-            blob_oid,
-            true,
-            &format!("Update {} {}", entity.entity_type, entity.id),
-        )
-        .map_err(|e| EngramError::Git(format!("Failed to create ref: {}", e)))?;
+            let memory_entity = MemoryEntity::new(
+                entity.id.clone(),
+                entity.entity_type.clone(),
+                entity.agent.clone(),
+                entity.timestamp,
+                data_map,
+            );
+
+            let json_content = serde_json::to_string_pretty(&memory_entity)?;
+
+            let blob_oid = repo
+                .blob(json_content.as_bytes())
+                .map_err(|e| EngramError::Git(format!("Failed to create blob: {}", e)))?;
+
+            repo.reference(
+                &ref_name,
+                blob_oid,
+                true,
+                &format!("Update {} {}", entity.entity_type, entity.id),
+            )
+            .map_err(|e| EngramError::Git(format!("Failed to create ref: {}", e)))?;
 
-        write_version_sidecar(&repo, entity, &self.project_id)?;
+            let index_ref = time_index_ref(entity);
+            for stale in existing.take_time_index(entity) {
+                if stale != index_ref {
+                    delete_ref(repo, &stale)?;
+                }
+            }
+            repo.reference(
+                &index_ref,
+                blob_oid,
+                true,
+                &format!("Index {} {}", entity.entity_type, entity.id),
+            )
+            .map_err(|e| EngramError::Git(format!("Failed to create index ref: {}", e)))?;
+
+            let version = existing.next_version(entity);
+            write_version_sidecar(
+                repo,
+                entity,
+                &self.project_id,
+                version,
+                author.as_ref(),
+                &memory_entity,
+            )?;
+        }
 
         Ok(())
     }
 
-    /// Load entity from Git ref, supporting short ID lookup
-    fn load_entity_from_ref(
+    /// Write time index refs for entities stored before the index existed
+    /// (or fetched without one). Only reads the blobs of unindexed entities.
+    fn index_unindexed_entities(&self) -> Result<(), EngramError> {
+        let unindexed = {
+            let repo = self.repository.lock().map_err(|_| {
+                EngramError::Storage(StorageError::InvalidState(
+                    "Repository lock failed".to_string(),
+                ))
+            })?;
+            let existing = ExistingRefs::scan(&repo)?;
+            let mut unindexed = Vec::new();
+            for ((entity_type, entity_id), _) in Self::entity_blob_refs(&repo)? {
+                if !existing
+                    .time_index
+                    .contains_key(&(entity_type.clone(), entity_id.clone()))
+                {
+                    unindexed.push(self.get_entity_ref(&entity_type, &entity_id));
+                }
+            }
+            unindexed
+        };
+        if unindexed.is_empty() {
+            return Ok(());
+        }
+
+        let _lock = self.lock_workspace()?;
+        let repo = self.repository.lock().map_err(|_| {
+            EngramError::Storage(StorageError::InvalidState(
+                "Repository lock failed".to_string(),
+            ))
+        })?;
+        for ref_name in unindexed {
+            // Re-resolve under the lock in case the entity changed meanwhile
+            let Ok(oid) = repo.refname_to_id(&ref_name) else {
+                continue;
+            };
+            let Ok(blob) = repo.find_blob(oid) else {
+                continue;
+            };
+            // Entities that fail to parse are reported by `consistency_check`
+            let Ok(entity) = Self::parse_entity_blob(blob.content()) else {
+                continue;
+            };
+            repo.reference(
+                &time_index_ref(&entity),
+                oid,
+                true,
+                &format!("Index {} {}", entity.entity_type, entity.id),
+            )
+            .map_err(|e| EngramError::Git(format!("Failed to create index ref: {}", e)))?;
+        }
+        Ok(())
+    }
+
+    /// IDs of entities of `entity_type` that may match the filter's agent and
+    /// time range, judged from time index ref names without reading blobs.
+    /// Only an index ref pointing at the entity's current blob, which `store`
+    /// wrote after serializing it, is trusted; entities without one are
+    /// always included as unverified.
+    fn indexed_candidates(
         &self,
         entity_type: &str,
+        filter: &QueryFilter,
+    ) -> Result<IndexedCandidates, EngramError> {
+        let repo = self.repository.lock().map_err(|_| {
+            EngramError::Storage(StorageError::InvalidState(
+                "Repository lock failed".to_string(),
+            ))
+        })?;
+
+        let type_prefix = format!("refs/engram/{}/", entity_type);
+        let refs = repo
+            .references_glob(&format!("{}*", type_prefix))
+            .map_err(|e| EngramError::Git(format!("Failed to list references: {}", e)))?;
+
+        let mut entity_ids = Vec::new();
+        let mut blobs = HashMap::new();
+        let mut entries = Vec::new();
+        for reference in refs {
+            let reference = reference
+                .map_err(|e| EngramError::Git(format!("Failed to read reference: {}", e)))?;
+            let (Some(rest), Some(oid)) = (
+                reference
+                    .name()
+                    .and_then(|name| name.strip_prefix(&type_prefix)),
+                reference.target(),
+            ) else {
+                continue;
+            };
+
+            if let Some(key) = rest
+                .strip_prefix(TIME_INDEX_SEGMENT)
+                .and_then(|key| key.strip_prefix('/'))
+            {
+                if let Some(entry) = TimeIndexEntry::parse(key) {
+                    entries.push((entry, oid));
+                }
+            } else if !rest.contains('/') {
+                entity_ids.push(rest.to_string());
+                blobs.insert(rest.to_string(), oid);
+            }
+        }
+
+        let mut indexed = HashSet::new();
+        let mut matching = HashSet::new();
+        let mut certain = HashSet::new();
+        for (entry, oid) in entries {
+            if blobs.get(&entry.entity_id) != Some(&oid) {
+                continue;
+            }
+            if entry.surely_matches(filter) {
+                certain.insert(entry.entity_id.clone());
+            }
+            if entry.may_match(filter) {
+                matching.insert(entry.entity_id.clone());
+            }
+            indexed.insert(entry.entity_id);
+        }
+
+        // Index refs left behind by deleted entities are dropped here
+        let mut candidates = IndexedCandidates::default();
+        for id in entity_ids {
+            if certain.contains(&id) {
+                candidates.matching.push(id);
+            } else if matching.contains(&id) || !indexed.contains(&id) {
+                candidates.unverified.push(id);
+            }
+        }
+        Ok(candidates)
+    }
+
+    /// Types searched by a query: the filter's, or the core entity types
+    fn query_types(filter: &QueryFilter) -> Vec<String> {
+        match &filter.entity_type {
+            Some(entity_type) => vec![entity_type.clone()],
+            None => [
+                "task",
+                "context",
+                "reasoning",
+                "knowledge",
+                "session",
+                "compliance",
+            ]
+            .iter()
+            .map(|entity_type| entity_type.to_string())
+            .collect(),
+        }
+    }
+
+    /// Load relationship entities in one batch, skipping IDs that no longer exist
+    fn load_relationships(
+        &self,
+        rel_ids: &[String],
+    ) -> Result<Vec<EntityRelationship>, EngramError> {
+        self.get_many(rel_ids, "relationship")?
+            .into_iter()
+            .flatten()
+            .map(|entity| {
+                serde_json::from_value(entity.data)
+                    .map_err(|e| EngramError::Deserialization(e.to_string()))
+            })
+            .collect()
+    }
+
+    /// Resolve the ref for an entity, supporting short ID lookup
+    fn find_entity_reference<'r>(
+        &self,
+        repo: &'r Repository,
+        entity_type: &str,
         entity_id: &str,
-    ) -> Result<Option<GenericEntity>, EngramError> {
+    ) -> Result<Option<git2::Reference<'r>>, EngramError> {
         // First try exact match
         let ref_name = self.get_entity_ref(entity_type, entity_id);
+        if let Ok(r) = repo.find_reference(&ref_name) {
+            return Ok(Some(r));
+        }
+
+        // If exact match fails and ID looks like a short ID (e.g. 8 chars), try to find a match
+        if entity_id.len() < 4 || entity_id.len() >= 36 {
+            return Ok(None);
+        }
+
+        let ref_prefix = format!("refs/engram/{}/", entity_type);
+        let all_refs = repo
+            .references()
+            .map_err(|e| EngramError::Git(format!("Failed to list references: {}", e)))?;
+
+        let mut matched_ref = None;
+        for r_result in all_refs {
+            let r = r_result
+                .map_err(|e| EngramError::Git(format!("Failed to read reference: {}", e)))?;
+            if let Some(name) = r.name() {
+                if name.starts_with(&ref_prefix) {
+                    let current_id = name.strip_prefix(&ref_prefix).unwrap();
+                    // Skip versioned sidecar refs (contain '/')
+                    if current_id.contains('/') {
+                        continue;
+                    }
+                    if current_id.starts_with(entity_id) {
+                        if matched_ref.is_some() {
+                            // Ambiguous match
+                            return Err(EngramError::Validation(format!(
+                                "Ambiguous short ID: {}",
+                                entity_id
+                            )));
+                        }
+                        matched_ref = Some(r);
+                    }
+                }
+            }
+        }
+        Ok(matched_ref)
+    }
 
+    /// Load entities of one type for a scan. Entities whose blob is missing
+    /// or does not parse are logged and skipped rather than failing the
+    /// whole scan; `engram storage fsck` reports and quarantines them.
+    fn load_listed_entities(
+        &self,
+        ids: &[String],
+        entity_type: &str,
+    ) -> Result<Vec<GenericEntity>, EngramError> {
         let repo = self.repository.lock().map_err(|_| {
             EngramError::Storage(StorageError::InvalidState(
                 "Repository lock failed".to_string(),
             ))
         })?;
+        let listed: Vec<(&String, git2::Oid)> = ids
+            .iter()
+            .filter_map(|id| {
+                let oid = repo
+                    .refname_to_id(&self.get_entity_ref(entity_type, id))
+                    .ok()?;
+                Some((id, oid))
+            })
+            .collect();
+        let oids: Vec<git2::Oid> = listed.iter().map(|(_, oid)| *oid).collect();
+        let blobs = self.read_entity_blobs(&repo, &oids)?;
+
+        let mut entities = Vec::with_capacity(blobs.len());
+        for ((id, _), entity) in listed.into_iter().zip(blobs) {
+            match entity {
+                Ok(entity) => entities.push(entity),
+                Err(e) => tracing::warn!(
+                    "Skipping unreadable {} '{}': {} (run `engram storage fsck`)",
+                    entity_type,
+                    id,
+                    e
+                ),
+            }
+        }
+        Ok(entities)
+    }
 
-        let reference = match repo.find_reference(&ref_name) {
-            Ok(r) => Some(r),
-            Err(_) => {
-                // If exact match fails and ID looks like a short ID (e.g. 8 chars), try to find a match
-                if entity_id.len() >= 4 && entity_id.len() < 36 {
-                    let ref_prefix = format!("refs/engram/{}/", entity_type);
-                    let all_refs = repo.references().map_err(|e| {
-                        EngramError::Git(format!("Failed to list references: {}", e))
+    /// Read, deserialize and decrypt entity blobs, in the order given.
+    ///
+    /// A blob that is missing or does not parse gives an error in its slot;
+    /// a decryption failure fails the whole batch. Batches of at least
+    /// [`PARALLEL_READ_THRESHOLD`] blobs are split across the read pool, each
+    /// worker reading through its own repository handle since `git2`
+    /// handles cannot be shared between threads.
+    fn read_entity_blobs(
+        &self,
+        repo: &Repository,
+        oids: &[git2::Oid],
+    ) -> Result<Vec<Result<GenericEntity, EngramError>>, EngramError> {
+        let threads = self
+            .read_pool
+            .as_ref()
+            .map_or_else(rayon::current_num_threads, |pool| {
+                pool.current_num_threads()
+            });
+        if oids.len() < PARALLEL_READ_THRESHOLD || threads <= 1 {
+            return self.read_entity_blobs_serial(&self.object_database(repo)?, oids);
+        }
+
+        let git_dir = repo.path().to_path_buf();
+        let chunk_size = oids.len().div_ceil(threads);
+        let read_chunks = || {
+            oids.par_chunks(chunk_size)
+                .map(|chunk| {
+                    let repo = Repository::open(&git_dir).map_err(|e| {
+                        EngramError::Git(format!("Failed to open {}: {}", git_dir.display(), e))
                     })?;
+                    let odb = self.object_database(&repo)?;
+                    self.read_entity_blobs_serial(&odb, chunk)
+                })
+                .collect::<Result<Vec<_>, EngramError>>()
+        };
+        let chunks = match &self.read_pool {
+            Some(pool) => pool.install(read_chunks),
+            None => read_chunks(),
+        }?;
+        Ok(chunks.into_iter().flatten().collect())
+    }
 
-                    let mut matched_ref = None;
-                    for r_result in all_refs {
-                        let r = r_result.map_err(|e| {
-                            EngramError::Git(format!("Failed to read reference: {}", e))
-                        })?;
-                        if let Some(name) = r.name() {
-                            if name.starts_with(&ref_prefix) {
-                                let current_id = name.strip_prefix(&ref_prefix).unwrap();
-                                // Skip versioned sidecar refs (contain '/')
-                                if current_id.contains('/') {
-                                    continue;
-                                }
-                                if current_id.starts_with(entity_id) {
-                                    if matched_ref.is_some() {
-                                        // Ambiguous match
-                                        return Err(EngramError::Validation(format!(
-                                            "Ambiguous short ID: {}",
-                                            entity_id
-                                        )));
-                                    }
-                                    matched_ref = Some(r);
-                                }
-                            }
-                        }
+    /// [`read_entity_blobs`](Self::read_entity_blobs) on the calling thread
+    fn read_entity_blobs_serial(
+        &self,
+        odb: &git2::Odb,
+        oids: &[git2::Oid],
+    ) -> Result<Vec<Result<GenericEntity, EngramError>>, EngramError> {
+        oids.iter()
+            .map(|&oid| {
+                let parsed = match odb.read(oid) {
+                    Ok(object) => {
+                        self.blob_reads.fetch_add(1, Ordering::Relaxed);
+                        Self::parse_entity_blob(object.data())
                     }
-                    matched_ref
-                } else {
-                    None
+                    Err(e) => Err(EngramError::Git(format!(
+                        "Failed to find blob {}: {}",
+                        oid, e
+                    ))),
+                };
+                match parsed {
+                    Ok(mut entity) => {
+                        self.encryption.decrypt_entity(&mut entity)?;
+                        Ok(Ok(entity))
+                    }
+                    Err(e) => Ok(Err(e)),
                 }
-            }
-        };
+            })
+            .collect()
+    }
 
-        let result = match reference {
-            Some(reference) => {
-                let oid = reference.target().ok_or_else(|| {
-                    EngramError::Storage(StorageError::InvalidState(format!(
-                        "Ref {} has no target",
-                        reference.name().unwrap_or("unknown")
-                    )))
-                })?;
+    /// Deserialize the entity blob a ref points at
+    fn read_entity_blob(
+        &self,
+        repo: &Repository,
+        reference: &git2::Reference,
+    ) -> Result<GenericEntity, EngramError> {
+        let oid = reference.target().ok_or_else(|| {
+            EngramError::Storage(StorageError::InvalidState(format!(
+                "Ref {} has no target",
+                reference.name().unwrap_or("unknown")
+            )))
+        })?;
 
-                let blob = repo
-                    .find_blob(oid)
-                    .map_err(|e| EngramError::Git(format!("Failed to find blob {}: {}", oid, e)))?;
+        let odb = self.object_database(repo)?;
+        let object = odb
+            .read(oid)
+            .map_err(|e| EngramError::Git(format!("Failed to find blob {}: {}", oid, e)))?;
 
-                let json_content = std::str::from_utf8(blob.content()).map_err(|e| {
-                    EngramError::Storage(StorageError::InvalidState(format!(
-                        "Invalid UTF-8 in blob: {}",
-                        e
-                    )))
-                })?;
+        self.read_stored_entity(object.data())
+    }
 
-                let memory_entity: MemoryEntity = serde_json::from_str(json_content)
-                    .map_err(|e| EngramError::Deserialization(e.to_string()))?;
+    /// Open the object database for blob reads, counting the handle
+    fn object_database<'r>(&self, repo: &'r Repository) -> Result<git2::Odb<'r>, EngramError> {
+        self.odb_handles.fetch_add(1, Ordering::Relaxed);
+        open_odb(repo)
+    }
 
-                let generic_entity = GenericEntity {
-                    id: memory_entity.id,
-                    entity_type: memory_entity.entity_type,
-                    agent: memory_entity.agent,
-                    timestamp: memory_entity.timestamp,
-                    data: serde_json::Value::Object(memory_entity.data.into_iter().collect()),
-                };
+    /// Deserialize stored entity JSON
+    fn parse_entity_blob(content: &[u8]) -> Result<GenericEntity, EngramError> {
+        let json_content = std::str::from_utf8(content).map_err(|e| {
+            EngramError::Storage(StorageError::InvalidState(format!(
+                "Invalid UTF-8 in blob: {}",
+                e
+            )))
+        })?;
 
-                Ok(Some(generic_entity))
-            }
-            None => Ok(None),
+        let memory_entity: MemoryEntity = serde_json::from_str(json_content)
+            .map_err(|e| EngramError::Deserialization(e.to_string()))?;
+
+        Ok(GenericEntity {
+            id: memory_entity.id,
+            entity_type: memory_entity.entity_type,
+            agent: memory_entity.agent,
+            timestamp: memory_entity.timestamp,
+            data: serde_json::Value::Object(memory_entity.data.into_iter().collect()),
+        })
+    }
+
+    /// Load entity from Git ref, supporting short ID lookup
+    fn load_entity_from_ref(
+        &self,
+        entity_type: &str,
+        entity_id: &str,
+    ) -> Result<Option<GenericEntity>, EngramError> {
+        let repo = self.repository.lock().map_err(|_| {
+            EngramError::Storage(StorageError::InvalidState(
+                "Repository lock failed".to_string(),
+            ))
+        })?;
+
+        let entity = match self.find_entity_reference(&repo, entity_type, entity_id)? {
+            Some(reference) => Some(self.read_entity_blob(&repo, &reference)?),
+            None => None,
         };
-        result
+        Ok(entity)
     }
 
     /// Delete entity ref
     fn delete_entity_ref(&self, entity_type: &str, entity_id: &str) -> Result<(), EngramError> {
-        let ref_name = self.get_entity_ref(entity_type, entity_id);
+        let _lock = self.lock_workspace()?;
+        let repo = self.repository.lock().map_err(|_| {
+            EngramError::Storage(StorageError::InvalidState(
+                "Repository lock failed".to_string(),
+            ))
+        })?;
 
+        delete_entity_refs(&repo, entity_type, entity_id)
+    }
+
+    /// Stored size in bytes of every entity blob, keyed by (entity type, ID).
+    /// Sizes come from object headers, so no entity is deserialized.
+    pub fn entity_blob_sizes(&self) -> Result<HashMap<(String, String), u64>, EngramError> {
         let repo = self.repository.lock().map_err(|_| {
             EngramError::Storage(StorageError::InvalidState(
                 "Repository lock failed".to_string(),
             ))
         })?;
+        let odb = repo
+            .odb()
+            .map_err(|e| EngramError::Git(format!("Failed to open object database: {}", e)))?;
+
+        let mut sizes = HashMap::new();
+        for (key, oid) in Self::entity_blob_refs(&repo)? {
+            let (size, _) = odb
+                .read_header(oid)
+                .map_err(|e| EngramError::Git(format!("Failed to read object {}: {}", oid, e)))?;
+            sizes.insert(key, size as u64);
+        }
+        Ok(sizes)
+    }
 
-        let result = match repo.find_reference(&ref_name) {
-            Ok(mut reference) => {
-                reference
-                    .delete()
-                    .map_err(|e| EngramError::Git(format!("Failed to delete ref: {}", e)))?;
-                Ok(())
+    /// Agent of every indexed entity, decoded from its time index ref name
+    ///
+    /// No blob is read. Index refs that no longer point at the entity ref's
+    /// blob, such as one left behind by an interrupted store, are skipped.
+    fn indexed_agents(&self) -> Result<HashMap<EntityKey, String>, EngramError> {
+        let repo = self.repository.lock().map_err(|_| {
+            EngramError::Storage(StorageError::InvalidState(
+                "Repository lock failed".to_string(),
+            ))
+        })?;
+        let blobs: HashMap<EntityKey, git2::Oid> =
+            Self::entity_blob_refs(&repo)?.into_iter().collect();
+        let refs = repo
+            .references_glob(&format!("refs/engram/*/{}/*", TIME_INDEX_SEGMENT))
+            .map_err(|e| EngramError::Git(format!("Failed to list references: {}", e)))?;
+
+        let mut agents = HashMap::new();
+        for reference in refs {
+            let reference = reference
+                .map_err(|e| EngramError::Git(format!("Failed to read reference: {}", e)))?;
+            let (Some(name), Some(oid)) = (reference.name(), reference.target()) else {
+                continue;
+            };
+            let Some((entity_type, entry)) = name
+                .strip_prefix("refs/engram/")
+                .and_then(|rest| rest.split_once('/'))
+                .and_then(|(entity_type, rest)| {
+                    let key = rest.strip_prefix(TIME_INDEX_SEGMENT)?.strip_prefix('/')?;
+                    Some((entity_type, TimeIndexEntry::parse(key)?))
+                })
+            else {
+                continue;
+            };
+            let key = (entity_type.to_string(), entry.entity_id);
+            if blobs.get(&key) == Some(&oid) {
+                agents.insert(key, entry.agent);
             }
-            Err(_) => Ok(()),
-        };
-        result
+        }
+        Ok(agents)
+    }
+
+    /// Blob of every entity ref, keyed by (entity type, ID)
+    fn entity_blob_refs(repo: &Repository) -> Result<Vec<(EntityKey, git2::Oid)>, EngramError> {
+        let refs = repo
+            .references_glob("refs/engram/*")
+            .map_err(|e| EngramError::Git(format!("Failed to list references: {}", e)))?;
+
+        let mut blobs = Vec::new();
+        for reference in refs {
+            let reference = reference
+                .map_err(|e| EngramError::Git(format!("Failed to read reference: {}", e)))?;
+            let (Some(name), Some(oid)) = (reference.name(), reference.target()) else {
+                continue;
+            };
+            // Skip the workspace config ref, versioned sidecars and index refs
+            let Some((entity_type, entity_id)) = name
+                .strip_prefix("refs/engram/")
+                .and_then(|rest| rest.split_once('/'))
+            else {
+                continue;
+            };
+            if entity_type == "config" || entity_id.contains('/') {
+                continue;
+            }
+            blobs.push(((entity_type.to_string(), entity_id.to_string()), oid));
+        }
+        Ok(blobs)
     }
 
     /// List all entity refs of a given type
@@ -546,27 +1463,109 @@ impl GitRefsStorage {
 // Storage trait implementation will be added next
 impl Storage for GitRefsStorage {
     fn store(&mut self, entity: &GenericEntity) -> Result<(), EngramError> {
-        self.store_entity_as_ref(entity)?;
+        self.validate_for_store(entity)?;
+        self.store_entities_as_refs(std::slice::from_ref(entity))?;
+        self.index_stored_relationship(entity)
+    }
 
-        // Update relationship index if this is a relationship entity
-        if entity.entity_type == "relationship" {
-            if let Ok(relationship) =
-                serde_json::from_value::<EntityRelationship>(entity.data.clone())
-            {
-                let mut index = self.relationship_index.lock().map_err(|_| {
-                    EngramError::Storage(StorageError::InvalidState(
-                        "Index lock failed".to_string(),
-                    ))
-                })?;
-                index.add_relationship(&relationship);
-            }
+    fn get(&self, id: &str, entity_type: &str) -> Result<Option<GenericEntity>, EngramError> {
+        self.load_entity_from_ref(entity_type, id)
+    }
+
+    /// Load, patch and store while holding the workspace lock and repository
+    /// mutex, so a concurrent writer cannot slip in between the read and the
+    /// write
+    fn patch(
+        &mut self,
+        id: &str,
+        entity_type: &str,
+        patch: Vec<JsonPatchOp>,
+    ) -> Result<(), EngramError> {
+        let entity = {
+            let _lock = self.lock_workspace()?;
+            let repo = self.repository.lock().map_err(|_| {
+                EngramError::Storage(StorageError::InvalidState(
+                    "Repository lock failed".to_string(),
+                ))
+            })?;
+            let Some(reference) = self.find_entity_reference(&repo, entity_type, id)? else {
+                return Err(EngramError::not_found(entity_type, id));
+            };
+            let mut entity = self.read_entity_blob(&repo, &reference)?;
+            apply_entity_patch(&mut entity, &patch)?;
+            self.validate_for_store(&entity)?;
+            self.write_entity_refs(&repo, std::slice::from_ref(&entity))?;
+            entity
+        };
+        self.index_stored_relationship(&entity)
+    }
+
+    /// Validate a registered entity against its typed schema before it is written
+    fn validate_for_store(&self, entity: &GenericEntity) -> Result<(), EngramError> {
+        if self.entity_registry.validate(entity)? {
+            return Ok(());
         }
 
+        if self.strict_entities {
+            return Err(EngramError::Validation(format!(
+                "Unknown entity type '{}' for {} (strict entities enabled)",
+                entity.entity_type, entity.id
+            )));
+        }
+
+        tracing::warn!(
+            "Storing {} '{}' without validation: entity type is not registered",
+            entity.entity_type,
+            entity.id
+        );
         Ok(())
     }
 
-    fn get(&self, id: &str, entity_type: &str) -> Result<Option<GenericEntity>, EngramError> {
-        self.load_entity_from_ref(entity_type, id)
+    fn exists(&self, id: &str, entity_type: &str) -> Result<bool, EngramError> {
+        let repo = self.repository.lock().map_err(|_| {
+            EngramError::Storage(StorageError::InvalidState(
+                "Repository lock failed".to_string(),
+            ))
+        })?;
+
+        let found = self
+            .find_entity_reference(&repo, entity_type, id)?
+            .is_some();
+        Ok(found)
+    }
+
+    fn get_many(
+        &self,
+        ids: &[String],
+        entity_type: &str,
+    ) -> Result<Vec<Option<GenericEntity>>, EngramError> {
+        let repo = self.repository.lock().map_err(|_| {
+            EngramError::Storage(StorageError::InvalidState(
+                "Repository lock failed".to_string(),
+            ))
+        })?;
+        // Full IDs resolve straight to an oid without materialising a
+        // reference object
+        let oids = ids
+            .iter()
+            .map(
+                |id| match repo.refname_to_id(&self.get_entity_ref(entity_type, id)) {
+                    Ok(oid) => Ok(Some(oid)),
+                    Err(_) => Ok(self
+                        .find_entity_reference(&repo, entity_type, id)?
+                        .and_then(|reference| reference.target())),
+                },
+            )
+            .collect::<Result<Vec<_>, EngramError>>()?;
+        let found: Vec<git2::Oid> = oids.iter().flatten().copied().collect();
+        let mut blobs = self.read_entity_blobs(&repo, &found)?.into_iter();
+
+        oids.into_iter()
+            .map(|oid| match oid {
+                Some(_) => blobs.next().transpose(),
+                None => Ok(None),
+            })
+            .collect()
     }
 
     fn delete(&mut self, id: &str, entity_type: &str) -> Result<(), EngramError> {
@@ -589,139 +1588,37 @@ impl Storage for GitRefsStorage {
     }
 
     fn query(&self, filter: &QueryFilter) -> Result<QueryResult, EngramError> {
-        let mut results = Vec::new();
-
-        // Determine which entity types to search
-        let entity_types = if let Some(entity_type) = &filter.entity_type {
-            vec![entity_type.clone()]
-        } else {
-            vec![
-                "task".to_string(),
-                "context".to_string(),
-                "reasoning".to_string(),
-                "knowledge".to_string(),
-                "session".to_string(),
-                "compliance".to_string(),
-            ]
-        };
-
-        for entity_type in entity_types {
-            let entity_ids = self.list_entity_refs(&entity_type)?;
-
-            for entity_id in entity_ids {
-                if let Some(entity) = self.load_entity_from_ref(&entity_type, &entity_id)? {
-                    // Apply filters
-                    if let Some(agent_filter) = &filter.agent {
-                        if entity.agent != *agent_filter {
-                            continue;
-                        }
-                    }
-
-                    // Apply field filters
-                    let mut matches = true;
-                    for (field, value) in &filter.field_filters {
-                        if let Some(entity_value) = entity.data.get(field) {
-                            if entity_value != value {
-                                matches = false;
-                                break;
-                            }
-                        } else {
-                            matches = false;
-                            break;
-                        }
-                    }
+        let mut candidates = Vec::new();
 
-                    if matches {
-                        results.push(entity);
-                    }
-                }
-            }
-        }
-
-        // Apply sorting
-        results.sort_by(|a, b| {
-            if let Some(sort_field) = &filter.sort_by {
-                let a_val = a.data.get(sort_field);
-                let b_val = b.data.get(sort_field);
-                match filter.sort_order {
-                    SortOrder::Asc => match (a_val, b_val) {
-                        (Some(a), Some(b)) => a.to_string().cmp(&b.to_string()),
-                        (Some(_), None) => std::cmp::Ordering::Greater,
-                        (None, Some(_)) => std::cmp::Ordering::Less,
-                        (None, None) => std::cmp::Ordering::Equal,
-                    },
-                    SortOrder::Desc => match (b_val, a_val) {
-                        (Some(a), Some(b)) => a.to_string().cmp(&b.to_string()),
-                        (Some(_), None) => std::cmp::Ordering::Greater,
-                        (None, Some(_)) => std::cmp::Ordering::Less,
-                        (None, None) => std::cmp::Ordering::Equal,
-                    },
-                }
+        for entity_type in Self::query_types(filter) {
+            let entity_ids = if filter.agent.is_some() || filter.time_range.is_some() {
+                let indexed = self.indexed_candidates(&entity_type, filter)?;
+                [indexed.matching, indexed.unverified].concat()
             } else {
-                // Default sort by timestamp
-                match filter.sort_order {
-                    SortOrder::Asc => a.timestamp.cmp(&b.timestamp),
-                    SortOrder::Desc => b.timestamp.cmp(&a.timestamp),
-                }
-            }
-        });
-
-        // Apply pagination
-        let offset = filter.offset.unwrap_or(0);
-        let total = results.len();
-
-        let paginated_results: Vec<_> = match filter.limit {
-            Some(limit) => results.into_iter().skip(offset).take(limit).collect(),
-            None => results.into_iter().skip(offset).collect(),
-        };
+                self.list_entity_refs(&entity_type)?
+            };
+            candidates.extend(self.load_listed_entities(&entity_ids, &entity_type)?);
+        }
 
-        let has_more = filter
-            .limit
-            .map_or(false, |_| offset + paginated_results.len() < total);
-        Ok(QueryResult {
-            entities: paginated_results,
-            total_count: total,
-            has_more,
-        })
+        apply_filter(candidates, filter)
     }
 
     fn get_stats(&self) -> Result<StorageStats, EngramError> {
         let mut stats = StorageStats::default();
-
-        let entity_types = [
-            "task",
-            "context",
-            "reasoning",
-            "knowledge",
-            "session",
-            "compliance",
-            "relationship",
-        ];
-
-        for entity_type in &entity_types {
-            let entity_ids = self.list_entity_refs(entity_type)?;
-            let count = entity_ids.len();
-
-            stats.total_entities += count;
-            stats
-                .entities_by_type
-                .insert(entity_type.to_string(), count);
+        for ((entity_type, _), size) in self.entity_blob_sizes()? {
+            stats.total_entities += 1;
+            stats.total_storage_size += size;
+            *stats.entities_by_type.entry(entity_type).or_insert(0) += 1;
+        }
+        for agent in self.indexed_agents()?.into_values() {
+            *stats.entities_by_agent.entry(agent).or_insert(0) += 1;
         }
-
         Ok(stats)
     }
 
     fn get_all(&self, entity_type: &str) -> Result<Vec<GenericEntity>, EngramError> {
         let entity_ids = self.list_entity_refs(entity_type)?;
-        let mut entities = Vec::new();
-
-        for entity_id in entity_ids {
-            if let Some(entity) = self.load_entity_from_ref(entity_type, &entity_id)? {
-                entities.push(entity);
-            }
-        }
-
-        Ok(entities)
+        self.load_listed_entities(&entity_ids, entity_type)
     }
 
     fn query_by_agent(
@@ -742,16 +1639,12 @@ impl Storage for GitRefsStorage {
         start: chrono::DateTime<chrono::Utc>,
         end: chrono::DateTime<chrono::Utc>,
     ) -> Result<Vec<GenericEntity>, EngramError> {
-        let filter = QueryFilter::default();
-        let result = self.query(&filter)?;
-
-        let filtered_entities = result
-            .entities
-            .into_iter()
-            .filter(|entity| entity.timestamp >= start && entity.timestamp <= end)
-            .collect();
-
-        Ok(filtered_entities)
+        let filter = QueryFilter {
+            time_range: Some(TimeRange { start, end }),
+            limit: None,
+            ..Default::default()
+        };
+        self.query(&filter).map(|result| result.entities)
     }
 
     fn query_by_type(
@@ -781,8 +1674,8 @@ impl Storage for GitRefsStorage {
         entity_types: Option<&[String]>,
         limit: Option<usize>,
     ) -> Result<Vec<GenericEntity>, EngramError> {
-        let mut results = Vec::new();
-        let query_lower = query.to_lowercase();
+        let mut ranked = Vec::new();
+        let query_folded = fold_case(query);
 
         let default_types = [
             "task".to_string(),
@@ -806,28 +1699,56 @@ impl Storage for GitRefsStorage {
         let search_types = entity_types.unwrap_or(&default_types);
 
         for entity_type in search_types {
-            let entities = self.get_all(entity_type)?;
-
-            for entity in entities {
-                let entity_json = serde_json::to_string(&entity.data).unwrap_or_default();
-                if entity_json.to_lowercase().contains(&query_lower) {
-                    results.push(entity);
-                }
-
-                if let Some(limit) = limit {
-                    if results.len() >= limit {
-                        return Ok(results);
-                    }
+            for entity in self.get_all(entity_type)? {
+                // Any other field of the entity data counts as content
+                let matched = text_match(&entity, query, false).or_else(|| {
+                    let entity_json = serde_json::to_string(&entity.data).unwrap_or_default();
+                    fold_case(&entity_json)
+                        .contains(&query_folded)
+                        .then_some(TextMatch {
+                            tier: MatchTier::Content,
+                            similarity: 1.0,
+                        })
+                });
+                if let Some(matched) = matched {
+                    ranked.push((matched, entity));
                 }
             }
         }
 
-        Ok(results)
+        ranked.sort_by(|(a, _), (b, _)| a.better_first(b));
+        Ok(ranked
+            .into_iter()
+            .map(|(_, entity)| entity)
+            .take(limit.unwrap_or(usize::MAX))
+            .collect())
     }
 
+    /// Count matching entities, from ref names where the filter allows
+    ///
+    /// Only entities whose time index ref points at their current blob are
+    /// counted without reading it. Any other entity ref, such as one written
+    /// outside `store` whose blob is missing or unparseable, is read and
+    /// skipped just as `query` skips it, so the two agree before
+    /// `engram storage fsck --repair` quarantines such refs.
     fn count(&self, filter: &QueryFilter) -> Result<usize, EngramError> {
-        let result = self.query(filter)?;
-        Ok(result.total_count)
+        // Text and field predicates need entity data
+        if filter.text_search.is_some() || !filter.field_filters.is_empty() {
+            return Ok(self.query(filter)?.total_count);
+        }
+
+        // Otherwise ref names answer for all but unindexed or boundary entities
+        let mut count = 0;
+        for entity_type in Self::query_types(filter) {
+            let candidates = self.indexed_candidates(&entity_type, filter)?;
+            count += candidates.matching.len();
+            count += self
+                .load_listed_entities(&candidates.unverified, &entity_type)?
+                .iter()
+                .filter(|entity| matches_filter(*entity, filter))
+                .count();
+        }
+        Ok(count)
     }
 
     fn list_ids(&self, entity_type: &str) -> Result<Vec<String>, EngramError> {
@@ -859,6 +1780,7 @@ impl Storage for GitRefsStorage {
     }
 
     fn create_branch(&mut self, branch_name: &str) -> Result<(), EngramError> {
+        let _lock = self.lock_workspace()?;
         let repo = self.repository.lock().map_err(|_| {
             EngramError::Storage(StorageError::InvalidState(
                 "Repository lock failed".to_string(),
@@ -878,6 +1800,7 @@ impl Storage for GitRefsStorage {
     }
 
     fn switch_branch(&mut self, branch_name: &str) -> Result<(), EngramError> {
+        let _lock = self.lock_workspace()?;
         let repo = self.repository.lock().map_err(|_| {
             EngramError::Storage(StorageError::InvalidState(
                 "Repository lock failed".to_string(),
@@ -949,7 +1872,19 @@ impl Storage for GitRefsStorage {
 
     fn bulk_store(&mut self, entities: &[GenericEntity]) -> Result<(), EngramError> {
         for entity in entities {
-            self.store(entity)?;
+            self.validate_for_store(entity)?;
+        }
+        self.store_entities_as_refs(entities)?;
+
+        let mut index = self.relationship_index.lock().map_err(|_| {
+            EngramError::Storage(StorageError::InvalidState("Index lock failed".to_string()))
+        })?;
+        for entity in entities.iter().filter(|e| e.entity_type == "relationship") {
+            if let Ok(relationship) =
+                serde_json::from_value::<EntityRelationship>(entity.data.clone())
+            {
+                index.add_relationship(&relationship);
+            }
         }
         Ok(())
     }
@@ -1005,14 +1940,7 @@ impl RelationshipStorage for GitRefsStorage {
         let rel_ids = index.get_all_relationships(entity_id);
         drop(index);
 
-        let mut relationships = Vec::new();
-        for rel_id in rel_ids {
-            if let Some(rel) = self.get_relationship(&rel_id)? {
-                relationships.push(rel);
-            }
-        }
-
-        Ok(relationships)
+        self.load_relationships(&rel_ids)
     }
 
     fn get_outbound_relationships(
@@ -1026,14 +1954,7 @@ impl RelationshipStorage for GitRefsStorage {
         let rel_ids = index.get_outbound(entity_id);
         drop(index);
 
-        let mut relationships = Vec::new();
-        for rel_id in rel_ids {
-            if let Some(rel) = self.get_relationship(&rel_id)? {
-                relationships.push(rel);
-            }
-        }
-
-        Ok(relationships)
+        self.load_relationships(&rel_ids)
     }
 
     fn get_inbound_relationships(
@@ -1047,14 +1968,7 @@ impl RelationshipStorage for GitRefsStorage {
         let rel_ids = index.get_inbound(entity_id);
         drop(index);
 
-        let mut relationships = Vec::new();
-        for rel_id in rel_ids {
-            if let Some(rel) = self.get_relationship(&rel_id)? {
-                relationships.push(rel);
-            }
-        }
-
-        Ok(relationships)
+        self.load_relationships(&rel_ids)
     }
 
     fn find_paths(
@@ -1213,6 +2127,110 @@ impl ConsistencyCheckReport {
 }
 
 impl GitRefsStorage {
+    /// Check that every entity ref points at a blob that deserializes as an
+    /// entity matching its ref path and its registered type's schema.
+    ///
+    /// With `repair`, each broken entity ref is moved under
+    /// [`QUARANTINE_NAMESPACE`] and its time index refs are removed, so
+    /// queries no longer see it. Version sidecars are kept.
+    pub fn fsck(&self, repair: bool) -> Result<FsckReport, EngramError> {
+        let _lock = if repair {
+            Some(self.lock_workspace()?)
+        } else {
+            None
+        };
+        let repo = self.repository.lock().map_err(|_| {
+            EngramError::Storage(StorageError::InvalidState(
+                "Repository lock failed".to_string(),
+            ))
+        })?;
+
+        let mut report = FsckReport {
+            repaired: repair,
+            ..Default::default()
+        };
+        for ((entity_type, entity_id), oid) in Self::entity_blob_refs(&repo)? {
+            report.checked += 1;
+            let Some((problem, blob_exists)) =
+                self.check_entity_blob(&repo, &entity_type, &entity_id, oid)
+            else {
+                continue;
+            };
+
+            let quarantined_to = if repair {
+                let quarantine_ref = format!(
+                    "refs/engram/{}/{}/{}",
+                    QUARANTINE_NAMESPACE, entity_type, entity_id
+                );
+                if blob_exists {
+                    repo.reference(
+                        &quarantine_ref,
+                        oid,
+                        true,
+                        &format!("engram fsck: {}", problem),
+                    )
+                    .map_err(|e| {
+                        EngramError::Git(format!("Failed to write {}: {}", quarantine_ref, e))
+                    })?;
+                }
+                delete_entity_refs(&repo, &entity_type, &entity_id)?;
+                blob_exists.then_some(quarantine_ref)
+            } else {
+                None
+            };
+
+            report.corrupt.push(CorruptEntity {
+                ref_name: self.get_entity_ref(&entity_type, &entity_id),
+                entity_type,
+                entity_id,
+                problem,
+                quarantined_to,
+            });
+        }
+        Ok(report)
+    }
+
+    /// What is wrong with one entity ref's blob, and whether the blob exists
+    fn check_entity_blob(
+        &self,
+        repo: &Repository,
+        entity_type: &str,
+        entity_id: &str,
+        oid: git2::Oid,
+    ) -> Option<(String, bool)> {
+        let blob = match repo.find_blob(oid) {
+            Ok(blob) => blob,
+            Err(e) => return Some((format!("blob {} is unreadable: {}", oid, e), false)),
+        };
+        let mut entity = match Self::parse_entity_blob(blob.content()) {
+            Ok(entity) => entity,
+            Err(e) => return Some((e.to_string(), true)),
+        };
+        if entity.entity_type != entity_type {
+            return Some((
+                format!(
+                    "stored entity type '{}' does not match ref",
+                    entity.entity_type
+                ),
+                true,
+            ));
+        }
+        if entity.id != entity_id {
+            return Some((
+                format!("stored entity ID '{}' does not match ref", entity.id),
+                true,
+            ));
+        }
+        // Encrypted fields cannot be validated without the workspace key
+        if self.encryption.decrypt_entity(&mut entity).is_err() {
+            return None;
+        }
+        self.entity_registry
+            .validate(&entity)
+            .err()
+            .map(|e| (e.to_string(), true))
+    }
+
     pub fn consistency_check(&self) -> Result<ConsistencyCheckReport, EngramError> {
         let repo = self.repository.lock().map_err(|_| {
             EngramError::Storage(StorageError::InvalidState(
@@ -1252,6 +2270,21 @@ impl GitRefsStorage {
             if !ref_name.starts_with(engram_prefix) {
                 continue;
             }
+            // Quarantined refs are known-bad and reported by fsck instead
+            if ref_name
+                .strip_prefix(engram_prefix)
+                .is_some_and(|rest| rest.starts_with(&format!("{}/", QUARANTINE_NAMESPACE)))
+            {
+                continue;
+            }
+            // Index refs point at entity blobs already checked via their entity ref
+            if ref_name
+                .strip_prefix(engram_prefix)
+                .and_then(|rest| rest.split_once('/'))
+                .is_some_and(|(_, rest)| rest.starts_with(&format!("{}/", TIME_INDEX_SEGMENT)))
+            {
+                continue;
+            }
 
             report.total_refs += 1;
 
@@ -1444,92 +2477,367 @@ impl GitRefsStorage {
         report.checks = checks;
         Ok(report)
     }
-}
-
-fn count_orphaned_blobs(
-    repo: &git2::Repository,
-    referenced_oids: &HashSet<String>,
-) -> Result<usize, EngramError> {
-    let odb = repo
-        .odb()
-        .map_err(|e| EngramError::Git(format!("Failed to open ODB: {}", e)))?;
+}
+
+fn count_orphaned_blobs(
+    repo: &git2::Repository,
+    referenced_oids: &HashSet<String>,
+) -> Result<usize, EngramError> {
+    let odb = repo
+        .odb()
+        .map_err(|e| EngramError::Git(format!("Failed to open ODB: {}", e)))?;
+
+    let mut count = 0;
+    let mut error: Option<EngramError> = None;
+
+    odb.foreach(|oid| {
+        if error.is_some() {
+            return false;
+        }
+        match repo.find_object(*oid, None) {
+            Ok(obj) => {
+                if obj.kind() == Some(git2::ObjectType::Blob) {
+                    let oid_str = oid.to_string();
+                    if !referenced_oids.contains(&oid_str) {
+                        count += 1;
+                    }
+                }
+            }
+            Err(e) => {
+                error = Some(EngramError::Git(format!(
+                    "Failed to read object {}: {}",
+                    oid, e
+                )));
+                return false;
+            }
+        }
+        true
+    })
+    .map_err(|e| EngramError::Git(format!("Failed to iterate ODB: {}", e)))?;
+
+    if let Some(e) = error {
+        return Err(e);
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{
+        Context, ContextRelevance, Entity, Modification, Task, TaskPriority, LAST_MODIFIED_BY_FIELD,
+    };
+    use crate::feedback::StructuredFeedback;
+    use crate::storage::WorkspaceKey;
+    use chrono::Utc;
+    use serde_json::json;
+    use tempfile::tempdir;
+
+    fn create_test_entity(id: &str, agent: &str) -> GenericEntity {
+        let mut task = Task::new(
+            "Test Task".to_string(),
+            "Stored by git refs tests".to_string(),
+            agent.to_string(),
+            TaskPriority::Medium,
+            None,
+        );
+        task.id = id.to_string();
+        task.to_generic()
+    }
+
+    #[test]
+    fn test_git_refs_storage_creation() {
+        let dir = tempdir().unwrap();
+        let storage = GitRefsStorage::new(dir.path().to_str().unwrap(), "test-agent");
+        assert!(storage.is_ok());
+    }
+
+    #[test]
+    fn test_store_and_get() {
+        let dir = tempdir().unwrap();
+        let mut storage = GitRefsStorage::new(dir.path().to_str().unwrap(), "test-agent").unwrap();
+
+        let entity = create_test_entity("test-1", "test-agent");
+        storage.store(&entity).unwrap();
+
+        let retrieved = storage.get("test-1", "task").unwrap();
+        assert!(retrieved.is_some());
+        let retrieved = retrieved.unwrap();
+        assert_eq!(retrieved.id, "test-1");
+        assert_eq!(retrieved.agent, "test-agent");
+        assert_eq!(retrieved.entity_type, "task");
+    }
+
+    #[test]
+    fn test_store_tracks_last_modifier() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().to_str().unwrap();
+        let mut alice = GitRefsStorage::new(path, "alice").unwrap();
+        let mut bob = GitRefsStorage::new(path, "bob").unwrap();
+
+        let entity = create_test_entity("handoff", "alice");
+        alice.store(&entity).unwrap();
+        bob.store(&entity).unwrap();
+
+        let stored = alice.get("handoff", "task").unwrap().unwrap();
+        assert_eq!(stored.agent, "alice");
+        let modification = stored.modification();
+        assert!(modification.last_modified_at.is_some());
+        assert_eq!(
+            modification,
+            Modification {
+                last_modified_by: Some("bob".to_string()),
+                modification_count: 2,
+                last_modified_at: modification.last_modified_at,
+            }
+        );
+
+        let filter = QueryFilter {
+            entity_type: Some("task".to_string()),
+            field_filters: HashMap::from([(
+                LAST_MODIFIED_BY_FIELD.to_string(),
+                Value::String("bob".to_string()),
+            )]),
+            ..Default::default()
+        };
+        assert_eq!(alice.query(&filter).unwrap().total_count, 1);
+    }
+
+    #[test]
+    fn test_exists_and_get_many() {
+        let dir = tempdir().unwrap();
+        let mut storage = GitRefsStorage::new(dir.path().to_str().unwrap(), "test-agent").unwrap();
+
+        storage
+            .store(&create_test_entity("test-1", "test-agent"))
+            .unwrap();
+        storage
+            .store(&create_test_entity("test-2", "test-agent"))
+            .unwrap();
+
+        assert!(storage.exists("test-1", "task").unwrap());
+        assert!(!storage.exists("test-1", "context").unwrap());
+        assert!(!storage.exists("missing", "task").unwrap());
+
+        let ids = vec![
+            "test-2".to_string(),
+            "missing".to_string(),
+            "test-1".to_string(),
+        ];
+        let found = storage.get_many(&ids, "task").unwrap();
+        assert_eq!(found.len(), 3);
+        assert_eq!(found[0].as_ref().unwrap().id, "test-2");
+        assert!(found[1].is_none());
+        assert_eq!(found[2].as_ref().unwrap().id, "test-1");
+    }
+
+    #[test]
+    fn test_get_many_shares_odb_handles_across_the_batch() {
+        let dir = tempdir().unwrap();
+        let mut storage = GitRefsStorage::new(dir.path().to_str().unwrap(), "test-agent").unwrap();
+
+        let ids: Vec<String> = (0..1000).map(|i| format!("bulk-{:04}", i)).collect();
+        let entities: Vec<GenericEntity> = ids
+            .iter()
+            .map(|id| create_test_entity(id, "test-agent"))
+            .collect();
+        storage.bulk_store(&entities).unwrap();
+
+        let counts = |storage: &GitRefsStorage| {
+            (
+                storage.odb_handles.load(Ordering::Relaxed),
+                storage.blob_reads.load(Ordering::Relaxed),
+            )
+        };
+
+        let (handles_before, reads_before) = counts(&storage);
+        for id in &ids {
+            assert!(storage.get(id, "task").unwrap().is_some());
+        }
+        let (handles, reads) = counts(&storage);
+        let individual_handles = handles - handles_before;
+        assert_eq!(individual_handles, ids.len());
+        assert_eq!(reads - reads_before, ids.len());
+
+        let (handles_before, reads_before) = counts(&storage);
+        let found = storage.get_many(&ids, "task").unwrap();
+        let (handles, reads) = counts(&storage);
+        assert!(found.iter().all(Option::is_some));
+        assert_eq!(reads - reads_before, ids.len());
+        // One handle per read-pool chunk rather than one per entity
+        let batched_handles = handles - handles_before;
+        assert!(
+            batched_handles <= rayon::current_num_threads().max(1),
+            "get_many opened {} odb handles",
+            batched_handles
+        );
+        assert!(batched_handles < individual_handles);
+    }
 
-    let mut count = 0;
-    let mut error: Option<EngramError> = None;
+    #[test]
+    fn test_get_many_matches_individual_gets() {
+        let dir = tempdir().unwrap();
+        let mut storage = GitRefsStorage::new(dir.path().to_str().unwrap(), "test-agent").unwrap();
 
-    odb.foreach(|oid| {
-        if error.is_some() {
-            return false;
+        let ids: Vec<String> = (0..100).map(|i| format!("bulk-{:04}", i)).collect();
+        let entities: Vec<GenericEntity> = ids
+            .iter()
+            .map(|id| create_test_entity(id, "test-agent"))
+            .collect();
+        storage.bulk_store(&entities).unwrap();
+
+        // Reverse order with a missing ID in the middle
+        let mut requested: Vec<String> = ids.iter().rev().cloned().collect();
+        requested.insert(50, "missing".to_string());
+
+        let batched = storage.get_many(&requested, "task").unwrap();
+        assert_eq!(batched.len(), requested.len());
+        for (id, found) in requested.iter().zip(&batched) {
+            let individual = storage.get(id, "task").unwrap();
+            assert_eq!(
+                found.as_ref().map(|e| &e.id),
+                individual.as_ref().map(|e| &e.id)
+            );
         }
-        match repo.find_object(*oid, None) {
-            Ok(obj) => {
-                if obj.kind() == Some(git2::ObjectType::Blob) {
-                    let oid_str = oid.to_string();
-                    if !referenced_oids.contains(&oid_str) {
-                        count += 1;
+        assert!(batched[50].is_none());
+    }
+
+    #[test]
+    fn test_concurrent_stores_from_separate_instances() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().to_str().unwrap().to_string();
+        // Two independent handles, as with the watcher daemon and a CLI command
+        let first = GitRefsStorage::new(&path, "daemon").unwrap();
+        let second = GitRefsStorage::new(&path, "cli").unwrap();
+
+        let writers: Vec<_> = [(first, "daemon"), (second, "cli")]
+            .into_iter()
+            .map(|(mut storage, agent)| {
+                std::thread::spawn(move || {
+                    for i in 0..25 {
+                        let entity = create_test_entity(&format!("{}-{}", agent, i), agent);
+                        storage.store(&entity).unwrap();
                     }
-                }
-            }
-            Err(e) => {
-                error = Some(EngramError::Git(format!(
-                    "Failed to read object {}: {}",
-                    oid, e
-                )));
-                return false;
-            }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().expect("store failed under contention");
         }
-        true
-    })
-    .map_err(|e| EngramError::Git(format!("Failed to iterate ODB: {}", e)))?;
 
-    if let Some(e) = error {
-        return Err(e);
+        let storage = GitRefsStorage::new(&path, "reader").unwrap();
+        for agent in ["daemon", "cli"] {
+            for i in 0..25 {
+                assert!(storage.exists(&format!("{}-{}", agent, i), "task").unwrap());
+            }
+        }
     }
-    Ok(count)
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::feedback::StructuredFeedback;
-    use chrono::Utc;
-    use serde_json::json;
-    use tempfile::tempdir;
+    #[test]
+    fn test_concurrent_patches_do_not_lose_updates() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().to_str().unwrap().to_string();
+        let mut setup = GitRefsStorage::new(&path, "setup").unwrap();
+        let mut shared = create_test_entity("shared", "setup");
+        shared.data["tags"] = json!(["seed"]);
+        setup.store(&shared).unwrap();
+
+        // Each writer appends its own tags; a store landing between another
+        // writer's read and write would drop that writer's tag
+        let writers: Vec<_> = ["daemon", "cli"]
+            .into_iter()
+            .map(|agent| {
+                let mut storage = GitRefsStorage::new(&path, agent).unwrap();
+                std::thread::spawn(move || {
+                    for i in 0..20 {
+                        let patch = vec![JsonPatchOp::Add {
+                            path: "/data/tags/-".to_string(),
+                            value: json!(format!("{}-{}", agent, i)),
+                        }];
+                        storage.patch("shared", "task", patch).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().expect("patch failed under contention");
+        }
 
-    fn create_test_entity(id: &str, agent: &str) -> GenericEntity {
-        GenericEntity {
-            id: id.to_string(),
-            entity_type: "task".to_string(),
-            agent: agent.to_string(),
-            timestamp: Utc::now(),
-            data: json!({
-                "title": "Test Task",
-                "status": "pending"
-            }),
+        let storage = GitRefsStorage::new(&path, "reader").unwrap();
+        let task = storage.get("shared", "task").unwrap().unwrap();
+        let tags = task.data["tags"].as_array().unwrap();
+        assert_eq!(tags.len(), 41, "{:?}", tags);
+        for agent in ["daemon", "cli"] {
+            for i in 0..20 {
+                assert!(tags.contains(&json!(format!("{}-{}", agent, i))));
+            }
         }
     }
 
     #[test]
-    fn test_git_refs_storage_creation() {
+    fn test_patch_missing_entity_is_not_found() {
         let dir = tempdir().unwrap();
-        let storage = GitRefsStorage::new(dir.path().to_str().unwrap(), "test-agent");
-        assert!(storage.is_ok());
+        let mut storage = GitRefsStorage::new(dir.path().to_str().unwrap(), "test-agent").unwrap();
+        let err = storage.patch("missing", "task", Vec::new()).unwrap_err();
+        assert!(matches!(err, EngramError::NotFound { .. }), "{:?}", err);
     }
 
     #[test]
-    fn test_store_and_get() {
+    fn test_store_fails_with_locked_error_when_lock_held() {
+        let dir = tempdir().unwrap();
+        let mut storage = GitRefsStorage::new(dir.path().to_str().unwrap(), "test-agent")
+            .unwrap()
+            .with_lock_timeout(std::time::Duration::from_millis(50));
+
+        let _held = WorkspaceLock::acquire(dir.path(), std::time::Duration::from_secs(1)).unwrap();
+        let err = storage
+            .store(&create_test_entity("blocked", "test-agent"))
+            .unwrap_err();
+        assert!(matches!(err, EngramError::Locked(_)), "{:?}", err);
+        // Reads do not take the lock
+        assert!(storage.get("blocked", "task").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_store_rejects_invalid_registered_entity() {
         let dir = tempdir().unwrap();
         let mut storage = GitRefsStorage::new(dir.path().to_str().unwrap(), "test-agent").unwrap();
 
-        let entity = create_test_entity("test-1", "test-agent");
-        storage.store(&entity).unwrap();
+        let mut entity = create_test_entity("bad-status", "test-agent");
+        entity.data["status"] = json!("pending");
+        match storage.store(&entity).unwrap_err() {
+            EngramError::Validation(message) => {
+                assert!(message.contains("status"), "{}", message)
+            }
+            other => panic!("expected Validation, got {:?}", other),
+        }
+        assert!(!storage.exists("bad-status", "task").unwrap());
+    }
 
-        let retrieved = storage.get("test-1", "task").unwrap();
-        assert!(retrieved.is_some());
-        let retrieved = retrieved.unwrap();
-        assert_eq!(retrieved.id, "test-1");
-        assert_eq!(retrieved.agent, "test-agent");
-        assert_eq!(retrieved.entity_type, "task");
+    #[test]
+    fn test_unregistered_types_need_strict_entities_off() {
+        let dir = tempdir().unwrap();
+        let note = GenericEntity {
+            id: "note-1".to_string(),
+            entity_type: "note".to_string(),
+            agent: "test-agent".to_string(),
+            timestamp: Utc::now(),
+            data: json!({"text": "free-form"}),
+        };
+
+        let mut storage = GitRefsStorage::new(dir.path().to_str().unwrap(), "test-agent").unwrap();
+        storage.store(&note).unwrap();
+        assert!(storage.exists("note-1", "note").unwrap());
+
+        let mut strict = storage.with_strict_entities(true);
+        let err = strict
+            .store(&GenericEntity {
+                id: "note-2".to_string(),
+                ..note
+            })
+            .unwrap_err();
+        assert!(matches!(err, EngramError::Validation(_)), "{:?}", err);
+        assert!(!strict.exists("note-2", "note").unwrap());
     }
 
     #[test]
@@ -1573,6 +2881,146 @@ mod tests {
     ///
     /// Previously, `get_connected_entities` was a stub that always returned `Ok(Vec::new())`.
     /// This test verifies the fix: BFS now traverses the relationship index correctly.
+    #[test]
+    fn test_time_range_query_reads_only_matching_blobs() {
+        let dir = tempdir().unwrap();
+        let mut storage = GitRefsStorage::new(dir.path().to_str().unwrap(), "test-agent").unwrap();
+
+        // 5,000 entities spread evenly over a year
+        let year_start = Utc::now() - chrono::Duration::days(365);
+        let step = chrono::Duration::days(365) / 5000;
+        let entities: Vec<GenericEntity> = (0..5000)
+            .map(|i| {
+                let mut entity =
+                    create_test_entity(&format!("dated-{:04}", i), ["alice", "bob"][i % 2]);
+                entity.timestamp = year_start + step * i as i32;
+                entity
+            })
+            .collect();
+        storage.bulk_store(&entities).unwrap();
+
+        let start = year_start + chrono::Duration::days(100);
+        let end = start + chrono::Duration::weeks(1);
+        let expected = entities
+            .iter()
+            .filter(|e| e.timestamp >= start && e.timestamp <= end)
+            .count();
+
+        let reads_before = storage.blob_reads.load(Ordering::Relaxed);
+        let found = storage.query_by_time_range(start, end).unwrap();
+        let reads = storage.blob_reads.load(Ordering::Relaxed) - reads_before;
+
+        assert_eq!(found.len(), expected);
+        assert!(found
+            .iter()
+            .all(|e| e.timestamp >= start && e.timestamp <= end));
+        assert_eq!(
+            reads, expected,
+            "read {} blobs for {} matches",
+            reads, expected
+        );
+
+        // Agent filters prune the same way
+        let reads_before = storage.blob_reads.load(Ordering::Relaxed);
+        let filter = QueryFilter {
+            entity_type: Some("task".to_string()),
+            agent: Some("alice".to_string()),
+            time_range: Some(TimeRange { start, end }),
+            ..Default::default()
+        };
+        let alice = storage.query(&filter).unwrap();
+        let reads = storage.blob_reads.load(Ordering::Relaxed) - reads_before;
+        assert!(alice.entities.iter().all(|e| e.agent == "alice"));
+        assert_eq!(reads, alice.total_count);
+    }
+
+    #[test]
+    fn test_count_answers_from_ref_names() {
+        let dir = tempdir().unwrap();
+        let mut storage = GitRefsStorage::new(dir.path().to_str().unwrap(), "test-agent").unwrap();
+
+        let day_start = Utc::now() - chrono::Duration::days(1);
+        let entities: Vec<GenericEntity> = (0..200)
+            .map(|i| {
+                let mut entity =
+                    create_test_entity(&format!("counted-{:03}", i), ["alice", "bob"][i % 2]);
+                entity.timestamp = day_start + chrono::Duration::minutes(7 * i as i64);
+                entity
+            })
+            .collect();
+        storage.bulk_store(&entities).unwrap();
+
+        let start = day_start + chrono::Duration::hours(3);
+        let end = start + chrono::Duration::hours(5);
+        let filters = [
+            QueryFilter {
+                entity_type: Some("task".to_string()),
+                ..Default::default()
+            },
+            QueryFilter {
+                entity_type: Some("task".to_string()),
+                agent: Some("alice".to_string()),
+                ..Default::default()
+            },
+            QueryFilter {
+                entity_type: Some("task".to_string()),
+                agent: Some("bob".to_string()),
+                time_range: Some(TimeRange { start, end }),
+                ..Default::default()
+            },
+        ];
+        for filter in &filters {
+            let expected = entities
+                .iter()
+                .filter(|e| matches_filter(*e, filter))
+                .count();
+            let reads_before = storage.blob_reads.load(Ordering::Relaxed);
+            assert_eq!(storage.count(filter).unwrap(), expected);
+            assert_eq!(storage.blob_reads.load(Ordering::Relaxed), reads_before);
+        }
+    }
+
+    #[test]
+    fn test_time_index_follows_updates_and_deletes() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().to_str().unwrap();
+        let mut storage = GitRefsStorage::new(path, "test-agent").unwrap();
+        let index_refs = |storage: &GitRefsStorage| -> Vec<String> {
+            let repo = storage.repository.lock().unwrap();
+            let refs = repo
+                .references_glob("refs/engram/task/by-time/*")
+                .unwrap()
+                .filter_map(|r| r.ok()?.name().map(str::to_string))
+                .collect();
+            refs
+        };
+
+        let mut entity = create_test_entity("moving", "alice");
+        entity.timestamp = Utc::now() - chrono::Duration::days(30);
+        storage.store(&entity).unwrap();
+        entity.timestamp = Utc::now();
+        storage.store(&entity).unwrap();
+
+        let refs = index_refs(&storage);
+        assert_eq!(refs, vec![time_index_ref(&entity)]);
+        let recent = storage
+            .query_by_time_range(Utc::now() - chrono::Duration::days(1), Utc::now())
+            .unwrap();
+        assert_eq!(recent.len(), 1);
+
+        // Entities without an index ref are indexed when the workspace is opened
+        {
+            let repo = storage.repository.lock().unwrap();
+            delete_ref(&repo, &refs[0]).unwrap();
+        }
+        let storage = GitRefsStorage::new(path, "test-agent").unwrap();
+        assert_eq!(index_refs(&storage), refs);
+
+        let mut storage = storage;
+        storage.delete("moving", "task").unwrap();
+        assert!(index_refs(&storage).is_empty());
+    }
+
     #[test]
     fn test_get_connected_entities_bfs_returns_stored_relationships() {
         use crate::entities::{EntityRelationType, EntityRelationship};
@@ -1755,6 +3203,23 @@ mod tests {
         let _ = storage.project_id.len();
     }
 
+    #[test]
+    fn test_open_read_only_writes_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().to_str().unwrap();
+        assert!(GitRefsStorage::open_read_only(path, "test").is_err());
+
+        let repo = git2::Repository::init(dir.path()).unwrap();
+        let storage = GitRefsStorage::open_read_only(path, "test").unwrap();
+        assert!(storage.project_id.is_empty());
+        assert!(repo.is_empty().unwrap());
+        assert!(repo.references().unwrap().next().is_none());
+
+        let written = GitRefsStorage::new(path, "test").unwrap();
+        let reopened = GitRefsStorage::open_read_only(path, "test").unwrap();
+        assert_eq!(reopened.project_id, written.project_id);
+    }
+
     #[test]
     fn test_project_id_existing_repo_with_commits() {
         let dir = tempfile::tempdir().unwrap();
@@ -1774,12 +3239,16 @@ mod tests {
     }
 
     fn make_test_entity(entity_type: &str) -> GenericEntity {
-        GenericEntity {
-            id: uuid::Uuid::new_v4().to_string(),
-            entity_type: entity_type.to_string(),
-            agent: "test".to_string(),
-            timestamp: Utc::now(),
-            data: json!({"title": "test"}),
+        match entity_type {
+            "context" => Context::new(
+                "test".to_string(),
+                "test content".to_string(),
+                "test".to_string(),
+                ContextRelevance::Low,
+                "test".to_string(),
+            )
+            .to_generic(),
+            _ => create_test_entity(&uuid::Uuid::new_v4().to_string(), "test"),
         }
     }
 
@@ -1842,6 +3311,91 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_writes_record_git_identity() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = git2::Repository::init(dir.path()).unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Ada Human").unwrap();
+        config.set_str("user.email", "ada@example.com").unwrap();
+
+        let mut storage = GitRefsStorage::new(dir.path().to_str().unwrap(), "test").unwrap();
+        let ada = GitIdentity {
+            name: "Ada Human".to_string(),
+            email: "ada@example.com".to_string(),
+        };
+        assert_eq!(storage.author_identity(), Some(ada.clone()));
+
+        let entity = make_test_entity("task");
+        storage.store(&entity).unwrap();
+        let stored = storage.get(&entity.id, "task").unwrap().unwrap();
+        assert_eq!(
+            stored.data[CREATED_BY_IDENTITY_FIELD],
+            "Ada Human <ada@example.com>"
+        );
+
+        // A later write by someone else keeps the creator and records the
+        // new author on its version
+        config.set_str("user.name", "Bob Human").unwrap();
+        config.set_str("user.email", "bob@example.com").unwrap();
+        storage.store(&entity).unwrap();
+        let stored = storage.get(&entity.id, "task").unwrap().unwrap();
+        assert_eq!(
+            stored.data[CREATED_BY_IDENTITY_FIELD],
+            "Ada Human <ada@example.com>"
+        );
+
+        let history = storage.entity_history("task", &entity.id).unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].version, 1);
+        assert_eq!(history[0].author, Some(ada));
+        assert_eq!(
+            history[1].author.as_ref().map(|a| a.email.as_str()),
+            Some("bob@example.com")
+        );
+    }
+
+    #[test]
+    fn test_encrypted_fields_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = WorkspaceKey::generate().unwrap();
+        std::fs::write(dir.path().join("engram.key"), key.encode()).unwrap();
+        std::fs::write(
+            dir.path().join("engram.yaml"),
+            "encryption:\n  fields: [context.content]\n  key_file: engram.key\n",
+        )
+        .unwrap();
+
+        let mut storage = GitRefsStorage::new(dir.path().to_str().unwrap(), "test").unwrap();
+        let mut entity = make_test_entity("context");
+        entity.data["content"] = json!("db password is hunter2");
+        storage.store(&entity).unwrap();
+
+        let stored = storage.get(&entity.id, "context").unwrap().unwrap();
+        assert_eq!(stored.data["content"], "db password is hunter2");
+        let queried = storage
+            .get_many(std::slice::from_ref(&entity.id), "context")
+            .unwrap();
+        assert_eq!(
+            queried[0].as_ref().unwrap().data["content"],
+            "db password is hunter2"
+        );
+
+        // The blob in the repository holds only ciphertext
+        let repo = git2::Repository::open(dir.path()).unwrap();
+        let oid = repo
+            .refname_to_id(&format!("refs/engram/context/{}", entity.id))
+            .unwrap();
+        let raw = String::from_utf8(repo.find_blob(oid).unwrap().content().to_vec()).unwrap();
+        assert!(!raw.contains("hunter2"));
+        assert!(raw.contains(key.fingerprint()));
+
+        // Without the key the read fails naming the key it needs
+        let keyless = storage.with_encryption(WorkspaceEncryption::default());
+        let error = keyless.get(&entity.id, "context").unwrap_err().to_string();
+        assert!(error.contains(key.fingerprint()), "{}", error);
+    }
+
     #[test]
     fn test_consistency_check_clean_storage() {
         let dir = tempfile::tempdir().unwrap();
@@ -1884,6 +3438,182 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parallel_reads_match_serial_reads() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut storage = GitRefsStorage::new(dir.path().to_str().unwrap(), "test").unwrap();
+        let ids: Vec<String> = (0..PARALLEL_READ_THRESHOLD + 50)
+            .map(|i| format!("task-{:04}", i))
+            .collect();
+        let entities: Vec<GenericEntity> = ids
+            .iter()
+            .map(|id| create_test_entity(id, "test"))
+            .collect();
+        storage.bulk_store(&entities).unwrap();
+        let repo = git2::Repository::open(dir.path()).unwrap();
+        let truncated = repo.blob(br#"{"id": "trunc"#).unwrap();
+        repo.reference("refs/engram/task/trunc-1", truncated, true, "test")
+            .unwrap();
+
+        let parallel = storage.clone().with_read_threads(4).unwrap();
+        let serial = storage.with_read_threads(1).unwrap();
+        let sorted_ids = |storage: &GitRefsStorage| {
+            let mut ids: Vec<String> = storage
+                .get_all("task")
+                .unwrap()
+                .into_iter()
+                .map(|e| e.id)
+                .collect();
+            ids.sort();
+            ids
+        };
+        assert_eq!(sorted_ids(&parallel), ids);
+        assert_eq!(sorted_ids(&serial), ids);
+
+        // Batch lookups keep the order of the requested IDs
+        let mut requested = ids.clone();
+        requested.insert(1, "missing".to_string());
+        requested.reverse();
+        let found = parallel.get_many(&requested, "task").unwrap();
+        assert_eq!(found.len(), requested.len());
+        for (id, entity) in requested.iter().zip(&found) {
+            match entity {
+                Some(entity) => assert_eq!(&entity.id, id),
+                None => assert_eq!(id, "missing"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_fsck_quarantines_truncated_blob() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut storage = GitRefsStorage::new(dir.path().to_str().unwrap(), "test").unwrap();
+        let task = create_test_entity("good-task", "test");
+        storage.store(&task).unwrap();
+
+        // A crash mid-write left a ref pointing at a truncated blob
+        let repo = git2::Repository::open(dir.path()).unwrap();
+        let truncated = repo.blob(br#"{"id": "trunc"#).unwrap();
+        repo.reference("refs/engram/task/trunc-1", truncated, true, "test")
+            .unwrap();
+
+        let filter = QueryFilter {
+            entity_type: Some("task".to_string()),
+            ..Default::default()
+        };
+        let result = storage.query(&filter).unwrap();
+        assert_eq!(result.entities.len(), 1);
+        assert_eq!(result.entities[0].id, "good-task");
+        assert_eq!(storage.get_all("task").unwrap().len(), 1);
+
+        let report = storage.fsck(false).unwrap();
+        assert_eq!(report.checked, 2);
+        assert_eq!(report.corrupt.len(), 1);
+        assert_eq!(report.corrupt[0].ref_name, "refs/engram/task/trunc-1");
+        assert_eq!(report.corrupt[0].quarantined_to, None);
+        assert!(repo.find_reference("refs/engram/task/trunc-1").is_ok());
+
+        let report = storage.fsck(true).unwrap();
+        assert_eq!(
+            report.corrupt[0].quarantined_to.as_deref(),
+            Some("refs/engram/corrupt/task/trunc-1")
+        );
+        assert!(repo.find_reference("refs/engram/task/trunc-1").is_err());
+        assert_eq!(
+            repo.refname_to_id("refs/engram/corrupt/task/trunc-1")
+                .unwrap(),
+            truncated
+        );
+
+        assert!(storage.fsck(false).unwrap().is_clean());
+        assert!(storage
+            .consistency_check()
+            .unwrap()
+            .invalid_json_refs
+            .is_empty());
+        assert_eq!(
+            storage.get("good-task", "task").unwrap().unwrap().id,
+            "good-task"
+        );
+    }
+
+    #[test]
+    fn test_count_skips_corrupt_refs_before_quarantine() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut storage = GitRefsStorage::new(dir.path().to_str().unwrap(), "test").unwrap();
+        storage
+            .store(&create_test_entity("good-task", "test"))
+            .unwrap();
+        let repo = git2::Repository::open(dir.path()).unwrap();
+        let truncated = repo.blob(br#"{"id": "trunc"#).unwrap();
+        repo.reference("refs/engram/task/trunc-1", truncated, true, "test")
+            .unwrap();
+
+        let filter = QueryFilter {
+            entity_type: Some("task".to_string()),
+            ..Default::default()
+        };
+        // The unindexed ref is read, so its truncated blob is not counted
+        assert_eq!(storage.query(&filter).unwrap().total_count, 1);
+        assert_eq!(storage.count(&filter).unwrap(), 1);
+
+        // Nor is a stored entity whose ref was pointed at the truncated blob
+        storage
+            .store(&create_test_entity("bad-task", "test"))
+            .unwrap();
+        repo.reference("refs/engram/task/bad-task", truncated, true, "test")
+            .unwrap();
+        assert_eq!(storage.query(&filter).unwrap().total_count, 1);
+        assert_eq!(storage.count(&filter).unwrap(), 1);
+
+        storage.fsck(true).unwrap();
+        assert_eq!(storage.count(&filter).unwrap(), 1);
+        assert_eq!(storage.query(&filter).unwrap().total_count, 1);
+        let all_types = QueryFilter::default();
+        assert_eq!(
+            storage.count(&all_types).unwrap(),
+            storage.query(&all_types).unwrap().total_count
+        );
+    }
+
+    #[test]
+    fn test_get_stats_counts_entities_by_agent() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut storage = GitRefsStorage::new(dir.path().to_str().unwrap(), "test").unwrap();
+        for (id, agent) in [("t1", "alice"), ("t2", "alice"), ("t3", "bob")] {
+            storage.store(&create_test_entity(id, agent)).unwrap();
+        }
+        // Re-storing under another agent moves the entity's index ref
+        let mut moved = create_test_entity("t3", "alice");
+        moved.timestamp += chrono::Duration::seconds(5);
+        storage.store(&moved).unwrap();
+
+        let stats = storage.get_stats().unwrap();
+        assert_eq!(stats.total_entities, 3);
+        assert_eq!(
+            stats.entities_by_agent,
+            HashMap::from([("alice".to_string(), 3)])
+        );
+    }
+
+    #[test]
+    fn test_fsck_reports_ref_path_mismatch() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut storage = GitRefsStorage::new(dir.path().to_str().unwrap(), "test").unwrap();
+        let task = create_test_entity("real-id", "test");
+        storage.store(&task).unwrap();
+
+        let repo = git2::Repository::open(dir.path()).unwrap();
+        let oid = repo.refname_to_id("refs/engram/task/real-id").unwrap();
+        repo.reference("refs/engram/task/other-id", oid, true, "test")
+            .unwrap();
+
+        let report = storage.fsck(false).unwrap();
+        assert_eq!(report.corrupt.len(), 1);
+        assert_eq!(report.corrupt[0].entity_id, "other-id");
+        assert!(report.corrupt[0].problem.contains("real-id"));
+    }
+
     #[test]
     fn test_consistency_check_detects_missing_fields() {
         let dir = tempfile::tempdir().unwrap();
@@ -6,9 +6,10 @@
     clippy::needless_borrows_for_generic_args
 )]
 
+use super::query::{apply_filter_borrowed, matches_filter, EntityRef};
 use super::{
     GitCommit, MemoryEntity, QueryFilter, QueryResult, RelationshipIndex, RelationshipStats,
-    RelationshipStorage, SortOrder, Storage, StorageStats, TraversalAlgorithm,
+    RelationshipStorage, Storage, StorageStats, TraversalAlgorithm,
 };
 use crate::entities::{
     Entity, EntityRelationType, EntityRelationship, GenericEntity, RelationshipDirection,
@@ -18,14 +19,20 @@ use crate::error::EngramError;
 use chrono::{DateTime, Utc};
 use serde_json::Value;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 
 /// In-memory storage backend
+///
+/// Clones share the same entity map and relationship index.
+#[derive(Clone)]
 pub struct MemoryStorage {
     entities: Arc<Mutex<HashMap<String, MemoryEntity>>>,
     current_agent: String,
     commits: Vec<GitCommit>,
     relationship_index: Arc<Mutex<RelationshipIndex>>,
+    /// Entities copied out of the map by `query`, for tests
+    materialized: Arc<AtomicUsize>,
 }
 
 impl MemoryStorage {
@@ -36,12 +43,107 @@ impl MemoryStorage {
             current_agent: agent.to_string(),
             commits: Vec::new(),
             relationship_index: Arc::new(Mutex::new(RelationshipIndex::new())),
+            materialized: Arc::new(AtomicUsize::new(0)),
         }
     }
 }
 
+/// Top level of a TOML storage fixture
+#[derive(serde::Serialize, serde::Deserialize)]
+struct TomlFixture {
+    #[serde(default)]
+    entities: Vec<GenericEntity>,
+}
+
+/// TOML has no null, so null object fields are dropped; they read back as
+/// missing, which entity types deserialize as `None` or their default
+fn strip_nulls(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            map.retain(|_, v| !v.is_null());
+            map.values_mut().for_each(strip_nulls);
+        }
+        Value::Array(items) => items.iter_mut().for_each(strip_nulls),
+        _ => {}
+    }
+}
+
+impl MemoryStorage {
+    /// Every stored entity, ordered by type and ID
+    pub fn all_entities(&self) -> Vec<GenericEntity> {
+        let mut entities: Vec<GenericEntity> = self
+            .entities
+            .lock()
+            .unwrap()
+            .values()
+            .filter_map(|memory_entity| {
+                Some(GenericEntity {
+                    id: memory_entity.id.clone(),
+                    entity_type: memory_entity.entity_type.clone(),
+                    agent: memory_entity.agent.clone(),
+                    timestamp: memory_entity.timestamp,
+                    data: memory_entity.get_field("entity")?.clone(),
+                })
+            })
+            .collect();
+        entities.sort_by(|a, b| (&a.entity_type, &a.id).cmp(&(&b.entity_type, &b.id)));
+        entities
+    }
+
+    /// Load storage from a TOML fixture: an `[[entities]]` array of tables
+    /// with `id`, `entity_type`, `agent`, `timestamp` and `data`
+    pub fn from_toml(toml_str: &str) -> Result<Self, EngramError> {
+        let fixture: TomlFixture = toml::from_str(toml_str)
+            .map_err(|e| EngramError::Deserialization(format!("Invalid TOML fixture: {}", e)))?;
+        let mut storage = Self::new("default");
+        for entity in &fixture.entities {
+            storage.store(entity)?;
+        }
+        storage.rebuild_relationship_index()?;
+        Ok(storage)
+    }
+
+    /// Write every entity as a TOML fixture readable by [`MemoryStorage::from_toml`].
+    /// Flat `data` is written as an inline table.
+    pub fn to_toml(&self) -> Result<String, EngramError> {
+        let mut entities = self.all_entities();
+        for entity in &mut entities {
+            strip_nulls(&mut entity.data);
+        }
+
+        let serialized = toml::to_string(&TomlFixture { entities }).map_err(|e| {
+            EngramError::InvalidOperation(format!("Cannot write TOML fixture: {}", e))
+        })?;
+        let mut document: toml_edit::DocumentMut = serialized.parse().map_err(|e| {
+            EngramError::InvalidOperation(format!("Cannot write TOML fixture: {}", e))
+        })?;
+        if let Some(tables) = document
+            .get_mut("entities")
+            .and_then(|item| item.as_array_of_tables_mut())
+        {
+            for table in tables.iter_mut() {
+                let flat = table
+                    .get("data")
+                    .and_then(|data| data.as_table())
+                    .is_some_and(|data| data.iter().all(|(_, item)| item.is_value()));
+                if flat {
+                    if let Some(data) = table.remove("data").and_then(|d| d.into_table().ok()) {
+                        table.insert("data", toml_edit::value(data.into_inline_table()));
+                    }
+                }
+            }
+        }
+        Ok(document.to_string())
+    }
+}
+
 impl Storage for MemoryStorage {
     fn store(&mut self, entity: &GenericEntity) -> Result<(), EngramError> {
+        let previous = self.get(&entity.id, &entity.entity_type)?;
+        let mut entity = entity.clone();
+        entity.record_modification(&self.current_agent, previous.as_ref());
+        let entity = &entity;
+
         let memory_entity = MemoryEntity::new(
             entity.id.clone(),
             entity.entity_type.clone(),
@@ -92,6 +194,13 @@ impl Storage for MemoryStorage {
         }
     }
 
+    fn exists(&self, id: &str, entity_type: &str) -> Result<bool, EngramError> {
+        let entities = self.entities.lock().unwrap();
+        Ok(entities
+            .get(id)
+            .is_some_and(|e| e.entity_type == entity_type && e.get_field("entity").is_some()))
+    }
+
     fn query_by_agent(
         &self,
         agent: &str,
@@ -160,10 +269,7 @@ impl Storage for MemoryStorage {
         let mut entities = self.entities.lock().unwrap();
         if let Some(memory_entity) = entities.remove(id) {
             if memory_entity.entity_type != entity_type {
-                return Err(EngramError::NotFound(format!(
-                    "Entity {} of type {} not found",
-                    id, entity_type
-                )));
+                return Err(EngramError::not_found(entity_type, id));
             }
 
             // Create a commit record
@@ -184,7 +290,7 @@ impl Storage for MemoryStorage {
 
             Ok(())
         } else {
-            Err(EngramError::NotFound(format!("Entity {} not found", id)))
+            Err(EngramError::not_found("entity", id))
         }
     }
 
@@ -231,120 +337,26 @@ impl Storage for MemoryStorage {
     }
 
     fn query(&self, filter: &QueryFilter) -> Result<QueryResult, EngramError> {
+        // Filter and sort the stored fields in place; only the returned
+        // page is copied out
         let entities = self.entities.lock().unwrap();
-        let mut all_entities = Vec::new();
-
-        for memory_entity in entities.values() {
-            if let Some(entity_type_filter) = &filter.entity_type {
-                if memory_entity.entity_type != *entity_type_filter {
-                    continue;
-                }
-            }
-
-            if let Some(agent_filter) = &filter.agent {
-                if memory_entity.agent != *agent_filter {
-                    continue;
-                }
-            }
-
-            if let Some(time_range) = &filter.time_range {
-                if memory_entity.timestamp < time_range.start
-                    || memory_entity.timestamp > time_range.end
-                {
-                    continue;
-                }
-            }
-
-            if let Some(entity_data) = memory_entity.get_field("entity") {
-                if let Some(search_query) = &filter.text_search {
-                    let search_lower: String = search_query.to_lowercase();
-                    let entity_json = serde_json::to_string(&entity_data).unwrap_or_default();
-                    if !entity_json.to_lowercase().contains(&search_lower) {
-                        continue;
-                    }
-                }
-
-                let mut matches_field_filters = true;
-                for (field, expected_value) in &filter.field_filters {
-                    if let Some(actual_value) = entity_data.get(field) {
-                        if actual_value != expected_value {
-                            matches_field_filters = false;
-                            break;
-                        }
-                    } else {
-                        matches_field_filters = false;
-                        break;
-                    }
-                }
-
-                if !matches_field_filters {
-                    continue;
-                }
-
-                let generic = GenericEntity {
-                    id: memory_entity.id.clone(),
-                    entity_type: memory_entity.entity_type.clone(),
-                    agent: memory_entity.agent.clone(),
+        let candidates = entities
+            .values()
+            .filter_map(|memory_entity| {
+                Some(EntityRef {
+                    id: &memory_entity.id,
+                    entity_type: &memory_entity.entity_type,
+                    agent: &memory_entity.agent,
                     timestamp: memory_entity.timestamp,
-                    data: entity_data.clone(),
-                };
-
-                all_entities.push(generic);
-            }
-        }
-
-        if let Some(sort_field) = &filter.sort_by {
-            all_entities.sort_by(|a, b| {
-                let a_value = a.data.get(sort_field);
-                let b_value = b.data.get(sort_field);
-
-                let cmp = match (a_value, b_value) {
-                    (Some(a_val), Some(b_val)) => {
-                        let a_str = a_val.as_str().unwrap_or("");
-                        let b_str = b_val.as_str().unwrap_or("");
-                        a_str.cmp(b_str)
-                    }
-                    (Some(_), None) => std::cmp::Ordering::Greater,
-                    (None, Some(_)) => std::cmp::Ordering::Less,
-                    (None, None) => std::cmp::Ordering::Equal,
-                };
-
-                match filter.sort_order {
-                    SortOrder::Asc => cmp,
-                    SortOrder::Desc => cmp.reverse(),
-                }
-            });
-        } else {
-            all_entities.sort_by(|a, b| match filter.sort_order {
-                SortOrder::Asc => a.timestamp.cmp(&b.timestamp),
-                SortOrder::Desc => b.timestamp.cmp(&a.timestamp),
-            });
-        }
-
-        let total_count = all_entities.len();
-        let offset = filter.offset.unwrap_or(0);
-
-        let entities = if offset < total_count {
-            match filter.limit {
-                Some(limit) => {
-                    let end_idx = std::cmp::min(offset + limit, total_count);
-                    all_entities[offset..end_idx].to_vec()
-                }
-                None => all_entities[offset..].to_vec(),
-            }
-        } else {
-            Vec::new()
-        };
-
-        let has_more = filter
-            .limit
-            .map_or(false, |_| offset + entities.len() < total_count);
+                    data: memory_entity.get_field("entity")?,
+                })
+            })
+            .collect();
 
-        Ok(QueryResult {
-            entities,
-            total_count,
-            has_more,
-        })
+        let page = apply_filter_borrowed(candidates, filter)?;
+        self.materialized
+            .fetch_add(page.entities.len(), Ordering::Relaxed);
+        Ok(page.map(|entity| entity.to_entity()))
     }
 
     fn query_by_type(
@@ -391,14 +403,24 @@ impl Storage for MemoryStorage {
     }
 
     fn count(&self, filter: &QueryFilter) -> Result<usize, EngramError> {
-        let count_filter = QueryFilter {
-            limit: None,
-            offset: None,
-            ..filter.clone()
-        };
-
-        let result = self.query(&count_filter)?;
-        Ok(result.total_count)
+        // Match against the stored fields in place rather than cloning each
+        // entity out as `query` does
+        let entities = self.entities.lock().unwrap();
+        Ok(entities
+            .values()
+            .filter(|memory_entity| {
+                memory_entity.get_field("entity").is_some_and(|data| {
+                    let entity = EntityRef {
+                        id: &memory_entity.id,
+                        entity_type: &memory_entity.entity_type,
+                        agent: &memory_entity.agent,
+                        timestamp: memory_entity.timestamp,
+                        data,
+                    };
+                    matches_filter(entity, filter)
+                })
+            })
+            .count())
     }
 
     fn get_all(&self, entity_type: &str) -> Result<Vec<GenericEntity>, EngramError> {
@@ -719,6 +741,8 @@ mod tests {
             agent: "test-agent".to_string(),
             start_time: Utc::now(),
             end_time: None,
+            due_at: None,
+            estimate_seconds: None,
             parent: None,
             children: Vec::new(),
             tags: Vec::new(),
@@ -779,21 +803,58 @@ mod tests {
 
     #[test]
     fn test_query_by_agent() {
-        let mut storage = MemoryStorage::new("test-agent");
+        let storage = MemoryStorage::from_toml(include_str!("fixtures/scenario.toml")).unwrap();
 
-        let task1 = create_test_task("task-1");
-        let mut task2 = create_test_task("task-2");
-        task2.agent = "other-agent".to_string();
-
-        storage.store(&task1.to_generic()).unwrap();
-        storage.store(&task2.to_generic()).unwrap();
-
-        let results = storage.query_by_agent("test-agent", None).unwrap();
+        let results = storage.query_by_agent("test-agent", Some("task")).unwrap();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].id, "task-1");
 
-        let results_other = storage.query_by_agent("other-agent", None).unwrap();
+        let results_other = storage.query_by_agent("other-agent", Some("task")).unwrap();
         assert_eq!(results_other.len(), 1);
         assert_eq!(results_other[0].id, "task-2");
     }
+
+    #[test]
+    fn test_toml_round_trip() {
+        let storage = MemoryStorage::from_toml(include_str!("fixtures/scenario.toml")).unwrap();
+        let dependents = storage.get_entity_relationships("task-1").unwrap();
+        assert_eq!(dependents.len(), 1);
+        assert_eq!(dependents[0].source_id, "task-2");
+
+        let reloaded = MemoryStorage::from_toml(&storage.to_toml().unwrap()).unwrap();
+        assert_eq!(reloaded.to_toml().unwrap(), storage.to_toml().unwrap());
+
+        let original = Task::from_generic(storage.get("task-1", "task").unwrap().unwrap()).unwrap();
+        let round_tripped =
+            Task::from_generic(reloaded.get("task-1", "task").unwrap().unwrap()).unwrap();
+        assert_eq!(original.to_generic().data, round_tripped.to_generic().data);
+
+        let knowledge = reloaded.get("knowledge-1", "knowledge").unwrap().unwrap();
+        assert_eq!(knowledge.data["metadata"]["source"]["refs"][0], "task-2");
+    }
+    #[test]
+    fn test_count_does_not_materialize_entities() {
+        let mut storage = MemoryStorage::new("test-agent");
+        for i in 0..10_000 {
+            let mut task = create_test_task(&format!("task-{}", i));
+            if i % 4 == 0 {
+                task.status = TaskStatus::Done;
+            }
+            storage.store(&task.to_generic()).unwrap();
+        }
+        let before = storage.materialized.load(Ordering::Relaxed);
+
+        let all = QueryFilter {
+            entity_type: Some("task".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(storage.count(&all).unwrap(), 10_000);
+
+        let mut done = all.clone();
+        done.field_filters
+            .insert("status".to_string(), Value::String("done".to_string()));
+        assert_eq!(storage.count(&done).unwrap(), 2_500);
+
+        assert_eq!(storage.materialized.load(Ordering::Relaxed), before);
+    }
 }
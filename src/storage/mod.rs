@@ -2,18 +2,38 @@
 //!
 //! Provides Git-based persistence with content-addressable storage
 //! and multi-agent synchronization capabilities.
-
+//!
+//! [`GitRefsStorage`] is the primary backend: the CLI, the BDD suite and
+//! the storage conformance tests in [`query`] all run against it.
+//! [`MemoryStorage`] passes the same conformance suite and backs unit
+//! tests. Workspaces written by the older directory-based git backend are
+//! converted with [`crate::migration`].
+
+pub mod cached;
+pub mod conflict_resolvers;
+pub mod dry_run;
+pub mod encryption;
 pub mod git_refs_storage;
 pub mod memory_entity;
 pub mod memory_only_storage;
+pub mod query;
+pub mod quota;
+pub mod read_only;
 pub mod relationship_storage;
+pub mod workspace_lock;
 
+pub use cached::*;
+pub use conflict_resolvers::*;
+pub use dry_run::*;
+pub use encryption::*;
 pub use git_refs_storage::*;
 pub use memory_entity::*;
 pub use memory_only_storage::*;
+pub use quota::*;
+pub use read_only::*;
 pub use relationship_storage::*;
 
-use crate::entities::GenericEntity;
+use crate::entities::{apply_patch, GenericEntity, JsonPatchOp};
 use crate::error::EngramError;
 use serde_json::Value;
 use std::collections::HashMap;
@@ -24,6 +44,8 @@ pub struct QueryFilter {
     pub entity_type: Option<String>,
     pub agent: Option<String>,
     pub text_search: Option<String>,
+    /// Let `text_search` match words within a small edit distance
+    pub fuzzy: bool,
     pub field_filters: HashMap<String, Value>,
     pub time_range: Option<TimeRange>,
     pub sort_by: Option<String>,
@@ -38,6 +60,7 @@ impl Default for QueryFilter {
             entity_type: None,
             agent: None,
             text_search: None,
+            fuzzy: false,
             field_filters: HashMap::new(),
             time_range: None,
             sort_by: None,
@@ -61,13 +84,28 @@ pub enum SortOrder {
 }
 
 /// Query result with pagination info
+///
+/// Backends return owned entities. The shared filter code also produces
+/// results over borrowed [`query::EntityRef`] views or plain indices, so a
+/// page can be chosen before anything is copied.
 #[derive(Debug, Clone)]
-pub struct QueryResult {
-    pub entities: Vec<GenericEntity>,
+pub struct QueryResult<E = GenericEntity> {
+    pub entities: Vec<E>,
     pub total_count: usize,
     pub has_more: bool,
 }
 
+impl<E> QueryResult<E> {
+    /// Convert each entity of the page, keeping the pagination info
+    pub fn map<T>(self, f: impl FnMut(E) -> T) -> QueryResult<T> {
+        QueryResult {
+            entities: self.entities.into_iter().map(f).collect(),
+            total_count: self.total_count,
+            has_more: self.has_more,
+        }
+    }
+}
+
 /// Storage trait for different storage backends
 pub trait Storage: Send {
     /// Store a memory entity
@@ -76,6 +114,26 @@ pub trait Storage: Send {
     /// Retrieve an entity by ID and type
     fn get(&self, id: &str, entity_type: &str) -> Result<Option<GenericEntity>, EngramError>;
 
+    /// Check whether an entity exists without loading it
+    fn exists(&self, id: &str, entity_type: &str) -> Result<bool, EngramError> {
+        Ok(self.get(id, entity_type)?.is_some())
+    }
+
+    /// Retrieve several entities of one type; results are in the same order as `ids`
+    fn get_many(
+        &self,
+        ids: &[String],
+        entity_type: &str,
+    ) -> Result<Vec<Option<GenericEntity>>, EngramError> {
+        ids.iter().map(|id| self.get(id, entity_type)).collect()
+    }
+
+    /// Check an entity against the backend's write-time validation without
+    /// storing it
+    fn validate_for_store(&self, _entity: &GenericEntity) -> Result<(), EngramError> {
+        Ok(())
+    }
+
     /// Advanced query with filtering, sorting, and pagination
     fn query(&self, filter: &QueryFilter) -> Result<QueryResult, EngramError>;
 
@@ -113,6 +171,23 @@ pub trait Storage: Send {
     /// Count entities matching criteria
     fn count(&self, filter: &QueryFilter) -> Result<usize, EngramError>;
 
+    /// Apply a JSON Patch to a stored entity: load, patch, store
+    ///
+    /// Nothing is written if the entity does not exist or any operation
+    /// fails. A patch may not change the entity's ID or type.
+    fn patch(
+        &mut self,
+        id: &str,
+        entity_type: &str,
+        patch: Vec<JsonPatchOp>,
+    ) -> Result<(), EngramError> {
+        let mut entity = self
+            .get(id, entity_type)?
+            .ok_or_else(|| EngramError::not_found(entity_type, id))?;
+        apply_entity_patch(&mut entity, &patch)?;
+        self.store(&entity)
+    }
+
     /// Delete an entity
     fn delete(&mut self, id: &str, entity_type: &str) -> Result<(), EngramError>;
 
@@ -146,10 +221,294 @@ pub trait Storage: Send {
     /// Get statistics about stored entities
     fn get_stats(&self) -> Result<StorageStats, EngramError>;
 
+    /// Whether writes are refused or discarded, so callers can skip
+    /// optional writes such as cached reports
+    fn is_read_only(&self) -> bool {
+        false
+    }
+
     /// Cast to concrete type for accessing specific implementations
     fn as_any(&self) -> &dyn std::any::Any;
 }
 
+/// Boxed backends (as returned by `migration::open_backend`) forward to the backend
+/// Apply `patch` to a loaded entity, refusing patches that change its ID or type
+pub(crate) fn apply_entity_patch(
+    entity: &mut GenericEntity,
+    patch: &[JsonPatchOp],
+) -> Result<(), EngramError> {
+    let (id, entity_type) = (entity.id.clone(), entity.entity_type.clone());
+    apply_patch(entity, patch)?;
+    if entity.id != id || entity.entity_type != entity_type {
+        return Err(EngramError::InvalidOperation(
+            "A patch cannot change an entity's id or entity_type".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+impl<S: Storage + ?Sized> Storage for Box<S> {
+    fn store(&mut self, entity: &GenericEntity) -> Result<(), EngramError> {
+        (**self).store(entity)
+    }
+
+    fn get(&self, id: &str, entity_type: &str) -> Result<Option<GenericEntity>, EngramError> {
+        (**self).get(id, entity_type)
+    }
+
+    fn exists(&self, id: &str, entity_type: &str) -> Result<bool, EngramError> {
+        (**self).exists(id, entity_type)
+    }
+
+    fn validate_for_store(&self, entity: &GenericEntity) -> Result<(), EngramError> {
+        (**self).validate_for_store(entity)
+    }
+
+    fn get_many(
+        &self,
+        ids: &[String],
+        entity_type: &str,
+    ) -> Result<Vec<Option<GenericEntity>>, EngramError> {
+        (**self).get_many(ids, entity_type)
+    }
+
+    fn query(&self, filter: &QueryFilter) -> Result<QueryResult, EngramError> {
+        (**self).query(filter)
+    }
+
+    fn query_by_agent(
+        &self,
+        agent: &str,
+        entity_type: Option<&str>,
+    ) -> Result<Vec<GenericEntity>, EngramError> {
+        (**self).query_by_agent(agent, entity_type)
+    }
+
+    fn query_by_time_range(
+        &self,
+        start: chrono::DateTime<chrono::Utc>,
+        end: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<GenericEntity>, EngramError> {
+        (**self).query_by_time_range(start, end)
+    }
+
+    fn query_by_type(
+        &self,
+        entity_type: &str,
+        filters: Option<&HashMap<String, Value>>,
+        limit: Option<usize>,
+        offset: Option<usize>,
+    ) -> Result<QueryResult, EngramError> {
+        (**self).query_by_type(entity_type, filters, limit, offset)
+    }
+
+    fn text_search(
+        &self,
+        query: &str,
+        entity_types: Option<&[String]>,
+        limit: Option<usize>,
+    ) -> Result<Vec<GenericEntity>, EngramError> {
+        (**self).text_search(query, entity_types, limit)
+    }
+
+    fn count(&self, filter: &QueryFilter) -> Result<usize, EngramError> {
+        (**self).count(filter)
+    }
+
+    fn patch(
+        &mut self,
+        id: &str,
+        entity_type: &str,
+        patch: Vec<JsonPatchOp>,
+    ) -> Result<(), EngramError> {
+        (**self).patch(id, entity_type, patch)
+    }
+
+    fn delete(&mut self, id: &str, entity_type: &str) -> Result<(), EngramError> {
+        (**self).delete(id, entity_type)
+    }
+
+    fn list_ids(&self, entity_type: &str) -> Result<Vec<String>, EngramError> {
+        (**self).list_ids(entity_type)
+    }
+
+    fn get_all(&self, entity_type: &str) -> Result<Vec<GenericEntity>, EngramError> {
+        (**self).get_all(entity_type)
+    }
+
+    fn sync(&mut self) -> Result<(), EngramError> {
+        (**self).sync()
+    }
+
+    fn current_branch(&self) -> Result<String, EngramError> {
+        (**self).current_branch()
+    }
+
+    fn create_branch(&mut self, branch_name: &str) -> Result<(), EngramError> {
+        (**self).create_branch(branch_name)
+    }
+
+    fn switch_branch(&mut self, branch_name: &str) -> Result<(), EngramError> {
+        (**self).switch_branch(branch_name)
+    }
+
+    fn merge_branches(&mut self, source: &str, target: &str) -> Result<(), EngramError> {
+        (**self).merge_branches(source, target)
+    }
+
+    fn history(&self, limit: Option<usize>) -> Result<Vec<GitCommit>, EngramError> {
+        (**self).history(limit)
+    }
+
+    fn bulk_store(&mut self, entities: &[GenericEntity]) -> Result<(), EngramError> {
+        (**self).bulk_store(entities)
+    }
+
+    fn get_stats(&self) -> Result<StorageStats, EngramError> {
+        (**self).get_stats()
+    }
+
+    fn is_read_only(&self) -> bool {
+        (**self).is_read_only()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        (**self).as_any()
+    }
+}
+
+/// Borrowed backends forward too, so a handler given `&mut S` can wrap it
+/// in an [`crate::Engram`]
+impl<S: Storage + ?Sized> Storage for &mut S {
+    fn store(&mut self, entity: &GenericEntity) -> Result<(), EngramError> {
+        (**self).store(entity)
+    }
+
+    fn get(&self, id: &str, entity_type: &str) -> Result<Option<GenericEntity>, EngramError> {
+        (**self).get(id, entity_type)
+    }
+
+    fn exists(&self, id: &str, entity_type: &str) -> Result<bool, EngramError> {
+        (**self).exists(id, entity_type)
+    }
+
+    fn validate_for_store(&self, entity: &GenericEntity) -> Result<(), EngramError> {
+        (**self).validate_for_store(entity)
+    }
+
+    fn get_many(
+        &self,
+        ids: &[String],
+        entity_type: &str,
+    ) -> Result<Vec<Option<GenericEntity>>, EngramError> {
+        (**self).get_many(ids, entity_type)
+    }
+
+    fn query(&self, filter: &QueryFilter) -> Result<QueryResult, EngramError> {
+        (**self).query(filter)
+    }
+
+    fn query_by_agent(
+        &self,
+        agent: &str,
+        entity_type: Option<&str>,
+    ) -> Result<Vec<GenericEntity>, EngramError> {
+        (**self).query_by_agent(agent, entity_type)
+    }
+
+    fn query_by_time_range(
+        &self,
+        start: chrono::DateTime<chrono::Utc>,
+        end: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<GenericEntity>, EngramError> {
+        (**self).query_by_time_range(start, end)
+    }
+
+    fn query_by_type(
+        &self,
+        entity_type: &str,
+        filters: Option<&HashMap<String, Value>>,
+        limit: Option<usize>,
+        offset: Option<usize>,
+    ) -> Result<QueryResult, EngramError> {
+        (**self).query_by_type(entity_type, filters, limit, offset)
+    }
+
+    fn text_search(
+        &self,
+        query: &str,
+        entity_types: Option<&[String]>,
+        limit: Option<usize>,
+    ) -> Result<Vec<GenericEntity>, EngramError> {
+        (**self).text_search(query, entity_types, limit)
+    }
+
+    fn count(&self, filter: &QueryFilter) -> Result<usize, EngramError> {
+        (**self).count(filter)
+    }
+
+    fn patch(
+        &mut self,
+        id: &str,
+        entity_type: &str,
+        patch: Vec<JsonPatchOp>,
+    ) -> Result<(), EngramError> {
+        (**self).patch(id, entity_type, patch)
+    }
+
+    fn delete(&mut self, id: &str, entity_type: &str) -> Result<(), EngramError> {
+        (**self).delete(id, entity_type)
+    }
+
+    fn list_ids(&self, entity_type: &str) -> Result<Vec<String>, EngramError> {
+        (**self).list_ids(entity_type)
+    }
+
+    fn get_all(&self, entity_type: &str) -> Result<Vec<GenericEntity>, EngramError> {
+        (**self).get_all(entity_type)
+    }
+
+    fn sync(&mut self) -> Result<(), EngramError> {
+        (**self).sync()
+    }
+
+    fn current_branch(&self) -> Result<String, EngramError> {
+        (**self).current_branch()
+    }
+
+    fn create_branch(&mut self, branch_name: &str) -> Result<(), EngramError> {
+        (**self).create_branch(branch_name)
+    }
+
+    fn switch_branch(&mut self, branch_name: &str) -> Result<(), EngramError> {
+        (**self).switch_branch(branch_name)
+    }
+
+    fn merge_branches(&mut self, source: &str, target: &str) -> Result<(), EngramError> {
+        (**self).merge_branches(source, target)
+    }
+
+    fn history(&self, limit: Option<usize>) -> Result<Vec<GitCommit>, EngramError> {
+        (**self).history(limit)
+    }
+
+    fn bulk_store(&mut self, entities: &[GenericEntity]) -> Result<(), EngramError> {
+        (**self).bulk_store(entities)
+    }
+
+    fn get_stats(&self) -> Result<StorageStats, EngramError> {
+        (**self).get_stats()
+    }
+
+    fn is_read_only(&self) -> bool {
+        (**self).is_read_only()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        (**self).as_any()
+    }
+}
+
 /// Git commit information
 #[derive(Debug, Clone)]
 pub struct GitCommit {
@@ -228,3 +587,317 @@ pub struct RemoteSyncOptions {
     pub dry_run: bool,
     pub auth: RemoteAuth,
 }
+
+/// Entities written per `bulk_store` call by [`copy_to`]
+pub const COPY_BATCH_SIZE: usize = 100;
+
+/// Progress callback for [`copy_to`]: (entities processed, total entities)
+pub type ProgressCallback = Box<dyn FnMut(usize, usize)>;
+
+/// Outcome of copying entities between storage backends
+#[derive(Debug, Clone, Default)]
+pub struct CopyReport {
+    pub entities_copied: usize,
+    /// Entities already present at the destination
+    pub entities_skipped: usize,
+    /// (entity id, error) for entities that could not be copied
+    pub errors: Vec<(String, String)>,
+}
+
+/// Resolve a full ID or unique ID prefix to a stored `entity_type` ID.
+///
+/// An exact match wins even when it is also a prefix of other IDs. Fails
+/// with `NotFound` when nothing matches and `InvalidOperation` when the
+/// prefix is ambiguous.
+pub fn resolve_id_prefix(
+    storage: &dyn Storage,
+    entity_type: &str,
+    prefix: &str,
+) -> Result<String, EngramError> {
+    if storage.exists(prefix, entity_type)? {
+        return Ok(prefix.to_string());
+    }
+
+    let mut matches: Vec<String> = storage
+        .list_ids(entity_type)?
+        .into_iter()
+        .filter(|id| id.starts_with(prefix))
+        .collect();
+    match matches.len() {
+        0 => Err(EngramError::not_found(entity_type, prefix)),
+        1 => Ok(matches.remove(0)),
+        n => Err(EngramError::InvalidOperation(format!(
+            "'{}' matches {} {} entities",
+            prefix, n, entity_type
+        ))),
+    }
+}
+
+/// Every entity in `storage` matching `filter`, oldest first.
+///
+/// Without a filter every built-in entity type is read, plus any other type
+/// the backend reports in its stats. A filter without an entity type is
+/// applied to each of those types.
+pub fn collect_entities(
+    storage: &dyn Storage,
+    filter: Option<&QueryFilter>,
+) -> Result<Vec<GenericEntity>, EngramError> {
+    let entity_types: Vec<String> = match filter.and_then(|f| f.entity_type.clone()) {
+        Some(entity_type) => vec![entity_type],
+        None => {
+            let mut types: std::collections::BTreeSet<String> =
+                crate::entities::EntityRegistry::with_builtin_types()
+                    .list_types()
+                    .into_iter()
+                    .map(str::to_string)
+                    .collect();
+            types.extend(storage.get_stats()?.entities_by_type.into_keys());
+            types.into_iter().collect()
+        }
+    };
+
+    let mut entities = Vec::new();
+    for entity_type in entity_types {
+        match filter {
+            Some(filter) => {
+                let typed = QueryFilter {
+                    entity_type: Some(entity_type),
+                    ..filter.clone()
+                };
+                entities.extend(storage.query(&typed)?.entities);
+            }
+            None => entities.extend(storage.get_all(&entity_type)?),
+        }
+    }
+
+    entities.sort_by(|a, b| a.timestamp.cmp(&b.timestamp).then_with(|| a.id.cmp(&b.id)));
+    Ok(entities)
+}
+
+/// Copy entities from `src` to `dst` in timestamp order, skipping entities
+/// the destination already has.
+///
+/// Entities are written with `bulk_store` in batches of [`COPY_BATCH_SIZE`].
+/// When a batch fails, its entities are retried one by one so a single bad
+/// entity is reported in [`CopyReport::errors`] without losing the rest.
+pub fn copy_to(
+    src: &dyn Storage,
+    dst: &mut dyn Storage,
+    filter: Option<QueryFilter>,
+    mut progress: Option<ProgressCallback>,
+) -> Result<CopyReport, EngramError> {
+    let entities = collect_entities(src, filter.as_ref())?;
+    let total = entities.len();
+    let mut report = CopyReport::default();
+    let mut processed = 0;
+
+    for chunk in entities.chunks(COPY_BATCH_SIZE) {
+        let mut batch = Vec::with_capacity(chunk.len());
+        for entity in chunk {
+            if dst.exists(&entity.id, &entity.entity_type)? {
+                report.entities_skipped += 1;
+            } else {
+                batch.push(entity.clone());
+            }
+        }
+
+        if dst.bulk_store(&batch).is_ok() {
+            report.entities_copied += batch.len();
+        } else {
+            for entity in &batch {
+                match dst.store(entity) {
+                    Ok(()) => report.entities_copied += 1,
+                    Err(e) => report.errors.push((entity.id.clone(), e.to_string())),
+                }
+            }
+        }
+
+        processed += chunk.len();
+        if let Some(callback) = progress.as_mut() {
+            callback(processed, total);
+        }
+    }
+
+    Ok(report)
+}
+
+/// Content hash of an entity, used to verify copies
+pub fn entity_hash(entity: &GenericEntity) -> Result<String, EngramError> {
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
+    hasher.update(entity.id.as_bytes());
+    hasher.update(entity.entity_type.as_bytes());
+    hasher.update(entity.agent.as_bytes());
+    hasher.update(entity.timestamp.to_rfc3339().as_bytes());
+    hasher.update(serde_json::to_vec(&entity.data)?);
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Compare hashes of every source entity matching `filter` with its copy in
+/// `dst`. Returns (entity id, problem) for missing or differing copies.
+pub fn verify_copy(
+    src: &dyn Storage,
+    dst: &dyn Storage,
+    filter: Option<&QueryFilter>,
+) -> Result<Vec<(String, String)>, EngramError> {
+    let mut mismatches = Vec::new();
+    for entity in collect_entities(src, filter)? {
+        match dst.get(&entity.id, &entity.entity_type)? {
+            None => mismatches.push((entity.id, "missing at destination".to_string())),
+            Some(copy) => {
+                if entity_hash(&copy)? != entity_hash(&entity)? {
+                    mismatches.push((entity.id, "content hash differs".to_string()));
+                }
+            }
+        }
+    }
+    Ok(mismatches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    fn seeded(count: usize) -> MemoryStorage {
+        let mut storage = MemoryStorage::new("copier");
+        let base = Utc::now();
+        for i in 0..count {
+            let entity_type = if i % 2 == 0 { "task" } else { "context" };
+            storage
+                .store(&GenericEntity {
+                    id: format!("entity-{:02}", i),
+                    entity_type: entity_type.to_string(),
+                    agent: "copier".to_string(),
+                    // Stored newest first so ordering has to come from timestamps
+                    timestamp: base - Duration::seconds(i as i64),
+                    data: serde_json::json!({ "title": format!("Entity {}", i) }),
+                })
+                .unwrap();
+        }
+        storage
+    }
+
+    #[test]
+    fn test_patch_applies_to_stored_entity() {
+        let mut storage = seeded(1);
+        let patch: Vec<JsonPatchOp> = serde_json::from_str(
+            r#"[{"op": "replace", "path": "/data/title", "value": "Renamed"}]"#,
+        )
+        .unwrap();
+        storage.patch("entity-00", "task", patch).unwrap();
+        let stored = storage.get("entity-00", "task").unwrap().unwrap();
+        assert_eq!(stored.data["title"], "Renamed");
+
+        let rename: Vec<JsonPatchOp> =
+            serde_json::from_str(r#"[{"op": "replace", "path": "/id", "value": "other"}]"#)
+                .unwrap();
+        assert!(matches!(
+            storage.patch("entity-00", "task", rename),
+            Err(EngramError::InvalidOperation(_))
+        ));
+        assert!(storage.get("other", "task").unwrap().is_none());
+        assert!(matches!(
+            storage.patch("missing", "task", vec![]),
+            Err(EngramError::NotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_resolve_id_prefix() {
+        let storage = seeded(30);
+        assert_eq!(
+            resolve_id_prefix(&storage, "task", "entity-00").unwrap(),
+            "entity-00"
+        );
+        assert_eq!(
+            resolve_id_prefix(&storage, "task", "entity-2")
+                .unwrap_err()
+                .to_string(),
+            "Invalid operation: 'entity-2' matches 5 task entities"
+        );
+        assert!(matches!(
+            resolve_id_prefix(&storage, "task", "entity-01"),
+            Err(EngramError::NotFound { .. })
+        ));
+
+        let mut storage = MemoryStorage::new("copier");
+        for id in ["abc", "abcdef"] {
+            storage
+                .store(&GenericEntity {
+                    id: id.to_string(),
+                    entity_type: "task".to_string(),
+                    agent: "copier".to_string(),
+                    timestamp: Utc::now(),
+                    data: serde_json::json!({}),
+                })
+                .unwrap();
+        }
+        assert_eq!(resolve_id_prefix(&storage, "task", "abc").unwrap(), "abc");
+        assert_eq!(
+            resolve_id_prefix(&storage, "task", "abcd").unwrap(),
+            "abcdef"
+        );
+    }
+
+    #[test]
+    fn test_copy_to_copies_then_skips() {
+        let src = seeded(50);
+        let mut dst = MemoryStorage::new("copier");
+
+        let calls = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = calls.clone();
+        let report = copy_to(
+            &src,
+            &mut dst,
+            None,
+            Some(Box::new(move |done, total| {
+                recorded.lock().unwrap().push((done, total))
+            })),
+        )
+        .unwrap();
+
+        assert_eq!(report.entities_copied, 50);
+        assert_eq!(report.entities_skipped, 0);
+        assert!(report.errors.is_empty());
+        assert_eq!(*calls.lock().unwrap(), vec![(50, 50)]);
+        assert_eq!(dst.get_all("task").unwrap().len(), 25);
+        assert_eq!(dst.get_all("context").unwrap().len(), 25);
+        assert!(verify_copy(&src, &dst, None).unwrap().is_empty());
+
+        let again = copy_to(&src, &mut dst, None, None).unwrap();
+        assert_eq!(again.entities_copied, 0);
+        assert_eq!(again.entities_skipped, 50);
+    }
+
+    #[test]
+    fn test_collect_entities_orders_by_timestamp() {
+        let src = seeded(5);
+        let ids: Vec<String> = collect_entities(&src, None)
+            .unwrap()
+            .into_iter()
+            .map(|e| e.id)
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                "entity-04",
+                "entity-03",
+                "entity-02",
+                "entity-01",
+                "entity-00"
+            ]
+        );
+
+        let tasks_only = QueryFilter {
+            entity_type: Some("task".to_string()),
+            limit: None,
+            ..Default::default()
+        };
+        let mut dst = MemoryStorage::new("copier");
+        let report = copy_to(&src, &mut dst, Some(tasks_only), None).unwrap();
+        assert_eq!(report.entities_copied, 3);
+        assert!(dst.get_all("context").unwrap().is_empty());
+    }
+}
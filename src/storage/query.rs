@@ -0,0 +1,745 @@
+//! Shared `QueryFilter` semantics for storage backends
+//!
+//! Every backend loads its candidate entities and hands them to
+//! [`apply_filter`], so filtering, sorting and pagination behave identically
+//! regardless of where the entities are stored:
+//!
+//! - `entity_type` and `agent` match exactly.
+//! - `time_range` is inclusive on both ends.
+//! - `text_search` is a case-insensitive substring match against the
+//!   `title`, `description` and `content` fields of the entity data, using
+//!   Unicode case folding (`"STRASSE"` finds `"Straße"`). An entity whose ID
+//!   equals the query also matches. With `fuzzy`, words within
+//!   [`FUZZY_THRESHOLD`] similarity match too (`"logn"` finds `"login"`).
+//! - `field_filters` keys may use dotted paths (`"metadata.owner"`) to reach
+//!   nested objects; values must be equal.
+//! - `sort_by` accepts the same dotted paths and compares numbers
+//!   numerically, RFC 3339 dates chronologically, enum values with a
+//!   [`known_ordering`] (task priority, status, ...) by declared order,
+//!   other strings lexically and anything else by its JSON text. Entities
+//!   missing the field sort before those that have it. A key that no
+//!   candidate entity has is rejected with the available [`sort_keys`].
+//!   Without `sort_by` entities are ordered by timestamp.
+//! - Sorting is stable: ties keep ascending timestamp, then id, order in both
+//!   directions.
+//! - Text search results put exact ID matches first; without `sort_by` the
+//!   rest are ranked title matches before other fields, substring matches
+//!   before fuzzy ones, then by the order above.
+//! - `offset` and `limit` are applied last; `total_count` is the number of
+//!   matches before pagination.
+
+use super::{QueryFilter, QueryResult, SortOrder};
+use crate::entities::GenericEntity;
+use crate::error::EngramError;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+
+/// Fields searched by `QueryFilter::text_search`
+pub const TEXT_SEARCH_FIELDS: [&str; 3] = ["title", "description", "content"];
+
+/// Minimum normalized Levenshtein similarity for a fuzzy text match; one
+/// typo in a five-letter word scores 0.8
+pub const FUZZY_THRESHOLD: f64 = 0.75;
+
+/// Where a text search matched, in increasing order of relevance
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MatchTier {
+    Content,
+    Title,
+    Id,
+}
+
+/// How well an entity matched a text search
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextMatch {
+    pub tier: MatchTier,
+    /// 1.0 for substring matches, lower for fuzzy ones
+    pub similarity: f64,
+}
+
+impl TextMatch {
+    /// Ordering that puts the better match first
+    pub fn better_first(&self, other: &Self) -> Ordering {
+        other
+            .tier
+            .cmp(&self.tier)
+            .then(other.similarity.total_cmp(&self.similarity))
+    }
+}
+
+/// Filter, sort and paginate `entities` according to `filter`
+///
+/// Fails when `sort_by` names a field none of `entities` has.
+pub fn apply_filter(
+    entities: Vec<GenericEntity>,
+    filter: &QueryFilter,
+) -> Result<QueryResult, EngramError> {
+    let views: Vec<EntityRef> = entities.iter().map(EntityRef::from).collect();
+    let page = filter_page(&views, filter)?;
+    drop(views);
+
+    let mut slots: Vec<Option<GenericEntity>> = entities.into_iter().map(Some).collect();
+    Ok(page.map(|index| slots[index].take().expect("a page never repeats an entity")))
+}
+
+/// [`apply_filter`] over borrowed views, so a backend can query entities it
+/// holds in another form and copy out only the page it returns
+pub fn apply_filter_borrowed<'a>(
+    entities: Vec<EntityRef<'a>>,
+    filter: &QueryFilter,
+) -> Result<QueryResult<EntityRef<'a>>, EngramError> {
+    let page = filter_page(&entities, filter)?;
+    Ok(page.map(|index| entities[index]))
+}
+
+/// Indices into `entities` of the page `filter` selects, in result order.
+/// Sorting and ranking move indices rather than entities.
+pub fn filter_page(
+    entities: &[EntityRef<'_>],
+    filter: &QueryFilter,
+) -> Result<QueryResult<usize>, EngramError> {
+    if let Some(key) = &filter.sort_by {
+        check_sort_key(entities, key, filter.entity_type.as_deref())?;
+    }
+
+    let mut matches: Vec<usize> = (0..entities.len())
+        .filter(|&index| matches_filter(entities[index], filter))
+        .collect();
+
+    sort_entities(entities, &mut matches, filter);
+    if let Some(query) = &filter.text_search {
+        rank_text_matches(entities, &mut matches, query, filter);
+    }
+
+    let total_count = matches.len();
+    let offset = filter.offset.unwrap_or(0);
+    let page: Vec<usize> = match filter.limit {
+        Some(limit) => matches.into_iter().skip(offset).take(limit).collect(),
+        None => matches.into_iter().skip(offset).collect(),
+    };
+    let has_more = filter.limit.is_some() && offset + page.len() < total_count;
+
+    Ok(QueryResult {
+        entities: page,
+        total_count,
+        has_more,
+    })
+}
+
+/// Dotted paths of the scalar fields present in any of `entities`: the keys
+/// `sort_by` accepts for them
+pub fn sort_keys<'a>(entities: impl IntoIterator<Item = EntityRef<'a>>) -> Vec<String> {
+    fn collect(prefix: &str, value: &Value, keys: &mut BTreeSet<String>) {
+        match value {
+            Value::Object(map) => {
+                for (name, child) in map {
+                    let path = if prefix.is_empty() {
+                        name.clone()
+                    } else {
+                        format!("{}.{}", prefix, name)
+                    };
+                    collect(&path, child, keys);
+                }
+            }
+            Value::Array(_) => {}
+            _ if !prefix.is_empty() => {
+                keys.insert(prefix.to_string());
+            }
+            _ => {}
+        }
+    }
+
+    let mut keys = BTreeSet::new();
+    for entity in entities {
+        collect("", entity.data, &mut keys);
+    }
+    keys.into_iter().collect()
+}
+
+/// Reject a sort key that no candidate entity has. An empty candidate set
+/// accepts any key, since there is nothing to sort.
+fn check_sort_key(
+    entities: &[EntityRef<'_>],
+    key: &str,
+    entity_type: Option<&str>,
+) -> Result<(), EngramError> {
+    if entities.is_empty()
+        || entities
+            .iter()
+            .any(|entity| lookup_field(entity.data, key).is_some())
+    {
+        return Ok(());
+    }
+
+    Err(EngramError::Validation(format!(
+        "Unknown sort key '{}' for {}; available keys: {}",
+        key,
+        entity_type.unwrap_or("these entities"),
+        sort_keys(entities.iter().copied()).join(", ")
+    )))
+}
+
+/// Declared order, lowest first, of an enum-valued field of an entity type
+pub fn known_ordering(entity_type: &str, field: &str) -> Option<&'static [&'static str]> {
+    const LEVELS: &[&str] = &["low", "medium", "high", "critical"];
+    let ordering: &'static [&'static str] = match (entity_type, field) {
+        ("task", "priority") | ("rule", "priority") | ("standard", "priority") => LEVELS,
+        ("task", "status") => &["todo", "inprogress", "done", "blocked", "cancelled"],
+        ("adr", "status") => &["proposed", "accepted", "deprecated", "superseded"],
+        ("standard", "status") => &["draft", "active", "deprecated", "superseded"],
+        ("rule", "status") => &["active", "inactive", "deprecated"],
+        ("compliance", "status") => &["compliant", "noncompliant", "pending", "exempt"],
+        ("escalation_request", "priority") => &["low", "normal", "high", "critical"],
+        ("escalation_request", "status") => {
+            &["pending", "approved", "denied", "expired", "cancelled"]
+        }
+        ("lesson", "severity") => &["low", "medium", "high"],
+        ("session", "status") => &["active", "paused", "completed", "cancelled", "reflecting"],
+        ("workflow", "status") | ("workflow_instance", "status") => {
+            &["active", "inactive", "draft", "archived"]
+        }
+        _ => return None,
+    };
+    Some(ordering)
+}
+
+/// Borrowed view of the entity fields the filter predicates read, so a
+/// backend can test stored entities without materializing a `GenericEntity`
+#[derive(Debug, Clone, Copy)]
+pub struct EntityRef<'a> {
+    pub id: &'a str,
+    pub entity_type: &'a str,
+    pub agent: &'a str,
+    pub timestamp: DateTime<Utc>,
+    pub data: &'a Value,
+}
+
+impl EntityRef<'_> {
+    /// Owned copy of the viewed entity
+    pub fn to_entity(&self) -> GenericEntity {
+        GenericEntity {
+            id: self.id.to_string(),
+            entity_type: self.entity_type.to_string(),
+            agent: self.agent.to_string(),
+            timestamp: self.timestamp,
+            data: self.data.clone(),
+        }
+    }
+}
+
+impl<'a> From<&'a GenericEntity> for EntityRef<'a> {
+    fn from(entity: &'a GenericEntity) -> Self {
+        Self {
+            id: &entity.id,
+            entity_type: &entity.entity_type,
+            agent: &entity.agent,
+            timestamp: entity.timestamp,
+            data: &entity.data,
+        }
+    }
+}
+
+/// Whether `entity` satisfies every predicate in `filter` (ignores sorting and pagination)
+pub fn matches_filter<'a>(entity: impl Into<EntityRef<'a>>, filter: &QueryFilter) -> bool {
+    let entity = entity.into();
+    if let Some(entity_type) = &filter.entity_type {
+        if entity.entity_type != *entity_type {
+            return false;
+        }
+    }
+
+    if let Some(agent) = &filter.agent {
+        if entity.agent != *agent {
+            return false;
+        }
+    }
+
+    if let Some(range) = &filter.time_range {
+        if entity.timestamp < range.start || entity.timestamp > range.end {
+            return false;
+        }
+    }
+
+    if let Some(query) = &filter.text_search {
+        if text_match(entity, query, filter.fuzzy).is_none() {
+            return false;
+        }
+    }
+
+    filter
+        .field_filters
+        .iter()
+        .all(|(path, expected)| lookup_field(entity.data, path) == Some(expected))
+}
+
+/// Best match of `query` against the entity's ID and [`TEXT_SEARCH_FIELDS`]
+pub fn text_match<'a>(
+    entity: impl Into<EntityRef<'a>>,
+    query: &str,
+    fuzzy: bool,
+) -> Option<TextMatch> {
+    let entity = entity.into();
+    let query = fold_case(query);
+    if fold_case(entity.id) == query {
+        return Some(TextMatch {
+            tier: MatchTier::Id,
+            similarity: 1.0,
+        });
+    }
+
+    TEXT_SEARCH_FIELDS
+        .iter()
+        .filter_map(|field| {
+            let text = entity.data.get(field)?.as_str()?;
+            let similarity = text_similarity(&fold_case(text), &query, fuzzy)?;
+            let tier = if *field == "title" {
+                MatchTier::Title
+            } else {
+                MatchTier::Content
+            };
+            Some(TextMatch { tier, similarity })
+        })
+        .min_by(TextMatch::better_first)
+}
+
+/// Lowercase `text`, applying the full case foldings `to_lowercase` leaves
+/// out so that differently cased spellings compare equal
+pub fn fold_case(text: &str) -> String {
+    let mut folded = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            'ß' | 'ẞ' => folded.push_str("ss"),
+            'ς' => folded.push('σ'),
+            'ſ' => folded.push('s'),
+            _ => folded.extend(c.to_lowercase()),
+        }
+    }
+    folded
+}
+
+/// 1.0 when folded `text` contains `query`. With `fuzzy`, otherwise the best
+/// similarity between the query's words and any run of as many words in
+/// `text`, if it reaches [`FUZZY_THRESHOLD`].
+fn text_similarity(text: &str, query: &str, fuzzy: bool) -> Option<f64> {
+    if text.contains(query) {
+        return Some(1.0);
+    }
+    if !fuzzy {
+        return None;
+    }
+
+    let words = |s: &str| -> Vec<String> {
+        s.split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .map(str::to_string)
+            .collect()
+    };
+    let query_words = words(query);
+    let text_words = words(text);
+    if query_words.is_empty() || text_words.is_empty() {
+        return None;
+    }
+
+    let query = query_words.join(" ");
+    text_words
+        .windows(query_words.len().min(text_words.len()))
+        .map(|window| strsim::normalized_levenshtein(&window.join(" "), &query))
+        .filter(|similarity| *similarity >= FUZZY_THRESHOLD)
+        .max_by(f64::total_cmp)
+}
+
+/// Put exact ID matches first and, unless `sort_by` was requested, rank the
+/// rest by match quality. The sort is stable, so ties keep their order.
+fn rank_text_matches(
+    entities: &[EntityRef<'_>],
+    matches: &mut [usize],
+    query: &str,
+    filter: &QueryFilter,
+) {
+    let mut ranked: Vec<(Option<TextMatch>, usize)> = matches
+        .iter()
+        .map(|&index| (text_match(entities[index], query, filter.fuzzy), index))
+        .collect();
+    ranked.sort_by(|(a, _), (b, _)| match (a, b) {
+        (Some(a), Some(b)) if filter.sort_by.is_none() => a.better_first(b),
+        (Some(a), Some(b)) => (b.tier == MatchTier::Id).cmp(&(a.tier == MatchTier::Id)),
+        _ => Ordering::Equal,
+    });
+    for (slot, (_, index)) in matches.iter_mut().zip(ranked) {
+        *slot = index;
+    }
+}
+
+/// Resolve a dotted field path (`"a.b.c"`) inside entity data
+pub fn lookup_field<'a>(data: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .try_fold(data, |value, segment| value.get(segment))
+}
+
+fn compare_values(ordering: Option<&[&str]>, a: &Value, b: &Value) -> Ordering {
+    match (a, b) {
+        (Value::Number(x), Value::Number(y)) => {
+            let x = x.as_f64().unwrap_or(0.0);
+            let y = y.as_f64().unwrap_or(0.0);
+            x.total_cmp(&y)
+        }
+        (Value::String(x), Value::String(y)) => {
+            let rank = |s: &str| ordering?.iter().position(|v| *v == s);
+            if let (Some(x), Some(y)) = (rank(x), rank(y)) {
+                return x.cmp(&y);
+            }
+            match (
+                chrono::DateTime::parse_from_rfc3339(x),
+                chrono::DateTime::parse_from_rfc3339(y),
+            ) {
+                (Ok(x), Ok(y)) => x.cmp(&y),
+                _ => x.cmp(y),
+            }
+        }
+        (Value::Bool(x), Value::Bool(y)) => x.cmp(y),
+        _ => a.to_string().cmp(&b.to_string()),
+    }
+}
+
+/// Order `matches`, indices into `entities`, as the filter asks
+fn sort_entities(entities: &[EntityRef<'_>], matches: &mut [usize], filter: &QueryFilter) {
+    // Canonical order first so ties are deterministic across backends
+    matches.sort_by(|&a, &b| {
+        let (a, b) = (&entities[a], &entities[b]);
+        a.timestamp.cmp(&b.timestamp).then_with(|| a.id.cmp(b.id))
+    });
+
+    matches.sort_by(|&a, &b| {
+        let (a, b) = (&entities[a], &entities[b]);
+        let cmp = match &filter.sort_by {
+            Some(field) => match (lookup_field(a.data, field), lookup_field(b.data, field)) {
+                (Some(x), Some(y)) => compare_values(known_ordering(a.entity_type, field), x, y),
+                (Some(_), None) => Ordering::Greater,
+                (None, Some(_)) => Ordering::Less,
+                (None, None) => Ordering::Equal,
+            },
+            None => a.timestamp.cmp(&b.timestamp),
+        };
+        match filter.sort_order {
+            SortOrder::Asc => cmp,
+            SortOrder::Desc => cmp.reverse(),
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{GitRefsStorage, MemoryStorage, Storage, TimeRange};
+    use chrono::{Duration, TimeZone, Utc};
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    // An unregistered type, so backends store the free-form fixture data unvalidated
+    const FIXTURE_TYPE: &str = "note";
+
+    fn entity(id: &str, agent: &str, minutes: i64, data: Value) -> GenericEntity {
+        GenericEntity {
+            id: id.to_string(),
+            entity_type: FIXTURE_TYPE.to_string(),
+            agent: agent.to_string(),
+            timestamp: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()
+                + Duration::minutes(minutes),
+            data,
+        }
+    }
+
+    fn fixtures() -> Vec<GenericEntity> {
+        vec![
+            entity(
+                "task-a",
+                "alice",
+                0,
+                json!({"title": "Fix Login Bug", "priority": 2, "meta": {"team": "core"}}),
+            ),
+            entity(
+                "task-b",
+                "bob",
+                10,
+                json!({"title": "Write docs", "description": "login flow", "priority": 10}),
+            ),
+            entity(
+                "task-c",
+                "alice",
+                20,
+                json!({"title": "Refactor", "content": "nothing here", "priority": 2,
+                       "meta": {"team": "infra"}}),
+            ),
+            entity(
+                "task-d",
+                "alice",
+                30,
+                json!({"title": "Unrelated", "notes": "login mentioned outside searched fields"}),
+            ),
+        ]
+    }
+
+    fn ids(result: &QueryResult) -> Vec<&str> {
+        result.entities.iter().map(|e| e.id.as_str()).collect()
+    }
+
+    /// Conformance scenario run against every storage backend
+    fn assert_conformance(storage: &mut dyn Storage) {
+        for fixture in fixtures() {
+            storage.store(&fixture).unwrap();
+        }
+        let base = QueryFilter {
+            entity_type: Some(FIXTURE_TYPE.to_string()),
+            limit: None,
+            ..Default::default()
+        };
+
+        // Case-insensitive text search over title/description/content only
+        let result = storage
+            .query(&QueryFilter {
+                text_search: Some("LOGIN".to_string()),
+                sort_order: SortOrder::Asc,
+                ..base.clone()
+            })
+            .unwrap();
+        assert_eq!(ids(&result), vec!["task-a", "task-b"]);
+
+        // Title matches rank above content matches regardless of timestamp order
+        let search = |query: &str, fuzzy: bool| QueryFilter {
+            text_search: Some(query.to_string()),
+            fuzzy,
+            ..base.clone()
+        };
+        let result = storage.query(&search("login", false)).unwrap();
+        assert_eq!(ids(&result), vec!["task-a", "task-b"]);
+
+        // Fuzzy matching tolerates a one-character typo; exact IDs come first
+        assert!(storage
+            .query(&search("logn", false))
+            .unwrap()
+            .entities
+            .is_empty());
+        let result = storage.query(&search("logn", true)).unwrap();
+        assert_eq!(ids(&result), vec!["task-a", "task-b"]);
+        let result = storage.query(&search("TASK-C", true)).unwrap();
+        assert_eq!(ids(&result)[0], "task-c");
+
+        // Nested field filter
+        let mut nested = base.clone();
+        nested
+            .field_filters
+            .insert("meta.team".to_string(), json!("infra"));
+        assert_eq!(ids(&storage.query(&nested).unwrap()), vec!["task-c"]);
+
+        // Agent + time range
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let result = storage
+            .query(&QueryFilter {
+                agent: Some("alice".to_string()),
+                time_range: Some(TimeRange {
+                    start,
+                    end: start + Duration::minutes(20),
+                }),
+                ..base.clone()
+            })
+            .unwrap();
+        assert_eq!(ids(&result), vec!["task-c", "task-a"]);
+
+        // Numeric sort, stable on ties, missing fields first
+        let result = storage
+            .query(&QueryFilter {
+                sort_by: Some("priority".to_string()),
+                sort_order: SortOrder::Asc,
+                ..base.clone()
+            })
+            .unwrap();
+        assert_eq!(ids(&result), vec!["task-d", "task-a", "task-c", "task-b"]);
+
+        let result = storage
+            .query(&QueryFilter {
+                sort_by: Some("priority".to_string()),
+                sort_order: SortOrder::Desc,
+                ..base.clone()
+            })
+            .unwrap();
+        assert_eq!(ids(&result), vec!["task-b", "task-a", "task-c", "task-d"]);
+
+        // Pagination
+        let result = storage
+            .query(&QueryFilter {
+                sort_order: SortOrder::Asc,
+                limit: Some(2),
+                offset: Some(1),
+                ..base.clone()
+            })
+            .unwrap();
+        assert_eq!(ids(&result), vec!["task-b", "task-c"]);
+        assert_eq!(result.total_count, 4);
+        assert!(result.has_more);
+    }
+
+    #[test]
+    fn test_conformance_memory_storage() {
+        let mut storage = MemoryStorage::new("test-agent");
+        assert_conformance(&mut storage);
+    }
+
+    // GitStorage was superseded by GitRefsStorage, which is the only git backend
+    #[test]
+    fn test_conformance_git_refs_storage() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut storage =
+            GitRefsStorage::new(temp_dir.path().to_str().unwrap(), "test-agent").unwrap();
+        assert_conformance(&mut storage);
+    }
+
+    #[test]
+    fn test_borrowed_and_owned_filtering_agree() {
+        let base = QueryFilter {
+            entity_type: Some(FIXTURE_TYPE.to_string()),
+            limit: None,
+            ..Default::default()
+        };
+        let filters = [
+            base.clone(),
+            QueryFilter {
+                text_search: Some("logn".to_string()),
+                fuzzy: true,
+                ..base.clone()
+            },
+            QueryFilter {
+                sort_by: Some("priority".to_string()),
+                sort_order: SortOrder::Desc,
+                ..base.clone()
+            },
+            QueryFilter {
+                limit: Some(2),
+                offset: Some(1),
+                ..base.clone()
+            },
+        ];
+
+        let stored = fixtures();
+        for filter in &filters {
+            let owned = apply_filter(stored.clone(), filter).unwrap();
+            let views = stored.iter().map(EntityRef::from).collect();
+            let borrowed = apply_filter_borrowed(views, filter)
+                .unwrap()
+                .map(|entity| entity.to_entity());
+            assert_eq!(owned.entities, borrowed.entities);
+            assert_eq!(owned.total_count, borrowed.total_count);
+            assert_eq!(owned.has_more, borrowed.has_more);
+        }
+    }
+
+    #[test]
+    fn test_text_search_folds_unicode_case() {
+        let street = entity("street", "alice", 0, json!({"title": "Straße"}));
+        assert!(text_match(&street, "STRASSE", false).is_some());
+        assert!(text_match(&street, "straße", false).is_some());
+
+        let greek = entity("greek", "alice", 0, json!({"content": "ΟΔΟΣ"}));
+        assert_eq!(
+            text_match(&greek, "οδος", false).map(|m| m.tier),
+            Some(MatchTier::Content)
+        );
+        assert!(text_match(&greek, "οδοσ", false).is_some());
+    }
+
+    #[test]
+    fn test_fuzzy_match_scores_one_typo() {
+        let oauth = entity("oauth", "alice", 0, json!({"title": "Add OAuth login"}));
+        assert!(text_match(&oauth, "oaut", false).is_some());
+        assert!(text_match(&oauth, "oauht login", false).is_none());
+
+        let typo = text_match(&oauth, "oaurh", true).unwrap();
+        assert_eq!(typo.tier, MatchTier::Title);
+        assert!((typo.similarity - 0.8).abs() < 1e-9);
+        assert!(text_match(&oauth, "github", true).is_none());
+    }
+
+    #[test]
+    fn test_lookup_field_nested_path() {
+        let data = json!({"a": {"b": {"c": 1}}});
+        assert_eq!(lookup_field(&data, "a.b.c"), Some(&json!(1)));
+        assert_eq!(lookup_field(&data, "a.x"), None);
+    }
+
+    fn sorted(entities: Vec<GenericEntity>, entity_type: &str, key: &str) -> Vec<String> {
+        let filter = QueryFilter {
+            entity_type: Some(entity_type.to_string()),
+            sort_by: Some(key.to_string()),
+            sort_order: SortOrder::Asc,
+            limit: None,
+            ..Default::default()
+        };
+        apply_filter(entities, &filter)
+            .unwrap()
+            .entities
+            .into_iter()
+            .map(|e| e.id)
+            .collect()
+    }
+
+    #[test]
+    fn test_sort_by_known_ordering() {
+        let tasks: Vec<GenericEntity> = ["medium", "critical", "low", "high"]
+            .iter()
+            .enumerate()
+            .map(|(i, priority)| GenericEntity {
+                entity_type: "task".to_string(),
+                ..entity(priority, "alice", i as i64, json!({"priority": priority}))
+            })
+            .collect();
+        assert_eq!(
+            sorted(tasks, "task", "priority"),
+            ["low", "medium", "high", "critical"]
+        );
+    }
+
+    #[test]
+    fn test_sort_dates_chronologically() {
+        let notes = vec![
+            entity("utc", "alice", 0, json!({"due": "2024-01-01T06:00:00Z"})),
+            entity(
+                "offset",
+                "alice",
+                1,
+                json!({"due": "2024-01-01T10:00:00+05:00"}),
+            ),
+        ];
+        assert_eq!(sorted(notes, FIXTURE_TYPE, "due"), ["offset", "utc"]);
+    }
+
+    #[test]
+    fn test_sort_by_nested_path() {
+        assert_eq!(
+            sorted(fixtures(), FIXTURE_TYPE, "meta.team"),
+            ["task-b", "task-d", "task-a", "task-c"]
+        );
+    }
+
+    #[test]
+    fn test_unknown_sort_key_lists_available_keys() {
+        let filter = QueryFilter {
+            sort_by: Some("nope".to_string()),
+            ..Default::default()
+        };
+        let err = apply_filter(fixtures(), &filter).unwrap_err().to_string();
+        assert!(err.contains("Unknown sort key 'nope'"), "{err}");
+        assert!(err.contains("meta.team, notes, priority"), "{err}");
+    }
+
+    #[test]
+    fn test_has_more_without_limit_is_false() {
+        let filter = QueryFilter {
+            limit: None,
+            ..Default::default()
+        };
+        let result = apply_filter(fixtures(), &filter).unwrap();
+        assert_eq!(result.total_count, 4);
+        assert!(!result.has_more);
+    }
+}
@@ -0,0 +1,561 @@
+//! Per-agent entity creation quotas
+//!
+//! [`QuotaStorage`] wraps another backend and rejects stores that would
+//! create more than the configured number of entities of one type per agent
+//! within a rolling hour. Creations are charged to the acting identity
+//! ([`current_agent`](crate::cli::identity::current_agent)), whatever `agent`
+//! the entities name. Updates to existing entities are never limited.
+//! Limits come from the `quotas` section of `engram.yaml`:
+//!
+//! ```yaml
+//! quotas:
+//!   per_hour:
+//!     context: 500
+//!     "*": 2000
+//!   agents:
+//!     bulk-importer:
+//!       context: 5000
+//!   exempt_agents: [default, alice]
+//! ```
+//!
+//! Per-agent limits take precedence over `per_hour`, and an entity type's own
+//! limit over the `"*"` fallback. Types without a limit are not counted.
+
+use super::{
+    EntityPath, GitCommit, QueryFilter, QueryResult, RelationshipIndex, RelationshipStats,
+    RelationshipStorage, Storage, StorageStats, TimeRange, TraversalAlgorithm,
+};
+use crate::config::Config;
+use crate::entities::{
+    EntityRelationship, GenericEntity, JsonPatchOp, RelationshipFilter, LAST_MODIFIED_BY_FIELD,
+};
+use crate::error::EngramError;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Key that applies a limit to every entity type
+pub const ANY_ENTITY_TYPE: &str = "*";
+
+/// Length of the rolling quota window
+pub const QUOTA_WINDOW_SECONDS: i64 = 3600;
+
+fn default_exempt_agents() -> Vec<String> {
+    vec!["default".to_string()]
+}
+
+/// `quotas` section of `engram.yaml`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuotaConfig {
+    /// Entities per hour by entity type, for every agent
+    #[serde(default)]
+    pub per_hour: HashMap<String, u32>,
+
+    /// Per-agent overrides, by agent then entity type
+    #[serde(default)]
+    pub agents: HashMap<String, HashMap<String, u32>>,
+
+    /// Agents never limited (human users, the default agent)
+    #[serde(default = "default_exempt_agents")]
+    pub exempt_agents: Vec<String>,
+}
+
+impl Default for QuotaConfig {
+    fn default() -> Self {
+        Self {
+            per_hour: HashMap::new(),
+            agents: HashMap::new(),
+            exempt_agents: default_exempt_agents(),
+        }
+    }
+}
+
+impl QuotaConfig {
+    /// Read the `quotas` section of `<workspace>/engram.yaml` (or
+    /// `engram.yml`). A missing file or section means no limits.
+    pub fn load(workspace: &Path) -> Result<Self, EngramError> {
+        Config::load_section(workspace, "quotas")
+    }
+
+    pub fn from_yaml(content: &str) -> Result<Self, EngramError> {
+        Config::section_from_yaml(content, "quotas")
+    }
+
+    /// Hourly limit for `agent` creating `entity_type`, if any
+    pub fn limit_for(&self, agent: &str, entity_type: &str) -> Option<u32> {
+        if self.exempt_agents.iter().any(|a| a == agent) {
+            return None;
+        }
+        let lookup = |limits: &HashMap<String, u32>| {
+            limits
+                .get(entity_type)
+                .or_else(|| limits.get(ANY_ENTITY_TYPE))
+                .copied()
+        };
+        self.agents
+            .get(agent)
+            .and_then(lookup)
+            .or_else(|| lookup(&self.per_hour))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.per_hour.is_empty() && self.agents.is_empty()
+    }
+}
+
+/// Source of the current time, replaceable in tests
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// Wall-clock time
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Storage wrapper enforcing [`QuotaConfig`] on entity creation
+///
+/// Recent creations are counted from the wrapped backend: entities of the
+/// type last stored by the acting identity with a timestamp in the window.
+/// The quota therefore holds across separate CLI invocations, provided the
+/// backend stamps [`LAST_MODIFIED_BY_FIELD`] with the same identity.
+#[derive(Clone)]
+pub struct QuotaStorage<S> {
+    inner: S,
+    config: Arc<QuotaConfig>,
+    clock: Arc<dyn Clock>,
+    agent: String,
+}
+
+impl<S: Storage> QuotaStorage<S> {
+    /// Enforce `config` on creations by the current acting identity
+    pub fn new(inner: S, config: QuotaConfig) -> Self {
+        Self {
+            inner,
+            config: Arc::new(config),
+            clock: Arc::new(SystemClock),
+            agent: crate::cli::identity::current_agent(),
+        }
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Charge creations to `agent` instead of the current identity
+    pub fn with_agent(mut self, agent: impl Into<String>) -> Self {
+        self.agent = agent.into();
+        self
+    }
+
+    /// The wrapped backend
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    pub fn config(&self) -> &QuotaConfig {
+        &self.config
+    }
+
+    /// Reject if the acting identity creating `additional` more
+    /// `entity_type` entities now would exceed its hourly limit
+    pub fn check_quota(&self, entity_type: &str, additional: usize) -> Result<(), EngramError> {
+        let Some(limit) = self.config.limit_for(&self.agent, entity_type) else {
+            return Ok(());
+        };
+
+        let now = self.clock.now();
+        let window = Duration::seconds(QUOTA_WINDOW_SECONDS);
+        let filter = QueryFilter {
+            entity_type: Some(entity_type.to_string()),
+            field_filters: HashMap::from([(
+                LAST_MODIFIED_BY_FIELD.to_string(),
+                Value::String(self.agent.clone()),
+            )]),
+            time_range: Some(TimeRange {
+                start: now - window,
+                end: now,
+            }),
+            ..Default::default()
+        };
+        if self.inner.count(&filter)? + additional <= limit as usize {
+            return Ok(());
+        }
+
+        // Only a rejection needs the entities, to say when the window frees up
+        let oldest = self
+            .inner
+            .query(&filter)?
+            .entities
+            .iter()
+            .map(|e| e.timestamp)
+            .min()
+            .unwrap_or(now);
+        Err(EngramError::QuotaExceeded {
+            agent: self.agent.clone(),
+            entity_type: entity_type.to_string(),
+            limit,
+            resets_at: oldest + window,
+        })
+    }
+
+    fn is_new(&self, entity: &GenericEntity) -> Result<bool, EngramError> {
+        Ok(!self.inner.exists(&entity.id, &entity.entity_type)?)
+    }
+
+    fn check_store(&self, entity: &GenericEntity) -> Result<(), EngramError> {
+        if self.config.is_empty()
+            || self
+                .config
+                .limit_for(&self.agent, &entity.entity_type)
+                .is_none()
+            || !self.is_new(entity)?
+        {
+            return Ok(());
+        }
+        self.check_quota(&entity.entity_type, 1)
+    }
+}
+
+impl<S: Storage + 'static> Storage for QuotaStorage<S> {
+    fn store(&mut self, entity: &GenericEntity) -> Result<(), EngramError> {
+        self.check_store(entity)?;
+        self.inner.store(entity)
+    }
+
+    /// A patch only updates an existing entity, so it never counts against a quota
+    fn patch(
+        &mut self,
+        id: &str,
+        entity_type: &str,
+        patch: Vec<JsonPatchOp>,
+    ) -> Result<(), EngramError> {
+        self.inner.patch(id, entity_type, patch)
+    }
+
+    fn get(&self, id: &str, entity_type: &str) -> Result<Option<GenericEntity>, EngramError> {
+        self.inner.get(id, entity_type)
+    }
+
+    fn exists(&self, id: &str, entity_type: &str) -> Result<bool, EngramError> {
+        self.inner.exists(id, entity_type)
+    }
+
+    fn validate_for_store(&self, entity: &GenericEntity) -> Result<(), EngramError> {
+        self.inner.validate_for_store(entity)
+    }
+
+    fn get_many(
+        &self,
+        ids: &[String],
+        entity_type: &str,
+    ) -> Result<Vec<Option<GenericEntity>>, EngramError> {
+        self.inner.get_many(ids, entity_type)
+    }
+
+    fn query(&self, filter: &QueryFilter) -> Result<QueryResult, EngramError> {
+        self.inner.query(filter)
+    }
+
+    fn query_by_agent(
+        &self,
+        agent: &str,
+        entity_type: Option<&str>,
+    ) -> Result<Vec<GenericEntity>, EngramError> {
+        self.inner.query_by_agent(agent, entity_type)
+    }
+
+    fn query_by_time_range(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<GenericEntity>, EngramError> {
+        self.inner.query_by_time_range(start, end)
+    }
+
+    fn query_by_type(
+        &self,
+        entity_type: &str,
+        filters: Option<&HashMap<String, Value>>,
+        limit: Option<usize>,
+        offset: Option<usize>,
+    ) -> Result<QueryResult, EngramError> {
+        self.inner
+            .query_by_type(entity_type, filters, limit, offset)
+    }
+
+    fn text_search(
+        &self,
+        query: &str,
+        entity_types: Option<&[String]>,
+        limit: Option<usize>,
+    ) -> Result<Vec<GenericEntity>, EngramError> {
+        self.inner.text_search(query, entity_types, limit)
+    }
+
+    fn count(&self, filter: &QueryFilter) -> Result<usize, EngramError> {
+        self.inner.count(filter)
+    }
+
+    fn delete(&mut self, id: &str, entity_type: &str) -> Result<(), EngramError> {
+        self.inner.delete(id, entity_type)
+    }
+
+    fn list_ids(&self, entity_type: &str) -> Result<Vec<String>, EngramError> {
+        self.inner.list_ids(entity_type)
+    }
+
+    fn get_all(&self, entity_type: &str) -> Result<Vec<GenericEntity>, EngramError> {
+        self.inner.get_all(entity_type)
+    }
+
+    fn sync(&mut self) -> Result<(), EngramError> {
+        self.inner.sync()
+    }
+
+    fn current_branch(&self) -> Result<String, EngramError> {
+        self.inner.current_branch()
+    }
+
+    fn create_branch(&mut self, branch_name: &str) -> Result<(), EngramError> {
+        self.inner.create_branch(branch_name)
+    }
+
+    fn switch_branch(&mut self, branch_name: &str) -> Result<(), EngramError> {
+        self.inner.switch_branch(branch_name)
+    }
+
+    fn merge_branches(&mut self, source: &str, target: &str) -> Result<(), EngramError> {
+        self.inner.merge_branches(source, target)
+    }
+
+    fn history(&self, limit: Option<usize>) -> Result<Vec<GitCommit>, EngramError> {
+        self.inner.history(limit)
+    }
+
+    fn bulk_store(&mut self, entities: &[GenericEntity]) -> Result<(), EngramError> {
+        if !self.config.is_empty() {
+            let mut new_counts: HashMap<&str, usize> = HashMap::new();
+            for entity in entities {
+                if self
+                    .config
+                    .limit_for(&self.agent, &entity.entity_type)
+                    .is_some()
+                    && self.is_new(entity)?
+                {
+                    *new_counts.entry(entity.entity_type.as_str()).or_default() += 1;
+                }
+            }
+            for (entity_type, count) in new_counts {
+                self.check_quota(entity_type, count)?;
+            }
+        }
+        self.inner.bulk_store(entities)
+    }
+
+    fn get_stats(&self) -> Result<StorageStats, EngramError> {
+        self.inner.get_stats()
+    }
+
+    fn is_read_only(&self) -> bool {
+        self.inner.is_read_only()
+    }
+
+    /// Quotas are transparent, so downcasts see the wrapped backend
+    fn as_any(&self) -> &dyn std::any::Any {
+        self.inner.as_any()
+    }
+}
+
+impl<S: RelationshipStorage + 'static> RelationshipStorage for QuotaStorage<S> {
+    fn store_relationship(&mut self, relationship: &EntityRelationship) -> Result<(), EngramError> {
+        use crate::entities::Entity;
+        self.check_store(&relationship.to_generic())?;
+        self.inner.store_relationship(relationship)
+    }
+
+    fn get_relationship(&self, id: &str) -> Result<Option<EntityRelationship>, EngramError> {
+        self.inner.get_relationship(id)
+    }
+
+    fn query_relationships(
+        &self,
+        filter: &RelationshipFilter,
+    ) -> Result<Vec<EntityRelationship>, EngramError> {
+        self.inner.query_relationships(filter)
+    }
+
+    fn get_entity_relationships(
+        &self,
+        entity_id: &str,
+    ) -> Result<Vec<EntityRelationship>, EngramError> {
+        self.inner.get_entity_relationships(entity_id)
+    }
+
+    fn get_outbound_relationships(
+        &self,
+        entity_id: &str,
+    ) -> Result<Vec<EntityRelationship>, EngramError> {
+        self.inner.get_outbound_relationships(entity_id)
+    }
+
+    fn get_inbound_relationships(
+        &self,
+        entity_id: &str,
+    ) -> Result<Vec<EntityRelationship>, EngramError> {
+        self.inner.get_inbound_relationships(entity_id)
+    }
+
+    fn find_paths(
+        &self,
+        source_id: &str,
+        target_id: &str,
+        algorithm: TraversalAlgorithm,
+        max_depth: Option<usize>,
+    ) -> Result<Vec<EntityPath>, EngramError> {
+        self.inner
+            .find_paths(source_id, target_id, algorithm, max_depth)
+    }
+
+    fn get_connected_entities(
+        &self,
+        entity_id: &str,
+        algorithm: TraversalAlgorithm,
+        max_depth: Option<usize>,
+    ) -> Result<Vec<String>, EngramError> {
+        self.inner
+            .get_connected_entities(entity_id, algorithm, max_depth)
+    }
+
+    fn delete_relationship(&mut self, id: &str) -> Result<(), EngramError> {
+        self.inner.delete_relationship(id)
+    }
+
+    fn get_relationship_index(&self) -> Result<&RelationshipIndex, EngramError> {
+        self.inner.get_relationship_index()
+    }
+
+    fn rebuild_relationship_index(&mut self) -> Result<(), EngramError> {
+        self.inner.rebuild_relationship_index()
+    }
+
+    fn get_relationship_stats(&self) -> Result<RelationshipStats, EngramError> {
+        self.inner.get_relationship_stats()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::MemoryStorage;
+    use std::sync::Mutex;
+
+    struct MockClock(Mutex<DateTime<Utc>>);
+
+    impl MockClock {
+        fn advance(&self, seconds: i64) {
+            *self.0.lock().unwrap() += Duration::seconds(seconds);
+        }
+    }
+
+    impl Clock for MockClock {
+        fn now(&self) -> DateTime<Utc> {
+            *self.0.lock().unwrap()
+        }
+    }
+
+    fn context(agent: &str, at: DateTime<Utc>) -> GenericEntity {
+        GenericEntity {
+            id: uuid::Uuid::new_v4().to_string(),
+            entity_type: "context".to_string(),
+            agent: agent.to_string(),
+            timestamp: at,
+            data: serde_json::json!({}),
+        }
+    }
+
+    /// Quota storage acting as `agent` over a backend stamping the same identity
+    fn quota_storage(clock: Arc<MockClock>, agent: &str) -> QuotaStorage<MemoryStorage> {
+        let config = QuotaConfig::from_yaml(
+            "quotas:\n  per_hour:\n    context: 3\n  agents:\n    importer:\n      \"*\": 10\n",
+        )
+        .unwrap();
+        QuotaStorage::new(MemoryStorage::new(agent), config)
+            .with_agent(agent)
+            .with_clock(clock)
+    }
+
+    #[test]
+    fn test_quota_rejects_then_resets_after_window() {
+        let start = Utc::now();
+        let clock = Arc::new(MockClock(Mutex::new(start)));
+        let mut storage = quota_storage(clock.clone(), "looper");
+
+        for _ in 0..3 {
+            storage.store(&context("looper", clock.now())).unwrap();
+            clock.advance(60);
+        }
+
+        // Naming another agent on the entity does not dodge the quota
+        let err = storage
+            .store(&context("someone-else", clock.now()))
+            .unwrap_err();
+        match &err {
+            EngramError::QuotaExceeded {
+                agent,
+                limit,
+                resets_at,
+                ..
+            } => {
+                assert_eq!(agent, "looper");
+                assert_eq!(*limit, 3);
+                assert_eq!(*resets_at, start + Duration::seconds(QUOTA_WINDOW_SECONDS));
+            }
+            other => panic!("expected QuotaExceeded, got {:?}", other),
+        }
+        assert!(err.to_string().contains("engram escalation create"));
+
+        // Updating an existing entity is not a creation
+        let existing = storage.get_all("context").unwrap().remove(0);
+        storage.store(&existing).unwrap();
+
+        // Once the first creation leaves the window a slot frees up
+        clock.advance(QUOTA_WINDOW_SECONDS - 180 + 1);
+        storage.store(&context("looper", clock.now())).unwrap();
+        assert!(storage.store(&context("looper", clock.now())).is_err());
+    }
+
+    #[test]
+    fn test_exempt_and_overridden_agents() {
+        let clock = Arc::new(MockClock(Mutex::new(Utc::now())));
+
+        let mut exempt = quota_storage(clock.clone(), "default");
+        for _ in 0..5 {
+            exempt.store(&context("default", clock.now())).unwrap();
+        }
+        let mut importer = quota_storage(clock.clone(), "importer");
+        for _ in 0..10 {
+            importer.store(&context("importer", clock.now())).unwrap();
+        }
+        assert!(importer.store(&context("importer", clock.now())).is_err());
+
+        // Batches are checked as a whole before anything is written
+        let mut other = quota_storage(clock.clone(), "other");
+        let batch: Vec<GenericEntity> = (0..4).map(|_| context("other", clock.now())).collect();
+        assert!(other.bulk_store(&batch).is_err());
+        assert!(other
+            .query_by_agent("other", Some("context"))
+            .unwrap()
+            .is_empty());
+        other.bulk_store(&batch[..3]).unwrap();
+    }
+}
@@ -0,0 +1,392 @@
+//! Read-only storage guard
+//!
+//! [`ReadOnlyStorage`] wraps another backend for shared or CI checkouts where
+//! nothing may be written. Reads go straight to the wrapped backend. Writes
+//! are dropped with a warning on stderr in [`ReadOnlyMode::Warn`], rejected
+//! with an error in [`ReadOnlyMode::Strict`], and passed through when the
+//! mode is [`ReadOnlyMode::Off`]. Enabled with `--read-only[=strict]` or the
+//! `ENGRAM_READ_ONLY` environment variable.
+
+use super::{
+    EntityPath, GitCommit, QueryFilter, QueryResult, RelationshipIndex, RelationshipStats,
+    RelationshipStorage, Storage, StorageStats, TraversalAlgorithm,
+};
+use crate::entities::{EntityRelationship, GenericEntity, JsonPatchOp, RelationshipFilter};
+use crate::error::EngramError;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Environment variable enabling read-only mode: `1`/`true`/`warn` or `strict`
+pub const READ_ONLY_ENV_VAR: &str = "ENGRAM_READ_ONLY";
+
+/// How a read-only guard treats writes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReadOnlyMode {
+    /// Writes go to the wrapped backend
+    #[default]
+    Off,
+    /// Drop the write and print a warning
+    Warn,
+    /// Fail the write
+    Strict,
+}
+
+impl FromStr for ReadOnlyMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "0" | "false" | "no" | "off" => Ok(ReadOnlyMode::Off),
+            "" | "1" | "true" | "yes" | "warn" => Ok(ReadOnlyMode::Warn),
+            "strict" => Ok(ReadOnlyMode::Strict),
+            other => Err(format!(
+                "Invalid read-only mode '{}'. Use 'warn', 'strict' or 'off'",
+                other
+            )),
+        }
+    }
+}
+
+impl ReadOnlyMode {
+    /// Mode requested by `ENGRAM_READ_ONLY`; off when unset or empty
+    pub fn from_env() -> Result<Self, EngramError> {
+        match std::env::var(READ_ONLY_ENV_VAR) {
+            Ok(value) if !value.trim().is_empty() => value
+                .parse()
+                .map_err(|e| EngramError::Validation(format!("{}: {}", READ_ONLY_ENV_VAR, e))),
+            _ => Ok(ReadOnlyMode::Off),
+        }
+    }
+
+    /// Mode from the `--read-only` flag, falling back to `ENGRAM_READ_ONLY`
+    pub fn resolve(flag: Option<ReadOnlyMode>) -> Result<Self, EngramError> {
+        flag.map_or_else(Self::from_env, Ok)
+    }
+
+    pub fn is_enabled(self) -> bool {
+        self != ReadOnlyMode::Off
+    }
+}
+
+/// Storage wrapper that keeps writes away from the wrapped backend
+#[derive(Debug, Clone)]
+pub struct ReadOnlyStorage<S> {
+    inner: S,
+    mode: ReadOnlyMode,
+}
+
+impl<S: Storage> ReadOnlyStorage<S> {
+    pub fn new(inner: S, mode: ReadOnlyMode) -> Self {
+        Self { inner, mode }
+    }
+
+    /// The wrapped backend
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    pub fn mode(&self) -> ReadOnlyMode {
+        self.mode
+    }
+
+    /// Whether a write may go through; `Ok(false)` means it was skipped
+    fn allow(&self, operation: impl FnOnce() -> String) -> Result<bool, EngramError> {
+        match self.mode {
+            ReadOnlyMode::Off => Ok(true),
+            ReadOnlyMode::Warn => {
+                eprintln!("⚠️  Read-only mode: skipped {}", operation());
+                Ok(false)
+            }
+            ReadOnlyMode::Strict => Err(EngramError::InvalidOperation(format!(
+                "Workspace is read-only; refused {}",
+                operation()
+            ))),
+        }
+    }
+}
+
+impl<S: Storage + 'static> Storage for ReadOnlyStorage<S> {
+    fn store(&mut self, entity: &GenericEntity) -> Result<(), EngramError> {
+        if self.allow(|| format!("store of {} {}", entity.entity_type, entity.id))? {
+            self.inner.store(entity)?;
+        }
+        Ok(())
+    }
+
+    fn patch(
+        &mut self,
+        id: &str,
+        entity_type: &str,
+        patch: Vec<JsonPatchOp>,
+    ) -> Result<(), EngramError> {
+        if self.allow(|| format!("patch of {} {}", entity_type, id))? {
+            self.inner.patch(id, entity_type, patch)?;
+        }
+        Ok(())
+    }
+
+    fn get(&self, id: &str, entity_type: &str) -> Result<Option<GenericEntity>, EngramError> {
+        self.inner.get(id, entity_type)
+    }
+
+    fn validate_for_store(&self, entity: &GenericEntity) -> Result<(), EngramError> {
+        self.inner.validate_for_store(entity)
+    }
+
+    fn query(&self, filter: &QueryFilter) -> Result<QueryResult, EngramError> {
+        self.inner.query(filter)
+    }
+
+    fn query_by_agent(
+        &self,
+        agent: &str,
+        entity_type: Option<&str>,
+    ) -> Result<Vec<GenericEntity>, EngramError> {
+        self.inner.query_by_agent(agent, entity_type)
+    }
+
+    fn query_by_time_range(
+        &self,
+        start: chrono::DateTime<chrono::Utc>,
+        end: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<GenericEntity>, EngramError> {
+        self.inner.query_by_time_range(start, end)
+    }
+
+    fn query_by_type(
+        &self,
+        entity_type: &str,
+        filters: Option<&HashMap<String, Value>>,
+        limit: Option<usize>,
+        offset: Option<usize>,
+    ) -> Result<QueryResult, EngramError> {
+        self.inner
+            .query_by_type(entity_type, filters, limit, offset)
+    }
+
+    fn text_search(
+        &self,
+        query: &str,
+        entity_types: Option<&[String]>,
+        limit: Option<usize>,
+    ) -> Result<Vec<GenericEntity>, EngramError> {
+        self.inner.text_search(query, entity_types, limit)
+    }
+
+    fn count(&self, filter: &QueryFilter) -> Result<usize, EngramError> {
+        self.inner.count(filter)
+    }
+
+    fn delete(&mut self, id: &str, entity_type: &str) -> Result<(), EngramError> {
+        if self.allow(|| format!("delete of {} {}", entity_type, id))? {
+            self.inner.delete(id, entity_type)?;
+        }
+        Ok(())
+    }
+
+    fn list_ids(&self, entity_type: &str) -> Result<Vec<String>, EngramError> {
+        self.inner.list_ids(entity_type)
+    }
+
+    fn get_all(&self, entity_type: &str) -> Result<Vec<GenericEntity>, EngramError> {
+        self.inner.get_all(entity_type)
+    }
+
+    fn sync(&mut self) -> Result<(), EngramError> {
+        if self.allow(|| "sync".to_string())? {
+            self.inner.sync()?;
+        }
+        Ok(())
+    }
+
+    fn current_branch(&self) -> Result<String, EngramError> {
+        self.inner.current_branch()
+    }
+
+    fn create_branch(&mut self, branch_name: &str) -> Result<(), EngramError> {
+        if self.allow(|| format!("creation of branch {}", branch_name))? {
+            self.inner.create_branch(branch_name)?;
+        }
+        Ok(())
+    }
+
+    fn switch_branch(&mut self, branch_name: &str) -> Result<(), EngramError> {
+        if self.allow(|| format!("switch to branch {}", branch_name))? {
+            self.inner.switch_branch(branch_name)?;
+        }
+        Ok(())
+    }
+
+    fn merge_branches(&mut self, source: &str, target: &str) -> Result<(), EngramError> {
+        if self.allow(|| format!("merge of {} into {}", source, target))? {
+            self.inner.merge_branches(source, target)?;
+        }
+        Ok(())
+    }
+
+    fn history(&self, limit: Option<usize>) -> Result<Vec<GitCommit>, EngramError> {
+        self.inner.history(limit)
+    }
+
+    fn bulk_store(&mut self, entities: &[GenericEntity]) -> Result<(), EngramError> {
+        if entities.is_empty() {
+            return Ok(());
+        }
+        if self.allow(|| format!("bulk store of {} entities", entities.len()))? {
+            self.inner.bulk_store(entities)?;
+        }
+        Ok(())
+    }
+
+    fn get_stats(&self) -> Result<StorageStats, EngramError> {
+        self.inner.get_stats()
+    }
+
+    fn is_read_only(&self) -> bool {
+        self.mode.is_enabled() || self.inner.is_read_only()
+    }
+
+    /// The guard is transparent, so downcasts see the wrapped backend
+    fn as_any(&self) -> &dyn std::any::Any {
+        self.inner.as_any()
+    }
+}
+
+impl<S: RelationshipStorage + 'static> RelationshipStorage for ReadOnlyStorage<S> {
+    fn store_relationship(&mut self, relationship: &EntityRelationship) -> Result<(), EngramError> {
+        if self.allow(|| format!("store of relationship {}", relationship.id))? {
+            self.inner.store_relationship(relationship)?;
+        }
+        Ok(())
+    }
+
+    fn get_relationship(&self, id: &str) -> Result<Option<EntityRelationship>, EngramError> {
+        self.inner.get_relationship(id)
+    }
+
+    fn query_relationships(
+        &self,
+        filter: &RelationshipFilter,
+    ) -> Result<Vec<EntityRelationship>, EngramError> {
+        self.inner.query_relationships(filter)
+    }
+
+    fn get_entity_relationships(
+        &self,
+        entity_id: &str,
+    ) -> Result<Vec<EntityRelationship>, EngramError> {
+        self.inner.get_entity_relationships(entity_id)
+    }
+
+    fn get_outbound_relationships(
+        &self,
+        entity_id: &str,
+    ) -> Result<Vec<EntityRelationship>, EngramError> {
+        self.inner.get_outbound_relationships(entity_id)
+    }
+
+    fn get_inbound_relationships(
+        &self,
+        entity_id: &str,
+    ) -> Result<Vec<EntityRelationship>, EngramError> {
+        self.inner.get_inbound_relationships(entity_id)
+    }
+
+    fn find_paths(
+        &self,
+        source_id: &str,
+        target_id: &str,
+        algorithm: TraversalAlgorithm,
+        max_depth: Option<usize>,
+    ) -> Result<Vec<EntityPath>, EngramError> {
+        self.inner
+            .find_paths(source_id, target_id, algorithm, max_depth)
+    }
+
+    fn get_connected_entities(
+        &self,
+        entity_id: &str,
+        algorithm: TraversalAlgorithm,
+        max_depth: Option<usize>,
+    ) -> Result<Vec<String>, EngramError> {
+        self.inner
+            .get_connected_entities(entity_id, algorithm, max_depth)
+    }
+
+    fn delete_relationship(&mut self, id: &str) -> Result<(), EngramError> {
+        if self.allow(|| format!("delete of relationship {}", id))? {
+            self.inner.delete_relationship(id)?;
+        }
+        Ok(())
+    }
+
+    fn get_relationship_index(&self) -> Result<&RelationshipIndex, EngramError> {
+        self.inner.get_relationship_index()
+    }
+
+    /// The index lives in memory, so rebuilding it is not a write
+    fn rebuild_relationship_index(&mut self) -> Result<(), EngramError> {
+        self.inner.rebuild_relationship_index()
+    }
+
+    fn get_relationship_stats(&self) -> Result<RelationshipStats, EngramError> {
+        self.inner.get_relationship_stats()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::MemoryStorage;
+
+    fn entity() -> GenericEntity {
+        GenericEntity {
+            id: "ctx-1".to_string(),
+            entity_type: "context".to_string(),
+            agent: "default".to_string(),
+            timestamp: chrono::Utc::now(),
+            data: serde_json::json!({"title": "Shared"}),
+        }
+    }
+
+    #[test]
+    fn test_mode_parsing() {
+        assert_eq!("1".parse::<ReadOnlyMode>(), Ok(ReadOnlyMode::Warn));
+        assert_eq!("warn".parse::<ReadOnlyMode>(), Ok(ReadOnlyMode::Warn));
+        assert_eq!("STRICT".parse::<ReadOnlyMode>(), Ok(ReadOnlyMode::Strict));
+        assert_eq!("0".parse::<ReadOnlyMode>(), Ok(ReadOnlyMode::Off));
+        assert!("sometimes".parse::<ReadOnlyMode>().is_err());
+    }
+
+    #[test]
+    fn test_writes_never_reach_inner() {
+        let mut memory = MemoryStorage::new("default");
+        memory.store(&entity()).unwrap();
+
+        let mut warn = ReadOnlyStorage::new(memory.clone(), ReadOnlyMode::Warn);
+        let mut updated = entity();
+        updated.data = serde_json::json!({"title": "Changed"});
+        warn.store(&updated).unwrap();
+        warn.delete("ctx-1", "context").unwrap();
+        assert!(warn.is_read_only());
+        assert_eq!(
+            warn.get("ctx-1", "context").unwrap().unwrap().data["title"],
+            "Shared"
+        );
+
+        let mut strict = ReadOnlyStorage::new(memory.clone(), ReadOnlyMode::Strict);
+        assert!(matches!(
+            strict.store(&updated),
+            Err(EngramError::InvalidOperation(_))
+        ));
+        assert!(strict.delete("ctx-1", "context").is_err());
+        assert!(strict.bulk_store(&[]).is_ok());
+        assert_eq!(memory.get_all("context").unwrap().len(), 1);
+
+        let mut off = ReadOnlyStorage::new(memory.clone(), ReadOnlyMode::Off);
+        assert!(!off.is_read_only());
+        off.delete("ctx-1", "context").unwrap();
+        assert!(memory.get_all("context").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,144 @@
+//! Workspace-level advisory lock serialising storage writes
+//!
+//! The watcher daemon and CLI commands can write to the same repository at
+//! once. Writers take an exclusive `flock` on `.engram/storage.lock` for the
+//! duration of a write; readers never take it. The holder's pid and start
+//! time are written into the lockfile so a timed-out waiter can say who is
+//! holding it.
+
+use crate::error::EngramError;
+use chrono::{DateTime, Utc};
+use std::fs::{File, OpenOptions, TryLockError};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Lockfile name inside the `.engram` directory
+pub const LOCK_FILE_NAME: &str = "storage.lock";
+
+/// Environment variable overriding the lock wait timeout, in seconds
+pub const LOCK_TIMEOUT_ENV_VAR: &str = "ENGRAM_LOCK_TIMEOUT_SECS";
+
+/// Wait timeout used when none is configured
+pub const DEFAULT_LOCK_TIMEOUT: Duration = Duration::from_secs(10);
+
+const RETRY_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Lock wait timeout from the environment, falling back to [`DEFAULT_LOCK_TIMEOUT`]
+pub fn lock_timeout_from_env() -> Duration {
+    std::env::var(LOCK_TIMEOUT_ENV_VAR)
+        .ok()
+        .and_then(|v| v.trim().parse::<f64>().ok())
+        .filter(|secs| secs.is_finite() && *secs >= 0.0)
+        .map(Duration::from_secs_f64)
+        .unwrap_or(DEFAULT_LOCK_TIMEOUT)
+}
+
+/// Path of the lockfile for a workspace
+pub fn lock_path(workspace_path: &Path) -> PathBuf {
+    workspace_path.join(".engram").join(LOCK_FILE_NAME)
+}
+
+/// Held exclusive workspace lock; released on drop
+#[derive(Debug)]
+pub struct WorkspaceLock {
+    file: File,
+}
+
+impl WorkspaceLock {
+    /// Acquire the workspace lock, waiting up to `timeout`
+    pub fn acquire(workspace_path: &Path, timeout: Duration) -> Result<Self, EngramError> {
+        let path = lock_path(workspace_path);
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)?;
+
+        let deadline = Instant::now() + timeout;
+        loop {
+            match file.try_lock() {
+                Ok(()) => break,
+                Err(TryLockError::WouldBlock) => {
+                    if Instant::now() >= deadline {
+                        return Err(EngramError::Locked(describe_holder(&mut file)));
+                    }
+                    std::thread::sleep(RETRY_INTERVAL);
+                }
+                Err(TryLockError::Error(e)) => return Err(EngramError::Io(e)),
+            }
+        }
+
+        file.set_len(0)?;
+        file.seek(SeekFrom::Start(0))?;
+        writeln!(file, "{}\n{}", std::process::id(), Utc::now().to_rfc3339())?;
+        file.flush()?;
+
+        Ok(Self { file })
+    }
+}
+
+impl Drop for WorkspaceLock {
+    fn drop(&mut self) {
+        let _ = self.file.unlock();
+    }
+}
+
+fn describe_holder(file: &mut File) -> String {
+    let mut content = String::new();
+    let _ = file
+        .seek(SeekFrom::Start(0))
+        .and_then(|_| file.read_to_string(&mut content));
+
+    let mut lines = content.lines();
+    let pid = lines.next().and_then(|l| l.trim().parse::<u32>().ok());
+    let since = lines
+        .next()
+        .and_then(|l| DateTime::parse_from_rfc3339(l.trim()).ok());
+
+    match (pid, since) {
+        (Some(pid), Some(since)) => format!(
+            "held by pid {} since {}",
+            pid,
+            since.with_timezone(&Utc).format("%Y-%m-%d %H:%M:%S UTC")
+        ),
+        (Some(pid), None) => format!("held by pid {}", pid),
+        _ => "held by another process".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_second_acquire_times_out_with_holder() {
+        let dir = tempdir().unwrap();
+        let _held = WorkspaceLock::acquire(dir.path(), Duration::from_secs(1)).unwrap();
+
+        let err = WorkspaceLock::acquire(dir.path(), Duration::from_millis(50)).unwrap_err();
+        match err {
+            EngramError::Locked(message) => {
+                assert!(
+                    message.contains(&format!("held by pid {}", std::process::id())),
+                    "{}",
+                    message
+                );
+            }
+            other => panic!("expected Locked, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_lock_released_on_drop() {
+        let dir = tempdir().unwrap();
+        drop(WorkspaceLock::acquire(dir.path(), Duration::from_secs(1)).unwrap());
+        assert!(WorkspaceLock::acquire(dir.path(), Duration::from_millis(50)).is_ok());
+    }
+}
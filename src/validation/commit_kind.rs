@@ -0,0 +1,114 @@
+//! Commit kinds the commit hook can pass without validating
+//!
+//! Merge commits (an in-progress merge leaves `MERGE_HEAD` in the git
+//! directory), `fixup!` / `squash!` commits that will be folded into
+//! another commit, and reverts carry no new work to check. Which of them
+//! skip validation is set by `skip_commit_kinds` in the validation config.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// What kind of commit a message is being validated for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommitKind {
+    Merge,
+    Fixup,
+    Squash,
+    Revert,
+    Regular,
+}
+
+impl CommitKind {
+    /// Kinds skipped unless the config says otherwise
+    pub fn default_skipped() -> Vec<CommitKind> {
+        vec![Self::Merge, Self::Fixup, Self::Squash, Self::Revert]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Merge => "merge",
+            Self::Fixup => "fixup",
+            Self::Squash => "squash",
+            Self::Revert => "revert",
+            Self::Regular => "regular",
+        }
+    }
+}
+
+/// Classify a commit from its message and, when given, the state of the
+/// git directory it is being made in
+pub fn classify_commit(message: &str, git_dir: Option<&Path>) -> CommitKind {
+    if git_dir.is_some_and(|dir| dir.join("MERGE_HEAD").exists()) {
+        return CommitKind::Merge;
+    }
+    let subject = message.trim_start();
+    if subject.starts_with("fixup!") || subject.starts_with("amend!") {
+        return CommitKind::Fixup;
+    }
+    if subject.starts_with("squash!") {
+        return CommitKind::Squash;
+    }
+    if subject.starts_with("Revert \"")
+        || git_dir.is_some_and(|dir| dir.join("REVERT_HEAD").exists())
+    {
+        return CommitKind::Revert;
+    }
+    if subject.starts_with("Merge branch ") || subject.starts_with("Merge pull request ") {
+        return CommitKind::Merge;
+    }
+    CommitKind::Regular
+}
+
+/// The repository's git directory, as reported by `git rev-parse`
+pub fn git_dir() -> Option<PathBuf> {
+    git_dir_in(Path::new("."))
+}
+
+/// The git directory of the repository containing `root`
+pub fn git_dir_in(root: &Path) -> Option<PathBuf> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(root)
+        .args(["rev-parse", "--git-dir"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    let dir = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!dir.is_empty()).then(|| root.join(dir))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_commit() {
+        assert_eq!(classify_commit("fixup! feat: x", None), CommitKind::Fixup);
+        assert_eq!(classify_commit("squash! feat: x", None), CommitKind::Squash);
+        assert_eq!(
+            classify_commit("Revert \"feat: x\"\n\nThis reverts commit abc.", None),
+            CommitKind::Revert
+        );
+        assert_eq!(
+            classify_commit("Merge branch 'main' into feature", None),
+            CommitKind::Merge
+        );
+        assert_eq!(
+            classify_commit("feat: add login [TASK-1]", None),
+            CommitKind::Regular
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(
+            classify_commit("feat: resolve conflicts", Some(dir.path())),
+            CommitKind::Regular
+        );
+        std::fs::write(dir.path().join("MERGE_HEAD"), "abc\n").unwrap();
+        assert_eq!(
+            classify_commit("feat: resolve conflicts", Some(dir.path())),
+            CommitKind::Merge
+        );
+    }
+}
@@ -1,8 +1,10 @@
 //! Configuration for pre-commit hook validation
 
 use crate::error::EngramError;
+use crate::validation::CommitKind;
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 /// Configuration for validation rules
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,6 +32,111 @@ pub struct ValidationConfig {
 
     /// Performance settings
     pub performance: PerformanceConfig,
+
+    /// Maximum number of staged files per commit (unlimited when unset)
+    #[serde(default)]
+    pub max_files_per_commit: Option<usize>,
+
+    /// Minimum reasoning steps across the task's linked reasoning entities
+    #[serde(default)]
+    pub min_reasoning_steps: usize,
+
+    /// Overrides keyed by agent name or git `user.email` / `user.name`
+    #[serde(default)]
+    pub per_agent_config: HashMap<String, AgentValidationOverride>,
+
+    /// Warn when a commit touches a file a linked context was sourced from
+    #[serde(default)]
+    pub warn_stale_context: bool,
+
+    /// Trailer keys whose values are task IDs, e.g. `Engram-Task: <uuid>`
+    #[serde(default = "default_task_trailers")]
+    pub task_trailers: Vec<String>,
+
+    /// Alias a GitHub issue reference maps to, with `{number}` and `{repo}`
+    /// placeholders (e.g. `GH-{number}`). The task whose `alias` metadata
+    /// matches then counts as referenced. Unset, issue references are only
+    /// collected and never satisfy the task reference requirement.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issue_task_alias: Option<String>,
+
+    /// Commit kinds the hook passes without validating
+    #[serde(default = "CommitKind::default_skipped")]
+    pub skip_commit_kinds: Vec<CommitKind>,
+
+    /// Time budget for validating one commit, in milliseconds (0 disables)
+    #[serde(default = "default_time_budget_ms")]
+    pub time_budget_ms: u64,
+
+    /// Keep checking after the time budget runs out instead of passing the
+    /// commit with the remaining checks skipped
+    #[serde(default)]
+    pub strict_time_budget: bool,
+}
+
+fn default_time_budget_ms() -> u64 {
+    2000
+}
+
+fn default_task_trailers() -> Vec<String> {
+    vec!["Engram-Task".to_string()]
+}
+
+/// Per-agent adjustments to validation strictness; unset fields keep the base value
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AgentValidationOverride {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub require_task_reference: Option<bool>,
+
+    /// Applies to both the reasoning and context relationship requirements
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub require_relationships: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_files_per_commit: Option<usize>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_reasoning_steps: Option<usize>,
+}
+
+impl AgentValidationOverride {
+    /// Take every field that is set in `other`
+    pub fn merge(&mut self, other: &AgentValidationOverride) {
+        if other.require_task_reference.is_some() {
+            self.require_task_reference = other.require_task_reference;
+        }
+        if other.require_relationships.is_some() {
+            self.require_relationships = other.require_relationships;
+        }
+        if other.max_files_per_commit.is_some() {
+            self.max_files_per_commit = other.max_files_per_commit;
+        }
+        if other.min_reasoning_steps.is_some() {
+            self.min_reasoning_steps = other.min_reasoning_steps;
+        }
+    }
+
+    /// Human-readable list of the fields this override sets
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(v) = self.require_task_reference {
+            parts.push(format!("require_task_reference={}", v));
+        }
+        if let Some(v) = self.require_relationships {
+            parts.push(format!("require_relationships={}", v));
+        }
+        if let Some(v) = self.max_files_per_commit {
+            parts.push(format!("max_files_per_commit={}", v));
+        }
+        if let Some(v) = self.min_reasoning_steps {
+            parts.push(format!("min_reasoning_steps={}", v));
+        }
+        if parts.is_empty() {
+            "(no changes)".to_string()
+        } else {
+            parts.join(", ")
+        }
+    }
 }
 
 /// Pattern for matching task IDs in commit messages
@@ -119,6 +226,15 @@ impl Default for ValidationConfig {
                 },
             ],
             performance: PerformanceConfig::default(),
+            max_files_per_commit: None,
+            min_reasoning_steps: 0,
+            per_agent_config: HashMap::new(),
+            warn_stale_context: false,
+            task_trailers: default_task_trailers(),
+            issue_task_alias: None,
+            skip_commit_kinds: CommitKind::default_skipped(),
+            time_budget_ms: default_time_budget_ms(),
+            strict_time_budget: false,
         }
     }
 }
@@ -126,7 +242,7 @@ impl Default for ValidationConfig {
 impl Default for PerformanceConfig {
     fn default() -> Self {
         Self {
-            cache_ttl_seconds: 300, // 5 minutes
+            cache_ttl_seconds: 120, // 2 minutes
             max_cache_entries: 1000,
             enable_parallel_validation: true,
             validation_timeout_seconds: 30,
@@ -135,6 +251,44 @@ impl Default for PerformanceConfig {
 }
 
 impl ValidationConfig {
+    /// Location of the validation config inside a workspace
+    pub fn workspace_path(workspace: &Path) -> PathBuf {
+        workspace.join(".engram").join("validation.yaml")
+    }
+
+    /// Load the workspace validation config, or the defaults if none is saved
+    pub fn load_for_workspace(workspace: &Path) -> Result<Self, EngramError> {
+        let path = Self::workspace_path(workspace);
+        if path.exists() {
+            Self::load_from_file(path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    /// Copy of this config with `agent`'s override applied, if one exists
+    pub fn for_agent(&self, agent: Option<&str>) -> ValidationConfig {
+        let mut config = self.clone();
+        let Some(over) = agent.and_then(|a| self.per_agent_config.get(a)) else {
+            return config;
+        };
+
+        if let Some(v) = over.require_task_reference {
+            config.require_task_reference = v;
+        }
+        if let Some(v) = over.require_relationships {
+            config.require_reasoning_relationship = v;
+            config.require_context_relationship = v;
+        }
+        if let Some(v) = over.max_files_per_commit {
+            config.max_files_per_commit = Some(v);
+        }
+        if let Some(v) = over.min_reasoning_steps {
+            config.min_reasoning_steps = v;
+        }
+        config
+    }
+
     /// Load configuration from file
     pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self, EngramError> {
         let content = std::fs::read_to_string(path).map_err(|e| EngramError::Io(e))?;
@@ -237,6 +391,15 @@ impl ValidationConfig {
         for pattern in &self.task_id_patterns {
             examples.push(format!("  - {}: {}", pattern.name, pattern.example));
         }
+        for trailer in &self.task_trailers {
+            examples.push(format!("  - Trailer: {}: <task-id>", trailer));
+        }
+        if let Some(alias) = &self.issue_task_alias {
+            examples.push(format!(
+                "  - Issue reference: Fixes #123 (task with alias {})",
+                alias
+            ));
+        }
 
         examples.push("\nExemptions:".to_string());
         for exemption in &self.exemptions {
@@ -257,3 +420,53 @@ impl ValidationConfig {
         examples.join("\n")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_for_agent_applies_only_set_fields() {
+        let mut config = ValidationConfig::default();
+        config.per_agent_config.insert(
+            "junior".to_string(),
+            AgentValidationOverride {
+                require_relationships: Some(false),
+                max_files_per_commit: Some(5),
+                ..Default::default()
+            },
+        );
+
+        let junior = config.for_agent(Some("junior"));
+        assert!(!junior.require_reasoning_relationship);
+        assert!(!junior.require_context_relationship);
+        assert_eq!(junior.max_files_per_commit, Some(5));
+        assert!(junior.require_task_reference);
+
+        let other = config.for_agent(Some("senior"));
+        assert!(other.require_reasoning_relationship);
+        assert_eq!(other.max_files_per_commit, None);
+    }
+
+    #[test]
+    fn test_override_merge_and_yaml_round_trip() {
+        let mut over = AgentValidationOverride {
+            require_task_reference: Some(true),
+            ..Default::default()
+        };
+        over.merge(&AgentValidationOverride {
+            min_reasoning_steps: Some(2),
+            ..Default::default()
+        });
+        assert_eq!(over.require_task_reference, Some(true));
+        assert_eq!(over.min_reasoning_steps, Some(2));
+
+        let mut config = ValidationConfig::default();
+        config
+            .per_agent_config
+            .insert("alice".to_string(), over.clone());
+        let yaml = serde_yaml::to_string(&config).unwrap();
+        let parsed: ValidationConfig = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(parsed.per_agent_config.get("alice"), Some(&over));
+    }
+}
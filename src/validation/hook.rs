@@ -1,7 +1,8 @@
-//! Git pre-commit hook management
+//! Git pre-commit and pre-push hook management
 
 use crate::error::EngramError;
 use crate::validation::config::ValidationConfig;
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
 
@@ -71,7 +72,8 @@ COMMIT_MSG="$(cat "$1")"
 
 # Run engram validation
 echo "🔍 Validating commit with engram..."
-if ! "$ENGRAM_BIN" validate commit --message "$COMMIT_MSG"; then
+# With ENGRAM_SIGNING_KEY_PATH set, --record leaves a signed result in .engram/last_validation.json
+if ! "$ENGRAM_BIN" validate commit --message "$COMMIT_MSG" --record; then
     echo "❌ Commit validation failed"
     echo ""
     echo "To fix the commit:"
@@ -87,6 +89,110 @@ exit 0
         )
     }
 
+    /// Generate the pre-push hook script content
+    fn generate_pre_push_script() -> String {
+        r#"#!/usr/bin/env bash
+# ENGRAM_PRE_PUSH_HOOK
+
+set -e
+
+# git passes the remote name and URL as arguments and the refs being pushed on stdin
+REMOTE="$1"
+URL="$2"
+
+SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
+REPO_ROOT="$(cd "$SCRIPT_DIR/../.." && pwd)"
+
+ENGRAM_BIN=""
+
+if command -v engram >/dev/null 2>&1; then
+    ENGRAM_BIN="engram"
+elif [ -x "$REPO_ROOT/result/bin/engram" ]; then
+    ENGRAM_BIN="$REPO_ROOT/result/bin/engram"
+elif [ -x "$REPO_ROOT/target/release/engram" ]; then
+    ENGRAM_BIN="$REPO_ROOT/target/release/engram"
+else
+    echo "❌ Error: engram binary not found"
+    echo "Please install engram or run: cargo install --path ."
+    exit 1
+fi
+
+cd "$REPO_ROOT"
+
+echo "🔍 Validating pushed commits with engram..."
+if ! "$ENGRAM_BIN" validate pre-push --remote "$REMOTE" --url "$URL"; then
+    echo "❌ Push validation failed"
+    echo ""
+    echo "Every pushed commit must reference a task that is InProgress or Done."
+    exit 1
+fi
+
+exit 0
+"#
+        .to_string()
+    }
+
+    /// Install the pre-push hook into `<repo_path>/.git/hooks/pre-push`
+    pub fn install_pre_push_hook(repo_path: &Path) -> Result<(), EngramError> {
+        let hook_path = repo_path.join(".git").join("hooks").join("pre-push");
+
+        if let Some(hooks_dir) = hook_path.parent() {
+            fs::create_dir_all(hooks_dir).map_err(EngramError::Io)?;
+        }
+
+        fs::write(&hook_path, Self::generate_pre_push_script()).map_err(EngramError::Io)?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mut perms = fs::metadata(&hook_path)
+                .map_err(EngramError::Io)?
+                .permissions();
+            perms.set_mode(0o755);
+            fs::set_permissions(&hook_path, perms).map_err(EngramError::Io)?;
+        }
+
+        Ok(())
+    }
+
+    /// Check if the pre-push hook is installed
+    pub fn is_pre_push_installed(&self) -> Result<bool, EngramError> {
+        let hook_path = Path::new(&self.git_dir)
+            .join(".git")
+            .join("hooks")
+            .join("pre-push");
+
+        if !hook_path.exists() {
+            return Ok(false);
+        }
+
+        let content = fs::read_to_string(&hook_path).map_err(EngramError::Io)?;
+
+        Ok(content.contains("ENGRAM_PRE_PUSH_HOOK"))
+    }
+
+    /// Uninstall the pre-push hook
+    pub fn uninstall_pre_push(&mut self) -> Result<(), EngramError> {
+        let hook_path = Path::new(&self.git_dir)
+            .join(".git")
+            .join("hooks")
+            .join("pre-push");
+
+        if hook_path.exists() {
+            let content = fs::read_to_string(&hook_path).map_err(EngramError::Io)?;
+
+            if content.contains("ENGRAM_PRE_PUSH_HOOK") {
+                fs::remove_file(&hook_path).map_err(EngramError::Io)?;
+            } else {
+                return Err(EngramError::Validation(
+                    "Pre-push hook exists but was not installed by Engram".to_string(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
     /// Check if hook is installed
     pub fn is_installed(&self) -> Result<bool, EngramError> {
         let hook_path = Path::new(&self.git_dir)
@@ -173,6 +279,14 @@ exit 0
             "  Hook Installed: {}",
             if status.hook_installed { "✅" } else { "❌" }
         );
+        println!(
+            "  Pre-push Hook Installed: {}",
+            if status.pre_push_installed {
+                "✅"
+            } else {
+                "❌"
+            }
+        );
         println!(
             "  Engram Available: {}",
             if status.engram_available {
@@ -194,6 +308,15 @@ exit 0
             }
         );
 
+        let config = ValidationConfig::load_for_workspace(Path::new(&self.git_dir))?;
+        if !config.per_agent_config.is_empty() {
+            println!("\nPer-agent Overrides:");
+            let overrides: BTreeMap<_, _> = config.per_agent_config.iter().collect();
+            for (agent, over) in overrides {
+                println!("  {}: {}", agent, over.describe());
+            }
+        }
+
         if !status.is_healthy() {
             println!("\nIssues:");
             for issue in status.get_issues() {
@@ -214,6 +337,7 @@ exit 0
 
         // Check if hook is installed
         status.hook_installed = self.is_installed()?;
+        status.pre_push_installed = self.is_pre_push_installed()?;
 
         // Check if engram command is available
         status.engram_available = std::process::Command::new("which")
@@ -237,6 +361,8 @@ exit 0
 pub struct HookStatus {
     pub in_git_repo: bool,
     pub hook_installed: bool,
+    /// Optional; not counted by `is_healthy`
+    pub pre_push_installed: bool,
     pub engram_available: bool,
     pub config_valid: bool,
     pub validation_works: bool,
@@ -287,6 +413,22 @@ mod tests {
 
         let script = hook_manager.generate_hook_script();
         assert!(script.contains("ENGRAM_PRE_COMMIT_HOOK"));
+        assert!(script.contains("validate commit --message \"$COMMIT_MSG\" --record"));
+    }
+
+    #[test]
+    fn test_install_pre_push_hook() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let hook_manager = HookManager::new(temp_dir.path()).unwrap();
+        assert!(!hook_manager.is_pre_push_installed().unwrap());
+
+        HookManager::install_pre_push_hook(temp_dir.path()).unwrap();
+
+        let script = fs::read_to_string(temp_dir.path().join(".git/hooks/pre-push")).unwrap();
+        assert!(script.contains("validate pre-push --remote \"$REMOTE\" --url \"$URL\""));
+        assert!(hook_manager.is_pre_push_installed().unwrap());
+        // The commit-msg hook is managed separately
+        assert!(!hook_manager.is_installed().unwrap());
     }
 
     #[test]
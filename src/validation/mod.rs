@@ -4,32 +4,49 @@
 //! disciplined development practices with proper task referencing and
 //! relationship requirements.
 
+pub mod commit_kind;
 pub mod config;
 pub mod flakiness_tracker;
 pub mod hook;
 pub mod parser;
+pub mod pre_push;
 pub mod quality_gates;
 pub mod stage_transitions;
 pub mod validator;
 pub mod workflow_validator;
 
+use crate::error::EngramError;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use ed25519_dalek::pkcs8::{DecodePrivateKey, DecodePublicKey};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
-pub use config::ValidationConfig;
+pub use commit_kind::{classify_commit, CommitKind};
+pub use config::{AgentValidationOverride, ValidationConfig};
 pub use flakiness_tracker::{
     FlakinessAssessment, FlakinessBlacklistEntry, FlakinessConfig, FlakinessTracker,
 };
 pub use hook::HookManager;
 pub use parser::{CommitMessageParser, ConventionalCommit};
+pub use pre_push::{
+    validate_pre_push, PrePushCommitResult, PrePushTaskCheck, PrePushValidationResult,
+};
 pub use quality_gates::{
-    BuiltinValidators, ComplexityAnalyzer, ComplexityLevel, GateContext, GateResult, LevelSelector,
-    QualityGate, QualityGateError, QualityGateResult, QualityGatesExecutor,
+    BuiltinValidators, ComplexityAnalyzer, ComplexityLevel, GateContext, GateResult,
+    GateStrictness, LevelSelector, ProgressiveEngine, ProgressiveRun, QualityGate,
+    QualityGateError, QualityGateResult, QualityGatesExecutor,
 };
 pub use stage_transitions::{
     StageTransitionManager, StageTransitionRule, TransitionCondition, TransitionEligibility,
 };
-pub use validator::CommitValidator;
+pub use validator::{
+    staged_files, validate_staged_entities, validate_staged_entities_with_config,
+    AggregateValidationResult, CommitValidator, ValidationCacheStats,
+};
 pub use workflow_validator::{StagePolicy, WorkflowValidator};
 
 /// Result of commit validation
@@ -41,6 +58,42 @@ pub struct ValidationResult {
     pub validated_relationships: Vec<String>,
     pub validated_files: Vec<String>,
     pub validation_time_ms: u64,
+    /// Non-blocking findings, such as commits touching a stale context's source
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    /// Where `validation_time_ms` went
+    #[serde(default, skip_serializing_if = "PhaseTimings::is_empty")]
+    pub phase_timings: PhaseTimings,
+}
+
+/// Time spent in each validation phase, in microseconds
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PhaseTimings {
+    /// Message parsing and per-commit limits
+    pub parse_us: u64,
+    /// Finding the referenced task
+    pub task_lookup_us: u64,
+    /// Required relationships and reasoning steps
+    pub relationship_check_us: u64,
+    pub file_scope_us: u64,
+}
+
+impl PhaseTimings {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// One-line breakdown in milliseconds, e.g. for budget warnings
+    pub fn summary(&self) -> String {
+        let ms = |us: u64| us as f64 / 1000.0;
+        format!(
+            "parse {:.1}ms, task lookup {:.1}ms, relationship check {:.1}ms, file scope {:.1}ms",
+            ms(self.parse_us),
+            ms(self.task_lookup_us),
+            ms(self.relationship_check_us),
+            ms(self.file_scope_us)
+        )
+    }
 }
 
 /// Individual validation error
@@ -79,9 +132,30 @@ pub enum TaskIdFormat {
     Brackets, // [TASK-123]
     Colon,    // task:auth-impl-001
     Refs,     // Refs: #456
+    Trailer,  // Engram-Task: <uuid>
+    /// GitHub issue reference mapped to a task alias; `task_id` holds the
+    /// alias until the validator resolves it
+    IssueReference,
     Custom(String),
 }
 
+/// GitHub-style issue cross-reference: `#123`, `owner/repo#45`, `Fixes #7`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IssueReference {
+    /// `owner/repo` when the reference names another repository
+    pub repository: Option<String>,
+    pub number: u64,
+    /// Closing keyword before the reference (`fixes`, `closes`, ...), lowercased
+    pub keyword: Option<String>,
+}
+
+impl IssueReference {
+    /// Whether merging the commit closes the issue on GitHub
+    pub fn closes(&self) -> bool {
+        self.keyword.is_some()
+    }
+}
+
 /// Performance cache for validation results
 #[derive(Debug, Default)]
 pub struct ValidationCache {
@@ -113,6 +187,8 @@ impl ValidationResult {
             validated_relationships,
             validated_files,
             validation_time_ms,
+            warnings: Vec::new(),
+            phase_timings: PhaseTimings::default(),
         }
     }
 
@@ -125,9 +201,23 @@ impl ValidationResult {
             validated_relationships: Vec::new(),
             validated_files: Vec::new(),
             validation_time_ms,
+            warnings: Vec::new(),
+            phase_timings: PhaseTimings::default(),
         }
     }
 
+    /// Record the per-phase breakdown of the validation time
+    pub fn with_phase_timings(mut self, phase_timings: PhaseTimings) -> Self {
+        self.phase_timings = phase_timings;
+        self
+    }
+
+    /// Attach non-blocking warnings to an existing result
+    pub fn with_warnings(mut self, warnings: Vec<String>) -> Self {
+        self.warnings.extend(warnings);
+        self
+    }
+
     /// Add an error to an existing result
     pub fn with_error(mut self, error: ValidationError) -> Self {
         self.errors.push(error);
@@ -239,3 +329,193 @@ impl CachedTaskInfo {
         self.cached_at.elapsed() < self.ttl
     }
 }
+
+/// Environment variable pointing at the agent's PEM private key
+pub const SIGNING_KEY_ENV_VAR: &str = "ENGRAM_SIGNING_KEY_PATH";
+
+/// File, relative to the workspace, where the commit hook records the last signed result
+pub const LAST_VALIDATION_FILE: &str = ".engram/last_validation.json";
+
+/// A validation result with an Ed25519 signature from the agent that produced it
+///
+/// Downstream agents in a pipeline can check that a commit was actually
+/// validated by verifying it against the signing agent's public key. Keys are
+/// PKCS#8 PEM files; generate one with
+/// `openssl genpkey -algorithm ed25519 -out engram-signing.pem` and share the
+/// public half from `openssl pkey -in engram-signing.pem -pubout`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedValidationResult {
+    pub result: ValidationResult,
+    /// Base64 Ed25519 signature over `result` and `signed_at`
+    pub signature: String,
+    /// Base64 raw Ed25519 public key of the signer
+    pub signer_public_key: String,
+    pub signed_at: DateTime<Utc>,
+}
+
+/// Bytes covered by the signature
+#[derive(Serialize)]
+struct SignedPayload<'a> {
+    result: &'a ValidationResult,
+    signed_at: &'a DateTime<Utc>,
+}
+
+fn signed_payload(
+    result: &ValidationResult,
+    signed_at: &DateTime<Utc>,
+) -> Result<Vec<u8>, EngramError> {
+    Ok(serde_json::to_vec(&SignedPayload { result, signed_at })?)
+}
+
+/// Parse a public key given as PEM or as base64 raw key bytes
+fn parse_public_key(key: &str) -> Result<VerifyingKey, EngramError> {
+    let key = key.trim();
+    if key.starts_with("-----BEGIN") {
+        return VerifyingKey::from_public_key_pem(key)
+            .map_err(|e| EngramError::Validation(format!("Invalid public key: {}", e)));
+    }
+    let raw: [u8; 32] = BASE64
+        .decode(key)
+        .map_err(|e| EngramError::Validation(format!("Invalid public key: {}", e)))?
+        .try_into()
+        .map_err(|_| {
+            EngramError::Validation("Invalid public key: expected 32 bytes".to_string())
+        })?;
+    VerifyingKey::from_bytes(&raw)
+        .map_err(|e| EngramError::Validation(format!("Invalid public key: {}", e)))
+}
+
+/// Sign `result` with a PEM-encoded Ed25519 private key
+pub fn sign_validation_result(
+    result: &ValidationResult,
+    private_key_pem: &str,
+) -> Result<SignedValidationResult, EngramError> {
+    let key = SigningKey::from_pkcs8_pem(private_key_pem.trim()).map_err(|e| {
+        EngramError::Validation(format!("Signing key must be an Ed25519 PEM key: {}", e))
+    })?;
+    let signed_at = Utc::now();
+    let signature = key.sign(&signed_payload(result, &signed_at)?);
+
+    Ok(SignedValidationResult {
+        result: result.clone(),
+        signature: BASE64.encode(signature.to_bytes()),
+        signer_public_key: BASE64.encode(key.verifying_key().as_bytes()),
+        signed_at,
+    })
+}
+
+/// Whether `signed` was signed by `trusted_public_key` (PEM or base64) and
+/// has not been altered since. Malformed keys or signatures are errors.
+pub fn verify_signed_result(
+    signed: &SignedValidationResult,
+    trusted_public_key: &str,
+) -> Result<bool, EngramError> {
+    let trusted = parse_public_key(trusted_public_key)?;
+    let signer = parse_public_key(&signed.signer_public_key)?;
+    if trusted != signer {
+        return Ok(false);
+    }
+
+    let signature = BASE64
+        .decode(&signed.signature)
+        .map_err(|e| EngramError::Validation(format!("Invalid signature: {}", e)))?;
+    let signature = Signature::from_slice(&signature)
+        .map_err(|e| EngramError::Validation(format!("Invalid signature: {}", e)))?;
+    let data = signed_payload(&signed.result, &signed.signed_at)?;
+    Ok(trusted.verify(&data, &signature).is_ok())
+}
+
+/// Read the private key named by `ENGRAM_SIGNING_KEY_PATH`, if set
+pub fn signing_key_from_env() -> Result<Option<String>, EngramError> {
+    match std::env::var(SIGNING_KEY_ENV_VAR) {
+        Ok(path) if !path.trim().is_empty() => read_signing_key(Path::new(path.trim())).map(Some),
+        _ => Ok(None),
+    }
+}
+
+/// Read a PEM private key from `path`
+pub fn read_signing_key(path: &Path) -> Result<String, EngramError> {
+    std::fs::read_to_string(path).map_err(|e| {
+        EngramError::Validation(format!("Cannot read signing key {}: {}", path.display(), e))
+    })
+}
+
+/// Write `signed` to [`LAST_VALIDATION_FILE`] under `workspace`
+pub fn record_signed_result(
+    workspace: &Path,
+    signed: &SignedValidationResult,
+) -> Result<PathBuf, EngramError> {
+    let path = workspace.join(LAST_VALIDATION_FILE);
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(&path, serde_json::to_string_pretty(signed)?)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::pkcs8::spki::der::pem::LineEnding;
+    use ed25519_dalek::pkcs8::{EncodePrivateKey, EncodePublicKey};
+
+    fn key_pair() -> (String, String) {
+        let mut seed = [0u8; 32];
+        seed.iter_mut().for_each(|byte| *byte = fastrand::u8(..));
+        let key = SigningKey::from_bytes(&seed);
+        let private = key.to_pkcs8_pem(LineEnding::LF).unwrap().to_string();
+        let public = key
+            .verifying_key()
+            .to_public_key_pem(LineEnding::LF)
+            .unwrap();
+        (private, public)
+    }
+
+    #[test]
+    fn test_sign_verify_and_tamper() {
+        let (private, public) = key_pair();
+        let result = ValidationResult::success(
+            "task-1".to_string(),
+            vec!["relates_to:reasoning".to_string()],
+            vec!["src/lib.rs".to_string()],
+            3,
+        );
+
+        let signed = sign_validation_result(&result, &private).unwrap();
+        assert!(verify_signed_result(&signed, &public).unwrap());
+        // The embedded base64 key is accepted as a trusted key too
+        assert!(verify_signed_result(&signed, &signed.signer_public_key).unwrap());
+
+        let round_trip: SignedValidationResult =
+            serde_json::from_str(&serde_json::to_string(&signed).unwrap()).unwrap();
+        assert!(verify_signed_result(&round_trip, &public).unwrap());
+
+        let mut tampered = signed.clone();
+        tampered.result.task_id = Some("task-2".to_string());
+        assert!(!verify_signed_result(&tampered, &public).unwrap());
+
+        let mut backdated = signed.clone();
+        backdated.signed_at -= chrono::Duration::days(1);
+        assert!(!verify_signed_result(&backdated, &public).unwrap());
+
+        let (_, other_public) = key_pair();
+        assert!(!verify_signed_result(&signed, &other_public).unwrap());
+    }
+
+    #[test]
+    fn test_rejects_non_ed25519_key() {
+        let rsa = openssl::rsa::Rsa::generate(2048).unwrap();
+        let pem = String::from_utf8(
+            openssl::pkey::PKey::from_rsa(rsa)
+                .unwrap()
+                .private_key_to_pem_pkcs8()
+                .unwrap(),
+        )
+        .unwrap();
+        let result = ValidationResult::failure(vec![], 0);
+        assert!(matches!(
+            sign_validation_result(&result, &pem),
+            Err(EngramError::Validation(_))
+        ));
+    }
+}
@@ -1,8 +1,74 @@
 //! Commit message parsing for task ID extraction
+//!
+//! Task IDs come from trailer lines (`Engram-Task: <uuid>`) and from the
+//! configured patterns. GitHub issue references (`Fixes #123`) are collected
+//! separately and only count as task references when `issue_task_alias`
+//! maps them to a task alias. Fenced code blocks and inline code spans are
+//! ignored, so example references in them never match.
 
 use crate::error::EngramError;
-use crate::validation::{config::ValidationConfig, ParsedTaskInfo, TaskIdFormat};
+use crate::validation::{config::ValidationConfig, IssueReference, ParsedTaskInfo, TaskIdFormat};
 use regex::Regex;
+use std::sync::OnceLock;
+
+/// `message` with fenced code blocks and inline code spans blanked out
+fn strip_code(message: &str) -> String {
+    static INLINE: OnceLock<Regex> = OnceLock::new();
+    let inline = INLINE.get_or_init(|| Regex::new(r"`[^`\n]*`").expect("code span regex compiles"));
+
+    let mut fence: Option<&str> = None;
+    let mut lines = Vec::new();
+    for line in message.lines() {
+        let trimmed = line.trim_start();
+        let marker = ["```", "~~~"].into_iter().find(|m| trimmed.starts_with(m));
+        match (fence, marker) {
+            (None, Some(marker)) => {
+                fence = Some(marker);
+                lines.push(String::new());
+            }
+            (Some(open), Some(marker)) if open == marker => {
+                fence = None;
+                lines.push(String::new());
+            }
+            (Some(_), _) => lines.push(String::new()),
+            (None, None) => lines.push(inline.replace_all(line, "").into_owned()),
+        }
+    }
+    lines.join("\n")
+}
+
+/// `Key: value` trailers in the last paragraph of `message`, if every line
+/// of that paragraph is one. The subject line is never a trailer.
+pub fn parse_trailers(message: &str) -> Vec<(String, String)> {
+    static TRAILER: OnceLock<Regex> = OnceLock::new();
+    let trailer = TRAILER.get_or_init(|| {
+        Regex::new(r"^([A-Za-z0-9][A-Za-z0-9-]*):\s*(\S.*)$").expect("trailer regex compiles")
+    });
+
+    let text = strip_code(message);
+    let body: Vec<&str> = text.trim_end().lines().skip(1).collect();
+    let start = body
+        .iter()
+        .rposition(|line| line.trim().is_empty())
+        .map_or(0, |blank| blank + 1);
+    // The last paragraph must follow a blank line after the subject
+    if start == 0 {
+        return Vec::new();
+    }
+    let paragraph = &body[start..];
+    let trailers: Vec<(String, String)> = paragraph
+        .iter()
+        .filter_map(|line| {
+            let captures = trailer.captures(line.trim_end())?;
+            Some((captures[1].to_string(), captures[2].trim().to_string()))
+        })
+        .collect();
+    if trailers.len() == paragraph.len() {
+        trailers
+    } else {
+        Vec::new()
+    }
+}
 
 /// Parser for extracting task IDs from commit messages
 pub struct CommitMessageParser {
@@ -26,16 +92,110 @@ impl CommitMessageParser {
         })
     }
 
-    /// Parse task ID from commit message
+    /// Task IDs given in the configured trailers; a trailer may list
+    /// several, separated by commas or whitespace
+    pub fn parse_trailer_task_ids(&self, message: &str) -> Vec<ParsedTaskInfo> {
+        let mut task_ids: Vec<ParsedTaskInfo> = Vec::new();
+        for (key, value) in parse_trailers(message) {
+            if !self
+                .config
+                .task_trailers
+                .iter()
+                .any(|trailer| trailer.eq_ignore_ascii_case(&key))
+            {
+                continue;
+            }
+            for task_id in value.split(|c: char| c == ',' || c.is_whitespace()) {
+                if !task_id.is_empty() && !task_ids.iter().any(|t| t.task_id == task_id) {
+                    task_ids.push(ParsedTaskInfo {
+                        task_id: task_id.to_string(),
+                        format: TaskIdFormat::Trailer,
+                    });
+                }
+            }
+        }
+        task_ids
+    }
+
+    /// GitHub-style issue references outside code, in order, without
+    /// duplicates
+    pub fn parse_issue_refs(&self, message: &str) -> Vec<IssueReference> {
+        static ISSUE: OnceLock<Regex> = OnceLock::new();
+        let issue = ISSUE.get_or_init(|| {
+            Regex::new(
+                r"(?i)(?:\b(close[sd]?|fix(?:e[sd])?|resolve[sd]?):?\s+)?(?:\b([\w.-]+/[\w.-]+))?#(\d+)\b",
+            )
+            .expect("issue reference regex compiles")
+        });
+
+        let text = strip_code(message);
+        let mut refs: Vec<IssueReference> = Vec::new();
+        for captures in issue.captures_iter(&text) {
+            let hash_start = captures.get(3).map_or(0, |m| m.start() - 1);
+            // `abc#1` or `&#39;` is not a reference
+            if captures.get(2).is_none()
+                && text[..hash_start]
+                    .chars()
+                    .next_back()
+                    .is_some_and(|c| c.is_alphanumeric() || c == '&' || c == '/')
+            {
+                continue;
+            }
+            let Ok(number) = captures[3].parse::<u64>() else {
+                continue;
+            };
+            let reference = IssueReference {
+                repository: captures.get(2).map(|m| m.as_str().to_string()),
+                number,
+                keyword: captures.get(1).map(|m| m.as_str().to_lowercase()),
+            };
+            match refs
+                .iter_mut()
+                .find(|r| r.repository == reference.repository && r.number == number)
+            {
+                Some(existing) => {
+                    if existing.keyword.is_none() {
+                        existing.keyword = reference.keyword;
+                    }
+                }
+                None => refs.push(reference),
+            }
+        }
+        refs
+    }
+
+    /// Task aliases the issue references map to under `issue_task_alias`
+    fn issue_task_aliases(&self, message: &str) -> Vec<ParsedTaskInfo> {
+        let Some(template) = &self.config.issue_task_alias else {
+            return Vec::new();
+        };
+        self.parse_issue_refs(message)
+            .into_iter()
+            .map(|reference| ParsedTaskInfo {
+                task_id: template
+                    .replace("{number}", &reference.number.to_string())
+                    .replace("{repo}", reference.repository.as_deref().unwrap_or("")),
+                format: TaskIdFormat::IssueReference,
+            })
+            .collect()
+    }
+
+    /// Parse task ID from commit message. Trailers come first, then the
+    /// configured patterns, then issue references mapped to task aliases.
     pub fn parse_task_id(&self, message: &str) -> Result<Option<ParsedTaskInfo>, EngramError> {
         // Check for exemptions first
         if self.config.should_exempt(message, "require_task_reference") {
             return Ok(None);
         }
 
+        if let Some(info) = self.parse_trailer_task_ids(message).into_iter().next() {
+            return Ok(Some(info));
+        }
+
         // Try each pattern in order
+        let text = strip_code(message);
         for (pattern_index, pattern) in self.task_id_patterns.iter().enumerate() {
-            if let Some(captures) = pattern.captures(message) {
+            if let Some(captures) = pattern.captures(&text) {
                 if let Some(task_id_match) = captures.get(1) {
                     let task_id = task_id_match.as_str().to_string();
                     let format = match pattern_index {
@@ -52,7 +212,7 @@ impl CommitMessageParser {
             }
         }
 
-        Ok(None)
+        Ok(self.issue_task_aliases(message).into_iter().next())
     }
 
     /// Extract all task IDs from a message (multiple tasks per commit)
@@ -62,12 +222,13 @@ impl CommitMessageParser {
             return Ok(vec![]);
         }
 
-        let mut task_ids = Vec::new();
+        let mut task_ids = self.parse_trailer_task_ids(message);
         let mut used_positions: Vec<std::ops::Range<usize>> = Vec::new();
 
         // Try each pattern in order
+        let text = strip_code(message);
         for (pattern_index, pattern) in self.task_id_patterns.iter().enumerate() {
-            for capture in pattern.captures_iter(message) {
+            for capture in pattern.captures_iter(&text) {
                 if let Some(task_id_match) = capture.get(1) {
                     let position = task_id_match.range();
 
@@ -91,12 +252,20 @@ impl CommitMessageParser {
                         ),
                     };
 
-                    task_ids.push(ParsedTaskInfo { task_id, format });
                     used_positions.push(position);
+                    if !task_ids.iter().any(|t| t.task_id == task_id) {
+                        task_ids.push(ParsedTaskInfo { task_id, format });
+                    }
                 }
             }
         }
 
+        for info in self.issue_task_aliases(message) {
+            if !task_ids.iter().any(|t| t.task_id == info.task_id) {
+                task_ids.push(info);
+            }
+        }
+
         Ok(task_ids)
     }
 
@@ -120,10 +289,7 @@ impl CommitMessageParser {
 
         // Check for task ID requirement
         if !self.config.should_exempt(message, "require_task_reference") {
-            let has_task_id = self
-                .task_id_patterns
-                .iter()
-                .any(|pattern| pattern.is_match(message));
+            let has_task_id = self.parse_task_id(message)?.is_some();
 
             if !has_task_id {
                 errors.push(format!(
@@ -208,6 +374,7 @@ impl CommitMessageParser {
             breaking_change,
             description,
             task_ids,
+            issue_refs: self.parse_issue_refs(message),
             body: self.extract_body(message),
         })
     }
@@ -236,6 +403,8 @@ pub struct ConventionalCommit {
     pub breaking_change: bool,
     pub description: String,
     pub task_ids: Vec<ParsedTaskInfo>,
+    /// GitHub issue cross-references; these are not task references
+    pub issue_refs: Vec<IssueReference>,
     pub body: Option<String>,
 }
 
@@ -362,4 +531,105 @@ mod tests {
         assert_eq!(result[0].task_id, "TASK-123");
         assert_eq!(result[1].task_id, "TASK-456");
     }
+
+    #[test]
+    fn test_trailers_with_multiple_values() {
+        let parser = CommitMessageParser::new().unwrap();
+        let message = "feat: split auth module\n\nMoves token handling out.\n\n\
+                       Engram-Task: 69190cf0-243a-4979-b4c1-604ba48f72eb, auth-2\n\
+                       Reviewed-by: Sam <sam@example.com>\n\
+                       engram-task: auth-3 auth-2";
+
+        let parsed = parser.parse_task_id(message).unwrap().unwrap();
+        assert_eq!(parsed.task_id, "69190cf0-243a-4979-b4c1-604ba48f72eb");
+        assert!(matches!(parsed.format, TaskIdFormat::Trailer));
+
+        let all: Vec<String> = parser
+            .parse_all_task_ids(message)
+            .unwrap()
+            .into_iter()
+            .map(|t| t.task_id)
+            .collect();
+        assert_eq!(
+            all,
+            ["69190cf0-243a-4979-b4c1-604ba48f72eb", "auth-2", "auth-3"]
+        );
+
+        // Only the final paragraph, and only if every line is a trailer
+        let not_trailers = "feat: x\n\nEngram-Task: auth-1\nthis line is prose";
+        assert!(parser.parse_trailer_task_ids(not_trailers).is_empty());
+        assert!(parser
+            .parse_trailer_task_ids("Engram-Task: auth-1")
+            .is_empty());
+    }
+
+    #[test]
+    fn test_mixed_formats_and_issue_refs() {
+        let parser = CommitMessageParser::new().unwrap();
+        let message = "fix(api): handle timeouts [TASK-12]\n\n\
+                       Fixes #123, see acme/api#45 and #123 again.\n\
+                       Not issues: abc#9, &#39;\n\n\
+                       Engram-Task: auth-7";
+
+        let all = parser.parse_all_task_ids(message).unwrap();
+        let ids: Vec<&str> = all.iter().map(|t| t.task_id.as_str()).collect();
+        assert_eq!(ids, ["auth-7", "TASK-12"]);
+        assert!(matches!(all[1].format, TaskIdFormat::Brackets));
+
+        let commit = parser.parse_conventional_commit(message).unwrap();
+        assert_eq!(
+            commit.issue_refs,
+            [
+                IssueReference {
+                    repository: None,
+                    number: 123,
+                    keyword: Some("fixes".to_string()),
+                },
+                IssueReference {
+                    repository: Some("acme/api".to_string()),
+                    number: 45,
+                    keyword: None,
+                },
+            ]
+        );
+        assert!(commit.issue_refs[0].closes());
+
+        // Issue references alone are not task references...
+        let issue_only = "fix: handle timeouts\n\nFixes #123";
+        assert!(parser.parse_task_id(issue_only).unwrap().is_none());
+        assert!(!parser.validate_message(issue_only).unwrap().is_empty());
+
+        // ...unless mapped to task aliases
+        let config = ValidationConfig {
+            issue_task_alias: Some("GH-{number}".to_string()),
+            ..ValidationConfig::default()
+        };
+        let parser = CommitMessageParser::with_config(config).unwrap();
+        let parsed = parser.parse_task_id(issue_only).unwrap().unwrap();
+        assert_eq!(parsed.task_id, "GH-123");
+        assert!(matches!(parsed.format, TaskIdFormat::IssueReference));
+    }
+
+    #[test]
+    fn test_code_blocks_hide_fake_references() {
+        let parser = CommitMessageParser::new().unwrap();
+        let message = "feat: document commit format\n\n\
+                       Example:\n\n\
+                       ```\n\
+                       feat: thing [TASK-999]\n\
+                       Fixes #1\n\
+                       ```\n\n\
+                       Inline `[TASK-998]` too.";
+
+        assert!(parser.parse_task_id(message).unwrap().is_none());
+        assert!(parser.parse_all_task_ids(message).unwrap().is_empty());
+        assert!(parser.parse_issue_refs(message).is_empty());
+
+        // A fenced block cannot fake a trailer block either
+        let fenced_trailer = "feat: x\n\n~~~\nEngram-Task: fake-1\n~~~";
+        assert!(parser.parse_trailer_task_ids(fenced_trailer).is_empty());
+
+        let real = format!("{}\n\nRefs: #77", message);
+        assert_eq!(parser.parse_task_id(&real).unwrap().unwrap().task_id, "77");
+    }
 }
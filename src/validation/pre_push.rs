@@ -0,0 +1,190 @@
+//! Pre-push validation of every commit being pushed
+//!
+//! Where the commit-msg hook checks one commit at a time, the pre-push hook
+//! validates the whole batch and then checks the referenced tasks together:
+//! a task that is still `Todo` cannot be pushed.
+
+use crate::entities::{Entity, Task, TaskStatus};
+use crate::error::EngramError;
+use crate::storage::Storage;
+use crate::validation::CommitMessageParser;
+use serde::{Deserialize, Serialize};
+
+/// Validation outcome for a single commit message
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrePushCommitResult {
+    /// First line of the commit message
+    pub summary: String,
+    pub task_ids: Vec<String>,
+    pub errors: Vec<String>,
+    pub valid: bool,
+}
+
+/// Aggregate status check for a task referenced by the pushed commits
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrePushTaskCheck {
+    pub task_id: String,
+    /// Current status, `None` if the task could not be found
+    pub status: Option<TaskStatus>,
+    pub error: Option<String>,
+}
+
+impl PrePushTaskCheck {
+    pub fn is_valid(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Result of validating a batch of commits before push
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrePushValidationResult {
+    pub commits: Vec<PrePushCommitResult>,
+    pub tasks: Vec<PrePushTaskCheck>,
+    pub valid: bool,
+}
+
+/// Validate the messages of all commits being pushed and the tasks they reference
+pub fn validate_pre_push(
+    storage: &dyn Storage,
+    commits: Vec<String>,
+) -> Result<PrePushValidationResult, EngramError> {
+    let parser = CommitMessageParser::new()?;
+    let mut commit_results = Vec::with_capacity(commits.len());
+    let mut referenced: Vec<String> = Vec::new();
+
+    for message in &commits {
+        let errors = parser.validate_message(message)?;
+        let task_ids: Vec<String> = parser
+            .parse_all_task_ids(message)?
+            .into_iter()
+            .map(|info| info.task_id)
+            .collect();
+
+        for task_id in &task_ids {
+            if !referenced.contains(task_id) {
+                referenced.push(task_id.clone());
+            }
+        }
+
+        commit_results.push(PrePushCommitResult {
+            summary: message.lines().next().unwrap_or("").to_string(),
+            task_ids,
+            valid: errors.is_empty(),
+            errors,
+        });
+    }
+
+    let mut task_checks = Vec::with_capacity(referenced.len());
+    for task_id in referenced {
+        let task = match storage.get(&task_id, Task::entity_type())? {
+            Some(generic) => Some(Task::from_generic(generic)?),
+            None => None,
+        };
+
+        let check = match task {
+            None => PrePushTaskCheck {
+                error: Some(format!("Task {} not found", task_id)),
+                task_id,
+                status: None,
+            },
+            Some(task) => {
+                let error = match task.status {
+                    TaskStatus::InProgress | TaskStatus::Done => None,
+                    ref status => Some(format!(
+                        "Task {} is {:?}; it must be InProgress or Done before pushing",
+                        task_id, status
+                    )),
+                };
+                PrePushTaskCheck {
+                    task_id,
+                    status: Some(task.status),
+                    error,
+                }
+            }
+        };
+        task_checks.push(check);
+    }
+
+    let valid = commit_results.iter().all(|c| c.valid) && task_checks.iter().all(|t| t.is_valid());
+
+    Ok(PrePushValidationResult {
+        commits: commit_results,
+        tasks: task_checks,
+        valid,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::TaskPriority;
+    use crate::storage::MemoryStorage;
+
+    fn store_task(storage: &mut MemoryStorage, status: TaskStatus) -> String {
+        let mut task = Task::new(
+            "Pushed task".to_string(),
+            "Referenced from a commit".to_string(),
+            "test-agent".to_string(),
+            TaskPriority::Medium,
+            None,
+        );
+        task.status = status;
+        storage.store(&task.to_generic()).unwrap();
+        task.id
+    }
+
+    #[test]
+    fn test_pre_push_passes_for_started_tasks() {
+        let mut storage = MemoryStorage::new("test-agent");
+        let in_progress = store_task(&mut storage, TaskStatus::InProgress);
+        let done = store_task(&mut storage, TaskStatus::Done);
+
+        let result = validate_pre_push(
+            &storage,
+            vec![
+                format!("feat: first change [{}]", in_progress),
+                format!("fix: second change [{}]", done),
+                format!("test: more coverage [{}]", in_progress),
+            ],
+        )
+        .unwrap();
+
+        assert!(result.valid);
+        assert_eq!(result.commits.len(), 3);
+        // Each referenced task is checked once
+        assert_eq!(result.tasks.len(), 2);
+    }
+
+    #[test]
+    fn test_pre_push_rejects_todo_task() {
+        let mut storage = MemoryStorage::new("test-agent");
+        let todo = store_task(&mut storage, TaskStatus::Todo);
+
+        let result =
+            validate_pre_push(&storage, vec![format!("feat: not started [{}]", todo)]).unwrap();
+
+        assert!(!result.valid);
+        assert!(result.commits[0].valid);
+        assert_eq!(result.tasks[0].status, Some(TaskStatus::Todo));
+        assert!(!result.tasks[0].is_valid());
+    }
+
+    #[test]
+    fn test_pre_push_rejects_missing_reference_and_unknown_task() {
+        let storage = MemoryStorage::new("test-agent");
+
+        let result = validate_pre_push(
+            &storage,
+            vec![
+                "feat: no task here".to_string(),
+                "feat: unknown task [TASK-999]".to_string(),
+            ],
+        )
+        .unwrap();
+
+        assert!(!result.valid);
+        assert!(!result.commits[0].valid);
+        assert!(result.commits[1].valid);
+        assert!(result.tasks[0].status.is_none());
+    }
+}
@@ -1,6 +1,9 @@
 use crate::entities::Task;
+use serde::{Deserialize, Serialize};
+use std::process::Command;
 
-#[derive(Debug, Clone, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ComplexityLevel {
     Low,
     Medium,
@@ -8,6 +11,95 @@ pub enum ComplexityLevel {
     Critical,
 }
 
+/// Kind of file touched by a change
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileKind {
+    Source,
+    Test,
+    Docs,
+    Other,
+}
+
+impl FileKind {
+    pub fn classify(path: &str) -> Self {
+        let lower = path.to_lowercase();
+        let file_name = lower.rsplit('/').next().unwrap_or(&lower);
+        let extension = file_name.rsplit_once('.').map(|(_, ext)| ext).unwrap_or("");
+
+        if lower.starts_with("tests/")
+            || lower.contains("/tests/")
+            || lower.contains("/test/")
+            || file_name.starts_with("test_")
+            || file_name.contains("_test.")
+            || file_name.contains("_tests.")
+            || file_name.contains(".test.")
+            || file_name.contains(".spec.")
+            || extension == "feature"
+        {
+            Self::Test
+        } else if lower.starts_with("docs/")
+            || lower.contains("/docs/")
+            || matches!(extension, "md" | "rst" | "adoc" | "txt")
+        {
+            Self::Docs
+        } else if matches!(
+            extension,
+            "rs" | "py"
+                | "ts"
+                | "tsx"
+                | "js"
+                | "jsx"
+                | "go"
+                | "java"
+                | "kt"
+                | "c"
+                | "h"
+                | "cpp"
+                | "hpp"
+                | "rb"
+                | "swift"
+                | "sql"
+                | "sh"
+        ) {
+            Self::Source
+        } else {
+            Self::Other
+        }
+    }
+}
+
+/// One line of `git diff --numstat` output
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileChange {
+    pub path: String,
+    pub lines_added: u64,
+    pub lines_removed: u64,
+    pub kind: FileKind,
+}
+
+/// Signals gathered from a task and its staged changes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComplexitySignals {
+    pub lines_added: u64,
+    pub lines_removed: u64,
+    pub files_changed: usize,
+    pub source_files: usize,
+    pub test_files: usize,
+    pub doc_files: usize,
+    pub other_files: usize,
+    pub breaking: bool,
+    pub security: bool,
+    pub score: u32,
+    pub level: ComplexityLevel,
+}
+
+impl ComplexitySignals {
+    pub fn lines_changed(&self) -> u64 {
+        self.lines_added + self.lines_removed
+    }
+}
+
 pub struct ComplexityAnalyzer;
 
 impl ComplexityAnalyzer {
@@ -72,6 +164,127 @@ impl ComplexityAnalyzer {
             _ => ComplexityLevel::Critical,
         }
     }
+
+    /// Parse `git diff --numstat` output. Binary files (`-` counts) count as
+    /// touched with no line changes.
+    pub fn parse_numstat(numstat: &str) -> Vec<FileChange> {
+        numstat
+            .lines()
+            .filter_map(|line| {
+                let mut parts = line.splitn(3, '\t');
+                let added = parts.next()?.trim();
+                let removed = parts.next()?.trim();
+                let path = parts.next()?.trim();
+                if path.is_empty() {
+                    return None;
+                }
+                Some(FileChange {
+                    path: path.to_string(),
+                    lines_added: added.parse().unwrap_or(0),
+                    lines_removed: removed.parse().unwrap_or(0),
+                    kind: FileKind::classify(path),
+                })
+            })
+            .collect()
+    }
+
+    /// Analyze a task against the staged changes (`git diff --cached
+    /// --numstat`), limited to `changed_files` when given
+    pub fn analyze(task: &Task, changed_files: &[String]) -> ComplexitySignals {
+        let mut cmd = Command::new("git");
+        cmd.args(["diff", "--cached", "--numstat"]);
+        if !changed_files.is_empty() {
+            cmd.arg("--").args(changed_files);
+        }
+        let numstat = cmd
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).to_string())
+            .unwrap_or_default();
+
+        Self::analyze_numstat(task, changed_files, &numstat)
+    }
+
+    /// Analyze a task against `numstat` diff output. Files in `changed_files`
+    /// missing from the diff still count as touched.
+    pub fn analyze_numstat(
+        task: &Task,
+        changed_files: &[String],
+        numstat: &str,
+    ) -> ComplexitySignals {
+        let mut changes = Self::parse_numstat(numstat);
+        for file in changed_files {
+            if !changes.iter().any(|c| &c.path == file) {
+                changes.push(FileChange {
+                    path: file.clone(),
+                    lines_added: 0,
+                    lines_removed: 0,
+                    kind: FileKind::classify(file),
+                });
+            }
+        }
+
+        let count = |kind: FileKind| changes.iter().filter(|c| c.kind == kind).count();
+        let has_tag = |name: &str| task.tags.iter().any(|t| t.eq_ignore_ascii_case(name));
+
+        let mut signals = ComplexitySignals {
+            lines_added: changes.iter().map(|c| c.lines_added).sum(),
+            lines_removed: changes.iter().map(|c| c.lines_removed).sum(),
+            files_changed: changes.len(),
+            source_files: count(FileKind::Source),
+            test_files: count(FileKind::Test),
+            doc_files: count(FileKind::Docs),
+            other_files: count(FileKind::Other),
+            breaking: has_tag("breaking"),
+            security: has_tag("security"),
+            score: 0,
+            level: ComplexityLevel::Low,
+        };
+
+        let mut score = match signals.lines_changed() {
+            0..=50 => 0,
+            51..=200 => 1,
+            201..=500 => 2,
+            _ => 3,
+        };
+        score += match signals.files_changed {
+            0..=5 => 0,
+            6..=20 => 1,
+            _ => 2,
+        };
+        if signals.source_files > 0 && signals.test_files == 0 {
+            score += 1;
+        }
+        if signals.breaking {
+            score += 3;
+        }
+        if signals.security {
+            score += 3;
+        }
+
+        let docs_only = signals.files_changed > 0 && signals.doc_files == signals.files_changed;
+        let level = if docs_only && !signals.breaking && !signals.security {
+            ComplexityLevel::Low
+        } else {
+            let from_diff = match score {
+                0..=1 => ComplexityLevel::Low,
+                2..=3 => ComplexityLevel::Medium,
+                4..=5 => ComplexityLevel::High,
+                _ => ComplexityLevel::Critical,
+            };
+            let from_task = Self::analyze_task(task);
+            if from_task > from_diff {
+                from_task
+            } else {
+                from_diff
+            }
+        };
+
+        signals.score = score;
+        signals.level = level;
+        signals
+    }
 }
 
 #[cfg(test)]
@@ -90,6 +303,8 @@ mod tests {
             agent: "test".to_string(),
             start_time: chrono::Utc::now(),
             end_time: None,
+            due_at: None,
+            estimate_seconds: None,
             parent: None,
             children: Vec::new(),
             tags: Vec::new(),
@@ -237,4 +452,75 @@ mod tests {
         );
         assert_eq!(result, ComplexityLevel::Medium);
     }
+
+    const SMALL_FIX: &str = "3\t1\tsrc/cli/task.rs\n5\t0\ttests/task_tests.rs\n";
+    const DOCS_ONLY: &str = "120\t40\tdocs/guide.md\n10\t2\tREADME.md\n";
+    const LARGE_UNTESTED: &str = "300\t120\tsrc/storage/git_refs_storage.rs\n\
+        150\t20\tsrc/storage/mod.rs\n-\t-\tassets/logo.png\n";
+
+    #[test]
+    fn test_parse_numstat() {
+        let changes = ComplexityAnalyzer::parse_numstat(LARGE_UNTESTED);
+        assert_eq!(changes.len(), 3);
+        assert_eq!(changes[0].lines_added, 300);
+        assert_eq!(changes[0].kind, FileKind::Source);
+        assert_eq!(changes[2].lines_added, 0);
+        assert_eq!(changes[2].kind, FileKind::Other);
+    }
+
+    #[test]
+    fn test_classify_file_kinds() {
+        assert_eq!(FileKind::classify("src/lib.rs"), FileKind::Source);
+        assert_eq!(FileKind::classify("tests/bdd/steps.rs"), FileKind::Test);
+        assert_eq!(FileKind::classify("src/app.test.ts"), FileKind::Test);
+        assert_eq!(FileKind::classify("docs/setup.md"), FileKind::Docs);
+        assert_eq!(FileKind::classify("Cargo.toml"), FileKind::Other);
+    }
+
+    #[test]
+    fn test_analyze_numstat_small_tested_fix_is_low() {
+        let signals = ComplexityAnalyzer::analyze_numstat(&make_task("fix"), &[], SMALL_FIX);
+        assert_eq!(signals.lines_changed(), 9);
+        assert_eq!(signals.source_files, 1);
+        assert_eq!(signals.test_files, 1);
+        assert_eq!(signals.level, ComplexityLevel::Low);
+    }
+
+    #[test]
+    fn test_analyze_numstat_docs_only_is_low() {
+        let signals = ComplexityAnalyzer::analyze_numstat(&make_task("docs"), &[], DOCS_ONLY);
+        assert_eq!(signals.doc_files, 2);
+        assert_eq!(signals.level, ComplexityLevel::Low);
+    }
+
+    #[test]
+    fn test_analyze_numstat_large_untested_change_is_high() {
+        let signals =
+            ComplexityAnalyzer::analyze_numstat(&make_task("storage"), &[], LARGE_UNTESTED);
+        assert_eq!(signals.lines_changed(), 590);
+        assert_eq!(signals.score, 4);
+        assert_eq!(signals.level, ComplexityLevel::High);
+    }
+
+    #[test]
+    fn test_analyze_numstat_tags_raise_level() {
+        let mut task = make_task("fix");
+        task.tags = vec!["Security".to_string()];
+        let signals = ComplexityAnalyzer::analyze_numstat(&task, &[], SMALL_FIX);
+        assert!(signals.security);
+        assert!(!signals.breaking);
+        assert_eq!(signals.level, ComplexityLevel::Medium);
+
+        task.tags.push("breaking".to_string());
+        let signals = ComplexityAnalyzer::analyze_numstat(&task, &[], DOCS_ONLY);
+        assert_eq!(signals.level, ComplexityLevel::Critical);
+    }
+
+    #[test]
+    fn test_analyze_numstat_counts_unlisted_changed_files() {
+        let files = vec!["src/cli/task.rs".to_string(), "src/new.rs".to_string()];
+        let signals = ComplexityAnalyzer::analyze_numstat(&make_task("fix"), &files, SMALL_FIX);
+        assert_eq!(signals.files_changed, 3);
+        assert_eq!(signals.source_files, 2);
+    }
 }
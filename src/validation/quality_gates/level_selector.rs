@@ -1,9 +1,31 @@
-use super::{GateContext, QualityGateError, QualityGateResult};
+use super::{
+    BuiltinValidators, ComplexityLevel, GateContext, GateStrictness, QualityGate, QualityGateError,
+    QualityGateResult,
+};
 use crate::entities::progressive_config::GateLevel;
 
 pub struct LevelSelector;
 
 impl LevelSelector {
+    /// Builtin gate set for a complexity level
+    pub fn select_gate_set(level: &ComplexityLevel) -> GateStrictness {
+        match level {
+            ComplexityLevel::Low => GateStrictness::Basic,
+            ComplexityLevel::Medium => GateStrictness::Standard,
+            ComplexityLevel::High | ComplexityLevel::Critical => GateStrictness::Strict,
+        }
+    }
+
+    /// Gates for a fixed strictness; `Auto` has no fixed set and yields none
+    pub fn gates_for(strictness: GateStrictness) -> Vec<QualityGate> {
+        match strictness {
+            GateStrictness::Basic => BuiltinValidators::basic(),
+            GateStrictness::Standard => BuiltinValidators::standard(),
+            GateStrictness::Strict => BuiltinValidators::strict(),
+            GateStrictness::Auto => Vec::new(),
+        }
+    }
+
     pub fn select_level<'a>(
         context: &GateContext,
         available_levels: &'a [GateLevel],
@@ -49,6 +71,8 @@ mod tests {
             agent: "test".to_string(),
             start_time: chrono::Utc::now(),
             end_time: None,
+            due_at: None,
+            estimate_seconds: None,
             parent: None,
             children: Vec::new(),
             tags: Vec::new(),
@@ -167,4 +191,21 @@ mod tests {
         let result = LevelSelector::select_level(&context, &levels).unwrap();
         assert_eq!(result.name, "test-level");
     }
+
+    #[test]
+    fn test_select_gate_set_by_complexity() {
+        assert_eq!(
+            LevelSelector::select_gate_set(&ComplexityLevel::Low),
+            GateStrictness::Basic
+        );
+        assert_eq!(
+            LevelSelector::select_gate_set(&ComplexityLevel::Medium),
+            GateStrictness::Standard
+        );
+        assert_eq!(
+            LevelSelector::select_gate_set(&ComplexityLevel::Critical),
+            GateStrictness::Strict
+        );
+        assert!(LevelSelector::gates_for(GateStrictness::Auto).is_empty());
+    }
 }
@@ -7,14 +7,26 @@
 
 pub mod complexity_analyzer;
 pub mod level_selector;
+pub mod progressive_engine;
 pub mod validators;
 
-pub use complexity_analyzer::{ComplexityAnalyzer, ComplexityLevel};
+pub use complexity_analyzer::{
+    ComplexityAnalyzer, ComplexityLevel, ComplexitySignals, FileChange, FileKind,
+};
 pub use level_selector::LevelSelector;
+pub use progressive_engine::{
+    active_levels, select_gates, GateSelection, GateStrictness, ProgressiveEngine, ProgressiveRun,
+    PROGRESSIVE_STAGE,
+};
 pub use validators::*;
 
-use crate::entities::{Entity, ExecutionResult, ExpectedResult, ValidationStatus};
+use crate::entities::progressive_config::GateLevel;
+use crate::entities::{
+    Entity, ExecutionResult, ExpectedResult, GateDefinition, ProgressiveGateConfig,
+    ValidationStatus,
+};
 use crate::error::EngramError;
+use crate::health::{EngineHealthCheck, HealthState, HealthStatus};
 use crate::storage::Storage;
 use crate::validation::flakiness_tracker::{FlakinessConfig, FlakinessTracker};
 use serde::{Deserialize, Serialize};
@@ -123,6 +135,64 @@ impl QualityGate {
     }
 }
 
+impl From<&GateDefinition> for QualityGate {
+    fn from(definition: &GateDefinition) -> Self {
+        let gate = QualityGate::new(definition.name.clone(), definition.command.clone())
+            .with_timeout(definition.timeout.as_secs().max(1))
+            .with_environment(definition.environment.clone())
+            .with_retry_count(definition.retry_policy.max_attempts.saturating_sub(1));
+        if definition.required {
+            gate
+        } else {
+            gate.optional()
+        }
+    }
+}
+
+impl From<&ExecutionResult> for GateResult {
+    /// The single conversion from executor output to feedback-facing results.
+    /// Skipped gates (optional failures, blacklisted gates) count as success.
+    fn from(result: &ExecutionResult) -> Self {
+        let mut details = HashMap::new();
+        details.insert("command".to_string(), serde_json::json!(result.command));
+        details.insert("exit_code".to_string(), serde_json::json!(result.exit_code));
+        details.insert(
+            "status".to_string(),
+            serde_json::to_value(&result.validation_status).unwrap_or(serde_json::Value::Null),
+        );
+        if result.retry_count > 0 {
+            details.insert(
+                "retry_count".to_string(),
+                serde_json::json!(result.retry_count),
+            );
+        }
+
+        let mut recommendations = Vec::new();
+        match &result.validation_status {
+            ValidationStatus::Failed { reason } | ValidationStatus::Skipped { reason } => {
+                recommendations.push(reason.clone())
+            }
+            ValidationStatus::Passed => {}
+        }
+        if let Some(message) = result
+            .metadata
+            .get("custom_failure_message")
+            .and_then(|v| v.as_str())
+        {
+            recommendations.push(message.to_string());
+        }
+
+        Self {
+            gate_type: result.quality_gate.clone(),
+            success: !result.failed(),
+            score: None,
+            details,
+            execution_time_ms: result.duration_ms,
+            recommendations,
+        }
+    }
+}
+
 /// Quality gates executor
 pub struct QualityGatesExecutor<S: Storage> {
     storage: S,
@@ -359,40 +429,7 @@ impl<S: Storage> QualityGatesExecutor<S> {
         task_id: &str,
         workflow_stage: Option<&str>,
     ) -> Result<Vec<ExecutionResult>, EngramError> {
-        use crate::storage::QueryFilter;
-
-        let mut filter = QueryFilter {
-            entity_type: Some("execution_result".to_string()),
-            limit: Some(100),
-            ..Default::default()
-        };
-
-        let mut field_filters = HashMap::new();
-        field_filters.insert(
-            "task_id".to_string(),
-            serde_json::Value::String(task_id.to_string()),
-        );
-
-        if let Some(stage) = workflow_stage {
-            field_filters.insert(
-                "workflow_stage".to_string(),
-                serde_json::Value::String(stage.to_string()),
-            );
-        }
-
-        filter.field_filters = field_filters;
-
-        let query_result = self.storage.query(&filter)?;
-        let mut results = Vec::new();
-
-        for entity in query_result.entities {
-            if let Ok(execution_result) = ExecutionResult::from_generic(entity) {
-                results.push(execution_result);
-            }
-        }
-
-        results.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
-        Ok(results)
+        execution_results(&self.storage, task_id, workflow_stage)
     }
 
     /// Check if all required gates passed for a workflow stage
@@ -421,6 +458,80 @@ impl<S: Storage> QualityGatesExecutor<S> {
     }
 }
 
+/// Execution results recorded for a task, newest first
+pub fn execution_results(
+    storage: &dyn Storage,
+    task_id: &str,
+    workflow_stage: Option<&str>,
+) -> Result<Vec<ExecutionResult>, EngramError> {
+    use crate::storage::QueryFilter;
+
+    let mut filter = QueryFilter {
+        entity_type: Some("execution_result".to_string()),
+        limit: Some(100),
+        ..Default::default()
+    };
+
+    let mut field_filters = HashMap::new();
+    field_filters.insert(
+        "task_id".to_string(),
+        serde_json::Value::String(task_id.to_string()),
+    );
+
+    if let Some(stage) = workflow_stage {
+        field_filters.insert(
+            "workflow_stage".to_string(),
+            serde_json::Value::String(stage.to_string()),
+        );
+    }
+
+    filter.field_filters = field_filters;
+
+    let query_result = storage.query(&filter)?;
+    let mut results = Vec::new();
+
+    for entity in query_result.entities {
+        if let Ok(execution_result) = ExecutionResult::from_generic(entity) {
+            results.push(execution_result);
+        }
+    }
+
+    results.sort_by_key(|result| std::cmp::Reverse(result.timestamp));
+    Ok(results)
+}
+
+/// Gate levels of the first active progressive gate config, if any
+pub fn configured_gate_levels(storage: &dyn Storage) -> Result<Vec<GateLevel>, EngramError> {
+    Ok(storage
+        .get_all(ProgressiveGateConfig::entity_type())?
+        .into_iter()
+        .filter_map(|e| ProgressiveGateConfig::from_generic(e).ok())
+        .find(|c| c.active)
+        .map(|c| c.gate_levels)
+        .unwrap_or_default())
+}
+
+impl<S: Storage> EngineHealthCheck for QualityGatesExecutor<S> {
+    /// Gates blacklisted as flaky are skipped, which degrades validation
+    fn health_check(&self) -> crate::Result<HealthStatus> {
+        let blacklisted = self.flakiness_tracker.get_all_blacklisted(&self.storage)?;
+        Ok(if blacklisted.is_empty() {
+            HealthStatus::new(
+                "quality_gates",
+                HealthState::Healthy,
+                "no gates blacklisted",
+            )
+        } else {
+            let names: Vec<&str> = blacklisted.iter().map(|e| e.gate_name.as_str()).collect();
+            HealthStatus::new(
+                "quality_gates",
+                HealthState::Degraded,
+                format!("blacklisted as flaky: {}", names.join(", ")),
+            )
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
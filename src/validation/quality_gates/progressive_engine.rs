@@ -0,0 +1,340 @@
+//! Progressive gate selection
+//!
+//! Picks a gate set for a task based on complexity (or a stored
+//! `ProgressiveGateConfig`) and runs it through [`QualityGatesExecutor`].
+
+use super::{
+    ComplexityAnalyzer, ComplexityLevel, ComplexitySignals, GateContext, GateResult, LevelSelector,
+    QualityGate, QualityGatesExecutor,
+};
+use crate::entities::progressive_config::GateLevel;
+use crate::error::EngramError;
+use crate::storage::Storage;
+use serde::{Deserialize, Serialize};
+
+/// Workflow stage recorded on execution results produced by the engine
+pub const PROGRESSIVE_STAGE: &str = "progressive";
+
+/// Requested gate strictness
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GateStrictness {
+    /// Choose from configured levels, or from change complexity
+    Auto,
+    Basic,
+    Standard,
+    Strict,
+}
+
+impl GateStrictness {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "auto" => Some(Self::Auto),
+            "basic" => Some(Self::Basic),
+            "standard" => Some(Self::Standard),
+            "strict" => Some(Self::Strict),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Basic => "basic",
+            Self::Standard => "standard",
+            Self::Strict => "strict",
+        }
+    }
+}
+
+/// Gates chosen for a context
+#[derive(Debug, Clone)]
+pub struct GateSelection {
+    pub level: String,
+    pub signals: ComplexitySignals,
+    pub gates: Vec<QualityGate>,
+}
+
+/// Outcome of a progressive gate run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProgressiveRun {
+    pub task_id: String,
+    pub level: String,
+    pub complexity: ComplexityLevel,
+    pub signals: ComplexitySignals,
+    pub results: Vec<GateResult>,
+}
+
+impl ProgressiveRun {
+    pub fn passed(&self) -> bool {
+        self.results.iter().all(|r| r.success)
+    }
+}
+
+/// Selects gate sets by complexity and delegates execution to
+/// [`QualityGatesExecutor`]
+pub struct ProgressiveEngine<S: Storage> {
+    executor: QualityGatesExecutor<S>,
+    levels: Vec<GateLevel>,
+}
+
+impl<S: Storage> ProgressiveEngine<S> {
+    pub fn new(storage: S) -> Self {
+        Self {
+            executor: QualityGatesExecutor::new(storage),
+            levels: Vec::new(),
+        }
+    }
+
+    /// Use configured gate levels for `auto` selection instead of the builtin
+    /// gate sets. Disabled levels are dropped; the rest are ordered by
+    /// priority.
+    pub fn with_gate_levels(mut self, levels: Vec<GateLevel>) -> Self {
+        self.levels = active_levels(levels);
+        self
+    }
+
+    pub fn executor(&self) -> &QualityGatesExecutor<S> {
+        &self.executor
+    }
+
+    /// Select gates using signals from the staged diff
+    pub fn select(&self, context: &GateContext, strictness: GateStrictness) -> GateSelection {
+        select_gates(&self.levels, context, strictness)
+    }
+
+    pub fn select_with_signals(
+        &self,
+        context: &GateContext,
+        strictness: GateStrictness,
+        signals: ComplexitySignals,
+    ) -> GateSelection {
+        select_with_signals(&self.levels, context, strictness, signals)
+    }
+
+    /// Select and execute gates for the context's task
+    pub fn run(
+        &mut self,
+        context: &GateContext,
+        strictness: GateStrictness,
+        agent: &str,
+    ) -> Result<ProgressiveRun, EngramError> {
+        let selection = self.select(context, strictness);
+        self.execute(context, selection, agent)
+    }
+
+    /// Execute a previously computed selection
+    pub fn execute(
+        &mut self,
+        context: &GateContext,
+        selection: GateSelection,
+        agent: &str,
+    ) -> Result<ProgressiveRun, EngramError> {
+        let results = self.executor.execute_gates(
+            &context.task.id,
+            PROGRESSIVE_STAGE,
+            &selection.gates,
+            agent,
+        )?;
+
+        Ok(ProgressiveRun {
+            task_id: context.task.id.clone(),
+            level: selection.level,
+            complexity: selection.signals.level.clone(),
+            signals: selection.signals,
+            results: results.iter().map(GateResult::from).collect(),
+        })
+    }
+}
+
+/// Enabled levels ordered by priority, as `auto` selection expects them
+pub fn active_levels(levels: Vec<GateLevel>) -> Vec<GateLevel> {
+    let mut levels: Vec<GateLevel> = levels.into_iter().filter(|l| l.enabled).collect();
+    levels.sort_by_key(|l| l.priority);
+    levels
+}
+
+/// Select gates for `context` from `levels` (see [`active_levels`]), falling
+/// back to the builtin gate sets
+pub fn select_gates(
+    levels: &[GateLevel],
+    context: &GateContext,
+    strictness: GateStrictness,
+) -> GateSelection {
+    let signals = ComplexityAnalyzer::analyze(&context.task, &context.changed_files);
+    select_with_signals(levels, context, strictness, signals)
+}
+
+fn select_with_signals(
+    levels: &[GateLevel],
+    context: &GateContext,
+    strictness: GateStrictness,
+    signals: ComplexitySignals,
+) -> GateSelection {
+    let strictness = match strictness {
+        GateStrictness::Auto => match LevelSelector::select_level(context, levels) {
+            Ok(level) => {
+                return GateSelection {
+                    level: level.name.clone(),
+                    signals,
+                    gates: level
+                        .required_gates
+                        .iter()
+                        .map(QualityGate::from)
+                        .chain(
+                            level
+                                .optional_gates
+                                .iter()
+                                .map(|g| QualityGate::from(g).optional()),
+                        )
+                        .collect(),
+                };
+            }
+            Err(_) => LevelSelector::select_gate_set(&signals.level),
+        },
+        fixed => fixed,
+    };
+
+    GateSelection {
+        level: strictness.as_str().to_string(),
+        signals,
+        gates: LevelSelector::gates_for(strictness),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::progressive_config::*;
+    use crate::entities::{Task, TaskPriority};
+    use crate::storage::MemoryStorage;
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    fn make_context(description: &str) -> GateContext {
+        GateContext {
+            task: Task::new(
+                "Gate me".to_string(),
+                description.to_string(),
+                "test-agent".to_string(),
+                TaskPriority::Medium,
+                None,
+            ),
+            changed_files: vec!["src/lib.rs".to_string()],
+            commit_message: None,
+            branch_name: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    fn definition(name: &str, command: &str, required: bool) -> GateDefinition {
+        GateDefinition {
+            name: name.to_string(),
+            command: command.to_string(),
+            timeout: Duration::from_secs(30),
+            required,
+            condition: None,
+            environment: HashMap::new(),
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+
+    fn make_level(required: Vec<GateDefinition>, optional: Vec<GateDefinition>) -> GateLevel {
+        GateLevel {
+            name: "quick".to_string(),
+            threshold: ChangeThreshold {
+                max_lines_changed: 500,
+                max_files_affected: 10,
+                max_complexity_delta: 1.0,
+                allowed_change_types: vec![ChangeType::Feature],
+                risk_level_limit: ProgressiveRiskLevel::Low,
+                file_patterns: vec![],
+            },
+            required_gates: required,
+            optional_gates: optional,
+            max_execution_time: Duration::from_secs(300),
+            parallelization: ParallelizationStrategy::Sequential,
+            failure_handling: FailureHandling::default(),
+            enabled: true,
+            priority: 0,
+        }
+    }
+
+    fn select(description: &str, numstat: &str, strictness: GateStrictness) -> GateSelection {
+        let engine = ProgressiveEngine::new(MemoryStorage::new("test-agent"));
+        let context = make_context(description);
+        let signals = ComplexityAnalyzer::analyze_numstat(&context.task, &[], numstat);
+        engine.select_with_signals(&context, strictness, signals)
+    }
+
+    #[test]
+    fn test_auto_selects_by_complexity() {
+        let simple = select(
+            "fix typo",
+            "4\t1\tsrc/lib.rs\n2\t0\ttests/lib.rs\n",
+            GateStrictness::Auto,
+        );
+        assert_eq!(simple.level, "basic");
+        assert_eq!(simple.signals.level, ComplexityLevel::Low);
+
+        let medium = select(
+            "add endpoint",
+            "180\t20\tsrc/api.rs\n",
+            GateStrictness::Auto,
+        );
+        assert_eq!(medium.level, "standard");
+
+        let complex = select(
+            "security architecture refactor",
+            "4\t1\tsrc/lib.rs\n",
+            GateStrictness::Auto,
+        );
+        assert_eq!(complex.level, "strict");
+        assert!(complex.signals.level >= ComplexityLevel::High);
+        assert!(complex.gates.iter().any(|g| g.name == "format-check"));
+
+        let forced = select("fix typo", "", GateStrictness::Strict);
+        assert_eq!(forced.level, "strict");
+    }
+
+    #[test]
+    fn test_run_configured_level_converts_results() {
+        let level = make_level(
+            vec![definition("echo-ok", "echo ok", true)],
+            vec![definition("lint-advisory", "false", true)],
+        );
+        let mut engine =
+            ProgressiveEngine::new(MemoryStorage::new("test-agent")).with_gate_levels(vec![level]);
+
+        let context = make_context("fix typo");
+        let run = engine
+            .run(&context, GateStrictness::Auto, "test-agent")
+            .unwrap();
+
+        assert_eq!(run.level, "quick");
+        assert_eq!(run.task_id, context.task.id);
+        assert_eq!(run.results.len(), 2);
+        assert_eq!(run.results[0].gate_type, "echo-ok");
+        assert!(run.results[0].success);
+        assert_eq!(run.results[0].details["exit_code"], serde_json::json!(0));
+        // Optional gates never fail the run, but explain why they were skipped
+        assert!(run.results[1].success);
+        assert!(!run.results[1].recommendations.is_empty());
+        assert!(run.passed());
+    }
+
+    #[test]
+    fn test_gate_strictness_parse() {
+        assert_eq!(GateStrictness::parse("AUTO"), Some(GateStrictness::Auto));
+        assert_eq!(GateStrictness::parse("basic"), Some(GateStrictness::Basic));
+        assert_eq!(
+            GateStrictness::parse("Standard"),
+            Some(GateStrictness::Standard)
+        );
+        assert_eq!(
+            GateStrictness::parse("strict"),
+            Some(GateStrictness::Strict)
+        );
+        assert_eq!(GateStrictness::parse("paranoid"), None);
+    }
+}
@@ -88,6 +88,32 @@ impl BuiltinValidators {
         }
     }
 
+    /// Gate set for low-complexity changes: tests plus advisory lint
+    pub fn basic() -> Vec<QualityGate> {
+        vec![Self::cargo_test(), Self::cargo_clippy_optional()]
+    }
+
+    /// Gate set for medium-complexity changes: formatting, tests and strict
+    /// lint
+    pub fn standard() -> Vec<QualityGate> {
+        vec![
+            Self::format_check(),
+            Self::cargo_test(),
+            Self::cargo_clippy(),
+        ]
+    }
+
+    /// Gate set for high-complexity changes: formatting, strict lint, full
+    /// test suite and security audit
+    pub fn strict() -> Vec<QualityGate> {
+        vec![
+            Self::format_check(),
+            Self::cargo_clippy(),
+            Self::full_test_suite(),
+            Self::security_audit().optional(),
+        ]
+    }
+
     /// Create development environment setup gates
     pub fn development_setup() -> Vec<QualityGate> {
         vec![
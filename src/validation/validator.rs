@@ -1,12 +1,25 @@
 //! Core validation engine for commit validation
 
+use crate::entities::progressive_config::GateLevel;
+use crate::entities::{
+    Context, ContextSourceStatus, Entity, EntityRelationship, Reasoning, Task, TaskStatus,
+};
 use crate::error::EngramError;
 use crate::storage::{RelationshipStorage, Storage};
+use crate::validation::quality_gates::{
+    active_levels, configured_gate_levels, execution_results, select_gates, GateContext,
+    GateStrictness, PROGRESSIVE_STAGE,
+};
 use crate::validation::{
-    config::ValidationConfig, parser::CommitMessageParser, CachedTaskInfo, ValidationCache,
-    ValidationError, ValidationErrorType, ValidationResult,
+    config::ValidationConfig, parser::CommitMessageParser, CachedTaskInfo, ParsedTaskInfo,
+    PhaseTimings, TaskIdFormat, ValidationCache, ValidationError, ValidationErrorType,
+    ValidationResult,
 };
-use std::time::Instant;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use tracing::instrument;
 
 /// Main commit validator
 pub struct CommitValidator<S: Storage + RelationshipStorage> {
@@ -14,16 +27,33 @@ pub struct CommitValidator<S: Storage + RelationshipStorage> {
     config: ValidationConfig,
     parser: CommitMessageParser,
     cache: ValidationCache,
+    cache_stats: ValidationCacheStats,
+    /// Task assumed when the message references none, e.g. the task bound
+    /// to the current branch
+    default_task: Option<String>,
+    /// Git checkout whose identity is the committing agent; the current
+    /// directory when unset
+    repo_root: Option<PathBuf>,
 }
 
 impl<S: Storage + RelationshipStorage> CommitValidator<S> {
     /// Create a new validator with default configuration
+    #[instrument(
+        level = "trace",
+        skip_all,
+        fields(entity_type = "commit", operation = "new_validator")
+    )]
     pub fn new(storage: S) -> Result<Self, EngramError> {
         let config = ValidationConfig::default();
         Self::with_config(storage, config)
     }
 
     /// Create a new validator with custom configuration
+    #[instrument(
+        level = "trace",
+        skip_all,
+        fields(entity_type = "commit", operation = "new_validator")
+    )]
     pub fn with_config(storage: S, config: ValidationConfig) -> Result<Self, EngramError> {
         let parser = CommitMessageParser::with_config(config.clone())?;
         Ok(Self {
@@ -31,37 +61,137 @@ impl<S: Storage + RelationshipStorage> CommitValidator<S> {
             config,
             parser,
             cache: ValidationCache::new(),
+            cache_stats: ValidationCacheStats::default(),
+            default_task: None,
+            repo_root: None,
         })
     }
 
+    /// Validate messages without a task reference against `task_id`
+    pub fn with_default_task(mut self, task_id: Option<String>) -> Self {
+        self.default_task = task_id;
+        self
+    }
+
+    /// Read the committer identity from the git checkout at `root`
+    pub fn with_repo_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.repo_root = Some(root.into());
+        self
+    }
+
     /// Validate a commit with staged changes
+    ///
+    /// The committing agent is taken from git `user.email` or `user.name`,
+    /// whichever has a per-agent override configured, read from the
+    /// checkout set with [`with_repo_root`](Self::with_repo_root).
+    #[instrument(skip_all, fields(entity_type = "commit", operation = "validate"))]
     pub fn validate_commit(
         &mut self,
         commit_message: &str,
         staged_files: &[String],
+    ) -> ValidationResult {
+        let agent = self.committing_agent();
+        self.validate_commit_as(commit_message, staged_files, agent.as_deref())
+    }
+
+    /// Validate a commit on behalf of `agent`, applying its override if any
+    #[instrument(skip_all, fields(entity_type = "commit", operation = "validate", agent = ?agent, files = staged_files.len()))]
+    pub fn validate_commit_as(
+        &mut self,
+        commit_message: &str,
+        staged_files: &[String],
+        agent: Option<&str>,
+    ) -> ValidationResult {
+        let result = self.check_commit(commit_message, staged_files, agent);
+        if result.valid {
+            tracing::info!(task_id = ?result.task_id, "commit validated");
+        } else {
+            tracing::info!(
+                errors = result.errors.len(),
+                first_error = ?result.errors.first().map(|e| &e.message),
+                "commit rejected"
+            );
+        }
+        result
+    }
+
+    fn check_commit(
+        &mut self,
+        commit_message: &str,
+        staged_files: &[String],
+        agent: Option<&str>,
     ) -> ValidationResult {
         let start_time = Instant::now();
+        let config = self.config.for_agent(agent);
+        let mut timings = PhaseTimings::default();
+        let result = self.run_phases(
+            commit_message,
+            staged_files,
+            &config,
+            start_time,
+            &mut timings,
+        );
+
+        let budget_warning = over_budget(&config, start_time).then(|| {
+            tracing::warn!(
+                elapsed_ms = start_time.elapsed().as_millis() as u64,
+                budget_ms = config.time_budget_ms,
+                parse_us = timings.parse_us,
+                task_lookup_us = timings.task_lookup_us,
+                relationship_check_us = timings.relationship_check_us,
+                file_scope_us = timings.file_scope_us,
+                "commit validation over its time budget"
+            );
+            format!(
+                "Validation took {}ms, over the {}ms budget ({})",
+                start_time.elapsed().as_millis(),
+                config.time_budget_ms,
+                timings.summary()
+            )
+        });
+        result
+            .with_warnings(budget_warning.into_iter().collect())
+            .with_phase_timings(timings)
+    }
+
+    /// The validation phases in order, each timed into `timings`. Unless
+    /// the budget is strict, running out of time passes the commit with
+    /// the remaining phases skipped.
+    fn run_phases(
+        &mut self,
+        commit_message: &str,
+        staged_files: &[String],
+        config: &ValidationConfig,
+        start_time: Instant,
+        timings: &mut PhaseTimings,
+    ) -> ValidationResult {
+        let phase_start = Instant::now();
 
         // Parse task ID from commit message
         let task_info = match self.parser.parse_task_id(commit_message) {
             Ok(Some(info)) => info,
             Ok(None) => {
-                if self.config.require_task_reference
-                    && !self
-                        .config
-                        .should_exempt(commit_message, "require_task_reference")
+                if config.require_task_reference
+                    && !config.should_exempt(commit_message, "require_task_reference")
                 {
-                    return ValidationResult::failure(
-                        vec![ValidationError::new(
-                            ValidationErrorType::NoTaskReference,
-                            "Commit message must reference a task".to_string(),
-                        )
-                        .with_suggestion(
-                            "Use formats like [TASK-123], [task:auth-impl-001], or Refs: #456"
-                                .to_string(),
-                        )],
-                        start_time.elapsed().as_millis() as u64,
-                    );
+                    if let Some(task_id) = self.default_task.clone() {
+                        ParsedTaskInfo {
+                            task_id,
+                            format: TaskIdFormat::Custom("branch binding".to_string()),
+                        }
+                    } else {
+                        return ValidationResult::failure(
+                            vec![ValidationError::new(
+                                ValidationErrorType::NoTaskReference,
+                                "Commit message must reference a task".to_string(),
+                            )
+                            .with_suggestion(
+                                "Use formats like [TASK-123], [task:auth-impl-001], or Refs: #456"
+                                    .to_string(),
+                            )],
+                            start_time.elapsed().as_millis() as u64,
+                        );
+                    }
                 } else {
                     // Exempt commit - pass validation
                     return ValidationResult::success(
@@ -83,92 +213,167 @@ impl<S: Storage + RelationshipStorage> CommitValidator<S> {
             }
         };
 
+        let task_info = match resolve_issue_alias(&self.storage, task_info) {
+            Ok(task_info) => task_info,
+            Err(error) => {
+                return ValidationResult::failure(
+                    vec![error],
+                    start_time.elapsed().as_millis() as u64,
+                )
+            }
+        };
+
+        if let Some(max_files) = config.max_files_per_commit {
+            if staged_files.len() > max_files {
+                return ValidationResult::failure(
+                    vec![ValidationError::new(
+                        ValidationErrorType::PolicyViolation,
+                        format!(
+                            "Commit touches {} files; at most {} are allowed",
+                            staged_files.len(),
+                            max_files
+                        ),
+                    )
+                    .with_suggestion("Split the change into smaller commits".to_string())],
+                    start_time.elapsed().as_millis() as u64,
+                );
+            }
+        }
+
+        timings.parse_us = phase_start.elapsed().as_micros() as u64;
+        if let Some(result) = skip_rest(config, start_time, &task_info.task_id, staged_files) {
+            return result;
+        }
+
         // Validate task exists and has required relationships
         let (validated_relationships, errors) =
-            self.validate_task_relationships(&task_info.task_id);
+            self.validate_task_relationships(&task_info.task_id, config, timings);
         if !errors.is_empty() {
             return ValidationResult::failure(errors, start_time.elapsed().as_millis() as u64);
         }
+        if let Some(result) = skip_rest(config, start_time, &task_info.task_id, staged_files) {
+            return result;
+        }
 
         // Validate file scope matches task context
-        let (validated_files, errors) = if self.config.require_file_scope_match {
+        let phase_start = Instant::now();
+        let (validated_files, errors) = if config.require_file_scope_match {
             self.validate_file_scope(&task_info.task_id, staged_files)
         } else {
             (staged_files.to_vec(), vec![])
         };
+        timings.file_scope_us = phase_start.elapsed().as_micros() as u64;
 
         if !errors.is_empty() {
             return ValidationResult::failure(errors, start_time.elapsed().as_millis() as u64);
         }
 
+        let warnings = if config.warn_stale_context {
+            let relationships = self
+                .storage
+                .get_entity_relationships(&task_info.task_id)
+                .unwrap_or_default();
+            stale_context_warnings(
+                &self.storage,
+                &task_info.task_id,
+                &relationships,
+                staged_files,
+            )
+        } else {
+            Vec::new()
+        };
+
         ValidationResult::success(
             task_info.task_id,
             validated_relationships,
             validated_files,
             start_time.elapsed().as_millis() as u64,
         )
+        .with_warnings(warnings)
+    }
+
+    /// Git identity of the committer that has a per-agent override, if any
+    fn committing_agent(&self) -> Option<String> {
+        if self.config.per_agent_config.is_empty() {
+            return None;
+        }
+
+        ["user.email", "user.name"].iter().find_map(|key| {
+            let mut git = std::process::Command::new("git");
+            if let Some(root) = &self.repo_root {
+                git.arg("-C").arg(root);
+            }
+            let output = git
+                .args(["config", key])
+                .output()
+                .ok()
+                .filter(|output| output.status.success())?;
+            let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            self.config
+                .per_agent_config
+                .contains_key(&value)
+                .then_some(value)
+        })
+    }
+
+    /// Total steps across the reasoning entities linked to a task
+    fn linked_reasoning_steps(&self, task_id: &str) -> Result<usize, EngramError> {
+        let relationships = self.storage.get_entity_relationships(task_id)?;
+        reasoning_steps(&self.storage, &relationships)
     }
 
     /// Validate task exists and has required relationships
     fn validate_task_relationships(
         &mut self,
         task_id: &str,
+        config: &ValidationConfig,
+        timings: &mut PhaseTimings,
     ) -> (Vec<String>, Vec<ValidationError>) {
         let mut validated_relationships = Vec::new();
-        let mut errors = Vec::new();
+        let lookup_start = Instant::now();
 
-        // Check cache first
+        // Check cache first; entries hold "relationship_type:target_type" pairs
         if let Some(cached_info) = self.cache.get_task_info(task_id) {
-            if self.config.require_reasoning_relationship
-                && !cached_info.relationships.contains(&"reasoning".to_string())
-            {
-                errors.push(
-                    ValidationError::new(
-                        ValidationErrorType::MissingRequiredRelationship,
-                        "Task must have a reasoning relationship".to_string(),
-                    )
-                    .with_suggestion("Create a reasoning entity linked to this task".to_string()),
-                );
-            }
-
-            if self.config.require_context_relationship
-                && !cached_info.relationships.contains(&"context".to_string())
-            {
-                errors.push(
-                    ValidationError::new(
-                        ValidationErrorType::MissingRequiredRelationship,
-                        "Task must have a context relationship".to_string(),
-                    )
-                    .with_suggestion("Create a context entity linked to this task".to_string()),
-                );
-            }
-
+            self.cache_stats.hits += 1;
+            timings.task_lookup_us = lookup_start.elapsed().as_micros() as u64;
+            let check_start = Instant::now();
+            let relationship_types: Vec<String> = cached_info
+                .relationships
+                .iter()
+                .map(|rel| {
+                    rel.rsplit_once(':')
+                        .map_or(rel.as_str(), |(_, target)| target)
+                        .to_string()
+                })
+                .collect();
+            let mut errors = missing_relationship_errors(config, &relationship_types);
             if errors.is_empty() {
                 validated_relationships = cached_info.relationships.clone();
             }
+            errors.extend(self.reasoning_step_errors(task_id, config));
+            timings.relationship_check_us = check_start.elapsed().as_micros() as u64;
 
             return (validated_relationships, errors);
         }
+        self.cache_stats.misses += 1;
 
         // Check if task exists in storage
-        let _task = match self.storage.get(task_id, "task") {
-            Ok(Some(entity)) => entity,
-            Ok(None) => {
-                errors.push(
-                    ValidationError::new(
-                        ValidationErrorType::TaskNotFound,
-                        format!("Task '{}' not found in Engram", task_id),
-                    )
-                    .with_suggestion("Create the task in Engram before committing".to_string()),
-                );
-                return (validated_relationships, errors);
+        let exists = self.storage.exists(task_id, "task");
+        timings.task_lookup_us = lookup_start.elapsed().as_micros() as u64;
+        let check_start = Instant::now();
+        match exists {
+            Ok(true) => {}
+            Ok(false) => {
+                return (validated_relationships, vec![task_not_found(task_id)]);
             }
             Err(_) => {
-                errors.push(ValidationError::new(
-                    ValidationErrorType::ConfigurationError,
-                    "Failed to access Engram storage".to_string(),
-                ));
-                return (validated_relationships, errors);
+                return (
+                    validated_relationships,
+                    vec![ValidationError::new(
+                        ValidationErrorType::ConfigurationError,
+                        "Failed to access Engram storage".to_string(),
+                    )],
+                );
             }
         };
 
@@ -176,11 +381,13 @@ impl<S: Storage + RelationshipStorage> CommitValidator<S> {
         let relationships = match self.storage.get_entity_relationships(task_id) {
             Ok(rels) => rels,
             Err(_) => {
-                errors.push(ValidationError::new(
-                    ValidationErrorType::ConfigurationError,
-                    "Failed to access task relationships".to_string(),
-                ));
-                return (validated_relationships, errors);
+                return (
+                    validated_relationships,
+                    vec![ValidationError::new(
+                        ValidationErrorType::ConfigurationError,
+                        "Failed to access task relationships".to_string(),
+                    )],
+                );
             }
         };
 
@@ -192,37 +399,27 @@ impl<S: Storage + RelationshipStorage> CommitValidator<S> {
         }
 
         // Check required relationships
-        if self.config.require_reasoning_relationship
-            && !relationship_types.iter().any(|t| t == "reasoning")
-        {
-            errors.push(
-                ValidationError::new(
-                    ValidationErrorType::MissingRequiredRelationship,
-                    "Task must have a reasoning relationship".to_string(),
-                )
-                .with_suggestion("Create a reasoning entity linked to this task".to_string()),
-            );
-        }
-
-        if self.config.require_context_relationship
-            && !relationship_types.iter().any(|t| t == "context")
-        {
-            errors.push(
-                ValidationError::new(
-                    ValidationErrorType::MissingRequiredRelationship,
-                    "Task must have a context relationship".to_string(),
-                )
-                .with_suggestion("Create a context entity linked to this task".to_string()),
-            );
-        }
+        let mut errors = missing_relationship_errors(config, &relationship_types);
+        errors.extend(self.reasoning_step_errors(task_id, config));
 
         // Cache the results
-        let cached_info = CachedTaskInfo::new(relationship_types, vec![]);
+        let ttl = Duration::from_secs(self.config.performance.cache_ttl_seconds);
+        let cached_info = CachedTaskInfo::with_ttl(validated_relationships.clone(), vec![], ttl);
         self.cache.cache_task_info(task_id.to_string(), cached_info);
+        timings.relationship_check_us = check_start.elapsed().as_micros() as u64;
 
         (validated_relationships, errors)
     }
 
+    /// Error when the task has fewer linked reasoning steps than required
+    fn reasoning_step_errors(
+        &self,
+        task_id: &str,
+        config: &ValidationConfig,
+    ) -> Vec<ValidationError> {
+        step_count_errors(config, || self.linked_reasoning_steps(task_id))
+    }
+
     /// Validate that changed files are within task scope
     fn validate_file_scope(
         &mut self,
@@ -253,41 +450,31 @@ impl<S: Storage + RelationshipStorage> CommitValidator<S> {
 
     /// Get staged files from git
     pub fn get_staged_files(&self) -> Result<Vec<String>, EngramError> {
-        use std::process::Command;
-
-        let output = Command::new("git")
-            .args(&["diff", "--name-only", "--cached"])
-            .output()
-            .map_err(|e| EngramError::Io(e))?;
-
-        if !output.status.success() {
-            return Err(EngramError::Git(format!(
-                "Failed to get staged files: {}",
-                String::from_utf8_lossy(&output.stderr)
-            )));
-        }
-
-        let output_str = String::from_utf8_lossy(&output.stdout);
-        let files: Vec<String> = output_str
-            .lines()
-            .map(|s| s.trim().to_string())
-            .filter(|s| !s.is_empty())
-            .collect();
-
-        Ok(files)
+        staged_files()
     }
 
     /// Check if validation is enabled
+    #[instrument(
+        level = "trace",
+        skip_all,
+        fields(entity_type = "commit", operation = "is_enabled")
+    )]
     pub fn is_enabled(&self) -> bool {
         self.config.enabled
     }
 
     /// Get configuration
+    #[instrument(
+        level = "trace",
+        skip_all,
+        fields(entity_type = "commit", operation = "get_config")
+    )]
     pub fn get_config(&self) -> &ValidationConfig {
         &self.config
     }
 
     /// Update configuration
+    #[instrument(skip_all, fields(entity_type = "commit", operation = "update_config"))]
     pub fn update_config(&mut self, config: ValidationConfig) -> Result<(), EngramError> {
         config.validate()?;
         self.parser = CommitMessageParser::with_config(config.clone())?;
@@ -297,11 +484,32 @@ impl<S: Storage + RelationshipStorage> CommitValidator<S> {
     }
 
     /// Clear cache
+    #[instrument(
+        level = "debug",
+        skip_all,
+        fields(entity_type = "commit", operation = "clear_cache")
+    )]
     pub fn clear_cache(&mut self) {
         self.cache = ValidationCache::new();
+        self.cache_stats = ValidationCacheStats::default();
+    }
+
+    /// Task cache hits and misses since the validator was created
+    #[instrument(
+        level = "trace",
+        skip_all,
+        fields(entity_type = "commit", operation = "cache_stats")
+    )]
+    pub fn cache_stats(&self) -> ValidationCacheStats {
+        self.cache_stats
     }
 
     /// Get cache statistics
+    #[instrument(
+        level = "trace",
+        skip_all,
+        fields(entity_type = "commit", operation = "cache_stats")
+    )]
     pub fn get_cache_stats(&self) -> CacheStats {
         CacheStats {
             task_cache_size: self.cache.task_cache.len(),
@@ -310,23 +518,35 @@ impl<S: Storage + RelationshipStorage> CommitValidator<S> {
     }
 
     /// Get reference to the storage (for workflow validator integration)
+    #[instrument(
+        level = "trace",
+        skip_all,
+        fields(entity_type = "commit", operation = "storage")
+    )]
     pub fn storage(&self) -> &S {
         &self.storage
     }
 
     /// Warm up cache with common task IDs
+    #[instrument(skip_all, fields(entity_type = "commit", operation = "warm_cache", tasks = task_ids.len()))]
     pub fn warm_cache(&mut self, task_ids: &[String]) -> Result<(), EngramError> {
         for task_id in task_ids {
             // Check if already cached
             if self.cache.get_task_info(task_id).is_none() {
                 // Cache the task info
-                let _task_info = self.validate_task_relationships(task_id);
+                let config = self.config.clone();
+                let _task_info = self.validate_task_relationships(
+                    task_id,
+                    &config,
+                    &mut PhaseTimings::default(),
+                );
             }
         }
         Ok(())
     }
 
     /// Validate a commit with staged changes (deprecated alias)
+    #[instrument(skip_all, fields(entity_type = "commit", operation = "validate"))]
     pub fn validate(
         &mut self,
         commit_message: &str,
@@ -344,10 +564,563 @@ pub struct CacheStats {
     pub file_cache_size: usize,
 }
 
+/// Task relationship cache lookups
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ValidationCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+/// Checks that need only the commit message and staged files: the task
+/// reference and the per-commit file limit
+///
+/// `Err` holds the finished result, either a failure or an exempt pass.
+#[allow(clippy::result_large_err)]
+fn check_message(
+    parser: &CommitMessageParser,
+    config: &ValidationConfig,
+    commit_message: &str,
+    staged_files: &[String],
+    start_time: Instant,
+) -> Result<ParsedTaskInfo, ValidationResult> {
+    // Parse task ID from commit message
+    let task_info = match parser.parse_task_id(commit_message) {
+        Ok(Some(info)) => info,
+        Ok(None) => {
+            if config.require_task_reference
+                && !config.should_exempt(commit_message, "require_task_reference")
+            {
+                return Err(ValidationResult::failure(
+                    vec![ValidationError::new(
+                        ValidationErrorType::NoTaskReference,
+                        "Commit message must reference a task".to_string(),
+                    )
+                    .with_suggestion(
+                        "Use formats like [TASK-123], [task:auth-impl-001], or Refs: #456"
+                            .to_string(),
+                    )],
+                    start_time.elapsed().as_millis() as u64,
+                ));
+            } else {
+                // Exempt commit - pass validation
+                return Err(ValidationResult::success(
+                    "exempt".to_string(),
+                    vec![],
+                    vec![],
+                    start_time.elapsed().as_millis() as u64,
+                ));
+            }
+        }
+        Err(e) => {
+            return Err(ValidationResult::failure(
+                vec![ValidationError::new(
+                    ValidationErrorType::InvalidTaskIdFormat,
+                    format!("Failed to parse task ID: {}", e),
+                )],
+                start_time.elapsed().as_millis() as u64,
+            ));
+        }
+    };
+
+    if let Some(max_files) = config.max_files_per_commit {
+        if staged_files.len() > max_files {
+            return Err(ValidationResult::failure(
+                vec![ValidationError::new(
+                    ValidationErrorType::PolicyViolation,
+                    format!(
+                        "Commit touches {} files; at most {} are allowed",
+                        staged_files.len(),
+                        max_files
+                    ),
+                )
+                .with_suggestion("Split the change into smaller commits".to_string())],
+                start_time.elapsed().as_millis() as u64,
+            ));
+        }
+    }
+
+    Ok(task_info)
+}
+
+/// Errors for required relationships missing from `relationship_types`
+fn missing_relationship_errors(
+    config: &ValidationConfig,
+    relationship_types: &[String],
+) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+
+    if config.require_reasoning_relationship && !relationship_types.iter().any(|t| t == "reasoning")
+    {
+        errors.push(
+            ValidationError::new(
+                ValidationErrorType::MissingRequiredRelationship,
+                "Task must have a reasoning relationship".to_string(),
+            )
+            .with_suggestion("Create a reasoning entity linked to this task".to_string()),
+        );
+    }
+
+    if config.require_context_relationship && !relationship_types.iter().any(|t| t == "context") {
+        errors.push(
+            ValidationError::new(
+                ValidationErrorType::MissingRequiredRelationship,
+                "Task must have a context relationship".to_string(),
+            )
+            .with_suggestion("Create a context entity linked to this task".to_string()),
+        );
+    }
+
+    errors
+}
+
+/// Error for a referenced task that is not in storage
+/// Whether validation started at `start_time` has used up its budget
+fn over_budget(config: &ValidationConfig, start_time: Instant) -> bool {
+    config.time_budget_ms > 0 && start_time.elapsed() > Duration::from_millis(config.time_budget_ms)
+}
+
+/// Pass the commit without the remaining phases once a non-strict budget
+/// is used up
+fn skip_rest(
+    config: &ValidationConfig,
+    start_time: Instant,
+    task_id: &str,
+    staged_files: &[String],
+) -> Option<ValidationResult> {
+    if config.strict_time_budget || !over_budget(config, start_time) {
+        return None;
+    }
+    Some(
+        ValidationResult::success(
+            task_id.to_string(),
+            vec![],
+            staged_files.to_vec(),
+            start_time.elapsed().as_millis() as u64,
+        )
+        .with_warnings(vec![
+            "Remaining checks skipped: validation ran out of time".to_string()
+        ]),
+    )
+}
+
+/// Swap an issue reference's task alias for the ID of the task carrying
+/// that `alias` metadata; other references pass through unchanged
+fn resolve_issue_alias(
+    storage: &dyn Storage,
+    task_info: ParsedTaskInfo,
+) -> Result<ParsedTaskInfo, ValidationError> {
+    if !matches!(task_info.format, TaskIdFormat::IssueReference) {
+        return Ok(task_info);
+    }
+    let alias = &task_info.task_id;
+    let tasks = storage.get_all("task").unwrap_or_default();
+    let mut matches = tasks.iter().filter(|task| {
+        task.data
+            .get("metadata")
+            .and_then(|metadata| metadata.get("alias"))
+            .and_then(|value| value.as_str())
+            .is_some_and(|value| value.eq_ignore_ascii_case(alias))
+    });
+    match (matches.next(), matches.next()) {
+        (Some(task), None) => Ok(ParsedTaskInfo {
+            task_id: task.id.clone(),
+            format: TaskIdFormat::IssueReference,
+        }),
+        (Some(_), Some(_)) => Err(ValidationError::new(
+            ValidationErrorType::InvalidTaskIdFormat,
+            format!("Several tasks have alias '{}'", alias),
+        )
+        .with_suggestion("Reference the task by ID instead".to_string())),
+        (None, _) => Err(ValidationError::new(
+            ValidationErrorType::TaskNotFound,
+            format!("No task has alias '{}' for the referenced issue", alias),
+        )
+        .with_suggestion(format!(
+            "Set metadata alias '{}' on the task, or reference it by ID",
+            alias
+        ))),
+    }
+}
+
+fn task_not_found(task_id: &str) -> ValidationError {
+    ValidationError::new(
+        ValidationErrorType::TaskNotFound,
+        format!("Task '{}' not found in Engram", task_id),
+    )
+    .with_suggestion("Create the task in Engram before committing".to_string())
+}
+
+/// Total steps across the reasoning entities among `relationships`
+fn reasoning_steps(
+    storage: &dyn Storage,
+    relationships: &[EntityRelationship],
+) -> Result<usize, EngramError> {
+    let reasoning_ids: Vec<String> = relationships
+        .iter()
+        .filter_map(|rel| {
+            if rel.target_type == "reasoning" {
+                Some(rel.target_id.clone())
+            } else if rel.source_type == "reasoning" {
+                Some(rel.source_id.clone())
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    Ok(storage
+        .get_many(&reasoning_ids, "reasoning")?
+        .into_iter()
+        .flatten()
+        .filter_map(|entity| Reasoning::from_generic(entity).ok())
+        .map(|reasoning| reasoning.steps.len())
+        .sum())
+}
+
+/// Error when fewer reasoning steps than required are linked; `steps` is
+/// only counted when a minimum is configured
+fn step_count_errors(
+    config: &ValidationConfig,
+    steps: impl FnOnce() -> Result<usize, EngramError>,
+) -> Vec<ValidationError> {
+    if config.min_reasoning_steps == 0 {
+        return Vec::new();
+    }
+
+    match steps() {
+        Ok(steps) if steps >= config.min_reasoning_steps => Vec::new(),
+        Ok(steps) => vec![ValidationError::new(
+            ValidationErrorType::PolicyViolation,
+            format!(
+                "Task has {} reasoning step(s); at least {} required",
+                steps, config.min_reasoning_steps
+            ),
+        )
+        .with_suggestion("Add steps with 'engram reasoning add-step'".to_string())],
+        Err(_) => vec![ValidationError::new(
+            ValidationErrorType::ConfigurationError,
+            "Failed to access task reasoning".to_string(),
+        )],
+    }
+}
+
+/// Validation report covering every commit staged for a pull request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AggregateValidationResult {
+    pub total_commits_checked: usize,
+    /// Distinct tasks referenced across all commits
+    pub total_tasks_referenced: usize,
+    pub per_commit_results: Vec<ValidationResult>,
+    /// Referenced tasks that are missing, done or cancelled
+    pub orphaned_task_ids: Vec<String>,
+    /// Required quality gates without a passing run, by task ID
+    pub missing_gates: HashMap<String, Vec<String>>,
+    pub overall_valid: bool,
+}
+
+/// Validate every commit staged for a pull request with the default
+/// configuration
+///
+/// See [`validate_staged_entities_with_config`].
+pub fn validate_staged_entities(
+    storage: &dyn Storage,
+    commit_messages: &[String],
+    staged_files: &[String],
+) -> Result<AggregateValidationResult, EngramError> {
+    validate_staged_entities_with_config(
+        storage,
+        &ValidationConfig::default(),
+        commit_messages,
+        staged_files,
+    )
+}
+
+/// Validate every commit staged for a pull request in one report
+///
+/// Each message gets the same checks as [`CommitValidator::validate_commit`]
+/// without per-agent overrides. The referenced tasks are then checked
+/// together: tasks that are gone or no longer open are orphaned, and open
+/// tasks must have a passing run of every required progressive gate
+/// selected for `staged_files`.
+#[instrument(skip_all, fields(entity_type = "commit", operation = "validate_staged", commits = commit_messages.len()))]
+pub fn validate_staged_entities_with_config(
+    storage: &dyn Storage,
+    config: &ValidationConfig,
+    commit_messages: &[String],
+    staged_files: &[String],
+) -> Result<AggregateValidationResult, EngramError> {
+    let parser = CommitMessageParser::with_config(config.clone())?;
+    let config = config.for_agent(None);
+
+    let mut per_commit_results = Vec::with_capacity(commit_messages.len());
+    let mut referenced: Vec<String> = Vec::new();
+    for message in commit_messages {
+        per_commit_results.push(check_staged_commit(
+            storage,
+            &parser,
+            &config,
+            message,
+            staged_files,
+        ));
+        for info in parser.parse_all_task_ids(message)? {
+            if !referenced.contains(&info.task_id) {
+                referenced.push(info.task_id);
+            }
+        }
+    }
+
+    let levels = active_levels(configured_gate_levels(storage)?);
+    let mut orphaned_task_ids = Vec::new();
+    let mut missing_gates = HashMap::new();
+    for task_id in &referenced {
+        let task = match storage.get(task_id, Task::entity_type())? {
+            Some(generic) => Task::from_generic(generic)?,
+            None => {
+                orphaned_task_ids.push(task_id.clone());
+                continue;
+            }
+        };
+        if matches!(task.status, TaskStatus::Done | TaskStatus::Cancelled) {
+            orphaned_task_ids.push(task_id.clone());
+            continue;
+        }
+
+        let missing = missing_required_gates(storage, &levels, task, staged_files)?;
+        if !missing.is_empty() {
+            missing_gates.insert(task_id.clone(), missing);
+        }
+    }
+
+    let overall_valid = per_commit_results.iter().all(|r| r.valid)
+        && orphaned_task_ids.is_empty()
+        && missing_gates.is_empty();
+    tracing::info!(
+        commits = per_commit_results.len(),
+        tasks = referenced.len(),
+        overall_valid,
+        "staged commits validated"
+    );
+
+    Ok(AggregateValidationResult {
+        total_commits_checked: per_commit_results.len(),
+        total_tasks_referenced: referenced.len(),
+        per_commit_results,
+        orphaned_task_ids,
+        missing_gates,
+        overall_valid,
+    })
+}
+
+/// [`CommitValidator::validate_commit`]'s checks against a plain
+/// [`Storage`], reading relationships from the stored relationship entities
+fn check_staged_commit(
+    storage: &dyn Storage,
+    parser: &CommitMessageParser,
+    config: &ValidationConfig,
+    commit_message: &str,
+    staged_files: &[String],
+) -> ValidationResult {
+    let start_time = Instant::now();
+    let elapsed = || start_time.elapsed().as_millis() as u64;
+    let task_info = match check_message(parser, config, commit_message, staged_files, start_time) {
+        Ok(task_info) => task_info,
+        Err(result) => return result,
+    };
+    let task_info = match resolve_issue_alias(storage, task_info) {
+        Ok(task_info) => task_info,
+        Err(error) => return ValidationResult::failure(vec![error], elapsed()),
+    };
+
+    match storage.exists(&task_info.task_id, "task") {
+        Ok(true) => {}
+        Ok(false) => {
+            return ValidationResult::failure(vec![task_not_found(&task_info.task_id)], elapsed())
+        }
+        Err(_) => {
+            return ValidationResult::failure(
+                vec![ValidationError::new(
+                    ValidationErrorType::ConfigurationError,
+                    "Failed to access Engram storage".to_string(),
+                )],
+                elapsed(),
+            )
+        }
+    }
+
+    let relationships = match stored_relationships(storage, &task_info.task_id) {
+        Ok(relationships) => relationships,
+        Err(_) => {
+            return ValidationResult::failure(
+                vec![ValidationError::new(
+                    ValidationErrorType::ConfigurationError,
+                    "Failed to access task relationships".to_string(),
+                )],
+                elapsed(),
+            )
+        }
+    };
+    let relationship_types: Vec<String> = relationships
+        .iter()
+        .map(|rel| rel.target_type.clone())
+        .collect();
+    let mut errors = missing_relationship_errors(config, &relationship_types);
+    errors.extend(step_count_errors(config, || {
+        reasoning_steps(storage, &relationships)
+    }));
+    if !errors.is_empty() {
+        return ValidationResult::failure(errors, elapsed());
+    }
+
+    let warnings = if config.warn_stale_context {
+        stale_context_warnings(storage, &task_info.task_id, &relationships, staged_files)
+    } else {
+        Vec::new()
+    };
+
+    ValidationResult::success(
+        task_info.task_id,
+        relationships
+            .iter()
+            .map(|rel| format!("{}:{}", rel.relationship_type, rel.target_type))
+            .collect(),
+        staged_files.to_vec(),
+        elapsed(),
+    )
+    .with_warnings(warnings)
+}
+
+/// Warnings for staged files that a context linked to `task_id` was captured
+/// from, since the context no longer reflects the committed source
+fn stale_context_warnings<S: Storage + ?Sized>(
+    storage: &S,
+    task_id: &str,
+    relationships: &[EntityRelationship],
+    staged_files: &[String],
+) -> Vec<String> {
+    let mut context_ids: Vec<String> = storage
+        .get(task_id, Task::entity_type())
+        .ok()
+        .flatten()
+        .and_then(|generic| Task::from_generic(generic).ok())
+        .map(|task| task.context_ids)
+        .unwrap_or_default();
+    for rel in relationships {
+        if rel.source_id == task_id && rel.target_type == Context::entity_type() {
+            context_ids.push(rel.target_id.clone());
+        } else if rel.target_id == task_id && rel.source_type == Context::entity_type() {
+            context_ids.push(rel.source_id.clone());
+        }
+    }
+    context_ids.sort();
+    context_ids.dedup();
+
+    context_ids
+        .iter()
+        .filter_map(|id| storage.get(id, Context::entity_type()).ok().flatten())
+        .filter_map(|generic| Context::from_generic(generic).ok())
+        .filter(|context| context.source_hash.is_some())
+        .filter_map(|context| {
+            let source = context.source.trim_start_matches("./");
+            let file = staged_files.iter().find(|file| {
+                let file = file.trim_start_matches("./");
+                source == file || source.ends_with(&format!("/{}", file))
+            })?;
+            let note = match context.source_status() {
+                Some(ContextSourceStatus::Missing) => "no longer exists",
+                _ => "is being changed",
+            };
+            Some(format!(
+                "{} {} but context '{}' ({}) was captured from it; run 'engram context refresh {}'",
+                file, note, context.title, context.id, context.id
+            ))
+        })
+        .collect()
+}
+
+/// Relationships involving `entity_id`, read from the stored relationship
+/// entities
+fn stored_relationships(
+    storage: &dyn Storage,
+    entity_id: &str,
+) -> Result<Vec<EntityRelationship>, EngramError> {
+    Ok(storage
+        .get_all(EntityRelationship::entity_type())?
+        .into_iter()
+        .filter_map(|entity| serde_json::from_value::<EntityRelationship>(entity.data).ok())
+        .filter(|rel| rel.source_id == entity_id || rel.target_id == entity_id)
+        .collect())
+}
+
+/// Required gates selected for `task` that have no passing or skipped run
+/// as their latest result
+fn missing_required_gates(
+    storage: &dyn Storage,
+    levels: &[GateLevel],
+    task: Task,
+    staged_files: &[String],
+) -> Result<Vec<String>, EngramError> {
+    let task_id = task.id.clone();
+    let context = GateContext {
+        task,
+        changed_files: staged_files.to_vec(),
+        commit_message: None,
+        branch_name: None,
+        metadata: HashMap::new(),
+    };
+    let selection = select_gates(levels, &context, GateStrictness::Auto);
+
+    // Results are newest first, so the first one seen per gate is the latest
+    let mut latest_passed: HashMap<String, bool> = HashMap::new();
+    for result in execution_results(storage, &task_id, Some(PROGRESSIVE_STAGE))? {
+        let passed = result.passed() || result.skipped();
+        latest_passed.entry(result.quality_gate).or_insert(passed);
+    }
+
+    Ok(selection
+        .gates
+        .iter()
+        .filter(|gate| gate.required && !latest_passed.get(&gate.name).copied().unwrap_or(false))
+        .map(|gate| gate.name.clone())
+        .collect())
+}
+
+/// Files staged in the git index of the current directory
+#[instrument(skip_all, fields(entity_type = "commit", operation = "staged_files"))]
+pub fn staged_files() -> Result<Vec<String>, EngramError> {
+    use std::process::Command;
+
+    let output = Command::new("git")
+        .args(["diff", "--name-only", "--cached"])
+        .output()
+        .map_err(EngramError::Io)?;
+
+    if !output.status.success() {
+        return Err(EngramError::Git(format!(
+            "Failed to get staged files: {}",
+            String::from_utf8_lossy(&output.stderr)
+        )));
+    }
+
+    let output_str = String::from_utf8_lossy(&output.stdout);
+    let files: Vec<String> = output_str
+        .lines()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    Ok(files)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::storage::MemoryStorage;
+    use crate::entities::{EntityRelationship, GenericEntity, RelationshipFilter};
+    use crate::storage::{
+        EntityPath, GitCommit, MemoryStorage, QueryFilter, QueryResult, RelationshipIndex,
+        RelationshipStats, StorageStats, TraversalAlgorithm,
+    };
+    use serde_json::Value;
+    use std::collections::HashMap;
 
     #[test]
     fn test_validate_commit_with_task() {
@@ -382,6 +1155,102 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_default_task_fills_missing_reference() {
+        let storage = MemoryStorage::new("test");
+        let mut validator = CommitValidator::new(storage)
+            .unwrap()
+            .with_default_task(Some("TASK-BRANCH".to_string()));
+
+        let result = validator.validate_commit("feat: invalid commit message", &[]);
+        assert!(!result.valid);
+        assert!(result
+            .errors
+            .iter()
+            .all(|e| e.error_type == ValidationErrorType::TaskNotFound));
+
+        // An explicit reference still wins, and exempt messages stay exempt
+        let result = validator.validate_commit("feat: [TASK-999] implement feature", &[]);
+        assert!(result.errors.iter().any(|e| e.message.contains("TASK-999")));
+        assert!(
+            validator
+                .validate_commit("chore: update dependencies", &[])
+                .valid
+        );
+    }
+
+    #[test]
+    fn test_issue_reference_resolves_task_alias() {
+        use crate::entities::{Entity, Task, TaskPriority};
+
+        let mut storage = MemoryStorage::new("test");
+        let mut task = Task::new(
+            "Issue-backed task".to_string(),
+            String::new(),
+            "agent".to_string(),
+            TaskPriority::Medium,
+            None,
+        );
+        task.metadata
+            .insert("alias".to_string(), serde_json::json!("gh-123"));
+        storage.store(&task.to_generic()).unwrap();
+
+        let config = ValidationConfig {
+            require_reasoning_relationship: false,
+            require_context_relationship: false,
+            require_file_scope_match: false,
+            issue_task_alias: Some("GH-{number}".to_string()),
+            ..ValidationConfig::default()
+        };
+        let mut validator = CommitValidator::with_config(storage, config).unwrap();
+
+        let result = validator.validate_commit("fix: handle timeouts\n\nFixes #123", &[]);
+        assert!(result.valid, "{}", result.error_summary());
+        assert_eq!(result.task_id.as_deref(), Some(task.id.as_str()));
+
+        let result = validator.validate_commit("fix: handle timeouts\n\nFixes #124", &[]);
+        assert!(!result.valid);
+        assert_eq!(
+            result.errors[0].error_type,
+            ValidationErrorType::TaskNotFound
+        );
+    }
+
+    #[test]
+    fn test_time_budget_skips_or_warns() {
+        let started = Instant::now() - Duration::from_millis(50);
+        let files = vec!["src/lib.rs".to_string()];
+        let mut config = ValidationConfig {
+            time_budget_ms: 10,
+            ..ValidationConfig::default()
+        };
+
+        let skipped = skip_rest(&config, started, "TASK-1", &files).unwrap();
+        assert!(skipped.valid);
+        assert_eq!(skipped.validated_files, files);
+        assert_eq!(skipped.warnings.len(), 1);
+
+        config.strict_time_budget = true;
+        assert!(skip_rest(&config, started, "TASK-1", &files).is_none());
+
+        config.strict_time_budget = false;
+        config.time_budget_ms = 0;
+        assert!(!over_budget(&config, started));
+        config.time_budget_ms = 60_000;
+        assert!(skip_rest(&config, started, "TASK-1", &files).is_none());
+
+        // Within budget: no warning, timings recorded and serialized
+        let storage = MemoryStorage::new("test");
+        let mut validator = CommitValidator::with_config(storage, config).unwrap();
+        let result = validator.validate_commit("feat: [TASK-999] implement feature", &[]);
+        assert!(result.warnings.is_empty());
+        let timings = PhaseTimings {
+            parse_us: 1500,
+            ..result.phase_timings
+        };
+        assert!(timings.summary().starts_with("parse 1.5ms, task lookup"));
+    }
+
     #[test]
     fn test_validate_task_not_found() {
         let storage = MemoryStorage::new("test");
@@ -401,6 +1270,97 @@ mod tests {
         assert!(found_task_error, "Should report TaskNotFound error");
     }
 
+    #[test]
+    fn test_agent_override_relaxes_relationship_requirement() {
+        use crate::entities::{Entity, Task, TaskPriority};
+        use crate::validation::config::AgentValidationOverride;
+
+        let mut storage = MemoryStorage::new("test");
+        let task = Task::new(
+            "Unlinked task".to_string(),
+            "Has no reasoning or context".to_string(),
+            "junior".to_string(),
+            TaskPriority::Medium,
+            None,
+        );
+        storage.store(&task.to_generic()).unwrap();
+
+        let mut config = ValidationConfig::default();
+        config.per_agent_config.insert(
+            "junior".to_string(),
+            AgentValidationOverride {
+                require_relationships: Some(false),
+                ..Default::default()
+            },
+        );
+        let mut validator = CommitValidator::with_config(storage, config).unwrap();
+        let message = format!("feat: add feature [{}]", task.id);
+
+        let junior = validator.validate_commit_as(&message, &[], Some("junior"));
+        assert!(junior.valid, "{}", junior.error_summary());
+
+        let senior = validator.validate_commit_as(&message, &[], Some("senior"));
+        assert!(!senior.valid);
+        assert!(senior
+            .errors
+            .iter()
+            .all(|e| e.error_type == ValidationErrorType::MissingRequiredRelationship));
+    }
+
+    #[test]
+    fn test_agent_override_limits_files_per_commit() {
+        use crate::validation::config::AgentValidationOverride;
+
+        let mut config = ValidationConfig::default();
+        config.per_agent_config.insert(
+            "careful".to_string(),
+            AgentValidationOverride {
+                max_files_per_commit: Some(1),
+                ..Default::default()
+            },
+        );
+        let mut validator =
+            CommitValidator::with_config(MemoryStorage::new("test"), config).unwrap();
+
+        let files = vec!["a.rs".to_string(), "b.rs".to_string()];
+        let result = validator.validate_commit_as("feat: change [TASK-1]", &files, Some("careful"));
+        assert_eq!(
+            result.errors[0].error_type,
+            ValidationErrorType::PolicyViolation
+        );
+    }
+
+    #[test]
+    fn test_committing_agent_read_from_repo_root() {
+        use crate::validation::config::AgentValidationOverride;
+
+        let dir = tempfile::tempdir().unwrap();
+        let repo = git2::Repository::init(dir.path()).unwrap();
+        repo.config()
+            .unwrap()
+            .set_str("user.email", "careful@example.com")
+            .unwrap();
+
+        let mut config = ValidationConfig::default();
+        config.per_agent_config.insert(
+            "careful@example.com".to_string(),
+            AgentValidationOverride {
+                max_files_per_commit: Some(1),
+                ..Default::default()
+            },
+        );
+        let mut validator = CommitValidator::with_config(MemoryStorage::new("test"), config)
+            .unwrap()
+            .with_repo_root(dir.path());
+
+        let files = vec!["a.rs".to_string(), "b.rs".to_string()];
+        let result = validator.validate_commit("feat: change [TASK-1]", &files);
+        assert_eq!(
+            result.errors[0].error_type,
+            ValidationErrorType::PolicyViolation
+        );
+    }
+
     #[test]
     fn test_exempt_patterns() {
         let storage = MemoryStorage::new("test");
@@ -420,4 +1380,339 @@ mod tests {
         let result = validator.validate_commit("Merge branch 'main' into feature", &vec![]);
         assert!(result.valid);
     }
+
+    /// Storage that panics when a task or its relationships are read twice
+    struct QueryOnce {
+        inner: MemoryStorage,
+        gets: std::cell::Cell<usize>,
+        relationship_reads: std::cell::Cell<usize>,
+    }
+
+    impl QueryOnce {
+        fn new(inner: MemoryStorage) -> Self {
+            Self {
+                inner,
+                gets: std::cell::Cell::new(0),
+                relationship_reads: std::cell::Cell::new(0),
+            }
+        }
+
+        fn read_once(counter: &std::cell::Cell<usize>, what: &str) {
+            counter.set(counter.get() + 1);
+            assert!(counter.get() == 1, "{} read more than once", what);
+        }
+    }
+
+    impl Storage for QueryOnce {
+        fn store(&mut self, entity: &GenericEntity) -> Result<(), EngramError> {
+            self.inner.store(entity)
+        }
+        fn get(&self, id: &str, entity_type: &str) -> Result<Option<GenericEntity>, EngramError> {
+            Self::read_once(&self.gets, "task");
+            self.inner.get(id, entity_type)
+        }
+        fn query(&self, filter: &QueryFilter) -> Result<QueryResult, EngramError> {
+            self.inner.query(filter)
+        }
+        fn query_by_agent(
+            &self,
+            agent: &str,
+            entity_type: Option<&str>,
+        ) -> Result<Vec<GenericEntity>, EngramError> {
+            self.inner.query_by_agent(agent, entity_type)
+        }
+        fn query_by_time_range(
+            &self,
+            start: chrono::DateTime<chrono::Utc>,
+            end: chrono::DateTime<chrono::Utc>,
+        ) -> Result<Vec<GenericEntity>, EngramError> {
+            self.inner.query_by_time_range(start, end)
+        }
+        fn query_by_type(
+            &self,
+            entity_type: &str,
+            filters: Option<&HashMap<String, Value>>,
+            limit: Option<usize>,
+            offset: Option<usize>,
+        ) -> Result<QueryResult, EngramError> {
+            self.inner
+                .query_by_type(entity_type, filters, limit, offset)
+        }
+        fn text_search(
+            &self,
+            query: &str,
+            entity_types: Option<&[String]>,
+            limit: Option<usize>,
+        ) -> Result<Vec<GenericEntity>, EngramError> {
+            self.inner.text_search(query, entity_types, limit)
+        }
+        fn count(&self, filter: &QueryFilter) -> Result<usize, EngramError> {
+            self.inner.count(filter)
+        }
+        fn delete(&mut self, id: &str, entity_type: &str) -> Result<(), EngramError> {
+            self.inner.delete(id, entity_type)
+        }
+        fn list_ids(&self, entity_type: &str) -> Result<Vec<String>, EngramError> {
+            self.inner.list_ids(entity_type)
+        }
+        fn get_all(&self, entity_type: &str) -> Result<Vec<GenericEntity>, EngramError> {
+            self.inner.get_all(entity_type)
+        }
+        fn sync(&mut self) -> Result<(), EngramError> {
+            self.inner.sync()
+        }
+        fn current_branch(&self) -> Result<String, EngramError> {
+            self.inner.current_branch()
+        }
+        fn create_branch(&mut self, branch_name: &str) -> Result<(), EngramError> {
+            self.inner.create_branch(branch_name)
+        }
+        fn switch_branch(&mut self, branch_name: &str) -> Result<(), EngramError> {
+            self.inner.switch_branch(branch_name)
+        }
+        fn merge_branches(&mut self, source: &str, target: &str) -> Result<(), EngramError> {
+            self.inner.merge_branches(source, target)
+        }
+        fn history(&self, limit: Option<usize>) -> Result<Vec<GitCommit>, EngramError> {
+            self.inner.history(limit)
+        }
+        fn bulk_store(&mut self, entities: &[GenericEntity]) -> Result<(), EngramError> {
+            self.inner.bulk_store(entities)
+        }
+        fn get_stats(&self) -> Result<StorageStats, EngramError> {
+            self.inner.get_stats()
+        }
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    impl RelationshipStorage for QueryOnce {
+        fn store_relationship(
+            &mut self,
+            relationship: &EntityRelationship,
+        ) -> Result<(), EngramError> {
+            self.inner.store_relationship(relationship)
+        }
+        fn get_relationship(&self, id: &str) -> Result<Option<EntityRelationship>, EngramError> {
+            self.inner.get_relationship(id)
+        }
+        fn query_relationships(
+            &self,
+            filter: &RelationshipFilter,
+        ) -> Result<Vec<EntityRelationship>, EngramError> {
+            self.inner.query_relationships(filter)
+        }
+        fn get_entity_relationships(
+            &self,
+            entity_id: &str,
+        ) -> Result<Vec<EntityRelationship>, EngramError> {
+            Self::read_once(&self.relationship_reads, "relationships");
+            self.inner.get_entity_relationships(entity_id)
+        }
+        fn get_outbound_relationships(
+            &self,
+            entity_id: &str,
+        ) -> Result<Vec<EntityRelationship>, EngramError> {
+            self.inner.get_outbound_relationships(entity_id)
+        }
+        fn get_inbound_relationships(
+            &self,
+            entity_id: &str,
+        ) -> Result<Vec<EntityRelationship>, EngramError> {
+            self.inner.get_inbound_relationships(entity_id)
+        }
+        fn find_paths(
+            &self,
+            source_id: &str,
+            target_id: &str,
+            algorithm: TraversalAlgorithm,
+            max_depth: Option<usize>,
+        ) -> Result<Vec<EntityPath>, EngramError> {
+            self.inner
+                .find_paths(source_id, target_id, algorithm, max_depth)
+        }
+        fn get_connected_entities(
+            &self,
+            entity_id: &str,
+            algorithm: TraversalAlgorithm,
+            max_depth: Option<usize>,
+        ) -> Result<Vec<String>, EngramError> {
+            self.inner
+                .get_connected_entities(entity_id, algorithm, max_depth)
+        }
+        fn delete_relationship(&mut self, id: &str) -> Result<(), EngramError> {
+            self.inner.delete_relationship(id)
+        }
+        fn get_relationship_index(&self) -> Result<&RelationshipIndex, EngramError> {
+            self.inner.get_relationship_index()
+        }
+        fn rebuild_relationship_index(&mut self) -> Result<(), EngramError> {
+            self.inner.rebuild_relationship_index()
+        }
+        fn get_relationship_stats(&self) -> Result<RelationshipStats, EngramError> {
+            self.inner.get_relationship_stats()
+        }
+    }
+
+    #[test]
+    fn test_repeated_validation_reads_task_from_cache() {
+        use crate::entities::{EntityRelationType, Task, TaskPriority};
+
+        let mut memory = MemoryStorage::new("test");
+        let task = Task::new(
+            "Cached task".to_string(),
+            "Validated twice".to_string(),
+            "test".to_string(),
+            TaskPriority::Medium,
+            None,
+        );
+        memory.store(&task.to_generic()).unwrap();
+        for target_type in ["reasoning", "context"] {
+            memory
+                .store_relationship(&EntityRelationship::new(
+                    uuid::Uuid::new_v4().to_string(),
+                    "test".to_string(),
+                    task.id.clone(),
+                    "task".to_string(),
+                    uuid::Uuid::new_v4().to_string(),
+                    target_type.to_string(),
+                    EntityRelationType::References,
+                ))
+                .unwrap();
+        }
+
+        let mut validator = CommitValidator::new(QueryOnce::new(memory)).unwrap();
+        assert_eq!(validator.get_config().performance.cache_ttl_seconds, 120);
+        let message = format!("feat: cached [{}]", task.id);
+        let first = validator.validate_commit_as(&message, &[], None);
+        let second = validator.validate_commit_as(&message, &[], None);
+
+        assert!(first.valid, "{}", first.error_summary());
+        assert!(second.valid, "{}", second.error_summary());
+        assert_eq!(
+            first.validated_relationships,
+            second.validated_relationships
+        );
+        assert_eq!(
+            validator.cache_stats(),
+            ValidationCacheStats { hits: 1, misses: 1 }
+        );
+    }
+
+    #[test]
+    fn test_warn_stale_context_when_commit_touches_context_source() {
+        use crate::entities::{ContextRelevance, EntityRelationType, TaskPriority};
+
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("requirements.md");
+        std::fs::write(&source, "must support SSO").unwrap();
+        let source = source.to_string_lossy().to_string();
+
+        let mut storage = MemoryStorage::new("test");
+        let task = Task::new(
+            "Add SSO".to_string(),
+            String::new(),
+            "test".to_string(),
+            TaskPriority::Medium,
+            None,
+        );
+        let mut context = Context::new(
+            "Requirements".to_string(),
+            "must support SSO".to_string(),
+            source.clone(),
+            ContextRelevance::High,
+            "test".to_string(),
+        );
+        context.capture_source();
+        storage.store(&task.to_generic()).unwrap();
+        storage.store(&context.to_generic()).unwrap();
+        storage
+            .store_relationship(&EntityRelationship::new(
+                uuid::Uuid::new_v4().to_string(),
+                "test".to_string(),
+                task.id.clone(),
+                "task".to_string(),
+                context.id.clone(),
+                "context".to_string(),
+                EntityRelationType::References,
+            ))
+            .unwrap();
+
+        let mut config = ValidationConfig {
+            require_reasoning_relationship: false,
+            require_file_scope_match: false,
+            ..ValidationConfig::default()
+        };
+        let message = format!("feat: tighten requirements [{}]", task.id);
+        let staged = vec![source.clone(), "src/lib.rs".to_string()];
+
+        let mut validator = CommitValidator::with_config(storage.clone(), config.clone()).unwrap();
+        let quiet = validator.validate_commit_as(&message, &staged, None);
+        assert!(quiet.valid, "{}", quiet.error_summary());
+        assert!(quiet.warnings.is_empty());
+
+        config.warn_stale_context = true;
+        let mut validator = CommitValidator::with_config(storage.clone(), config.clone()).unwrap();
+        let warned = validator.validate_commit_as(&message, &staged, None);
+        assert!(warned.valid);
+        assert_eq!(warned.warnings.len(), 1);
+        assert!(warned.warnings[0].contains(&context.id));
+
+        let report = validate_staged_entities_with_config(
+            &storage,
+            &config,
+            std::slice::from_ref(&message),
+            &["src/lib.rs".to_string()],
+        )
+        .unwrap();
+        assert!(report.per_commit_results[0].warnings.is_empty());
+    }
+
+    #[test]
+    fn test_validate_staged_entities_reports_each_commit() {
+        use crate::entities::{EntityRelationType, TaskPriority};
+
+        let mut storage = MemoryStorage::new("test");
+        let mut messages = Vec::new();
+        for title in ["Add login", "Add logout"] {
+            let task = Task::new(
+                title.to_string(),
+                String::new(),
+                "test".to_string(),
+                TaskPriority::Medium,
+                None,
+            );
+            storage.store(&task.to_generic()).unwrap();
+            for target_type in ["reasoning", "context"] {
+                storage
+                    .store_relationship(&EntityRelationship::new(
+                        uuid::Uuid::new_v4().to_string(),
+                        "test".to_string(),
+                        task.id.clone(),
+                        "task".to_string(),
+                        uuid::Uuid::new_v4().to_string(),
+                        target_type.to_string(),
+                        EntityRelationType::References,
+                    ))
+                    .unwrap();
+            }
+            messages.push(format!("feat: {} [{}]", title.to_lowercase(), task.id));
+        }
+        messages.insert(1, "feat: untracked change".to_string());
+
+        let report =
+            validate_staged_entities(&storage, &messages, &["src/auth.rs".to_string()]).unwrap();
+
+        assert_eq!(report.total_commits_checked, 3);
+        assert_eq!(report.total_tasks_referenced, 2);
+        let validities: Vec<bool> = report.per_commit_results.iter().map(|r| r.valid).collect();
+        assert_eq!(validities, vec![true, false, true]);
+        assert_eq!(
+            report.per_commit_results[1].errors[0].error_type,
+            ValidationErrorType::NoTaskReference
+        );
+        assert!(report.orphaned_task_ids.is_empty());
+        assert!(!report.overall_valid);
+    }
 }
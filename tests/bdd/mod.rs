@@ -672,6 +672,7 @@ impl EngramWorld {
             content: content.to_string(),
             source: "test".to_string(),
             source_id: None,
+            source_hash: None,
             relevance: relevance_enum,
             agent: self
                 .current_agent
@@ -1107,6 +1108,8 @@ impl EngramWorld {
             start_time: chrono::Utc::now(),
             end_time: None,
             duration_seconds: None,
+            paused_at: None,
+            paused_seconds: 0,
             task_ids: vec![],
             context_ids: vec![],
             knowledge_ids: vec![],
@@ -9,6 +9,15 @@ use cucumber::{given, then, when};
 // Function to force linking of this module
 pub fn register() {}
 
+/// Stored `TaskStatus` value for a status as written in a scenario ("in-progress", "pending")
+fn task_status_value(status: &str) -> String {
+    match status.to_lowercase().replace('-', "_").as_str() {
+        "pending" => "todo".to_string(),
+        "in_progress" => "inprogress".to_string(),
+        other => other.to_string(),
+    }
+}
+
 // ============================================================================
 // GIVEN steps - Setup and preconditions
 // ============================================================================
@@ -38,7 +47,7 @@ async fn given_agent_has_task_with_status(
 ) {
     world.initialize_storage(&agent);
     world.create_task(&title, "Test description", "medium");
-    let status_value = serde_json::Value::String(_status.to_lowercase().replace("-", "_"));
+    let status_value = serde_json::Value::String(task_status_value(&_status));
     let _ = world.update_last_entity_field("task", "status", status_value);
 }
 
@@ -164,7 +173,7 @@ async fn when_list_tasks_for_agent(world: &mut EngramWorld, agent: String) {
 
 #[when(expr = "I update the task status to {string}")]
 async fn when_update_task_status(world: &mut EngramWorld, status: String) {
-    let status_value = serde_json::Value::String(status.to_lowercase().replace("-", "_"));
+    let status_value = serde_json::Value::String(task_status_value(&status));
     let _ = world.update_last_entity_field("task", "status", status_value);
 }
 
@@ -346,11 +355,7 @@ async fn then_task_status_should_be(world: &mut EngramWorld, _status: String) {
         .get_last_entity_field("task", "status")
         .and_then(|v| v.as_str().map(String::from))
         .unwrap_or_default();
-    assert_eq!(
-        actual,
-        _status.to_lowercase().replace("-", "_"),
-        "Task status mismatch"
-    );
+    assert_eq!(actual, task_status_value(&_status), "Task status mismatch");
 }
 
 #[then("I should see the task title")]
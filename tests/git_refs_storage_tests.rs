@@ -1,7 +1,9 @@
 //! Tests for Git refs storage implementation
 
-use chrono::Utc;
-use engram::entities::{EntityRelationType as RelationshipType, EntityRelationship, GenericEntity};
+use engram::entities::{
+    Context, ContextRelevance, Entity, EntityRelationType as RelationshipType, EntityRelationship,
+    GenericEntity, Task, TaskPriority,
+};
 use engram::storage::{GitRefsStorage, QueryFilter, RelationshipStorage, Storage};
 use serde_json::json;
 use tempfile::TempDir;
@@ -14,30 +16,28 @@ fn create_test_storage() -> (TempDir, GitRefsStorage) {
 }
 
 fn create_test_task(id: &str, title: &str, status: &str) -> GenericEntity {
-    GenericEntity {
-        id: id.to_string(),
-        entity_type: "task".to_string(),
-        agent: "test-agent".to_string(),
-        timestamp: Utc::now(),
-        data: json!({
-            "title": title,
-            "status": status,
-            "priority": "medium"
-        }),
-    }
+    let mut task = Task::new(
+        title.to_string(),
+        "Test task description".to_string(),
+        "test-agent".to_string(),
+        TaskPriority::Medium,
+        None,
+    );
+    task.id = id.to_string();
+    task.status = serde_json::from_value(json!(status)).expect("Invalid task status");
+    task.to_generic()
 }
 
 fn create_test_context(id: &str, title: &str) -> GenericEntity {
-    GenericEntity {
-        id: id.to_string(),
-        entity_type: "context".to_string(),
-        agent: "test-agent".to_string(),
-        timestamp: Utc::now(),
-        data: json!({
-            "title": title,
-            "content": "Test context content"
-        }),
-    }
+    let mut context = Context::new(
+        title.to_string(),
+        "Test context content".to_string(),
+        "test".to_string(),
+        ContextRelevance::Medium,
+        "test-agent".to_string(),
+    );
+    context.id = id.to_string();
+    context.to_generic()
 }
 
 #[test]
@@ -180,7 +180,7 @@ fn test_git_refs_update() {
 
     storage.store(&task).expect("Failed to store task");
 
-    task.data["status"] = json!("in_progress");
+    task.data["status"] = json!("done");
     task.data["title"] = json!("Updated Task");
 
     storage.store(&task).expect("Failed to update task");
@@ -189,7 +189,7 @@ fn test_git_refs_update() {
         .get("task-011", "task")
         .expect("Failed to get updated task")
         .unwrap();
-    assert_eq!(updated.data["status"], "in_progress");
+    assert_eq!(updated.data["status"], "done");
     assert_eq!(updated.data["title"], "Updated Task");
 }
 
@@ -277,3 +277,33 @@ fn test_git_refs_pagination() {
     assert_eq!(result2.entities.len(), 5);
     assert_eq!(result2.total_count, 10);
 }
+
+/// Serial vs parallel full-type scans over 10k tasks. Run with
+/// `cargo test --release --test git_refs_storage_tests -- --ignored --nocapture`
+#[test]
+#[ignore]
+fn bench_serial_vs_parallel_scan() {
+    let (temp_dir, mut storage) = create_test_storage();
+    let tasks: Vec<GenericEntity> = (0..10_000)
+        .map(|i| create_test_task(&format!("bench-{:05}", i), "Benchmark task", "todo"))
+        .collect();
+    storage.bulk_store(&tasks).unwrap();
+
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    for (label, threads) in [("serial", 1), ("parallel", threads)] {
+        let storage = GitRefsStorage::new(temp_dir.path().to_str().unwrap(), "test-agent")
+            .unwrap()
+            .with_read_threads(threads)
+            .unwrap();
+        let started = std::time::Instant::now();
+        let loaded = storage.get_all("task").unwrap();
+        println!(
+            "{:>8} ({} thread(s)): {} tasks in {:?}",
+            label,
+            threads,
+            loaded.len(),
+            started.elapsed()
+        );
+        assert_eq!(loaded.len(), tasks.len());
+    }
+}